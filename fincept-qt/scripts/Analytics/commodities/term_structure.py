@@ -0,0 +1,246 @@
+"""
+Commodity Term Structure — contango/backwardation, roll yield and seasonality.
+
+Energy curves come from the EIA daily NYMEX futures series (contracts 1-4,
+roughly 1-4 months to expiry) plus the matching spot series. The VIX curve
+uses the CBOE futures curve for the current snapshot and the constant-maturity
+VIX (1M) / VIX3M (3M) indexes for history.
+
+Per commodity, on every date with a front and second contract:
+  slope_pct        - annualised log slope front -> back contract, % (> 0 contango)
+  roll_yield_pct   - annualised log(front / second), % (> 0 backwardation, what a
+                     long position earns rolling before expiry)
+  basis            - spot minus front contract
+Seasonality: mean slope / roll yield by calendar month and the mean spot
+return and hit rate per calendar month.
+
+Usage:
+    python term_structure.py <commodity|all> [years]
+    commodities: wti, natgas, heating_oil, rbob, vix
+
+Energy curves need EIA_API_KEY.
+"""
+import sys
+import json
+from datetime import datetime
+from pathlib import Path
+
+import numpy as np
+import pandas as pd
+
+# EIA / CBOE fetchers live in the scripts root
+SCRIPTS_DIR = Path(__file__).resolve().parent.parent.parent
+sys.path.insert(0, str(SCRIPTS_DIR))
+
+import eia_petroleum_data  # noqa: E402
+import cboe_vix_data  # noqa: E402
+
+COMMODITIES = {
+    "wti": {
+        "name": "WTI crude oil",
+        "unit": "USD/bbl",
+        "spot": "PET.RWTC.D",
+        "contracts": [f"PET.RCLC{i}.D" for i in range(1, 5)],
+    },
+    "natgas": {
+        "name": "Henry Hub natural gas",
+        "unit": "USD/MMBtu",
+        "spot": "NG.RNGWHHD.D",
+        "contracts": [f"NG.RNGC{i}.D" for i in range(1, 5)],
+    },
+    "heating_oil": {
+        "name": "NY Harbor ULSD (heating oil)",
+        "unit": "USD/gal",
+        "spot": "PET.EER_EPD2F_PF4_Y35NY_DPG.D",
+        "contracts": [f"PET.EER_EPD2F_PE{i}_Y35NY_DPG.D" for i in range(1, 5)],
+    },
+    "rbob": {
+        "name": "NY Harbor RBOB gasoline",
+        "unit": "USD/gal",
+        "spot": "PET.EER_EPMRR_PF4_Y35NY_DPG.D",
+        "contracts": [f"PET.EER_EPMRR_PE{i}_Y35NY_DPG.D" for i in range(1, 5)],
+    },
+    "vix": {
+        "name": "CBOE VIX",
+        "unit": "index points",
+    },
+}
+
+# |roll yield| below this (annualised %) is reported as flat
+FLAT_THRESHOLD_PCT = 1.0
+
+
+def fetch_eia(series_id: str, start: str) -> pd.Series:
+    result = eia_petroleum_data.get_series(series_id, start, datetime.now().strftime("%Y-%m-%d"))
+    if "error" in result:
+        raise RuntimeError(f"{series_id}: {result['error']}")
+    rows = result.get("response", {}).get("data", [])
+    points = {pd.Timestamp(r["period"]): r["value"] for r in rows if r.get("value") not in (None, "")}
+    if not points:
+        raise RuntimeError(f"{series_id}: no observations")
+    return pd.Series(points, dtype=float).sort_index()
+
+
+def fetch_cboe_index(name: str, start: str) -> pd.Series:
+    parsed = cboe_vix_data.get_index_data(name, None, None)
+    if "error" in parsed:
+        raise RuntimeError(f"{name}: {parsed['error']}")
+    points = {}
+    for row in parsed.get("data", []):
+        date = row.get("DATE", row.get("Date"))
+        close = row.get("CLOSE", row.get("Close"))
+        if date and close:
+            points[pd.Timestamp(date)] = float(close)
+    s = pd.Series(points, dtype=float).sort_index()
+    return s[s.index >= pd.Timestamp(start)]
+
+
+def vix_futures_curve() -> list:
+    """Current VIX futures settlements as [{tenor_months, price, expiration}], [] if unavailable."""
+    try:
+        from cboe_data import CBOEDataAPI
+        result = CBOEDataAPI().get_futures_curve("VX_EOD")
+    except Exception:
+        return []
+    if not result.get("success"):
+        return []
+    today = pd.Timestamp(datetime.now().date())
+    points = []
+    for rec in result["data"].get("futures", []):
+        expiry = next((rec[k] for k in ("expiration", "expirationDate", "expire_date", "expiry") if rec.get(k)), None)
+        price = next((rec[k] for k in ("price", "settlement", "last_price", "close") if rec.get(k)), None)
+        if expiry is None or price is None:
+            continue
+        try:
+            months = (pd.Timestamp(expiry) - today).days / 30.4375
+            points.append({"tenor_months": round(months, 2), "price": float(price),
+                           "expiration": pd.Timestamp(expiry).strftime("%Y-%m-%d")})
+        except (ValueError, TypeError):
+            continue
+    return sorted([p for p in points if p["tenor_months"] >= 0], key=lambda p: p["tenor_months"])
+
+
+def load_frame(commodity: str, start: str) -> tuple:
+    """Daily frame with columns spot, c1..cN plus the tenor (months) of each contract column."""
+    if commodity == "vix":
+        # VIX itself is the 1M point, so there is no separate spot/basis
+        df = pd.DataFrame({"c1": fetch_cboe_index("VIX", start), "c2": fetch_cboe_index("VIX3M", start)})
+        return df.dropna(subset=["c1", "c2"]), {"c1": 1, "c2": 3}
+    spec = COMMODITIES[commodity]
+    cols = {f"c{i + 1}": fetch_eia(sid, start) for i, sid in enumerate(spec["contracts"])}
+    try:
+        cols["spot"] = fetch_eia(spec["spot"], start)
+    except RuntimeError:
+        pass  # spot series are discontinued for some products; basis is then omitted
+    df = pd.DataFrame(cols).dropna(subset=["c1", "c2"])
+    return df, {f"c{i + 1}": i + 1 for i in range(len(spec["contracts"]))}
+
+
+def term_structure(commodity: str, years: int) -> dict:
+    start = (pd.Timestamp(datetime.now().date()) - pd.DateOffset(years=years)).strftime("%Y-%m-%d")
+    df, tenors = load_frame(commodity, start)
+    if df.empty:
+        raise RuntimeError(f"{commodity}: no overlapping contract history")
+
+    back = max((c for c in tenors if df[c].notna().any()), key=tenors.get)
+    span_front_second = (tenors["c2"] - tenors["c1"]) / 12.0
+    df["roll_yield_pct"] = 100 * np.log(df["c1"] / df["c2"]) / span_front_second
+    span_back = (tenors[back] - tenors["c1"]) / 12.0
+    df["slope_pct"] = 100 * np.log(df[back] / df["c1"]) / span_back
+    has_spot = "spot" in df and df["spot"].notna().any()
+    if has_spot:
+        df["basis"] = df["spot"] - df["c1"]
+
+    latest = df.iloc[-1]
+    roll = float(latest["roll_yield_pct"])
+    structure = "flat" if abs(roll) < FLAT_THRESHOLD_PCT else ("backwardation" if roll > 0 else "contango")
+
+    futures = vix_futures_curve() if commodity == "vix" else []
+    if futures:
+        curve = [{"tenor_months": 0, "price": round(float(latest["c1"]), 4)}] + futures
+    else:
+        curve = [{"tenor_months": tenors[c], "price": round(float(latest[c]), 4)}
+                 for c in sorted(tenors, key=tenors.get) if pd.notna(latest[c])]
+    if has_spot and pd.notna(latest["spot"]):
+        curve.insert(0, {"tenor_months": 0, "price": round(float(latest["spot"]), 4)})
+
+    # Seasonality by calendar month
+    monthly = df.resample("ME").last()
+    ref = monthly["spot"] if has_spot else monthly["c1"]
+    ret = 100 * ref.pct_change()
+    seasonality = []
+    for m in range(1, 13):
+        in_month = df[df.index.month == m]
+        rets = ret[ret.index.month == m].dropna()
+        seasonality.append({
+            "month": m,
+            "roll_yield_pct": round(float(in_month["roll_yield_pct"].mean()), 3) if len(in_month) else None,
+            "slope_pct": round(float(in_month["slope_pct"].mean()), 3) if len(in_month) else None,
+            "avg_return_pct": round(float(rets.mean()), 3) if len(rets) else None,
+            "hit_rate": round(float((rets > 0).mean()), 3) if len(rets) else None,
+            "years": int(len(rets)),
+        })
+
+    # Chart-ready weekly history as column arrays
+    weekly = df.resample("W-FRI").last().dropna(subset=["c1"])
+
+    def column(name):
+        return [None if pd.isna(v) else round(float(v), 4) for v in weekly[name]] if name in weekly else []
+
+    history = {
+        "dates": [d.strftime("%Y-%m-%d") for d in weekly.index],
+        "front": column("c1"),
+        "second": column("c2"),
+        "roll_yield_pct": column("roll_yield_pct"),
+        "slope_pct": column("slope_pct"),
+    }
+    if has_spot:
+        history["spot"] = column("spot")
+
+    roll_hist = df["roll_yield_pct"].dropna()
+    return {
+        "commodity": commodity,
+        "name": COMMODITIES[commodity]["name"],
+        "unit": COMMODITIES[commodity]["unit"],
+        "as_of": df.index[-1].strftime("%Y-%m-%d"),
+        "structure": structure,
+        "roll_yield_pct": round(roll, 3),
+        "roll_yield_percentile": round(float((roll_hist <= roll).mean()), 3),
+        "slope_pct": round(float(latest["slope_pct"]), 3) if pd.notna(latest["slope_pct"]) else None,
+        "basis": round(float(latest["basis"]), 4) if has_spot and pd.notna(latest.get("basis")) else None,
+        "curve": curve,
+        "seasonality": seasonality,
+        "history": history,
+        "observations": int(len(df)),
+    }
+
+
+def main(args=None):
+    if args is None:
+        args = sys.argv[1:]
+    target = args[0].lower() if args else "all"
+    years = int(args[1]) if len(args) > 1 else 5
+
+    if target != "all" and target not in COMMODITIES:
+        print(json.dumps({"success": False, "error": f"Unknown commodity: {target}",
+                          "commodities": list(COMMODITIES)}))
+        return
+    names = list(COMMODITIES) if target == "all" else [target]
+    results, errors = {}, {}
+    for name in names:
+        try:
+            results[name] = term_structure(name, years)
+        except Exception as e:
+            errors[name] = str(e)
+    if not results:
+        print(json.dumps({"success": False, "error": "; ".join(f"{k}: {v}" for k, v in errors.items())}))
+        return
+    print(json.dumps({"success": True, "data": {
+        "commodities": results,
+        "errors": errors,
+        "computed_at": datetime.utcnow().strftime("%Y-%m-%dT%H:%M:%SZ"),
+    }}))
+
+
+if __name__ == "__main__":
+    main()
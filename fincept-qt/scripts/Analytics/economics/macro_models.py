@@ -0,0 +1,261 @@
+"""
+Macro Models — recession probability (probit) and GDP nowcast (bridge equations)
+built from FRED series.
+
+Recession probability
+  P(NBER recession 12 months ahead) = Phi(b0 + b1*spread + b2*claims_yoy + b3*activity)
+    spread     - 10Y minus 3M Treasury (T10Y3M, monthly average, pct points)
+    claims_yoy - initial jobless claims (ICSA, monthly average), 12-month log change x100
+    activity   - regional manufacturing PMI proxy: mean of the Philadelphia and
+                 New York Fed general business activity diffusion indexes
+  Fitted by maximum likelihood on every month whose 12-month-ahead outcome is
+  known (USREC). A spread-only model (Estrella-Mishkin) is reported alongside.
+
+GDP nowcast
+  Real GDP growth (GDPC1, q/q SAAR %) regressed on quarterly averages of
+  industrial production, payrolls and real retail sales (q/q SAAR %) and
+  initial claims (q/q log change x100). The current quarter's months that are
+  not yet published carry the last observation forward. 2020 is left out of
+  the estimation.
+
+Usage:
+    python macro_models.py all [history_months]
+    python macro_models.py recession [history_months]
+    python macro_models.py nowcast
+
+Needs FRED_API_KEY.
+"""
+import sys
+import json
+from datetime import datetime
+from pathlib import Path
+
+import numpy as np
+import pandas as pd
+from scipy.stats import norm
+
+# fred_data.py lives in the scripts root
+SCRIPTS_DIR = Path(__file__).resolve().parent.parent.parent
+sys.path.insert(0, str(SCRIPTS_DIR))
+
+from fred_data import get_series  # noqa: E402
+
+START_DATE = "1985-01-01"
+HORIZON_MONTHS = 12
+
+RECESSION_SERIES = {
+    "spread": "T10Y3M",
+    "claims": "ICSA",
+    "philly": "GACDFSA066MSFRBPHI",
+    "empire": "GACDINA066MSFRBNY",
+    "recession": "USREC",
+}
+
+NOWCAST_SERIES = {
+    "gdp": "GDPC1",
+    "indpro": "INDPRO",
+    "payems": "PAYEMS",
+    "retail": "RRSFS",
+    "claims": "ICSA",
+}
+
+
+def fetch_monthly(series_id: str, frequency: str = "m", transform: str = None) -> pd.Series:
+    """FRED series as a month-start indexed Series (FRED aggregates by average)."""
+    result = get_series(series_id, START_DATE, None, frequency, transform)
+    if "error" in result:
+        raise RuntimeError(f"{series_id}: {result['error']}")
+    obs = result.get("observations", [])
+    if not obs:
+        raise RuntimeError(f"{series_id}: no observations")
+    s = pd.Series({pd.Timestamp(o["date"]): o["value"] for o in obs}, dtype=float).sort_index()
+    s.name = series_id
+    return s
+
+
+def fit_probit(X: np.ndarray, y: np.ndarray, max_iter: int = 100):
+    """Probit MLE by Fisher scoring. Returns (beta, std_err, pseudo_r2)."""
+    beta = np.zeros(X.shape[1])
+    for _ in range(max_iter):
+        xb = X @ beta
+        p = np.clip(norm.cdf(xb), 1e-10, 1 - 1e-10)
+        pdf = norm.pdf(xb)
+        w = pdf ** 2 / (p * (1 - p))
+        grad = X.T @ (pdf * (y - p) / (p * (1 - p)))
+        info = X.T @ (X * w[:, None])
+        step = np.linalg.solve(info, grad)
+        beta = beta + step
+        if np.max(np.abs(step)) < 1e-8:
+            break
+    p = np.clip(norm.cdf(X @ beta), 1e-10, 1 - 1e-10)
+    w = norm.pdf(X @ beta) ** 2 / (p * (1 - p))
+    cov = np.linalg.inv(X.T @ (X * w[:, None]))
+    ll = np.sum(y * np.log(p) + (1 - y) * np.log(1 - p))
+    p0 = np.clip(y.mean(), 1e-10, 1 - 1e-10)
+    ll0 = np.sum(y * np.log(p0) + (1 - y) * np.log(1 - p0))
+    return beta, np.sqrt(np.diag(cov)), 1 - ll / ll0
+
+
+def recession_model(history_months: int = 120) -> dict:
+    spread = fetch_monthly(RECESSION_SERIES["spread"])
+    claims = fetch_monthly(RECESSION_SERIES["claims"])
+    philly = fetch_monthly(RECESSION_SERIES["philly"])
+    rec = fetch_monthly(RECESSION_SERIES["recession"])
+    try:
+        empire = fetch_monthly(RECESSION_SERIES["empire"])
+        activity = pd.concat([philly, empire], axis=1).mean(axis=1)
+    except RuntimeError:
+        activity = philly  # Empire State survey starts in 2001 and may be unavailable
+
+    df = pd.DataFrame({
+        "spread": spread,
+        "claims_yoy": 100 * np.log(claims).diff(12),
+        "activity": activity,
+    })
+    # Carry the latest readings into the newest month so the current
+    # probability uses whatever has been published.
+    df = df.ffill(limit=2).dropna()
+    target = rec.shift(-HORIZON_MONTHS).reindex(df.index)
+    train = df[target.notna()]
+    y = target[target.notna()].values
+
+    features = ["spread", "claims_yoy", "activity"]
+    X = np.column_stack([np.ones(len(train))] + [train[c].values for c in features])
+    beta, se, r2 = fit_probit(X, y)
+    Xs = np.column_stack([np.ones(len(train)), train["spread"].values])
+    beta_s, se_s, r2_s = fit_probit(Xs, y)
+
+    X_all = np.column_stack([np.ones(len(df))] + [df[c].values for c in features])
+    prob = norm.cdf(X_all @ beta)
+    prob_s = norm.cdf(beta_s[0] + beta_s[1] * df["spread"].values)
+
+    history = []
+    for i in range(max(0, len(df) - history_months), len(df)):
+        history.append({
+            "date": df.index[i].strftime("%Y-%m-%d"),
+            "probability": round(float(prob[i]), 4),
+            "probability_spread_only": round(float(prob_s[i]), 4),
+            "recession": int(rec[df.index[i]]) if df.index[i] in rec.index else None,
+        })
+
+    latest = df.iloc[-1]
+    return {
+        "as_of": df.index[-1].strftime("%Y-%m-%d"),
+        "horizon_months": HORIZON_MONTHS,
+        "probability": round(float(prob[-1]), 4),
+        "probability_spread_only": round(float(prob_s[-1]), 4),
+        "inputs": {c: round(float(latest[c]), 4) for c in features},
+        "coefficients": {
+            name: {"beta": round(float(b), 6), "std_err": round(float(s), 6)}
+            for name, b, s in zip(["const"] + features, beta, se)
+        },
+        "coefficients_spread_only": {
+            name: {"beta": round(float(b), 6), "std_err": round(float(s), 6)}
+            for name, b, s in zip(["const", "spread"], beta_s, se_s)
+        },
+        "pseudo_r2": round(float(r2), 4),
+        "pseudo_r2_spread_only": round(float(r2_s), 4),
+        "observations": int(len(train)),
+        "history": history,
+    }
+
+
+def quarterly_growth(monthly: pd.Series, log_change: bool = False) -> pd.Series:
+    """Quarterly average of a monthly series -> q/q SAAR % (or log change x100)."""
+    q = monthly.resample("QS").mean()
+    if log_change:
+        return 100 * np.log(q).diff()
+    return 100 * ((q / q.shift(1)) ** 4 - 1)
+
+
+def nowcast_model() -> dict:
+    gdp = fetch_monthly(NOWCAST_SERIES["gdp"], frequency="q", transform="pca")
+    gdp.index = gdp.index.to_period("Q").to_timestamp()
+    indicators = {k: fetch_monthly(NOWCAST_SERIES[k]) for k in ("indpro", "payems", "retail", "claims")}
+
+    # Current quarter: fill unpublished months with the last observation.
+    today = pd.Timestamp(datetime.now().date())
+    current_q = today.to_period("Q").to_timestamp()
+    months_available = {}
+    filled = {}
+    for k, s in indicators.items():
+        end = current_q + pd.DateOffset(months=2)
+        idx = pd.date_range(s.index.min(), end, freq="MS")
+        months_available[k] = int(((s.index >= current_q) & (s.index <= end)).sum())
+        filled[k] = s.reindex(idx).ffill()
+
+    X = pd.DataFrame({
+        "indpro": quarterly_growth(filled["indpro"]),
+        "payems": quarterly_growth(filled["payems"]),
+        "retail": quarterly_growth(filled["retail"]),
+        "claims": quarterly_growth(filled["claims"], log_change=True),
+    }).dropna()
+
+    train = X.join(gdp.rename("gdp"), how="inner").dropna()
+    train = train[train.index.year != 2020]
+    features = ["indpro", "payems", "retail", "claims"]
+    A = np.column_stack([np.ones(len(train))] + [train[c].values for c in features])
+    coef, *_ = np.linalg.lstsq(A, train["gdp"].values, rcond=None)
+    fitted = A @ coef
+    resid = train["gdp"].values - fitted
+    r2 = 1 - np.sum(resid ** 2) / np.sum((train["gdp"].values - train["gdp"].mean()) ** 2)
+
+    # Nowcast the latest quarter GDP has not been published for yet.
+    pending = [q for q in X.index if q > gdp.index.max()]
+    target_q = pending[-1] if pending else X.index[-1]
+    row = X.loc[target_q]
+    nowcast = float(coef[0] + sum(c * row[f] for c, f in zip(coef[1:], features)))
+
+    recent = []
+    for q in X.index[-8:]:
+        x = X.loc[q]
+        recent.append({
+            "quarter": f"{q.year}Q{(q.month - 1) // 3 + 1}",
+            "model": round(float(coef[0] + sum(c * x[f] for c, f in zip(coef[1:], features))), 3),
+            "actual": round(float(gdp[q]), 3) if q in gdp.index else None,
+        })
+
+    return {
+        "quarter": f"{target_q.year}Q{(target_q.month - 1) // 3 + 1}",
+        "nowcast_saar_pct": round(nowcast, 3),
+        "last_release": {
+            "quarter": f"{gdp.index[-1].year}Q{(gdp.index[-1].month - 1) // 3 + 1}",
+            "saar_pct": round(float(gdp.iloc[-1]), 3),
+        },
+        "inputs": {f: round(float(row[f]), 4) for f in features},
+        "months_available": months_available if target_q == current_q else {k: 3 for k in features},
+        "coefficients": {
+            name: round(float(c), 6) for name, c in zip(["const"] + features, coef)
+        },
+        "r_squared": round(float(r2), 4),
+        "rmse": round(float(np.sqrt(np.mean(resid ** 2))), 4),
+        "observations": int(len(train)),
+        "recent": recent,
+    }
+
+
+def main(args=None):
+    if args is None:
+        args = sys.argv[1:]
+    command = args[0] if args else "all"
+    history_months = int(args[1]) if len(args) > 1 else 120
+
+    try:
+        if command == "recession":
+            data = {"recession": recession_model(history_months)}
+        elif command == "nowcast":
+            data = {"nowcast": nowcast_model()}
+        elif command == "all":
+            data = {"recession": recession_model(history_months), "nowcast": nowcast_model()}
+        else:
+            print(json.dumps({"success": False, "error": f"Unknown command: {command}",
+                              "commands": ["all [history_months]", "recession [history_months]", "nowcast"]}))
+            return
+        data["computed_at"] = datetime.utcnow().strftime("%Y-%m-%dT%H:%M:%SZ")
+        print(json.dumps({"success": True, "data": data}))
+    except Exception as e:
+        print(json.dumps({"success": False, "error": str(e)}))
+
+
+if __name__ == "__main__":
+    main()
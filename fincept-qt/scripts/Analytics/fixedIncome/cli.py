@@ -141,6 +141,12 @@ COMMANDS = {
         'description': 'Fit Nelson-Siegel model to yield curve',
         'params': ['maturities', 'yields']
     },
+    'curve_history': {
+        'module': 'yield_curve',
+        'analysis_type': 'curve_history',
+        'description': 'Fit Nelson-Siegel/Svensson parameters to a series of dated curves',
+        'params': ['curves', 'model', 'prev_taus']
+    },
     'curve_shape': {
         'module': 'yield_curve',
         'analysis_type': 'curve_shape',
@@ -0,0 +1,112 @@
+"""
+Yield Curve History
+===================
+
+Fetches daily government curves and fits Nelson-Siegel or Svensson
+parameters to every date in a range, for curve animation and
+level/slope/curvature factor series.
+
+Sources:
+  - treasury: US Treasury constant maturities (Federal Reserve H.15, 1M-30Y)
+  - ecb:      Euro area AAA spot curve (ECB, 3M-30Y)
+
+Usage:
+    python curve_history.py <treasury|ecb> <nelson_siegel|svensson> <start_date> [end_date] [prev_taus]
+
+prev_taus is a comma-separated list of the taus fitted on the date before
+start_date; it seeds the first fit so stored factor series stay continuous.
+Yields are decimals (0.0425 = 4.25%), maturities are years.
+"""
+
+import sys
+import json
+import asyncio
+from datetime import datetime
+from pathlib import Path
+
+# Data fetchers live in the scripts root
+SCRIPTS_DIR = Path(__file__).resolve().parent.parent.parent
+sys.path.insert(0, str(SCRIPTS_DIR))
+
+from yield_curve import YieldCurveBuilder
+
+
+def maturity_years(name: str) -> float:
+    """'month_3' -> 0.25, 'year_10' -> 10.0"""
+    unit, _, n = name.partition('_')
+    return float(n) / 12.0 if unit == 'month' else float(n)
+
+
+def treasury_curves(start_date: str, end_date: str) -> list:
+    from federal_reserve_data import FederalReserveWrapper, TREASURY_MATURITIES
+
+    result = FederalReserveWrapper().get_treasury_rates(start_date, end_date)
+    if not result.get('success'):
+        raise RuntimeError(result.get('error', 'treasury_rates failed'))
+    curves = []
+    for row in result.get('data', []):
+        points = [(maturity_years(m), row.get(m)) for m in TREASURY_MATURITIES]
+        points = [(t, y) for t, y in points if y is not None]
+        curves.append({'date': row['date'], 'maturities': [t for t, _ in points], 'yields': [y for _, y in points]})
+    return curves
+
+
+def ecb_curves(start_date: str, end_date: str) -> list:
+    from ecb_data import ECBDataWrapper
+
+    result = asyncio.run(ECBDataWrapper().get_yield_curve_data('aaa', 'spot_rate'))
+    if not result.get('success'):
+        raise RuntimeError(result.get('error', 'ECB yield_curve failed'))
+    by_date = {}
+    for p in result.get('data', []):
+        date = (p.get('date') or '')[:10]
+        if start_date <= date <= end_date and p.get('rate') is not None:
+            by_date.setdefault(date, []).append((maturity_years(p['maturity']), p['rate']))
+    curves = []
+    for date in sorted(by_date):
+        points = sorted(by_date[date])
+        curves.append({'date': date, 'maturities': [t for t, _ in points], 'yields': [y for _, y in points]})
+    return curves
+
+
+def main(args=None):
+    if args is None:
+        args = sys.argv[1:]
+    if len(args) < 3:
+        print(json.dumps({
+            'success': False,
+            'error': 'Usage: python curve_history.py <treasury|ecb> <nelson_siegel|svensson> '
+                     '<start_date> [end_date] [prev_taus]'
+        }))
+        return
+
+    source, model, start_date = args[0], args[1], args[2]
+    end_date = args[3] if len(args) > 3 and args[3] else datetime.now().strftime('%Y-%m-%d')
+    prev_taus = [float(x) for x in args[4].split(',') if x] if len(args) > 4 and args[4] else None
+
+    try:
+        if source == 'treasury':
+            curves = treasury_curves(start_date, end_date)
+        elif source == 'ecb':
+            curves = ecb_curves(start_date, end_date)
+        else:
+            print(json.dumps({'success': False, 'error': f'Unknown source: {source}'}))
+            return
+        curves.sort(key=lambda c: c['date'])
+        fitted = YieldCurveBuilder().fit_curve_history(curves, model, prev_taus)
+        print(json.dumps({
+            'success': True,
+            'data': {
+                'source': source,
+                'model': fitted['model'],
+                'start_date': start_date,
+                'end_date': end_date,
+                'fits': fitted['fits'],
+            }
+        }))
+    except Exception as e:
+        print(json.dumps({'success': False, 'error': str(e)}))
+
+
+if __name__ == '__main__':
+    main()
@@ -374,6 +374,100 @@ class YieldCurveBuilder:
             }
         }
 
+    def fit_curve_history(
+        self,
+        curves: List[Dict[str, Any]],
+        model: str = 'nelson_siegel',
+        prev_taus: Optional[List[float]] = None,
+    ) -> Dict[str, Any]:
+        """
+        Fit Nelson-Siegel or Svensson parameters to a sequence of dated curves.
+
+        Unlike fit_nelson_siegel/fit_svensson, the betas are solved by least
+        squares for each candidate tau (the model is linear in them) and only
+        the tau(s) are searched - a coarse grid, then a bounded refinement.
+        Each date's search is also seeded from the previous date's taus, so
+        the fitted factors move smoothly instead of jumping between local
+        minima, which is what a level/slope/curvature time series needs.
+
+        Args:
+            curves: [{'date', 'maturities', 'yields'}], oldest first
+            model: 'nelson_siegel' or 'svensson'
+            prev_taus: taus of the fit preceding curves[0], if any
+
+        Returns:
+            Dictionary with one parameter set per date
+        """
+        svensson = model == 'svensson'
+        n_taus = 2 if svensson else 1
+        tau_lo, tau_hi = 0.1, 30.0
+
+        def design(t, taus):
+            cols = [np.ones_like(t)]
+            for i, tau in enumerate(taus):
+                x = t / tau
+                f1 = (1 - np.exp(-x)) / x
+                if i == 0:
+                    cols.append(f1)
+                cols.append(f1 - np.exp(-x))
+            return np.column_stack(cols)
+
+        def solve(t, y, taus):
+            X = design(t, taus)
+            betas, *_ = np.linalg.lstsq(X, y, rcond=None)
+            resid = y - X @ betas
+            return betas, float(np.sum(resid ** 2))
+
+        grid = np.geomspace(0.2, 20.0, 24)
+        if svensson:
+            candidates = [(a, b) for a in grid for b in grid if b > a * 1.5]
+        else:
+            candidates = [(a,) for a in grid]
+
+        fits = []
+        seed = list(prev_taus) if prev_taus and len(prev_taus) == n_taus else None
+        for curve in curves:
+            t = np.array(curve.get('maturities', []), dtype=float)
+            y = np.array(curve.get('yields', []), dtype=float)
+            mask = np.isfinite(t) & np.isfinite(y) & (t > 0)
+            t, y = t[mask], y[mask]
+            if len(t) < n_taus + 3:
+                continue
+
+            starts = [seed] if seed else []
+            best = min(candidates, key=lambda c: solve(t, y, c)[1])
+            starts.append(list(best))
+            result = None
+            for x0 in starts:
+                try:
+                    r = optimize.minimize(lambda p: solve(t, y, p)[1], x0, method='L-BFGS-B',
+                                          bounds=[(tau_lo, tau_hi)] * n_taus)
+                except Exception:
+                    continue
+                if result is None or r.fun < result.fun:
+                    result = r
+            taus = list(result.x) if result is not None else list(best)
+            betas, sse = solve(t, y, taus)
+            seed = taus
+
+            params = {'beta0': float(betas[0]), 'beta1': float(betas[1]), 'beta2': float(betas[2])}
+            if svensson:
+                params.update({'beta3': float(betas[3]), 'tau1': float(taus[0]), 'tau2': float(taus[1])})
+            else:
+                params['tau1'] = float(taus[0])
+            fits.append({
+                'date': curve.get('date'),
+                'parameters': {k: round(v, 8) for k, v in params.items()},
+                'rmse': round(float(np.sqrt(sse / len(t))), 8),
+                'observed': [{'maturity': float(m), 'yield': float(v)} for m, v in zip(t, y)],
+            })
+
+        return {
+            'model': 'svensson' if svensson else 'nelson_siegel',
+            'fits': fits,
+            'num_fits': len(fits),
+        }
+
     def interpolate_curve(
         self,
         maturities: List[float],
@@ -743,6 +837,14 @@ def run_yield_curve_analysis(params: Dict[str, Any]) -> Dict[str, Any]:
                 yields=params.get('yields', [])
             )
 
+        elif analysis_type == 'curve_history':
+            builder = YieldCurveBuilder()
+            return builder.fit_curve_history(
+                curves=params.get('curves', []),
+                model=params.get('model', 'nelson_siegel'),
+                prev_taus=params.get('prev_taus')
+            )
+
         elif analysis_type == 'interpolate':
             builder = YieldCurveBuilder()
             method = InterpolationMethod(params.get('method', 'cubic_spline'))
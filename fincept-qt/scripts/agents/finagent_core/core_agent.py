@@ -276,6 +276,23 @@ class CoreAgent:
         )
         return workflow.run(portfolio_data)
 
+    def run_debate(self, symbol: str, config: Dict[str, Any] = None, rounds: int = 2,
+                   context: str = "") -> Dict[str, Any]:
+        """Run a bull / bear / risk debate and return its structured verdict."""
+        from finagent_core.modules import DebateOrchestrator
+        from finagent_core.modules.workflow_module import _load_workflow_tools
+        cfg = config or {}
+        model_config = cfg.get("model") or self._resolve_model_config()
+        tools = _load_workflow_tools(
+            self.api_keys,
+            cfg.get("terminal_mcp_endpoint"),
+            cfg.get("terminal_tools", []),
+            cfg.get("terminal_mcp_token"),
+            cfg.get("terminal_mcp_destructive_token"),
+            bool(cfg.get("tools_dry_run", False)),
+        )
+        return DebateOrchestrator(self.api_keys, model_config, tools=tools, rounds=rounds).run(symbol, context)
+
     # ============================================================
     # Knowledge & Memory
     # ============================================================
@@ -517,6 +534,28 @@ class CoreAgent:
         # Fallback to string conversion
         return str(response)
 
+    def get_response_usage(self, response) -> Optional[Dict[str, int]]:
+        """Token usage of a run as {input_tokens, output_tokens, total_tokens}.
+
+        Agno reports ``metrics`` either as an object with int fields or (older
+        releases) as a dict of per-call lists; both are summed. None when the
+        model did not report usage.
+        """
+        metrics = getattr(response, 'metrics', None)
+        if metrics is None:
+            return None
+
+        def _total(name: str) -> int:
+            value = metrics.get(name) if isinstance(metrics, dict) else getattr(metrics, name, None)
+            if isinstance(value, (list, tuple)):
+                return int(sum(v for v in value if isinstance(v, (int, float))))
+            return int(value) if isinstance(value, (int, float)) else 0
+
+        usage = {k: _total(k) for k in ('input_tokens', 'output_tokens', 'total_tokens')}
+        if not usage['total_tokens']:
+            usage['total_tokens'] = usage['input_tokens'] + usage['output_tokens']
+        return usage if usage['total_tokens'] else None
+
     def get_module(self, name: str) -> Optional[Any]:
         """Get a specific module instance."""
         return self._modules.get(name)
@@ -0,0 +1,446 @@
+"""
+DocumentIndex — local retrieval store over SEC filings, transcripts, notes
+and files, used by agents to ground answers with citations.
+
+Documents are split into overlapping chunks; every chunk is embedded and
+mirrored into FTS5. Retrieval is hybrid: cosine similarity over the stored
+vectors fused with BM25 by reciprocal rank, so exact terms (tickers, line
+items) and paraphrases both surface.
+
+Embedders:
+- "local" (default) — signed feature hashing of words and word bigrams into
+  512 dims. Deterministic, offline, no model download.
+- "<provider>[:<model>]" — any EmbedderRegistry provider (openai, ollama, …)
+  using the api_keys of the request.
+Each document remembers its embedder; a search embeds the query once per
+embedder present, so re-indexing with a different embedder never mixes
+incompatible vectors.
+
+Schema (doc_index.db under the finagent data dir):
+  doc_index_documents(id, kind, title, ticker, source_ref, doc_date, embedder,
+                      content_hash, chunk_count, char_count, metadata_json,
+                      indexed_at)
+  doc_index_chunks(id, doc_id, ord, text, embedding)  — float32, L2-normalised
+  + FTS5 mirror doc_index_chunks_fts(text) when available
+"""
+from __future__ import annotations
+
+import hashlib
+import json
+import re
+import sqlite3
+import subprocess
+import sys
+import uuid
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Tuple
+
+import numpy as np
+
+from finagent_core.resources import base_dir
+
+KINDS = ("filing", "transcript", "note", "file", "other")
+LOCAL_EMBEDDER = "local"
+LOCAL_DIMS = 512
+CHUNK_CHARS = 1200
+CHUNK_OVERLAP = 200
+_RRF_K = 60  # reciprocal rank fusion constant
+
+_FILING_COMMANDS = {"10-K": "10k_full_text", "10-Q": "10q_full_text", "8-K": "8k_full_text"}
+
+_SCHEMA = [
+    """
+    CREATE TABLE IF NOT EXISTS doc_index_documents (
+        id            TEXT PRIMARY KEY,
+        kind          TEXT NOT NULL,
+        title         TEXT NOT NULL,
+        ticker        TEXT NOT NULL DEFAULT '',
+        source_ref    TEXT NOT NULL DEFAULT '',
+        doc_date      TEXT NOT NULL DEFAULT '',
+        embedder      TEXT NOT NULL,
+        content_hash  TEXT NOT NULL,
+        chunk_count   INTEGER NOT NULL DEFAULT 0,
+        char_count    INTEGER NOT NULL DEFAULT 0,
+        metadata_json TEXT NOT NULL DEFAULT '{}',
+        indexed_at    TEXT NOT NULL
+    )
+    """,
+    """
+    CREATE TABLE IF NOT EXISTS doc_index_chunks (
+        id        INTEGER PRIMARY KEY AUTOINCREMENT,
+        doc_id    TEXT NOT NULL,
+        ord       INTEGER NOT NULL,
+        text      TEXT NOT NULL,
+        embedding BLOB NOT NULL
+    )
+    """,
+    "CREATE INDEX IF NOT EXISTS idx_doc_index_chunks_doc ON doc_index_chunks(doc_id, ord)",
+    "CREATE INDEX IF NOT EXISTS idx_doc_index_documents_ticker ON doc_index_documents(ticker, kind)",
+]
+
+_CREATE_FTS = """
+CREATE VIRTUAL TABLE IF NOT EXISTS doc_index_chunks_fts USING fts5(
+    text,
+    content='doc_index_chunks', content_rowid='id'
+)
+"""
+
+_CREATE_TRIGGERS = [
+    """
+    CREATE TRIGGER IF NOT EXISTS doc_index_chunks_ai AFTER INSERT ON doc_index_chunks BEGIN
+      INSERT INTO doc_index_chunks_fts(rowid, text) VALUES (new.id, new.text);
+    END;
+    """,
+    """
+    CREATE TRIGGER IF NOT EXISTS doc_index_chunks_ad AFTER DELETE ON doc_index_chunks BEGIN
+      INSERT INTO doc_index_chunks_fts(doc_index_chunks_fts, rowid, text) VALUES('delete', old.id, old.text);
+    END;
+    """,
+]
+
+_WORD_RE = re.compile(r"[a-z0-9][a-z0-9.\-']*")
+_TAG_RE = re.compile(r"<[^>]+>")
+
+
+def default_db_path() -> str:
+    d = Path(base_dir())
+    d.mkdir(parents=True, exist_ok=True)
+    return str(d / "doc_index.db")
+
+
+# ── Chunking ─────────────────────────────────────────────────────────────────
+
+def chunk_text(text: str, size: int = CHUNK_CHARS, overlap: int = CHUNK_OVERLAP) -> List[str]:
+    """Pack paragraphs into ~size-char chunks; consecutive chunks share `overlap` chars."""
+    paragraphs = [p.strip() for p in re.split(r"\n\s*\n", text or "") if p.strip()]
+    pieces: List[str] = []
+    for p in paragraphs:
+        while len(p) > size:
+            cut = p.rfind(" ", 0, size)
+            cut = cut if cut > size // 2 else size
+            pieces.append(p[:cut])
+            p = p[cut:].lstrip()
+        if p:
+            pieces.append(p)
+
+    chunks: List[str] = []
+    current = ""
+    for piece in pieces:
+        if current and len(current) + len(piece) + 2 > size:
+            chunks.append(current)
+            tail = current[-overlap:]
+            space = tail.find(" ")
+            current = (tail[space + 1:] if space >= 0 else tail) + "\n\n" + piece
+        else:
+            current = f"{current}\n\n{piece}" if current else piece
+    if current:
+        chunks.append(current)
+    return chunks
+
+
+# ── Embedding ────────────────────────────────────────────────────────────────
+
+def _local_embed(text: str) -> np.ndarray:
+    vec = np.zeros(LOCAL_DIMS, dtype=np.float32)
+    words = _WORD_RE.findall(text.lower())
+    features = words + [f"{a} {b}" for a, b in zip(words, words[1:])]
+    for f in features:
+        h = int.from_bytes(hashlib.blake2b(f.encode(), digest_size=8).digest(), "little")
+        vec[h % LOCAL_DIMS] += 1.0 if (h >> 63) & 1 else -1.0
+    return vec
+
+
+class _Embedder:
+    """Wraps the local hashing embedder or an EmbedderRegistry provider."""
+
+    def __init__(self, spec: str, api_keys: Dict[str, str]):
+        self.spec = (spec or LOCAL_EMBEDDER).strip()
+        self._remote = None
+        if self.spec != LOCAL_EMBEDDER:
+            from finagent_core.registries.embedder_registry import EmbedderRegistry
+            provider, _, model = self.spec.partition(":")
+            self._remote = EmbedderRegistry.create_embedder(provider, model or None, api_keys=api_keys)
+
+    def embed(self, text: str) -> np.ndarray:
+        vec = (np.asarray(self._remote.get_embedding(text), dtype=np.float32)
+               if self._remote is not None else _local_embed(text))
+        norm = float(np.linalg.norm(vec))
+        return vec / norm if norm > 0 else vec
+
+
+# ── Text extraction ──────────────────────────────────────────────────────────
+
+def read_file_text(path: str) -> str:
+    p = Path(path)
+    if not p.is_file():
+        raise FileNotFoundError(path)
+    suffix = p.suffix.lower()
+    if suffix == ".pdf":
+        try:
+            from pypdf import PdfReader
+        except ImportError:
+            raise RuntimeError("Indexing PDFs needs the 'pypdf' package")
+        return "\n\n".join((page.extract_text() or "") for page in PdfReader(str(p)).pages)
+    raw = p.read_text(encoding="utf-8", errors="replace")
+    if suffix in (".html", ".htm", ".xml"):
+        return _TAG_RE.sub(" ", raw)
+    return raw
+
+
+def fetch_filing_text(ticker: str, form: str) -> Dict[str, Any]:
+    """Latest filing text via the Edgar CLI (scripts/mcp/edgar), run out of process so
+    its top-level ``mcp`` package never shadows the MCP client library here."""
+    command = _FILING_COMMANDS.get(form.upper())
+    if not command:
+        raise ValueError(f"Unsupported form '{form}'. Use one of {list(_FILING_COMMANDS)}")
+    scripts_dir = Path(__file__).resolve().parents[2]
+    proc = subprocess.run([sys.executable, "-m", "mcp.edgar.main", command, ticker],
+                          cwd=str(scripts_dir), capture_output=True, text=True, timeout=300)
+    out = proc.stdout
+    start = out.find("{")
+    if start < 0:
+        raise RuntimeError((proc.stderr or "Edgar returned no output").strip()[-500:])
+    result = json.loads(out[start:])
+    if not result.get("success"):
+        err = result.get("error")
+        raise RuntimeError(err.get("message", str(err)) if isinstance(err, dict) else str(err))
+    return result["data"]
+
+
+# ── Store ────────────────────────────────────────────────────────────────────
+
+class DocumentIndex:
+    """Chunk / embed / store documents and run hybrid retrieval over them."""
+
+    def __init__(self, db_path: Optional[str] = None, api_keys: Optional[Dict[str, str]] = None):
+        self.db_path = db_path or default_db_path()
+        self.api_keys = api_keys or {}
+        self._embedders: Dict[str, _Embedder] = {}
+        self._has_fts = False
+        self._ensure_schema()
+
+    def _conn(self) -> sqlite3.Connection:
+        conn = sqlite3.connect(self.db_path)
+        conn.row_factory = sqlite3.Row
+        return conn
+
+    def _ensure_schema(self) -> None:
+        with self._conn() as conn:
+            for stmt in _SCHEMA:
+                conn.execute(stmt)
+            try:
+                conn.execute(_CREATE_FTS)
+                for t in _CREATE_TRIGGERS:
+                    conn.execute(t)
+                self._has_fts = True
+            except sqlite3.OperationalError:
+                self._has_fts = False
+
+    def _embedder(self, spec: str) -> _Embedder:
+        spec = (spec or LOCAL_EMBEDDER).strip()
+        if spec not in self._embedders:
+            self._embedders[spec] = _Embedder(spec, self.api_keys)
+        return self._embedders[spec]
+
+    # ── Indexing ─────────────────────────────────────────────────────────────
+
+    def add(self, kind: str, title: str, text: str, ticker: str = "", source_ref: str = "",
+            doc_date: str = "", metadata: Optional[Dict[str, Any]] = None,
+            embedder: str = LOCAL_EMBEDDER) -> Dict[str, Any]:
+        """Index one document. A document with the same kind + source_ref is replaced,
+        or left alone when its text and embedder are unchanged."""
+        kind = kind if kind in KINDS else "other"
+        text = (text or "").strip()
+        if not text:
+            raise ValueError("Document has no text")
+        ticker = (ticker or "").upper()
+        embedder = (embedder or LOCAL_EMBEDDER).strip()
+        doc_id = (hashlib.sha1(f"{kind}|{source_ref}".encode()).hexdigest()[:16]
+                  if source_ref else uuid.uuid4().hex[:16])
+        content_hash = hashlib.sha256(f"{embedder}\n{text}".encode()).hexdigest()
+
+        with self._conn() as conn:
+            row = conn.execute("SELECT content_hash, chunk_count FROM doc_index_documents WHERE id = ?",
+                               (doc_id,)).fetchone()
+        if row and row["content_hash"] == content_hash:
+            return {"id": doc_id, "title": title, "chunks": row["chunk_count"], "unchanged": True}
+
+        emb = self._embedder(embedder)
+        chunks = chunk_text(text)
+        vectors = [emb.embed(c).astype(np.float32).tobytes() for c in chunks]
+        now = datetime.utcnow().isoformat()
+        with self._conn() as conn:
+            conn.execute("DELETE FROM doc_index_chunks WHERE doc_id = ?", (doc_id,))
+            conn.execute(
+                "INSERT OR REPLACE INTO doc_index_documents (id, kind, title, ticker, source_ref, doc_date, "
+                "embedder, content_hash, chunk_count, char_count, metadata_json, indexed_at) "
+                "VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                (doc_id, kind, title or source_ref or kind, ticker, source_ref, doc_date or "", embedder,
+                 content_hash, len(chunks), len(text), json.dumps(metadata or {}), now),
+            )
+            conn.executemany(
+                "INSERT INTO doc_index_chunks (doc_id, ord, text, embedding) VALUES (?, ?, ?, ?)",
+                [(doc_id, i, c, v) for i, (c, v) in enumerate(zip(chunks, vectors))],
+            )
+        return {"id": doc_id, "title": title, "chunks": len(chunks), "unchanged": False}
+
+    def add_filing(self, ticker: str, form: str = "10-K", embedder: str = LOCAL_EMBEDDER) -> Dict[str, Any]:
+        data = fetch_filing_text(ticker, form)
+        accession = data.get("accession_number", "")
+        filed = str(data.get("filing_date", ""))
+        return self.add(
+            kind="filing",
+            title=f"{ticker.upper()} {form.upper()} {filed}".strip(),
+            text=data.get("text", ""),
+            ticker=ticker,
+            source_ref=f"sec:{accession}" if accession else f"sec:{ticker.upper()}:{form.upper()}:{filed}",
+            doc_date=filed,
+            metadata={"form": form.upper(), "accession_number": accession},
+            embedder=embedder,
+        )
+
+    def remove(self, doc_id: str) -> bool:
+        with self._conn() as conn:
+            conn.execute("DELETE FROM doc_index_chunks WHERE doc_id = ?", (doc_id,))
+            return conn.execute("DELETE FROM doc_index_documents WHERE id = ?", (doc_id,)).rowcount > 0
+
+    def list(self, kind: str = "", ticker: str = "", limit: int = 100) -> List[Dict[str, Any]]:
+        clauses, args = [], []
+        if kind:
+            clauses.append("kind = ?"); args.append(kind)
+        if ticker:
+            clauses.append("ticker = ?"); args.append(ticker.upper())
+        where = ("WHERE " + " AND ".join(clauses)) if clauses else ""
+        args.append(limit)
+        with self._conn() as conn:
+            rows = conn.execute(f"SELECT * FROM doc_index_documents {where} ORDER BY indexed_at DESC LIMIT ?",
+                                args).fetchall()
+        return [self._doc_to_dict(r) for r in rows]
+
+    # ── Retrieval ────────────────────────────────────────────────────────────
+
+    def search(self, query: str, k: int = 8, kind: str = "", ticker: str = "") -> List[Dict[str, Any]]:
+        query = (query or "").strip()
+        if not query:
+            return []
+        clauses, args = [], []
+        if kind:
+            clauses.append("d.kind = ?"); args.append(kind)
+        if ticker:
+            clauses.append("d.ticker = ?"); args.append(ticker.upper())
+        where = (" AND " + " AND ".join(clauses)) if clauses else ""
+
+        with self._conn() as conn:
+            rows = conn.execute(
+                "SELECT c.id, c.embedding, d.embedder FROM doc_index_chunks c "
+                f"JOIN doc_index_documents d ON d.id = c.doc_id WHERE 1 = 1{where}", args,
+            ).fetchall()
+        vector_rank = self._vector_rank(query, rows, k * 4)
+        lexical_rank = self._lexical_rank(query, where, args, k * 4)
+
+        fused: Dict[int, float] = {}
+        for ranking in (vector_rank, lexical_rank):
+            for pos, chunk_id in enumerate(ranking):
+                fused[chunk_id] = fused.get(chunk_id, 0.0) + 1.0 / (_RRF_K + pos + 1)
+        top = sorted(fused.items(), key=lambda kv: kv[1], reverse=True)[:k]
+        if not top:
+            return []
+
+        ids = [cid for cid, _ in top]
+        with self._conn() as conn:
+            hits = {r["chunk_id"]: r for r in conn.execute(
+                "SELECT c.id AS chunk_id, c.ord, c.text, d.* FROM doc_index_chunks c "
+                "JOIN doc_index_documents d ON d.id = c.doc_id "
+                f"WHERE c.id IN ({','.join('?' * len(ids))})", ids).fetchall()}
+
+        results = []
+        for n, (cid, score) in enumerate(top, start=1):
+            r = hits.get(cid)
+            if r is None:
+                continue
+            doc = self._doc_to_dict(r)
+            results.append({
+                "ref": n,
+                "score": round(score, 5),
+                "text": r["text"],
+                "chunk": r["ord"] + 1,
+                "document": doc,
+                "citation": self._citation(n, doc, r["ord"] + 1),
+            })
+        return results
+
+    def _vector_rank(self, query: str, rows: List[sqlite3.Row], n: int) -> List[int]:
+        by_embedder: Dict[str, List[Tuple[int, bytes]]] = {}
+        for r in rows:
+            by_embedder.setdefault(r["embedder"], []).append((r["id"], r["embedding"]))
+        scored: List[Tuple[float, int]] = []
+        for spec, items in by_embedder.items():
+            try:
+                q = self._embedder(spec).embed(query)
+            except Exception:
+                continue  # provider unavailable now; lexical ranking still covers these chunks
+            matrix = np.vstack([np.frombuffer(blob, dtype=np.float32) for _, blob in items])
+            if matrix.shape[1] != q.shape[0]:
+                continue
+            sims = matrix @ q
+            scored.extend((float(s), cid) for (cid, _), s in zip(items, sims))
+        scored.sort(reverse=True)
+        return [cid for _, cid in scored[:n]]
+
+    def _lexical_rank(self, query: str, where: str, args: List[Any], n: int) -> List[int]:
+        terms = [t for t in re.sub(r"[^\w\s-]", " ", query).split() if len(t) > 1]
+        if not terms:
+            return []
+        with self._conn() as conn:
+            if self._has_fts:
+                try:
+                    match = " OR ".join(f'"{t}"' for t in terms)
+                    rows = conn.execute(
+                        "SELECT c.id FROM doc_index_chunks_fts f "
+                        "JOIN doc_index_chunks c ON c.id = f.rowid "
+                        "JOIN doc_index_documents d ON d.id = c.doc_id "
+                        f"WHERE doc_index_chunks_fts MATCH ?{where} "
+                        "ORDER BY bm25(doc_index_chunks_fts) ASC LIMIT ?",
+                        [match, *args, n],
+                    ).fetchall()
+                    return [r["id"] for r in rows]
+                except sqlite3.OperationalError:
+                    pass
+            rows = conn.execute(
+                "SELECT c.id FROM doc_index_chunks c JOIN doc_index_documents d ON d.id = c.doc_id "
+                f"WHERE c.text LIKE ?{where} LIMIT ?",
+                [f"%{terms[0]}%", *args, n],
+            ).fetchall()
+        return [r["id"] for r in rows]
+
+    @staticmethod
+    def _citation(n: int, doc: Dict[str, Any], chunk: int) -> str:
+        parts = [doc["title"]]
+        if doc["doc_date"] and doc["doc_date"] not in doc["title"]:
+            parts.append(doc["doc_date"])
+        parts.append(f"{doc['kind']}, chunk {chunk}/{doc['chunk_count']}")
+        if doc["source_ref"]:
+            parts.append(doc["source_ref"])
+        return f"[{n}] " + " — ".join(parts)
+
+    @staticmethod
+    def _doc_to_dict(r: sqlite3.Row) -> Dict[str, Any]:
+        return {
+            "id": r["id"],
+            "kind": r["kind"],
+            "title": r["title"],
+            "ticker": r["ticker"],
+            "source_ref": r["source_ref"],
+            "doc_date": r["doc_date"],
+            "embedder": r["embedder"],
+            "chunk_count": r["chunk_count"],
+            "char_count": r["char_count"],
+            "metadata": json.loads(r["metadata_json"] or "{}"),
+            "indexed_at": r["indexed_at"],
+        }
+
+
+def grounding_context(results: List[Dict[str, Any]]) -> str:
+    """Numbered excerpts an agent can quote and cite as [n]."""
+    return "\n\n".join(f"{r['citation']}\n{r['text']}" for r in results)
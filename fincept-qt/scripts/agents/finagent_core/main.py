@@ -148,6 +148,8 @@ def main(args=None):
 
     Actions:
     - Core agent: run, run_team, run_workflow, run_structured
+    - Debate: debate (bull / bear / risk debate, structured verdict)
+    - Document index: doc_index_add, doc_index_filing, doc_index_search, doc_index_list, doc_index_remove
     - Dynamic loading: discover_agents, list_agents, create_agent
     - SuperAgent: route_query, execute_query
     - Execution planner: create_plan, execute_plan, get_plan_status
@@ -331,6 +333,9 @@ def dispatch_action(
 
             response = agent.run(query, full_config, session_id, stream)
             result = {"success": True, "response": agent.get_response_content(response)}
+            usage = agent.get_response_usage(response)
+            if usage:
+                result["usage"] = usage
 
             # Check guardrails on output if enabled
             if agent._guardrails:
@@ -362,7 +367,11 @@ def dispatch_action(
                 return {"success": False, "error": "Input rejected by guardrails", "violations": guard_result["violations"]}
             query = guard_result["text"]
         response = agent.run_team(query, team_config, params.get("session_id"))
-        return {"success": True, "response": agent.get_response_content(response)}
+        result = {"success": True, "response": agent.get_response_content(response)}
+        usage = agent.get_response_usage(response)
+        if usage:
+            result["usage"] = usage
+        return result
 
     if action == "run_workflow":
         from finagent_core.core_agent import CoreAgent
@@ -646,6 +655,15 @@ def dispatch_action(
         response_text = _extract_workflow_response(result)
         return {"success": True, "symbol": symbol, "response": response_text, "result": result if isinstance(result, dict) else None}
 
+    if action == "debate":
+        from finagent_core.core_agent import CoreAgent
+        agent = CoreAgent(api_keys=api_keys, user_id=params.get("user_id"))
+        _setup_agent_modules(agent, config, params)
+        symbol = params.get("symbol")
+        if not symbol:
+            return {"success": False, "error": "Missing 'symbol' in params"}
+        return agent.run_debate(symbol, config, int(params.get("rounds", 2)), params.get("context", ""))
+
     if action == "portfolio_rebal":
         from finagent_core.core_agent import CoreAgent
         agent = CoreAgent(api_keys=api_keys, user_id=params.get("user_id"))
@@ -811,6 +829,64 @@ def dispatch_action(
         results = agent.search_knowledge(query, params.get("limit", 5))
         return {"success": True, "results": results, "count": len(results)}
 
+    # =========================================================================
+    # Document Index (RAG over filings, transcripts, notes, files)
+    # =========================================================================
+
+    if action == "doc_index_add":
+        from finagent_core.doc_index import DocumentIndex, read_file_text
+        index = DocumentIndex(api_keys=api_keys)
+        embedder = params.get("embedder", "local")
+        documents = params.get("documents") or [params]
+        indexed, errors = [], []
+        for doc in documents:
+            try:
+                text = doc.get("text") or (read_file_text(doc["path"]) if doc.get("path") else "")
+                indexed.append(index.add(doc.get("kind", "other"), doc.get("title", ""), text,
+                                         doc.get("ticker", ""), doc.get("source_ref", ""), doc.get("doc_date", ""),
+                                         doc.get("metadata"), embedder))
+            except Exception as e:
+                errors.append({"title": doc.get("title", ""), "source_ref": doc.get("source_ref", ""),
+                               "error": str(e)})
+        if not indexed and errors:
+            return {"success": False, "error": errors[0]["error"], "errors": errors}
+        return {"success": True, "indexed": indexed, "errors": errors, "count": len(indexed)}
+
+    if action == "doc_index_filing":
+        from finagent_core.doc_index import DocumentIndex
+        ticker = params.get("ticker")
+        if not ticker:
+            return {"success": False, "error": "Missing 'ticker' in params"}
+        try:
+            index = DocumentIndex(api_keys=api_keys)
+            doc = index.add_filing(ticker, params.get("form", "10-K"), params.get("embedder", "local"))
+            return {"success": True, "indexed": [doc], "errors": [], "count": 1}
+        except Exception as e:
+            return {"success": False, "error": str(e)}
+
+    if action == "doc_index_search":
+        from finagent_core.doc_index import DocumentIndex, grounding_context
+        query = params.get("query")
+        if not query:
+            return {"success": False, "error": "Missing 'query' in params"}
+        index = DocumentIndex(api_keys=api_keys)
+        results = index.search(query, int(params.get("k", 8)), params.get("kind", ""), params.get("ticker", ""))
+        return {"success": True, "results": results, "count": len(results), "context": grounding_context(results)}
+
+    if action == "doc_index_list":
+        from finagent_core.doc_index import DocumentIndex
+        docs = DocumentIndex().list(params.get("kind", ""), params.get("ticker", ""), int(params.get("limit", 100)))
+        return {"success": True, "documents": docs, "count": len(docs)}
+
+    if action == "doc_index_remove":
+        from finagent_core.doc_index import DocumentIndex
+        doc_id = params.get("id")
+        if not doc_id:
+            return {"success": False, "error": "Missing 'id' in params"}
+        if not DocumentIndex().remove(doc_id):
+            return {"success": False, "error": f"Unknown document: {doc_id}"}
+        return {"success": True, "id": doc_id}
+
     # =========================================================================
     # System Information
     # =========================================================================
@@ -5,6 +5,7 @@ Provides unified access to:
 - Memory (conversation history, user preferences, agentic memory)
 - Teams (multi-agent collaboration)
 - Workflows (complex task orchestration with Parallel, Condition, Loop, Router)
+- Debate (bull / bear / risk debate with a structured verdict)
 - Knowledge (RAG knowledge bases)
 - Reasoning (step-by-step reasoning)
 - Session (conversation state persistence, AI summaries, history search)
@@ -23,6 +24,7 @@ from finagent_core.modules.workflow_module import (
     WorkflowBuilder,
     FinancialWorkflowTemplates
 )
+from finagent_core.modules.debate_module import DebateOrchestrator
 from finagent_core.modules.knowledge_module import KnowledgeModule, KnowledgeBuilder
 from finagent_core.modules.reasoning_module import ReasoningModule, ReasoningBuilder
 from finagent_core.modules.session_module import SessionModule, SessionManager
@@ -87,6 +89,8 @@ __all__ = [
     "WorkflowModule",
     "WorkflowBuilder",
     "FinancialWorkflowTemplates",
+    # Debate
+    "DebateOrchestrator",
     # Knowledge
     "KnowledgeModule",
     "KnowledgeBuilder",
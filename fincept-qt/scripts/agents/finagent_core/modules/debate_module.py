@@ -0,0 +1,213 @@
+"""
+Debate Module - Bull / bear / risk debate with a structured verdict.
+
+Three analyst agents argue a ticker:
+1. Opening statements (bull, bear, risk run concurrently)
+2. Up to ``MAX_ROUNDS`` rebuttal rounds, each agent seeing the others'
+   latest arguments; stops early once no agent changes its stance
+3. A judge weighs the transcript and returns a JSON verdict:
+   direction, conviction, key evidence and dissent
+
+The verdict is persisted per ticker and date by the terminal
+(agent_debate_verdicts) and surfaced as a factor in the screener.
+"""
+
+from __future__ import annotations
+
+import json
+import re
+from concurrent.futures import ThreadPoolExecutor
+from datetime import date
+from typing import Any, Dict, List, Optional
+import logging
+
+from finagent_core.modules.workflow_module import _make_workflow_agent
+
+logger = logging.getLogger(__name__)
+
+MAX_ROUNDS = 4
+DEFAULT_ROUNDS = 2
+
+DIRECTIONS = ("bullish", "bearish", "neutral")
+
+ROLES: Dict[str, str] = {
+    "bull": (
+        "You are the BULL analyst on an investment committee. Build the strongest evidence-based "
+        "case for owning the stock: growth, margins, valuation upside, catalysts, positioning. "
+        "Cite concrete numbers from your tools. Concede points only when the evidence forces you to."
+    ),
+    "bear": (
+        "You are the BEAR analyst on an investment committee. Build the strongest evidence-based "
+        "case against owning the stock: deteriorating fundamentals, stretched valuation, competition, "
+        "negative catalysts. Cite concrete numbers from your tools. Concede points only when the "
+        "evidence forces you to."
+    ),
+    "risk": (
+        "You are the RISK officer on an investment committee. You do not pick a side up front: assess "
+        "volatility, drawdown history, leverage, liquidity, event and macro risk, and say whether the "
+        "risk/reward justifies a position at all. Cite concrete numbers from your tools."
+    ),
+}
+
+_STANCE_RE = re.compile(r"STANCE:\s*(bullish|bearish|neutral)", re.IGNORECASE)
+_JSON_RE = re.compile(r"\{.*\}", re.DOTALL)
+
+_STANCE_FOOTER = (
+    "\n\nKeep it under 250 words. End with a final line of exactly "
+    "'STANCE: bullish', 'STANCE: bearish' or 'STANCE: neutral'."
+)
+
+
+def _content(response: Any) -> str:
+    return str(getattr(response, "content", response) or "")
+
+
+def _stance(text: str) -> str:
+    matches = _STANCE_RE.findall(text or "")
+    return matches[-1].lower() if matches else "neutral"
+
+
+class DebateOrchestrator:
+    """
+    Bounded multi-agent debate over a single ticker.
+
+    Each role gets its own agent instance so the concurrent calls never
+    share conversation state.
+    """
+
+    def __init__(self, api_keys: Dict[str, str], model_config: Optional[Dict] = None,
+                 tools: Optional[List] = None, rounds: int = DEFAULT_ROUNDS):
+        self.api_keys = api_keys
+        self.model_config = model_config or {}
+        self._tools = tools
+        self.rounds = max(0, min(int(rounds), MAX_ROUNDS))
+
+    def _run_roles(self, agents: Dict[str, Any], prompts: Dict[str, str]) -> Dict[str, str]:
+        """Run one prompt per role concurrently; a failed role yields an error note."""
+        with ThreadPoolExecutor(max_workers=len(prompts)) as pool:
+            futures = {role: pool.submit(agents[role].run, prompt) for role, prompt in prompts.items()}
+            out = {}
+            for role, fut in futures.items():
+                try:
+                    out[role] = _content(fut.result())
+                except Exception as e:
+                    logger.warning(f"Debate: {role} agent failed: {e}")
+                    out[role] = f"[{role} agent unavailable: {e}]\nSTANCE: neutral"
+            return out
+
+    def run(self, symbol: str, context: str = "") -> Dict[str, Any]:
+        today = date.today().isoformat()
+        agents = {
+            role: _make_workflow_agent(
+                self.api_keys, f"{instructions} Today is {today}.", self.model_config, tools=self._tools)
+            for role, instructions in ROLES.items()
+        }
+        brief = f"Ticker: {symbol}." + (f" Context from the user: {context}" if context else "")
+
+        transcript: List[Dict[str, Any]] = []
+        latest = self._run_roles(agents, {
+            role: f"{brief}\nGive your opening statement on {symbol}.{_STANCE_FOOTER}" for role in ROLES
+        })
+        stances = {role: _stance(text) for role, text in latest.items()}
+        transcript.append({"round": 0, "arguments": dict(latest), "stances": dict(stances)})
+
+        rounds_run = 0
+        for n in range(1, self.rounds + 1):
+            prompts = {}
+            for role in ROLES:
+                others = "\n\n".join(f"[{other.upper()}]\n{text}" for other, text in latest.items() if other != role)
+                prompts[role] = (
+                    f"{brief}\nRound {n} rebuttal. The other committee members argued:\n\n{others}\n\n"
+                    f"Rebut their strongest points, update your view if the evidence warrants it, and "
+                    f"add any new evidence.{_STANCE_FOOTER}"
+                )
+            latest = self._run_roles(agents, prompts)
+            new_stances = {role: _stance(text) for role, text in latest.items()}
+            transcript.append({"round": n, "arguments": dict(latest), "stances": dict(new_stances)})
+            rounds_run = n
+            converged = new_stances == stances
+            stances = new_stances
+            if converged:
+                break
+
+        verdict = self._judge(symbol, today, transcript, stances)
+        summary = (f"{symbol} {verdict['direction'].upper()} (conviction {verdict['conviction']:.2f}) "
+                   f"after {rounds_run} rebuttal round(s). {verdict.get('summary', '')}").strip()
+        return {
+            "success": True,
+            "symbol": symbol,
+            "as_of": today,
+            "rounds": rounds_run,
+            "verdict": verdict,
+            "transcript": transcript,
+            "response": summary,
+        }
+
+    def _judge(self, symbol: str, today: str, transcript: List[Dict[str, Any]],
+               stances: Dict[str, str]) -> Dict[str, Any]:
+        judge = _make_workflow_agent(
+            self.api_keys,
+            f"You are the chair of an investment committee. Today is {today}. You weigh arguments on "
+            f"evidence quality, not on who argued last, and you answer with JSON only.",
+            self.model_config,
+        )
+        record = "\n\n".join(
+            f"=== Round {r['round']} ===\n" + "\n\n".join(f"[{role.upper()}]\n{text}"
+                                                       for role, text in r["arguments"].items())
+            for r in transcript
+        )
+        prompt = (
+            f"Debate transcript for {symbol}:\n\n{record}\n\n"
+            "Return a single JSON object with exactly these keys:\n"
+            '  "direction": "bullish" | "bearish" | "neutral",\n'
+            '  "conviction": number from 0 to 1,\n'
+            '  "key_evidence": up to 5 short strings, the facts that decided the verdict,\n'
+            '  "dissent": list of {"role": "bull"|"bear"|"risk", "stance": ..., "argument": short string} '
+            "for members whose final stance differs from the verdict,\n"
+            '  "summary": one or two sentences.\n'
+            "No markdown, no prose outside the JSON."
+        )
+        try:
+            raw = _content(judge.run(prompt))
+            match = _JSON_RE.search(raw)
+            parsed = json.loads(match.group(0)) if match else {}
+        except Exception as e:
+            logger.warning(f"Debate: judge failed for {symbol}: {e}")
+            parsed = {}
+        return self._normalize(parsed, stances)
+
+    @staticmethod
+    def _normalize(parsed: Dict[str, Any], stances: Dict[str, str]) -> Dict[str, Any]:
+        """Coerce the judge's JSON into the verdict schema; fall back to a stance tally."""
+        direction = str(parsed.get("direction", "")).lower()
+        if direction not in DIRECTIONS:
+            tally = {d: list(stances.values()).count(d) for d in DIRECTIONS}
+            direction = max(DIRECTIONS, key=lambda d: tally[d])
+            parsed.setdefault("conviction", tally[direction] / max(len(stances), 1) * 0.5)
+        try:
+            conviction = max(0.0, min(1.0, float(parsed.get("conviction", 0.5))))
+        except (TypeError, ValueError):
+            conviction = 0.5
+
+        evidence = [str(e) for e in parsed.get("key_evidence", []) if e][:5]
+
+        dissent = []
+        raw_dissent = parsed.get("dissent")
+        if isinstance(raw_dissent, list):
+            for d in raw_dissent:
+                if isinstance(d, dict) and d.get("role"):
+                    dissent.append({"role": str(d["role"]).lower(),
+                                    "stance": str(d.get("stance", stances.get(str(d["role"]).lower(), ""))),
+                                    "argument": str(d.get("argument", ""))})
+        if not dissent:
+            dissent = [{"role": role, "stance": stance, "argument": ""}
+                       for role, stance in stances.items() if stance != direction]
+
+        return {
+            "direction": direction,
+            "conviction": round(conviction, 3),
+            "key_evidence": evidence,
+            "dissent": dissent,
+            "summary": str(parsed.get("summary", "")),
+            "final_stances": dict(stances),
+        }
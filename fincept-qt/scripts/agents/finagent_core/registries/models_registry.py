@@ -88,9 +88,10 @@ class ModelsRegistry:
         },
         "llama_cpp": {
             "class": "agno.models.llama_cpp.LlamaCpp",
-            "models": [],
+            "models": [],  # llama-server serves the one GGUF it was started with
             "api_key_env": None,
             "default_model": None,
+            "base_url": "http://localhost:8080/v1",
         },
         "vllm": {
             "class": "agno.models.vllm.vLLM",
@@ -438,6 +439,14 @@ class ModelsRegistry:
                 )
                 return _OAI(**{k: v for k, v in oai_kwargs.items() if v is not None})
 
+            # Local OpenAI-compatible servers (llama.cpp's llama-server, LM Studio,
+            # vLLM) are configured in Settings as host:port; the OpenAI SDK does
+            # not add /v1 itself, so requests would 404 without it.
+            OPENAI_V1_LOCAL_PROVIDERS = {"llama_cpp", "lmstudio", "vllm"}
+            if effective_base_url and provider_lower in OPENAI_V1_LOCAL_PROVIDERS:
+                if not effective_base_url.rstrip('/').endswith('/v1'):
+                    effective_base_url = effective_base_url.rstrip('/') + '/v1'
+
             if provider_lower not in NO_BASE_URL_PROVIDERS:
                 if effective_base_url:
                     if provider_lower in HOST_NOT_BASE_URL_PROVIDERS:
@@ -204,6 +204,15 @@ class TerminalToolkit:
 
             return json.dumps(data_val) if not isinstance(data_val, str) else data_val
 
+        except urllib.error.HTTPError as e:
+            # 401/403 carry a JSON {error} body, e.g. a tool outside this
+            # run's allowlist — pass the reason on so the model can adapt.
+            try:
+                error_msg = json.loads(e.read().decode("utf-8")).get("error") or str(e)
+            except Exception:
+                error_msg = str(e)
+            logger.warning(f"Terminal tool '{tool_name}' rejected ({e.code}): {error_msg}")
+            return json.dumps({"error": error_msg})
         except urllib.error.URLError as e:
             logger.error(f"Terminal tool '{tool_name}' HTTP error: {e}")
             return json.dumps({"error": f"Bridge unreachable: {e}"})
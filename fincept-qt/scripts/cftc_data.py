@@ -649,6 +649,140 @@ class CFTCDataWrapper:
         except Exception as e:
             return {"error": CFTCError("cot_historical_trend", str(e)).to_dict()}
 
+    # Trader groups per report type: group -> (long field candidates, short field candidates).
+    # Socrata field names differ between datasets and sometimes drop the "_all" suffix.
+    POSITIONING_GROUPS = {
+        "legacy": {
+            "non_commercial": (["noncomm_positions_long_all"], ["noncomm_positions_short_all"]),
+            "commercial": (["comm_positions_long_all"], ["comm_positions_short_all"]),
+            "non_reportable": (["nonrept_positions_long_all"], ["nonrept_positions_short_all"]),
+        },
+        "disaggregated": {
+            "managed_money": (["m_money_positions_long_all", "m_money_positions_long"],
+                              ["m_money_positions_short_all", "m_money_positions_short"]),
+            "producer_merchant": (["prod_merc_positions_long", "prod_merc_positions_long_all"],
+                                  ["prod_merc_positions_short", "prod_merc_positions_short_all"]),
+            "swap_dealer": (["swap_positions_long_all", "swap_positions_long"],
+                            ["swap_positions_short_all", "swap_positions_short"]),
+        },
+        "financial": {
+            "leveraged_funds": (["lev_money_positions_long", "lev_money_positions_long_all"],
+                                ["lev_money_positions_short", "lev_money_positions_short_all"]),
+            "asset_manager": (["asset_mgr_positions_long", "asset_mgr_positions_long_all"],
+                              ["asset_mgr_positions_short", "asset_mgr_positions_short_all"]),
+            "dealer": (["dealer_positions_long_all", "dealer_positions_long"],
+                       ["dealer_positions_short_all", "dealer_positions_short"]),
+        },
+    }
+
+    def get_cot_positioning(self, identifier: str, report_type: str = "legacy", years: int = 3,
+                            zscore_window: int = 52) -> Dict[str, Any]:
+        """COT index and week-over-week z-scores of net positioning per trader group.
+
+        COT index = (net - min) / (max - min) * 100 over the trailing `years`;
+        z-score = this week's change in net / std of weekly changes over the
+        trailing `zscore_window` weeks. When the identifier matches several
+        contracts the one with the largest open interest is used.
+        """
+        try:
+            report_key = "financial" if report_type.lower() in ("financial", "tff") else report_type.lower()
+            groups = self.POSITIONING_GROUPS.get(report_key)
+            if not groups:
+                return {"error": CFTCError("cot_positioning", f"Unsupported report type: {report_type}").to_dict()}
+
+            lookback = int(years * 52)
+            start = (datetime.now() - timedelta(weeks=lookback + zscore_window + 2)).strftime("%Y-%m-%d")
+            cot_result = self.get_cot_data(identifier=identifier, report_type=report_key, start_date=start,
+                                           limit=50000)
+            if not cot_result.get("success"):
+                return cot_result
+            rows = cot_result["data"]
+            if not rows:
+                return {"error": CFTCError("cot_positioning", f"No COT data: {identifier}").to_dict()}
+
+            # Pick the contract with the largest latest open interest
+            by_code = {}
+            for r in rows:
+                by_code.setdefault(r.get("cftc_contract_market_code", ""), []).append(r)
+            code, contract_rows = max(
+                by_code.items(),
+                key=lambda kv: self._safe_int(max(kv[1], key=lambda r: r.get("report_date_as_yyyy_mm_dd", ""))
+                                              .get("open_interest_all")))
+
+            def pick(record, fields):
+                for f in fields:
+                    if f in record:
+                        return self._safe_int(record[f])
+                return None
+
+            frame = {}
+            for r in contract_rows:
+                date = r.get("report_date_as_yyyy_mm_dd")
+                point = {"open_interest": self._safe_int(r.get("open_interest_all"))}
+                for group, (long_fields, short_fields) in groups.items():
+                    lng, sht = pick(r, long_fields), pick(r, short_fields)
+                    point[group] = None if lng is None or sht is None else lng - sht
+                frame[date] = point
+            df = pd.DataFrame.from_dict(frame, orient="index").sort_index()
+            df.index = pd.to_datetime(df.index)
+
+            result_groups = {}
+            history = {"dates": [d.strftime("%Y-%m-%d") for d in df.index[-lookback:]],
+                       "open_interest": [int(v) for v in df["open_interest"].iloc[-lookback:]]}
+            for group in groups:
+                net = df[group].astype(float)
+                if net.dropna().empty:
+                    continue
+                lo = net.rolling(lookback, min_periods=min(lookback, 26)).min()
+                hi = net.rolling(lookback, min_periods=min(lookback, 26)).max()
+                cot_index = 100 * (net - lo) / (hi - lo).where(hi > lo)
+                change = net.diff()
+                std = change.rolling(zscore_window, min_periods=min(zscore_window, 13)).std()
+                zscore = change / std.where(std > 0)
+                pct_oi = 100 * net / df["open_interest"].where(df["open_interest"] > 0)
+
+                def last(series, digits=2):
+                    v = series.iloc[-1]
+                    return None if pd.isna(v) else round(float(v), digits)
+
+                result_groups[group] = {
+                    "net": None if pd.isna(net.iloc[-1]) else int(net.iloc[-1]),
+                    "net_change": None if pd.isna(change.iloc[-1]) else int(change.iloc[-1]),
+                    "net_pct_oi": last(pct_oi),
+                    "cot_index": last(cot_index, 1),
+                    "zscore": last(zscore),
+                    "range_min": None if pd.isna(lo.iloc[-1]) else int(lo.iloc[-1]),
+                    "range_max": None if pd.isna(hi.iloc[-1]) else int(hi.iloc[-1]),
+                }
+                history[group] = {
+                    "net": [None if pd.isna(v) else int(v) for v in net.iloc[-lookback:]],
+                    "cot_index": [None if pd.isna(v) else round(float(v), 1) for v in cot_index.iloc[-lookback:]],
+                    "zscore": [None if pd.isna(v) else round(float(v), 2) for v in zscore.iloc[-lookback:]],
+                }
+
+            latest_row = max(contract_rows, key=lambda r: r.get("report_date_as_yyyy_mm_dd", ""))
+            return {
+                "success": True,
+                "data": {
+                    "market": latest_row.get("market_and_exchange_names", ""),
+                    "contract_code": code,
+                    "report_type": report_key,
+                    "report_date": df.index[-1].strftime("%Y-%m-%d"),
+                    "open_interest": int(df["open_interest"].iloc[-1]),
+                    "groups": result_groups,
+                    "history": history,
+                },
+                "parameters": {
+                    "identifier": identifier,
+                    "report_type": report_type,
+                    "years": years,
+                    "zscore_window": zscore_window,
+                }
+            }
+
+        except Exception as e:
+            return {"error": CFTCError("cot_positioning", str(e)).to_dict()}
+
 
 def main(args=None):
     
@@ -665,7 +799,8 @@ def main(args=None):
                 "market_sentiment [identifier] [report_type]",
                 "position_summary [identifier] [report_type]",
                 "comprehensive_cot_overview [identifiers] [report_type]",
-                "cot_historical_trend [identifier] [report_type] [period]"
+                "cot_historical_trend [identifier] [report_type] [period]",
+                "cot_positioning [identifier] [report_type] [years] [zscore_window]"
             ],
             "note": "CFTC app token optional but recommended for rate limiting. Set CFTC_APP_TOKEN environment variable.",
             "identifier_examples": ["all", "gold", "crude_oil", "euro", "bitcoin", "002602", "088691"]
@@ -714,6 +849,13 @@ def main(args=None):
             period = int(args[3]) if len(args) + 1 > 4 else 52
             result = wrapper.get_cot_historical_trend(identifier, report_type, period)
 
+        elif command == "cot_positioning":
+            identifier = args[1] if len(args) + 1 > 2 else "gold"
+            report_type = args[2] if len(args) + 1 > 3 else "legacy"
+            years = float(args[3]) if len(args) + 1 > 4 else 3
+            zscore_window = int(args[4]) if len(args) + 1 > 5 else 52
+            result = wrapper.get_cot_positioning(identifier, report_type, years, zscore_window)
+
         else:
             result = {"error": CFTCError(command, f"Unknown command: {command}").to_dict()}
 
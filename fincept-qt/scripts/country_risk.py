@@ -0,0 +1,228 @@
+"""
+Country Risk Aggregator
+Fans out to the IMF DataMapper, World Bank, BIS and OECD fetchers concurrently
+and folds the latest readings into one composite sovereign-risk snapshot.
+
+Pillars (each scored 0 = benign .. 100 = stressed, linear between thresholds)
+  fiscal     - general government gross debt % GDP, fiscal balance % GDP (IMF WEO)
+  external   - current account % GDP (IMF WEO), reserves in months of imports and
+               external debt % GNI (World Bank)
+  financial  - credit-to-GDP gap (BIS, Basel III buffer thresholds 2..10 pp)
+  macro      - real GDP growth, inflation distance from 2% (IMF WEO),
+               unemployment rate (OECD, members only)
+Composite = weighted mean of the pillars that have data (weights renormalised).
+A source that fails only drops its indicators; the failure is listed in "errors".
+
+Usage:
+    python country_risk.py <country>      ISO2 or ISO3 code, e.g. US, USA, BR, DEU
+
+Returns JSON output for Qt/C++ integration
+"""
+import sys
+import json
+import asyncio
+from datetime import datetime
+from concurrent.futures import ThreadPoolExecutor
+
+import imf_datamapper_data
+import worldbank_data
+
+# IMF WEO indicators (annual, include projections)
+IMF_INDICATORS = {
+    "gross_debt_pct_gdp": "GGXWDG_NGDP",
+    "fiscal_balance_pct_gdp": "GGXCNL_NGDP",
+    "current_account_pct_gdp": "BCA_NGDPD",
+    "real_gdp_growth": "NGDP_RPCH",
+    "inflation": "PCPIPCH",
+}
+
+# World Bank WDI indicators (annual)
+WB_INDICATORS = {
+    "reserves_months_imports": "FI.RES.TOTL.MO",
+    "external_debt_pct_gni": "DT.DOD.DECT.GN.ZS",
+}
+
+# BIS credit-to-GDP gap: FREQ.BORROWERS_CTY.TC_BORROWERS(P=private non-fin).TC_LENDERS(A=all).CG_DTYPE(C=gap)
+BIS_CREDIT_GAP_KEY = "Q.{iso2}.P.A.C"
+
+# (indicator, pillar, value at score 0, value at score 100)
+SCORING = [
+    ("gross_debt_pct_gdp", "fiscal", 30.0, 120.0),
+    ("fiscal_balance_pct_gdp", "fiscal", 0.0, -8.0),
+    ("current_account_pct_gdp", "external", 2.0, -6.0),
+    ("reserves_months_imports", "external", 12.0, 2.0),
+    ("external_debt_pct_gni", "external", 20.0, 100.0),
+    ("credit_gap_pp", "financial", 2.0, 10.0),
+    ("real_gdp_growth", "macro", 3.0, -2.0),
+    ("inflation_gap_pp", "macro", 0.0, 10.0),
+    ("unemployment_rate", "macro", 4.0, 15.0),
+]
+
+PILLAR_WEIGHTS = {"fiscal": 0.30, "external": 0.30, "financial": 0.20, "macro": 0.20}
+
+BANDS = [(25.0, "low"), (50.0, "moderate"), (75.0, "elevated"), (101.0, "high")]
+
+
+def resolve_country(code: str) -> dict:
+    """ISO2/ISO3 -> {iso2, iso3, name} via the World Bank country endpoint."""
+    result = worldbank_data._make_request(f"country/{code}")
+    rows = result.get("data") or []
+    if result.get("error") or not rows or not isinstance(rows[0], dict):
+        raise ValueError(f"Unknown country code: {code}")
+    row = rows[0]
+    return {"iso2": row.get("iso2Code"), "iso3": row.get("id"), "name": row.get("name")}
+
+
+def fetch_imf(iso3: str) -> dict:
+    """Latest actual (last year or earlier) and current-year projection per WEO indicator."""
+    this_year = datetime.now().year
+    out = {}
+    for key, code in IMF_INDICATORS.items():
+        raw = imf_datamapper_data.get_weo_data(code, iso3, str(this_year - 10), str(this_year))
+        if not isinstance(raw, dict) or "error" in raw:
+            raise RuntimeError(f"{code}: {raw.get('error') if isinstance(raw, dict) else 'invalid response'}")
+        series = raw.get("values", {}).get(code, {}).get(iso3, {})
+        actual = [(int(y), v) for y, v in series.items() if v is not None and int(y) < this_year]
+        if not actual:
+            continue
+        year, value = max(actual)
+        out[key] = {"value": round(float(value), 3), "period": str(year), "source": "imf"}
+        if str(this_year) in series and series[str(this_year)] is not None:
+            out[key]["projection"] = {"value": round(float(series[str(this_year)]), 3), "period": str(this_year)}
+    if "inflation" in out:
+        out["inflation_gap_pp"] = dict(out["inflation"], value=round(abs(out["inflation"]["value"] - 2.0), 3))
+        out["inflation_gap_pp"].pop("projection", None)
+    return out
+
+
+def fetch_worldbank(iso3: str) -> dict:
+    this_year = datetime.now().year
+    out = {}
+    for key, code in WB_INDICATORS.items():
+        result = worldbank_data.get_indicators(iso3, code, f"{this_year - 10}:{this_year}")
+        if result.get("error"):
+            raise RuntimeError(f"{code}: {result['error']}")
+        rows = [r for r in result.get("data", []) if r.get("value") is not None]
+        if not rows:
+            continue
+        latest = max(rows, key=lambda r: r["date"])
+        out[key] = {"value": round(float(latest["value"]), 3), "period": latest["date"], "source": "worldbank"}
+    return out
+
+
+def fetch_bis(iso2: str) -> dict:
+    from bis_data import BISAPI
+
+    async def run():
+        async with BISAPI() as bis:
+            return await bis.get_data("WS_CREDIT_GAP", BIS_CREDIT_GAP_KEY.format(iso2=iso2),
+                                      last_n_observations=1, detail="dataonly")
+
+    raw = asyncio.run(run())
+    if not raw.get("success"):
+        raise RuntimeError(raw.get("error", "request failed"))
+    resp = raw.get("data", {})
+    sdmx = resp.get("data", resp)
+    obs_dims = sdmx.get("structure", resp.get("structure", {})).get("dimensions", {}).get("observation", [])
+    periods = [v.get("id") for v in obs_dims[0].get("values", [])] if obs_dims else []
+    points = []
+    for ds in sdmx.get("dataSets", []):
+        for series in ds.get("series", {}).values():
+            for idx, obs in series.get("observations", {}).items():
+                if isinstance(obs, list) and obs and obs[0] is not None:
+                    period = periods[int(idx)] if int(idx) < len(periods) else idx
+                    points.append((str(period), float(obs[0])))
+    if not points:
+        return {}
+    period, value = max(points)
+    return {"credit_gap_pp": {"value": round(value, 3), "period": period, "source": "bis"}}
+
+
+def fetch_oecd(iso3: str) -> dict:
+    from oecd_data import OECDWrapper
+
+    start = f"{datetime.now().year - 2}-01-01"
+    result = OECDWrapper().get_unemployment(iso3, "quarter", start)
+    if not result.get("success"):
+        raise RuntimeError(result.get("error", "request failed"))
+    rows = [r for r in result.get("data", []) if r.get("value") is not None]
+    if not rows:
+        return {}
+    latest = max(rows, key=lambda r: r["date"])
+    return {"unemployment_rate": {"value": round(float(latest["value"]), 3), "period": latest["date"],
+                                  "source": "oecd"}}
+
+
+def score(value: float, benign: float, stressed: float) -> float:
+    t = (value - benign) / (stressed - benign)
+    return round(100.0 * min(1.0, max(0.0, t)), 1)
+
+
+def build_scores(indicators: dict) -> tuple:
+    pillars = {}
+    for key, pillar, benign, stressed in SCORING:
+        if key not in indicators:
+            continue
+        entry = pillars.setdefault(pillar, {"inputs": {}})
+        entry["inputs"][key] = score(indicators[key]["value"], benign, stressed)
+    for entry in pillars.values():
+        entry["score"] = round(sum(entry["inputs"].values()) / len(entry["inputs"]), 1)
+
+    weight = sum(PILLAR_WEIGHTS[p] for p in pillars)
+    if weight == 0:
+        return pillars, None
+    composite = round(sum(PILLAR_WEIGHTS[p] * e["score"] for p, e in pillars.items()) / weight, 1)
+    band = next(label for limit, label in BANDS if composite < limit)
+    return pillars, {"score": composite, "band": band, "coverage": round(weight, 2)}
+
+
+def country_risk(code: str) -> dict:
+    country = resolve_country(code)
+    fetchers = {
+        "imf": lambda: fetch_imf(country["iso3"]),
+        "worldbank": lambda: fetch_worldbank(country["iso3"]),
+        "bis": lambda: fetch_bis(country["iso2"]),
+        "oecd": lambda: fetch_oecd(country["iso3"]),
+    }
+    indicators, errors, sources = {}, {}, {}
+    with ThreadPoolExecutor(max_workers=len(fetchers)) as pool:
+        futures = {name: pool.submit(fn) for name, fn in fetchers.items()}
+        for name, future in futures.items():
+            try:
+                data = future.result()
+                indicators.update(data)
+                sources[name] = len(data)
+            except Exception as e:
+                errors[name] = str(e)
+                sources[name] = 0
+
+    pillars, composite = build_scores(indicators)
+    return {
+        "country": country,
+        "composite": composite,
+        "pillars": pillars,
+        "indicators": indicators,
+        "sources": sources,
+        "errors": errors,
+        "computed_at": datetime.utcnow().strftime("%Y-%m-%dT%H:%M:%SZ"),
+    }
+
+
+def main(args=None):
+    if args is None:
+        args = sys.argv[1:]
+    if not args:
+        print(json.dumps({"success": False, "error": "Usage: python country_risk.py <country ISO2|ISO3>"}))
+        return
+    try:
+        data = country_risk(args[0].strip().upper())
+        if data["composite"] is None:
+            print(json.dumps({"success": False, "error": "No indicators available", "errors": data["errors"]}))
+            return
+        print(json.dumps({"success": True, "data": data}))
+    except Exception as e:
+        print(json.dumps({"success": False, "error": str(e)}))
+
+
+if __name__ == "__main__":
+    main()
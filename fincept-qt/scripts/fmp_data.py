@@ -733,6 +733,90 @@ class FMPDataWrapper:
         except Exception as e:
             return {"error": FMPError("financial_statements", str(e)).to_dict()}
 
+    # Point-in-time field map: statement → (metric, FMP field). Metric keys match
+    # sec_xbrl_data.py PIT_CONCEPTS so both sources land in one store.
+    PIT_FIELDS = {
+        "income-statement": [
+            ("revenue", "revenue"), ("gross_profit", "grossProfit"), ("operating_income", "operatingIncome"),
+            ("net_income", "netIncome"), ("eps_diluted", "epsdiluted"), ("shares_diluted", "weightedAverageShsOutDil"),
+        ],
+        "balance-sheet-statement": [
+            ("total_assets", "totalAssets"), ("total_liabilities", "totalLiabilities"),
+            ("total_equity", "totalStockholdersEquity"), ("current_assets", "totalCurrentAssets"),
+            ("current_liabilities", "totalCurrentLiabilities"), ("cash", "cashAndCashEquivalents"),
+            ("total_debt", "longTermDebt"),
+        ],
+        "cash-flow-statement": [
+            ("operating_cash_flow", "operatingCashFlow"), ("capex", "capitalExpenditure"),
+            ("dividends_paid", "dividendsPaid"),
+        ],
+    }
+
+    def get_pit_fundamentals(self, symbol: str, period: str = "all", limit: int = 40) -> Dict[str, Any]:
+        """Statement line items keyed by report date (period end) and filing date"""
+        try:
+            if not symbol:
+                return {"error": FMPError("pit_fundamentals", "Symbol parameter is required").to_dict()}
+
+            periods = [period] if period in self.statement_periods else self.statement_periods
+
+            data = []
+            for statement, fields in self.PIT_FIELDS.items():
+                rows = []
+                for p in periods:
+                    fetched = self._make_request(self._build_url(f"{statement}/{symbol}", {"period": p, "limit": limit}))
+                    if isinstance(fetched, list):
+                        rows.extend(fetched)
+                instant = statement == "balance-sheet-statement"
+                for row in rows:
+                    period_end = row.get("date")
+                    # FMP spells it "fillingDate" on v3; accept the corrected key too.
+                    filed = row.get("fillingDate") or row.get("filingDate")
+                    if not period_end or not filed:
+                        continue
+                    fiscal_period = row.get("period") or ""
+                    kind = "I" if instant else ("FY" if fiscal_period == "FY" else "Q")
+                    for metric, field in fields:
+                        value = row.get(field)
+                        if value is None:
+                            continue
+                        value = float(value)
+                        if metric in ("capex", "dividends_paid"):
+                            value = abs(value)
+                        data.append({
+                            "metric": metric,
+                            "period": kind,
+                            "period_start": "",
+                            "period_end": period_end,
+                            "filed": filed[:10],
+                            "value": value,
+                            "unit": row.get("reportedCurrency") or "",
+                            "fiscal_year": row.get("calendarYear"),
+                            "fiscal_period": fiscal_period,
+                            "form": "10-K" if fiscal_period == "FY" else "10-Q",
+                            "accession": "",
+                            "concept": field,
+                        })
+
+            if not data:
+                return {"error": FMPError("pit_fundamentals", f"No statement data found for symbol: {symbol}").to_dict()}
+
+            return {
+                "success": True,
+                "source": "fmp",
+                "ticker": symbol,
+                "count": len(data),
+                "data": data,
+                "parameters": {
+                    "symbol": symbol,
+                    "period": period,
+                    "limit": limit
+                }
+            }
+
+        except Exception as e:
+            return {"error": FMPError("pit_fundamentals", str(e)).to_dict()}
+
 
 def main(args=None):
     """Main function for CLI interface
@@ -769,7 +853,8 @@ def main(args=None):
                 "insider_trading [symbol] [limit]",
                 "institutional_ownership [symbol] [limit]",
                 "company_overview [symbol]",
-                "financial_statements [symbol] [period] [limit]"
+                "financial_statements [symbol] [period] [limit]",
+                "pit_fundamentals [symbol] [period] [limit]"
             ],
             "note": "FMP API key required. Set FMP_API_KEY environment variable or pass to wrapper constructor."
         }))
@@ -878,6 +963,12 @@ def main(args=None):
             limit = int(args[3]) if len(args) > 3 else 5
             result = wrapper.get_financial_statements(symbol, period, limit)
 
+        elif command == "pit_fundamentals":
+            symbol = args[1] if len(args) > 1 else None
+            period = args[2] if len(args) > 2 else "all"
+            limit = int(args[3]) if len(args) > 3 else 40
+            result = wrapper.get_pit_fundamentals(symbol, period, limit)
+
         else:
             result = {"error": FMPError(command, f"Unknown command: {command}").to_dict()}
 
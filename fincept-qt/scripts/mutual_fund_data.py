@@ -0,0 +1,225 @@
+"""
+Mutual Fund Data Fetcher
+India: AMFI daily NAV file (all schemes, with SEBI category) and per-scheme
+NAV history via api.mfapi.in (no API key required).
+US: mutual fund NAV history and profile (category, expense ratio, family)
+via yfinance.
+Returns JSON output for Qt/C++ integration.
+"""
+import sys
+import json
+import requests
+from datetime import datetime
+from typing import Any, Dict, List, Optional
+
+AMFI_NAV_URL = "https://www.amfiindia.com/spages/NAVAll.txt"
+MFAPI_URL = "https://api.mfapi.in/mf"
+
+session = requests.Session()
+adapter = requests.adapters.HTTPAdapter(pool_connections=10, pool_maxsize=10, max_retries=3)
+session.mount('https://', adapter)
+session.mount('http://', adapter)
+
+
+def _get(url: str, params: Dict = None, as_json: bool = True) -> Any:
+    try:
+        response = session.get(url, params=params, timeout=30)
+        response.raise_for_status()
+        return response.json() if as_json else response.text
+    except requests.exceptions.HTTPError as e:
+        return {"error": f"HTTP {e.response.status_code}: {str(e)}"}
+    except requests.exceptions.RequestException as e:
+        return {"error": f"Request failed: {str(e)}"}
+    except (json.JSONDecodeError, ValueError) as e:
+        return {"error": f"JSON decode error: {str(e)}"}
+
+
+def _to_iso(date_str: str, fmt: str) -> Optional[str]:
+    try:
+        return datetime.strptime(date_str.strip(), fmt).strftime("%Y-%m-%d")
+    except ValueError:
+        return None
+
+
+# ── India (AMFI) ──────────────────────────────────────────────────────────────
+
+def _parse_amfi_nav_file(text: str) -> List[Dict]:
+    """Parse NAVAll.txt.
+
+    The file interleaves three kinds of line:
+      - category headers, e.g. "Open Ended Schemes(Equity Scheme - Large Cap Fund)"
+      - AMC names (a bare line with no ';')
+      - scheme rows: code;ISIN growth/payout;ISIN reinvest;name;NAV;date
+    """
+    schemes = []
+    category = ""
+    structure = ""
+    amc = ""
+    for raw in text.splitlines():
+        line = raw.strip()
+        if not line or line.startswith("Scheme Code"):
+            continue
+        if ";" not in line:
+            if "Schemes(" in line and line.endswith(")"):
+                structure, _, rest = line.partition("(")
+                structure = structure.strip()
+                category = rest[:-1].strip()
+            else:
+                amc = line
+            continue
+        parts = [p.strip() for p in line.split(";")]
+        if len(parts) < 6 or not parts[0].isdigit():
+            continue
+        try:
+            nav = float(parts[4])
+        except ValueError:
+            continue
+        asset_class, _, sub_category = category.partition(" - ")
+        schemes.append({
+            "scheme_code": parts[0],
+            "isin_growth": parts[1] if parts[1] not in ("-", "") else None,
+            "isin_reinvest": parts[2] if parts[2] not in ("-", "") else None,
+            "scheme_name": parts[3],
+            "nav": nav,
+            "date": _to_iso(parts[5], "%d-%b-%Y"),
+            "amc": amc,
+            "structure": structure,
+            "category": category,
+            "asset_class": asset_class.replace(" Scheme", "").strip(),
+            "sub_category": sub_category.strip(),
+        })
+    return schemes
+
+
+def amfi_nav_all(category_filter: str = "") -> Any:
+    text = _get(AMFI_NAV_URL, as_json=False)
+    if isinstance(text, dict):
+        return text
+    schemes = _parse_amfi_nav_file(text)
+    if category_filter:
+        needle = category_filter.lower()
+        schemes = [s for s in schemes if needle in s["category"].lower()]
+    categories = sorted({s["category"] for s in schemes if s["category"]})
+    return {"source": "AMFI", "count": len(schemes), "categories": categories, "data": schemes}
+
+
+def amfi_nav_history(scheme_code: str) -> Any:
+    data = _get(f"{MFAPI_URL}/{scheme_code}")
+    if isinstance(data, dict) and data.get("error"):
+        return data
+    meta = data.get("meta", {}) if isinstance(data, dict) else {}
+    points = []
+    for row in (data.get("data", []) if isinstance(data, dict) else []):
+        iso = _to_iso(row.get("date", ""), "%d-%m-%Y")
+        try:
+            nav = float(row.get("nav", ""))
+        except ValueError:
+            continue
+        if iso and nav > 0:
+            points.append({"date": iso, "nav": nav})
+    points.sort(key=lambda p: p["date"])
+    if not points:
+        return {"error": f"No NAV history for scheme {scheme_code}"}
+    return {
+        "source": "AMFI",
+        "region": "IN",
+        "id": str(scheme_code),
+        "name": meta.get("scheme_name", ""),
+        "fund_house": meta.get("fund_house", ""),
+        "category": meta.get("scheme_category", ""),
+        "currency": "INR",
+        "data": points,
+    }
+
+
+def amfi_search(query: str) -> Any:
+    data = _get(f"{MFAPI_URL}/search", params={"q": query})
+    if isinstance(data, dict) and data.get("error"):
+        return data
+    rows = [{"id": str(r.get("schemeCode")), "name": r.get("schemeName", ""), "region": "IN"}
+            for r in (data or [])]
+    return {"count": len(rows), "data": rows}
+
+
+# ── US (yfinance) ─────────────────────────────────────────────────────────────
+
+def us_nav_history(ticker: str, period: str = "max") -> Any:
+    try:
+        import io
+        import contextlib
+        import yfinance as yf
+        t = yf.Ticker(ticker)
+        buf = io.StringIO()
+        with contextlib.redirect_stdout(buf):
+            hist = t.history(period=period, auto_adjust=False)
+            info = t.info or {}
+        if hist.empty:
+            return {"error": f"No NAV history for {ticker}"}
+        points = [{"date": idx.strftime("%Y-%m-%d"), "nav": round(float(row["Close"]), 4)}
+                  for idx, row in hist.iterrows() if float(row["Close"]) > 0]
+        return {
+            "source": "yfinance",
+            "region": "US",
+            "id": ticker.upper(),
+            "name": info.get("longName") or info.get("shortName", ""),
+            "fund_house": info.get("fundFamily", ""),
+            "category": info.get("category", ""),
+            "currency": info.get("currency", "USD"),
+            "data": points,
+        }
+    except Exception as e:
+        return {"error": str(e)}
+
+
+def us_fund_profile(ticker: str) -> Any:
+    try:
+        import io
+        import contextlib
+        import yfinance as yf
+        buf = io.StringIO()
+        with contextlib.redirect_stdout(buf):
+            info = yf.Ticker(ticker).info or {}
+        if not info:
+            return {"error": f"No profile for {ticker}"}
+        return {
+            "id": ticker.upper(),
+            "region": "US",
+            "name": info.get("longName") or info.get("shortName", ""),
+            "fund_house": info.get("fundFamily", ""),
+            "category": info.get("category", ""),
+            "expense_ratio": info.get("annualReportExpenseRatio") or info.get("netExpenseRatio"),
+            "total_assets": info.get("totalAssets"),
+            "yield": info.get("yield"),
+            "inception_date": info.get("fundInceptionDate"),
+            "morningstar_rating": info.get("morningStarOverallRating"),
+            "currency": info.get("currency", "USD"),
+        }
+    except Exception as e:
+        return {"error": str(e)}
+
+
+def main(args=None):
+    if args is None:
+        args = sys.argv[1:]
+    if not args:
+        print(json.dumps({"error": "No command provided"}))
+        return
+    command = args[0]
+    result = {"error": f"Unknown command: {command}"}
+    if command == "amfi_nav_all":
+        result = amfi_nav_all(args[1] if len(args) > 1 else "")
+    elif command == "nav_history":
+        if len(args) < 2:
+            result = {"error": "nav_history requires a scheme code or ticker"}
+        else:
+            region = (args[2] if len(args) > 2 else "IN").upper()
+            result = amfi_nav_history(args[1]) if region == "IN" else us_nav_history(args[1])
+    elif command == "search":
+        result = amfi_search(args[1] if len(args) > 1 else "")
+    elif command == "us_profile":
+        result = us_fund_profile(args[1]) if len(args) > 1 else {"error": "us_profile requires a ticker"}
+    print(json.dumps(result))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,240 @@
+"""
+Jupyter kernel bridge for the Fincept Notebook.
+
+Launches an IPython kernel (ipykernel, via jupyter_client's ZeroMQ client) and
+relays it to NotebookKernel (src/python/NotebookKernel.cpp) over stdin/stdout,
+so variables, imports and figures persist between cells the way they do in
+Jupyter.
+
+Framing matches `yfinance_data.py --daemon`: 4-byte big-endian length prefix,
+UTF-8 JSON body, both directions.
+
+Requests (C++ → bridge):
+    {"action": "execute", "id": "<exec id>", "code": "..."}
+    {"action": "interrupt"}
+    {"action": "restart"}
+    {"action": "shutdown"}
+
+Events (bridge → C++):
+    {"event": "ready", "pid", "kernel", "language_version"}
+    {"event": "unavailable", "error"}          jupyter_client / ipykernel missing
+    {"event": "status", "state": "busy"|"idle"}
+    {"event": "output", "id", "output": {type, name?, text?, image_png?,
+                                         ename?, evalue?, traceback?}}
+    {"event": "finished", "id", "execution_count", "ok"}
+    {"event": "restarted"}
+
+Output types follow nbformat: stream, execute_result, display_data, error.
+An execution is finished when the kernel reports idle for its request, which
+Jupyter guarantees comes after all of its outputs.
+"""
+
+import json
+import os
+import queue
+import re
+import sys
+import threading
+
+_ANSI = re.compile(r"\x1b\[[0-9;]*[A-Za-z]")
+
+
+def _read_frame(stream):
+    """Read one length-prefixed frame. Returns bytes payload, or None on EOF."""
+    header = b""
+    while len(header) < 4:
+        chunk = stream.read(4 - len(header))
+        if not chunk:
+            return None
+        header += chunk
+    n = int.from_bytes(header, byteorder="big", signed=False)
+    if n == 0 or n > 64 * 1024 * 1024:
+        return None
+    buf = b""
+    while len(buf) < n:
+        chunk = stream.read(n - len(buf))
+        if not chunk:
+            return None
+        buf += chunk
+    return buf
+
+
+class _Writer:
+    def __init__(self, stream):
+        self._stream = stream
+        self._lock = threading.Lock()
+
+    def send(self, obj):
+        data = json.dumps(obj).encode("utf-8")
+        with self._lock:
+            self._stream.write(len(data).to_bytes(4, byteorder="big", signed=False))
+            self._stream.write(data)
+            self._stream.flush()
+
+
+def _stdin_reader(requests):
+    stdin = sys.stdin.buffer
+    while True:
+        frame = _read_frame(stdin)
+        if frame is None:
+            requests.put({"action": "shutdown"})
+            return
+        try:
+            requests.put(json.loads(frame.decode("utf-8")))
+        except Exception:
+            continue
+
+
+def _to_output(msg_type, content):
+    if msg_type == "stream":
+        return {"type": "stream", "name": content.get("name", "stdout"), "text": content.get("text", "")}
+    if msg_type in ("execute_result", "display_data"):
+        data = content.get("data") or {}
+        out = {"type": msg_type, "text": data.get("text/plain", "")}
+        if "image/png" in data:
+            out["image_png"] = data["image/png"]
+        return out
+    if msg_type == "error":
+        return {
+            "type": "error",
+            "ename": content.get("ename", "Error"),
+            "evalue": content.get("evalue", ""),
+            "traceback": [_ANSI.sub("", line) for line in content.get("traceback") or []],
+        }
+    return None
+
+
+def main():
+    # Private frame channel; the kernel subprocess and native code inherit an
+    # fd 1 that points at stderr, so nothing else can write into the framing.
+    sys.stdout.flush()
+    out = _Writer(os.fdopen(os.dup(1), "wb"))
+    os.dup2(2, 1)
+
+    try:
+        from jupyter_client import KernelManager
+    except Exception as e:
+        out.send({"event": "unavailable", "error": f"jupyter_client not installed: {e}"})
+        return
+
+    cwd = sys.argv[1] if len(sys.argv) > 1 else os.getcwd()
+    try:
+        km = KernelManager(kernel_name="python3")
+        km.start_kernel(cwd=cwd)
+        kc = km.client()
+        kc.start_channels()
+        kc.wait_for_ready(timeout=60)
+    except Exception as e:
+        out.send({"event": "unavailable", "error": f"kernel failed to start: {e}"})
+        return
+
+    info = {}
+    try:
+        kc.kernel_info()
+        reply = kc.get_shell_msg(timeout=10)
+        info = reply.get("content") or {}
+    except Exception:
+        pass
+    out.send({
+        "event": "ready",
+        "pid": os.getpid(),
+        "kernel": info.get("implementation", "ipykernel") + " " + info.get("implementation_version", ""),
+        "language_version": (info.get("language_info") or {}).get("version", ""),
+    })
+
+    requests = queue.Queue()
+    threading.Thread(target=_stdin_reader, args=(requests,), daemon=True).start()
+
+    pending = {}  # kernel msg_id -> {"id", "execution_count", "ok"}
+
+    def abort_pending(reason):
+        for run in pending.values():
+            out.send({"event": "output", "id": run["id"],
+                      "output": {"type": "error", "ename": "KernelRestarted", "evalue": reason, "traceback": []}})
+            out.send({"event": "finished", "id": run["id"], "execution_count": run["execution_count"], "ok": False})
+        pending.clear()
+
+    while True:
+        try:
+            req = requests.get_nowait()
+        except queue.Empty:
+            req = None
+
+        if req is not None:
+            action = req.get("action")
+            if action == "shutdown":
+                break
+            if action == "execute":
+                msg_id = kc.execute(req.get("code", ""), store_history=True, allow_stdin=False)
+                pending[msg_id] = {"id": req.get("id", ""), "execution_count": 0, "ok": True}
+            elif action == "interrupt":
+                try:
+                    km.interrupt_kernel()
+                except Exception as e:
+                    print(f"interrupt failed: {e}", file=sys.stderr)
+            elif action == "restart":
+                abort_pending("Kernel restarted")
+                try:
+                    km.restart_kernel(now=True)
+                    kc.wait_for_ready(timeout=60)
+                except Exception as e:
+                    out.send({"event": "unavailable", "error": f"kernel restart failed: {e}"})
+                    break
+                out.send({"event": "restarted"})
+            continue
+
+        if not km.is_alive():
+            abort_pending("Kernel died")
+            out.send({"event": "unavailable", "error": "kernel process exited"})
+            break
+
+        # Execute replies duplicate what iopub already reported; drop them.
+        while True:
+            try:
+                kc.get_shell_msg(timeout=0)
+            except queue.Empty:
+                break
+            except Exception:
+                break
+
+        try:
+            msg = kc.get_iopub_msg(timeout=0.05)
+        except queue.Empty:
+            continue
+        except Exception:
+            continue
+
+        msg_type = msg.get("header", {}).get("msg_type", "")
+        content = msg.get("content") or {}
+        parent = (msg.get("parent_header") or {}).get("msg_id")
+        run = pending.get(parent)
+
+        if msg_type == "status":
+            state = content.get("execution_state", "")
+            if state in ("busy", "idle"):
+                out.send({"event": "status", "state": state})
+            if state == "idle" and run is not None:
+                pending.pop(parent, None)
+                out.send({"event": "finished", "id": run["id"],
+                          "execution_count": run["execution_count"], "ok": run["ok"]})
+            continue
+        if run is None:
+            continue
+        if msg_type == "execute_input":
+            run["execution_count"] = content.get("execution_count", 0) or 0
+            continue
+        if msg_type == "error":
+            run["ok"] = False
+        output = _to_output(msg_type, content)
+        if output is not None:
+            out.send({"event": "output", "id": run["id"], "output": output})
+
+    try:
+        kc.stop_channels()
+        km.shutdown_kernel(now=True)
+    except Exception:
+        pass
+
+
+if __name__ == "__main__":
+    main()
@@ -121,6 +121,47 @@ def get_figi(figi_id: str) -> Any:
     return data
 
 
+def resolve_identifier(id_type: str, id_value: str, exch_code: str = None) -> Any:
+    """Map one identifier and pick the listing a symbology table should keep:
+    the match on `exch_code` when given, else the composite (country-level)
+    listing, else the first match. Returns {"success", "data": {figi,
+    composite_figi, share_class_figi, ticker, exch_code, name, security_type,
+    market_sector}, "listings": n}.
+    """
+    job = {"idType": id_type, "idValue": id_value}
+    if exch_code:
+        job["exchCode"] = exch_code
+    data = _make_post("mapping", [job])
+    if isinstance(data, dict) and "error" in data:
+        return {"success": False, "error": data["error"]}
+    if not isinstance(data, list) or not data:
+        return {"success": False, "error": "Unexpected OpenFIGI response"}
+    item = data[0]
+    matches = item.get("data") or []
+    if not matches:
+        return {"success": False, "error": item.get("error") or item.get("warning") or "No match"}
+
+    best = matches[0]
+    for m in matches:
+        if m.get("figi") and m.get("figi") == m.get("compositeFIGI"):
+            best = m
+            break
+    return {
+        "success": True,
+        "data": {
+            "figi": best.get("figi") or "",
+            "composite_figi": best.get("compositeFIGI") or "",
+            "share_class_figi": best.get("shareClassFIGI") or "",
+            "ticker": best.get("ticker") or "",
+            "exch_code": best.get("exchCode") or "",
+            "name": best.get("name") or "",
+            "security_type": best.get("securityType2") or best.get("securityType") or "",
+            "market_sector": best.get("marketSector") or "",
+        },
+        "listings": len(matches),
+    }
+
+
 def get_enum_values(enum_type: str) -> Any:
     """Get valid enumeration values for OpenFIGI fields.
     enum_type options: idType, exchCode, micCode, currency, marketSecDes, securityType, securityType2.
@@ -135,7 +176,7 @@ def main(args=None):
     if args is None:
         args = sys.argv[1:]
     if not args:
-        print(json.dumps({"error": "No command provided. Available: map, search, lookup, enum"}))
+        print(json.dumps({"error": "No command provided. Available: map, search, lookup, resolve, enum"}))
         return
 
     command = args[0]
@@ -156,13 +197,18 @@ def main(args=None):
             result = {"error": "Usage: lookup <figi_id>"}
         else:
             result = get_figi(args[1])
+    elif command == "resolve":
+        if len(args) < 3:
+            result = {"error": "Usage: resolve <idType> <idValue> [exchCode]"}
+        else:
+            result = resolve_identifier(args[1], args[2], args[3] if len(args) > 3 else None)
     elif command == "enum":
         if len(args) < 2:
             result = {"error": "Usage: enum <enum_type> (idType, exchCode, micCode, currency, marketSecDes, securityType, securityType2)"}
         else:
             result = get_enum_values(args[1])
     else:
-        result = {"error": f"Unknown command: {command}. Available: map, search, lookup, enum"}
+        result = {"error": f"Unknown command: {command}. Available: map, search, lookup, resolve, enum"}
 
     print(json.dumps(result))
 
@@ -1,7 +1,7 @@
 """
 Polygon.io Data Fetcher
-US stocks OHLCV, trades, quotes, market-wide aggregates, options, crypto,
-and forex data via the Polygon.io REST API.
+US stocks OHLCV, trades, quotes, market-wide aggregates, options chain
+snapshots, crypto, and forex data via the Polygon.io REST API.
 """
 import sys
 import json
@@ -91,6 +91,47 @@ def get_crypto_daily(ticker: str, date: str) -> Any:
     return _make_request(endpoint)
 
 
+def get_options_snapshot(underlying: str, max_pages: int = 20) -> Any:
+    """Snapshot of every listed contract on `underlying`, normalised to the
+    option-chain shape cboe_data.py returns (data.metadata / data.options)."""
+    endpoint = f"snapshot/options/{underlying.upper()}"
+    params = {"limit": 250}
+    options: List[Dict[str, Any]] = []
+    spot = None
+    for _ in range(max(1, max_pages)):
+        page = _make_request(endpoint, {}) if endpoint.startswith('http') else _make_request_v3(endpoint, params)
+        if "error" in page:
+            if options:
+                break
+            return page
+        for r in page.get("results", []):
+            details = r.get("details", {})
+            day = r.get("day", {})
+            quote = r.get("last_quote", {})
+            trade = r.get("last_trade", {})
+            spot = r.get("underlying_asset", {}).get("price", spot)
+            options.append({
+                "contract_symbol": details.get("ticker"),
+                "expiration": details.get("expiration_date"),
+                "strike": details.get("strike_price"),
+                "option_type": details.get("contract_type"),
+                "last": trade.get("price", day.get("close")),
+                "bid": quote.get("bid"),
+                "ask": quote.get("ask"),
+                "volume": day.get("volume"),
+                "open_interest": r.get("open_interest"),
+                "implied_volatility": r.get("implied_volatility"),
+                "delta": r.get("greeks", {}).get("delta"),
+            })
+        endpoint = page.get("next_url")
+        if not endpoint:
+            break
+    if not options:
+        return {"error": f"No option contracts found for {underlying.upper()}"}
+    return {"success": True, "data": {"metadata": {"symbol": underlying.upper(), "current_price": spot},
+                                      "options": options}}
+
+
 def main(args=None):
     if args is None:
         args = sys.argv[1:]
@@ -123,6 +164,10 @@ def main(args=None):
         ticker = args[1] if len(args) > 1 else "BTC-USD"
         date = args[2] if len(args) > 2 else "2024-01-02"
         result = get_crypto_daily(ticker, date)
+    elif command == "options_snapshot":
+        underlying = args[1] if len(args) > 1 else "AAPL"
+        max_pages = int(args[2]) if len(args) > 2 else 20
+        result = get_options_snapshot(underlying, max_pages)
 
     print(json.dumps(result))
 
@@ -0,0 +1,238 @@
+"""
+Quote Router
+Fetches one quote through a provider priority list (e.g. polygon,fmp,yfinance).
+A provider that errors, has no API key or is rate limited is skipped for the
+next one; fields the serving provider leaves empty are filled from the
+providers after it. Every field records which provider supplied it.
+
+Usage: python quote_router.py <symbol> [providers]
+  providers  comma-separated, tried in order (default: yfinance)
+             yfinance, fmp, polygon, finnhub, alphavantage, twelvedata
+"""
+import sys
+import json
+import os
+import time
+from typing import Dict, Any, Callable, List, Optional
+
+SCRIPT_DIR = os.path.dirname(os.path.abspath(__file__))
+if SCRIPT_DIR not in sys.path:
+    sys.path.insert(0, SCRIPT_DIR)
+
+# Fields every provider is asked for. `name` is taken when offered but never
+# worth another provider call on its own.
+CORE_FIELDS = ["price", "change", "change_pct", "open", "high", "low", "previous_close", "volume"]
+FIELDS = CORE_FIELDS + ["name"]
+
+KEY_ENV = {
+    "fmp": "FMP_API_KEY",
+    "polygon": "POLYGON_API_KEY",
+    "finnhub": "FINNHUB_API_KEY",
+    "alphavantage": "ALPHA_VANTAGE_API_KEY",
+    "twelvedata": "TWELVE_DATA_API_KEY",
+}
+
+RATE_LIMIT_MARKERS = ("429", "rate limit", "too many requests", "api call frequency", "run out of api credits",
+                      "limit reached")
+
+
+class ProviderError(Exception):
+    def __init__(self, message: str, rate_limited: bool = False):
+        super().__init__(message)
+        self.rate_limited = rate_limited or any(m in message.lower() for m in RATE_LIMIT_MARKERS)
+
+
+def _num(value) -> Optional[float]:
+    if value is None:
+        return None
+    try:
+        if isinstance(value, str):
+            value = value.strip().rstrip("%")
+            if not value:
+                return None
+        return float(value)
+    except (TypeError, ValueError):
+        return None
+
+
+def _error_of(payload: Any) -> Optional[str]:
+    if isinstance(payload, dict):
+        err = payload.get("error")
+        if isinstance(err, dict):
+            return err.get("error") or err.get("message") or json.dumps(err)
+        if err:
+            return str(err)
+        if payload.get("status") == "error":
+            return str(payload.get("message") or payload.get("code") or "provider error")
+    return None
+
+
+# ── Provider adapters ───────────────────────────────────────────────────────
+# Each returns {field: value} (missing fields omitted or None) or raises
+# ProviderError.
+
+def _yfinance(symbol: str) -> Dict[str, Any]:
+    import yfinance_data
+    q = yfinance_data.get_quote(symbol)
+    err = _error_of(q)
+    if err:
+        raise ProviderError(err)
+    return {"price": q.get("price"), "change": q.get("change"), "change_pct": q.get("change_percent"),
+            "open": q.get("open"), "high": q.get("high"), "low": q.get("low"),
+            "previous_close": q.get("previous_close"), "volume": q.get("volume")}
+
+
+def _fmp(symbol: str) -> Dict[str, Any]:
+    from fmp_data import FMPDataWrapper
+    res = FMPDataWrapper().get_equity_quote(symbol)
+    err = _error_of(res)
+    if err:
+        raise ProviderError(err)
+    rows = res.get("data") or []
+    if not rows:
+        raise ProviderError("no quote returned")
+    q = rows[0]
+    return {"price": q.get("price"), "change": q.get("change"), "change_pct": q.get("changesPercentage"),
+            "open": q.get("open"), "high": q.get("dayHigh"), "low": q.get("dayLow"),
+            "previous_close": q.get("previousClose"), "volume": q.get("volume"), "name": q.get("name")}
+
+
+def _polygon(symbol: str) -> Dict[str, Any]:
+    import polygon_io_data
+    res = polygon_io_data._make_request(f"snapshot/locale/us/markets/stocks/tickers/{symbol.upper()}")
+    err = _error_of(res)
+    if err:
+        raise ProviderError(err)
+    t = res.get("ticker") or {}
+    if not t:
+        raise ProviderError(res.get("message") or "no snapshot returned")
+    day = t.get("day") or {}
+    prev = t.get("prevDay") or {}
+    last = (t.get("lastTrade") or {}).get("p") or day.get("c")
+    return {"price": last, "change": t.get("todaysChange"), "change_pct": t.get("todaysChangePerc"),
+            "open": day.get("o"), "high": day.get("h"), "low": day.get("l"),
+            "previous_close": prev.get("c"), "volume": day.get("v")}
+
+
+def _finnhub(symbol: str) -> Dict[str, Any]:
+    import finnhub_data
+    q = finnhub_data.get_quote(symbol)
+    err = _error_of(q)
+    if err:
+        raise ProviderError(err)
+    if not q or not _num(q.get("c")):
+        raise ProviderError("symbol not covered")  # Finnhub answers unknown symbols with zeros
+    return {"price": q.get("c"), "change": q.get("d"), "change_pct": q.get("dp"), "open": q.get("o"),
+            "high": q.get("h"), "low": q.get("l"), "previous_close": q.get("pc")}
+
+
+def _alphavantage(symbol: str) -> Dict[str, Any]:
+    import alphavantage_data
+    q = alphavantage_data.get_quote(symbol)
+    err = _error_of(q)
+    if err:
+        # An exhausted daily quota comes back as a note instead of "Global Quote".
+        raise ProviderError(err, rate_limited=err.startswith("No data returned"))
+    return {"price": q.get("price"), "change": q.get("change"), "change_pct": q.get("change_percent"),
+            "open": q.get("open"), "high": q.get("high"), "low": q.get("low"),
+            "previous_close": q.get("previous_close"), "volume": q.get("volume")}
+
+
+def _twelvedata(symbol: str) -> Dict[str, Any]:
+    import twelve_data
+    q = twelve_data.get_quote(symbol)
+    err = _error_of(q)
+    if err:
+        raise ProviderError(err, rate_limited=isinstance(q, dict) and str(q.get("code")) == "429")
+    return {"price": q.get("close"), "change": q.get("change"), "change_pct": q.get("percent_change"),
+            "open": q.get("open"), "high": q.get("high"), "low": q.get("low"),
+            "previous_close": q.get("previous_close"), "volume": q.get("volume"), "name": q.get("name")}
+
+
+PROVIDERS: Dict[str, Callable[[str], Dict[str, Any]]] = {
+    "yfinance": _yfinance,
+    "fmp": _fmp,
+    "polygon": _polygon,
+    "finnhub": _finnhub,
+    "alphavantage": _alphavantage,
+    "twelvedata": _twelvedata,
+}
+
+
+def route_quote(symbol: str, chain: List[str]) -> Dict[str, Any]:
+    quote: Dict[str, Any] = {}
+    sources: Dict[str, str] = {}
+    attempts: List[Dict[str, Any]] = []
+    served_by = None
+
+    for provider in chain:
+        if all(f in quote for f in CORE_FIELDS):
+            break
+        fetch = PROVIDERS.get(provider)
+        if fetch is None:
+            attempts.append({"provider": provider, "ok": False, "error": "unknown provider"})
+            continue
+        env = KEY_ENV.get(provider)
+        if env and not os.environ.get(env):
+            attempts.append({"provider": provider, "ok": False, "error": f"{env} not set"})
+            continue
+
+        started = time.time()
+        attempt: Dict[str, Any] = {"provider": provider}
+        try:
+            fields = fetch(symbol)
+        except ProviderError as e:
+            attempt.update({"ok": False, "error": str(e), "rate_limited": e.rate_limited})
+            fields = None
+        except Exception as e:
+            attempt.update({"ok": False, "error": str(e), "rate_limited": ProviderError(str(e)).rate_limited})
+            fields = None
+        attempt["ms"] = int((time.time() - started) * 1000)
+
+        filled = []
+        for field, raw in (fields or {}).items():
+            if field in quote:
+                continue
+            value = raw if field == "name" else _num(raw)
+            if value is None or value == "" or (field in ("price", "previous_close") and value <= 0):
+                continue
+            quote[field] = value
+            sources[field] = provider
+            filled.append(field)
+        if fields is not None:
+            attempt["ok"] = True
+            attempt["fields"] = filled
+            if served_by is None and "price" in filled:
+                served_by = provider
+        attempts.append(attempt)
+
+    if "price" not in quote:
+        return {"success": False, "error": f"No provider in [{', '.join(chain)}] returned a quote for {symbol}",
+                "attempts": attempts}
+
+    # Derive what arithmetic can recover before reporting a field as missing.
+    price, prev = quote["price"], quote.get("previous_close")
+    if prev:
+        if "change" not in quote:
+            quote["change"], sources["change"] = price - prev, "derived"
+        if "change_pct" not in quote:
+            quote["change_pct"], sources["change_pct"] = (price - prev) / prev * 100, "derived"
+
+    data = {"symbol": symbol.upper(), **{f: quote.get(f) for f in FIELDS}, "provider": served_by,
+            "sources": sources, "missing": [f for f in CORE_FIELDS if f not in quote], "attempts": attempts}
+    return {"success": True, "data": data}
+
+
+def main(args=None):
+    if args is None:
+        args = sys.argv[1:]
+    if not args:
+        print(json.dumps({"success": False, "error": "Usage: quote_router.py <symbol> [providers]"}))
+        return
+    symbol = args[0].strip()
+    chain = [p.strip().lower() for p in (args[1] if len(args) > 1 else "yfinance").split(",") if p.strip()]
+    print(json.dumps(route_quote(symbol, chain or ["yfinance"])))
+
+
+if __name__ == "__main__":
+    main()
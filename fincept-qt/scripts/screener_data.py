@@ -0,0 +1,240 @@
+"""
+Screener data snapshot — fundamentals, technicals and ownership per symbol.
+
+Feeds the native screener (ScreenerEngine), which stores one row per symbol in
+screener_data and evaluates filter trees against it locally. Every metric is a
+flat number so any field can be compared with any other; percentages are in
+percent (12.5 = 12.5%), not fractions.
+
+Usage:
+    python screener_data.py snapshot AAPL,MSFT,NVDA [--workers 6]
+
+Output:
+    {"success": true,
+     "data": [{"symbol", "name", "sector", "industry", "exchange", "country",
+               "currency", "fields": {...}}],
+     "failed": [{"symbol", "error"}]}
+"""
+
+import json
+import math
+import sys
+from concurrent.futures import ThreadPoolExecutor
+
+import pandas as pd
+import yfinance as yf
+
+TRADING_DAYS = 252
+
+
+def _num(value, scale=1.0):
+    """Finite float or None — yfinance mixes None, NaN, 'Infinity' and strings."""
+    try:
+        v = float(value)
+    except (TypeError, ValueError):
+        return None
+    if math.isnan(v) or math.isinf(v):
+        return None
+    return v * scale
+
+
+def _ratio(a, b, scale=1.0):
+    a, b = _num(a), _num(b)
+    if a is None or not b:
+        return None
+    return a / b * scale
+
+
+def _fundamentals(info):
+    price = _num(info.get("currentPrice")) or _num(info.get("regularMarketPrice"))
+    market_cap = _num(info.get("marketCap"))
+    # dividendYield switched from a fraction to percent across yfinance
+    # releases; the trailing rate over price is unambiguous.
+    div_yield = _ratio(info.get("trailingAnnualDividendRate"), price, 100.0)
+    if div_yield is None:
+        div_yield = _num(info.get("dividendYield"))
+    return {
+        "market_cap": market_cap,
+        "enterprise_value": _num(info.get("enterpriseValue")),
+        "pe_ratio": _num(info.get("trailingPE")),
+        "forward_pe": _num(info.get("forwardPE")),
+        "peg_ratio": _num(info.get("trailingPegRatio")) or _num(info.get("pegRatio")),
+        "price_to_book": _num(info.get("priceToBook")),
+        "price_to_sales": _num(info.get("priceToSalesTrailing12Months")),
+        "ev_to_ebitda": _num(info.get("enterpriseToEbitda")),
+        "dividend_yield": div_yield,
+        "gross_margin": _num(info.get("grossMargins"), 100.0),
+        "operating_margin": _num(info.get("operatingMargins"), 100.0),
+        "profit_margin": _num(info.get("profitMargins"), 100.0),
+        "roe": _num(info.get("returnOnEquity"), 100.0),
+        "roa": _num(info.get("returnOnAssets"), 100.0),
+        "revenue_growth": _num(info.get("revenueGrowth"), 100.0),
+        "earnings_growth": _num(info.get("earningsGrowth"), 100.0),
+        "debt_to_equity": _num(info.get("debtToEquity")),
+        "current_ratio": _num(info.get("currentRatio")),
+        "fcf_yield": _ratio(info.get("freeCashflow"), market_cap, 100.0),
+        "beta": _num(info.get("beta")),
+    }
+
+
+def _ownership(info, ticker):
+    fields = {
+        "insider_pct": _num(info.get("heldPercentInsiders"), 100.0),
+        "institution_pct": _num(info.get("heldPercentInstitutions"), 100.0),
+        "short_pct_float": _num(info.get("shortPercentOfFloat"), 100.0),
+        "short_ratio": _num(info.get("shortRatio")),
+        "float_shares": _num(info.get("floatShares")),
+        "insider_net_pct": None,
+    }
+    # Six-month insider purchases minus sales as % of insider holdings.
+    try:
+        table = ticker.insider_purchases
+        if table is not None and not table.empty:
+            label_col = table.columns[0]
+            for _, row in table.iterrows():
+                if "% Net Shares" in str(row[label_col]):
+                    fields["insider_net_pct"] = _num(row.get("Shares"), 100.0)
+                    break
+    except Exception:
+        pass
+    return fields
+
+
+def _alternative(info, price):
+    target = _num(info.get("targetMeanPrice"))
+    return {
+        "analyst_rating": _num(info.get("recommendationMean")),  # 1 strong buy … 5 sell
+        "analyst_count": _num(info.get("numberOfAnalystOpinions")),
+        "target_upside_pct": (target / price - 1.0) * 100.0 if target and price else None,
+    }
+
+
+def _rsi(close, period=14):
+    delta = close.diff()
+    gain = delta.clip(lower=0).ewm(alpha=1.0 / period, adjust=False).mean()
+    loss = (-delta.clip(upper=0)).ewm(alpha=1.0 / period, adjust=False).mean()
+    last_loss = loss.iloc[-1]
+    if not last_loss:
+        return 100.0
+    return 100.0 - 100.0 / (1.0 + gain.iloc[-1] / last_loss)
+
+
+def _technicals(hist):
+    empty = {k: None for k in (
+        "price", "change_pct", "return_1m", "return_3m", "return_6m", "return_12m", "rsi_14", "sma_50",
+        "sma_200", "price_vs_sma50_pct", "price_vs_sma200_pct", "pct_from_52w_high", "pct_from_52w_low",
+        "volatility_3m", "atr_14_pct", "avg_volume_3m", "relative_volume")}
+    if hist is None or hist.empty or len(hist) < 2:
+        return empty
+
+    close = hist["Close"].astype(float)
+    high = hist["High"].astype(float)
+    low = hist["Low"].astype(float)
+    volume = hist["Volume"].astype(float)
+    price = float(close.iloc[-1])
+
+    def ret(days):
+        if len(close) <= days:
+            return None
+        base = float(close.iloc[-days - 1])
+        return (price / base - 1.0) * 100.0 if base else None
+
+    def sma(n):
+        return float(close.tail(n).mean()) if len(close) >= n else None
+
+    sma50, sma200 = sma(50), sma(200)
+    year = close.tail(TRADING_DAYS)
+    hi52, lo52 = float(year.max()), float(year.min())
+    returns = close.pct_change().dropna().tail(63)
+    prev_close = close.shift(1)
+    true_range = pd.concat([high - low, (high - prev_close).abs(), (low - prev_close).abs()], axis=1).max(axis=1)
+    atr = float(true_range.tail(14).mean()) if len(true_range) >= 15 else None
+    avg_vol = float(volume.tail(63).mean()) if len(volume) else None
+
+    out = dict(empty)
+    out.update({
+        "price": price,
+        "change_pct": ret(1),
+        "return_1m": ret(21),
+        "return_3m": ret(63),
+        "return_6m": ret(126),
+        "return_12m": ret(TRADING_DAYS - 1),
+        "rsi_14": _num(_rsi(close)) if len(close) > 15 else None,
+        "sma_50": sma50,
+        "sma_200": sma200,
+        "price_vs_sma50_pct": (price / sma50 - 1.0) * 100.0 if sma50 else None,
+        "price_vs_sma200_pct": (price / sma200 - 1.0) * 100.0 if sma200 else None,
+        "pct_from_52w_high": (price / hi52 - 1.0) * 100.0 if hi52 else None,
+        "pct_from_52w_low": (price / lo52 - 1.0) * 100.0 if lo52 else None,
+        "volatility_3m": float(returns.std() * math.sqrt(TRADING_DAYS) * 100.0) if len(returns) > 10 else None,
+        "atr_14_pct": atr / price * 100.0 if atr and price else None,
+        "avg_volume_3m": avg_vol,
+        "relative_volume": float(volume.iloc[-1]) / avg_vol if avg_vol else None,
+    })
+    return {k: _num(v) for k, v in out.items()}
+
+
+def snapshot_symbol(symbol):
+    ticker = yf.Ticker(symbol)
+    hist = ticker.history(period="1y", interval="1d", auto_adjust=True)
+    try:
+        info = ticker.info or {}
+    except Exception:
+        info = {}
+    if (hist is None or hist.empty) and not info.get("quoteType"):
+        raise ValueError("no data (delisted or unknown symbol)")
+
+    fields = _technicals(hist)
+    fields.update(_fundamentals(info))
+    fields.update(_ownership(info, ticker))
+    fields.update(_alternative(info, fields.get("price")))
+    return {
+        "symbol": symbol,
+        "name": info.get("longName") or info.get("shortName") or symbol,
+        "sector": info.get("sector") or "",
+        "industry": info.get("industry") or "",
+        "exchange": info.get("exchange") or "",
+        "country": info.get("country") or "",
+        "currency": info.get("currency") or "",
+        "fields": {k: v for k, v in fields.items() if v is not None},
+    }
+
+
+def snapshot(symbols, workers=6):
+    data, failed = [], []
+
+    def one(symbol):
+        try:
+            return snapshot_symbol(symbol), None
+        except Exception as e:
+            return None, {"symbol": symbol, "error": str(e)}
+
+    with ThreadPoolExecutor(max_workers=max(1, workers)) as pool:
+        for row, err in pool.map(one, symbols):
+            if row:
+                data.append(row)
+            else:
+                failed.append(err)
+    return {"success": True, "data": data, "failed": failed}
+
+
+def main(args=None):
+    if args is None:
+        args = sys.argv[1:]
+    if len(args) < 2 or args[0] != "snapshot":
+        return json.dumps({"success": False, "error": "Usage: python screener_data.py snapshot SYM1,SYM2,... "
+                                                     "[--workers N]"})
+    symbols = [s.strip().upper() for s in args[1].split(",") if s.strip()]
+    workers = 6
+    if "--workers" in args:
+        try:
+            workers = int(args[args.index("--workers") + 1])
+        except (IndexError, ValueError):
+            pass
+    if not symbols:
+        return json.dumps({"success": False, "error": "No symbols given"})
+    return json.dumps(snapshot(list(dict.fromkeys(symbols)), workers))
+
+
+if __name__ == "__main__":
+    print(main())
@@ -0,0 +1,238 @@
+"""
+Persistent script host for PythonRunner.
+
+Runs Analytics/ and agents/ scripts inside one long-lived interpreter so a call
+no longer pays interpreter start-up plus the numpy / pandas / scipy imports
+(200-800 ms per call). Started and pooled by PythonScriptHost
+(src/python/PythonScriptHost.cpp), one process per pool slot.
+
+Framing matches `yfinance_data.py --daemon`: 4-byte big-endian length prefix,
+UTF-8 JSON body, both directions.
+
+    → {"ready": true, "pid": N}                      once, after warm imports
+    ← {"id", "script", "module"?, "args": [...], "env": {...}, "cwd"}
+    → {"id", "ok": true, "stdout", "stderr", "exit_code"}
+    ← {"id", "action": "shutdown"}
+
+A request behaves like `python <script> args...` (or `python -m <module>`):
+sys.argv, sys.path, os.environ and the working directory are set for the run
+and restored after it, stdout / stderr are captured, and SystemExit becomes the
+exit code. Logging handlers, levels and loggers a script sets up are undone
+after it, as are warning filters. Modules from the scripts directory are
+dropped after every run so two scripts with same-named helper modules never
+see each other's; third-party imports stay cached, which is the point.
+
+The frame channel is a private dup of fd 1; fd 1 itself is pointed at stderr
+so native code writing straight to stdout cannot corrupt the framing.
+"""
+
+import contextlib
+import io
+import json
+import logging
+import os
+import runpy
+import sys
+import traceback
+import warnings
+
+# Imported once at start-up; failures are fine (the venv may not have them).
+_WARM_IMPORTS = ("numpy", "pandas", "scipy", "scipy.stats")
+
+
+class _CaptureBuffer(io.BytesIO):
+    """BytesIO that survives close() — scripts that rewrap sys.stdout
+    (io.TextIOWrapper(sys.stdout.buffer, ...)) close it when the wrapper dies."""
+
+    def close(self):
+        pass
+
+
+def _read_frame(stream):
+    """Read one length-prefixed frame. Returns bytes payload, or None on EOF."""
+    header = b""
+    while len(header) < 4:
+        chunk = stream.read(4 - len(header))
+        if not chunk:
+            return None
+        header += chunk
+    n = int.from_bytes(header, byteorder="big", signed=False)
+    if n == 0 or n > 64 * 1024 * 1024:
+        return None
+    buf = b""
+    while len(buf) < n:
+        chunk = stream.read(n - len(buf))
+        if not chunk:
+            return None
+        buf += chunk
+    return buf
+
+
+def _write_frame(stream, obj):
+    data = json.dumps(obj).encode("utf-8")
+    stream.write(len(data).to_bytes(4, byteorder="big", signed=False))
+    stream.write(data)
+    stream.flush()
+
+
+def _under(path, root):
+    try:
+        return os.path.commonpath([os.path.abspath(path), root]) == root
+    except ValueError:  # different drives on Windows
+        return False
+
+
+def _snapshot_logging():
+    loggers = [logging.getLogger()] + [
+        lg for lg in logging.Logger.manager.loggerDict.values() if isinstance(lg, logging.Logger)
+    ]
+    state = {lg: (lg.handlers[:], lg.level, lg.propagate, lg.disabled) for lg in loggers}
+    return state, set(logging.Logger.manager.loggerDict), logging.root.manager.disable
+
+
+def _restore_logging(saved):
+    state, names, disable = saved
+    manager = logging.Logger.manager
+    # Loggers a script created go; existing ones keep their object (modules
+    # hold references) but get back their handlers and levels.
+    for name in set(manager.loggerDict) - names:
+        lg = manager.loggerDict.pop(name, None)
+        for h in getattr(lg, "handlers", []):
+            _close_handler(h)
+    for lg, (handlers, level, propagate, disabled) in state.items():
+        for h in lg.handlers:
+            if h not in handlers:
+                _close_handler(h)
+        lg.handlers[:] = handlers
+        lg.setLevel(level)
+        lg.propagate = propagate
+        lg.disabled = disabled
+    logging.disable(disable)
+
+
+def _close_handler(handler):
+    try:
+        handler.close()
+    except Exception:
+        pass
+
+
+def _script_local(mod, scripts_root):
+    paths = [getattr(mod, "__file__", None)] + list(getattr(mod, "__path__", None) or [])
+    return any(p and _under(p, scripts_root) for p in paths)
+
+
+def _run(req, scripts_root):
+    script = req.get("script") or ""
+    module = req.get("module") or ""
+    args = [str(a) for a in (req.get("args") or [])]
+
+    saved_argv = sys.argv[:]
+    saved_path = sys.path[:]
+    saved_env = dict(os.environ)
+    saved_cwd = os.getcwd()
+    saved_logging = _snapshot_logging()
+    saved_warnings = warnings.filters[:]
+
+    out = io.TextIOWrapper(_CaptureBuffer(), encoding="utf-8", errors="replace", write_through=True)
+    err = io.TextIOWrapper(_CaptureBuffer(), encoding="utf-8", errors="replace", write_through=True)
+    exit_code = 0
+
+    env = req.get("env")
+    if isinstance(env, dict):
+        os.environ.clear()
+        os.environ.update({str(k): str(v) for k, v in env.items()})
+    if req.get("cwd"):
+        os.chdir(req["cwd"])
+
+    try:
+        with contextlib.redirect_stdout(out), contextlib.redirect_stderr(err):
+            try:
+                sys.argv = [script] + args
+                if module:
+                    runpy.run_module(module, run_name="__main__", alter_sys=True)
+                else:
+                    # `python script.py` puts the script's directory first on sys.path.
+                    sys.path.insert(0, os.path.dirname(script))
+                    runpy.run_path(script, run_name="__main__")
+            except SystemExit as e:
+                if e.code is None:
+                    exit_code = 0
+                elif isinstance(e.code, int):
+                    exit_code = e.code
+                else:
+                    print(e.code, file=sys.stderr)
+                    exit_code = 1
+            except Exception:
+                traceback.print_exc()
+                exit_code = 1
+    finally:
+        sys.argv = saved_argv
+        sys.path[:] = saved_path
+        os.environ.clear()
+        os.environ.update(saved_env)
+        os.chdir(saved_cwd)
+        _restore_logging(saved_logging)
+        warnings.filters[:] = saved_warnings
+        getattr(warnings, "_filters_mutated", lambda: None)()
+        for name, mod in list(sys.modules.items()):
+            if name != "__main__" and mod is not None and _script_local(mod, scripts_root):
+                sys.modules.pop(name, None)
+
+    for stream in (out, err):
+        try:
+            stream.flush()
+        except ValueError:
+            pass
+    return {
+        "stdout": out.buffer.getvalue().decode("utf-8", "replace"),
+        "stderr": err.buffer.getvalue().decode("utf-8", "replace"),
+        "exit_code": exit_code,
+    }
+
+
+def main():
+    scripts_root = os.path.abspath(sys.argv[1] if len(sys.argv) > 1 else os.path.dirname(__file__))
+
+    # Private frame channel; stray native writes to fd 1 go to stderr instead.
+    sys.stdout.flush()
+    frames = os.fdopen(os.dup(1), "wb")
+    os.dup2(2, 1)
+    stdin = sys.stdin.buffer
+
+    for name in _WARM_IMPORTS:
+        try:
+            __import__(name)
+        except Exception:
+            pass
+
+    _write_frame(frames, {"ready": True, "pid": os.getpid()})
+
+    while True:
+        frame = _read_frame(stdin)
+        if frame is None:
+            break
+        try:
+            req = json.loads(frame.decode("utf-8"))
+        except Exception as e:
+            _write_frame(frames, {"id": 0, "ok": False, "error": f"bad request JSON: {e}"})
+            continue
+
+        req_id = req.get("id", 0)
+        if req.get("action") == "shutdown":
+            _write_frame(frames, {"id": req_id, "ok": True})
+            break
+
+        try:
+            resp = {"id": req_id, "ok": True}
+            resp.update(_run(req, scripts_root))
+        except Exception as e:
+            resp = {"id": req_id, "ok": False, "error": str(e)}
+        try:
+            _write_frame(frames, resp)
+        except Exception:
+            break
+
+
+if __name__ == "__main__":
+    main()
@@ -7,6 +7,7 @@ import sys
 import json
 import os
 import requests
+from datetime import date
 from typing import Dict, Any, Optional, List
 
 BASE_URL = "https://data.sec.gov/api/xbrl"
@@ -125,6 +126,124 @@ def get_recent_filings(cik: str) -> Any:
         return {"error": f"JSON decode error: {str(e)}"}
 
 
+# Point-in-time metric → us-gaap concepts, most preferred first. Companies
+# switch concepts over the years (e.g. Revenues → RevenueFromContract...), so
+# every listed concept is read and the first one reporting a period wins.
+PIT_CONCEPTS = {
+    "revenue": ["Revenues", "RevenueFromContractWithCustomerExcludingAssessedTax", "SalesRevenueNet",
+                "RevenueFromContractWithCustomerIncludingAssessedTax"],
+    "gross_profit": ["GrossProfit"],
+    "operating_income": ["OperatingIncomeLoss"],
+    "net_income": ["NetIncomeLoss", "ProfitLoss"],
+    "eps_diluted": ["EarningsPerShareDiluted"],
+    "shares_diluted": ["WeightedAverageNumberOfDilutedSharesOutstanding"],
+    "operating_cash_flow": ["NetCashProvidedByUsedInOperatingActivities"],
+    "capex": ["PaymentsToAcquirePropertyPlantAndEquipment"],
+    "dividends_paid": ["PaymentsOfDividends", "PaymentsOfDividendsCommonStock"],
+    "total_assets": ["Assets"],
+    "total_liabilities": ["Liabilities"],
+    "total_equity": ["StockholdersEquity",
+                     "StockholdersEquityIncludingPortionAttributableToNoncontrollingInterest"],
+    "current_assets": ["AssetsCurrent"],
+    "current_liabilities": ["LiabilitiesCurrent"],
+    "cash": ["CashAndCashEquivalentsAtCarryingValue"],
+    "total_debt": ["LongTermDebt", "LongTermDebtNoncurrent"],
+}
+
+PIT_FORMS = {"10-K", "10-K/A", "10-Q", "10-Q/A", "20-F", "20-F/A", "40-F", "40-F/A"}
+
+
+def _resolve_cik(ticker_or_cik: str) -> Any:
+    if str(ticker_or_cik).isdigit():
+        return {"cik": str(ticker_or_cik), "ticker": ""}
+    tickers_data = get_company_tickers()
+    if "error" in tickers_data:
+        return tickers_data
+    wanted = ticker_or_cik.upper().replace(".", "-")
+    for t in tickers_data.get("tickers", []):
+        if (t.get("ticker") or "").upper() == wanted:
+            return {"cik": str(t.get("cik")), "ticker": t.get("ticker")}
+    return {"error": f"No SEC registrant found for ticker: {ticker_or_cik}"}
+
+
+def _period_kind(start: Optional[str], end: str) -> Optional[str]:
+    """'I' for balance sheet instants, 'Q' for ~3-month and 'FY' for ~1-year
+    durations; year-to-date spans (6 / 9 months) are dropped."""
+    if not start:
+        return "I"
+    try:
+        days = (date.fromisoformat(end) - date.fromisoformat(start)).days
+    except ValueError:
+        return None
+    if 80 <= days <= 100:
+        return "Q"
+    if 350 <= days <= 380:
+        return "FY"
+    return None
+
+
+def get_pit_fundamentals(ticker_or_cik: str) -> Any:
+    """Every reported value of the PIT_CONCEPTS metrics with both its period
+    end (report date) and the date the filing carrying it was made. Restated
+    and comparative values appear once per filing, so a consumer can ask what
+    was known on any given day."""
+    resolved = _resolve_cik(ticker_or_cik)
+    if "error" in resolved:
+        return resolved
+    facts = get_company_facts(resolved["cik"])
+    if "error" in facts:
+        return facts
+    gaap = facts.get("facts", {}).get("us-gaap", {})
+
+    rows = {}
+    for metric, concepts in PIT_CONCEPTS.items():
+        for priority, concept in enumerate(concepts):
+            units = gaap.get(concept, {}).get("units", {})
+            for unit, entries in units.items():
+                for e in entries:
+                    if e.get("form") not in PIT_FORMS or not e.get("end") or not e.get("filed"):
+                        continue
+                    kind = _period_kind(e.get("start"), e["end"])
+                    if kind is None or e.get("val") is None:
+                        continue
+                    key = (metric, kind, e["end"], e["filed"])
+                    if key in rows and rows[key]["_priority"] <= priority:
+                        continue
+                    value = float(e["val"])
+                    # Cash outflows are reported positive by some filers and
+                    # negative by others; store magnitudes.
+                    if metric in ("capex", "dividends_paid"):
+                        value = abs(value)
+                    rows[key] = {
+                        "metric": metric,
+                        "period": kind,
+                        "period_start": e.get("start") or "",
+                        "period_end": e["end"],
+                        "filed": e["filed"],
+                        "value": value,
+                        "unit": unit,
+                        "fiscal_year": e.get("fy"),
+                        "fiscal_period": e.get("fp") or "",
+                        "form": e.get("form") or "",
+                        "accession": e.get("accn") or "",
+                        "concept": concept,
+                        "_priority": priority,
+                    }
+
+    data = sorted(rows.values(), key=lambda r: (r["metric"], r["period_end"], r["filed"]))
+    for r in data:
+        del r["_priority"]
+    return {
+        "success": True,
+        "source": "sec",
+        "cik": resolved["cik"],
+        "ticker": resolved["ticker"] or ticker_or_cik,
+        "name": facts.get("entityName", ""),
+        "count": len(data),
+        "data": data,
+    }
+
+
 def main(args=None):
     if args is None:
         args = sys.argv[1:]
@@ -155,6 +274,9 @@ def main(args=None):
     elif command == "filings":
         cik = args[1] if len(args) > 1 else "320193"
         result = get_recent_filings(cik)
+    elif command == "pit":
+        ticker = args[1] if len(args) > 1 else "AAPL"
+        result = get_pit_fundamentals(ticker)
     print(json.dumps(result))
 
 
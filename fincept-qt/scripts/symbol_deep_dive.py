@@ -0,0 +1,332 @@
+"""
+Symbol deep dive — one process, one payload for the equity research page.
+
+Usage:
+    python symbol_deep_dive.py <symbol> [history_period]
+
+Fetches every section concurrently (thread pool over yfinance) and returns:
+    quote, fundamentals, history, financials, key_levels, insider,
+    news {articles, sentiment}, events, analyst, errors
+
+Sections fail independently: a failed section is null and its message is
+recorded under "errors" so the rest of the page still renders.
+"""
+
+import contextlib
+import io
+import json
+import math
+import os
+import sys
+from concurrent.futures import ThreadPoolExecutor
+from datetime import datetime, timedelta
+
+script_dir = os.path.dirname(os.path.abspath(__file__))
+sys.path.insert(0, script_dir)
+
+import yfinance as yf  # noqa: E402
+import pandas as pd  # noqa: E402
+
+from yfinance_data import (  # noqa: E402
+    get_quote,
+    get_info,
+    get_historical_period,
+    get_financials,
+    get_news,
+    _to_date_str,
+)
+
+
+def _clean(value):
+    """Recursively replace NaN/inf (invalid JSON) and pandas/numpy scalars."""
+    if isinstance(value, dict):
+        return {str(k): _clean(v) for k, v in value.items()}
+    if isinstance(value, (list, tuple)):
+        return [_clean(v) for v in value]
+    if isinstance(value, float):
+        return None if math.isnan(value) or math.isinf(value) else value
+    if hasattr(value, "item") and not isinstance(value, (str, bytes)):
+        try:
+            return _clean(value.item())
+        except Exception:
+            return str(value)
+    if isinstance(value, (pd.Timestamp, datetime)):
+        return value.strftime("%Y-%m-%d")
+    return value
+
+
+def _num(value):
+    try:
+        f = float(value)
+        return None if math.isnan(f) or math.isinf(f) else f
+    except (TypeError, ValueError):
+        return None
+
+
+# ── Key levels ───────────────────────────────────────────────────────────────
+
+def _sma(closes, n):
+    return round(sum(closes[-n:]) / n, 2) if len(closes) >= n else None
+
+
+def _cluster(levels, tolerance=0.015):
+    """Merge swing levels within `tolerance` of each other; strength = touches."""
+    merged = []
+    for level in sorted(levels):
+        if merged and abs(level - merged[-1]["price"]) / merged[-1]["price"] <= tolerance:
+            m = merged[-1]
+            m["price"] = (m["price"] * m["touches"] + level) / (m["touches"] + 1)
+            m["touches"] += 1
+        else:
+            merged.append({"price": level, "touches": 1})
+    for m in merged:
+        m["price"] = round(m["price"], 2)
+    return merged
+
+
+def compute_key_levels(candles, window=5):
+    if not isinstance(candles, list) or len(candles) < 2 * window + 1:
+        return None
+    highs = [c["high"] for c in candles]
+    lows = [c["low"] for c in candles]
+    closes = [c["close"] for c in candles]
+    last = closes[-1]
+
+    swing_highs, swing_lows = [], []
+    for i in range(window, len(candles) - window):
+        if highs[i] == max(highs[i - window:i + window + 1]):
+            swing_highs.append(highs[i])
+        if lows[i] == min(lows[i - window:i + window + 1]):
+            swing_lows.append(lows[i])
+    zones = _cluster(swing_highs + swing_lows)
+    support = sorted([z for z in zones if z["price"] < last], key=lambda z: -z["price"])[:3]
+    resistance = sorted([z for z in zones if z["price"] > last], key=lambda z: z["price"])[:3]
+
+    # Classic floor pivots from the last complete session.
+    h, l, c = highs[-2], lows[-2], closes[-2]
+    p = (h + l + c) / 3
+    pivots = {
+        "pivot": round(p, 2),
+        "r1": round(2 * p - l, 2),
+        "r2": round(p + (h - l), 2),
+        "s1": round(2 * p - h, 2),
+        "s2": round(p - (h - l), 2),
+    }
+
+    trs = []
+    for i in range(1, len(candles)):
+        trs.append(max(highs[i] - lows[i], abs(highs[i] - closes[i - 1]), abs(lows[i] - closes[i - 1])))
+    atr = round(sum(trs[-14:]) / min(14, len(trs)), 2) if trs else None
+
+    window_52w = candles[-252:]
+    hi_52w = max(c["high"] for c in window_52w)
+    lo_52w = min(c["low"] for c in window_52w)
+    return {
+        "last_close": last,
+        "high_52w": hi_52w,
+        "low_52w": lo_52w,
+        "pct_from_high_52w": round((last / hi_52w - 1) * 100, 2) if hi_52w else None,
+        "pct_from_low_52w": round((last / lo_52w - 1) * 100, 2) if lo_52w else None,
+        "sma_20": _sma(closes, 20),
+        "sma_50": _sma(closes, 50),
+        "sma_200": _sma(closes, 200),
+        "atr_14": atr,
+        "pivots": pivots,
+        "support": support,
+        "resistance": resistance,
+    }
+
+
+# ── Insider activity ─────────────────────────────────────────────────────────
+
+def get_insider_activity(symbol, limit=20, lookback_days=180):
+    df = yf.Ticker(symbol).insider_transactions
+    if df is None or df.empty:
+        return {"transactions": [], "summary": {"buys": 0, "sells": 0, "net_shares": 0, "net_value": 0}}
+
+    cutoff = datetime.now() - timedelta(days=lookback_days)
+    transactions = []
+    buys = sells = 0
+    net_shares = net_value = 0.0
+    for _, row in df.iterrows():
+        date = _to_date_str(row.get("Start Date"))
+        text = str(row.get("Text") or row.get("Transaction") or "")
+        shares = _num(row.get("Shares")) or 0.0
+        value = _num(row.get("Value")) or 0.0
+        lower = text.lower()
+        kind = "buy" if "purchase" in lower or "buy" in lower else "sell" if "sale" in lower else "other"
+        if date and datetime.strptime(date, "%Y-%m-%d") >= cutoff:
+            if kind == "buy":
+                buys += 1
+                net_shares += shares
+                net_value += value
+            elif kind == "sell":
+                sells += 1
+                net_shares -= shares
+                net_value -= value
+        if len(transactions) < limit:
+            transactions.append({
+                "date": date,
+                "insider": str(row.get("Insider") or ""),
+                "position": str(row.get("Position") or ""),
+                "type": kind,
+                "text": text,
+                "shares": shares,
+                "value": value,
+            })
+    return {
+        "transactions": transactions,
+        "summary": {
+            "lookback_days": lookback_days,
+            "buys": buys,
+            "sells": sells,
+            "net_shares": net_shares,
+            "net_value": net_value,
+        },
+    }
+
+
+# ── News + sentiment ─────────────────────────────────────────────────────────
+
+def get_news_sentiment(symbol, count=20):
+    news = get_news(symbol, count)
+    if "error" in news:
+        raise RuntimeError(news["error"])
+    articles = news.get("articles", [])
+    sentiment = None
+    if articles:
+        from news_nlp import analyze_sentiment_batch
+        batch = [{"id": str(i), "headline": a.get("title", ""), "summary": a.get("description", "")}
+                 for i, a in enumerate(articles)]
+        scored = analyze_sentiment_batch(batch)
+        if scored.get("success"):
+            for a, r in zip(articles, scored["results"]):
+                a["sentiment"] = r["sentiment"]
+                a["sentiment_score"] = r["score"]
+            sentiment = {
+                "engine": scored["engine"],
+                "overall_score": scored["overall_score"],
+                **scored["aggregate"],
+            }
+    return {"articles": articles, "sentiment": sentiment}
+
+
+# ── Upcoming events ──────────────────────────────────────────────────────────
+
+def get_events(symbol):
+    cal = yf.Ticker(symbol).calendar
+    if cal is None:
+        return {"earnings": None, "ex_dividend_date": None, "dividend_date": None}
+    if isinstance(cal, pd.DataFrame):  # older yfinance: one column per field
+        cal = {k: v.iloc[0] for k, v in cal.T.items()} if not cal.empty else {}
+    earnings_dates = cal.get("Earnings Date") or []
+    if not isinstance(earnings_dates, (list, tuple)):
+        earnings_dates = [earnings_dates]
+    dates = [d for d in (_to_date_str(x) for x in earnings_dates) if d]
+    return {
+        "earnings": {
+            "date": dates[0] if dates else None,
+            "date_range_end": dates[-1] if len(dates) > 1 else None,
+            "eps_estimate": _num(cal.get("Earnings Average")),
+            "eps_low": _num(cal.get("Earnings Low")),
+            "eps_high": _num(cal.get("Earnings High")),
+            "revenue_estimate": _num(cal.get("Revenue Average")),
+        } if dates else None,
+        "ex_dividend_date": _to_date_str(cal.get("Ex-Dividend Date")),
+        "dividend_date": _to_date_str(cal.get("Dividend Date")),
+    }
+
+
+# ── Analyst data ─────────────────────────────────────────────────────────────
+
+def get_analyst(symbol, changes=10):
+    ticker = yf.Ticker(symbol)
+    out = {"recommendations": [], "price_targets": None, "rating_changes": []}
+
+    rec = ticker.recommendations_summary
+    if rec is not None and not rec.empty:
+        for _, row in rec.iterrows():
+            out["recommendations"].append({
+                "period": str(row.get("period", "")),
+                "strong_buy": int(row.get("strongBuy", 0) or 0),
+                "buy": int(row.get("buy", 0) or 0),
+                "hold": int(row.get("hold", 0) or 0),
+                "sell": int(row.get("sell", 0) or 0),
+                "strong_sell": int(row.get("strongSell", 0) or 0),
+            })
+
+    try:
+        targets = ticker.analyst_price_targets
+        if targets:
+            out["price_targets"] = {k: _num(v) for k, v in dict(targets).items()}
+    except Exception:
+        pass
+
+    ud = ticker.upgrades_downgrades
+    if ud is not None and not ud.empty:
+        ud = ud.sort_index(ascending=False).head(changes)
+        for idx, row in ud.iterrows():
+            out["rating_changes"].append({
+                "date": _to_date_str(idx),
+                "firm": str(row.get("Firm", "")),
+                "to_grade": str(row.get("ToGrade", "")),
+                "from_grade": str(row.get("FromGrade", "")),
+                "action": str(row.get("Action", "")),
+            })
+    return out
+
+
+# ── Assembly ─────────────────────────────────────────────────────────────────
+
+def _unwrap(result):
+    """yfinance_data helpers report failure in-band as {"error": ...}."""
+    if isinstance(result, dict) and "error" in result:
+        raise RuntimeError(result["error"])
+    return result
+
+
+def get_deep_dive(symbol, history_period="1y"):
+    tasks = {
+        "quote": lambda: _unwrap(get_quote(symbol)),
+        "fundamentals": lambda: _unwrap(get_info(symbol)),
+        "history": lambda: _unwrap(get_historical_period(symbol, history_period, "1d")),
+        "financials": lambda: _unwrap(get_financials(symbol)),
+        "insider": lambda: get_insider_activity(symbol),
+        "news": lambda: get_news_sentiment(symbol),
+        "events": lambda: get_events(symbol),
+        "analyst": lambda: get_analyst(symbol),
+    }
+    result = {"symbol": symbol, "generated_at": int(datetime.now().timestamp()), "errors": {}}
+    with ThreadPoolExecutor(max_workers=len(tasks)) as pool:
+        futures = {name: pool.submit(fn) for name, fn in tasks.items()}
+        for name, fut in futures.items():
+            try:
+                result[name] = fut.result(timeout=45)
+            except Exception as e:
+                result[name] = None
+                result["errors"][name] = str(e) or type(e).__name__
+
+    try:
+        result["key_levels"] = compute_key_levels(result.get("history"))
+    except Exception as e:
+        result["key_levels"] = None
+        result["errors"]["key_levels"] = str(e)
+    return result
+
+
+def main(args=None):
+    if args is None:
+        args = sys.argv[1:]
+    if len(args) < 1:
+        return json.dumps({"error": "Usage: python symbol_deep_dive.py <symbol> [history_period]"})
+    symbol = args[0].strip().upper()
+    period = args[1] if len(args) > 1 else "1y"
+    # yfinance prints progress/warnings to stdout; keep stdout clean for JSON.
+    buf = io.StringIO()
+    with contextlib.redirect_stdout(buf):
+        result = get_deep_dive(symbol, period)
+    return json.dumps(_clean(result))
+
+
+if __name__ == "__main__":
+    print(main())
@@ -0,0 +1,121 @@
+"""
+Tradier Market Data Fetcher
+Option chains (with greeks) and quotes via the Tradier brokerage market
+data API. Requires TRADIER_API_KEY (an access token); set TRADIER_SANDBOX=1
+to use the sandbox endpoint.
+"""
+import sys
+import json
+import os
+import requests
+from typing import Dict, Any, List
+
+API_KEY = os.environ.get('TRADIER_API_KEY', '')
+BASE_URL = ("https://sandbox.tradier.com/v1" if os.environ.get('TRADIER_SANDBOX') == '1'
+            else "https://api.tradier.com/v1")
+
+session = requests.Session()
+adapter = requests.adapters.HTTPAdapter(pool_connections=10, pool_maxsize=10, max_retries=3)
+session.mount('https://', adapter)
+
+
+def _make_request(endpoint: str, params: Dict = None) -> Any:
+    if not API_KEY:
+        return {"error": "TRADIER_API_KEY is not set"}
+    headers = {"Authorization": f"Bearer {API_KEY}", "Accept": "application/json"}
+    try:
+        response = session.get(f"{BASE_URL}/{endpoint}", params=params or {}, headers=headers, timeout=30)
+        response.raise_for_status()
+        return response.json()
+    except requests.exceptions.HTTPError as e:
+        return {"error": f"HTTP {e.response.status_code}: {str(e)}"}
+    except requests.exceptions.RequestException as e:
+        return {"error": f"Request failed: {str(e)}"}
+    except (json.JSONDecodeError, ValueError) as e:
+        return {"error": f"JSON decode error: {str(e)}"}
+
+
+def _as_list(v: Any) -> List[Any]:
+    # Tradier returns a bare object instead of a one-element list.
+    if v is None:
+        return []
+    return v if isinstance(v, list) else [v]
+
+
+def get_quotes(symbols: str) -> Any:
+    return _make_request("markets/quotes", {"symbols": symbols.upper()})
+
+
+def get_expirations(symbol: str) -> Any:
+    return _make_request("markets/options/expirations", {"symbol": symbol.upper()})
+
+
+def get_options_chain(symbol: str, max_expirations: int = 4) -> Any:
+    """Chains for the nearest `max_expirations` expirations, normalised to the
+    option-chain shape cboe_data.py returns (data.metadata / data.options)."""
+    exp = get_expirations(symbol)
+    if "error" in exp:
+        return exp
+    dates = _as_list((exp.get("expirations") or {}).get("date"))[:max(1, max_expirations)]
+    if not dates:
+        return {"error": f"No option expirations for {symbol.upper()}"}
+
+    spot = None
+    quote = get_quotes(symbol)
+    if "error" not in quote:
+        q = _as_list((quote.get("quotes") or {}).get("quote"))
+        if q:
+            spot = q[0].get("last")
+
+    options: List[Dict[str, Any]] = []
+    for date in dates:
+        chain = _make_request("markets/options/chains", {"symbol": symbol.upper(), "expiration": date,
+                                                          "greeks": "true"})
+        if "error" in chain:
+            continue
+        for o in _as_list((chain.get("options") or {}).get("option")):
+            greeks = o.get("greeks") or {}
+            options.append({
+                "contract_symbol": o.get("symbol"),
+                "expiration": o.get("expiration_date"),
+                "strike": o.get("strike"),
+                "option_type": o.get("option_type"),
+                "last": o.get("last"),
+                "bid": o.get("bid"),
+                "ask": o.get("ask"),
+                "volume": o.get("volume"),
+                "open_interest": o.get("open_interest"),
+                "implied_volatility": greeks.get("mid_iv"),
+                "delta": greeks.get("delta"),
+            })
+    if not options:
+        return {"error": f"No option chains returned for {symbol.upper()}"}
+    return {"success": True, "data": {"metadata": {"symbol": symbol.upper(), "current_price": spot},
+                                      "options": options}}
+
+
+def main(args=None):
+    if args is None:
+        args = sys.argv[1:]
+    if not args:
+        print(json.dumps({"error": "No command provided"}))
+        return
+    command = args[0]
+    result = {"error": f"Unknown command: {command}"}
+
+    if command == "options_chain":
+        symbol = args[1] if len(args) > 1 else "SPY"
+        max_expirations = int(args[2]) if len(args) > 2 else 4
+        result = get_options_chain(symbol, max_expirations)
+    elif command == "expirations":
+        symbol = args[1] if len(args) > 1 else "SPY"
+        result = get_expirations(symbol)
+    elif command == "quotes":
+        symbols = args[1] if len(args) > 1 else "SPY"
+        result = get_quotes(symbols)
+
+    print(json.dumps(result))
+
+
+if __name__ == "__main__":
+    main()
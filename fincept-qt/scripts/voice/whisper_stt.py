@@ -0,0 +1,285 @@
+#!/usr/bin/env python3
+"""
+whisper_stt.py — Local, offline speech-to-text via whisper.cpp (pywhispercpp).
+
+Two modes:
+    live (default)    Mic (sounddevice) -> energy VAD -> one Whisper pass per
+                      utterance -> JSON stdout. Runs until killed.
+    --file PATH.wav   Transcribe one WAV file, print a single {"text"} (or
+                      {"error"}) line, then exit. Used for voice commands
+                      recorded elsewhere (SpeechService::transcribe_file and
+                      the transcribe_voice_command MCP tool). --model,
+                      --language and --threads override the env vars below.
+
+Stdout protocol (one JSON object per line, same as the other STT scripts):
+    {"status": "loading"|"calibrating"|"listening"|"stopped"}
+    {"text":   "..."}                       finalized transcription
+    {"error":  "..."}                       recoverable
+    {"fatal":  "..."}                       unrecoverable, process exits
+
+Environment variables (set by WhisperSttProvider in C++):
+    FINCEPT_WHISPER_MODEL     ggml model name, default "base.en"
+                              (tiny.en / base.en / small.en / medium.en, or the
+                              multilingual tiny / base / small / medium)
+    FINCEPT_WHISPER_LANGUAGE  default "en"; "auto" lets Whisper detect it
+    FINCEPT_WHISPER_THREADS   decoder threads, default 4
+    FINCEPT_STT_GAIN          audio gain multiplier (default "1.0")
+    FINCEPT_STT_DEVICE        case-insensitive substring of input-device name
+    FINCEPT_DATA_DIR          models are cached under <dir>/models/whisper
+
+Nothing leaves the machine: the model is downloaded once on first use and
+every later transcription runs on the CPU.
+"""
+
+import argparse
+import json
+import os
+import signal
+import sys
+import threading
+import time
+import wave
+
+sys.path.insert(0, os.path.dirname(os.path.abspath(__file__)))
+from deepgram_stt import apply_gain_int16, pick_device, rms_int16  # noqa: E402
+
+
+def emit(obj: dict) -> None:
+    sys.stdout.write(json.dumps(obj) + "\n")
+    sys.stdout.flush()
+
+
+def diag(msg: str) -> None:
+    sys.stderr.write(f"[whisper_stt] {msg}\n")
+    sys.stderr.flush()
+
+
+# ── Audio / VAD constants ─────────────────────────────────────────────────────
+SAMPLE_RATE = 16000       # whisper.cpp expects 16 kHz mono float32
+CHUNK_SAMPLES = 1600      # 100 ms
+CALIBRATE_S = 0.8         # ambient-noise sampling before listening
+MIN_SPEECH_RMS = 300.0    # floor for the speech threshold (int16 RMS)
+NOISE_FACTOR = 2.5        # threshold = max(floor, ambient * factor)
+SILENCE_END_S = 0.9       # trailing silence that closes an utterance
+MIN_UTTERANCE_S = 0.4     # shorter bursts (clicks, coughs) are dropped
+MAX_UTTERANCE_S = 20.0    # hard cap — flush even if the speaker never pauses
+PRE_ROLL_CHUNKS = 3       # audio kept from before the threshold was crossed
+
+# Whisper hallucinates these on near-silent input; never forward them.
+_NOISE_TRANSCRIPTS = {"", "you", "thank you.", "thanks for watching!", "[blank_audio]", "(silence)"}
+
+
+def load_model(model_name: str, threads: int):
+    from pywhispercpp.model import Model
+
+    models_dir = os.path.join(os.environ.get("FINCEPT_DATA_DIR", "") or os.path.expanduser("~"),
+                              "models", "whisper")
+    os.makedirs(models_dir, exist_ok=True)
+    diag(f"loading model '{model_name}' from {models_dir} (threads={threads})")
+    return Model(model_name, models_dir=models_dir, n_threads=threads,
+                 print_realtime=False, print_progress=False)
+
+
+def transcribe(model, samples, language: str) -> str:
+    """Run one Whisper pass over float32 samples in [-1, 1] at 16 kHz."""
+    kwargs = {} if language in ("", "auto") else {"language": language}
+    segments = model.transcribe(samples, **kwargs)
+    text = " ".join(seg.text.strip() for seg in segments).strip()
+    return "" if text.lower() in _NOISE_TRANSCRIPTS else text
+
+
+def read_wav(path: str):
+    """Decode a PCM WAV file to 16 kHz mono float32 (linear resampling)."""
+    import numpy as np
+
+    with wave.open(path, "rb") as wf:
+        channels = wf.getnchannels()
+        width = wf.getsampwidth()
+        rate = wf.getframerate()
+        raw = wf.readframes(wf.getnframes())
+
+    if width == 2:
+        data = np.frombuffer(raw, dtype="<i2").astype(np.float32) / 32768.0
+    elif width == 4:
+        data = np.frombuffer(raw, dtype="<i4").astype(np.float32) / 2147483648.0
+    elif width == 1:
+        data = (np.frombuffer(raw, dtype=np.uint8).astype(np.float32) - 128.0) / 128.0
+    else:
+        raise ValueError(f"unsupported sample width: {width * 8}-bit")
+
+    if channels > 1:
+        data = data.reshape(-1, channels).mean(axis=1)
+    if rate != SAMPLE_RATE and len(data) > 1:
+        duration = len(data) / rate
+        target = np.linspace(0.0, duration, int(duration * SAMPLE_RATE), endpoint=False)
+        data = np.interp(target, np.arange(len(data)) / rate, data).astype(np.float32)
+    return data
+
+
+def run_file(path: str, model_name: str, language: str, threads: int) -> int:
+    if not os.path.isfile(path):
+        emit({"error": f"Audio file not found: {path}"})
+        return 1
+    try:
+        samples = read_wav(path)
+    except (wave.Error, ValueError, EOFError) as e:
+        emit({"error": f"Could not read WAV file ({e}). Only PCM .wav is supported."})
+        return 1
+    if len(samples) < SAMPLE_RATE * MIN_UTTERANCE_S:
+        emit({"error": "Recording is too short"})
+        return 1
+
+    model = load_model(model_name, threads)
+    started = time.time()
+    text = transcribe(model, samples, language)
+    diag(f"file transcribed in {time.time() - started:.1f}s — {len(samples) / SAMPLE_RATE:.1f}s of audio")
+    emit({"text": text} if text else {"error": "No speech recognised"})
+    return 0
+
+
+def run_live(model_name: str, language: str, threads: int) -> None:
+    import numpy as np
+
+    try:
+        import sounddevice as sd
+    except ImportError as e:
+        emit({"fatal": (f"sounddevice not available in venv-numpy2 ({e}). "
+                        "Open Settings -> Python Env -> Reinstall packages.")})
+        sys.exit(1)
+
+    device_pref = os.environ.get("FINCEPT_STT_DEVICE", "").strip()
+    try:
+        gain = float(os.environ.get("FINCEPT_STT_GAIN", "1.0"))
+    except ValueError:
+        gain = 1.0
+
+    stop_flag = threading.Event()
+
+    def on_signal(_signum, _frame):
+        stop_flag.set()
+    if hasattr(signal, "SIGTERM"):
+        try:
+            signal.signal(signal.SIGTERM, on_signal)
+        except (ValueError, OSError):
+            pass
+
+    emit({"status": "loading"})
+    try:
+        model = load_model(model_name, threads)
+    except Exception as e:
+        emit({"fatal": f"Could not load Whisper model '{model_name}': {e}"})
+        sys.exit(1)
+
+    device_index = pick_device(sd, device_pref)
+    if device_index is None:
+        emit({"fatal": "No input audio device available"})
+        sys.exit(1)
+    try:
+        stream = sd.RawInputStream(samplerate=SAMPLE_RATE, channels=1, dtype="int16",
+                                   blocksize=CHUNK_SAMPLES, device=device_index)
+        stream.start()
+    except Exception as ex:
+        emit({"fatal": f"Could not open microphone stream: {ex}"})
+        sys.exit(1)
+
+    def read_chunk() -> bytes:
+        data, _overflowed = stream.read(CHUNK_SAMPLES)
+        buf = bytes(data)
+        return apply_gain_int16(buf, gain) if gain != 1.0 else buf
+
+    try:
+        emit({"status": "calibrating"})
+        ambient = []
+        for _ in range(int(CALIBRATE_S * SAMPLE_RATE / CHUNK_SAMPLES)):
+            ambient.append(rms_int16(read_chunk()))
+        threshold = max(MIN_SPEECH_RMS, (sum(ambient) / max(len(ambient), 1)) * NOISE_FACTOR)
+        diag(f"speech threshold rms={threshold:.0f}")
+        emit({"status": "listening"})
+
+        chunk_s = CHUNK_SAMPLES / SAMPLE_RATE
+        pre_roll = []
+        utterance = []
+        silence_s = 0.0
+        while not stop_flag.is_set():
+            try:
+                buf = read_chunk()
+            except Exception as ex:
+                emit({"error": f"Audio read failed: {ex}"})
+                break
+            loud = rms_int16(buf) >= threshold
+
+            if not utterance:
+                pre_roll = (pre_roll + [buf])[-PRE_ROLL_CHUNKS:]
+                if loud:
+                    utterance = list(pre_roll)
+                    silence_s = 0.0
+                continue
+
+            utterance.append(buf)
+            silence_s = 0.0 if loud else silence_s + chunk_s
+            length_s = len(utterance) * chunk_s
+            if silence_s < SILENCE_END_S and length_s < MAX_UTTERANCE_S:
+                continue
+
+            speech_s = length_s - silence_s
+            audio = b"".join(utterance)
+            utterance, pre_roll = [], []
+            if speech_s < MIN_UTTERANCE_S:
+                continue
+            samples = np.frombuffer(audio, dtype="<i2").astype(np.float32) / 32768.0
+            started = time.time()
+            try:
+                text = transcribe(model, samples, language)
+            except Exception as ex:
+                emit({"error": f"Whisper transcription failed: {ex}"})
+                continue
+            diag(f"utterance {speech_s:.1f}s transcribed in {time.time() - started:.1f}s")
+            if text:
+                emit({"text": text})
+    except KeyboardInterrupt:
+        pass
+    finally:
+        try:
+            stream.stop()
+            stream.close()
+        except Exception:
+            pass
+        emit({"status": "stopped"})
+
+
+def main() -> None:
+    parser = argparse.ArgumentParser(description="Local Whisper speech-to-text")
+    parser.add_argument("--file", help="transcribe this WAV file once and exit")
+    # One-shot runs go through PythonRunner, which has no per-call environment —
+    # the provider settings arrive as flags instead and override the env vars.
+    parser.add_argument("--model", default=os.environ.get("FINCEPT_WHISPER_MODEL", ""))
+    parser.add_argument("--language", default=os.environ.get("FINCEPT_WHISPER_LANGUAGE", ""))
+    parser.add_argument("--threads", default=os.environ.get("FINCEPT_WHISPER_THREADS", ""))
+    args = parser.parse_args()
+
+    model_name = args.model.strip() or "base.en"
+    language = args.language.strip().lower() or "en"
+    try:
+        threads = max(1, int(args.threads or "4"))
+    except ValueError:
+        threads = 4
+    diag(f"config model={model_name} language={language} threads={threads} file={args.file or '-'}")
+
+    try:
+        import pywhispercpp  # noqa: F401
+    except ImportError as e:
+        emit({"fatal": (f"pywhispercpp not available in venv-numpy2 ({e}). "
+                        "Open Settings -> Python Env -> Reinstall packages.")})
+        sys.exit(1)
+
+    if args.file:
+        try:
+            sys.exit(run_file(args.file, model_name, language, threads))
+        except Exception as e:
+            emit({"error": f"Whisper transcription failed: {e}"})
+            sys.exit(1)
+    run_live(model_name, language, threads)
+
+
+if __name__ == "__main__":
+    main()
@@ -84,6 +84,49 @@ def get_historical(symbol, start_date, end_date, interval='1d'):
     except Exception as e:
         return {"error": str(e), "symbol": symbol}
 
+def get_batch_history(symbols, start_date, end_date):
+    """Split- and dividend-adjusted daily bars for many symbols in one download
+
+    Returns {"success", "data": {symbol: [[date, open, high, low, close, volume, split_factor], ...]},
+    "failed": [{symbol, error}]}; rows oldest first, dates YYYY-MM-DD. split_factor is the product of
+    the splits after that date: close * split_factor is the price as it traded then.
+    """
+    try:
+        import io, contextlib
+        _buf = io.StringIO()
+        with contextlib.redirect_stdout(_buf):
+            frame = yf.download(symbols, start=start_date, end=end_date, interval='1d', auto_adjust=True,
+                                group_by='ticker', threads=True, progress=False, actions=True)
+
+        data, failed = {}, []
+        for symbol in symbols:
+            try:
+                df = frame[symbol] if isinstance(frame.columns, pd.MultiIndex) else frame
+                df = df.dropna(subset=['Close'])
+            except KeyError:
+                df = pd.DataFrame()
+            if df.empty:
+                failed.append({"symbol": symbol, "error": "No data available"})
+                continue
+            if 'Stock Splits' in df.columns:
+                splits = df['Stock Splits'].fillna(0).replace(0, 1.0)
+            else:
+                splits = pd.Series(1.0, index=df.index)
+            after = splits[::-1].cumprod()[::-1].shift(-1).fillna(1.0)
+            rows = []
+            for index, row in df.iterrows():
+                volume = row.get('Volume')
+                rows.append([
+                    index.strftime('%Y-%m-%d'),
+                    float(row['Open']), float(row['High']), float(row['Low']), float(row['Close']),
+                    float(volume) if pd.notna(volume) else 0.0,
+                    float(after.loc[index]),
+                ])
+            data[symbol] = rows
+        return {"success": True, "data": data, "failed": failed}
+    except Exception as e:
+        return {"success": False, "error": str(e)}
+
 def get_historical_price(symbol, target_date):
     """Fetch closing price for a specific date
 
@@ -207,6 +250,112 @@ def get_info(symbol):
     except Exception as e:
         return {"error": str(e), "symbol": symbol}
 
+def _to_date_str(value):
+    """Normalise yfinance calendar/info dates (date, Timestamp, epoch) to YYYY-MM-DD."""
+    if value is None:
+        return None
+    if isinstance(value, (list, tuple)):
+        value = value[0] if value else None
+        if value is None:
+            return None
+    try:
+        if isinstance(value, (int, float)):
+            return datetime.utcfromtimestamp(value).strftime('%Y-%m-%d')
+        return pd.Timestamp(value).strftime('%Y-%m-%d')
+    except Exception:
+        return None
+
+
+def get_dividends(symbols, years=5):
+    """Dividend history, announced (upcoming) ex/pay dates and rate per symbol"""
+    data = {}
+    cutoff = pd.Timestamp.now(tz='UTC') - pd.DateOffset(years=years)
+    for symbol in symbols:
+        try:
+            ticker = yf.Ticker(symbol)
+            history = []
+            divs = ticker.dividends
+            if divs is not None and not divs.empty:
+                for idx, amount in divs.items():
+                    ts = pd.Timestamp(idx)
+                    ts_utc = ts.tz_convert('UTC') if ts.tzinfo else ts.tz_localize('UTC')
+                    if ts_utc >= cutoff and float(amount) > 0:
+                        history.append({"ex_date": ts.strftime('%Y-%m-%d'), "amount": round(float(amount), 6)})
+
+            announced = None
+            try:
+                cal = ticker.calendar
+                if isinstance(cal, dict):
+                    ex_date = _to_date_str(cal.get('Ex-Dividend Date'))
+                    pay_date = _to_date_str(cal.get('Dividend Date'))
+                    if ex_date:
+                        announced = {"ex_date": ex_date, "pay_date": pay_date}
+            except Exception:
+                pass
+
+            info = {}
+            try:
+                info = ticker.info or {}
+            except Exception:
+                pass
+            if announced is None:
+                ex_date = _to_date_str(info.get('exDividendDate'))
+                if ex_date and ex_date >= datetime.now().strftime('%Y-%m-%d'):
+                    announced = {"ex_date": ex_date, "pay_date": None}
+            if announced is not None:
+                announced["amount"] = info.get('lastDividendValue')
+
+            data[symbol] = {
+                "history": history,
+                "announced": announced,
+                "dividend_rate": info.get('dividendRate'),
+                "trailing_rate": info.get('trailingAnnualDividendRate'),
+                "currency": info.get('currency'),
+                "price": info.get('currentPrice') or info.get('regularMarketPrice'),
+            }
+        except Exception as e:
+            data[symbol] = {"error": str(e)}
+    return {"data": data, "timestamp": int(datetime.now().timestamp())}
+
+def get_earnings_dates(symbols):
+    """Next scheduled earnings date (or window) and consensus estimates per symbol"""
+    data = {}
+    today = datetime.now().strftime('%Y-%m-%d')
+    for symbol in symbols:
+        try:
+            ticker = yf.Ticker(symbol)
+            entry = {"earnings_date": None, "earnings_date_end": None}
+            try:
+                cal = ticker.calendar
+                if isinstance(cal, dict):
+                    dates = cal.get('Earnings Date') or []
+                    if not isinstance(dates, (list, tuple)):
+                        dates = [dates]
+                    dates = sorted(d for d in (_to_date_str(v) for v in dates) if d)
+                    if dates:
+                        entry["earnings_date"] = dates[0]
+                        entry["earnings_date_end"] = dates[-1] if len(dates) > 1 else None
+                    for key, field in (('Earnings Average', 'eps_estimate'), ('Revenue Average', 'revenue_estimate')):
+                        value = cal.get(key)
+                        # NaN would not survive json.dumps as valid JSON
+                        entry[field] = float(value) if isinstance(value, (int, float)) and value == value else None
+            except Exception:
+                pass
+            if entry["earnings_date"] is None:
+                try:
+                    info = ticker.info or {}
+                    start = _to_date_str(info.get('earningsTimestampStart') or info.get('earningsTimestamp'))
+                    if start and start >= today:
+                        entry["earnings_date"] = start
+                        end = _to_date_str(info.get('earningsTimestampEnd'))
+                        entry["earnings_date_end"] = end if end and end != start else None
+                except Exception:
+                    pass
+            data[symbol] = entry
+        except Exception as e:
+            data[symbol] = {"error": str(e)}
+    return {"data": data, "timestamp": int(datetime.now().timestamp())}
+
 def get_financials(symbol):
     """Fetch financial statements for a symbol"""
     try:
@@ -1242,6 +1391,18 @@ def main(args=None):
                 except json.JSONDecodeError as e:
                     result = {"error": f"batch_all: invalid JSON payload: {e}"}
 
+    elif command == "dividends":
+        if len(args) < 2:
+            result = {"error": "Usage: python yfinance_data.py dividends <symbol1> <symbol2> ..."}
+        else:
+            result = get_dividends(args[1:])
+
+    elif command == "earnings_dates":
+        if len(args) < 2:
+            result = {"error": "Usage: python yfinance_data.py earnings_dates <symbol1> <symbol2> ..."}
+        else:
+            result = get_earnings_dates(args[1:])
+
     elif command == "resolve_symbol":
         if len(args) < 2:
             result = {"error": "Usage: python yfinance_data.py resolve_symbol <symbol>"}
@@ -1249,6 +1410,12 @@ def main(args=None):
             symbol = args[1]
             result = resolve_symbol(symbol)
 
+    elif command == "batch_history":
+        if len(args) < 4:
+            result = {"error": "Usage: python yfinance_data.py batch_history <start_date> <end_date> <symbol1> ..."}
+        else:
+            result = get_batch_history(args[3:], args[1], args[2])
+
     elif command == "historical_price":
         # Get closing price for a specific date
         if len(args) < 3:
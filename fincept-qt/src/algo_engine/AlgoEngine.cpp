@@ -1,14 +1,19 @@
 // src/algo_engine/AlgoEngine.cpp
 #include "algo_engine/AlgoEngine.h"
 
+#include "algo_engine/ConditionEvaluator.h"
 #include "algo_engine/fno/FnoExecution.h"
 #include "core/logging/Logger.h"
 #include "datahub/DataHub.h"
+#include "services/audit/AuditTrail.h"
+#include "storage/repositories/SettingsRepository.h"
 #include "storage/sqlite/Database.h"
 #include "trading/AccountManager.h"
+#include "trading/BrokerRegistry.h"
 #include "trading/PaperTrading.h"
 #include "trading/UnifiedTrading.h"
 
+#include <QCoreApplication>
 #include <QDateTime>
 #include <QJsonArray>
 #include <QJsonDocument>
@@ -19,8 +24,149 @@
 #include <QUuid>
 #include <QtConcurrent>
 
+#include <algorithm>
+#include <cmath>
+
 namespace fincept::algo {
 
+namespace {
+
+// "1" once the app quits normally, reset to "0" at startup: finding "0" (or
+// nothing) on the next start means the last run died, possibly mid-order.
+constexpr const char* kAeCleanExitKey = "algo_engine.clean_exit";
+constexpr double kAeQtyEpsilon = 1e-6;
+// Reason the kill switch was engaged with; empty while it is reset. Survives a
+// restart so a crash or quit can't silently re-arm trading.
+constexpr const char* kAeKillSwitchKey = "algo_engine.kill_switch";
+
+bool ae_take_clean_exit() {
+    auto& settings = SettingsRepository::instance();
+    const auto r = settings.get(kAeCleanExitKey);
+    const bool clean = r.is_ok() && r.value() == QLatin1String("1");
+    settings.set(kAeCleanExitKey, QStringLiteral("0"), QStringLiteral("algo"));
+    return clean;
+}
+
+services::algo::AlgoDeployment ae_deployment_from_row(const QSqlQuery& q) {
+    services::algo::AlgoDeployment d;
+    d.id = q.value("id").toString();
+    d.strategy_id = q.value("strategy_id").toString();
+    d.strategy_name = q.value("strategy_name").toString();
+    d.strategy_kind = q.value("strategy_kind").toString();
+    d.symbol = q.value("symbol").toString();
+    d.exchange = q.value("exchange").toString();
+    d.product_type = q.value("product_type").toString();
+    d.mode = q.value("mode").toString();
+    d.entry_side = q.value("entry_side").toString();
+    d.backend = q.value("backend").toString();
+    d.broker_id = q.value("broker_id").toString();
+    d.broker_account_id = q.value("broker_account_id").toString();
+    d.paper_portfolio_id = q.value("paper_portfolio_id").toString();
+    d.timeframe = q.value("timeframe").toString();
+    d.quantity = q.value("quantity").toDouble();
+    d.max_order_value = q.value("max_order_value").toDouble();
+    d.max_daily_loss = q.value("max_daily_loss").toDouble();
+    d.max_position_value = q.value("max_position_value").toDouble();
+    d.max_orders_per_minute = q.value("max_orders_per_minute").toInt();
+    // F&O: needed so the resumed runner takes the multi-leg path and can
+    // reattach its open basket (resolved_legs_json read in restore_state_from_db).
+    d.instrument_type = q.value("instrument_type").toString();
+    d.underlying = q.value("underlying").toString();
+    d.resolved_expiry = q.value("resolved_expiry").toString();
+    return d;
+}
+
+// Live orders are journalled in algo_order_signals as "dispatched" before they
+// go to the broker and settled when the response comes back, so a row still
+// "dispatched" at startup is an order whose outcome the engine never saw.
+QString ae_journal_order(const AlgoOrderSignal& signal, const QString& symbol, const QString& side, double qty,
+                         double price) {
+    const QString id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    QSqlQuery q(fincept::Database::instance().connection());
+    q.prepare(QStringLiteral("INSERT INTO algo_order_signals (id, deployment_id, symbol, exchange, product_type, "
+                             "side, quantity, order_type, price, status, attempt_count, dispatched_at) "
+                             "VALUES (?,?,?,?,?,?,?,?,?,'dispatched',1,datetime('now'))"));
+    q.addBindValue(id);
+    q.addBindValue(signal.deployment_id);
+    q.addBindValue(symbol);
+    q.addBindValue(signal.exchange);
+    q.addBindValue(signal.product_type);
+    q.addBindValue(side);
+    q.addBindValue(qty);
+    q.addBindValue(signal.order_type);
+    q.addBindValue(price);
+    if (!q.exec())
+        LOG_ERROR("AlgoEngine", QString("Deployment %1: order journal insert failed: %2")
+                                    .arg(signal.deployment_id, q.lastError().text()));
+    return id;
+}
+
+// The engine books an accepted live order as filled (see execute_order), so
+// the journal row follows suit.
+void ae_settle_order(const QString& journal_id, bool ok, const QString& broker_order_id, const QString& error) {
+    QSqlQuery q(fincept::Database::instance().connection());
+    q.prepare(QStringLiteral("UPDATE algo_order_signals SET status = ?, broker_order_id = ?, error = ?, "
+                             "updated_at = datetime('now') WHERE id = ?"));
+    q.addBindValue(ok ? QStringLiteral("filled") : QStringLiteral("failed"));
+    q.addBindValue(broker_order_id);
+    q.addBindValue(error);
+    q.addBindValue(journal_id);
+    q.exec();
+}
+
+int ae_inflight_orders(const QString& deployment_id) {
+    QSqlQuery q(fincept::Database::instance().connection());
+    q.prepare(QStringLiteral("SELECT COUNT(*) FROM algo_order_signals WHERE deployment_id = ? "
+                             "AND status IN ('pending','dispatched')"));
+    q.addBindValue(deployment_id);
+    return q.exec() && q.next() ? q.value(0).toInt() : 0;
+}
+
+// Same set the broker base class's cancel_all_orders treats as still working.
+bool ae_order_working(const QString& status) {
+    const QString s = status.toLower();
+    return s == "open" || s == "pending" || s == "new" || s == "trigger pending" || s == "trigger_pending" ||
+           s == "ordered" || s == "transit" || s == "accepted" || s == "partially_filled" || s == "working";
+}
+
+// One account's positions and order book, fetched once per recovery report.
+struct AeBrokerBook {
+    QString error;
+    QVector<fincept::trading::BrokerPosition> positions;
+    QVector<fincept::trading::BrokerOrderInfo> orders;
+};
+
+AeBrokerBook ae_fetch_broker_book(const QString& account_id) {
+    AeBrokerBook book;
+    auto& accounts = fincept::trading::AccountManager::instance();
+    const auto account = accounts.get_account(account_id);
+    if (account.account_id.isEmpty()) {
+        book.error = "Account not found: " + account_id;
+        return book;
+    }
+    auto* broker = fincept::trading::BrokerRegistry::instance().get(account.broker_id);
+    if (!broker) {
+        book.error = "Broker not found: " + account.broker_id;
+        return book;
+    }
+    const auto creds = accounts.load_credentials(account_id);
+    const auto positions = broker->get_positions(creds);
+    if (!positions.success) {
+        book.error = "Positions unavailable: " + positions.error;
+        return book;
+    }
+    const auto orders = broker->get_orders(creds);
+    if (!orders.success) {
+        book.error = "Order book unavailable: " + orders.error;
+        return book;
+    }
+    book.positions = positions.data.value_or(QVector<fincept::trading::BrokerPosition>{});
+    book.orders = orders.data.value_or(QVector<fincept::trading::BrokerOrderInfo>{});
+    return book;
+}
+
+} // namespace
+
 AlgoEngine& AlgoEngine::instance() {
     static AlgoEngine s;
     return s;
@@ -40,6 +186,21 @@ AlgoEngine::AlgoEngine() {
     engine_thread_.setObjectName(QStringLiteral("AlgoEngineThread"));
     engine_thread_.start();
     moveToThread(&engine_thread_);
+
+    if (const auto r = SettingsRepository::instance().get(kAeKillSwitchKey); r.is_ok() && !r.value().isEmpty()) {
+        kill_switch_ = true;
+        LOG_WARN("AlgoEngine", "Kill switch still engaged from a previous session (" + r.value() +
+                                   ") — reset it before deploying");
+    }
+
+    // Direct: `this` lives on the engine thread, which may never run another
+    // event once the app starts quitting.
+    if (auto* app = QCoreApplication::instance()) {
+        connect(
+            app, &QCoreApplication::aboutToQuit, this,
+            []() { SettingsRepository::instance().set(kAeCleanExitKey, QStringLiteral("1"), QStringLiteral("algo")); },
+            Qt::DirectConnection);
+    }
 }
 
 AlgoEngine::~AlgoEngine() {
@@ -52,6 +213,16 @@ AlgoEngine::~AlgoEngine() {
 
 void AlgoEngine::start_deployment(const services::algo::AlgoDeployment& deployment,
                                   const services::algo::AlgoStrategy& strategy) {
+    if (kill_switch_.load()) {
+        emit error_occurred(deployment.id, QStringLiteral("Kill switch is engaged — reset it before deploying"));
+        return;
+    }
+    for (const auto* rules : {&strategy.entry_conditions, &strategy.exit_conditions}) {
+        if (const QString over = ConditionEvaluator::check_limits(*rules); !over.isEmpty()) {
+            emit error_occurred(deployment.id, QString("Strategy rules exceed the evaluation limits: %1").arg(over));
+            return;
+        }
+    }
     QMutexLocker lock(&mutex_);
     if (runners_.contains(deployment.id)) {
         emit error_occurred(deployment.id, QStringLiteral("Deployment already running"));
@@ -66,6 +237,7 @@ void AlgoEngine::start_deployment(const services::algo::AlgoDeployment& deployme
     connect(runner, &DeploymentRunner::metrics_updated, this, &AlgoEngine::metrics_updated);
     connect(runner, &DeploymentRunner::error_occurred, this, &AlgoEngine::error_occurred);
     connect(runner, &DeploymentRunner::live_update, this, &AlgoEngine::live_update);
+    connect(runner, &DeploymentRunner::strategy_reloaded, this, &AlgoEngine::strategy_reloaded);
     connect(runner, &DeploymentRunner::order_requested, this, &AlgoEngine::on_order_requested);
     connect(runner, &DeploymentRunner::status_changed, this, [this](const QString& id, const QString& status) {
         if (status == "stopped" || status == "error") {
@@ -129,6 +301,35 @@ void AlgoEngine::resume_deployment(const QString& deployment_id) {
     QMetaObject::invokeMethod(runner, &DeploymentRunner::resume, Qt::QueuedConnection);
 }
 
+void AlgoEngine::reload_strategy(const QString& deployment_id) {
+    // The row, not the runner's copy: an earlier reload may have pointed it at another strategy.
+    QString strategy_id;
+    auto db = fincept::Database::instance().connection();
+    QSqlQuery q(db);
+    q.prepare(QStringLiteral("SELECT strategy_id FROM algo_deployments WHERE id = ?"));
+    q.addBindValue(deployment_id);
+    if (q.exec() && q.next())
+        strategy_id = q.value(0).toString();
+    const auto strategy = load_strategy(strategy_id);
+    if (strategy.id.isEmpty()) {
+        emit strategy_reloaded(deployment_id, false, QString("Strategy %1 not found").arg(strategy_id));
+        return;
+    }
+    reload_strategy(deployment_id, strategy);
+}
+
+void AlgoEngine::reload_strategy(const QString& deployment_id, const services::algo::AlgoStrategy& strategy) {
+    QMutexLocker lock(&mutex_);
+    auto* runner = runners_.value(deployment_id, nullptr);
+    lock.unlock();
+    if (!runner) {
+        emit strategy_reloaded(deployment_id, false, QStringLiteral("Deployment is not running"));
+        return;
+    }
+    QMetaObject::invokeMethod(runner, [runner, strategy]() { runner->reload_strategy(strategy); },
+                              Qt::QueuedConnection);
+}
+
 void AlgoEngine::stop_all() {
     QMutexLocker lock(&mutex_);
     auto ids = runners_.keys();
@@ -137,6 +338,76 @@ void AlgoEngine::stop_all() {
         stop_deployment(id);
 }
 
+// ── Kill switch ──────────────────────────────────────────────────────────────
+
+void AlgoEngine::kill_switch(const QString& reason) {
+    const QString why = reason.trimmed().isEmpty() ? QStringLiteral("manual") : reason.trimmed();
+    kill_switch_ = true;
+    SettingsRepository::instance().set(kAeKillSwitchKey, why, QStringLiteral("algo"));
+
+    QMutexLocker lock(&mutex_);
+    QStringList live_accounts;
+    for (auto* runner : std::as_const(runners_)) {
+        runner->request_halt(); // no new entries from here on
+        const auto& d = runner->deployment();
+        if (d.mode == QLatin1String("live") && !d.broker_account_id.isEmpty() &&
+            !live_accounts.contains(d.broker_account_id))
+            live_accounts << d.broker_account_id;
+    }
+    const int count = runners_.size();
+    lock.unlock();
+
+    LOG_WARN("AlgoEngine", QString("KILL SWITCH engaged (%1): %2 deployment(s), %3 live account(s)")
+                               .arg(why)
+                               .arg(count)
+                               .arg(live_accounts.size()));
+    emit kill_switch_changed(true, why);
+
+    // Cancel first so a resting order can't fill after the flatten; the
+    // broker calls block, so they run off the engine thread.
+    QPointer<AlgoEngine> self = this;
+    (void)QtConcurrent::run([self, live_accounts, why]() {
+        QStringList failures;
+        for (const auto& account_id : live_accounts) {
+            const auto r = fincept::trading::UnifiedTrading::instance().cancel_all_orders(account_id);
+            if (!r.success)
+                failures << QString("%1: %2").arg(account_id, r.error);
+            else if (r.data && !r.data->failed.isEmpty())
+                failures << QString("%1: %2 order(s) not cancelled").arg(account_id).arg(r.data->failed.size());
+            else
+                LOG_INFO("AlgoEngine", QString("Kill switch: cancelled %1 open order(s) on %2")
+                                           .arg(r.data ? r.data->canceled_order_ids.size() : 0)
+                                           .arg(account_id));
+        }
+        if (!self)
+            return;
+        QMetaObject::invokeMethod(
+            self,
+            [self, why, failures]() {
+                if (!self)
+                    return;
+                for (const auto& f : failures) {
+                    LOG_ERROR("AlgoEngine", "Kill switch: cancel failed — " + f);
+                    emit self->error_occurred(QString(), QStringLiteral("Kill switch: cancel failed — ") + f);
+                }
+                QMutexLocker lock(&self->mutex_);
+                const auto runners = self->runners_.values();
+                lock.unlock();
+                for (auto* runner : runners)
+                    QMetaObject::invokeMethod(runner, [runner, why]() { runner->kill(why); }, Qt::QueuedConnection);
+            },
+            Qt::QueuedConnection);
+    });
+}
+
+void AlgoEngine::reset_kill_switch() {
+    if (!kill_switch_.exchange(false))
+        return;
+    SettingsRepository::instance().set(kAeKillSwitchKey, QString(), QStringLiteral("algo"));
+    LOG_INFO("AlgoEngine", "Kill switch reset — deployments may start again");
+    emit kill_switch_changed(false, QString());
+}
+
 bool AlgoEngine::is_running(const QString& deployment_id) const {
     QMutexLocker lock(&mutex_);
     auto* runner = runners_.value(deployment_id, nullptr);
@@ -175,12 +446,15 @@ void AlgoEngine::execute_order(const AlgoOrderSignal& signal) {
     const double submitted_price = signal.price;
     const double qty = signal.quantity;
 
-    // ── Paper: never touch a real broker ────────────────────────────────────────
+    // ── Paper / shadow: never touch a real broker ───────────────────────────────
     // Simulate an immediate fill at the signal's reference price. This is the hard
     // safety gate: even though a paper deployment carries a real broker account (its
-    // data source), its orders are never sent to that broker.
+    // data source), its orders are never sent to that broker. Shadow fills carry a
+    // "shadow-" id so their trade rows read as hypothetical.
     if (signal.mode != QStringLiteral("live")) {
-        const QString sim_id = QStringLiteral("paper-") + QUuid::createUuid().toString(QUuid::WithoutBraces);
+        const bool shadow = signal.mode == QLatin1String("shadow");
+        const QString sim_id = (shadow ? QStringLiteral("shadow-") : QStringLiteral("paper-")) +
+                               QUuid::createUuid().toString(QUuid::WithoutBraces);
         QMetaObject::invokeMethod(
             this,
             [self, dep_id, sim_id, submitted_price, qty]() {
@@ -194,8 +468,8 @@ void AlgoEngine::execute_order(const AlgoOrderSignal& signal) {
                 runner->on_order_filled(sim_id, submitted_price, qty);
             },
             Qt::QueuedConnection);
-        LOG_INFO("AlgoEngine", QString("Deployment %1: PAPER simulated fill %2 %3 @ %4")
-                                   .arg(dep_id, signal.side)
+        LOG_INFO("AlgoEngine", QString("Deployment %1: %2 simulated fill %3 %4 @ %5")
+                                   .arg(dep_id, QLatin1String(shadow ? "SHADOW" : "PAPER"), signal.side)
                                    .arg(qty)
                                    .arg(submitted_price));
         return;
@@ -215,17 +489,20 @@ void AlgoEngine::execute_order(const AlgoOrderSignal& signal) {
         order.product_type = trading::ProductType::Intraday;
 
     const QString account_id = signal.account_id;
+    const QString journal_id = ae_journal_order(signal, signal.symbol, signal.side, qty, submitted_price);
 
-    (void)QtConcurrent::run([self, dep_id, account_id, order, submitted_price]() {
+    (void)QtConcurrent::run([self, dep_id, account_id, order, submitted_price, journal_id]() {
         auto response = fincept::trading::UnifiedTrading::instance().place_order(account_id, order);
 
         if (!self)
             return;
         QMetaObject::invokeMethod(
             self,
-            [self, dep_id, response, order, submitted_price]() {
+            [self, dep_id, response, order, submitted_price, journal_id]() {
                 if (!self)
                     return;
+                ae_settle_order(journal_id, response.success, response.order_id,
+                                response.success ? QString() : response.message);
                 QMutexLocker lock(&self->mutex_);
                 auto* runner = self->runners_.value(dep_id, nullptr);
                 if (!runner)
@@ -271,6 +548,30 @@ void AlgoEngine::execute_basket(const AlgoOrderSignal& signal) {
     const QString dep_id = signal.deployment_id;
     const QVector<AlgoOrderLeg> legs = signal.legs;
 
+    // ── Shadow: hypothetical fill of every leg, no portfolio, no broker ──────────
+    // Forward testing records the basket exactly as paper would — one trade row
+    // per leg at its reference price — without touching the paper portfolio.
+    if (signal.mode == QLatin1String("shadow")) {
+        QMetaObject::invokeMethod(
+            this,
+            [self, dep_id, legs]() {
+                if (!self)
+                    return;
+                QMutexLocker lock(&self->mutex_);
+                auto* runner = self->runners_.value(dep_id, nullptr);
+                if (!runner)
+                    return;
+                lock.unlock();
+                for (int i = 0; i < legs.size(); ++i)
+                    runner->on_leg_filled(i, legs[i], legs[i].price, legs[i].quantity);
+            },
+            Qt::QueuedConnection);
+        LOG_INFO("AlgoEngine", QString("Deployment %1: SHADOW basket %2 legs (hypothetical fills)")
+                                   .arg(dep_id)
+                                   .arg(legs.size()));
+        return;
+    }
+
     // ── Paper: route every leg to the paper portfolio, NEVER the broker ──────────
     // Hard safety gate: a paper deployment carries a real broker account as its
     // data source, but its basket is simulated against pt_place_order. We never
@@ -359,16 +660,27 @@ void AlgoEngine::execute_basket(const AlgoOrderSignal& signal) {
     const QString account_id = signal.account_id;
     const fincept::trading::BasketOrderRequest basket =
         fincept::algo::fno::build_basket_request(legs, signal.product_type);
+    QStringList journal_ids;
+    for (const auto& leg : legs)
+        journal_ids << ae_journal_order(signal, leg.symbol, leg.side, leg.quantity, leg.price);
 
     fincept::trading::UnifiedTrading::instance().place_basket_orders(
-        account_id, basket, [self, dep_id, legs, account_id](const fincept::trading::BasketOrderResult& res) {
+        account_id, basket,
+        [self, dep_id, legs, account_id, journal_ids](const fincept::trading::BasketOrderResult& res) {
             if (!self)
                 return;
             QMetaObject::invokeMethod(
                 self,
-                [self, dep_id, legs, account_id, res]() {
+                [self, dep_id, legs, account_id, journal_ids, res]() {
                     if (!self)
                         return;
+                    for (int i = 0; i < legs.size(); ++i) {
+                        auto r = std::find_if(res.results.cbegin(), res.results.cend(),
+                                              [&](const auto& o) { return o.symbol == legs[i].symbol; });
+                        const bool ok = r != res.results.cend() && r->success;
+                        ae_settle_order(journal_ids.value(i), ok, ok ? r->order_id : QString(),
+                                        r == res.results.cend() ? QStringLiteral("no broker result") : r->error);
+                    }
                     QMutexLocker lock(&self->mutex_);
                     auto* runner = self->runners_.value(dep_id, nullptr);
                     if (!runner)
@@ -433,9 +745,9 @@ void AlgoEngine::persist_deployment(const services::algo::AlgoDeployment& d) {
     q.prepare(QStringLiteral("INSERT OR REPLACE INTO algo_deployments "
                              "(id, strategy_id, strategy_name, strategy_kind, symbol, exchange, product_type, "
                              " mode, entry_side, backend, broker_id, broker_account_id, paper_portfolio_id, "
-                             " timeframe, quantity, max_order_value, max_daily_loss, "
-                             " instrument_type, underlying, status, created_at, updated_at) "
-                             "VALUES (?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?, datetime('now'), datetime('now'))"));
+                             " timeframe, quantity, max_order_value, max_daily_loss, max_position_value, "
+                             " max_orders_per_minute, instrument_type, underlying, status, created_at, updated_at) "
+                             "VALUES (?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?, datetime('now'), datetime('now'))"));
     q.addBindValue(d.id);
     q.addBindValue(d.strategy_id);
     q.addBindValue(d.strategy_name);
@@ -453,6 +765,8 @@ void AlgoEngine::persist_deployment(const services::algo::AlgoDeployment& d) {
     q.addBindValue(d.quantity);
     q.addBindValue(d.max_order_value);
     q.addBindValue(d.max_daily_loss);
+    q.addBindValue(d.max_position_value);
+    q.addBindValue(d.max_orders_per_minute);
     q.addBindValue(d.instrument_type); // F&O: option|future — drives the multi-leg path on resume
     q.addBindValue(d.underlying);      // F&O underlying for chain lookup on resume
     q.addBindValue(QStringLiteral("starting"));
@@ -493,6 +807,8 @@ void AlgoEngine::list_deployments() {
             d.quantity = q.value("quantity").toDouble();
             d.max_order_value = q.value("max_order_value").toDouble();
             d.max_daily_loss = q.value("max_daily_loss").toDouble();
+            d.max_position_value = q.value("max_position_value").toDouble();
+            d.max_orders_per_minute = q.value("max_orders_per_minute").toInt();
             d.created_at = q.value("created_at").toString();
             deployments.append(d);
         }
@@ -536,38 +852,48 @@ void AlgoEngine::recover_orphaned() {
     // On restart, deployments that were active (or stuck in error from the prior
     // no-data bug) should resume rather than silently die — that's what users
     // expect of a deployed algo. Reload each one + its strategy and restart it.
+    //
+    // Live ones are the exception after a crash: an order sent just before the
+    // app died may have filled at the broker without being recorded (or the
+    // reverse), so resuming on the recorded position can double an entry or
+    // orphan one. They wait in "recovering" until resolve_recovery().
+    const bool clean_exit = ae_take_clean_exit();
     auto db = fincept::Database::instance().connection();
     QSqlQuery q(db);
-    q.exec(QStringLiteral("SELECT * FROM algo_deployments WHERE status IN ('running','starting','error','crashed')"));
+    q.exec(QStringLiteral("SELECT * FROM algo_deployments "
+                          "WHERE status IN ('running','starting','error','crashed','recovering')"));
 
     QVector<services::algo::AlgoDeployment> to_resume;
+    QStringList held, newly_held;
     while (q.next()) {
-        services::algo::AlgoDeployment d;
-        d.id = q.value("id").toString();
-        d.strategy_id = q.value("strategy_id").toString();
-        d.strategy_name = q.value("strategy_name").toString();
-        d.strategy_kind = q.value("strategy_kind").toString();
-        d.symbol = q.value("symbol").toString();
-        d.exchange = q.value("exchange").toString();
-        d.product_type = q.value("product_type").toString();
-        d.mode = q.value("mode").toString();
-        d.entry_side = q.value("entry_side").toString();
-        d.backend = q.value("backend").toString();
-        d.broker_id = q.value("broker_id").toString();
-        d.broker_account_id = q.value("broker_account_id").toString();
-        d.paper_portfolio_id = q.value("paper_portfolio_id").toString();
-        d.timeframe = q.value("timeframe").toString();
-        d.quantity = q.value("quantity").toDouble();
-        d.max_order_value = q.value("max_order_value").toDouble();
-        d.max_daily_loss = q.value("max_daily_loss").toDouble();
-        // F&O: needed so the resumed runner takes the multi-leg path and can
-        // reattach its open basket (resolved_legs_json read in restore_state_from_db).
-        d.instrument_type = q.value("instrument_type").toString();
-        d.underlying = q.value("underlying").toString();
-        d.resolved_expiry = q.value("resolved_expiry").toString();
+        const auto d = ae_deployment_from_row(q);
+        if (q.value("status").toString() == QLatin1String("recovering")) {
+            held << d.id; // still unresolved from an earlier start
+            continue;
+        }
+        if (d.mode == QLatin1String("live") && (!clean_exit || ae_inflight_orders(d.id) > 0)) {
+            held << d.id;
+            newly_held << d.id;
+            continue;
+        }
         to_resume.append(d);
     }
 
+    for (const auto& id : std::as_const(newly_held)) {
+        const QString reason = clean_exit ? QStringLiteral("An order was still in flight at exit — awaiting recovery")
+                                          : QStringLiteral("App did not shut down cleanly — awaiting recovery");
+        QSqlQuery u(db);
+        u.prepare(QStringLiteral("UPDATE algo_deployments SET status='recovering', error_message=?, "
+                                 "updated_at=datetime('now') WHERE id=?"));
+        u.addBindValue(reason);
+        u.addBindValue(id);
+        u.exec();
+        LOG_WARN("AlgoEngine", QString("Live deployment %1 held for recovery: %2").arg(id, reason));
+        emit deployment_crashed(id, reason);
+    }
+    if (!held.isEmpty())
+        emit recovery_required(held);
+
     for (const auto& d : to_resume) {
         auto strat = load_strategy(d.strategy_id);
         if (strat.id.isEmpty()) {
@@ -586,6 +912,278 @@ void AlgoEngine::recover_orphaned() {
     }
 }
 
+// ── Crash recovery ───────────────────────────────────────────────────────────
+
+QStringList AlgoEngine::recovering_deployment_ids() const {
+    QStringList ids;
+    QSqlQuery q(fincept::Database::instance().connection());
+    if (q.exec(QStringLiteral("SELECT id FROM algo_deployments WHERE status = 'recovering' ORDER BY created_at"))) {
+        while (q.next())
+            ids << q.value(0).toString();
+    }
+    return ids;
+}
+
+QVector<AlgoRecoveryItem> AlgoEngine::recovery_report(const QString& deployment_id) const {
+    QString sql = QStringLiteral(
+        "SELECT d.id, d.strategy_name, d.symbol, d.underlying, d.exchange, d.product_type, d.broker_account_id, "
+        "d.instrument_type, d.resolved_legs_json, m.current_position_qty, m.current_position_side, "
+        "m.current_position_entry FROM algo_deployments d LEFT JOIN algo_metrics m ON m.deployment_id = d.id "
+        "WHERE d.status = 'recovering'");
+    if (!deployment_id.isEmpty())
+        sql += QStringLiteral(" AND d.id = ?");
+    QSqlQuery q(fincept::Database::instance().connection());
+    q.prepare(sql + QStringLiteral(" ORDER BY d.created_at"));
+    if (!deployment_id.isEmpty())
+        q.addBindValue(deployment_id);
+    if (!q.exec()) {
+        LOG_ERROR("AlgoEngine", "recovery_report: " + q.lastError().text());
+        return {};
+    }
+
+    QVector<AlgoRecoveryItem> items;
+    while (q.next()) {
+        AlgoRecoveryItem it;
+        it.deployment_id = q.value("id").toString();
+        it.strategy_name = q.value("strategy_name").toString();
+        it.symbol = q.value("symbol").toString();
+        if (it.symbol.isEmpty())
+            it.symbol = q.value("underlying").toString();
+        it.exchange = q.value("exchange").toString();
+        it.product_type = q.value("product_type").toString();
+        it.account_id = q.value("broker_account_id").toString();
+        it.basket = q.value("instrument_type").toString() != QLatin1String("equity");
+        it.recorded_side = q.value("current_position_side").toString();
+        if (it.recorded_side.isEmpty())
+            it.recorded_side = QStringLiteral("NONE");
+        if (it.basket) {
+            const auto doc = QJsonDocument::fromJson(q.value("resolved_legs_json").toString().toUtf8());
+            for (const auto& leg : fincept::algo::fno::leg_positions_from_json(doc.array()))
+                it.recorded_legs[leg.symbol] += leg.side_sign * leg.quantity;
+        } else {
+            it.recorded_qty = q.value("current_position_qty").toDouble();
+            it.recorded_entry = q.value("current_position_entry").toDouble();
+        }
+        items.append(it);
+    }
+
+    QHash<QString, AeBrokerBook> books;
+    for (auto& it : items) {
+        it.inflight_orders = ae_inflight_orders(it.deployment_id);
+        if (!books.contains(it.account_id))
+            books.insert(it.account_id, ae_fetch_broker_book(it.account_id));
+        const AeBrokerBook& book = books[it.account_id];
+        if (!book.error.isEmpty()) {
+            it.error = book.error;
+            it.state = QStringLiteral("unverified");
+            continue;
+        }
+        it.broker_checked = true;
+
+        if (it.basket) {
+            for (auto leg = it.recorded_legs.cbegin(); leg != it.recorded_legs.cend(); ++leg)
+                it.broker_legs[leg.key()] = 0;
+            for (const auto& p : book.positions) {
+                if (it.broker_legs.contains(p.symbol))
+                    it.broker_legs[p.symbol] += p.quantity;
+            }
+            for (auto leg = it.recorded_legs.cbegin(); leg != it.recorded_legs.cend(); ++leg) {
+                const double held_qty = it.broker_legs.value(leg.key());
+                if (std::abs(held_qty - leg.value()) > kAeQtyEpsilon)
+                    it.notes << QString("Leg %1: broker holds %2, deployment recorded %3")
+                                    .arg(leg.key())
+                                    .arg(held_qty)
+                                    .arg(leg.value());
+            }
+        } else {
+            for (const auto& p : book.positions) {
+                if (p.symbol != it.symbol || (!it.exchange.isEmpty() && !p.exchange.isEmpty() &&
+                                              p.exchange.compare(it.exchange, Qt::CaseInsensitive) != 0))
+                    continue;
+                it.broker_qty += p.quantity;
+                if (p.avg_price > 0)
+                    it.broker_avg_price = p.avg_price;
+            }
+            const double expected = it.recorded_side == QLatin1String("LONG")    ? it.recorded_qty
+                                    : it.recorded_side == QLatin1String("SHORT") ? -it.recorded_qty
+                                                                                 : 0.0;
+            if (std::abs(it.broker_qty - expected) > kAeQtyEpsilon)
+                it.notes << QString("Broker holds %1 %2, deployment recorded %3")
+                                .arg(it.broker_qty)
+                                .arg(it.symbol)
+                                .arg(expected);
+        }
+
+        for (const auto& o : book.orders) {
+            if (ae_order_working(o.status) && (o.symbol == it.symbol || it.recorded_legs.contains(o.symbol)))
+                it.open_order_ids << o.order_id;
+        }
+        if (!it.open_order_ids.isEmpty())
+            it.notes << QString("%1 working order(s) at the broker").arg(it.open_order_ids.size());
+        it.state = it.notes.isEmpty() ? QStringLiteral("in_sync") : QStringLiteral("mismatch");
+    }
+    return items;
+}
+
+void AlgoEngine::resolve_recovery(const QString& deployment_id, const QString& action) {
+    if (action != QLatin1String("resume") && action != QLatin1String("flatten") && action != QLatin1String("stop")) {
+        emit recovery_resolved(deployment_id, action, false, QStringLiteral("Action must be resume, flatten or stop"));
+        return;
+    }
+    if (!recovering_deployment_ids().contains(deployment_id)) {
+        emit recovery_resolved(deployment_id, action, false, QStringLiteral("Deployment is not awaiting recovery"));
+        return;
+    }
+    if (action == QLatin1String("stop")) {
+        AlgoRecoveryItem item;
+        item.deployment_id = deployment_id;
+        finish_recovery(item, action, {});
+        return;
+    }
+
+    // Reconcile and act on the broker off the calling thread; the rows and the
+    // restart are handled back on the engine thread in finish_recovery().
+    QPointer<AlgoEngine> self = this;
+    (void)QtConcurrent::run([self, deployment_id, action]() {
+        if (!self)
+            return;
+        const auto items = self->recovery_report(deployment_id);
+        AlgoRecoveryItem item = items.value(0);
+        item.deployment_id = deployment_id;
+        QStringList failures;
+        auto& unified = fincept::trading::UnifiedTrading::instance();
+        if (!item.broker_checked) {
+            failures << "Cannot verify the broker position: " + item.error;
+        } else if (action == QLatin1String("resume")) {
+            // The runner knows nothing of working orders and can only adopt a
+            // single-symbol position, so those cases must be flattened instead.
+            if (!item.open_order_ids.isEmpty())
+                failures << QString("%1 working order(s) at the broker — cancel them or flatten")
+                                .arg(item.open_order_ids.size());
+            if (item.basket && item.state != QLatin1String("in_sync"))
+                failures << "F&O legs differ from the broker — flatten or stop instead";
+        } else {
+            for (const auto& order_id : std::as_const(item.open_order_ids)) {
+                const auto r = unified.cancel_order(item.account_id, order_id);
+                if (!r.success)
+                    failures << QString("cancel %1: %2").arg(order_id, r.message);
+            }
+            if (item.basket) {
+                for (auto leg = item.broker_legs.cbegin(); leg != item.broker_legs.cend(); ++leg) {
+                    if (std::abs(leg.value()) <= kAeQtyEpsilon)
+                        continue;
+                    const auto r = unified.close_position(item.account_id, leg.key(), QStringLiteral("NFO"));
+                    if (!r.success)
+                        failures << QString("close %1: %2").arg(leg.key(), r.error);
+                }
+            } else if (std::abs(item.broker_qty) > kAeQtyEpsilon) {
+                const auto r = unified.close_position(item.account_id, item.symbol, item.exchange);
+                if (!r.success)
+                    failures << QString("close %1: %2").arg(item.symbol, r.error);
+            }
+        }
+        if (!self)
+            return;
+        QMetaObject::invokeMethod(
+            self,
+            [self, item, action, failures]() {
+                if (self)
+                    self->finish_recovery(item, action, failures);
+            },
+            Qt::QueuedConnection);
+    });
+}
+
+void AlgoEngine::finish_recovery(const AlgoRecoveryItem& item, const QString& action, const QStringList& failures) {
+    const QString& id = item.deployment_id;
+    if (!failures.isEmpty()) {
+        // Stays "recovering" so the user can retry or pick another action.
+        const QString message = failures.join(QStringLiteral("; "));
+        LOG_WARN("AlgoEngine", QString("Recovery '%1' of deployment %2 failed: %3").arg(action, id, message));
+        emit recovery_resolved(id, action, false, message);
+        return;
+    }
+
+    auto db = fincept::Database::instance().connection();
+    // Whatever the journal still shows in flight has now been reconciled.
+    QSqlQuery jq(db);
+    jq.prepare(QStringLiteral("UPDATE algo_order_signals SET status='failed', error=?, updated_at=datetime('now') "
+                              "WHERE deployment_id=? AND status IN ('pending','dispatched')"));
+    jq.addBindValue(QString("Unconfirmed at crash; settled by recovery (%1)").arg(action));
+    jq.addBindValue(id);
+    jq.exec();
+
+    if (action == QLatin1String("resume")) {
+        if (kill_switch_.load()) {
+            emit recovery_resolved(id, action, false, QStringLiteral("Kill switch is engaged — reset it first"));
+            return;
+        }
+        QSqlQuery dq(db);
+        dq.prepare(QStringLiteral("SELECT * FROM algo_deployments WHERE id = ?"));
+        dq.addBindValue(id);
+        if (!dq.exec() || !dq.next()) {
+            emit recovery_resolved(id, action, false, QStringLiteral("Deployment not found"));
+            return;
+        }
+        const auto d = ae_deployment_from_row(dq);
+        const auto strategy = load_strategy(d.strategy_id);
+        if (strategy.id.isEmpty()) {
+            emit recovery_resolved(id, action, false, QString("Strategy %1 not found").arg(d.strategy_id));
+            return;
+        }
+        if (!item.basket) {
+            // The broker is the truth: the runner restores this on start().
+            const double qty = std::abs(item.broker_qty);
+            const QString side = item.broker_qty > kAeQtyEpsilon    ? QStringLiteral("LONG")
+                                 : item.broker_qty < -kAeQtyEpsilon ? QStringLiteral("SHORT")
+                                                                    : QStringLiteral("NONE");
+            const double entry = item.broker_avg_price > 0 ? item.broker_avg_price : item.recorded_entry;
+            QSqlQuery mq(db);
+            mq.prepare(QStringLiteral("INSERT INTO algo_metrics (deployment_id, current_position_qty, "
+                                      "current_position_side, current_position_entry) VALUES (?,?,?,?) "
+                                      "ON CONFLICT(deployment_id) DO UPDATE SET "
+                                      "current_position_qty=excluded.current_position_qty, "
+                                      "current_position_side=excluded.current_position_side, "
+                                      "current_position_entry=excluded.current_position_entry"));
+            mq.addBindValue(id);
+            mq.addBindValue(side == QLatin1String("NONE") ? 0.0 : qty);
+            mq.addBindValue(side);
+            mq.addBindValue(side == QLatin1String("NONE") ? 0.0 : entry);
+            if (!mq.exec()) {
+                const QString err = mq.lastError().text();
+                emit recovery_resolved(id, action, false, "Could not adopt the broker position: " + err);
+                return;
+            }
+        }
+        LOG_INFO("AlgoEngine", QString("Deployment %1 resumed after crash recovery").arg(id));
+        start_deployment(d, strategy);
+        emit recovery_resolved(id, action, true, QStringLiteral("Reconciled with the broker and resumed"));
+        return;
+    }
+
+    const bool flatten = action == QLatin1String("flatten");
+    if (flatten) {
+        QSqlQuery mq(db);
+        mq.prepare(QStringLiteral("UPDATE algo_metrics SET current_position_qty=0, current_position_side='NONE', "
+                                  "current_position_entry=0, unrealized_pnl=0 WHERE deployment_id=?"));
+        mq.addBindValue(id);
+        mq.exec();
+    }
+    const QString message = flatten ? QStringLiteral("Flattened at the broker and stopped after crash recovery")
+                                    : QStringLiteral("Stopped after crash recovery; broker position left as is");
+    QSqlQuery u(db);
+    u.prepare(flatten ? QStringLiteral("UPDATE algo_deployments SET status='stopped', error_message=?, "
+                                       "resolved_legs_json='[]', updated_at=datetime('now') WHERE id=?")
+                      : QStringLiteral("UPDATE algo_deployments SET status='stopped', error_message=?, "
+                                       "updated_at=datetime('now') WHERE id=?"));
+    u.addBindValue(message);
+    u.addBindValue(id);
+    u.exec();
+    LOG_INFO("AlgoEngine", QString("Deployment %1: %2").arg(id, message));
+    emit recovery_resolved(id, action, true, message);
+    list_deployments(); // refresh the Dashboard
+}
+
 bool AlgoEngine::has_active_duplicate(const QString& strategy_id, const QString& symbol, const QString& mode,
                                       const QString& entry_side) const {
     auto db = fincept::Database::instance().connection();
@@ -601,6 +1199,113 @@ bool AlgoEngine::has_active_duplicate(const QString& strategy_id, const QString&
     return false;
 }
 
+QJsonObject AlgoEngine::deployment_analytics(const QString& deployment_id) const {
+    auto db = fincept::Database::instance().connection();
+    QSqlQuery dq(db);
+    dq.prepare(QStringLiteral("SELECT id, strategy_id, strategy_name, symbol, underlying, mode, status, timeframe, "
+                              "quantity, created_at FROM algo_deployments WHERE id = ?"));
+    dq.addBindValue(deployment_id);
+    if (!dq.exec() || !dq.next())
+        return {};
+    const QString mode = dq.value("mode").toString();
+    QJsonObject out{{"deployment_id", deployment_id},
+                    {"strategy_id", dq.value("strategy_id").toString()},
+                    {"strategy_name", dq.value("strategy_name").toString()},
+                    {"symbol", dq.value("symbol").toString().isEmpty() ? dq.value("underlying").toString()
+                                                                       : dq.value("symbol").toString()},
+                    {"mode", mode},
+                    {"hypothetical", mode != QLatin1String("live")},
+                    {"status", dq.value("status").toString()},
+                    {"timeframe", dq.value("timeframe").toString()},
+                    {"quantity", dq.value("quantity").toDouble()},
+                    {"started_at", dq.value("created_at").toString()}};
+
+    // Fills in order. Single-symbol exits carry their realized P&L; basket legs
+    // do not (the basket's P&L lands in algo_metrics), so round trips and the
+    // curve below cover the single-symbol path and the totals cover both.
+    QSqlQuery tq(db);
+    tq.prepare(QStringLiteral("SELECT side, quantity, price, pnl, reason, broker_order_id, leg_symbol, leg_index, "
+                              "created_at FROM algo_trades WHERE deployment_id = ? ORDER BY created_at, rowid"));
+    tq.addBindValue(deployment_id);
+    QJsonArray fills, curve;
+    double realized = 0, peak = 0, max_dd = 0, gross_win = 0, gross_loss = 0, best = 0, worst = 0;
+    int wins = 0, losses = 0, round_trips = 0;
+    if (tq.exec()) {
+        while (tq.next()) {
+            const QString reason = tq.value("reason").toString();
+            const double pnl = tq.value("pnl").toDouble();
+            const int leg_index = tq.value("leg_index").toInt();
+            const QString time = tq.value("created_at").toString();
+            QJsonObject f{{"time", time},
+                          {"side", tq.value("side").toString()},
+                          {"quantity", tq.value("quantity").toDouble()},
+                          {"price", tq.value("price").toDouble()},
+                          {"pnl", pnl},
+                          {"reason", reason},
+                          {"order_id", tq.value("broker_order_id").toString()}};
+            if (leg_index >= 0) {
+                f["leg_symbol"] = tq.value("leg_symbol").toString();
+                f["leg_index"] = leg_index;
+            }
+            fills.append(f);
+            if (reason == QLatin1String("entry_signal") || leg_index >= 0)
+                continue;
+
+            ++round_trips;
+            realized += pnl;
+            if (pnl > 0) {
+                ++wins;
+                gross_win += pnl;
+            } else if (pnl < 0) {
+                ++losses;
+                gross_loss -= pnl;
+            }
+            best = std::max(best, pnl);
+            worst = std::min(worst, pnl);
+            peak = std::max(peak, realized);
+            max_dd = std::max(max_dd, peak - realized);
+            curve.append(QJsonObject{{"time", time}, {"equity", realized}, {"drawdown", peak - realized}});
+        }
+    } else {
+        LOG_ERROR("AlgoEngine", QString("deployment_analytics(%1): %2").arg(deployment_id, tq.lastError().text()));
+    }
+
+    QSqlQuery mq(db);
+    mq.prepare(QStringLiteral("SELECT total_pnl, unrealized_pnl, total_trades, win_rate, max_drawdown, "
+                              "current_position_qty, current_position_side, current_position_entry, current_price "
+                              "FROM algo_metrics WHERE deployment_id = ?"));
+    mq.addBindValue(deployment_id);
+    double total_realized = realized, unrealized = 0;
+    QJsonObject position;
+    if (mq.exec() && mq.next()) {
+        total_realized = mq.value("total_pnl").toDouble();
+        unrealized = mq.value("unrealized_pnl").toDouble();
+        position = QJsonObject{{"quantity", mq.value("current_position_qty").toDouble()},
+                               {"side", mq.value("current_position_side").toString()},
+                               {"entry_price", mq.value("current_position_entry").toDouble()},
+                               {"current_price", mq.value("current_price").toDouble()}};
+    }
+
+    out["fills"] = fills;
+    out["total_fills"] = fills.size();
+    out["round_trips"] = round_trips;
+    out["wins"] = wins;
+    out["losses"] = losses;
+    out["win_rate"] = round_trips > 0 ? 100.0 * wins / round_trips : 0.0;
+    out["profit_factor"] = gross_loss > 0 ? gross_win / gross_loss : 0.0;
+    out["avg_win"] = wins > 0 ? gross_win / wins : 0.0;
+    out["avg_loss"] = losses > 0 ? -gross_loss / losses : 0.0;
+    out["largest_win"] = best;
+    out["largest_loss"] = worst;
+    out["equity_curve"] = curve;
+    out["max_drawdown"] = max_dd;
+    out["realized_pnl"] = total_realized;
+    out["unrealized_pnl"] = unrealized;
+    out["total_pnl"] = total_realized + unrealized;
+    out["position"] = position;
+    return out;
+}
+
 void AlgoEngine::remove_deployment(const QString& deployment_id) {
     // Stop the runner first if it happens to be live (REMOVE is normally only shown
     // for already-stopped rows, but guard anyway).
@@ -628,6 +1333,7 @@ void AlgoEngine::remove_deployment(const QString& deployment_id) {
             LOG_ERROR("AlgoEngine", QString("remove_deployment(%1): %2").arg(deployment_id, q.lastError().text()));
     }
     LOG_INFO("AlgoEngine", QString("Removed deployment %1").arg(deployment_id));
+    services::audit::AuditTrail::instance().record("destructive", "deployment_removed", deployment_id);
     list_deployments(); // refresh the Dashboard
 }
 
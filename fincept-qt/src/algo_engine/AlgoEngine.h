@@ -6,11 +6,14 @@
 #include "services/algo_trading/AlgoTradingTypes.h"
 
 #include <QHash>
+#include <QJsonObject>
 #include <QMutex>
 #include <QObject>
 #include <QThread>
 #include <QTimer>
 
+#include <atomic>
+
 namespace fincept::algo {
 
 class AlgoEngine : public QObject {
@@ -29,18 +32,67 @@ class AlgoEngine : public QObject {
     void remove_deployment(const QString& deployment_id);
     void stop_all();
 
+    // Hot-swaps a running deployment's strategy without flattening it (see
+    // DeploymentRunner::reload_strategy). The one-argument form reloads the
+    // deployment's saved strategy — e.g. after editing it in the builder.
+    // Emits strategy_reloaded.
+    void reload_strategy(const QString& deployment_id);
+    void reload_strategy(const QString& deployment_id, const fincept::services::algo::AlgoStrategy& strategy);
+
+    // Global kill switch. Every runner stops taking entries at once; open
+    // orders are cancelled on each live broker account a deployment trades
+    // on, then every deployment flattens its position with a market exit and
+    // stops (status "stopped", so nothing resumes on restart). While engaged
+    // no deployment can start until reset_kill_switch(). The engaged state is
+    // persisted and restored at startup.
+    void kill_switch(const QString& reason);
+    void reset_kill_switch();
+    bool kill_switch_engaged() const { return kill_switch_.load(); }
+
     bool is_running(const QString& deployment_id) const;
     QStringList active_deployment_ids() const;
     AlgoMetrics metrics(const QString& deployment_id) const;
     int active_count() const;
 
     void list_deployments();
+    // Resumes the deployments the last run left active. After a crash (no
+    // clean-exit marker) live ones are NOT resumed: they are held in status
+    // "recovering", deployment_crashed fires for each and recovery_required
+    // lists them. A live deployment with an order still in flight is held
+    // even after a clean exit.
     void recover_orphaned();
     // True if a running/starting deployment with the same strategy+symbol+mode+side
     // already exists — used to confirm before deploying an exact duplicate.
     bool has_active_duplicate(const QString& strategy_id, const QString& symbol, const QString& mode,
                               const QString& entry_side) const;
 
+    // Trade and equity analytics of one deployment from its persisted fills
+    // (algo_trades) and metrics (algo_metrics) — the same for paper, live and
+    // shadow (forward-test) deployments, so a shadow run can be judged exactly
+    // as it would trade live. Returns the fills, closed round trips (win rate,
+    // profit factor, average win/loss), the realized-P&L equity curve with its
+    // max drawdown, and realized/unrealized/total P&L. Empty object when the
+    // deployment is unknown.
+    QJsonObject deployment_analytics(const QString& deployment_id) const;
+
+    // ── Crash recovery ───────────────────────────────────────────────────────
+    // Deployments waiting in "recovering" (see recover_orphaned).
+    QStringList recovering_deployment_ids() const;
+    // Compares each recovering deployment's recorded position with its broker
+    // account: the net position on its symbol (or each F&O leg), working
+    // orders there, and orders the engine sent but never saw confirmed. A
+    // position opened by hand on the same symbol shows as a mismatch too.
+    // Empty deployment_id = all. BLOCKING (broker calls) — call off the UI thread.
+    QVector<AlgoRecoveryItem> recovery_report(const QString& deployment_id = {}) const;
+    // Settles a recovering deployment; the broker work runs on a worker thread
+    // and recovery_resolved reports the outcome.
+    //   "resume"  — adopt the broker's net position as the deployment's and
+    //               restart it (F&O only when the broker legs match)
+    //   "flatten" — cancel its working orders, close its position or legs at
+    //               market and stop it
+    //   "stop"    — stop it and leave the broker untouched
+    void resolve_recovery(const QString& deployment_id, const QString& action);
+
     // GUI-thread bridge for option-chain data. Created before moveToThread so it
     // stays on the main thread. Accessible to callers that need to check its state
     // from the main thread (e.g. the F&O Algo deploy dialog).
@@ -56,6 +108,11 @@ class AlgoEngine : public QObject {
     void error_occurred(const QString& deployment_id, const QString& error);
     // Real-time per-tick snapshot relayed from each runner to the Dashboard.
     void live_update(const QString& deployment_id, const fincept::algo::AlgoLiveSnapshot& snap);
+    void kill_switch_changed(bool engaged, const QString& reason);
+    void strategy_reloaded(const QString& deployment_id, bool ok, const QString& message);
+    // Live deployments held after a crash, waiting for resolve_recovery().
+    void recovery_required(const QStringList& deployment_ids);
+    void recovery_resolved(const QString& deployment_id, const QString& action, bool ok, const QString& message);
 
   private slots:
     void on_order_requested(const fincept::algo::AlgoOrderSignal& signal);
@@ -67,9 +124,10 @@ class AlgoEngine : public QObject {
 
     void execute_order(const AlgoOrderSignal& signal);
     // Multi-leg F&O basket execution (P3.4). Dispatched from execute_order when
-    // signal.legs is non-empty. Paper (signal.mode != "live") routes every leg to
-    // the deployment's paper portfolio via pt_place_order — NEVER the broker, even
-    // on a live account (the safety gate). Live (signal.mode == "live" only) sends
+    // signal.legs is non-empty. Shadow fills every leg hypothetically in place;
+    // paper (any other non-"live" mode) routes every leg to the deployment's
+    // paper portfolio via pt_place_order — NEVER the broker, even on a live
+    // account (the safety gate). Live (signal.mode == "live" only) sends
     // a broker basket and rolls back filled legs if any leg fails on entry.
     void execute_basket(const AlgoOrderSignal& signal);
     // Resolve the paper portfolio for a basket: the signal's paper_portfolio_id,
@@ -82,11 +140,15 @@ class AlgoEngine : public QObject {
     // Loads a full AlgoStrategy (incl. parsed entry/exit conditions) from the
     // algo_strategies table — used to resume deployments after an app restart.
     fincept::services::algo::AlgoStrategy load_strategy(const QString& strategy_id);
+    // Second half of resolve_recovery(), back on the engine thread once the
+    // broker work is done: updates the rows, then restarts or stops.
+    void finish_recovery(const AlgoRecoveryItem& item, const QString& action, const QStringList& failures);
 
     QThread engine_thread_;
     mutable QMutex mutex_;
     QHash<QString, DeploymentRunner*> runners_;
     fincept::algo::fno::FnoDataBridge* fno_bridge_ = nullptr;
+    std::atomic<bool> kill_switch_{false};
 };
 
 } // namespace fincept::algo
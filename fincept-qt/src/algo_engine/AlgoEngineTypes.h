@@ -3,6 +3,7 @@
 #include <QHash>
 #include <QMetaType>
 #include <QString>
+#include <QStringList>
 #include <QVector>
 
 #include <cstdint>
@@ -23,6 +24,20 @@ struct OhlcvCandle {
     bool is_closed = false;
 };
 
+// ── Tick ────────────────────────────────────────────────────────────────────
+
+// One trade / quote update replayed by TickBacktestEngine. Quote fields are 0
+// when the source only has trades.
+struct TickEvent {
+    int64_t timestamp_ms = 0;
+    double price = 0;
+    double size = 0;
+    double bid = 0;
+    double ask = 0;
+    double bid_size = 0;
+    double ask_size = 0;
+};
+
 // ── Timeframe ───────────────────────────────────────────────────────────────
 
 enum class Timeframe { M1, M3, M5, M15, M30, H1, H4, D1 };
@@ -258,6 +273,33 @@ struct AlgoLiveSnapshot {
     QString note;                            // short activity line, e.g. "entry not met"
 };
 
+// ── Crash recovery ────────────────────────────────────────────────────────────
+// A live deployment held at startup because the app did not exit cleanly,
+// reconciled against its broker account (AlgoEngine::recovery_report).
+
+struct AlgoRecoveryItem {
+    QString deployment_id;
+    QString strategy_name;
+    QString symbol; // equity symbol; F&O: the underlying
+    QString exchange;
+    QString product_type;
+    QString account_id;
+    bool basket = false;                  // F&O multi-leg deployment
+    QString recorded_side;                // LONG | SHORT | NONE, from algo_metrics
+    double recorded_qty = 0;              // equity only
+    double recorded_entry = 0;            // equity only
+    QHash<QString, double> recorded_legs; // F&O: leg symbol -> signed contracts
+    bool broker_checked = false;          // false: broker unreachable, see error
+    double broker_qty = 0;                // equity: signed net broker quantity (+long / -short)
+    double broker_avg_price = 0;          // equity
+    QHash<QString, double> broker_legs;   // F&O: leg symbol -> signed broker quantity
+    QStringList open_order_ids;           // working broker orders on the symbol or its legs
+    int inflight_orders = 0;              // orders sent but unconfirmed when the app died
+    QString state;                        // in_sync | mismatch | unverified
+    QStringList notes;                    // what differs, one line each
+    QString error;
+};
+
 } // namespace fincept::algo
 
 Q_DECLARE_METATYPE(fincept::algo::AlgoMetrics)
@@ -0,0 +1,129 @@
+// src/algo_engine/AlgoNotificationBridge.cpp
+#include "algo_engine/AlgoNotificationBridge.h"
+
+#include "algo_engine/AlgoEngine.h"
+#include "algo_engine/RegimeMonitor.h"
+#include "core/logging/Logger.h"
+#include "services/notifications/NotificationService.h"
+
+#include <QDateTime>
+
+namespace fincept::algo {
+
+using notifications::NotificationRequest;
+using notifications::NotificationService;
+using notifications::NotifLevel;
+using notifications::NotifTrigger;
+
+namespace {
+
+constexpr qint64 kErrorCooldownMs = 10 * 60 * 1000; // same error, same deployment
+constexpr qint64 kFillCooldownMs = 24 * 60 * 60 * 1000; // a trade id is only ever announced once
+constexpr qint64 kKillCooldownMs = 60 * 1000;
+constexpr qint64 kRegimeCooldownMs = 60 * 60 * 1000; // a regime flip-flopping across refreshes
+
+void anb_send(const QString& title, const QString& message, NotifLevel level) {
+    NotificationRequest req;
+    req.title = title;
+    req.message = message;
+    req.level = level;
+    req.trigger = NotifTrigger::AlgoAlert;
+    NotificationService::instance().send(req);
+}
+
+} // namespace
+
+AlgoNotificationBridge& AlgoNotificationBridge::instance() {
+    static AlgoNotificationBridge b;
+    return b;
+}
+
+bool AlgoNotificationBridge::admit(const QString& key, qint64 cooldown_ms) {
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    // Keep the table small: forget keys whose longest cooldown has passed.
+    if (last_sent_ms_.size() > 512) {
+        for (auto it = last_sent_ms_.begin(); it != last_sent_ms_.end();)
+            it = now - it.value() > kFillCooldownMs ? last_sent_ms_.erase(it) : std::next(it);
+    }
+    const auto it = last_sent_ms_.constFind(key);
+    if (it != last_sent_ms_.constEnd() && now - it.value() < cooldown_ms)
+        return false;
+    last_sent_ms_.insert(key, now);
+    return true;
+}
+
+void AlgoNotificationBridge::install() {
+    if (installed_)
+        return;
+    installed_ = true;
+    auto& engine = AlgoEngine::instance();
+
+    connect(&engine, &AlgoEngine::trade_executed, this, [this](const AlgoTradeRecord& t) {
+        // A multi-leg basket records one trade per leg — announce it once.
+        if (t.leg_index > 0 || !admit(QStringLiteral("fill:") + t.id, kFillCooldownMs))
+            return;
+        const bool basket = t.leg_index == 0;
+        QString message = basket ? QString("%1 basket %2 (%3)").arg(t.symbol, t.side, t.reason)
+                                 : QString("%1 %2 %3 @ %4 (%5)")
+                                       .arg(t.side)
+                                       .arg(t.quantity)
+                                       .arg(t.symbol)
+                                       .arg(t.price, 0, 'f', 2)
+                                       .arg(t.reason);
+        if (t.pnl != 0)
+            message += QString(" — P&L %1").arg(t.pnl, 0, 'f', 2);
+        anb_send(QStringLiteral("Algo Fill"), message, NotifLevel::Info);
+    });
+
+    connect(&engine, &AlgoEngine::error_occurred, this, [this](const QString& deployment_id, const QString& error) {
+        if (!admit(QString("error:%1:%2").arg(deployment_id, error), kErrorCooldownMs))
+            return;
+        anb_send(QStringLiteral("Algo Deployment Error"), QString("%1: %2").arg(deployment_id.left(8), error),
+                 NotifLevel::Warning);
+    });
+
+    connect(&engine, &AlgoEngine::deployment_crashed, this,
+            [this](const QString& deployment_id, const QString& reason) {
+                if (!admit(QStringLiteral("crash:") + deployment_id, kErrorCooldownMs))
+                    return;
+                anb_send(QStringLiteral("Algo Deployment Crashed"),
+                         QString("%1: %2").arg(deployment_id.left(8), reason), NotifLevel::Critical);
+            });
+
+    connect(&engine, &AlgoEngine::recovery_required, this, [this](const QStringList& deployment_ids) {
+        if (!admit(QStringLiteral("recovery"), kErrorCooldownMs))
+            return;
+        anb_send(QStringLiteral("Algo Crash Recovery Needed"),
+                 QString("%1 live deployment(s) held after an unclean shutdown — run \"Algo Crash Recovery\" "
+                         "to resume, flatten or stop them")
+                     .arg(deployment_ids.size()),
+                 NotifLevel::Critical);
+    });
+
+    connect(&engine, &AlgoEngine::recovery_resolved, this,
+            [this](const QString& deployment_id, const QString& action, bool ok, const QString& message) {
+                anb_send(ok ? QStringLiteral("Algo Recovery Done") : QStringLiteral("Algo Recovery Failed"),
+                         QString("%1 (%2): %3").arg(deployment_id.left(8), action, message),
+                         ok ? NotifLevel::Info : NotifLevel::Warning);
+            });
+
+    connect(&engine, &AlgoEngine::kill_switch_changed, this, [this](bool engaged, const QString& reason) {
+        if (!engaged || !admit(QStringLiteral("kill_switch"), kKillCooldownMs))
+            return;
+        anb_send(QStringLiteral("Algo Kill Switch Engaged"),
+                 QString("All deployments halted and flattened (%1)").arg(reason), NotifLevel::Critical);
+    });
+
+    connect(&RegimeMonitor::instance(), &RegimeMonitor::regime_changed, this,
+            [this](const QString& from, const QString& to, const QJsonObject& regime) {
+                const QString index = regime.value("index").toString();
+                if (!admit(QStringLiteral("regime:") + index, kRegimeCooldownMs))
+                    return;
+                anb_send(QStringLiteral("Market Regime Change"), QString("%1: %2 → %3").arg(index, from, to),
+                         to == QLatin1String("turbulent") ? NotifLevel::Warning : NotifLevel::Info);
+            });
+
+    LOG_INFO("AlgoEngine", "Notification bridge installed");
+}
+
+} // namespace fincept::algo
@@ -0,0 +1,38 @@
+// src/algo_engine/AlgoNotificationBridge.h
+#pragma once
+#include <QHash>
+#include <QObject>
+#include <QString>
+
+namespace fincept::algo {
+
+/// Forwards AlgoEngine events to NotificationService (toast, bell and every
+/// enabled external provider — Telegram, webhook, …) under the AlgoAlert
+/// trigger: fills (one per multi-leg basket), deployment errors, crashes and
+/// the kill switch — plus market regime changes from RegimeMonitor.
+///
+/// Each notification carries a dedupe key; a key is not re-sent within its
+/// cooldown, so a deployment that logs the same error every bar notifies once
+/// every few minutes rather than on every candle.
+///
+/// Lives on the main thread; AlgoEngine's signals arrive queued from the
+/// engine thread. Install once at startup.
+class AlgoNotificationBridge : public QObject {
+    Q_OBJECT
+  public:
+    static AlgoNotificationBridge& instance();
+
+    // Connect to AlgoEngine and RegimeMonitor. Idempotent.
+    void install();
+
+  private:
+    AlgoNotificationBridge() = default;
+
+    // False when `key` was sent less than `cooldown_ms` ago; otherwise records now.
+    bool admit(const QString& key, qint64 cooldown_ms);
+
+    bool installed_ = false;
+    QHash<QString, qint64> last_sent_ms_; // dedupe key → last send (epoch ms)
+};
+
+} // namespace fincept::algo
@@ -8,6 +8,7 @@
 
 #include <algorithm>
 #include <cmath>
+#include <cstring>
 
 namespace fincept::algo {
 
@@ -15,20 +16,27 @@ namespace {
 
 // Bars required before any evaluation (indicator warm-up). Matches the old
 // Python engine's WARMUP_BARS so results are comparable in shape.
-constexpr int kWarmupBars = 50;
+constexpr int kWarmupBars = BacktestEngine::kWarmupBars;
 
 // Trailing window passed to the indicator/condition evaluator each bar. Bounds
 // per-bar cost to O(window) instead of O(i) — 500 bars is ample warm-up for any
 // indicator in the catalog (largest typical period ~200).
-constexpr int kEvalWindow = 500;
+constexpr int kEvalWindow = BacktestEngine::kEvalWindow;
 
 double round_to(double v, int decimals) {
     double f = std::pow(10.0, decimals);
     return std::round(v * f) / f;
 }
 
+} // namespace
+
+QJsonObject BacktestEngine::run(const QVector<OhlcvCandle>& candles, const BacktestParams& p, bool verbose) {
+    return run(candles, p.entry_conditions, p.entry_logic, p.exit_conditions, p.exit_logic, p.stop_loss_pct,
+               p.take_profit_pct, p.trailing_stop_pct, p.initial_capital, p.timeframe, p.position_size_pct, verbose);
+}
+
 // Bars per year for timeframe-aware Sharpe annualisation (mirrors the old engine).
-double bars_per_year(const QString& tf) {
+double BacktestEngine::bars_per_year(const QString& tf) {
     if (tf == "1m")
         return 252.0 * 390.0;
     if (tf == "3m")
@@ -48,13 +56,11 @@ double bars_per_year(const QString& tf) {
     return 252.0;
 }
 
-} // namespace
-
 QJsonObject BacktestEngine::run(const QVector<OhlcvCandle>& candles, const QJsonArray& entry_conditions,
                                 const QString& entry_logic, const QJsonArray& exit_conditions,
                                 const QString& exit_logic, double stop_loss_pct, double take_profit_pct,
                                 double trailing_stop_pct, double initial_capital, const QString& timeframe,
-                                double position_size_pct) {
+                                double position_size_pct, bool verbose) {
     const int n = candles.size();
     const double size_frac = std::clamp(position_size_pct, 1.0, 100.0) / 100.0;
     if (n < kWarmupBars + 10) {
@@ -64,14 +70,15 @@ QJsonObject BacktestEngine::run(const QVector<OhlcvCandle>& candles, const QJson
         return err;
     }
 
-    LOG_INFO("Backtest", QString("run: candles=%1 tf=%2 entryConds=%3 exitConds=%4 sl=%5 tp=%6 sizePct=%7")
-                             .arg(n)
-                             .arg(timeframe)
-                             .arg(entry_conditions.size())
-                             .arg(exit_conditions.size())
-                             .arg(stop_loss_pct)
-                             .arg(take_profit_pct)
-                             .arg(position_size_pct));
+    if (verbose)
+        LOG_INFO("Backtest", QString("run: candles=%1 tf=%2 entryConds=%3 exitConds=%4 sl=%5 tp=%6 sizePct=%7")
+                                 .arg(n)
+                                 .arg(timeframe)
+                                 .arg(entry_conditions.size())
+                                 .arg(exit_conditions.size())
+                                 .arg(stop_loss_pct)
+                                 .arg(take_profit_pct)
+                                 .arg(position_size_pct));
 
     // ── Diagnostics ──────────────────────────────────────────────────────────
     int entry_eval_count = 0, entry_true_count = 0, exit_true_count = 0, entry_err_count = 0;
@@ -82,6 +89,8 @@ QJsonObject BacktestEngine::run(const QVector<OhlcvCandle>& candles, const QJson
     bool in_pos = false;
     double entry_price = 0.0;
     int entry_bar = 0;
+    int entry_signal_bar = -1; // bar whose close latched the entry rule
+    int exit_signal_bar = -1;  // bar whose close latched the exit rule (-1 = stop/target/eod)
     long long shares = 0;
     double highest = 0.0; // high-watermark for trailing stop (long)
 
@@ -106,6 +115,10 @@ QJsonObject BacktestEngine::run(const QVector<OhlcvCandle>& candles, const QJson
         QJsonObject t;
         t["entry_bar"] = entry_bar;
         t["exit_bar"] = exit_bar;
+        t["entry_signal_bar"] = entry_signal_bar;
+        t["exit_signal_bar"] = std::strcmp(reason, "exit_signal") == 0 ? exit_signal_bar : -1;
+        t["entry_time"] = static_cast<double>(candles[entry_bar].open_time);
+        t["exit_time"] = static_cast<double>(candles[exit_bar].open_time);
         t["entry_price"] = round_to(entry_price, 2);
         t["exit_price"] = round_to(exit_price, 2);
         t["shares"] = static_cast<double>(shares);
@@ -116,6 +129,7 @@ QJsonObject BacktestEngine::run(const QVector<OhlcvCandle>& candles, const QJson
         trades.append(t);
         in_pos = false;
         shares = 0;
+        exit_signal_bar = -1;
     };
 
     for (int i = kWarmupBars; i < n; ++i) {
@@ -172,6 +186,7 @@ QJsonObject BacktestEngine::run(const QVector<OhlcvCandle>& candles, const QJson
             ++entry_eval_count;
             if (g.triggered) {
                 entry_signal = true;
+                entry_signal_bar = i;
                 ++entry_true_count;
             }
             for (const auto& d : g.details)
@@ -182,7 +197,8 @@ QJsonObject BacktestEngine::run(const QVector<OhlcvCandle>& candles, const QJson
                 }
             // Snapshot the first evaluation where the LHS operand is actually computed
             // (past indicator warm-up) so we can see real operand values vs targets.
-            if (!entry_sampled && !g.details.isEmpty() && !std::isnan(g.details.first().computed_value)) {
+            if (verbose && !entry_sampled && !g.details.isEmpty() &&
+                !std::isnan(g.details.first().computed_value)) {
                 entry_sampled = true;
                 for (const auto& d : g.details)
                     LOG_INFO("Backtest", QString("  entry[bar %1] %2.%3 %4  lhs=%5 rhs=%6 met=%7 err=%8")
@@ -196,6 +212,7 @@ QJsonObject BacktestEngine::run(const QVector<OhlcvCandle>& candles, const QJson
         } else if (in_pos && !exit_signal && !exit_conditions.isEmpty()) {
             if (ConditionEvaluator::evaluate_group(exit_conditions, exit_logic, window).triggered) {
                 exit_signal = true;
+                exit_signal_bar = i;
                 ++exit_true_count;
             }
         }
@@ -215,13 +232,14 @@ QJsonObject BacktestEngine::run(const QVector<OhlcvCandle>& candles, const QJson
     if (in_pos)
         close_trade(candles[n - 1].close, "end_of_data", n - 1);
 
-    LOG_INFO("Backtest", QString("done: evalBars=%1 entryTrue=%2 exitTrue=%3 entryErr=%4 trades=%5 lastErr='%6'")
-                             .arg(entry_eval_count)
-                             .arg(entry_true_count)
-                             .arg(exit_true_count)
-                             .arg(entry_err_count)
-                             .arg(trades.size())
-                             .arg(last_entry_err));
+    if (verbose)
+        LOG_INFO("Backtest", QString("done: evalBars=%1 entryTrue=%2 exitTrue=%3 entryErr=%4 trades=%5 lastErr='%6'")
+                                 .arg(entry_eval_count)
+                                 .arg(entry_true_count)
+                                 .arg(exit_true_count)
+                                 .arg(entry_err_count)
+                                 .arg(trades.size())
+                                 .arg(last_entry_err));
 
     // ── Metrics ─────────────────────────────────────────────────────────────
     const int total_trades = trades.size();
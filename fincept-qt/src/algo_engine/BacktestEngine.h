@@ -9,6 +9,22 @@
 
 namespace fincept::algo {
 
+/// Everything BacktestEngine::run takes besides the candles, so callers that
+/// run the same strategy many times (walk-forward, sweeps) can pass it around
+/// and vary one field at a time.
+struct BacktestParams {
+    QJsonArray entry_conditions;
+    QString entry_logic = QStringLiteral("AND");
+    QJsonArray exit_conditions;
+    QString exit_logic = QStringLiteral("AND");
+    double stop_loss_pct = 0.0;
+    double take_profit_pct = 0.0;
+    double trailing_stop_pct = 0.0;
+    double initial_capital = 100000.0;
+    QString timeframe = QStringLiteral("1d");
+    double position_size_pct = 100.0;
+};
+
 /// Event-driven, single-symbol, long-only backtester.
 ///
 /// Pure computation: given candles + strategy parameters it returns a FLAT
@@ -23,13 +39,29 @@ namespace fincept::algo {
 ///   - Stop-loss / take-profit (incl. trailing) are checked intrabar against
 ///     each bar's high/low, filling at the stop/target price.
 ///
+/// Each trade carries `entry_signal_bar` / `exit_signal_bar` (the bar whose
+/// close latched the rule that caused the fill) so BacktestTradeInspector can
+/// replay the rule evaluations behind it.
+///
 /// On insufficient data the returned object has {"success": false, "error": …}.
 class BacktestEngine {
   public:
+    /// Bars required before any evaluation (indicator warm-up).
+    static constexpr int kWarmupBars = 50;
+    /// Trailing window passed to the indicator/condition evaluator each bar.
+    static constexpr int kEvalWindow = 500;
+
     static QJsonObject run(const QVector<OhlcvCandle>& candles, const QJsonArray& entry_conditions,
                            const QString& entry_logic, const QJsonArray& exit_conditions, const QString& exit_logic,
                            double stop_loss_pct, double take_profit_pct, double trailing_stop_pct,
-                           double initial_capital, const QString& timeframe, double position_size_pct = 100.0);
+                           double initial_capital, const QString& timeframe, double position_size_pct = 100.0,
+                           bool verbose = true);
+    /// Same as above; `verbose` = false skips the per-run diagnostics log
+    /// lines (for callers that run hundreds of backtests).
+    static QJsonObject run(const QVector<OhlcvCandle>& candles, const BacktestParams& p, bool verbose = true);
+
+    /// Bars per year for timeframe-aware Sharpe/Sortino annualisation.
+    static double bars_per_year(const QString& tf);
 };
 
 } // namespace fincept::algo
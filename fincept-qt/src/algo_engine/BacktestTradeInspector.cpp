@@ -0,0 +1,328 @@
+// src/algo_engine/BacktestTradeInspector.cpp
+#include "algo_engine/BacktestTradeInspector.h"
+
+#include "algo_engine/BacktestEngine.h"
+#include "algo_engine/ConditionEvaluator.h"
+#include "algo_engine/IndicatorEngine.h"
+
+#include <QHash>
+#include <QStringList>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::algo {
+
+namespace {
+
+double round_to(double v, int decimals) {
+    double f = std::pow(10.0, decimals);
+    return std::round(v * f) / f;
+}
+
+// NaN / inf are not representable in JSON — surface them as null.
+QJsonValue num_or_null(double v) {
+    return std::isfinite(v) ? QJsonValue(round_to(v, 6)) : QJsonValue();
+}
+
+// Stable display key for an indicator operand, e.g. "MACD(fast=12,signal=9,slow=26)".
+QString indicator_key(const QString& name, const QJsonObject& params) {
+    if (params.isEmpty())
+        return name;
+    QStringList parts;
+    for (auto it = params.constBegin(); it != params.constEnd(); ++it) {
+        const QJsonValue v = it.value();
+        const QString sv = v.isDouble() ? QString::number(v.toDouble()) : v.toVariant().toString();
+        parts << QStringLiteral("%1=%2").arg(it.key(), sv);
+    }
+    return QStringLiteral("%1(%2)").arg(name, parts.join(','));
+}
+
+QJsonObject candle_json(const OhlcvCandle& c, int bar) {
+    QJsonObject o;
+    o["bar"] = bar;
+    o["time"] = static_cast<double>(c.open_time);
+    o["open"] = c.open;
+    o["high"] = c.high;
+    o["low"] = c.low;
+    o["close"] = c.close;
+    o["volume"] = c.volume;
+    return o;
+}
+
+} // namespace
+
+QVector<OhlcvCandle> BacktestTradeInspector::window_ending_at(const QVector<OhlcvCandle>& candles, int bar) {
+    // Same trailing window BacktestEngine hands the evaluator, so the replayed
+    // values match what the engine saw bar-for-bar.
+    const int start = std::max(0, bar - BacktestEngine::kEvalWindow + 1);
+    return candles.mid(start, bar - start + 1);
+}
+
+QJsonObject BacktestTradeInspector::evaluate_tree(const QJsonArray& children, const QString& logic,
+                                                  const QVector<OhlcvCandle>& window) {
+    const bool is_and = logic.toUpper() != "OR";
+    bool overall = is_and;
+    QJsonArray nodes;
+
+    for (const auto& val : children) {
+        const QJsonObject node = val.toObject();
+        bool met = false;
+        QJsonObject out;
+
+        if (ConditionEvaluator::is_group_node(node)) {
+            const QString sub_logic = node.value("logic").toString(node.value("op").toString("AND"));
+            out = evaluate_tree(node.value("children").toArray(), sub_logic, window);
+            const bool negate = node.value("negate").toBool(false);
+            met = out.value("met").toBool() != negate;
+            out["negate"] = negate;
+            out["met"] = met;
+        } else {
+            const auto cond = ConditionEvaluator::parse_condition(node);
+            const ConditionResult r = ConditionEvaluator::evaluate_single(cond, window);
+            met = r.met;
+            out["type"] = QStringLiteral("condition");
+            out["indicator"] = cond.indicator;
+            out["params"] = cond.params;
+            out["field"] = cond.field;
+            out["offset"] = cond.offset;
+            out["operator"] = cond.op;
+            out["compare_mode"] = cond.compare_mode;
+            if (cond.compare_mode == "indicator") {
+                out["compare_indicator"] = cond.compare_indicator;
+                out["compare_params"] = cond.compare_params;
+                out["compare_field"] = cond.compare_field;
+            }
+            out["computed_value"] = num_or_null(r.computed_value);
+            out["target_value"] = num_or_null(r.target_value);
+            out["met"] = met;
+            if (!r.error.isEmpty())
+                out["error"] = r.error;
+        }
+
+        overall = is_and ? (overall && met) : (overall || met);
+        nodes.append(out);
+    }
+
+    QJsonObject group;
+    group["type"] = QStringLiteral("group");
+    group["logic"] = is_and ? QStringLiteral("AND") : QStringLiteral("OR");
+    group["met"] = !children.isEmpty() && overall;
+    group["children"] = nodes;
+    return group;
+}
+
+void BacktestTradeInspector::collect_indicators(const QJsonArray& children, QJsonObject& out) {
+    for (const auto& val : children) {
+        const QJsonObject node = val.toObject();
+        if (ConditionEvaluator::is_group_node(node)) {
+            collect_indicators(node.value("children").toArray(), out);
+            continue;
+        }
+        const auto cond = ConditionEvaluator::parse_condition(node);
+        if (!cond.indicator.isEmpty())
+            out[indicator_key(cond.indicator, cond.params)] =
+                QJsonObject{{"indicator", cond.indicator}, {"params", cond.params}};
+        if (cond.compare_mode == "indicator" && !cond.compare_indicator.isEmpty())
+            out[indicator_key(cond.compare_indicator, cond.compare_params)] =
+                QJsonObject{{"indicator", cond.compare_indicator}, {"params", cond.compare_params}};
+    }
+}
+
+QJsonObject BacktestTradeInspector::indicator_values(const QJsonObject& refs, const QVector<OhlcvCandle>& window) {
+    QJsonObject out;
+    for (auto it = refs.constBegin(); it != refs.constEnd(); ++it) {
+        const QJsonObject ref = it.value().toObject();
+        const IndicatorResult r =
+            IndicatorEngine::compute(ref.value("indicator").toString(), window, ref.value("params").toObject(), {});
+        QJsonObject fields;
+        if (r.valid) {
+            for (auto f = r.current.constBegin(); f != r.current.constEnd(); ++f)
+                fields[f.key()] = num_or_null(f.value());
+        } else {
+            fields["error"] = r.error;
+        }
+        out[it.key()] = fields;
+    }
+    return out;
+}
+
+std::optional<bool> BacktestTradeInspector::node_met(const QString& path, const QJsonArray& entry_conditions,
+                                                    const QString& entry_logic, const QJsonArray& exit_conditions,
+                                                    const QString& exit_logic, const QVector<OhlcvCandle>& window) {
+    const QStringList parts = path.split('.');
+    const bool is_entry = parts.first() == QLatin1String("entry");
+    if (!is_entry && parts.first() != QLatin1String("exit"))
+        return std::nullopt;
+
+    // Walk down: numeric parts index the current children, "children" is the
+    // (optional) hop into a group's children.
+    QJsonArray children = is_entry ? entry_conditions : exit_conditions;
+    QString logic = is_entry ? entry_logic : exit_logic;
+    std::optional<QJsonObject> node;
+    for (int i = 1; i < parts.size(); ++i) {
+        if (parts[i] == QLatin1String("children")) {
+            if (!node || !ConditionEvaluator::is_group_node(*node))
+                return std::nullopt;
+            continue;
+        }
+        if (node) {
+            if (!ConditionEvaluator::is_group_node(*node))
+                return std::nullopt;
+            children = node->value("children").toArray();
+        }
+        bool ok = false;
+        const int index = parts[i].toInt(&ok);
+        if (!ok || index < 0 || index >= children.size())
+            return std::nullopt;
+        node = children.at(index).toObject();
+    }
+
+    if (!node)
+        return !children.isEmpty() && ConditionEvaluator::evaluate_group(children, logic, window).triggered;
+    if (ConditionEvaluator::is_group_node(*node)) {
+        const auto sub = ConditionEvaluator::evaluate_group(
+            node->value("children").toArray(), node->value("logic").toString(node->value("op").toString("AND")),
+            window);
+        return sub.triggered != node->value("negate").toBool(false);
+    }
+    return ConditionEvaluator::evaluate_single(ConditionEvaluator::parse_condition(*node), window).met;
+}
+
+QJsonObject BacktestTradeInspector::step(const QVector<OhlcvCandle>& candles, const QJsonArray& entry_conditions,
+                                         const QString& entry_logic, const QJsonArray& exit_conditions,
+                                         const QString& exit_logic, int from_bar, const QStringList& breakpoints,
+                                         bool backward) {
+    const int n = candles.size();
+    const QString e_logic = entry_logic.isEmpty() ? QStringLiteral("AND") : entry_logic;
+    const QString x_logic = exit_logic.isEmpty() ? QStringLiteral("AND") : exit_logic;
+    auto fail = [](const QString& msg) { return QJsonObject{{"success", false}, {"error", msg}}; };
+    if (n == 0)
+        return fail(QStringLiteral("No candles loaded"));
+    for (const auto& bp : breakpoints)
+        if (!node_met(bp, entry_conditions, e_logic, exit_conditions, x_logic, window_ending_at(candles, 0)))
+            return fail(QString("Breakpoint '%1' addresses no rule").arg(bp));
+
+    const int dir = backward ? -1 : 1;
+    int bar = std::clamp(from_bar + dir, 0, n - 1);
+    QString hit;
+    bool end_of_data = false;
+    if (!breakpoints.isEmpty()) {
+        // met(bar) && !met(bar - 1), per breakpoint.
+        auto met_at = [&](const QString& bp, int b) {
+            return b >= 0 && node_met(bp, entry_conditions, e_logic, exit_conditions, x_logic,
+                                      window_ending_at(candles, b))
+                                 .value_or(false);
+        };
+        QHash<QString, bool> prev; // forward scan: met on the bar before, carried over
+        for (; bar >= 0 && bar < n && hit.isEmpty(); bar += dir) {
+            for (const auto& bp : breakpoints) {
+                const bool now = met_at(bp, bar);
+                const bool before = (!backward && prev.contains(bp)) ? prev.value(bp) : met_at(bp, bar - 1);
+                prev[bp] = now;
+                if (now && !before) {
+                    hit = bp;
+                    break;
+                }
+            }
+        }
+        bar -= dir; // the loop stepped past the hit (or off the range)
+        end_of_data = hit.isEmpty();
+        bar = std::clamp(bar, 0, n - 1);
+    } else {
+        end_of_data = from_bar + dir < 0 || from_bar + dir >= n;
+    }
+
+    QJsonObject refs;
+    collect_indicators(entry_conditions, refs);
+    collect_indicators(exit_conditions, refs);
+    const auto w = window_ending_at(candles, bar);
+
+    QJsonObject out;
+    out["success"] = true;
+    out["bar"] = bar;
+    out["bars"] = n;
+    out["hit"] = hit;
+    out["end_of_data"] = end_of_data;
+    out["candle"] = candle_json(candles[bar], bar);
+    out["entry"] = evaluate_tree(entry_conditions, e_logic, w);
+    out["exit"] = evaluate_tree(exit_conditions, x_logic, w);
+    out["indicators"] = indicator_values(refs, w);
+    return out;
+}
+
+QJsonObject BacktestTradeInspector::inspect(const QVector<OhlcvCandle>& candles, const QJsonObject& trade,
+                                            const QJsonArray& entry_conditions, const QString& entry_logic,
+                                            const QJsonArray& exit_conditions, const QString& exit_logic,
+                                            int context_bars) {
+    const int n = candles.size();
+    const int entry_bar = trade.value("entry_bar").toInt(-1);
+    const int exit_bar = trade.value("exit_bar").toInt(-1);
+    if (entry_bar < 0 || exit_bar < entry_bar || exit_bar >= n) {
+        QJsonObject err;
+        err["success"] = false;
+        err["error"] = QStringLiteral("Trade bars [%1, %2] are outside the candle range (%3 bars)")
+                           .arg(entry_bar)
+                           .arg(exit_bar)
+                           .arg(n);
+        return err;
+    }
+    context_bars = std::clamp(context_bars, 0, 500);
+
+    // Older results (pre signal-bar tracking) only know the fill bar; every
+    // rule-driven fill happens on the open of the bar after the signal.
+    const int entry_signal_bar = trade.value("entry_signal_bar").toInt(entry_bar - 1);
+    const QString reason = trade.value("reason").toString();
+    const int exit_signal_bar =
+        trade.value("exit_signal_bar").toInt(reason == "exit_signal" ? exit_bar - 1 : -1);
+
+    QJsonObject refs;
+    collect_indicators(entry_conditions, refs);
+    collect_indicators(exit_conditions, refs);
+
+    // ── Surrounding candles ─────────────────────────────────────────────────
+    const int from = std::max(0, std::min(entry_signal_bar, entry_bar) - context_bars);
+    const int to = std::min(n - 1, exit_bar + context_bars);
+    QJsonArray window_out;
+    for (int i = from; i <= to; ++i)
+        window_out.append(candle_json(candles[i], i));
+
+    // ── Entry side ──────────────────────────────────────────────────────────
+    QJsonObject entry;
+    entry["fill_bar"] = entry_bar;
+    entry["fill_time"] = static_cast<double>(candles[entry_bar].open_time);
+    entry["fill_price"] = trade.value("entry_price");
+    entry["indicators_at_fill"] = indicator_values(refs, window_ending_at(candles, entry_bar));
+    if (entry_signal_bar >= 0 && entry_signal_bar < n) {
+        const auto w = window_ending_at(candles, entry_signal_bar);
+        entry["signal_bar"] = entry_signal_bar;
+        entry["signal_time"] = static_cast<double>(candles[entry_signal_bar].open_time);
+        entry["rules"] = evaluate_tree(entry_conditions, entry_logic.isEmpty() ? "AND" : entry_logic, w);
+        entry["indicators_at_signal"] = indicator_values(refs, w);
+    }
+
+    // ── Exit side ───────────────────────────────────────────────────────────
+    QJsonObject exit;
+    exit["fill_bar"] = exit_bar;
+    exit["fill_time"] = static_cast<double>(candles[exit_bar].open_time);
+    exit["fill_price"] = trade.value("exit_price");
+    exit["trigger"] = reason;
+    exit["indicators_at_fill"] = indicator_values(refs, window_ending_at(candles, exit_bar));
+    if (exit_signal_bar >= 0 && exit_signal_bar < n) {
+        const auto w = window_ending_at(candles, exit_signal_bar);
+        exit["signal_bar"] = exit_signal_bar;
+        exit["signal_time"] = static_cast<double>(candles[exit_signal_bar].open_time);
+        exit["rules"] = evaluate_tree(exit_conditions, exit_logic.isEmpty() ? "AND" : exit_logic, w);
+        exit["indicators_at_signal"] = indicator_values(refs, w);
+    }
+
+    QJsonObject out;
+    out["success"] = true;
+    out["trade"] = trade;
+    out["candles"] = window_out;
+    out["entry"] = entry;
+    out["exit"] = exit;
+    return out;
+}
+
+} // namespace fincept::algo
@@ -0,0 +1,62 @@
+// src/algo_engine/BacktestTradeInspector.h
+#pragma once
+#include "algo_engine/AlgoEngineTypes.h"
+
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+#include <optional>
+
+namespace fincept::algo {
+
+/// "Why did this trade happen" drill-down for a single BacktestEngine trade.
+///
+/// Pure computation over the same candles + conditions the backtest ran on.
+/// For the selected trade it returns:
+///   - `candles`  — `context_bars` bars either side of the entry→exit span,
+///                  each tagged with its absolute `bar` index;
+///   - `entry` / `exit` — the signal bar, the fill bar, the full (non
+///                  short-circuited) rule tree evaluated on the signal bar's
+///                  close, and every referenced indicator's value at both the
+///                  signal bar and the fill bar.
+///
+/// Stop-loss / take-profit / end-of-data exits have no rule evaluation; their
+/// `exit.rules` is empty and `exit.trigger` carries the stop/target reason.
+class BacktestTradeInspector {
+  public:
+    static QJsonObject inspect(const QVector<OhlcvCandle>& candles, const QJsonObject& trade,
+                               const QJsonArray& entry_conditions, const QString& entry_logic,
+                               const QJsonArray& exit_conditions, const QString& exit_logic, int context_bars = 20);
+
+    /// Debugger-style stepping over the same bars. Scans forward from the bar
+    /// after `from_bar` (backward from the one before it when `backward`) to
+    /// the first bar where a breakpoint hits and returns that bar's state: the
+    /// candle, both rule trees evaluated in full and every referenced
+    /// indicator's value. A breakpoint is a rule path — "entry", "exit" or a
+    /// node such as "entry.1" / "exit.0.children.2" — and hits where that node
+    /// is met but was not on the previous bar, so a condition that stays true
+    /// breaks once. With no breakpoints it steps a single bar. `hit` names the
+    /// breakpoint; `end_of_data` is set when the scan ran off the range.
+    static QJsonObject step(const QVector<OhlcvCandle>& candles, const QJsonArray& entry_conditions,
+                            const QString& entry_logic, const QJsonArray& exit_conditions, const QString& exit_logic,
+                            int from_bar, const QStringList& breakpoints, bool backward = false);
+
+  private:
+    /// Evaluates every node of a condition tree (no short-circuit) and returns
+    /// it as a nested JSON tree mirroring the input shape.
+    static QJsonObject evaluate_tree(const QJsonArray& children, const QString& logic,
+                                     const QVector<OhlcvCandle>& window);
+    static void collect_indicators(const QJsonArray& children, QJsonObject& out);
+    static QJsonObject indicator_values(const QJsonObject& refs, const QVector<OhlcvCandle>& window);
+    static QVector<OhlcvCandle> window_ending_at(const QVector<OhlcvCandle>& candles, int bar);
+    /// Whether the rule node at `path` ("entry", "exit.1.children.0" …) is met
+    /// on `window`; nullopt when the path addresses no node.
+    static std::optional<bool> node_met(const QString& path, const QJsonArray& entry_conditions,
+                                        const QString& entry_logic, const QJsonArray& exit_conditions,
+                                        const QString& exit_logic, const QVector<OhlcvCandle>& window);
+};
+
+} // namespace fincept::algo
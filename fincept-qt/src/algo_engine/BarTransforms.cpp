@@ -0,0 +1,139 @@
+// src/algo_engine/BarTransforms.cpp
+#include "algo_engine/BarTransforms.h"
+
+#include <algorithm>
+
+namespace fincept::algo {
+
+// ── Config ──────────────────────────────────────────────────────────────────
+
+BarTransform BarTransform::from_json(const QJsonObject& o, QString* error) {
+    BarTransform t;
+    const QString type = o.value("type").toString(QStringLiteral("time")).toLower();
+    if (type == "time" || type.isEmpty())
+        t.type = Type::Time;
+    else if (type == "heikin_ashi" || type == "heikinashi" || type == "ha")
+        t.type = Type::HeikinAshi;
+    else if (type == "renko")
+        t.type = Type::Renko;
+    else if (type == "range" || type == "range_bars")
+        t.type = Type::Range;
+    else if (error)
+        *error = QString("Unknown bar type '%1' (time, heikin_ashi, renko, range)").arg(type);
+
+    t.size = std::max(0.0, o.value("size").toDouble());
+    if ((t.type == Type::Renko || t.type == Type::Range) && t.size <= 0 && error && error->isEmpty())
+        *error = QString("Bar type '%1' needs a positive 'size' in price units").arg(type);
+    return t;
+}
+
+QJsonObject BarTransform::to_json() const {
+    static const char* kNames[] = {"time", "heikin_ashi", "renko", "range"};
+    QJsonObject o{{"type", kNames[int(type)]}};
+    if (type == Type::Renko || type == Type::Range)
+        o["size"] = size;
+    return o;
+}
+
+// ── Transforms ──────────────────────────────────────────────────────────────
+
+QVector<OhlcvCandle> BarTransforms::apply(const BarTransform& transform, const QVector<OhlcvCandle>& candles) {
+    switch (transform.type) {
+    case BarTransform::Type::HeikinAshi:
+        return heikin_ashi(candles);
+    case BarTransform::Type::Renko:
+        return renko(candles, transform.size);
+    case BarTransform::Type::Range:
+        return range_bars(candles, transform.size);
+    case BarTransform::Type::Time:
+        break;
+    }
+    return candles;
+}
+
+QVector<OhlcvCandle> BarTransforms::heikin_ashi(const QVector<OhlcvCandle>& candles) {
+    QVector<OhlcvCandle> out;
+    out.reserve(candles.size());
+    for (int i = 0; i < candles.size(); ++i) {
+        const auto& c = candles[i];
+        OhlcvCandle ha = c;
+        ha.close = (c.open + c.high + c.low + c.close) / 4.0;
+        ha.open = i == 0 ? (c.open + c.close) / 2.0 : (out.last().open + out.last().close) / 2.0;
+        ha.high = std::max({c.high, ha.open, ha.close});
+        ha.low = std::min({c.low, ha.open, ha.close});
+        out.append(ha);
+    }
+    return out;
+}
+
+QVector<OhlcvCandle> BarTransforms::renko(const QVector<OhlcvCandle>& candles, double brick) {
+    QVector<OhlcvCandle> out;
+    if (candles.isEmpty() || brick <= 0)
+        return out;
+
+    // [bottom, top] is the body of the last brick; before the first one both
+    // sit on the first close.
+    double top = candles.first().close;
+    double bottom = top;
+    double volume = 0;
+    int64_t open_time = candles.first().open_time;
+
+    auto emit_brick = [&](double open, double close, const OhlcvCandle& src) {
+        OhlcvCandle b;
+        b.open_time = open_time;
+        b.close_time = src.close_time;
+        b.open = open;
+        b.close = close;
+        b.high = std::max(open, close);
+        b.low = std::min(open, close);
+        b.volume = volume;
+        b.is_closed = true;
+        out.append(b);
+        volume = 0;
+        open_time = src.open_time;
+    };
+
+    for (const auto& c : candles) {
+        volume += c.volume;
+        while (c.close >= top + brick) {
+            emit_brick(top, top + brick, c);
+            bottom = top;
+            top += brick;
+        }
+        while (c.close <= bottom - brick) {
+            emit_brick(bottom, bottom - brick, c);
+            top = bottom;
+            bottom -= brick;
+        }
+    }
+    return out;
+}
+
+QVector<OhlcvCandle> BarTransforms::range_bars(const QVector<OhlcvCandle>& candles, double range) {
+    QVector<OhlcvCandle> out;
+    if (range <= 0)
+        return out;
+
+    bool open_bar = false;
+    OhlcvCandle bar;
+    for (const auto& c : candles) {
+        if (!open_bar) {
+            bar = c;
+            open_bar = true;
+        } else {
+            bar.high = std::max(bar.high, c.high);
+            bar.low = std::min(bar.low, c.low);
+            bar.close = c.close;
+            bar.close_time = c.close_time;
+            bar.volume += c.volume;
+        }
+        if (bar.high - bar.low >= range) {
+            bar.is_closed = true;
+            out.append(bar);
+            open_bar = false;
+        }
+    }
+    return out;
+}
+
+} // namespace fincept::algo
@@ -0,0 +1,50 @@
+// src/algo_engine/BarTransforms.h
+#pragma once
+#include "algo_engine/AlgoEngineTypes.h"
+
+#include <QJsonObject>
+#include <QString>
+#include <QVector>
+
+namespace fincept::algo {
+
+/// Which bars a strategy sees in place of plain time bars.
+///   time        — the source bars unchanged;
+///   heikin_ashi — averaged candles (HA close = (O+H+L+C)/4, HA open = midpoint
+///                 of the previous HA body), one per source bar;
+///   renko       — close-driven bricks of `size` price units; a reversal needs
+///                 a move of two bricks from the last brick's close;
+///   range       — consecutive source bars merged until their high − low
+///                 reaches `size` price units.
+struct BarTransform {
+    enum class Type { Time, HeikinAshi, Renko, Range };
+    Type type = Type::Time;
+    double size = 0; // brick / range size in price units (renko, range)
+
+    bool is_identity() const { return type == Type::Time; }
+
+    /// {type, size}. An unknown type or a missing size for renko / range sets
+    /// `error`.
+    static BarTransform from_json(const QJsonObject& o, QString* error = nullptr);
+    QJsonObject to_json() const;
+};
+
+/// Chart-type transforms over OHLCV bars. Output bars keep the open_time of
+/// the source bar they start in and the close_time of the one they end in, so
+/// several bricks built inside one source bar share its times. Renko and range
+/// bars are only emitted once complete — the unfinished last one is dropped —
+/// and both are built from source bars, not ticks: a source bar wider than the
+/// range still yields one range bar, and bricks follow closes only.
+///
+/// Prices on transformed bars are synthetic: a backtest on them fills at
+/// prices that may never have traded.
+class BarTransforms {
+  public:
+    static QVector<OhlcvCandle> apply(const BarTransform& transform, const QVector<OhlcvCandle>& candles);
+
+    static QVector<OhlcvCandle> heikin_ashi(const QVector<OhlcvCandle>& candles);
+    static QVector<OhlcvCandle> renko(const QVector<OhlcvCandle>& candles, double brick);
+    static QVector<OhlcvCandle> range_bars(const QVector<OhlcvCandle>& candles, double range);
+};
+
+} // namespace fincept::algo
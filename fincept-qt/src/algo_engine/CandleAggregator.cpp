@@ -1,8 +1,11 @@
 // src/algo_engine/CandleAggregator.cpp
 #include "algo_engine/CandleAggregator.h"
 
+#include <QMap>
 #include <QMutexLocker>
 
+#include <algorithm>
+
 namespace fincept::algo {
 
 CandleAggregator::CandleAggregator(const QString& symbol, Timeframe tf, int buffer_size, QObject* parent)
@@ -90,4 +93,64 @@ void CandleAggregator::warm_from(const QVector<OhlcvCandle>& historical) {
     }
 }
 
+int CandleAggregator::fill_gap(const QVector<OhlcvCandle>& bars) {
+    // Roll the outage bars up to this timeframe first.
+    QVector<OhlcvCandle> sorted = bars;
+    std::sort(sorted.begin(), sorted.end(),
+              [](const OhlcvCandle& a, const OhlcvCandle& b) { return a.open_time < b.open_time; });
+    QMap<int64_t, OhlcvCandle> rolled;
+    QMap<int64_t, int64_t> first_bar; // period start -> earliest outage bar in it
+    for (const auto& b : sorted) {
+        const int64_t start = align_to_period(b.open_time);
+        auto it = rolled.find(start);
+        if (it == rolled.end()) {
+            first_bar.insert(start, b.open_time);
+            OhlcvCandle c = b;
+            c.open_time = start;
+            c.close_time = start + period_ms_;
+            c.is_closed = true;
+            rolled.insert(start, c);
+            continue;
+        }
+        it->high = std::max(it->high, b.high);
+        it->low = std::min(it->low, b.low);
+        it->close = b.close;
+        it->volume += b.volume;
+    }
+
+    QMutexLocker lock(&mutex_);
+    if (!has_first_tick_)
+        return 0; // nothing streamed yet, so there is no hole to patch
+    int changed = 0;
+    for (const auto& g : rolled) {
+        if (g.open_time > current_.open_time)
+            continue;
+        if (g.open_time == current_.open_time) {
+            // Ticks since the reconnect cover the tail of this candle; the
+            // outage also has its open when it began before the period did.
+            if (first_bar.value(g.open_time) == g.open_time)
+                current_.open = g.open;
+            current_.high = std::max(current_.high, g.high);
+            current_.low = std::min(current_.low, g.low);
+            current_.volume = std::max(current_.volume, g.volume);
+            continue;
+        }
+        auto pos = std::lower_bound(buffer_.begin(), buffer_.end(), g.open_time,
+                                    [](const OhlcvCandle& c, int64_t t) { return c.open_time < t; });
+        if (pos != buffer_.end() && pos->open_time == g.open_time) {
+            // Closed early by the first tick after the outage — the outage has the rest.
+            pos->high = std::max(pos->high, g.high);
+            pos->low = std::min(pos->low, g.low);
+            pos->close = g.close;
+            pos->volume = std::max(pos->volume, g.volume);
+        } else {
+            buffer_.insert(pos, g);
+        }
+        ++changed;
+    }
+    while (buffer_.size() > max_buffer_)
+        buffer_.removeFirst();
+    return changed;
+}
+
 } // namespace fincept::algo
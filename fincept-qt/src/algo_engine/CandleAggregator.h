@@ -21,6 +21,14 @@ class CandleAggregator : public QObject {
 
     void warm_from(const QVector<OhlcvCandle>& historical);
 
+    /// Merges bars covering a feed outage (this timeframe or finer, e.g. the
+    /// 1m bars of a BrokerGapFill) into the series: periods the outage skipped
+    /// are inserted, the period it cut short is completed, and the open candle
+    /// takes its start from the outage. No candle_closed is emitted for them —
+    /// nothing should trade on bars that closed while the feed was down.
+    /// Returns the number of closed periods inserted or changed.
+    int fill_gap(const QVector<OhlcvCandle>& bars);
+
   signals:
     void candle_closed(const fincept::algo::OhlcvCandle& candle);
 
@@ -2,6 +2,8 @@
 #include "algo_engine/CandleDataFetcher.h"
 
 #include "core/logging/Logger.h"
+#include "services/futures/FuturesService.h"
+#include "services/markets/CustomIndexService.h"
 #include "trading/AccountManager.h"
 #include "trading/BrokerRegistry.h"
 #include "trading/HistoricalDataService.h"
@@ -182,6 +184,28 @@ QVector<OhlcvCandle> CandleDataFetcher::broker_candles_to_ohlcv(const QVector<tr
     return out;
 }
 
+QVector<OhlcvCandle> CandleDataFetcher::continuous_candles(const QString& symbol, const QString& timeframe,
+                                                           int lookback_days, QString* error) {
+    const qint64 to_ms = QDateTime::currentMSecsSinceEpoch();
+    const qint64 from_ms = to_ms - qint64(lookback_days > 0 ? lookback_days : 3650) * 86400 * 1000;
+    const auto series =
+        services::futures::FuturesService::instance().continuous(symbol, timeframe, from_ms, to_ms, error);
+    return broker_candles_to_ohlcv(series.candles, timeframe);
+}
+
+QVector<OhlcvCandle> CandleDataFetcher::custom_index_candles(const QString& symbol, const QString& timeframe,
+                                                             int lookback_days, QString* error) {
+    if (timeframe != "1d") {
+        if (error)
+            *error = QStringLiteral("Custom indices have daily bars only");
+        return {};
+    }
+    const qint64 from_ms =
+        lookback_days > 0 ? QDateTime::currentMSecsSinceEpoch() - qint64(lookback_days) * 86400 * 1000 : 0;
+    const auto candles = services::CustomIndexService::instance().candles(symbol, from_ms, 0, error);
+    return broker_candles_to_ohlcv(candles, timeframe);
+}
+
 QString CandleDataFetcher::timeframe_to_broker_resolution(const QString& tf) {
     if (tf == "1m")
         return QStringLiteral("1");
@@ -204,6 +228,18 @@ QString CandleDataFetcher::timeframe_to_broker_resolution(const QString& tf) {
 
 void CandleDataFetcher::fetch(const QString& symbol, const QString& timeframe, int lookback_days, DataSource source,
                               const QString& broker_id, const QString& account_id, CandleCallback callback) {
+    if (services::futures::is_continuous_symbol(symbol)) {
+        QString err;
+        const auto candles = continuous_candles(symbol, timeframe, lookback_days, &err);
+        callback(!candles.isEmpty(), candles, err);
+        return;
+    }
+    if (services::CustomIndexService::is_index_symbol(symbol)) {
+        QString err;
+        const auto candles = custom_index_candles(symbol, timeframe, lookback_days, &err);
+        callback(!candles.isEmpty(), candles, err);
+        return;
+    }
     if (source == DataSource::Broker || (source == DataSource::Auto && !broker_id.isEmpty())) {
         fetch_from_broker(
             symbol, timeframe, lookback_days, broker_id, account_id,
@@ -242,6 +278,40 @@ void CandleDataFetcher::fetch(const QString& symbol, const QString& timeframe, i
 void CandleDataFetcher::fetch_multi(const QStringList& symbols, const QString& timeframe, int lookback_days,
                                     DataSource source, const QString& broker_id, const QString& account_id,
                                     MultiCandleCallback callback) {
+    // Serve continuous futures and custom indices from the store, fetch the rest, then merge.
+    QStringList direct;
+    QHash<QString, QVector<OhlcvCandle>> stitched;
+    QStringList stitch_errors;
+    for (const auto& sym : symbols) {
+        const bool index = services::CustomIndexService::is_index_symbol(sym);
+        if (!index && !services::futures::is_continuous_symbol(sym)) {
+            direct.append(sym);
+            continue;
+        }
+        QString err;
+        const auto candles = index ? custom_index_candles(sym, timeframe, lookback_days, &err)
+                                   : continuous_candles(sym, timeframe, lookback_days, &err);
+        if (candles.isEmpty())
+            stitch_errors.append(QString("%1: %2").arg(sym, err));
+        else
+            stitched.insert(sym, candles);
+    }
+    if (direct.size() != symbols.size()) {
+        if (direct.isEmpty()) {
+            callback(stitched, stitch_errors);
+            return;
+        }
+        fetch_multi(direct, timeframe, lookback_days, source, broker_id, account_id,
+                    [callback, stitched, stitch_errors](const QHash<QString, QVector<OhlcvCandle>>& data,
+                                                        const QStringList& errors) {
+                        auto merged = stitched;
+                        for (auto it = data.begin(); it != data.end(); ++it)
+                            merged.insert(it.key(), it.value());
+                        callback(merged, stitch_errors + errors);
+                    });
+        return;
+    }
+
     if (source == DataSource::Broker || (source == DataSource::Auto && !broker_id.isEmpty())) {
         QPointer<CandleDataFetcher> self = this;
         auto results = std::make_shared<QHash<QString, QVector<OhlcvCandle>>>();
@@ -61,6 +61,15 @@ class CandleDataFetcher : public QObject {
     void fetch_from_yahoo(const QStringList& symbols, const QString& timeframe, int lookback_days,
                           MultiCandleCallback callback);
 
+    // Continuous futures (ES#C) are stitched from cached contracts by
+    // services::futures::FuturesService instead of being fetched.
+    static QVector<OhlcvCandle> continuous_candles(const QString& symbol, const QString& timeframe, int lookback_days,
+                                                   QString* error);
+    // Custom indices (@NAME) are daily series services::CustomIndexService
+    // builds from the candle cache.
+    static QVector<OhlcvCandle> custom_index_candles(const QString& symbol, const QString& timeframe,
+                                                     int lookback_days, QString* error);
+
     static QVector<OhlcvCandle> broker_candles_to_ohlcv(const QVector<fincept::trading::BrokerCandle>& src,
                                                         const QString& timeframe);
     static QString timeframe_to_broker_resolution(const QString& tf);
@@ -3,6 +3,7 @@
 
 #include <QJsonObject>
 
+#include <algorithm>
 #include <cmath>
 #include <limits>
 
@@ -42,6 +43,30 @@ bool ConditionEvaluator::is_group_node(const QJsonObject& node) {
     return node.contains("children") || node.value("type").toString() == "group";
 }
 
+namespace {
+// Deepest group level and leaf count of a rule array (depth 1 = top level).
+void ce_measure(const QJsonArray& children, int depth, int* max_depth, int* leaves) {
+    *max_depth = std::max(*max_depth, depth);
+    for (const auto& v : children) {
+        const QJsonObject node = v.toObject();
+        if (ConditionEvaluator::is_group_node(node))
+            ce_measure(node.value("children").toArray(), depth + 1, max_depth, leaves);
+        else
+            ++*leaves;
+    }
+}
+} // namespace
+
+QString ConditionEvaluator::check_limits(const QJsonArray& children) {
+    int depth = 0, leaves = 0;
+    ce_measure(children, 1, &depth, &leaves);
+    if (depth > kMaxGroupDepth)
+        return QString("groups nest %1 levels deep (limit %2)").arg(depth).arg(kMaxGroupDepth);
+    if (leaves > kMaxConditions)
+        return QString("%1 conditions (limit %2)").arg(leaves).arg(kMaxConditions);
+    return {};
+}
+
 bool ConditionEvaluator::apply_comparison(double lhs, const QString& op, double rhs) {
     if (std::isnan(lhs) || std::isnan(rhs))
         return false;
@@ -169,6 +194,11 @@ ConditionResult ConditionEvaluator::evaluate_single(const ConditionDef& conditio
 
 GroupEvalResult ConditionEvaluator::evaluate_group(const QJsonArray& children, const QString& logic,
                                                    const QVector<OhlcvCandle>& candles) {
+    return evaluate_group_at(children, logic, candles, 1);
+}
+
+GroupEvalResult ConditionEvaluator::evaluate_group_at(const QJsonArray& children, const QString& logic,
+                                                      const QVector<OhlcvCandle>& candles, int depth) {
 
     GroupEvalResult group;
     group.logic = logic;
@@ -185,9 +215,16 @@ GroupEvalResult ConditionEvaluator::evaluate_group(const QJsonArray& children, c
         const QJsonObject node = val.toObject();
 
         bool met;
-        if (is_group_node(node)) {
-            auto sub = evaluate_group(node.value("children").toArray(),
-                                      node.value("logic").toString(node.value("op").toString("AND")), candles);
+        if (is_group_node(node) && depth >= kMaxGroupDepth) {
+            // Too deep to evaluate: not met, even when negated.
+            ConditionResult r;
+            r.error = QString("group nesting exceeds %1 levels").arg(kMaxGroupDepth);
+            group.details.append(r);
+            met = false;
+        } else if (is_group_node(node)) {
+            auto sub = evaluate_group_at(node.value("children").toArray(),
+                                         node.value("logic").toString(node.value("op").toString("AND")), candles,
+                                         depth + 1);
             met = node.value("negate").toBool(false) ? !sub.triggered : sub.triggered;
             group.details.append(sub.details); // flatten nested detail for reporting
         } else {
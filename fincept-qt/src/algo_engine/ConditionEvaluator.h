@@ -26,17 +26,34 @@ namespace fincept::algo {
 /// N bars back is obtained by recomputing the indicator on the window truncated
 /// by N bars. `crosses_*` / `rising` / `falling` read the operand one extra bar
 /// back.
+///
+/// Rules are bounded so one evaluation can't stall the engine thread: groups
+/// nest at most kMaxGroupDepth deep (deeper groups evaluate as not met, with an
+/// error detail) and a rule set holds at most kMaxConditions leaves. Deploying
+/// or hot-reloading rules over either cap is refused (check_limits).
 class ConditionEvaluator {
   public:
+    static constexpr int kMaxGroupDepth = 16;
+    static constexpr int kMaxConditions = 128;
+
     static ConditionResult evaluate_single(const fincept::services::algo::ConditionDef& condition,
                                            const QVector<OhlcvCandle>& candles);
 
     static GroupEvalResult evaluate_group(const QJsonArray& children, const QString& logic,
                                           const QVector<OhlcvCandle>& candles);
 
-  private:
+    /// Leaf node → ConditionDef, for BacktestTradeInspector's per-leaf drill-down.
     static fincept::services::algo::ConditionDef parse_condition(const QJsonObject& obj);
+    /// True for `{children, logic}` group nodes (or `type: "group"`); every
+    /// rule-tree walker (trade inspector, StrategyAnalyzer) branches on it.
     static bool is_group_node(const QJsonObject& node);
+
+    /// The first cap `children` exceeds, as a message; empty when within them.
+    static QString check_limits(const QJsonArray& children);
+
+  private:
+    static GroupEvalResult evaluate_group_at(const QJsonArray& children, const QString& logic,
+                                             const QVector<OhlcvCandle>& candles, int depth);
     static bool apply_comparison(double lhs, const QString& op, double rhs);
     static bool apply_crossing(double curr, double prev, double target_curr, double target_prev, const QString& op);
     /// Resolves an indicator operand `offset` bars back. Returns NaN and sets
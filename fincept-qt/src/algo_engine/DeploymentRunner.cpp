@@ -5,6 +5,7 @@
 #include "algo_engine/ConditionEvaluator.h"
 #include "algo_engine/fno/FnoExecution.h"
 #include "core/logging/Logger.h"
+#include "core/market/MarketCalendar.h"
 #include "datahub/DataHub.h"
 #include "services/options/OptionChainService.h"
 #include "storage/sqlite/Database.h"
@@ -19,6 +20,7 @@ using fincept::Database;
 
 #include <QDate>
 #include <QDateTime>
+#include <QElapsedTimer>
 #include <QJsonArray>
 #include <QJsonDocument>
 #include <QPointer>
@@ -27,6 +29,8 @@ using fincept::Database;
 #include <QVariant>
 #include <QtConcurrent>
 
+#include <cmath>
+
 namespace fincept::algo {
 
 DeploymentRunner::DeploymentRunner(const services::algo::AlgoDeployment& deployment,
@@ -48,6 +52,7 @@ DeploymentRunner::DeploymentRunner(const services::algo::AlgoDeployment& deploym
     position_mgr_ = std::make_unique<PositionManager>(deployment.id, strategy.stop_loss, strategy.take_profit,
                                                       strategy.trailing_stop, deployment.max_order_value,
                                                       deployment.max_daily_loss);
+    position_mgr_->set_max_position_value(deployment.max_position_value);
 
     heartbeat_timer_ = new QTimer(this);
     heartbeat_timer_->setInterval(5000);
@@ -149,6 +154,18 @@ void DeploymentRunner::start_market_data() {
         [self](const trading::BrokerQuote& q) {
             if (self && self->running_.load() && !self->paused_.load())
                 self->on_tick_data(QVariant::fromValue(q));
+        },
+        // After a feed outage: patch the skipped bars so indicators don't read
+        // the first post-reconnect tick as one long candle.
+        [self](const trading::BrokerGapFill& fill) {
+            if (!self || !self->running_.load())
+                return;
+            const auto bars = CandleDataFetcher::broker_candles_to_ohlcv(fill.candles, QStringLiteral("1m"));
+            const int n = self->aggregator_->fill_gap(bars);
+            LOG_INFO("AlgoEngine", QString("Deployment %1: gap-filled %2 candle(s) from %3 bars after feed outage")
+                                       .arg(self->deployment_.id)
+                                       .arg(n)
+                                       .arg(fill.candles.size()));
         });
 
     // F&O basket reattached across a restart: re-establish the chain stream and
@@ -278,10 +295,38 @@ void DeploymentRunner::evaluate_live(double price) {
     auto candles = live_eval_window(price);
     if (candles.size() < 2)
         return;
+    evaluate_rules(candles);
+}
+
+void DeploymentRunner::evaluate_rules(const QVector<OhlcvCandle>& candles) {
+    QElapsedTimer timer;
+    timer.start();
     if (in_position())
         evaluate_exit(candles);
     else
         evaluate_entry(candles);
+
+    // Watchdog: rules that keep overrunning the budget would starve every other
+    // deployment on the engine thread — pause this one instead.
+    const qint64 elapsed = timer.elapsed();
+    if (elapsed <= kEvalBudgetMs) {
+        slow_evals_ = 0;
+        return;
+    }
+    LOG_WARN("AlgoEngine", QString("Deployment %1: rule evaluation took %2 ms (budget %3 ms)")
+                               .arg(deployment_.id)
+                               .arg(elapsed)
+                               .arg(kEvalBudgetMs));
+    if (++slow_evals_ < kMaxSlowEvals)
+        return;
+    slow_evals_ = 0;
+    const QString msg = QString("Rule evaluation overran its %1 ms budget %2 times in a row — deployment paused; "
+                                "simplify the rules or use a slower timeframe")
+                            .arg(kEvalBudgetMs)
+                            .arg(kMaxSlowEvals);
+    LOG_ERROR("AlgoEngine", QString("Deployment %1: %2").arg(deployment_.id, msg));
+    pause();
+    emit error_occurred(deployment_.id, msg);
 }
 
 bool DeploymentRunner::in_position() const {
@@ -357,18 +402,25 @@ void DeploymentRunner::on_candle_closed(const OhlcvCandle& /*candle*/) {
     if (candles.size() < 20)
         return;
 
-    if (in_position()) {
-        evaluate_exit(candles);
-    } else {
-        evaluate_entry(candles);
-    }
+    evaluate_rules(candles);
 
     auto m = position_mgr_->metrics();
     m.last_signal_time = QDateTime::currentMSecsSinceEpoch();
     emit metrics_updated(deployment_.id, m);
 }
 
+bool DeploymentRunner::market_closed() const {
+    return !deployment_.exchange.isEmpty() &&
+           !core::market::MarketCalendar::instance().is_market_open(deployment_.exchange);
+}
+
 void DeploymentRunner::evaluate_entry(const QVector<OhlcvCandle>& candles) {
+    if (halted_.load())
+        return;
+    // A stale quote outside the session would open a position the broker
+    // can't fill (or fills at the next open, far from the signal price).
+    if (market_closed())
+        return;
     auto result = ConditionEvaluator::evaluate_group(strategy_.entry_conditions, strategy_.entry_logic, candles);
 
     if (!result.triggered)
@@ -415,6 +467,16 @@ void DeploymentRunner::evaluate_entry(const QVector<OhlcvCandle>& candles) {
             return;
         }
 
+        double notional = 0;
+        for (const auto& l : legs)
+            notional += std::abs(l.price * l.quantity);
+        if (!position_mgr_->validate_position_value(notional)) {
+            LOG_WARN("AlgoEngine", QString("Deployment %1: basket value %2 exceeds the position limit, skipping entry")
+                                       .arg(deployment_.id)
+                                       .arg(notional, 0, 'f', 0));
+            return;
+        }
+
         // Pin the resolved leg symbols into the live WS subscription window so
         // they stay fresh for mark-to-market during the life of the position.
         QStringList syms;
@@ -437,6 +499,11 @@ void DeploymentRunner::evaluate_entry(const QVector<OhlcvCandle>& candles) {
         LOG_WARN("AlgoEngine", QString("Deployment %1: order value exceeds limit, skipping entry").arg(deployment_.id));
         return;
     }
+    if (!position_mgr_->validate_position_value(signal.quantity * candles.last().close)) {
+        LOG_WARN("AlgoEngine",
+                 QString("Deployment %1: position value exceeds limit, skipping entry").arg(deployment_.id));
+        return;
+    }
 
     emit_order_signal(signal);
 }
@@ -479,11 +546,23 @@ void DeploymentRunner::emit_order_signal(const AlgoOrderSignal& signal_in) {
     // entry/exit on every candle) until the fill lands — flooding duplicate orders.
     if (!pending_orders_.isEmpty())
         return;
+    if (signal_in.reason == QLatin1String("entry_signal") && halted_.load())
+        return;
+    if (!order_rate_allows(signal_in))
+        return;
+    if (signal_in.reason == QLatin1String("daily_loss_limit") || signal_in.reason == QLatin1String("position_limit")) {
+        const QString msg = signal_in.reason == QLatin1String("daily_loss_limit")
+                                ? QStringLiteral("Daily loss limit reached — flattening; no new entries today")
+                                : QStringLiteral("Open position value above the limit — flattening");
+        LOG_WARN("AlgoEngine", QString("Deployment %1: %2").arg(deployment_.id, msg));
+        emit error_occurred(deployment_.id, msg);
+    }
 
-    // Stamp the deployment's mode so the engine routes paper→simulated-fill and
-    // live→broker. This is the single safety gate that stops a PAPER deployment
-    // from ever hitting a real broker, even though it carries a real account id
-    // (the account is its data source).
+    // Stamp the deployment's mode so the engine routes paper→simulated-fill,
+    // shadow→hypothetical fill (nothing routed) and live→broker. This is the
+    // single safety gate that stops a PAPER or SHADOW deployment from ever
+    // hitting a real broker, even though it carries a real account id (the
+    // account is its data source).
     AlgoOrderSignal signal = signal_in;
     signal.mode = deployment_.mode;
     signal.paper_portfolio_id = deployment_.paper_portfolio_id; // paper basket target
@@ -504,6 +583,144 @@ void DeploymentRunner::emit_order_signal(const AlgoOrderSignal& signal_in) {
                  .arg(deployment_.id, signal.side, QString::number(signal.quantity), signal.symbol, signal.reason));
 }
 
+bool DeploymentRunner::order_rate_allows(const AlgoOrderSignal& signal) {
+    if (deployment_.max_orders_per_minute <= 0)
+        return true;
+    const int64_t now = QDateTime::currentMSecsSinceEpoch();
+    while (!order_times_.isEmpty() && now - order_times_.first() >= 60000)
+        order_times_.removeFirst();
+    if (signal.reason == QLatin1String("entry_signal") && order_times_.size() >= deployment_.max_orders_per_minute) {
+        if (!rate_limit_noted_) {
+            rate_limit_noted_ = true;
+            const QString msg = QString("Order rate limit (%1/min) reached — entry dropped")
+                                    .arg(deployment_.max_orders_per_minute);
+            LOG_WARN("AlgoEngine", QString("Deployment %1: %2").arg(deployment_.id, msg));
+            emit error_occurred(deployment_.id, msg);
+        }
+        return false;
+    }
+    rate_limit_noted_ = false;
+    order_times_.append(now);
+    return true;
+}
+
+// ── Strategy hot reload ──────────────────────────────────────────────────────
+
+void DeploymentRunner::reload_strategy(const services::algo::AlgoStrategy& strategy) {
+    auto refuse = [this](const QString& msg) {
+        LOG_WARN("AlgoEngine", QString("Deployment %1: strategy reload refused — %2").arg(deployment_.id, msg));
+        emit strategy_reloaded(deployment_.id, false, msg);
+    };
+    if (!running_.load())
+        return refuse(QStringLiteral("deployment is not running"));
+    if (strategy.entry_conditions.isEmpty())
+        return refuse(QStringLiteral("the new strategy has no entry conditions"));
+    for (const auto* rules : {&strategy.entry_conditions, &strategy.exit_conditions})
+        if (const QString over = ConditionEvaluator::check_limits(*rules); !over.isEmpty())
+            return refuse(QString("the new rules exceed the evaluation limits: %1").arg(over));
+
+    services::algo::AlgoStrategy next = strategy;
+    if (next.legs.isEmpty())
+        next.legs = strategy_.legs;
+    if (next.timeframe.isEmpty())
+        next.timeframe = strategy_.timeframe;
+    // The deployment's own timeframe wins; otherwise the runner follows the strategy's.
+    if (deployment_.timeframe.isEmpty() && next.timeframe.compare(strategy_.timeframe, Qt::CaseInsensitive) != 0)
+        return refuse(QString("timeframe change (%1 → %2) needs a redeploy").arg(strategy_.timeframe, next.timeframe));
+    if (position_mgr_->has_legs() && next.legs != strategy_.legs)
+        return refuse(QStringLiteral("leg rules can't change while a basket is open"));
+
+    // Dry-run the new rules on the current bars so a typo shows up now rather
+    // than as a rule that silently never fires.
+    QStringList warnings;
+    const auto candles = aggregator_->closed_candles();
+    if (candles.size() >= 20) {
+        const auto check = [&](const QJsonArray& rules, const QString& logic, const QString& side) {
+            for (const auto& d : ConditionEvaluator::evaluate_group(rules, logic, candles).details)
+                if (!d.error.isEmpty())
+                    warnings << QString("%1 %2: %3").arg(side, d.indicator, d.error);
+        };
+        check(next.entry_conditions, next.entry_logic, QStringLiteral("entry"));
+        check(next.exit_conditions, next.exit_logic, QStringLiteral("exit"));
+    }
+
+    position_mgr_->set_exit_rules(next.stop_loss, next.take_profit, next.trailing_stop);
+    const QString previous = strategy_.name;
+    strategy_ = next;
+
+    // Point the row at the new strategy so a restart resumes with these rules
+    // (an inline strategy without an id keeps the saved one for resume).
+    auto db = Database::instance().connection();
+    QSqlQuery q(db);
+    if (!next.id.isEmpty()) {
+        q.prepare(QStringLiteral("UPDATE algo_deployments SET strategy_id=?, strategy_name=?, "
+                                 "updated_at=datetime('now') WHERE id=?"));
+        q.addBindValue(next.id);
+        q.addBindValue(next.name);
+        q.addBindValue(deployment_.id);
+        q.exec();
+    }
+
+    const QString msg = QString("Strategy reloaded: '%1' → '%2'%3")
+                            .arg(previous, next.name,
+                                 in_position() ? QStringLiteral(" (open position kept)") : QString());
+    LOG_INFO("AlgoEngine", QString("Deployment %1: %2").arg(deployment_.id, msg));
+    for (const auto& w : warnings)
+        LOG_WARN("AlgoEngine", QString("Deployment %1: reloaded rule — %2").arg(deployment_.id, w));
+    emit_live_snapshot(position_mgr_->metrics().current_price, QStringLiteral("STRATEGY RELOADED"));
+    emit strategy_reloaded(deployment_.id, true,
+                           warnings.isEmpty() ? msg : msg + QStringLiteral("; warnings: ") + warnings.join("; "));
+}
+
+// ── Kill switch ──────────────────────────────────────────────────────────────
+
+AlgoOrderSignal DeploymentRunner::flatten_signal(const QString& reason) const {
+    AlgoOrderSignal signal;
+    signal.deployment_id = deployment_.id;
+    signal.account_id = deployment_.broker_account_id;
+    signal.exchange = deployment_.exchange;
+    signal.product_type = deployment_.product_type;
+    signal.order_type = "MARKET";
+    signal.price = position_mgr_->metrics().current_price;
+    signal.reason = reason;
+    if (position_mgr_->has_legs()) {
+        signal.symbol = deployment_.underlying;
+        signal.legs = fincept::algo::fno::build_exit_legs(position_mgr_->legs());
+        return signal;
+    }
+    const auto pos = position_mgr_->position();
+    signal.symbol = deployment_.symbol;
+    signal.side = (pos.side == PositionSide::Long) ? "SELL" : "BUY";
+    signal.quantity = pos.quantity;
+    return signal;
+}
+
+void DeploymentRunner::kill(const QString& reason) {
+    if (!running_.load())
+        return;
+    halted_ = true;
+    halt_reason_ = reason;
+    LOG_WARN("AlgoEngine", QString("Deployment %1: kill switch — %2").arg(deployment_.id, reason));
+    emit_live_snapshot(0, QStringLiteral("KILL SWITCH — flattening"));
+    continue_kill();
+}
+
+void DeploymentRunner::continue_kill() {
+    if (!halted_.load() || !running_.load() || !pending_orders_.isEmpty())
+        return; // an in-flight order settles (or times out) first
+    if (in_position()) {
+        emit_order_signal(flatten_signal(QStringLiteral("kill_switch")));
+        return;
+    }
+    auto db = Database::instance().connection();
+    QSqlQuery q(db);
+    q.prepare(QStringLiteral("UPDATE algo_deployments SET error_message=?, updated_at=datetime('now') WHERE id=?"));
+    q.addBindValue(QStringLiteral("Kill switch: ") + halt_reason_);
+    q.addBindValue(deployment_.id);
+    q.exec();
+    stop();
+}
+
 void DeploymentRunner::on_order_filled(const QString& broker_order_id, double fill_price, double fill_qty) {
     int idx = -1;
     for (int i = 0; i < pending_orders_.size(); ++i) {
@@ -548,6 +765,7 @@ void DeploymentRunner::on_order_filled(const QString& broker_order_id, double fi
                                        .arg(is_entry ? QStringLiteral("ENTRY") : QStringLiteral("EXIT"))
                                        .arg(fill_qty, 0, 'f', 0)
                                        .arg(fill_price, 0, 'f', 2));
+    continue_kill();
 }
 
 void DeploymentRunner::on_order_rejected(const QString& /*broker_order_id*/, const QString& reason) {
@@ -660,13 +878,37 @@ void DeploymentRunner::finalize_basket_if_complete() {
     basket_fills_.clear();
     basket_rejected_ = 0;
     emit metrics_updated(deployment_.id, position_mgr_->metrics());
+    continue_kill();
 }
 
+namespace {
+// Exchange trading day an instant belongs to: the exchange-local date, rolled
+// back over weekends and holidays so activity outside a session counts toward
+// the last one. Unknown exchanges use the machine's local date.
+QDate dr_trading_day(const QString& exchange, int64_t epoch_ms) {
+    const auto& cal = core::market::MarketCalendar::instance();
+    const QDateTime at = QDateTime::fromMSecsSinceEpoch(epoch_ms);
+    if (!cal.knows(exchange))
+        return at.date();
+    QDate day = at.toTimeZone(cal.calendar(exchange).zone()).date();
+    for (int i = 0; i < 14 && !cal.is_trading_day(exchange, day); ++i)
+        day = day.addDays(-1);
+    return day;
+}
+} // namespace
+
 void DeploymentRunner::on_heartbeat() {
     if (!running_.load())
         return;
     int64_t now = QDateTime::currentMSecsSinceEpoch();
 
+    // New trading day: the daily loss counter (and a loss-limit pause) resets.
+    if (dr_trading_day(deployment_.exchange, position_mgr_->risk_state().day_start_epoch) !=
+        dr_trading_day(deployment_.exchange, now)) {
+        position_mgr_->reset_daily();
+        LOG_INFO("AlgoEngine", QString("Deployment %1: new trading day, daily loss counter reset").arg(deployment_.id));
+    }
+
     // Keep the DB metrics row fresh (~5s) so the summary cards and any structural
     // rebuild show current values instead of stale zeros.
     persist_metrics();
@@ -681,6 +923,15 @@ void DeploymentRunner::on_heartbeat() {
         }
     }
 
+    // A kill waiting on an in-flight order, or whose flatten was rejected, retries here.
+    continue_kill();
+    if (!running_.load())
+        return;
+
+    // No ticks while the exchange is closed is expected, not a feed failure.
+    if (market_closed())
+        last_heartbeat_ms_ = now;
+
     if (now - last_heartbeat_ms_ > 30000) {
         const QString msg = deployment_.broker_id.isEmpty()
                                 ? QStringLiteral("No market data in 30s — no broker is attached to this deployment.")
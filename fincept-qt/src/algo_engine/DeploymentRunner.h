@@ -28,6 +28,24 @@ class DeploymentRunner : public QObject {
     bool is_running() const { return running_.load(); }
     bool is_paused() const { return paused_.load(); }
     QString deployment_id() const { return deployment_.id; }
+    // Immutable after construction — safe to read from any thread.
+    const fincept::services::algo::AlgoDeployment& deployment() const { return deployment_; }
+
+    // Kill switch (see AlgoEngine::kill_switch). request_halt() is thread-safe
+    // and blocks new entries at once; kill() (on the engine thread) then
+    // flattens the position — after any in-flight order settles — and stops.
+    void request_halt() { halted_ = true; }
+    void kill(const QString& reason);
+    bool is_halted() const { return halted_.load(); }
+
+    // Hot reload (engine thread): swaps the entry/exit rules, logic and
+    // stop/target/trailing thresholds between two ticks, so no evaluation sees
+    // a half-applied strategy. The open position (single or basket), pending
+    // order, metrics and daily risk state carry over untouched. Refused when
+    // the timeframe would change (the bar history is built for the old one) or
+    // when leg rules change while a basket is open; empty leg rules keep the
+    // current ones. Emits strategy_reloaded.
+    void reload_strategy(const fincept::services::algo::AlgoStrategy& strategy);
 
     // Set the GUI-thread FnoDataBridge so F&O runners can request chain snapshots
     // and pin their legs. Called from AlgoEngine::start_deployment on the calling
@@ -45,6 +63,7 @@ class DeploymentRunner : public QObject {
     void error_occurred(const QString& deployment_id, const QString& error);
     // Real-time snapshot for the Dashboard (LTP, P&L, position, per-condition status).
     void live_update(const QString& deployment_id, const fincept::algo::AlgoLiveSnapshot& snap);
+    void strategy_reloaded(const QString& deployment_id, bool ok, const QString& message);
 
   public slots:
     void on_order_filled(const QString& broker_order_id, double fill_price, double fill_qty);
@@ -78,9 +97,22 @@ class DeploymentRunner : public QObject {
     // Pushes the real-time snapshot (LTP, P&L, position, per-condition status) to
     // the Dashboard, throttled. `note` is a short activity line.
     void emit_live_snapshot(double price, const QString& note);
+    // Entry or exit evaluation under the time budget (see kEvalBudgetMs).
+    void evaluate_rules(const QVector<OhlcvCandle>& candles);
     void evaluate_entry(const QVector<OhlcvCandle>& candles);
     void evaluate_exit(const QVector<OhlcvCandle>& candles);
+    // The deployment's exchange is outside its regular session (weekend,
+    // holiday, overnight) per core::market::MarketCalendar. Crypto and
+    // exchanges the calendar does not know always read as open.
+    bool market_closed() const;
     void emit_order_signal(const AlgoOrderSignal& signal);
+    // Entry rate limit (max_orders_per_minute over a sliding 60 s window).
+    // Exits always pass but count toward the window.
+    bool order_rate_allows(const AlgoOrderSignal& signal);
+    // Market exit of whatever is open — single position or F&O basket.
+    AlgoOrderSignal flatten_signal(const QString& reason) const;
+    // Drives a kill: flatten when nothing is in flight, stop once flat.
+    void continue_kill();
     void persist_trade(const AlgoTradeRecord& trade);
     void persist_metrics();
     // True when the runner holds an open position of EITHER kind — a single-symbol
@@ -108,6 +140,10 @@ class DeploymentRunner : public QObject {
 
     std::atomic<bool> running_{false};
     std::atomic<bool> paused_{false};
+    std::atomic<bool> halted_{false}; // kill switch — no new entries, flatten, stop
+    QString halt_reason_;
+    QVector<int64_t> order_times_; // submission times inside the rate window
+    bool rate_limit_noted_ = false; // one warning per throttled window
     QTimer* heartbeat_timer_ = nullptr;
     int64_t last_heartbeat_ms_ = 0;
     bool first_tick_logged_ = false; // log the first live quote once, for trackability
@@ -115,6 +151,12 @@ class DeploymentRunner : public QObject {
     int64_t last_emit_ms_ = 0;       // throttle for live_update emission
     double last_tick_price_ = 0;     // previous tick price → tick-to-tick crossovers
 
+    // One evaluation over budget is logged; kMaxSlowEvals in a row pause the
+    // deployment. Rule size itself is capped by ConditionEvaluator's limits.
+    static constexpr qint64 kEvalBudgetMs = 250;
+    static constexpr int kMaxSlowEvals = 3;
+    int slow_evals_ = 0;
+
     // Finalize the in-flight multi-leg basket once every leg has reported a
     // fill or rejection (called from on_leg_filled / on_leg_rejected).
     void finalize_basket_if_complete();
@@ -1,6 +1,11 @@
 // src/algo_engine/IndicatorEngine.cpp
 #include "algo_engine/IndicatorEngine.h"
 
+#include "services/options/VolatilityEstimators.h"
+
+#include <QDateTime>
+#include <QTimeZone>
+
 #include <algorithm>
 #include <cmath>
 #include <limits>
@@ -43,17 +48,23 @@ QVector<double> IndicatorEngine::sma_series(const QVector<double>& src, int peri
     return out;
 }
 
+// Leading NaNs (the warm-up of an input that is itself an EMA) are skipped:
+// the seed is the SMA of the first `period` values after them, so EMAs of
+// EMAs (DEMA, TEMA, TRIX, the MACD signal line) become valid.
 QVector<double> IndicatorEngine::ema_series(const QVector<double>& src, int period) {
     const int n = src.size();
     QVector<double> out(n, std::numeric_limits<double>::quiet_NaN());
-    if (n < period)
+    int first = 0;
+    while (first < n && std::isnan(src[first]))
+        ++first;
+    if (n - first < period)
         return out;
     double sum = 0;
-    for (int i = 0; i < period; ++i)
+    for (int i = first; i < first + period; ++i)
         sum += src[i];
-    out[period - 1] = sum / period;
+    out[first + period - 1] = sum / period;
     double mult = 2.0 / (period + 1);
-    for (int i = period; i < n; ++i)
+    for (int i = first + period; i < n; ++i)
         out[i] = (src[i] - out[i - 1]) * mult + out[i - 1];
     return out;
 }
@@ -98,6 +109,13 @@ IndicatorResult IndicatorEngine::compute(const QString& name, const QVector<Ohlc
     if (name == "CLOSE" || name == "OPEN" || name == "HIGH" || name == "LOW" || name == "VOLUME" || name == "VWAP")
         return compute_stock_attr(candles, name);
 
+    // Calendar / session
+    if (name == "TIME")
+        return compute_time(candles, params.value("utc_offset").toInt(0));
+    if (name == "SESSION")
+        return compute_session(candles, params.value("start").toInt(930), params.value("end").toInt(1600),
+                               params.value("utc_offset").toInt(0));
+
     QVector<double> open, high, low, close, vol;
     extract_arrays(candles, open, high, low, close, vol);
 
@@ -114,6 +132,11 @@ IndicatorResult IndicatorEngine::compute(const QString& name, const QVector<Ohlc
         return compute_dema(close, period);
     if (name == "TEMA")
         return compute_tema(close, period);
+    if (name == "KAMA") {
+        int fast = params.value("fast").toInt(2);
+        int slow = params.value("slow").toInt(30);
+        return compute_kama(close, period, fast, slow);
+    }
 
     // Momentum
     if (name == "RSI")
@@ -137,6 +160,8 @@ IndicatorResult IndicatorEngine::compute(const QString& name, const QVector<Ohlc
         return compute_mfi(high, low, close, vol, period);
     if (name == "ROC")
         return compute_roc(close, period);
+    if (name == "TRIX")
+        return compute_trix(close, period);
 
     // Trend
     if (name == "ADX")
@@ -167,12 +192,19 @@ IndicatorResult IndicatorEngine::compute(const QString& name, const QVector<Ohlc
     }
     if (name == "DONCHIAN")
         return compute_donchian(high, low, period);
+    if (name == "REALIZED_VOL")
+        return compute_realized_vol(open, high, low, close, period, params.value("annualize").toDouble(252));
 
     // Volume
     if (name == "OBV")
         return compute_obv(close, vol);
     if (name == "CMF")
         return compute_cmf(high, low, close, vol, period);
+    if (name == "CHAIKIN_OSC") {
+        int fast = params.value("fast").toInt(3);
+        int slow = params.value("slow").toInt(10);
+        return compute_chaikin_osc(high, low, close, vol, fast, slow);
+    }
     if (name == "VOL_WIN_CHG") {
         int window = params.value("window").toInt(10);
         return compute_vol_win_chg(vol, window);
@@ -181,6 +213,54 @@ IndicatorResult IndicatorEngine::compute(const QString& name, const QVector<Ohlc
     return make_error(QStringLiteral("Unknown indicator: ") + name);
 }
 
+// ── Calendar / session ──────────────────────────────────────────────────────
+
+static QDateTime bar_local_time(const OhlcvCandle& c, int utc_offset) {
+    return QDateTime::fromMSecsSinceEpoch(c.open_time, QTimeZone::fromSecondsAheadOfUtc(utc_offset * 60));
+}
+
+IndicatorResult IndicatorEngine::compute_time(const QVector<OhlcvCandle>& candles, int utc_offset) {
+    IndicatorResult r;
+    r.valid = true;
+    auto fill = [utc_offset](const OhlcvCandle& c, QHash<QString, double>& out) {
+        const QDateTime t = bar_local_time(c, utc_offset);
+        out["hour"] = t.time().hour();
+        out["minute"] = t.time().minute();
+        out["minute_of_day"] = t.time().hour() * 60 + t.time().minute();
+        out["day_of_week"] = t.date().dayOfWeek(); // 1 = Monday … 7 = Sunday
+        out["day_of_month"] = t.date().day();
+        out["month"] = t.date().month();
+        out["year"] = t.date().year();
+    };
+    fill(candles.last(), r.current);
+    fill(candles[candles.size() - 2], r.previous);
+    return r;
+}
+
+IndicatorResult IndicatorEngine::compute_session(const QVector<OhlcvCandle>& candles, int start_hhmm, int end_hhmm,
+                                                 int utc_offset) {
+    const int start = (start_hhmm / 100) * 60 + start_hhmm % 100;
+    const int end = (end_hhmm / 100) * 60 + end_hhmm % 100;
+    if (start_hhmm < 0 || end_hhmm < 0 || start >= 1440 || end > 1440 || start_hhmm % 100 >= 60 ||
+        end_hhmm % 100 >= 60)
+        return make_error(QStringLiteral("SESSION: start/end must be HHMM times"));
+
+    IndicatorResult r;
+    r.valid = true;
+    auto fill = [=](const OhlcvCandle& c, QHash<QString, double>& out) {
+        const QDateTime t = bar_local_time(c, utc_offset);
+        const int m = t.time().hour() * 60 + t.time().minute();
+        // end < start is an overnight session (e.g. 1800–0200).
+        const bool in = start <= end ? (m >= start && m < end) : (m >= start || m < end);
+        out["in_session"] = in ? 1.0 : 0.0;
+        out["minutes_from_open"] = in ? double((m - start + 1440) % 1440) : -1.0;
+        out["minutes_to_close"] = in ? double((end - m + 1440) % 1440) : -1.0;
+    };
+    fill(candles.last(), r.current);
+    fill(candles[candles.size() - 2], r.previous);
+    return r;
+}
+
 // ── Stock attributes ────────────────────────────────────────────────────────
 
 IndicatorResult IndicatorEngine::compute_stock_attr(const QVector<OhlcvCandle>& candles, const QString& attr) {
@@ -289,6 +369,31 @@ IndicatorResult IndicatorEngine::compute_tema(const QVector<double>& src, int pe
     return make_result(curr, prev);
 }
 
+// Kaufman's adaptive MA: the smoothing constant slides between the fast and
+// slow EMA constants with the efficiency ratio (net move / path length) over
+// `period` bars. Seeded with the close at bar `period`.
+IndicatorResult IndicatorEngine::compute_kama(const QVector<double>& close, int period, int fast, int slow) {
+    const int n = close.size();
+    if (period < 1 || fast < 1 || slow < 1)
+        return make_error(QStringLiteral("KAMA: period, fast and slow must be >= 1"));
+    if (n <= period)
+        return make_error(QStringLiteral("Insufficient data for KAMA"));
+    const double fast_sc = 2.0 / (fast + 1);
+    const double slow_sc = 2.0 / (slow + 1);
+    double kama = close[period];
+    double prev = kama;
+    for (int i = period + 1; i < n; ++i) {
+        double path = 0;
+        for (int j = i - period + 1; j <= i; ++j)
+            path += std::abs(close[j] - close[j - 1]);
+        const double er = path > 1e-10 ? std::abs(close[i] - close[i - period]) / path : 0;
+        const double sc = std::pow(er * (fast_sc - slow_sc) + slow_sc, 2);
+        prev = kama;
+        kama += sc * (close[i] - kama);
+    }
+    return make_result(kama, prev);
+}
+
 // ── Momentum ────────────────────────────────────────────────────────────────
 
 IndicatorResult IndicatorEngine::compute_rsi(const QVector<double>& close, int period) {
@@ -476,6 +581,23 @@ IndicatorResult IndicatorEngine::compute_roc(const QVector<double>& close, int p
     return make_result(curr, prev);
 }
 
+// One-bar percent change of the triple-smoothed EMA of close.
+IndicatorResult IndicatorEngine::compute_trix(const QVector<double>& close, int period) {
+    if (period < 1)
+        return make_error(QStringLiteral("TRIX: period must be >= 1"));
+    auto e3 = ema_series(ema_series(ema_series(close, period), period), period);
+    const int n = e3.size();
+    auto pct = [&e3](int i) {
+        return std::abs(e3[i - 1]) > 1e-10 ? (e3[i] - e3[i - 1]) / e3[i - 1] * 100.0
+                                           : std::numeric_limits<double>::quiet_NaN();
+    };
+    if (n < 2 || std::isnan(e3[n - 2]))
+        return make_error(QStringLiteral("Insufficient data for TRIX"));
+    double curr = pct(n - 1);
+    double prev = (n >= 3 && !std::isnan(e3[n - 3])) ? pct(n - 2) : curr;
+    return make_result(curr, prev);
+}
+
 // ── Trend ───────────────────────────────────────────────────────────────────
 
 IndicatorResult IndicatorEngine::compute_adx(const QVector<double>& high, const QVector<double>& low,
@@ -798,6 +920,31 @@ IndicatorResult IndicatorEngine::compute_donchian(const QVector<double>& high, c
     return r;
 }
 
+// Annualised realised volatility in percent, one field per estimator.
+IndicatorResult IndicatorEngine::compute_realized_vol(const QVector<double>& open, const QVector<double>& high,
+                                                      const QVector<double>& low, const QVector<double>& close,
+                                                      int period, double annualize) {
+    namespace vol = fincept::services::options::vol;
+    const int n = close.size();
+    if (period < 2 || annualize <= 0)
+        return make_error(QStringLiteral("REALIZED_VOL: period must be >= 2 and annualize > 0"));
+    if (n <= period)
+        return make_error(QStringLiteral("Insufficient data for REALIZED_VOL"));
+
+    auto fill = [&](int end, QHash<QString, double>& out) {
+        out[QStringLiteral("close_to_close")] = 100.0 * vol::close_to_close(close, end, period, annualize);
+        out[QStringLiteral("parkinson")] = 100.0 * vol::parkinson(high, low, end, period, annualize);
+        out[QStringLiteral("garman_klass")] = 100.0 * vol::garman_klass(open, high, low, close, end, period, annualize);
+        out[QStringLiteral("yang_zhang")] = 100.0 * vol::yang_zhang(open, high, low, close, end, period, annualize);
+    };
+    IndicatorResult r;
+    r.valid = true;
+    fill(n - 1, r.current);
+    if (n >= period + 2)
+        fill(n - 2, r.previous);
+    return r;
+}
+
 // ── Volume ──────────────────────────────────────────────────────────────────
 
 IndicatorResult IndicatorEngine::compute_obv(const QVector<double>& close, const QVector<double>& volume) {
@@ -863,4 +1010,30 @@ IndicatorResult IndicatorEngine::compute_cmf(const QVector<double>& high, const
     return make_result(curr, prev);
 }
 
+// EMA(fast) − EMA(slow) of the accumulation/distribution line.
+IndicatorResult IndicatorEngine::compute_chaikin_osc(const QVector<double>& high, const QVector<double>& low,
+                                                     const QVector<double>& close, const QVector<double>& volume,
+                                                     int fast, int slow) {
+    if (fast < 1 || slow < 1)
+        return make_error(QStringLiteral("CHAIKIN_OSC: fast and slow must be >= 1"));
+    const int n = close.size();
+    QVector<double> adl(n, 0);
+    double acc = 0;
+    for (int i = 0; i < n; ++i) {
+        double hl = high[i] - low[i];
+        double mf_mult = hl > 1e-10 ? ((close[i] - low[i]) - (high[i] - close[i])) / hl : 0;
+        acc += mf_mult * volume[i];
+        adl[i] = acc;
+    }
+    auto ef = ema_series(adl, fast);
+    auto es = ema_series(adl, slow);
+    if (n < 1 || std::isnan(ef[n - 1]) || std::isnan(es[n - 1]))
+        return make_error(QStringLiteral("Insufficient data for Chaikin Oscillator"));
+    double curr = ef[n - 1] - es[n - 1];
+    double prev = curr;
+    if (n >= 2 && !std::isnan(ef[n - 2]) && !std::isnan(es[n - 2]))
+        prev = ef[n - 2] - es[n - 2];
+    return make_result(curr, prev);
+}
+
 } // namespace fincept::algo
@@ -23,6 +23,7 @@ class IndicatorEngine {
     static IndicatorResult compute_wma(const QVector<double>& src, int period);
     static IndicatorResult compute_dema(const QVector<double>& src, int period);
     static IndicatorResult compute_tema(const QVector<double>& src, int period);
+    static IndicatorResult compute_kama(const QVector<double>& close, int period, int fast, int slow);
 
     // Momentum
     static IndicatorResult compute_rsi(const QVector<double>& close, int period);
@@ -36,6 +37,7 @@ class IndicatorEngine {
     static IndicatorResult compute_mfi(const QVector<double>& high, const QVector<double>& low,
                                        const QVector<double>& close, const QVector<double>& volume, int period);
     static IndicatorResult compute_roc(const QVector<double>& close, int period);
+    static IndicatorResult compute_trix(const QVector<double>& close, int period);
 
     // Trend
     static IndicatorResult compute_adx(const QVector<double>& high, const QVector<double>& low,
@@ -53,13 +55,25 @@ class IndicatorEngine {
     static IndicatorResult compute_keltner(const QVector<double>& high, const QVector<double>& low,
                                            const QVector<double>& close, int period, double multiplier);
     static IndicatorResult compute_donchian(const QVector<double>& high, const QVector<double>& low, int period);
+    static IndicatorResult compute_realized_vol(const QVector<double>& open, const QVector<double>& high,
+                                                const QVector<double>& low, const QVector<double>& close, int period,
+                                                double annualize);
 
     // Volume
     static IndicatorResult compute_obv(const QVector<double>& close, const QVector<double>& volume);
     static IndicatorResult compute_cmf(const QVector<double>& high, const QVector<double>& low,
                                        const QVector<double>& close, const QVector<double>& volume, int period);
+    static IndicatorResult compute_chaikin_osc(const QVector<double>& high, const QVector<double>& low,
+                                               const QVector<double>& close, const QVector<double>& volume, int fast,
+                                               int slow);
     static IndicatorResult compute_vol_win_chg(const QVector<double>& volume, int window);
 
+    // Calendar / session pseudo-indicators, from each bar's open time shifted
+    // by a fixed UTC offset in minutes (no DST rules)
+    static IndicatorResult compute_time(const QVector<OhlcvCandle>& candles, int utc_offset);
+    static IndicatorResult compute_session(const QVector<OhlcvCandle>& candles, int start_hhmm, int end_hhmm,
+                                           int utc_offset);
+
     // Stock attribute pseudo-indicators
     static IndicatorResult compute_stock_attr(const QVector<OhlcvCandle>& candles, const QString& attr);
 
@@ -0,0 +1,402 @@
+// src/algo_engine/PairsAnalytics.cpp
+#include "algo_engine/PairsAnalytics.h"
+
+#include <QJsonArray>
+#include <QStringList>
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+
+namespace fincept::algo {
+
+namespace {
+
+constexpr double kNaN = std::numeric_limits<double>::quiet_NaN();
+
+// MacKinnon (2010) response surfaces, constant term: c(T) = b0 + b1/T + b2/T².
+struct PaSurface {
+    double b0, b1, b2;
+    double at(int t) const { return b0 + b1 / t + b2 / (double(t) * t); }
+};
+constexpr PaSurface kAdf1{-3.43035, -6.5393, -16.786};
+constexpr PaSurface kAdf5{-2.86154, -2.8903, -4.234};
+constexpr PaSurface kAdf10{-2.56677, -1.5384, -2.809};
+constexpr PaSurface kEg1{-3.89644, -10.9519, -22.527};
+constexpr PaSurface kEg5{-3.33613, -6.1101, -6.823};
+constexpr PaSurface kEg10{-3.04445, -4.2412, -2.720};
+
+struct PaOls {
+    bool ok = false;
+    QVector<double> coef;
+    QVector<double> se;
+    double resid_var = 0;
+};
+
+// OLS of y on the rows of X (each row one observation, intercept included by
+// the caller) via the inverse of X'X (Gauss-Jordan with partial pivoting).
+PaOls pa_ols(const QVector<QVector<double>>& X, const QVector<double>& y) {
+    PaOls out;
+    const int n = X.size();
+    const int k = n > 0 ? X[0].size() : 0;
+    if (k == 0 || n <= k)
+        return out;
+
+    QVector<QVector<double>> a(k, QVector<double>(2 * k, 0.0));
+    QVector<double> xty(k, 0.0);
+    for (int r = 0; r < n; ++r)
+        for (int i = 0; i < k; ++i) {
+            xty[i] += X[r][i] * y[r];
+            for (int j = 0; j < k; ++j)
+                a[i][j] += X[r][i] * X[r][j];
+        }
+    for (int i = 0; i < k; ++i)
+        a[i][k + i] = 1.0;
+    for (int c = 0; c < k; ++c) {
+        int pivot = c;
+        for (int r = c + 1; r < k; ++r)
+            if (std::abs(a[r][c]) > std::abs(a[pivot][c]))
+                pivot = r;
+        if (std::abs(a[pivot][c]) < 1e-12)
+            return out;
+        std::swap(a[c], a[pivot]);
+        const double d = a[c][c];
+        for (double& v : a[c])
+            v /= d;
+        for (int r = 0; r < k; ++r) {
+            if (r == c)
+                continue;
+            const double f = a[r][c];
+            for (int j = 0; j < 2 * k; ++j)
+                a[r][j] -= f * a[c][j];
+        }
+    }
+
+    out.coef.fill(0.0, k);
+    for (int i = 0; i < k; ++i)
+        for (int j = 0; j < k; ++j)
+            out.coef[i] += a[i][k + j] * xty[j];
+    double ssr = 0;
+    for (int r = 0; r < n; ++r) {
+        double fit = 0;
+        for (int i = 0; i < k; ++i)
+            fit += X[r][i] * out.coef[i];
+        ssr += (y[r] - fit) * (y[r] - fit);
+    }
+    out.resid_var = ssr / (n - k);
+    out.se.resize(k);
+    for (int i = 0; i < k; ++i)
+        out.se[i] = std::sqrt(std::max(0.0, out.resid_var * a[i][k + i]));
+    out.ok = true;
+    return out;
+}
+
+// y = alpha + beta·x over [from, to).
+PaOls pa_simple_ols(const QVector<double>& y, const QVector<double>& x, int from, int to) {
+    QVector<QVector<double>> X;
+    QVector<double> ys;
+    X.reserve(to - from);
+    ys.reserve(to - from);
+    for (int i = from; i < to; ++i) {
+        X.append(QVector<double>{1.0, x[i]});
+        ys.append(y[i]);
+    }
+    return pa_ols(X, ys);
+}
+
+AdfResult pa_adf(const QVector<double>& x, int lags, const PaSurface& c1, const PaSurface& c5,
+                 const PaSurface& c10) {
+    AdfResult out;
+    const int n = x.size();
+    if (n < 20)
+        return out;
+    int p = lags >= 0 ? lags : int(std::floor(12.0 * std::pow(n / 100.0, 0.25)));
+    p = std::min(p, n / 4);
+
+    QVector<double> dx(n, 0.0);
+    for (int t = 1; t < n; ++t)
+        dx[t] = x[t] - x[t - 1];
+    QVector<QVector<double>> X;
+    QVector<double> y;
+    for (int t = p + 1; t < n; ++t) {
+        QVector<double> row{1.0, x[t - 1]};
+        for (int i = 1; i <= p; ++i)
+            row.append(dx[t - i]);
+        X.append(row);
+        y.append(dx[t]);
+    }
+    const PaOls fit = pa_ols(X, y);
+    if (!fit.ok || fit.se[1] <= 0)
+        return out;
+
+    out.ok = true;
+    out.statistic = fit.coef[1] / fit.se[1];
+    out.lags = p;
+    out.observations = int(X.size());
+    out.crit_1 = c1.at(out.observations);
+    out.crit_5 = c5.at(out.observations);
+    out.crit_10 = c10.at(out.observations);
+    out.significance = out.statistic < out.crit_1 ? 0.01
+                       : out.statistic < out.crit_5 ? 0.05
+                       : out.statistic < out.crit_10 ? 0.10
+                                                     : 0.0;
+    return out;
+}
+
+QJsonValue pa_num(double v) {
+    return std::isfinite(v) ? QJsonValue(v) : QJsonValue();
+}
+
+// Mean and sample stdev of the finite values in [from, to].
+std::pair<double, double> pa_mean_std(const QVector<double>& v, int from, int to) {
+    double sum = 0, sq = 0;
+    int n = 0;
+    for (int i = std::max(0, from); i <= to; ++i)
+        if (std::isfinite(v[i])) {
+            sum += v[i];
+            sq += v[i] * v[i];
+            ++n;
+        }
+    if (n < 2)
+        return {kNaN, kNaN};
+    const double mean = sum / n;
+    return {mean, std::sqrt(std::max(0.0, (sq - n * mean * mean) / (n - 1)))};
+}
+
+} // namespace
+
+// ── Results ─────────────────────────────────────────────────────────────────
+
+QJsonObject AdfResult::to_json() const {
+    return {{"ok", ok},
+            {"statistic", statistic},
+            {"lags", lags},
+            {"observations", observations},
+            {"critical_values", QJsonObject{{"1%", crit_1}, {"5%", crit_5}, {"10%", crit_10}}},
+            {"significance", significance}};
+}
+
+QJsonObject CointegrationResult::to_json() const {
+    return {{"ok", ok},
+            {"alpha", alpha},
+            {"beta", beta},
+            {"adf", adf.to_json()},
+            {"half_life", half_life},
+            {"cointegrated", cointegrated()}};
+}
+
+PairsConfig PairsConfig::from_json(const QJsonObject& o) {
+    PairsConfig c;
+    c.method = o.value("method").toString(c.method).toLower() == "kalman" ? QStringLiteral("kalman")
+                                                                           : QStringLiteral("ols");
+    c.window = std::clamp(o.value("window").toInt(c.window), 10, 1000);
+    c.kalman_delta = std::clamp(o.value("kalman_delta").toDouble(c.kalman_delta), 1e-8, 0.1);
+    c.adf_lags = std::clamp(o.value("adf_lags").toInt(c.adf_lags), -1, 50);
+    c.series_bars = std::clamp(o.value("series_bars").toInt(c.series_bars), 0, 5000);
+    return c;
+}
+
+// ── Tests ───────────────────────────────────────────────────────────────────
+
+AdfResult PairsAnalytics::adf(const QVector<double>& x, int lags) {
+    return pa_adf(x, lags, kAdf1, kAdf5, kAdf10);
+}
+
+CointegrationResult PairsAnalytics::engle_granger(const QVector<double>& y, const QVector<double>& x, int lags) {
+    CointegrationResult out;
+    const int n = std::min(y.size(), x.size());
+    const PaOls fit = pa_simple_ols(y, x, 0, n);
+    if (!fit.ok)
+        return out;
+    QVector<double> resid(n);
+    for (int i = 0; i < n; ++i)
+        resid[i] = y[i] - fit.coef[0] - fit.coef[1] * x[i];
+    out.alpha = fit.coef[0];
+    out.beta = fit.coef[1];
+    out.adf = pa_adf(resid, lags, kEg1, kEg5, kEg10);
+    out.ok = out.adf.ok;
+
+    // Δe_t = c + λ·e_t−1 → half-life −ln2 / λ.
+    QVector<double> lagged(resid.begin(), resid.end() - 1), diff(n - 1);
+    for (int i = 1; i < n; ++i)
+        diff[i - 1] = resid[i] - resid[i - 1];
+    const PaOls ar = pa_simple_ols(diff, lagged, 0, n - 1);
+    if (ar.ok && ar.coef[1] < 0)
+        out.half_life = -std::log(2.0) / ar.coef[1];
+    return out;
+}
+
+// ── Hedge ratios ────────────────────────────────────────────────────────────
+
+HedgeSeries PairsAnalytics::rolling_ols(const QVector<double>& y, const QVector<double>& x, int window) {
+    const int n = std::min(y.size(), x.size());
+    HedgeSeries h{QVector<double>(n, kNaN), QVector<double>(n, kNaN)};
+    for (int i = window - 1; i < n; ++i) {
+        const PaOls fit = pa_simple_ols(y, x, i - window + 1, i + 1);
+        if (fit.ok) {
+            h.alpha[i] = fit.coef[0];
+            h.beta[i] = fit.coef[1];
+        }
+    }
+    return h;
+}
+
+HedgeSeries PairsAnalytics::kalman(const QVector<double>& y, const QVector<double>& x, double delta, int window) {
+    const int n = std::min(y.size(), x.size());
+    HedgeSeries h{QVector<double>(n, kNaN), QVector<double>(n, kNaN)};
+    const PaOls seed = pa_simple_ols(y, x, 0, std::min(n, std::max(window, 3)));
+    if (!seed.ok)
+        return h;
+
+    double a = seed.coef[0], b = seed.coef[1];
+    double p00 = seed.se[0] * seed.se[0], p01 = 0, p11 = seed.se[1] * seed.se[1];
+    const double q = delta / (1.0 - delta);
+    const double r = std::max(seed.resid_var, 1e-12);
+    for (int t = 0; t < n; ++t) {
+        p00 += q;
+        p11 += q;
+        // F = [1, x_t]
+        const double e = y[t] - (a + b * x[t]);
+        const double pf0 = p00 + p01 * x[t];
+        const double pf1 = p01 + p11 * x[t];
+        const double s = pf0 + pf1 * x[t] + r;
+        const double k0 = pf0 / s, k1 = pf1 / s;
+        a += k0 * e;
+        b += k1 * e;
+        const double n00 = p00 - k0 * pf0, n01 = p01 - k0 * pf1, n11 = p11 - k1 * pf1;
+        p00 = n00;
+        p01 = n01;
+        p11 = n11;
+        h.alpha[t] = a;
+        h.beta[t] = b;
+    }
+    return h;
+}
+
+// ── Reports ─────────────────────────────────────────────────────────────────
+
+void PairsAnalytics::align(const QVector<OhlcvCandle>& a, const QVector<OhlcvCandle>& b, QVector<int64_t>& times,
+                           QVector<double>& y, QVector<double>& x) {
+    QHash<int64_t, double> b_close;
+    for (const auto& c : b)
+        b_close.insert(c.open_time, c.close);
+    times.clear();
+    y.clear();
+    x.clear();
+    for (const auto& c : a) {
+        const auto it = b_close.constFind(c.open_time);
+        if (it == b_close.constEnd() || c.close <= 0 || *it <= 0)
+            continue;
+        times.append(c.open_time);
+        y.append(c.close);
+        x.append(*it);
+    }
+}
+
+QJsonObject PairsAnalytics::analyze(const QString& y_symbol, const QVector<OhlcvCandle>& y_candles,
+                                    const QString& x_symbol, const QVector<OhlcvCandle>& x_candles,
+                                    const PairsConfig& config) {
+    QVector<int64_t> times;
+    QVector<double> y, x;
+    align(y_candles, x_candles, times, y, x);
+    const int n = y.size();
+    if (n < std::max(kMinBars, config.window + 1))
+        return {{"success", false},
+                {"error", QString("Only %1 overlapping bars for %2/%3 (need %4)")
+                              .arg(n)
+                              .arg(y_symbol, x_symbol)
+                              .arg(std::max(kMinBars, config.window + 1))}};
+
+    const CointegrationResult eg = engle_granger(y, x, config.adf_lags);
+    const CointegrationResult eg_reverse = engle_granger(x, y, config.adf_lags);
+    const HedgeSeries hedge = config.method == QLatin1String("kalman")
+                                  ? kalman(y, x, config.kalman_delta, config.window)
+                                  : rolling_ols(y, x, config.window);
+
+    QVector<double> spread(n, kNaN), z(n, kNaN);
+    for (int i = 0; i < n; ++i)
+        if (std::isfinite(hedge.beta[i]))
+            spread[i] = y[i] - (hedge.alpha[i] + hedge.beta[i] * x[i]);
+    for (int i = 0; i < n; ++i) {
+        if (!std::isfinite(spread[i]))
+            continue;
+        const auto [mean, sd] = pa_mean_std(spread, i - config.window + 1, i);
+        if (std::isfinite(sd) && sd > 1e-12)
+            z[i] = (spread[i] - mean) / sd;
+    }
+
+    QJsonArray series;
+    for (int i = std::max(0, n - config.series_bars); i < n; ++i)
+        series.append(QJsonObject{{"time", static_cast<double>(times[i])},
+                                  {"alpha", pa_num(hedge.alpha[i])},
+                                  {"beta", pa_num(hedge.beta[i])},
+                                  {"spread", pa_num(spread[i])},
+                                  {"zscore", pa_num(z[i])}});
+
+    return {{"success", true},
+            {"y", y_symbol},
+            {"x", x_symbol},
+            {"bars", n},
+            {"adf_y", adf(y, config.adf_lags).to_json()},
+            {"adf_x", adf(x, config.adf_lags).to_json()},
+            {"engle_granger", eg.to_json()},
+            {"engle_granger_reverse", eg_reverse.to_json()},
+            {"hedge", QJsonObject{{"method", config.method},
+                                  {"window", config.window},
+                                  {"alpha", pa_num(hedge.alpha[n - 1])},
+                                  {"beta", pa_num(hedge.beta[n - 1])}}},
+            {"spread", pa_num(spread[n - 1])},
+            {"zscore", pa_num(z[n - 1])},
+            {"series", series}};
+}
+
+QJsonObject PairsAnalytics::scan(const QHash<QString, QVector<OhlcvCandle>>& universe, const PairsConfig& config,
+                                 double max_significance) {
+    QStringList symbols = universe.keys();
+    symbols.sort();
+    QVector<std::pair<double, QJsonObject>> found;
+    int tested = 0;
+    for (int i = 0; i < symbols.size(); ++i) {
+        for (int j = i + 1; j < symbols.size(); ++j) {
+            QVector<int64_t> times;
+            QVector<double> a, b;
+            align(universe.value(symbols[i]), universe.value(symbols[j]), times, a, b);
+            if (a.size() < kMinBars)
+                continue;
+            ++tested;
+            const CointegrationResult ab = engle_granger(a, b, config.adf_lags);
+            const CointegrationResult ba = engle_granger(b, a, config.adf_lags);
+            const bool forward = ab.ok && (!ba.ok || ab.adf.statistic <= ba.adf.statistic);
+            const CointegrationResult& eg = forward ? ab : ba;
+            if (!eg.cointegrated(max_significance))
+                continue;
+            const QVector<double>& y = forward ? a : b;
+            const QVector<double>& x = forward ? b : a;
+            QVector<double> resid(y.size());
+            for (int k = 0; k < y.size(); ++k)
+                resid[k] = y[k] - eg.alpha - eg.beta * x[k];
+            const auto [mean, sd] = pa_mean_std(resid, 0, resid.size() - 1);
+            found.append({eg.adf.statistic,
+                          QJsonObject{{"y", forward ? symbols[i] : symbols[j]},
+                                      {"x", forward ? symbols[j] : symbols[i]},
+                                      {"bars", int(y.size())},
+                                      {"alpha", eg.alpha},
+                                      {"beta", eg.beta},
+                                      {"adf_statistic", eg.adf.statistic},
+                                      {"significance", eg.adf.significance},
+                                      {"half_life", eg.half_life},
+                                      {"zscore", pa_num(sd > 1e-12 ? (resid.last() - mean) / sd : kNaN)}}});
+        }
+    }
+    std::sort(found.begin(), found.end(), [](const auto& l, const auto& r) { return l.first < r.first; });
+    QJsonArray pairs_json;
+    for (const auto& f : found)
+        pairs_json.append(f.second);
+    return {{"success", true},
+            {"symbols", int(symbols.size())},
+            {"pairs_tested", tested},
+            {"max_significance", max_significance},
+            {"pairs", pairs_json}};
+}
+
+} // namespace fincept::algo
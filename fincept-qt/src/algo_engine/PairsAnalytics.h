@@ -0,0 +1,96 @@
+// src/algo_engine/PairsAnalytics.h
+// Statistical-arbitrage helpers for pairs trading: unit-root and cointegration
+// tests, hedge ratios and the z-scored spread. Pure computation over aligned
+// close prices, no I/O — AlgoTradingService fetches the history.
+#pragma once
+#include "algo_engine/AlgoEngineTypes.h"
+
+#include <QHash>
+#include <QJsonObject>
+#include <QString>
+#include <QVector>
+
+namespace fincept::algo {
+
+/// Augmented Dickey-Fuller regression with a constant:
+///   Δx_t = a + γ·x_t−1 + Σ φ_i·Δx_t−i + e_t,   statistic = γ / se(γ).
+/// Critical values are MacKinnon's (2010) response surfaces for the sample
+/// size; `significance` is the smallest of 0.01 / 0.05 / 0.10 the statistic
+/// clears (0 = cannot reject a unit root).
+struct AdfResult {
+    bool ok = false;
+    double statistic = 0;
+    int lags = 0;
+    int observations = 0;
+    double crit_1 = 0, crit_5 = 0, crit_10 = 0;
+    double significance = 0;
+
+    QJsonObject to_json() const;
+};
+
+/// Engle-Granger two-step test of y against x: OLS y = alpha + beta·x, then an
+/// ADF test of the residuals against the two-variable cointegration critical
+/// values. `half_life` is the mean-reversion half-life of the residual in bars
+/// (from an AR(1) fit; 0 when it does not revert).
+struct CointegrationResult {
+    bool ok = false;
+    double alpha = 0;
+    double beta = 0;
+    AdfResult adf;
+    double half_life = 0;
+
+    bool cointegrated(double level = 0.05) const { return ok && adf.significance > 0 && adf.significance <= level; }
+    QJsonObject to_json() const;
+};
+
+/// Hedge ratio per bar.
+///   ols    — OLS over the trailing `window` bars (NaN during warm-up);
+///   kalman — random-walk (alpha, beta) state, transition variance
+///            delta/(1−delta), seeded (state, covariance and observation
+///            variance) from an OLS over the first `window` bars.
+struct HedgeSeries {
+    QVector<double> alpha;
+    QVector<double> beta;
+};
+
+struct PairsConfig {
+    QString method = QStringLiteral("ols"); // ols | kalman
+    int window = 60;                         // rolling OLS window and z-score window
+    double kalman_delta = 1e-4;
+    int adf_lags = -1; // −1 → ⌊12·(n/100)^¼⌋
+    int series_bars = 250;
+
+    static PairsConfig from_json(const QJsonObject& o);
+};
+
+class PairsAnalytics {
+  public:
+    /// `lags` < 0 picks ⌊12·(n/100)^¼⌋. Needs at least 20 observations.
+    static AdfResult adf(const QVector<double>& x, int lags = -1);
+    static CointegrationResult engle_granger(const QVector<double>& y, const QVector<double>& x, int lags = -1);
+    static HedgeSeries rolling_ols(const QVector<double>& y, const QVector<double>& x, int window);
+    static HedgeSeries kalman(const QVector<double>& y, const QVector<double>& x, double delta, int window);
+
+    /// Aligns two candle series on open_time (bars missing in either are
+    /// dropped) and returns {times, y, x} closes.
+    static void align(const QVector<OhlcvCandle>& a, const QVector<OhlcvCandle>& b, QVector<int64_t>& times,
+                      QVector<double>& y, QVector<double>& x);
+
+    /// Full pair report: ADF of each leg, Engle-Granger both ways (y on x is
+    /// the reported direction), hedge ratio, spread y − (alpha + beta·x), its
+    /// rolling z-score and the last `series_bars` of all three. Returns
+    /// {"success": false, "error": …} when too few bars overlap.
+    static QJsonObject analyze(const QString& y_symbol, const QVector<OhlcvCandle>& y_candles,
+                               const QString& x_symbol, const QVector<OhlcvCandle>& x_candles,
+                               const PairsConfig& config);
+
+    /// Engle-Granger over every pair of the universe (each pair tested in the
+    /// direction with the stronger statistic), ranked by statistic. Only pairs
+    /// cointegrated at `max_significance` are listed.
+    static QJsonObject scan(const QHash<QString, QVector<OhlcvCandle>>& universe, const PairsConfig& config,
+                            double max_significance = 0.05);
+
+    static constexpr int kMinBars = 40;
+};
+
+} // namespace fincept::algo
@@ -0,0 +1,156 @@
+// src/algo_engine/ParameterGrid.cpp
+#include "algo_engine/ParameterGrid.h"
+
+#include <algorithm>
+
+namespace fincept::algo {
+
+namespace {
+
+QJsonValue grid_set_path(const QJsonValue& node, const QStringList& keys, int k, const QJsonValue& value, bool* ok) {
+    if (k == keys.size())
+        return value;
+    if (node.isArray()) {
+        QJsonArray a = node.toArray();
+        bool is_index = false;
+        const int i = keys[k].toInt(&is_index);
+        if (!is_index || i < 0 || i >= a.size()) {
+            *ok = false;
+            return node;
+        }
+        a.replace(i, grid_set_path(a.at(i), keys, k + 1, value, ok));
+        return a;
+    }
+    if (node.isObject()) {
+        QJsonObject o = node.toObject();
+        // The leaf may be new (a param the indicator otherwise defaults);
+        // anything above it must already exist.
+        if (k + 1 < keys.size() && !o.contains(keys[k])) {
+            *ok = false;
+            return node;
+        }
+        o[keys[k]] = grid_set_path(o.value(keys[k]), keys, k + 1, value, ok);
+        return o;
+    }
+    *ok = false;
+    return node;
+}
+
+} // namespace
+
+ParameterGrid ParameterGrid::from_json(const QJsonObject& grid, const BacktestParams& base, int max_combinations,
+                                       QString* error) {
+    ParameterGrid g;
+    long long combinations = 1;
+    for (auto it = grid.begin(); it != grid.end(); ++it) {
+        const QJsonArray values = it.value().isArray() ? it.value().toArray() : QJsonArray{it.value()};
+        QString err;
+        if (values.isEmpty())
+            err = QString("Parameter '%1' has no candidate values").arg(it.key());
+        BacktestParams probe = base;
+        for (const auto& v : values)
+            if (err.isEmpty() && !apply(probe, it.key(), v, &err))
+                break;
+        combinations *= std::max<qsizetype>(1, values.size());
+        if (err.isEmpty() && combinations > max_combinations)
+            err = QString("Parameter grid has more than %1 combinations — narrow it or raise max_combinations")
+                      .arg(max_combinations);
+        if (!err.isEmpty()) {
+            if (error)
+                *error = err;
+            return {};
+        }
+        g.keys_ << it.key();
+        g.values_ << values;
+    }
+    g.size_ = int(combinations);
+    return g;
+}
+
+BacktestParams ParameterGrid::params(const BacktestParams& base, int index, QJsonObject* chosen) const {
+    BacktestParams p = base;
+    for (int k = keys_.size() - 1; k >= 0; --k) {
+        const int radix = values_[k].size();
+        const QJsonValue v = values_[k].at(index % radix);
+        index /= radix;
+        apply(p, keys_[k], v);
+        if (chosen)
+            chosen->insert(keys_[k], v);
+    }
+    return p;
+}
+
+QJsonObject ParameterGrid::combination(int index) const {
+    QJsonObject out;
+    for (int k = keys_.size() - 1; k >= 0; --k) {
+        const int radix = values_[k].size();
+        out.insert(keys_[k], values_[k].at(index % radix));
+        index /= radix;
+    }
+    return out;
+}
+
+bool ParameterGrid::apply(BacktestParams& p, const QString& path, const QJsonValue& value, QString* error) {
+    auto fail = [error](const QString& msg) {
+        if (error)
+            *error = msg;
+        return false;
+    };
+    for (const QString& raw : path.split('|', Qt::SkipEmptyParts)) {
+        const QString key = raw.trimmed();
+        double* number = nullptr;
+        if (key == "stop_loss")
+            number = &p.stop_loss_pct;
+        else if (key == "take_profit")
+            number = &p.take_profit_pct;
+        else if (key == "trailing_stop")
+            number = &p.trailing_stop_pct;
+        else if (key == "position_size_pct")
+            number = &p.position_size_pct;
+        if (number) {
+            if (!value.isDouble())
+                return fail(QString("'%1' expects a number").arg(key));
+            *number = value.toDouble();
+            continue;
+        }
+        if (key == "entry_logic" || key == "exit_logic") {
+            const QString logic = value.toString().toUpper();
+            if (logic != "AND" && logic != "OR")
+                return fail(QString("'%1' expects AND or OR").arg(key));
+            (key == "entry_logic" ? p.entry_logic : p.exit_logic) = logic;
+            continue;
+        }
+        QStringList keys = key.split('.');
+        if (keys.size() < 3 || (keys.first() != "entry" && keys.first() != "exit"))
+            return fail(QString("Unknown parameter '%1'").arg(key));
+        QJsonArray& conditions = keys.first() == "entry" ? p.entry_conditions : p.exit_conditions;
+        keys.removeFirst();
+        bool ok = true;
+        const QJsonValue updated = grid_set_path(conditions, keys, 0, value, &ok);
+        if (!ok)
+            return fail(QString("'%1' does not match the strategy's conditions").arg(key));
+        conditions = updated.toArray();
+    }
+    return true;
+}
+
+QStringList ParameterGrid::objectives() {
+    return {"sharpe_ratio", "sortino",  "calmar",     "total_return",
+            "profit_factor", "win_rate", "expectancy", "max_drawdown"};
+}
+
+QString ParameterGrid::normalise_objective(const QString& name) {
+    const QString n = name.trimmed().toLower();
+    if (n == "sharpe")
+        return QStringLiteral("sharpe_ratio");
+    if (n == "return")
+        return QStringLiteral("total_return");
+    return objectives().contains(n) ? n : QString();
+}
+
+double ParameterGrid::score(const QJsonObject& result, const QString& objective) {
+    const double v = result.value(objective).toDouble();
+    return objective == QLatin1String("max_drawdown") ? -v : v;
+}
+
+} // namespace fincept::algo
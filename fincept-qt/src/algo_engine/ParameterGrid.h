@@ -0,0 +1,63 @@
+// src/algo_engine/ParameterGrid.h
+#pragma once
+#include "algo_engine/BacktestEngine.h"
+
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+namespace fincept::algo {
+
+/// Cartesian grid of strategy parameter values, shared by walk-forward
+/// optimisation and the parameter sweep.
+///
+/// The grid JSON maps a parameter path to its candidate values:
+///   stop_loss | take_profit | trailing_stop | position_size_pct |
+///   entry_logic | exit_logic
+///   entry.<i>.<key>[.<key>…] / exit.<i>.<key>…  — e.g. entry.0.params.period,
+///   exit.1.value, entry.0.conditions.1.params.period for nested groups.
+/// Join paths with "|" to tie them to one value
+/// ("entry.0.params.period|exit.0.params.period").
+///
+/// Combination indices run 0..size()-1 with the last key varying fastest.
+class ParameterGrid {
+  public:
+    /// Validates every value of every path against `base`; returns an empty
+    /// grid with `error` set on a bad path or when the grid has more than
+    /// `max_combinations` combinations.
+    static ParameterGrid from_json(const QJsonObject& grid, const BacktestParams& base, int max_combinations,
+                                   QString* error);
+
+    bool is_empty() const { return keys_.isEmpty(); }
+    int size() const { return size_; }
+    const QStringList& keys() const { return keys_; }
+    const QJsonArray& values(int key) const { return values_[key]; }
+
+    /// `base` with combination `index` applied; `chosen` receives path → value.
+    BacktestParams params(const BacktestParams& base, int index, QJsonObject* chosen = nullptr) const;
+    /// Path → value of combination `index`.
+    QJsonObject combination(int index) const;
+
+    /// Sets one grid path on `params`. False (with `error`) when the path does
+    /// not resolve against the strategy's conditions or the value has the
+    /// wrong type.
+    static bool apply(BacktestParams& params, const QString& path, const QJsonValue& value,
+                      QString* error = nullptr);
+
+    /// Metrics of a BacktestEngine result a search can rank by. Every one is
+    /// maximised except max_drawdown, which is minimised.
+    static QStringList objectives();
+    /// "sharpe" / "return" → their full names; empty when unknown.
+    static QString normalise_objective(const QString& name);
+    /// Higher is better: the metric, negated for max_drawdown.
+    static double score(const QJsonObject& result, const QString& objective);
+
+  private:
+    QStringList keys_;
+    QVector<QJsonArray> values_;
+    int size_ = 1;
+};
+
+} // namespace fincept::algo
@@ -0,0 +1,290 @@
+// src/algo_engine/ParameterSweepEngine.cpp
+#include "algo_engine/ParameterSweepEngine.h"
+
+#include "algo_engine/ParameterGrid.h"
+#include "core/logging/Logger.h"
+
+#include <QElapsedTimer>
+#include <QHash>
+#include <QJsonDocument>
+#include <QThread>
+#include <QThreadPool>
+#include <QVariant>
+#include <QtConcurrent>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::algo {
+
+namespace {
+
+double sweep_round(double v, int decimals) {
+    const double f = std::pow(10.0, decimals);
+    return std::round(v * f) / f;
+}
+
+QString sweep_key(const QJsonValue& v) {
+    return QString::fromUtf8(QJsonDocument(QJsonArray{v}).toJson(QJsonDocument::Compact));
+}
+
+// Distinct values of one param across the rows, numeric ones ascending.
+QJsonArray sweep_axis(const QJsonArray& rows, const QString& key) {
+    QVector<QJsonValue> values;
+    QStringList seen;
+    bool numeric = true;
+    for (const auto& r : rows) {
+        const QJsonValue v = r.toObject().value("params").toObject().value(key);
+        if (v.isUndefined() || seen.contains(sweep_key(v)))
+            continue;
+        seen << sweep_key(v);
+        values.append(v);
+        numeric = numeric && v.isDouble();
+    }
+    std::stable_sort(values.begin(), values.end(), [numeric](const QJsonValue& a, const QJsonValue& b) {
+        return numeric ? a.toDouble() < b.toDouble() : a.toVariant().toString() < b.toVariant().toString();
+    });
+    QJsonArray out;
+    for (const auto& v : values)
+        out.append(v);
+    return out;
+}
+
+// One grid row: params plus the metrics the matrix / heat map show.
+QJsonObject sweep_row(int index, const QJsonObject& params, const QJsonObject& r, const QString& objective,
+                      int min_trades) {
+    QJsonObject row{{"index", index}, {"params", params}};
+    if (!r.value("success").toBool()) {
+        row["error"] = r.value("error").toString(QStringLiteral("Backtest failed"));
+        return row;
+    }
+    row["objective"] = sweep_round(ParameterGrid::score(r, objective), 4);
+    row["total_return"] = r.value("total_return").toDouble();
+    row["sharpe_ratio"] = r.value("sharpe_ratio").toDouble();
+    row["sortino"] = r.value("sortino").toDouble();
+    row["calmar"] = r.value("calmar").toDouble();
+    row["max_drawdown"] = r.value("max_drawdown").toDouble();
+    row["total_trades"] = r.value("total_trades").toInt();
+    row["win_rate"] = r.value("win_rate").toDouble();
+    row["profit_factor"] = r.value("profit_factor").toDouble();
+    row["final_value"] = r.value("final_value").toDouble();
+    row["qualified"] = r.value("total_trades").toInt() >= min_trades;
+    return row;
+}
+
+// Ranking: qualified before unqualified, then objective descending, failed last.
+bool sweep_better(const QJsonObject& a, const QJsonObject& b) {
+    const bool a_ok = !a.contains("error"), b_ok = !b.contains("error");
+    if (a_ok != b_ok)
+        return a_ok;
+    const bool aq = a.value("qualified").toBool(), bq = b.value("qualified").toBool();
+    if (aq != bq)
+        return aq;
+    return a.value("objective").toDouble() > b.value("objective").toDouble();
+}
+
+} // namespace
+
+// ── Config ───────────────────────────────────────────────────────────────────
+
+SweepConfig SweepConfig::from_json(const QJsonObject& o, QString* error) {
+    SweepConfig c;
+    c.param_grid = o.value("param_grid").toObject();
+    c.min_trades = std::max(0, o.value("min_trades").toInt(c.min_trades));
+    c.max_combinations = std::clamp(o.value("max_combinations").toInt(c.max_combinations), 1, 20000);
+    c.threads = std::clamp(o.value("threads").toInt(0), 0, 64);
+    c.patience = std::max(0, o.value("patience").toInt(0));
+    if (o.value("target").isDouble())
+        c.target = o.value("target").toDouble();
+
+    const QString objective = o.value("objective").toString(c.objective);
+    c.objective = ParameterGrid::normalise_objective(objective);
+    if (c.objective.isEmpty()) {
+        c.objective = QStringLiteral("sharpe_ratio");
+        if (error)
+            *error = QString("Unknown objective '%1' (%2)").arg(objective, ParameterGrid::objectives().join(", "));
+    }
+    return c;
+}
+
+QJsonObject SweepConfig::to_json() const {
+    return QJsonObject{{"param_grid", param_grid},
+                       {"objective", objective},
+                       {"min_trades", min_trades},
+                       {"max_combinations", max_combinations},
+                       {"threads", threads},
+                       {"patience", patience},
+                       {"target", target ? QJsonValue(*target) : QJsonValue(QJsonValue::Null)}};
+}
+
+// ── Run ──────────────────────────────────────────────────────────────────────
+
+QJsonObject ParameterSweepEngine::run(const QVector<OhlcvCandle>& candles, const BacktestParams& base,
+                                      const SweepConfig& config, const Progress& progress,
+                                      const std::atomic_bool* cancel) {
+    auto fail = [](const QString& msg) { return QJsonObject{{"success", false}, {"error", msg}}; };
+    if (config.param_grid.isEmpty())
+        return fail(QStringLiteral("Parameter grid is empty — nothing to sweep"));
+    QString grid_error;
+    const ParameterGrid grid = ParameterGrid::from_json(config.param_grid, base, config.max_combinations, &grid_error);
+    if (!grid_error.isEmpty())
+        return fail(grid_error);
+    if (candles.size() < BacktestEngine::kWarmupBars + 10)
+        return fail(QString("Insufficient data: %1 candles (need at least %2)")
+                        .arg(candles.size())
+                        .arg(BacktestEngine::kWarmupBars + 10));
+
+    const int total = grid.size();
+    const int threads = config.threads > 0 ? config.threads : std::max(1, QThread::idealThreadCount());
+    const int batch = threads * kBatchPerThread;
+    QThreadPool pool;
+    pool.setMaxThreadCount(threads);
+
+    LOG_INFO("ParamSweep", QString("run: candles=%1 combos=%2 threads=%3 objective=%4 patience=%5")
+                               .arg(candles.size())
+                               .arg(total)
+                               .arg(threads)
+                               .arg(config.objective)
+                               .arg(config.patience));
+
+    QElapsedTimer timer;
+    timer.start();
+    QVector<QJsonObject> rows;
+    rows.reserve(total);
+    QJsonObject best;
+    int stale_batches = 0;
+    QString stop_reason;
+
+    for (int start = 0; start < total; start += batch) {
+        if (cancel && cancel->load()) {
+            stop_reason = QStringLiteral("cancelled");
+            break;
+        }
+        QVector<int> indices;
+        for (int c = start; c < std::min(start + batch, total); ++c)
+            indices.append(c);
+        const QList<QJsonObject> done = QtConcurrent::blockingMapped<QList<QJsonObject>>(
+            &pool, indices, [&candles, &base, &grid, &config](int c) {
+                QJsonObject params;
+                const BacktestParams p = grid.params(base, c, &params);
+                return sweep_row(c, params, BacktestEngine::run(candles, p, false), config.objective,
+                                 config.min_trades);
+            });
+
+        bool improved = false;
+        for (const auto& row : done) {
+            rows.append(row);
+            if (best.isEmpty() || sweep_better(row, best)) {
+                improved = improved || !row.contains("error");
+                best = row;
+            }
+        }
+        if (progress)
+            progress(int(rows.size()), total);
+
+        const bool best_qualified = best.value("qualified").toBool();
+        if (config.target && best_qualified && best.value("objective").toDouble() >= *config.target) {
+            stop_reason = QStringLiteral("target");
+            break;
+        }
+        stale_batches = improved ? 0 : stale_batches + 1;
+        if (config.patience > 0 && stale_batches >= config.patience) {
+            stop_reason = QStringLiteral("patience");
+            break;
+        }
+    }
+
+    std::stable_sort(rows.begin(), rows.end(), sweep_better);
+    QJsonArray results;
+    int failed = 0;
+    for (int i = 0; i < rows.size(); ++i) {
+        QJsonObject row = rows[i];
+        row["rank"] = i + 1;
+        if (row.contains("error"))
+            ++failed;
+        results.append(row);
+    }
+
+    QStringList warnings;
+    if (failed > 0)
+        warnings << QString("%1 combination(s) could not be backtested").arg(failed);
+    if (!best.isEmpty() && !best.value("qualified").toBool())
+        warnings << QString("No combination reached %1 trade(s) — the ranking is by objective only")
+                        .arg(config.min_trades);
+    if (stop_reason == "patience" || stop_reason == "target")
+        warnings << QString("Stopped early (%1) after %2 of %3 combinations")
+                        .arg(stop_reason)
+                        .arg(rows.size())
+                        .arg(total);
+
+    const QStringList keys = grid.keys();
+    QJsonObject out{{"success", true},
+                    {"objective", config.objective},
+                    {"combinations", total},
+                    {"evaluated", int(rows.size())},
+                    {"stopped_early", !stop_reason.isEmpty()},
+                    {"stop_reason", stop_reason},
+                    {"threads", threads},
+                    {"elapsed_ms", timer.elapsed()},
+                    {"param_keys", QJsonArray::fromStringList(keys)},
+                    {"best", results.isEmpty() ? QJsonObject() : results.first().toObject()},
+                    {"results", results},
+                    {"heatmap", heatmap(results, keys.value(0), keys.value(1), QStringLiteral("objective"))},
+                    {"config", config.to_json()},
+                    {"warnings", QJsonArray::fromStringList(warnings)}};
+
+    LOG_INFO("ParamSweep", QString("done: %1/%2 combos in %3 ms%4")
+                               .arg(rows.size())
+                               .arg(total)
+                               .arg(timer.elapsed())
+                               .arg(stop_reason.isEmpty() ? QString() : " — stopped: " + stop_reason));
+    return out;
+}
+
+// ── Heat map ─────────────────────────────────────────────────────────────────
+
+QJsonObject ParameterSweepEngine::heatmap(const QJsonArray& results, const QString& x_key, const QString& y_key,
+                                          const QString& metric) {
+    const QJsonArray xs = sweep_axis(results, x_key);
+    const QJsonArray ys = y_key.isEmpty() ? QJsonArray{QJsonValue::Null} : sweep_axis(results, y_key);
+
+    // Best row per cell; the grid may have more keys than the two axes.
+    QHash<QString, QJsonObject> cells;
+    for (const auto& rv : results) {
+        const QJsonObject row = rv.toObject();
+        if (row.contains("error"))
+            continue;
+        const QJsonObject params = row.value("params").toObject();
+        const QString cell = sweep_key(params.value(x_key)) + '|' +
+                             (y_key.isEmpty() ? QString() : sweep_key(params.value(y_key)));
+        auto it = cells.find(cell);
+        if (it == cells.end() || row.value("objective").toDouble() > it->value("objective").toDouble())
+            cells.insert(cell, row);
+    }
+
+    QJsonArray z;
+    QJsonObject best_cell;
+    double best_score = 0.0;
+    for (const auto& y : ys) {
+        QJsonArray line;
+        for (const auto& x : xs) {
+            const QString cell = sweep_key(x) + '|' + (y_key.isEmpty() ? QString() : sweep_key(y));
+            const auto it = cells.constFind(cell);
+            if (it == cells.constEnd()) {
+                line.append(QJsonValue::Null);
+                continue;
+            }
+            line.append(it->value(metric));
+            const double s = it->value("objective").toDouble();
+            if (best_cell.isEmpty() || s > best_score) {
+                best_score = s;
+                best_cell = QJsonObject{{"x", x}, {"y", y}, {"index", it->value("index")}};
+            }
+        }
+        z.append(line);
+    }
+    return QJsonObject{{"x_key", x_key},  {"y_key", y_key}, {"metric", metric}, {"x_values", xs},
+                       {"y_values", ys},  {"z", z},         {"best_cell", best_cell}};
+}
+
+} // namespace fincept::algo
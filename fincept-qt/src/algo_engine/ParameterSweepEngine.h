@@ -0,0 +1,60 @@
+// src/algo_engine/ParameterSweepEngine.h
+#pragma once
+#include "algo_engine/AlgoEngineTypes.h"
+#include "algo_engine/BacktestEngine.h"
+
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QString>
+#include <QVector>
+
+#include <atomic>
+#include <functional>
+#include <optional>
+
+namespace fincept::algo {
+
+struct SweepConfig {
+    QJsonObject param_grid; // see ParameterGrid
+    QString objective = QStringLiteral("sharpe_ratio");
+    int min_trades = 1;          // combinations with fewer trades rank below every one that qualifies
+    int max_combinations = 2000; // grid larger than this is rejected
+    int threads = 0;             // 0 → QThread::idealThreadCount()
+
+    // Early stopping, checked between batches of threads × kBatchPerThread
+    // combinations. Grid order is deterministic, so a stopped sweep is
+    // reproducible.
+    int patience = 0;             // stop after this many batches without a new best; 0 = off
+    std::optional<double> target; // stop once the best qualifying objective reaches this
+
+    /// Unknown keys are ignored; an unknown objective sets `error`.
+    static SweepConfig from_json(const QJsonObject& o, QString* error = nullptr);
+    QJsonObject to_json() const;
+};
+
+/// Exhaustive parameter sweep around BacktestEngine, run in parallel on a
+/// private thread pool. Every combination of the grid is backtested over the
+/// full candle range and ranked by `objective`; the result carries one row per
+/// combination (params + Sharpe / return / drawdown / trades …), the best
+/// combination, and a heat map over the first two grid keys.
+///
+/// Pure computation apart from the thread pool; on bad input returns
+/// {"success": false, "error": …}.
+class ParameterSweepEngine {
+  public:
+    using Progress = std::function<void(int done, int total)>;
+
+    static QJsonObject run(const QVector<OhlcvCandle>& candles, const BacktestParams& base, const SweepConfig& config,
+                           const Progress& progress = {}, const std::atomic_bool* cancel = nullptr);
+
+    /// `metric` (any numeric row field) over two grid keys from a sweep's
+    /// result rows: x_values × y_values with z[y][x]. Any other grid keys are
+    /// collapsed to the best-scoring combination in each cell; empty cells
+    /// are null. An empty `y_key` gives a single row.
+    static QJsonObject heatmap(const QJsonArray& results, const QString& x_key, const QString& y_key,
+                               const QString& metric);
+
+    static constexpr int kBatchPerThread = 4;
+};
+
+} // namespace fincept::algo
@@ -0,0 +1,1121 @@
+// src/algo_engine/PineImporter.cpp
+#include "algo_engine/PineImporter.h"
+
+#include <QHash>
+#include <QJsonArray>
+#include <QRegularExpression>
+
+#include <algorithm>
+#include <cmath>
+#include <optional>
+#include <vector>
+
+namespace fincept::algo {
+
+namespace {
+
+// ── Lexer ───────────────────────────────────────────────────────────────────
+
+struct PineToken {
+    enum class Kind { Number, Ident, String, Op };
+    Kind kind = Kind::Op;
+    QString text;
+    double number = 0;
+};
+
+QVector<PineToken> pine_lex(const QString& line, QString* error) {
+    static const QStringList kTwoCharOps = {":=", "==", "!=", "<=", ">=", "=>", "+=", "-=", "*=", "/="};
+    QVector<PineToken> out;
+    const int n = line.size();
+    int i = 0;
+    while (i < n) {
+        const QChar c = line[i];
+        if (c.isSpace()) {
+            ++i;
+        } else if (c.isDigit() || (c == '.' && i + 1 < n && line[i + 1].isDigit())) {
+            int j = i;
+            while (j < n && (line[j].isDigit() || line[j] == '.'))
+                ++j;
+            if (j < n && (line[j] == 'e' || line[j] == 'E')) {
+                ++j;
+                if (j < n && (line[j] == '+' || line[j] == '-'))
+                    ++j;
+                while (j < n && line[j].isDigit())
+                    ++j;
+            }
+            const QString text = line.mid(i, j - i);
+            out.append({PineToken::Kind::Number, text, text.toDouble()});
+            i = j;
+        } else if (c.isLetter() || c == '_') {
+            int j = i;
+            while (j < n && (line[j].isLetterOrNumber() || line[j] == '_' || line[j] == '.'))
+                ++j;
+            out.append({PineToken::Kind::Ident, line.mid(i, j - i)});
+            i = j;
+        } else if (c == '"' || c == '\'') {
+            QString s;
+            int j = i + 1;
+            for (; j < n && line[j] != c; ++j) {
+                if (line[j] == '\\' && j + 1 < n)
+                    ++j;
+                s += line[j];
+            }
+            if (j >= n) {
+                *error = QStringLiteral("unterminated string");
+                return {};
+            }
+            out.append({PineToken::Kind::String, s});
+            i = j + 1;
+        } else if (c == '#') { // color literal — only ever a cosmetic argument
+            int j = i + 1;
+            while (j < n && line[j].isLetterOrNumber())
+                ++j;
+            out.append({PineToken::Kind::String, line.mid(i, j - i)});
+            i = j;
+        } else if (kTwoCharOps.contains(line.mid(i, 2))) {
+            out.append({PineToken::Kind::Op, line.mid(i, 2)});
+            i += 2;
+        } else if (QStringLiteral("()[],=<>+-*/%?:").contains(c)) {
+            out.append({PineToken::Kind::Op, QString(c)});
+            ++i;
+        } else {
+            *error = QString("unexpected character '%1'").arg(c);
+            return {};
+        }
+    }
+    return out;
+}
+
+// ── Parser ──────────────────────────────────────────────────────────────────
+
+struct PineNode {
+    enum class Kind { Number, Bool, String, Name, Call, Index, Unary, Binary, Ternary };
+    Kind kind = Kind::Number;
+    QString text; // name, callee, operator or string value
+    double number = 0;
+    std::vector<PineNode> args; // operands / call arguments
+    QStringList arg_names;      // call argument names, "" when positional
+
+    // A call argument by name, else by position among the positional ones.
+    const PineNode* arg(const QString& name, int pos) const {
+        const int named = arg_names.indexOf(name);
+        if (named >= 0)
+            return &args[named];
+        int p = 0;
+        for (size_t k = 0; k < args.size(); ++k) {
+            if (!arg_names[int(k)].isEmpty())
+                continue;
+            if (p++ == pos)
+                return &args[k];
+        }
+        return nullptr;
+    }
+};
+
+PineNode pine_binary(const QString& op, PineNode lhs, PineNode rhs) {
+    PineNode node;
+    node.kind = PineNode::Kind::Binary;
+    node.text = op;
+    node.args.push_back(std::move(lhs));
+    node.args.push_back(std::move(rhs));
+    return node;
+}
+
+// Recursive descent over one logical line, Pine precedence:
+// ?: < or < and < not < comparison < + - < * / % < unary - < call/index.
+class PineParser {
+  public:
+    explicit PineParser(const QVector<PineToken>& tokens, int start = 0) : t_(tokens), pos_(start) {}
+
+    QString error;
+
+    bool at_end() const { return pos_ >= t_.size(); }
+    int pos() const { return pos_; }
+    bool is_op(const QString& s) const {
+        return !at_end() && t_[pos_].kind == PineToken::Kind::Op && t_[pos_].text == s;
+    }
+    bool is_word(const QString& s) const {
+        return !at_end() && t_[pos_].kind == PineToken::Kind::Ident && t_[pos_].text == s;
+    }
+
+    PineNode expression() {
+        PineNode cond = disjunction();
+        if (!is_op("?"))
+            return cond;
+        ++pos_;
+        PineNode node;
+        node.kind = PineNode::Kind::Ternary;
+        node.args.push_back(std::move(cond));
+        node.args.push_back(expression());
+        expect(":");
+        node.args.push_back(expression());
+        return node;
+    }
+
+  private:
+    const QVector<PineToken>& t_;
+    int pos_ = 0;
+
+    void fail(const QString& msg) {
+        if (error.isEmpty())
+            error = msg;
+        pos_ = t_.size();
+    }
+    void expect(const QString& op) {
+        if (is_op(op))
+            ++pos_;
+        else
+            fail(QString("expected '%1'").arg(op));
+    }
+
+    PineNode disjunction() {
+        PineNode lhs = conjunction();
+        while (is_word("or")) {
+            ++pos_;
+            lhs = pine_binary("or", std::move(lhs), conjunction());
+        }
+        return lhs;
+    }
+    PineNode conjunction() {
+        PineNode lhs = negation();
+        while (is_word("and")) {
+            ++pos_;
+            lhs = pine_binary("and", std::move(lhs), negation());
+        }
+        return lhs;
+    }
+    PineNode negation() {
+        if (!is_word("not"))
+            return comparison();
+        ++pos_;
+        PineNode node;
+        node.kind = PineNode::Kind::Unary;
+        node.text = QStringLiteral("not");
+        node.args.push_back(negation());
+        return node;
+    }
+    PineNode comparison() {
+        PineNode lhs = additive();
+        for (const char* op : {"<=", ">=", "==", "!=", "<", ">"}) {
+            if (is_op(op)) {
+                ++pos_;
+                return pine_binary(op, std::move(lhs), additive());
+            }
+        }
+        return lhs;
+    }
+    PineNode additive() {
+        PineNode lhs = multiplicative();
+        while (is_op("+") || is_op("-")) {
+            const QString op = t_[pos_++].text;
+            lhs = pine_binary(op, std::move(lhs), multiplicative());
+        }
+        return lhs;
+    }
+    PineNode multiplicative() {
+        PineNode lhs = unary();
+        while (is_op("*") || is_op("/") || is_op("%")) {
+            const QString op = t_[pos_++].text;
+            lhs = pine_binary(op, std::move(lhs), unary());
+        }
+        return lhs;
+    }
+    PineNode unary() {
+        if (!is_op("-") && !is_op("+"))
+            return postfix();
+        const QString op = t_[pos_++].text;
+        PineNode node;
+        node.kind = PineNode::Kind::Unary;
+        node.text = op;
+        node.args.push_back(unary());
+        return node;
+    }
+    PineNode postfix() {
+        PineNode node = primary();
+        for (;;) {
+            if (is_op("(") && node.kind == PineNode::Kind::Name) {
+                ++pos_;
+                node.kind = PineNode::Kind::Call;
+                while (!at_end() && !is_op(")")) {
+                    QString name;
+                    if (t_[pos_].kind == PineToken::Kind::Ident && pos_ + 1 < t_.size() &&
+                        t_[pos_ + 1].kind == PineToken::Kind::Op && t_[pos_ + 1].text == "=") {
+                        name = t_[pos_].text;
+                        pos_ += 2;
+                    }
+                    node.args.push_back(expression());
+                    node.arg_names.append(name);
+                    if (!is_op(","))
+                        break;
+                    ++pos_;
+                }
+                expect(")");
+            } else if (is_op("[")) {
+                ++pos_;
+                PineNode index;
+                index.kind = PineNode::Kind::Index;
+                index.args.push_back(std::move(node));
+                index.args.push_back(expression());
+                expect("]");
+                node = std::move(index);
+            } else {
+                return node;
+            }
+        }
+    }
+    PineNode primary() {
+        PineNode node;
+        if (at_end()) {
+            fail(QStringLiteral("unexpected end of line"));
+            return node;
+        }
+        const PineToken& tok = t_[pos_++];
+        switch (tok.kind) {
+            case PineToken::Kind::Number:
+                node.number = tok.number;
+                return node;
+            case PineToken::Kind::String:
+                node.kind = PineNode::Kind::String;
+                node.text = tok.text;
+                return node;
+            case PineToken::Kind::Ident:
+                if (tok.text == "true" || tok.text == "false") {
+                    node.kind = PineNode::Kind::Bool;
+                    node.number = tok.text == "true" ? 1 : 0;
+                } else {
+                    node.kind = PineNode::Kind::Name;
+                    node.text = tok.text;
+                }
+                return node;
+            case PineToken::Kind::Op:
+                if (tok.text == "(") {
+                    node = expression();
+                    expect(")");
+                    return node;
+                }
+                break;
+        }
+        fail(QString("unexpected '%1'").arg(tok.text));
+        return node;
+    }
+};
+
+// ── Translation ─────────────────────────────────────────────────────────────
+
+struct PineOperand {
+    QString indicator;
+    QJsonObject params;
+    QString field = QStringLiteral("value");
+    int offset = 0;
+    bool inverted = false; // Pine's supertrend direction is the negation of the engine's
+};
+
+// A series operand or a constant.
+struct PineValue {
+    std::optional<double> constant;
+    PineOperand operand;
+};
+
+const QStringList kPineCosmetics = {"plot",    "plotshape", "plotchar", "plotarrow", "plotcandle", "plotbar",
+                                    "hline",   "fill",      "bgcolor",  "barcolor",  "alertcondition",
+                                    "alert",   "label",     "line",     "box",       "table",
+                                    "runtime", "log",       "linefill", "polyline",  "max_bars_back"};
+const QStringList kPineTypeWords = {"var",   "varip",  "float", "int",    "bool",
+                                    "color", "string", "series", "simple", "const"};
+
+QString pine_flip(const QString& op) {
+    if (op == "<")
+        return QStringLiteral(">");
+    if (op == ">")
+        return QStringLiteral("<");
+    if (op == "<=")
+        return QStringLiteral(">=");
+    if (op == ">=")
+        return QStringLiteral("<=");
+    return op;
+}
+
+class PineTranslator {
+  public:
+    QStringList warnings;
+    QStringList errors;
+    int line = 0;
+    QHash<QString, PineNode> vars;
+    QHash<QString, std::pair<PineNode, int>> tuple_vars; // name → (call, output index)
+
+    void warn(const QString& msg) { warnings.append(QString("line %1: %2").arg(line).arg(msg)); }
+    void fail(const QString& msg) { errors.append(QString("line %1: %2").arg(line).arg(msg)); }
+
+    // Follows plain name aliases (`src = close`) to the defining expression.
+    const PineNode& resolve(const PineNode& node, int depth = 0) const {
+        if (node.kind == PineNode::Kind::Name && vars.contains(node.text) && depth < 32)
+            return resolve(*vars.constFind(node.text), depth + 1);
+        return node;
+    }
+
+    bool is_name(const PineNode* node, const QString& name) const {
+        if (!node)
+            return false;
+        const PineNode& r = resolve(*node);
+        if (r.kind == PineNode::Kind::Call && r.text == "input.source" && r.arg("defval", 0))
+            return is_name(r.arg("defval", 0), name);
+        return r.kind == PineNode::Kind::Name && r.text == name;
+    }
+
+    std::optional<double> constant(const PineNode& node, int depth = 0) const {
+        if (depth > 32)
+            return std::nullopt;
+        switch (node.kind) {
+            case PineNode::Kind::Number:
+                return node.number;
+            case PineNode::Kind::Name:
+                if (vars.contains(node.text))
+                    return constant(vars[node.text], depth + 1);
+                return std::nullopt;
+            case PineNode::Kind::Call:
+                if (node.text == "input" || node.text.startsWith("input.")) {
+                    if (const PineNode* def = node.arg("defval", 0))
+                        return constant(*def, depth + 1);
+                }
+                return std::nullopt;
+            case PineNode::Kind::Unary: {
+                const auto v = constant(node.args[0], depth + 1);
+                if (!v || node.text == "not")
+                    return std::nullopt;
+                return node.text == "-" ? -*v : *v;
+            }
+            case PineNode::Kind::Binary: {
+                const auto a = constant(node.args[0], depth + 1);
+                const auto b = constant(node.args[1], depth + 1);
+                if (!a || !b)
+                    return std::nullopt;
+                if (node.text == "+")
+                    return *a + *b;
+                if (node.text == "-")
+                    return *a - *b;
+                if (node.text == "*")
+                    return *a * *b;
+                if (node.text == "/" && *b != 0)
+                    return *a / *b;
+                if (node.text == "%" && *b != 0)
+                    return std::fmod(*a, *b);
+                return std::nullopt;
+            }
+            default:
+                return std::nullopt;
+        }
+    }
+
+    int int_arg(const PineNode& call, const QString& name, int pos, int fallback) {
+        const PineNode* a = call.arg(name, pos);
+        if (!a)
+            return fallback;
+        const auto v = constant(*a);
+        if (!v) {
+            fail(QString("%1: '%2' must be a constant").arg(call.text, name));
+            return fallback;
+        }
+        return int(std::lround(*v));
+    }
+    double num_arg(const PineNode& call, const QString& name, int pos, double fallback) {
+        const PineNode* a = call.arg(name, pos);
+        if (!a)
+            return fallback;
+        const auto v = constant(*a);
+        if (!v) {
+            fail(QString("%1: '%2' must be a constant").arg(call.text, name));
+            return fallback;
+        }
+        return *v;
+    }
+
+    // Pine's price sources → the stock attributes; only `close` feeds the
+    // engine's moving averages and oscillators.
+    bool require_close(const PineNode& call, const QString& name = QStringLiteral("source"), int pos = 0) {
+        const PineNode* src = call.arg(name, pos);
+        if (!src || is_name(src, "close"))
+            return true;
+        fail(QString("%1 on a source other than close has no equivalent").arg(call.text));
+        return false;
+    }
+
+    static PineOperand op(const QString& indicator, const QJsonObject& params, const QString& field = "value") {
+        PineOperand o;
+        o.indicator = indicator;
+        o.params = params;
+        o.field = field;
+        return o;
+    }
+
+    // Tuple-returning ta.* functions: the operand for output `index`.
+    std::optional<PineOperand> tuple_call(const PineNode& call, int index) {
+        const QString& f = call.text;
+        if (f == "ta.macd") {
+            if (!require_close(call))
+                return std::nullopt;
+            static const QStringList fields = {"line", "signal_line", "histogram"};
+            return op("MACD",
+                      {{"fast", int_arg(call, "fastlen", 1, 12)},
+                       {"slow", int_arg(call, "slowlen", 2, 26)},
+                       {"signal", int_arg(call, "siglen", 3, 9)}},
+                      fields.value(index));
+        }
+        if (f == "ta.bb" || f == "ta.kc") {
+            if (!require_close(call, "series"))
+                return std::nullopt;
+            static const QStringList fields = {"middle", "upper", "lower"};
+            const int length = int_arg(call, "length", 1, 20);
+            const double mult = num_arg(call, "mult", 2, 2.0);
+            if (f == "ta.kc") {
+                if (call.arg("useTrueRange", 3))
+                    warn("ta.kc useTrueRange is ignored — the engine's Keltner uses the true range");
+                return op("KELTNER", {{"period", length}, {"multiplier", mult}}, fields.value(index));
+            }
+            return op("BOLLINGER", {{"period", length}, {"std_dev", mult}}, fields.value(index));
+        }
+        if (f == "ta.supertrend") {
+            PineOperand o = op("SUPERTREND",
+                               {{"period", int_arg(call, "atrPeriod", 1, 10)},
+                                {"multiplier", num_arg(call, "factor", 0, 3.0)}},
+                               index == 0 ? "value" : "direction");
+            o.inverted = index == 1;
+            return o;
+        }
+        if (f == "ta.dmi") {
+            const int di = int_arg(call, "diLength", 0, 14);
+            const int smoothing = int_arg(call, "adxSmoothing", 1, di);
+            if (smoothing != di)
+                warn(QString("ta.dmi adxSmoothing %1 differs from diLength %2 — the engine smooths ADX over %2")
+                         .arg(smoothing)
+                         .arg(di));
+            static const QStringList fields = {"plus_di", "minus_di", "value"};
+            return op("ADX", {{"period", di}}, fields.value(index));
+        }
+        fail(QString("%1 does not return a tuple").arg(f));
+        return std::nullopt;
+    }
+
+    // Single-output ta.* functions.
+    std::optional<PineOperand> ta_call(const PineNode& call) {
+        const QString& f = call.text;
+        if (f == "ta.sma" || f == "ta.ema" || f == "ta.wma") {
+            const PineNode* src = call.arg("source", 0);
+            const PineNode& inner = src ? resolve(*src) : call;
+            if (f == "ta.sma" && src && inner.kind == PineNode::Kind::Call && inner.text == "ta.stoch") {
+                // %D: the engine's Stochastic d is the SMA of raw %K.
+                if (!require_close(inner))
+                    return std::nullopt;
+                return op("STOCHASTIC",
+                          {{"k_period", int_arg(inner, "length", 3, 14)}, {"d_period", int_arg(call, "length", 1, 3)}},
+                          "d");
+            }
+            if (!require_close(call))
+                return std::nullopt;
+            return op(f.mid(3).toUpper(), {{"period", int_arg(call, "length", 1, 14)}});
+        }
+        if (f == "ta.rsi" || f == "ta.roc") {
+            if (!require_close(call))
+                return std::nullopt;
+            return op(f.mid(3).toUpper(), {{"period", int_arg(call, "length", 1, 14)}});
+        }
+        if (f == "ta.cci" || f == "ta.mfi") {
+            const QString arg = f == "ta.cci" ? QStringLiteral("source") : QStringLiteral("series");
+            if (!is_name(call.arg(arg, 0), "hlc3"))
+                warn(QString("%1 is translated on the typical price (hlc3), as the engine computes it").arg(f));
+            return op(f.mid(3).toUpper(), {{"period", int_arg(call, "length", 1, 14)}});
+        }
+        if (f == "ta.atr")
+            return op("ATR", {{"period", int_arg(call, "length", 0, 14)}});
+        if (f == "ta.wpr")
+            return op("WILLIAMS_R", {{"period", int_arg(call, "length", 0, 14)}});
+        if (f == "ta.vwap") {
+            if (call.arg("source", 0) && !is_name(call.arg("source", 0), "hlc3"))
+                warn("ta.vwap is translated as the session VWAP of the typical price");
+            return op("VWAP", {});
+        }
+        if (f == "ta.stoch") {
+            if (!require_close(call))
+                return std::nullopt;
+            return op("STOCHASTIC", {{"k_period", int_arg(call, "length", 3, 14)}, {"d_period", 3}}, "k");
+        }
+        if (f == "ta.highest" || f == "ta.lowest") {
+            const bool high = f == "ta.highest";
+            // ta.highest(length) or ta.highest(source, length)
+            const bool one_arg = call.args.size() == 1 && call.arg_names.value(0).isEmpty();
+            const PineNode* src = one_arg ? nullptr : call.arg("source", 0);
+            if (src && !is_name(src, high ? "high" : "low")) {
+                fail(QString("%1 is only translated on %2 (a Donchian channel)").arg(f, high ? "high" : "low"));
+                return std::nullopt;
+            }
+            const int length = one_arg ? int_arg(call, "length", 0, 20) : int_arg(call, "length", 1, 20);
+            return op("DONCHIAN", {{"period", length}}, high ? "upper" : "lower");
+        }
+        if (f == "ta.macd" || f == "ta.bb" || f == "ta.kc" || f == "ta.supertrend" || f == "ta.dmi") {
+            fail(QString("%1 returns a tuple — assign it first: [a, b, c] = %1(…)").arg(f));
+            return std::nullopt;
+        }
+        fail(QString("%1 has no equivalent indicator").arg(f));
+        return std::nullopt;
+    }
+
+    std::optional<PineValue> value(const PineNode& node, int depth = 0) {
+        if (depth > 32) {
+            fail(QStringLiteral("definitions nest too deeply"));
+            return std::nullopt;
+        }
+        if (const auto c = constant(node))
+            return PineValue{c, {}};
+        switch (node.kind) {
+            case PineNode::Kind::Name: {
+                static const QHash<QString, QString> series = {{"close", "CLOSE"},  {"open", "OPEN"},
+                                                               {"high", "HIGH"},    {"low", "LOW"},
+                                                               {"volume", "VOLUME"}, {"ta.obv", "OBV"},
+                                                               {"ta.vwap", "VWAP"}};
+                if (series.contains(node.text))
+                    return PineValue{std::nullopt, op(series[node.text], {})};
+                if (tuple_vars.contains(node.text)) {
+                    const auto& [call, index] = tuple_vars[node.text];
+                    if (auto o = tuple_call(call, index))
+                        return PineValue{std::nullopt, *o};
+                    return std::nullopt;
+                }
+                if (vars.contains(node.text))
+                    return value(vars[node.text], depth + 1);
+                fail(QString("'%1' has no equivalent").arg(node.text));
+                return std::nullopt;
+            }
+            case PineNode::Kind::Index: {
+                auto base = value(node.args[0], depth + 1);
+                const auto bars = constant(node.args[1]);
+                if (!base)
+                    return std::nullopt;
+                if (!bars || *bars < 0) {
+                    fail(QStringLiteral("history references need a constant, non-negative offset"));
+                    return std::nullopt;
+                }
+                if (!base->constant)
+                    base->operand.offset += int(*bars);
+                return base;
+            }
+            case PineNode::Kind::Call:
+                if (node.text == "nz" || node.text == "fixnan") {
+                    if (!node.args.empty())
+                        return value(node.args[0], depth + 1);
+                    break;
+                }
+                if (node.text == "input.source" && node.arg("defval", 0))
+                    return value(*node.arg("defval", 0), depth + 1);
+                if (node.text.startsWith("ta.")) {
+                    if (auto o = ta_call(node))
+                        return PineValue{std::nullopt, *o};
+                    return std::nullopt;
+                }
+                fail(QString("%1() has no equivalent").arg(node.text));
+                return std::nullopt;
+            case PineNode::Kind::Binary:
+            case PineNode::Kind::Unary:
+                fail(QStringLiteral("arithmetic on a series has no equivalent in the condition rules"));
+                return std::nullopt;
+            default:
+                break;
+        }
+        fail(QStringLiteral("expression has no equivalent"));
+        return std::nullopt;
+    }
+
+    static QJsonObject leaf(const PineOperand& o, const QString& op) {
+        return QJsonObject{{"indicator", o.indicator}, {"params", o.params}, {"field", o.field},
+                           {"offset", o.offset},       {"operator", op}};
+    }
+    static QJsonObject group(const QString& logic, const QJsonArray& children, bool negate = false) {
+        QJsonObject g{{"type", "group"}, {"logic", logic}, {"children", children}};
+        if (negate)
+            g["negate"] = true;
+        return g;
+    }
+
+    // lhs `op` rhs, constants moved to the right and Pine's inverted
+    // supertrend direction corrected.
+    std::optional<QJsonObject> compare(const PineNode& lhs_node, QString op, const PineNode& rhs_node) {
+        auto lhs = value(lhs_node);
+        auto rhs = value(rhs_node);
+        if (!lhs || !rhs)
+            return std::nullopt;
+        if (lhs->constant && rhs->constant) {
+            fail(QStringLiteral("comparison of two constants"));
+            return std::nullopt;
+        }
+        if (lhs->constant) {
+            std::swap(lhs, rhs);
+            op = op == "crosses_above"   ? QStringLiteral("crosses_below")
+                 : op == "crosses_below" ? QStringLiteral("crosses_above")
+                                         : pine_flip(op);
+        }
+        if (rhs->constant) {
+            double v = *rhs->constant;
+            if (lhs->operand.inverted) {
+                v = -v;
+                op = op == "crosses_above"   ? QStringLiteral("crosses_below")
+                     : op == "crosses_below" ? QStringLiteral("crosses_above")
+                                             : pine_flip(op);
+            }
+            QJsonObject c = leaf(lhs->operand, op);
+            c["compare_mode"] = QStringLiteral("value");
+            c["value"] = v;
+            return c;
+        }
+        if (lhs->operand.inverted || rhs->operand.inverted)
+            warn("supertrend direction compared with a series — Pine's sign is the opposite of the engine's");
+        QJsonObject c = leaf(lhs->operand, op);
+        c["compare_mode"] = QStringLiteral("indicator");
+        c["compare_indicator"] = rhs->operand.indicator;
+        c["compare_params"] = rhs->operand.params;
+        c["compare_field"] = rhs->operand.field;
+        c["compare_offset"] = rhs->operand.offset;
+        return c;
+    }
+
+    void flatten(const PineNode& node, const QString& logic, std::vector<const PineNode*>& out) {
+        const PineNode& r = resolve(node);
+        if (r.kind == PineNode::Kind::Binary && r.text == logic) {
+            flatten(r.args[0], logic, out);
+            flatten(r.args[1], logic, out);
+        } else {
+            out.push_back(&r);
+        }
+    }
+
+    std::optional<QJsonObject> condition(const PineNode& node, int depth = 0) {
+        if (depth > 32) {
+            fail(QStringLiteral("definitions nest too deeply"));
+            return std::nullopt;
+        }
+        switch (node.kind) {
+            case PineNode::Kind::Name:
+                if (vars.contains(node.text))
+                    return condition(vars[node.text], depth + 1);
+                fail(QString("'%1' is not a known condition").arg(node.text));
+                return std::nullopt;
+            case PineNode::Kind::Bool:
+                fail(QStringLiteral("constant condition"));
+                return std::nullopt;
+            case PineNode::Kind::Unary:
+                if (node.text == "not") {
+                    auto inner = condition(node.args[0], depth + 1);
+                    if (!inner)
+                        return std::nullopt;
+                    return group("AND", QJsonArray{*inner}, true);
+                }
+                break;
+            case PineNode::Kind::Binary: {
+                if (node.text == "and" || node.text == "or") {
+                    std::vector<const PineNode*> parts;
+                    flatten(node, node.text, parts);
+                    QJsonArray children;
+                    for (const PineNode* part : parts) {
+                        auto c = condition(*part, depth + 1);
+                        if (!c)
+                            return std::nullopt;
+                        children.append(*c);
+                    }
+                    return group(node.text.toUpper(), children);
+                }
+                if (node.text == "!=") {
+                    warn("'!=' is translated as NOT (touches the level) — the engine's '==' is a level touch");
+                    auto eq = compare(node.args[0], "==", node.args[1]);
+                    if (!eq)
+                        return std::nullopt;
+                    return group("AND", QJsonArray{*eq}, true);
+                }
+                if (node.text == "==")
+                    warn("'==' is a level touch in the engine: met when the value sits on or passes the level");
+                if (QStringList{"<", ">", "<=", ">=", "=="}.contains(node.text))
+                    return compare(node.args[0], node.text, node.args[1]);
+                break;
+            }
+            case PineNode::Kind::Call: {
+                const QString& f = node.text;
+                const PineNode* a = node.arg("source1", 0);
+                const PineNode* b = node.arg("source2", 1);
+                if (f == "ta.crossover" || f == "ta.crossunder" || f == "ta.cross") {
+                    if (!a || !b) {
+                        fail(QString("%1 needs two arguments").arg(f));
+                        return std::nullopt;
+                    }
+                    if (f != "ta.cross")
+                        return compare(*a, f == "ta.crossover" ? "crosses_above" : "crosses_below", *b);
+                    auto up = compare(*a, "crosses_above", *b);
+                    auto down = compare(*a, "crosses_below", *b);
+                    if (!up || !down)
+                        return std::nullopt;
+                    return group("OR", QJsonArray{*up, *down});
+                }
+                if (f == "ta.rising" || f == "ta.falling") {
+                    const PineNode* src = node.arg("source", 0);
+                    if (!src) {
+                        fail(QString("%1 needs a source").arg(f));
+                        return std::nullopt;
+                    }
+                    if (int_arg(node, "length", 1, 1) != 1) {
+                        fail(QString("%1 over more than one bar has no equivalent — the engine compares with the "
+                                     "previous bar")
+                                 .arg(f));
+                        return std::nullopt;
+                    }
+                    auto v = value(*src);
+                    if (!v)
+                        return std::nullopt;
+                    if (v->constant) {
+                        fail(QString("%1 of a constant").arg(f));
+                        return std::nullopt;
+                    }
+                    const bool rising = (f == "ta.rising") != v->operand.inverted;
+                    return leaf(v->operand, rising ? "rising" : "falling");
+                }
+                break;
+            }
+            default:
+                break;
+        }
+        fail(QStringLiteral("not a condition the rules can express"));
+        return std::nullopt;
+    }
+
+    // strategy.exit stop/limit: strategy.position_avg_price * k → |k − 1| as a percent.
+    std::optional<double> avg_price_pct(const PineNode* node) {
+        if (!node)
+            return std::nullopt;
+        const PineNode& r = resolve(*node);
+        if (r.kind != PineNode::Kind::Binary || r.text != "*")
+            return std::nullopt;
+        for (int side = 0; side < 2; ++side) {
+            if (!is_name(&r.args[side], "strategy.position_avg_price"))
+                continue;
+            if (const auto k = constant(r.args[1 - side]))
+                return std::abs(*k - 1.0) * 100.0;
+        }
+        return std::nullopt;
+    }
+};
+
+struct PineLine {
+    int number = 0;
+    int indent = 0;
+    QString text;
+};
+
+// Comments stripped, continuation lines (open brackets) joined, blank lines dropped.
+QVector<PineLine> pine_lines(const QString& source, int* version) {
+    QVector<PineLine> out;
+    const QStringList raw = source.split('\n');
+    int depth = 0;
+    for (int i = 0; i < raw.size(); ++i) {
+        QString line = raw[i];
+        line.remove('\r');
+        static const QRegularExpression version_re(R"(^\s*//\s*@version\s*=\s*(\d+))");
+        if (const auto m = version_re.match(line); m.hasMatch())
+            *version = m.captured(1).toInt();
+        QChar quote;
+        int cut = line.size();
+        for (int j = 0; j < line.size(); ++j) {
+            const QChar c = line[j];
+            if (!quote.isNull()) {
+                if (c == quote)
+                    quote = QChar();
+            } else if (c == '"' || c == '\'') {
+                quote = c;
+            } else if (c == '/' && j + 1 < line.size() && line[j + 1] == '/') {
+                cut = j;
+                break;
+            }
+        }
+        line.truncate(cut);
+        if (line.trimmed().isEmpty())
+            continue;
+        if (depth > 0 && !out.isEmpty()) {
+            out.last().text += ' ' + line.trimmed();
+        } else {
+            int indent = 0;
+            for (const QChar c : line) {
+                if (c == ' ')
+                    ++indent;
+                else if (c == '\t')
+                    indent += 4;
+                else
+                    break;
+            }
+            out.append({i + 1, indent, line.trimmed()});
+        }
+        for (const QChar c : line) {
+            if (c == '(' || c == '[')
+                ++depth;
+            else if (c == ')' || c == ']')
+                depth = std::max(0, depth - 1);
+        }
+    }
+    return out;
+}
+
+// A trigger list → the strategy's conditions and logic: one AND/OR group is
+// unwrapped to its children, several triggers are OR-ed.
+void pine_assign(const QVector<QJsonObject>& triggers, QJsonArray& conditions, QString& logic) {
+    conditions = QJsonArray{};
+    logic = QStringLiteral("AND");
+    if (triggers.isEmpty())
+        return;
+    if (triggers.size() == 1) {
+        const QJsonObject& t = triggers.first();
+        if (t.contains("children") && !t.value("negate").toBool(false)) {
+            conditions = t.value("children").toArray();
+            logic = t.value("logic").toString("AND");
+        } else {
+            conditions.append(t);
+        }
+        return;
+    }
+    for (const auto& t : triggers)
+        conditions.append(t);
+    logic = QStringLiteral("OR");
+}
+
+} // namespace
+
+QJsonObject PineImport::to_json() const {
+    return QJsonObject{{"success", ok},
+                       {"strategy",
+                        QJsonObject{{"name", strategy.name},
+                                    {"description", strategy.description},
+                                    {"timeframe", strategy.timeframe},
+                                    {"entry_conditions", strategy.entry_conditions},
+                                    {"exit_conditions", strategy.exit_conditions},
+                                    {"entry_logic", strategy.entry_logic},
+                                    {"exit_logic", strategy.exit_logic},
+                                    {"stop_loss", strategy.stop_loss},
+                                    {"take_profit", strategy.take_profit},
+                                    {"trailing_stop", strategy.trailing_stop},
+                                    {"position_size_pct", strategy.position_size_pct}}},
+                       {"warnings", QJsonArray::fromStringList(warnings)},
+                       {"errors", QJsonArray::fromStringList(errors)}};
+}
+
+PineImport PineImporter::transpile(const QString& source) {
+    PineImport result;
+    PineTranslator tx;
+    auto& s = result.strategy;
+    s.name = QStringLiteral("Pine import");
+    s.description = QStringLiteral("Imported from Pine Script");
+
+    int version = 0;
+    const QVector<PineLine> lines = pine_lines(source, &version);
+    if (version > 0 && version < 5)
+        tx.warnings.append(QString("written for Pine v%1 — translated with v5 semantics").arg(version));
+
+    struct Block {
+        int indent;
+        std::optional<PineNode> cond; // nullopt = an unsupported branch, skipped
+    };
+    QVector<Block> blocks;
+    QVector<QJsonObject> long_entries, short_entries, exits;
+    bool is_indicator = false;
+
+    for (const PineLine& pl : lines) {
+        tx.line = pl.number;
+        while (!blocks.isEmpty() && pl.indent <= blocks.last().indent)
+            blocks.removeLast();
+        bool skipped = false;
+        for (const auto& b : blocks)
+            skipped = skipped || !b.cond;
+        if (skipped)
+            continue;
+
+        QString lex_error;
+        const QVector<PineToken> toks = pine_lex(pl.text, &lex_error);
+        if (!lex_error.isEmpty()) {
+            tx.fail(lex_error);
+            continue;
+        }
+        if (toks.isEmpty())
+            continue;
+        const QString head = toks[0].kind == PineToken::Kind::Ident ? toks[0].text : QString();
+
+        // ── Control flow ──
+        if (head == "if") {
+            PineParser p(toks, 1);
+            PineNode cond = p.expression();
+            if (!p.error.isEmpty() || !p.at_end()) {
+                tx.fail(p.error.isEmpty() ? QStringLiteral("could not parse the if condition") : p.error);
+                blocks.append({pl.indent, std::nullopt});
+                continue;
+            }
+            blocks.append({pl.indent, std::move(cond)});
+            continue;
+        }
+        if (head == "else") {
+            tx.warn("else branches are not translated — the block is skipped");
+            blocks.append({pl.indent, std::nullopt});
+            continue;
+        }
+        if (head == "for" || head == "while" || head == "switch" || head == "type" || head == "method" ||
+            head == "import" || head == "export") {
+            tx.warn(QString("'%1' is not translated — the block is skipped").arg(head));
+            blocks.append({pl.indent, std::nullopt});
+            continue;
+        }
+        if (toks.size() > 1 && toks[1].kind == PineToken::Kind::Op && toks[1].text == "(" &&
+            std::any_of(toks.begin(), toks.end(), [](const PineToken& t) { return t.text == "=>"; })) {
+            tx.fail(QString("user function '%1' is not translated").arg(head));
+            blocks.append({pl.indent, std::nullopt});
+            continue;
+        }
+        if (kPineCosmetics.contains(head.section('.', 0, 0)))
+            continue;
+
+        // ── Declarations ──
+        if (head == "strategy" || head == "indicator" || head == "study") {
+            PineParser p(toks);
+            const PineNode call = p.expression();
+            if (head != "strategy") {
+                is_indicator = true;
+                continue;
+            }
+            if (const PineNode* title = call.arg("title", 0); title && title->kind == PineNode::Kind::String)
+                s.name = title->text;
+            const PineNode* qty_type = call.arg("default_qty_type", -1);
+            if (qty_type && tx.is_name(qty_type, "strategy.percent_of_equity"))
+                s.position_size_pct = tx.num_arg(call, "default_qty_value", -1, 100.0);
+            continue;
+        }
+
+        // ── Orders ──
+        if (head.startsWith("strategy.")) {
+            PineParser p(toks);
+            const PineNode call = p.expression();
+            if (!p.error.isEmpty() || call.kind != PineNode::Kind::Call) {
+                tx.fail(p.error.isEmpty() ? QString("could not parse %1").arg(head) : p.error);
+                continue;
+            }
+            // The enclosing if-conditions AND the `when` argument.
+            std::optional<PineNode> cond;
+            for (const auto& b : blocks)
+                cond = cond ? pine_binary("and", std::move(*cond), *b.cond) : *b.cond;
+            if (const PineNode* when = call.arg("when", -1))
+                cond = cond ? pine_binary("and", std::move(*cond), *when) : *when;
+
+            if (head == "strategy.entry" || head == "strategy.close" || head == "strategy.close_all") {
+                if (!cond) {
+                    tx.fail(QString("%1 without a condition would fire on every bar").arg(head));
+                    continue;
+                }
+                auto c = tx.condition(*cond);
+                if (!c)
+                    continue;
+                if (head != "strategy.entry")
+                    exits.append(*c);
+                else if (tx.is_name(call.arg("direction", 1), "strategy.short"))
+                    short_entries.append(*c);
+                else
+                    long_entries.append(*c);
+                continue;
+            }
+            if (head == "strategy.exit") {
+                if (cond)
+                    tx.warn("strategy.exit stops/limits apply from the entry on, whatever encloses them");
+                if (call.arg("stop", -1)) {
+                    if (const auto pct = tx.avg_price_pct(call.arg("stop", -1)))
+                        s.stop_loss = *pct;
+                    else
+                        tx.fail("stop is only translated as strategy.position_avg_price * (1 - x)");
+                }
+                if (call.arg("limit", -1)) {
+                    if (const auto pct = tx.avg_price_pct(call.arg("limit", -1)))
+                        s.take_profit = *pct;
+                    else
+                        tx.fail("limit is only translated as strategy.position_avg_price * (1 + x)");
+                }
+                for (const char* ticks : {"loss", "profit", "trail_points", "trail_offset", "trail_price"})
+                    if (call.arg(ticks, -1))
+                        tx.warn(QString("strategy.exit %1 (in ticks) is not translated — set the stop, target or "
+                                        "trailing % in the builder")
+                                    .arg(ticks));
+                continue;
+            }
+            if (head != "strategy.cancel" && head != "strategy.cancel_all")
+                tx.warn(QString("%1 is not translated").arg(head));
+            continue;
+        }
+
+        // ── Assignments ──
+        if (!blocks.isEmpty()) {
+            tx.warn("assignments inside if blocks are not translated");
+            continue;
+        }
+        if (toks[0].kind == PineToken::Kind::Op && toks[0].text == "[") {
+            QStringList names;
+            int i = 1;
+            for (; i < toks.size() && toks[i].text != "]"; ++i)
+                if (toks[i].kind == PineToken::Kind::Ident)
+                    names.append(toks[i].text);
+            if (i + 1 >= toks.size() || toks[i + 1].text != "=") {
+                tx.fail(QStringLiteral("could not parse the tuple assignment"));
+                continue;
+            }
+            PineParser p(toks, i + 2);
+            const PineNode call = p.expression();
+            if (!p.error.isEmpty() || call.kind != PineNode::Kind::Call) {
+                tx.fail(p.error.isEmpty() ? QStringLiteral("tuple assignment from a non-call") : p.error);
+                continue;
+            }
+            for (int k = 0; k < names.size(); ++k)
+                if (names[k] != "_")
+                    tx.tuple_vars.insert(names[k], {call, k});
+            continue;
+        }
+        int i = 0;
+        while (i < toks.size() - 1 && toks[i].kind == PineToken::Kind::Ident && kPineTypeWords.contains(toks[i].text) &&
+               toks[i + 1].kind == PineToken::Kind::Ident)
+            ++i;
+        if (i + 1 < toks.size() && toks[i].kind == PineToken::Kind::Ident && toks[i + 1].kind == PineToken::Kind::Op) {
+            const QString& assign = toks[i + 1].text;
+            if (assign == "=") {
+                PineParser p(toks, i + 2);
+                PineNode rhs = p.expression();
+                if (!p.error.isEmpty() || !p.at_end()) {
+                    tx.fail(p.error.isEmpty() ? QString("could not parse '%1'").arg(toks[i].text) : p.error);
+                    continue;
+                }
+                tx.vars.insert(toks[i].text, std::move(rhs));
+                continue;
+            }
+            if (assign == ":=" || assign == "+=" || assign == "-=" || assign == "*=" || assign == "/=") {
+                tx.warn(QString("reassignment of '%1' is not translated — its first definition is used")
+                            .arg(toks[i].text));
+                continue;
+            }
+        }
+        tx.warn(QString("statement not translated: %1").arg(pl.text.left(60)));
+    }
+
+    tx.line = 0;
+    if (!long_entries.isEmpty()) {
+        pine_assign(long_entries, s.entry_conditions, s.entry_logic);
+        QVector<QJsonObject> all_exits = exits;
+        all_exits += short_entries;
+        if (!short_entries.isEmpty())
+            tx.warnings.append("short entries are translated as exits of the long — the engine holds one side");
+        pine_assign(all_exits, s.exit_conditions, s.exit_logic);
+    } else if (!short_entries.isEmpty()) {
+        pine_assign(short_entries, s.entry_conditions, s.entry_logic);
+        pine_assign(exits, s.exit_conditions, s.exit_logic);
+        tx.warnings.append("the script only goes short — deploy it with Entry Side SELL");
+    } else {
+        tx.errors.append(is_indicator ? QStringLiteral("an indicator() script places no orders — nothing to trade")
+                                      : QStringLiteral("no strategy.entry could be translated"));
+    }
+
+    result.ok = !s.entry_conditions.isEmpty();
+    result.warnings = tx.warnings;
+    result.errors = tx.errors;
+    return result;
+}
+
+} // namespace fincept::algo
@@ -0,0 +1,53 @@
+// src/algo_engine/PineImporter.h
+#pragma once
+#include "services/algo_trading/AlgoTradingTypes.h"
+
+#include <QJsonObject>
+#include <QString>
+#include <QStringList>
+
+namespace fincept::algo {
+
+/// Result of translating a Pine Script strategy. `ok` is false when nothing
+/// tradable came out (no entry could be translated); `warnings` list what was
+/// approximated or dropped and `errors` what could not be translated, each
+/// prefixed with its source line ("line 12: …").
+struct PineImport {
+    bool ok = false;
+    fincept::services::algo::AlgoStrategy strategy;
+    QStringList warnings;
+    QStringList errors;
+
+    QJsonObject to_json() const;
+};
+
+/// Translates a Pine Script v5 strategy into the Strategy Builder's condition
+/// DSL, so it can be edited, backtested and deployed like a native strategy.
+///
+/// Understood:
+///   • `strategy("Name", …)` — the name, and default_qty_value as the
+///     position size when default_qty_type is strategy.percent_of_equity;
+///   • constants and `input.*()` defaults, with arithmetic between them;
+///   • series variables and tuple assignments (`[m, s, h] = ta.macd(…)`),
+///     `x[n]` history references (bar offsets);
+///   • ta.sma/ema/wma (on close), rsi, atr, cci, mfi, roc, wpr, obv, vwap,
+///     macd, bb, kc, supertrend, dmi, stoch (raw %K, or %D as
+///     `ta.sma(ta.stoch(…), d)`), highest(high)/lowest(low) as Donchian;
+///   • comparisons, ta.crossover/crossunder/cross, ta.rising/falling over one
+///     bar, and/or/not — nested into condition groups;
+///   • `if cond` blocks and `when =` around strategy.entry / close /
+///     close_all, and strategy.exit stops and limits written as
+///     `strategy.position_avg_price * (1 ∓ x)` (→ stop loss / take profit %).
+///
+/// The engine is long-only per deployment: short entries become exits of the
+/// long (or the entries themselves when the script only goes short — deploy
+/// with Entry Side SELL). Several entry (exit) triggers are OR-ed. Arithmetic
+/// on series, other sources, ternaries and `else` branches are reported as
+/// errors; plots, alerts and other cosmetics are ignored. The timeframe is
+/// left empty — Pine takes it from the chart.
+class PineImporter {
+  public:
+    static PineImport transpile(const QString& source);
+};
+
+} // namespace fincept::algo
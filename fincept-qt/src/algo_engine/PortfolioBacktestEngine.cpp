@@ -0,0 +1,448 @@
+// src/algo_engine/PortfolioBacktestEngine.cpp
+#include "algo_engine/PortfolioBacktestEngine.h"
+
+#include "core/logging/Logger.h"
+
+#include <QDateTime>
+#include <QHash>
+#include <QJsonArray>
+#include <QTimeZone>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::algo {
+
+namespace {
+
+double pbt_round(double v, int decimals) {
+    const double f = std::pow(10.0, decimals);
+    return std::round(v * f) / f;
+}
+
+// One standalone BacktestEngine trade, replayed by the pool.
+struct PbtTrade {
+    int64_t entry_time = 0;
+    int64_t exit_time = 0;
+    int entry_bar = 0;
+    double entry_price = 0;
+    double exit_price = 0;
+    QString reason;
+};
+
+struct PbtSleeve {
+    QVector<PbtTrade> trades;
+    QHash<int64_t, int> bar_at; // open_time → candle index
+    double bars_per_year = 252.0;
+    double weight = 0.0;
+    double standalone_return = 0.0;
+    int next = 0;    // next standalone trade not yet entered or skipped
+    int active = -1; // trade currently held
+    double shares = 0;
+    double entry_price = 0;
+    double entry_fee = 0;
+    double entry_alloc_pct = 0;
+    double last_close = 0;
+
+    double pnl = 0;
+    int taken = 0;
+    int skipped = 0;
+    int wins = 0;
+    double win_pct_sum = 0;
+    double loss_pct_sum = 0;
+    double alloc_pct_sum = 0;
+    QJsonArray log;
+};
+
+// An order at one timestamp, before netting.
+struct PbtOrder {
+    int sleeve = 0;
+    bool buy = true;
+    double quantity = 0;
+    double price = 0;
+    double alloc_pct = 0;
+};
+
+// Annualised close-to-close volatility over `lookback` bars before bar `i`.
+double pbt_realised_vol(const QVector<OhlcvCandle>& c, int i, int lookback, double bars_per_year) {
+    const int from = std::max(1, i - lookback);
+    QVector<double> rets;
+    for (int k = from; k < i; ++k)
+        if (c[k - 1].close > 0)
+            rets.append(c[k].close / c[k - 1].close - 1.0);
+    if (rets.size() < 2)
+        return 0.0;
+    double mean = 0.0, var = 0.0;
+    for (double r : rets)
+        mean += r;
+    mean /= rets.size();
+    for (double r : rets)
+        var += (r - mean) * (r - mean);
+    return std::sqrt(var / (rets.size() - 1) * bars_per_year);
+}
+
+// Fraction of equity for the sleeve's next entry under `a`; ≤ 0 skips it.
+double pbt_fraction(const PbtSleeve& s, const PortfolioSleeve& in, const PortfolioAllocation& a, int bar) {
+    switch (a.method) {
+        case PortfolioAllocation::Method::FixedFraction:
+            return s.weight;
+        case PortfolioAllocation::Method::VolTarget: {
+            const double vol = pbt_realised_vol(in.candles, bar, a.vol_lookback, s.bars_per_year);
+            if (vol <= 0)
+                return s.weight;
+            return s.weight * std::min(a.max_vol_scale, a.target_vol / vol);
+        }
+        case PortfolioAllocation::Method::Kelly: {
+            const int closed = s.taken - (s.active >= 0 ? 1 : 0);
+            if (closed < a.kelly_min_trades)
+                return s.weight;
+            const int losses = closed - s.wins;
+            if (losses == 0 || s.loss_pct_sum <= 0)
+                return a.kelly_cap;
+            if (s.wins == 0)
+                return 0.0;
+            const double p = static_cast<double>(s.wins) / closed;
+            const double payoff = (s.win_pct_sum / s.wins) / (s.loss_pct_sum / losses);
+            const double kelly = payoff > 0 ? p - (1.0 - p) / payoff : 0.0;
+            return std::clamp(kelly * a.kelly_multiplier, 0.0, a.kelly_cap);
+        }
+    }
+    return s.weight;
+}
+
+} // namespace
+
+// ── Allocation ───────────────────────────────────────────────────────────────
+
+PortfolioAllocation PortfolioAllocation::from_json(const QJsonObject& o, QString* error) {
+    PortfolioAllocation a;
+    const QString method = o.value("method").toString(QStringLiteral("fixed_fraction")).toLower();
+    if (method == "vol_target" || method == "volatility_target")
+        a.method = Method::VolTarget;
+    else if (method == "kelly" || method == "kelly_cap")
+        a.method = Method::Kelly;
+    else if (method != "fixed_fraction" && method != "fixed" && error)
+        *error = QString("Unknown allocation method '%1' (fixed_fraction, vol_target, kelly)").arg(method);
+    a.target_vol = std::clamp(o.value("target_vol").toDouble(a.target_vol), 0.01, 2.0);
+    a.vol_lookback = std::clamp(o.value("vol_lookback").toInt(a.vol_lookback), 5, 500);
+    a.max_vol_scale = std::clamp(o.value("max_vol_scale").toDouble(a.max_vol_scale), 0.1, 10.0);
+    a.kelly_multiplier = std::clamp(o.value("kelly_multiplier").toDouble(a.kelly_multiplier), 0.0, 1.0);
+    a.kelly_cap = std::clamp(o.value("kelly_cap").toDouble(a.kelly_cap), 0.0, 1.0);
+    a.kelly_min_trades = std::clamp(o.value("kelly_min_trades").toInt(a.kelly_min_trades), 1, 1000);
+    a.commission_bps = std::clamp(o.value("commission_bps").toDouble(0.0), 0.0, 1000.0);
+    return a;
+}
+
+QJsonObject PortfolioAllocation::to_json() const {
+    static const char* kNames[] = {"fixed_fraction", "vol_target", "kelly"};
+    return QJsonObject{{"method", kNames[static_cast<int>(method)]},
+                       {"target_vol", target_vol},
+                       {"vol_lookback", vol_lookback},
+                       {"max_vol_scale", max_vol_scale},
+                       {"kelly_multiplier", kelly_multiplier},
+                       {"kelly_cap", kelly_cap},
+                       {"kelly_min_trades", kelly_min_trades},
+                       {"commission_bps", commission_bps}};
+}
+
+// ── Run ──────────────────────────────────────────────────────────────────────
+
+QJsonObject PortfolioBacktestEngine::run(const QVector<PortfolioSleeve>& sleeves, double initial_capital,
+                                         const PortfolioAllocation& allocation) {
+    auto fail = [](const QString& msg) { return QJsonObject{{"success", false}, {"error", msg}}; };
+    if (sleeves.isEmpty())
+        return fail(QStringLiteral("No strategies to backtest"));
+    if (initial_capital <= 0)
+        return fail(QStringLiteral("Initial capital must be positive"));
+
+    // ── Standalone signals per sleeve ───────────────────────────────────────
+    QVector<PbtSleeve> state(sleeves.size());
+    QStringList warnings;
+    double weight_sum = 0.0;
+    int unweighted = 0;
+    for (const auto& in : sleeves) {
+        weight_sum += std::max(0.0, in.weight);
+        unweighted += in.weight > 0 ? 0 : 1;
+    }
+    const double default_weight = unweighted > 0 ? std::max(0.0, 1.0 - weight_sum) / unweighted : 0.0;
+    if (weight_sum > 1.0 + 1e-9)
+        warnings << QString("Weights sum to %1 — entries are capped by available cash").arg(weight_sum, 0, 'f', 2);
+
+    QVector<int64_t> timeline;
+    for (int i = 0; i < sleeves.size(); ++i) {
+        const PortfolioSleeve& in = sleeves[i];
+        PbtSleeve& s = state[i];
+        s.weight = in.weight > 0 ? in.weight : (default_weight > 0 ? default_weight : 1.0 / sleeves.size());
+        s.bars_per_year = BacktestEngine::bars_per_year(in.params.timeframe);
+
+        BacktestParams p = in.params;
+        p.position_size_pct = 100.0;
+        p.initial_capital = initial_capital;
+        const QJsonObject r = BacktestEngine::run(in.candles, p, false);
+        if (!r.value("success").toBool()) {
+            warnings << QString("%1 (%2) skipped: %3").arg(in.name, in.symbol, r.value("error").toString());
+            continue;
+        }
+        s.standalone_return = r.value("total_return").toDouble();
+        for (int b = 0; b < in.candles.size(); ++b) {
+            s.bar_at.insert(in.candles[b].open_time, b);
+            timeline.append(in.candles[b].open_time);
+        }
+        for (const auto& tv : r.value("trades").toArray()) {
+            const QJsonObject t = tv.toObject();
+            PbtTrade trade;
+            trade.entry_bar = t.value("entry_bar").toInt();
+            trade.entry_time = in.candles[trade.entry_bar].open_time;
+            trade.exit_time = in.candles[t.value("exit_bar").toInt()].open_time;
+            trade.entry_price = in.candles[trade.entry_bar].open;
+            trade.exit_price = t.value("exit_price").toDouble();
+            trade.reason = t.value("reason").toString();
+            s.trades.append(trade);
+        }
+    }
+    if (timeline.isEmpty())
+        return fail(QStringLiteral("No strategy could be backtested: ") + warnings.join("; "));
+    std::sort(timeline.begin(), timeline.end());
+    timeline.erase(std::unique(timeline.begin(), timeline.end()), timeline.end());
+
+    // ── Shared-pool replay ──────────────────────────────────────────────────
+    double cash = initial_capital;
+    double equity = initial_capital, peak = initial_capital, max_dd = 0.0;
+    double exposure_sum = 0.0, max_exposure = 0.0;
+    int max_positions = 0;
+    int netted_events = 0;
+    double crossed_qty = 0.0, crossed_notional = 0.0, commission = 0.0, commission_saved = 0.0;
+    QVector<int64_t> curve_t;
+    QVector<double> curve_v;
+
+    for (const int64_t t : timeline) {
+        QVector<PbtOrder> orders;
+        double available = cash;
+
+        // Exits first — they free cash for this timestamp's entries.
+        for (int i = 0; i < state.size(); ++i) {
+            PbtSleeve& s = state[i];
+            if (s.active >= 0 && s.trades[s.active].exit_time == t) {
+                orders.append({i, false, s.shares, s.trades[s.active].exit_price, 0.0});
+                available += s.shares * s.trades[s.active].exit_price;
+            }
+        }
+        for (int i = 0; i < state.size(); ++i) {
+            PbtSleeve& s = state[i];
+            if (s.active >= 0 || s.next >= s.trades.size() || s.trades[s.next].entry_time != t)
+                continue;
+            const PbtTrade& trade = s.trades[s.next];
+            const double fraction = pbt_fraction(s, sleeves[i], allocation, trade.entry_bar);
+            const double unit = trade.entry_price * (1.0 + allocation.commission_bps / 10000.0);
+            const double budget = std::min(fraction * equity, available);
+            const double qty = fraction > 0 && unit > 0 ? std::floor(budget / unit) : 0.0;
+            if (qty < 1.0) {
+                ++s.skipped;
+                ++s.next;
+                continue;
+            }
+            available -= qty * unit;
+            orders.append({i, true, qty, trade.entry_price, qty * trade.entry_price / equity * 100.0});
+        }
+
+        // Net buys against sells per symbol: only the difference reaches the
+        // market and pays commission, shared pro rata across the orders.
+        QHash<QString, QPair<double, double>> per_symbol; // symbol → (bought, sold)
+        for (const auto& o : orders) {
+            auto& bs = per_symbol[sleeves[o.sleeve].symbol];
+            (o.buy ? bs.first : bs.second) += o.quantity;
+        }
+        QHash<QString, double> fee_share; // symbol → net / gross
+        for (auto it = per_symbol.cbegin(); it != per_symbol.cend(); ++it) {
+            const double gross = it.value().first + it.value().second;
+            const double crossed = std::min(it.value().first, it.value().second);
+            fee_share.insert(it.key(), gross > 0 ? (gross - 2.0 * crossed) / gross : 1.0);
+            if (crossed > 0) {
+                ++netted_events;
+                crossed_qty += crossed;
+            }
+        }
+
+        for (const auto& o : orders) {
+            PbtSleeve& s = state[o.sleeve];
+            const QString& symbol = sleeves[o.sleeve].symbol;
+            const double notional = o.quantity * o.price;
+            const double full_fee = notional * allocation.commission_bps / 10000.0;
+            const double fee = full_fee * fee_share.value(symbol, 1.0);
+            commission += fee;
+            commission_saved += full_fee - fee;
+            if (fee_share.value(symbol, 1.0) < 1.0)
+                crossed_notional += notional * (1.0 - fee_share.value(symbol, 1.0)) / 2.0;
+
+            if (o.buy) {
+                cash -= notional + fee;
+                s.active = s.next++;
+                s.shares = o.quantity;
+                s.entry_price = o.price;
+                s.entry_fee = fee;
+                s.entry_alloc_pct = o.alloc_pct;
+                s.alloc_pct_sum += o.alloc_pct;
+                ++s.taken;
+                continue;
+            }
+            const PbtTrade& trade = s.trades[s.active];
+            cash += notional - fee;
+            const double pnl = (o.price - s.entry_price) * o.quantity - s.entry_fee - fee;
+            const double pnl_pct = pnl / (s.entry_price * o.quantity) * 100.0;
+            s.pnl += pnl;
+            if (pnl > 0) {
+                ++s.wins;
+                s.win_pct_sum += pnl_pct;
+            } else {
+                s.loss_pct_sum += std::abs(pnl_pct);
+            }
+            s.log.append(QJsonObject{{"entry_time", static_cast<double>(trade.entry_time)},
+                                     {"exit_time", static_cast<double>(trade.exit_time)},
+                                     {"entry_price", pbt_round(s.entry_price, 4)},
+                                     {"exit_price", pbt_round(o.price, 4)},
+                                     {"shares", o.quantity},
+                                     {"allocation_pct", pbt_round(s.entry_alloc_pct, 2)},
+                                     {"pnl", pbt_round(pnl, 2)},
+                                     {"pnl_pct", pbt_round(pnl_pct, 2)},
+                                     {"reason", trade.reason}});
+            s.active = -1;
+            s.shares = 0;
+        }
+
+        // Mark every open position at its latest close.
+        double gross = 0.0;
+        int positions = 0;
+        for (int i = 0; i < state.size(); ++i) {
+            PbtSleeve& s = state[i];
+            const auto bar = s.bar_at.constFind(t);
+            if (bar != s.bar_at.constEnd())
+                s.last_close = sleeves[i].candles[*bar].close;
+            if (s.active >= 0) {
+                gross += s.shares * s.last_close;
+                ++positions;
+            }
+        }
+        equity = cash + gross;
+        peak = std::max(peak, equity);
+        if (peak > 0)
+            max_dd = std::max(max_dd, (peak - equity) / peak * 100.0);
+        const double exposure = equity > 0 ? gross / equity * 100.0 : 0.0;
+        exposure_sum += exposure;
+        max_exposure = std::max(max_exposure, exposure);
+        max_positions = std::max(max_positions, positions);
+        curve_t.append(t);
+        curve_v.append(equity);
+    }
+
+    // ── Metrics ─────────────────────────────────────────────────────────────
+    // Daily equity returns so sleeves on different timeframes share one scale.
+    QVector<double> daily;
+    for (int i = 0; i < curve_t.size(); ++i) {
+        const QDate d = QDateTime::fromMSecsSinceEpoch(curve_t[i], QTimeZone::utc()).date();
+        const bool last_of_day = i + 1 == curve_t.size() ||
+                                 QDateTime::fromMSecsSinceEpoch(curve_t[i + 1], QTimeZone::utc()).date() != d;
+        if (last_of_day)
+            daily.append(curve_v[i]);
+    }
+    double sharpe = 0.0, sortino = 0.0;
+    if (daily.size() > 2) {
+        QVector<double> rets;
+        for (int i = 1; i < daily.size(); ++i)
+            if (daily[i - 1] > 0)
+                rets.append(daily[i] / daily[i - 1] - 1.0);
+        double mean = 0.0, var = 0.0, dvar = 0.0;
+        for (double r : rets)
+            mean += r;
+        mean /= rets.size();
+        for (double r : rets) {
+            var += (r - mean) * (r - mean);
+            if (r < 0)
+                dvar += r * r;
+        }
+        var /= rets.size();
+        dvar /= rets.size();
+        if (var > 0)
+            sharpe = mean / std::sqrt(var) * std::sqrt(252.0);
+        if (dvar > 0)
+            sortino = mean / std::sqrt(dvar) * std::sqrt(252.0);
+    }
+
+    const double final_value = equity;
+    const double total_return_pct = (final_value - initial_capital) / initial_capital * 100.0;
+
+    QJsonArray equity_out, drawdown_out;
+    {
+        const int sz = curve_v.size();
+        const int step = sz > 500 ? sz / 500 : 1;
+        double running_peak = initial_capital;
+        for (int i = 0; i < sz; ++i) {
+            running_peak = std::max(running_peak, curve_v[i]);
+            if (i % step != 0 && i != sz - 1)
+                continue;
+            equity_out.append(QJsonObject{{"time", static_cast<double>(curve_t[i])},
+                                          {"value", pbt_round(curve_v[i], 2)}});
+            drawdown_out.append(QJsonObject{{"time", static_cast<double>(curve_t[i])},
+                                            {"value", pbt_round((running_peak - curve_v[i]) / running_peak * 100.0,
+                                                                2)}});
+        }
+    }
+
+    QJsonArray sleeves_out;
+    int total_trades = 0, total_wins = 0;
+    for (int i = 0; i < state.size(); ++i) {
+        const PbtSleeve& s = state[i];
+        const PortfolioSleeve& in = sleeves[i];
+        total_trades += s.taken;
+        total_wins += s.wins;
+        sleeves_out.append(QJsonObject{
+            {"id", in.id},
+            {"name", in.name},
+            {"symbol", in.symbol},
+            {"timeframe", in.params.timeframe},
+            {"weight", pbt_round(s.weight, 4)},
+            {"signals", int(s.trades.size())},
+            {"trades", s.taken},
+            {"skipped", s.skipped},
+            {"win_rate", s.taken > 0 ? pbt_round(100.0 * s.wins / s.taken, 1) : 0.0},
+            {"pnl", pbt_round(s.pnl, 2)},
+            {"contribution", pbt_round(s.pnl / initial_capital * 100.0, 2)},
+            {"avg_allocation_pct", s.taken > 0 ? pbt_round(s.alloc_pct_sum / s.taken, 2) : 0.0},
+            {"standalone_return", s.standalone_return},
+            {"trade_log", s.log}});
+    }
+
+    LOG_INFO("PortfolioBacktest", QString("%1 sleeves, %2 bars, %3 trades — return %4%, max DD %5%")
+                                      .arg(sleeves.size())
+                                      .arg(timeline.size())
+                                      .arg(total_trades)
+                                      .arg(total_return_pct, 0, 'f', 2)
+                                      .arg(max_dd, 0, 'f', 2));
+
+    return QJsonObject{
+        {"success", true},
+        {"initial_capital", initial_capital},
+        {"final_value", pbt_round(final_value, 2)},
+        {"total_return", pbt_round(total_return_pct, 2)},
+        {"sharpe_ratio", pbt_round(sharpe, 3)},
+        {"sortino", pbt_round(sortino, 3)},
+        {"max_drawdown", pbt_round(max_dd, 2)},
+        {"calmar", max_dd > 0 ? pbt_round(total_return_pct / max_dd, 2) : 0.0},
+        {"total_trades", total_trades},
+        {"win_rate", total_trades > 0 ? pbt_round(100.0 * total_wins / total_trades, 1) : 0.0},
+        {"exposure",
+         QJsonObject{{"avg_gross_pct", pbt_round(exposure_sum / std::max<qsizetype>(1, timeline.size()), 2)},
+                     {"max_gross_pct", pbt_round(max_exposure, 2)},
+                     {"max_concurrent_positions", max_positions}}},
+        {"netting", QJsonObject{{"netted_events", netted_events},
+                                {"crossed_quantity", crossed_qty},
+                                {"crossed_notional", pbt_round(crossed_notional, 2)},
+                                {"commission", pbt_round(commission, 2)},
+                                {"commission_saved", pbt_round(commission_saved, 2)}}},
+        {"allocation", allocation.to_json()},
+        {"equity_curve", equity_out},
+        {"drawdown_curve", drawdown_out},
+        {"sleeves", sleeves_out},
+        {"warnings", QJsonArray::fromStringList(warnings)}};
+}
+
+} // namespace fincept::algo
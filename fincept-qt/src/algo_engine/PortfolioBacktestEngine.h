@@ -0,0 +1,71 @@
+// src/algo_engine/PortfolioBacktestEngine.h
+#pragma once
+#include "algo_engine/AlgoEngineTypes.h"
+#include "algo_engine/BacktestEngine.h"
+
+#include <QJsonObject>
+#include <QString>
+#include <QVector>
+
+namespace fincept::algo {
+
+/// One strategy-on-a-symbol inside a portfolio backtest.
+struct PortfolioSleeve {
+    QString id; // strategy id, or a label for inline strategies
+    QString name;
+    QString symbol;
+    BacktestParams params; // position_size_pct / initial_capital are ignored — the allocation sizes
+    QVector<OhlcvCandle> candles;
+    double weight = 0.0; // share of equity per entry; 0 → an equal split of what the others leave
+};
+
+/// How each entry is sized against the shared pool. Every rule sizes off the
+/// portfolio's current equity and is capped by the cash on hand (no leverage).
+///   fixed_fraction — weight × equity;
+///   vol_target     — weight × equity × target_vol / the symbol's realised
+///                    volatility over `vol_lookback` bars before the entry,
+///                    the multiplier capped at `max_vol_scale`;
+///   kelly          — equity × kelly_multiplier × the Kelly fraction of the
+///                    sleeve's own closed trades so far (win rate, payoff
+///                    ratio), capped at `kelly_cap`; weight × equity until the
+///                    sleeve has `kelly_min_trades` trades, nothing when the
+///                    Kelly fraction is ≤ 0.
+struct PortfolioAllocation {
+    enum class Method { FixedFraction, VolTarget, Kelly };
+    Method method = Method::FixedFraction;
+    double target_vol = 0.15; // annualised
+    int vol_lookback = 20;
+    double max_vol_scale = 2.0;
+    double kelly_multiplier = 0.5;
+    double kelly_cap = 0.25;
+    int kelly_min_trades = 5;
+    double commission_bps = 0.0; // charged on the netted quantity
+
+    /// Unknown keys are ignored; an unknown method sets `error`.
+    static PortfolioAllocation from_json(const QJsonObject& o, QString* error = nullptr);
+    QJsonObject to_json() const;
+};
+
+/// Several strategies backtested against one capital pool.
+///
+/// Each sleeve's signals and fill prices come from BacktestEngine on its own
+/// candles (timing does not depend on size for a long-only book); the pool
+/// then replays every sleeve's trades on one merged timeline. At each
+/// timestamp exits settle first, entries are sized by the allocation rule
+/// and skipped when no cash is left, and orders for the same symbol from
+/// different sleeves are netted — only the difference between buys and sells
+/// trades (and pays commission); the crossed quantity is reported. Positions
+/// are marked at every close.
+///
+/// Returns the combined equity and drawdown curves, total return / Sharpe /
+/// Sortino (daily equity returns) / max drawdown / Calmar, gross exposure,
+/// netting statistics and per-sleeve results (trades taken and skipped, P&L,
+/// contribution, average allocation). On bad input returns
+/// {"success": false, "error": …}.
+class PortfolioBacktestEngine {
+  public:
+    static QJsonObject run(const QVector<PortfolioSleeve>& sleeves, double initial_capital,
+                           const PortfolioAllocation& allocation);
+};
+
+} // namespace fincept::algo
@@ -36,8 +36,8 @@ std::optional<AlgoOrderSignal> PositionManager::check_risk(double current_price)
 
         double basket_pnl_pct = basket_entry_value_ > 1e-10 ? basket_pnl / basket_entry_value_ * 100.0 : 0;
 
-        // Daily loss limit
-        if (max_daily_loss_ > 0 && risk_.daily_pnl <= -std::abs(max_daily_loss_)) {
+        // Daily loss limit — realized today plus the open basket's mark
+        if (max_daily_loss_ > 0 && risk_.daily_pnl + basket_pnl <= -std::abs(max_daily_loss_)) {
             risk_.paused_by_loss_limit = true;
             AlgoOrderSignal sig;
             sig.deployment_id = deployment_id_;
@@ -45,6 +45,19 @@ std::optional<AlgoOrderSignal> PositionManager::check_risk(double current_price)
             return sig;
         }
 
+        // Open position value limit — Σ |mark × quantity| over the legs
+        if (max_position_value_ > 0) {
+            double notional = 0;
+            for (const auto& leg : std::as_const(legs_))
+                notional += std::abs(leg.current_price * leg.quantity);
+            if (notional > max_position_value_) {
+                AlgoOrderSignal sig;
+                sig.deployment_id = deployment_id_;
+                sig.reason = "position_limit";
+                return sig;
+            }
+        }
+
         // Stop loss
         if (stop_loss_pct_ > 0 && basket_pnl_pct <= -std::abs(stop_loss_pct_)) {
             AlgoOrderSignal sig;
@@ -81,8 +94,8 @@ std::optional<AlgoOrderSignal> PositionManager::check_risk(double current_price)
     if (position_.side == PositionSide::None)
         return std::nullopt;
 
-    // Daily loss limit
-    if (max_daily_loss_ > 0 && risk_.daily_pnl <= -std::abs(max_daily_loss_)) {
+    // Daily loss limit — realized today plus the open position's mark
+    if (max_daily_loss_ > 0 && risk_.daily_pnl + position_.unrealized_pnl <= -std::abs(max_daily_loss_)) {
         risk_.paused_by_loss_limit = true;
         AlgoOrderSignal sig;
         sig.deployment_id = deployment_id_;
@@ -92,6 +105,16 @@ std::optional<AlgoOrderSignal> PositionManager::check_risk(double current_price)
         return sig;
     }
 
+    // Open position value limit
+    if (max_position_value_ > 0 && std::abs(position_.quantity * current_price) > max_position_value_) {
+        AlgoOrderSignal sig;
+        sig.deployment_id = deployment_id_;
+        sig.side = (position_.side == PositionSide::Long) ? "SELL" : "BUY";
+        sig.quantity = position_.quantity;
+        sig.reason = "position_limit";
+        return sig;
+    }
+
     double pnl_pct = 0;
     if (position_.side == PositionSide::Long && position_.entry_price > 1e-10)
         pnl_pct = (current_price - position_.entry_price) / position_.entry_price * 100.0;
@@ -257,6 +280,23 @@ bool PositionManager::validate_order_value(double qty, double price) const {
     return (qty * price) <= max_order_value_;
 }
 
+void PositionManager::set_max_position_value(double value) {
+    QMutexLocker lock(&mutex_);
+    max_position_value_ = value;
+}
+
+void PositionManager::set_exit_rules(double stop_loss_pct, double take_profit_pct, double trailing_stop_pct) {
+    QMutexLocker lock(&mutex_);
+    stop_loss_pct_ = stop_loss_pct;
+    take_profit_pct_ = take_profit_pct;
+    trailing_stop_pct_ = trailing_stop_pct;
+}
+
+bool PositionManager::validate_position_value(double notional) const {
+    QMutexLocker lock(&mutex_);
+    return max_position_value_ <= 0 || std::abs(notional) <= max_position_value_;
+}
+
 AlgoPosition PositionManager::position() const {
     QMutexLocker lock(&mutex_);
     return position_;
@@ -26,6 +26,13 @@ class PositionManager {
     bool has_position() const;
     bool is_paused() const;
     bool validate_order_value(double qty, double price) const;
+    // Open-position notional cap (0 = no limit). Entries above it are refused
+    // and an open position marked above it is flattened ("position_limit").
+    void set_max_position_value(double value);
+    // Swaps the exit thresholds in place (strategy hot reload); the open
+    // position and its trailing high/low water marks are kept.
+    void set_exit_rules(double stop_loss_pct, double take_profit_pct, double trailing_stop_pct);
+    bool validate_position_value(double notional) const;
 
     AlgoPosition position() const;
     AlgoMetrics metrics() const;
@@ -53,6 +60,7 @@ class PositionManager {
     double trailing_stop_pct_;
     double max_order_value_;
     double max_daily_loss_;
+    double max_position_value_ = 0;
 
     // Multi-leg basket state (P3)
     QVector<fincept::algo::AlgoLegPosition> legs_;
@@ -1,6 +1,7 @@
 // src/algo_engine/RealtimeScanRunner.cpp
 #include "algo_engine/RealtimeScanRunner.h"
 
+#include "algo_engine/CandleDataFetcher.h"
 #include "algo_engine/ConditionEvaluator.h"
 #include "core/logging/Logger.h"
 #include "trading/DataStreamManager.h"
@@ -77,8 +78,10 @@ void RealtimeScanRunner::start() {
     // `this` is the owner.
     auto& dsm = fincept::trading::DataStreamManager::instance();
     for (const QString& sym : universe_) {
-        dsm.open_quote_feed(this, consumer_id(sym), account_id_, sym,
-                            [this, sym](const fincept::trading::BrokerQuote& q) { on_quote(sym, q); });
+        dsm.open_quote_feed(
+            this, consumer_id(sym), account_id_, sym,
+            [this, sym](const fincept::trading::BrokerQuote& q) { on_quote(sym, q); },
+            [this, sym](const fincept::trading::BrokerGapFill& fill) { on_gap_fill(sym, fill); });
     }
 
     sweep_timer_ = new QTimer(this);
@@ -116,6 +119,15 @@ void RealtimeScanRunner::warm(const QString& symbol, const QVector<OhlcvCandle>&
     st->dirty = true; // evaluate on the next sweep
 }
 
+void RealtimeScanRunner::on_gap_fill(const QString& symbol, const fincept::trading::BrokerGapFill& fill) {
+    auto it = states_.find(symbol);
+    if (it == states_.end())
+        return;
+    SymbolState* st = it.value();
+    if (st->agg->fill_gap(CandleDataFetcher::broker_candles_to_ohlcv(fill.candles, QStringLiteral("1m"))) > 0)
+        st->dirty = true;
+}
+
 void RealtimeScanRunner::on_quote(const QString& symbol, const fincept::trading::BrokerQuote& q) {
     auto it = states_.find(symbol);
     if (it == states_.end())
@@ -16,6 +16,7 @@ class QTimer;
 
 namespace fincept::trading {
 struct BrokerQuote;
+struct BrokerGapFill;
 }
 
 namespace fincept::algo {
@@ -61,6 +62,8 @@ class RealtimeScanRunner : public QObject {
     };
 
     void on_quote(const QString& symbol, const fincept::trading::BrokerQuote& q);
+    // Bars covering a feed outage (see CandleAggregator::fill_gap).
+    void on_gap_fill(const QString& symbol, const fincept::trading::BrokerGapFill& fill);
     void on_sweep();
     QString consumer_id(const QString& symbol) const;
     QVector<OhlcvCandle> eval_window(const SymbolState& st) const;
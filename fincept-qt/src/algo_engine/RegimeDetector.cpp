@@ -0,0 +1,382 @@
+// src/algo_engine/RegimeDetector.cpp
+#include "algo_engine/RegimeDetector.h"
+
+#include <QJsonArray>
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+#include <numeric>
+
+namespace fincept::algo {
+
+namespace {
+
+constexpr double kRdLog2Pi = 1.8378770664093453;
+
+double rd_mean(const QVector<double>& v) {
+    return v.isEmpty() ? 0.0 : std::accumulate(v.begin(), v.end(), 0.0) / v.size();
+}
+
+double rd_var(const QVector<double>& v) {
+    if (v.size() < 2)
+        return 0.0;
+    const double m = rd_mean(v);
+    double ss = 0;
+    for (double x : v)
+        ss += (x - m) * (x - m);
+    return ss / (v.size() - 1);
+}
+
+// Fills the per-state statistics, occupancy and the run length of the last
+// state from `fit.path`.
+void rd_describe(RegimeFit& fit, const QVector<double>& returns, const QVector<double>& vol_index, int k) {
+    const int n = fit.path.size();
+    const QStringList names = RegimeDetector::labels(k);
+    fit.states.resize(k);
+    for (int s = 0; s < k; ++s) {
+        QVector<double> r, v;
+        for (int t = 0; t < n; ++t)
+            if (fit.path[t] == s) {
+                r.append(returns[t]);
+                if (!vol_index.isEmpty())
+                    v.append(vol_index[t]);
+            }
+        fit.states[s].label = names[s];
+        fit.states[s].mean_return = rd_mean(r);
+        fit.states[s].volatility = std::sqrt(rd_var(r));
+        fit.states[s].vol_index = rd_mean(v);
+        fit.states[s].occupancy = n > 0 ? double(r.size()) / n : 0.0;
+    }
+    fit.current = n > 0 ? fit.path.last() : -1;
+    fit.bars_in_current = 0;
+    for (int t = n - 1; t >= 0 && fit.path[t] == fit.current; --t)
+        ++fit.bars_in_current;
+}
+
+} // namespace
+
+QStringList RegimeDetector::labels(int states) {
+    return states == 2 ? QStringList{"calm", "turbulent"} : QStringList{"calm", "normal", "turbulent"};
+}
+
+QJsonObject RegimeFit::to_json(const QVector<int64_t>& times, int path_bars) const {
+    if (!ok)
+        return {{"ok", false}, {"error", error}};
+
+    QJsonArray states_json;
+    QJsonObject probs;
+    for (int s = 0; s < states.size(); ++s) {
+        const auto& st = states[s];
+        states_json.append(QJsonObject{{"state", s},
+                                       {"label", st.label},
+                                       {"mean_return", st.mean_return},
+                                       {"volatility", st.volatility},
+                                       {"vol_index", st.vol_index},
+                                       {"occupancy", st.occupancy}});
+        if (s < current_probs.size())
+            probs[st.label] = current_probs[s];
+    }
+    QJsonArray matrix;
+    for (const auto& row : transition) {
+        QJsonArray r;
+        for (double p : row)
+            r.append(p);
+        matrix.append(r);
+    }
+    QJsonArray path_json;
+    for (int t = std::max(0, int(path.size()) - path_bars); t < path.size(); ++t) {
+        QJsonObject p{{"state", path[t]}};
+        if (t < times.size())
+            p["time"] = static_cast<double>(times[t]);
+        path_json.append(p);
+    }
+    return {{"ok", true},
+            {"method", method},
+            {"current", QJsonObject{{"state", current},
+                                    {"label", current_label()},
+                                    {"bars", bars_in_current},
+                                    {"probabilities", probs}}},
+            {"states", states_json},
+            {"transition", matrix},
+            {"log_likelihood", log_likelihood},
+            {"iterations", iterations},
+            {"path", path_json}};
+}
+
+// ── Hidden Markov model ─────────────────────────────────────────────────────
+
+RegimeFit RegimeDetector::fit_hmm(const QVector<double>& returns, const QVector<double>& vol_index, int states,
+                                  int max_iterations) {
+    RegimeFit fit;
+    fit.method = QStringLiteral("hmm");
+    const int n = returns.size();
+    const int k = states;
+    if (k < 2 || k > 3) {
+        fit.error = QStringLiteral("The HMM supports 2 or 3 states");
+        return fit;
+    }
+    if (n < kMinBars) {
+        fit.error = QString("Need at least %1 bars (got %2)").arg(kMinBars).arg(n);
+        return fit;
+    }
+    const bool with_vix = !vol_index.isEmpty();
+    if (with_vix && vol_index.size() != n) {
+        fit.error = QStringLiteral("Volatility index is not aligned with the returns");
+        return fit;
+    }
+
+    // Observations: return, and log(vol index) when given.
+    const int d = with_vix ? 2 : 1;
+    QVector<QVector<double>> obs(d, QVector<double>(n));
+    for (int t = 0; t < n; ++t) {
+        obs[0][t] = returns[t];
+        if (with_vix)
+            obs[1][t] = std::log(std::max(vol_index[t], 1e-6));
+    }
+
+    // Start from states that differ only in spread (and vol-index level):
+    // calm is tight, turbulent wide, all centred on the sample mean.
+    const QVector<double> spread_factor = k == 2 ? QVector<double>{0.4, 2.5} : QVector<double>{0.3, 1.0, 3.0};
+    const QVector<double> vix_offset = k == 2 ? QVector<double>{-1.0, 1.0} : QVector<double>{-1.0, 0.0, 1.0};
+    QVector<QVector<double>> mu(k, QVector<double>(d)), var(k, QVector<double>(d));
+    QVector<double> floor_var(d);
+    for (int j = 0; j < d; ++j) {
+        const double m = rd_mean(obs[j]);
+        const double v = std::max(rd_var(obs[j]), 1e-12);
+        floor_var[j] = v * 1e-3;
+        for (int s = 0; s < k; ++s) {
+            mu[s][j] = j == 0 ? m : m + vix_offset[s] * std::sqrt(v);
+            var[s][j] = j == 0 ? v * spread_factor[s] : v * 0.5;
+        }
+    }
+    QVector<QVector<double>> a(k, QVector<double>(k, 0.05 / (k - 1)));
+    for (int s = 0; s < k; ++s)
+        a[s][s] = 0.95;
+    QVector<double> pi(k, 1.0 / k);
+
+    QVector<QVector<double>> log_b(n, QVector<double>(k));
+    auto emissions = [&]() {
+        for (int t = 0; t < n; ++t)
+            for (int s = 0; s < k; ++s) {
+                double lb = 0;
+                for (int j = 0; j < d; ++j) {
+                    const double z = obs[j][t] - mu[s][j];
+                    lb -= 0.5 * (kRdLog2Pi + std::log(var[s][j]) + z * z / var[s][j]);
+                }
+                log_b[t][s] = lb;
+            }
+    };
+
+    QVector<QVector<double>> alpha(n, QVector<double>(k)), beta(n, QVector<double>(k));
+    QVector<QVector<double>> posterior(n, QVector<double>(k));
+    QVector<double> scale(n);
+    double prev_ll = -std::numeric_limits<double>::infinity();
+    int it = 0;
+    for (; it < max_iterations; ++it) {
+        emissions();
+
+        // Forward pass, scaled per bar; emissions are shifted by their max so
+        // exp() stays in range.
+        double ll = 0;
+        QVector<double> shift(n);
+        for (int t = 0; t < n; ++t) {
+            shift[t] = *std::max_element(log_b[t].begin(), log_b[t].end());
+            double c = 0;
+            for (int s = 0; s < k; ++s) {
+                double p = 0;
+                if (t == 0)
+                    p = pi[s];
+                else
+                    for (int r = 0; r < k; ++r)
+                        p += alpha[t - 1][r] * a[r][s];
+                alpha[t][s] = p * std::exp(log_b[t][s] - shift[t]);
+                c += alpha[t][s];
+            }
+            if (!(c > 0)) {
+                fit.error = QStringLiteral("HMM likelihood underflow");
+                return fit;
+            }
+            for (int s = 0; s < k; ++s)
+                alpha[t][s] /= c;
+            scale[t] = c;
+            ll += std::log(c) + shift[t];
+        }
+
+        // Backward pass with the same scaling.
+        for (int s = 0; s < k; ++s)
+            beta[n - 1][s] = 1.0;
+        for (int t = n - 2; t >= 0; --t)
+            for (int s = 0; s < k; ++s) {
+                double b = 0;
+                for (int r = 0; r < k; ++r)
+                    b += a[s][r] * std::exp(log_b[t + 1][r] - shift[t + 1]) * beta[t + 1][r];
+                beta[t][s] = b / scale[t + 1];
+            }
+
+        // E-step: state and transition posteriors.
+        QVector<QVector<double>> xi_sum(k, QVector<double>(k, 0.0));
+        for (int t = 0; t < n; ++t) {
+            double norm = 0;
+            for (int s = 0; s < k; ++s) {
+                posterior[t][s] = alpha[t][s] * beta[t][s];
+                norm += posterior[t][s];
+            }
+            for (int s = 0; s < k; ++s)
+                posterior[t][s] /= norm;
+            if (t + 1 < n)
+                for (int s = 0; s < k; ++s)
+                    for (int r = 0; r < k; ++r)
+                        xi_sum[s][r] += alpha[t][s] * a[s][r] * std::exp(log_b[t + 1][r] - shift[t + 1]) *
+                                        beta[t + 1][r] / scale[t + 1];
+        }
+
+        // M-step.
+        for (int s = 0; s < k; ++s) {
+            pi[s] = posterior[0][s];
+            double row = std::accumulate(xi_sum[s].begin(), xi_sum[s].end(), 0.0);
+            for (int r = 0; r < k; ++r)
+                a[s][r] = row > 0 ? xi_sum[s][r] / row : 1.0 / k;
+            double w = 0;
+            for (int t = 0; t < n; ++t)
+                w += posterior[t][s];
+            for (int j = 0; j < d; ++j) {
+                double m = 0;
+                for (int t = 0; t < n; ++t)
+                    m += posterior[t][s] * obs[j][t];
+                m = w > 0 ? m / w : mu[s][j];
+                double v = 0;
+                for (int t = 0; t < n; ++t)
+                    v += posterior[t][s] * (obs[j][t] - m) * (obs[j][t] - m);
+                mu[s][j] = m;
+                var[s][j] = std::max(w > 0 ? v / w : var[s][j], floor_var[j]);
+            }
+        }
+
+        fit.log_likelihood = ll;
+        if (std::abs(ll - prev_ll) < 1e-6 * (1.0 + std::abs(ll)))
+            break;
+        prev_ll = ll;
+    }
+    fit.iterations = it;
+    emissions();
+
+    // Order states calmest first.
+    QVector<int> order(k);
+    std::iota(order.begin(), order.end(), 0);
+    std::sort(order.begin(), order.end(), [&var](int l, int r) { return var[l][0] < var[r][0]; });
+    QVector<int> rank(k);
+    for (int s = 0; s < k; ++s)
+        rank[order[s]] = s;
+
+    fit.transition = QVector<QVector<double>>(k, QVector<double>(k));
+    for (int s = 0; s < k; ++s)
+        for (int r = 0; r < k; ++r)
+            fit.transition[rank[s]][rank[r]] = a[s][r];
+
+    // Filtered probabilities of the last bar (alpha is already normalised).
+    fit.current_probs = QVector<double>(k);
+    for (int s = 0; s < k; ++s)
+        fit.current_probs[rank[s]] = alpha[n - 1][s];
+
+    // Viterbi.
+    QVector<QVector<double>> delta(n, QVector<double>(k));
+    QVector<QVector<int>> back(n, QVector<int>(k, 0));
+    for (int s = 0; s < k; ++s)
+        delta[0][s] = std::log(std::max(pi[s], 1e-300)) + log_b[0][s];
+    for (int t = 1; t < n; ++t)
+        for (int s = 0; s < k; ++s) {
+            int best = 0;
+            double best_v = -std::numeric_limits<double>::infinity();
+            for (int r = 0; r < k; ++r) {
+                const double v = delta[t - 1][r] + std::log(std::max(a[r][s], 1e-300));
+                if (v > best_v) {
+                    best_v = v;
+                    best = r;
+                }
+            }
+            delta[t][s] = best_v + log_b[t][s];
+            back[t][s] = best;
+        }
+    QVector<int> raw(n);
+    raw[n - 1] = int(std::max_element(delta[n - 1].begin(), delta[n - 1].end()) - delta[n - 1].begin());
+    for (int t = n - 1; t > 0; --t)
+        raw[t - 1] = back[t][raw[t]];
+    fit.path.resize(n);
+    for (int t = 0; t < n; ++t)
+        fit.path[t] = rank[raw[t]];
+
+    rd_describe(fit, returns, vol_index, k);
+    fit.ok = true;
+    return fit;
+}
+
+// ── Threshold classifier ────────────────────────────────────────────────────
+
+RegimeFit RegimeDetector::classify_threshold(const QVector<double>& returns, const QVector<double>& vol_index,
+                                             int states, double calm_level, double turbulent_level) {
+    RegimeFit fit;
+    fit.method = QStringLiteral("threshold");
+    const int n = returns.size();
+    const int k = states;
+    if (k < 2 || k > 3) {
+        fit.error = QStringLiteral("The classifier supports 2 or 3 states");
+        return fit;
+    }
+    if (n < kMinBars) {
+        fit.error = QString("Need at least %1 bars (got %2)").arg(kMinBars).arg(n);
+        return fit;
+    }
+    const bool with_vix = !vol_index.isEmpty();
+    if (with_vix && vol_index.size() != n) {
+        fit.error = QStringLiteral("Volatility index is not aligned with the returns");
+        return fit;
+    }
+
+    // The level each bar is judged on, and the cut points between states.
+    QVector<double> level(n);
+    QVector<double> cuts;
+    if (with_vix) {
+        level = vol_index;
+        cuts = k == 2 ? QVector<double>{(calm_level + turbulent_level) / 2.0}
+                      : QVector<double>{calm_level, turbulent_level};
+    } else {
+        constexpr int kWindow = 20;
+        for (int t = 0; t < n; ++t) {
+            const int from = std::max(0, t - kWindow + 1);
+            level[t] = std::sqrt(rd_var(QVector<double>(returns.begin() + from, returns.begin() + t + 1)));
+        }
+        // The warm-up bars take the first full window's level.
+        for (int t = 0; t < kWindow - 1; ++t)
+            level[t] = level[kWindow - 1];
+        QVector<double> sorted(level.begin() + kWindow - 1, level.end());
+        std::sort(sorted.begin(), sorted.end());
+        auto quantile = [&sorted](double q) {
+            return sorted[std::min(int(q * sorted.size()), int(sorted.size()) - 1)];
+        };
+        cuts = k == 2 ? QVector<double>{quantile(0.5)} : QVector<double>{quantile(1.0 / 3), quantile(2.0 / 3)};
+    }
+
+    fit.path.resize(n);
+    for (int t = 0; t < n; ++t)
+        fit.path[t] = int(std::upper_bound(cuts.begin(), cuts.end(), level[t]) - cuts.begin());
+
+    // Empirical transition frequencies.
+    fit.transition = QVector<QVector<double>>(k, QVector<double>(k, 0.0));
+    for (int t = 1; t < n; ++t)
+        fit.transition[fit.path[t - 1]][fit.path[t]] += 1.0;
+    for (auto& row : fit.transition) {
+        const double total = std::accumulate(row.begin(), row.end(), 0.0);
+        for (double& p : row)
+            p = total > 0 ? p / total : 0.0;
+    }
+
+    rd_describe(fit, returns, vol_index, k);
+    fit.current_probs = QVector<double>(k, 0.0);
+    fit.current_probs[fit.current] = 1.0;
+    fit.iterations = 0;
+    fit.ok = true;
+    return fit;
+}
+
+} // namespace fincept::algo
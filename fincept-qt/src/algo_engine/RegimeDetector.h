@@ -0,0 +1,63 @@
+// src/algo_engine/RegimeDetector.h
+// Market regime classification over an index's daily returns, optionally with
+// a volatility index (VIX) alongside. Pure computation, no I/O — RegimeMonitor
+// fetches the history and publishes changes.
+#pragma once
+
+#include <QJsonObject>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+namespace fincept::algo {
+
+/// One fitted regime. States are ordered by return volatility, calmest first,
+/// and labelled calm / turbulent (2 states) or calm / normal / turbulent (3).
+struct RegimeState {
+    QString label;
+    double mean_return = 0; // per bar
+    double volatility = 0;  // per-bar stdev of returns
+    double vol_index = 0;   // mean volatility-index level (0 when none was given)
+    double occupancy = 0;   // share of bars spent in the state
+};
+
+struct RegimeFit {
+    bool ok = false;
+    QString error;
+    QString method;
+    QVector<RegimeState> states;
+    QVector<QVector<double>> transition; // hmm: P(state j at t+1 | state i at t)
+    QVector<int> path;                   // most likely state per bar
+    QVector<double> current_probs;       // P(state) at the last bar
+    int current = -1;
+    int bars_in_current = 0; // consecutive bars the current state has held
+    double log_likelihood = 0;
+    int iterations = 0;
+
+    QString current_label() const { return current >= 0 ? states[current].label : QString(); }
+    QJsonObject to_json(const QVector<int64_t>& times = {}, int path_bars = 0) const;
+};
+
+class RegimeDetector {
+  public:
+    /// Gaussian hidden Markov model (diagonal covariance) fitted by Baum-Welch.
+    /// Observations are the returns and, when `vol_index` is non-empty (same
+    /// length), the log of the volatility index. `path` is the Viterbi path
+    /// and `current_probs` the filtered (not smoothed) state probabilities of
+    /// the last bar. Needs 2–3 states and at least 60 bars.
+    static RegimeFit fit_hmm(const QVector<double>& returns, const QVector<double>& vol_index, int states,
+                             int max_iterations = 200);
+
+    /// Rule-based alternative: with a volatility index, calm below `calm_level`
+    /// and turbulent above `turbulent_level`; without one, the trailing
+    /// 20-bar realised volatility against its own terciles (medians for two
+    /// states) over the sample.
+    static RegimeFit classify_threshold(const QVector<double>& returns, const QVector<double>& vol_index, int states,
+                                        double calm_level = 15.0, double turbulent_level = 25.0);
+
+    static QStringList labels(int states);
+
+    static constexpr int kMinBars = 60;
+};
+
+} // namespace fincept::algo
@@ -0,0 +1,130 @@
+// src/algo_engine/RegimeMonitor.cpp
+#include "algo_engine/RegimeMonitor.h"
+
+#include "algo_engine/CandleDataFetcher.h"
+#include "algo_engine/RegimeDetector.h"
+#include "core/events/EventBus.h"
+#include "core/logging/Logger.h"
+
+#include <QDateTime>
+#include <QHash>
+#include <QJsonArray>
+#include <QTimer>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::algo {
+
+RegimeMonitor& RegimeMonitor::instance() {
+    static RegimeMonitor m;
+    return m;
+}
+
+RegimeMonitor::RegimeMonitor() {
+    config_ = QJsonObject{{"index", "^GSPC"},    {"vol_index", "^VIX"},  {"method", "hmm"},
+                          {"states", 3},         {"lookback_days", 1095}, {"calm_level", 15.0},
+                          {"turbulent_level", 25.0}};
+    timer_ = new QTimer(this);
+    connect(timer_, &QTimer::timeout, this, [this]() { refresh(); });
+}
+
+void RegimeMonitor::set_auto_refresh(int minutes) {
+    auto_minutes_ = std::max(0, minutes);
+    if (auto_minutes_ == 0) {
+        timer_->stop();
+        return;
+    }
+    timer_->start(auto_minutes_ * 60 * 1000);
+}
+
+void RegimeMonitor::refresh(const QJsonObject& config) {
+    QJsonObject merged = config_;
+    for (auto it = config.begin(); it != config.end(); ++it)
+        if (merged.contains(it.key()))
+            merged[it.key()] = it.value();
+
+    const QString index = merged.value("index").toString().trimmed().toUpper();
+    const QString vol_index = merged.value("vol_index").toString().trimmed().toUpper();
+    const QString method = merged.value("method").toString().toLower();
+    const int states = merged.value("states").toInt(3);
+    const int lookback = std::clamp(merged.value("lookback_days").toInt(1095), 120, 7300);
+    const double calm = merged.value("calm_level").toDouble(15.0);
+    const double turbulent = merged.value("turbulent_level").toDouble(25.0);
+    if (index.isEmpty() || (method != "hmm" && method != "threshold") || states < 2 || states > 3) {
+        emit error_occurred(QStringLiteral("Regime config needs an index, method hmm|threshold and 2–3 states"));
+        return;
+    }
+    config_ = merged;
+    if (in_flight_)
+        return;
+
+    QStringList symbols{index};
+    if (!vol_index.isEmpty())
+        symbols << vol_index;
+    in_flight_ = true;
+    CandleDataFetcher::instance().fetch_multi(
+        symbols, QStringLiteral("1d"), lookback, DataSource::YFinance, {}, {},
+        [this, index, vol_index, method, states, calm, turbulent](const QHash<QString, QVector<OhlcvCandle>>& data,
+                                                                  const QStringList& errors) {
+            in_flight_ = false;
+            const auto idx = data.value(index);
+            const auto vix = data.value(vol_index);
+            if (idx.isEmpty() || (!vol_index.isEmpty() && vix.isEmpty())) {
+                emit error_occurred(QString("No data for %1%2")
+                                        .arg(idx.isEmpty() ? index : vol_index,
+                                             errors.isEmpty() ? QString() : ": " + errors.join("; ")));
+                return;
+            }
+
+            // Returns of the index, each paired with the vol index on the
+            // same bar (bars missing from either are dropped).
+            QHash<int64_t, double> vix_close;
+            for (const auto& c : vix)
+                vix_close.insert(c.open_time, c.close);
+            QVector<int64_t> times;
+            QVector<double> returns, levels;
+            for (int i = 1; i < idx.size(); ++i) {
+                if (idx[i].close <= 0 || idx[i - 1].close <= 0)
+                    continue;
+                if (!vol_index.isEmpty() && !vix_close.contains(idx[i].open_time))
+                    continue;
+                times.append(idx[i].open_time);
+                returns.append(std::log(idx[i].close / idx[i - 1].close));
+                if (!vol_index.isEmpty())
+                    levels.append(vix_close.value(idx[i].open_time));
+            }
+
+            const RegimeFit fit = method == QLatin1String("threshold")
+                                      ? RegimeDetector::classify_threshold(returns, levels, states, calm, turbulent)
+                                      : RegimeDetector::fit_hmm(returns, levels, states);
+            if (!fit.ok) {
+                emit error_occurred(fit.error);
+                return;
+            }
+
+            QJsonObject regime = fit.to_json(times, 60);
+            regime["index"] = index;
+            regime["vol_index"] = vol_index;
+            regime["bars"] = int(returns.size());
+            regime["updated_at"] = QDateTime::currentDateTimeUtc().toString(Qt::ISODate);
+
+            const QString from = current_.value("current").toObject().value("label").toString();
+            const QString to = fit.current_label();
+            const bool same_series = current_.value("index").toString() == index &&
+                                     current_.value("method").toString() == fit.method &&
+                                     current_.value("states").toArray().size() == states;
+            current_ = regime;
+            emit regime_updated(regime);
+
+            if (same_series && !from.isEmpty() && from != to) {
+                const double p = fit.current_probs.value(fit.current);
+                LOG_INFO("RegimeMonitor", QString("%1 regime %2 → %3 (p=%4)").arg(index, from, to).arg(p, 0, 'f', 2));
+                emit regime_changed(from, to, regime);
+                EventBus::instance().publish("algo.regime_changed",
+                                             {{"index", index}, {"from", from}, {"to", to}, {"probability", p}});
+            }
+        });
+}
+
+} // namespace fincept::algo
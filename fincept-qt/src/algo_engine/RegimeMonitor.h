@@ -0,0 +1,60 @@
+// src/algo_engine/RegimeMonitor.h
+#pragma once
+#include <QJsonObject>
+#include <QObject>
+#include <QString>
+
+class QTimer;
+
+namespace fincept::algo {
+
+/// Keeps the market regime of a reference index current and announces when
+/// it changes. Each refresh fetches daily bars of the index (and its
+/// volatility index, when set), fits RegimeDetector and stores the result.
+///
+/// A change of the current regime label emits regime_changed and publishes
+/// "algo.regime_changed" on the EventBus ({index, from, to, probability}), so
+/// screens, strategies and allocators can react without depending on this
+/// class. The first fit after startup only sets the baseline.
+///
+/// Refreshes run on demand; set_auto_refresh() repeats them on a timer.
+/// Main thread only.
+class RegimeMonitor : public QObject {
+    Q_OBJECT
+  public:
+    static RegimeMonitor& instance();
+
+    /// `config` keys (all optional; missing ones keep the previous value):
+    ///   index ("^GSPC"), vol_index ("^VIX", "" = returns only),
+    ///   method ("hmm" | "threshold"), states (3), lookback_days (1095),
+    ///   calm_level (15), turbulent_level (25).
+    /// Emits regime_updated, or error_occurred. A refresh requested while one
+    /// is running only updates the config; the running one's result answers it.
+    void refresh(const QJsonObject& config = {});
+
+    /// Re-runs refresh() with the current config every `minutes` (0 = off).
+    void set_auto_refresh(int minutes);
+    int auto_refresh_minutes() const { return auto_minutes_; }
+
+    /// Last successful fit with its config and `updated_at`; empty before the
+    /// first one.
+    QJsonObject current() const { return current_; }
+    QJsonObject config() const { return config_; }
+
+  signals:
+    void regime_updated(QJsonObject regime);
+    void regime_changed(QString from, QString to, QJsonObject regime);
+    void error_occurred(QString message);
+
+  private:
+    RegimeMonitor();
+    Q_DISABLE_COPY(RegimeMonitor)
+
+    QJsonObject config_;
+    QJsonObject current_;
+    QTimer* timer_ = nullptr;
+    int auto_minutes_ = 0;
+    bool in_flight_ = false;
+};
+
+} // namespace fincept::algo
@@ -0,0 +1,236 @@
+// src/algo_engine/SpreadBacktestEngine.cpp
+#include "algo_engine/SpreadBacktestEngine.h"
+
+#include "algo_engine/BacktestEngine.h"
+#include "algo_engine/ConditionEvaluator.h"
+#include "core/logging/Logger.h"
+
+#include <algorithm>
+#include <cmath>
+#include <cstring>
+
+namespace fincept::algo {
+
+namespace {
+
+double round_to(double v, int decimals) {
+    double f = std::pow(10.0, decimals);
+    return std::round(v * f) / f;
+}
+
+} // namespace
+
+QJsonObject SpreadBacktestEngine::run(const SpreadDefinition& def, const SpreadSeries& series,
+                                      const QJsonArray& entry_conditions, const QString& entry_logic,
+                                      const QJsonArray& exit_conditions, const QString& exit_logic,
+                                      const QString& direction, double units, double stop_points,
+                                      double target_points, double trailing_points, double initial_capital,
+                                      const QString& timeframe) {
+    const QVector<OhlcvCandle>& candles = series.spread;
+    const int n = candles.size();
+    const int warmup = BacktestEngine::kWarmupBars;
+
+    QString def_error;
+    if (!def.is_valid(&def_error))
+        return QJsonObject{{"success", false}, {"error", def_error}};
+    if (n < warmup + 10)
+        return QJsonObject{{"success", false},
+                           {"error", QString("Insufficient aligned spread data: %1 bars (need at least %2)")
+                                         .arg(n)
+                                         .arg(warmup + 10)}};
+    if (!(units > 0))
+        return QJsonObject{{"success", false}, {"error", QStringLiteral("Spread units must be positive")}};
+
+    const int dir = direction.compare("short", Qt::CaseInsensitive) == 0 ? -1 : 1;
+
+    LOG_INFO("SpreadBacktest", QString("run: spread='%1' legs=%2 bars=%3 dir=%4 units=%5")
+                                   .arg(def.name)
+                                   .arg(def.legs.size())
+                                   .arg(n)
+                                   .arg(dir > 0 ? "long" : "short")
+                                   .arg(units));
+
+    double realized = 0.0;
+    bool in_pos = false;
+    double entry_price = 0.0;
+    int entry_bar = 0;
+    int entry_signal_bar = -1;
+    int exit_signal_bar = -1;
+    double favourable = 0.0; // best spread price since entry in the trade's direction
+    QJsonArray entry_legs;
+
+    bool entry_signal = false;
+    bool exit_signal = false;
+
+    QJsonArray trades;
+    QVector<double> equity_curve;
+    equity_curve.reserve(n - warmup);
+    double peak_equity = initial_capital;
+    double max_dd = 0.0;
+
+    auto close_trade = [&](double exit_price, const char* reason, int exit_bar, bool at_open) {
+        const double pnl = dir * (exit_price - entry_price) * units;
+        realized += pnl;
+        QJsonObject t;
+        t["entry_bar"] = entry_bar;
+        t["exit_bar"] = exit_bar;
+        t["entry_signal_bar"] = entry_signal_bar;
+        t["exit_signal_bar"] = std::strcmp(reason, "exit_signal") == 0 ? exit_signal_bar : -1;
+        t["entry_time"] = static_cast<double>(candles[entry_bar].open_time);
+        t["exit_time"] = static_cast<double>(candles[exit_bar].open_time);
+        t["direction"] = dir > 0 ? QStringLiteral("long") : QStringLiteral("short");
+        t["entry_price"] = round_to(entry_price, 4);
+        t["exit_price"] = round_to(exit_price, 4);
+        t["units"] = units;
+        t["pnl"] = round_to(pnl, 2);
+        t["pnl_points"] = round_to(dir * (exit_price - entry_price), 4);
+        t["reason"] = QString::fromLatin1(reason);
+        t["bars_held"] = exit_bar - entry_bar;
+        t["entry_legs"] = entry_legs;
+        t["exit_legs"] = decompose_spread_fill(def, series, exit_bar, -dir, units, exit_price, at_open);
+        trades.append(t);
+        in_pos = false;
+        exit_signal_bar = -1;
+    };
+
+    for (int i = warmup; i < n; ++i) {
+        const OhlcvCandle& bar = candles[i];
+
+        // ── 1. Pending signal fills at this bar's open ──────────────────────
+        if (!in_pos && entry_signal) {
+            in_pos = true;
+            entry_price = bar.open;
+            entry_bar = i;
+            favourable = bar.open;
+            entry_legs = decompose_spread_fill(def, series, i, dir, units, bar.open, true);
+            entry_signal = false;
+        } else if (in_pos && exit_signal) {
+            close_trade(bar.open, "exit_signal", i, true);
+            exit_signal = false;
+        }
+
+        // ── 2. Intrabar stop / target / trailing in spread points ───────────
+        if (in_pos) {
+            favourable = dir > 0 ? std::max(favourable, bar.high) : std::min(favourable, bar.low);
+            const double adverse_px = dir > 0 ? bar.low : bar.high;
+            const double favour_px = dir > 0 ? bar.high : bar.low;
+
+            bool have_stop = false;
+            double stop_price = 0.0;
+            if (stop_points > 0) {
+                stop_price = entry_price - dir * stop_points;
+                have_stop = true;
+            }
+            if (trailing_points > 0) {
+                const double trail = favourable - dir * trailing_points;
+                stop_price = !have_stop ? trail : (dir > 0 ? std::max(stop_price, trail) : std::min(stop_price, trail));
+                have_stop = true;
+            }
+            const double target_price = entry_price + dir * target_points;
+
+            // Stop first (conservative when both are touched in one bar).
+            if (have_stop && dir * (adverse_px - stop_price) <= 0)
+                close_trade(stop_price, "stop_loss", i, false);
+            else if (target_points > 0 && dir * (favour_px - target_price) >= 0)
+                close_trade(target_price, "take_profit", i, false);
+        }
+
+        // ── 3. Evaluate rules on the spread close → latch for next bar ──────
+        const int start = std::max(0, i - BacktestEngine::kEvalWindow + 1);
+        const QVector<OhlcvCandle> window = candles.mid(start, i - start + 1);
+        if (!in_pos && !entry_signal && !entry_conditions.isEmpty()) {
+            if (ConditionEvaluator::evaluate_group(entry_conditions, entry_logic, window).triggered) {
+                entry_signal = true;
+                entry_signal_bar = i;
+            }
+        } else if (in_pos && !exit_signal && !exit_conditions.isEmpty()) {
+            if (ConditionEvaluator::evaluate_group(exit_conditions, exit_logic, window).triggered) {
+                exit_signal = true;
+                exit_signal_bar = i;
+            }
+        }
+
+        // ── 4. Mark-to-market ───────────────────────────────────────────────
+        const double unrealized = in_pos ? dir * (bar.close - entry_price) * units : 0.0;
+        const double equity = initial_capital + realized + unrealized;
+        equity_curve.append(equity);
+        peak_equity = std::max(peak_equity, equity);
+        const double dd = peak_equity > 0 ? (peak_equity - equity) / peak_equity * 100.0 : 0.0;
+        max_dd = std::max(max_dd, dd);
+    }
+
+    if (in_pos)
+        close_trade(candles[n - 1].close, "end_of_data", n - 1, false);
+
+    // ── Metrics ─────────────────────────────────────────────────────────────
+    const int total_trades = trades.size();
+    const double final_value = initial_capital + realized;
+    const double total_return_pct = initial_capital > 0 ? realized / initial_capital * 100.0 : 0.0;
+
+    int wins = 0;
+    double gross_profit = 0.0, gross_loss = 0.0;
+    for (const auto& tv : trades) {
+        const double pnl = tv.toObject().value("pnl").toDouble();
+        if (pnl > 0) {
+            ++wins;
+            gross_profit += pnl;
+        } else {
+            gross_loss += std::abs(pnl);
+        }
+    }
+    double profit_factor = gross_loss > 0 ? gross_profit / gross_loss : (gross_profit > 0 ? 999.99 : 0.0);
+    profit_factor = std::min(profit_factor, 999.99);
+
+    double sharpe = 0.0;
+    if (equity_curve.size() > 1) {
+        QVector<double> rets;
+        rets.reserve(equity_curve.size() - 1);
+        for (int i = 1; i < equity_curve.size(); ++i)
+            if (equity_curve[i - 1] != 0.0)
+                rets.append((equity_curve[i] - equity_curve[i - 1]) / equity_curve[i - 1]);
+        if (!rets.isEmpty()) {
+            double mean = 0.0;
+            for (double r : rets)
+                mean += r;
+            mean /= rets.size();
+            double var = 0.0;
+            for (double r : rets)
+                var += (r - mean) * (r - mean);
+            const double sd = std::sqrt(var / rets.size());
+            if (sd > 0.0)
+                sharpe = mean / sd * std::sqrt(BacktestEngine::bars_per_year(timeframe));
+        }
+    }
+
+    QJsonArray equity_out, spread_out;
+    {
+        const int sz = equity_curve.size();
+        const int step = sz > 500 ? sz / 500 : 1;
+        for (int i = 0; i < sz; i += step) {
+            equity_out.append(round_to(equity_curve[i], 2));
+            spread_out.append(round_to(candles[warmup + i].close, 4));
+        }
+    }
+
+    QJsonObject out;
+    out["success"] = true;
+    out["spread"] = def.to_json();
+    out["aligned_bars"] = n;
+    out["total_trades"] = total_trades;
+    out["winning_trades"] = wins;
+    out["losing_trades"] = total_trades - wins;
+    out["win_rate"] = total_trades > 0 ? round_to(static_cast<double>(wins) / total_trades * 100.0, 1) : 0.0;
+    out["total_return"] = round_to(total_return_pct, 2);
+    out["total_return_abs"] = round_to(realized, 2);
+    out["final_value"] = round_to(final_value, 2);
+    out["max_drawdown"] = round_to(max_dd, 2);
+    out["profit_factor"] = round_to(profit_factor, 2);
+    out["sharpe_ratio"] = round_to(sharpe, 3);
+    out["avg_pnl"] = total_trades > 0 ? round_to(realized / total_trades, 2) : 0.0;
+    out["equity_curve"] = equity_out;
+    out["spread_curve"] = spread_out;
+    out["trades"] = trades;
+    return out;
+}
+
+} // namespace fincept::algo
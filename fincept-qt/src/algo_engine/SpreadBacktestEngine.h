@@ -0,0 +1,32 @@
+// src/algo_engine/SpreadBacktestEngine.h
+#pragma once
+#include "algo_engine/SpreadInstrument.h"
+
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QString>
+
+namespace fincept::algo {
+
+/// Backtester for synthetic spread instruments (see SpreadInstrument.h).
+///
+/// Same fill model as BacktestEngine — rules evaluated on the close of bar i
+/// fill at the open of bar i+1, stops/targets are checked intrabar — but
+/// adapted to spreads, whose price can be zero or negative:
+///   - positions are sized in spread `units`, not cash / price;
+///   - `direction` is "long" (buy the spread on entry) or "short";
+///   - stop / target / trailing distances are absolute spread points;
+///   - P&L = direction · Δspread · units (leg multipliers are already folded
+///     into the spread price).
+///
+/// Every trade carries `entry_legs` / `exit_legs` — the spread fill decomposed
+/// into per-leg BUY/SELL fills that reconcile exactly to the spread price.
+class SpreadBacktestEngine {
+  public:
+    static QJsonObject run(const SpreadDefinition& def, const SpreadSeries& series, const QJsonArray& entry_conditions,
+                           const QString& entry_logic, const QJsonArray& exit_conditions, const QString& exit_logic,
+                           const QString& direction, double units, double stop_points, double target_points,
+                           double trailing_points, double initial_capital, const QString& timeframe);
+};
+
+} // namespace fincept::algo
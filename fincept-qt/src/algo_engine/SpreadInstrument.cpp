@@ -0,0 +1,217 @@
+// src/algo_engine/SpreadInstrument.cpp
+#include "algo_engine/SpreadInstrument.h"
+
+#include <QHash>
+#include <QStringList>
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+
+namespace fincept::algo {
+
+namespace {
+
+double round_to(double v, int decimals) {
+    double f = std::pow(10.0, decimals);
+    return std::round(v * f) / f;
+}
+
+} // namespace
+
+// ── SpreadDefinition ──────────────────────────────────────────────────────────
+
+bool SpreadDefinition::is_valid(QString* error) const {
+    auto fail = [error](const QString& msg) {
+        if (error)
+            *error = msg;
+        return false;
+    };
+    if (legs.size() < 2)
+        return fail(QStringLiteral("A spread needs at least two legs"));
+    QStringList seen;
+    for (const auto& l : legs) {
+        if (l.symbol.trimmed().isEmpty())
+            return fail(QStringLiteral("Spread leg has an empty symbol"));
+        if (seen.contains(l.symbol))
+            return fail(QStringLiteral("Duplicate spread leg: %1").arg(l.symbol));
+        if (!std::isfinite(l.ratio) || l.ratio == 0.0)
+            return fail(QStringLiteral("Leg %1 has a zero/invalid ratio").arg(l.symbol));
+        if (!std::isfinite(l.multiplier) || l.multiplier <= 0.0)
+            return fail(QStringLiteral("Leg %1 has a non-positive multiplier").arg(l.symbol));
+        seen << l.symbol;
+    }
+    return true;
+}
+
+QStringList SpreadDefinition::symbols() const {
+    QStringList out;
+    for (const auto& l : legs)
+        out << l.symbol;
+    return out;
+}
+
+QJsonObject SpreadDefinition::to_json() const {
+    QJsonArray arr;
+    for (const auto& l : legs)
+        arr.append(QJsonObject{{"symbol", l.symbol}, {"ratio", l.ratio}, {"multiplier", l.multiplier}});
+    return QJsonObject{{"name", name}, {"kind", kind}, {"legs", arr}};
+}
+
+SpreadDefinition SpreadDefinition::from_json(const QJsonObject& obj) {
+    SpreadDefinition d;
+    d.name = obj.value("name").toString();
+    d.kind = obj.value("kind").toString(QStringLiteral("custom"));
+    for (const auto& v : obj.value("legs").toArray()) {
+        const QJsonObject o = v.toObject();
+        SpreadLeg l;
+        l.symbol = o.value("symbol").toString().trimmed();
+        l.ratio = o.value("ratio").toDouble(1.0);
+        l.multiplier = o.value("multiplier").toDouble(1.0);
+        d.legs.append(l);
+    }
+    if (d.name.isEmpty()) {
+        QStringList parts;
+        for (const auto& l : d.legs)
+            parts << QStringLiteral("%1%2%3")
+                         .arg(l.ratio >= 0 ? "+" : "-")
+                         .arg(std::abs(l.ratio) == 1.0 ? QString() : QString::number(std::abs(l.ratio)))
+                         .arg(l.symbol);
+        d.name = parts.join(' ');
+    }
+    return d;
+}
+
+// ── Templates ─────────────────────────────────────────────────────────────────
+
+SpreadDefinition make_calendar_spread(const QString& near_symbol, const QString& far_symbol) {
+    SpreadDefinition d;
+    d.name = QStringLiteral("%1/%2 calendar").arg(near_symbol, far_symbol);
+    d.kind = QStringLiteral("calendar");
+    d.legs = {{near_symbol, 1.0, 1.0}, {far_symbol, -1.0, 1.0}};
+    return d;
+}
+
+SpreadDefinition make_butterfly_spread(const QString& lower, const QString& body, const QString& upper) {
+    SpreadDefinition d;
+    d.name = QStringLiteral("%1/%2/%3 butterfly").arg(lower, body, upper);
+    d.kind = QStringLiteral("butterfly");
+    d.legs = {{lower, 1.0, 1.0}, {body, -2.0, 1.0}, {upper, 1.0, 1.0}};
+    return d;
+}
+
+SpreadDefinition make_crack_321(const QString& crude, const QString& gasoline, const QString& distillate,
+                                double product_multiplier) {
+    SpreadDefinition d;
+    d.name = QStringLiteral("3:2:1 crack");
+    d.kind = QStringLiteral("crack");
+    d.legs = {{crude, -3.0, 1.0}, {gasoline, 2.0, product_multiplier}, {distillate, 1.0, product_multiplier}};
+    return d;
+}
+
+// ── Series construction ───────────────────────────────────────────────────────
+
+SpreadSeries build_spread_series(const SpreadDefinition& def, const QVector<QVector<OhlcvCandle>>& leg_candles) {
+    SpreadSeries out;
+    const int k = def.legs.size();
+    if (k == 0 || leg_candles.size() != k)
+        return out;
+
+    // Index every leg by open_time, then walk the first leg's timeline and keep
+    // only bars present in all legs (inner join — no forward-filling, which
+    // would fabricate spread prints on illiquid back months).
+    QVector<QHash<int64_t, int>> by_time(k);
+    for (int j = 0; j < k; ++j) {
+        by_time[j].reserve(leg_candles[j].size());
+        for (int i = 0; i < leg_candles[j].size(); ++i)
+            by_time[j].insert(leg_candles[j][i].open_time, i);
+    }
+
+    out.legs.resize(k);
+    for (const auto& anchor : leg_candles[0]) {
+        QVector<int> idx(k);
+        bool complete = true;
+        for (int j = 0; j < k && complete; ++j) {
+            auto it = by_time[j].constFind(anchor.open_time);
+            if (it == by_time[j].constEnd())
+                complete = false;
+            else
+                idx[j] = it.value();
+        }
+        if (!complete)
+            continue;
+
+        OhlcvCandle s;
+        s.open_time = anchor.open_time;
+        s.close_time = anchor.close_time;
+        s.is_closed = true;
+        s.volume = std::numeric_limits<double>::max();
+        for (int j = 0; j < k; ++j) {
+            const OhlcvCandle& c = leg_candles[j][idx[j]];
+            const double w = def.legs[j].ratio * def.legs[j].multiplier;
+            s.open += w * c.open;
+            s.close += w * c.close;
+            s.high += w * (w > 0 ? c.high : c.low);
+            s.low += w * (w > 0 ? c.low : c.high);
+            s.volume = std::min(s.volume, c.volume / std::abs(def.legs[j].ratio));
+            out.legs[j].append(c);
+        }
+        out.spread.append(s);
+    }
+    return out;
+}
+
+// ── Fill decomposition ────────────────────────────────────────────────────────
+
+QJsonArray decompose_spread_fill(const SpreadDefinition& def, const SpreadSeries& series, int bar, int spread_side,
+                                 double units, double spread_price, bool at_open) {
+    QJsonArray out;
+    const int k = def.legs.size();
+    if (k == 0 || series.legs.size() != k || bar < 0 || bar >= series.spread.size())
+        return out;
+
+    QVector<double> px(k);
+    double implied = 0.0;
+    for (int j = 0; j < k; ++j) {
+        const OhlcvCandle& c = series.legs[j][bar];
+        px[j] = at_open ? c.open : c.close;
+        implied += def.legs[j].ratio * def.legs[j].multiplier * px[j];
+    }
+    // Book the residual on leg 0 so Σ ratio·mult·price == spread_price.
+    const double w0 = def.legs[0].ratio * def.legs[0].multiplier;
+    if (w0 != 0.0)
+        px[0] += (spread_price - implied) / w0;
+
+    for (int j = 0; j < k; ++j) {
+        const SpreadLeg& l = def.legs[j];
+        const double signed_qty = spread_side * l.ratio * units;
+        QJsonObject f;
+        f["symbol"] = l.symbol;
+        f["side"] = signed_qty >= 0 ? QStringLiteral("BUY") : QStringLiteral("SELL");
+        f["quantity"] = round_to(std::abs(signed_qty), 6);
+        f["price"] = round_to(px[j], 4);
+        f["ratio"] = l.ratio;
+        f["multiplier"] = l.multiplier;
+        out.append(f);
+    }
+    return out;
+}
+
+QVector<AlgoOrderLeg> spread_order_legs(const SpreadDefinition& def, int spread_side, double units,
+                                        const QVector<double>& leg_prices) {
+    QVector<AlgoOrderLeg> out;
+    out.reserve(def.legs.size());
+    for (int j = 0; j < def.legs.size(); ++j) {
+        const SpreadLeg& l = def.legs[j];
+        const double signed_qty = spread_side * l.ratio * units;
+        AlgoOrderLeg leg;
+        leg.symbol = l.symbol;
+        leg.side = signed_qty >= 0 ? QStringLiteral("BUY") : QStringLiteral("SELL");
+        leg.quantity = std::abs(signed_qty);
+        leg.price = j < leg_prices.size() ? leg_prices[j] : 0.0;
+        out.append(leg);
+    }
+    return out;
+}
+
+} // namespace fincept::algo
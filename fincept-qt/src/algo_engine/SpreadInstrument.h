@@ -0,0 +1,82 @@
+// src/algo_engine/SpreadInstrument.h
+// Synthetic spread instruments: a weighted basket of existing symbols traded as
+// one price series (calendar, crack, butterfly, or any custom combination).
+// Pure helpers, no I/O — the backtester builds spread candles from per-leg
+// history and the live/paper path decomposes spread orders into leg orders.
+#pragma once
+#include "algo_engine/AlgoEngineTypes.h"
+
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+namespace fincept::algo {
+
+/// One leg of a spread. `ratio` is signed: +1 = long the leg when long the
+/// spread, -1 = short it. `multiplier` is the contract point value used to put
+/// legs in a common currency unit (e.g. 42 for a crude-vs-products crack).
+struct SpreadLeg {
+    QString symbol;
+    double ratio = 1.0;
+    double multiplier = 1.0;
+};
+
+struct SpreadDefinition {
+    QString name;
+    QString kind = QStringLiteral("custom"); // calendar | crack | butterfly | custom
+    QVector<SpreadLeg> legs;
+
+    bool is_valid(QString* error = nullptr) const;
+    QStringList symbols() const;
+
+    QJsonObject to_json() const;
+    static SpreadDefinition from_json(const QJsonObject& obj);
+};
+
+/// Spread candles plus each leg's candles, all aligned to the same bar index.
+struct SpreadSeries {
+    QVector<OhlcvCandle> spread;
+    QVector<QVector<OhlcvCandle>> legs; // legs[k][i] is leg k at spread bar i
+};
+
+// ── Templates ───────────────────────────────────────────────────────────────
+
+/// Long near / short far, 1:1.
+SpreadDefinition make_calendar_spread(const QString& near_symbol, const QString& far_symbol);
+/// Long lower wing, short 2× body, long upper wing.
+SpreadDefinition make_butterfly_spread(const QString& lower, const QString& body, const QString& upper);
+/// 3:2:1 crack — short 3 crude, long 2 gasoline, long 1 distillate. Product
+/// multipliers convert $/gal quotes to $/bbl.
+SpreadDefinition make_crack_321(const QString& crude, const QString& gasoline, const QString& distillate,
+                                double product_multiplier = 42.0);
+
+// ── Series construction ─────────────────────────────────────────────────────
+
+/// Aligns per-leg candles on open_time (bars missing in any leg are dropped)
+/// and combines them into spread candles:
+///   open/close = Σ ratio·multiplier·leg.open/close
+///   high       = Σ over legs of the extreme that maximises the spread
+///                (leg high when the weight is positive, leg low otherwise)
+///   low        = the mirror image
+///   volume     = min over legs of leg.volume / |ratio| (spread units tradable)
+/// `leg_candles` is indexed like `def.legs`.
+SpreadSeries build_spread_series(const SpreadDefinition& def, const QVector<QVector<OhlcvCandle>>& leg_candles);
+
+// ── Fill decomposition ──────────────────────────────────────────────────────
+
+/// Splits a spread-level fill at bar `bar` into per-leg fills. `spread_side` is
+/// +1 to buy the spread, -1 to sell it. Leg prices are taken from the bar's
+/// open (`at_open`) or close; any difference between the leg-implied spread
+/// price and `spread_price` (intrabar stop/target fills) is booked on the
+/// first leg so the legs always reconcile to the spread fill exactly.
+QJsonArray decompose_spread_fill(const SpreadDefinition& def, const SpreadSeries& series, int bar, int spread_side,
+                                 double units, double spread_price, bool at_open);
+
+/// Leg orders for a spread order of `units` at reference `leg_prices` (indexed
+/// like `def.legs`; empty = market). Feeds the existing multi-leg basket path.
+QVector<AlgoOrderLeg> spread_order_legs(const SpreadDefinition& def, int spread_side, double units,
+                                        const QVector<double>& leg_prices = {});
+
+} // namespace fincept::algo
@@ -0,0 +1,670 @@
+// src/algo_engine/StrategyAnalyzer.cpp
+#include "algo_engine/StrategyAnalyzer.h"
+
+#include "algo_engine/ConditionEvaluator.h"
+
+#include <QHash>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QMap>
+#include <QSet>
+
+#include <algorithm>
+#include <cmath>
+#include <optional>
+#include <utility>
+
+namespace fincept::algo {
+
+using services::algo::AlgoStrategy;
+using services::algo::IndicatorDef;
+using services::algo::ParamSpec;
+
+namespace {
+
+const QSet<QString> kSaLeafKeys = {"indicator",         "params",         "field",         "offset",
+                                   "operator",          "compare_mode",   "value",         "value2",
+                                   "compare_indicator", "compare_params", "compare_field", "compare_offset"};
+const QSet<QString> kSaGroupKeys = {"type", "logic", "op", "negate", "children"};
+
+struct SaOperator {
+    const char* name;
+    const char* description;
+    bool binary; // takes a right-hand operand
+    bool needs_prev;
+};
+
+const SaOperator kSaOperators[] = {
+    {">", "Left operand above the right operand on the current bar.", true, false},
+    {"<", "Left operand below the right operand on the current bar.", true, false},
+    {">=", "Left operand at or above the right operand.", true, false},
+    {"<=", "Left operand at or below the right operand.", true, false},
+    {"==", "Left operand touches the level, or passed through it since the previous bar.", true, true},
+    {"crosses_above", "Left operand was at or below the right operand on the previous bar and is above it now.",
+     true, true},
+    {"crosses_below", "Left operand was at or above the right operand on the previous bar and is below it now.",
+     true, true},
+    {"rising", "Left operand higher than on the previous bar (no right-hand operand).", false, true},
+    {"falling", "Left operand lower than on the previous bar (no right-hand operand).", false, true},
+    {"between", "value ≤ left operand ≤ value2.", false, false},
+};
+
+const SaOperator* sa_operator(const QString& name) {
+    for (const auto& op : kSaOperators)
+        if (name == QLatin1String(op.name))
+            return &op;
+    return nullptr;
+}
+
+std::optional<IndicatorDef> sa_indicator(const QString& id) {
+    for (const auto& def : services::algo::algo_indicators())
+        if (def.id.compare(id, Qt::CaseInsensitive) == 0)
+            return def;
+    return std::nullopt;
+}
+
+// What IndicatorEngine::compute falls back to when a param is missing — not
+// always the builder's default (a missing `period` is 14 everywhere).
+double sa_engine_default(const QString& indicator, const ParamSpec& spec) {
+    if (spec.name == QLatin1String("period"))
+        return 14;
+    if (indicator == QLatin1String("KELTNER") && spec.name == QLatin1String("multiplier"))
+        return 1.5;
+    return spec.def;
+}
+
+// Value ranges of bounded oscillators, for "can never be met" checks.
+std::optional<std::pair<double, double>> sa_bounds(const QString& indicator, const QString& field) {
+    if (indicator == "RSI" || indicator == "MFI" || indicator == "STOCHASTIC" || indicator == "AROON" ||
+        (indicator == "ADX" && field == "value"))
+        return std::make_pair(0.0, 100.0);
+    if (indicator == "WILLIAMS_R")
+        return std::make_pair(-100.0, 0.0);
+    if (indicator == "CMF")
+        return std::make_pair(-1.0, 1.0);
+    if (indicator == "SESSION" && field == "in_session")
+        return std::make_pair(0.0, 1.0);
+    if (indicator == "TIME") {
+        static const QHash<QString, std::pair<double, double>> kCalendar = {
+            {"hour", {0, 23}},       {"minute", {0, 59}},       {"minute_of_day", {0, 1439}},
+            {"day_of_week", {1, 7}}, {"day_of_month", {1, 31}}, {"month", {1, 12}}};
+        if (kCalendar.contains(field))
+            return kCalendar.value(field);
+    }
+    return std::nullopt;
+}
+
+// Closed bars an operand needs before its value is valid.
+int sa_lookback(const QString& indicator, const QJsonObject& p) {
+    auto v = [&p](const char* key) { return std::max(1, int(p.value(key).toDouble())); };
+    if (indicator == "SMA" || indicator == "WMA" || indicator == "EMA" || indicator == "BOLLINGER" ||
+        indicator == "DONCHIAN" || indicator == "CCI" || indicator == "WILLIAMS_R" || indicator == "CMF")
+        return v("period");
+    if (indicator == "DEMA")
+        return 2 * v("period");
+    if (indicator == "TEMA" || indicator == "TRIX")
+        return 3 * v("period");
+    if (indicator == "KAMA" || indicator == "REALIZED_VOL")
+        return v("period") + 1;
+    if (indicator == "CHAIKIN_OSC")
+        return std::max(v("fast"), v("slow"));
+    if (indicator == "ADX")
+        return 2 * v("period");
+    if (indicator == "RSI" || indicator == "ROC" || indicator == "MFI" || indicator == "ATR" ||
+        indicator == "AROON" || indicator == "SUPERTREND" || indicator == "KELTNER")
+        return v("period") + 1;
+    if (indicator == "MACD")
+        return v("slow") + v("signal");
+    if (indicator == "STOCHASTIC")
+        return v("k_period") + v("d_period");
+    if (indicator == "ICHIMOKU")
+        return std::max({v("tenkan"), v("kijun"), v("senkou")});
+    if (indicator == "VOL_WIN_CHG")
+        return 2 * v("window");
+    return 2; // price attributes and OBV: the current and the previous bar
+}
+
+int sa_edit_distance(const QString& a, const QString& b) {
+    QVector<int> row(b.size() + 1);
+    for (int j = 0; j <= b.size(); ++j)
+        row[j] = j;
+    for (int i = 1; i <= a.size(); ++i) {
+        int diag = row[0];
+        row[0] = i;
+        for (int j = 1; j <= b.size(); ++j) {
+            const int up = row[j];
+            row[j] = std::min({row[j] + 1, row[j - 1] + 1, diag + (a[i - 1] == b[j - 1] ? 0 : 1)});
+            diag = up;
+        }
+    }
+    return row[b.size()];
+}
+
+// " — did you mean X?" for a near miss, empty otherwise.
+QString sa_suggest(const QString& name, const QStringList& candidates) {
+    QString best;
+    int best_d = 3;
+    for (const auto& c : candidates) {
+        const int d = sa_edit_distance(name.toLower(), c.toLower());
+        if (d < best_d) {
+            best_d = d;
+            best = c;
+        }
+    }
+    return best.isEmpty() ? QString() : QString(" — did you mean '%1'?").arg(best);
+}
+
+QJsonObject sa_signature(const IndicatorDef& def) {
+    QJsonArray params;
+    for (const auto& spec : def.params)
+        params.append(QJsonObject{{"name", spec.name},
+                                  {"min", spec.min},
+                                  {"max", spec.max},
+                                  {"default", spec.def},
+                                  {"engine_default", sa_engine_default(def.id, spec)},
+                                  {"step", spec.step},
+                                  {"decimals", spec.decimals}});
+    QJsonObject defaults;
+    for (const auto& spec : def.params)
+        defaults[spec.name] = spec.def;
+    return QJsonObject{{"kind", "indicator"},
+                       {"id", def.id},
+                       {"label", def.label},
+                       {"category", def.category},
+                       {"params", params},
+                       {"fields", QJsonArray::fromStringList(def.fields)},
+                       {"default_field", def.fields.value(0)},
+                       {"lookback_bars", sa_lookback(def.id, defaults)}};
+}
+
+QJsonObject sa_operator_signature(const SaOperator& op) {
+    return QJsonObject{{"kind", "operator"},
+                       {"id", op.name},
+                       {"description", QString::fromUtf8(op.description)},
+                       {"right_operand", op.binary},
+                       {"uses_previous_bar", op.needs_prev}};
+}
+
+class SaAnalysis {
+  public:
+    QJsonArray diagnostics;
+    QMap<QString, QJsonObject> symbols; // operand key → {indicator, params, field, paths}
+    QSet<QString> referenced;
+    int errors = 0;
+    int warnings = 0;
+    int leaves = 0;
+    int groups = 0;
+    int max_depth = 0;
+    int warmup = 0;
+
+    void error(const QString& code, const QString& path, const QString& message) {
+        ++errors;
+        diagnostics.append(QJsonObject{{"severity", "error"}, {"code", code}, {"path", path}, {"message", message}});
+    }
+    void warn(const QString& code, const QString& path, const QString& message) {
+        ++warnings;
+        diagnostics.append(QJsonObject{{"severity", "warning"}, {"code", code}, {"path", path}, {"message", message}});
+    }
+
+    void walk(const QJsonArray& nodes, const QString& path, int depth) {
+        max_depth = std::max(max_depth, depth);
+        for (int i = 0; i < nodes.size(); ++i) {
+            const QString at = QString("%1.%2").arg(path).arg(i);
+            if (!nodes[i].isObject()) {
+                error("not_an_object", at, "Condition must be a JSON object");
+                continue;
+            }
+            const QJsonObject node = nodes[i].toObject();
+            if (ConditionEvaluator::is_group_node(node))
+                group(node, at, depth);
+            else
+                leaf(node, at);
+        }
+    }
+
+  private:
+    void group(const QJsonObject& node, const QString& at, int depth) {
+        ++groups;
+        for (auto it = node.begin(); it != node.end(); ++it)
+            if (!kSaGroupKeys.contains(it.key()))
+                warn("unknown_key", at + "." + it.key(), QString("Unknown group key '%1' is ignored").arg(it.key()));
+        const QString logic = node.value("logic").toString(node.value("op").toString("AND"));
+        if (logic.toUpper() != "AND" && logic.toUpper() != "OR")
+            warn("bad_logic", at + ".logic", QString("Logic '%1' is evaluated as AND").arg(logic));
+        if (node.contains("negate") && !node.value("negate").isBool())
+            warn("bad_type", at + ".negate", "'negate' must be true or false; anything else is false");
+        if (!node.value("children").isArray()) {
+            error("bad_type", at + ".children", "'children' must be an array of conditions");
+            return;
+        }
+        const QJsonArray children = node.value("children").toArray();
+        if (children.isEmpty())
+            warn("empty_group", at + ".children",
+                 node.value("negate").toBool(false) ? "An empty negated group is always met"
+                                                    : "An empty group is never met");
+        walk(children, at + ".children", depth + 1);
+    }
+
+    // Validates one operand; returns its effective params (engine defaults
+    // filled in) or nullopt when the indicator is unknown.
+    std::optional<QJsonObject> operand(const QJsonObject& node, const QString& at, const QString& ind_key,
+                                       const QString& params_key, const QString& field_key) {
+        const QString name = node.value(ind_key).toString();
+        if (name.isEmpty()) {
+            error("missing_indicator", at + "." + ind_key, QString("'%1' is required").arg(ind_key));
+            return std::nullopt;
+        }
+        QStringList ids;
+        for (const auto& d : services::algo::algo_indicators())
+            ids.append(d.id);
+        const auto def = sa_indicator(name);
+        if (!def) {
+            error("unknown_indicator", at + "." + ind_key,
+                  QString("Unknown indicator '%1'%2").arg(name, sa_suggest(name, ids)));
+            return std::nullopt;
+        }
+        if (def->id != name)
+            error("indicator_case", at + "." + ind_key,
+                  QString("Indicator ids are case-sensitive: use '%1'").arg(def->id));
+        referenced.insert(def->id);
+
+        const QJsonObject params = node.value(params_key).toObject();
+        QJsonObject effective;
+        QStringList known;
+        for (const auto& spec : def->params) {
+            known.append(spec.name);
+            const QString ppath = QString("%1.%2.%3").arg(at, params_key, spec.name);
+            if (!params.contains(spec.name)) {
+                const double fallback = sa_engine_default(def->id, spec);
+                effective[spec.name] = fallback;
+                if (fallback != spec.def)
+                    warn("param_default", ppath,
+                         QString("'%1' is not set — the engine uses %2, not the builder default %3")
+                             .arg(spec.name)
+                             .arg(fallback)
+                             .arg(spec.def));
+                continue;
+            }
+            const QJsonValue v = params.value(spec.name);
+            if (!v.isDouble()) {
+                error("bad_type", ppath, QString("'%1' must be a number").arg(spec.name));
+                effective[spec.name] = sa_engine_default(def->id, spec);
+                continue;
+            }
+            const double x = v.toDouble();
+            effective[spec.name] = x;
+            if (spec.decimals == 0 && x != std::floor(x))
+                warn("not_integer", ppath, QString("'%1' is truncated to %2").arg(spec.name).arg(int(x)));
+            if (x < spec.min || x > spec.max)
+                warn("param_range", ppath,
+                     QString("'%1' = %2 is outside %3…%4").arg(spec.name).arg(x).arg(spec.min).arg(spec.max));
+        }
+        for (auto it = params.begin(); it != params.end(); ++it)
+            if (!known.contains(it.key()))
+                warn("unknown_param", QString("%1.%2.%3").arg(at, params_key, it.key()),
+                     QString("%1 has no parameter '%2'%3")
+                         .arg(def->id, it.key(), sa_suggest(it.key(), known)));
+
+        const QString field = node.value(field_key).toString(def->fields.value(0));
+        if (!def->fields.contains(field))
+            error("unknown_field", at + "." + field_key,
+                  QString("%1 has no field '%2' (fields: %3)%4")
+                      .arg(def->id, field, def->fields.join(", "), sa_suggest(field, def->fields)));
+        return effective;
+    }
+
+    void add_symbol(const QString& indicator, const QJsonObject& params, const QString& field, const QString& at) {
+        const QString key = indicator + QJsonDocument(params).toJson(QJsonDocument::Compact) + "." + field;
+        QJsonObject sym = symbols.value(key, QJsonObject{{"indicator", indicator}, {"params", params},
+                                                         {"field", field}, {"paths", QJsonArray{}}});
+        QJsonArray paths = sym["paths"].toArray();
+        paths.append(at);
+        sym["paths"] = paths;
+        symbols.insert(key, sym);
+    }
+
+    int offset_of(const QJsonObject& node, const QString& key, const QString& at) {
+        if (!node.contains(key))
+            return 0;
+        const int offset = node.value(key).toInt(0);
+        if (offset < 0) {
+            warn("bad_offset", at + "." + key, "Negative offsets are read as 0 (the current bar)");
+            return 0;
+        }
+        return offset;
+    }
+
+    void leaf(const QJsonObject& node, const QString& at) {
+        ++leaves;
+        for (auto it = node.begin(); it != node.end(); ++it)
+            if (!kSaLeafKeys.contains(it.key()))
+                warn("unknown_key", at + "." + it.key(),
+                     QString("Unknown condition key '%1' is ignored%2")
+                         .arg(it.key(), sa_suggest(it.key(), QStringList(kSaLeafKeys.begin(), kSaLeafKeys.end()))));
+
+        const QString op_name = node.value("operator").toString(">");
+        const SaOperator* op = sa_operator(op_name);
+        if (!op) {
+            QStringList names;
+            for (const auto& o : kSaOperators)
+                names.append(QString::fromLatin1(o.name));
+            error("unknown_operator", at + ".operator",
+                  QString("Unknown operator '%1'%2 — it is never met").arg(op_name, sa_suggest(op_name, names)));
+        }
+        const bool needs_prev = op && op->needs_prev;
+
+        const auto lhs = operand(node, at, "indicator", "params", "field");
+        const int lhs_offset = offset_of(node, "offset", at);
+        QString lhs_field;
+        if (lhs) {
+            const auto def = sa_indicator(node.value("indicator").toString());
+            lhs_field = node.value("field").toString(def->fields.value(0));
+            add_symbol(def->id, *lhs, lhs_field, at);
+            warmup = std::max(warmup, sa_lookback(def->id, *lhs) + lhs_offset + (needs_prev ? 1 : 0));
+        }
+
+        const QString mode = node.value("compare_mode").toString("value");
+        if (mode != "value" && mode != "indicator") {
+            error("bad_compare_mode", at + ".compare_mode",
+                  QString("compare_mode '%1' must be 'value' or 'indicator'").arg(mode));
+            return;
+        }
+        if (!op)
+            return;
+
+        if (op_name == "between") {
+            if (!node.contains("value2"))
+                error("missing_value", at + ".value2", "'between' needs 'value' (low) and 'value2' (high)");
+            else if (node.value("value2").toDouble() < node.value("value").toDouble())
+                error("empty_range", at + ".value2", "'value2' is below 'value' — the range is empty");
+            if (mode == "indicator")
+                warn("ignored_operand", at + ".compare_indicator", "'between' compares against value…value2 only");
+            return;
+        }
+        if (!op->binary) {
+            if (mode == "indicator")
+                warn("ignored_operand", at + ".compare_indicator",
+                     QString("'%1' has no right-hand operand; the comparison indicator is ignored").arg(op_name));
+            return;
+        }
+
+        if (mode == "value") {
+            if (!node.contains("value"))
+                warn("missing_value", at + ".value", "'value' is not set — comparing against 0");
+            const double value = node.value("value").toDouble();
+            const QString ind = sa_indicator(node.value("indicator").toString()).value_or(IndicatorDef{}).id;
+            if (const auto b = sa_bounds(ind, lhs_field)) {
+                const bool never = ((op_name == ">" || op_name == "crosses_above") && value >= b->second) ||
+                                   (op_name == ">=" && value > b->second) ||
+                                   ((op_name == "<" || op_name == "crosses_below") && value <= b->first) ||
+                                   (op_name == "<=" && value < b->first) ||
+                                   (op_name == "==" && (value < b->first || value > b->second));
+                if (never)
+                    warn("never_met", at + ".value",
+                         QString("%1 stays within %2…%3 — '%4 %5' is never met")
+                             .arg(ind)
+                             .arg(b->first)
+                             .arg(b->second)
+                             .arg(op_name)
+                             .arg(value));
+            }
+            return;
+        }
+
+        const auto rhs = operand(node, at, "compare_indicator", "compare_params", "compare_field");
+        const int rhs_offset = offset_of(node, "compare_offset", at);
+        if (!rhs)
+            return;
+        const auto rdef = sa_indicator(node.value("compare_indicator").toString());
+        const QString rhs_field = node.value("compare_field").toString(rdef->fields.value(0));
+        add_symbol(rdef->id, *rhs, rhs_field, at);
+        warmup = std::max(warmup, sa_lookback(rdef->id, *rhs) + rhs_offset + (needs_prev ? 1 : 0));
+        if (lhs && rdef->id == sa_indicator(node.value("indicator").toString())->id && *lhs == *rhs &&
+            lhs_field == rhs_field && lhs_offset == rhs_offset)
+            warn("self_comparison", at + ".compare_indicator",
+                 QString("Compares %1.%2 with itself — '%3' is %4")
+                     .arg(rdef->id, rhs_field, op_name,
+                          op_name == ">=" || op_name == "<=" || op_name == "==" ? QStringLiteral("always met")
+                                                                                 : QStringLiteral("never met")));
+    }
+};
+
+// ── Formatting ──────────────────────────────────────────────────────────────
+
+QString sa_number(double v) {
+    return QString::number(v, 'g', 10);
+}
+
+// Operand params with the engine defaults filled in, in catalog order.
+QJsonObject sa_full_params(const IndicatorDef& def, const QJsonObject& params) {
+    QJsonObject out = params;
+    for (const auto& spec : def.params)
+        if (!out.contains(spec.name))
+            out[spec.name] = sa_engine_default(def.id, spec);
+    return out;
+}
+
+// Canonicalises the operand stored under (ind_key, params_key, field_key,
+// offset_key) in place; unknown indicators are left untouched.
+void sa_format_operand(QJsonObject& node, const QString& ind_key, const QString& params_key,
+                       const QString& field_key, const QString& offset_key) {
+    const auto def = sa_indicator(node.value(ind_key).toString());
+    if (!def)
+        return;
+    node[ind_key] = def->id;
+    node[params_key] = sa_full_params(*def, node.value(params_key).toObject());
+    node[field_key] = node.value(field_key).toString(def->fields.value(0));
+    node[offset_key] = std::max(0, node.value(offset_key).toInt(0));
+}
+
+QJsonArray sa_format_nodes(const QJsonArray& nodes) {
+    QJsonArray out;
+    for (const auto& v : nodes) {
+        if (!v.isObject()) {
+            out.append(v);
+            continue;
+        }
+        QJsonObject node = v.toObject();
+        if (ConditionEvaluator::is_group_node(node)) {
+            const QString logic = node.value("logic").toString(node.value("op").toString("AND")).toUpper();
+            node.remove("op");
+            node["type"] = QStringLiteral("group");
+            node["logic"] = logic == QLatin1String("OR") ? logic : QStringLiteral("AND");
+            node["negate"] = node.value("negate").toBool(false);
+            node["children"] = sa_format_nodes(node.value("children").toArray());
+            out.append(node);
+            continue;
+        }
+        const QString op = node.value("operator").toString(">");
+        node["operator"] = op;
+        sa_format_operand(node, "indicator", "params", "field", "offset");
+        const SaOperator* info = sa_operator(op);
+        const bool indicator_rhs = info && info->binary && node.value("compare_mode").toString() == "indicator";
+        if (indicator_rhs) {
+            node["compare_mode"] = QStringLiteral("indicator");
+            sa_format_operand(node, "compare_indicator", "compare_params", "compare_field", "compare_offset");
+            node.remove("value");
+        } else {
+            node["compare_mode"] = QStringLiteral("value");
+            for (const char* key : {"compare_indicator", "compare_params", "compare_field", "compare_offset"})
+                node.remove(key);
+            node["value"] = info && !info->binary && op != "between" ? 0.0 : node.value("value").toDouble();
+        }
+        if (op == "between")
+            node["value2"] = node.value("value2").toDouble();
+        else
+            node.remove("value2");
+        out.append(node);
+    }
+    return out;
+}
+
+QString sa_operand_text(const QJsonObject& node, const QString& ind_key, const QString& params_key,
+                        const QString& field_key, const QString& offset_key) {
+    const QString name = node.value(ind_key).toString();
+    const auto def = sa_indicator(name);
+    QString text = def ? def->id : name;
+    const QJsonObject params = node.value(params_key).toObject();
+    QStringList args;
+    if (def) {
+        for (const auto& spec : def->params)
+            args.append(sa_number(params.contains(spec.name) ? params.value(spec.name).toDouble()
+                                                             : sa_engine_default(def->id, spec)));
+    } else {
+        for (auto it = params.begin(); it != params.end(); ++it)
+            args.append(QString("%1=%2").arg(it.key(), sa_number(it.value().toDouble())));
+    }
+    if (!args.isEmpty())
+        text += "(" + args.join(", ") + ")";
+    const QString field = node.value(field_key).toString();
+    if (!field.isEmpty() && (!def || field != def->fields.value(0)))
+        text += "." + field;
+    if (const int offset = node.value(offset_key).toInt(0); offset > 0)
+        text += QString("[%1]").arg(offset);
+    return text;
+}
+
+QString sa_condition_text(const QJsonObject& node) {
+    const QString op = node.value("operator").toString(">");
+    const QString lhs = sa_operand_text(node, "indicator", "params", "field", "offset");
+    if (op == "rising" || op == "falling")
+        return lhs + " " + op;
+    if (op == "between")
+        return QString("%1 between %2 and %3")
+            .arg(lhs, sa_number(node.value("value").toDouble()), sa_number(node.value("value2").toDouble()));
+    const QString rhs = node.value("compare_mode").toString() == "indicator"
+                            ? sa_operand_text(node, "compare_indicator", "compare_params", "compare_field",
+                                              "compare_offset")
+                            : sa_number(node.value("value").toDouble());
+    QString pretty = op;
+    pretty.replace('_', ' ');
+    return QString("%1 %2 %3").arg(lhs, pretty, rhs);
+}
+
+void sa_nodes_text(const QJsonArray& nodes, int indent, QStringList& lines) {
+    const QString pad(indent * 2, ' ');
+    for (const auto& v : nodes) {
+        const QJsonObject node = v.toObject();
+        if (!ConditionEvaluator::is_group_node(node)) {
+            lines.append(pad + sa_condition_text(node));
+            continue;
+        }
+        const bool any = node.value("logic").toString(node.value("op").toString("AND")).toUpper() == "OR";
+        lines.append(pad + (node.value("negate").toBool(false) ? "NOT " : "") + (any ? "any of:" : "all of:"));
+        sa_nodes_text(node.value("children").toArray(), indent + 1, lines);
+    }
+}
+
+} // namespace
+
+QJsonObject StrategyAnalyzer::analyze(const AlgoStrategy& strategy) {
+    SaAnalysis a;
+
+    if (!strategy.timeframe.isEmpty() && !services::algo::algo_timeframes().contains(strategy.timeframe))
+        a.error("unknown_timeframe", "timeframe",
+                QString("Unknown timeframe '%1'%2")
+                    .arg(strategy.timeframe, sa_suggest(strategy.timeframe, services::algo::algo_timeframes())));
+    for (const auto& [key, logic] : {std::pair<QString, QString>{"entry_logic", strategy.entry_logic},
+                                     std::pair<QString, QString>{"exit_logic", strategy.exit_logic}})
+        if (!logic.isEmpty() && logic.toUpper() != "AND" && logic.toUpper() != "OR")
+            a.warn("bad_logic", key, QString("Logic '%1' is evaluated as AND").arg(logic));
+    for (const auto& [key, value] : {std::pair<QString, double>{"stop_loss", strategy.stop_loss},
+                                     std::pair<QString, double>{"take_profit", strategy.take_profit},
+                                     std::pair<QString, double>{"trailing_stop", strategy.trailing_stop}})
+        if (value < 0)
+            a.error("negative_risk", key, QString("'%1' must be ≥ 0 (0 = off)").arg(key));
+    if (strategy.stop_loss >= 100)
+        a.warn("stop_out_of_range", "stop_loss", "A stop loss of 100% or more never triggers on a long position");
+    if (strategy.position_size_pct <= 0 || strategy.position_size_pct > 100)
+        a.warn("size_out_of_range", "position_size_pct", "Position size should be within 0…100% of capital");
+
+    if (strategy.entry_conditions.isEmpty())
+        a.error("no_entry", "entry", "No entry conditions — the strategy never trades");
+    if (strategy.exit_conditions.isEmpty() && strategy.stop_loss <= 0 && strategy.take_profit <= 0 &&
+        strategy.trailing_stop <= 0)
+        a.warn("no_exit", "exit",
+               "No exit conditions, stop loss, take profit or trailing stop — positions never close on their own");
+
+    for (const auto& [key, rules] : {std::pair<QString, QJsonArray>{"entry", strategy.entry_conditions},
+                                     std::pair<QString, QJsonArray>{"exit", strategy.exit_conditions}})
+        if (const QString over = ConditionEvaluator::check_limits(rules); !over.isEmpty())
+            a.error("over_limit", key,
+                    QString("Over the evaluation limits: %1 — the engine won't deploy it").arg(over));
+
+    a.walk(strategy.entry_conditions, "entry", 0);
+    const int entry_leaves = a.leaves;
+    a.walk(strategy.exit_conditions, "exit", 0);
+
+    QJsonArray symbols, signatures;
+    for (const auto& s : a.symbols)
+        symbols.append(s);
+    for (const auto& def : services::algo::algo_indicators())
+        if (a.referenced.contains(def.id))
+            signatures.append(sa_signature(def));
+
+    return QJsonObject{{"success", a.errors == 0},
+                       {"errors", a.errors},
+                       {"warnings", a.warnings},
+                       {"diagnostics", a.diagnostics},
+                       {"symbols", symbols},
+                       {"signatures", signatures},
+                       {"warmup_bars", std::max(2, a.warmup)},
+                       {"outline", QJsonObject{{"entry_rules", entry_leaves},
+                                               {"exit_rules", a.leaves - entry_leaves},
+                                               {"groups", a.groups},
+                                               {"max_depth", a.max_depth}}}};
+}
+
+QJsonObject StrategyAnalyzer::catalog() {
+    QJsonArray indicators, operators;
+    for (const auto& def : services::algo::algo_indicators())
+        indicators.append(sa_signature(def));
+    for (const auto& op : kSaOperators)
+        operators.append(sa_operator_signature(op));
+    return QJsonObject{{"indicators", indicators},
+                       {"operators", operators},
+                       {"timeframes", QJsonArray::fromStringList(services::algo::algo_timeframes())},
+                       {"logic", QJsonArray{"AND", "OR"}}};
+}
+
+QJsonObject StrategyAnalyzer::describe(const QString& name) {
+    if (const auto def = sa_indicator(name))
+        return sa_signature(*def);
+    for (const auto& op : kSaOperators)
+        if (name.compare(QLatin1String(op.name), Qt::CaseInsensitive) == 0)
+            return sa_operator_signature(op);
+    return {};
+}
+
+AlgoStrategy StrategyAnalyzer::format(const AlgoStrategy& strategy) {
+    AlgoStrategy out = strategy;
+    out.entry_logic = strategy.entry_logic.toUpper() == QLatin1String("OR") ? "OR" : "AND";
+    out.exit_logic = strategy.exit_logic.toUpper() == QLatin1String("OR") ? "OR" : "AND";
+    out.entry_conditions = sa_format_nodes(strategy.entry_conditions);
+    out.exit_conditions = sa_format_nodes(strategy.exit_conditions);
+    return out;
+}
+
+QString StrategyAnalyzer::to_text(const AlgoStrategy& strategy) {
+    QStringList lines;
+    auto section = [&lines](const QString& title, const QJsonArray& nodes, const QString& logic) {
+        if (nodes.isEmpty())
+            return;
+        lines.append(QString("%1 (%2)").arg(title, logic.toUpper() == "OR" ? "any of" : "all of"));
+        sa_nodes_text(nodes, 1, lines);
+    };
+    section("ENTRY", strategy.entry_conditions, strategy.entry_logic);
+    section("EXIT", strategy.exit_conditions, strategy.exit_logic);
+    QStringList risk;
+    if (strategy.stop_loss > 0)
+        risk.append(QString("stop %1%").arg(sa_number(strategy.stop_loss)));
+    if (strategy.take_profit > 0)
+        risk.append(QString("target %1%").arg(sa_number(strategy.take_profit)));
+    if (strategy.trailing_stop > 0)
+        risk.append(QString("trailing %1%").arg(sa_number(strategy.trailing_stop)));
+    if (!risk.isEmpty())
+        lines.append("RISK " + risk.join(" · "));
+    return lines.join('\n');
+}
+
+} // namespace fincept::algo
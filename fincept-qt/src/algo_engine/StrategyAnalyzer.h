@@ -0,0 +1,59 @@
+// src/algo_engine/StrategyAnalyzer.h
+#pragma once
+#include "services/algo_trading/AlgoTradingTypes.h"
+
+#include <QJsonObject>
+#include <QString>
+
+namespace fincept::algo {
+
+/// Static analysis of a strategy's condition DSL — what an editor needs for
+/// squiggles, an outline and autocomplete — without fetching a candle or
+/// evaluating a rule.
+///
+/// Diagnostics are {severity, code, path, message}. `path` addresses the
+/// offending key the way ParameterGrid does (entry.0.params.period,
+/// exit.1.children.0.operator; strategy-level keys by name) so a client can
+/// underline the exact field. Errors are what ConditionEvaluator would reject
+/// or silently mis-evaluate: an unknown indicator, field or operator, an
+/// indicator comparison without a right-hand operand, a `between` with an
+/// empty range. Warnings are legal but probably unintended: parameters
+/// outside the builder's range or left to the engine default, unknown keys,
+/// an operand compared with itself, no way out of a position.
+///
+/// `symbols` lists each distinct operand (indicator, effective params, field)
+/// with the paths that use it, `signatures` the catalog entry of every
+/// indicator referenced (hover info) and `warmup_bars` the closed bars the
+/// slowest operand needs — offsets and crossings included — before it is
+/// valid.
+class StrategyAnalyzer {
+  public:
+    static QJsonObject analyze(const fincept::services::algo::AlgoStrategy& strategy);
+
+    /// Every indicator (params with range and default, fields), operator and
+    /// timeframe the DSL accepts — autocomplete data.
+    static QJsonObject catalog();
+
+    /// Catalog entry of one indicator or operator (case-insensitive); empty
+    /// when unknown.
+    static QJsonObject describe(const QString& name);
+
+    /// Canonical form of the rules: logic upper-cased, every parameter, field
+    /// and offset explicit (engine defaults filled in), legacy group `op`
+    /// renamed to `logic`, and keys the operator ignores dropped (the
+    /// right-hand operand of rising/falling, `value2` outside `between`,
+    /// compare_* in value mode). Rules evaluate exactly as before, except that
+    /// indicator ids in the wrong case — which the engine rejects — are
+    /// corrected. Unknown indicators and keys are kept as they are.
+    static fincept::services::algo::AlgoStrategy format(const fincept::services::algo::AlgoStrategy& strategy);
+
+    /// The rules as readable text, one condition per line, e.g.
+    ///   ENTRY (all of)
+    ///     RSI(14) < 30
+    ///     SMA(50) crosses above SMA(200)
+    /// Operands are written `ID(params).field[offset]`, the field only when
+    /// it is not the default and the offset only when non-zero.
+    static QString to_text(const fincept::services::algo::AlgoStrategy& strategy);
+};
+
+} // namespace fincept::algo
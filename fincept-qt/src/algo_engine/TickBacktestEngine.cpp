@@ -0,0 +1,366 @@
+// src/algo_engine/TickBacktestEngine.cpp
+#include "algo_engine/TickBacktestEngine.h"
+
+#include "algo_engine/CandleAggregator.h"
+#include "algo_engine/ConditionEvaluator.h"
+#include "algo_engine/PositionManager.h"
+#include "core/logging/Logger.h"
+
+#include <QJsonArray>
+
+#include <algorithm>
+#include <cmath>
+#include <optional>
+#include <random>
+
+namespace fincept::algo {
+
+namespace {
+
+double tick_round(double v, int decimals) {
+    const double f = std::pow(10.0, decimals);
+    return std::round(v * f) / f;
+}
+
+// An order from the bar/tick whose rule fired it to its last fill.
+struct TickOrder {
+    bool buy = true;
+    double quantity = 0;
+    double filled = 0;
+    double notional = 0;     // Σ fill qty × fill price
+    double signal_price = 0; // tick price when the rule fired
+    int64_t signal_ms = 0;
+    int64_t arrive_ms = 0; // signal_ms + latency
+    int fills = 0;
+    QString reason;
+};
+
+struct TickOpenTrade {
+    double quantity = 0;
+    double entry_price = 0; // fill VWAP
+    double entry_fee = 0;
+    int64_t entry_time = 0;
+};
+
+// Price-only bar for the live (per-tick) evaluation window.
+OhlcvCandle tick_bar(double price, int64_t time_ms) {
+    OhlcvCandle c;
+    c.open = c.high = c.low = c.close = price;
+    c.open_time = c.close_time = time_ms;
+    c.is_closed = true;
+    return c;
+}
+
+} // namespace
+
+// ── Fill model ───────────────────────────────────────────────────────────────
+
+TickFillModel TickFillModel::from_json(const QJsonObject& o) {
+    TickFillModel m;
+    m.latency_ms = std::clamp(o.value("latency_ms").toInt(m.latency_ms), 0, 60000);
+    m.latency_jitter_ms = std::clamp(o.value("latency_jitter_ms").toInt(0), 0, m.latency_ms);
+    m.slippage_bps = std::clamp(o.value("slippage_bps").toDouble(0.0), 0.0, 1000.0);
+    m.commission_bps = std::clamp(o.value("commission_bps").toDouble(0.0), 0.0, 1000.0);
+    m.cross_spread = o.value("cross_spread").toBool(m.cross_spread);
+    m.respect_size = o.value("respect_size").toBool(m.respect_size);
+    m.max_queue_ms = std::max(0, o.value("max_queue_ms").toInt(0));
+    m.seed = static_cast<quint32>(o.value("seed").toInteger(m.seed));
+    return m;
+}
+
+QJsonObject TickFillModel::to_json() const {
+    return QJsonObject{{"latency_ms", latency_ms},
+                       {"latency_jitter_ms", latency_jitter_ms},
+                       {"slippage_bps", slippage_bps},
+                       {"commission_bps", commission_bps},
+                       {"cross_spread", cross_spread},
+                       {"respect_size", respect_size},
+                       {"max_queue_ms", max_queue_ms},
+                       {"seed", static_cast<qint64>(seed)}};
+}
+
+// ── Run ──────────────────────────────────────────────────────────────────────
+
+QJsonObject TickBacktestEngine::run(const QVector<TickEvent>& ticks, const QVector<OhlcvCandle>& warmup,
+                                    const BacktestParams& p, const TickFillModel& fill, bool verbose) {
+    auto fail = [](const QString& msg) { return QJsonObject{{"success", false}, {"error", msg}}; };
+    if (ticks.size() < 2)
+        return fail(QString("Insufficient tick data: %1 tick(s)").arg(ticks.size()));
+    if (p.entry_conditions.isEmpty())
+        return fail(QStringLiteral("Strategy has no entry conditions"));
+
+    // "live" evaluates per tick; bars still aggregate on the fallback timeframe
+    // (as DeploymentRunner does) for warm-up and the equity sampling.
+    const bool live = p.timeframe.compare(QStringLiteral("live"), Qt::CaseInsensitive) == 0;
+    const Timeframe tf = timeframe_from_string(p.timeframe);
+    const QString bar_tf = timeframe_to_string(tf);
+
+    CandleAggregator aggregator(QStringLiteral("tick-backtest"), tf, BacktestEngine::kEvalWindow);
+    QVector<OhlcvCandle> seed;
+    for (const auto& c : warmup)
+        if (c.close_time <= ticks.first().timestamp_ms)
+            seed.append(c);
+    aggregator.warm_from(seed);
+    bool bar_closed = false;
+    QObject::connect(&aggregator, &CandleAggregator::candle_closed,
+                     [&bar_closed](const OhlcvCandle&) { bar_closed = true; });
+
+    PositionManager positions(QStringLiteral("tick-backtest"), p.stop_loss_pct, p.take_profit_pct,
+                              p.trailing_stop_pct, 0, 0);
+    std::mt19937 rng(fill.seed);
+    std::uniform_int_distribution<int> jitter(-fill.latency_jitter_ms, fill.latency_jitter_ms);
+
+    double cash = p.initial_capital;
+    std::optional<TickOrder> pending;
+    std::optional<TickOpenTrade> open;
+    QJsonArray trades;
+    QVector<double> bar_equity;
+    double peak = cash, max_dd = 0.0;
+    double gross_profit = 0.0, gross_loss = 0.0;
+    int wins = 0;
+
+    int orders = 0, partial_orders = 0, cancelled = 0, dropped = 0, filled_orders = 0, signals = 0;
+    double latency_sum = 0.0, slippage_sum = 0.0, commission = 0.0;
+
+    auto submit = [&](bool buy, double qty, double ref_price, int64_t now, const QString& reason) {
+        ++signals;
+        if (pending) {
+            ++dropped;
+            return;
+        }
+        TickOrder o;
+        o.buy = buy;
+        o.quantity = qty;
+        o.signal_price = ref_price;
+        o.signal_ms = now;
+        o.arrive_ms = now + std::max(0, fill.latency_ms + (fill.latency_jitter_ms > 0 ? jitter(rng) : 0));
+        o.reason = reason;
+        pending = o;
+        ++orders;
+    };
+
+    auto complete = [&](int64_t now) {
+        const TickOrder o = *pending;
+        pending.reset();
+        const double vwap = o.notional / o.filled;
+        const double fee = o.notional * fill.commission_bps / 10000.0;
+        commission += fee;
+        latency_sum += static_cast<double>(now - o.signal_ms);
+        slippage_sum += (o.buy ? vwap - o.signal_price : o.signal_price - vwap) / o.signal_price * 10000.0;
+        ++filled_orders;
+        if (o.fills > 1)
+            ++partial_orders;
+
+        if (o.buy) {
+            cash -= o.notional + fee;
+            positions.record_entry(PositionSide::Long, o.filled, vwap, now);
+            open = TickOpenTrade{o.filled, vwap, fee, now};
+            return;
+        }
+        cash += o.notional - fee;
+        positions.record_exit(o.filled, vwap, now);
+        const double pnl = (vwap - open->entry_price) * o.filled - open->entry_fee - fee;
+        const double cost = open->entry_price * o.filled;
+        if (pnl > 0) {
+            ++wins;
+            gross_profit += pnl;
+        } else {
+            gross_loss += std::abs(pnl);
+        }
+        trades.append(QJsonObject{{"entry_time", static_cast<double>(open->entry_time)},
+                                  {"exit_time", static_cast<double>(now)},
+                                  {"entry_price", tick_round(open->entry_price, 4)},
+                                  {"exit_price", tick_round(vwap, 4)},
+                                  {"shares", o.filled},
+                                  {"pnl", tick_round(pnl, 2)},
+                                  {"pnl_pct", cost > 0 ? tick_round(pnl / cost * 100.0, 2) : 0.0},
+                                  {"reason", o.reason},
+                                  {"held_ms", static_cast<double>(now - open->entry_time)}});
+        open.reset();
+    };
+
+    // Match the pending order against one tick of the book.
+    auto match = [&](const TickEvent& t) {
+        if (!pending || t.timestamp_ms < pending->arrive_ms)
+            return;
+        TickOrder& o = *pending;
+        const bool quoted = fill.cross_spread && (o.buy ? t.ask > 0 : t.bid > 0);
+        const double touch = quoted ? (o.buy ? t.ask : t.bid) : t.price;
+        double take = o.quantity - o.filled;
+        if (fill.respect_size) {
+            const double shown = quoted ? (o.buy ? t.ask_size : t.bid_size) : t.size;
+            if (shown > 0)
+                take = std::min(take, shown);
+        }
+        const double px = touch * (1.0 + (o.buy ? 1.0 : -1.0) * fill.slippage_bps / 10000.0);
+        o.filled += take;
+        o.notional += take * px;
+        ++o.fills;
+        if (o.quantity - o.filled <= 1e-9) {
+            complete(t.timestamp_ms);
+        } else if (o.buy && fill.max_queue_ms > 0 && t.timestamp_ms - o.arrive_ms >= fill.max_queue_ms) {
+            ++cancelled; // keep what filled
+            complete(t.timestamp_ms);
+        }
+    };
+
+    auto evaluate = [&](const QVector<OhlcvCandle>& window, const TickEvent& t) {
+        if (window.size() < kMinEvalBars)
+            return;
+        if (!positions.has_position()) {
+            if (!ConditionEvaluator::evaluate_group(p.entry_conditions, p.entry_logic, window).triggered)
+                return;
+            const double unit_cost = t.price * (1.0 + (fill.slippage_bps + fill.commission_bps) / 10000.0);
+            const double shares = std::floor(cash * p.position_size_pct / 100.0 / unit_cost);
+            if (shares >= 1.0)
+                submit(true, shares, t.price, t.timestamp_ms, QStringLiteral("entry_signal"));
+            return;
+        }
+        if (!p.exit_conditions.isEmpty() &&
+            ConditionEvaluator::evaluate_group(p.exit_conditions, p.exit_logic, window).triggered)
+            submit(false, positions.position().quantity, t.price, t.timestamp_ms, QStringLiteral("exit_signal"));
+    };
+
+    double last_price = 0.0;
+    int replayed = 0;
+    for (const auto& t : ticks) {
+        if (t.price <= 0)
+            continue;
+        ++replayed;
+        match(t);
+
+        // Stops / targets on every tick, as the live runner does.
+        positions.update_price(t.price);
+        if (positions.has_position() && !pending) {
+            if (const auto risk = positions.check_risk(t.price))
+                submit(false, positions.position().quantity, t.price, t.timestamp_ms, risk->reason);
+        }
+
+        bar_closed = false;
+        aggregator.on_tick(t.price, t.size, t.timestamp_ms);
+        if (live) {
+            QVector<OhlcvCandle> window = aggregator.closed_candles();
+            if (last_price > 0)
+                window.append(tick_bar(last_price, t.timestamp_ms - 1));
+            window.append(tick_bar(t.price, t.timestamp_ms));
+            evaluate(window, t);
+        } else if (bar_closed) {
+            evaluate(aggregator.closed_candles(), t);
+        }
+
+        const double equity = cash + (open ? open->quantity * t.price : 0.0);
+        peak = std::max(peak, equity);
+        if (peak > 0)
+            max_dd = std::max(max_dd, (peak - equity) / peak * 100.0);
+        if (bar_closed)
+            bar_equity.append(equity);
+        last_price = t.price;
+    }
+
+    // The data has ended: a pending entry keeps what filled, a pending exit is
+    // dropped, and any position is flattened at the last price without latency.
+    const int64_t end_ms = ticks.last().timestamp_ms;
+    if (pending && pending->buy && pending->filled > 0) {
+        ++cancelled;
+        complete(end_ms);
+    }
+    pending.reset();
+    if (open) {
+        TickOrder o;
+        o.buy = false;
+        o.quantity = o.filled = open->quantity;
+        o.notional = open->quantity * last_price;
+        o.signal_price = last_price;
+        o.signal_ms = o.arrive_ms = end_ms;
+        o.fills = 1;
+        o.reason = QStringLiteral("end_of_data");
+        pending = o;
+        complete(end_ms);
+    }
+    const double final_value = cash;
+    bar_equity.append(final_value);
+
+    double sharpe = 0.0, sortino = 0.0;
+    if (bar_equity.size() > 2) {
+        QVector<double> rets;
+        for (int i = 1; i < bar_equity.size(); ++i)
+            if (bar_equity[i - 1] != 0.0)
+                rets.append((bar_equity[i] - bar_equity[i - 1]) / bar_equity[i - 1]);
+        double mean = 0.0, var = 0.0, dvar = 0.0;
+        for (double r : rets)
+            mean += r;
+        mean /= std::max<qsizetype>(1, rets.size());
+        for (double r : rets) {
+            var += (r - mean) * (r - mean);
+            if (r < 0.0)
+                dvar += r * r;
+        }
+        const double n = std::max<qsizetype>(1, rets.size());
+        const double ann = std::sqrt(BacktestEngine::bars_per_year(bar_tf));
+        if (var > 0.0)
+            sharpe = mean / std::sqrt(var / n) * ann;
+        if (dvar > 0.0)
+            sortino = mean / std::sqrt(dvar / n) * ann;
+    }
+
+    const int total_trades = int(trades.size());
+    const double total_return_pct = (final_value - p.initial_capital) / p.initial_capital * 100.0;
+    double profit_factor = gross_loss > 0 ? gross_profit / gross_loss : (gross_profit > 0 ? 999.99 : 0.0);
+    profit_factor = std::min(profit_factor, 999.99);
+
+    QJsonArray equity_out;
+    const int step = bar_equity.size() > 500 ? int(bar_equity.size()) / 500 : 1;
+    for (int i = 0; i < bar_equity.size(); i += step)
+        equity_out.append(tick_round(bar_equity[i], 2));
+    if (equity_out.last().toDouble() != tick_round(final_value, 2))
+        equity_out.append(tick_round(final_value, 2));
+
+    const QJsonObject execution{
+        {"ticks", replayed},
+        {"signals", signals},
+        {"orders", orders},
+        {"filled_orders", filled_orders},
+        {"partial_fills", partial_orders},
+        {"cancelled_remainders", cancelled},
+        {"dropped_signals", dropped},
+        {"avg_fill_latency_ms", filled_orders > 0 ? tick_round(latency_sum / filled_orders, 1) : 0.0},
+        {"avg_slippage_bps", filled_orders > 0 ? tick_round(slippage_sum / filled_orders, 2) : 0.0},
+        {"commission", tick_round(commission, 2)},
+        {"fill_model", fill.to_json()}};
+
+    if (verbose)
+        LOG_INFO("TickBacktest", QString("%1 ticks, %2 bars [%3%4] — %5 trades, return %6%, %7 dropped signal(s)")
+                                     .arg(replayed)
+                                     .arg(bar_equity.size())
+                                     .arg(bar_tf, live ? QStringLiteral(", live") : QString())
+                                     .arg(total_trades)
+                                     .arg(total_return_pct, 0, 'f', 2)
+                                     .arg(dropped));
+
+    return QJsonObject{{"success", true},
+                       {"mode", QStringLiteral("tick")},
+                       {"timeframe", live ? QStringLiteral("live") : bar_tf},
+                       {"start_time", static_cast<double>(ticks.first().timestamp_ms)},
+                       {"end_time", static_cast<double>(end_ms)},
+                       {"warmup_bars", int(seed.size())},
+                       {"total_trades", total_trades},
+                       {"winning_trades", wins},
+                       {"losing_trades", total_trades - wins},
+                       {"win_rate", total_trades > 0 ? tick_round(100.0 * wins / total_trades, 1) : 0.0},
+                       {"total_return", tick_round(total_return_pct, 2)},
+                       {"total_return_abs", tick_round(final_value - p.initial_capital, 2)},
+                       {"final_value", tick_round(final_value, 2)},
+                       {"max_drawdown", tick_round(max_dd, 2)},
+                       {"profit_factor", tick_round(profit_factor, 2)},
+                       {"sharpe_ratio", tick_round(sharpe, 3)},
+                       {"sortino", tick_round(sortino, 3)},
+                       {"calmar", max_dd > 0 ? tick_round(total_return_pct / max_dd, 2) : 0.0},
+                       {"expectancy", total_trades > 0 ? tick_round((final_value - p.initial_capital) /
+                                                                        total_trades, 2)
+                                                       : 0.0},
+                       {"equity_curve", equity_out},
+                       {"trades", trades},
+                       {"execution", execution}};
+}
+
+} // namespace fincept::algo
@@ -0,0 +1,65 @@
+// src/algo_engine/TickBacktestEngine.h
+#pragma once
+#include "algo_engine/AlgoEngineTypes.h"
+#include "algo_engine/BacktestEngine.h"
+
+#include <QJsonObject>
+#include <QString>
+#include <QVector>
+
+namespace fincept::algo {
+
+/// Order execution model for tick replay.
+///
+/// An order is sent when a signal fires and reaches the market `latency_ms`
+/// (± `latency_jitter_ms`) later; it is matched against the first tick at or
+/// after that time. BUYs lift the ask and SELLs hit the bid when the tick has
+/// a quote (otherwise the trade price), plus `slippage_bps`. With
+/// `respect_size` an order takes at most the displayed size on its side of
+/// the book (or the trade size when there is no quote) per tick and waits in
+/// the queue for the next tick for the rest; entries still unfilled after
+/// `max_queue_ms` keep what filled and cancel the remainder. Exits never
+/// cancel.
+struct TickFillModel {
+    int latency_ms = 50;
+    int latency_jitter_ms = 0;
+    double slippage_bps = 0.0;
+    double commission_bps = 0.0;
+    bool cross_spread = true;
+    bool respect_size = true;
+    int max_queue_ms = 0; // 0 = entries wait until filled
+    quint32 seed = 42;    // jitter RNG — runs are reproducible
+
+    /// Unknown keys are ignored; out-of-range values are clamped.
+    static TickFillModel from_json(const QJsonObject& o);
+    QJsonObject to_json() const;
+};
+
+/// Event-driven, single-symbol, long-only backtester over stored ticks.
+///
+/// Replays each tick through the same pieces a live deployment uses
+/// (DeploymentRunner): CandleAggregator builds bars from the ticks,
+/// ConditionEvaluator runs the entry/exit rules on every bar close — or on
+/// every tick for the "live" timeframe, against the previous tick — and
+/// PositionManager tracks the position and fires stop-loss / take-profit /
+/// trailing exits tick by tick. Like the live runner, only one order is in
+/// flight at a time; signals raised while one is pending are dropped and
+/// counted.
+///
+/// `warmup` candles (closed bars before the first tick) seed the aggregator so
+/// indicators are valid from the first tick. Returns the same flat metrics as
+/// BacktestEngine (total_return, sharpe_ratio, max_drawdown, total_trades,
+/// win_rate, profit_factor, final_value, equity_curve, trades) plus an
+/// `execution` block: orders, partial fills, latency, slippage vs. the
+/// signal price, commission and dropped signals. On bad input returns
+/// {"success": false, "error": …}.
+class TickBacktestEngine {
+  public:
+    static QJsonObject run(const QVector<TickEvent>& ticks, const QVector<OhlcvCandle>& warmup,
+                           const BacktestParams& params, const TickFillModel& fill, bool verbose = true);
+
+    /// Closed bars the rules need before they are evaluated (as the live runner).
+    static constexpr int kMinEvalBars = 20;
+};
+
+} // namespace fincept::algo
@@ -0,0 +1,219 @@
+// src/algo_engine/TradeResampler.cpp
+#include "algo_engine/TradeResampler.h"
+
+#include <QVector>
+
+#include <algorithm>
+#include <cmath>
+#include <random>
+
+namespace fincept::algo {
+
+namespace {
+
+constexpr double kMsPerYear = 365.25 * 24 * 3600 * 1000;
+
+double tr_round(double v, int decimals) {
+    const double f = std::pow(10.0, decimals);
+    return std::round(v * f) / f;
+}
+
+struct TrPath {
+    double final_equity = 0;
+    double max_drawdown = 0; // percent of the running peak
+    double sharpe = 0;
+};
+
+// Compounds `returns` (in order) from `capital`.
+TrPath tr_path(const QVector<double>& returns, double capital, double annualize) {
+    TrPath p;
+    double equity = capital, peak = capital, sum = 0, sum_sq = 0;
+    for (double r : returns) {
+        equity *= 1.0 + r;
+        peak = std::max(peak, equity);
+        if (peak > 0)
+            p.max_drawdown = std::max(p.max_drawdown, (peak - equity) / peak * 100.0);
+        sum += r;
+        sum_sq += r * r;
+    }
+    const int n = returns.size();
+    const double mean = sum / n;
+    const double var = sum_sq / n - mean * mean;
+    p.final_equity = equity;
+    p.sharpe = var > 0 ? mean / std::sqrt(var) * annualize : 0.0;
+    return p;
+}
+
+// Linear-interpolated percentile of a sorted sample, q in [0, 1].
+double tr_quantile(const QVector<double>& sorted, double q) {
+    const double pos = q * (sorted.size() - 1);
+    const int lo = int(std::floor(pos));
+    const int hi = std::min(lo + 1, int(sorted.size()) - 1);
+    return sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo);
+}
+
+QJsonObject tr_summary(QVector<double> sample, double observed, double confidence, int decimals) {
+    std::sort(sample.begin(), sample.end());
+    double mean = 0;
+    for (double v : sample)
+        mean += v;
+    mean /= sample.size();
+    const double tail = (1.0 - confidence) / 2.0;
+    // Share of simulations at or below the observed value.
+    const double rank = double(std::upper_bound(sample.begin(), sample.end(), observed) - sample.begin()) /
+                        sample.size();
+    return QJsonObject{{"observed", tr_round(observed, decimals)},
+                       {"mean", tr_round(mean, decimals)},
+                       {"median", tr_round(tr_quantile(sample, 0.5), decimals)},
+                       {"lower", tr_round(tr_quantile(sample, tail), decimals)},
+                       {"upper", tr_round(tr_quantile(sample, 1.0 - tail), decimals)},
+                       {"worst_5pct", tr_round(tr_quantile(sample, 0.05), decimals)},
+                       {"best_5pct", tr_round(tr_quantile(sample, 0.95), decimals)},
+                       {"observed_percentile", tr_round(rank * 100.0, 1)}};
+}
+
+} // namespace
+
+ResampleConfig ResampleConfig::from_json(const QJsonObject& o) {
+    ResampleConfig c;
+    c.simulations = std::clamp(o.value("simulations").toInt(c.simulations), 100, 20000);
+    c.confidence = std::clamp(o.value("confidence").toDouble(c.confidence), 0.5, 0.999);
+    const QString method = o.value("method").toString().toLower();
+    if (method == "bootstrap" || method == "permutation" || method == "both")
+        c.method = method;
+    if (o.contains("seed"))
+        c.seed = quint32(o.value("seed").toInteger(c.seed));
+    return c;
+}
+
+QJsonObject TradeResampler::run(const QJsonArray& trades, double initial_capital, const ResampleConfig& config) {
+    auto fail = [](const QString& msg) { return QJsonObject{{"success", false}, {"error", msg}}; };
+    if (initial_capital <= 0)
+        return fail(QStringLiteral("The run has no initial capital"));
+
+    // Closed trades in exit order.
+    QVector<QJsonObject> closed;
+    for (const auto& v : trades) {
+        const QJsonObject t = v.toObject();
+        if (t.contains("pnl"))
+            closed.append(t);
+    }
+    if (closed.size() < kMinTrades)
+        return fail(QString("Need at least %1 closed trades to resample (run has %2)")
+                        .arg(kMinTrades)
+                        .arg(closed.size()));
+    std::stable_sort(closed.begin(), closed.end(), [](const QJsonObject& a, const QJsonObject& b) {
+        return a.value("exit_time").toDouble() < b.value("exit_time").toDouble();
+    });
+
+    QVector<double> returns;
+    returns.reserve(closed.size());
+    double equity = initial_capital;
+    for (const auto& t : closed) {
+        if (equity <= 0)
+            break; // account wiped out; later trades cannot compound
+        const double pnl = t.value("pnl").toDouble();
+        returns.append(pnl / equity);
+        equity += pnl;
+    }
+    const int n = returns.size();
+    if (n < kMinTrades)
+        return fail(QStringLiteral("Too few trades before the account was exhausted"));
+
+    const double span_ms = closed.last().value("exit_time").toDouble() - closed.first().value("entry_time").toDouble();
+    const double trades_per_year = span_ms > 0 ? n / (span_ms / kMsPerYear) : 0.0;
+    const double annualize = trades_per_year > 0 ? std::sqrt(trades_per_year) : 1.0;
+
+    const TrPath observed = tr_path(returns, initial_capital, annualize);
+    double mean_return = 0;
+    for (double r : returns)
+        mean_return += r;
+    mean_return /= n;
+
+    std::mt19937 rng(config.seed);
+    std::uniform_int_distribution<int> pick(0, n - 1);
+    const int sims = config.simulations;
+    const bool do_bootstrap = config.method != QLatin1String("permutation");
+    const bool do_permutation = config.method != QLatin1String("bootstrap");
+
+    QJsonObject out{{"success", true},
+                    {"trades", n},
+                    {"initial_capital", initial_capital},
+                    {"simulations", sims},
+                    {"confidence", config.confidence},
+                    {"method", config.method},
+                    {"seed", double(config.seed)},
+                    {"trades_per_year", tr_round(trades_per_year, 2)}};
+
+    QVector<double> sample(n);
+    if (do_bootstrap) {
+        QVector<double> finals, totals, drawdowns, sharpes;
+        int losing = 0;
+        for (int s = 0; s < sims; ++s) {
+            for (int i = 0; i < n; ++i)
+                sample[i] = returns[pick(rng)];
+            const TrPath p = tr_path(sample, initial_capital, annualize);
+            finals.append(p.final_equity);
+            totals.append((p.final_equity / initial_capital - 1.0) * 100.0);
+            drawdowns.append(p.max_drawdown);
+            sharpes.append(p.sharpe);
+            if (p.final_equity < initial_capital)
+                ++losing;
+        }
+        const double observed_total = (observed.final_equity / initial_capital - 1.0) * 100.0;
+        out["bootstrap"] = QJsonObject{
+            {"final_equity", tr_summary(finals, observed.final_equity, config.confidence, 2)},
+            {"total_return", tr_summary(totals, observed_total, config.confidence, 2)},
+            {"max_drawdown", tr_summary(drawdowns, observed.max_drawdown, config.confidence, 2)},
+            {"sharpe", tr_summary(sharpes, observed.sharpe, config.confidence, 3)},
+            {"probability_of_loss", tr_round(double(losing) / sims, 4)}};
+    }
+
+    if (do_permutation) {
+        QVector<double> drawdowns;
+        sample = returns;
+        for (int s = 0; s < sims; ++s) {
+            std::shuffle(sample.begin(), sample.end(), rng);
+            drawdowns.append(tr_path(sample, initial_capital, annualize).max_drawdown);
+        }
+        out["permutation"] =
+            QJsonObject{{"max_drawdown", tr_summary(drawdowns, observed.max_drawdown, config.confidence, 2)}};
+    }
+
+    // Luck test: how often a zero-edge strategy with the same trade dispersion
+    // averages at least the observed return per trade.
+    {
+        int as_good = 0;
+        for (int s = 0; s < sims; ++s) {
+            double m = 0;
+            for (int i = 0; i < n; ++i)
+                m += returns[pick(rng)] - mean_return;
+            if (m / n >= mean_return)
+                ++as_good;
+        }
+        double var = 0;
+        for (double r : returns)
+            var += (r - mean_return) * (r - mean_return);
+        const double sd = std::sqrt(var / (n - 1));
+        const double p_value = (as_good + 1.0) / (sims + 1.0);
+        const double alpha = 1.0 - config.confidence;
+        const bool significant = p_value < alpha;
+        QString verdict;
+        if (mean_return <= 0)
+            verdict = QStringLiteral("No positive edge: the average trade loses money.");
+        else if (significant)
+            verdict = QString("The edge is unlikely to be luck (p = %1 < %2).").arg(p_value, 0, 'f', 4).arg(alpha);
+        else
+            verdict = QString("The results cannot be told apart from luck at the %1% level (p = %2).")
+                          .arg(config.confidence * 100.0)
+                          .arg(p_value, 0, 'f', 4);
+        out["luck_test"] = QJsonObject{{"mean_trade_return_pct", tr_round(mean_return * 100.0, 4)},
+                                       {"t_stat", sd > 0 ? tr_round(mean_return / (sd / std::sqrt(n)), 3) : 0.0},
+                                       {"p_value", tr_round(p_value, 4)},
+                                       {"significant", significant},
+                                       {"verdict", verdict}};
+    }
+    return out;
+}
+
+} // namespace fincept::algo
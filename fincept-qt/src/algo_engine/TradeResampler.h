@@ -0,0 +1,48 @@
+// src/algo_engine/TradeResampler.h
+// Monte Carlo robustness check of a finished backtest from its trade list
+// alone — no candles, no re-run. Pure computation; AlgoTradingService loads
+// the saved run.
+#pragma once
+
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QString>
+
+namespace fincept::algo {
+
+struct ResampleConfig {
+    int simulations = 2000;
+    double confidence = 0.95; // two-sided interval width
+    QString method = QStringLiteral("both"); // bootstrap | permutation | both
+    quint32 seed = 42;                       // results are reproducible
+
+    /// Unknown keys are ignored; out-of-range values are clamped.
+    static ResampleConfig from_json(const QJsonObject& o);
+};
+
+/// Each trade becomes a return on the equity it was opened with (P&L over
+/// the closed-trade equity before it, in exit order), so resampled sequences
+/// compound like the original.
+///
+///   bootstrap   — draws N trades with replacement: confidence intervals of
+///                 final equity, total return, max drawdown and Sharpe, and
+///                 the probability of ending below the starting capital;
+///   permutation — reshuffles the order of the same trades: final equity is
+///                 unchanged, so this isolates how lucky the drawdown was.
+///
+/// The luck test bootstraps the trade returns re-centred on zero (a strategy
+/// with no edge but the same dispersion) and reports the one-sided p-value
+/// of a mean trade return at least as large as the observed one.
+///
+/// Drawdown and Sharpe are over closed-trade equity, so they differ from the
+/// bar-level figures of the backtest. Sharpe is annualised by the trade
+/// frequency over the run's span. Returns {"success": false, "error": …}
+/// with fewer than kMinTrades trades.
+class TradeResampler {
+  public:
+    static QJsonObject run(const QJsonArray& trades, double initial_capital, const ResampleConfig& config);
+
+    static constexpr int kMinTrades = 10;
+};
+
+} // namespace fincept::algo
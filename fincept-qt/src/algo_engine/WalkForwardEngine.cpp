@@ -0,0 +1,476 @@
+// src/algo_engine/WalkForwardEngine.cpp
+#include "algo_engine/WalkForwardEngine.h"
+
+#include "algo_engine/ParameterGrid.h"
+#include "core/logging/Logger.h"
+
+#include <QDateTime>
+#include <QHash>
+#include <QJsonDocument>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::algo {
+
+namespace {
+
+constexpr int kWfMaxWindows = 100;
+
+// [start, end) in absolute bar indices of the full candle series.
+struct WfSegment {
+    int start = 0;
+    int end = 0;
+    int bars() const { return end - start; }
+};
+
+struct WfWindow {
+    QVector<WfSegment> train; // one segment, or the other folds in k-fold
+    WfSegment test;
+};
+
+// Train-side score of one parameter combination.
+struct WfScore {
+    double objective = 0.0;
+    double total_return = 0.0; // %, averaged across segments
+    double sharpe = 0.0;
+    double max_drawdown = 0.0;
+    int trades = 0; // summed across segments
+    bool ok = false;
+};
+
+double wf_round(double v, int decimals) {
+    const double f = std::pow(10.0, decimals);
+    return std::round(v * f) / f;
+}
+
+QString wf_date(const QVector<OhlcvCandle>& candles, int bar) {
+    return QDateTime::fromMSecsSinceEpoch(candles[std::clamp(bar, 0, int(candles.size()) - 1)].open_time,
+                                          QTimeZone::UTC)
+        .toString(Qt::ISODate);
+}
+
+// The segment plus the warm-up bars before it, so BacktestEngine's first
+// evaluated bar is the segment's first bar.
+QVector<OhlcvCandle> wf_slice(const QVector<OhlcvCandle>& candles, const WfSegment& s) {
+    const int from = s.start - BacktestEngine::kWarmupBars;
+    return candles.mid(from, s.end - from);
+}
+
+QJsonObject wf_metrics(const QJsonObject& r) {
+    return QJsonObject{{"total_return", r.value("total_return").toDouble()},
+                       {"sharpe_ratio", r.value("sharpe_ratio").toDouble()},
+                       {"max_drawdown", r.value("max_drawdown").toDouble()},
+                       {"total_trades", r.value("total_trades").toInt()},
+                       {"win_rate", r.value("win_rate").toDouble()},
+                       {"profit_factor", r.value("profit_factor").toDouble()}};
+}
+
+double wf_median(QVector<double> v) {
+    if (v.isEmpty())
+        return 0.0;
+    std::sort(v.begin(), v.end());
+    const int m = v.size() / 2;
+    return v.size() % 2 ? v[m] : (v[m - 1] + v[m]) / 2.0;
+}
+
+} // namespace
+
+// ── Config ───────────────────────────────────────────────────────────────────
+
+WalkForwardConfig WalkForwardConfig::from_json(const QJsonObject& o, QString* error) {
+    WalkForwardConfig c;
+    const QString mode = o.value("mode").toString(QStringLiteral("rolling")).toLower();
+    if (mode == "rolling")
+        c.mode = Mode::Rolling;
+    else if (mode == "anchored" || mode == "expanding")
+        c.mode = Mode::Anchored;
+    else if (mode == "kfold" || mode == "k_fold" || mode == "cross_validation")
+        c.mode = Mode::KFold;
+    else if (error)
+        *error = QString("Unknown walk-forward mode '%1' (rolling, anchored, kfold)").arg(mode);
+
+    c.windows = std::clamp(o.value("windows").toInt(o.value("folds").toInt(c.windows)), 2, kWfMaxWindows);
+    c.train_bars = std::max(0, o.value("train_bars").toInt());
+    c.test_bars = std::max(0, o.value("test_bars").toInt());
+    c.step_bars = std::max(0, o.value("step_bars").toInt());
+    c.train_pct = std::clamp(o.value("train_pct").toDouble(c.train_pct), 10.0, 95.0);
+    c.embargo_bars = std::max(0, o.value("embargo_bars").toInt());
+    c.min_trades = std::max(0, o.value("min_trades").toInt(c.min_trades));
+    c.max_combinations = std::clamp(o.value("max_combinations").toInt(c.max_combinations), 1, 5000);
+    c.param_grid = o.value("param_grid").toObject();
+
+    const QString objective = o.value("objective").toString(c.objective);
+    c.objective = ParameterGrid::normalise_objective(objective);
+    if (c.objective.isEmpty()) {
+        c.objective = QStringLiteral("sharpe_ratio");
+        if (error && error->isEmpty())
+            *error = QString("Unknown objective '%1' (%2)").arg(objective, ParameterGrid::objectives().join(", "));
+    }
+    return c;
+}
+
+QJsonObject WalkForwardConfig::to_json() const {
+    static const char* kModes[] = {"rolling", "anchored", "kfold"};
+    return QJsonObject{{"mode", kModes[int(mode)]},
+                       {"windows", windows},
+                       {"train_bars", train_bars},
+                       {"test_bars", test_bars},
+                       {"step_bars", step_bars},
+                       {"train_pct", train_pct},
+                       {"embargo_bars", embargo_bars},
+                       {"objective", objective},
+                       {"min_trades", min_trades},
+                       {"max_combinations", max_combinations},
+                       {"param_grid", param_grid}};
+}
+
+// ── Run ──────────────────────────────────────────────────────────────────────
+
+QJsonObject WalkForwardEngine::run(const QVector<OhlcvCandle>& candles, const BacktestParams& base,
+                                   const WalkForwardConfig& config, const Progress& progress) {
+    auto fail = [](const QString& msg) { return QJsonObject{{"success", false}, {"error", msg}}; };
+    const int n = candles.size();
+    const int first = BacktestEngine::kWarmupBars;
+    const int span = n - first;
+
+    QString grid_error;
+    const ParameterGrid grid = ParameterGrid::from_json(config.param_grid, base, config.max_combinations, &grid_error);
+    if (!grid_error.isEmpty())
+        return fail(grid_error);
+    const bool optimised = !grid.is_empty();
+    const int combo_count = grid.size();
+
+    // ── Segments ────────────────────────────────────────────────────────────
+    QVector<WfWindow> windows;
+    if (config.mode == WalkForwardConfig::Mode::KFold) {
+        const int k = std::min(config.windows, 20);
+        const int len = span / k;
+        if (len < kMinSegmentBars + 2 * config.embargo_bars)
+            return fail(QString("History too short: %1 bars for %2 folds (need %3 per fold after the %4-bar warm-up)")
+                            .arg(n)
+                            .arg(k)
+                            .arg(kMinSegmentBars + 2 * config.embargo_bars)
+                            .arg(first));
+        QVector<WfSegment> folds;
+        for (int i = 0; i < k; ++i)
+            folds.append({first + i * len, i == k - 1 ? n : first + (i + 1) * len});
+        for (int f = 0; f < k; ++f) {
+            WfWindow w;
+            w.test = folds[f];
+            for (int i = 0; i < k; ++i) {
+                if (i == f)
+                    continue;
+                WfSegment s = folds[i];
+                if (i == f - 1)
+                    s.end -= config.embargo_bars;
+                if (i == f + 1)
+                    s.start += config.embargo_bars;
+                if (s.bars() >= kMinSegmentBars)
+                    w.train.append(s);
+            }
+            windows.append(w);
+        }
+    } else {
+        const double p = config.train_pct / 100.0;
+        int train = config.train_bars;
+        int test = config.test_bars;
+        if (train <= 0 && test <= 0) {
+            test = int(span / (config.windows + p / (1.0 - p)));
+            train = span - config.windows * test;
+        } else if (test <= 0) {
+            test = (span - train) / config.windows;
+        } else if (train <= 0) {
+            train = int(test * p / (1.0 - p));
+        }
+        if (train < kMinSegmentBars || test < kMinSegmentBars || first + train + kMinSegmentBars > n)
+            return fail(QString("History too short: %1 bars for train %2 / test %3 (plus a %4-bar warm-up)")
+                            .arg(n)
+                            .arg(train)
+                            .arg(test)
+                            .arg(first));
+        const int step = config.step_bars > 0 ? config.step_bars : test;
+        for (int w = 0; w < kWfMaxWindows; ++w) {
+            const int test_start = first + train + w * step;
+            if (test_start + kMinSegmentBars > n)
+                break;
+            WfWindow win;
+            win.train.append({config.mode == WalkForwardConfig::Mode::Anchored ? first : test_start - train,
+                              test_start});
+            win.test = {test_start, std::min(test_start + test, n)};
+            windows.append(win);
+        }
+    }
+
+    int total_runs = 0, done_runs = 0;
+    for (const auto& w : windows)
+        total_runs += combo_count * w.train.size() + 1 + (optimised ? 1 : 0);
+
+    LOG_INFO("WalkForward", QString("run: candles=%1 windows=%2 combos=%3 backtests<=%4 objective=%5")
+                                .arg(n)
+                                .arg(windows.size())
+                                .arg(combo_count)
+                                .arg(total_runs)
+                                .arg(config.objective));
+
+    // Segment results keyed by (segment, combination): rolling windows and
+    // k-fold train sets revisit the same segments.
+    QHash<QString, QJsonObject> cache;
+    auto backtest = [&](const WfSegment& s, int combo, const BacktestParams& p) {
+        const QString key = QString("%1:%2:%3").arg(s.start).arg(s.end).arg(combo);
+        auto hit = cache.constFind(key);
+        if (hit != cache.constEnd())
+            return *hit;
+        const QJsonObject r = BacktestEngine::run(wf_slice(candles, s), p, false);
+        cache.insert(key, r);
+        if (progress)
+            progress(++done_runs, total_runs);
+        return r;
+    };
+
+    // ── Windows ─────────────────────────────────────────────────────────────
+    QStringList warnings;
+    QJsonArray window_out;
+    QJsonArray equity_curve;
+    QJsonArray last_params;
+    double equity = base.initial_capital;
+    double baseline_equity = base.initial_capital;
+    double peak = equity, max_dd = 0.0;
+    double sum_is_return = 0.0, sum_is_bars = 0.0, sum_oos_return = 0.0, sum_oos_bars = 0.0;
+    double sum_is_sharpe = 0.0, sum_is_objective = 0.0, sum_oos_sharpe = 0.0, sum_baseline_sharpe = 0.0;
+    int oos_trades = 0, oos_wins = 0, baseline_positive = 0;
+    double gross_profit = 0.0, gross_loss = 0.0;
+    QVector<double> oos_returns;
+    QHash<QString, QStringList> chosen_by_key;
+    QJsonObject recommended;
+
+    for (int wi = 0; wi < windows.size(); ++wi) {
+        const WfWindow& w = windows[wi];
+
+        // Grid search on the train side.
+        int best = -1;
+        bool best_qualified = false;
+        WfScore best_score;
+        for (int c = 0; c < combo_count; ++c) {
+            const BacktestParams p = grid.params(base, c);
+            WfScore score;
+            int segments = 0;
+            for (const auto& s : w.train) {
+                const QJsonObject r = backtest(s, c, p);
+                if (!r.value("success").toBool())
+                    continue;
+                ++segments;
+                score.objective += ParameterGrid::score(r, config.objective);
+                score.total_return += r.value("total_return").toDouble();
+                score.sharpe += r.value("sharpe_ratio").toDouble();
+                score.max_drawdown += r.value("max_drawdown").toDouble();
+                score.trades += r.value("total_trades").toInt();
+            }
+            if (segments == 0)
+                continue;
+            score.objective /= segments;
+            score.total_return /= segments;
+            score.sharpe /= segments;
+            score.max_drawdown /= segments;
+            score.ok = true;
+            const bool qualified = score.trades >= config.min_trades;
+            if (best < 0 || (qualified && !best_qualified) ||
+                (qualified == best_qualified && score.objective > best_score.objective)) {
+                best = c;
+                best_score = score;
+                best_qualified = qualified;
+            }
+        }
+        if (best < 0)
+            return fail(QString("Window %1: no train segment could be backtested").arg(wi + 1));
+        if (!best_qualified)
+            warnings << QString("Window %1: no combination reached %2 trade(s) in-sample — best of the rest used")
+                            .arg(wi + 1)
+                            .arg(config.min_trades);
+
+        // Best combination on the unseen test segment.
+        QJsonObject chosen;
+        const BacktestParams best_params = grid.params(base, best, &chosen);
+        const QJsonObject oos = backtest(w.test, best, best_params);
+        if (!oos.value("success").toBool())
+            return fail(QString("Window %1: %2").arg(wi + 1).arg(oos.value("error").toString()));
+
+        const double oos_return = oos.value("total_return").toDouble();
+        const double growth = 1.0 + oos_return / 100.0;
+        const double scale = equity / base.initial_capital;
+        const QJsonArray curve = oos.value("equity_curve").toArray();
+        if (curve.isEmpty())
+            equity_curve.append(wf_round(equity, 2));
+        for (const auto& v : curve) {
+            const double e = v.toDouble() * scale;
+            equity_curve.append(wf_round(e, 2));
+            peak = std::max(peak, e);
+            if (peak > 0)
+                max_dd = std::max(max_dd, (peak - e) / peak * 100.0);
+        }
+        equity *= growth;
+
+        for (const auto& tv : oos.value("trades").toArray()) {
+            const double pnl = tv.toObject().value("pnl").toDouble();
+            ++oos_trades;
+            if (pnl > 0) {
+                ++oos_wins;
+                gross_profit += pnl;
+            } else {
+                gross_loss += std::abs(pnl);
+            }
+        }
+
+        double train_bars = 0.0;
+        for (const auto& s : w.train)
+            train_bars += s.bars();
+        train_bars /= std::max<qsizetype>(1, w.train.size());
+        sum_is_return += best_score.total_return;
+        sum_is_bars += train_bars;
+        sum_oos_return += oos_return;
+        sum_oos_bars += w.test.bars();
+        sum_is_sharpe += best_score.sharpe;
+        sum_is_objective += best_score.objective;
+        sum_oos_sharpe += oos.value("sharpe_ratio").toDouble();
+        oos_returns.append(oos_return);
+
+        for (auto it = chosen.begin(); it != chosen.end(); ++it)
+            chosen_by_key[it.key()] << QString::fromUtf8(QJsonDocument(QJsonArray{it.value()}).toJson(
+                QJsonDocument::Compact));
+        recommended = chosen;
+
+        QJsonArray train_ranges;
+        for (const auto& s : w.train)
+            train_ranges.append(QJsonObject{{"start", wf_date(candles, s.start)},
+                                            {"end", wf_date(candles, s.end - 1)},
+                                            {"bars", s.bars()}});
+        const double is_rate = train_bars > 0 ? best_score.total_return / train_bars : 0.0;
+        QJsonObject win{{"index", wi + 1},
+                        {"train", train_ranges},
+                        {"test_start", wf_date(candles, w.test.start)},
+                        {"test_end", wf_date(candles, w.test.end - 1)},
+                        {"test_bars", w.test.bars()},
+                        {"params", chosen},
+                        {"in_sample",
+                         QJsonObject{{"objective", wf_round(best_score.objective, 4)},
+                                     {"total_return", wf_round(best_score.total_return, 2)},
+                                     {"sharpe_ratio", wf_round(best_score.sharpe, 3)},
+                                     {"max_drawdown", wf_round(best_score.max_drawdown, 2)},
+                                     {"total_trades", best_score.trades}}},
+                        {"out_of_sample", wf_metrics(oos)},
+                        {"efficiency", is_rate > 0 ? QJsonValue(wf_round(oos_return / w.test.bars() / is_rate, 3))
+                                                   : QJsonValue(QJsonValue::Null)}};
+
+        // Same test window with the strategy's own parameters.
+        if (optimised) {
+            const QJsonObject fixed = backtest(w.test, -1, base);
+            if (fixed.value("success").toBool()) {
+                const double r = fixed.value("total_return").toDouble();
+                baseline_equity *= 1.0 + r / 100.0;
+                sum_baseline_sharpe += fixed.value("sharpe_ratio").toDouble();
+                if (r > 0)
+                    ++baseline_positive;
+                win["baseline"] = wf_metrics(fixed);
+            }
+        }
+        window_out.append(win);
+    }
+
+    if (progress)
+        progress(total_runs, total_runs);
+
+    // ── Aggregate ───────────────────────────────────────────────────────────
+    const int wn = windows.size();
+    int positive = 0;
+    double sum_ret = 0.0;
+    for (double r : oos_returns) {
+        sum_ret += r;
+        if (r > 0)
+            ++positive;
+    }
+    double profit_factor = gross_loss > 0 ? gross_profit / gross_loss : (gross_profit > 0 ? 999.99 : 0.0);
+    profit_factor = std::min(profit_factor, 999.99);
+
+    const double oos_total = (equity / base.initial_capital - 1.0) * 100.0;
+    QJsonObject oos_out{{"total_return", wf_round(oos_total, 2)},
+                        {"final_value", wf_round(equity, 2)},
+                        {"avg_window_return", wf_round(sum_ret / wn, 2)},
+                        {"median_window_return", wf_round(wf_median(oos_returns), 2)},
+                        {"positive_windows_pct", wf_round(100.0 * positive / wn, 1)},
+                        {"avg_sharpe", wf_round(sum_oos_sharpe / wn, 3)},
+                        {"max_drawdown", wf_round(max_dd, 2)},
+                        {"total_trades", oos_trades},
+                        {"win_rate", oos_trades > 0 ? wf_round(100.0 * oos_wins / oos_trades, 1) : 0.0},
+                        {"profit_factor", wf_round(profit_factor, 2)}};
+    QJsonObject is_out{{"avg_return", wf_round(sum_is_return / wn, 2)},
+                       {"avg_sharpe", wf_round(sum_is_sharpe / wn, 3)},
+                       {"avg_objective", wf_round(sum_is_objective / wn, 4)}};
+
+    // OOS return per bar relative to IS return per bar; ~1 means the edge
+    // held up out of sample, well under 0.5 is the classic overfit signature.
+    const double is_rate = sum_is_bars > 0 ? sum_is_return / sum_is_bars : 0.0;
+    const double oos_rate = sum_oos_bars > 0 ? sum_oos_return / sum_oos_bars : 0.0;
+    QJsonValue efficiency = QJsonValue::Null;
+    if (is_rate > 0) {
+        efficiency = wf_round(oos_rate / is_rate, 3);
+        if (oos_rate / is_rate < 0.5)
+            warnings << QString("Walk-forward efficiency %1 — out-of-sample returns are under half of in-sample; "
+                                "the parameters look overfit")
+                            .arg(oos_rate / is_rate, 0, 'f', 2);
+    } else {
+        warnings << "In-sample returns were not positive — walk-forward efficiency is undefined";
+    }
+    if (wn < 3)
+        warnings << QString("Only %1 window(s) — too few to judge robustness").arg(wn);
+
+    QJsonObject stability;
+    for (auto it = chosen_by_key.constBegin(); it != chosen_by_key.constEnd(); ++it) {
+        QHash<QString, int> counts;
+        QString modal;
+        for (const auto& v : it.value())
+            if (++counts[v] > counts.value(modal))
+                modal = v;
+        QJsonArray per_window;
+        for (const auto& v : it.value())
+            per_window.append(QJsonDocument::fromJson(v.toUtf8()).array().at(0));
+        stability[it.key()] =
+            QJsonObject{{"values", per_window},
+                        {"modal", QJsonDocument::fromJson(modal.toUtf8()).array().at(0)},
+                        {"consistency_pct", wf_round(100.0 * counts.value(modal) / it.value().size(), 1)}};
+    }
+
+    QJsonObject out{{"success", true},
+                    {"mode", config.to_json().value("mode")},
+                    {"objective", config.objective},
+                    {"optimised", optimised},
+                    {"combinations", combo_count},
+                    {"window_count", wn},
+                    {"backtests_run", done_runs},
+                    {"windows", window_out},
+                    {"out_of_sample", oos_out},
+                    {"in_sample", is_out},
+                    {"walk_forward_efficiency", efficiency},
+                    {"parameter_stability", stability},
+                    {"recommended_params", recommended},
+                    {"equity_curve", equity_curve},
+                    {"config", config.to_json()}};
+    if (optimised) {
+        const double baseline_total = (baseline_equity / base.initial_capital - 1.0) * 100.0;
+        out["baseline"] = QJsonObject{{"total_return", wf_round(baseline_total, 2)},
+                                      {"avg_sharpe", wf_round(sum_baseline_sharpe / wn, 3)},
+                                      {"positive_windows_pct", wf_round(100.0 * baseline_positive / wn, 1)}};
+        if (baseline_total > oos_total)
+            warnings << "Re-optimising each window did worse out of sample than the strategy's own parameters";
+    }
+    out["warnings"] = QJsonArray::fromStringList(warnings);
+
+    LOG_INFO("WalkForward", QString("done: windows=%1 backtests=%2 oos=%3% efficiency=%4")
+                                .arg(wn)
+                                .arg(done_runs)
+                                .arg(oos_total, 0, 'f', 2)
+                                .arg(efficiency.isNull() ? QStringLiteral("n/a")
+                                                         : QString::number(efficiency.toDouble())));
+    return out;
+}
+
+} // namespace fincept::algo
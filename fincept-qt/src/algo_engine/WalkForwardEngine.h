@@ -0,0 +1,69 @@
+// src/algo_engine/WalkForwardEngine.h
+#pragma once
+#include "algo_engine/AlgoEngineTypes.h"
+#include "algo_engine/BacktestEngine.h"
+
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+#include <functional>
+
+namespace fincept::algo {
+
+/// How the history is cut into train / test segments.
+///   rolling  — fixed-length train window slides forward by `step_bars`;
+///   anchored — train always starts at the first bar and grows;
+///   kfold    — `windows` contiguous folds; each fold is the test set once and
+///              the remaining folds (each backtested separately) are the
+///              train set, with `embargo_bars` trimmed either side of the test
+///              fold so indicator warm-up cannot see it.
+struct WalkForwardConfig {
+    enum class Mode { Rolling, Anchored, KFold };
+    Mode mode = Mode::Rolling;
+    int windows = 5;         // rolling/anchored: OOS windows when sizes are derived; kfold: folds
+    int train_bars = 0;      // 0 → derived from train_pct
+    int test_bars = 0;       // 0 → derived from train_pct
+    int step_bars = 0;       // 0 → test_bars (back-to-back OOS windows)
+    double train_pct = 70.0; // train share of one train+test span when sizes are derived
+    int embargo_bars = 0;
+    QString objective = QStringLiteral("sharpe_ratio");
+    int min_trades = 1;         // train candidates with fewer trades only win when none qualify
+    int max_combinations = 200; // grid larger than this is rejected
+
+    /// Parameter path → candidate values (see ParameterGrid for the paths).
+    QJsonObject param_grid;
+
+    /// Unknown keys are ignored; an unknown mode/objective sets `error`.
+    static WalkForwardConfig from_json(const QJsonObject& o, QString* error = nullptr);
+    QJsonObject to_json() const;
+};
+
+/// Walk-forward optimisation / cross-validation around BacktestEngine.
+///
+/// For each window the parameter grid is searched on the train segment, the
+/// best combination by `objective` is backtested on the unseen test segment,
+/// and the test results are aggregated: compounded out-of-sample return and a
+/// stitched OOS equity curve, per-window in/out-of-sample metrics, walk-forward
+/// efficiency (OOS return per bar ÷ IS return per bar), how consistently each
+/// parameter was chosen, and — when a grid is given — the same test windows
+/// run with the strategy's own parameters as a no-reoptimisation baseline.
+///
+/// Every segment is backtested with BacktestEngine::kWarmupBars of preceding
+/// history prepended, so the first trade can come on the segment's first bar.
+/// Pure computation; on bad input returns {"success": false, "error": …}.
+class WalkForwardEngine {
+  public:
+    using Progress = std::function<void(int done, int total)>;
+
+    static QJsonObject run(const QVector<OhlcvCandle>& candles, const BacktestParams& base,
+                           const WalkForwardConfig& config, const Progress& progress = {});
+
+    /// Segments shorter than this are not backtested (BacktestEngine needs
+    /// kWarmupBars + 10 bars).
+    static constexpr int kMinSegmentBars = 10;
+};
+
+} // namespace fincept::algo
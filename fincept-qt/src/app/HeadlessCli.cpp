@@ -0,0 +1,292 @@
+#include "app/HeadlessCli.h"
+
+#include "core/logging/Logger.h"
+#include "mcp/McpProvider.h"
+#include "screens/report_builder/ReportBuilderScreen.h"
+#include "services/algo_trading/AlgoTradingService.h"
+#include "services/report_builder/ReportBuilderService.h"
+
+#include <QCoreApplication>
+#include <QDate>
+#include <QEventLoop>
+#include <QFile>
+#include <QFileInfo>
+#include <QFutureWatcher>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QJsonObject>
+#include <QTimer>
+#include <QtConcurrent>
+
+#include <algorithm>
+#include <cstdio>
+
+namespace fincept {
+
+namespace {
+
+constexpr const char* HL_TAG = "Headless";
+constexpr int kHlUsage = 2;
+constexpr int kHlDownloadPollMs = 2000;
+
+const char* const kHlUsageText =
+    "Usage: FinceptTerminal [--profile <name>] --headless <command> [options]\n"
+    "\n"
+    "  backtest --strategy <id|file.json> --symbol <sym> [--start yyyy-MM-dd] [--end yyyy-MM-dd] [--capital n]\n"
+    "  script   <name> [arg ...]                  run a Python analytics script\n"
+    "  refresh  --symbols A,B [--from yyyy-MM-dd] [--interval 1d] [--exchange YF] [--screener]\n"
+    "  report   (--template <name> | --load <file.fincept>) --out <file.pdf>\n"
+    "  tool     <name> [--args <json|@file.json>] [--allow-destructive]\n"
+    "  tools    list tool names\n"
+    "\n"
+    "Prints one JSON result to stdout. Exit code 0 = success, 1 = failed, 2 = usage error.\n";
+
+int hl_usage(const QString& error = {}) {
+    if (!error.isEmpty())
+        std::fprintf(stderr, "%s\n\n", qUtf8Printable(error));
+    std::fputs(kHlUsageText, stderr);
+    return kHlUsage;
+}
+
+// Value following `flag`, or `fallback` when absent.
+QString hl_opt(const QStringList& args, const QString& flag, const QString& fallback = {}) {
+    const int i = args.indexOf(flag);
+    return i >= 0 && i + 1 < args.size() ? args.at(i + 1) : fallback;
+}
+
+int hl_print(const mcp::ToolResult& r) {
+    std::fprintf(stdout, "%s\n", QJsonDocument(r.to_json()).toJson(QJsonDocument::Indented).constData());
+    std::fflush(stdout);
+    return r.success ? 0 : 1;
+}
+
+// Runs the tool on a worker thread, as the AI chat does: async handlers post
+// back to this (main) thread, so its event loop keeps turning meanwhile.
+mcp::ToolResult hl_call(const QString& name, const QJsonObject& args) {
+    QFutureWatcher<mcp::ToolResult> watcher;
+    QEventLoop loop;
+    QObject::connect(&watcher, &QFutureWatcher<mcp::ToolResult>::finished, &loop, &QEventLoop::quit);
+    watcher.setFuture(QtConcurrent::run([name, args]() { return mcp::McpProvider::instance().call_tool(name, args); }));
+    if (!watcher.isFinished())
+        loop.exec();
+    return watcher.result();
+}
+
+// Waits for a queued history download to settle, reporting progress on stderr.
+mcp::ToolResult hl_wait_download(const QString& job_id) {
+    while (true) {
+        const auto r = hl_call("download_jobs", QJsonObject{{"job_id", job_id}, {"limit", 1}});
+        if (!r.success)
+            return r;
+        const QJsonObject job = r.data.toObject();
+        const QString status = job.value("status").toString();
+        std::fprintf(stderr, "[download] %s %d/%d\n", qUtf8Printable(status), job.value("done").toInt(),
+                     job.value("total").toInt());
+        if (status == QLatin1String("completed"))
+            return mcp::ToolResult::ok("History refreshed", job);
+        if (status == QLatin1String("failed") || status == QLatin1String("cancelled"))
+            return mcp::ToolResult::fail(
+                QString("Download %1: %2").arg(status, job.value("error").toString(QStringLiteral("see the log"))));
+        QEventLoop pause;
+        QTimer::singleShot(kHlDownloadPollMs, &pause, &QEventLoop::quit);
+        pause.exec();
+    }
+}
+
+// ── Commands ────────────────────────────────────────────────────────────────
+
+// A saved strategy id, or a JSON file in the strategy builder's schema.
+int hl_backtest(const QStringList& args) {
+    const QString ref = hl_opt(args, "--strategy");
+    const QString symbol = hl_opt(args, "--symbol");
+    if (ref.isEmpty() || symbol.isEmpty())
+        return hl_usage("backtest needs --strategy and --symbol");
+
+    auto& svc = services::algo::AlgoTradingService::instance();
+    services::algo::AlgoStrategy strategy;
+    if (QFileInfo(ref).isFile()) {
+        QFile f(ref);
+        if (!f.open(QIODevice::ReadOnly))
+            return hl_print(mcp::ToolResult::fail("Cannot read " + ref + ": " + f.errorString()));
+        const QJsonObject s = QJsonDocument::fromJson(f.readAll()).object();
+        if (s.value("entry_conditions").toArray().isEmpty())
+            return hl_print(mcp::ToolResult::fail(ref + " has no entry_conditions"));
+        strategy.name = s.value("name").toString(QFileInfo(ref).completeBaseName());
+        strategy.timeframe = s.value("timeframe").toString(QStringLiteral("1d"));
+        strategy.entry_conditions = s.value("entry_conditions").toArray();
+        strategy.exit_conditions = s.value("exit_conditions").toArray();
+        strategy.entry_logic = s.value("entry_logic").toString(QStringLiteral("AND"));
+        strategy.exit_logic = s.value("exit_logic").toString(QStringLiteral("AND"));
+        strategy.stop_loss = s.value("stop_loss").toDouble();
+        strategy.take_profit = s.value("take_profit").toDouble();
+        strategy.trailing_stop = s.value("trailing_stop").toDouble();
+        strategy.position_size_pct = s.value("position_size_pct").toDouble(100.0);
+    } else {
+        const auto found = svc.strategy_by_id(ref);
+        if (!found)
+            return hl_print(mcp::ToolResult::fail(QString("No strategy file or saved strategy '%1'").arg(ref)));
+        strategy = *found;
+    }
+
+    const QString start = hl_opt(args, "--start", QDate::currentDate().addYears(-1).toString(Qt::ISODate));
+    const QString end = hl_opt(args, "--end", QDate::currentDate().toString(Qt::ISODate));
+    const double capital = hl_opt(args, "--capital", "100000").toDouble();
+
+    mcp::ToolResult result = mcp::ToolResult::fail("Backtest produced no result");
+    bool done = false;
+    QEventLoop loop;
+    QObject::connect(&svc, &services::algo::AlgoTradingService::backtest_result, &loop, [&](QJsonObject data) {
+        result = mcp::ToolResult::ok("Backtest complete: " + strategy.name + " on " + symbol, data);
+        done = true;
+        loop.quit();
+    });
+    QObject::connect(&svc, &services::algo::AlgoTradingService::error_occurred, &loop,
+                     [&](QString context, QString message) {
+                         if (context != QLatin1String("backtest"))
+                             return;
+                         result = mcp::ToolResult::fail(message);
+                         done = true;
+                         loop.quit();
+                     });
+    LOG_INFO(HL_TAG, QString("Backtest %1 on %2 (%3 → %4)").arg(strategy.name, symbol, start, end));
+    svc.run_backtest(strategy, symbol, start, end, capital);
+    if (!done) // input errors are reported before run_backtest returns
+        loop.exec();
+    return hl_print(result);
+}
+
+int hl_script(const QStringList& args) {
+    if (args.size() < 2)
+        return hl_usage("script needs a script name");
+    return hl_print(hl_call("run_python_script",
+                            QJsonObject{{"script", args.at(1)}, {"args", QJsonArray::fromStringList(args.mid(2))}}));
+}
+
+// Backfills daily (or --interval) bars into the candle store that backtests
+// read, and optionally the screener's fundamentals store.
+int hl_refresh(const QStringList& args) {
+    QStringList symbols;
+    for (const auto& s : hl_opt(args, "--symbols").split(',', Qt::SkipEmptyParts))
+        symbols << s.trimmed();
+    if (symbols.isEmpty())
+        return hl_usage("refresh needs --symbols");
+
+    const QJsonObject spec{{"symbols", QJsonArray::fromStringList(symbols)},
+                           {"exchange", hl_opt(args, "--exchange", "YF")},
+                           {"interval", hl_opt(args, "--interval", "1d")},
+                           {"from", hl_opt(args, "--from", QDate::currentDate().addYears(-1).toString(Qt::ISODate))},
+                           {"name", "Headless refresh"}};
+    const auto queued = hl_call("download_queue", spec);
+    if (!queued.success)
+        return hl_print(queued);
+    auto history = hl_wait_download(queued.data.toObject().value("job_id").toString());
+    if (!history.success || !args.contains("--screener"))
+        return hl_print(history);
+
+    const auto screener =
+        hl_call("screener_refresh_data", QJsonObject{{"symbols", QJsonArray::fromStringList(symbols)}});
+    if (!screener.success)
+        return hl_print(screener);
+    return hl_print(mcp::ToolResult::ok("History and screener data refreshed",
+                                        QJsonObject{{"history", history.data}, {"screener", screener.data}}));
+}
+
+// The PDF is laid out by the Report Builder's canvas, so a hidden screen is
+// built to render it (the platform is offscreen in headless mode).
+int hl_report(const QStringList& args) {
+    const QString out = hl_opt(args, "--out");
+    const QString tmpl = hl_opt(args, "--template");
+    const QString load = hl_opt(args, "--load");
+    if (out.isEmpty() || tmpl.isEmpty() == load.isEmpty())
+        return hl_usage("report needs --out and one of --template / --load");
+
+    auto& svc = services::ReportBuilderService::instance();
+    if (!load.isEmpty()) {
+        auto r = svc.load_from(load);
+        if (r.is_err())
+            return hl_print(mcp::ToolResult::fail(QString::fromStdString(r.error())));
+    } else {
+        svc.apply_template(tmpl);
+    }
+
+    const QString path = QFileInfo(out).absoluteFilePath();
+    QFile::remove(path);
+    screens::ReportBuilderScreen screen;
+    QCoreApplication::processEvents();
+    screen.export_pdf_to(path);
+    if (QFileInfo(path).size() <= 0)
+        return hl_print(mcp::ToolResult::fail("PDF export failed: " + path));
+    return hl_print(mcp::ToolResult::ok("Report written to " + path, QJsonObject{{"path", path}}));
+}
+
+int hl_tool(const QStringList& args) {
+    if (args.size() < 2)
+        return hl_usage("tool needs a tool name");
+    const QString name = args.at(1);
+    const auto def = mcp::McpProvider::instance().find_tool(name);
+    if (!def)
+        return hl_print(mcp::ToolResult::fail("Unknown tool: " + name));
+    if (def->is_destructive && !args.contains("--allow-destructive"))
+        return hl_print(mcp::ToolResult::fail(name + " changes data or places orders — pass --allow-destructive"));
+
+    QByteArray raw = hl_opt(args, "--args", "{}").toUtf8();
+    if (raw.startsWith('@')) {
+        QFile f(QString::fromUtf8(raw.mid(1)));
+        if (!f.open(QIODevice::ReadOnly))
+            return hl_usage("Cannot read " + f.fileName());
+        raw = f.readAll();
+    }
+    QJsonParseError err;
+    const QJsonDocument doc = QJsonDocument::fromJson(raw, &err);
+    if (err.error != QJsonParseError::NoError || !doc.isObject())
+        return hl_usage("--args must be a JSON object: " + err.errorString());
+    return hl_print(hl_call(name, doc.object()));
+}
+
+int hl_tools() {
+    auto tools = mcp::McpProvider::instance().audit_all_tools();
+    std::sort(tools.begin(), tools.end(), [](const auto& a, const auto& b) { return a.name < b.name; });
+    for (const auto& t : tools) {
+        if (t.enabled)
+            std::fprintf(stdout, "%s\n", qUtf8Printable(t.name));
+    }
+    return 0;
+}
+
+} // namespace
+
+bool headless_requested(int argc, char* argv[]) {
+    for (int i = 1; i < argc; ++i) {
+        if (qstrcmp(argv[i], "--headless") == 0)
+            return true;
+    }
+    return false;
+}
+
+int run_headless(const QStringList& argv) {
+    // Everything after --headless, minus the global --profile option.
+    QStringList args = argv.mid(argv.indexOf("--headless") + 1);
+    const int profile = args.indexOf("--profile");
+    if (profile >= 0)
+        args.remove(profile, std::min<qsizetype>(2, args.size() - profile));
+    if (args.isEmpty())
+        return hl_usage();
+
+    const QString command = args.first();
+    LOG_INFO(HL_TAG, "Command: " + args.join(' '));
+    if (command == QLatin1String("backtest"))
+        return hl_backtest(args);
+    if (command == QLatin1String("script"))
+        return hl_script(args);
+    if (command == QLatin1String("refresh"))
+        return hl_refresh(args);
+    if (command == QLatin1String("report"))
+        return hl_report(args);
+    if (command == QLatin1String("tool"))
+        return hl_tool(args);
+    if (command == QLatin1String("tools"))
+        return hl_tools();
+    return hl_usage("Unknown command: " + command);
+}
+
+} // namespace fincept
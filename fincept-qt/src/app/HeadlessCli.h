@@ -0,0 +1,36 @@
+#pragma once
+
+#include <QStringList>
+
+namespace fincept {
+
+// Headless command-line mode: `FinceptTerminal --headless <command> ...`.
+// Automation and CI for user strategies without opening a window — the same
+// binary, profile, database and tool catalog as the GUI, so a strategy that
+// passes here behaves the same on the desk.
+//
+//   backtest --strategy <id | file.json> --symbol <sym> [--start d] [--end d] [--capital n]
+//   script   <name> [arg ...]                 Python analytics script
+//   refresh  --symbols A,B [--from d] [--interval 1d] [--exchange YF] [--screener]
+//   report   (--template <name> | --load <file.fincept>) --out <file.pdf>
+//   tool     <name> [--args <json | @file.json>] [--allow-destructive]
+//   tools                                     tool names, one per line
+//
+// Every command prints one JSON object ({success, data, message, error} —
+// ToolResult's shape) to stdout; logs stay in the log file. Most commands go
+// through the MCP tool registry, so they run exactly what the AI chat runs.
+// `tool` refuses destructive tools unless --allow-destructive is given.
+//
+// main() sets the offscreen platform and skips the instance lock (a headless
+// run must not hand its argv to a running terminal) and the deferred service
+// init (crash recovery, audit hooks, cloud sync — owned by the GUI) when
+// --headless is on the command line, then calls this after the tool catalog
+// is registered.
+//
+// Exit codes: 0 success, 1 command failed, 2 usage error.
+int run_headless(const QStringList& args);
+
+/// True when argv asks for headless mode. Safe before QApplication exists.
+bool headless_requested(int argc, char* argv[]);
+
+} // namespace fincept
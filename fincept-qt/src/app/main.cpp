@@ -1,7 +1,9 @@
 ﻿#include "algo_engine/AlgoEngineProducer.h"
+#include "algo_engine/AlgoNotificationBridge.h"
 #include "algo_engine/ScanMonitor.h"
 #include "algo_engine/UniverseScanSelftest.h"
 #include "algo_engine/fno/FnoAlgoSelftest.h"
+#include "app/HeadlessCli.h"
 #include "app/InstanceLock.h"
 #include "app/MonitorPickerDialog.h"
 #include "app/ScreenSmokeTest.h"
@@ -25,13 +27,16 @@
 #include "core/session/SessionManager.h"
 #include "core/symbol/SymbolGroup.h"
 #include "core/symbol/SymbolRef.h"
+#include "core/telemetry/UsageMetrics.h"
 #include "core/window/WindowRegistry.h"
 #include "datahub/DataHub.h"
 #include "datahub/DataHubMetaTypes.h"
 #include "datahub/TopicPolicy.h"
+#include "mcp/LocalApiServer.h"
 #include "mcp/McpInit.h"
 #include "mcp/ToolSelfTest.h"
 #include "network/http/HttpClient.h"
+#include "network/websocket/WebSocketMonitor.h"
 #include "python/PythonSetupManager.h"
 #include "screens/launchpad/LaunchpadScreen.h"
 #include "screens/recovery/CrashRecoveryDialog.h"
@@ -39,6 +44,7 @@
 #include "services/agents/AgentService.h"
 #include "services/alpha_arena/ArenaEngine.h"
 #include "services/alpha_arena/ArenaSelftest.h"
+#include "services/audit/AuditTrail.h"
 #include "services/billing/FeeDiscountService.h"
 #include "services/billing/TierService.h"
 #include "services/cloud/AgentConfigCloudAdapter.h"
@@ -53,24 +59,40 @@
 #include "services/cloud/SettingsCloudAdapter.h"
 #include "services/cloud/WatchlistCloudAdapter.h"
 #include "services/cloud/WorkflowCloudAdapter.h"
+#include "services/crypto/FundingRateService.h"
+#include "services/crypto/StablecoinPegService.h"
 #include "services/dbnomics/DBnomicsService.h"
+#include "services/economics/CotSignalService.h"
 #include "services/economics/EconomicsService.h"
 #include "services/economics/MacroCalendarService.h"
+#include "services/economics/MacroModelService.h"
+#include "services/economics/YieldCurveService.h"
 #include "services/feeds/FeedSelfTest.h"
 #include "services/forum/ForumService.h"
 #include "services/geopolitics/GeopoliticsService.h"
 #include "services/gov_data/GovDataService.h"
+#include "services/journal/TradeJournalService.h"
 #include "services/llm/LlmService.h"
 #include "services/ma_analytics/MAAnalyticsService.h"
 #include "services/maritime/MaritimeService.h"
 #include "services/maritime/PortsCatalog.h"
+#include "services/markets/CachePrimer.h"
+#include "services/markets/CustomIndexService.h"
+#include "services/markets/DataQualityService.h"
+#include "services/markets/HistoryDownloadManager.h"
+#include "services/markets/MarketBreadthService.h"
 #include "services/markets/MarketDataService.h"
+#include "services/markets/SymbologyService.h"
+#include "services/markets/TickRecorder.h"
 #include "services/news/NewsService.h"
 #include "services/notebooks/NotebookLibraryService.h"
 #include "services/options/FiiDiiService.h"
 #include "services/options/OISnapshotter.h"
 #include "services/options/OptionChainService.h"
+#include "services/options/OptionsFlowScanner.h"
 #include "services/polymarket/PolymarketWebSocket.h"
+#include "services/portfolio/DividendService.h"
+#include "services/portfolio/EventRiskService.h"
 #include "services/prediction/PredictionCredentialStore.h"
 #include "services/prediction/PredictionExchangeRegistry.h"
 #include "services/prediction/fincept_internal/FinceptInternalAdapter.h"
@@ -78,6 +100,7 @@
 #include "services/prediction/polymarket/PolymarketAdapter.h"
 #include "services/relationship_map/RelationshipMapService.h"
 #include "services/report_builder/ReportBuilderService.h"
+#include "services/screener/ScreenerEngine.h"
 #include "services/wallet/BuybackBurnService.h"
 #include "services/wallet/RealYieldService.h"
 #include "services/wallet/StakingService.h"
@@ -96,6 +119,7 @@
 #include "trading/DataStreamManager.h"
 #include "trading/ExchangeService.h"
 #include "trading/ExchangeSessionManager.h"
+#include "trading/IntradayPnlService.h"
 #include "trading/PaperMarkService.h"
 #include "trading/PaperTradingSelftest.h"
 #include "trading/UnifiedPortfolioService.h"
@@ -108,6 +132,7 @@
 #include <QDir>
 #include <QFile>
 #include <QGuiApplication>
+#include <QInputDialog>
 #include <QLibrary>
 #include <QPointer>
 #include <QSqlDatabase>
@@ -178,6 +203,64 @@ static void wire_app_lifecycle(QApplication& app, fincept::InstanceLock& lock) {
     });
 }
 
+// Profile setup commands. Each one edits the profiles manifest, prints the
+// result and exits before any window opens:
+//   --list-profiles                    names, with the startup one marked
+//   --create-profile <name>            new empty profile (own DB, vault, logs)
+//   --set-startup-profile <name>       profile opened when no --profile is given
+//   --ask-profile-at-startup on|off    show the profile picker at each launch
+// Returns the exit code, or -1 when argv holds none of them.
+static int run_profile_command(int argc, char* argv[]) {
+    auto& pm = fincept::ProfileManager::instance();
+    for (int i = 1; i < argc; ++i) {
+        const QString arg = QString::fromUtf8(argv[i]);
+        const QString value = i + 1 < argc ? QString::fromUtf8(argv[i + 1]) : QString();
+
+        if (arg == "--list-profiles") {
+            const QString startup = pm.startup_profile();
+            for (const QString& name : pm.list_profiles())
+                std::fprintf(stdout, "%s%s\n", qPrintable(name), name == startup ? "  (startup)" : "");
+            std::fprintf(stdout, "ask at startup: %s\n", pm.ask_at_startup() ? "on" : "off");
+            return 0;
+        }
+        if (arg == "--create-profile" || arg == "--set-startup-profile" || arg == "--ask-profile-at-startup") {
+            if (value.isEmpty()) {
+                std::fprintf(stderr, "%s needs a value\n", argv[i]);
+                return 2;
+            }
+        }
+        if (arg == "--create-profile") {
+            if (pm.has_profile(value)) {
+                std::fprintf(stderr, "Profile '%s' already exists\n", qPrintable(value));
+                return 1;
+            }
+            const QString name = pm.profile_name_for(pm.create_profile(value));
+            std::fprintf(stdout, "Created profile '%s' (run with --profile %s)\n", qPrintable(name),
+                         qPrintable(name));
+            return 0;
+        }
+        if (arg == "--set-startup-profile") {
+            if (!pm.has_profile(value)) {
+                std::fprintf(stderr, "No profile '%s' (see --list-profiles)\n", qPrintable(value));
+                return 1;
+            }
+            pm.set_startup_profile(value);
+            std::fprintf(stdout, "Startup profile: %s\n", qPrintable(pm.startup_profile()));
+            return 0;
+        }
+        if (arg == "--ask-profile-at-startup") {
+            if (value != "on" && value != "off") {
+                std::fprintf(stderr, "--ask-profile-at-startup takes on or off\n");
+                return 2;
+            }
+            pm.set_ask_at_startup(value == "on");
+            std::fprintf(stdout, "Ask for profile at startup: %s\n", qPrintable(value));
+            return 0;
+        }
+    }
+    return -1;
+}
+
 int main(int argc, char* argv[]) {
     // ── TLS backend selection (must happen before any Qt plugin loading) ────
     // Force QtNetwork to use the OpenSSL TLS backend across platforms.
@@ -228,18 +311,37 @@ int main(int argc, char* argv[]) {
     //   1. AppPaths returns the correct per-profile directories
     //   2. InstanceLock uses a profile-scoped IPC key so two different
     //      profiles can run simultaneously as independent primary instances
+    // Without --profile the manifest's startup profile opens (see
+    // run_profile_command for the setup commands that change it).
+    bool profile_from_cli = false;
+    bool choose_profile = false;
     {
-        for (int i = 1; i < argc - 1; ++i) {
-            if (qstrcmp(argv[i], "--profile") == 0) {
-                fincept::ProfileManager::instance().set_active(QString::fromUtf8(argv[i + 1]));
-                break;
-            }
-        }
         // AppPaths::root() must exist before ensure_all() so ProfileManager can
         // write the manifest. Create root now (single mkdir, idempotent).
         QDir().mkpath(fincept::AppPaths::root());
+
+        const int rc = run_profile_command(argc, argv);
+        if (rc >= 0)
+            return rc;
+
+        for (int i = 1; i < argc; ++i) {
+            if (qstrcmp(argv[i], "--choose-profile") == 0)
+                choose_profile = true;
+            if (i + 1 < argc && qstrcmp(argv[i], "--profile") == 0 && !profile_from_cli) {
+                fincept::ProfileManager::instance().set_active(QString::fromUtf8(argv[i + 1]));
+                profile_from_cli = true;
+            }
+        }
+        if (!profile_from_cli)
+            fincept::ProfileManager::instance().set_active(fincept::ProfileManager::instance().startup_profile());
     }
 
+    // --headless: command-line automation (see HeadlessCli.h). No display is
+    // needed, so default to the offscreen platform unless the caller chose one.
+    const bool headless = fincept::headless_requested(argc, argv);
+    if (headless && qEnvironmentVariableIsEmpty("QT_QPA_PLATFORM"))
+        qputenv("QT_QPA_PLATFORM", "offscreen");
+
     // Install the unhandled-exception filter BEFORE any Qt object is
     // constructed. On Windows this writes a minidump to AppPaths::crashdumps()
     // when the process dies from an access violation, stack overflow, or GS
@@ -289,10 +391,32 @@ int main(int argc, char* argv[]) {
         }
     }
 
+    // ── Profile picker ───────────────────────────────────────────────────────
+    // Shown for --choose-profile, or when "ask at startup" is on, unless
+    // --profile already named one. Runs before the instance lock so the lock
+    // key and every per-profile path follow the choice. Crash dumps from this
+    // early window still land in the startup profile's directory.
+    {
+        auto& pm = fincept::ProfileManager::instance();
+        const QStringList profiles = pm.list_profiles();
+        if (!headless && !profile_from_cli && (choose_profile || pm.ask_at_startup()) && profiles.size() > 1) {
+            bool ok = false;
+            const QString chosen =
+                QInputDialog::getItem(nullptr, QStringLiteral("Fincept Terminal"), QStringLiteral("Open profile:"),
+                                      profiles, std::max(0, int(profiles.indexOf(pm.active()))), false, &ok);
+            if (!ok)
+                return 0;
+            pm.set_active(chosen);
+        }
+    }
+
     // ── Single-instance lock + new-window IPC ────────────────────────────────
+    // A headless run never takes the lock: with a terminal already open it
+    // would otherwise hand its argv over and open a window instead.
     const QString profile_key = QString("FinceptTerminal-%1").arg(fincept::ProfileManager::instance().active());
     fincept::InstanceLock instance_lock;
-    const auto lock_status = instance_lock.acquire(profile_key, QCoreApplication::arguments());
+    const auto lock_status = headless ? fincept::InstanceLock::Status::Primary
+                                      : instance_lock.acquire(profile_key, QCoreApplication::arguments());
 
     // ── Secondary instance: argv was already shipped to the primary. Exit. ──
     if (lock_status == fincept::InstanceLock::Status::Secondary) {
@@ -357,54 +481,6 @@ int main(int argc, char* argv[]) {
     fincept::services::ma::MAAnalyticsService::instance().ensure_registered_with_hub();
     fincept::wallet::TokenMetadataService::instance().load_from_storage();
 
-    // ── Pre-warm the dashboard topics ────────────────────────────────────────
-    // The user spends real time on the login / setup / recovery flow before
-    // the dashboard ever paints. Kick the hub now so producers start fetching
-    // immediately; by the time the dashboard widgets subscribe in showEvent,
-    // peek() returns a fresh value and deliver_initial_value() paints it on
-    // the first frame instead of showing the loading overlay.
-    //
-    // Set is the union of topics used by every widget in the default
-    // `portfolio_manager` template plus the global indices/forex/crypto/
-    // commodities universes (so any user-customised default still hits the
-    // warm cache). Late-registered producers (the QTimer::singleShot(0)
-    // batch below) warm themselves on their own next scheduler pass once
-    // subscribers exist; pre-warming them here would orphan-log because
-    // ensure_registered_with_hub() hasn't run for them yet.
-    QTimer::singleShot(0, qApp, []() {
-        auto& hub = fincept::datahub::DataHub::instance();
-        QStringList topics;
-
-        auto add_quotes = [&](const QStringList& syms) {
-            topics.reserve(topics.size() + syms.size());
-            for (const auto& s : syms)
-                topics.append(QStringLiteral("market:quote:") + s);
-        };
-
-        // Default-template widget symbol sets (kept aligned with the widget
-        // source — if a widget's hardcoded list changes, update here too).
-        add_quotes(fincept::services::MarketDataService::indices_symbols());
-        add_quotes(fincept::services::MarketDataService::forex_symbols());
-        add_quotes(fincept::services::MarketDataService::crypto_symbols());
-        add_quotes(fincept::services::MarketDataService::commodity_symbols());
-        add_quotes({"^GSPC", "^IXIC", "^DJI", "^RUT", "^VIX", "GC=F"});                                  // performance
-        add_quotes({"^VIX", "SPY", "QQQ", "IWM", "TLT", "NVDA", "TSLA", "AMD", "META", "PLTR", "COIN"}); // risk_metrics
-        add_quotes({"AAPL", "MSFT", "GOOGL", "AMZN", "NVDA", "TSLA", "META", "JPM"}); // watchlist default
-
-        // Non-quote topics used by the default template.
-        topics.append(QStringLiteral("news:general"));
-        topics.append(QStringLiteral("econ:fincept:upcoming_events"));
-
-        // De-duplicate (several add_quotes calls overlap on common symbols).
-        topics.removeDuplicates();
-
-        // force=true bypasses min_interval_ms so the very first cold-start
-        // fetch isn't gated by an unrelated test refresh; producer rate
-        // limits still apply at dispatch (DataHub::flush_coalesced_requests).
-        hub.request(topics, /*force=*/true);
-        LOG_INFO("App", QString("Pre-warmed %1 dashboard topics during login screen").arg(topics.size()));
-    });
-
     // ── Deferred service init — fires after first window paint ───────────────
     // These services back tab-specific screens (F&O, prediction markets,
     // alpha arena, agents, wallet/treasury/staking, etc.) — none of them is
@@ -412,7 +488,14 @@ int main(int argc, char* argv[]) {
     // only add latency to the user-visible cold start. Late registration is
     // safe: the hub's scheduler tick picks up matching subscriptions on the
     // next pass once the producer is registered.
-    QTimer::singleShot(0, qApp, []() {
+    //
+    // Never in a headless run: its commands spin nested event loops, so this
+    // would still fire — and crash recovery (AlgoEngine::recover_orphaned,
+    // the clean-exit flag), the audit hooks, cloud sync and wallet restore
+    // must stay with the terminal that owns the profile.
+    QTimer::singleShot(0, qApp, [headless]() {
+        if (headless)
+            return;
         // F&O / Options chain — `option:chain:*`, `option:tick:*`,
         // `option:atm_iv:*`, `fno:pcr:*`, `fno:max_pain:*`.
         fincept::services::options::OptionChainService::instance().ensure_registered_with_hub();
@@ -470,6 +553,10 @@ int main(int argc, char* argv[]) {
 
         // Algo Engine — `algo:metrics:*`, `algo:trade:*`, `algo:state:*`.
         fincept::algo::AlgoEngineProducer::instance().ensure_registered_with_hub();
+        // Algo fills / errors / kill switch → notifications (deduped, with cooldowns).
+        fincept::algo::AlgoNotificationBridge::instance().install();
+        // Order / auth / deployment / destructive actions → append-only audit_trail.
+        fincept::services::audit::AuditTrail::instance().install();
 
         // Fincept Cloud sync — drains the durable outbox (push) + pulls cloud→local.
         // NOT a DataHub producer; reads stay on the local repo cache. Adapters are
@@ -589,9 +676,78 @@ int main(int argc, char* argv[]) {
                          []() { fincept::storage::HistoricalDataStore::instance().refresh_watchlist(); });
         historify_timer->start();
 
+        // Daily market breadth: hourly check that brings every breadth
+        // universe not updated in the last 12 h up to the latest session.
+        fincept::services::MarketBreadthService::instance().start_auto_update();
+
+        // Unusual options activity: polls watched underlyings' option chains
+        // on their own intervals and prunes events past the retention window.
+        fincept::services::options::OptionsFlowScanner::instance().start();
+
+        // Yield curve fits: hourly check that continues every stored
+        // Nelson-Siegel / Svensson series with the latest published curves.
+        fincept::services::YieldCurveService::instance().start_auto_update();
+
+        // Recession probability / GDP nowcast: re-run once a day after the
+        // first successful run so new FRED releases flow into the dashboard.
+        fincept::services::MacroModelService::instance().start_auto_refresh();
+
+        // COT positioning signals: re-checks active rules every few hours so
+        // each weekly CFTC report is evaluated soon after release.
+        fincept::services::CotSignalService::instance().start();
+
+        // Saved screens: runs every scheduled screen when it falls due,
+        // refreshing its metrics first, and toasts changes in the results.
+        fincept::services::ScreenerEngine::instance().start();
+
+        // Crypto funding / open interest: polls Binance, Bybit and Hyperliquid
+        // every few minutes, stores tracked assets and evaluates alert rules.
+        fincept::services::FundingRateService::instance().start();
+
+        // Stablecoin peg: venue prices every minute, Curve pool balances every
+        // few minutes; sustained deviations open depeg episodes and alert.
+        fincept::services::StablecoinPegService::instance().start();
+
+        // Data quality: scans the Historify candle store and subscribed
+        // streams every few hours and re-fetches gaps, duplicates and spikes.
+        fincept::services::DataQualityService::instance().start();
+
+        // Symbology: seeds the cross-provider mapping table from the broker
+        // master contracts and again after every instrument refresh.
+        fincept::services::SymbologyService::instance().start();
+
+        // Bulk downloads: resumes backfill jobs a restart interrupted.
+        fincept::services::HistoryDownloadManager::instance().start();
+
+        // Custom indices: assigns @-symbols and tops up / rebuilds each
+        // index's daily series from the candle cache.
+        fincept::services::CustomIndexService::instance().start();
+
+        // Tick recorder: resumes capturing live streams when it was left on.
+        fincept::services::TickRecorder::instance().start();
+
+        // Websocket backpressure: restores the saved inbound queue policy.
+        fincept::WebSocketMonitor::instance().load_settings();
+
         LOG_INFO("App", "Deferred service init complete");
     });
 
+    // ── Warm-start cache priming ─────────────────────────────────────────────
+    // The user spends real time on the login / setup / recovery flow before
+    // the dashboard ever paints. CachePrimer kicks the hub now — default
+    // dashboard and saved-dashboard topics first, then MRU symbols, then
+    // watchlists — so by the time widgets subscribe in showEvent, peek()
+    // returns a fresh value and deliver_initial_value() paints it on the first
+    // frame instead of showing the loading overlay. Queued behind the deferred
+    // init batch so every producer a saved dashboard binds to is registered.
+    QTimer::singleShot(0, qApp, [headless]() {
+        if (headless)
+            return;
+        auto& primer = fincept::services::CachePrimer::instance();
+        primer.start_mru_tracking();
+        primer.prime();
+    });
+
     // Create all application directories under %LOCALAPPDATA%/com.fincept.terminal
     fincept::AppPaths::ensure_all();
 
@@ -754,6 +910,34 @@ int main(int argc, char* argv[]) {
     fincept::register_migration_v048();
     fincept::register_migration_v049();
     fincept::register_migration_v050();
+    fincept::register_migration_v051();
+    fincept::register_migration_v052();
+    fincept::register_migration_v053();
+    fincept::register_migration_v054();
+    fincept::register_migration_v055();
+    fincept::register_migration_v056();
+    fincept::register_migration_v057();
+    fincept::register_migration_v058();
+    fincept::register_migration_v059();
+    fincept::register_migration_v060();
+    fincept::register_migration_v061();
+    fincept::register_migration_v062();
+    fincept::register_migration_v063();
+    fincept::register_migration_v064();
+    fincept::register_migration_v065();
+    fincept::register_migration_v066();
+    fincept::register_migration_v067();
+    fincept::register_migration_v068();
+    fincept::register_migration_v069();
+    fincept::register_migration_v070();
+    fincept::register_migration_v071();
+    fincept::register_migration_v072();
+    fincept::register_migration_v073();
+    fincept::register_migration_v074();
+    fincept::register_migration_v075();
+    fincept::register_migration_v076();
+    fincept::register_migration_v077();
+    fincept::register_migration_v078();
 
     // Open main database
     QString db_path = fincept::AppPaths::data() + "/fincept.db";
@@ -809,6 +993,10 @@ int main(int argc, char* argv[]) {
         // Load persisted display-currency preference so the symbol is correct
         // on first paint of any calculator/analytics surface.
         fincept::currency::CurrencyManager::instance().initialize();
+
+        // Opt-in local usage metrics (off unless `metrics.enabled`); the flush
+        // timer needs the settings table and the v078 schema.
+        fincept::telemetry::UsageMetrics::instance().start();
     }
 
     // Open cache database (non-fatal if fails)
@@ -896,7 +1084,6 @@ int main(int argc, char* argv[]) {
     // Initialize MCP tool system — registers all internal tools and starts
     // external MCP servers in the background (non-blocking).
     fincept::mcp::initialize_all_tools();
-
     // ── Headless tool-system self-test / catalog dump ────────────────────────
     // Runs after the real tool registration above but before any window or
     // network init, so it exercises exactly what ships. Exits without starting
@@ -924,6 +1111,8 @@ int main(int argc, char* argv[]) {
         if (qstrcmp(argv[i], "--selftest-arena") == 0)
             return fincept::arena::run_arena_selftest();
     }
+    if (headless)
+        return fincept::run_headless(QCoreApplication::arguments());
 
     // Start the scan-watch background service. Runs after Database::open() (which
     // applies the scan_watches migration) and after bootstrap_auth() (broker
@@ -941,10 +1130,32 @@ int main(int argc, char* argv[]) {
     // early-returns, so it stays off in headless --selftest runs).
     fincept::trading::PaperMarkService::instance().start();
 
+    // Streaming intraday P&L across every active account (broker + paper), with
+    // per-position / per-strategy / per-account rollups on `pnl:intraday`.
+    fincept::trading::IntradayPnlService::instance().start();
+
+    // Trade journal: hourly copy of the fills ledger into journal_fills (live
+    // order books only cover today) and a rebuild of the round trips.
+    fincept::services::journal::TradeJournalService::instance().start();
+
+    // Dividend calendar refresh + ex-date reminders for held symbols. Runs off
+    // the Python yfinance script, so the first pass is delayed until well after
+    // startup; a missing Python env just logs and retries on the next tick.
+    fincept::services::DividendService::instance().start();
+
+    // Opt-in reminders ahead of earnings and central-bank decisions on open
+    // positions (event_risk.alert_days); a no-op until the user sets a window.
+    fincept::services::EventRiskService::instance().start();
+
     // Native desktop notifications (Win toast / macOS Notification Center / Linux
     // libnotify) via a tray icon — also surfaces every in-app ToastService toast.
     fincept::ui::DesktopNotifier::instance().init();
 
+    // Optional localhost JSON-RPC API for external research code (Settings ›
+    // Developer). Off by default; after the self-test early-returns so
+    // headless runs never bind its port.
+    fincept::mcp::LocalApiServer::instance().start_from_settings();
+
     // ── Python environment check ─────────────────────────────────────────────
     // check_status() fast path (sentinel + markers present) is synchronous and
     // cheap. The slow path (first run) can spawn processes — but at this point
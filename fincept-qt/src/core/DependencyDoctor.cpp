@@ -0,0 +1,436 @@
+#include "core/DependencyDoctor.h"
+
+#include "core/config/AppConfig.h"
+#include "core/config/AppPaths.h"
+#include "core/logging/Logger.h"
+#include "python/PythonRunner.h"
+#include "python/PythonSetupManager.h"
+
+#include <QDir>
+#include <QElapsedTimer>
+#include <QEventLoop>
+#include <QFile>
+#include <QFileInfo>
+#include <QJsonArray>
+#include <QNetworkAccessManager>
+#include <QNetworkReply>
+#include <QNetworkRequest>
+#include <QProcess>
+#include <QSqlDatabase>
+#include <QSqlError>
+#include <QSqlQuery>
+#include <QStorageInfo>
+#include <QSysInfo>
+#include <QThread>
+#include <QTimer>
+#include <QUuid>
+
+#include <iterator>
+
+// FINCEPT_VERSION_STRING is injected by CMake; same fallback as SystemTools.
+#ifndef FINCEPT_VERSION_STRING
+#    define FINCEPT_VERSION_STRING "0.0.0-dev"
+#endif
+
+namespace fincept {
+
+namespace {
+
+constexpr const char* DD_TAG = "DependencyDoctor";
+constexpr qint64 kDdLowDiskBytes = 1024LL * 1024 * 1024;     // warn below 1 GiB free
+constexpr qint64 kDdCriticalDiskBytes = 200LL * 1024 * 1024; // fail below 200 MiB
+constexpr qint64 kDdSlowNetworkMs = 2000;
+
+using Check = DiagnosticsReport::Check;
+using Level = DiagnosticsReport::Level;
+
+QString dd_tidy(const QString& path) {
+    const QString home = QDir::homePath();
+    QString p = QDir::toNativeSeparators(path);
+    const QString native_home = QDir::toNativeSeparators(home);
+    if (!home.isEmpty() && p.startsWith(native_home))
+        p.replace(0, native_home.size(), "~");
+    return p;
+}
+
+Check dd_check(const QString& section, const QString& name, Level level, const QString& detail,
+               const QJsonObject& data = {}) {
+    return Check{section, name, level, detail, data};
+}
+
+// First line of `program args` stdout, empty when it can't run.
+QString dd_version_of(const QString& program, const QStringList& args) {
+    QProcess proc;
+#ifdef _WIN32
+    proc.setCreateProcessArgumentsModifier([](QProcess::CreateProcessArguments* cpa) {
+        cpa->flags |= 0x08000000; // CREATE_NO_WINDOW
+    });
+#endif
+    proc.start(program, args);
+    if (!proc.waitForFinished(10000) || proc.exitCode() != 0)
+        return {};
+    // Python 3 prints --version to stdout, some older tools to stderr.
+    QString out = QString::fromUtf8(proc.readAllStandardOutput()).trimmed();
+    if (out.isEmpty())
+        out = QString::fromUtf8(proc.readAllStandardError()).trimmed();
+    return out.section('\n', 0, 0);
+}
+
+} // namespace
+
+// ── DiagnosticsReport ────────────────────────────────────────────────────────
+
+QString diagnostics_level_name(DiagnosticsReport::Level level) {
+    switch (level) {
+        case Level::Ok:
+            return "ok";
+        case Level::Warn:
+            return "warn";
+        case Level::Fail:
+            return "fail";
+        case Level::Skipped:
+            return "skipped";
+    }
+    return "ok";
+}
+
+int DiagnosticsReport::count(Level level) const {
+    int n = 0;
+    for (const auto& c : checks) {
+        if (c.level == level)
+            ++n;
+    }
+    return n;
+}
+
+QJsonObject DiagnosticsReport::to_json() const {
+    QJsonArray arr;
+    for (const auto& c : checks) {
+        QJsonObject o{
+            {"section", c.section},
+            {"name", c.name},
+            {"status", diagnostics_level_name(c.level)},
+            {"detail", c.detail},
+        };
+        if (!c.data.isEmpty())
+            o["data"] = c.data;
+        arr.append(o);
+    }
+    return QJsonObject{
+        {"ok", ok()},
+        {"generated_at", generated_at.toUTC().toString(Qt::ISODate)},
+        {"duration_ms", duration_ms},
+        {"summary", QJsonObject{{"ok", count(Level::Ok)},
+                                {"warn", count(Level::Warn)},
+                                {"fail", count(Level::Fail)},
+                                {"skipped", count(Level::Skipped)}}},
+        {"environment", environment},
+        {"checks", arr},
+    };
+}
+
+QString DiagnosticsReport::to_text() const {
+    QStringList lines;
+    lines << "### Fincept Terminal diagnostics";
+    lines << QString("Generated %1 in %2 ms — %3 ok, %4 warn, %5 fail")
+                 .arg(generated_at.toUTC().toString(Qt::ISODate))
+                 .arg(duration_ms)
+                 .arg(count(Level::Ok))
+                 .arg(count(Level::Warn))
+                 .arg(count(Level::Fail));
+    lines << "" << "**Environment**";
+    for (auto it = environment.begin(); it != environment.end(); ++it)
+        lines << QString("- %1: %2").arg(it.key(), it.value().toVariant().toString());
+
+    QString section;
+    for (const auto& c : checks) {
+        if (c.section != section) {
+            section = c.section;
+            lines << "" << QString("**%1**").arg(section);
+        }
+        lines << QString("- [%1] %2 — %3").arg(diagnostics_level_name(c.level).toUpper(), c.name, c.detail);
+    }
+    return lines.join('\n');
+}
+
+// ── DependencyDoctor ─────────────────────────────────────────────────────────
+
+DependencyDoctor& DependencyDoctor::instance() {
+    static DependencyDoctor inst;
+    return inst;
+}
+
+DiagnosticsReport DependencyDoctor::run(const DiagnosticsOptions& options) const {
+    QElapsedTimer timer;
+    timer.start();
+
+    DiagnosticsReport report;
+    report.generated_at = QDateTime::currentDateTimeUtc();
+    report.environment = environment();
+    check_python(report.checks);
+    if (options.packages)
+        check_packages(report.checks);
+    else
+        report.checks.append(dd_check("packages", "packages", Level::Skipped, "Not requested"));
+    check_directories(report.checks);
+    check_sqlite(report.checks);
+    if (options.network)
+        check_network(report.checks, options.network_timeout_ms);
+    else
+        report.checks.append(dd_check("network", "network", Level::Skipped, "Not requested"));
+    report.duration_ms = timer.elapsed();
+
+    LOG_INFO(DD_TAG, QString("Diagnostics: %1 ok, %2 warn, %3 fail in %4 ms")
+                         .arg(report.count(Level::Ok))
+                         .arg(report.count(Level::Warn))
+                         .arg(report.count(Level::Fail))
+                         .arg(report.duration_ms));
+    return report;
+}
+
+QJsonObject DependencyDoctor::environment() const {
+    return QJsonObject{
+        {"app_version", QString::fromUtf8(FINCEPT_VERSION_STRING)},
+        {"os", QSysInfo::prettyProductName()},
+        {"kernel", QSysInfo::kernelType() + " " + QSysInfo::kernelVersion()},
+        {"cpu_arch", QSysInfo::currentCpuArchitecture()},
+        {"cpu_cores", QThread::idealThreadCount()},
+        {"qt_version", QString::fromLatin1(qVersion())},
+        {"app_root", dd_tidy(AppPaths::root())},
+    };
+}
+
+void DependencyDoctor::check_python(QVector<Check>& out) const {
+    auto& setup = python::PythonSetupManager::instance();
+    const auto status = setup.check_status();
+
+    const QString uv = setup.uv_path();
+    if (!QFileInfo::exists(uv)) {
+        out.append(dd_check("python", "uv", Level::Fail, "UV not installed — run setup",
+                            QJsonObject{{"path", dd_tidy(uv)}}));
+    } else {
+        const QString version = dd_version_of(uv, {"--version"});
+        out.append(dd_check("python", "uv", version.isEmpty() ? Level::Fail : Level::Ok,
+                            version.isEmpty() ? "UV present but does not run" : version,
+                            QJsonObject{{"path", dd_tidy(uv)}}));
+    }
+
+    const QString py = status.python_installed ? setup.base_python_path() : QString();
+    if (py.isEmpty()) {
+        out.append(dd_check("python", "python_runtime", Level::Fail, "Python runtime not installed — run setup"));
+    } else {
+        const QString version = dd_version_of(py, {"--version"});
+        out.append(dd_check("python", "python_runtime", version.isEmpty() ? Level::Fail : Level::Ok,
+                            version.isEmpty() ? "Python present but does not run" : version,
+                            QJsonObject{{"path", dd_tidy(py)}}));
+    }
+
+    const struct {
+        const char* venv;
+        bool created;
+        bool ready;
+    } venvs[] = {{"venv-numpy1", status.venv_numpy1_created, status.venv_numpy1_ready},
+                 {"venv-numpy2", status.venv_numpy2_created, status.venv_numpy2_ready}};
+    for (const auto& v : venvs) {
+        const QString path = setup.python_path(v.venv);
+        const Level level = !v.created ? Level::Fail : (v.ready ? Level::Ok : Level::Warn);
+        const QString detail = !v.created ? "Not created — run setup"
+                               : v.ready  ? "Ready"
+                                          : "Packages out of date with the requirements — setup will resync";
+        out.append(dd_check("python", v.venv, level, detail, QJsonObject{{"python", dd_tidy(path)}}));
+    }
+
+    const bool runner = python::PythonRunner::instance().is_available();
+    out.append(dd_check("python", "python_runner", runner ? Level::Ok : Level::Fail,
+                        runner ? "Interpreter available to analytics" : "No interpreter available to analytics"));
+
+    const QString bundle = setup.find_offline_bundle();
+    if (!bundle.isEmpty())
+        out.append(dd_check("python", "offline_bundle", Level::Ok, "Offline setup bundle found",
+                            QJsonObject{{"path", dd_tidy(bundle)}}));
+}
+
+void DependencyDoctor::check_packages(QVector<Check>& out) const {
+    auto& setup = python::PythonSetupManager::instance();
+    const QPair<QString, QString> venvs[] = {{"venv-numpy1", "requirements-numpy1.txt"},
+                                             {"venv-numpy2", "requirements-numpy2.txt"}};
+    for (const auto& [venv, req] : venvs) {
+        if (!QFileInfo::exists(setup.python_path(venv))) {
+            out.append(dd_check("packages", venv, Level::Skipped, "venv not created"));
+            continue;
+        }
+        const QJsonObject r = setup.package_report(venv, req);
+        const QJsonArray missing = r.value("missing").toArray();
+        if (r.contains("error")) {
+            out.append(dd_check("packages", venv, Level::Fail, r.value("error").toString(), r));
+        } else if (!missing.isEmpty()) {
+            QStringList names;
+            for (const auto& m : missing)
+                names << m.toString();
+            out.append(dd_check("packages", venv, Level::Fail,
+                                QString("%1 of %2 required package(s) missing: %3")
+                                    .arg(missing.size())
+                                    .arg(r.value("required").toInt())
+                                    .arg(names.join(", ").left(300)),
+                                r));
+        } else {
+            out.append(dd_check("packages", venv, Level::Ok,
+                                QString("All %1 required packages installed (%2 total)")
+                                    .arg(r.value("required").toInt())
+                                    .arg(r.value("installed").toInt()),
+                                r));
+        }
+    }
+}
+
+void DependencyDoctor::check_directories(QVector<Check>& out) const {
+    const QPair<const char*, QString> dirs[] = {
+        {"root", AppPaths::root()},           {"data", AppPaths::data()},
+        {"logs", AppPaths::logs()},           {"files", AppPaths::files()},
+        {"cache", AppPaths::cache()},         {"models", AppPaths::models()},
+        {"runtime", AppPaths::runtime()},     {"plugins", AppPaths::plugins()},
+        {"workspaces", AppPaths::workspaces()}, {"crashdumps", AppPaths::crashdumps()},
+    };
+
+    // Permission bits lie on Windows ACLs and read-only mounts, so write and
+    // remove a real probe file.
+    QJsonObject data;
+    QStringList failed;
+    for (const auto& [name, dir] : dirs) {
+        QFile probe(dir + "/.doctor-probe-" + QUuid::createUuid().toString(QUuid::WithoutBraces));
+        const bool ok = QFileInfo(dir).isDir() && probe.open(QIODevice::WriteOnly) && probe.write("ok", 2) == 2;
+        probe.close();
+        probe.remove();
+        data[QString::fromLatin1(name)] = QJsonObject{{"path", dd_tidy(dir)}, {"writable", ok}};
+        if (!ok)
+            failed << QString::fromLatin1(name);
+    }
+    out.append(dd_check("directories", "writable", failed.isEmpty() ? Level::Ok : Level::Fail,
+                        failed.isEmpty() ? QString("All %1 app directories writable").arg(int(std::size(dirs)))
+                                         : "Not writable: " + failed.join(", "),
+                        data));
+
+    const QStorageInfo disk(AppPaths::root());
+    const qint64 free_bytes = disk.isValid() ? disk.bytesAvailable() : -1;
+    const Level level = free_bytes < 0                      ? Level::Warn
+                        : free_bytes < kDdCriticalDiskBytes ? Level::Fail
+                        : free_bytes < kDdLowDiskBytes      ? Level::Warn
+                                                            : Level::Ok;
+    out.append(dd_check("directories", "disk_space", level,
+                        free_bytes < 0 ? QString("Free space unknown")
+                                       : QString("%1 MiB free").arg(free_bytes / (1024 * 1024)),
+                        QJsonObject{{"free_bytes", free_bytes}, {"volume", dd_tidy(disk.rootPath())}}));
+}
+
+// Every SQLite file in data/, opened on a private read-only connection so the
+// check never contends with the app's own handles. quick_check covers page
+// and index structure without the full cross-reference pass integrity_check
+// makes, which can take minutes on a large tick store.
+void DependencyDoctor::check_sqlite(QVector<Check>& out) const {
+    const QDir dir(AppPaths::data());
+    const QStringList files = dir.entryList({"*.db", "*.sqlite"}, QDir::Files, QDir::Name);
+    if (files.isEmpty()) {
+        out.append(dd_check("sqlite", "databases", Level::Warn, "No databases in " + dd_tidy(dir.path())));
+        return;
+    }
+
+    for (const auto& file : files) {
+        const QString path = dir.filePath(file);
+        const QString conn_name = "doctor_" + QUuid::createUuid().toString(QUuid::WithoutBraces);
+        Check c = dd_check("sqlite", file, Level::Ok, {},
+                           QJsonObject{{"path", dd_tidy(path)}, {"size_bytes", QFileInfo(path).size()}});
+        {
+            QSqlDatabase db = QSqlDatabase::addDatabase("QSQLITE", conn_name);
+            db.setDatabaseName(path);
+            db.setConnectOptions("QSQLITE_OPEN_READONLY;QSQLITE_BUSY_TIMEOUT=5000");
+            if (!db.open()) {
+                c.level = Level::Fail;
+                c.detail = "Cannot open: " + db.lastError().text();
+            } else {
+                QSqlQuery q(db);
+                QStringList problems;
+                if (!q.exec("PRAGMA quick_check")) {
+                    problems << q.lastError().text();
+                } else {
+                    while (q.next()) {
+                        const QString row = q.value(0).toString();
+                        if (row != "ok" && problems.size() < 5)
+                            problems << row;
+                    }
+                }
+                if (q.exec("PRAGMA journal_mode") && q.next())
+                    c.data["journal_mode"] = q.value(0).toString();
+                c.level = problems.isEmpty() ? Level::Ok : Level::Fail;
+                c.detail = problems.isEmpty() ? "Integrity ok" : "Integrity problems: " + problems.join("; ");
+                q.finish();
+                db.close();
+            }
+        }
+        QSqlDatabase::removeDatabase(conn_name);
+        out.append(c);
+    }
+}
+
+// Any HTTP response (even 4xx) proves DNS, TLS and the route work; only
+// transport errors and timeouts count as unreachable.
+void DependencyDoctor::check_network(QVector<Check>& out, int timeout_ms) const {
+    const struct {
+        const char* name;
+        QString url;
+        const char* purpose;
+    } endpoints[] = {
+        {"fincept_api", AppConfig::instance().api_base_url(), "login, AI, news and data services"},
+        {"market_data", "https://query1.finance.yahoo.com", "quotes and history"},
+        {"pypi", "https://pypi.org/simple/", "Python package index"},
+        {"python_packages", "https://files.pythonhosted.org", "Python package downloads"},
+        {"github", "https://github.com", "UV and Python runtime downloads"},
+    };
+
+    // Own manager and loop on this worker thread — all requests go out in
+    // parallel and the whole check is bounded by one timeout.
+    QNetworkAccessManager nam;
+    QEventLoop loop;
+    QVector<QNetworkReply*> replies;
+    QVector<qint64> latency(int(std::size(endpoints)), -1);
+    QElapsedTimer clock;
+    clock.start();
+    int pending = int(std::size(endpoints));
+    for (int i = 0; i < int(std::size(endpoints)); ++i) {
+        QNetworkRequest req{QUrl(endpoints[i].url)};
+        req.setTransferTimeout(timeout_ms);
+        req.setAttribute(QNetworkRequest::RedirectPolicyAttribute, QNetworkRequest::NoLessSafeRedirectPolicy);
+        QNetworkReply* reply = nam.head(req);
+        QObject::connect(reply, &QNetworkReply::finished, &loop, [&, i]() {
+            latency[i] = clock.elapsed();
+            if (--pending == 0)
+                loop.quit();
+        });
+        replies.append(reply);
+    }
+    QTimer::singleShot(timeout_ms + 1000, &loop, &QEventLoop::quit);
+    loop.exec();
+
+    for (int i = 0; i < replies.size(); ++i) {
+        QNetworkReply* reply = replies[i];
+        const auto& ep = endpoints[i];
+        QJsonObject data{{"url", ep.url}, {"purpose", QString::fromLatin1(ep.purpose)}};
+        Check c = dd_check("network", QString::fromLatin1(ep.name), Level::Ok, {}, data);
+        const int http = reply->attribute(QNetworkRequest::HttpStatusCodeAttribute).toInt();
+        if (!reply->isFinished()) {
+            reply->abort();
+            c.level = Level::Fail;
+            c.detail = QString("No response within %1 ms").arg(timeout_ms);
+        } else if (http == 0) {
+            c.level = Level::Fail;
+            c.detail = "Unreachable: " + reply->errorString();
+        } else {
+            c.level = latency[i] > kDdSlowNetworkMs ? Level::Warn : Level::Ok;
+            c.detail = QString("HTTP %1 in %2 ms").arg(http).arg(latency[i]);
+            c.data["http_status"] = http;
+            c.data["latency_ms"] = latency[i];
+        }
+        out.append(c); // replies are owned by `nam`
+    }
+}
+
+} // namespace fincept
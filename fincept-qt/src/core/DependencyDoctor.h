@@ -0,0 +1,76 @@
+#pragma once
+// DependencyDoctor — on-demand diagnosis of the runtime environment.
+//
+// HealthMonitor answers "are the live subsystems up right now"; the doctor
+// answers "can this install work at all": the Python runtime and its venvs,
+// required packages with their installed versions, writable app directories,
+// SQLite file integrity and reachability of the hosts the app depends on.
+// The report is structured (to_json) and also renders as Markdown (to_text)
+// for users to paste into a support issue. The home directory is shown as
+// "~" in both so reports don't carry the OS user name.
+//
+// BLOCKING: spawns `uv pip list`, runs PRAGMA quick_check over every database
+// and makes HEAD requests. Call it from a worker thread (MCP tool handlers
+// already run on one), never the UI thread.
+
+#include <QDateTime>
+#include <QJsonObject>
+#include <QString>
+#include <QVector>
+
+namespace fincept {
+
+struct DiagnosticsReport {
+    enum class Level { Ok, Warn, Fail, Skipped };
+
+    struct Check {
+        QString section; ///< "python", "packages", "directories", "sqlite", "network"
+        QString name;
+        Level level = Level::Ok;
+        QString detail;   ///< one-line human-readable result
+        QJsonObject data; ///< check-specific extras: paths, versions, latency
+    };
+
+    QDateTime generated_at;
+    qint64 duration_ms = 0;
+    QJsonObject environment; ///< app version, OS, CPU, Qt, install root
+    QVector<Check> checks;
+
+    int count(Level level) const;
+    /// True when no check failed (warnings allowed).
+    bool ok() const { return count(Level::Fail) == 0; }
+
+    QJsonObject to_json() const;
+    /// Markdown summary: environment, then one line per check.
+    QString to_text() const;
+};
+
+QString diagnostics_level_name(DiagnosticsReport::Level level);
+
+struct DiagnosticsOptions {
+    bool packages = true; ///< compare venv packages against the requirements files (slowest part)
+    bool network = true;
+    int network_timeout_ms = 5000;
+};
+
+class DependencyDoctor {
+  public:
+    static DependencyDoctor& instance();
+
+    DiagnosticsReport run(const DiagnosticsOptions& options = {}) const;
+
+  private:
+    DependencyDoctor() = default;
+    DependencyDoctor(const DependencyDoctor&) = delete;
+    DependencyDoctor& operator=(const DependencyDoctor&) = delete;
+
+    // Each appends its checks and never throws.
+    QJsonObject environment() const;
+    void check_python(QVector<DiagnosticsReport::Check>& out) const;
+    void check_packages(QVector<DiagnosticsReport::Check>& out) const;
+    void check_directories(QVector<DiagnosticsReport::Check>& out) const;
+    void check_sqlite(QVector<DiagnosticsReport::Check>& out) const;
+    void check_network(QVector<DiagnosticsReport::Check>& out, int timeout_ms) const;
+};
+
+} // namespace fincept
@@ -2,7 +2,9 @@
 
 #include "core/logging/Logger.h"
 #include "core/telemetry/TelemetryProvider.h"
+#include "core/telemetry/UsageMetrics.h"
 
+#include <QElapsedTimer>
 #include <QVariantMap>
 
 #include <algorithm>
@@ -136,7 +138,11 @@ Result<void> ActionRegistry::invoke(const QString& id, const CommandContext& ctx
         return Result<void>::err(("Action unavailable in current context: " + id).toStdString());
     }
 
+    QElapsedTimer timer;
+    timer.start();
     Result<void> result = def->handler ? def->handler(ctx) : Result<void>::ok();
+    telemetry::UsageMetrics::instance().record_latency(telemetry::UsageMetrics::Kind::Action, id, timer.elapsed(),
+                                                       result.is_ok());
     if (!def->handler) {
         // Placeholder handler. Common during phased rollout — registered
         // for discoverability but not yet wired. Treat as success so
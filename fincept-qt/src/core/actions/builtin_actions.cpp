@@ -1,8 +1,10 @@
 #include "core/actions/builtin_actions.h"
 
+#include "algo_engine/AlgoEngine.h"
 #include "app/DockScreenRouter.h"
 #include "app/WindowFrame.h"
 #include "auth/InactivityGuard.h"
+#include "core/DependencyDoctor.h"
 #include "core/actions/ActionRegistry.h"
 #include "core/debug/StressLoad.h"
 #include "core/keys/KeyConfigManager.h"
@@ -17,16 +19,23 @@
 #include "core/symbol/SymbolGroup.h"
 #include "core/symbol/SymbolGroupRegistry.h"
 #include "core/symbol/SymbolRef.h"
+#include "core/telemetry/UsageMetrics.h"
 #include "core/window/WindowRegistry.h"
 #include "screens/launchpad/OnboardingTour.h"
+#include "storage/ConfigBundle.h"
 
 #include <QApplication>
+#include <QClipboard>
 #include <QDateTime>
 #include <QDir>
 #include <QFileDialog>
 #include <QGuiApplication>
+#include <QInputDialog>
+#include <QMessageBox>
 #include <QPixmap>
+#include <QPointer>
 #include <QScreen>
+#include <QtConcurrent>
 
 namespace fincept::actions {
 
@@ -484,6 +493,205 @@ Result<void> handler_replay_tour(const CommandContext& ctx) {
     return Result<void>::ok();
 }
 
+Result<void> handler_run_diagnostics(const CommandContext& ctx) {
+    // The doctor blocks for up to a minute (uv pip list, quick_check, HEAD
+    // probes), so it runs off the UI thread; the Markdown report lands on the
+    // clipboard ready to paste into a support issue.
+    QPointer<QWidget> parent = ctx.focused_frame;
+    (void)QtConcurrent::run([parent]() {
+        const auto report = DependencyDoctor::instance().run();
+        const QString text = report.to_text();
+        const QString summary = QString("%1 ok, %2 warnings, %3 failures")
+                                    .arg(report.count(DiagnosticsReport::Level::Ok))
+                                    .arg(report.count(DiagnosticsReport::Level::Warn))
+                                    .arg(report.count(DiagnosticsReport::Level::Fail));
+        QMetaObject::invokeMethod(
+            qApp,
+            [parent, text, summary]() {
+                QGuiApplication::clipboard()->setText(text);
+                QMessageBox box(parent);
+                box.setWindowTitle(QObject::tr("Diagnostics"));
+                box.setText(QObject::tr("Diagnostics finished: %1.\nThe full report was copied to the clipboard — "
+                                        "paste it into your support issue.")
+                                .arg(summary));
+                box.setDetailedText(text);
+                box.exec();
+            },
+            Qt::QueuedConnection);
+    });
+    return Result<void>::ok();
+}
+
+Result<void> handler_usage_metrics(const CommandContext& ctx) {
+    // Recording is opt-in, so the first use asks; after that the last 24 h
+    // are shown and copied, with a button to turn recording back off.
+    auto& metrics = telemetry::UsageMetrics::instance();
+    if (!metrics.enabled()) {
+        const auto answer = QMessageBox::question(
+            ctx.focused_frame, QObject::tr("Usage Metrics"),
+            QObject::tr("Local usage metrics are off.\n\nRecord command latencies, data-source refresh times, "
+                        "cache hit rates and websocket throughput on this machine? Nothing is uploaded."));
+        if (answer != QMessageBox::Yes)
+            return Result<void>::ok();
+        return metrics.set_enabled(true);
+    }
+
+    const QString text = telemetry::UsageMetrics::summary_text(metrics.summary());
+    QGuiApplication::clipboard()->setText(text);
+    QMessageBox box(ctx.focused_frame);
+    box.setWindowTitle(QObject::tr("Usage Metrics"));
+    box.setText(QObject::tr("Usage metrics for the last 24 hours were copied to the clipboard."));
+    box.setDetailedText(text);
+    auto* disable = box.addButton(QObject::tr("Stop Recording"), QMessageBox::DestructiveRole);
+    box.addButton(QMessageBox::Close);
+    box.exec();
+    if (box.clickedButton() == disable)
+        return metrics.set_enabled(false);
+    return Result<void>::ok();
+}
+
+Result<void> handler_algo_crash_recovery(const CommandContext& ctx) {
+    // The report queries each broker, so it is built off the UI thread; then
+    // one prompt per held deployment. Closing a prompt leaves it held.
+    QPointer<QWidget> parent = ctx.focused_frame;
+    (void)QtConcurrent::run([parent]() {
+        const auto items = algo::AlgoEngine::instance().recovery_report();
+        QMetaObject::invokeMethod(
+            qApp,
+            [parent, items]() {
+                if (items.isEmpty()) {
+                    QMessageBox::information(parent, QObject::tr("Algo Crash Recovery"),
+                                             QObject::tr("No live deployments are awaiting crash recovery."));
+                    return;
+                }
+                for (const auto& it : items) {
+                    QString detail = it.broker_checked
+                                         ? (it.notes.isEmpty() ? QObject::tr("Broker position matches the record.")
+                                                               : it.notes.join('\n'))
+                                         : QObject::tr("Broker unreachable: %1").arg(it.error);
+                    if (it.inflight_orders > 0)
+                        detail += QObject::tr("\n%1 order(s) sent before the crash were never confirmed.")
+                                      .arg(it.inflight_orders);
+                    QMessageBox box(parent);
+                    box.setIcon(it.state == QLatin1String("in_sync") ? QMessageBox::Question : QMessageBox::Warning);
+                    box.setWindowTitle(QObject::tr("Algo Crash Recovery"));
+                    box.setText(QObject::tr("Live deployment '%1' on %2 was running when the terminal last "
+                                            "closed unexpectedly.\n\n%3")
+                                    .arg(it.strategy_name, it.symbol, detail));
+                    auto* resume = box.addButton(QObject::tr("Resume"), QMessageBox::AcceptRole);
+                    auto* flatten = box.addButton(QObject::tr("Flatten && Stop"), QMessageBox::DestructiveRole);
+                    auto* stop = box.addButton(QObject::tr("Stop Only"), QMessageBox::RejectRole);
+                    box.addButton(QObject::tr("Decide Later"), QMessageBox::NoRole);
+                    box.exec();
+                    const auto* clicked = box.clickedButton();
+                    const QString action = clicked == resume    ? QStringLiteral("resume")
+                                           : clicked == flatten ? QStringLiteral("flatten")
+                                           : clicked == stop    ? QStringLiteral("stop")
+                                                                : QString();
+                    if (!action.isEmpty())
+                        algo::AlgoEngine::instance().resolve_recovery(it.deployment_id, action);
+                }
+            },
+            Qt::QueuedConnection);
+    });
+    return Result<void>::ok();
+}
+
+Result<void> handler_export_config(const CommandContext& ctx) {
+    QWidget* parent = ctx.focused_frame;
+    const QString suffix = QString::fromLatin1(ConfigBundle::kFileSuffix);
+    const QString path = QFileDialog::getSaveFileName(
+        parent, QObject::tr("Export Configuration"),
+        QDir::home().filePath("fincept_config_" + QDateTime::currentDateTime().toString("yyyyMMdd") + "." + suffix),
+        QObject::tr("Fincept config bundle (*.%1)").arg(suffix));
+    if (path.isEmpty())
+        return Result<void>::ok();
+
+    ConfigBundleOptions opts;
+    opts.credentials =
+        QMessageBox::question(parent, QObject::tr("Export Configuration"),
+                              QObject::tr("Include broker accounts and stored API keys?\n\nThey are encrypted with "
+                                          "a passphrase you choose now and need again on import."),
+                              QMessageBox::Yes | QMessageBox::No, QMessageBox::No) == QMessageBox::Yes;
+    if (opts.credentials) {
+        bool ok = false;
+        opts.passphrase = QInputDialog::getText(
+            parent, QObject::tr("Export Configuration"),
+            QObject::tr("Passphrase (at least %1 characters):").arg(ConfigBundle::kMinPassphraseLength),
+            QLineEdit::Password, {}, &ok);
+        if (!ok)
+            return Result<void>::ok();
+        const QString confirm = QInputDialog::getText(parent, QObject::tr("Export Configuration"),
+                                                      QObject::tr("Repeat the passphrase:"), QLineEdit::Password,
+                                                      {}, &ok);
+        if (!ok)
+            return Result<void>::ok();
+        if (confirm != opts.passphrase)
+            return Result<void>::err("Passphrases do not match");
+    }
+
+    auto r = ConfigBundle::export_to(path, opts);
+    if (r.is_err())
+        return Result<void>::err(r.error());
+    QMessageBox::information(parent, QObject::tr("Export Configuration"),
+                             QObject::tr("Configuration exported to\n%1").arg(QDir::toNativeSeparators(path)));
+    return Result<void>::ok();
+}
+
+Result<void> handler_import_config(const CommandContext& ctx) {
+    QWidget* parent = ctx.focused_frame;
+    const QString path = QFileDialog::getOpenFileName(
+        parent, QObject::tr("Import Configuration"), QDir::homePath(),
+        QObject::tr("Fincept config bundle (*.%1)").arg(QString::fromLatin1(ConfigBundle::kFileSuffix)));
+    if (path.isEmpty())
+        return Result<void>::ok();
+
+    auto info = ConfigBundle::inspect(path);
+    if (info.is_err())
+        return Result<void>::err(info.error());
+    const QJsonObject header = info.value();
+    QStringList lines;
+    const QJsonObject sections = header.value("sections").toObject();
+    for (auto it = sections.begin(); it != sections.end(); ++it)
+        lines << QString("  %1: %2 item(s)").arg(it.key()).arg(it.value().toInt());
+
+    ConfigBundleOptions opts;
+    if (header.value("has_credentials").toBool()) {
+        bool ok = false;
+        opts.passphrase = QInputDialog::getText(
+            parent, QObject::tr("Import Configuration"),
+            QObject::tr("This bundle includes encrypted credentials.\nEnter its passphrase, or leave empty to skip "
+                        "them:"),
+            QLineEdit::Password, {}, &ok);
+        if (!ok)
+            return Result<void>::ok();
+        opts.credentials = !opts.passphrase.isEmpty();
+    }
+
+    const auto answer = QMessageBox::question(
+        parent, QObject::tr("Import Configuration"),
+        QObject::tr("Merge this configuration (exported %1 on %2)?\n\n%3\n\nItems with the same id are replaced. "
+                    "Restart the terminal afterwards to apply everything.")
+            .arg(header.value("created_at").toString(), header.value("source_os").toString(), lines.join('\n')));
+    if (answer != QMessageBox::Yes)
+        return Result<void>::ok();
+
+    auto r = ConfigBundle::import_from(path, opts);
+    if (r.is_err())
+        return Result<void>::err(r.error());
+    QString message = QObject::tr("Configuration imported. Restart the terminal to apply it.");
+    const QJsonObject missing = r.value().value("needs_credentials").toObject();
+    if (!missing.isEmpty()) {
+        QStringList tables;
+        for (auto it = missing.begin(); it != missing.end(); ++it)
+            tables << QString("  %1: %2").arg(it.key()).arg(it.value().toInt());
+        message += "\n\n" + QObject::tr("These came without their API keys — re-enter them in Settings:") + '\n' +
+                   tables.join('\n');
+    }
+    QMessageBox::information(parent, QObject::tr("Import Configuration"), message);
+    return Result<void>::ok();
+}
+
 // ── Mapping from KeyAction enum to action id strings ──────────────────────
 //
 // The id is what the registry, command bar, and hotkey-binding layer use.
@@ -941,6 +1149,39 @@ void register_builtins() {
         {},
     });
 
+    register_one(ActionDef{
+        "algo.crash_recovery",
+        "Algo Crash Recovery (resume / flatten)",
+        "Terminal",
+        {"algo", "crash", "recovery", "flatten", "resume deployment"},
+        QKeySequence{},
+        /*predicate*/ {},
+        &handler_algo_crash_recovery,
+        {},
+    });
+
+    register_one(ActionDef{
+        "terminal.export_config",
+        "Export Configuration",
+        "Terminal",
+        {"export config", "backup settings", "config bundle", "move to new machine"},
+        QKeySequence{},
+        /*predicate*/ {},
+        &handler_export_config,
+        {},
+    });
+
+    register_one(ActionDef{
+        "terminal.import_config",
+        "Import Configuration",
+        "Terminal",
+        {"import config", "restore settings", "config bundle"},
+        QKeySequence{},
+        /*predicate*/ {},
+        &handler_import_config,
+        {},
+    });
+
     // ── Help-level actions ──────────────────────────────────────────────────
 
     register_one(ActionDef{
@@ -954,6 +1195,28 @@ void register_builtins() {
         {},
     });
 
+    register_one(ActionDef{
+        "help.run_diagnostics",
+        "Run Diagnostics (copy report)",
+        "Help",
+        {"diagnostics", "doctor", "support", "troubleshoot"},
+        QKeySequence{},
+        /*predicate*/ {},
+        &handler_run_diagnostics,
+        {},
+    });
+
+    register_one(ActionDef{
+        "help.usage_metrics",
+        "Usage Metrics (local profiler)",
+        "Help",
+        {"metrics", "profiler", "performance", "slow", "latency"},
+        QKeySequence{},
+        /*predicate*/ {},
+        &handler_usage_metrics,
+        {},
+    });
+
     LOG_INFO(kBuiltinTag, QString("Registered %1 builtin actions").arg(ActionRegistry::instance().size()));
 }
 
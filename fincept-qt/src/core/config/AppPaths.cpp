@@ -48,6 +48,10 @@ QString AppPaths::runtime() {
     return root() + "/runtime";
 }
 
+QString AppPaths::plugins() {
+    return root() + "/plugins";
+}
+
 QString AppPaths::workspaces() {
     return ProfileManager::instance().profile_root() + "/workspaces";
 }
@@ -78,6 +82,7 @@ void AppPaths::ensure_all() {
     try_mkpath(cache());
     try_mkpath(models());
     try_mkpath(runtime());
+    try_mkpath(plugins());
     try_mkpath(workspaces());
     try_mkpath(crashdumps());
 }
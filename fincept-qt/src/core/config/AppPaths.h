@@ -17,6 +17,7 @@ namespace fincept {
 ///   cache/   — Tile caches and other transient network data
 ///   models/  — ML model files
 ///   runtime/ — Python interpreter, UV, virtual environments
+///   plugins/ — WebAssembly plugins (indicators, transforms, sources)
 ///
 /// Call AppPaths::ensure_all() once at startup to create every sub-directory.
 class AppPaths {
@@ -42,6 +43,9 @@ class AppPaths {
     /// root/runtime — Python interpreter, UV, virtual environments
     static QString runtime();
 
+    /// root/plugins — WebAssembly plugins (<id>.wasm + <id>.json)
+    static QString plugins();
+
     /// root/workspaces — saved workspace files (.fwsp)
     static QString workspaces();
 
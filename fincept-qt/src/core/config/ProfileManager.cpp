@@ -85,6 +85,36 @@ bool ProfileManager::is_custom_profile() const {
     return active_profile_ != "default";
 }
 
+bool ProfileManager::has_profile(const QString& name) const {
+    load_id_cache_locked();
+    return id_cache_.contains(sanitise_name(name));
+}
+
+QString ProfileManager::startup_profile() const {
+    load_id_cache_locked();
+    return id_cache_.contains(startup_profile_) ? startup_profile_ : QStringLiteral("default");
+}
+
+void ProfileManager::set_startup_profile(const QString& name) {
+    load_id_cache_locked();
+    const QString clean = sanitise_name(name);
+    if (!id_cache_.contains(clean))
+        return;
+    startup_profile_ = clean;
+    save_manifest(list_profiles());
+}
+
+bool ProfileManager::ask_at_startup() const {
+    load_id_cache_locked();
+    return ask_at_startup_;
+}
+
+void ProfileManager::set_ask_at_startup(bool ask) {
+    load_id_cache_locked();
+    ask_at_startup_ = ask;
+    save_manifest(list_profiles());
+}
+
 QString ProfileManager::profile_root() const {
     if (active_profile_ == "default")
         return AppPaths::root();
@@ -170,6 +200,8 @@ void ProfileManager::delete_profile(const QString& name) {
 
     load_id_cache_locked();
     id_cache_.remove(name);
+    if (startup_profile_ == name)
+        startup_profile_ = "default";
 
     QStringList profiles = list_profiles();
     profiles.removeAll(name);
@@ -200,6 +232,9 @@ void ProfileManager::load_id_cache_locked() const {
     if (!doc.isObject())
         return;
 
+    startup_profile_ = doc.object().value("startup_profile").toString("default");
+    ask_at_startup_ = doc.object().value("ask_at_startup").toBool(false);
+
     const QJsonArray arr = doc.object().value("profiles").toArray();
     bool needs_rewrite = false;
     for (const QJsonValue& v : arr) {
@@ -260,6 +295,8 @@ void ProfileManager::save_manifest(const QStringList& profiles) const {
 
     QJsonObject obj;
     obj["profiles"] = arr;
+    obj["startup_profile"] = startup_profile_;
+    obj["ask_at_startup"] = ask_at_startup_;
     obj["schema_version"] = 2; // v1 = bare strings; v2 = {name, id} objects.
 
     QFile f(manifest_path());
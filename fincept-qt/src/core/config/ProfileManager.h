@@ -26,6 +26,11 @@ namespace fincept {
 ///
 /// The default profile is named "default" and is auto-created on first run.
 ///
+/// Which profile opens when no --profile is given is also kept in the
+/// manifest: `startup_profile` (default "default"), or — with
+/// `ask_at_startup` and more than one profile — a picker shown before the
+/// main window.
+///
 /// Call set_active() BEFORE AppPaths::ensure_all() in main().
 class ProfileManager {
   public:
@@ -74,6 +79,19 @@ class ProfileManager {
     /// (user must do that manually to avoid accidental data loss).
     void delete_profile(const QString& name);
 
+    /// True if `name` (sanitised) is in the manifest.
+    bool has_profile(const QString& name) const;
+
+    /// Profile opened when the terminal starts without --profile. Falls back
+    /// to "default" when the stored name is no longer in the manifest.
+    QString startup_profile() const;
+    void set_startup_profile(const QString& name);
+
+    /// Whether to show the profile picker at startup (no --profile given,
+    /// more than one profile).
+    bool ask_at_startup() const;
+    void set_ask_at_startup(bool ask);
+
     /// Returns the root directory for the active profile.
     /// When active == "default", returns AppPaths::root() directly (no migration needed).
     /// Otherwise returns AppPaths::root()/profiles/<name>/.
@@ -98,6 +116,10 @@ class ProfileManager {
     mutable QHash<QString, ProfileId> id_cache_;
     mutable bool id_cache_loaded_ = false;
 
+    // Startup choice, read from / written to the manifest with id_cache_.
+    mutable QString startup_profile_{"default"};
+    mutable bool ask_at_startup_ = false;
+
     QString manifest_path() const;
 
     /// Read the manifest into id_cache_. Idempotent. Tolerates legacy
@@ -106,8 +128,8 @@ class ProfileManager {
     /// next save.
     void load_id_cache_locked() const;
 
-    /// Persist the current name list + UUID map back to disk. Always writes
-    /// the new {name, id} object format.
+    /// Persist the current name list + UUID map (and the startup choice)
+    /// back to disk. Always writes the new {name, id} object format.
     void save_manifest(const QStringList& profiles) const;
 };
 
@@ -43,6 +43,18 @@ struct EquityResearchLoadSymbol {
     static constexpr const char* name() { return "equity_research.load_symbol"; }
 };
 
+/// Sort / sector preset for the Stock Screener (sent by voice commands).
+/// Empty fields leave the current selection alone.
+struct ScreenerApplyPreset {
+    QString sort;   // change_desc | change_asc | volume | price_desc | price_asc | debate
+    QString sector; // tech | financials | consumer | healthcare | energy | utilities | industrials | all
+    QVariantMap to_map() const { return {{"sort", sort}, {"sector", sector}}; }
+    static ScreenerApplyPreset from_map(const QVariantMap& m) {
+        return {m.value(QStringLiteral("sort")).toString(), m.value(QStringLiteral("sector")).toString()};
+    }
+    static constexpr const char* name() { return "screener.apply_preset"; }
+};
+
 // ── Typed API ────────────────────────────────────────────────────────────────
 
 /// Publish a typed event. Delegates to the existing string bus.
@@ -0,0 +1,433 @@
+#include "core/market/MarketCalendar.h"
+
+#include "core/config/AppPaths.h"
+#include "core/logging/Logger.h"
+
+#include <QDir>
+#include <QFile>
+#include <QFileInfo>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QSaveFile>
+
+#include <algorithm>
+
+namespace fincept::core::market {
+
+namespace {
+
+constexpr int kMarketSearchDays = 30; // longest run of closed days we look across (Golden Week, year end)
+
+struct MarketBuiltin {
+    ExchangeCalendar cal;
+    QStringList aliases;
+};
+
+ExchangeCalendar market_session(const QString& code, const QString& name, const QString& tz, int offset_min,
+                                QTime open, QTime close, QTime pre = {}, QTime post = {}) {
+    ExchangeCalendar c;
+    c.code = code;
+    c.name = name;
+    c.tz_id = tz;
+    c.fallback_offset_min = offset_min;
+    c.open = open;
+    c.close = close;
+    c.pre_open = pre;
+    c.post_close = post;
+    return c;
+}
+
+/// Regular sessions. Holidays come from the files, never from here.
+QVector<MarketBuiltin> market_builtin_calendars() {
+    QVector<MarketBuiltin> out;
+
+    out.append({market_session("NYSE", "NYSE / Nasdaq", "America/New_York", -300, QTime(9, 30), QTime(16, 0),
+                               QTime(4, 0), QTime(20, 0)),
+                {"NYSE", "NASDAQ", "NYSEARCA", "ARCA", "AMEX", "BATS", "CBOE", "US", "NMS", "NYQ", "NGM", "NCM",
+                 "PCX", "ASE", "XNYS", "XNAS"}});
+    out.append({market_session("LSE", "London Stock Exchange", "Europe/London", 0, QTime(8, 0), QTime(16, 30),
+                               QTime(7, 50)),
+                {"LSE", "LON", "XLON", "UK", "IOB"}});
+    out.append({market_session("XETRA", "Deutsche Börse Xetra", "Europe/Berlin", 60, QTime(9, 0), QTime(17, 30)),
+                {"XETRA", "XETR", "ETR", "GER", "FRA", "DE"}});
+    out.append({market_session("NSE", "NSE / BSE", "Asia/Kolkata", 330, QTime(9, 15), QTime(15, 30), QTime(9, 0),
+                               QTime(16, 0)),
+                {"NSE", "BSE", "NFO", "BFO", "NSI", "BOM", "XNSE", "XBOM", "IN"}});
+    out.append({market_session("MCX", "MCX commodities", "Asia/Kolkata", 330, QTime(9, 0), QTime(23, 30)),
+                {"MCX", "NCDEX"}});
+
+    ExchangeCalendar cds =
+        market_session("CDS", "NSE / BSE currency derivatives", "Asia/Kolkata", 330, QTime(9, 0), QTime(17, 0));
+    cds.holidays_from = "NSE";
+    out.append({cds, {"CDS", "BCD"}});
+
+    ExchangeCalendar tse = market_session("TSE", "Tokyo Stock Exchange", "Asia/Tokyo", 540, QTime(9, 0), QTime(15, 30));
+    tse.breaks = {{QTime(11, 30), QTime(12, 30)}};
+    out.append({tse, {"TSE", "JPX", "TYO", "XTKS", "JP"}});
+
+    ExchangeCalendar sse = market_session("SSE", "Shanghai / Shenzhen", "Asia/Shanghai", 480, QTime(9, 30),
+                                          QTime(15, 0), QTime(9, 15));
+    sse.breaks = {{QTime(11, 30), QTime(13, 0)}};
+    out.append({sse, {"SSE", "SZSE", "SHH", "SHZ", "XSHG", "XSHE", "CN"}});
+
+    ExchangeCalendar hk = market_session("HKEX", "Hong Kong Exchanges", "Asia/Hong_Kong", 480, QTime(9, 30),
+                                         QTime(16, 0), QTime(9, 0));
+    hk.breaks = {{QTime(12, 0), QTime(13, 0)}};
+    out.append({hk, {"HKEX", "HKG", "HK", "XHKG"}});
+
+    ExchangeCalendar crypto = market_session("CRYPTO", "Crypto (24/7)", "UTC", 0, QTime(0, 0), QTime(23, 59, 59));
+    crypto.always_open = true;
+    crypto.weekend.clear();
+    out.append({crypto, {"CRYPTO", "BINANCE", "COINBASE", "KRAKEN", "BYBIT", "OKX", "HYPERLIQUID", "CCC"}});
+    return out;
+}
+
+QString market_user_path(const QString& code) {
+    return AppPaths::data() + "/market_calendars/" + code.toLower() + ".json";
+}
+
+/// Merges one holiday file into `c`. Returns false when it is missing.
+bool market_merge_file(ExchangeCalendar& c, const QString& path) {
+    QFile f(path);
+    if (!f.open(QIODevice::ReadOnly))
+        return false;
+    const QJsonDocument doc = QJsonDocument::fromJson(f.readAll());
+    if (!doc.isObject()) {
+        LOG_WARN("MarketCalendar", QString("Invalid JSON in %1").arg(path));
+        return false;
+    }
+    const QJsonObject root = doc.object();
+    for (const auto& v : root.value("years").toArray())
+        if (!c.holiday_years.contains(v.toInt()))
+            c.holiday_years.append(v.toInt());
+    for (const auto& v : root.value("holidays").toArray()) {
+        const QJsonObject o = v.toObject();
+        const QDate d = QDate::fromString(o.value("date").toString(), Qt::ISODate);
+        if (d.isValid())
+            c.holidays.insert(d, o.value("name").toString());
+    }
+    for (const auto& v : root.value("early_closes").toArray()) {
+        const QJsonObject o = v.toObject();
+        const QDate d = QDate::fromString(o.value("date").toString(), Qt::ISODate);
+        const QTime t = QTime::fromString(o.value("close").toString(), "HH:mm");
+        if (d.isValid() && t.isValid())
+            c.early_closes.insert(d, {t, o.value("name").toString()});
+    }
+    return true;
+}
+
+QString market_iso(const QDateTime& dt) {
+    return dt.isValid() ? dt.toUTC().toString(Qt::ISODate) : QString();
+}
+
+} // namespace
+
+QString session_phase_name(SessionPhase p) {
+    switch (p) {
+        case SessionPhase::PreMarket:
+            return QStringLiteral("pre_market");
+        case SessionPhase::Regular:
+            return QStringLiteral("regular");
+        case SessionPhase::Break:
+            return QStringLiteral("break");
+        case SessionPhase::PostMarket:
+            return QStringLiteral("post_market");
+        case SessionPhase::Closed:
+            break;
+    }
+    return QStringLiteral("closed");
+}
+
+QTimeZone ExchangeCalendar::zone() const {
+    const QTimeZone tz(tz_id.toUtf8());
+    return tz.isValid() ? tz : QTimeZone(fallback_offset_min * 60);
+}
+
+QJsonObject MarketStatus::to_json() const {
+    QJsonObject o{{"exchange", exchange},
+                  {"name", name},
+                  {"open", open},
+                  {"phase", session_phase_name(phase)},
+                  {"local_time", local_time.toString(Qt::ISODate)},
+                  {"always_open", always_open},
+                  {"holidays_known", holidays_known}};
+    if (next_open.isValid())
+        o["next_open"] = market_iso(next_open);
+    if (next_close.isValid())
+        o["next_close"] = market_iso(next_close);
+    if (!holiday.isEmpty())
+        o["holiday"] = holiday;
+    if (early_close.isValid())
+        o["early_close"] = early_close.toString("HH:mm");
+    return o;
+}
+
+// ── MarketCalendar ───────────────────────────────────────────────────────────
+
+MarketCalendar& MarketCalendar::instance() {
+    static MarketCalendar s;
+    return s;
+}
+
+MarketCalendar::MarketCalendar() {
+    reload();
+}
+
+void MarketCalendar::reload() {
+    QVector<ExchangeCalendar> calendars;
+    QHash<QString, int> aliases;
+    int files = 0;
+    for (auto& b : market_builtin_calendars()) {
+        ExchangeCalendar c = b.cal;
+        files += market_merge_file(c, ":/market_calendars/" + c.code.toLower() + ".json") ? 1 : 0;
+        files += market_merge_file(c, market_user_path(c.code)) ? 1 : 0;
+        if (!c.always_open && !QTimeZone(c.tz_id.toUtf8()).isValid()) {
+            const QString offset = QString::asprintf("%+d", c.fallback_offset_min);
+            LOG_WARN("MarketCalendar", QString("Time zone %1 unavailable; %2 uses a fixed %3 min UTC offset")
+                                           .arg(c.tz_id, c.code, offset));
+        }
+        for (const auto& a : b.aliases)
+            aliases.insert(a.toUpper(), int(calendars.size()));
+        calendars.append(c);
+    }
+
+    QWriteLocker lock(&lock_);
+    calendars_ = calendars;
+    aliases_ = aliases;
+    LOG_INFO("MarketCalendar",
+             QString("Loaded %1 exchange calendars (%2 holiday files)").arg(calendars.size()).arg(files));
+}
+
+QStringList MarketCalendar::exchanges() const {
+    QReadLocker lock(&lock_);
+    QStringList out;
+    for (const auto& c : calendars_)
+        out << c.code;
+    return out;
+}
+
+const ExchangeCalendar* MarketCalendar::find(const QString& exchange) const {
+    const auto it = aliases_.constFind(exchange.trimmed().toUpper());
+    return it == aliases_.cend() ? nullptr : &calendars_[*it];
+}
+
+const ExchangeCalendar* MarketCalendar::holiday_source(const ExchangeCalendar& c) const {
+    if (c.holidays_from.isEmpty())
+        return &c;
+    const ExchangeCalendar* src = find(c.holidays_from);
+    return src ? src : &c;
+}
+
+QString MarketCalendar::resolve(const QString& exchange) const {
+    QReadLocker lock(&lock_);
+    const ExchangeCalendar* c = find(exchange);
+    return c ? c->code : QString();
+}
+
+ExchangeCalendar MarketCalendar::calendar(const QString& exchange) const {
+    QReadLocker lock(&lock_);
+    const ExchangeCalendar* c = find(exchange);
+    return c ? *c : ExchangeCalendar{};
+}
+
+QVector<QPair<QDateTime, QDateTime>> MarketCalendar::intervals(const ExchangeCalendar& c, const QDate& day) const {
+    const ExchangeCalendar* src = holiday_source(c);
+    if (c.weekend.contains(day.dayOfWeek()) || src->holidays.contains(day))
+        return {};
+    const QTime close = src->early_closes.contains(day) ? src->early_closes.value(day).first : c.close;
+    const QTimeZone tz = c.zone();
+
+    QVector<QPair<QDateTime, QDateTime>> out;
+    QTime start = c.open;
+    for (const auto& [from, to] : c.breaks) {
+        if (from >= close)
+            break;
+        out.append({QDateTime(day, start, tz).toUTC(), QDateTime(day, from, tz).toUTC()});
+        start = to;
+    }
+    if (start < close)
+        out.append({QDateTime(day, start, tz).toUTC(), QDateTime(day, close, tz).toUTC()});
+    return out;
+}
+
+SessionPhase MarketCalendar::phase_of(const ExchangeCalendar& c, const QDateTime& at) const {
+    if (c.always_open)
+        return SessionPhase::Regular;
+    const QTimeZone tz = c.zone();
+    const QDate day = at.toTimeZone(tz).date();
+    const auto ivs = intervals(c, day);
+    if (ivs.isEmpty())
+        return SessionPhase::Closed;
+    for (const auto& [from, to] : ivs)
+        if (at >= from && at < to)
+            return SessionPhase::Regular;
+    if (at >= ivs.first().first && at < ivs.last().second)
+        return SessionPhase::Break;
+    if (c.pre_open.isValid() && at >= QDateTime(day, c.pre_open, tz) && at < ivs.first().first)
+        return SessionPhase::PreMarket;
+    if (c.post_close.isValid() && at >= ivs.last().second && at < QDateTime(day, c.post_close, tz))
+        return SessionPhase::PostMarket;
+    return SessionPhase::Closed;
+}
+
+QDateTime MarketCalendar::next_open_of(const ExchangeCalendar& c, const QDateTime& at) const {
+    if (c.always_open)
+        return {};
+    const QDate today = at.toTimeZone(c.zone()).date();
+    for (int i = 0; i <= kMarketSearchDays; ++i)
+        for (const auto& iv : intervals(c, today.addDays(i)))
+            if (iv.first > at)
+                return iv.first;
+    return {};
+}
+
+QDateTime MarketCalendar::next_close_of(const ExchangeCalendar& c, const QDateTime& at) const {
+    if (c.always_open)
+        return {};
+    const QDate today = at.toTimeZone(c.zone()).date();
+    for (int i = 0; i <= kMarketSearchDays; ++i)
+        for (const auto& iv : intervals(c, today.addDays(i)))
+            if (iv.second > at)
+                return iv.second;
+    return {};
+}
+
+bool MarketCalendar::is_market_open(const QString& exchange, const QDateTime& at) const {
+    return phase(exchange, at) == SessionPhase::Regular;
+}
+
+SessionPhase MarketCalendar::phase(const QString& exchange, const QDateTime& at) const {
+    QReadLocker lock(&lock_);
+    const ExchangeCalendar* c = find(exchange);
+    return c ? phase_of(*c, at) : SessionPhase::Regular;
+}
+
+QDateTime MarketCalendar::next_open(const QString& exchange, const QDateTime& at) const {
+    QReadLocker lock(&lock_);
+    const ExchangeCalendar* c = find(exchange);
+    return c ? next_open_of(*c, at) : QDateTime();
+}
+
+QDateTime MarketCalendar::next_close(const QString& exchange, const QDateTime& at) const {
+    QReadLocker lock(&lock_);
+    const ExchangeCalendar* c = find(exchange);
+    return c ? next_close_of(*c, at) : QDateTime();
+}
+
+MarketStatus MarketCalendar::status(const QString& exchange, const QDateTime& at) const {
+    MarketStatus s;
+    QReadLocker lock(&lock_);
+    const ExchangeCalendar* c = find(exchange);
+    if (!c) {
+        s.exchange = exchange.trimmed().toUpper();
+        s.name = QStringLiteral("Unknown exchange");
+        s.phase = SessionPhase::Regular;
+        s.open = true;
+        s.always_open = true;
+        s.holidays_known = false;
+        s.local_time = at.toUTC();
+        return s;
+    }
+    const ExchangeCalendar* src = holiday_source(*c);
+    s.exchange = c->code;
+    s.name = c->name;
+    s.always_open = c->always_open;
+    s.local_time = at.toTimeZone(c->zone());
+    s.phase = phase_of(*c, at);
+    s.open = s.phase == SessionPhase::Regular;
+    s.next_open = next_open_of(*c, at);
+    s.next_close = next_close_of(*c, at);
+    const QDate day = s.local_time.date();
+    if (!c->always_open) {
+        s.holiday = src->holidays.value(day);
+        if (src->early_closes.contains(day))
+            s.early_close = src->early_closes.value(day).first;
+        s.holidays_known = src->holiday_years.contains(day.year());
+    }
+    return s;
+}
+
+bool MarketCalendar::is_trading_day(const QString& exchange, const QDate& date) const {
+    QReadLocker lock(&lock_);
+    const ExchangeCalendar* c = find(exchange);
+    if (!c || c->always_open)
+        return true;
+    return !c->weekend.contains(date.dayOfWeek()) && !holiday_source(*c)->holidays.contains(date);
+}
+
+QString MarketCalendar::holiday(const QString& exchange, const QDate& date) const {
+    QReadLocker lock(&lock_);
+    const ExchangeCalendar* c = find(exchange);
+    return c ? holiday_source(*c)->holidays.value(date) : QString();
+}
+
+QVector<QJsonObject> MarketCalendar::holidays(const QString& exchange, const QDate& from, const QDate& to) const {
+    QReadLocker lock(&lock_);
+    const ExchangeCalendar* c = find(exchange);
+    if (!c)
+        return {};
+    const ExchangeCalendar* src = holiday_source(*c);
+    QVector<QPair<QDate, QJsonObject>> rows;
+    for (auto it = src->holidays.cbegin(); it != src->holidays.cend(); ++it)
+        if (it.key() >= from && it.key() <= to)
+            rows.append({it.key(), QJsonObject{{"date", it.key().toString(Qt::ISODate)},
+                                               {"name", it.value()},
+                                               {"type", "closed"}}});
+    for (auto it = src->early_closes.cbegin(); it != src->early_closes.cend(); ++it)
+        if (it.key() >= from && it.key() <= to)
+            rows.append({it.key(), QJsonObject{{"date", it.key().toString(Qt::ISODate)},
+                                               {"name", it.value().second},
+                                               {"type", "early_close"},
+                                               {"close", it.value().first.toString("HH:mm")}}});
+    std::sort(rows.begin(), rows.end(), [](const auto& a, const auto& b) { return a.first < b.first; });
+    QVector<QJsonObject> out;
+    for (const auto& r : rows)
+        out.append(r.second);
+    return out;
+}
+
+Result<void> MarketCalendar::add_holiday(const QString& exchange, const QDate& date, const QString& name,
+                                         const QTime& early_close) {
+    QString code = resolve(exchange);
+    if (code.isEmpty())
+        return Result<void>::err("Unknown exchange: " + exchange.toStdString());
+    if (!date.isValid())
+        return Result<void>::err("Invalid date");
+    {
+        // Holidays of a borrowing calendar (CDS) are recorded on its source.
+        QReadLocker lock(&lock_);
+        code = holiday_source(*find(code))->code;
+    }
+
+    const QString path = market_user_path(code);
+    QJsonObject root;
+    {
+        QFile f(path);
+        if (f.open(QIODevice::ReadOnly))
+            root = QJsonDocument::fromJson(f.readAll()).object();
+    }
+    root["exchange"] = code;
+    const QString key = early_close.isValid() ? QStringLiteral("early_closes") : QStringLiteral("holidays");
+    const QString iso = date.toString(Qt::ISODate);
+    QJsonArray rows;
+    for (const auto& v : root.value(key).toArray())
+        if (v.toObject().value("date").toString() != iso)
+            rows.append(v);
+    QJsonObject entry{{"date", iso}, {"name", name}};
+    if (early_close.isValid())
+        entry["close"] = early_close.toString("HH:mm");
+    rows.append(entry);
+    root[key] = rows;
+
+    QDir().mkpath(QFileInfo(path).absolutePath());
+    QSaveFile out(path);
+    if (!out.open(QIODevice::WriteOnly))
+        return Result<void>::err("Cannot write " + path.toStdString());
+    out.write(QJsonDocument(root).toJson(QJsonDocument::Indented));
+    if (!out.commit())
+        return Result<void>::err("Cannot write " + path.toStdString());
+    reload();
+    return Result<void>::ok();
+}
+
+} // namespace fincept::core::market
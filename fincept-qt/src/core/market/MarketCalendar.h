@@ -0,0 +1,127 @@
+#pragma once
+// MarketCalendar — exchange trading sessions and holidays.
+//
+// Session hours live in a built-in table (MarketCalendar.cpp); holidays and
+// early closes come from JSON files: the bundled ones embedded at
+// :/market_calendars/<code>.json, then user additions under
+// AppPaths::data()/market_calendars/<code>.json, merged on top. A date in a
+// year no file covers trades on its weekday schedule and reports
+// holidays_known = false.
+//
+// Exchange names resolve through aliases ("NASDAQ" → NYSE, "NFO" → NSE,
+// "BINANCE" → CRYPTO). Unknown exchanges read as always open so a calendar
+// gap never blocks a caller — check knows() where that matters.
+//
+// Thread-safe: the tables are read under a lock and only replaced by reload()
+// and add_holiday().
+
+#include "core/result/Result.h"
+
+#include <QDate>
+#include <QDateTime>
+#include <QHash>
+#include <QJsonObject>
+#include <QReadWriteLock>
+#include <QStringList>
+#include <QTime>
+#include <QTimeZone>
+#include <QVector>
+
+namespace fincept::core::market {
+
+enum class SessionPhase { Closed, PreMarket, Regular, Break, PostMarket };
+
+QString session_phase_name(SessionPhase p);
+
+/// One exchange's schedule. Times are exchange-local.
+struct ExchangeCalendar {
+    QString code; // NYSE, NSE, LSE, ..., CRYPTO
+    QString name;
+    QString tz_id;               // IANA zone
+    int fallback_offset_min = 0; // standard UTC offset when the zone database lacks tz_id
+    bool always_open = false;    // 24/7
+    QTime pre_open;              // invalid = no pre-market
+    QTime open;
+    QTime close;
+    QTime post_close;                    // invalid = no post-market
+    QVector<QPair<QTime, QTime>> breaks; // lunch breaks inside the regular session
+    QVector<int> weekend{6, 7};          // ISO days of week without trading
+    QString holidays_from;               // borrow another calendar's holidays (CDS → NSE)
+    QHash<QDate, QString> holidays;
+    QHash<QDate, QPair<QTime, QString>> early_closes; // date → (close, reason)
+    QVector<int> holiday_years;                       // years the holiday files cover
+
+    QTimeZone zone() const;
+};
+
+struct MarketStatus {
+    QString exchange; // resolved calendar code
+    QString name;
+    SessionPhase phase = SessionPhase::Closed;
+    bool open = false; // regular session
+    QDateTime local_time;
+    QDateTime next_open;  // UTC; invalid for 24/7 markets
+    QDateTime next_close; // UTC; invalid for 24/7 markets
+    QString holiday;      // today's holiday name, if any
+    QTime early_close;    // today's shortened close, if any
+    bool holidays_known = true;
+    bool always_open = false;
+
+    QJsonObject to_json() const;
+};
+
+class MarketCalendar {
+  public:
+    static MarketCalendar& instance();
+
+    /// Re-reads the bundled and user holiday files.
+    void reload();
+
+    /// Calendar codes, in table order.
+    QStringList exchanges() const;
+    /// Calendar code for an exchange name or alias; empty when unknown.
+    QString resolve(const QString& exchange) const;
+    bool knows(const QString& exchange) const { return !resolve(exchange).isEmpty(); }
+    ExchangeCalendar calendar(const QString& exchange) const;
+
+    /// True inside the regular session (breaks excluded).
+    bool is_market_open(const QString& exchange, const QDateTime& at = QDateTime::currentDateTimeUtc()) const;
+    SessionPhase phase(const QString& exchange, const QDateTime& at = QDateTime::currentDateTimeUtc()) const;
+    /// Start of the next regular-session interval after `at` (UTC); invalid
+    /// for 24/7 and unknown exchanges.
+    QDateTime next_open(const QString& exchange, const QDateTime& at = QDateTime::currentDateTimeUtc()) const;
+    /// End of the current regular-session interval, or of the next one when
+    /// closed (UTC); invalid for 24/7 and unknown exchanges.
+    QDateTime next_close(const QString& exchange, const QDateTime& at = QDateTime::currentDateTimeUtc()) const;
+    MarketStatus status(const QString& exchange, const QDateTime& at = QDateTime::currentDateTimeUtc()) const;
+
+    /// A weekday that is not a holiday, in the exchange's local calendar.
+    bool is_trading_day(const QString& exchange, const QDate& date) const;
+    /// Holiday name for `date`, or empty.
+    QString holiday(const QString& exchange, const QDate& date) const;
+    /// Holidays and early closes in [from, to], by date.
+    QVector<QJsonObject> holidays(const QString& exchange, const QDate& from, const QDate& to) const;
+
+    /// Records a closure (`early_close` invalid) or a shortened session in
+    /// the user file for the exchange, then reloads.
+    Result<void> add_holiday(const QString& exchange, const QDate& date, const QString& name,
+                             const QTime& early_close = {});
+
+  private:
+    MarketCalendar();
+    MarketCalendar(const MarketCalendar&) = delete;
+    MarketCalendar& operator=(const MarketCalendar&) = delete;
+
+    const ExchangeCalendar* find(const QString& exchange) const; // caller holds lock_
+    const ExchangeCalendar* holiday_source(const ExchangeCalendar& c) const;
+    QVector<QPair<QDateTime, QDateTime>> intervals(const ExchangeCalendar& c, const QDate& local_day) const;
+    SessionPhase phase_of(const ExchangeCalendar& c, const QDateTime& at) const;
+    QDateTime next_open_of(const ExchangeCalendar& c, const QDateTime& at) const;
+    QDateTime next_close_of(const ExchangeCalendar& c, const QDateTime& at) const;
+
+    mutable QReadWriteLock lock_;
+    QVector<ExchangeCalendar> calendars_;
+    QHash<QString, int> aliases_; // upper-case name → index into calendars_
+};
+
+} // namespace fincept::core::market
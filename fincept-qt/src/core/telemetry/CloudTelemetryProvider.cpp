@@ -2,6 +2,7 @@
 
 #include "core/logging/Logger.h"
 #include "storage/repositories/SettingsRepository.h"
+#include "storage/secure/SecureStorage.h"
 
 #include <QDateTime>
 #include <QJsonDocument>
@@ -154,8 +155,15 @@ bool CloudTelemetryProvider::read_config(QString& endpoint_out, QString& api_key
     if (!endpoint_out.startsWith("http://") && !endpoint_out.startsWith("https://"))
         return false; // bad config; refuse rather than silently use raw text
 
-    auto key_r = SettingsRepository::instance().get("telemetry.cloud_api_key");
-    api_key_out = key_r.is_ok() ? key_r.value().trimmed() : QString{};
+    // The key lives in SecureStorage; a plaintext one set before the vault
+    // existed is moved across on first use.
+    auto& settings = SettingsRepository::instance();
+    auto plain_r = settings.get("telemetry.cloud_api_key");
+    const QString plaintext = plain_r.is_ok() ? plain_r.value().trimmed() : QString{};
+    auto key_r = SecureStorage::instance().adopt("telemetry.cloud_api_key", plaintext);
+    if (key_r.is_ok() && !plaintext.isEmpty())
+        settings.remove("telemetry.cloud_api_key");
+    api_key_out = key_r.is_ok() ? key_r.value().trimmed() : plaintext;
     return true;
 }
 
@@ -22,9 +22,11 @@ namespace fincept::telemetry {
 /// (in workspace.db) regardless of network state, and additionally
 /// batches them for HTTPS POST to a user-configured endpoint.
 ///
-/// **Endpoint config** — read from `SettingsRepository`:
-///   - `telemetry.cloud_endpoint` — full URL (empty = uploader idle)
-///   - `telemetry.cloud_api_key`  — sent as `Authorization: Bearer <key>`
+/// **Endpoint config**:
+///   - `telemetry.cloud_endpoint` — full URL (empty = uploader idle),
+///     from `SettingsRepository`
+///   - `telemetry.cloud_api_key`  — sent as `Authorization: Bearer <key>`,
+///     from `SecureStorage`
 ///
 /// **Privacy contract** (decision 10.4): caller is responsible for
 /// what's in payloads. The provider does NOT inspect payloads beyond
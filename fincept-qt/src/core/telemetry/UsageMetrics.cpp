@@ -0,0 +1,384 @@
+#include "core/telemetry/UsageMetrics.h"
+
+#include "core/logging/Logger.h"
+#include "network/websocket/WebSocketMonitor.h"
+#include "storage/repositories/SettingsRepository.h"
+#include "storage/sqlite/Database.h"
+
+#include <QCoreApplication>
+#include <QDateTime>
+#include <QJsonArray>
+#include <QMutexLocker>
+#include <QSqlError>
+#include <QSqlQuery>
+#include <QTimer>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::telemetry {
+
+namespace {
+
+constexpr const char* kUmTag = "UsageMetrics";
+constexpr const char* kUmEnabledKey = "metrics.enabled";
+constexpr qint64 kUmHourMs = 3600LL * 1000;
+constexpr int kUmMaxRows = 50;
+
+/// Topic prefixes whose stream name runs past the usual two segments (or
+/// stops short of them), with how many leading segments name the family.
+/// Anything else keeps two. See UsageMetrics::topic_family.
+struct UmFamilyDepth {
+    const char* prefix;
+    int segments;
+};
+constexpr UmFamilyDepth kUmFamilyDepths[] = {
+    {"ws:", 3},                 // ws:<exchange>:<stream>:<pair>[:<interval>]
+    {"prediction:", 3},         // prediction:<venue>:<stream>:<market>
+    {"market:price:token:", 3}, // market:price:token:<mint>
+    {"geopolitics:hdx:", 3},    // geopolitics:hdx:<kind>[:<country or query>]
+    {"ma:", 1},                 // ma:<context>
+};
+
+double um_round(double v) {
+    return std::round(v * 10.0) / 10.0;
+}
+
+QString um_bytes(double bytes) {
+    if (bytes >= 1024.0 * 1024.0)
+        return QString::number(bytes / (1024.0 * 1024.0), 'f', 1) + " MB";
+    if (bytes >= 1024.0)
+        return QString::number(bytes / 1024.0, 'f', 1) + " KB";
+    return QString::number(qint64(bytes)) + " B";
+}
+
+} // namespace
+
+UsageMetrics& UsageMetrics::instance() {
+    static UsageMetrics s;
+    return s;
+}
+
+QString UsageMetrics::kind_name(Kind kind) {
+    switch (kind) {
+        case Kind::Action:
+            return QStringLiteral("action");
+        case Kind::Tool:
+            return QStringLiteral("tool");
+        case Kind::DataSource:
+            return QStringLiteral("datasource");
+        case Kind::Cache:
+            return QStringLiteral("cache");
+        case Kind::WebSocket:
+            return QStringLiteral("websocket");
+    }
+    return QStringLiteral("action");
+}
+
+QString UsageMetrics::topic_family(const QString& topic) {
+    const QStringList parts = topic.split(':');
+    if (parts.size() < 2)
+        return topic;
+    int segments = 2;
+    for (const auto& d : kUmFamilyDepths) {
+        if (topic.startsWith(QLatin1String(d.prefix))) {
+            segments = d.segments;
+            break;
+        }
+    }
+    // The last segment always goes: it is the symbol, id, context or search
+    // text, even in a two-segment topic like "ma:<context>".
+    segments = std::min(segments, int(parts.size()) - 1);
+    return parts.mid(0, segments).join(':');
+}
+
+void UsageMetrics::start() {
+    if (flush_timer_)
+        return;
+    const auto r = SettingsRepository::instance().get(kUmEnabledKey);
+    enabled_.store(r.is_ok() && r.value() == QLatin1String("1"), std::memory_order_relaxed);
+
+    flush_timer_ = new QTimer(this);
+    flush_timer_->setInterval(kFlushIntervalMs);
+    connect(flush_timer_, &QTimer::timeout, this, &UsageMetrics::flush);
+    flush_timer_->start();
+    // Direct: the buffered minute must land before the database closes.
+    connect(qApp, &QCoreApplication::aboutToQuit, this, &UsageMetrics::flush, Qt::DirectConnection);
+    LOG_INFO(kUmTag, enabled() ? "Local usage metrics on" : "Local usage metrics off (opt-in)");
+}
+
+Result<void> UsageMetrics::set_enabled(bool on) {
+    auto r = SettingsRepository::instance().set(kUmEnabledKey, on ? "1" : "0", "metrics");
+    if (r.is_err())
+        return r;
+    if (!on)
+        flush();
+    enabled_.store(on, std::memory_order_relaxed);
+    if (on) {
+        // Start websocket deltas from now, not from each connection's lifetime totals.
+        {
+            QMutexLocker lock(&mutex_);
+            ws_seen_.clear();
+        }
+        sample_websockets();
+    }
+    LOG_INFO(kUmTag, on ? "Local usage metrics enabled" : "Local usage metrics disabled");
+    return Result<void>::ok();
+}
+
+void UsageMetrics::record_latency(Kind kind, const QString& name, qint64 ms, bool ok) {
+    if (!enabled())
+        return;
+    QMutexLocker lock(&mutex_);
+    auto& s = pending_[{int(kind), name}];
+    s.count += 1;
+    s.errors += ok ? 0 : 1;
+    s.slow += ms > kSlowMs ? 1 : 0;
+    s.total_ms += double(ms);
+    s.max_ms = std::max(s.max_ms, double(ms));
+}
+
+void UsageMetrics::record_cache(const QString& name, bool hit) {
+    if (!enabled())
+        return;
+    QMutexLocker lock(&mutex_);
+    auto& s = pending_[{int(Kind::Cache), name}];
+    s.count += 1;
+    s.hits += hit ? 1 : 0;
+}
+
+// Websocket clients already count everything (WebSocketMonitor); record the
+// growth since the last flush instead of hooking every frame.
+void UsageMetrics::sample_websockets() {
+    if (!enabled())
+        return;
+    const QJsonArray live = WebSocketMonitor::instance().snapshot();
+    QMutexLocker lock(&mutex_);
+    QHash<int, WsSeen> seen;
+    for (const auto& v : live) {
+        const QJsonObject c = v.toObject();
+        const int id = c.value("id").toInt();
+        const WsSeen now{c.value("messages_in").toDouble(), c.value("bytes_in").toDouble(),
+                         c.value("dropped").toDouble()};
+        const WsSeen before = ws_seen_.value(id, now); // first sighting: baseline only
+        seen.insert(id, now);
+        const double messages = std::max(0.0, now.messages - before.messages);
+        if (messages <= 0)
+            continue;
+        auto& s = pending_[{int(Kind::WebSocket), c.value("label").toString()}];
+        s.count += qint64(messages);
+        s.bytes += qint64(std::max(0.0, now.bytes - before.bytes));
+        s.errors += qint64(std::max(0.0, now.dropped - before.dropped));
+    }
+    ws_seen_ = seen;
+}
+
+void UsageMetrics::flush() {
+    sample_websockets();
+    QHash<QPair<int, QString>, Sample> batch;
+    {
+        QMutexLocker lock(&mutex_);
+        batch.swap(pending_);
+    }
+    if (batch.isEmpty())
+        return;
+
+    auto db = Database::instance().connection();
+    if (!db.isOpen())
+        return;
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    const qint64 bucket = now - now % kUmHourMs;
+    db.transaction();
+    QSqlQuery q(db);
+    q.prepare("INSERT INTO usage_metrics (bucket_ms, kind, name, count, errors, slow, hits, total_ms, max_ms, bytes) "
+              "VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?) "
+              "ON CONFLICT(bucket_ms, kind, name) DO UPDATE SET "
+              "count = count + excluded.count, errors = errors + excluded.errors, slow = slow + excluded.slow, "
+              "hits = hits + excluded.hits, total_ms = total_ms + excluded.total_ms, "
+              "max_ms = MAX(max_ms, excluded.max_ms), bytes = bytes + excluded.bytes");
+    for (auto it = batch.cbegin(); it != batch.cend(); ++it) {
+        const Sample& s = it.value();
+        q.addBindValue(bucket);
+        q.addBindValue(kind_name(Kind(it.key().first)));
+        q.addBindValue(it.key().second);
+        q.addBindValue(s.count);
+        q.addBindValue(s.errors);
+        q.addBindValue(s.slow);
+        q.addBindValue(s.hits);
+        q.addBindValue(s.total_ms);
+        q.addBindValue(s.max_ms);
+        q.addBindValue(s.bytes);
+        if (!q.exec()) {
+            LOG_WARN(kUmTag, "Flush failed: " + q.lastError().text());
+            db.rollback();
+            return;
+        }
+    }
+    QSqlQuery prune(db);
+    prune.prepare("DELETE FROM usage_metrics WHERE bucket_ms < ?");
+    prune.addBindValue(now - qint64(kRetentionDays) * 24 * kUmHourMs);
+    prune.exec();
+    db.commit();
+}
+
+QJsonObject UsageMetrics::summary(int hours, const QString& kind) {
+    flush();
+    hours = std::clamp(hours, 1, kRetentionDays * 24);
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    const qint64 since = now - now % kUmHourMs - qint64(hours - 1) * kUmHourMs;
+    auto db = Database::instance().connection();
+
+    QJsonArray latency;
+    QJsonObject slowest;
+    {
+        QSqlQuery q(db);
+        q.prepare(QString("SELECT kind, name, SUM(count), SUM(errors), SUM(slow), SUM(total_ms), MAX(max_ms) "
+                          "FROM usage_metrics WHERE bucket_ms >= ? AND kind IN ('action', 'tool', 'datasource')%1 "
+                          "GROUP BY kind, name ORDER BY SUM(total_ms) DESC LIMIT %2")
+                      .arg(kind.isEmpty() ? QString() : QStringLiteral(" AND kind = ?"))
+                      .arg(kUmMaxRows));
+        q.addBindValue(since);
+        if (!kind.isEmpty())
+            q.addBindValue(kind);
+        if (q.exec()) {
+            while (q.next()) {
+                const qint64 count = q.value(2).toLongLong();
+                const double total = q.value(5).toDouble();
+                const QJsonObject row{{"kind", q.value(0).toString()},
+                                      {"name", q.value(1).toString()},
+                                      {"count", count},
+                                      {"errors", q.value(3).toLongLong()},
+                                      {"error_rate", count > 0 ? um_round(100.0 * q.value(3).toDouble() / count) : 0.0},
+                                      {"slow", q.value(4).toLongLong()},
+                                      {"avg_ms", count > 0 ? um_round(total / count) : 0.0},
+                                      {"max_ms", um_round(q.value(6).toDouble())},
+                                      {"total_ms", um_round(total)}};
+                latency.append(row);
+                // A source needs a few samples before it can be called the slow one.
+                if (row["kind"].toString() == QLatin1String("datasource") && count >= 3 &&
+                    row["avg_ms"].toDouble() > slowest.value("avg_ms").toDouble())
+                    slowest = row;
+            }
+        }
+    }
+
+    QJsonArray cache;
+    {
+        QSqlQuery q(db);
+        q.prepare(QString("SELECT name, SUM(count), SUM(hits) FROM usage_metrics WHERE bucket_ms >= ? AND kind = "
+                          "'cache' GROUP BY name ORDER BY SUM(count) DESC LIMIT %1")
+                      .arg(kUmMaxRows));
+        q.addBindValue(since);
+        if (q.exec()) {
+            while (q.next()) {
+                const qint64 lookups = q.value(1).toLongLong();
+                cache.append(QJsonObject{{"name", q.value(0).toString()},
+                                         {"lookups", lookups},
+                                         {"hits", q.value(2).toLongLong()},
+                                         {"hit_rate", lookups > 0 ? um_round(100.0 * q.value(2).toDouble() / lookups)
+                                                                  : 0.0}});
+            }
+        }
+    }
+
+    QJsonArray websocket;
+    {
+        QSqlQuery q(db);
+        q.prepare(QString("SELECT name, SUM(count), SUM(bytes), SUM(errors), MIN(bucket_ms) FROM usage_metrics "
+                          "WHERE bucket_ms >= ? AND kind = 'websocket' GROUP BY name ORDER BY SUM(bytes) DESC "
+                          "LIMIT %1")
+                      .arg(kUmMaxRows));
+        q.addBindValue(since);
+        if (q.exec()) {
+            while (q.next()) {
+                const double span_sec = std::max(1.0, double(now - q.value(4).toLongLong()) / 1000.0);
+                websocket.append(QJsonObject{{"name", q.value(0).toString()},
+                                             {"messages", q.value(1).toLongLong()},
+                                             {"bytes", q.value(2).toLongLong()},
+                                             {"dropped", q.value(3).toLongLong()},
+                                             {"msgs_per_sec", um_round(q.value(1).toDouble() / span_sec)}});
+            }
+        }
+    }
+
+    QJsonObject out{{"enabled", enabled()},
+                    {"hours", hours},
+                    {"since", QDateTime::fromMSecsSinceEpoch(since, Qt::UTC).toString(Qt::ISODate)},
+                    {"latency", latency},
+                    {"cache", cache},
+                    {"websocket", websocket}};
+    if (!slowest.isEmpty())
+        out["slowest_source"] = slowest;
+    return out;
+}
+
+QString UsageMetrics::summary_text(const QJsonObject& summary) {
+    QStringList lines;
+    lines << QString("## Usage metrics — last %1 h (local only)").arg(summary.value("hours").toInt());
+    if (!summary.value("enabled").toBool())
+        lines << "" << "_Recording is off — enable it to collect new samples._";
+    if (summary.contains("slowest_source")) {
+        const QJsonObject s = summary.value("slowest_source").toObject();
+        lines << "" << QString("**Slowest data source:** %1 — avg %2 ms, max %3 ms over %4 refreshes")
+                          .arg(s.value("name").toString())
+                          .arg(s.value("avg_ms").toDouble())
+                          .arg(s.value("max_ms").toDouble())
+                          .arg(s.value("count").toInt());
+    }
+
+    lines << "" << "### Latency (by total time)";
+    const QJsonArray latency = summary.value("latency").toArray();
+    if (latency.isEmpty())
+        lines << "- none recorded";
+    for (const auto& v : latency) {
+        const QJsonObject r = v.toObject();
+        lines << QString("- [%1] %2: %3× avg %4 ms, max %5 ms, %6 slow, %7% errors")
+                     .arg(r.value("kind").toString(), r.value("name").toString())
+                     .arg(r.value("count").toInt())
+                     .arg(r.value("avg_ms").toDouble())
+                     .arg(r.value("max_ms").toDouble())
+                     .arg(r.value("slow").toInt())
+                     .arg(r.value("error_rate").toDouble());
+    }
+
+    lines << "" << "### Cache hit rate";
+    const QJsonArray cache = summary.value("cache").toArray();
+    if (cache.isEmpty())
+        lines << "- none recorded";
+    for (const auto& v : cache) {
+        const QJsonObject r = v.toObject();
+        lines << QString("- %1: %2% of %3 lookups")
+                     .arg(r.value("name").toString())
+                     .arg(r.value("hit_rate").toDouble())
+                     .arg(r.value("lookups").toInt());
+    }
+
+    lines << "" << "### Websocket throughput";
+    const QJsonArray websocket = summary.value("websocket").toArray();
+    if (websocket.isEmpty())
+        lines << "- none recorded";
+    for (const auto& v : websocket) {
+        const QJsonObject r = v.toObject();
+        lines << QString("- %1: %2 msgs (%3/s), %4, %5 dropped")
+                     .arg(r.value("name").toString())
+                     .arg(r.value("messages").toInt())
+                     .arg(r.value("msgs_per_sec").toDouble())
+                     .arg(um_bytes(r.value("bytes").toDouble()))
+                     .arg(r.value("dropped").toInt());
+    }
+    return lines.join('\n');
+}
+
+Result<void> UsageMetrics::reset() {
+    {
+        QMutexLocker lock(&mutex_);
+        pending_.clear();
+    }
+    QSqlQuery q(Database::instance().connection());
+    if (!q.exec("DELETE FROM usage_metrics"))
+        return Result<void>::err(q.lastError().text().toStdString());
+    LOG_INFO(kUmTag, "Usage metrics cleared");
+    return Result<void>::ok();
+}
+
+} // namespace fincept::telemetry
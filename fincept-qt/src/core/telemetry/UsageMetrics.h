@@ -0,0 +1,107 @@
+#pragma once
+#include "core/result/Result.h"
+
+#include <QHash>
+#include <QJsonObject>
+#include <QMutex>
+#include <QObject>
+#include <QString>
+
+#include <atomic>
+
+class QTimer;
+
+namespace fincept::telemetry {
+
+/// Opt-in local usage metrics and profiler. Answers "what is making my
+/// workspace slow" from the user's own machine: command (action) and MCP
+/// tool latencies, DataHub refresh latency per data source, cache hit rates
+/// (DataHub freshness on subscribe, the SQLite cache) and websocket
+/// throughput per feed.
+///
+/// Nothing is uploaded and this is not a TelemetryProvider — aggregates go
+/// to the `usage_metrics` table (v078) in hourly buckets and stay there,
+/// pruned after kRetentionDays. Names are action / tool ids, topic families
+/// ("market:quote"), cache key prefixes and websocket labels — never symbols,
+/// arguments or account ids.
+///
+/// Off by default (setting `metrics.enabled`). When off every record_*() is
+/// one relaxed atomic load, so the hooks stay in hot paths unconditionally.
+///
+/// Threading: record_*() from any thread (samples buffer under a mutex);
+/// the flush timer runs on the thread that called start() — the UI thread.
+class UsageMetrics : public QObject {
+    Q_OBJECT
+  public:
+    enum class Kind { Action, Tool, DataSource, Cache, WebSocket };
+
+    static UsageMetrics& instance();
+    static QString kind_name(Kind kind);
+
+    static constexpr int kFlushIntervalMs = 60 * 1000;
+    static constexpr int kSlowMs = 1000; ///< latency samples above this count as "slow"
+    static constexpr int kRetentionDays = 30;
+
+    /// Reads `metrics.enabled` and starts the flush timer. Call once, after
+    /// the database is open.
+    void start();
+
+    bool enabled() const { return enabled_.load(std::memory_order_relaxed); }
+    /// Persists `metrics.enabled`. Disabling flushes what is buffered.
+    Result<void> set_enabled(bool on);
+
+    void record_latency(Kind kind, const QString& name, qint64 ms, bool ok = true);
+    void record_cache(const QString& name, bool hit);
+
+    /// DataHub topic → its family, with the symbol / id / user text dropped:
+    /// "market:quote:AAPL" → "market:quote", "ws:kraken:ticker:BTC/USD" →
+    /// "ws:kraken:ticker", "ma:<context>" → "ma". The last segment is never
+    /// kept, so no user data reaches the metrics table.
+    static QString topic_family(const QString& topic);
+
+    /// Writes buffered samples (and websocket deltas) to SQLite.
+    void flush();
+
+    /// Aggregates over the last `hours`, each section sorted worst first:
+    /// {enabled, hours, since, latency: [{kind, name, count, errors,
+    /// error_rate, slow, avg_ms, max_ms, total_ms}], cache: [{name, lookups,
+    /// hits, hit_rate}], websocket: [{name, messages, bytes, dropped,
+    /// msgs_per_sec}], slowest_source}. `kind` narrows the latency section.
+    QJsonObject summary(int hours = 24, const QString& kind = {});
+    /// summary() as Markdown, for the command palette.
+    static QString summary_text(const QJsonObject& summary);
+
+    /// Deletes every stored and buffered sample.
+    Result<void> reset();
+
+  private:
+    UsageMetrics() = default;
+    Q_DISABLE_COPY(UsageMetrics)
+
+    struct Sample {
+        qint64 count = 0;
+        qint64 errors = 0;
+        qint64 slow = 0;
+        qint64 hits = 0;
+        qint64 bytes = 0;
+        double total_ms = 0;
+        double max_ms = 0;
+    };
+
+    /// Websocket counters as of the last flush, per connection id.
+    struct WsSeen {
+        double messages = 0;
+        double bytes = 0;
+        double dropped = 0;
+    };
+
+    void sample_websockets();
+
+    std::atomic<bool> enabled_{false};
+    QMutex mutex_;
+    QHash<QPair<int, QString>, Sample> pending_; ///< (kind, name) → samples since the last flush
+    QHash<int, WsSeen> ws_seen_;
+    QTimer* flush_timer_ = nullptr;
+};
+
+} // namespace fincept::telemetry
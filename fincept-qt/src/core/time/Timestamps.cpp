@@ -0,0 +1,147 @@
+#include "core/time/Timestamps.h"
+
+#include "core/market/MarketCalendar.h"
+
+#include <QRegularExpression>
+
+namespace fincept::core::time {
+
+namespace {
+
+// Magnitude thresholds: 1e11 s is the year 5138 while 1e11 ms is 1973, so
+// anything below is seconds; likewise for the finer units.
+constexpr qint64 kTsSecondsLimit = 100000000000LL;      // 1e11
+constexpr qint64 kTsMillisLimit = 100000000000000LL;    // 1e14
+constexpr qint64 kTsMicrosLimit = 100000000000000000LL; // 1e17
+
+QDate ts_parse_date(const QString& s) {
+    static const char* kFormats[] = {"yyyy-MM-dd", "dd-MM-yyyy", "dd/MM/yyyy", "yyyy/MM/dd", "dd-MMM-yyyy",
+                                     "dd MMM yyyy", "yyyyMMdd"};
+    for (const char* fmt : kFormats) {
+        const QDate d = QDate::fromString(s, QString::fromLatin1(fmt));
+        if (d.isValid())
+            return d;
+    }
+    return {};
+}
+
+// "HH:mm[:ss[.fff...]]"; fractions beyond milliseconds are truncated.
+QTime ts_parse_time(QString s) {
+    if (const int dot = s.indexOf('.'); dot >= 0)
+        s = s.left(dot + 4).leftJustified(dot + 4, '0');
+    for (const char* fmt : {"HH:mm:ss.zzz", "HH:mm:ss", "HH:mm"}) {
+        const QTime t = QTime::fromString(s, QString::fromLatin1(fmt));
+        if (t.isValid())
+            return t;
+    }
+    return {};
+}
+
+} // namespace
+
+qint64 epoch_to_ms(qint64 v) {
+    if (v <= 0)
+        return v;
+    if (v < kTsSecondsLimit)
+        return v * 1000;
+    if (v < kTsMillisLimit)
+        return v;
+    if (v < kTsMicrosLimit)
+        return v / 1000;
+    return v / 1000000;
+}
+
+QTimeZone exchange_zone(const QString& exchange) {
+    auto& cal = market::MarketCalendar::instance();
+    if (!cal.knows(exchange))
+        return QTimeZone::utc();
+    const auto c = cal.calendar(exchange);
+    return c.always_open ? QTimeZone::utc() : c.zone();
+}
+
+QString exchange_tz_id(const QString& exchange) {
+    return QString::fromUtf8(exchange_zone(exchange).id());
+}
+
+qint64 wall_clock_ms(const QDate& date, const QTime& time, const QTimeZone& zone) {
+    if (!date.isValid())
+        return 0;
+    return QDateTime(date, time.isValid() ? time : QTime(0, 0), zone).toMSecsSinceEpoch();
+}
+
+qint64 to_utc_ms(const QString& raw, const QTimeZone& naive_zone) {
+    QString s = raw.trimmed();
+    if (s.isEmpty())
+        return 0;
+
+    // .NET JSON dates: "/Date(1690000000000)/" or "/Date(1690000000000+0530)/";
+    // the number is UTC epoch ms and the offset only a display hint.
+    static const QRegularExpression dotnet(QStringLiteral("^/Date\\((-?\\d+)"));
+    if (const auto m = dotnet.match(s); m.hasMatch())
+        return m.captured(1).toLongLong();
+
+    bool numeric = false;
+    const qint64 n = s.toLongLong(&numeric);
+    if (numeric && s.size() != 8) // 8 digits is yyyyMMdd, not a 1970 epoch
+        return epoch_to_ms(n);
+    if (s.contains('.')) { // decimal epoch, e.g. "1690000000.123" seconds
+        const double f = s.toDouble(&numeric);
+        if (numeric)
+            return f < kTsSecondsLimit ? qint64(f * 1000.0) : epoch_to_ms(qint64(f));
+    }
+
+    // Explicit zone: a trailing Z / UTC or ±hh[:]mm after the time.
+    QTimeZone zone = naive_zone;
+    static const QRegularExpression offset(QStringLiteral("(?:\\s*(Z|UTC|GMT)|([+-])(\\d{2}):?(\\d{2}))$"),
+                                           QRegularExpression::CaseInsensitiveOption);
+    if (const auto m = offset.match(s); m.hasMatch() && s.left(m.capturedStart()).contains(':')) {
+        if (!m.captured(1).isEmpty()) {
+            zone = QTimeZone::utc();
+        } else {
+            const int secs = (m.captured(3).toInt() * 60 + m.captured(4).toInt()) * 60;
+            zone = QTimeZone(m.captured(2) == "-" ? -secs : secs);
+        }
+        s = s.left(m.capturedStart()).trimmed();
+    }
+
+    static const QRegularExpression time_sep(QStringLiteral("[T ](?=\\d{1,2}:)"));
+    const int sep = s.indexOf(time_sep);
+    const QDate date = ts_parse_date(sep < 0 ? s : s.left(sep));
+    if (!date.isValid())
+        return 0;
+    QTime time(0, 0);
+    if (sep >= 0) {
+        time = ts_parse_time(s.mid(sep + 1).trimmed());
+        if (!time.isValid())
+            return 0;
+    }
+    return wall_clock_ms(date, time, zone.isValid() ? zone : QTimeZone::utc());
+}
+
+qint64 to_utc_ms(const QJsonValue& raw, const QTimeZone& naive_zone) {
+    if (raw.isDouble())
+        return epoch_to_ms(qint64(raw.toDouble()));
+    return to_utc_ms(raw.toVariant().toString(), naive_zone);
+}
+
+qint64 to_utc_ms(const QString& raw, const QString& exchange) {
+    return to_utc_ms(raw, exchange_zone(exchange));
+}
+
+QDateTime in_exchange_time(qint64 ms, const QString& exchange) {
+    return QDateTime::fromMSecsSinceEpoch(ms, exchange_zone(exchange));
+}
+
+QString iso_utc(qint64 ms) {
+    return ms > 0 ? QDateTime::fromMSecsSinceEpoch(ms, QTimeZone::utc()).toString(Qt::ISODateWithMs) : QString();
+}
+
+QJsonObject describe(qint64 ms, const QString& exchange) {
+    const QTimeZone zone = exchange_zone(exchange);
+    return QJsonObject{{"timestamp_ms", ms},
+                       {"utc", iso_utc(ms)},
+                       {"tz", QString::fromUtf8(zone.id())},
+                       {"exchange_time", QDateTime::fromMSecsSinceEpoch(ms, zone).toString(Qt::ISODate)}};
+}
+
+} // namespace fincept::core::time
@@ -0,0 +1,60 @@
+#pragma once
+// Timestamps — one place to turn provider timestamps into UTC epoch ms.
+//
+// Providers disagree on units (epoch s / ms / µs / ns), on format (ISO-8601
+// with or without an offset, "dd-MM-yyyy HH:mm:ss", .NET "/Date(...)/") and,
+// worst, on zone: many Indian brokers send naive IST wall-clock strings.
+// QDateTime::fromString() reads a naive string as the MACHINE's local time, so
+// the same candle landed at a different instant on every user's clock and
+// charts drifted by the user's UTC offset. Everything ingested goes through
+// here instead: explicit offsets win, naive strings are read in the zone the
+// caller names (normally the exchange's, from MarketCalendar), and the result
+// is always UTC epoch milliseconds — the BrokerCandle / BrokerQuote contract.
+//
+// Stored series keep UTC; the exchange zone travels as metadata (tz id) so
+// views can render exchange-local time without guessing.
+
+#include <QDate>
+#include <QDateTime>
+#include <QJsonObject>
+#include <QJsonValue>
+#include <QString>
+#include <QTime>
+#include <QTimeZone>
+
+namespace fincept::core::time {
+
+/// Epoch value in seconds, ms, µs or ns (told apart by magnitude) → epoch ms.
+/// Zero and negative values pass through unchanged.
+qint64 epoch_to_ms(qint64 v);
+
+/// Zone an exchange's wall-clock timestamps are in ("NSE" → Asia/Kolkata),
+/// from MarketCalendar; UTC for unknown exchanges and 24/7 markets. Copies
+/// the calendar on every call — hoist it out of per-row loops.
+QTimeZone exchange_zone(const QString& exchange);
+/// IANA id of exchange_zone(), e.g. "America/New_York"; "UTC" when unknown.
+QString exchange_tz_id(const QString& exchange);
+
+/// Epoch ms of a wall-clock date and time read in `zone`.
+qint64 wall_clock_ms(const QDate& date, const QTime& time, const QTimeZone& zone);
+
+/// Parses a provider timestamp to UTC epoch ms; 0 when unreadable. Accepts
+/// epoch numbers in any unit, "/Date(ms)/", ISO-8601 with Z or ±hh[:]mm, and
+/// naive date / date-time strings (yyyy-MM-dd, dd-MM-yyyy, dd/MM/yyyy,
+/// dd-MMM-yyyy, yyyyMMdd; T or space separator; fractional seconds), which
+/// are read in `naive_zone`.
+qint64 to_utc_ms(const QString& raw, const QTimeZone& naive_zone);
+/// to_utc_ms() for a JSON field that may hold a number or a string.
+qint64 to_utc_ms(const QJsonValue& raw, const QTimeZone& naive_zone);
+/// to_utc_ms() with naive strings read in the exchange's zone (one-off
+/// parses; loops should pass a hoisted exchange_zone()).
+qint64 to_utc_ms(const QString& raw, const QString& exchange);
+
+/// `ms` as the exchange's local date-time.
+QDateTime in_exchange_time(qint64 ms, const QString& exchange);
+/// ISO-8601 UTC ("2025-03-03T03:45:00.000Z"); empty for ms <= 0.
+QString iso_utc(qint64 ms);
+/// {timestamp_ms, utc, tz, exchange_time} for a stored timestamp.
+QJsonObject describe(qint64 ms, const QString& exchange);
+
+} // namespace fincept::core::time
@@ -1,6 +1,7 @@
 #include "datahub/DataHub.h"
 
 #include "core/logging/Logger.h"
+#include "core/telemetry/UsageMetrics.h"
 
 #include <QDateTime>
 #include <QMetaObject>
@@ -18,6 +19,16 @@ qint64 now_ms() {
     return QDateTime::currentMSecsSinceEpoch();
 }
 
+// Refresh latency for the local usage metrics: request → publish (or error),
+// per topic family. topic_family() drops the trailing symbol, context or
+// query segment, so user data never reaches the metrics table.
+void record_refresh(const QString& topic, qint64 latency_ms, bool ok) {
+    if (latency_ms >= 0)
+        telemetry::UsageMetrics::instance().record_latency(telemetry::UsageMetrics::Kind::DataSource,
+                                                           telemetry::UsageMetrics::topic_family(topic),
+                                                           latency_ms, ok);
+}
+
 // Gap 3 helper: returns the freshness window in ms for a topic state.
 // Picks the per-publish override if set; otherwise the policy TTL.
 template <typename TopicStateT>
@@ -328,6 +339,9 @@ QMetaObject::Connection DataHub::subscribe(QObject* owner, const QString& topic,
         if (!have_fresh_value && !st.in_flight && !st.policy.push_only) {
             needs_cold_start_fetch = true;
         }
+        if (!st.policy.push_only)
+            telemetry::UsageMetrics::instance().record_cache(
+                "hub:" + telemetry::UsageMetrics::topic_family(topic), have_fresh_value);
     }
 
     // Auto-cleanup on owner destruction. The QObject::destroyed signal
@@ -614,9 +628,12 @@ void DataHub::emit_to_subscribers(const QString& topic, const QVariant& value) {
 void DataHub::do_publish(const QString& topic, const QVariant& value, std::chrono::milliseconds ttl_override) {
     int coalesce_ms = 0;
     bool defer = false;
+    qint64 latency_ms = -1;
     {
         QMutexLocker lock(&mutex_);
         auto& st = state_for(topic);
+        if (st.in_flight && st.last_refresh_request_ms > 0)
+            latency_ms = now_ms() - st.last_refresh_request_ms;
         st.value = value;
         st.last_publish_ms = now_ms();
         st.total_publishes += 1;
@@ -636,6 +653,7 @@ void DataHub::do_publish(const QString& topic, const QVariant& value, std::chron
             // else: a timer is already armed; it will pick up the new latest value
         }
     }
+    record_refresh(topic, latency_ms, true);
     if (coalesce_ms > 0) {
         if (defer) {
             // Arm a one-shot timer. Runs on hub thread (we're already there
@@ -697,9 +715,12 @@ void DataHub::publish_error(const QString& topic, const QString& error) {
         bool is_pattern = false;
     };
     QVector<PendingErr> error_targets;
+    qint64 latency_ms = -1;
     {
         QMutexLocker lock(&mutex_);
         auto& st = state_for(topic);
+        if (st.in_flight && st.last_refresh_request_ms > 0)
+            latency_ms = now_ms() - st.last_refresh_request_ms;
         st.in_flight = false;
         st.last_error = error;
         st.last_error_ms = now_ms();
@@ -718,6 +739,7 @@ void DataHub::publish_error(const QString& topic, const QString& error) {
                     error_targets.append({e.owner, {}, e.pattern_slot, true});
         }
     }
+    record_refresh(topic, latency_ms, false);
     for (const auto& t : error_targets) {
         if (!t.owner)
             continue;
@@ -0,0 +1,478 @@
+// LocalApiServer.cpp — Localhost JSON-RPC 2.0 server over the internal tool catalog.
+//
+// Same hand-rolled HTTP/1.1 framing as TerminalMcpBridge (no QHttpServer in
+// this Qt build). Tool calls go through McpProvider::call_tool_async inside
+// TerminalMcpBridge::ScopedCallFlags, so the process-wide auth checker gates
+// them like any other non-interactive caller.
+
+#include "mcp/LocalApiServer.h"
+
+#include "core/logging/Logger.h"
+#include "mcp/McpProvider.h"
+#include "mcp/TerminalMcpBridge.h"
+#include "storage/repositories/SettingsRepository.h"
+#include "storage/secure/SecureStorage.h"
+
+#include <QCoreApplication>
+#include <QFutureWatcher>
+#include <QHostAddress>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QPointer>
+#include <QRandomGenerator>
+#include <QTcpServer>
+#include <QTcpSocket>
+
+#include <algorithm>
+
+namespace fincept::mcp {
+
+static constexpr const char* kApiTag = "LocalApiServer";
+static constexpr int kApiMaxHeaderBytes = 16 * 1024;
+static constexpr int kApiMaxBodyBytes = 1024 * 1024;
+
+// JSON-RPC 2.0 error codes.
+static constexpr int kRpcParseError = -32700;
+static constexpr int kRpcInvalidRequest = -32600;
+static constexpr int kRpcMethodNotFound = -32601;
+static constexpr int kRpcInvalidParams = -32602;
+static constexpr int kRpcToolError = -32000;
+static constexpr int kRpcForbidden = -32001;
+
+namespace {
+
+struct ApiAlias {
+    const char* method;
+    const char* tool;
+};
+
+// Convenience methods; params are passed to the tool unchanged.
+constexpr ApiAlias kApiAliases[] = {
+    {"market.quote", "get_quote"},
+    {"market.history", "get_history"},
+    {"market.lookup", "lookup_symbol"},
+    {"portfolio.list", "list_portfolios"},
+    {"portfolio.get", "get_portfolio"},
+    {"portfolio.holdings", "get_holdings"},
+    {"portfolio.overview", "get_portfolio_overview"},
+    {"portfolio.transactions", "get_transactions"},
+    {"paper.portfolios", "pt_list_portfolios"},
+    {"paper.positions", "pt_get_positions"},
+    {"paper.orders", "pt_get_orders"},
+    {"paper.place_order", "pt_place_order"},
+    {"paper.cancel_order", "pt_cancel_order"},
+    {"live.quote", "live_get_quote"},
+    {"live.positions", "live_get_positions"},
+    {"live.holdings", "live_get_holdings"},
+    {"live.orders", "live_get_orders"},
+    {"live.funds", "live_get_funds"},
+    {"live.place_order", "live_place_order"},
+    {"live.cancel_order", "live_cancel_order"},
+};
+
+// Tools that submit or cancel orders; callable only with allow_orders.
+const QStringList& api_order_tools() {
+    static const QStringList tools{"pt_place_order", "pt_cancel_order", "live_place_order", "live_cancel_order"};
+    return tools;
+}
+
+// Read-only market data and portfolio tools; everything else (settings,
+// navigation, files, agents, portfolio edits) stays off the API.
+const QStringList& api_query_tools() {
+    static const QStringList tools{
+        // Market data
+        "get_quote", "get_history", "lookup_symbol", "get_market_status", "get_market_holidays", "get_option_chain",
+        "search_equity_symbols", "get_equity_financials", "get_equity_technicals", "get_equity_peers",
+        // Portfolios and watchlists
+        "list_portfolios", "get_portfolio", "get_holdings", "get_portfolio_assets", "get_transactions",
+        "get_portfolio_snapshots", "get_portfolio_overview", "get_portfolio_dividends", "get_capital_gains",
+        "get_benchmark_analytics", "get_benchmark_series", "get_watchlists", "get_watchlist_values",
+        // Paper trading
+        "pt_list_portfolios", "pt_get_portfolio", "pt_get_positions", "pt_get_orders", "pt_get_stats",
+        // Live broker accounts
+        "live_get_quote", "live_get_multi_quotes", "live_get_depth", "live_get_option_chain", "live_get_positions",
+        "live_get_holdings", "live_get_orders", "live_get_trades", "live_get_funds", "live_get_intraday_pnl"};
+    return tools;
+}
+
+// Constant-time so response timing doesn't leak how much of a guess matched.
+bool api_token_matches(const QString& header, const QString& token) {
+    const QByteArray got = header.toUtf8();
+    const QByteArray want = ("Bearer " + token).toUtf8();
+    if (token.isEmpty() || got.size() != want.size())
+        return false;
+    unsigned char diff = 0;
+    for (int i = 0; i < want.size(); ++i)
+        diff |= static_cast<unsigned char>(got[i] ^ want[i]);
+    return diff == 0;
+}
+
+QString api_random_token() {
+    QByteArray buf(32, Qt::Uninitialized);
+    QRandomGenerator::system()->generate(reinterpret_cast<quint32*>(buf.data()),
+                                         reinterpret_cast<quint32*>(buf.data() + buf.size()));
+    return QString::fromLatin1(buf.toHex());
+}
+
+const char* api_status_text(int code) {
+    switch (code) {
+        case 200:
+            return "OK";
+        case 400:
+            return "Bad Request";
+        case 401:
+            return "Unauthorized";
+        case 404:
+            return "Not Found";
+        case 413:
+            return "Payload Too Large";
+        case 431:
+            return "Request Header Fields Too Large";
+        default:
+            return "Unknown";
+    }
+}
+
+} // namespace
+
+LocalApiServer& LocalApiServer::instance() {
+    static LocalApiServer s;
+    return s;
+}
+
+LocalApiServer::LocalApiServer(QObject* parent) : QObject(parent) {}
+
+// ── Config ──────────────────────────────────────────────────────────────────
+
+LocalApiServer::Config LocalApiServer::config() const {
+    auto& repo = SettingsRepository::instance();
+    Config c;
+    auto enabled = repo.get(QStringLiteral("api_server.enabled"), QStringLiteral("false"));
+    c.enabled = enabled.is_ok() && enabled.value() == QStringLiteral("true");
+    auto port = repo.get(QStringLiteral("api_server.port"), QStringLiteral("8765"));
+    if (port.is_ok())
+        c.port = std::clamp(port.value().toInt(), 1024, 65535);
+    auto orders = repo.get(QStringLiteral("api_server.allow_orders"), QStringLiteral("false"));
+    c.allow_orders = orders.is_ok() && orders.value() == QStringLiteral("true");
+    return c;
+}
+
+bool LocalApiServer::apply(const Config& config, QString* error) {
+    auto& repo = SettingsRepository::instance();
+    const QString category = QStringLiteral("api_server");
+    repo.set(QStringLiteral("api_server.enabled"), config.enabled ? "true" : "false", category);
+    repo.set(QStringLiteral("api_server.port"), QString::number(config.port), category);
+    repo.set(QStringLiteral("api_server.allow_orders"), config.allow_orders ? "true" : "false", category);
+    allow_orders_ = config.allow_orders;
+
+    if (!config.enabled) {
+        close();
+        return true;
+    }
+    if (server_ && server_->serverPort() == config.port)
+        return true;
+    close();
+    return listen(config.port, error);
+}
+
+void LocalApiServer::start_from_settings() {
+    const Config c = config();
+    allow_orders_ = c.allow_orders;
+    if (c.enabled)
+        listen(c.port, nullptr);
+}
+
+QString LocalApiServer::token() {
+    const QString existing = stored_token();
+    return existing.isEmpty() ? rotate_token() : existing;
+}
+
+QString LocalApiServer::stored_token() {
+    if (token_.isEmpty()) {
+        auto stored = SecureStorage::instance().retrieve(QStringLiteral("api_server_token"));
+        if (stored.is_ok())
+            token_ = stored.value();
+    }
+    return token_;
+}
+
+QString LocalApiServer::rotate_token() {
+    token_ = api_random_token();
+    auto r = SecureStorage::instance().store(QStringLiteral("api_server_token"), token_);
+    if (r.is_err())
+        LOG_WARN(kApiTag, "Token not persisted: " + QString::fromStdString(r.error()));
+    LOG_INFO(kApiTag, "API token rotated");
+    return token_;
+}
+
+// ── Lifecycle ───────────────────────────────────────────────────────────────
+
+bool LocalApiServer::listen(int port, QString* error) {
+    server_ = new QTcpServer(this);
+    connect(server_, &QTcpServer::newConnection, this, &LocalApiServer::on_new_connection);
+    if (!server_->listen(QHostAddress::LocalHost, quint16(port))) {
+        const QString msg = QString("Cannot bind 127.0.0.1:%1: %2").arg(port).arg(server_->errorString());
+        LOG_ERROR(kApiTag, msg);
+        if (error)
+            *error = msg;
+        server_->deleteLater();
+        server_ = nullptr;
+        emit status_changed(false);
+        return false;
+    }
+    (void)token();
+    LOG_INFO(kApiTag, QString("Listening on %1 (orders %2)")
+                          .arg(endpoint(), allow_orders_ ? QStringLiteral("allowed") : QStringLiteral("disabled")));
+    emit status_changed(true);
+    return true;
+}
+
+void LocalApiServer::close() {
+    if (!server_)
+        return;
+    for (auto it = states_.constBegin(); it != states_.constEnd(); ++it) {
+        it.key()->disconnectFromHost();
+        it.key()->deleteLater();
+    }
+    states_.clear();
+    server_->close();
+    server_->deleteLater();
+    server_ = nullptr;
+    LOG_INFO(kApiTag, "Stopped");
+    emit status_changed(false);
+}
+
+bool LocalApiServer::is_listening() const {
+    return server_ && server_->isListening();
+}
+
+QString LocalApiServer::endpoint() const {
+    return is_listening() ? QString("http://127.0.0.1:%1").arg(server_->serverPort()) : QString();
+}
+
+// ── Connection handling ─────────────────────────────────────────────────────
+
+void LocalApiServer::on_new_connection() {
+    while (server_ && server_->hasPendingConnections()) {
+        QTcpSocket* sock = server_->nextPendingConnection();
+        states_.insert(sock, RequestState{});
+        connect(sock, &QTcpSocket::readyRead, this, &LocalApiServer::on_ready_read);
+        connect(sock, &QTcpSocket::disconnected, this, [this, sock]() {
+            states_.remove(sock);
+            sock->deleteLater();
+        });
+    }
+}
+
+void LocalApiServer::on_ready_read() {
+    auto* sock = qobject_cast<QTcpSocket*>(sender());
+    auto it = states_.find(sock);
+    if (!sock || it == states_.end())
+        return;
+    RequestState& st = it.value();
+    if (st.dispatched) {
+        sock->readAll(); // one request per connection; pipelined or trailing bytes are dropped
+        return;
+    }
+    st.buffer.append(sock->readAll());
+
+    if (!st.headers_parsed) {
+        if (st.buffer.indexOf("\r\n\r\n") < 0) {
+            if (st.buffer.size() > kApiMaxHeaderBytes)
+                write_json(sock, 431, {{"error", "Request header too large"}});
+            return;
+        }
+        if (!parse_headers(st)) {
+            write_json(sock, 400, {{"error", "Malformed request"}});
+            return;
+        }
+        if (st.content_length > kApiMaxBodyBytes) {
+            write_json(sock, 413, {{"error", "Payload too large"}});
+            return;
+        }
+    }
+    const int body_start = st.buffer.indexOf("\r\n\r\n") + 4;
+    if (st.buffer.size() - body_start < st.content_length)
+        return;
+    // Marked before dispatch so a later readyRead cannot send it again.
+    st.dispatched = true;
+    const RequestState request = st; // dispatch may drop the state
+    dispatch(sock, request);
+}
+
+bool LocalApiServer::parse_headers(RequestState& st) {
+    const QByteArray head = st.buffer.left(st.buffer.indexOf("\r\n\r\n"));
+    const QList<QByteArray> lines = head.split('\n');
+    const QList<QByteArray> parts = lines.value(0).trimmed().split(' ');
+    if (parts.size() < 2)
+        return false;
+    st.method = QString::fromLatin1(parts[0]);
+    st.path = QString::fromLatin1(parts[1]).section('?', 0, 0);
+    for (int i = 1; i < lines.size(); ++i) {
+        const QByteArray line = lines[i].trimmed();
+        const int colon = line.indexOf(':');
+        if (colon <= 0)
+            continue;
+        st.headers.insert(QString::fromLatin1(line.left(colon).trimmed()).toLower(),
+                          QString::fromLatin1(line.mid(colon + 1).trimmed()));
+    }
+    bool ok = false;
+    st.content_length = st.headers.value("content-length", "0").toLongLong(&ok);
+    if (!ok || st.content_length < 0)
+        return false;
+    st.headers_parsed = true;
+    return true;
+}
+
+// ── Routing ─────────────────────────────────────────────────────────────────
+
+void LocalApiServer::dispatch(QTcpSocket* sock, const RequestState& st) {
+    if (st.method == "GET" && st.path == "/health") {
+        write_json(sock, 200, {{"ok", true}, {"version", QCoreApplication::applicationVersion()}});
+        return;
+    }
+    if (st.method != "POST" || st.path != "/rpc") {
+        write_json(sock, 404, {{"error", QString("Unknown route: %1 %2").arg(st.method, st.path)}});
+        return;
+    }
+    if (!api_token_matches(st.headers.value("authorization"), stored_token())) {
+        LOG_WARN(kApiTag, "Rejected /rpc call with a missing or wrong token");
+        write_json(sock, 401, {{"error", "Invalid or missing bearer token"}});
+        return;
+    }
+
+    const QByteArray body = st.buffer.mid(st.buffer.indexOf("\r\n\r\n") + 4, st.content_length);
+    QJsonParseError perr;
+    const QJsonDocument doc = QJsonDocument::fromJson(body, &perr);
+    if (perr.error != QJsonParseError::NoError) {
+        write_rpc_error(sock, QJsonValue::Null, kRpcParseError, perr.errorString());
+        return;
+    }
+    if (!doc.isObject()) {
+        write_rpc_error(sock, QJsonValue::Null, kRpcInvalidRequest, "Expected one JSON-RPC request object");
+        return;
+    }
+    handle_rpc(sock, doc.object());
+}
+
+bool LocalApiServer::tool_allowed(const QString& name, bool is_destructive) const {
+    if (api_order_tools().contains(name))
+        return allow_orders_;
+    return !is_destructive && api_query_tools().contains(name);
+}
+
+void LocalApiServer::handle_rpc(QTcpSocket* sock, const QJsonObject& request) {
+    const QJsonValue id = request.value("id");
+    const QString method = request.value("method").toString();
+    const QJsonObject params = request.value("params").toObject();
+    if (request.value("jsonrpc").toString() != "2.0" || method.isEmpty()) {
+        write_rpc_error(sock, id, kRpcInvalidRequest, "Expected jsonrpc \"2.0\" and a method");
+        return;
+    }
+
+    if (method == "rpc.methods") {
+        QJsonArray out;
+        for (const auto& a : kApiAliases)
+            out.append(QJsonObject{{"method", a.method}, {"tool", a.tool}});
+        write_rpc_result(sock, id, out);
+        return;
+    }
+
+    if (method == "tools.list") {
+        QJsonArray out;
+        for (const auto& t : McpProvider::instance().list_tools()) {
+            if (!tool_allowed(t.name, t.is_destructive))
+                continue;
+            out.append(QJsonObject{{"name", t.name},
+                                   {"description", t.description},
+                                   {"category", t.category},
+                                   {"inputSchema", t.input_schema}});
+        }
+        write_rpc_result(sock, id, out);
+        return;
+    }
+
+    QString tool;
+    QJsonObject args = params;
+    if (method == "tools.call") {
+        tool = params.value("name").toString();
+        args = params.value("arguments").toObject();
+        if (tool.isEmpty()) {
+            write_rpc_error(sock, id, kRpcInvalidParams, "tools.call needs params.name");
+            return;
+        }
+    } else {
+        for (const auto& a : kApiAliases)
+            if (method == QLatin1String(a.method))
+                tool = QString::fromLatin1(a.tool);
+        if (tool.isEmpty()) {
+            write_rpc_error(sock, id, kRpcMethodNotFound, "Unknown method: " + method);
+            return;
+        }
+    }
+
+    const auto info = McpProvider::instance().find_tool(tool);
+    if (!info) {
+        write_rpc_error(sock, id, kRpcMethodNotFound, "Unknown or disabled tool: " + tool);
+        return;
+    }
+    if (!tool_allowed(tool, info->is_destructive)) {
+        write_rpc_error(sock, id, kRpcForbidden,
+                        api_order_tools().contains(tool)
+                            ? QStringLiteral("Order submission is disabled in Settings › Developer")
+                            : QString("Tool '%1' is not exposed by the API server").arg(tool));
+        return;
+    }
+
+    LOG_INFO(kApiTag, QString("%1 → %2").arg(method, tool));
+    QFuture<ToolResult> future;
+    {
+        TerminalMcpBridge::ScopedCallFlags flags(true, allow_orders_);
+        future = McpProvider::instance().call_tool_async(tool, args);
+    }
+    QPointer<LocalApiServer> self = this;
+    QPointer<QTcpSocket> guard = sock;
+    auto* watcher = new QFutureWatcher<ToolResult>(this);
+    connect(watcher, &QFutureWatcher<ToolResult>::finished, this, [self, guard, watcher, id]() {
+        const auto fut = watcher->future();
+        const ToolResult r = fut.resultCount() > 0 ? fut.result() : ToolResult::fail("Tool produced no result");
+        watcher->deleteLater();
+        if (!self || !guard)
+            return;
+        if (r.success)
+            self->write_rpc_result(guard, id, r.to_json());
+        else
+            self->write_rpc_error(guard, id, kRpcToolError, r.error.isEmpty() ? r.message : r.error);
+    });
+    watcher->setFuture(future);
+}
+
+// ── Responses ───────────────────────────────────────────────────────────────
+
+void LocalApiServer::write_json(QTcpSocket* sock, int status, const QJsonObject& body) {
+    states_.remove(sock);
+    if (!sock || sock->state() != QAbstractSocket::ConnectedState)
+        return;
+    const QByteArray bytes = QJsonDocument(body).toJson(QJsonDocument::Compact);
+    QByteArray response;
+    response.reserve(bytes.size() + 128);
+    response += "HTTP/1.1 " + QByteArray::number(status) + ' ' + api_status_text(status) + "\r\n";
+    response += "Content-Type: application/json\r\n";
+    response += "Content-Length: " + QByteArray::number(bytes.size()) + "\r\n";
+    response += "Connection: close\r\n\r\n";
+    response += bytes;
+    sock->write(response);
+    sock->flush();
+    sock->disconnectFromHost();
+}
+
+void LocalApiServer::write_rpc_result(QTcpSocket* sock, const QJsonValue& id, const QJsonValue& result) {
+    write_json(sock, 200, {{"jsonrpc", "2.0"}, {"id", id}, {"result", result}});
+}
+
+void LocalApiServer::write_rpc_error(QTcpSocket* sock, const QJsonValue& id, int code, const QString& message) {
+    write_json(sock, 200,
+               {{"jsonrpc", "2.0"}, {"id", id}, {"error", QJsonObject{{"code", code}, {"message", message}}}});
+}
+
+} // namespace fincept::mcp
@@ -0,0 +1,113 @@
+#pragma once
+// LocalApiServer.h — Optional localhost JSON-RPC 2.0 API for external processes.
+//
+// Lets research code running beside the terminal (Python, R, notebooks) read
+// market data and portfolio state — and, when the user allows it, submit
+// orders — through the same internal tool catalog the AI chat uses:
+//
+//   POST /rpc      {"jsonrpc":"2.0","id":1,"method":"market.quote","params":{"symbol":"AAPL"}}
+//   GET  /health   → {"ok":true,"version":...}   (no token needed)
+//
+// Methods:
+//   rpc.methods                   — the aliases below with the tool each maps to
+//   tools.list                    — tools callable through this server
+//   tools.call {name, arguments}  — run one of them
+//   market.* / portfolio.* / paper.* / live.*  — aliases for single tools
+//                                   (params are the tool's arguments)
+//
+// What is callable: a fixed list of read-only market data, portfolio, paper
+// and live account queries (api_query_tools in the .cpp), plus the paper /
+// live order tools when "Allow order submission" is on. Nothing else — no
+// settings, navigation, files or portfolio edits.
+//
+// Auth: `Authorization: Bearer <token>` on every /rpc call. The token is made
+// on first enable, kept in SecureStorage, and shown (and rotated) in
+// Settings › Developer.
+//
+// Off by default. Binds 127.0.0.1 on the configured port only. HTTP framing as
+// in TerminalMcpBridge; one request per connection. Batches are not supported.
+
+#include "mcp/McpTypes.h"
+
+#include <QHash>
+#include <QJsonObject>
+#include <QObject>
+#include <QString>
+
+class QTcpServer;
+class QTcpSocket;
+
+namespace fincept::mcp {
+
+class LocalApiServer : public QObject {
+    Q_OBJECT
+  public:
+    static LocalApiServer& instance();
+
+    struct Config {
+        bool enabled = false;
+        int port = 8765;
+        bool allow_orders = false;
+    };
+
+    /// Persisted config (settings table, category "api_server").
+    Config config() const;
+
+    /// Persists `config` and starts, stops or rebinds the server to match.
+    /// Returns false with `error` when the port cannot be bound.
+    bool apply(const Config& config, QString* error = nullptr);
+
+    /// Starts the server at launch when it was left enabled.
+    void start_from_settings();
+
+    bool is_listening() const;
+    /// "http://127.0.0.1:<port>"; empty when not listening.
+    QString endpoint() const;
+
+    /// Bearer token clients must send; generated on first use.
+    QString token();
+    /// The persisted token, or empty when none was generated yet.
+    QString stored_token();
+    /// Replaces the token; clients holding the old one get 401 from now on.
+    QString rotate_token();
+
+    LocalApiServer(const LocalApiServer&) = delete;
+    LocalApiServer& operator=(const LocalApiServer&) = delete;
+
+  signals:
+    void status_changed(bool listening);
+
+  private:
+    explicit LocalApiServer(QObject* parent = nullptr);
+
+    struct RequestState {
+        QByteArray buffer;
+        bool headers_parsed = false;
+        bool dispatched = false;
+        QString method;
+        QString path;
+        qint64 content_length = 0;
+        QHash<QString, QString> headers;
+    };
+
+    bool listen(int port, QString* error);
+    void close();
+
+    void on_new_connection();
+    void on_ready_read();
+    bool parse_headers(RequestState& st);
+    void dispatch(QTcpSocket* sock, const RequestState& st);
+    void handle_rpc(QTcpSocket* sock, const QJsonObject& request);
+    bool tool_allowed(const QString& name, bool is_destructive) const;
+
+    void write_json(QTcpSocket* sock, int status, const QJsonObject& body);
+    void write_rpc_result(QTcpSocket* sock, const QJsonValue& id, const QJsonValue& result);
+    void write_rpc_error(QTcpSocket* sock, const QJsonValue& id, int code, const QString& message);
+
+    QTcpServer* server_ = nullptr;
+    QHash<QTcpSocket*, RequestState> states_;
+    QString token_;
+    bool allow_orders_ = false;
+};
+
+} // namespace fincept::mcp
@@ -8,38 +8,70 @@
 #include "mcp/tools/AgenticMemoryTools.h"
 #include "mcp/tools/AgentsTools.h"
 #include "mcp/tools/AiChatTools.h"
+#include "mcp/tools/AlgoTradingTools.h"
 #include "mcp/tools/AltInvestmentsTools.h"
+#include "mcp/tools/AuditTools.h"
+#include "mcp/tools/BreadthTools.h"
+#include "mcp/tools/CommodityCurveTools.h"
+#include "mcp/tools/CotTools.h"
+#include "mcp/tools/CountryRiskTools.h"
+#include "mcp/tools/CryptoFundingTools.h"
 #include "mcp/tools/CryptoTradingTools.h"
+#include "mcp/tools/CustomIndexTools.h"
 #include "mcp/tools/DBnomicsTools.h"
 #include "mcp/tools/DashboardTools.h"
 #include "mcp/tools/DataHubTools.h"
+#include "mcp/tools/DataQualityTools.h"
 #include "mcp/tools/DataSourcesTools.h"
+#include "mcp/tools/DocumentIndexTools.h"
+#include "mcp/tools/DownloadTools.h"
 #include "mcp/tools/EdgarTools.h"
 #include "mcp/tools/EquityResearchTools.h"
 #include "mcp/tools/ExcelTools.h"
+#include "mcp/tools/FactorBacktestTools.h"
 #include "mcp/tools/FileManagerTools.h"
 #include "mcp/tools/ForumTools.h"
+#include "mcp/tools/FuturesTools.h"
+#include "mcp/tools/GammaExposureTools.h"
 #include "mcp/tools/GeopoliticsTools.h"
 #include "mcp/tools/GovDataTools.h"
+#include "mcp/tools/HeatmapTools.h"
 #include "mcp/tools/LiveTradingTools.h"
 #include "mcp/tools/MAAnalyticsTools.h"
+#include "mcp/tools/MacroModelTools.h"
+#include "mcp/tools/MarketCalendarTools.h"
 #include "mcp/tools/MarketsTools.h"
 #include "mcp/tools/McpServersTools.h"
 #include "mcp/tools/MetaTools.h"
+#include "mcp/tools/MutualFundTools.h"
 #include "mcp/tools/NavigationTools.h"
 #include "mcp/tools/NewsTools.h"
+#include "mcp/tools/NlQueryTools.h"
 #include "mcp/tools/NotesTools.h"
+#include "mcp/tools/OptionChainTools.h"
+#include "mcp/tools/OptionsFlowTools.h"
 #include "mcp/tools/PaperTradingTools.h"
+#include "mcp/tools/PitFundamentalsTools.h"
+#include "mcp/tools/PluginTools.h"
 #include "mcp/tools/PortfolioTools.h"
 #include "mcp/tools/ProfileTools.h"
 #include "mcp/tools/PythonTools.h"
 #include "mcp/tools/QuantLabTools.h"
 #include "mcp/tools/ReportBuilderTools.h"
+#include "mcp/tools/ScreenerTools.h"
 #include "mcp/tools/SettingsTools.h"
+#include "mcp/tools/StablecoinPegTools.h"
 #include "mcp/tools/SurfaceAnalyticsTools.h"
+#include "mcp/tools/SymbologyTools.h"
 #include "mcp/tools/SystemTools.h"
+#include "mcp/tools/TcaTools.h"
+#include "mcp/tools/TickRecorderTools.h"
+#include "mcp/tools/TradeJournalTools.h"
+#include "mcp/tools/VoiceTools.h"
 #include "mcp/tools/WatchlistTools.h"
+#include "mcp/tools/WebSocketTools.h"
 #include "mcp/tools/WorkspaceTools.h"
+#include "mcp/tools/YieldCurveTools.h"
 
 #include <QJsonDocument>
 
@@ -100,6 +132,73 @@ void initialize_all_tools() {
 
     // markets tab (quotes, symbol search)
     provider.register_tools(tools::get_markets_tools());
+    provider.register_tools(tools::get_market_calendar_tools());
+
+    // live index heatmaps (sector/industry treemaps)
+    provider.register_tools(tools::get_heatmap_tools());
+
+    // market breadth (A/D line, new highs/lows, McClellan)
+    provider.register_tools(tools::get_breadth_tools());
+
+    // unusual options activity (vol/OI, sweeps, skew) and alerts
+    provider.register_tools(tools::get_options_flow_tools());
+
+    // dealer gamma exposure (per-strike GEX, zero gamma)
+    provider.register_tools(tools::get_gamma_exposure_tools());
+
+    // unified option chains (CBOE / Polygon / Tradier / broker, local greeks)
+    provider.register_tools(tools::get_option_chain_tools());
+
+    // yield curve fits (Nelson-Siegel / Svensson factors, animation frames)
+    provider.register_tools(tools::get_yield_curve_tools());
+
+    // macro models (recession probability, GDP nowcast)
+    provider.register_tools(tools::get_macro_model_tools());
+
+    // natural-language data questions → FRED / Yahoo Finance commands, merged series
+    provider.register_tools(tools::get_nl_query_tools());
+
+    // country risk (IMF / World Bank / BIS / OECD composite)
+    provider.register_tools(tools::get_country_risk_tools());
+
+    // commodity term structure (contango/backwardation, roll yield, seasonality)
+    provider.register_tools(tools::get_commodity_curve_tools());
+
+    // futures contract specs, expiry calendar and continuous series (ES#C)
+    provider.register_tools(tools::get_futures_tools());
+
+    // CFTC COT positioning index, z-scores and signal rules
+    provider.register_tools(tools::get_cot_tools());
+
+    // native screener (filter trees over cached metrics, saved / scheduled screens, CSV export)
+    provider.register_tools(tools::get_screener_tools());
+
+    // point-in-time fundamentals keyed by report and filing date (SEC / FMP)
+    provider.register_tools(tools::get_pit_fundamentals_tools());
+
+    // perpetual funding rates, open interest, cross-exchange arb spreads and alerts
+    provider.register_tools(tools::get_crypto_funding_tools());
+
+    // stablecoin peg / Curve pool monitoring and depeg episodes
+    provider.register_tools(tools::get_stablecoin_peg_tools());
+
+    // data quality (candle store / stream health, range re-fetch)
+    provider.register_tools(tools::get_data_quality_tools());
+
+    // symbology (identifier mapping across providers and brokers)
+    provider.register_tools(tools::get_symbology_tools());
+
+    // bulk historical downloads (persistent, rate-limited backfill jobs)
+    provider.register_tools(tools::get_download_tools());
+
+    // custom indices (weighting schemes, rebalancing, @-symbols from the candle cache)
+    provider.register_tools(tools::get_custom_index_tools());
+
+    // tick recorder (live stream capture, replay, import for tick backtests)
+    provider.register_tools(tools::get_tick_recorder_tools());
+
+    // websocket connection metrics and inbound backpressure policy
+    provider.register_tools(tools::get_websocket_tools());
 
     // watchlist tab
     provider.register_tools(tools::get_watchlist_tools());
@@ -122,12 +221,34 @@ void initialize_all_tools() {
     // paper trading tab
     provider.register_tools(tools::get_paper_trading_tools());
 
+    // algo trading (native backtest commands)
+    provider.register_tools(tools::get_algo_trading_tools());
+
+    // factor backtests (ranked bucket portfolios, turnover, IC statistics)
+    provider.register_tools(tools::get_factor_backtest_tools());
+
     // live broker trading (order placement/cancel, account state, market data)
     provider.register_tools(tools::get_live_trading_tools());
 
+    // transaction cost analysis (fill slippage vs arrival / VWAP / close)
+    provider.register_tools(tools::get_tca_tools());
+    provider.register_tools(tools::get_trade_journal_tools());
+
+    // wasm plugins (community indicators, transforms, data sources)
+    provider.register_tools(tools::get_plugin_tools());
+
+    // audit trail (order / auth / deployment / destructive actions; export, verify)
+    provider.register_tools(tools::get_audit_tools());
+
     // sec edgar (CIK resolution, XBRL financials, filing search)
     provider.register_tools(tools::get_edgar_tools());
 
+    // document index (RAG over filings, transcripts, notes and files)
+    provider.register_tools(tools::get_document_index_tools());
+
+    // voice commands (local Whisper transcription, spoken command routing)
+    provider.register_tools(tools::get_voice_tools());
+
     // m&a analytics tab
     provider.register_tools(tools::get_ma_analytics_tools());
 
@@ -190,6 +311,9 @@ void initialize_all_tools() {
     // surface-analytics — 35-surface capability catalog + Databento fetches
     provider.register_tools(tools::get_surface_analytics_tools());
 
+    // mutual-funds — AMFI / US NAV history, rolling & SIP returns, category compare
+    provider.register_tools(tools::get_mutual_fund_tools());
+
     // Phase 6: meta tools — tool_list, tool_describe, mcp_health.
     // Always exposed so the LLM can lazy-discover specialised tools.
     provider.register_tools(tools::get_meta_tools());
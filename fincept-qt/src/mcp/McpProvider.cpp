@@ -3,9 +3,12 @@
 #include "mcp/McpProvider.h"
 
 #include "core/logging/Logger.h"
+#include "core/telemetry/UsageMetrics.h"
 #include "mcp/SchemaValidator.h"
+#include "services/audit/AuditTrail.h"
 
 #include <QCoreApplication>
+#include <QElapsedTimer>
 #include <QFutureWatcher>
 #include <QPromise>
 #include <QRegularExpression>
@@ -165,6 +168,42 @@ bool McpProvider::has_tool(const QString& name) const {
 // Execution
 // ============================================================================
 
+static bool is_secret_arg(const QString& key) {
+    const QString k = key.toLower();
+    return k.contains("api_key") || k.contains("apikey") || k.contains("secret") || k.contains("password") ||
+           k.contains("passphrase") || k == "pin" || k == "totp" || k == "token" || k.endsWith("_token");
+}
+
+// Tool args with every credential-looking value replaced, recursively.
+static QJsonValue redact_args(const QJsonValue& v) {
+    if (v.isArray()) {
+        QJsonArray out;
+        for (const auto& e : v.toArray())
+            out.append(redact_args(e));
+        return out;
+    }
+    if (!v.isObject())
+        return v;
+    QJsonObject out;
+    const QJsonObject o = v.toObject();
+    for (auto it = o.begin(); it != o.end(); ++it)
+        out.insert(it.key(), is_secret_arg(it.key()) ? QJsonValue("[redacted]") : redact_args(it.value()));
+    return out;
+}
+
+// Every call of a destructive tool lands in the audit trail — refused,
+// rejected and failed calls included, since those are what a reviewer asks
+// about first. The result body is left out; outcome and message are enough.
+static void audit_destructive(const QString& name, const QJsonObject& args, const ToolResult& r,
+                              const QString& outcome) {
+    QJsonObject detail{{"args", redact_args(args)}, {"outcome", outcome}, {"success", r.success}};
+    if (!r.message.isEmpty())
+        detail["message"] = r.message;
+    if (!r.error.isEmpty())
+        detail["error"] = r.error;
+    services::audit::AuditTrail::instance().record("destructive", name, {}, detail);
+}
+
 ToolResult McpProvider::call_tool(const QString& name, const QJsonObject& args) {
     // Sync entry point. If the registered tool is async, we still want to
     // give legacy callers (LlmService, TerminalToolBridge, workflow nodes)
@@ -233,6 +272,8 @@ QFuture<ToolResult> McpProvider::call_tool_async(const QString& name, const QJso
     // identical auth/destructive rules. See that method for the no-checker
     // semantics.
     if (auto denied = check_authorization(name, auth_required, is_destructive)) {
+        if (is_destructive)
+            audit_destructive(name, args, *denied, "refused");
         QPromise<ToolResult> p;
         p.start();
         p.addResult(*denied);
@@ -245,9 +286,12 @@ QFuture<ToolResult> McpProvider::call_tool_async(const QString& name, const QJso
     auto vr = validate_args(schema, normalized);
     if (vr.is_err()) {
         LOG_WARN(TAG, QString("Tool '%1' rejected: %2").arg(name, QString::fromStdString(vr.error())));
+        const ToolResult rejected = ToolResult::fail(QString::fromStdString(vr.error()));
+        if (is_destructive)
+            audit_destructive(name, args, rejected, "rejected");
         QPromise<ToolResult> p;
         p.start();
-        p.addResult(ToolResult::fail(QString::fromStdString(vr.error())));
+        p.addResult(rejected);
         p.finish();
         return p.future();
     }
@@ -259,6 +303,8 @@ QFuture<ToolResult> McpProvider::call_tool_async(const QString& name, const QJso
 
     // Async preferred; fall back to sync wrapped in an immediately-resolved
     // future so call_tool() works uniformly for legacy handlers.
+    QElapsedTimer timer;
+    timer.start();
     if (async_handler) {
         auto promise = std::make_shared<QPromise<ToolResult>>();
         promise->start();
@@ -307,7 +353,14 @@ QFuture<ToolResult> McpProvider::call_tool_async(const QString& name, const QJso
                 promise->finish();
             }
         }
-        return promise->future();
+        // Latency is measured to resolution (or timeout), not to the handler's return.
+        return promise->future().then([name, timer, is_destructive, normalized](ToolResult r) {
+            telemetry::UsageMetrics::instance().record_latency(telemetry::UsageMetrics::Kind::Tool, name,
+                                                               timer.elapsed(), r.success);
+            if (is_destructive)
+                audit_destructive(name, normalized, r, r.success ? "ok" : "failed");
+            return r;
+        });
     }
 
     // Legacy sync path — invoke immediately, wrap in a resolved future.
@@ -324,6 +377,11 @@ QFuture<ToolResult> McpProvider::call_tool_async(const QString& name, const QJso
         p.addResult(ToolResult::fail("Unknown error during tool execution"));
     }
     p.finish();
+    const ToolResult r = p.future().result();
+    telemetry::UsageMetrics::instance().record_latency(telemetry::UsageMetrics::Kind::Tool, name, timer.elapsed(),
+                                                       r.success);
+    if (is_destructive)
+        audit_destructive(name, normalized, r, r.success ? "ok" : "failed");
     return p.future();
 }
 
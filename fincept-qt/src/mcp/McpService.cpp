@@ -113,6 +113,8 @@ QByteArray McpService::filter_signature(const ToolFilter& f) {
     key += "xc=" + f.exclude_categories.join('|').toUtf8() + ';';
     key += "n=" + f.name_patterns.join('|').toUtf8() + ';';
     key += "xn=" + f.exclude_name_patterns.join('|').toUtf8() + ';';
+    key += "nm=" + f.names.join('|').toUtf8() + ';';
+    key += "ro=" + QByteArray(f.exclude_destructive ? "1" : "0") + ';';
     key += "m=" + QByteArray::number(f.max_tools);
     return key;
 }
@@ -126,9 +128,10 @@ QByteArray McpService::filter_signature(const ToolFilter& f) {
 static constexpr int kHardMaxTools = 50;
 
 // Apply ToolFilter (categories include/exclude, name regex include/exclude,
-// max_tools cap) to a list of UnifiedTools. Pulled out as a free function so
-// both the OpenAI-format path and the Anthropic / Gemini / Fincept catalog
-// builders can share identical filter semantics.
+// exact-name allowlist, read-only, max_tools cap) to a list of UnifiedTools.
+// Pulled out as a free function so both the OpenAI-format path and the
+// Anthropic / Gemini / Fincept catalog builders can share identical filter
+// semantics.
 //
 // Telemetry: counts how many candidates were truncated by the max_tools
 // cap and emits a warning so we can spot screens whose Tier-2 categories
@@ -155,6 +158,10 @@ static std::vector<UnifiedTool> apply_tool_filter(std::vector<UnifiedTool> tools
         }
         if (!filter.exclude_categories.isEmpty() && filter.exclude_categories.contains(tool.category))
             continue;
+        if (!filter.names.isEmpty() && !filter.names.contains(tool.name))
+            continue;
+        if (filter.exclude_destructive && tool.is_destructive)
+            continue;
 
         if (!include_rx.isEmpty()) {
             bool any_match = false;
@@ -341,6 +341,14 @@ struct ToolFilter {
     /// Regex exclude filter on tool name. Applied after include.
     QStringList exclude_name_patterns;
 
+    /// Exact tool-name allowlist (e.g. get_quote, edgar_get_financials).
+    /// Empty = no allowlist. Applied together with the filters above.
+    QStringList names;
+
+    /// Drop tools flagged is_destructive (orders, writes, settings) so only
+    /// read-only data tools remain.
+    bool exclude_destructive = false;
+
     /// Hard cap on returned tool count. 0 = no cap.
     /// Truncation happens after all other filters have run, in the order
     /// tools were registered (effectively: most-recently-registered last).
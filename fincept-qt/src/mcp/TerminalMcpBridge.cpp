@@ -12,6 +12,7 @@
 #include "mcp/McpProvider.h"
 #include "mcp/McpService.h"
 
+#include <QDateTime>
 #include <QFutureWatcher>
 #include <QHostAddress>
 #include <QJsonDocument>
@@ -21,6 +22,8 @@
 #include <QTcpSocket>
 #include <QUuid>
 
+#include <algorithm>
+
 namespace fincept::mcp {
 
 static constexpr const char* TAG = "TerminalMcpBridge";
@@ -261,12 +264,19 @@ void TerminalMcpBridge::try_dispatch(QTcpSocket* sock) {
         return;
     RequestState& st = it.value();
 
-    // Authn — must come before any work happens.
+    // Authn — must come before any work happens. The process token is
+    // unrestricted; a run-scoped token carries that run's tool allowlist.
     const QString supplied = st.headers.value("x-mcp-token");
+    const ToolScope* scope = nullptr;
     if (supplied != token_) {
-        LOG_WARN(TAG, QString("Rejecting request — token mismatch (path=%1)").arg(st.path));
-        write_error(sock, 401, "Invalid or missing X-MCP-Token");
-        return;
+        const auto sit = scopes_.constFind(supplied);
+        if (supplied.isEmpty() || sit == scopes_.constEnd() ||
+            (!sit->bound && sit->expires_at < QDateTime::currentMSecsSinceEpoch())) {
+            LOG_WARN(TAG, QString("Rejecting request — token mismatch (path=%1)").arg(st.path));
+            write_error(sock, 401, "Invalid or missing X-MCP-Token");
+            return;
+        }
+        scope = &sit.value();
     }
 
     // Strip query string for path matching.
@@ -280,12 +290,12 @@ void TerminalMcpBridge::try_dispatch(QTcpSocket* sock) {
             write_error(sock, 400, "Invalid JSON body");
             return;
         }
-        handle_post_tool(sock, doc.object());
+        handle_post_tool(sock, doc.object(), scope);
         return;
     }
 
     if (st.method == "GET" && path_only == "/tools") {
-        handle_get_tools(sock);
+        handle_get_tools(sock, scope);
         return;
     }
 
@@ -294,7 +304,7 @@ void TerminalMcpBridge::try_dispatch(QTcpSocket* sock) {
 
 // ── POST /tool ──────────────────────────────────────────────────────────────
 
-void TerminalMcpBridge::handle_post_tool(QTcpSocket* sock, const QJsonObject& body) {
+void TerminalMcpBridge::handle_post_tool(QTcpSocket* sock, const QJsonObject& body, const ToolScope* scope) {
     const QString call_id = body.value("id").toString();
     const QString tool_name = body.value("tool").toString();
     const QJsonObject args = body.value("args").toObject();
@@ -307,6 +317,13 @@ void TerminalMcpBridge::handle_post_tool(QTcpSocket* sock, const QJsonObject& bo
         return;
     }
 
+    if (scope && !scope->tools.contains(tool_name)) {
+        LOG_WARN(TAG, QString("Tool call %1: %2 is not in this run's tool set").arg(call_id, tool_name));
+        write_error(sock, 403, QString("Tool '%1' is not available to this agent").arg(tool_name));
+        return;
+    }
+    const int max_result_chars = scope ? scope->max_result_chars : 0;
+
     LOG_INFO(TAG, QString("Tool call %1: %2 on %3").arg(call_id, tool_name, server_id));
 
     QPointer<TerminalMcpBridge> self = this;
@@ -340,37 +357,124 @@ void TerminalMcpBridge::handle_post_tool(QTcpSocket* sock, const QJsonObject& bo
     }
 
     auto* watcher = new QFutureWatcher<ToolResult>(this);
-    connect(watcher, &QFutureWatcher<ToolResult>::finished, this, [self, sock_guard, tool_name, call_id, watcher]() {
-        const auto fut = watcher->future();
-        ToolResult result = (fut.resultCount() > 0) ? fut.result() : ToolResult::fail("Tool produced no result");
-        watcher->deleteLater();
-
-        if (!self || !sock_guard) {
-            return; // bridge or peer gone
-        }
-
-        QJsonObject payload = result.to_json();
-        if (!call_id.isEmpty())
-            payload["id"] = call_id;
-        self->write_json_response(sock_guard, 200, payload);
-        emit self->tool_called(tool_name, result.success);
-    });
+    connect(watcher, &QFutureWatcher<ToolResult>::finished, this,
+            [self, sock_guard, tool_name, call_id, watcher, max_result_chars]() {
+                const auto fut = watcher->future();
+                ToolResult result =
+                    (fut.resultCount() > 0) ? fut.result() : ToolResult::fail("Tool produced no result");
+                watcher->deleteLater();
+                if (max_result_chars > 0)
+                    truncate_result(result, max_result_chars);
+
+                if (!self || !sock_guard) {
+                    return; // bridge or peer gone
+                }
+
+                QJsonObject payload = result.to_json();
+                if (!call_id.isEmpty())
+                    payload["id"] = call_id;
+                self->write_json_response(sock_guard, 200, payload);
+                emit self->tool_called(tool_name, result.success);
+            });
     watcher->setFuture(future);
 }
 
 // ── GET /tools ──────────────────────────────────────────────────────────────
 
-void TerminalMcpBridge::handle_get_tools(QTcpSocket* sock) {
+void TerminalMcpBridge::handle_get_tools(QTcpSocket* sock, const ToolScope* scope) {
     // Default filter — the catalog the agent gets at boot is also what
     // dynamic refresh returns. AgentService::build_payload owns the per-run
-    // filter; this endpoint is a fallback and uses the same defaults.
+    // filter; this endpoint is a fallback and uses the same defaults. A
+    // scoped token only ever sees its own run's tools.
     ToolFilter filter;
     filter.exclude_categories = {"navigation", "system", "settings", "ai-chat", "meta"};
+    if (scope) {
+        filter.names = QStringList(scope->tools.cbegin(), scope->tools.cend());
+        filter.max_tools = static_cast<int>(filter.names.size());
+    }
     QJsonObject payload;
     payload["tools"] = tool_definitions(filter);
     write_json_response(sock, 200, payload);
 }
 
+// ── Run-scoped tokens ───────────────────────────────────────────────────────
+
+QString TerminalMcpBridge::issue_scoped_token(const QJsonArray& tool_definitions, int max_result_chars) {
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    for (auto it = scopes_.begin(); it != scopes_.end();) {
+        if (!it->bound && it->expires_at < now)
+            it = scopes_.erase(it);
+        else
+            ++it;
+    }
+
+    ToolScope scope;
+    for (const auto& v : tool_definitions) {
+        const QString name = v.toObject().value("name").toString();
+        if (!name.isEmpty())
+            scope.tools.insert(name);
+    }
+    scope.max_result_chars = std::max(0, max_result_chars);
+    scope.expires_at = now + kScopeTtlMs;
+
+    const QString token = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    scopes_.insert(token, std::move(scope));
+    return token;
+}
+
+void TerminalMcpBridge::bind_scoped_token(const QString& token, QObject* owner) {
+    auto it = scopes_.find(token);
+    if (it == scopes_.end() || !owner)
+        return;
+    it->bound = true;
+    connect(owner, &QObject::destroyed, this, [this, token]() { revoke_scoped_token(token); });
+}
+
+void TerminalMcpBridge::revoke_scoped_token(const QString& token) {
+    scopes_.remove(token);
+}
+
+void TerminalMcpBridge::truncate_result(ToolResult& result, int max_chars) {
+    if (result.data.isNull() || result.data.isUndefined() || result.data.isBool() || result.data.isDouble())
+        return;
+    const auto compact_size = [](const QJsonValue& v) -> qsizetype {
+        if (v.isString())
+            return v.toString().size();
+        // Wrapping in an array serialises any value; drop the two brackets.
+        return QJsonDocument(QJsonArray{v}).toJson(QJsonDocument::Compact).size() - 2;
+    };
+    const qsizetype total = compact_size(result.data);
+    if (total <= max_chars)
+        return;
+
+    // Arrays keep whole leading rows — a valid prefix the model can reason over
+    // beats a cut mid-row. The TerminalToolkit hands the LLM `data` only, so the
+    // truncation marker has to live inside it.
+    if (result.data.isArray()) {
+        const QJsonArray rows = result.data.toArray();
+        QJsonArray kept;
+        qsizetype used = 64; // room for the wrapper keys
+        for (const auto& row : rows) {
+            const qsizetype n = compact_size(row) + 1;
+            if (used + n > max_chars)
+                break;
+            kept.append(row);
+            used += n;
+        }
+        result.data = QJsonObject{{"items", kept},
+                                  {"truncated", true},
+                                  {"returned", kept.size()},
+                                  {"total", rows.size()}};
+        return;
+    }
+
+    const QString text = result.data.isString()
+                             ? result.data.toString()
+                             : QString::fromUtf8(QJsonDocument(result.data.toObject()).toJson(QJsonDocument::Compact));
+    result.data = text.left(max_chars) +
+                  QString("… [truncated: %1 of %2 chars — narrow the request for the rest]").arg(max_chars).arg(total);
+}
+
 // ── HTTP response helpers ───────────────────────────────────────────────────
 
 static const char* status_text(int code) {
@@ -381,6 +485,8 @@ static const char* status_text(int code) {
             return "Bad Request";
         case 401:
             return "Unauthorized";
+        case 403:
+            return "Forbidden";
         case 404:
             return "Not Found";
         case 413:
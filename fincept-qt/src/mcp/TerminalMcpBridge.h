@@ -11,7 +11,9 @@
 // Auth: every request must include `X-MCP-Token: <token>`. The token is a
 // UUID generated per-process and injected into the agent config payload by
 // AgentService — agents share the parent's user session, so the token only
-// guards against OTHER local processes stumbling onto the port.
+// guards against OTHER local processes stumbling onto the port. AgentService
+// hands each run a scoped token instead (issue_scoped_token), which also pins
+// the run to the tools it was offered and truncates oversized results.
 //
 // Transport: QTcpServer + manual HTTP/1.1 framing (Qt6::HttpServer is not
 // available in this Qt 6.8.3 build). Localhost-only binding on 127.0.0.1:0
@@ -22,6 +24,7 @@
 #include <QHash>
 #include <QJsonArray>
 #include <QObject>
+#include <QSet>
 #include <QString>
 
 class QTcpServer;
@@ -49,6 +52,23 @@ class TerminalMcpBridge : public QObject {
     /// Per-process UUID. Agents include this as `X-MCP-Token` on every call.
     QString token() const { return token_; }
 
+    /// Token scoped to one agent run, presented as `X-MCP-Token` like token().
+    /// The bridge dispatches only the tools named in `tool_definitions` (the
+    /// catalog the run was given; GET /tools lists just those) and caps each
+    /// result at `max_result_chars` (0 = no cap) — array payloads keep their
+    /// leading items, anything else is cut with a marker. Expires after
+    /// kScopeTtlMs unless bound to its run. Main thread only.
+    QString issue_scoped_token(const QJsonArray& tool_definitions, int max_result_chars);
+    /// Ties a scoped token to the object driving its run (the agent process):
+    /// it no longer expires while `owner` lives and is revoked when `owner` is
+    /// destroyed. No-op for the process token or an unknown token.
+    void bind_scoped_token(const QString& token, QObject* owner);
+    /// Drops a scoped token at once; later calls with it get 401.
+    void revoke_scoped_token(const QString& token);
+
+    static constexpr int kDefaultMaxResultChars = 16000;
+    static constexpr qint64 kScopeTtlMs = 6LL * 60 * 60 * 1000;
+
     /// Second per-process UUID. Only injected into agent configs whose
     /// `allow_destructive_tools=true`; the agent's toolkit echoes it back as
     /// `X-MCP-Allow-Destructive` on each request. Bridge compares the header
@@ -112,6 +132,13 @@ class TerminalMcpBridge : public QObject {
   private:
     explicit TerminalMcpBridge(QObject* parent = nullptr);
 
+    struct ToolScope {
+        QSet<QString> tools;
+        int max_result_chars = 0;
+        qint64 expires_at = 0; // epoch ms; ignored while bound
+        bool bound = false;    // lives as long as its owner (bind_scoped_token)
+    };
+
     struct RequestState {
         QByteArray buffer;
         bool headers_parsed = false;
@@ -124,8 +151,9 @@ class TerminalMcpBridge : public QObject {
     bool parse_headers(RequestState& st);
     void try_dispatch(QTcpSocket* sock);
 
-    void handle_post_tool(QTcpSocket* sock, const QJsonObject& body);
-    void handle_get_tools(QTcpSocket* sock);
+    void handle_post_tool(QTcpSocket* sock, const QJsonObject& body, const ToolScope* scope);
+    void handle_get_tools(QTcpSocket* sock, const ToolScope* scope);
+    static void truncate_result(ToolResult& result, int max_chars);
 
     void write_json_response(QTcpSocket* sock, int status, const QJsonObject& body);
     void write_error(QTcpSocket* sock, int status, const QString& message);
@@ -135,6 +163,7 @@ class TerminalMcpBridge : public QObject {
     QString token_;
     QString destructive_token_;
     QHash<QTcpSocket*, RequestState> states_;
+    QHash<QString, ToolScope> scopes_; // scoped token → run's tool allowlist
 };
 
 } // namespace fincept::mcp
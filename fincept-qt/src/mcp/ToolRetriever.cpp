@@ -255,6 +255,9 @@ QString ToolRetriever::classify_category(const QStringList& query_stems) {
         {"news", {"new", "feed", "rss", "headlin", "articl", "file", "edgar"}},
         {"report-builder", {"report", "builder", "generat", "document", "templat", "pdf"}},
         {"quant-lab", {"quant", "factor", "backtest", "alpha", "risk", "metric", "sharp", "var"}},
+        {"algo-trading",
+         {"algo", "strategi", "walk", "forward", "overfit", "fold", "backtest", "sweep", "heat", "paramet",
+          "tick"}},
         {"markets", {"market", "quot", "pric", "stock", "equity", "ticker", "chart", "ohlc"}},
         {"notes", {"not", "memo", "mind", "journal", "writ"}},
         {"file_manager", {"fil", "folder", "directory", "path", "open", "read", "writ"}},
@@ -4,22 +4,29 @@
 //   • discovery / introspection (sync + signal-bridged)
 //   • agent execution (run / structured / route / multi-query)
 //   • financial workflows (stock analysis, portfolio rebalance/risk)
+//   • bull / bear / risk debate with stored verdicts
 //   • teams + workflows execution
 //   • planner CRUD + execute
 //   • memory + knowledge
-//   • config CRUD (AgentConfigRepository — sync)
+//   • config CRUD (AgentConfigRepository — sync) + per-agent LLM pin
 //   • workflow CRUD (WorkflowRepository — sync)
 //   • cache ops (sync)
+//   • run history: list / get / rerun / delete (AgentRunRepository)
+//   • debate verdicts: list / delete (AgentDebateRepository)
 //
 // AgentService methods that return a request_id and emit a `*_result` signal
 // later are wrapped with a per-request signal filter so concurrent callers
 // don't cross results.
 //
-// The tool definitions are partitioned across three sibling TUs:
+// The tool definitions are partitioned across four sibling TUs:
 //   - AgentsTools_Discovery.cpp  — list / discover / introspection / cache
-//   - AgentsTools_Execution.cpp  — run_agent + routing + workflows + streaming
+//   - AgentsTools_Execution.cpp  — run_agent + routing + workflows + debate +
+//                                  streaming
 //   - AgentsTools_Repos.cpp      — planner + memory + config/workflow CRUD +
-//                                  sessions + paper trading + decisions
+//                                  agent LLM + sessions + paper trading +
+//                                  decisions
+//   - AgentsTools_History.cpp    — agent run history, cache lookups, rerun,
+//                                  debate verdicts
 // Shared helpers (JSON converters, dispatch_agent_run) live in
 // AgentsTools_internal.h.
 
@@ -37,6 +44,7 @@ std::vector<ToolDef> get_agents_tools() {
     agents_internal::register_discovery_tools(tools);
     agents_internal::register_execution_tools(tools);
     agents_internal::register_repos_tools(tools);
+    agents_internal::register_history_tools(tools);
     LOG_INFO(agents_internal::TAG, QString("Defined %1 agents tools").arg(tools.size()));
     return tools;
 }
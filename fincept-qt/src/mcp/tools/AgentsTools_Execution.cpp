@@ -2,7 +2,8 @@
 //
 // Execution path: run_agent / run_agent_structured / route_agent_query /
 // execute_multi_agent_query, financial workflows (stock / portfolio / risk),
-// run_agent_team / run_agent_workflow, streaming agent, execute_routed_agent_query.
+// run_agent_debate, run_agent_team / run_agent_workflow, streaming agent,
+// execute_routed_agent_query.
 //
 // Part of the topic-based split of AgentsTools.cpp.
 
@@ -23,6 +24,8 @@
 #include <QTimer>
 #include <QUuid>
 
+#include <algorithm>
+
 namespace fincept::mcp::tools {
 
 using namespace fincept::mcp::tools::agents_internal;
@@ -209,6 +212,65 @@ void agents_internal::register_execution_tools(std::vector<ToolDef>& tools) {
         tools.push_back(std::move(t));
     }
 
+    {
+        ToolDef t;
+        t.name = "run_agent_debate";
+        t.description = "Bull, bear and risk agents debate a ticker concurrently for a bounded number of rebuttal "
+                        "rounds, then a judge returns a structured verdict: direction, conviction (0-1), key "
+                        "evidence and dissent. The verdict is stored per ticker and date and feeds the "
+                        "screener's DEBATE factor.";
+        t.category = "agents";
+        t.is_destructive = true;
+        t.default_timeout_ms = 2 * kDefaultAgentTimeoutMs;
+        t.input_schema = ToolSchemaBuilder()
+                             .string("ticker", "Ticker symbol (e.g. AAPL)")
+                             .required()
+                             .length(1, 16)
+                             .integer("rounds", "Rebuttal rounds after the opening statements; stops early "
+                                                "once no agent changes stance")
+                             .default_int(2)
+                             .between(0, 4)
+                             .string("context", "Optional thesis or question to frame the debate")
+                             .length(0, 2000)
+                             .object("config", "Optional agent config override (e.g. model)")
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString ticker = args["ticker"].toString().trimmed().toUpper();
+            const int rounds = std::clamp(args["rounds"].toInt(2), 0, 4);
+            const QString context = args["context"].toString();
+            const QJsonObject config = args["config"].toObject();
+            auto* svc = &services::AgentService::instance();
+            AsyncDispatch::callback_to_promise(
+                svc, std::move(ctx), promise, [svc, ticker, rounds, context, config](auto resolve) {
+                    const QString req_id = svc->run_debate(ticker, config, rounds, context);
+                    auto* holder = new QObject(svc);
+                    QObject::connect(svc, &services::AgentService::agent_result, holder,
+                                     [req_id, ticker, resolve, holder](services::AgentExecutionResult r) {
+                                         if (r.request_id != req_id)
+                                             return;
+                                         holder->deleteLater();
+                                         if (!r.success) {
+                                             resolve(ToolResult::fail(r.error.isEmpty() ? "Debate failed" : r.error));
+                                             return;
+                                         }
+                                         auto latest = AgentDebateRepository::instance().list(ticker, {}, 1);
+                                         if (latest.is_err() || latest.value().isEmpty()) {
+                                             resolve(ToolResult::ok(r.response, result_to_json(r)));
+                                             return;
+                                         }
+                                         resolve(ToolResult::ok(r.response,
+                                                                debate_verdict_to_json(latest.value().first(), true)));
+                                     });
+                    QObject::connect(svc, &services::AgentService::error_occurred, holder,
+                                     [resolve, holder](QString, QString msg) {
+                                         resolve(ToolResult::fail(msg));
+                                         holder->deleteLater();
+                                     });
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
     {
         ToolDef t;
         t.name = "run_portfolio_rebalancing_agent";
@@ -0,0 +1,246 @@
+// src/mcp/tools/AgentsTools_History.cpp
+//
+// Run history: list / search / get stored agent runs (agent_runs), compare
+// runs with identical inputs, re-run one with the same inputs (optionally
+// served from a recent identical run), delete; stored debate verdicts
+// (agent_debate_verdicts).
+//
+// Part of the topic-based split of AgentsTools.cpp.
+
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "mcp/tools/AgentsTools.h"
+#include "mcp/tools/AgentsTools_internal.h"
+#include "services/agents/AgentService.h"
+#include "storage/repositories/AgentDebateRepository.h"
+#include "storage/repositories/AgentRunRepository.h"
+
+#include <QDate>
+#include <QDateTime>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+
+#include <algorithm>
+
+namespace fincept::mcp::tools {
+
+using namespace fincept::mcp::tools::agents_internal;
+
+void agents_internal::register_history_tools(std::vector<ToolDef>& tools) {
+    // ── list_agent_runs ────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "list_agent_runs";
+        t.description = "Stored agent runs, newest first: query, kind, provider/model, status, token usage, "
+                        "latency and a response preview. Filter by text (query, response or agent name), kind, "
+                        "status, or same_inputs_as=<run id> to line up every run with identical inputs.";
+        t.category = "agents";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("text", "Substring of the query, response or agent name")
+                             .length(0, 512)
+                             .string("kind", "Execution kind")
+                             .enums({"run", "stream", "structured", "team"})
+                             .string("status", "Run status")
+                             .enums({"success", "error", "running"})
+                             .string("same_inputs_as", "Run id whose inputs the runs must match")
+                             .length(0, 128)
+                             .integer("days", "Look back this many days")
+                             .default_int(30)
+                             .between(1, 3650)
+                             .integer("limit", "Maximum runs")
+                             .default_int(50)
+                             .between(1, 500)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            auto& repo = AgentRunRepository::instance();
+            QString input_hash;
+            const QString same_as = args["same_inputs_as"].toString().trimmed();
+            if (!same_as.isEmpty()) {
+                const auto ref = repo.get(same_as);
+                if (!ref)
+                    return ToolResult::fail("Unknown agent run: " + same_as);
+                input_hash = ref->input_hash;
+            }
+            const int days = std::clamp(args["days"].toInt(30), 1, 3650);
+            const qint64 since = QDateTime::currentDateTimeUtc().addDays(-days).toMSecsSinceEpoch();
+            auto r = repo.list(args["text"].toString().trimmed(), args["kind"].toString(), args["status"].toString(),
+                               input_hash, since, std::clamp(args["limit"].toInt(50), 1, 500));
+            if (r.is_err())
+                return ToolResult::fail(QString::fromStdString(r.error()));
+            QJsonArray arr;
+            for (const auto& run : r.value())
+                arr.append(run_record_to_json(run, false));
+            return ToolResult::ok_data(QJsonObject{{"runs", arr}, {"count", arr.size()}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── get_agent_run ──────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "get_agent_run";
+        t.description = "One stored agent run in full: query, config (credentials stripped), output, usage.";
+        t.category = "agents";
+        t.input_schema = ToolSchemaBuilder().string("id", "Run id (request_id)").required().length(1, 128).build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const auto run = AgentRunRepository::instance().get(args["id"].toString().trimmed());
+            if (!run)
+                return ToolResult::fail("Unknown agent run: " + args["id"].toString());
+            return ToolResult::ok_data(run_record_to_json(*run, true));
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── rerun_agent_run ────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "rerun_agent_run";
+        t.description = "Run a stored agent run again with the same kind, query, config and output model. With "
+                        "max_cache_age_min > 0 a successful run with identical inputs that recent is returned "
+                        "instead (cached=true) without calling the model.";
+        t.category = "agents";
+        t.is_destructive = true;
+        t.default_timeout_ms = kDefaultAgentTimeoutMs;
+        t.input_schema = ToolSchemaBuilder()
+                             .string("id", "Run id to repeat")
+                             .required()
+                             .length(1, 128)
+                             .integer("max_cache_age_min", "Serve an identical successful run this recent (0 = off)")
+                             .default_int(0)
+                             .between(0, 525600)
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString run_id = args["id"].toString().trimmed();
+            const int max_age_min = std::max(0, args["max_cache_age_min"].toInt(0));
+            auto* svc = &services::AgentService::instance();
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, run_id, max_age_min](auto resolve) {
+                auto& repo = AgentRunRepository::instance();
+                const auto prev = repo.get(run_id);
+                if (!prev) {
+                    resolve(ToolResult::fail("Unknown agent run: " + run_id));
+                    return;
+                }
+                if (max_age_min > 0) {
+                    const qint64 since = QDateTime::currentMSecsSinceEpoch() - qint64(max_age_min) * 60 * 1000;
+                    if (const auto hit = repo.find_cached(prev->input_hash, since)) {
+                        QJsonObject data = run_record_to_json(*hit, true);
+                        data["cached"] = true;
+                        resolve(ToolResult::ok(hit->response, data));
+                        return;
+                    }
+                }
+                const QString req_id = svc->rerun(run_id);
+                if (req_id.isEmpty()) {
+                    resolve(ToolResult::fail("Failed to start agent run"));
+                    return;
+                }
+                // Streaming and team runs finish on agent_stream_done, the rest on agent_result.
+                auto* holder = new QObject(svc);
+                auto on_done = [req_id, run_id, resolve, holder](services::AgentExecutionResult r) {
+                    if (r.request_id != req_id)
+                        return;
+                    if (r.success) {
+                        QJsonObject data = result_to_json(r);
+                        data["cached"] = false;
+                        data["rerun_of"] = run_id;
+                        resolve(ToolResult::ok(r.response.isEmpty() ? "OK" : r.response, data));
+                    } else {
+                        resolve(ToolResult::fail(r.error.isEmpty() ? "Agent run failed" : r.error));
+                    }
+                    holder->deleteLater();
+                };
+                QObject::connect(svc, &services::AgentService::agent_result, holder, on_done);
+                QObject::connect(svc, &services::AgentService::agent_stream_done, holder, on_done);
+                QObject::connect(svc, &services::AgentService::error_occurred, holder,
+                                 [resolve, holder](QString, QString msg) {
+                                     resolve(ToolResult::fail(msg));
+                                     holder->deleteLater();
+                                 });
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── delete_agent_run ───────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "delete_agent_run";
+        t.description = "Delete a stored agent run by id.";
+        t.category = "agents";
+        t.is_destructive = true;
+        t.input_schema = ToolSchemaBuilder().string("id", "Run id").required().length(1, 128).build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString id = args["id"].toString().trimmed();
+            auto r = AgentRunRepository::instance().remove(id);
+            if (r.is_err())
+                return ToolResult::fail(QString::fromStdString(r.error()));
+            return ToolResult::ok("Deleted agent run " + id);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── list_debate_verdicts ───────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "list_debate_verdicts";
+        t.description = "Stored bull / bear / risk debate verdicts, newest first: direction, conviction, signed "
+                        "score (+bullish / -bearish), key evidence and dissent. Filter by ticker; "
+                        "include_transcript adds the round-by-round arguments.";
+        t.category = "agents";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("ticker", "Ticker symbol; empty for all")
+                             .length(0, 16)
+                             .integer("days", "Look back this many days")
+                             .default_int(30)
+                             .between(1, 3650)
+                             .integer("limit", "Maximum verdicts")
+                             .default_int(50)
+                             .between(1, 500)
+                             .boolean("include_transcript", "Include the debate transcript")
+                             .default_bool(false)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const int days = std::clamp(args["days"].toInt(30), 1, 3650);
+            const QString since = QDate::currentDate().addDays(-days).toString(Qt::ISODate);
+            auto r = AgentDebateRepository::instance().list(args["ticker"].toString().trimmed().toUpper(), since,
+                                                            std::clamp(args["limit"].toInt(50), 1, 500));
+            if (r.is_err())
+                return ToolResult::fail(QString::fromStdString(r.error()));
+            const bool full = args["include_transcript"].toBool(false);
+            QJsonArray arr;
+            for (const auto& v : r.value())
+                arr.append(debate_verdict_to_json(v, full));
+            return ToolResult::ok_data(QJsonObject{{"verdicts", arr}, {"count", arr.size()}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── delete_debate_verdict ──────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "delete_debate_verdict";
+        t.description = "Delete the stored debate verdict for a ticker on a date (yyyy-MM-dd).";
+        t.category = "agents";
+        t.is_destructive = true;
+        t.input_schema = ToolSchemaBuilder()
+                             .string("ticker", "Ticker symbol")
+                             .required()
+                             .length(1, 16)
+                             .string("as_of_date", "Verdict date, yyyy-MM-dd")
+                             .required()
+                             .length(10, 10)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString ticker = args["ticker"].toString().trimmed().toUpper();
+            const QString date = args["as_of_date"].toString().trimmed();
+            auto r = AgentDebateRepository::instance().remove(ticker, date);
+            if (r.is_err())
+                return ToolResult::fail(QString::fromStdString(r.error()));
+            return ToolResult::ok(QString("Deleted debate verdict %1 %2").arg(ticker, date));
+        };
+        tools.push_back(std::move(t));
+    }
+}
+
+} // namespace fincept::mcp::tools
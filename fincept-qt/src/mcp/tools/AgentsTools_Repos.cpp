@@ -1,8 +1,9 @@
 // src/mcp/tools/AgentsTools_Repos.cpp
 //
 // Repository / persistence tools: planner CRUD + execute, memory + knowledge,
-// AgentConfigRepository CRUD, WorkflowRepository CRUD, memory repo variants,
-// session management, paper trading, trade decisions.
+// AgentConfigRepository CRUD, per-agent LLM assignment, WorkflowRepository
+// CRUD, memory repo variants, session management, paper trading, trade
+// decisions.
 //
 // Part of the topic-based split of AgentsTools.cpp.
 
@@ -13,7 +14,11 @@
 #include "mcp/tools/AgentsTools_internal.h"
 #include "screens/node_editor/NodeEditorTypes.h"
 #include "services/agents/AgentService.h"
+#include "services/llm/LlmService.h"
+#include "services/llm/ProviderCatalog.h"
 #include "storage/repositories/AgentConfigRepository.h"
+#include "storage/repositories/LlmConfigRepository.h"
+#include "storage/repositories/LlmProfileRepository.h"
 #include "storage/repositories/WorkflowRepository.h"
 
 #include <QJsonArray>
@@ -428,6 +433,123 @@ void agents_internal::register_repos_tools(std::vector<ToolDef>& tools) {
         tools.push_back(std::move(t));
     }
 
+    // ── Per-agent LLM (LlmProfileRepository assignments) ───────────────
+    {
+        ToolDef t;
+        t.name = "get_agent_llm";
+        t.description = "Which LLM a saved agent runs on: its own assigned profile, or the one it inherits "
+                        "(agent default → global default profile → active provider). local=true for Ollama / "
+                        "llama.cpp models, which run offline at no API cost. API keys are not exposed.";
+        t.category = "agents";
+        t.input_schema = ToolSchemaBuilder().string("agent_id", "Agent config id").required().length(1, 128).build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString agent_id = args["agent_id"].toString();
+            const QString assigned = LlmProfileRepository::instance().get_assignment("agent", agent_id);
+            const auto p = ai_chat::LlmService::instance().resolve_profile("agent", agent_id);
+            return ToolResult::ok_data(QJsonObject{
+                {"agent_id", agent_id},
+                {"inherited", assigned.isEmpty()},
+                {"profile_id", p.profile_id},
+                {"profile_name", p.profile_name},
+                {"provider", p.provider},
+                {"model_id", p.model_id},
+                {"base_url", p.base_url},
+                {"temperature", p.temperature},
+                {"max_tokens", p.max_tokens},
+                {"local", ai_chat::ProviderCatalog::is_local(p.provider)},
+                {"configured", !p.provider.isEmpty()},
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    {
+        ToolDef t;
+        t.name = "set_agent_llm";
+        t.description = "Pin the LLM a saved agent runs on. Pass profile_id to reuse an existing profile, or "
+                        "provider + model_id to create one (key and base URL come from Settings > LLM Config; "
+                        "ollama / llama_cpp need no key). clear=true removes the pin so the agent inherits again.";
+        t.category = "agents";
+        t.is_destructive = true;
+        t.input_schema = ToolSchemaBuilder()
+                             .string("agent_id", "Agent config id")
+                             .required()
+                             .length(1, 128)
+                             .string("profile_id", "Existing LLM profile id")
+                             .length(0, 128)
+                             .string("provider", "Provider id, e.g. ollama, llama_cpp, openai, anthropic")
+                             .length(0, 64)
+                             .string("model_id", "Model id, e.g. llama3.3 or qwen2.5:14b")
+                             .length(0, 256)
+                             .string("base_url", "Endpoint override (default: the provider's Settings row)")
+                             .length(0, 512)
+                             .number("temperature", "Sampling temperature")
+                             .default_num(0.7)
+                             .between(0, 2)
+                             .integer("max_tokens", "Completion token cap")
+                             .default_int(4096)
+                             .between(1, 200000)
+                             .boolean("clear", "Remove the agent's pinned profile")
+                             .default_bool(false)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            auto& profiles = LlmProfileRepository::instance();
+            const QString agent_id = args["agent_id"].toString();
+            if (args["clear"].toBool(false)) {
+                const auto r = profiles.remove_assignment("agent", agent_id);
+                if (r.is_err())
+                    return ToolResult::fail(QString::fromStdString(r.error()));
+                return ToolResult::ok("Agent LLM pin removed", QJsonObject{{"agent_id", agent_id}});
+            }
+
+            QString profile_id = args["profile_id"].toString();
+            if (profile_id.isEmpty()) {
+                const QString provider = args["provider"].toString().trimmed().toLower();
+                const QString model_id = args["model_id"].toString().trimmed();
+                if (provider.isEmpty() || model_id.isEmpty())
+                    return ToolResult::fail("Pass profile_id, or provider and model_id");
+                if (ai_chat::ProviderCatalog::is_blocked(provider, args["base_url"].toString()))
+                    return ToolResult::fail("Provider is not available: " + provider);
+
+                LlmProfile p;
+                p.id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+                p.provider = provider;
+                p.model_id = model_id;
+                p.base_url = args["base_url"].toString().trimmed();
+                p.temperature = args["temperature"].toDouble(0.7);
+                p.max_tokens = args["max_tokens"].toInt(4096);
+                auto cfgs = LlmConfigRepository::instance().list_providers();
+                if (cfgs.is_ok()) {
+                    for (const auto& c : cfgs.value()) {
+                        if (c.provider.toLower() != provider)
+                            continue;
+                        p.api_key = c.api_key;
+                        if (p.base_url.isEmpty())
+                            p.base_url = c.base_url;
+                    }
+                }
+                if (p.base_url.isEmpty())
+                    p.base_url = ai_chat::ProviderCatalog::default_base_url(provider);
+                if (p.api_key.isEmpty() && ai_chat::ProviderCatalog::requires_api_key(provider))
+                    return ToolResult::fail("No API key for " + provider + " — add it in Settings > LLM Config");
+                auto agent = AgentConfigRepository::instance().get(agent_id);
+                p.name = QString("%1 · %2/%3").arg(agent.is_ok() ? agent.value().name : agent_id, provider, model_id);
+                const auto w = profiles.save_profile(p);
+                if (w.is_err())
+                    return ToolResult::fail(QString::fromStdString(w.error()));
+                profile_id = p.id;
+            } else if (profiles.get_profile(profile_id).is_err()) {
+                return ToolResult::fail("Unknown LLM profile: " + profile_id);
+            }
+
+            const auto r = profiles.assign_profile("agent", agent_id, profile_id);
+            if (r.is_err())
+                return ToolResult::fail(QString::fromStdString(r.error()));
+            return ToolResult::ok("Agent LLM set", QJsonObject{{"agent_id", agent_id}, {"profile_id", profile_id}});
+        };
+        tools.push_back(std::move(t));
+    }
+
     // ── 31-34. WorkflowRepository CRUD ─────────────────────────────────
     {
         ToolDef t;
@@ -13,7 +13,10 @@
 #include "screens/node_editor/NodeEditorTypes.h"
 #include "services/agents/AgentService.h"
 #include "storage/repositories/AgentConfigRepository.h"
+#include "storage/repositories/AgentDebateRepository.h"
+#include "storage/repositories/AgentRunRepository.h"
 
+#include <QDateTime>
 #include <QJsonArray>
 #include <QJsonObject>
 #include <QObject>
@@ -46,7 +49,7 @@ inline QJsonObject result_to_json(const services::AgentExecutionResult& r) {
     return QJsonObject{
         {"success", r.success},       {"response", r.response},
         {"error", r.error},           {"execution_time_ms", r.execution_time_ms},
-        {"request_id", r.request_id},
+        {"request_id", r.request_id}, {"usage", r.usage},
     };
 }
 
@@ -103,6 +106,61 @@ inline QJsonObject config_to_json(const AgentConfig& c) {
     };
 }
 
+// `full` = false trims the response to a preview and drops the config.
+inline QJsonObject run_record_to_json(const AgentRunRecord& r, bool full) {
+    auto iso = [](qint64 ms) {
+        return ms > 0 ? QJsonValue(QDateTime::fromMSecsSinceEpoch(ms).toString(Qt::ISODate)) : QJsonValue();
+    };
+    QJsonObject o{
+        {"id", r.id},
+        {"kind", r.kind},
+        {"query", r.query},
+        {"provider", r.provider},
+        {"model", r.model},
+        {"agent_name", r.agent_name},
+        {"input_hash", r.input_hash},
+        {"status", r.status},
+        {"error", r.error},
+        {"input_tokens", r.input_tokens},
+        {"output_tokens", r.output_tokens},
+        {"total_tokens", r.total_tokens},
+        {"latency_ms", r.latency_ms},
+        {"started_at", iso(r.started_at)},
+        {"finished_at", iso(r.finished_at)},
+        {"rerun_of", r.rerun_of.isEmpty() ? QJsonValue() : QJsonValue(r.rerun_of)},
+    };
+    if (!r.output_model.isEmpty())
+        o["output_model"] = r.output_model;
+    if (full) {
+        o["response"] = r.response;
+        o["config"] = r.config;
+    } else {
+        o["response_preview"] = r.response.left(300);
+    }
+    return o;
+}
+
+// `full` = false drops the round-by-round transcript.
+inline QJsonObject debate_verdict_to_json(const DebateVerdict& v, bool full) {
+    QJsonObject o{
+        {"ticker", v.ticker},
+        {"as_of_date", v.as_of_date},
+        {"direction", v.direction},
+        {"conviction", v.conviction},
+        {"score", v.score},
+        {"key_evidence", v.key_evidence},
+        {"dissent", v.dissent},
+        {"summary", v.summary},
+        {"rounds", v.rounds},
+        {"provider", v.provider},
+        {"model", v.model},
+        {"created_at", QDateTime::fromMSecsSinceEpoch(v.created_at).toString(Qt::ISODate)},
+    };
+    if (full)
+        o["transcript"] = v.transcript;
+    return o;
+}
+
 // Generic kicker: invoke a member fn that returns request_id, bridge
 // agent_result(req_id) signal back to the promise.
 template <typename KickFn>
@@ -251,6 +309,7 @@ inline workflow::WorkflowDef json_to_workflow(const QJsonObject& j) {
 void register_discovery_tools(std::vector<ToolDef>& tools);
 void register_execution_tools(std::vector<ToolDef>& tools);
 void register_repos_tools(std::vector<ToolDef>& tools);
+void register_history_tools(std::vector<ToolDef>& tools);
 
 } // namespace agents_internal
 
@@ -1,10 +1,12 @@
-// AiChatTools.cpp — AI Chat tab MCP tools (session management)
+// AiChatTools.cpp — AI Chat tab MCP tools (session management, context window)
 
 #include "mcp/tools/AiChatTools.h"
 
 #include "core/events/EventBus.h"
 #include "core/logging/Logger.h"
 #include "mcp/ToolSchemaBuilder.h"
+#include "services/llm/ChatMemoryManager.h"
+#include "services/llm/LlmService.h"
 #include "storage/repositories/ChatRepository.h"
 
 #include <QVariantMap>
@@ -66,6 +68,58 @@ std::vector<ToolDef> get_ai_chat_tools() {
         tools.push_back(std::move(t));
     }
 
+    // ── get_chat_context_window ─────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "get_chat_context_window";
+        t.description = "Get the history a chat session would send to a model on its next message: the rolling "
+                        "summary of older turns plus the newest turns that fit the model's context budget, with "
+                        "token estimates. Defaults to the active LLM provider and model.";
+        t.category = "ai-chat";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("session_id", "Chat session id")
+                             .required()
+                             .length(1, 64)
+                             .string("provider", "LLM provider id (default: active provider)")
+                             .length(0, 64)
+                             .string("model", "Model id (default: active model)")
+                             .length(0, 200)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString session_id = args["session_id"].toString().trimmed();
+            auto& llm = ai_chat::LlmService::instance();
+            const QString provider = args["provider"].toString().trimmed().isEmpty()
+                                         ? llm.active_provider()
+                                         : args["provider"].toString().trimmed().toLower();
+            const QString model =
+                args["model"].toString().trimmed().isEmpty() ? llm.active_model() : args["model"].toString().trimmed();
+
+            auto session = ChatRepository::instance().get_session(session_id);
+            if (session.is_err())
+                return ToolResult::fail("Chat session not found: " + session_id);
+            auto r = ai_chat::ChatMemoryManager::instance().get_context_window(session_id, provider, model);
+            if (r.is_err())
+                return ToolResult::fail("Failed to build context window: " + QString::fromStdString(r.error()));
+
+            const auto& w = r.value();
+            QJsonArray messages;
+            for (const auto& m : w.messages)
+                messages.append(QJsonObject{{"role", m.role}, {"content", m.content}});
+            return ToolResult::ok_data(QJsonObject{{"session_id", session_id},
+                                                   {"provider", provider},
+                                                   {"model", model},
+                                                   {"budget_tokens", w.budget_tokens},
+                                                   {"used_tokens", w.used_tokens},
+                                                   {"total_messages", w.total_messages},
+                                                   {"summarized_messages", w.summarized_messages},
+                                                   {"included_messages", w.included_messages},
+                                                   {"dropped_messages", w.dropped_messages},
+                                                   {"summary", w.summary},
+                                                   {"messages", messages}});
+        };
+        tools.push_back(std::move(t));
+    }
+
     return tools;
 }
 
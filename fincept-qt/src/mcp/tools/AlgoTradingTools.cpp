@@ -0,0 +1,1538 @@
+// AlgoTradingTools.cpp — Algo Trading tab MCP tools (native backtest commands, strategy analysis, kill switch,
+// crash recovery)
+
+#include "mcp/tools/AlgoTradingTools.h"
+
+#include "algo_engine/AlgoEngine.h"
+#include "algo_engine/CandleDataFetcher.h"
+#include "algo_engine/PineImporter.h"
+#include "algo_engine/RegimeMonitor.h"
+#include "algo_engine/StrategyAnalyzer.h"
+#include "core/logging/Logger.h"
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "services/algo_trading/AlgoTradingService.h"
+#include "services/options/VolatilityEstimators.h"
+#include "storage/HistoricalDataStore.h"
+
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+#include <QTimer>
+#include <QUuid>
+
+#include <algorithm>
+#include <cmath>
+#include <optional>
+
+namespace fincept::mcp::tools {
+
+static constexpr const char* TAG = "AlgoTradingTools";
+
+// A saved strategy by `strategy_id`, or an inline `strategy` object in the
+// builder's condition schema.
+static bool strategy_from_args(const QJsonObject& args, services::algo::AlgoStrategy& out, QString* error) {
+    const QString id = args["strategy_id"].toString().trimmed();
+    if (!id.isEmpty()) {
+        const auto found = services::algo::AlgoTradingService::instance().strategy_by_id(id);
+        if (!found) {
+            *error = QString("Strategy '%1' not found").arg(id);
+            return false;
+        }
+        out = *found;
+        return true;
+    }
+    const QJsonObject s = args["strategy"].toObject();
+    if (s["entry_conditions"].toArray().isEmpty()) {
+        *error = QStringLiteral("Provide 'strategy_id' or a 'strategy' with entry_conditions");
+        return false;
+    }
+    out.name = s["name"].toString(QStringLiteral("Inline strategy"));
+    out.timeframe = s["timeframe"].toString(QStringLiteral("1d"));
+    out.entry_conditions = s["entry_conditions"].toArray();
+    out.exit_conditions = s["exit_conditions"].toArray();
+    out.entry_logic = s["entry_logic"].toString(QStringLiteral("AND"));
+    out.exit_logic = s["exit_logic"].toString(QStringLiteral("AND"));
+    out.stop_loss = s["stop_loss"].toDouble();
+    out.take_profit = s["take_profit"].toDouble();
+    out.trailing_stop = s["trailing_stop"].toDouble();
+    out.position_size_pct = s["position_size_pct"].toDouble(100.0);
+    return true;
+}
+
+// Both walk-forward commands share one service call; `mode` is forced for
+// algo_cross_validate and taken from the args otherwise.
+static void walk_forward_handler(const QJsonObject& args, ToolContext ctx,
+                                 std::shared_ptr<QPromise<ToolResult>> promise, const QString& mode) {
+    auto fail = [promise](const QString& msg) {
+        promise->addResult(ToolResult::fail(msg));
+        promise->finish();
+    };
+    const QString symbol = args["symbol"].toString().trimmed();
+    if (symbol.isEmpty())
+        return fail("Missing 'symbol'");
+    services::algo::AlgoStrategy strategy;
+    QString err;
+    if (!strategy_from_args(args, strategy, &err))
+        return fail(err);
+
+    QJsonObject config = args;
+    for (const char* key : {"symbol", "strategy_id", "strategy", "start_date", "end_date", "capital"})
+        config.remove(key);
+    if (!mode.isEmpty())
+        config["mode"] = mode;
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    config["request_id"] = request_id;
+
+    const QString start = args["start_date"].toString();
+    const QString end = args["end_date"].toString();
+    const double capital = args["capital"].toDouble(100000.0);
+    LOG_INFO(TAG, QString("Walk-forward requested: %1 on %2").arg(strategy.name, symbol));
+    auto* svc = &services::algo::AlgoTradingService::instance();
+    AsyncDispatch::callback_to_promise(
+        svc, std::move(ctx), promise, [svc, strategy, symbol, start, end, capital, config, request_id](auto resolve) {
+            auto* holder = new QObject(svc);
+            QObject::connect(svc, &services::algo::AlgoTradingService::walk_forward_result, holder,
+                             [resolve, holder, request_id](QJsonObject data) {
+                                 if (data.value("request_id").toString() != request_id)
+                                     return;
+                                 data.remove("request_id");
+                                 resolve(ToolResult::ok_data(data));
+                                 holder->deleteLater();
+                             });
+            QObject::connect(svc, &services::algo::AlgoTradingService::error_occurred, holder,
+                             [resolve, holder](QString context, QString msg) {
+                                 if (context != QLatin1String("walk_forward"))
+                                     return;
+                                 resolve(ToolResult::fail(msg));
+                                 holder->deleteLater();
+                             });
+            svc->run_walk_forward(strategy, symbol, start, end, capital, config);
+        });
+}
+
+static void parameter_sweep_handler(const QJsonObject& args, ToolContext ctx,
+                                    std::shared_ptr<QPromise<ToolResult>> promise) {
+    auto fail = [promise](const QString& msg) {
+        promise->addResult(ToolResult::fail(msg));
+        promise->finish();
+    };
+    const QString symbol = args["symbol"].toString().trimmed();
+    if (symbol.isEmpty())
+        return fail("Missing 'symbol'");
+    if (args["param_grid"].toObject().isEmpty())
+        return fail("Missing 'param_grid'");
+    services::algo::AlgoStrategy strategy;
+    QString err;
+    if (!strategy_from_args(args, strategy, &err))
+        return fail(err);
+
+    QJsonObject config = args;
+    for (const char* key : {"symbol", "strategy_id", "strategy", "start_date", "end_date", "capital", "top"})
+        config.remove(key);
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    config["request_id"] = request_id;
+
+    const QString start = args["start_date"].toString();
+    const QString end = args["end_date"].toString();
+    const double capital = args["capital"].toDouble(100000.0);
+    const int top = args["top"].toInt(25);
+    LOG_INFO(TAG, QString("Parameter sweep requested: %1 on %2").arg(strategy.name, symbol));
+    auto* svc = &services::algo::AlgoTradingService::instance();
+    AsyncDispatch::callback_to_promise(
+        svc, std::move(ctx), promise,
+        [svc, strategy, symbol, start, end, capital, config, request_id, top](auto resolve) {
+            auto* holder = new QObject(svc);
+            QObject::connect(svc, &services::algo::AlgoTradingService::parameter_sweep_result, holder,
+                             [resolve, holder, request_id, top](QJsonObject data) {
+                                 if (data.value("request_id").toString() != request_id)
+                                     return;
+                                 data.remove("request_id");
+                                 // The full matrix is saved; algo_get_parameter_sweep returns it.
+                                 const QJsonArray results = data.value("results").toArray();
+                                 QJsonArray head;
+                                 for (int i = 0; i < std::min(top, int(results.size())); ++i)
+                                     head.append(results.at(i));
+                                 data["results"] = head;
+                                 resolve(ToolResult::ok_data(data));
+                                 holder->deleteLater();
+                             });
+            QObject::connect(svc, &services::algo::AlgoTradingService::error_occurred, holder,
+                             [resolve, holder](QString context, QString msg) {
+                                 if (context != QLatin1String("parameter_sweep"))
+                                     return;
+                                 resolve(ToolResult::fail(msg));
+                                 holder->deleteLater();
+                             });
+            svc->run_parameter_sweep(strategy, symbol, start, end, capital, config);
+        });
+}
+
+static void tick_backtest_handler(const QJsonObject& args, ToolContext ctx,
+                                  std::shared_ptr<QPromise<ToolResult>> promise) {
+    auto fail = [promise](const QString& msg) {
+        promise->addResult(ToolResult::fail(msg));
+        promise->finish();
+    };
+    const QString symbol = args["symbol"].toString().trimmed();
+    const QString exchange = args["exchange"].toString().trimmed();
+    if (symbol.isEmpty() || exchange.isEmpty())
+        return fail("Missing 'symbol' or 'exchange'");
+    services::algo::AlgoStrategy strategy;
+    QString err;
+    if (!strategy_from_args(args, strategy, &err))
+        return fail(err);
+
+    QJsonObject fill = args["fill_model"].toObject();
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    fill["request_id"] = request_id;
+    const QString start = args["start_date"].toString();
+    const QString end = args["end_date"].toString();
+    const double capital = args["capital"].toDouble(100000.0);
+    LOG_INFO(TAG, QString("Tick backtest requested: %1 on %2:%3").arg(strategy.name, symbol, exchange));
+    auto* svc = &services::algo::AlgoTradingService::instance();
+    AsyncDispatch::callback_to_promise(
+        svc, std::move(ctx), promise,
+        [svc, strategy, symbol, exchange, start, end, capital, fill, request_id](auto resolve) {
+            auto* holder = new QObject(svc);
+            QObject::connect(svc, &services::algo::AlgoTradingService::tick_backtest_result, holder,
+                             [resolve, holder, request_id](QJsonObject data) {
+                                 if (data.value("request_id").toString() != request_id)
+                                     return;
+                                 data.remove("request_id");
+                                 resolve(ToolResult::ok_data(data));
+                                 holder->deleteLater();
+                             });
+            QObject::connect(svc, &services::algo::AlgoTradingService::error_occurred, holder,
+                             [resolve, holder](QString context, QString msg) {
+                                 if (context != QLatin1String("tick_backtest"))
+                                     return;
+                                 resolve(ToolResult::fail(msg));
+                                 holder->deleteLater();
+                             });
+            svc->run_tick_backtest(strategy, symbol, exchange, start, end, capital, fill);
+        });
+}
+
+static constexpr int kReloadTimeoutMs = 15'000;
+
+static void reload_strategy_handler(const QJsonObject& args, ToolContext ctx,
+                                    std::shared_ptr<QPromise<ToolResult>> promise) {
+    const QString deployment_id = args["deployment_id"].toString().trimmed();
+    if (deployment_id.isEmpty()) {
+        promise->addResult(ToolResult::fail("Missing 'deployment_id'"));
+        promise->finish();
+        return;
+    }
+    // Neither strategy_id nor strategy → the deployment's own saved strategy.
+    std::optional<services::algo::AlgoStrategy> strategy;
+    if (args.contains("strategy_id") || args.contains("strategy")) {
+        services::algo::AlgoStrategy s;
+        QString err;
+        if (!strategy_from_args(args, s, &err)) {
+            promise->addResult(ToolResult::fail(err));
+            promise->finish();
+            return;
+        }
+        // An inline strategy without a timeframe keeps the deployment's.
+        if (args.contains("strategy") && !args["strategy"].toObject().contains("timeframe"))
+            s.timeframe.clear();
+        strategy = s;
+    }
+    auto* engine = &algo::AlgoEngine::instance();
+    AsyncDispatch::callback_to_promise(
+        engine, std::move(ctx), promise, [engine, deployment_id, strategy](auto resolve) {
+            auto* holder = new QObject(engine);
+            auto settle = [resolve, holder](ToolResult r) {
+                resolve(std::move(r));
+                holder->deleteLater();
+            };
+            QObject::connect(engine, &algo::AlgoEngine::strategy_reloaded, holder,
+                             [settle, deployment_id](const QString& id, bool ok, const QString& message) {
+                                 if (id != deployment_id)
+                                     return;
+                                 settle(ok ? ToolResult::ok(message, QJsonObject{{"deployment_id", id}})
+                                           : ToolResult::fail(message));
+                             });
+            // The runner answers between two ticks; if it stops or crashes
+            // first, or never gets there, report that instead of waiting on.
+            QObject::connect(engine, &algo::AlgoEngine::deployment_stopped, holder,
+                             [settle, deployment_id](const QString& id) {
+                                 if (id == deployment_id)
+                                     settle(ToolResult::fail("Deployment stopped before the reload was applied"));
+                             });
+            QObject::connect(engine, &algo::AlgoEngine::deployment_crashed, holder,
+                             [settle, deployment_id](const QString& id, const QString& reason) {
+                                 if (id == deployment_id)
+                                     settle(ToolResult::fail("Deployment crashed before the reload was applied: " +
+                                                             reason));
+                             });
+            QTimer::singleShot(kReloadTimeoutMs, holder, [settle]() {
+                settle(ToolResult::fail(QString("Reload not confirmed within %1 s").arg(kReloadTimeoutMs / 1000)));
+            });
+            if (strategy)
+                engine->reload_strategy(deployment_id, *strategy);
+            else
+                engine->reload_strategy(deployment_id);
+        });
+}
+
+static QJsonObject recovery_item_json(const algo::AlgoRecoveryItem& it) {
+    auto legs_json = [](const QHash<QString, double>& legs) {
+        QJsonObject o;
+        for (auto i = legs.cbegin(); i != legs.cend(); ++i)
+            o[i.key()] = i.value();
+        return o;
+    };
+    QJsonObject o{{"deployment_id", it.deployment_id},
+                  {"strategy_name", it.strategy_name},
+                  {"symbol", it.symbol},
+                  {"exchange", it.exchange},
+                  {"account_id", it.account_id},
+                  {"basket", it.basket},
+                  {"state", it.state},
+                  {"broker_checked", it.broker_checked},
+                  {"open_order_ids", QJsonArray::fromStringList(it.open_order_ids)},
+                  {"inflight_orders", it.inflight_orders},
+                  {"notes", QJsonArray::fromStringList(it.notes)}};
+    if (it.basket) {
+        o["recorded_legs"] = legs_json(it.recorded_legs);
+        o["broker_legs"] = legs_json(it.broker_legs);
+    } else {
+        o["recorded"] = QJsonObject{{"side", it.recorded_side},
+                                    {"quantity", it.recorded_qty},
+                                    {"entry_price", it.recorded_entry}};
+        o["broker"] = QJsonObject{{"net_quantity", it.broker_qty}, {"avg_price", it.broker_avg_price}};
+    }
+    if (!it.error.isEmpty())
+        o["error"] = it.error;
+    return o;
+}
+
+static void resolve_recovery_handler(const QJsonObject& args, ToolContext ctx,
+                                     std::shared_ptr<QPromise<ToolResult>> promise) {
+    const QString deployment_id = args["deployment_id"].toString().trimmed();
+    const QString action = args["action"].toString();
+    if (deployment_id.isEmpty()) {
+        promise->addResult(ToolResult::fail("Missing 'deployment_id'"));
+        promise->finish();
+        return;
+    }
+    auto* engine = &algo::AlgoEngine::instance();
+    AsyncDispatch::callback_to_promise(
+        engine, std::move(ctx), promise, [engine, deployment_id, action](auto resolve) {
+            auto* holder = new QObject(engine);
+            QObject::connect(engine, &algo::AlgoEngine::recovery_resolved, holder,
+                             [resolve, holder, deployment_id](const QString& id, const QString& act, bool ok,
+                                                              const QString& message) {
+                                 if (id != deployment_id)
+                                     return;
+                                 resolve(ok ? ToolResult::ok(message, QJsonObject{{"deployment_id", id},
+                                                                                  {"action", act}})
+                                            : ToolResult::fail(message));
+                                 holder->deleteLater();
+                             });
+            engine->resolve_recovery(deployment_id, action);
+        });
+}
+
+static void portfolio_backtest_handler(const QJsonObject& args, ToolContext ctx,
+                                       std::shared_ptr<QPromise<ToolResult>> promise) {
+    auto fail = [promise](const QString& msg) {
+        promise->addResult(ToolResult::fail(msg));
+        promise->finish();
+    };
+    const QJsonArray items = args["sleeves"].toArray();
+    if (items.isEmpty())
+        return fail("Missing 'sleeves'");
+    QVector<services::algo::AlgoTradingService::PortfolioSleeveSpec> sleeves;
+    for (int i = 0; i < items.size(); ++i) {
+        const QJsonObject item = items[i].toObject();
+        services::algo::AlgoTradingService::PortfolioSleeveSpec spec;
+        spec.symbol = item["symbol"].toString().trimmed();
+        spec.weight = item["weight"].toDouble(0.0);
+        QString err;
+        if (spec.symbol.isEmpty())
+            return fail(QString("Sleeve %1: missing 'symbol'").arg(i));
+        if (!strategy_from_args(item, spec.strategy, &err))
+            return fail(QString("Sleeve %1: %2").arg(i).arg(err));
+        sleeves.append(spec);
+    }
+
+    QJsonObject allocation = args["allocation"].toObject();
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    allocation["request_id"] = request_id;
+    const QString start = args["start_date"].toString();
+    const QString end = args["end_date"].toString();
+    const double capital = args["capital"].toDouble(100000.0);
+    LOG_INFO(TAG, QString("Portfolio backtest requested: %1 sleeve(s)").arg(sleeves.size()));
+    auto* svc = &services::algo::AlgoTradingService::instance();
+    AsyncDispatch::callback_to_promise(
+        svc, std::move(ctx), promise, [svc, sleeves, start, end, capital, allocation, request_id](auto resolve) {
+            auto* holder = new QObject(svc);
+            QObject::connect(svc, &services::algo::AlgoTradingService::portfolio_backtest_result, holder,
+                             [resolve, holder, request_id](QJsonObject data) {
+                                 if (data.value("request_id").toString() != request_id)
+                                     return;
+                                 data.remove("request_id");
+                                 resolve(ToolResult::ok_data(data));
+                                 holder->deleteLater();
+                             });
+            QObject::connect(svc, &services::algo::AlgoTradingService::error_occurred, holder,
+                             [resolve, holder](QString context, QString msg) {
+                                 if (context != QLatin1String("portfolio_backtest"))
+                                     return;
+                                 resolve(ToolResult::fail(msg));
+                                 holder->deleteLater();
+                             });
+            svc->run_portfolio_backtest(sleeves, start, end, capital, allocation);
+        });
+}
+
+std::vector<ToolDef> get_algo_trading_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── algo_inspect_backtest_trade ────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_inspect_backtest_trade";
+        t.description = "Why did this trade happen: drill into one trade of the most recent backtest. Returns the "
+                        "candles around it, the entry and exit rule trees evaluated in full on the signal bars and "
+                        "every referenced indicator's value at signal and fill. Stop / target exits carry the "
+                        "trigger instead of rules.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .integer("trade_index", "Index into the backtest's trades array (0 = first trade)")
+                             .required()
+                             .min(0)
+                             .integer("context_bars", "Bars to include either side of the trade")
+                             .default_int(20)
+                             .between(0, 500)
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const int trade_index = args["trade_index"].toInt();
+            const int context_bars = args["context_bars"].toInt(20);
+            auto* svc = &services::algo::AlgoTradingService::instance();
+            AsyncDispatch::callback_to_promise(
+                svc, std::move(ctx), promise, [svc, trade_index, context_bars](auto resolve) {
+                    auto* holder = new QObject(svc);
+                    // Replies are matched on the request id: the screen and
+                    // other callers inspect trades through the same signals.
+                    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+                    QObject::connect(svc, &services::algo::AlgoTradingService::trade_inspection, holder,
+                                     [resolve, holder, request_id](QJsonObject data) {
+                                         if (data.value("request_id").toString() != request_id)
+                                             return;
+                                         data.remove("request_id");
+                                         resolve(ToolResult::ok_data(data));
+                                         holder->deleteLater();
+                                     });
+                    QObject::connect(svc, &services::algo::AlgoTradingService::error_occurred, holder,
+                                     [resolve, holder, request_id](QString context, QString msg) {
+                                         if (context != QLatin1String("inspect_trade:") + request_id)
+                                             return;
+                                         resolve(ToolResult::fail(msg));
+                                         holder->deleteLater();
+                                     });
+                    svc->inspect_backtest_trade(trade_index, context_bars, request_id);
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    const QString strategy_desc = "Inline strategy when no strategy_id: {timeframe, entry_conditions, "
+                                  "exit_conditions, entry_logic, exit_logic, stop_loss, take_profit, "
+                                  "trailing_stop, position_size_pct} in the Strategy Builder condition schema";
+    const QString grid_paths = "Paths: stop_loss, take_profit, trailing_stop, position_size_pct, entry_logic, "
+                               "exit_logic, entry.<i>.params.<name>, entry.<i>.value, exit.<i>.… ; join paths "
+                               "with | to tie them, e.g. {\"entry.0.params.period|exit.0.params.period\": "
+                               "[10, 20, 50]}";
+    const QString grid_desc = "Parameter path → candidate values, re-optimised on every train window. " + grid_paths;
+    const QStringList objectives = {"sharpe_ratio", "sortino",  "calmar",     "total_return",
+                                    "profit_factor", "win_rate", "expectancy", "max_drawdown"};
+
+    // ── algo_walk_forward ──────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_walk_forward";
+        t.description = "Walk-forward analysis of an algo strategy: rolling or anchored train/test windows, the "
+                        "parameter grid re-optimised on each train window and scored on the following unseen "
+                        "test window. Returns per-window in/out-of-sample metrics, compounded out-of-sample "
+                        "return and stitched equity, walk-forward efficiency, parameter stability and a "
+                        "fixed-parameter baseline — the overfitting check a single backtest cannot give.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("symbol", "Symbol to backtest")
+                             .required()
+                             .string("strategy_id", "Saved strategy id (see the Algo Trading strategy list)")
+                             .object("strategy", strategy_desc)
+                             .string("start_date", "History start, yyyy-MM-dd (default: 1 year back)")
+                             .string("end_date", "History end, yyyy-MM-dd")
+                             .number("capital", "Initial capital per window")
+                             .default_num(100000.0)
+                             .string("mode", "Window scheme")
+                             .enums({"rolling", "anchored"})
+                             .default_str("rolling")
+                             .integer("windows", "Out-of-sample windows when bar counts are derived")
+                             .default_int(5)
+                             .between(2, 100)
+                             .integer("train_bars", "Train window length in bars (default: derived from train_pct)")
+                             .integer("test_bars", "Test window length in bars (default: derived)")
+                             .integer("step_bars", "Bars between window starts (default: test_bars)")
+                             .number("train_pct", "Train share of a train+test span when deriving lengths")
+                             .default_num(70.0)
+                             .object("param_grid", grid_desc)
+                             .string("objective", "Train-window selection metric")
+                             .enums(objectives)
+                             .default_str("sharpe_ratio")
+                             .integer("min_trades", "Train trades a combination needs to be preferred")
+                             .default_int(1)
+                             .integer("max_combinations", "Reject grids larger than this")
+                             .default_int(200)
+                             .build();
+        t.default_timeout_ms = 600000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString mode = args["mode"].toString(QStringLiteral("rolling"));
+            walk_forward_handler(args, std::move(ctx), promise, mode == "anchored" ? mode : QStringLiteral("rolling"));
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_cross_validate ────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_cross_validate";
+        t.description = "K-fold cross-validation of an algo strategy: the history is cut into contiguous folds, "
+                        "each fold is held out once while the parameter grid is scored on the others (with an "
+                        "optional embargo around the held-out fold). Returns per-fold out-of-sample metrics and "
+                        "the same aggregates as algo_walk_forward.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("symbol", "Symbol to backtest")
+                             .required()
+                             .string("strategy_id", "Saved strategy id (see the Algo Trading strategy list)")
+                             .object("strategy", strategy_desc)
+                             .string("start_date", "History start, yyyy-MM-dd (default: 1 year back)")
+                             .string("end_date", "History end, yyyy-MM-dd")
+                             .number("capital", "Initial capital per fold")
+                             .default_num(100000.0)
+                             .integer("folds", "Number of folds")
+                             .default_int(5)
+                             .between(2, 20)
+                             .integer("embargo_bars", "Bars dropped from the train folds either side of the "
+                                                      "held-out fold")
+                             .default_int(0)
+                             .object("param_grid", grid_desc)
+                             .string("objective", "Selection metric, averaged across the train folds")
+                             .enums(objectives)
+                             .default_str("sharpe_ratio")
+                             .integer("min_trades", "Train trades a combination needs to be preferred")
+                             .default_int(1)
+                             .integer("max_combinations", "Reject grids larger than this")
+                             .default_int(200)
+                             .build();
+        t.default_timeout_ms = 600000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            walk_forward_handler(args, std::move(ctx), promise, QStringLiteral("kfold"));
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_parameter_sweep ───────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_parameter_sweep";
+        t.description = "Parameter optimisation for an algo strategy: every combination of the grid is backtested "
+                        "in parallel across CPU cores over the full date range and ranked by the objective, with "
+                        "optional early stopping (patience / target). The results matrix (Sharpe, return, "
+                        "drawdown, trades per combination) is saved for heat-map display; returns the sweep_id, "
+                        "best combination, top results and a heat map over the first two grid keys.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("symbol", "Symbol to backtest")
+                             .required()
+                             .string("strategy_id", "Saved strategy id (see the Algo Trading strategy list)")
+                             .object("strategy", strategy_desc)
+                             .string("start_date", "History start, yyyy-MM-dd (default: 1 year back)")
+                             .string("end_date", "History end, yyyy-MM-dd")
+                             .number("capital", "Initial capital per backtest")
+                             .default_num(100000.0)
+                             .object("param_grid", "Parameter path → candidate values. " + grid_paths)
+                             .required()
+                             .string("objective", "Ranking metric")
+                             .enums(objectives)
+                             .default_str("sharpe_ratio")
+                             .integer("min_trades", "Trades a combination needs to rank above those with fewer")
+                             .default_int(1)
+                             .integer("max_combinations", "Reject grids larger than this")
+                             .default_int(2000)
+                             .between(1, 20000)
+                             .integer("threads", "Worker threads (0 = one per core)")
+                             .default_int(0)
+                             .integer("patience", "Stop after this many batches without a new best (0 = off)")
+                             .default_int(0)
+                             .number("target", "Stop once a qualifying combination reaches this objective")
+                             .integer("top", "Ranked results to return (all are saved)")
+                             .default_int(25)
+                             .between(1, 500)
+                             .build();
+        t.default_timeout_ms = 600000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            parameter_sweep_handler(args, std::move(ctx), promise);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_list_parameter_sweeps ─────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_list_parameter_sweeps";
+        t.description = "List saved algo parameter sweeps, newest first: symbol, strategy, objective, "
+                        "combinations evaluated, early-stop reason and best parameters.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("strategy_id", "Only sweeps of this strategy")
+                             .integer("limit", "Maximum sweeps")
+                             .default_int(50)
+                             .between(1, 500)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QJsonArray sweeps = services::algo::AlgoTradingService::instance().parameter_sweeps(
+                args["strategy_id"].toString(), args["limit"].toInt(50));
+            return ToolResult::ok_data(QJsonObject{{"sweeps", sweeps}, {"count", sweeps.size()}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_get_parameter_sweep ───────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_get_parameter_sweep";
+        t.description = "Load a saved algo parameter sweep: its ranked results matrix and a heat map of one metric "
+                        "over two grid parameters (other parameters collapsed to the best combination per cell).";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("sweep_id", "Sweep id from algo_parameter_sweep / algo_list_parameter_sweeps")
+                             .required()
+                             .string("x_key", "Grid path on the heat map x axis (default: first grid key)")
+                             .string("y_key", "Grid path on the heat map y axis (default: second grid key)")
+                             .string("metric", "Heat map cell value")
+                             .enums({"objective", "sharpe_ratio", "total_return", "max_drawdown", "sortino",
+                                     "calmar", "win_rate", "profit_factor", "total_trades", "final_value"})
+                             .default_str("objective")
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString id = args["sweep_id"].toString().trimmed();
+            if (id.isEmpty())
+                return ToolResult::fail("Missing 'sweep_id'");
+            const QJsonObject sweep = services::algo::AlgoTradingService::instance().parameter_sweep(
+                id, args["x_key"].toString(), args["y_key"].toString(), args["metric"].toString());
+            if (sweep.isEmpty())
+                return ToolResult::fail(QString("Parameter sweep '%1' not found").arg(id));
+            return ToolResult::ok_data(sweep);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_tick_backtest ─────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_tick_backtest";
+        t.description = "Tick-level, event-driven backtest of an algo strategy over stored trades/quotes (see "
+                        "algo_import_ticks). Ticks are replayed through the live deployment's bar aggregator, "
+                        "rule evaluator and position/risk manager; orders arrive after a modelled latency, cross "
+                        "the spread and fill against displayed size, queueing for the rest. Returns the usual "
+                        "backtest metrics plus execution stats (latency, slippage, partial fills, dropped signals).";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("symbol", "Symbol whose ticks are stored")
+                             .required()
+                             .string("exchange", "Exchange the ticks were stored under (e.g. NSE, NASDAQ)")
+                             .required()
+                             .string("strategy_id", "Saved strategy id (see the Algo Trading strategy list)")
+                             .object("strategy", strategy_desc)
+                             .string("start_date", "First day replayed, yyyy-MM-dd (default: all stored ticks)")
+                             .string("end_date", "Last day replayed, yyyy-MM-dd")
+                             .number("capital", "Initial capital")
+                             .default_num(100000.0)
+                             .object("fill_model", "{latency_ms (50), latency_jitter_ms, slippage_bps, "
+                                                   "commission_bps, cross_spread (true), respect_size (true), "
+                                                   "max_queue_ms (0 = wait), seed}")
+                             .build();
+        t.default_timeout_ms = 600000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            tick_backtest_handler(args, std::move(ctx), promise);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_portfolio_backtest ────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_portfolio_backtest";
+        t.description = "Backtest several algo strategies together against one shared capital pool. Each sleeve "
+                        "is a strategy on a symbol; entries are sized by the allocation rule (fixed fraction, "
+                        "volatility targeting or capped Kelly) and skipped when cash runs out, and same-symbol "
+                        "orders from different sleeves are netted. Returns combined equity and drawdown curves, "
+                        "return / Sharpe / max drawdown, exposure, netting stats and per-sleeve contribution.";
+        t.category = "algo-trading";
+        const QJsonObject sleeve_schema{
+            {"type", "object"},
+            {"properties",
+             QJsonObject{{"strategy_id", QJsonObject{{"type", "string"}}},
+                         {"strategy", QJsonObject{{"type", "object"}, {"description", strategy_desc}}},
+                         {"symbol", QJsonObject{{"type", "string"}}},
+                         {"weight", QJsonObject{{"type", "number"},
+                                                {"description", "Share of equity per entry, 0-1 (default: equal)"}}}}},
+            {"required", QJsonArray{"symbol"}}};
+        t.input_schema = ToolSchemaBuilder()
+                             .array("sleeves", "Strategies to combine: {strategy_id | strategy, symbol, weight}",
+                                    sleeve_schema)
+                             .required()
+                             .string("start_date", "History start, yyyy-MM-dd (default: 1 year back)")
+                             .string("end_date", "History end, yyyy-MM-dd")
+                             .number("capital", "Shared capital pool")
+                             .default_num(100000.0)
+                             .object("allocation", "{method: fixed_fraction | vol_target | kelly, target_vol "
+                                                   "(0.15), vol_lookback (20), max_vol_scale (2), "
+                                                   "kelly_multiplier (0.5), kelly_cap (0.25), kelly_min_trades "
+                                                   "(5), commission_bps}")
+                             .build();
+        t.default_timeout_ms = 300000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            portfolio_backtest_handler(args, std::move(ctx), promise);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_import_ticks ──────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_import_ticks";
+        t.description = "Import a CSV of trades/quotes into the local tick store for algo_tick_backtest. The "
+                        "header names the columns: timestamp (epoch ms/s or ISO-8601) and price are required; "
+                        "size, bid, ask, bid_size, ask_size are optional. Re-importing a file is idempotent.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("symbol", "Symbol the ticks belong to")
+                             .required()
+                             .string("exchange", "Exchange to store them under")
+                             .required()
+                             .string("file_path", "Absolute path of the CSV file")
+                             .required()
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString symbol = args["symbol"].toString().trimmed();
+            const QString exchange = args["exchange"].toString().trimmed();
+            const QString path = args["file_path"].toString().trimmed();
+            if (symbol.isEmpty() || exchange.isEmpty() || path.isEmpty())
+                return ToolResult::fail("Missing 'symbol', 'exchange' or 'file_path'");
+            QString err;
+            const int n = storage::HistoricalDataStore::instance().import_ticks_csv(symbol, exchange, path, &err);
+            if (n < 0)
+                return ToolResult::fail(err);
+            return ToolResult::ok(QString("Imported %1 ticks for %2:%3").arg(n).arg(symbol.toUpper(),
+                                                                                   exchange.toUpper()),
+                                  QJsonObject{{"imported", n}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_analyze_strategy ──────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_analyze_strategy";
+        t.description = "Check an algo strategy's condition rules without running them: errors and warnings with "
+                        "the path of the offending key (entry.0.params.period), the indicator operands used, "
+                        "signatures of the indicators referenced and the warm-up bars the slowest one needs.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("strategy_id", "Saved strategy id (or pass 'strategy')")
+                             .object("strategy", strategy_desc)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            services::algo::AlgoStrategy strategy;
+            QString err;
+            if (!strategy_from_args(args, strategy, &err))
+                return ToolResult::fail(err);
+            return ToolResult::ok_data(algo::StrategyAnalyzer::analyze(strategy));
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_format_strategy ───────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_format_strategy";
+        t.description = "Canonical form of an algo strategy's rules (defaults made explicit, ignored keys "
+                        "dropped, same evaluation) and a readable one-condition-per-line rendering of them.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("strategy_id", "Saved strategy id (or pass 'strategy')")
+                             .object("strategy", strategy_desc)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            services::algo::AlgoStrategy strategy;
+            QString err;
+            if (!strategy_from_args(args, strategy, &err))
+                return ToolResult::fail(err);
+            const auto formatted = algo::StrategyAnalyzer::format(strategy);
+            return ToolResult::ok_data(QJsonObject{{"entry_conditions", formatted.entry_conditions},
+                                                   {"exit_conditions", formatted.exit_conditions},
+                                                   {"entry_logic", formatted.entry_logic},
+                                                   {"exit_logic", formatted.exit_logic},
+                                                   {"text", algo::StrategyAnalyzer::to_text(formatted)}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_import_pine ───────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_import_pine";
+        t.description = "Translate a Pine Script v5 strategy into algo strategy rules. Returns the strategy, what "
+                        "was approximated (warnings) and what could not be translated (errors). Pass save=true to "
+                        "save it when an entry rule was translated.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("code", "Pine Script source")
+                             .required()
+                             .string("name", "Strategy name (default: the strategy() title)")
+                             .string("timeframe", "Timeframe to run on, e.g. 1h (default: 1d)")
+                             .boolean("save", "Save the translated strategy (default false)")
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            algo::PineImport imported = algo::PineImporter::transpile(args["code"].toString());
+            if (!args["name"].toString().trimmed().isEmpty())
+                imported.strategy.name = args["name"].toString().trimmed();
+            imported.strategy.timeframe = args["timeframe"].toString("1d");
+            QJsonObject data = imported.to_json();
+            data["text"] = algo::StrategyAnalyzer::to_text(imported.strategy);
+            if (!args["save"].toBool(false) || !imported.ok) {
+                promise->addResult(ToolResult::ok_data(data));
+                promise->finish();
+                return;
+            }
+            auto* svc = &services::algo::AlgoTradingService::instance();
+            const auto strategy = imported.strategy;
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, strategy, data](auto resolve) {
+                auto* holder = new QObject(svc);
+                QObject::connect(svc, &services::algo::AlgoTradingService::strategy_saved, holder,
+                                 [resolve, holder, data](QString id) mutable {
+                                     data["strategy_id"] = id;
+                                     resolve(ToolResult::ok_data(data));
+                                     holder->deleteLater();
+                                 });
+                QObject::connect(svc, &services::algo::AlgoTradingService::error_occurred, holder,
+                                 [resolve, holder](QString context, QString msg) {
+                                     if (context != QLatin1String("save_strategy"))
+                                         return;
+                                     resolve(ToolResult::fail(msg));
+                                     holder->deleteLater();
+                                 });
+                svc->save_strategy(strategy);
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_strategy_catalog ──────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_strategy_catalog";
+        t.description = "Indicators (parameters with range and default, output fields, look-back), operators and "
+                        "timeframes the algo strategy rules accept. Pass 'name' for one indicator or operator.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder().string("name", "Indicator id (e.g. MACD) or operator").build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString name = args["name"].toString().trimmed();
+            if (name.isEmpty())
+                return ToolResult::ok_data(algo::StrategyAnalyzer::catalog());
+            const QJsonObject entry = algo::StrategyAnalyzer::describe(name);
+            if (entry.isEmpty())
+                return ToolResult::fail(QString("No indicator or operator named '%1'").arg(name));
+            return ToolResult::ok_data(entry);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_step_backtest ─────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_step_backtest";
+        t.description = "Rule debugger over the most recent backtest: step from a bar to the next (or previous) bar "
+                        "where a breakpoint rule turns met — or a single bar without breakpoints — and return the "
+                        "candle, both rule trees fully evaluated and every indicator value there.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .integer("from_bar", "Bar to step from (-1 = before the first bar)")
+                             .default_int(-1)
+                             .array("breakpoints",
+                                    "Rule paths that stop the scan when they turn met: 'entry', 'exit', "
+                                    "'entry.1', 'exit.0.children.2'",
+                                    QJsonObject{{"type", "string"}})
+                             .boolean("backward", "Step towards earlier bars")
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const int from_bar = args["from_bar"].toInt(-1);
+            QStringList breakpoints;
+            for (const auto& v : args["breakpoints"].toArray())
+                breakpoints << v.toString().trimmed();
+            const bool backward = args["backward"].toBool(false);
+            auto* svc = &services::algo::AlgoTradingService::instance();
+            AsyncDispatch::callback_to_promise(
+                svc, std::move(ctx), promise, [svc, from_bar, breakpoints, backward](auto resolve) {
+                    auto* holder = new QObject(svc);
+                    QObject::connect(svc, &services::algo::AlgoTradingService::backtest_step, holder,
+                                     [resolve, holder](QJsonObject data) {
+                                         resolve(ToolResult::ok_data(data));
+                                         holder->deleteLater();
+                                     });
+                    QObject::connect(svc, &services::algo::AlgoTradingService::error_occurred, holder,
+                                     [resolve, holder](QString context, QString msg) {
+                                         if (context != QLatin1String("backtest_step"))
+                                             return;
+                                         resolve(ToolResult::fail(msg));
+                                         holder->deleteLater();
+                                     });
+                    svc->step_backtest(from_bar, breakpoints, backward);
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_transform_bars ────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_transform_bars";
+        t.description = "Convert a symbol's OHLCV bars over a date range into Heikin-Ashi candles, Renko bricks "
+                        "(close-driven, two-brick reversals) or range bars (source bars merged until high − low "
+                        "reaches the range). Returns the transformed bars oldest first.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("symbol", "Symbol to fetch")
+                             .required()
+                             .string("type", "Bar type")
+                             .enums({"heikin_ashi", "renko", "range", "time"})
+                             .required()
+                             .number("size", "Brick (renko) or range (range) size in price units")
+                             .string("timeframe", "Source bar timeframe")
+                             .default_str("1d")
+                             .string("start_date", "yyyy-MM-dd (default: one year back)")
+                             .string("end_date", "yyyy-MM-dd")
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString symbol = args["symbol"].toString().trimmed();
+            if (symbol.isEmpty()) {
+                promise->addResult(ToolResult::fail("Missing 'symbol'"));
+                promise->finish();
+                return;
+            }
+            const QJsonObject transform{{"type", args["type"].toString()}, {"size", args["size"].toDouble()}};
+            const QString timeframe = args["timeframe"].toString(QStringLiteral("1d"));
+            const QString start = args["start_date"].toString();
+            const QString end = args["end_date"].toString();
+            auto* svc = &services::algo::AlgoTradingService::instance();
+            AsyncDispatch::callback_to_promise(
+                svc, std::move(ctx), promise, [svc, symbol, timeframe, start, end, transform](auto resolve) {
+                    auto* holder = new QObject(svc);
+                    QObject::connect(svc, &services::algo::AlgoTradingService::bars_transformed, holder,
+                                     [resolve, holder](QJsonObject data) {
+                                         resolve(ToolResult::ok_data(data));
+                                         holder->deleteLater();
+                                     });
+                    QObject::connect(svc, &services::algo::AlgoTradingService::error_occurred, holder,
+                                     [resolve, holder](QString context, QString msg) {
+                                         if (context != QLatin1String("transform_bars"))
+                                             return;
+                                         resolve(ToolResult::fail(msg));
+                                         holder->deleteLater();
+                                     });
+                    svc->transform_bars(symbol, timeframe, start, end, transform);
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_volatility ────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_volatility";
+        t.description = "Realised volatility of a symbol from its OHLC bars — close-to-close, Parkinson, "
+                        "Garman-Klass and Yang-Zhang over the last `window` bars — plus a GARCH(1,1) maximum-"
+                        "likelihood fit of the log returns with its conditional-vol forecast. All volatilities "
+                        "are annualised fractions (0.2 = 20%).";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("symbol", "Symbol to fetch")
+                             .required()
+                             .string("timeframe", "Bar timeframe")
+                             .default_str("1d")
+                             .integer("lookback_days", "History fetched for the GARCH fit")
+                             .default_int(730)
+                             .between(30, 7300)
+                             .integer("window", "Bars in the realised-vol window")
+                             .default_int(20)
+                             .between(2, 1000)
+                             .integer("horizon", "GARCH forecast horizon in bars")
+                             .default_int(10)
+                             .between(1, 250)
+                             .number("periods_per_year", "Bars per year for annualising (252 daily, 52 weekly)")
+                             .default_num(252.0)
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString symbol = args["symbol"].toString().trimmed().toUpper();
+            if (symbol.isEmpty()) {
+                promise->addResult(ToolResult::fail("Missing 'symbol'"));
+                promise->finish();
+                return;
+            }
+            const QString timeframe = args["timeframe"].toString(QStringLiteral("1d"));
+            const int lookback = std::clamp(args["lookback_days"].toInt(730), 30, 7300);
+            const int window = std::clamp(args["window"].toInt(20), 2, 1000);
+            const int horizon = std::clamp(args["horizon"].toInt(10), 1, 250);
+            const double ppy = args["periods_per_year"].toDouble(252.0);
+            auto* fetcher = &algo::CandleDataFetcher::instance();
+            AsyncDispatch::callback_to_promise(
+                fetcher, std::move(ctx), promise,
+                [fetcher, symbol, timeframe, lookback, window, horizon, ppy](auto resolve) {
+                    fetcher->fetch(
+                        symbol, timeframe, lookback, algo::DataSource::YFinance, {}, {},
+                        [resolve, symbol, timeframe, window, horizon, ppy](
+                            bool ok, const QVector<algo::OhlcvCandle>& candles, const QString& err) {
+                            if (!ok || candles.isEmpty()) {
+                                resolve(ToolResult::fail(err.isEmpty() ? QStringLiteral("No data") : err));
+                                return;
+                            }
+                            if (candles.size() <= window) {
+                                resolve(ToolResult::fail(QString("Only %1 bars — need more than the %2-bar window")
+                                                             .arg(candles.size())
+                                                             .arg(window)));
+                                return;
+                            }
+                            namespace vol = services::options::vol;
+                            QVector<double> o, h, l, c;
+                            for (const auto& k : candles) {
+                                o.append(k.open);
+                                h.append(k.high);
+                                l.append(k.low);
+                                c.append(k.close);
+                            }
+                            const int end = int(candles.size()) - 1;
+                            auto num = [](double v) { return std::isfinite(v) ? QJsonValue(v) : QJsonValue(); };
+                            const QJsonObject realized{
+                                {"close_to_close", num(vol::close_to_close(c, end, window, ppy))},
+                                {"parkinson", num(vol::parkinson(h, l, end, window, ppy))},
+                                {"garman_klass", num(vol::garman_klass(o, h, l, c, end, window, ppy))},
+                                {"yang_zhang", num(vol::yang_zhang(o, h, l, c, end, window, ppy))}};
+
+                            const vol::Garch11 g = vol::fit_garch11(vol::log_returns(c), horizon, ppy);
+                            QJsonObject garch{{"ok", g.ok}};
+                            if (g.ok) {
+                                QJsonArray forecast;
+                                for (double v : g.forecast)
+                                    forecast.append(num(v));
+                                garch = {{"ok", true},
+                                         {"omega", g.omega},
+                                         {"alpha", g.alpha},
+                                         {"beta", g.beta},
+                                         {"persistence", g.persistence},
+                                         {"long_run_vol", num(g.long_run_vol)},
+                                         {"current_vol", num(g.current_vol)},
+                                         {"log_likelihood", g.log_likelihood},
+                                         {"observations", g.observations},
+                                         {"forecast", forecast}};
+                            } else {
+                                garch["error"] = g.error;
+                            }
+                            resolve(ToolResult::ok_data(QJsonObject{{"symbol", symbol},
+                                                                    {"timeframe", timeframe},
+                                                                    {"bars", int(candles.size())},
+                                                                    {"window", window},
+                                                                    {"periods_per_year", ppy},
+                                                                    {"realized", realized},
+                                                                    {"garch", garch}}));
+                        });
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_pair_analysis ─────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_pair_analysis";
+        t.description = "Pairs-trading analytics for two symbols: ADF unit-root test of each, Engle-Granger "
+                        "cointegration (both directions) with the mean-reversion half-life, a rolling OLS or Kalman "
+                        "hedge ratio, and the spread y − (alpha + beta·x) with its rolling z-score.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("y_symbol", "Dependent leg")
+                             .required()
+                             .string("x_symbol", "Hedge leg")
+                             .required()
+                             .string("timeframe", "Bar timeframe")
+                             .default_str("1d")
+                             .integer("lookback_days", "History fetched")
+                             .default_int(730)
+                             .between(30, 7300)
+                             .string("method", "Hedge ratio estimator")
+                             .enums({"ols", "kalman"})
+                             .default_str("ols")
+                             .integer("window", "Rolling OLS / z-score window in bars")
+                             .default_int(60)
+                             .between(10, 1000)
+                             .number("kalman_delta", "Kalman state drift (higher = faster-moving beta)")
+                             .default_num(1e-4)
+                             .integer("series_bars", "Trailing bars of hedge ratio / spread / z-score returned")
+                             .default_int(250)
+                             .build();
+        t.default_timeout_ms = 120000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString y = args["y_symbol"].toString();
+            const QString x = args["x_symbol"].toString();
+            const QString timeframe = args["timeframe"].toString(QStringLiteral("1d"));
+            const int lookback = std::clamp(args["lookback_days"].toInt(730), 30, 7300);
+            auto* svc = &services::algo::AlgoTradingService::instance();
+            AsyncDispatch::callback_to_promise(
+                svc, std::move(ctx), promise, [svc, y, x, timeframe, lookback, args](auto resolve) {
+                    auto* holder = new QObject(svc);
+                    QObject::connect(svc, &services::algo::AlgoTradingService::pair_analysis, holder,
+                                     [resolve, holder](QJsonObject data) {
+                                         resolve(ToolResult::ok_data(data));
+                                         holder->deleteLater();
+                                     });
+                    QObject::connect(svc, &services::algo::AlgoTradingService::error_occurred, holder,
+                                     [resolve, holder](QString context, QString msg) {
+                                         if (context != QLatin1String("pair_analysis"))
+                                             return;
+                                         resolve(ToolResult::fail(msg));
+                                         holder->deleteLater();
+                                     });
+                    svc->analyze_pair(y, x, timeframe, lookback, args);
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_pair_scan ─────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_pair_scan";
+        t.description = "Search a universe for cointegrated pairs: Engle-Granger on every pair of symbols (in the "
+                        "direction with the stronger statistic). Returns the pairs significant at max_significance, "
+                        "strongest first, with hedge ratio, half-life and current spread z-score.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .array("symbols", "Universe, 2–60 symbols", QJsonObject{{"type", "string"}})
+                             .required()
+                             .string("timeframe", "Bar timeframe")
+                             .default_str("1d")
+                             .integer("lookback_days", "History fetched")
+                             .default_int(730)
+                             .between(30, 7300)
+                             .number("max_significance", "Largest test level listed: 0.01, 0.05 or 0.10")
+                             .default_num(0.05)
+                             .build();
+        t.default_timeout_ms = 300000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            QStringList symbols;
+            for (const auto& v : args["symbols"].toArray())
+                symbols << v.toString();
+            if (symbols.size() < 2 || symbols.size() > 60) {
+                promise->addResult(ToolResult::fail("'symbols' must list 2 to 60 symbols"));
+                promise->finish();
+                return;
+            }
+            const QString timeframe = args["timeframe"].toString(QStringLiteral("1d"));
+            const int lookback = std::clamp(args["lookback_days"].toInt(730), 30, 7300);
+            const double max_sig = args["max_significance"].toDouble(0.05);
+            auto* svc = &services::algo::AlgoTradingService::instance();
+            AsyncDispatch::callback_to_promise(
+                svc, std::move(ctx), promise, [svc, symbols, timeframe, lookback, max_sig, args](auto resolve) {
+                    auto* holder = new QObject(svc);
+                    QObject::connect(svc, &services::algo::AlgoTradingService::pair_scan_result, holder,
+                                     [resolve, holder](QJsonObject data) {
+                                         resolve(ToolResult::ok_data(data));
+                                         holder->deleteLater();
+                                     });
+                    QObject::connect(svc, &services::algo::AlgoTradingService::error_occurred, holder,
+                                     [resolve, holder](QString context, QString msg) {
+                                         if (context != QLatin1String("pair_scan"))
+                                             return;
+                                         resolve(ToolResult::fail(msg));
+                                         holder->deleteLater();
+                                     });
+                    svc->scan_pairs(symbols, timeframe, lookback, args, max_sig);
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_market_regime ─────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_market_regime";
+        t.description = "Classify the current market regime (calm / normal / turbulent) of a reference index from "
+                        "its daily returns and a volatility index: a Gaussian HMM fitted by Baum-Welch, or fixed "
+                        "volatility-index thresholds. Returns the regimes' statistics, transition matrix, current "
+                        "regime with its probability and recent path. Regime changes are published as "
+                        "'algo.regime_changed' events and notifications.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("index", "Reference index")
+                             .default_str("^GSPC")
+                             .string("vol_index", "Volatility index (empty = returns only)")
+                             .default_str("^VIX")
+                             .string("method", "Classifier")
+                             .enums({"hmm", "threshold"})
+                             .default_str("hmm")
+                             .integer("states", "Number of regimes")
+                             .default_int(3)
+                             .between(2, 3)
+                             .integer("lookback_days", "History fitted")
+                             .default_int(1095)
+                             .between(120, 7300)
+                             .number("calm_level", "threshold: volatility index below this is calm")
+                             .default_num(15)
+                             .number("turbulent_level", "threshold: volatility index above this is turbulent")
+                             .default_num(25)
+                             .integer("auto_refresh_minutes", "Re-fit periodically to watch for changes (0 = off)")
+                             .between(0, 1440)
+                             .boolean("cached", "Return the last fit without refitting when one exists")
+                             .build();
+        t.default_timeout_ms = 120000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            auto* monitor = &algo::RegimeMonitor::instance();
+            if (args.contains("auto_refresh_minutes"))
+                monitor->set_auto_refresh(args["auto_refresh_minutes"].toInt());
+            if (args["cached"].toBool() && !monitor->current().isEmpty()) {
+                promise->addResult(ToolResult::ok_data(monitor->current()));
+                promise->finish();
+                return;
+            }
+            QJsonObject config;
+            for (const char* key : {"index", "vol_index", "method", "states", "lookback_days", "calm_level",
+                                    "turbulent_level"})
+                if (args.contains(key))
+                    config[key] = args[key];
+            AsyncDispatch::callback_to_promise(monitor, std::move(ctx), promise, [monitor, config](auto resolve) {
+                auto* holder = new QObject(monitor);
+                QObject::connect(monitor, &algo::RegimeMonitor::regime_updated, holder,
+                                 [resolve, holder](QJsonObject regime) {
+                                     resolve(ToolResult::ok_data(regime));
+                                     holder->deleteLater();
+                                 });
+                QObject::connect(monitor, &algo::RegimeMonitor::error_occurred, holder,
+                                 [resolve, holder](QString msg) {
+                                     resolve(ToolResult::fail(msg));
+                                     holder->deleteLater();
+                                 });
+                monitor->refresh(config);
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_list_backtest_runs ────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_list_backtest_runs";
+        t.description = "List saved algo backtest runs, newest first: strategy and its rule version, config hash, "
+                        "symbol, date range and headline metrics. Every successful bar or tick backtest is saved.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("strategy_id", "Only runs of this strategy")
+                             .string("symbol", "Only runs on this symbol")
+                             .integer("limit", "Maximum runs")
+                             .default_int(50)
+                             .between(1, 500)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QJsonArray runs = services::algo::AlgoTradingService::instance().backtest_runs(
+                args["strategy_id"].toString(), args["symbol"].toString().trimmed(), args["limit"].toInt(50));
+            return ToolResult::ok_data(QJsonObject{{"runs", runs}, {"count", runs.size()}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_get_backtest_run ──────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_get_backtest_run";
+        t.description = "Load one saved algo backtest run: its configuration (rules, symbol, range, capital), "
+                        "full metrics, trade list and equity curve.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder().string("run_id", "Run id from algo_list_backtest_runs").required().build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString id = args["run_id"].toString().trimmed();
+            if (id.isEmpty())
+                return ToolResult::fail("Missing 'run_id'");
+            const QJsonObject run = services::algo::AlgoTradingService::instance().backtest_run(id);
+            if (run.isEmpty())
+                return ToolResult::fail(QString("Backtest run '%1' not found").arg(id));
+            return ToolResult::ok_data(run);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_compare_backtest_runs ─────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_compare_backtest_runs";
+        t.description = "Compare two or more saved algo backtest runs side by side: every metric per run, its "
+                        "change against the first run and which run is best, plus the equity curves.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .array("run_ids", "Run ids; the first is the baseline",
+                                    QJsonObject{{"type", "string"}})
+                             .required()
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            QStringList ids;
+            for (const auto& v : args["run_ids"].toArray())
+                if (!v.toString().trimmed().isEmpty())
+                    ids << v.toString().trimmed();
+            const QJsonObject result = services::algo::AlgoTradingService::instance().compare_backtest_runs(ids);
+            if (!result.value("success").toBool())
+                return ToolResult::fail(result.value("error").toString());
+            return ToolResult::ok_data(result);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_diff_backtest_runs ────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_diff_backtest_runs";
+        t.description = "Diff two saved algo backtest runs: which configuration fields changed (conditions, "
+                        "logic, stops, sizing, symbol, range), metric deltas, and trades added, removed or "
+                        "closed differently (matched by entry time).";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("base_run_id", "The earlier / reference run")
+                             .required()
+                             .string("other_run_id", "The run compared against it")
+                             .required()
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString a = args["base_run_id"].toString().trimmed();
+            const QString b = args["other_run_id"].toString().trimmed();
+            if (a.isEmpty() || b.isEmpty())
+                return ToolResult::fail("Missing 'base_run_id' or 'other_run_id'");
+            const QJsonObject result = services::algo::AlgoTradingService::instance().diff_backtest_runs(a, b);
+            if (!result.value("success").toBool())
+                return ToolResult::fail(result.value("error").toString());
+            return ToolResult::ok_data(result);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_backtest_monte_carlo ──────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_backtest_monte_carlo";
+        t.description = "Monte Carlo robustness check of a saved algo backtest run from its trades: bootstrap "
+                        "confidence intervals for final equity, total return, max drawdown and Sharpe, the "
+                        "probability of a loss, a permutation test of how lucky the trade order was for the "
+                        "drawdown, and a p-value for whether the edge is distinguishable from luck.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("run_id", "Run id from algo_list_backtest_runs")
+                             .required()
+                             .integer("simulations", "Resampled trade sequences")
+                             .default_int(2000)
+                             .between(100, 20000)
+                             .number("confidence", "Interval width and test level, e.g. 0.95")
+                             .default_num(0.95)
+                             .between(0.5, 0.999)
+                             .string("method", "Resampling scheme")
+                             .enums({"bootstrap", "permutation", "both"})
+                             .default_str("both")
+                             .integer("seed", "Random seed (same seed, same result)")
+                             .default_int(42)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString id = args["run_id"].toString().trimmed();
+            if (id.isEmpty())
+                return ToolResult::fail("Missing 'run_id'");
+            const QJsonObject result = services::algo::AlgoTradingService::instance().resample_backtest_run(id, args);
+            if (!result.value("success").toBool())
+                return ToolResult::fail(result.value("error").toString());
+            return ToolResult::ok_data(result);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_annotate_backtest_run ─────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_annotate_backtest_run";
+        t.description = "Set the label and notes of a saved algo backtest run (e.g. 'v3 — tighter stop').";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("run_id", "Run id")
+                             .required()
+                             .string("label", "Short label")
+                             .string("notes", "Free-form notes")
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString id = args["run_id"].toString().trimmed();
+            if (!services::algo::AlgoTradingService::instance().annotate_backtest_run(id, args["label"].toString(),
+                                                                                       args["notes"].toString()))
+                return ToolResult::fail(QString("Backtest run '%1' not found").arg(id));
+            return ToolResult::ok(QString("Annotated backtest run %1").arg(id));
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_delete_backtest_run ───────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_delete_backtest_run";
+        t.description = "Delete a saved algo backtest run.";
+        t.category = "algo-trading";
+        t.is_destructive = true;
+        t.input_schema = ToolSchemaBuilder().string("run_id", "Run id").required().build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString id = args["run_id"].toString().trimmed();
+            if (!services::algo::AlgoTradingService::instance().delete_backtest_run(id))
+                return ToolResult::fail(QString("Backtest run '%1' not found").arg(id));
+            return ToolResult::ok(QString("Deleted backtest run %1").arg(id));
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_reload_deployment_strategy ────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_reload_deployment_strategy";
+        t.description = "Hot-reload the rules of a running algo deployment without flattening it: entry/exit "
+                        "conditions, logic and stop/target/trailing thresholds are swapped between two ticks "
+                        "while the open position, pending order and metrics carry over. Defaults to the "
+                        "deployment's saved strategy (after editing it); pass strategy_id or an inline strategy "
+                        "to switch. Timeframe changes, and leg changes while a basket is open, need a redeploy.";
+        t.category = "algo-trading";
+        t.is_destructive = true;
+        t.input_schema = ToolSchemaBuilder()
+                             .string("deployment_id", "Running deployment id")
+                             .required()
+                             .string("strategy_id", "Saved strategy to switch to (default: the deployment's own)")
+                             .object("strategy", strategy_desc)
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            reload_strategy_handler(args, std::move(ctx), promise);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_deployment_analytics ──────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_deployment_analytics";
+        t.description = "Trade and equity analytics of an algo deployment from its recorded fills: round trips, "
+                        "win rate, profit factor, average win/loss, realized-P&L equity curve and max drawdown, "
+                        "realized/unrealized/total P&L and the open position. Works the same for paper, live and "
+                        "shadow (forward-test) deployments; shadow fills are hypothetical — no order was routed.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder().string("deployment_id", "Deployment id").required().build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString id = args["deployment_id"].toString().trimmed();
+            if (id.isEmpty())
+                return ToolResult::fail("Missing 'deployment_id'");
+            const QJsonObject analytics = algo::AlgoEngine::instance().deployment_analytics(id);
+            if (analytics.isEmpty())
+                return ToolResult::fail(QString("Deployment '%1' not found").arg(id));
+            return ToolResult::ok_data(analytics);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_kill_switch ───────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_kill_switch";
+        t.description = "Global algo kill switch. 'engage' stops new entries on every deployment, cancels open "
+                        "orders on each live broker account the deployments trade on, flattens every "
+                        "deployment's position at market and stops them; no deployment can start until 'reset'. "
+                        "'status' reports whether it is engaged.";
+        t.category = "algo-trading";
+        t.is_destructive = true;
+        t.input_schema = ToolSchemaBuilder()
+                             .string("action", "What to do")
+                             .enums({"engage", "reset", "status"})
+                             .required()
+                             .string("reason", "Why (logged and stored on each stopped deployment)")
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            auto& engine = algo::AlgoEngine::instance();
+            const QString action = args["action"].toString();
+            if (action == "engage") {
+                const int count = engine.active_count();
+                engine.kill_switch(args["reason"].toString(QStringLiteral("mcp")));
+                return ToolResult::ok(QString("Kill switch engaged — flattening and stopping %1 deployment(s)")
+                                          .arg(count),
+                                      QJsonObject{{"engaged", true}, {"deployments", count}});
+            }
+            if (action == "reset") {
+                engine.reset_kill_switch();
+                return ToolResult::ok("Kill switch reset", QJsonObject{{"engaged", false}});
+            }
+            if (action == "status")
+                return ToolResult::ok_data(QJsonObject{{"engaged", engine.kill_switch_engaged()},
+                                                       {"active_deployments", engine.active_count()}});
+            return ToolResult::fail("'action' must be engage, reset or status");
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_recovery_report ───────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_recovery_report";
+        t.description = "Live algo deployments held after the app did not shut down cleanly (status "
+                        "'recovering'), each reconciled against its broker account: recorded vs broker net "
+                        "position (per leg for F&O), working broker orders on the symbol and orders sent but "
+                        "never confirmed before the crash. state is in_sync, mismatch or unverified (broker "
+                        "unreachable). Settle each with algo_resolve_recovery.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder().string("deployment_id", "One deployment (default: all held)").build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const auto items = algo::AlgoEngine::instance().recovery_report(args["deployment_id"].toString());
+            QJsonArray arr;
+            for (const auto& it : items)
+                arr.append(recovery_item_json(it));
+            return ToolResult::ok(QString("%1 deployment(s) awaiting recovery").arg(items.size()),
+                                  QJsonObject{{"deployments", arr}, {"count", items.size()}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── algo_resolve_recovery ──────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "algo_resolve_recovery";
+        t.description = "Settle a live deployment held for crash recovery. 'resume' adopts the broker's net "
+                        "position as the deployment's and restarts it (refused while working orders exist, or "
+                        "for F&O when the legs differ); 'flatten' cancels its working orders, closes its "
+                        "position or legs at market and stops it; 'stop' stops it and leaves the broker "
+                        "untouched.";
+        t.category = "algo-trading";
+        t.is_destructive = true;
+        t.input_schema = ToolSchemaBuilder()
+                             .string("deployment_id", "Deployment in status 'recovering'")
+                             .required()
+                             .string("action", "How to settle it")
+                             .enums({"resume", "flatten", "stop"})
+                             .required()
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            resolve_recovery_handler(args, std::move(ctx), promise);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
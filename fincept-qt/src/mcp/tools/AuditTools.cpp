@@ -0,0 +1,105 @@
+// AuditTools.cpp — Audit trail MCP tools (query, export, chain verification)
+
+#include "mcp/tools/AuditTools.h"
+
+#include "core/config/AppPaths.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "services/audit/AuditTrail.h"
+
+#include <QDateTime>
+#include <QJsonArray>
+#include <QJsonObject>
+
+namespace fincept::mcp::tools {
+
+using services::audit::AuditQuery;
+using services::audit::AuditTrail;
+
+namespace {
+
+// Filters shared by audit_log_query and audit_log_export.
+ToolSchemaBuilder audit_filter_schema() {
+    return ToolSchemaBuilder()
+        .integer("days", "Only the last N days (ignored when 'from' is given)")
+        .min(1)
+        .string("from", "Start time, ISO 8601")
+        .string("to", "End time, ISO 8601")
+        .array("categories", "Categories to include: order, auth, deployment, destructive",
+               QJsonObject{{"type", "string"}, {"enum", QJsonArray{"order", "auth", "deployment", "destructive"}}})
+        .string("action", "Exact action, e.g. order_placed, order_cancelled, kill_switch_engaged")
+        .string("subject", "Account, deployment or portfolio id")
+        .string("session_id", "One terminal session (see audit_log_verify for the current one)");
+}
+
+} // namespace
+
+std::vector<ToolDef> get_audit_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── audit_log_query ────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "audit_log_query";
+        t.description = "Query the append-only audit trail, newest first: order placements / failures / cancels / "
+                        "modifies and bulk actions, algo fills, broker account and credential events, algo "
+                        "deployment changes, and destructive actions (kill switch, deletions, resets).";
+        t.category = "trading";
+        t.input_schema = audit_filter_schema().integer("limit", "Max rows").default_int(200).between(1, 5000).build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            auto r = AuditTrail::instance().query(AuditQuery::from_json(args));
+            if (r.is_err())
+                return ToolResult::fail(QString::fromStdString(r.error()));
+            QJsonArray rows;
+            for (const auto& rec : r.value())
+                rows.append(rec.to_json());
+            return ToolResult::ok(QString("%1 audit rows").arg(rows.size()), QJsonObject{{"rows", rows}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── audit_log_export ───────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "audit_log_export";
+        t.description = "Export the audit trail (oldest first, with hashes) to a CSV file, or to JSON Lines when "
+                        "the path ends in .jsonl. Defaults to a timestamped CSV in the terminal's files folder.";
+        t.category = "trading";
+        t.input_schema = audit_filter_schema().string("path", "Output file path (.csv or .jsonl)").build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            QString path = args["path"].toString().trimmed();
+            if (path.isEmpty())
+                path = AppPaths::files() + "/audit_trail_" +
+                       QDateTime::currentDateTime().toString("yyyyMMdd_HHmmss") + ".csv";
+            auto r = AuditTrail::instance().export_to(AuditQuery::from_json(args), path);
+            if (r.is_err())
+                return ToolResult::fail(QString::fromStdString(r.error()));
+            return ToolResult::ok(QString("Exported %1 rows to %2").arg(r.value()).arg(path),
+                                  QJsonObject{{"path", path}, {"rows", r.value()}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── audit_log_verify ───────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "audit_log_verify";
+        t.description = "Verify the audit trail's hash chain end to end. Reports the first row that was edited, "
+                        "removed or reordered outside the terminal, if any, and the current session id.";
+        t.category = "trading";
+        t.input_schema = ToolSchemaBuilder().build();
+        t.handler = [](const QJsonObject&) -> ToolResult {
+            auto& trail = AuditTrail::instance();
+            const auto v = trail.verify();
+            QJsonObject data = v.to_json();
+            data["session_id"] = trail.session_id();
+            return ToolResult::ok(v.ok ? QString("Audit chain intact (%1 rows)").arg(v.checked)
+                                       : QString("Audit chain broken at row %1: %2").arg(v.first_bad_id).arg(v.problem),
+                                  data);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
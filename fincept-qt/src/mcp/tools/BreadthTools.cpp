@@ -0,0 +1,254 @@
+// BreadthTools.cpp — Market breadth MCP tools (universes, A/D line, McClellan)
+
+#include "mcp/tools/BreadthTools.h"
+
+#include "core/logging/Logger.h"
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "mcp/tools/ThreadHelper.h"
+#include "services/markets/MarketBreadthService.h"
+#include "storage/repositories/BreadthRepository.h"
+#include "storage/repositories/WatchlistRepository.h"
+
+#include <QCoreApplication>
+#include <QDate>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+static constexpr const char* TAG = "BreadthTools";
+
+namespace {
+
+using services::MarketBreadthService;
+
+QJsonObject universe_json(const BreadthUniverse& u) {
+    return QJsonObject{{"name", u.name},
+                       {"exchange", u.exchange},
+                       {"symbols", QJsonArray::fromStringList(u.symbols)},
+                       {"member_count", int(u.symbols.size())},
+                       {"last_update", u.last_update.isEmpty() ? QJsonValue() : QJsonValue(u.last_update)}};
+}
+
+} // namespace
+
+std::vector<ToolDef> get_breadth_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── breadth_universe_set ───────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "breadth_universe_set";
+        t.description = "Create or redefine a market breadth universe: a named list of symbols (or the symbols of "
+                        "a watchlist) whose daily candles feed the breadth series. Changing the members of an "
+                        "existing universe discards its stored history; run breadth_update afterwards.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("name", "Universe name, e.g. 'nasdaq100'")
+                             .required()
+                             .array("symbols", "Member tickers", QJsonObject{{"type", "string"}})
+                             .string("watchlist_id", "Copy the members from this watchlist instead")
+                             .string("exchange", "Candle store exchange key the daily bars are kept under")
+                             .default_str("YF")
+                             .build();
+        t.is_destructive = true; // mutation tool — penalise on read-style queries
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            BreadthUniverse u;
+            u.name = args["name"].toString().trimmed();
+            u.exchange = args["exchange"].toString("YF").trimmed().toUpper();
+            if (u.name.isEmpty())
+                return ToolResult::fail("Missing 'name'");
+            const QString watchlist_id = args["watchlist_id"].toString().trimmed();
+            QString error;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                QStringList raw = args["symbols"].toVariant().toStringList();
+                if (!watchlist_id.isEmpty()) {
+                    auto stocks = WatchlistRepository::instance().get_stocks(watchlist_id);
+                    if (stocks.is_err())
+                        error = "Failed to load watchlist: " + QString::fromStdString(stocks.error());
+                    else
+                        for (const auto& s : stocks.value())
+                            raw.append(s.symbol);
+                }
+                for (const QString& r : raw) {
+                    const QString sym = r.trimmed().toUpper();
+                    if (!sym.isEmpty() && !u.symbols.contains(sym))
+                        u.symbols.append(sym);
+                }
+                if (error.isEmpty() && u.symbols.isEmpty())
+                    error = "A universe needs at least one symbol ('symbols' or 'watchlist_id')";
+                if (error.isEmpty()) {
+                    auto r = BreadthRepository::instance().upsert_universe(u);
+                    if (r.is_err())
+                        error = QString::fromStdString(r.error());
+                }
+                signal_done();
+            });
+            if (!error.isEmpty())
+                return ToolResult::fail(error);
+            LOG_INFO(TAG, QString("Breadth universe %1 set (%2 members)").arg(u.name).arg(u.symbols.size()));
+            return ToolResult::ok(QString("Universe %1 has %2 members").arg(u.name).arg(u.symbols.size()),
+                                  universe_json(u));
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── breadth_universe_list ──────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "breadth_universe_list";
+        t.description = "List market breadth universes with their members and last update time.";
+        t.category = "markets";
+        t.handler = [](const QJsonObject&) -> ToolResult {
+            QJsonArray list;
+            QString error;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto r = BreadthRepository::instance().list_universes();
+                if (r.is_err())
+                    error = QString::fromStdString(r.error());
+                else
+                    for (const auto& u : r.value())
+                        list.append(universe_json(u));
+                signal_done();
+            });
+            if (!error.isEmpty())
+                return ToolResult::fail(error);
+            return ToolResult::ok_data(QJsonObject{{"universes", list}, {"count", list.size()}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── breadth_universe_remove ────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "breadth_universe_remove";
+        t.description = "Delete a market breadth universe and its stored breadth history.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder().string("name", "Universe name").required().build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString name = args["name"].toString().trimmed();
+            QString error;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto& repo = BreadthRepository::instance();
+                if (!repo.get_universe(name)) {
+                    error = "Unknown breadth universe: " + name;
+                } else {
+                    auto r = repo.remove_universe(name);
+                    if (r.is_err())
+                        error = QString::fromStdString(r.error());
+                }
+                signal_done();
+            });
+            if (!error.isEmpty())
+                return ToolResult::fail(error);
+            return ToolResult::ok("Removed breadth universe " + name);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── breadth_update ─────────────────────────────────────────────────
+    // Tops up the candle store and recomputes from the last stored session
+    // (or backfills). Universes are also brought up to date automatically.
+    {
+        ToolDef t;
+        t.name = "breadth_update";
+        t.description = "Bring a breadth universe up to date: fetch missing daily candles, then compute advance/"
+                        "decline, up/down volume, 52-week new highs/lows, % of members above their 50/200-day "
+                        "averages, the A/D line and the McClellan oscillator and summation index. Set backfill to "
+                        "rebuild `days` of history. Returns the latest session.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("name", "Universe name")
+                             .required()
+                             .boolean("backfill", "Rebuild the history instead of continuing it")
+                             .default_bool(false)
+                             .integer("days", "Calendar days of history for a backfill")
+                             .default_int(MarketBreadthService::kDefaultBackfillDays)
+                             .between(1, MarketBreadthService::kMaxBackfillDays)
+                             .build();
+        t.default_timeout_ms = 300000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            auto* svc = &MarketBreadthService::instance();
+            const QString name = args["name"].toString().trimmed();
+            const bool backfill = args["backfill"].toBool(false);
+            const int days = args["days"].toInt(MarketBreadthService::kDefaultBackfillDays);
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, name, backfill, days](auto resolve) {
+                auto* holder = new QObject(svc);
+                auto request = std::make_shared<QString>();
+                QObject::connect(svc, &MarketBreadthService::updated, holder,
+                                 [resolve, holder, request](QString id, QString universe, QJsonObject summary) {
+                                     if (id != *request)
+                                         return;
+                                     resolve(ToolResult::ok(QString("%1: %2 sessions written through %3")
+                                                                .arg(universe)
+                                                                .arg(summary["rows_written"].toInt())
+                                                                .arg(summary["last_date"].toString("-")),
+                                                            summary));
+                                     holder->deleteLater();
+                                 });
+                QObject::connect(svc, &MarketBreadthService::error_occurred, holder,
+                                 [resolve, holder, request](QString id, QString msg) {
+                                     if (id != *request)
+                                         return;
+                                     resolve(ToolResult::fail(msg));
+                                     holder->deleteLater();
+                                 });
+                *request = svc->update(name, backfill, days);
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── breadth_history ────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "breadth_history";
+        t.description = "Stored daily breadth series of a universe (oldest first): advances, declines, net "
+                        "advances, up/down volume, new highs/lows, % above 50/200-day averages, A/D line, "
+                        "McClellan oscillator and summation index.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("name", "Universe name")
+                             .required()
+                             .integer("days", "Calendar days back from today")
+                             .default_int(90)
+                             .between(1, MarketBreadthService::kMaxBackfillDays)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString name = args["name"].toString().trimmed();
+            const QString since = QDate::currentDate().addDays(-args["days"].toInt(90)).toString(Qt::ISODate);
+            QJsonArray rows;
+            QString error;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto& repo = BreadthRepository::instance();
+                if (!repo.get_universe(name)) {
+                    error = "Unknown breadth universe: " + name;
+                    signal_done();
+                    return;
+                }
+                auto r = repo.get_rows(name, since);
+                if (r.is_err())
+                    error = QString::fromStdString(r.error());
+                else
+                    for (const auto& row : r.value())
+                        rows.append(MarketBreadthService::row_to_json(row));
+                signal_done();
+            });
+            if (!error.isEmpty())
+                return ToolResult::fail(error);
+            if (rows.isEmpty())
+                return ToolResult::fail("No breadth history for " + name + " yet; run breadth_update");
+            return ToolResult::ok_data(QJsonObject{{"universe", name}, {"count", rows.size()}, {"rows", rows}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
@@ -0,0 +1,140 @@
+// CommodityCurveTools.cpp — Commodity term structure (contango/backwardation, roll yield, seasonality) MCP tools
+
+#include "mcp/tools/CommodityCurveTools.h"
+
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "services/commodities/CommodityCurveService.h"
+
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+namespace {
+
+using services::CommodityCurveService;
+
+QJsonObject shape_curve(QJsonObject curve, bool history, bool seasonality) {
+    if (!history)
+        curve.remove("history");
+    if (!seasonality)
+        curve.remove("seasonality");
+    return curve;
+}
+
+QString summary_line(const QJsonObject& c) {
+    return QString("%1: %2, roll yield %3%/yr, slope %4%/yr (as of %5)")
+        .arg(c["name"].toString(), c["structure"].toString())
+        .arg(c["roll_yield_pct"].toDouble(), 0, 'f', 2)
+        .arg(c["slope_pct"].toDouble(), 0, 'f', 2)
+        .arg(c["as_of"].toString());
+}
+
+} // namespace
+
+std::vector<ToolDef> get_commodity_curve_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── commodity_term_structure ───────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "commodity_term_structure";
+        t.description = "Futures term structure for WTI crude, Henry Hub natural gas, heating oil, RBOB gasoline "
+                        "(EIA NYMEX contracts 1-4) and VIX (CBOE): current curve, contango/backwardation, "
+                        "annualised roll yield (front vs second contract, > 0 = backwardation) and its percentile, "
+                        "annualised slope, spot-front basis, calendar-month seasonality and optional weekly "
+                        "history as column arrays. Results are cached; energy curves need EIA_API_KEY.";
+        t.category = "markets";
+        QStringList targets = CommodityCurveService::commodities();
+        targets.prepend("all");
+        t.input_schema = ToolSchemaBuilder()
+                             .string("commodity", "Commodity id, or 'all'")
+                             .default_str("all")
+                             .enums(targets)
+                             .integer("years", "Years of daily history behind the statistics")
+                             .default_int(CommodityCurveService::kDefaultYears)
+                             .between(1, 20)
+                             .boolean("include_history", "Include the weekly chart history arrays")
+                             .default_bool(false)
+                             .boolean("include_seasonality", "Include calendar-month seasonality")
+                             .default_bool(true)
+                             .boolean("refresh", "Recompute even if cached results are fresh")
+                             .default_bool(false)
+                             .build();
+        t.default_timeout_ms = 180000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString target = args["commodity"].toString("all");
+            const int years = args["years"].toInt(CommodityCurveService::kDefaultYears);
+            const bool history = args["include_history"].toBool(false);
+            const bool seasonality = args["include_seasonality"].toBool(true);
+            const bool force = args["refresh"].toBool(false);
+            const QStringList wanted = target == "all" ? CommodityCurveService::commodities() : QStringList{target};
+
+            auto finish = [history, seasonality](const QJsonObject& results, const QJsonObject& errors) {
+                QJsonObject out;
+                QStringList lines;
+                for (auto it = results.begin(); it != results.end(); ++it) {
+                    const QJsonObject c = it.value().toObject();
+                    out[it.key()] = shape_curve(c, history, seasonality);
+                    lines << summary_line(c);
+                }
+                if (out.isEmpty()) {
+                    QStringList msgs;
+                    for (auto it = errors.begin(); it != errors.end(); ++it)
+                        msgs << it.key() + ": " + it.value().toString();
+                    return ToolResult::fail(msgs.isEmpty() ? "No term-structure results" : msgs.join("; "));
+                }
+                QJsonObject data{{"commodities", out}};
+                if (!errors.isEmpty())
+                    data["errors"] = errors;
+                return ToolResult::ok(lines.join("\n"), data);
+            };
+
+            auto* svc = &CommodityCurveService::instance();
+            AsyncDispatch::callback_to_promise(
+                svc, std::move(ctx), promise, [svc, target, years, wanted, force, finish](auto resolve) {
+                    // Cached path: every requested commodity has a fresh result
+                    // computed over the same window.
+                    if (!force) {
+                        QJsonObject cached;
+                        for (const QString& c : wanted) {
+                            if (!svc->is_fresh(c) || svc->latest(c)["years"].toInt() != years)
+                                break;
+                            cached[c] = svc->latest(c);
+                        }
+                        if (cached.size() == wanted.size()) {
+                            resolve(finish(cached, {}));
+                            return;
+                        }
+                    }
+                    auto* holder = new QObject(svc);
+                    auto request = std::make_shared<QString>();
+                    QObject::connect(svc, &CommodityCurveService::updated, holder,
+                                     [resolve, holder, request, finish](QString id, QJsonObject results,
+                                                                        QJsonObject errors) {
+                                         if (id != *request)
+                                             return;
+                                         resolve(finish(results, errors));
+                                         holder->deleteLater();
+                                     });
+                    QObject::connect(svc, &CommodityCurveService::error_occurred, holder,
+                                     [resolve, holder, request](QString id, QString msg) {
+                                         if (id != *request)
+                                             return;
+                                         resolve(ToolResult::fail(msg));
+                                         holder->deleteLater();
+                                     });
+                    *request = svc->refresh(target, years);
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
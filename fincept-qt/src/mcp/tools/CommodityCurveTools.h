@@ -0,0 +1,8 @@
+#pragma once
+#include "mcp/McpTypes.h"
+
+#include <vector>
+
+namespace fincept::mcp::tools {
+std::vector<ToolDef> get_commodity_curve_tools();
+} // namespace fincept::mcp::tools
@@ -0,0 +1,321 @@
+// CotTools.cpp — CFTC Commitments of Traders positioning index, z-scores and signal rule MCP tools
+
+#include "mcp/tools/CotTools.h"
+
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "mcp/tools/ThreadHelper.h"
+#include "services/economics/CotSignalService.h"
+#include "storage/repositories/CotSignalRepository.h"
+
+#include <QCoreApplication>
+#include <QDateTime>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+
+#include <algorithm>
+#include <functional>
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+namespace {
+
+using services::CotSignalService;
+
+const QStringList kCotReportTypes = {"legacy", "disaggregated", "financial"};
+
+const QStringList kCotGroups = {"non_commercial", "commercial",      "non_reportable", "managed_money",
+                                "producer_merchant", "swap_dealer", "leveraged_funds", "asset_manager",
+                                "dealer"};
+
+// Runs `fn` against the repository on the main thread; a non-empty returned
+// string is the failure message.
+ToolResult on_main(const std::function<QString(CotSignalRepository&)>& fn, const std::function<ToolResult()>& ok) {
+    QString error;
+    detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+        error = fn(CotSignalRepository::instance());
+        signal_done();
+    });
+    return error.isEmpty() ? ok() : ToolResult::fail(error);
+}
+
+} // namespace
+
+std::vector<ToolDef> get_cot_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── cot_positioning ────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "cot_positioning";
+        t.description = "CFTC Commitments of Traders positioning for a futures market: per trader group net "
+                        "position, net % of open interest, COT index (net normalised 0-100 over N years; >90 = "
+                        "historically extreme long, <10 = extreme short), week-over-week change and its z-score. "
+                        "Groups: legacy = non_commercial/commercial/non_reportable, disaggregated = managed_money/"
+                        "producer_merchant/swap_dealer, financial = leveraged_funds/asset_manager/dealer.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("market", "Market alias (gold, crude, euro, sp500, bitcoin...), 6-digit CFTC "
+                                               "contract code or name fragment")
+                             .required()
+                             .string("report_type", "COT report")
+                             .default_str("legacy")
+                             .enums(kCotReportTypes)
+                             .number("years", "COT index lookback in years")
+                             .default_num(3)
+                             .between(0.5, 10)
+                             .integer("zscore_window", "Weeks of changes behind the z-score")
+                             .default_int(52)
+                             .between(8, 260)
+                             .boolean("include_history", "Include weekly net / COT index / z-score history")
+                             .default_bool(false)
+                             .build();
+        t.default_timeout_ms = 90000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            auto* svc = &CotSignalService::instance();
+            const QString market = args["market"].toString().trimmed();
+            const QString report_type = args["report_type"].toString("legacy");
+            const double years = args["years"].toDouble(3);
+            const int window = args["zscore_window"].toInt(52);
+            const bool history = args["include_history"].toBool(false);
+            AsyncDispatch::callback_to_promise(
+                svc, std::move(ctx), promise, [svc, market, report_type, years, window, history](auto resolve) {
+                    auto* holder = new QObject(svc);
+                    auto request = std::make_shared<QString>();
+                    QObject::connect(svc, &CotSignalService::positioning_ready, holder,
+                                     [resolve, holder, request, history](QString id, QJsonObject data) {
+                                         if (id != *request)
+                                             return;
+                                         if (!history)
+                                             data.remove("history");
+                                         QStringList parts;
+                                         const QJsonObject groups = data["groups"].toObject();
+                                         for (auto it = groups.begin(); it != groups.end(); ++it) {
+                                             const QJsonObject g = it.value().toObject();
+                                             parts << QString("%1 index %2, z %3")
+                                                          .arg(it.key())
+                                                          .arg(g["cot_index"].toDouble(), 0, 'f', 1)
+                                                          .arg(g["zscore"].toDouble(), 0, 'f', 2);
+                                         }
+                                         resolve(ToolResult::ok(QString("%1 (%2): %3")
+                                                                    .arg(data["market"].toString(),
+                                                                         data["report_date"].toString(),
+                                                                         parts.join("; ")),
+                                                                data));
+                                         holder->deleteLater();
+                                     });
+                    QObject::connect(svc, &CotSignalService::error_occurred, holder,
+                                     [resolve, holder, request](QString id, QString msg) {
+                                         if (id != *request)
+                                             return;
+                                         resolve(ToolResult::fail(msg));
+                                         holder->deleteLater();
+                                     });
+                    *request = svc->positioning(market, report_type, years, window);
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── cot_signal_rule_add ────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "cot_signal_rule_add";
+        t.description = "Create a COT positioning signal rule. Each new weekly report is checked automatically; a "
+                        "signal is stored (and toasted / sent to notification providers) when the group's COT index "
+                        "reaches index_high (extreme_long) or index_low (extreme_short), or the weekly net change "
+                        "z-score reaches zscore_abs (zscore_spike). Set a threshold to 0 to disable that kind.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("name", "Rule name")
+                             .required()
+                             .string("market", "Market alias, CFTC contract code or name fragment")
+                             .required()
+                             .string("report_type", "COT report")
+                             .default_str("legacy")
+                             .enums(kCotReportTypes)
+                             .string("trader_group", "Trader group the rule watches")
+                             .required()
+                             .enums(kCotGroups)
+                             .number("years", "COT index lookback in years")
+                             .default_num(3)
+                             .between(0.5, 10)
+                             .integer("zscore_window", "Weeks of changes behind the z-score")
+                             .default_int(52)
+                             .between(8, 260)
+                             .number("index_high", "COT index at or above which to signal extreme_long (0 = off)")
+                             .default_num(90)
+                             .between(0, 100)
+                             .number("index_low", "COT index at or below which to signal extreme_short (0 = off)")
+                             .default_num(10)
+                             .between(0, 100)
+                             .number("zscore_abs", "|z-score| at or above which to signal zscore_spike (0 = off)")
+                             .default_num(2)
+                             .min(0)
+                             .boolean("toast", "Show an in-app toast")
+                             .default_bool(true)
+                             .boolean("providers", "Also send through configured notification providers")
+                             .default_bool(false)
+                             .build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            CotSignalRule r;
+            r.name = args["name"].toString().trimmed();
+            r.market = args["market"].toString().trimmed();
+            r.report_type = args["report_type"].toString("legacy");
+            r.trader_group = args["trader_group"].toString();
+            r.years = args["years"].toDouble(3);
+            r.zscore_window = args["zscore_window"].toInt(52);
+            r.index_high = args["index_high"].toDouble(90);
+            r.index_low = args["index_low"].toDouble(10);
+            r.zscore_abs = args["zscore_abs"].toDouble(2);
+            r.toast = args["toast"].toBool(true);
+            r.providers = args["providers"].toBool(false);
+            if (r.name.isEmpty() || r.market.isEmpty())
+                return ToolResult::fail("'name' and 'market' are required");
+            if (r.index_low > 0 && r.index_high > 0 && r.index_low >= r.index_high)
+                return ToolResult::fail("'index_low' must be below 'index_high'");
+
+            CotSignalRule created;
+            return on_main(
+                [&](CotSignalRepository& repo) {
+                    auto res = repo.create_rule(r);
+                    if (res.is_err())
+                        return QString::fromStdString(res.error());
+                    created = res.value();
+                    // Evaluate the latest report straight away instead of waiting for the next tick.
+                    CotSignalService::instance().check(created.id);
+                    return QString();
+                },
+                [&]() {
+                    return ToolResult::ok("Created COT signal rule " + created.name,
+                                          CotSignalService::rule_to_json(created));
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── cot_signal_rule_remove ─────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "cot_signal_rule_remove";
+        t.description = "Delete a COT signal rule by id. Signals it already produced are kept.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder().string("id", "Rule id").required().build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString id = args["id"].toString().trimmed();
+            return on_main(
+                [&](CotSignalRepository& repo) {
+                    auto r = repo.remove_rule(id);
+                    return r.is_err() ? QString::fromStdString(r.error()) : QString();
+                },
+                [&]() { return ToolResult::ok("Removed COT signal rule " + id); });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── cot_signal_rules ───────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "cot_signal_rules";
+        t.description = "List COT signal rules with their thresholds, last check time and last report evaluated.";
+        t.category = "markets";
+        t.handler = [](const QJsonObject&) -> ToolResult {
+            QJsonArray list;
+            return on_main(
+                [&](CotSignalRepository& repo) {
+                    auto r = repo.list_rules();
+                    if (r.is_err())
+                        return QString::fromStdString(r.error());
+                    for (const auto& rule : r.value())
+                        list.append(CotSignalService::rule_to_json(rule));
+                    return QString();
+                },
+                [&]() { return ToolResult::ok_data(QJsonObject{{"rules", list}, {"count", list.size()}}); });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── cot_signals ────────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "cot_signals";
+        t.description = "Stored COT positioning signals, newest first, optionally for one rule or market.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("rule_id", "Only signals from this rule")
+                             .string("market", "Market name fragment or CFTC contract code")
+                             .integer("days", "Look back this many days")
+                             .default_int(90)
+                             .between(1, 3650)
+                             .integer("limit", "Maximum signals")
+                             .default_int(50)
+                             .between(1, 500)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString rule_id = args["rule_id"].toString().trimmed();
+            const QString market = args["market"].toString().trimmed();
+            const qint64 since =
+                QDateTime::currentDateTimeUtc().addDays(-std::max(1, args["days"].toInt(90))).toMSecsSinceEpoch();
+            const int limit = std::clamp(args["limit"].toInt(50), 1, 500);
+            QJsonArray list;
+            return on_main(
+                [&](CotSignalRepository& repo) {
+                    auto r = repo.query_signals(rule_id, market, since, limit);
+                    if (r.is_err())
+                        return QString::fromStdString(r.error());
+                    for (const auto& s : r.value())
+                        list.append(CotSignalService::signal_to_json(s));
+                    return QString();
+                },
+                [&]() { return ToolResult::ok_data(QJsonObject{{"signals", list}, {"count", list.size()}}); });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── cot_signal_check ───────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "cot_signal_check";
+        t.description = "Check a COT signal rule against the latest report now. Returns the trader group snapshot "
+                        "and any signals stored for the first time by this check.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder().string("id", "Rule id").required().build();
+        t.default_timeout_ms = 90000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            auto* svc = &CotSignalService::instance();
+            const QString rule_id = args["id"].toString().trimmed();
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, rule_id](auto resolve) {
+                auto* holder = new QObject(svc);
+                auto request = std::make_shared<QString>();
+                QObject::connect(svc, &CotSignalService::rule_checked, holder,
+                                 [resolve, holder, request](QString id, QString, QJsonObject group, QJsonArray fired) {
+                                     if (id != *request)
+                                         return;
+                                     resolve(ToolResult::ok(QString("COT index %1, z-score %2 — %3 new signal(s)")
+                                                                .arg(group["cot_index"].toDouble(), 0, 'f', 1)
+                                                                .arg(group["zscore"].toDouble(), 0, 'f', 2)
+                                                                .arg(fired.size()),
+                                                            QJsonObject{{"group", group}, {"signals", fired}}));
+                                     holder->deleteLater();
+                                 });
+                QObject::connect(svc, &CotSignalService::error_occurred, holder,
+                                 [resolve, holder, request](QString id, QString msg) {
+                                     if (id != *request)
+                                         return;
+                                     resolve(ToolResult::fail(msg));
+                                     holder->deleteLater();
+                                 });
+                *request = svc->check(rule_id);
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
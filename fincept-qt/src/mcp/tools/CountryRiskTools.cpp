@@ -0,0 +1,116 @@
+// CountryRiskTools.cpp — Composite sovereign risk snapshot across IMF, World Bank, BIS and OECD
+
+#include "mcp/tools/CountryRiskTools.h"
+
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "python/PythonRunner.h"
+#include "storage/cache/CacheManager.h"
+
+#include <QJsonDocument>
+#include <QJsonObject>
+#include <QPromise>
+#include <QRegularExpression>
+
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+namespace {
+
+constexpr int kCrTtlSec = 12 * 60 * 60; // sources publish annually/quarterly
+
+} // namespace
+
+std::vector<ToolDef> get_country_risk_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── country_risk ───────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "country_risk";
+        t.description = "Composite country-risk snapshot in one call. Fetches IMF WEO (gross debt, fiscal balance, "
+                        "current account, growth, inflation), World Bank (reserves in months of imports, external "
+                        "debt % GNI), BIS (credit-to-GDP gap) and OECD (unemployment) concurrently, scores fiscal, "
+                        "external, financial and macro pillars 0-100 (higher = riskier) and returns a weighted "
+                        "composite with a low/moderate/elevated/high band. Sources that fail are listed in "
+                        "'errors' and left out of the score.";
+        t.category = "analytics";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("country", "ISO2 or ISO3 country code (e.g. 'US', 'BRA', 'DEU')")
+                             .required()
+                             .boolean("refresh", "Bypass the cached snapshot")
+                             .default_bool(false)
+                             .build();
+        t.default_timeout_ms = 120000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString country = args["country"].toString().trimmed().toUpper();
+            if (!QRegularExpression("^[A-Z]{2,3}$").match(country).hasMatch()) {
+                promise->addResult(ToolResult::fail("'country' must be an ISO2 or ISO3 code"));
+                promise->finish();
+                return;
+            }
+            const QString cache_key = "country_risk:" + country;
+            if (!args["refresh"].toBool(false)) {
+                if (auto cached = fincept::CacheManager::instance().try_get(cache_key)) {
+                    const auto doc = QJsonDocument::fromJson(cached->toUtf8());
+                    if (doc.isObject()) {
+                        promise->addResult(ToolResult::ok_data(doc.object()));
+                        promise->finish();
+                        return;
+                    }
+                }
+            }
+
+            auto* runner = &python::PythonRunner::instance();
+            AsyncDispatch::callback_to_promise(
+                runner, ctx, promise, [runner, country, cache_key, ctx](auto resolve) {
+                    runner->run("country_risk.py", {country},
+                                [resolve, cache_key, ctx](const python::PythonResult& result) {
+                                    if (ctx.cancelled()) {
+                                        resolve(ToolResult::fail("cancelled"));
+                                        return;
+                                    }
+                                    if (!result.success) {
+                                        resolve(ToolResult::fail(result.error.isEmpty() ? "Country risk failed"
+                                                                                        : result.error));
+                                        return;
+                                    }
+                                    const QString out = result.output;
+                                    const int start = out.indexOf('{');
+                                    const int end = out.lastIndexOf('}');
+                                    if (start < 0 || end <= start) {
+                                        resolve(ToolResult::fail("No JSON in output"));
+                                        return;
+                                    }
+                                    const auto obj =
+                                        QJsonDocument::fromJson(out.mid(start, end - start + 1).toUtf8()).object();
+                                    if (!obj["success"].toBool()) {
+                                        resolve(ToolResult::fail(obj["error"].toString("Invalid output")));
+                                        return;
+                                    }
+                                    const QJsonObject data = obj["data"].toObject();
+                                    // Partial snapshots (a source down) are not cached so the next call retries.
+                                    if (data["errors"].toObject().isEmpty()) {
+                                        fincept::CacheManager::instance().put(
+                                            cache_key,
+                                            QVariant(QString::fromUtf8(
+                                                QJsonDocument(data).toJson(QJsonDocument::Compact))),
+                                            kCrTtlSec, "economics");
+                                    }
+                                    const QJsonObject composite = data["composite"].toObject();
+                                    resolve(ToolResult::ok(QString("%1 country risk: %2 (%3)")
+                                                               .arg(data["country"].toObject()["name"].toString())
+                                                               .arg(composite["score"].toDouble(), 0, 'f', 1)
+                                                               .arg(composite["band"].toString()),
+                                                           data));
+                                });
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
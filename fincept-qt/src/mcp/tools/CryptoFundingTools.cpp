@@ -0,0 +1,348 @@
+// CryptoFundingTools.cpp — perpetual funding / open interest snapshot, history, arb spread and alert MCP tools
+
+#include "mcp/tools/CryptoFundingTools.h"
+
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "mcp/tools/ThreadHelper.h"
+#include "services/crypto/FundingRateService.h"
+#include "storage/repositories/CryptoFundingRepository.h"
+
+#include <QCoreApplication>
+#include <QDateTime>
+#include <QJsonArray>
+#include <QJsonObject>
+
+#include <algorithm>
+#include <functional>
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+namespace {
+
+using services::FundingRateService;
+
+// Runs `fn` on the main thread, where the service and repository live; a
+// non-empty returned string is the failure message.
+ToolResult cft_on_main(const std::function<QString()>& fn, const std::function<ToolResult()>& ok) {
+    QString error;
+    detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+        error = fn();
+        signal_done();
+    });
+    return error.isEmpty() ? ok() : ToolResult::fail(error);
+}
+
+} // namespace
+
+std::vector<ToolDef> get_crypto_funding_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── crypto_funding ─────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "crypto_funding";
+        t.description = "Perpetual funding rates and open interest on Binance, Bybit and Hyperliquid for one asset "
+                        "or every tracked asset. Per exchange: raw rate for its own interval, rate normalised to 8h, "
+                        "APR %, next funding time, mark/index price and OI (base and USD). Aggregate: total OI, "
+                        "OI-weighted 8h rate and APR, highest/lowest venue, APR spread and 24h OI change %. Polled "
+                        "every 5 minutes; refresh=true polls now.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("asset", "Base asset (BTC, ETH, PEPE...); omit for every tracked asset")
+                             .boolean("refresh", "Poll the exchanges before answering")
+                             .default_bool(false)
+                             .build();
+        t.default_timeout_ms = 60000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            auto* svc = &FundingRateService::instance();
+            const QString asset = args["asset"].toString().trimmed().toUpper();
+            const bool refresh = args["refresh"].toBool(false);
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, asset, refresh](auto resolve) {
+                auto answer = [svc, asset, resolve](const QStringList& errors) {
+                    QJsonObject data = svc->snapshot(asset);
+                    if (!asset.isEmpty() && data["exchanges"].toArray().isEmpty()) {
+                        resolve(ToolResult::fail(errors.isEmpty() ? "No perpetual listed for " + asset
+                                                                  : errors.join("; ")));
+                        return;
+                    }
+                    data["errors"] = QJsonArray::fromStringList(errors);
+                    resolve(ToolResult::ok_data(data));
+                };
+                if (refresh || svc->last_poll_ms() == 0)
+                    svc->refresh(answer);
+                else
+                    answer(svc->last_errors());
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── crypto_funding_history ─────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "crypto_funding_history";
+        t.description = "Stored funding rate / open interest history of a tracked asset, oldest first, one row per "
+                        "exchange and poll (kept 90 days).";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("asset", "Tracked base asset")
+                             .required()
+                             .string("exchange", "Only this exchange")
+                             .enums(FundingRateService::exchanges())
+                             .integer("days", "Look back this many days")
+                             .default_int(7)
+                             .between(1, 90)
+                             .integer("limit", "Maximum rows (newest kept)")
+                             .default_int(2000)
+                             .between(1, 20000)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString asset = args["asset"].toString().trimmed().toUpper();
+            const QString exchange = args["exchange"].toString().trimmed();
+            const qint64 from =
+                QDateTime::currentDateTimeUtc().addDays(-std::clamp(args["days"].toInt(7), 1, 90)).toMSecsSinceEpoch();
+            const int limit = std::clamp(args["limit"].toInt(2000), 1, 20000);
+            if (asset.isEmpty())
+                return ToolResult::fail("'asset' is required");
+            QJsonArray rows;
+            return cft_on_main(
+                [&]() {
+                    auto r = CryptoFundingRepository::instance().history(asset, exchange, from, limit);
+                    if (r.is_err())
+                        return QString::fromStdString(r.error());
+                    for (const auto& p : r.value())
+                        rows.append(FundingRateService::point_to_json(p));
+                    return QString();
+                },
+                [&]() {
+                    return ToolResult::ok_data(QJsonObject{{"asset", asset}, {"rows", rows}, {"count", rows.size()}});
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── crypto_funding_arb ─────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "crypto_funding_arb";
+        t.description = "Cross-exchange funding arbitrage candidates from the latest poll, widest APR spread first: "
+                        "short the perp where funding is highest, long it where it is lowest. Covers every asset "
+                        "listed on at least two of Binance, Bybit and Hyperliquid, not just tracked ones. Spreads "
+                        "ignore fees, basis and borrow.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .number("min_spread_apr", "Minimum APR spread in percent")
+                             .default_num(10)
+                             .min(0)
+                             .integer("limit", "Maximum candidates")
+                             .default_int(20)
+                             .between(1, 200)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const double min_spread = std::max(0.0, args["min_spread_apr"].toDouble(10));
+            const int limit = std::clamp(args["limit"].toInt(20), 1, 200);
+            QJsonArray list;
+            QString polled_at;
+            return cft_on_main(
+                [&]() {
+                    auto& svc = FundingRateService::instance();
+                    if (svc.last_poll_ms() == 0)
+                        return QString("No funding poll yet — call crypto_funding with refresh=true");
+                    list = svc.arbitrage(min_spread, limit);
+                    polled_at = QDateTime::fromMSecsSinceEpoch(svc.last_poll_ms()).toString(Qt::ISODate);
+                    return QString();
+                },
+                [&]() {
+                    return ToolResult::ok_data(
+                        QJsonObject{{"candidates", list}, {"count", list.size()}, {"polled_at", polled_at}});
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── crypto_funding_assets ──────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "crypto_funding_assets";
+        t.description = "Get or replace the tracked assets: only these have their history stored, Binance open "
+                        "interest fetched and appear in the default crypto_funding snapshot. Omit 'assets' to read.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .array("assets", "Base assets to track (replaces the list, max 50)",
+                                    QJsonObject{{"type", "string"}})
+                             .build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            QStringList wanted;
+            for (const auto& v : args["assets"].toArray())
+                wanted.append(v.toString());
+            const bool write = args.contains("assets");
+            QStringList current;
+            return cft_on_main(
+                [&]() {
+                    auto& svc = FundingRateService::instance();
+                    if (write) {
+                        auto r = svc.set_assets(wanted);
+                        if (r.is_err())
+                            return QString::fromStdString(r.error());
+                    }
+                    current = svc.assets();
+                    return QString();
+                },
+                [&]() {
+                    return ToolResult::ok(write ? "Tracking " + current.join(", ") : current.join(", "),
+                                          QJsonObject{{"assets", QJsonArray::fromStringList(current)}});
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── crypto_funding_rule_add ────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "crypto_funding_rule_add";
+        t.description = "Create a funding / open interest alert rule, evaluated after every poll. Metrics: "
+                        "funding_apr (one exchange, or OI-weighted across all when exchange is omitted), "
+                        "arb_spread_apr (highest minus lowest exchange APR), oi_usd (one exchange or total), "
+                        "oi_change_pct (total OI vs 24h earlier). Fires at most once per cooldown; stored, toasted "
+                        "and optionally sent to notification providers.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("asset", "Base asset")
+                             .required()
+                             .string("metric", "Metric to watch")
+                             .required()
+                             .enums(FundingRateService::metrics())
+                             .string("op", "Comparison")
+                             .default_str("above")
+                             .enums({"above", "below", "abs_above"})
+                             .number("threshold", "Threshold (percent for APR / change metrics, USD for oi_usd)")
+                             .required()
+                             .string("exchange", "Exchange for funding_apr / oi_usd; omit for the aggregate")
+                             .enums(FundingRateService::exchanges())
+                             .string("name", "Rule name")
+                             .integer("cooldown_min", "Minimum minutes between two alerts")
+                             .default_int(240)
+                             .between(5, 10080)
+                             .boolean("toast", "Show an in-app toast")
+                             .default_bool(true)
+                             .boolean("providers", "Also send through configured notification providers")
+                             .default_bool(false)
+                             .build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            FundingAlertRule r;
+            r.asset = args["asset"].toString();
+            r.metric = args["metric"].toString();
+            r.op = args["op"].toString("above");
+            r.threshold = args["threshold"].toDouble();
+            r.exchange = args["exchange"].toString();
+            r.name = args["name"].toString().trimmed();
+            r.cooldown_min = args["cooldown_min"].toInt(240);
+            r.toast = args["toast"].toBool(true);
+            r.providers = args["providers"].toBool(false);
+            if (!args.contains("threshold"))
+                return ToolResult::fail("'threshold' is required");
+
+            FundingAlertRule created;
+            return cft_on_main(
+                [&]() {
+                    auto res = FundingRateService::instance().add_rule(r);
+                    if (res.is_err())
+                        return QString::fromStdString(res.error());
+                    created = res.value();
+                    return QString();
+                },
+                [&]() {
+                    return ToolResult::ok("Created funding alert rule " + created.name,
+                                          FundingRateService::rule_to_json(created));
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── crypto_funding_rule_remove ─────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "crypto_funding_rule_remove";
+        t.description = "Delete a funding alert rule by id. Alerts it already fired are kept.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder().string("id", "Rule id").required().build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString id = args["id"].toString().trimmed();
+            return cft_on_main(
+                [&]() {
+                    auto r = CryptoFundingRepository::instance().remove_rule(id);
+                    return r.is_err() ? QString::fromStdString(r.error()) : QString();
+                },
+                [&]() { return ToolResult::ok("Removed funding alert rule " + id); });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── crypto_funding_rules ───────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "crypto_funding_rules";
+        t.description = "List funding / open interest alert rules with their thresholds and last fire time.";
+        t.category = "markets";
+        t.handler = [](const QJsonObject&) -> ToolResult {
+            QJsonArray list;
+            return cft_on_main(
+                [&]() {
+                    auto r = CryptoFundingRepository::instance().list_rules();
+                    if (r.is_err())
+                        return QString::fromStdString(r.error());
+                    for (const auto& rule : r.value())
+                        list.append(FundingRateService::rule_to_json(rule));
+                    return QString();
+                },
+                [&]() { return ToolResult::ok_data(QJsonObject{{"rules", list}, {"count", list.size()}}); });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── crypto_funding_alerts ──────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "crypto_funding_alerts";
+        t.description = "Fired funding / open interest alerts, newest first, optionally for one rule or asset.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("rule_id", "Only alerts from this rule")
+                             .string("asset", "Only alerts for this asset")
+                             .integer("days", "Look back this many days")
+                             .default_int(30)
+                             .between(1, 3650)
+                             .integer("limit", "Maximum alerts")
+                             .default_int(50)
+                             .between(1, 500)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString rule_id = args["rule_id"].toString().trimmed();
+            const QString asset = args["asset"].toString().trimmed();
+            const qint64 since =
+                QDateTime::currentDateTimeUtc().addDays(-std::max(1, args["days"].toInt(30))).toMSecsSinceEpoch();
+            const int limit = std::clamp(args["limit"].toInt(50), 1, 500);
+            QJsonArray list;
+            return cft_on_main(
+                [&]() {
+                    auto r = CryptoFundingRepository::instance().query_alerts(rule_id, asset, since, limit);
+                    if (r.is_err())
+                        return QString::fromStdString(r.error());
+                    for (const auto& a : r.value())
+                        list.append(FundingRateService::alert_to_json(a));
+                    return QString();
+                },
+                [&]() { return ToolResult::ok_data(QJsonObject{{"alerts", list}, {"count", list.size()}}); });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
@@ -0,0 +1,376 @@
+// CustomIndexTools.cpp — Custom index MCP tools: define indices (constituents, weighting, rebalancing) that are
+// maintained as @-symbols from the candle cache, read their series and rebalances, backfill constituents
+
+#include "mcp/tools/CustomIndexTools.h"
+
+#include "mcp/ToolSchemaBuilder.h"
+#include "mcp/tools/ThreadHelper.h"
+#include "services/markets/CustomIndexService.h"
+
+#include <QCoreApplication>
+#include <QDateTime>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QTimeZone>
+
+#include <algorithm>
+
+namespace fincept::mcp::tools {
+
+namespace {
+
+using services::CustomIndexService;
+
+// The index named by `key`: its @-symbol or its id.
+Result<CustomIndex> cix_find(const QString& key) {
+    auto& repo = CustomIndexRepository::instance();
+    auto r = CustomIndexService::is_index_symbol(key) ? repo.get_by_symbol(key.toUpper()) : repo.get(key);
+    if (r.is_err())
+        return Result<CustomIndex>::err(QString("Unknown custom index: %1").arg(key).toStdString());
+    return r;
+}
+
+// Adds the definition parameters create and update share; create requires
+// constituents and states the defaults.
+void cix_definition_params(ToolSchemaBuilder& b, bool create) {
+    b.array("constituents",
+            "Members as Yahoo tickers, or {symbol, weight} objects (weight in percent, used by the stored-weight "
+            "methods)");
+    if (create)
+        b.required();
+    b.string("method", "Weighting scheme").enums(CustomIndexService::methods());
+    if (create)
+        b.default_str("Equal Weighted");
+    b.string("rebalance", "When weights are reset; between rebalances they drift with prices")
+        .enums(CustomIndexService::rebalance_schedules());
+    if (create)
+        b.default_str("none");
+    b.number("base_value", "Index level on the first day").min(0.000001);
+    if (create)
+        b.default_num(1000.0);
+    b.string("start_date", "First index day, YYYY-MM-DD (default: once every constituent has cached bars)")
+        .string("metric", "Fundamental Weighted: point-in-time metric to weight by (default revenue)")
+        .integer("vol_lookback", "Risk Parity: daily returns in the volatility window (default 63)")
+        .between(20, 756)
+        .number("max_weight", "Capped Weighted: largest weight in percent (default 10)")
+        .between(0.1, 100);
+}
+
+// Applies the definition parameters present in `args` to `idx`.
+void cix_apply(const QJsonObject& args, CustomIndex& idx) {
+    if (args.contains("name"))
+        idx.name = args["name"].toString();
+    if (args.contains("constituents")) {
+        idx.constituents.clear();
+        for (const auto& v : args["constituents"].toArray()) {
+            CustomIndexConstituent c;
+            if (v.isString()) {
+                c.symbol = v.toString();
+            } else {
+                c.symbol = v.toObject()["symbol"].toString();
+                c.weight = v.toObject()["weight"].toDouble();
+            }
+            idx.constituents.append(c);
+        }
+    }
+    if (args.contains("method"))
+        idx.method = args["method"].toString();
+    if (args.contains("rebalance"))
+        idx.rebalance = args["rebalance"].toString();
+    if (args.contains("base_value"))
+        idx.base_value = args["base_value"].toDouble();
+    if (args.contains("start_date"))
+        idx.start_date = args["start_date"].toString().trimmed();
+    for (const char* key : {"metric", "vol_lookback", "max_weight"})
+        if (args.contains(key))
+            idx.options[key] = args[key];
+}
+
+QJsonObject cix_result_json(const CustomIndex& idx) {
+    QJsonObject o = CustomIndexService::index_to_json(idx);
+    if (idx.computed_at > 0)
+        o["last_value"] = CustomIndexRepository::instance().latest_value(idx.id);
+    return o;
+}
+
+} // namespace
+
+std::vector<ToolDef> get_custom_index_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── custom_index_create ────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "custom_index_create";
+        t.description = "Define a custom index maintained as a synthetic symbol (@NAME) usable in charts, "
+                        "watchlist / FinScript expressions, algo backtests and as a portfolio benchmark. Its daily "
+                        "level is chain-linked from the constituents' cached daily bars (Yahoo, exchange YF): "
+                        "weights are set at each rebalance close and drift until the next. Market Cap / Capped / "
+                        "Fundamental weighting use point-in-time fundamentals (fundamentals_pit_ingest first). "
+                        "Constituents without cached bars are reported as missing — run custom_index_backfill.";
+        t.category = "markets";
+        ToolSchemaBuilder b;
+        b.string("name", "Index name; the symbol is @ + the name upper-cased").required();
+        cix_definition_params(b, true);
+        t.input_schema = b.build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            CustomIndex idx;
+            idx.method = "Equal Weighted";
+            cix_apply(args, idx);
+
+            Result<CustomIndex> r = Result<CustomIndex>::err("not run");
+            QJsonObject out;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                r = CustomIndexService::instance().create(idx);
+                if (r.is_ok())
+                    out = cix_result_json(r.value());
+                signal_done();
+            });
+            if (r.is_err())
+                return ToolResult::fail(QString::fromStdString(r.error()));
+            const QString msg = r.value().computed_at > 0
+                                    ? QString("Created %1").arg(r.value().symbol)
+                                    : QString("Created %1; no cached bars yet — run custom_index_backfill")
+                                          .arg(r.value().symbol);
+            return ToolResult::ok(msg, out);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── custom_index_update ────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "custom_index_update";
+        t.description = "Change a custom index's constituents, weighting, rebalance schedule, base value, start "
+                        "date or name, then rebuild its series. The @-symbol stays the same through renames. "
+                        "Only the parameters given change; constituents replaces the whole list.";
+        t.category = "markets";
+        ToolSchemaBuilder b;
+        b.string("index", "@-symbol or id").required().string("name", "New name");
+        cix_definition_params(b, false);
+        t.input_schema = b.build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString key = args["index"].toString().trimmed();
+            Result<CustomIndex> r = Result<CustomIndex>::err("not run");
+            QJsonObject out;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto idx = cix_find(key);
+                if (idx.is_err()) {
+                    r = idx;
+                    signal_done();
+                    return;
+                }
+                CustomIndex changed = idx.value();
+                cix_apply(args, changed);
+                r = CustomIndexService::instance().update(changed);
+                if (r.is_ok())
+                    out = cix_result_json(r.value());
+                signal_done();
+            });
+            if (r.is_err())
+                return ToolResult::fail(QString::fromStdString(r.error()));
+            return ToolResult::ok(QString("Updated %1").arg(r.value().symbol), out);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── custom_index_list ──────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "custom_index_list";
+        t.description = "Custom indices with their @-symbol, constituents, weighting, rebalance schedule, last "
+                        "rebuild time and latest level.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder().build();
+        t.handler = [](const QJsonObject&) -> ToolResult {
+            QString error;
+            QJsonArray out;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto all = CustomIndexRepository::instance().list_all();
+                if (all.is_err())
+                    error = QString::fromStdString(all.error());
+                else
+                    for (const auto& idx : all.value())
+                        out.append(cix_result_json(idx));
+                signal_done();
+            });
+            if (!error.isEmpty())
+                return ToolResult::fail(error);
+            return ToolResult::ok(QString("%1 custom index(es)").arg(out.size()), QJsonObject{{"indices", out}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── custom_index_series ────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "custom_index_series";
+        t.description = "Daily OHLC of a custom index (rebuilt first when stale) and the weights set at each "
+                        "rebalance, plus constituents missing cached bars and names a rebalance left out.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("index", "@-symbol or id")
+                             .required()
+                             .string("from", "First day, YYYY-MM-DD")
+                             .string("to", "Last day, YYYY-MM-DD")
+                             .boolean("rebuild", "Rebuild from the candle cache even when fresh")
+                             .default_bool(false)
+                             .integer("limit", "Most recent bars returned")
+                             .default_int(500)
+                             .between(1, 20000)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString key = args["index"].toString().trimmed();
+            const QDate from = QDate::fromString(args["from"].toString(), Qt::ISODate);
+            const QDate to = QDate::fromString(args["to"].toString(), Qt::ISODate);
+            const int limit = args["limit"].toInt(500);
+            const bool force = args["rebuild"].toBool(false);
+
+            QString error;
+            QJsonObject out;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto idx = cix_find(key);
+                if (idx.is_err()) {
+                    error = QString::fromStdString(idx.error());
+                    signal_done();
+                    return;
+                }
+                auto& svc = CustomIndexService::instance();
+                QJsonArray missing, notes;
+                if (force) {
+                    auto built = svc.rebuild(idx.value());
+                    if (built.is_err()) {
+                        error = QString::fromStdString(built.error());
+                        signal_done();
+                        return;
+                    }
+                    for (const auto& s : built.value().missing)
+                        missing.append(s);
+                    for (const auto& n : built.value().notes)
+                        notes.append(n);
+                }
+                const qint64 from_ms = from.isValid() ? from.startOfDay(QTimeZone::UTC).toMSecsSinceEpoch() : 0;
+                const qint64 to_ms = to.isValid() ? to.endOfDay(QTimeZone::UTC).toMSecsSinceEpoch() : 0;
+                const auto candles = svc.candles(idx.value().symbol, from_ms, to_ms, &error);
+                if (candles.isEmpty()) {
+                    signal_done();
+                    return;
+                }
+                error.clear();
+
+                QJsonArray bars;
+                for (qsizetype i = std::max<qsizetype>(0, candles.size() - limit); i < candles.size(); ++i) {
+                    const auto& c = candles[i];
+                    const QDate d = QDateTime::fromMSecsSinceEpoch(c.timestamp, QTimeZone::UTC).date();
+                    bars.append(QJsonObject{{"date", d.toString(Qt::ISODate)},
+                                            {"open", c.open},
+                                            {"high", c.high},
+                                            {"low", c.low},
+                                            {"close", c.close}});
+                }
+                QJsonArray rebalances;
+                auto rb = CustomIndexRepository::instance().get_rebalances(idx.value().id);
+                if (rb.is_ok())
+                    for (const auto& r : rb.value())
+                        if ((!from.isValid() || r.date >= from.toString(Qt::ISODate)) &&
+                            (!to.isValid() || r.date <= to.toString(Qt::ISODate)))
+                            rebalances.append(QJsonObject{{"date", r.date}, {"weights", r.weights}});
+
+                auto fresh = CustomIndexRepository::instance().get(idx.value().id);
+                out = cix_result_json(fresh.is_ok() ? fresh.value() : idx.value());
+                out["bars"] = bars;
+                out["rebalances"] = rebalances;
+                if (force) {
+                    out["missing"] = missing;
+                    out["notes"] = notes;
+                }
+                signal_done();
+            });
+            if (!error.isEmpty())
+                return ToolResult::fail(error);
+            return ToolResult::ok(QString("%1: %2 bars, %3 rebalances")
+                                      .arg(out["symbol"].toString())
+                                      .arg(out["bars"].toArray().size())
+                                      .arg(out["rebalances"].toArray().size()),
+                                  out);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── custom_index_backfill ──────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "custom_index_backfill";
+        t.description = "Queue a Yahoo daily download of a custom index's constituents into the candle store "
+                        "(a bulk download job, see download_jobs); the index is rebuilt when it finishes.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("index", "@-symbol or id")
+                             .required()
+                             .string("from", "First day, YYYY-MM-DD (default: 10 years back)")
+                             .string("to", "Last day, YYYY-MM-DD (default today)")
+                             .build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString key = args["index"].toString().trimmed();
+            QDate from = QDate::fromString(args["from"].toString(), Qt::ISODate);
+            QDate to = QDate::fromString(args["to"].toString(), Qt::ISODate);
+            if (!to.isValid())
+                to = QDate::currentDate();
+            if (!from.isValid())
+                from = to.addYears(-10);
+
+            Result<DownloadJob> r = Result<DownloadJob>::err("not run");
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto idx = cix_find(key);
+                if (idx.is_err())
+                    r = Result<DownloadJob>::err(idx.error());
+                else
+                    r = CustomIndexService::instance().backfill(idx.value(), from, to);
+                signal_done();
+            });
+            if (r.is_err())
+                return ToolResult::fail(QString::fromStdString(r.error()));
+            return ToolResult::ok(QString("Queued %1 constituent(s) as job %2").arg(r.value().total).arg(r.value().id),
+                                  QJsonObject{{"job_id", r.value().id}, {"symbols", r.value().total}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── custom_index_delete ────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "custom_index_delete";
+        t.description = "Delete a custom index, its stored values and its series in the candle store. Charts and "
+                        "benchmarks using the @-symbol stop resolving.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder().string("index", "@-symbol or id").required().build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString key = args["index"].toString().trimmed();
+            QString error, symbol;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto idx = cix_find(key);
+                if (idx.is_err()) {
+                    error = QString::fromStdString(idx.error());
+                } else {
+                    symbol = idx.value().symbol;
+                    auto r = CustomIndexService::instance().remove(idx.value());
+                    if (r.is_err())
+                        error = QString::fromStdString(r.error());
+                }
+                signal_done();
+            });
+            if (!error.isEmpty())
+                return ToolResult::fail(error);
+            return ToolResult::ok(QString("Deleted %1").arg(symbol.isEmpty() ? key : symbol));
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
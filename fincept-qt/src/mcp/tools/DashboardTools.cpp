@@ -1,12 +1,14 @@
 // DashboardTools.cpp — Tools that drive the Dashboard tab.
 //
-// 24 tools in category "dashboard", grouped:
+// 29 tools in category "dashboard", grouped:
 //   • Discovery       (4)  — WidgetRegistry catalog + DashboardTemplates
 //   • Layout state    (3)  — read / load / save GridLayout via DashboardLayoutRepository
 //   • Layout mutation (6)  — apply_template / clear / add / remove / move / resize
 //   • Widget ops      (4)  — per-instance config get/set, refresh, row height
 //   • View / toolbar  (4)  — refresh all, toggle pulse, toggle compact, stats
 //   • Ticker bar      (3)  — get / set symbols, force refresh
+//   • Definitions     (5)  — saved dashboards with DataHub bindings, run headlessly
+//                            by DashboardRunner (no UI needed)
 //
 // All targets (DashboardCanvas, BaseWidget, repository) are UI-thread only.
 // Every tool dispatches its body onto qApp's thread via
@@ -28,6 +30,8 @@
 #include "screens/dashboard/canvas/WidgetRegistry.h"
 #include "screens/dashboard/canvas/WidgetTile.h"
 #include "screens/dashboard/widgets/BaseWidget.h"
+#include "services/dashboards/DashboardRunner.h"
+#include "storage/repositories/DashboardDefinitionRepository.h"
 #include "storage/repositories/DashboardLayoutRepository.h"
 #include "storage/repositories/SettingsRepository.h"
 
@@ -36,6 +40,7 @@
 #include <QJsonDocument>
 #include <QJsonObject>
 #include <QSet>
+#include <QTimer>
 
 namespace fincept::mcp::tools {
 
@@ -157,6 +162,22 @@ QJsonObject template_to_json(const screens::DashboardTemplate& t) {
     };
 }
 
+// Hub values are arbitrary QVariants; fall back to their string form when
+// Qt has no JSON conversion for the type.
+QJsonValue hub_value_to_json(const QVariant& v) {
+    const QJsonValue j = QJsonValue::fromVariant(v);
+    if (j.isNull() && v.isValid())
+        return v.toString().left(4096);
+    return j;
+}
+
+QJsonObject variant_map_to_json(const QVariantMap& m) {
+    QJsonObject out;
+    for (auto it = m.constBegin(); it != m.constEnd(); ++it)
+        out.insert(it.key(), hub_value_to_json(it.value()));
+    return out;
+}
+
 // Marshal a synchronous body onto the UI thread.
 template <typename BodyFn>
 void run_on_ui(ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise, BodyFn&& body) {
@@ -972,6 +993,188 @@ std::vector<ToolDef> get_dashboard_tools() {
         tools.push_back(std::move(t));
     }
 
+    // ═══════════════════════════════════════════════════════════════════
+    //  DEFINITIONS (5)
+    // ═══════════════════════════════════════════════════════════════════
+
+    {
+        ToolDef t;
+        t.name = "list_saved_dashboards";
+        t.description = "List saved dashboard definitions (id, name, description) and whether each is running.";
+        t.category = "dashboard";
+        t.default_timeout_ms = kDefaultTimeoutMs;
+        t.async_handler = [](const QJsonObject&, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            run_on_ui(std::move(ctx), promise, [](auto resolve) {
+                auto r = DashboardDefinitionRepository::instance().list();
+                if (r.is_err()) {
+                    resolve(ToolResult::fail(QString::fromStdString(r.error())));
+                    return;
+                }
+                auto& runner = services::dashboards::DashboardRunner::instance();
+                QJsonArray arr;
+                for (const auto& d : r.value()) {
+                    QJsonObject o = services::dashboards::dashboard_def_to_json(d);
+                    o.remove("widgets");
+                    o["running"] = runner.is_running(d.id);
+                    arr.append(o);
+                }
+                resolve(ToolResult::ok_data(arr));
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    {
+        ToolDef t;
+        t.name = "save_dashboard_definition";
+        t.description = "Create or replace a saved dashboard. Each widget lists the DataHub topics it is bound to "
+                        "(e.g. market:quote:AAPL, news:symbol:TSLA) and an optional refresh_ms. Omit id to create.";
+        t.category = "dashboard";
+        t.default_timeout_ms = kDefaultTimeoutMs;
+        t.input_schema =
+            ToolSchemaBuilder()
+                .string("id", "Existing dashboard id to replace; omit to create")
+                .string("name", "Dashboard name")
+                .required()
+                .length(1, 128)
+                .string("description", "Optional description")
+                .array("widgets", "Widgets: {widget_id?, widget_type, title?, bindings[], refresh_ms?, x?, y?, w?, h?, "
+                                  "config?}",
+                       QJsonObject{{"type", "object"}})
+                .required()
+                .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            run_on_ui(std::move(ctx), promise, [args](auto resolve) {
+                auto def = services::dashboards::dashboard_def_from_json(args);
+                for (const auto& w : def.widgets) {
+                    if (w.widget_type.isEmpty()) {
+                        resolve(ToolResult::fail("Every widget needs a widget_type"));
+                        return;
+                    }
+                    if (w.refresh_ms > 0 && w.refresh_ms < 1000) {
+                        resolve(ToolResult::fail("refresh_ms must be 0 or at least 1000"));
+                        return;
+                    }
+                }
+                auto r = DashboardDefinitionRepository::instance().save(def);
+                if (r.is_err()) {
+                    resolve(ToolResult::fail(QString::fromStdString(r.error())));
+                    return;
+                }
+                services::dashboards::DashboardRunner::instance().reload(r.value());
+                resolve(ToolResult::ok("Dashboard saved", QJsonObject{{"id", r.value()},
+                                                                      {"topics", QJsonArray::fromStringList(
+                                                                                     def.topics())}}));
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    {
+        ToolDef t;
+        t.name = "delete_dashboard_definition";
+        t.description = "Delete a saved dashboard definition (stops it if running).";
+        t.category = "dashboard";
+        t.is_destructive = true;
+        t.default_timeout_ms = kDefaultTimeoutMs;
+        t.input_schema = ToolSchemaBuilder().string("id", "Dashboard id").required().build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            run_on_ui(std::move(ctx), promise, [args](auto resolve) {
+                const QString id = args["id"].toString();
+                auto& runner = services::dashboards::DashboardRunner::instance();
+                while (runner.is_running(id))
+                    runner.stop(id);
+                auto r = DashboardDefinitionRepository::instance().remove(id);
+                if (r.is_err()) {
+                    resolve(ToolResult::fail(QString::fromStdString(r.error())));
+                    return;
+                }
+                resolve(ToolResult::ok("Dashboard deleted", QJsonObject{{"id", id}}));
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    {
+        ToolDef t;
+        t.name = "get_dashboard_data";
+        t.description = "Run a saved dashboard and return the latest value of every bound topic in one payload. "
+                        "Waits up to wait_ms for the first consolidated update. keep_running=true leaves it "
+                        "running in the background (stop with stop_dashboard_runner).";
+        t.category = "dashboard";
+        t.default_timeout_ms = 60000;
+        t.input_schema = ToolSchemaBuilder()
+                             .string("id", "Dashboard id")
+                             .required()
+                             .integer("wait_ms", "Max time to wait for data when not already running")
+                             .default_int(5000)
+                             .between(0, 45000)
+                             .boolean("keep_running", "Leave the dashboard running after returning")
+                             .default_bool(false)
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString id = args["id"].toString();
+            const int wait_ms = args["wait_ms"].toInt(5000);
+            const bool keep = args["keep_running"].toBool(false);
+            auto* runner = &services::dashboards::DashboardRunner::instance();
+            AsyncDispatch::callback_to_promise(runner, std::move(ctx), promise,
+                                               [runner, id, wait_ms, keep](auto resolve) {
+                const bool was_running = runner->is_running(id);
+                QString err;
+                if (!runner->start(id, &err)) {
+                    resolve(ToolResult::fail("Cannot start dashboard: " + err));
+                    return;
+                }
+                auto* holder = new QObject(runner);
+                auto finish = [runner, holder, id, keep, resolve]() {
+                    if (holder->property("done").toBool())
+                        return;
+                    holder->setProperty("done", true);
+                    resolve(ToolResult::ok_data(QJsonObject{{"id", id},
+                                                            {"values", variant_map_to_json(runner->snapshot(id))},
+                                                            {"errors", variant_map_to_json(runner->errors(id))}}));
+                    if (!keep)
+                        runner->stop(id);
+                    holder->deleteLater();
+                };
+                if (was_running || wait_ms == 0) {
+                    finish();
+                    return;
+                }
+                QObject::connect(runner, &services::dashboards::DashboardRunner::dashboard_updated, holder,
+                                 [id, finish](QString did, QVariantMap) {
+                                     if (did == id)
+                                         finish();
+                                 });
+                QTimer::singleShot(wait_ms, holder, finish);
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    {
+        ToolDef t;
+        t.name = "stop_dashboard_runner";
+        t.description = "Stop a dashboard left running by get_dashboard_data(keep_running=true).";
+        t.category = "dashboard";
+        t.default_timeout_ms = kDefaultTimeoutMs;
+        t.input_schema = ToolSchemaBuilder().string("id", "Dashboard id").required().build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            run_on_ui(std::move(ctx), promise, [args](auto resolve) {
+                const QString id = args["id"].toString();
+                auto& runner = services::dashboards::DashboardRunner::instance();
+                if (!runner.is_running(id)) {
+                    resolve(ToolResult::fail("Dashboard is not running"));
+                    return;
+                }
+                runner.stop(id);
+                resolve(ToolResult::ok("Dashboard runner released",
+                                       QJsonObject{{"id", id}, {"still_running", runner.is_running(id)}}));
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
     LOG_INFO(TAG, QString("Defined %1 dashboard tools").arg(tools.size()));
     return tools;
 }
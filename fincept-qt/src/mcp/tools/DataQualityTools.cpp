@@ -0,0 +1,220 @@
+// DataQualityTools.cpp — Candle store / stream health report, thresholds and range re-fetch MCP tools
+
+#include "mcp/tools/DataQualityTools.h"
+
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "mcp/tools/ThreadHelper.h"
+#include "services/markets/DataQualityService.h"
+
+#include <QCoreApplication>
+#include <QDateTime>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+#include <QTimeZone>
+
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+namespace {
+
+using services::DataQualityService;
+
+// Accepts "yyyy-MM-dd" or a full ISO-8601 timestamp (UTC unless it carries an offset).
+qint64 dq_parse_ms(const QString& s, bool end_of_day) {
+    const QDate d = QDate::fromString(s, Qt::ISODate);
+    if (d.isValid())
+        return QDateTime(end_of_day ? d.addDays(1) : d, QTime(0, 0), QTimeZone::utc()).toMSecsSinceEpoch() -
+               (end_of_day ? 1 : 0);
+    QDateTime dt = QDateTime::fromString(s, Qt::ISODate);
+    if (!dt.isValid())
+        return 0;
+    if (dt.timeSpec() == Qt::LocalTime)
+        dt.setTimeZone(QTimeZone::utc());
+    return dt.toMSecsSinceEpoch();
+}
+
+} // namespace
+
+std::vector<ToolDef> get_data_quality_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── data_quality_report ────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "data_quality_report";
+        t.description = "Health report for the historical candle store and live data streams: intraday gaps and "
+                        "missing sessions, duplicate bars, price spikes beyond the configured robust sigma, invalid "
+                        "bars (high < low, close outside range), stale series, and subscribed DataHub topics that "
+                        "stopped publishing or are erroring. With refetch=true the bad ranges are re-downloaded "
+                        "and replaced in the background (see data_quality_refetch).";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("symbol", "Only this stored symbol")
+                             .string("exchange", "Only this exchange key (e.g. NSE, YF)")
+                             .string("interval", "Only this interval (e.g. 1d, 5m)")
+                             .boolean("refetch", "Queue re-downloads for the ranges found")
+                             .default_bool(false)
+                             .boolean("cached", "Return the last report instead of scanning again")
+                             .default_bool(false)
+                             .build();
+        t.default_timeout_ms = 120000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            auto* svc = &DataQualityService::instance();
+            if (args["cached"].toBool(false) && !svc->last_report().isEmpty()) {
+                promise->addResult(ToolResult::ok_data(svc->last_report()));
+                promise->finish();
+                return;
+            }
+            const QString symbol = args["symbol"].toString().trimmed();
+            const QString exchange = args["exchange"].toString().trimmed();
+            const QString interval = args["interval"].toString().trimmed();
+            const bool refetch = args["refetch"].toBool(false);
+            AsyncDispatch::callback_to_promise(
+                svc, std::move(ctx), promise, [svc, symbol, exchange, interval, refetch](auto resolve) {
+                    auto* holder = new QObject(svc);
+                    auto request = std::make_shared<QString>();
+                    QObject::connect(svc, &DataQualityService::scan_finished, holder,
+                                     [resolve, holder, request](QString id, QJsonObject report) {
+                                         if (id != *request)
+                                             return;
+                                         int issues = 0;
+                                         for (const auto& v : report["issue_counts"].toObject())
+                                             issues += v.toInt();
+                                         resolve(ToolResult::ok(QString("%1 issue(s) in %2 of %3 series, %4 of %5 "
+                                                                        "streams; %6 repair(s) queued")
+                                                                    .arg(issues)
+                                                                    .arg(report["series"].toArray().size())
+                                                                    .arg(report["series_scanned"].toInt())
+                                                                    .arg(report["streams"].toArray().size())
+                                                                    .arg(report["streams_scanned"].toInt())
+                                                                    .arg(report["refetch_queued"].toInt()),
+                                                                report));
+                                         holder->deleteLater();
+                                     });
+                    QObject::connect(svc, &DataQualityService::error_occurred, holder,
+                                     [resolve, holder, request](QString id, QString msg) {
+                                         if (id != *request)
+                                             return;
+                                         resolve(ToolResult::fail(msg));
+                                         holder->deleteLater();
+                                     });
+                    *request = svc->scan(symbol, exchange, interval, refetch);
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── data_quality_config ────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "data_quality_config";
+        t.description = "Read or update the data-quality thresholds. Only the fields passed are changed; call with "
+                        "no arguments to read the current values.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .number("spike_sigma", "Flag returns this many robust (MAD) sigmas from the median")
+                             .min(1)
+                             .integer("gap_bars", "Intraday: missing bars within a session before a gap is flagged")
+                             .min(1)
+                             .integer("max_gap_days", "Calendar days between sessions before a gap is flagged")
+                             .min(1)
+                             .integer("stale_days", "Flag series whose last bar is older than this")
+                             .min(1)
+                             .integer("stream_stale_sec", "Flag subscribed topics silent for longer than this")
+                             .min(10)
+                             .integer("lookback_bars", "Most recent bars checked per series")
+                             .between(50, 100000)
+                             .boolean("auto_refetch", "Let the periodic scan repair what it finds")
+                             .integer("max_refetch", "Series repaired per scan")
+                             .between(0, 500)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            QJsonObject current;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto& svc = DataQualityService::instance();
+                QJsonObject merged = svc.config().to_json();
+                for (auto it = args.begin(); it != args.end(); ++it)
+                    if (merged.contains(it.key()))
+                        merged[it.key()] = it.value();
+                const auto cfg = DataQualityService::Config::from_json(merged);
+                if (!args.isEmpty())
+                    svc.set_config(cfg);
+                current = cfg.to_json();
+                signal_done();
+            });
+            return ToolResult::ok(args.isEmpty() ? "Data-quality thresholds" : "Updated data-quality thresholds",
+                                  current);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── data_quality_refetch ───────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "data_quality_refetch";
+        t.description = "Re-download one stored series over a date range and replace those bars in the candle "
+                        "store (the connected broker, or Yahoo for the YF exchange key). Existing bars in the range "
+                        "are removed first, so duplicates and bad prints go away; the range is left untouched if "
+                        "the source's history doesn't reach back that far.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("symbol", "Stored symbol")
+                             .required()
+                             .string("exchange", "Stored exchange key")
+                             .required()
+                             .string("interval", "Stored interval (1m, 3m, 5m, 15m, 30m, 1h, 4h, 1d)")
+                             .required()
+                             .string("from", "Range start, yyyy-MM-dd or ISO-8601")
+                             .required()
+                             .string("to", "Range end, yyyy-MM-dd or ISO-8601 (default now)")
+                             .build();
+        t.is_destructive = true;
+        t.default_timeout_ms = 120000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            auto* svc = &DataQualityService::instance();
+            const QString symbol = args["symbol"].toString().trimmed();
+            const QString exchange = args["exchange"].toString().trimmed();
+            const QString interval = args["interval"].toString().trimmed();
+            const qint64 from_ms = dq_parse_ms(args["from"].toString().trimmed(), false);
+            const QString to = args["to"].toString().trimmed();
+            const qint64 to_ms = to.isEmpty() ? QDateTime::currentMSecsSinceEpoch() : dq_parse_ms(to, true);
+            if (from_ms <= 0 || to_ms <= 0) {
+                promise->addResult(ToolResult::fail("'from' / 'to' must be yyyy-MM-dd or ISO-8601"));
+                promise->finish();
+                return;
+            }
+            AsyncDispatch::callback_to_promise(
+                svc, std::move(ctx), promise, [svc, symbol, exchange, interval, from_ms, to_ms](auto resolve) {
+                    auto* holder = new QObject(svc);
+                    auto request = std::make_shared<QString>();
+                    QObject::connect(svc, &DataQualityService::repaired, holder,
+                                     [resolve, holder, request](QString id, QJsonObject summary) {
+                                         if (id != *request)
+                                             return;
+                                         resolve(ToolResult::ok(QString("Replaced %1/%2 range(s) from %3 bars")
+                                                                    .arg(summary["replaced"].toInt())
+                                                                    .arg(summary["ranges"].toInt())
+                                                                    .arg(summary["bars_fetched"].toInt()),
+                                                                summary));
+                                         holder->deleteLater();
+                                     });
+                    QObject::connect(svc, &DataQualityService::error_occurred, holder,
+                                     [resolve, holder, request](QString id, QString msg) {
+                                         if (id != *request)
+                                             return;
+                                         resolve(ToolResult::fail(msg));
+                                         holder->deleteLater();
+                                     });
+                    *request = svc->refetch(symbol, exchange, interval, from_ms, to_ms);
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
@@ -0,0 +1,317 @@
+// DocumentIndexTools.cpp — Local document index (RAG) MCP tools.
+// Thin wrappers over AgentService's document index (finagent_core/doc_index.py):
+// every call returns a request id and resolves on document_index_result.
+
+#include "mcp/tools/DocumentIndexTools.h"
+
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "services/agents/AgentService.h"
+
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+#include <QStringList>
+
+#include <algorithm>
+#include <functional>
+
+namespace fincept::mcp::tools {
+
+namespace {
+
+// Indexing re-embeds every chunk; a long 10-K on a remote embedder takes a while.
+constexpr int kDocIndexTimeoutMs = 600000;
+constexpr int kDocSearchTimeoutMs = 120000;
+
+const QStringList kDocKinds = {"filing", "transcript", "note", "file", "other"};
+
+using DocKick = std::function<QString(services::AgentService*)>;
+using DocFormat = std::function<ToolResult(const QJsonObject&)>;
+
+void dispatch_doc_request(DocKick kick, DocFormat format, ToolContext ctx,
+                          std::shared_ptr<QPromise<ToolResult>> promise) {
+    auto* svc = &services::AgentService::instance();
+    AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, kick, format](auto resolve) {
+        auto* holder = new QObject(svc);
+        auto req_id = std::make_shared<QString>();
+        QObject::connect(svc, &services::AgentService::document_index_result, holder,
+                         [req_id, format, resolve, holder](const QString& id, bool ok, const QJsonObject& result) {
+                             if (id != *req_id)
+                                 return;
+                             holder->deleteLater();
+                             if (!ok) {
+                                 resolve(ToolResult::fail(result["error"].toString("Document index request failed")));
+                                 return;
+                             }
+                             resolve(format(result));
+                         });
+        *req_id = kick(svc);
+    });
+}
+
+ToolResult format_indexed(const QJsonObject& r) {
+    const QJsonArray indexed = r["indexed"].toArray();
+    int chunks = 0;
+    int unchanged = 0;
+    for (const auto& v : indexed) {
+        chunks += v.toObject()["chunks"].toInt();
+        unchanged += v.toObject()["unchanged"].toBool() ? 1 : 0;
+    }
+    QString msg = QString("Indexed %1 document(s), %2 chunk(s)").arg(indexed.size()).arg(chunks);
+    if (unchanged > 0)
+        msg += QString(", %1 unchanged").arg(unchanged);
+    const int failed = r["errors"].toArray().size();
+    if (failed > 0)
+        msg += QString(", %1 failed").arg(failed);
+    return ToolResult::ok(msg, r);
+}
+
+QString embedder_arg(const QJsonObject& args) {
+    const QString e = args["embedder"].toString().trimmed();
+    return e.isEmpty() ? QStringLiteral("local") : e;
+}
+
+} // namespace
+
+std::vector<ToolDef> get_document_index_tools() {
+    std::vector<ToolDef> tools;
+
+    const QString embedder_desc = "Embedder: 'local' (offline, default) or '<provider>[:<model>]', "
+                                  "e.g. 'openai:text-embedding-3-small' or 'ollama'";
+
+    // ── search_documents ─────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "search_documents";
+        t.description = "Search the local document index (SEC filings, earnings-call transcripts, the user's notes "
+                        "and files) for passages relevant to a question. Returns numbered excerpts with source "
+                        "citations; quote them and cite as [n] when answering.";
+        t.category = "documents";
+        t.default_timeout_ms = kDocSearchTimeoutMs;
+        t.input_schema = ToolSchemaBuilder()
+                             .string("query", "Question or keywords")
+                             .required()
+                             .length(1, 1000)
+                             .integer("k", "Number of excerpts")
+                             .default_int(8)
+                             .between(1, 30)
+                             .string("kind", "Only this kind of document")
+                             .enums(kDocKinds)
+                             .string("ticker", "Only documents filed under this ticker")
+                             .length(0, 16)
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString query = args["query"].toString().trimmed();
+            const int k = std::clamp(args["k"].toInt(8), 1, 30);
+            const QString kind = args["kind"].toString();
+            const QString ticker = args["ticker"].toString();
+            dispatch_doc_request(
+                [query, k, kind, ticker](services::AgentService* svc) {
+                    return svc->search_documents(query, k, kind, ticker);
+                },
+                [](const QJsonObject& r) {
+                    if (r["count"].toInt() == 0)
+                        return ToolResult::ok("No indexed passages match. Index filings, transcripts or notes "
+                                              "first (index_sec_filing, index_text_document, index_notes).",
+                                              r);
+                    return ToolResult::ok(r["context"].toString(), r);
+                },
+                std::move(ctx), promise);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── index_sec_filing ─────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "index_sec_filing";
+        t.description = "Download a company's latest 10-K, 10-Q or 8-K from SEC EDGAR and add it to the local "
+                        "document index. Re-indexing the same filing is a no-op.";
+        t.category = "documents";
+        t.is_destructive = true;
+        t.default_timeout_ms = kDocIndexTimeoutMs;
+        t.input_schema = ToolSchemaBuilder()
+                             .string("ticker", "Ticker symbol")
+                             .required()
+                             .length(1, 16)
+                             .string("form", "Filing form")
+                             .default_str("10-K")
+                             .enums({"10-K", "10-Q", "8-K"})
+                             .string("embedder", embedder_desc)
+                             .length(0, 128)
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString ticker = args["ticker"].toString();
+            const QString form = args["form"].toString("10-K");
+            const QString embedder = embedder_arg(args);
+            dispatch_doc_request(
+                [ticker, form, embedder](services::AgentService* svc) {
+                    return svc->index_filing(ticker, form, embedder);
+                },
+                format_indexed, std::move(ctx), promise);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── index_text_document ──────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "index_text_document";
+        t.description = "Add text (e.g. an earnings-call transcript or research excerpt) to the local document "
+                        "index. A document with the same kind and source_ref replaces the earlier one.";
+        t.category = "documents";
+        t.is_destructive = true;
+        t.default_timeout_ms = kDocIndexTimeoutMs;
+        t.input_schema = ToolSchemaBuilder()
+                             .string("title", "Document title, shown in citations")
+                             .required()
+                             .length(1, 256)
+                             .string("text", "Full document text")
+                             .required()
+                             .length(1, 5000000)
+                             .string("kind", "Document kind")
+                             .default_str("transcript")
+                             .enums(kDocKinds)
+                             .string("ticker", "Ticker the document is about")
+                             .length(0, 16)
+                             .string("source_ref", "Stable source id or URL (used for citations and de-duplication)")
+                             .length(0, 1024)
+                             .string("doc_date", "Document date, yyyy-MM-dd")
+                             .length(0, 10)
+                             .string("embedder", embedder_desc)
+                             .length(0, 128)
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QJsonObject doc{
+                {"kind", args["kind"].toString("transcript")},
+                {"title", args["title"].toString()},
+                {"text", args["text"].toString()},
+                {"ticker", args["ticker"].toString().trimmed().toUpper()},
+                {"source_ref", args["source_ref"].toString()},
+                {"doc_date", args["doc_date"].toString()},
+            };
+            const QString embedder = embedder_arg(args);
+            dispatch_doc_request(
+                [doc, embedder](services::AgentService* svc) { return svc->index_document(doc, embedder); },
+                format_indexed, std::move(ctx), promise);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── index_file_document ──────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "index_file_document";
+        t.description = "Add a file from the File Manager (PDF, text, Markdown, HTML, CSV) to the local document "
+                        "index by its file id.";
+        t.category = "documents";
+        t.is_destructive = true;
+        t.default_timeout_ms = kDocIndexTimeoutMs;
+        t.input_schema = ToolSchemaBuilder()
+                             .string("file_id", "File Manager file id")
+                             .required()
+                             .length(1, 128)
+                             .string("kind", "Document kind")
+                             .default_str("file")
+                             .enums(kDocKinds)
+                             .string("ticker", "Ticker the document is about")
+                             .length(0, 16)
+                             .string("embedder", embedder_desc)
+                             .length(0, 128)
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString file_id = args["file_id"].toString().trimmed();
+            const QString kind = args["kind"].toString("file");
+            const QString ticker = args["ticker"].toString();
+            const QString embedder = embedder_arg(args);
+            dispatch_doc_request(
+                [file_id, kind, ticker, embedder](services::AgentService* svc) {
+                    return svc->index_file(file_id, kind, ticker, embedder);
+                },
+                format_indexed, std::move(ctx), promise);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── index_notes ──────────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "index_notes";
+        t.description = "Add all of the user's notes to the local document index. Unchanged notes are skipped, "
+                        "edited ones re-indexed.";
+        t.category = "documents";
+        t.is_destructive = true;
+        t.default_timeout_ms = kDocIndexTimeoutMs;
+        t.input_schema = ToolSchemaBuilder()
+                             .boolean("include_archived", "Include archived notes")
+                             .default_bool(false)
+                             .string("embedder", embedder_desc)
+                             .length(0, 128)
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const bool archived = args["include_archived"].toBool(false);
+            const QString embedder = embedder_arg(args);
+            dispatch_doc_request(
+                [embedder, archived](services::AgentService* svc) { return svc->index_notes(embedder, archived); },
+                format_indexed, std::move(ctx), promise);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── list_indexed_documents ───────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "list_indexed_documents";
+        t.description = "Documents in the local index, newest first: kind, title, ticker, source, date, embedder "
+                        "and chunk count.";
+        t.category = "documents";
+        t.default_timeout_ms = kDocSearchTimeoutMs;
+        t.input_schema = ToolSchemaBuilder()
+                             .string("kind", "Only this kind of document")
+                             .enums(kDocKinds)
+                             .string("ticker", "Only documents filed under this ticker")
+                             .length(0, 16)
+                             .integer("limit", "Maximum documents")
+                             .default_int(100)
+                             .between(1, 1000)
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString kind = args["kind"].toString();
+            const QString ticker = args["ticker"].toString();
+            const int limit = std::clamp(args["limit"].toInt(100), 1, 1000);
+            dispatch_doc_request(
+                [kind, ticker, limit](services::AgentService* svc) {
+                    return svc->list_documents(kind, ticker, limit);
+                },
+                [](const QJsonObject& r) { return ToolResult::ok_data(r); }, std::move(ctx), promise);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── remove_indexed_document ──────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "remove_indexed_document";
+        t.description = "Remove a document and its chunks from the local index.";
+        t.category = "documents";
+        t.is_destructive = true;
+        t.default_timeout_ms = kDocSearchTimeoutMs;
+        t.input_schema = ToolSchemaBuilder()
+                             .string("id", "Document id (from list_indexed_documents)")
+                             .required()
+                             .length(1, 64)
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString id = args["id"].toString().trimmed();
+            dispatch_doc_request([id](services::AgentService* svc) { return svc->remove_document(id); },
+                                 [id](const QJsonObject&) { return ToolResult::ok("Removed document " + id); },
+                                 std::move(ctx), promise);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
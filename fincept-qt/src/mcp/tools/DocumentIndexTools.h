@@ -0,0 +1,13 @@
+#pragma once
+// DocumentIndexTools.h — Local document index (RAG) MCP tools
+// Index SEC filings, transcripts, notes and managed files into the
+// chunked / embedded store behind AgentService's document index, and search
+// it for numbered, citable excerpts agents ground their answers in.
+
+#include "mcp/McpTypes.h"
+
+#include <vector>
+
+namespace fincept::mcp::tools {
+std::vector<ToolDef> get_document_index_tools();
+} // namespace fincept::mcp::tools
@@ -0,0 +1,213 @@
+// DownloadTools.cpp — Bulk historical backfill queue MCP tools (queue, progress, pause / resume / cancel)
+
+#include "mcp/tools/DownloadTools.h"
+
+#include "mcp/ToolSchemaBuilder.h"
+#include "mcp/tools/ThreadHelper.h"
+#include "services/markets/HistoryDownloadManager.h"
+
+#include <QCoreApplication>
+#include <QJsonArray>
+#include <QJsonObject>
+
+namespace fincept::mcp::tools {
+
+namespace {
+
+using services::HistoryDownloadManager;
+
+QJsonObject dl_item_json(const DownloadItem& it) {
+    QJsonObject o{{"symbol", it.symbol}, {"status", it.status}, {"bars", it.bars}, {"attempts", it.attempts}};
+    if (!it.error.isEmpty())
+        o["error"] = it.error;
+    return o;
+}
+
+} // namespace
+
+std::vector<ToolDef> get_download_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── download_queue ─────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "download_queue";
+        t.description = "Queue a bulk historical backfill into the candle store — e.g. 500 symbols × 10 years of "
+                        "daily bars. Runs in the background one request at a time at the given rate, backs off "
+                        "when the source rate-limits, retries failures and resumes after a restart. Track it with "
+                        "download_jobs.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .array("symbols", "Symbols on `exchange` (or Yahoo tickers when exchange is YF)",
+                                    QJsonObject{{"type", "string"}})
+                             .required()
+                             .string("exchange", "Exchange of the symbols (NSE, BSE, US, LSE, ..., or YF)")
+                             .default_str("YF")
+                             .string("interval", "Bar interval")
+                             .default_str("1d")
+                             .enums({"1m", "3m", "5m", "15m", "30m", "1h", "1d", "1w"})
+                             .string("from", "First day, yyyy-MM-dd")
+                             .required()
+                             .string("to", "Last day, yyyy-MM-dd (default today)")
+                             .string("source", "yahoo stores under the YF key; broker uses the connected account")
+                             .default_str("yahoo")
+                             .enums({"yahoo", "broker"})
+                             .integer("rate_per_minute", "Requests per minute to stay under")
+                             .default_int(30)
+                             .between(1, 600)
+                             .string("name", "Label for the downloads panel")
+                             .build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            QStringList symbols;
+            for (const auto& v : args["symbols"].toArray())
+                symbols.append(v.toString());
+            DownloadJob spec;
+            spec.name = args["name"].toString();
+            spec.exchange = args["exchange"].toString("YF");
+            spec.interval = args["interval"].toString("1d");
+            spec.from_date = args["from"].toString().trimmed();
+            spec.to_date = args["to"].toString().trimmed();
+            spec.source = args["source"].toString("yahoo");
+            spec.rate_per_minute = args["rate_per_minute"].toInt(30);
+
+            Result<DownloadJob> r = Result<DownloadJob>::err("not run");
+            QJsonObject progress;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                r = HistoryDownloadManager::instance().enqueue(spec, symbols);
+                if (r.is_ok())
+                    progress = HistoryDownloadManager::instance().progress(r.value());
+                signal_done();
+            });
+            if (r.is_err())
+                return ToolResult::fail(QString::fromStdString(r.error()));
+            return ToolResult::ok(QString("Queued %1 symbol(s) as job %2").arg(r.value().total).arg(r.value().id),
+                                  progress);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── download_jobs ──────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "download_jobs";
+        t.description = "Progress of bulk download jobs: symbols done / failed / pending, bars stored, percent, "
+                        "current symbol and ETA. Pass job_id for one job with its per-symbol status.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("job_id", "One job, with its symbols")
+                             .string("status", "Filter jobs by status")
+                             .enums({"queued", "running", "paused", "completed", "failed", "cancelled"})
+                             .string("item_status", "With job_id: only symbols in this state")
+                             .enums({"pending", "done", "failed"})
+                             .integer("limit", "Max jobs (or symbols with job_id)")
+                             .default_int(50)
+                             .between(1, 5000)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString job_id = args["job_id"].toString().trimmed();
+            const QString status = args["status"].toString().trimmed();
+            const QString item_status = args["item_status"].toString().trimmed();
+            const int limit = args["limit"].toInt(50);
+            QString error;
+            QJsonObject out;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto& mgr = HistoryDownloadManager::instance();
+                auto& repo = DownloadJobRepository::instance();
+                if (!job_id.isEmpty()) {
+                    auto job = repo.get(job_id);
+                    if (!job) {
+                        error = "Unknown download job: " + job_id;
+                    } else {
+                        out = mgr.progress(*job);
+                        QJsonArray items;
+                        auto r = repo.items(job_id, item_status, limit);
+                        if (r.is_ok())
+                            for (const auto& it : r.value())
+                                items.append(dl_item_json(it));
+                        out["items"] = items;
+                    }
+                } else {
+                    auto r = repo.list(status, limit);
+                    if (r.is_err()) {
+                        error = QString::fromStdString(r.error());
+                    } else {
+                        QJsonArray jobs;
+                        for (const auto& j : r.value())
+                            jobs.append(mgr.progress(j));
+                        out["jobs"] = jobs;
+                        out["count"] = jobs.size();
+                    }
+                }
+                signal_done();
+            });
+            if (!error.isEmpty())
+                return ToolResult::fail(error);
+            if (!job_id.isEmpty())
+                return ToolResult::ok(QString("%1: %2, %3/%4 done")
+                                          .arg(out["name"].toString(), out["status"].toString())
+                                          .arg(out["done"].toInt())
+                                          .arg(out["total"].toInt()),
+                                      out);
+            return ToolResult::ok(QString("%1 download job(s)").arg(out["count"].toInt()), out);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── download_control ───────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "download_control";
+        t.description = "Pause, resume or cancel a bulk download job, re-queue its failed symbols (retry_failed), "
+                        "or delete it (remove; stored candles are kept).";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("job_id", "Job id")
+                             .required()
+                             .string("action", "What to do")
+                             .required()
+                             .enums({"pause", "resume", "cancel", "retry_failed", "remove"})
+                             .build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString job_id = args["job_id"].toString().trimmed();
+            const QString action = args["action"].toString().trimmed();
+            QString error;
+            int requeued = 0;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto& mgr = HistoryDownloadManager::instance();
+                Result<void> r = Result<void>::ok();
+                if (action == "pause")
+                    r = mgr.pause(job_id);
+                else if (action == "resume")
+                    r = mgr.resume(job_id);
+                else if (action == "cancel")
+                    r = mgr.cancel(job_id);
+                else if (action == "remove")
+                    r = mgr.remove(job_id);
+                else if (action == "retry_failed") {
+                    auto n = mgr.retry_failed(job_id);
+                    if (n.is_ok())
+                        requeued = n.value();
+                    else
+                        r = Result<void>::err(n.error());
+                } else
+                    r = Result<void>::err("Unknown action: " + action.toStdString());
+                if (r.is_err())
+                    error = QString::fromStdString(r.error());
+                signal_done();
+            });
+            if (!error.isEmpty())
+                return ToolResult::fail(error);
+            if (action == "retry_failed")
+                return ToolResult::ok(QString("Re-queued %1 failed symbol(s)").arg(requeued),
+                                      QJsonObject{{"job_id", job_id}, {"requeued", requeued}});
+            return ToolResult::ok(QString("%1: %2").arg(action, job_id));
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
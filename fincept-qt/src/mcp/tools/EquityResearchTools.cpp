@@ -1,6 +1,6 @@
 // EquityResearchTools.cpp — Tools that drive the Equity Research screen.
 //
-// 10 tools in category "equity-research":
+// 11 tools in category "equity-research":
 //   1. search_equity_symbols
 //   2. load_equity_symbol            — combined quote + info + historical
 //   3. get_equity_quote              — quote only (price/change/vol)
@@ -11,6 +11,7 @@
 //   8. get_equity_peers              — peer-group comparison
 //   9. get_equity_news               — recent news articles for a symbol
 //  10. get_equity_sentiment          — MarketSentimentService snapshot
+//  11. get_symbol_deep_dive          — every research slice in one cached payload
 //
 // EquityResearchService signals do NOT carry a per-call request_id; most
 // carry the symbol (or indicator) so we filter by that. Concurrent calls
@@ -652,6 +653,59 @@ std::vector<ToolDef> get_equity_research_tools() {
         tools.push_back(std::move(t));
     }
 
+    // ── 11. get_symbol_deep_dive ────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "get_symbol_deep_dive";
+        t.description = "One-call research snapshot for a symbol: quote, fundamentals, key price levels (52w range, "
+                        "SMAs, pivots, support/resistance), insider activity, news with sentiment, upcoming "
+                        "earnings/dividend dates and analyst ratings/targets. Cached for 5 minutes.";
+        t.category = "equity-research";
+        t.default_timeout_ms = kDefaultTimeoutMs;
+        t.input_schema = ToolSchemaBuilder()
+                             .string("symbol", "Ticker symbol")
+                             .required()
+                             .length(1, 32)
+                             .boolean("include_history", "Include the 1y daily OHLCV candles")
+                             .default_bool(false)
+                             .boolean("include_financials", "Include full income/balance/cash-flow statements")
+                             .default_bool(false)
+                             .boolean("force", "Bypass cache")
+                             .default_bool(false)
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString sym = args["symbol"].toString().toUpper();
+            const bool with_history = args["include_history"].toBool(false);
+            const bool with_financials = args["include_financials"].toBool(false);
+            const bool force = args["force"].toBool(false);
+            auto* svc = &services::equity::EquityResearchService::instance();
+            AsyncDispatch::callback_to_promise(
+                svc, std::move(ctx), promise, [svc, sym, with_history, with_financials, force](auto resolve) {
+                    auto* holder = new QObject(svc);
+                    QObject::connect(svc, &services::equity::EquityResearchService::deep_dive_loaded, holder,
+                                     [sym, with_history, with_financials, resolve, holder](QString s, QJsonObject p) {
+                                         if (s.toUpper() != sym)
+                                             return;
+                                         if (!with_history)
+                                             p.remove("history");
+                                         if (!with_financials)
+                                             p.remove("financials");
+                                         resolve(ToolResult::ok_data(p));
+                                         holder->deleteLater();
+                                     });
+                    QObject::connect(svc, &services::equity::EquityResearchService::error_occurred, holder,
+                                     [resolve, holder](QString context, QString msg) {
+                                         if (context != "DeepDive")
+                                             return;
+                                         resolve(ToolResult::fail(msg));
+                                         holder->deleteLater();
+                                     });
+                    svc->load_deep_dive(sym, force);
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
     LOG_INFO(TAG, QString("Defined %1 equity-research tools").arg(tools.size()));
     return tools;
 }
@@ -0,0 +1,103 @@
+// FactorBacktestTools.cpp — cross-sectional factor backtests: rank a universe on expression composites, hold
+// bucket portfolios between rebalances, report bucket returns, turnover and IC statistics
+
+#include "mcp/tools/FactorBacktestTools.h"
+
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "services/backtesting/FactorBacktest.h"
+
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+using services::backtest::FactorBacktest;
+
+std::vector<ToolDef> get_factor_backtest_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── factor_backtest ────────────────────────────────────────────────
+    {
+        const QString factor_desc =
+            "Ranking terms, each {expression, name?, weight? (default 1), ascending? (lower is better, default "
+            "false)}. Expressions use watchlist column syntax over daily bars (close, change(n), sma(n), "
+            "volatility(n), rsi(n), highest(n), …) and may read point-in-time fundamentals: " +
+            FactorBacktest::variables().join(", ") +
+            ". e.g. momentum = change(252) - change(21); value = earnings_yield; quality = roe - debt_to_equity / 10";
+
+        ToolDef t;
+        t.name = "factor_backtest";
+        t.description = "Backtest a cross-sectional factor: at every rebalance the universe is ranked on a composite "
+                        "of expressions (z-scored, weighted, clipped at ±3) and split into equal-weight buckets "
+                        "(deciles by default, the last bucket highest) held to the next rebalance. Returns "
+                        "per-period bucket returns, per-bucket annual return / volatility / Sharpe / drawdown / "
+                        "turnover, the top-minus-bottom spread, an equal-weight universe benchmark, Spearman IC "
+                        "statistics of the composite and each term, and a bucket monotonicity score. Returns are "
+                        "in percent, net of cost_bps. Fundamental terms need fundamentals_pit_ingest for the "
+                        "universe first. The universe is today's list, so delisted names are missing.";
+        t.category = "algo-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .array("universe", "Tickers to rank (Yahoo symbols)", QJsonObject{{"type", "string"}})
+                             .required()
+                             .array("factors", factor_desc, QJsonObject{{"type", "object"}})
+                             .required()
+                             .string("start_date", "First rebalance on or after, YYYY-MM-DD (default: 5 years back)")
+                             .string("end_date", "Last price date, YYYY-MM-DD (default: today)")
+                             .integer("buckets", "Number of rank buckets")
+                             .default_int(10)
+                             .between(2, 20)
+                             .integer("rebalance_days", "Trading days between rebalances")
+                             .default_int(21)
+                             .between(1, 252)
+                             .number("cost_bps", "Cost per unit traded, in basis points")
+                             .default_num(0.0)
+                             .integer("fundamentals_lag_days", "Days a filing must predate the rebalance")
+                             .default_int(1)
+                             .build();
+        t.default_timeout_ms = 600000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            auto spec = FactorBacktest::spec_from_json(args);
+            if (spec.is_err()) {
+                promise->addResult(ToolResult::fail(QString::fromStdString(spec.error())));
+                promise->finish();
+                return;
+            }
+            auto* svc = &FactorBacktest::instance();
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, spec = spec.value()](auto resolve) {
+                auto* holder = new QObject(svc);
+                auto request = std::make_shared<QString>();
+                QObject::connect(svc, &FactorBacktest::finished, holder,
+                                 [resolve, holder, request](QString id, QJsonObject result) {
+                                     if (id != *request)
+                                         return;
+                                     const QJsonObject ls = result["long_short"].toObject();
+                                     const QString msg = QString("%1 rebalances from %2: top-minus-bottom %3% a year, "
+                                                                 "mean IC %4")
+                                                             .arg(result["periods"].toArray().size())
+                                                             .arg(result["start"].toString())
+                                                             .arg(ls["annual_return"].toDouble())
+                                                             .arg(result["ic"].toObject()["mean"].toDouble());
+                                     resolve(ToolResult::ok(msg, result));
+                                     holder->deleteLater();
+                                 });
+                QObject::connect(svc, &FactorBacktest::error_occurred, holder,
+                                 [resolve, holder, request](QString id, QString msg) {
+                                     if (id != *request)
+                                         return;
+                                     resolve(ToolResult::fail(msg));
+                                     holder->deleteLater();
+                                 });
+                *request = svc->run(spec);
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
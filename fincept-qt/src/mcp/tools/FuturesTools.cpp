@@ -0,0 +1,335 @@
+// FuturesTools.cpp — Futures contract specs, expiry calendar and continuous-contract MCP tools
+
+#include "mcp/tools/FuturesTools.h"
+
+#include "mcp/ToolSchemaBuilder.h"
+#include "mcp/tools/ThreadHelper.h"
+#include "services/futures/FuturesService.h"
+#include "services/markets/HistoryDownloadManager.h"
+
+#include <QCoreApplication>
+#include <QDateTime>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QTimeZone>
+
+#include <algorithm>
+
+namespace fincept::mcp::tools {
+
+namespace {
+
+namespace fu = services::futures;
+
+QJsonObject fu_spec_json(const FuturesSpec& s) {
+    return QJsonObject{{"root", s.root},
+                       {"name", s.name},
+                       {"exchange", s.exchange},
+                       {"currency", s.currency},
+                       {"multiplier", s.multiplier},
+                       {"tick_size", s.tick_size},
+                       {"tick_value", s.multiplier * s.tick_size},
+                       {"months", s.months},
+                       {"expiry_rule", s.expiry_rule},
+                       {"roll_days", s.roll_days},
+                       {"adjust", s.adjust},
+                       {"yahoo_suffix", s.yahoo_suffix},
+                       {"continuous", s.root + "#C"}};
+}
+
+QJsonObject fu_contract_json(const FuturesSpec& s, const fu::FuturesContract& c) {
+    return QJsonObject{{"contract", c.code},
+                       {"year", c.year},
+                       {"month", c.month},
+                       {"expiry", c.expiry.toString(Qt::ISODate)},
+                       {"roll_date", c.roll_date.toString(Qt::ISODate)},
+                       {"expiry_override", c.from_override},
+                       {"yahoo_symbol", s.yahoo_suffix.isEmpty() ? QString() : fu::yahoo_symbol(s, c)}};
+}
+
+QDate fu_date(const QJsonObject& args, const char* key, const QDate& fallback) {
+    const QString v = args[key].toString().trimmed();
+    return v.isEmpty() ? fallback : QDate::fromString(v, Qt::ISODate);
+}
+
+} // namespace
+
+std::vector<ToolDef> get_futures_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── futures_specs ──────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "futures_specs";
+        t.description = "Futures contract specifications: exchange, multiplier, tick size and value, listed months "
+                        "(month codes), last-trade rule, roll offset and back-adjustment method per root (ES, CL, "
+                        "GC, ...). Each root has a continuous symbol ROOT#C usable in charts and backtests.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder().string("root", "One root, e.g. ES").build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString root = args["root"].toString().trimmed().toUpper();
+            QJsonArray out;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                for (const auto& s : fu::FuturesService::instance().specs()) {
+                    if (root.isEmpty() || s.root == root)
+                        out.append(fu_spec_json(s));
+                }
+                signal_done();
+            });
+            if (out.isEmpty() && !root.isEmpty())
+                return ToolResult::fail("No futures spec for root " + root);
+            return ToolResult::ok(QString("%1 futures spec(s)").arg(out.size()),
+                                  QJsonObject{{"specs", out}, {"count", out.size()}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── futures_save_spec ──────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "futures_save_spec";
+        t.description = "Add or update a futures root's specification. Omitted fields keep their current value "
+                        "(or the default for a new root).";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("root", "Root symbol, e.g. ES")
+                             .required()
+                             .length(1, 8)
+                             .string("name", "Description")
+                             .string("exchange", "Exchange key its contracts are stored under (CME, NYMEX, ...)")
+                             .string("currency", "Quote currency")
+                             .number("multiplier", "Contract multiplier")
+                             .min(0)
+                             .number("tick_size", "Minimum price increment")
+                             .min(0)
+                             .string("months", "Listed month codes, e.g. HMUZ")
+                             .string("expiry_rule", "Last-trade date rule")
+                             .enums(fu::expiry_rules())
+                             .integer("roll_days", "Business days before expiry the continuous series rolls")
+                             .between(0, 60)
+                             .string("adjust", "Default back-adjustment for ROOT#C")
+                             .enums(fu::adjust_methods())
+                             .string("yahoo_suffix", "Yahoo suffix for individual contracts, e.g. .CME")
+                             .build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString root = args["root"].toString().trimmed().toUpper();
+            Result<void> r = Result<void>::err("not run");
+            FuturesSpec saved;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto& svc = fu::FuturesService::instance();
+                FuturesSpec s = svc.spec(root).value_or(FuturesSpec{});
+                s.root = root;
+                if (args.contains("name"))
+                    s.name = args["name"].toString();
+                if (args.contains("exchange"))
+                    s.exchange = args["exchange"].toString().trimmed().toUpper();
+                if (args.contains("currency"))
+                    s.currency = args["currency"].toString().trimmed().toUpper();
+                if (args.contains("multiplier"))
+                    s.multiplier = args["multiplier"].toDouble();
+                if (args.contains("tick_size"))
+                    s.tick_size = args["tick_size"].toDouble();
+                if (args.contains("months"))
+                    s.months = args["months"].toString();
+                if (args.contains("expiry_rule"))
+                    s.expiry_rule = args["expiry_rule"].toString();
+                if (args.contains("roll_days"))
+                    s.roll_days = args["roll_days"].toInt();
+                if (args.contains("adjust"))
+                    s.adjust = args["adjust"].toString();
+                if (args.contains("yahoo_suffix"))
+                    s.yahoo_suffix = args["yahoo_suffix"].toString().trimmed().toUpper();
+                r = svc.save_spec(s);
+                saved = svc.spec(root).value_or(s);
+                signal_done();
+            });
+            if (r.is_err())
+                return ToolResult::fail(QString::fromStdString(r.error()));
+            return ToolResult::ok("Saved futures spec " + root, fu_spec_json(saved));
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── futures_calendar ───────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "futures_calendar";
+        t.description = "Expiry calendar for a futures root: each listed contract (ESH26, ...) with its last-trade "
+                        "date, the date the continuous series rolls to the next contract, and its Yahoo ticker. "
+                        "Pass contract + expiry to record an exchange-published last-trade date over the rule "
+                        "(empty expiry clears it).";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("root", "Root symbol, e.g. CL")
+                             .required()
+                             .string("from", "First expiry to list, yyyy-MM-dd (default today)")
+                             .string("to", "Last expiry to list, yyyy-MM-dd (default one year after from)")
+                             .string("contract", "Contract code to override, e.g. CLZ26")
+                             .string("expiry", "With contract: its last-trade date, yyyy-MM-dd")
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString root = args["root"].toString().trimmed().toUpper();
+            const QDate from = fu_date(args, "from", QDate::currentDate());
+            const QDate to = fu_date(args, "to", from.addYears(1));
+            const QString contract = args["contract"].toString().trimmed().toUpper();
+            const QString expiry_s = args["expiry"].toString().trimmed();
+            const QDate expiry = QDate::fromString(expiry_s, Qt::ISODate);
+            if (!from.isValid() || !to.isValid() || to < from)
+                return ToolResult::fail("'from' and 'to' must be yyyy-MM-dd with from <= to");
+            if (!expiry_s.isEmpty() && !expiry.isValid())
+                return ToolResult::fail("'expiry' must be yyyy-MM-dd");
+
+            QString error;
+            QJsonObject out;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto& svc = fu::FuturesService::instance();
+                const auto s = svc.spec(root);
+                if (!s) {
+                    error = "No futures spec for root " + root;
+                } else {
+                    if (!contract.isEmpty()) {
+                        auto r = svc.set_expiry_override(root, contract, expiry);
+                        if (r.is_err())
+                            error = QString::fromStdString(r.error());
+                    }
+                    QJsonArray rows;
+                    for (const auto& c : svc.calendar(root, from, to))
+                        rows.append(fu_contract_json(*s, c));
+                    out = QJsonObject{{"root", root}, {"expiry_rule", s->expiry_rule}, {"contracts", rows}};
+                }
+                signal_done();
+            });
+            if (!error.isEmpty())
+                return ToolResult::fail(error);
+            return ToolResult::ok(
+                QString("%1 %2 contract(s) expiring %3..%4")
+                    .arg(root)
+                    .arg(out["contracts"].toArray().size())
+                    .arg(from.toString(Qt::ISODate), to.toString(Qt::ISODate)),
+                out);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── futures_backfill ───────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "futures_backfill";
+        t.description = "Queue a Yahoo download of every contract of a futures root that is front month at some "
+                        "point in the range, so its continuous series (ROOT#C) can be built. Track it with "
+                        "download_jobs. Yahoo keeps limited history for expired contracts.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("root", "Root symbol, e.g. ES")
+                             .required()
+                             .string("from", "First day, yyyy-MM-dd")
+                             .required()
+                             .string("to", "Last day, yyyy-MM-dd (default today)")
+                             .string("interval", "Bar interval")
+                             .default_str("1d")
+                             .enums({"1m", "5m", "15m", "30m", "1h", "1d"})
+                             .build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString root = args["root"].toString().trimmed().toUpper();
+            const QDate from = fu_date(args, "from", {});
+            const QDate to = fu_date(args, "to", QDate::currentDate());
+            const QString interval = args["interval"].toString("1d");
+            if (!from.isValid() || !to.isValid() || to < from)
+                return ToolResult::fail("'from' and 'to' must be yyyy-MM-dd with from <= to");
+
+            Result<DownloadJob> r = Result<DownloadJob>::err("not run");
+            QJsonObject progress;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                r = fu::FuturesService::instance().backfill(root, interval, from, to);
+                if (r.is_ok())
+                    progress = services::HistoryDownloadManager::instance().progress(r.value());
+                signal_done();
+            });
+            if (r.is_err())
+                return ToolResult::fail(QString::fromStdString(r.error()));
+            return ToolResult::ok(
+                QString("Queued %1 %2 contract(s) as job %3").arg(r.value().total).arg(root, r.value().id), progress);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── futures_continuous ─────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "futures_continuous";
+        t.description = "Build a back-adjusted continuous futures series from cached contracts and store it under "
+                        "its synthetic symbol (ES#C = the root's default method, ES#P panama, ES#R ratio, ES#N "
+                        "unadjusted). Returns the roll log (date, contracts, closes, gap/ratio), contracts with "
+                        "no cached bars, and the last bars. Charts and backtests build the same series on demand.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("symbol", "Continuous symbol, e.g. ES#C")
+                             .required()
+                             .pattern("^[A-Za-z0-9]+#[CcPpRrNn]$")
+                             .string("interval", "Bar interval")
+                             .default_str("1d")
+                             .enums({"1m", "5m", "15m", "30m", "1h", "1d"})
+                             .string("from", "First day, yyyy-MM-dd (default two years ago)")
+                             .string("to", "Last day, yyyy-MM-dd (default today)")
+                             .integer("tail", "Bars to return from the end")
+                             .default_int(20)
+                             .between(0, 5000)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString symbol = args["symbol"].toString().trimmed().toUpper();
+            const QString interval = args["interval"].toString("1d");
+            const QDate to = fu_date(args, "to", QDate::currentDate());
+            const QDate from = fu_date(args, "from", to.addYears(-2));
+            const int tail = args["tail"].toInt(20);
+            if (!from.isValid() || !to.isValid() || to < from)
+                return ToolResult::fail("'from' and 'to' must be yyyy-MM-dd with from <= to");
+            const qint64 from_ms = from.startOfDay(QTimeZone::UTC).toMSecsSinceEpoch();
+            const qint64 to_ms = to.endOfDay(QTimeZone::UTC).toMSecsSinceEpoch();
+
+            QString error;
+            fu::ContinuousSeries series;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                series = fu::FuturesService::instance().continuous(symbol, interval, from_ms, to_ms, &error);
+                signal_done();
+            });
+            if (series.candles.isEmpty())
+                return ToolResult::fail(error.isEmpty() ? "No bars for " + symbol : error);
+
+            QJsonArray rolls;
+            for (const auto& r : series.rolls)
+                rolls.append(fu::roll_to_json(r));
+            QJsonArray bars;
+            for (qsizetype i = std::max<qsizetype>(0, series.candles.size() - tail); i < series.candles.size(); ++i) {
+                const auto& c = series.candles[i];
+                bars.append(QJsonObject{{"timestamp", static_cast<qint64>(c.timestamp)},
+                                        {"open", c.open},
+                                        {"high", c.high},
+                                        {"low", c.low},
+                                        {"close", c.close},
+                                        {"volume", c.volume},
+                                        {"oi", c.oi}});
+            }
+            QJsonObject data{{"symbol", series.symbol},
+                             {"adjust", series.adjust},
+                             {"interval", interval},
+                             {"bars", series.candles.size()},
+                             {"first", static_cast<qint64>(series.candles.first().timestamp)},
+                             {"last", static_cast<qint64>(series.candles.last().timestamp)},
+                             {"rolls", rolls},
+                             {"missing_contracts", QJsonArray::fromStringList(series.missing)},
+                             {"tail", bars}};
+            return ToolResult::ok(QString("%1 (%2): %3 bars, %4 rolls")
+                                      .arg(series.symbol, series.adjust)
+                                      .arg(series.candles.size())
+                                      .arg(series.rolls.size()),
+                                  data);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
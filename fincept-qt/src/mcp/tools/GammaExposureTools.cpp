@@ -0,0 +1,176 @@
+// GammaExposureTools.cpp — Dealer gamma exposure (GEX) MCP tool
+
+#include "mcp/tools/GammaExposureTools.h"
+
+#include "core/logging/Logger.h"
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "python/PythonRunner.h"
+#include "services/options/GammaExposure.h"
+#include "services/options/UnifiedOptionChain.h"
+
+#include <QDate>
+#include <QDateTime>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QJsonObject>
+#include <QPromise>
+#include <QtConcurrent>
+
+#include <cmath>
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+static constexpr const char* TAG = "GammaExposureTools";
+static constexpr int kGexTimeoutMs = 120000;
+
+namespace gex = services::options::gex;
+namespace unified = services::options::unified;
+
+static gex::GexOptions gex_options(const QJsonObject& args) {
+    gex::GexOptions o;
+    o.spot = args["spot"].toDouble(0);
+    o.risk_free_rate = args["risk_free_rate"].toDouble(o.risk_free_rate);
+    o.dividend_yield = args["dividend_yield"].toDouble(o.dividend_yield);
+    o.multiplier = args["multiplier"].toDouble(o.multiplier);
+    o.strike_range_pct = args["strike_range_pct"].toDouble(o.strike_range_pct * 100) / 100.0;
+    o.profile_range_pct = args["profile_range_pct"].toDouble(o.profile_range_pct * 100) / 100.0;
+    o.n_points = args["n_points"].toInt(o.n_points);
+    return o;
+}
+
+static ToolResult gex_result(const QString& symbol, const gex::GexResult& r) {
+    if (r.contracts == 0)
+        return ToolResult::fail("No contracts with open interest and implied volatility to compute GEX from");
+    QJsonObject data = gex::to_json(r);
+    if (!symbol.isEmpty())
+        data["symbol"] = symbol;
+    data["as_of"] = QDateTime::currentDateTimeUtc().toString(Qt::ISODate);
+    const QString zero = std::isfinite(r.zero_gamma) ? QString::number(r.zero_gamma, 'f', 2) : QStringLiteral("n/a");
+    return ToolResult::ok(QString("%1 net GEX %2 M per 1% (%3 contracts), zero gamma %4")
+                              .arg(symbol.isEmpty() ? QStringLiteral("Chain") : symbol)
+                              .arg(r.net_gex / 1e6, 0, 'f', 1)
+                              .arg(r.contracts)
+                              .arg(zero),
+                          data);
+}
+
+std::vector<ToolDef> get_gamma_exposure_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── options_gex ────────────────────────────────────────────────────
+    // Either fetches the chain (symbol + source) or takes one inline (chain +
+    // spot). Gamma is recomputed natively from each contract's IV, so only
+    // strike/expiration/option_type/open_interest/implied_volatility are read.
+    {
+        ToolDef t;
+        t.name = "options_gex";
+        t.description = "Dealer gamma exposure (GEX) from an option chain with open interest: per-strike call/put/"
+                        "net GEX (dollars per 1% move, calls positive, puts negative), aggregate GEX, call and put "
+                        "walls, the zero-gamma level and a net-GEX-vs-spot profile, as column arrays for charting. "
+                        "Pass symbol to fetch the chain, or chain + spot to use your own.";
+        t.category = "markets";
+        t.input_schema =
+            ToolSchemaBuilder()
+                .string("symbol", "Underlying to fetch, e.g. SPY")
+                .string("source", "Chain source (polygon needs POLYGON_API_KEY, tradier TRADIER_API_KEY)")
+                .enums({"cboe", "polygon", "tradier"})
+                .default_str("cboe")
+                .array("chain", "Inline contracts: {strike, expiration (YYYY-MM-DD), option_type (call|put), "
+                                "open_interest, implied_volatility (decimal)}",
+                       QJsonObject{{"type", "object"}})
+                .number("spot", "Underlying price (required with chain; overrides the fetched price)")
+                .min(0)
+                .integer("max_dte", "Only expiries within this many days (0 = all)")
+                .default_int(0)
+                .min(0)
+                .number("risk_free_rate", "Decimal")
+                .default_num(0.045)
+                .number("dividend_yield", "Continuous, decimal")
+                .default_num(0)
+                .number("multiplier", "Shares per contract of open interest")
+                .default_num(100)
+                .number("strike_range_pct", "Per-strike arrays cover spot ± this percent")
+                .default_num(20)
+                .between(1, 100)
+                .number("profile_range_pct", "GEX profile / zero-gamma search covers spot ± this percent")
+                .default_num(15)
+                .between(1, 50)
+                .integer("n_points", "GEX profile samples")
+                .default_int(121)
+                .between(11, 1001)
+                .build();
+        t.default_timeout_ms = kGexTimeoutMs;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString symbol = args["symbol"].toString().trimmed().toUpper();
+            const int max_dte = args["max_dte"].toInt(0);
+            const gex::GexOptions opts = gex_options(args);
+
+            if (args.contains("chain")) {
+                if (opts.spot <= 0) {
+                    promise->addResult(ToolResult::fail("'spot' is required with an inline chain"));
+                    promise->finish();
+                    return;
+                }
+                const auto contracts =
+                    gex::contracts_from_json(args["chain"].toArray(), QDate::currentDate(), max_dte);
+                promise->addResult(gex_result(symbol, gex::compute(contracts, opts)));
+                promise->finish();
+                return;
+            }
+            if (symbol.isEmpty()) {
+                promise->addResult(ToolResult::fail("Provide 'symbol' or 'chain'"));
+                promise->finish();
+                return;
+            }
+
+            const QString source = args["source"].toString("cboe").toLower();
+            const QString script = unified::source_script(source);
+            const QStringList script_args = unified::source_args(source, symbol);
+            auto* runner = &python::PythonRunner::instance();
+            AsyncDispatch::callback_to_promise(runner, ctx, promise, [=](auto resolve) {
+                runner->run(script, script_args, [=](python::PythonResult r) {
+                    if (ctx.cancelled()) {
+                        resolve(ToolResult::fail("cancelled"));
+                        return;
+                    }
+                    if (!r.success) {
+                        resolve(ToolResult::fail(r.error.isEmpty() ? "Chain script failed" : r.error));
+                        return;
+                    }
+                    // Full chains run to megabytes of JSON and the profile
+                    // re-prices every contract ~100 times — off the UI thread.
+                    (void)QtConcurrent::run([=, out = r.output]() {
+                        const QJsonObject o = QJsonDocument::fromJson(python::extract_json(out).toUtf8()).object();
+                        if (o.isEmpty() || (o.contains("error") && !o.value("success").toBool(false))) {
+                            resolve(ToolResult::fail(o.value("error").toString("No JSON in chain script output")));
+                            return;
+                        }
+                        const QJsonObject data = o["data"].toObject();
+                        gex::GexOptions run_opts = opts;
+                        if (run_opts.spot <= 0)
+                            run_opts.spot = data["metadata"].toObject()["current_price"].toDouble();
+                        if (run_opts.spot <= 0) {
+                            resolve(ToolResult::fail("No underlying price in the chain; pass 'spot'"));
+                            return;
+                        }
+                        const auto contracts =
+                            gex::contracts_from_json(data["options"].toArray(), QDate::currentDate(), max_dte);
+                        const gex::GexResult res = gex::compute(contracts, run_opts);
+                        LOG_INFO(TAG, QString("%1 GEX: %2 contracts, net %3")
+                                          .arg(symbol)
+                                          .arg(res.contracts)
+                                          .arg(res.net_gex, 0, 'f', 0));
+                        resolve(gex_result(symbol, res));
+                    });
+                });
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
@@ -0,0 +1,210 @@
+// HeatmapTools.cpp — Live index heatmap MCP tools (sector/industry treemaps)
+
+#include "mcp/tools/HeatmapTools.h"
+
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "mcp/tools/ThreadHelper.h"
+#include "services/markets/HeatmapService.h"
+
+#include <QCoreApplication>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+namespace {
+
+using services::HeatmapService;
+using services::HeatmapSpec;
+
+// Treemaps over a few hundred tickers are large; `depth` cuts the tree at
+// sector (1), industry (2) or ticker (3) level.
+QJsonObject hm_trim(QJsonObject node, int depth) {
+    if (!node.contains("children"))
+        return node;
+    if (depth <= 0) {
+        node["child_count"] = node["children"].toArray().size();
+        node.remove("children");
+        return node;
+    }
+    QJsonArray kids;
+    for (const auto& c : node["children"].toArray())
+        kids.append(hm_trim(c.toObject(), depth - 1));
+    node["children"] = kids;
+    return node;
+}
+
+int hm_depth(const QJsonObject& args) {
+    const QString d = args["depth"].toString("industry");
+    return d == "sector" ? 1 : d == "ticker" ? 3 : 2;
+}
+
+ToolResult hm_result(const QJsonObject& treemap, int depth) {
+    QJsonObject out = treemap;
+    out["root"] = hm_trim(treemap["root"].toObject(), depth);
+    const QJsonObject root = out["root"].toObject();
+    return ToolResult::ok(QString("%1: %2 constituents, %3% cap-weighted, %4 up / %5 down")
+                              .arg(treemap["label"].toString())
+                              .arg(treemap["constituents"].toInt())
+                              .arg(root["change_pct"].toDouble(), 0, 'f', 2)
+                              .arg(root["advancers"].toInt())
+                              .arg(root["decliners"].toInt()),
+                          out);
+}
+
+} // namespace
+
+std::vector<ToolDef> get_heatmap_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── heatmap_start ──────────────────────────────────────────────────
+    // Starts (or joins) a streaming heatmap and waits for its first
+    // treemap. Later updates flow through the `heatmap.updated` event.
+    {
+        ToolDef t;
+        t.name = "heatmap_start";
+        t.description = "Start a live market heatmap over an index's constituents and return its sector → industry "
+                        "→ ticker treemap: market-cap weights and cap-weighted % change per node, advancers and "
+                        "decliners. Sources: nasdaq (screener by exchange and cap tier), cboe (CBOE Europe index "
+                        "constituents, e.g. BUK100P) or an explicit symbol list. The heatmap keeps streaming "
+                        "quotes until heatmap_stop; read it again with heatmap_snapshot.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("source", "Constituent source")
+                             .enums({"nasdaq", "cboe", "symbols"})
+                             .default_str("nasdaq")
+                             .string("index", "nasdaq: exchange (nasdaq, nyse, amex, all); cboe: index symbol")
+                             .string("market_cap", "nasdaq cap tier")
+                             .enums({"mega", "large", "mid", "small", "micro", "all"})
+                             .default_str("mega")
+                             .array("symbols", "Tickers for source=symbols", QJsonObject{{"type", "string"}})
+                             .integer("limit", "Largest N constituents by market cap")
+                             .default_int(500)
+                             .between(1, HeatmapService::kMaxConstituents)
+                             .string("depth", "Deepest level returned")
+                             .enums({"sector", "industry", "ticker"})
+                             .default_str("industry")
+                             .build();
+        t.default_timeout_ms = 120000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            auto* svc = &HeatmapService::instance();
+            const HeatmapSpec spec = HeatmapSpec::from_json(args);
+            const int depth = hm_depth(args);
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, spec, depth](auto resolve) {
+                // Already running: answer from the last treemap. Updates and
+                // errors are always emitted later, so connecting after start()
+                // misses nothing.
+                const QString started = svc->start(spec);
+                const QJsonObject last = svc->snapshot(started);
+                if (!last.isEmpty()) {
+                    resolve(hm_result(last, depth));
+                    return;
+                }
+                auto* holder = new QObject(svc);
+                QObject::connect(svc, &HeatmapService::heatmap_updated, holder,
+                                 [resolve, holder, started, depth](QString id, QJsonObject treemap) {
+                                     if (id != started)
+                                         return;
+                                     resolve(hm_result(treemap, depth));
+                                     holder->deleteLater();
+                                 });
+                QObject::connect(svc, &HeatmapService::error_occurred, holder,
+                                 [resolve, holder, started](QString id, QString msg) {
+                                     if (id != started)
+                                         return;
+                                     resolve(ToolResult::fail(msg));
+                                     holder->deleteLater();
+                                 });
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── heatmap_snapshot ───────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "heatmap_snapshot";
+        t.description = "Latest treemap of a running heatmap (id from heatmap_start or heatmap_list).";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("id", "Heatmap id")
+                             .required()
+                             .string("depth", "Deepest level returned")
+                             .enums({"sector", "industry", "ticker"})
+                             .default_str("industry")
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString id = args["id"].toString().trimmed();
+            if (id.isEmpty())
+                return ToolResult::fail("Missing 'id'");
+            QJsonObject treemap;
+            bool running = false;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                running = HeatmapService::instance().active().contains(id);
+                treemap = HeatmapService::instance().snapshot(id);
+                signal_done();
+            });
+            if (!running)
+                return ToolResult::fail("No running heatmap " + id);
+            if (treemap.isEmpty())
+                return ToolResult::fail("Heatmap " + id + " is still loading its constituents");
+            return hm_result(treemap, hm_depth(args));
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── heatmap_list ───────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "heatmap_list";
+        t.description = "List running heatmaps with their constituent counts and last update time.";
+        t.category = "markets";
+        t.handler = [](const QJsonObject&) -> ToolResult {
+            QJsonArray list;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto& svc = HeatmapService::instance();
+                for (const QString& id : svc.active()) {
+                    const QJsonObject s = svc.snapshot(id);
+                    list.append(QJsonObject{{"id", id},
+                                            {"label", s["label"]},
+                                            {"constituents", s["constituents"]},
+                                            {"quoted", s["quoted"]},
+                                            {"updated_at", s["updated_at"]}});
+                }
+                signal_done();
+            });
+            return ToolResult::ok_data(QJsonObject{{"heatmaps", list}, {"count", list.size()}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── heatmap_stop ───────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "heatmap_stop";
+        t.description = "Stop a running heatmap and drop its quote subscriptions.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder().string("id", "Heatmap id").required().build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString id = args["id"].toString().trimmed();
+            bool running = false;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                running = HeatmapService::instance().active().contains(id);
+                HeatmapService::instance().stop(id);
+                signal_done();
+            });
+            if (!running)
+                return ToolResult::fail("No running heatmap " + id);
+            return ToolResult::ok("Stopped heatmap " + id);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
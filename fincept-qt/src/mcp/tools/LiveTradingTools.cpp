@@ -13,10 +13,13 @@
 
 #include "core/logging/Logger.h"
 #include "mcp/ToolSchemaBuilder.h"
+#include "mcp/tools/ThreadHelper.h"
 #include "trading/AccountManager.h"
 #include "trading/ActionCenter.h"
 #include "trading/BrokerInterface.h"
 #include "trading/BrokerRegistry.h"
+#include "trading/ExecutionAlgoManager.h"
+#include "trading/IntradayPnlService.h"
 #include "trading/TradingTypes.h"
 #include "trading/UnifiedTrading.h"
 
@@ -652,6 +655,175 @@ std::vector<ToolDef> get_live_trading_tools() {
         tools.push_back(std::move(t));
     }
 
+    // ── live_get_intraday_pnl ──────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "live_get_intraday_pnl";
+        t.description = "Streaming intraday P&L across every active account (broker and paper): realized and "
+                        "unrealized per position marked to the live feed, rolled up per account, per strategy "
+                        "(algo deployment, else Manual) and per currency.";
+        t.category = "live-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("account_id", "Limit to one account (default: all active accounts)")
+                             .boolean("include_positions", "Include the per-position rows")
+                             .default_bool(true)
+                             .boolean("refresh", "Re-read positions and realized P&L before answering")
+                             .default_bool(false)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString account_id = args["account_id"].toString().trimmed();
+            if (!account_id.isEmpty() && !AccountManager::instance().has_account(account_id))
+                return ToolResult::fail(QString("Unknown account_id: %1").arg(account_id));
+            auto& svc = IntradayPnlService::instance();
+            IntradayPnlSnapshot snap;
+            detail::run_on_target_thread_sync(&svc, [&]() {
+                if (args["refresh"].toBool(false))
+                    svc.resync();
+                snap = svc.snapshot(account_id);
+            });
+            return ToolResult::ok_data(snap.to_json(args["include_positions"].toBool(true)));
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ════════════════════════════════════════════════════════════════════
+    // Execution algos (ExecutionAlgoManager — paper or live account)
+    // ════════════════════════════════════════════════════════════════════
+
+    // ── live_exec_algo_start ───────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "live_exec_algo_start";
+        t.description = "Work a parent order with an execution algo: TWAP (equal slices over the duration), VWAP "
+                        "(slices weighted by an intraday volume profile), POV (a share of the traded volume each "
+                        "interval) or iceberg (shows display_quantity at a time). Children route like any order of "
+                        "the account — simulated on paper accounts, REAL orders on live ones. Returns the algo id; "
+                        "poll live_exec_algo_status for progress and implementation shortfall. Requires confirmation.";
+        t.category = "live-trading";
+        t.auth_required = AuthLevel::Authenticated;
+        t.is_destructive = true;
+        t.input_schema = ToolSchemaBuilder()
+                             .string("account_id", "Broker account ID (optional if exactly one active account)")
+                             .string("algo", "Execution algo")
+                             .required()
+                             .enums({"twap", "vwap", "pov", "iceberg"})
+                             .string("symbol", "Trading symbol")
+                             .required()
+                             .length(1, 64)
+                             .string("exchange", "Exchange (e.g. NSE, NFO)")
+                             .required()
+                             .string("side", "Order side")
+                             .required()
+                             .enums({"BUY", "SELL"})
+                             .number("quantity", "Parent quantity (must be > 0)")
+                             .required()
+                             .min(0.0)
+                             .string("product", "Product type")
+                             .default_str("MIS")
+                             .enums({"MIS", "CNC", "NRML"})
+                             .integer("duration_minutes", "TWAP/VWAP span; POV/iceberg time limit (0 = none)")
+                             .between(0, 1440)
+                             .integer("interval_seconds", "Time between children")
+                             .between(5, 3600)
+                             .number("participation", "POV: share of market volume, 0.01–0.5")
+                             .default_num(0.1)
+                             .number("display_quantity", "Iceberg: visible child size")
+                             .number("limit_price", "Limit price for every child (omit for market children)")
+                             .number("lot_size", "Child quantities are multiples of this")
+                             .default_num(1)
+                             .array("volume_profile", "VWAP: relative volume per slice (default U-shaped)",
+                                    QJsonObject{{"type", "number"}})
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            QString account_id, err;
+            if (!resolve_account(args["account_id"].toString(), account_id, err))
+                return ToolResult::fail(err);
+            // Semi-Auto accounts approve every order by hand; an algo sending
+            // children on a clock cannot wait for that.
+            if (ActionCenter::instance().should_queue(account_id, "placeorder"))
+                return ToolResult::fail("Account is in Semi-Auto mode — execution algos need Auto mode");
+
+            QJsonObject spec = args;
+            spec["account_id"] = account_id;
+            ExecAlgoParams params;
+            if (!ExecAlgoParams::from_json(spec, params, &err))
+                return ToolResult::fail(err);
+
+            auto& mgr = ExecutionAlgoManager::instance();
+            QString id;
+            detail::run_on_target_thread_sync(&mgr, [&]() { id = mgr.start(params, &err); });
+            if (id.isEmpty())
+                return ToolResult::fail(err);
+            QJsonObject status;
+            detail::run_on_target_thread_sync(&mgr, [&]() { status = mgr.status(id); });
+            return ToolResult::ok("Execution algo started", status);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── live_exec_algo_status ──────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "live_exec_algo_status";
+        t.description = "Progress of an execution algo: filled / working / remaining quantity, every child order "
+                        "and the implementation shortfall against the arrival price (execution and opportunity "
+                        "cost, in bps and currency).";
+        t.category = "live-trading";
+        t.input_schema = ToolSchemaBuilder().string("algo_id", "Id from live_exec_algo_start").required().build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString id = args["algo_id"].toString().trimmed();
+            auto& mgr = ExecutionAlgoManager::instance();
+            QJsonObject status;
+            detail::run_on_target_thread_sync(&mgr, [&]() { status = mgr.status(id); });
+            if (status.isEmpty())
+                return ToolResult::fail("Unknown execution algo: " + id);
+            return ToolResult::ok_data(status);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── live_exec_algo_list ────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "live_exec_algo_list";
+        t.description = "Execution algos of this session, newest first, with progress and shortfall.";
+        t.category = "live-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .boolean("include_finished", "Include completed / cancelled / expired algos")
+                             .default_bool(true)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            auto& mgr = ExecutionAlgoManager::instance();
+            QJsonArray algos;
+            detail::run_on_target_thread_sync(
+                &mgr, [&]() { algos = mgr.list(args["include_finished"].toBool(true)); });
+            return ToolResult::ok_data(algos);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── live_exec_algo_cancel ──────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "live_exec_algo_cancel";
+        t.description = "Stop an execution algo: no further children are sent and working ones are cancelled. "
+                        "Fills so far are kept.";
+        t.category = "live-trading";
+        t.auth_required = AuthLevel::Authenticated;
+        t.is_destructive = true;
+        t.input_schema = ToolSchemaBuilder().string("algo_id", "Id from live_exec_algo_start").required().build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString id = args["algo_id"].toString().trimmed();
+            auto& mgr = ExecutionAlgoManager::instance();
+            bool ok = false;
+            detail::run_on_target_thread_sync(&mgr, [&]() { ok = mgr.cancel(id); });
+            if (!ok)
+                return ToolResult::fail("No running execution algo with id " + id);
+            return ToolResult::ok("Execution algo cancelling", QJsonObject{{"algo_id", id}});
+        };
+        tools.push_back(std::move(t));
+    }
+
     // ════════════════════════════════════════════════════════════════════
     // Market data (read-only — is_destructive = false)
     // ════════════════════════════════════════════════════════════════════
@@ -0,0 +1,128 @@
+// MacroModelTools.cpp — Recession probability and GDP nowcast MCP tools
+
+#include "mcp/tools/MacroModelTools.h"
+
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "services/economics/MacroModelService.h"
+
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+
+#include <algorithm>
+#include <functional>
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+namespace {
+
+using services::MacroModelService;
+
+using Shape = std::function<ToolResult(const QJsonObject& result)>;
+
+// Resolves with `shape(latest)` — straight from the stored result when it is
+// fresh and no refresh was asked for, otherwise after a model run.
+void with_models(bool force, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise, Shape shape) {
+    auto* svc = &MacroModelService::instance();
+    AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, force, shape](auto resolve) {
+        if (!force && svc->is_fresh()) {
+            resolve(shape(svc->latest()));
+            return;
+        }
+        auto* holder = new QObject(svc);
+        auto request = std::make_shared<QString>();
+        QObject::connect(svc, &MacroModelService::updated, holder,
+                         [resolve, holder, request, shape](QString id, QJsonObject result) {
+                             if (id != *request)
+                                 return;
+                             resolve(shape(result));
+                             holder->deleteLater();
+                         });
+        QObject::connect(svc, &MacroModelService::error_occurred, holder,
+                         [resolve, holder, request, svc](QString id, QString msg) {
+                             if (id != *request)
+                                 return;
+                             // A failed refresh still leaves the last good run usable.
+                             const QJsonObject last = svc->latest();
+                             resolve(ToolResult::fail(last.isEmpty() ? msg
+                                                                     : msg + " (last successful run: " +
+                                                                           last["computed_at"].toString() + ")"));
+                             holder->deleteLater();
+                         });
+        *request = svc->refresh();
+    });
+}
+
+} // namespace
+
+std::vector<ToolDef> get_macro_model_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── macro_recession_probability ────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "macro_recession_probability";
+        t.description = "Probability of a US recession within 12 months from a probit model on FRED data (10Y-3M "
+                        "Treasury spread, initial jobless claims growth, Philly/Empire Fed manufacturing activity), "
+                        "with the classic spread-only model, coefficients and monthly history. Needs FRED_API_KEY.";
+        t.category = "analytics";
+        t.input_schema = ToolSchemaBuilder()
+                             .integer("history_months", "Months of probability history to return")
+                             .default_int(60)
+                             .between(0, MacroModelService::kHistoryMonths)
+                             .boolean("refresh", "Re-run the model even if the stored result is fresh")
+                             .default_bool(false)
+                             .build();
+        t.default_timeout_ms = 180000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const int months = args["history_months"].toInt(60);
+            with_models(args["refresh"].toBool(false), std::move(ctx), promise, [months](const QJsonObject& all) {
+                QJsonObject rec = all["recession"].toObject();
+                const QJsonArray history = rec["history"].toArray();
+                QJsonArray trimmed;
+                for (int i = std::max(0, int(history.size()) - months); i < history.size(); ++i)
+                    trimmed.append(history[i]);
+                rec["history"] = trimmed;
+                rec["computed_at"] = all["computed_at"];
+                return ToolResult::ok(QString("Recession probability (12m ahead, as of %1): %2%")
+                                          .arg(rec["as_of"].toString())
+                                          .arg(rec["probability"].toDouble() * 100, 0, 'f', 1),
+                                      rec);
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── macro_gdp_nowcast ──────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "macro_gdp_nowcast";
+        t.description = "Nowcast of current-quarter US real GDP growth (q/q SAAR %) from bridge equations on "
+                        "industrial production, payrolls, real retail sales and jobless claims, with the months of "
+                        "data available, coefficients, fit statistics and the last official print. Needs "
+                        "FRED_API_KEY.";
+        t.category = "analytics";
+        t.input_schema = ToolSchemaBuilder()
+                             .boolean("refresh", "Re-run the model even if the stored result is fresh")
+                             .default_bool(false)
+                             .build();
+        t.default_timeout_ms = 180000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            with_models(args["refresh"].toBool(false), std::move(ctx), promise, [](const QJsonObject& all) {
+                QJsonObject now = all["nowcast"].toObject();
+                now["computed_at"] = all["computed_at"];
+                return ToolResult::ok(QString("%1 GDP nowcast: %2% SAAR")
+                                          .arg(now["quarter"].toString())
+                                          .arg(now["nowcast_saar_pct"].toDouble(), 0, 'f', 2),
+                                      now);
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
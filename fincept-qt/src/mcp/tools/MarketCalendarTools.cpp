@@ -0,0 +1,145 @@
+// MarketCalendarTools.cpp — Exchange calendar MCP tools: session status and next open/close, holiday and early-close
+// listings, and user-recorded closures
+
+#include "mcp/tools/MarketCalendarTools.h"
+
+#include "core/market/MarketCalendar.h"
+#include "mcp/ToolSchemaBuilder.h"
+
+#include <QDate>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QTime>
+
+namespace fincept::mcp::tools {
+
+using core::market::MarketCalendar;
+
+std::vector<ToolDef> get_market_calendar_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── get_market_status ──────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "get_market_status";
+        t.description = "Whether an exchange is open right now: session phase (pre_market, regular, break, "
+                        "post_market, closed), exchange-local time, today's holiday or early close, and the next "
+                        "regular open and close in UTC. Aliases resolve (NASDAQ → NYSE, NFO/BSE → NSE, BINANCE → "
+                        "CRYPTO). Without an exchange, reports every calendar.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("exchange", "Exchange code or alias, e.g. NYSE, NSE, LSE, CRYPTO")
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            auto& cal = MarketCalendar::instance();
+            const QString exchange = args["exchange"].toString().trimmed();
+            if (!exchange.isEmpty()) {
+                if (!cal.knows(exchange))
+                    return ToolResult::fail(QString("Unknown exchange: %1 (known: %2)")
+                                                .arg(exchange, cal.exchanges().join(", ")));
+                const auto s = cal.status(exchange);
+                return ToolResult::ok(QString("%1 is %2").arg(s.exchange, s.open ? "open" : "closed"),
+                                      s.to_json());
+            }
+            QJsonArray out;
+            int open = 0;
+            for (const auto& code : cal.exchanges()) {
+                const auto s = cal.status(code);
+                open += s.open ? 1 : 0;
+                out.append(s.to_json());
+            }
+            return ToolResult::ok(QString("%1 of %2 markets open").arg(open).arg(out.size()),
+                                  QJsonObject{{"markets", out}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── get_market_holidays ────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "get_market_holidays";
+        t.description = "Trading holidays and early closes of an exchange between two dates, from the bundled "
+                        "calendar files plus user additions. covered_years lists the years the files cover; dates "
+                        "outside them trade on the weekday schedule.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("exchange", "Exchange code or alias")
+                             .required()
+                             .string("from", "First day, YYYY-MM-DD (default today)")
+                             .string("to", "Last day, YYYY-MM-DD (default one year after from)")
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            auto& cal = MarketCalendar::instance();
+            const QString exchange = args["exchange"].toString().trimmed();
+            if (!cal.knows(exchange))
+                return ToolResult::fail(QString("Unknown exchange: %1").arg(exchange));
+            QDate from = QDate::fromString(args["from"].toString(), Qt::ISODate);
+            if (!from.isValid())
+                from = QDate::currentDate();
+            QDate to = QDate::fromString(args["to"].toString(), Qt::ISODate);
+            if (!to.isValid())
+                to = from.addYears(1);
+            if (to < from)
+                return ToolResult::fail("'to' is before 'from'");
+
+            QJsonArray rows, years;
+            for (const auto& h : cal.holidays(exchange, from, to))
+                rows.append(h);
+            const auto c = cal.calendar(exchange);
+            for (int y : c.holiday_years)
+                years.append(y);
+            return ToolResult::ok(QString("%1: %2 holiday(s) / early close(s)").arg(c.code).arg(rows.size()),
+                                  QJsonObject{{"exchange", c.code},
+                                              {"from", from.toString(Qt::ISODate)},
+                                              {"to", to.toString(Qt::ISODate)},
+                                              {"covered_years", years},
+                                              {"holidays", rows}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── add_market_holiday ─────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "add_market_holiday";
+        t.description = "Record an exchange closure — or, with early_close, a shortened session — in the user "
+                        "calendar file for that exchange (e.g. an unscheduled closure or a year the bundled file "
+                        "does not cover). Replaces an existing entry for the same date. Paper trading, algo "
+                        "deployments and the trading-hours workflow node pick it up immediately.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("exchange", "Exchange code or alias")
+                             .required()
+                             .string("date", "YYYY-MM-DD")
+                             .required()
+                             .string("name", "Reason, e.g. 'National Day of Mourning'")
+                             .required()
+                             .string("early_close", "Exchange-local close time HH:MM for a shortened session")
+                             .build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString exchange = args["exchange"].toString().trimmed();
+            const QDate date = QDate::fromString(args["date"].toString(), Qt::ISODate);
+            if (!date.isValid())
+                return ToolResult::fail("date must be YYYY-MM-DD");
+            QTime early;
+            if (args.contains("early_close")) {
+                early = QTime::fromString(args["early_close"].toString(), "HH:mm");
+                if (!early.isValid())
+                    return ToolResult::fail("early_close must be HH:MM");
+            }
+            auto r = MarketCalendar::instance().add_holiday(exchange, date, args["name"].toString(), early);
+            if (r.is_err())
+                return ToolResult::fail(QString::fromStdString(r.error()));
+            const QString code = MarketCalendar::instance().resolve(exchange);
+            return ToolResult::ok(early.isValid() ? QString("%1 closes at %2 on %3")
+                                                        .arg(code, early.toString("HH:mm"), date.toString(Qt::ISODate))
+                                                  : QString("%1 closed on %2").arg(code, date.toString(Qt::ISODate)));
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
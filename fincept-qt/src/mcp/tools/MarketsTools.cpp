@@ -3,11 +3,16 @@
 #include "mcp/tools/MarketsTools.h"
 
 #include "core/logging/Logger.h"
+#include "core/time/Timestamps.h"
+#include "mcp/ToolSchemaBuilder.h"
 #include "mcp/tools/ThreadHelper.h"
 #include "python/PythonRunner.h"
 #include "services/markets/MarketDataService.h"
+#include "services/markets/QuoteRouter.h"
+#include "services/markets/SymbologyService.h"
 #include "storage/cache/CacheManager.h"
 
+#include <QCoreApplication>
 #include <QDateTime>
 #include <QJsonArray>
 #include <QJsonDocument>
@@ -21,62 +26,119 @@ static constexpr const char* TAG = "MarketsTools";
 // IPOs/delistings settle within a day, so an hour is a generous floor.
 static constexpr int kSymbolSearchTtlSec = 60 * 60;
 
+// Array item schema for a quote provider id.
+static QJsonObject quote_provider_item() {
+    return QJsonObject{{"type", "string"}, {"enum", QJsonArray::fromStringList(services::QuoteRouter::providers())}};
+}
+
 std::vector<ToolDef> get_markets_tools() {
     std::vector<ToolDef> tools;
 
     // ── get_quote ───────────────────────────────────────────────────────
-    // Synchronous fetch via MarketDataService. The service batches/dedups
-    // (100 ms window) and publishes results to the DataHub `market:quote:<sym>`
-    // topic as a side effect, so the same call warms the hub for any
-    // streaming subscribers. Worker-thread blocking is bridged via
-    // detail::run_async_wait — the service lives on the main thread.
+    // Routed through QuoteRouter. A yfinance-only chain (the default) is a
+    // synchronous fetch via MarketDataService, which batches/dedups (100 ms
+    // window) and publishes results to the DataHub `market:quote:<sym>` topic
+    // as a side effect, so the same call warms the hub for any streaming
+    // subscribers. Worker-thread blocking is bridged via
+    // detail::run_async_wait — both services live on the main thread.
     {
         ToolDef t;
         t.name = "get_quote";
         t.description = "Fetch the latest stock/ETF/crypto quote (price, change, "
-                        "high/low, volume). Backed by yfinance. Symbols accept "
-                        "AAPL, BTC-USD, ^GSPC, GBPUSD=X, GC=F, etc.";
+                        "high/low, volume). Symbols accept AAPL, BTC-USD, ^GSPC, "
+                        "GBPUSD=X, GC=F, etc. Providers are tried in priority order "
+                        "(the user's default chain, yfinance unless changed with "
+                        "set_quote_provider_chain) with failover on errors and rate "
+                        "limits; 'sources' says which provider served each field.";
         t.category = "markets";
         t.input_schema.properties = QJsonObject{
-            {"symbol", QJsonObject{{"type", "string"}, {"description", "Ticker symbol (e.g. AAPL, BTC-USD)"}}}};
+            {"symbol", QJsonObject{{"type", "string"}, {"description", "Ticker symbol (e.g. AAPL, BTC-USD)"}}},
+            {"providers",
+             QJsonObject{{"type", "array"},
+                         {"items", quote_provider_item()},
+                         {"description", "Provider priority list for this call, e.g. [\"polygon\", \"fmp\", "
+                                         "\"yfinance\"] (default: the user's chain)"}}}};
         t.input_schema.required = {"symbol"};
         t.handler = [](const QJsonObject& args) -> ToolResult {
             QString symbol = args["symbol"].toString().trimmed().toUpper();
             if (symbol.isEmpty())
                 return ToolResult::fail("Missing 'symbol'");
+            QStringList chain;
+            for (const auto& v : args["providers"].toArray())
+                chain.append(v.toString());
 
-            auto* svc = &services::MarketDataService::instance();
+            auto* router = &services::QuoteRouter::instance();
             bool ok = false;
-            services::QuoteData q;
-            detail::run_async_wait(svc, [svc, symbol, &ok, &q](auto signal_done) {
-                svc->fetch_quotes({symbol},
-                                  [&ok, &q, symbol, signal_done](bool success, QVector<services::QuoteData> quotes) {
-                                      if (success) {
-                                          for (const auto& candidate : quotes) {
-                                              if (candidate.symbol.compare(symbol, Qt::CaseInsensitive) == 0) {
-                                                  q = candidate;
-                                                  ok = true;
-                                                  break;
-                                              }
-                                          }
-                                      }
-                                      signal_done();
-                                  });
+            QJsonObject quote;
+            QString error;
+            detail::run_async_wait(router, [router, symbol, chain, &ok, &quote, &error](auto signal_done) {
+                router->fetch(symbol, chain,
+                              [&ok, &quote, &error, signal_done](bool success, const QJsonObject& q, const QString& e) {
+                                  ok = success;
+                                  quote = q;
+                                  error = e;
+                                  signal_done();
+                              });
             });
 
             if (!ok) {
-                LOG_WARN(TAG, "fetch_quotes returned no data for " + symbol);
-                return ToolResult::fail("No quote data available for " + symbol);
+                LOG_WARN(TAG, "No provider returned a quote for " + symbol + ": " + error);
+                return ToolResult::fail(error.isEmpty() ? "No quote data available for " + symbol : error);
             }
+            return ToolResult::ok_data(quote);
+        };
+        tools.push_back(std::move(t));
+    }
 
-            return ToolResult::ok_data(QJsonObject{{"symbol", q.symbol},
-                                                   {"name", q.name},
-                                                   {"price", q.price},
-                                                   {"change", q.change},
-                                                   {"change_pct", q.change_pct},
-                                                   {"high", q.high},
-                                                   {"low", q.low},
-                                                   {"volume", q.volume}});
+    // ── quote_provider_chain / set_quote_provider_chain ─────────────────
+    {
+        ToolDef t;
+        t.name = "quote_provider_chain";
+        t.description = "The user's default quote provider priority list used by get_quote, and the providers "
+                        "available to it.";
+        t.category = "markets";
+        t.handler = [](const QJsonObject&) -> ToolResult {
+            QStringList chain;
+            detail::run_async_wait(QCoreApplication::instance(), [&chain](auto signal_done) {
+                chain = services::QuoteRouter::instance().default_chain();
+                signal_done();
+            });
+            return ToolResult::ok_data(
+                QJsonObject{{"chain", QJsonArray::fromStringList(chain)},
+                            {"providers", QJsonArray::fromStringList(services::QuoteRouter::providers())}});
+        };
+        tools.push_back(std::move(t));
+    }
+    {
+        ToolDef t;
+        t.name = "set_quote_provider_chain";
+        t.description = "Set the user's default quote provider priority list, e.g. [\"polygon\", \"fmp\", "
+                        "\"yfinance\"]. Keyed providers need their API key in Settings > Credentials; ones "
+                        "without a key are skipped at fetch time.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .array("chain", "Providers in priority order", quote_provider_item())
+                             .required()
+                             .build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            QStringList chain;
+            for (const auto& v : args["chain"].toArray())
+                chain.append(v.toString());
+            QString error;
+            QStringList stored;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto& router = services::QuoteRouter::instance();
+                auto r = router.set_default_chain(chain);
+                if (r.is_err())
+                    error = QString::fromStdString(r.error());
+                stored = router.default_chain();
+                signal_done();
+            });
+            if (!error.isEmpty())
+                return ToolResult::fail(error);
+            return ToolResult::ok("Quote provider chain: " + stored.join(" → "),
+                                  QJsonObject{{"chain", QJsonArray::fromStringList(stored)}});
         };
         tools.push_back(std::move(t));
     }
@@ -258,6 +320,86 @@ std::vector<ToolDef> get_markets_tools() {
         tools.push_back(std::move(t));
     }
 
+    // ── get_broker_history ──────────────────────────────────────────────
+    // Intraday/daily OHLCV from the user's own connected broker via
+    // MarketDataService::fetch_broker_history. Each broker module serves it
+    // through its get_history endpoint; the normalized bars also land in the
+    // shared candle cache (HistoricalDataStore) for charts and backtests.
+    {
+        ToolDef t;
+        t.name = "get_broker_history";
+        t.description = "Fetch historical intraday or daily OHLCV candles from a connected broker account "
+                        "that lists the symbol (TCS.NS → NSE via an Indian broker, AAPL → US via Alpaca/IBKR). "
+                        "Bars are also saved to the local candle cache. Returns {broker, tz, bars[{timestamp_ms, "
+                        "date (UTC), exchange_time, open, high, low, close, volume}]}. Fails when no connected "
+                        "broker lists the market — use get_history for Yahoo data instead.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("symbol", "Yahoo-style symbol (e.g. RELIANCE.NS, AAPL)")
+                             .required()
+                             .string("timeframe", "Bar size (default 1m)")
+                             .enums({"1m", "5m", "15m", "30m", "1h", "1d"})
+                             .integer("days", "Lookback in calendar days (default 5)")
+                             .default_int(5)
+                             .between(1, 3650)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString symbol = args["symbol"].toString().trimmed().toUpper();
+            if (symbol.isEmpty())
+                return ToolResult::fail("Missing 'symbol'");
+            QString timeframe = args["timeframe"].toString().trimmed();
+            if (timeframe.isEmpty())
+                timeframe = "1m";
+            const int days = args.contains("days") ? args["days"].toInt() : 5;
+
+            auto* svc = &services::MarketDataService::instance();
+            bool ok = false;
+            QVector<trading::BrokerCandle> candles;
+            QString broker, error;
+            detail::run_async_wait(svc, [svc, symbol, timeframe, days, &ok, &candles, &broker,
+                                         &error](auto signal_done) {
+                svc->fetch_broker_history(symbol, timeframe, days,
+                                          [&, signal_done](bool success, const QVector<trading::BrokerCandle>& result,
+                                                           const QString& broker_id, const QString& err) {
+                                              ok = success;
+                                              candles = result;
+                                              broker = broker_id;
+                                              error = err;
+                                              signal_done();
+                                          });
+            });
+
+            if (!ok) {
+                LOG_WARN(TAG, QString("fetch_broker_history failed for %1 [%2]: %3").arg(symbol, timeframe, error));
+                return ToolResult::fail("No broker history for " + symbol + ": " + error);
+            }
+
+            // Bars are UTC; exchange_time is the same instant on the listing's clock.
+            const QString exchange = services::SymbologyService::parse(symbol).exchange;
+            const QTimeZone zone = core::time::exchange_zone(exchange.isEmpty() ? QStringLiteral("US") : exchange);
+            QJsonArray bars;
+            for (const auto& c : candles) {
+                bars.append(QJsonObject{
+                    {"timestamp_ms", static_cast<double>(c.timestamp)},
+                    {"date", QDateTime::fromMSecsSinceEpoch(c.timestamp, QTimeZone::UTC).toString(Qt::ISODate)},
+                    {"exchange_time", QDateTime::fromMSecsSinceEpoch(c.timestamp, zone).toString(Qt::ISODate)},
+                    {"open", c.open},
+                    {"high", c.high},
+                    {"low", c.low},
+                    {"close", c.close},
+                    {"volume", c.volume}});
+            }
+            return ToolResult::ok_data(QJsonObject{{"symbol", symbol},
+                                                   {"broker", broker},
+                                                   {"timeframe", timeframe},
+                                                   {"tz", QString::fromUtf8(zone.id())},
+                                                   {"days", days},
+                                                   {"count", bars.size()},
+                                                   {"bars", bars}});
+        };
+        tools.push_back(std::move(t));
+    }
+
     return tools;
 }
 
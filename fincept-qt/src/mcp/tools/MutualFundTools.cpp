@@ -0,0 +1,310 @@
+// MutualFundTools.cpp — Mutual fund MCP tools (AMFI + US NAV, rolling/SIP returns, category compare)
+
+#include "mcp/tools/MutualFundTools.h"
+
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "services/mutual_funds/MutualFundService.h"
+
+#include <QDate>
+#include <QJsonArray>
+#include <QPromise>
+
+#include <cmath>
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+using services::mutual_funds::MutualFundResult;
+using services::mutual_funds::MutualFundService;
+
+// NAV history downloads go through Python; mfapi/yfinance can take a while on
+// a cold cache for 20+ year histories.
+static constexpr int kMfTimeoutMs = 60000;
+
+static QString region_arg(const QJsonObject& args) {
+    return args["region"].toString("IN").toUpper();
+}
+
+// Trailing CAGR in percent, null when the history is too short.
+static QJsonValue trailing_pct(const QVector<services::mutual_funds::NavPoint>& navs, double years) {
+    const double r = MutualFundService::trailing_cagr(navs, years);
+    return std::isfinite(r) ? QJsonValue(std::round(r * 10000.0) / 100.0) : QJsonValue(QJsonValue::Null);
+}
+
+static QStringList fund_ids(const QJsonObject& args) {
+    QStringList ids;
+    for (const auto& v : args["funds"].toArray()) {
+        const QString id = v.toString().trimmed();
+        if (!id.isEmpty() && !ids.contains(id))
+            ids << id;
+    }
+    return ids;
+}
+
+std::vector<ToolDef> get_mutual_fund_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── mf_search ───────────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "mf_search";
+        t.description = "Search Indian mutual fund schemes by name. Returns AMFI scheme codes usable with the "
+                        "other mf_* tools (region IN). US funds are addressed by ticker directly.";
+        t.category = "mutual-funds";
+        t.input_schema =
+            ToolSchemaBuilder().string("query", "Scheme name fragment, e.g. 'parag parikh flexi'").required().build();
+        t.default_timeout_ms = kMfTimeoutMs;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString query = args["query"].toString().trimmed();
+            if (query.isEmpty()) {
+                promise->addResult(ToolResult::fail("Missing 'query'"));
+                promise->finish();
+                return;
+            }
+            auto* svc = &MutualFundService::instance();
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, query](auto resolve) {
+                svc->search(query, [resolve](const MutualFundResult& r) {
+                    resolve(r.success ? ToolResult::ok_data(r.data) : ToolResult::fail(r.error));
+                });
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── mf_amfi_nav_all ─────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "mf_amfi_nav_all";
+        t.description = "Latest AMFI daily NAV for every Indian mutual fund scheme with AMC and SEBI category. "
+                        "Filter by category substring (e.g. 'Large Cap', 'Liquid') to keep the result small.";
+        t.category = "mutual-funds";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("category", "SEBI category substring filter (empty = all schemes)")
+                             .default_str("")
+                             .integer("limit", "Maximum schemes returned")
+                             .default_int(200)
+                             .between(1, 5000)
+                             .build();
+        t.default_timeout_ms = kMfTimeoutMs;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString category = args["category"].toString();
+            const int limit = args["limit"].toInt(200);
+            auto* svc = &MutualFundService::instance();
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, category, limit](auto resolve) {
+                svc->fetch_amfi_nav_all(category, [resolve, limit](const MutualFundResult& r) {
+                    if (!r.success) {
+                        resolve(ToolResult::fail(r.error));
+                        return;
+                    }
+                    QJsonObject out = r.data;
+                    const QJsonArray all = out["data"].toArray();
+                    if (all.size() > limit) {
+                        QJsonArray trimmed;
+                        for (int i = 0; i < limit; ++i)
+                            trimmed.append(all[i]);
+                        out["data"] = trimmed;
+                        out["truncated"] = true;
+                    }
+                    resolve(ToolResult::ok_data(out));
+                });
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── mf_nav_history ──────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "mf_nav_history";
+        t.description = "Historical NAV for a mutual fund plus trailing 1/3/5y CAGR. India: AMFI scheme code; "
+                        "US: fund ticker (e.g. VFIAX). Cached for 12h.";
+        t.category = "mutual-funds";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("fund", "AMFI scheme code (IN) or fund ticker (US)")
+                             .required()
+                             .string("region", "Fund domicile")
+                             .enums({"IN", "US"})
+                             .default_str("IN")
+                             .string("from", "Only return NAVs on/after this date (YYYY-MM-DD)")
+                             .build();
+        t.default_timeout_ms = kMfTimeoutMs;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString fund = args["fund"].toString().trimmed();
+            const QString region = region_arg(args);
+            const QDate from = QDate::fromString(args["from"].toString(), Qt::ISODate);
+            auto* svc = &MutualFundService::instance();
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, fund, region, from](auto resolve) {
+                svc->fetch_nav_history(fund, region, [resolve, from](const MutualFundResult& r) {
+                    if (!r.success) {
+                        resolve(ToolResult::fail(r.error));
+                        return;
+                    }
+                    const auto navs = MutualFundService::parse_nav_points(r.data);
+                    QJsonObject out = r.data;
+                    out["return_1y"] = trailing_pct(navs, 1.0);
+                    out["return_3y"] = trailing_pct(navs, 3.0);
+                    out["return_5y"] = trailing_pct(navs, 5.0);
+                    if (from.isValid()) {
+                        QJsonArray kept;
+                        for (const auto& p : navs)
+                            if (p.date >= from)
+                                kept.append(QJsonObject{{"date", p.date.toString(Qt::ISODate)}, {"nav", p.nav}});
+                        out["data"] = kept;
+                    }
+                    resolve(ToolResult::ok_data(out));
+                });
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── mf_rolling_returns ──────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "mf_rolling_returns";
+        t.description = "Rolling annualised returns for a fund over a holding window (e.g. every 3-year period). "
+                        "Returns mean/median/min/max, % of periods positive / above 8% / above 12%, and the series.";
+        t.category = "mutual-funds";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("fund", "AMFI scheme code (IN) or fund ticker (US)")
+                             .required()
+                             .string("region", "Fund domicile")
+                             .enums({"IN", "US"})
+                             .default_str("IN")
+                             .number("window_years", "Holding period in years")
+                             .default_num(3.0)
+                             .between(0.25, 20.0)
+                             .build();
+        t.default_timeout_ms = kMfTimeoutMs;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString fund = args["fund"].toString().trimmed();
+            const QString region = region_arg(args);
+            const double window = args["window_years"].toDouble(3.0);
+            auto* svc = &MutualFundService::instance();
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, fund, region, window](auto resolve) {
+                svc->fetch_nav_history(fund, region, [resolve, window](const MutualFundResult& r) {
+                    if (!r.success) {
+                        resolve(ToolResult::fail(r.error));
+                        return;
+                    }
+                    QJsonObject out = MutualFundService::rolling_returns(MutualFundService::parse_nav_points(r.data),
+                                                                         window);
+                    if (out["observations"].toInt() == 0) {
+                        resolve(ToolResult::fail("NAV history is shorter than the rolling window"));
+                        return;
+                    }
+                    out["id"] = r.data["id"];
+                    out["name"] = r.data["name"];
+                    resolve(ToolResult::ok_data(out));
+                });
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── mf_sip_returns ──────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "mf_sip_returns";
+        t.description = "Simulate a monthly SIP (systematic investment plan) into a fund using historical NAVs. "
+                        "Returns invested amount, units, current value, absolute return and XIRR.";
+        t.category = "mutual-funds";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("fund", "AMFI scheme code (IN) or fund ticker (US)")
+                             .required()
+                             .string("region", "Fund domicile")
+                             .enums({"IN", "US"})
+                             .default_str("IN")
+                             .number("amount", "Monthly installment in fund currency")
+                             .required()
+                             .min(1.0)
+                             .string("start_date", "First installment month (YYYY-MM-DD; default: 5 years ago)")
+                             .string("end_date", "Valuation date (YYYY-MM-DD; default: latest NAV)")
+                             .integer("day_of_month", "Installment day (1-28; next NAV date if a holiday)")
+                             .default_int(1)
+                             .between(1, 28)
+                             .boolean("include_schedule", "Include per-installment rows")
+                             .default_bool(false)
+                             .build();
+        t.default_timeout_ms = kMfTimeoutMs;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString fund = args["fund"].toString().trimmed();
+            const QString region = region_arg(args);
+            const double amount = args["amount"].toDouble(0.0);
+            QDate start = QDate::fromString(args["start_date"].toString(), Qt::ISODate);
+            if (!start.isValid())
+                start = QDate::currentDate().addYears(-5);
+            const QDate end = QDate::fromString(args["end_date"].toString(), Qt::ISODate);
+            const int dom = args["day_of_month"].toInt(1);
+            const bool schedule = args["include_schedule"].toBool(false);
+            auto* svc = &MutualFundService::instance();
+            AsyncDispatch::callback_to_promise(
+                svc, std::move(ctx), promise, [svc, fund, region, amount, start, end, dom, schedule](auto resolve) {
+                    svc->fetch_nav_history(fund, region, [=](const MutualFundResult& r) {
+                        if (!r.success) {
+                            resolve(ToolResult::fail(r.error));
+                            return;
+                        }
+                        QJsonObject out = MutualFundService::sip_returns(MutualFundService::parse_nav_points(r.data),
+                                                                         amount, start, end, dom);
+                        if (out.contains("error")) {
+                            resolve(ToolResult::fail(out["error"].toString()));
+                            return;
+                        }
+                        if (!schedule)
+                            out.remove("schedule");
+                        out["id"] = r.data["id"];
+                        out["name"] = r.data["name"];
+                        out["currency"] = r.data["currency"];
+                        resolve(ToolResult::ok_data(out));
+                    });
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── mf_category_compare ─────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "mf_category_compare";
+        t.description = "Compare mutual funds (typically peers in one category) on 1/3/5y CAGR, 3y volatility, "
+                        "3y max drawdown and 3y rolling-return consistency, with per-metric ranks and peer averages.";
+        t.category = "mutual-funds";
+        t.input_schema = ToolSchemaBuilder()
+                             .array("funds", "AMFI scheme codes (IN) or fund tickers (US), 2-15 funds",
+                                    QJsonObject{{"type", "string"}})
+                             .required()
+                             .string("region", "Fund domicile")
+                             .enums({"IN", "US"})
+                             .default_str("IN")
+                             .build();
+        t.default_timeout_ms = 2 * kMfTimeoutMs;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QStringList ids = fund_ids(args);
+            if (ids.size() < 2 || ids.size() > 15) {
+                promise->addResult(ToolResult::fail("Provide between 2 and 15 distinct funds"));
+                promise->finish();
+                return;
+            }
+            const QString region = region_arg(args);
+            auto* svc = &MutualFundService::instance();
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, ids, region](auto resolve) {
+                svc->fetch_nav_histories(ids, region, [resolve](const MutualFundResult& r) {
+                    if (!r.success) {
+                        resolve(ToolResult::fail(r.error));
+                        return;
+                    }
+                    QJsonObject out = MutualFundService::compare_funds(r.data["funds"].toArray());
+                    out["errors"] = r.data["errors"];
+                    resolve(ToolResult::ok_data(out));
+                });
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
@@ -0,0 +1,84 @@
+// NlQueryTools.cpp — Natural-language query → data command translator MCP tool
+
+#include "mcp/tools/NlQueryTools.h"
+
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "services/nl_query/NlQueryService.h"
+
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+std::vector<ToolDef> get_nl_query_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── nl_data_query ──────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "nl_data_query";
+        t.description = "Answer a free-text data question (e.g. 'US CPI YoY last 5 years vs Fed funds') by "
+                        "translating it into FRED series / Yahoo Finance price commands, running them and "
+                        "returning the series merged on one date axis (chart-ready: dates[] plus one values[] "
+                        "per series, null where a series has no observation) with provenance per series. A local "
+                        "parser handles common US macro series, transforms (YoY, MoM, change, log), periods and "
+                        "tickers; the configured LLM is asked only for parts it cannot place. Needs FRED_API_KEY "
+                        "for macro series.";
+        t.category = "data-sources";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("question", "The question, in plain words")
+                             .required()
+                             .length(3, 500)
+                             .boolean("use_llm", "Let the LLM plan parts the local parser does not understand")
+                             .default_bool(true)
+                             .boolean("dry_run", "Return the translated plan without fetching data")
+                             .default_bool(false)
+                             .build();
+        t.default_timeout_ms = 180000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            auto* svc = &services::NlQueryService::instance();
+            const QString question = args["question"].toString();
+            const bool use_llm = args["use_llm"].toBool(true);
+            const bool dry_run = args["dry_run"].toBool(false);
+            AsyncDispatch::callback_to_promise(
+                svc, std::move(ctx), promise, [svc, question, use_llm, dry_run](auto resolve) {
+                    auto* holder = new QObject(svc);
+                    auto request = std::make_shared<QString>();
+                    QObject::connect(svc, &services::NlQueryService::finished, holder,
+                                     [resolve, holder, request](QString id, QJsonObject result) {
+                                         if (id != *request)
+                                             return;
+                                         const QJsonObject plan = result["plan"].toObject();
+                                         const int legs = plan["legs"].toArray().size();
+                                         const QString message =
+                                             result.contains("series")
+                                                 ? QString("%1 of %2 series over %3 dates (%4 → %5)")
+                                                       .arg(result["series"].toArray().size())
+                                                       .arg(legs)
+                                                       .arg(result["dates"].toArray().size())
+                                                       .arg(plan["start"].toString(), plan["end"].toString())
+                                                 : QString("Plan: %1 data command(s)").arg(legs);
+                                         resolve(ToolResult::ok(message, result));
+                                         holder->deleteLater();
+                                     });
+                    QObject::connect(svc, &services::NlQueryService::error_occurred, holder,
+                                     [resolve, holder, request](QString id, QString msg) {
+                                         if (id != *request)
+                                             return;
+                                         resolve(ToolResult::fail(msg));
+                                         holder->deleteLater();
+                                     });
+                    *request = svc->run(question, use_llm, dry_run);
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
@@ -0,0 +1,212 @@
+// OptionChainTools.cpp — Unified option chain MCP tool (CBOE / Polygon / Tradier / broker)
+
+#include "mcp/tools/OptionChainTools.h"
+
+#include "core/logging/Logger.h"
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "python/PythonRunner.h"
+#include "services/options/UnifiedOptionChain.h"
+#include "trading/AccountManager.h"
+#include "trading/BrokerInterface.h"
+#include "trading/BrokerRegistry.h"
+
+#include <QDate>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QJsonObject>
+#include <QPromise>
+#include <QtConcurrent>
+
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+static constexpr const char* TAG = "OptionChainTools";
+static constexpr int kOptionChainTimeoutMs = 120000;
+
+namespace unified = services::options::unified;
+
+namespace {
+
+struct OcArgs {
+    QString symbol;
+    QString source;
+    QDate expiry;
+    int max_dte = 0;
+    double spot = 0;
+    double risk_free_rate = 0.045;
+    double dividend_yield = 0;
+};
+
+// Runs off the UI thread: full chains are megabytes of JSON and thousands of IV solves.
+ToolResult oc_finish(unified::ContractChain chain, const OcArgs& a) {
+    const QDate today = QDate::currentDate();
+    if (a.spot > 0)
+        chain.spot = a.spot;
+    unified::filter_expiries(chain, today, a.max_dte, a.expiry);
+    if (chain.contracts.isEmpty())
+        return ToolResult::fail("No contracts left for " + a.symbol + " after the expiry filters");
+    const int with_greeks = unified::fill_greeks(chain, today, a.risk_free_rate, a.dividend_yield);
+    LOG_INFO(TAG, QString("%1 chain from %2: %3 contracts, %4 with greeks")
+                      .arg(a.symbol, chain.source)
+                      .arg(chain.contracts.size())
+                      .arg(with_greeks));
+    QJsonObject data = unified::to_json(chain);
+    data["with_greeks"] = with_greeks;
+    QString msg = QString("%1 %2 contracts from %3").arg(a.symbol).arg(chain.contracts.size()).arg(chain.source);
+    if (chain.spot <= 0)
+        msg += " — no underlying price, so no greeks; pass 'spot'";
+    return ToolResult::ok(msg, data);
+}
+
+} // namespace
+
+std::vector<ToolDef> get_option_chain_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── get_option_chain ───────────────────────────────────────────────
+    // One contract-per-row shape whatever the source. Greeks are always
+    // recomputed with BSM from each contract's IV (solved from the mid when
+    // the source has none) so every source reports the same units.
+    {
+        ToolDef t;
+        t.name = "get_option_chain";
+        t.description = "Option chain for an underlying in one normalized shape whatever the source: "
+                        "{source, underlying, spot, expiries[], contracts[{contract_symbol, expiry, strike, type, "
+                        "last, bid, ask, volume, open_interest, iv, delta, gamma, theta (per day), vega and rho "
+                        "(per 1.00)}]}. Greeks are computed locally from each contract's IV. Sources: cboe "
+                        "(delayed, no key), polygon (POLYGON_API_KEY), tradier (TRADIER_API_KEY), broker (the "
+                        "connected broker account's chain — needs expiry).";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("symbol", "Underlying, e.g. SPY")
+                             .required()
+                             .length(1, 32)
+                             .string("source", "Chain source")
+                             .enums(unified::sources())
+                             .default_str("cboe")
+                             .string("expiry", "Only this expiry, YYYY-MM-DD (required for source=broker)")
+                             .integer("max_dte", "Only expiries within this many days (0 = all)")
+                             .default_int(0)
+                             .min(0)
+                             .string("account_id", "Broker account for source=broker (default: first connected)")
+                             .string("exchange", "Exchange passed to the broker for source=broker")
+                             .integer("strike_count", "Strikes either side of ATM for source=broker (0 = all)")
+                             .default_int(0)
+                             .min(0)
+                             .number("spot", "Underlying price (overrides the source's)")
+                             .min(0)
+                             .number("risk_free_rate", "Decimal")
+                             .default_num(0.045)
+                             .number("dividend_yield", "Continuous, decimal")
+                             .default_num(0)
+                             .build();
+        t.default_timeout_ms = kOptionChainTimeoutMs;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            OcArgs a;
+            a.symbol = args["symbol"].toString().trimmed().toUpper();
+            a.source = args["source"].toString("cboe").toLower();
+            a.max_dte = args["max_dte"].toInt(0);
+            a.spot = args["spot"].toDouble(0);
+            a.risk_free_rate = args["risk_free_rate"].toDouble(a.risk_free_rate);
+            a.dividend_yield = args["dividend_yield"].toDouble(a.dividend_yield);
+            const QString expiry = args["expiry"].toString().trimmed();
+            a.expiry = QDate::fromString(expiry, Qt::ISODate);
+            if (a.symbol.isEmpty() || !unified::sources().contains(a.source) ||
+                (!expiry.isEmpty() && !a.expiry.isValid())) {
+                promise->addResult(ToolResult::fail("Need 'symbol', a known 'source' and expiry as YYYY-MM-DD"));
+                promise->finish();
+                return;
+            }
+
+            if (a.source == "broker") {
+                if (!a.expiry.isValid()) {
+                    promise->addResult(ToolResult::fail("'expiry' is required for source=broker"));
+                    promise->finish();
+                    return;
+                }
+                const QString account_arg = args["account_id"].toString().trimmed();
+                const QString exchange = args["exchange"].toString().trimmed();
+                const int strike_count = args["strike_count"].toInt(0);
+                // Credentials load on the main thread (SQLite connection affinity).
+                auto* am = &trading::AccountManager::instance();
+                AsyncDispatch::callback_to_promise(am, ctx, promise, [=](auto resolve) {
+                    QString account_id = account_arg;
+                    if (account_id.isEmpty()) {
+                        for (const auto& acct : am->active_accounts()) {
+                            if (am->connection_state(acct.account_id) == trading::ConnectionState::Connected) {
+                                account_id = acct.account_id;
+                                break;
+                            }
+                        }
+                    }
+                    if (account_id.isEmpty() || !am->has_account(account_id)) {
+                        resolve(ToolResult::fail("No connected broker account; pass 'account_id' or connect one"));
+                        return;
+                    }
+                    const QString broker_id = am->get_account(account_id).broker_id;
+                    auto* broker = trading::BrokerRegistry::instance().get(broker_id);
+                    if (!broker) {
+                        resolve(ToolResult::fail("No broker registered for id: " + broker_id));
+                        return;
+                    }
+                    const auto creds = am->load_credentials(account_id);
+                    (void)QtConcurrent::run([=]() {
+                        const QString exp_s = a.expiry.toString(Qt::ISODate);
+                        auto resp = broker->get_option_chain(creds, a.symbol, exchange, exp_s, strike_count);
+                        if (!resp.success) {
+                            resolve(ToolResult::fail(resp.error.isEmpty() ? "Failed to fetch option chain"
+                                                                          : resp.error));
+                            return;
+                        }
+                        double spot = a.spot;
+                        if (spot <= 0) {
+                            auto q = broker->get_quotes(creds, {a.symbol});
+                            if (q.success && q.data && !q.data->isEmpty())
+                                spot = q.data->first().ltp;
+                        }
+                        resolve(oc_finish(unified::from_broker_entries(broker_id, a.symbol, exp_s, spot,
+                                                                       resp.data.value_or(
+                                                                           QVector<trading::OptionChainEntry>{})),
+                                          a));
+                    });
+                });
+                return;
+            }
+
+            auto* runner = &python::PythonRunner::instance();
+            AsyncDispatch::callback_to_promise(runner, ctx, promise, [=](auto resolve) {
+                runner->run(unified::source_script(a.source), unified::source_args(a.source, a.symbol),
+                            [=](python::PythonResult r) {
+                                if (ctx.cancelled()) {
+                                    resolve(ToolResult::fail("cancelled"));
+                                    return;
+                                }
+                                if (!r.success) {
+                                    resolve(ToolResult::fail(r.error.isEmpty() ? "Chain script failed" : r.error));
+                                    return;
+                                }
+                                (void)QtConcurrent::run([=, out = r.output]() {
+                                    const QJsonObject o =
+                                        QJsonDocument::fromJson(python::extract_json(out).toUtf8()).object();
+                                    if (o.isEmpty() || (o.contains("error") && !o.value("success").toBool(false))) {
+                                        resolve(ToolResult::fail(
+                                            o.value("error").toString("No JSON in chain script output")));
+                                        return;
+                                    }
+                                    auto chain = unified::from_script_json(a.source, o["data"].toObject());
+                                    if (chain.underlying.isEmpty())
+                                        chain.underlying = a.symbol;
+                                    resolve(oc_finish(std::move(chain), a));
+                                });
+                            });
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
@@ -0,0 +1,336 @@
+// OptionsFlowTools.cpp — Unusual options activity MCP tools (watches, events, alerts)
+
+#include "mcp/tools/OptionsFlowTools.h"
+
+#include "core/logging/Logger.h"
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "mcp/tools/ThreadHelper.h"
+#include "services/options/OptionsFlowScanner.h"
+#include "storage/repositories/OptionsFlowRepository.h"
+
+#include <QCoreApplication>
+#include <QDateTime>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+
+#include <algorithm>
+#include <functional>
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+static constexpr const char* TAG = "OptionsFlowTools";
+
+namespace {
+
+using services::options::OptionsFlowScanner;
+
+// Event filter fields shared by options_flow_events and options_flow_alert_add.
+ToolSchemaBuilder flow_filter_schema() {
+    return ToolSchemaBuilder()
+        .array("symbols", "Underlyings (empty = all)", QJsonObject{{"type", "string"}})
+        .array("kinds", "Event kinds (empty = all)",
+               QJsonObject{{"type", "string"}, {"enum", QJsonArray{"vol_oi", "sweep", "skew"}}})
+        .string("option_type", "Only calls or puts")
+        .enums({"call", "put"})
+        .number("min_premium", "Minimum traded notional behind the flag")
+        .min(0)
+        .number("min_score", "Minimum score (vol/OI ratio, sweep premium multiple, skew change multiple)")
+        .min(0);
+}
+
+QJsonValue iso_or_null(qint64 ms) {
+    return ms > 0 ? QJsonValue(QDateTime::fromMSecsSinceEpoch(ms).toUTC().toString(Qt::ISODate)) : QJsonValue();
+}
+
+QJsonObject watch_json(const OptionsFlowWatch& w) {
+    return QJsonObject{
+        {"symbol", w.symbol},
+        {"source", w.source},
+        {"interval_sec", w.interval_sec},
+        {"thresholds", OptionsFlowScanner::Thresholds::from_json(w.thresholds).to_json()},
+        {"active", w.active},
+        {"last_scan_at", iso_or_null(w.last_scan_at)}};
+}
+
+QJsonObject alert_json(const OptionsFlowAlert& a) {
+    return QJsonObject{{"id", a.id},
+                       {"name", a.name},
+                       {"filter", a.filter.to_json()},
+                       {"toast", a.toast},
+                       {"providers", a.providers},
+                       {"active", a.active},
+                       {"last_fired_at", iso_or_null(a.last_fired_at)}};
+}
+
+// Runs `fn` against the repository on the main thread; a non-empty returned
+// string is the failure message.
+ToolResult on_main(const std::function<QString(OptionsFlowRepository&)>& fn, const std::function<ToolResult()>& ok) {
+    QString error;
+    detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+        error = fn(OptionsFlowRepository::instance());
+        signal_done();
+    });
+    return error.isEmpty() ? ok() : ToolResult::fail(error);
+}
+
+} // namespace
+
+std::vector<ToolDef> get_options_flow_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── options_flow_watch ─────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "options_flow_watch";
+        t.description = "Watch an underlying for unusual options activity: its option chain is polled every "
+                        "interval_sec and checked for volume far above open interest, sweeps (large volume bursts "
+                        "between polls) and moves in 25-delta put/call skew. Re-watching updates the settings.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("symbol", "Underlying, e.g. AAPL, SPY")
+                             .required()
+                             .string("source", "Chain source (polygon needs POLYGON_API_KEY, tradier TRADIER_API_KEY)")
+                             .enums({"cboe", "polygon", "tradier"})
+                             .default_str("cboe")
+                             .integer("interval_sec", "Poll interval")
+                             .default_int(300)
+                             .between(OptionsFlowScanner::kMinIntervalSec, 86400)
+                             .object("thresholds", "Overrides: min_volume, vol_oi_ratio, sweep_contracts, "
+                                                   "sweep_premium, skew_change (IV decimal)")
+                             .boolean("active", "Poll this watch")
+                             .default_bool(true)
+                             .build();
+        t.is_destructive = true; // mutation tool — penalise on read-style queries
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            OptionsFlowWatch w;
+            w.symbol = args["symbol"].toString().trimmed().toUpper();
+            w.source = args["source"].toString("cboe").trimmed().toLower();
+            w.interval_sec = std::max(args["interval_sec"].toInt(300), OptionsFlowScanner::kMinIntervalSec);
+            w.thresholds = args["thresholds"].toObject();
+            w.active = args["active"].toBool(true);
+            if (w.symbol.isEmpty())
+                return ToolResult::fail("Missing 'symbol'");
+            if (w.source != "cboe" && w.source != "polygon" && w.source != "tradier")
+                return ToolResult::fail("source must be cboe, polygon or tradier");
+            return on_main(
+                [&](OptionsFlowRepository& repo) {
+                    auto r = repo.upsert_watch(w);
+                    return r.is_err() ? QString::fromStdString(r.error()) : QString();
+                },
+                [&]() {
+                    LOG_INFO(TAG, QString("Watching %1 via %2 every %3 s").arg(w.symbol, w.source).arg(w.interval_sec));
+                    return ToolResult::ok("Watching " + w.symbol, watch_json(w));
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── options_flow_unwatch ───────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "options_flow_unwatch";
+        t.description = "Stop watching an underlying for unusual options activity. Stored events are kept.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder().string("symbol", "Underlying").required().build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString symbol = args["symbol"].toString().trimmed().toUpper();
+            return on_main(
+                [&](OptionsFlowRepository& repo) {
+                    auto r = repo.remove_watch(symbol);
+                    return r.is_err() ? QString::fromStdString(r.error()) : QString();
+                },
+                [&]() { return ToolResult::ok("Stopped watching " + symbol); });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── options_flow_watches ───────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "options_flow_watches";
+        t.description = "List underlyings watched for unusual options activity with their effective thresholds.";
+        t.category = "markets";
+        t.handler = [](const QJsonObject&) -> ToolResult {
+            QJsonArray list;
+            return on_main(
+                [&](OptionsFlowRepository& repo) {
+                    auto r = repo.list_watches();
+                    if (r.is_err())
+                        return QString::fromStdString(r.error());
+                    for (const auto& w : r.value())
+                        list.append(watch_json(w));
+                    return QString();
+                },
+                [&]() { return ToolResult::ok_data(QJsonObject{{"watches", list}, {"count", list.size()}}); });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── options_flow_scan ──────────────────────────────────────────────
+    // One chain snapshot now. Sweeps compare against the previous snapshot of
+    // the session, so the first scan of an underlying only seeds the baseline.
+    {
+        ToolDef t;
+        t.name = "options_flow_scan";
+        t.description = "Scan an underlying's option chain now for unusual activity. Returns chain totals (call/"
+                        "put volume, put/call ratio, front-month skew) and any events flagged by this scan. "
+                        "Sweep detection needs a previous scan of the same session.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("symbol", "Underlying")
+                             .required()
+                             .string("source", "Chain source when the symbol is not watched")
+                             .enums({"cboe", "polygon", "tradier"})
+                             .build();
+        t.default_timeout_ms = 120000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            auto* svc = &OptionsFlowScanner::instance();
+            const QString symbol = args["symbol"].toString();
+            const QString source = args["source"].toString();
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, symbol, source](auto resolve) {
+                auto* holder = new QObject(svc);
+                auto request = std::make_shared<QString>();
+                QObject::connect(svc, &OptionsFlowScanner::scan_finished, holder,
+                                 [resolve, holder, request](QString id, QString sym, QJsonObject summary) {
+                                     if (id != *request)
+                                         return;
+                                     resolve(ToolResult::ok(QString("%1: %2 contracts, %3 flagged")
+                                                                .arg(sym)
+                                                                .arg(summary["contracts"].toInt())
+                                                                .arg(summary["events"].toArray().size()),
+                                                            summary));
+                                     holder->deleteLater();
+                                 });
+                QObject::connect(svc, &OptionsFlowScanner::error_occurred, holder,
+                                 [resolve, holder, request](QString id, QString msg) {
+                                     if (id != *request)
+                                         return;
+                                     resolve(ToolResult::fail(msg));
+                                     holder->deleteLater();
+                                 });
+                *request = svc->scan(symbol, source);
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── options_flow_events ────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "options_flow_events";
+        t.description = "Stored unusual options activity events, newest first, filtered by underlying, kind "
+                        "(vol_oi, sweep, skew), calls/puts, minimum premium or score and look-back window.";
+        t.category = "markets";
+        t.input_schema = flow_filter_schema()
+                             .integer("hours", "Look back this many hours")
+                             .default_int(24)
+                             .between(1, 24 * OptionsFlowScanner::kRetentionDays)
+                             .integer("limit", "Maximum events")
+                             .default_int(100)
+                             .between(1, 1000)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            OptionsFlowFilter f = OptionsFlowFilter::from_json(args);
+            f.since_ms = QDateTime::currentMSecsSinceEpoch() - qint64(args["hours"].toInt(24)) * 3600 * 1000;
+            QJsonArray events;
+            return on_main(
+                [&](OptionsFlowRepository& repo) {
+                    auto r = repo.query_events(f);
+                    if (r.is_err())
+                        return QString::fromStdString(r.error());
+                    for (const auto& e : r.value())
+                        events.append(OptionsFlowScanner::event_to_json(e));
+                    return QString();
+                },
+                [&]() { return ToolResult::ok_data(QJsonObject{{"events", events}, {"count", events.size()}}); });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── options_flow_alert_add ─────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "options_flow_alert_add";
+        t.description = "Add an alert on unusual options activity: every new event matching the filter raises a "
+                        "toast and, with providers, a notification through the configured channels.";
+        t.category = "markets";
+        t.input_schema = flow_filter_schema()
+                             .string("name", "Alert name")
+                             .required()
+                             .boolean("toast", "Show an in-app toast")
+                             .default_bool(true)
+                             .boolean("providers", "Also notify through Telegram/Slack/etc.")
+                             .default_bool(false)
+                             .build();
+        t.is_destructive = true; // mutation tool — penalise on read-style queries
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            OptionsFlowAlert a;
+            a.name = args["name"].toString().trimmed();
+            a.filter = OptionsFlowFilter::from_json(args);
+            a.toast = args["toast"].toBool(true);
+            a.providers = args["providers"].toBool(false);
+            if (a.name.isEmpty())
+                return ToolResult::fail("Missing 'name'");
+            return on_main(
+                [&](OptionsFlowRepository& repo) {
+                    auto r = repo.create_alert(a);
+                    if (r.is_err())
+                        return QString::fromStdString(r.error());
+                    a = r.value();
+                    return QString();
+                },
+                [&]() { return ToolResult::ok("Added options flow alert " + a.name, alert_json(a)); });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── options_flow_alert_remove ──────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "options_flow_alert_remove";
+        t.description = "Delete an options flow alert by id.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder().string("id", "Alert id").required().build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString id = args["id"].toString().trimmed();
+            return on_main(
+                [&](OptionsFlowRepository& repo) {
+                    auto r = repo.remove_alert(id);
+                    return r.is_err() ? QString::fromStdString(r.error()) : QString();
+                },
+                [&]() { return ToolResult::ok("Removed options flow alert " + id); });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── options_flow_alerts ────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "options_flow_alerts";
+        t.description = "List options flow alerts with their filters and last fire time.";
+        t.category = "markets";
+        t.handler = [](const QJsonObject&) -> ToolResult {
+            QJsonArray list;
+            return on_main(
+                [&](OptionsFlowRepository& repo) {
+                    auto r = repo.list_alerts();
+                    if (r.is_err())
+                        return QString::fromStdString(r.error());
+                    for (const auto& a : r.value())
+                        list.append(alert_json(a));
+                    return QString();
+                },
+                [&]() { return ToolResult::ok_data(QJsonObject{{"alerts", list}, {"count", list.size()}}); });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
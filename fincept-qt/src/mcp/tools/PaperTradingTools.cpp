@@ -2,10 +2,13 @@
 
 #include "mcp/tools/PaperTradingTools.h"
 
+#include "algo_engine/SpreadInstrument.h"
 #include "core/logging/Logger.h"
 #include "mcp/ToolSchemaBuilder.h"
 #include "trading/PaperTrading.h"
 
+#include <QHash>
+
 namespace fincept::mcp::tools {
 
 static constexpr const char* TAG = "PaperTradingTools";
@@ -180,6 +183,117 @@ std::vector<ToolDef> get_paper_trading_tools() {
         tools.push_back(std::move(t));
     }
 
+    // ── pt_place_spread_order ──────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "pt_place_spread_order";
+        t.description = "Place a paper market order on a multi-leg spread (calendar/crack/butterfly/custom). "
+                        "The spread is decomposed into one market order per leg. If a leg is rejected, the legs "
+                        "already placed are cancelled or flattened. Accepted legs may still be pending or partly "
+                        "filled: each leg's status and filled quantity is reported, and 'all_filled' is false "
+                        "until every leg has filled.";
+        t.category = "paper-trading";
+        t.auth_required = AuthLevel::Authenticated;
+        t.is_destructive = true;
+        t.input_schema = ToolSchemaBuilder()
+                             .string("portfolio_id", "Portfolio ID")
+                             .required()
+                             .object("spread", "Spread definition: {name?, kind?, legs:[{symbol, ratio, multiplier?}]} "
+                                               "— ratio is signed (+ long leg, - short leg)")
+                             .required()
+                             .string("side", "buy = long the spread, sell = short it")
+                             .required()
+                             .enums({"buy", "sell"})
+                             .number("units", "Spread units (leg quantity = units x |ratio|)")
+                             .required()
+                             .min(0.0)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString portfolio_id = args["portfolio_id"].toString();
+            const QString side = args["side"].toString();
+            const double units = args["units"].toDouble(0.0);
+            if (portfolio_id.isEmpty() || side.isEmpty() || units <= 0)
+                return ToolResult::fail("Missing required: portfolio_id, side, units (>0)");
+
+            const auto def = fincept::algo::SpreadDefinition::from_json(args["spread"].toObject());
+            QString err;
+            if (!def.is_valid(&err))
+                return ToolResult::fail(err);
+
+            const int spread_side = side == "sell" ? -1 : 1;
+            const auto legs = fincept::algo::spread_order_legs(def, spread_side, units);
+
+            // Current state of the orders placed so far, by id.
+            auto current_orders = [&portfolio_id]() {
+                QHash<QString, trading::PtOrder> by_id;
+                for (const auto& o : trading::pt_get_orders(portfolio_id))
+                    by_id.insert(o.id, o);
+                return by_id;
+            };
+
+            // Place legs in order; if one is rejected, unwind the legs already
+            // placed so no partial spread is left: cancel what is still open
+            // and flatten (reduce-only, opposite side) whatever has filled.
+            QVector<trading::PtOrder> placed;
+            for (const auto& leg : legs) {
+                try {
+                    placed.append(
+                        trading::pt_place_order(portfolio_id, leg.symbol, leg.side.toLower(), "market", leg.quantity));
+                } catch (const std::exception& e) {
+                    const auto now = current_orders();
+                    for (const auto& done : placed) {
+                        const auto o = now.value(done.id, done);
+                        try {
+                            if (o.status == "pending" || o.status == "partial")
+                                trading::pt_cancel_order(o.id);
+                            if (o.filled_qty > 0.0)
+                                trading::pt_place_order(portfolio_id, o.symbol, o.side == "buy" ? "sell" : "buy",
+                                                        "market", o.filled_qty, std::nullopt, std::nullopt, true);
+                        } catch (const std::exception& re) {
+                            LOG_ERROR(TAG, QString("Spread rollback failed on %1: %2").arg(o.symbol, re.what()));
+                        }
+                    }
+                    return ToolResult::fail(QString("Spread leg %1 rejected: %2").arg(leg.symbol, e.what()));
+                }
+            }
+
+            // A market leg is accepted as "pending" and fills when the engine
+            // prices it, so read each leg back rather than assume it filled.
+            const auto now = current_orders();
+            QJsonArray leg_status;
+            QStringList open_legs;
+            for (const auto& placed_order : placed) {
+                const auto o = now.value(placed_order.id, placed_order);
+                const bool filled = o.status == "filled";
+                if (!filled)
+                    open_legs.append(
+                        QString("%1 (%2, %3/%4)").arg(o.symbol, o.status).arg(o.filled_qty).arg(o.quantity));
+                QJsonObject leg{{"order_id", o.id},     {"symbol", o.symbol},         {"side", o.side},
+                                {"quantity", o.quantity}, {"filled_qty", o.filled_qty}, {"status", o.status}};
+                if (o.avg_price)
+                    leg["avg_price"] = *o.avg_price;
+                leg_status.append(leg);
+            }
+            const bool all_filled = open_legs.isEmpty();
+            LOG_INFO(TAG, QString("Paper spread order: %1 %2 x%3 (%4 legs, %5 open)")
+                              .arg(side, def.name)
+                              .arg(units)
+                              .arg(legs.size())
+                              .arg(open_legs.size()));
+            const QString msg = all_filled ? QString("Spread order filled (%1 legs)").arg(placed.size())
+                                           : QString("Spread order placed; %1 of %2 legs not filled yet: %3")
+                                                 .arg(open_legs.size())
+                                                 .arg(placed.size())
+                                                 .arg(open_legs.join(", "));
+            return ToolResult::ok(msg, QJsonObject{{"spread", def.to_json()},
+                                                   {"side", side},
+                                                   {"units", units},
+                                                   {"all_filled", all_filled},
+                                                   {"legs", leg_status}});
+        };
+        tools.push_back(std::move(t));
+    }
+
     // ── pt_cancel_order ────────────────────────────────────────────────
     {
         ToolDef t;
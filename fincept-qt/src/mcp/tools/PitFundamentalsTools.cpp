@@ -0,0 +1,250 @@
+// PitFundamentalsTools.cpp — point-in-time fundamentals MCP tools: ingest SEC / FMP statements keyed by report and
+// filing date, read what was known on a date, audit restatements and store coverage
+
+#include "mcp/tools/PitFundamentalsTools.h"
+
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "mcp/tools/ThreadHelper.h"
+#include "services/fundamentals/PitFundamentals.h"
+#include "storage/repositories/PitFundamentalsRepository.h"
+
+#include <QCoreApplication>
+#include <QDate>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+
+#include <functional>
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+namespace {
+
+using services::PitFundamentals;
+
+// Runs `fn` on the main thread; a non-empty returned string is the failure message.
+ToolResult on_main(const std::function<QString()>& fn, const std::function<ToolResult()>& ok) {
+    QString error;
+    detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+        error = fn();
+        signal_done();
+    });
+    return error.isEmpty() ? ok() : ToolResult::fail(error);
+}
+
+QStringList string_list(const QJsonValue& v) {
+    QStringList out;
+    for (const auto& e : v.toArray()) {
+        const QString s = e.toString().trimmed().toUpper();
+        if (!s.isEmpty())
+            out.append(s);
+    }
+    return out;
+}
+
+} // namespace
+
+std::vector<ToolDef> get_pit_fundamentals_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── fundamentals_pit_ingest ────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "fundamentals_pit_ingest";
+        t.description = "Fetch statement fundamentals (revenue, profit lines, EPS, cash flow, balance sheet) "
+                        "into the point-in-time store, keyed by both report date and filing date. SEC "
+                        "companyfacts carries the full filing history including restatements (US filers, no key); "
+                        "FMP needs FMP_API_KEY. Re-ingesting is safe.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .array("symbols", "Tickers to ingest", QJsonObject{{"type", "string"}})
+                             .required()
+                             .array("sources", "Sources to pull: sec, fmp (default [\"sec\"])",
+                                    QJsonObject{{"type", "string"}, {"enum", QJsonArray{"sec", "fmp"}}})
+                             .build();
+        t.default_timeout_ms = 600000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            auto* svc = &PitFundamentals::instance();
+            const QStringList symbols = string_list(args["symbols"]);
+            QStringList sources;
+            for (const auto& v : args["sources"].toArray())
+                sources.append(v.toString().trimmed().toLower());
+            if (sources.isEmpty())
+                sources = {"sec"};
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, symbols, sources](auto resolve) {
+                auto* holder = new QObject(svc);
+                auto request = std::make_shared<QString>();
+                QObject::connect(svc, &PitFundamentals::ingested, holder,
+                                 [resolve, holder, request](QString id, QJsonArray stored, QJsonArray failed) {
+                                     if (id != *request)
+                                         return;
+                                     int facts = 0;
+                                     for (const auto& s : stored)
+                                         facts += s.toObject().value("facts").toInt();
+                                     const QString msg = QString("Stored %1 facts from %2 fetches, %3 failed")
+                                                             .arg(facts)
+                                                             .arg(stored.size())
+                                                             .arg(failed.size());
+                                     resolve(ToolResult::ok(msg, QJsonObject{{"stored", stored}, {"failed", failed}}));
+                                     holder->deleteLater();
+                                 });
+                QObject::connect(svc, &PitFundamentals::error_occurred, holder,
+                                 [resolve, holder, request](QString id, QString msg) {
+                                     if (id != *request)
+                                         return;
+                                     resolve(ToolResult::fail(msg));
+                                     holder->deleteLater();
+                                 });
+                *request = svc->ingest(symbols, sources);
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── fundamentals_pit_as_of ─────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "fundamentals_pit_as_of";
+        t.description = "Fundamentals exactly as they were public on a date: trailing-twelve-month flows, latest "
+                        "balance sheet and derived ratios built only from filings made on or before as_of, with the "
+                        "newest report and filing date used. Use for lookahead-free research and backtests; a "
+                        "strategy trading at the close of day D should ask for D - 1.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .array("symbols", "Tickers; omit for every symbol in the store",
+                                    QJsonObject{{"type", "string"}})
+                             .string("as_of", "Date (YYYY-MM-DD)")
+                             .required()
+                             .boolean("include_facts", "Also return the individual facts known on that date")
+                             .default_bool(false)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QStringList symbols = string_list(args["symbols"]);
+            const QDate as_of = QDate::fromString(args["as_of"].toString().trimmed(), Qt::ISODate);
+            if (!as_of.isValid())
+                return ToolResult::fail("as_of must be a YYYY-MM-DD date");
+            const bool include_facts = args["include_facts"].toBool(false);
+            QJsonObject out;
+            return on_main(
+                [&]() {
+                    auto snaps = PitFundamentals::snapshot(symbols, as_of);
+                    if (snaps.is_err())
+                        return QString::fromStdString(snaps.error());
+                    QJsonArray rows;
+                    QStringList keys = snaps.value().keys();
+                    keys.sort();
+                    for (const QString& sym : keys) {
+                        const auto& s = snaps.value().value(sym);
+                        rows.append(QJsonObject{{"symbol", s.symbol},
+                                                {"period_end", s.period_end},
+                                                {"filed_date", s.filed_date},
+                                                {"fields", s.fields}});
+                    }
+                    QJsonArray missing;
+                    for (const QString& sym : symbols) {
+                        if (!snaps.value().contains(sym))
+                            missing.append(sym);
+                    }
+                    out = QJsonObject{
+                        {"as_of", as_of.toString(Qt::ISODate)}, {"snapshots", rows}, {"missing", missing}};
+                    if (include_facts) {
+                        auto facts = PitFundamentalsRepository::instance().known_as_of(symbols,
+                                                                                      as_of.toString(Qt::ISODate));
+                        if (facts.is_err())
+                            return QString::fromStdString(facts.error());
+                        QJsonArray list;
+                        for (const auto& f : facts.value())
+                            list.append(PitFundamentals::fact_to_json(f));
+                        out["facts"] = list;
+                    }
+                    return QString();
+                },
+                [&]() {
+                    return ToolResult::ok(QString("Fundamentals for %1 symbols as of %2")
+                                              .arg(out["snapshots"].toArray().size())
+                                              .arg(out["as_of"].toString()),
+                                          out);
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── fundamentals_pit_history ───────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "fundamentals_pit_history";
+        t.description = "Every stored filing of one metric for a symbol, newest report date first, so original "
+                        "values and later restatements show side by side with their filing dates and forms.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("symbol", "Ticker")
+                             .required()
+                             .string("metric", "Metric key")
+                             .required()
+                             .enums(PitFundamentals::metrics())
+                             .string("period", "FY, Q or I (balance sheet instant); omit for all")
+                             .enums({"FY", "Q", "I"})
+                             .integer("limit", "Max rows")
+                             .default_int(100)
+                             .between(1, 2000)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString symbol = args["symbol"].toString().trimmed().toUpper();
+            const QString metric = args["metric"].toString().trimmed();
+            const QString period = args["period"].toString().trimmed();
+            const int limit = args["limit"].toInt(100);
+            QJsonArray rows;
+            return on_main(
+                [&]() {
+                    auto r = PitFundamentalsRepository::instance().history(symbol, metric, period);
+                    if (r.is_err())
+                        return QString::fromStdString(r.error());
+                    for (const auto& f : r.value()) {
+                        if (rows.size() >= limit)
+                            break;
+                        rows.append(PitFundamentals::fact_to_json(f));
+                    }
+                    return QString();
+                },
+                [&]() {
+                    return ToolResult::ok(QString("%1 filings of %2 for %3").arg(rows.size()).arg(metric, symbol),
+                                          QJsonObject{{"symbol", symbol}, {"metric", metric}, {"filings", rows}});
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── fundamentals_pit_coverage ──────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "fundamentals_pit_coverage";
+        t.description = "What the point-in-time store holds per symbol and source: fact count, first and last "
+                        "report date, newest filing date and when it was last ingested.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .array("symbols", "Tickers; omit for every symbol in the store",
+                                    QJsonObject{{"type", "string"}})
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QStringList symbols = string_list(args["symbols"]);
+            QJsonArray rows;
+            return on_main(
+                [&]() {
+                    auto r = PitFundamentalsRepository::instance().coverage(symbols);
+                    if (r.is_err())
+                        return QString::fromStdString(r.error());
+                    for (const auto& c : r.value())
+                        rows.append(PitFundamentals::coverage_to_json(c));
+                    return QString();
+                },
+                [&]() { return ToolResult::ok_data(QJsonObject{{"coverage", rows}}); });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
@@ -0,0 +1,99 @@
+// PluginTools.cpp — WebAssembly plugin MCP tools (community indicators, transforms, sources)
+
+#include "mcp/tools/PluginTools.h"
+
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "services/plugins/PluginService.h"
+
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+using services::plugins::PluginRunRequest;
+using services::plugins::PluginService;
+
+std::vector<ToolDef> get_plugin_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── list_plugins ───────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "list_plugins";
+        t.description = "List the WebAssembly plugins installed in the terminal's plugins directory: community "
+                        "indicators and series transforms (series in, series out) and data sources (series out), "
+                        "with their manifest (kind, version, author, params). Plugins with a manifest problem are "
+                        "listed with an error.";
+        t.category = "quant-lab";
+        t.input_schema = ToolSchemaBuilder().build();
+        t.handler = [](const QJsonObject&) -> ToolResult {
+            auto& svc = PluginService::instance();
+            QJsonArray arr;
+            for (const auto& p : svc.plugins())
+                arr.append(p.to_json());
+            return ToolResult::ok(QString("%1 plugins in %2").arg(arr.size()).arg(svc.plugins_dir()),
+                                  QJsonObject{{"plugins_dir", svc.plugins_dir()}, {"plugins", arr}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── run_plugin ─────────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "run_plugin";
+        t.description = "Run a WebAssembly plugin (no host access, 64 MB of memory, 30 s limit). Indicators / "
+                        "transforms take 'values', or the daily 'field' series of 'symbol' over the last 'days'; "
+                        "sources take no input. 'params' is passed to the plugin as JSON. Returns the output series "
+                        "(null = not defined, e.g. warm-up bars), with dates when a symbol was used.";
+        t.category = "quant-lab";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("plugin", "Plugin id (file name without .wasm), from list_plugins")
+                             .required()
+                             .array("values", "Input series, oldest first", QJsonObject{{"type", "number"}})
+                             .string("symbol", "Read the input series from this symbol's daily bars instead")
+                             .integer("days", "Days of history when 'symbol' is used")
+                             .default_int(365)
+                             .between(5, 3650)
+                             .string("field", "Bar field used as the input series")
+                             .default_str("close")
+                             .enums({"open", "high", "low", "close", "volume"})
+                             .object("params", "Plugin parameters (see the plugin's manifest)")
+                             .build();
+        t.default_timeout_ms = 120000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            auto* svc = &PluginService::instance();
+            const PluginRunRequest request = PluginRunRequest::from_json(args);
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, request](auto resolve) {
+                auto* holder = new QObject(svc);
+                auto id = std::make_shared<QString>();
+                QObject::connect(svc, &PluginService::result_ready, holder,
+                                 [resolve, holder, id](QString request_id, QJsonObject result) {
+                                     if (request_id != *id)
+                                         return;
+                                     resolve(ToolResult::ok(QString("%1 returned %2 values")
+                                                                .arg(result["plugin"].toString())
+                                                                .arg(result["count"].toInt()),
+                                                            result));
+                                     holder->deleteLater();
+                                 });
+                QObject::connect(svc, &PluginService::error_occurred, holder,
+                                 [resolve, holder, id](QString request_id, QString msg) {
+                                     if (request_id != *id)
+                                         return;
+                                     resolve(ToolResult::fail(msg));
+                                     holder->deleteLater();
+                                 });
+                *id = svc->run(request);
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
@@ -4,13 +4,193 @@
 #include "mcp/tools/PortfolioTools.h"
 
 #include "core/logging/Logger.h"
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "mcp/tools/ThreadHelper.h"
+#include "services/portfolio/DividendService.h"
+#include "services/portfolio/EventRiskService.h"
+#include "services/portfolio/PortfolioAnalyticsService.h"
+#include "services/portfolio/PortfolioService.h"
+#include "services/portfolio/RetirementSimulator.h"
+#include "services/portfolio/TaxLotEngine.h"
 #include "storage/repositories/PortfolioHoldingsRepository.h"
 #include "storage/repositories/PortfolioRepository.h"
 
+#include <QJsonDocument>
+
+#include <algorithm>
+
 namespace fincept::mcp::tools {
 
 static constexpr const char* TAG = "PortfolioTools";
 
+static QJsonValue opt_json(const std::optional<double>& v) {
+    return v ? QJsonValue(*v) : QJsonValue(QJsonValue::Null);
+}
+
+// Flat tool args → PortfolioAnalyticsService payload with the weight
+// constraints nested under "constraints".
+static QString optimizer_payload(const QJsonObject& args) {
+    QJsonObject payload = args;
+    QJsonObject constraints{{"long_only", args["long_only"].toBool(true)}};
+    for (const char* key : {"min_weight", "max_weight", "bounds", "sectors", "sector_caps"}) {
+        if (args.contains(key))
+            constraints[key] = args[key];
+        payload.remove(key);
+    }
+    payload.remove("long_only");
+    payload["constraints"] = constraints;
+    return QString::fromUtf8(QJsonDocument(payload).toJson(QJsonDocument::Compact));
+}
+
+static QJsonObject fx_report_to_json(const portfolio::FxExposureReport& r) {
+    QJsonArray exposures;
+    for (const auto& e : r.exposures) {
+        exposures.append(QJsonObject{{"currency", e.currency},
+                                     {"symbols", QJsonArray::fromStringList(e.symbols)},
+                                     {"market_value_local", e.market_value_local},
+                                     {"market_value_base", e.market_value_base},
+                                     {"weight", e.weight},
+                                     {"spot", e.spot},
+                                     {"fx_volatility", opt_json(e.fx_volatility)},
+                                     {"base_rate", e.base_rate},
+                                     {"foreign_rate", e.foreign_rate},
+                                     {"forward", e.forward},
+                                     {"forward_points", e.forward_points},
+                                     {"hedge_cost_annual_pct", e.hedge_cost_annual_pct},
+                                     {"suggested_hedge_ratio", e.suggested_hedge_ratio},
+                                     {"suggested_hedge_notional", e.suggested_hedge_notional},
+                                     {"hedge_cost_base", e.hedge_cost_base},
+                                     {"var_95_unhedged", opt_json(e.var_95_unhedged)},
+                                     {"var_95_hedged", opt_json(e.var_95_hedged)},
+                                     {"rationale", e.rationale}});
+    }
+    return QJsonObject{{"base_currency", r.base_currency},
+                       {"horizon_months", r.horizon_months},
+                       {"total_value_base", r.total_value_base},
+                       {"foreign_value_base", r.foreign_value_base},
+                       {"foreign_weight", r.foreign_weight},
+                       {"exposures", exposures},
+                       {"warnings", QJsonArray::fromStringList(r.warnings)},
+                       {"generated_at", r.generated_at}};
+}
+
+static QJsonObject dividend_summary_to_json(const portfolio::DividendSummary& d) {
+    QJsonArray holdings;
+    for (const auto& h : d.holdings) {
+        holdings.append(QJsonObject{{"symbol", h.symbol},
+                                    {"quantity", h.quantity},
+                                    {"price", h.price},
+                                    {"currency", h.currency},
+                                    {"payments_per_year", h.payments_per_year},
+                                    {"forward_annual_dividend", h.forward_annual_dividend},
+                                    {"forward_yield_pct", h.forward_yield},
+                                    {"yield_on_cost_pct", h.yield_on_cost},
+                                    {"projected_income_12m", h.projected_income_12m},
+                                    {"next_ex_date", h.next_ex_date},
+                                    {"next_pay_date", h.next_pay_date}});
+    }
+    QJsonArray months;
+    for (const auto& m : d.months)
+        months.append(QJsonObject{
+            {"month", m.month}, {"income", m.income}, {"symbols", QJsonArray::fromStringList(m.symbols)}});
+    QJsonArray upcoming;
+    for (const auto& e : d.upcoming)
+        upcoming.append(QJsonObject{{"symbol", e.symbol},
+                                    {"ex_date", e.ex_date},
+                                    {"pay_date", e.pay_date},
+                                    {"amount", e.amount},
+                                    {"currency", e.currency},
+                                    {"status", e.status}});
+    return QJsonObject{{"portfolio_id", d.portfolio_id},
+                       {"projected_income_12m", d.projected_income_12m},
+                       {"portfolio_forward_yield_pct", d.portfolio_forward_yield},
+                       {"holdings", holdings},
+                       {"months", months},
+                       {"upcoming", upcoming},
+                       {"synced_at", d.synced_at}};
+}
+
+static QJsonObject tax_report_to_json(const portfolio::TaxReport& r) {
+    QJsonArray realized;
+    for (const auto& g : r.realized) {
+        realized.append(QJsonObject{{"symbol", g.symbol},
+                                    {"acquired", g.acquired},
+                                    {"disposed", g.disposed},
+                                    {"quantity", g.quantity},
+                                    {"proceeds", g.proceeds},
+                                    {"cost_basis", g.cost_basis},
+                                    {"adjustment", g.adjustment},
+                                    {"gain", g.gain},
+                                    {"term", g.long_term ? "long" : "short"},
+                                    {"tax_year", g.tax_year},
+                                    {"wash_sale", g.wash_sale},
+                                    {"grandfathered", g.grandfathered}});
+    }
+    QJsonArray years;
+    for (const auto& y : r.years) {
+        years.append(QJsonObject{{"tax_year", y.tax_year},
+                                 {"short_term_gain", y.short_term_gain},
+                                 {"long_term_gain", y.long_term_gain},
+                                 {"net_gain", y.short_term_gain + y.long_term_gain},
+                                 {"proceeds", y.proceeds},
+                                 {"cost_basis", y.cost_basis},
+                                 {"disallowed_loss", y.disallowed_loss},
+                                 {"dispositions", y.dispositions}});
+    }
+    QJsonArray lots;
+    for (const auto& l : r.open_lots) {
+        lots.append(QJsonObject{{"symbol", l.symbol},
+                                {"acquired", l.acquired},
+                                {"quantity", l.quantity},
+                                {"cost_per_share", l.cost_per_share}});
+    }
+    return QJsonObject{{"portfolio_id", r.portfolio_id},
+                       {"jurisdiction", r.jurisdiction},
+                       {"currency", r.currency},
+                       {"years", years},
+                       {"realized", realized},
+                       {"open_lots", lots},
+                       {"warnings", QJsonArray::fromStringList(r.warnings)},
+                       {"generated_at", r.generated_at}};
+}
+
+static QJsonObject benchmark_analytics_to_json(const portfolio::BenchmarkAnalytics& a, bool include_series) {
+    QJsonObject out{{"portfolio_id", a.portfolio_id},
+                    {"benchmark", a.benchmark},
+                    {"benchmark_currency", a.benchmark_currency},
+                    {"base_currency", a.base_currency},
+                    {"fx_adjusted", a.fx_adjusted},
+                    {"window", a.window},
+                    {"observations", a.observations},
+                    {"portfolio_return_annual_pct", opt_json(a.portfolio_return)},
+                    {"benchmark_return_annual_pct", opt_json(a.benchmark_return)},
+                    {"active_return_annual_pct", opt_json(a.active_return)},
+                    {"tracking_error_pct", opt_json(a.tracking_error)},
+                    {"information_ratio", opt_json(a.information_ratio)},
+                    {"beta", opt_json(a.beta)},
+                    {"up_capture_pct", opt_json(a.up_capture)},
+                    {"down_capture_pct", opt_json(a.down_capture)},
+                    {"warnings", QJsonArray::fromStringList(a.warnings)},
+                    {"generated_at", a.generated_at}};
+    if (include_series) {
+        QJsonArray series;
+        for (const auto& p : a.series)
+            series.append(QJsonObject{{"date", p.date},
+                                      {"portfolio_return", p.portfolio_return},
+                                      {"benchmark_return", p.benchmark_return},
+                                      {"active_return", p.active_return},
+                                      {"portfolio_cumulative", p.portfolio_cumulative},
+                                      {"benchmark_cumulative", p.benchmark_cumulative},
+                                      {"active_cumulative", p.active_cumulative},
+                                      {"rolling_tracking_error_pct", opt_json(p.rolling_tracking_error)},
+                                      {"rolling_information_ratio", opt_json(p.rolling_information_ratio)},
+                                      {"rolling_beta", opt_json(p.rolling_beta)}});
+        out["series"] = series;
+    }
+    return out;
+}
+
 std::vector<ToolDef> get_portfolio_tools() {
     std::vector<ToolDef> tools;
 
@@ -272,6 +452,8 @@ std::vector<ToolDef> get_portfolio_tools() {
                                        {"symbol", a.symbol},
                                        {"quantity", a.quantity},
                                        {"avg_buy_price", a.avg_buy_price},
+                                       {"currency", a.currency},
+                                       {"cost_fx_rate", a.cost_fx_rate},
                                        {"first_purchase_date", a.first_purchase_date},
                                        {"last_updated", a.last_updated}});
             }
@@ -291,7 +473,13 @@ std::vector<ToolDef> get_portfolio_tools() {
             {"symbol", QJsonObject{{"type", "string"}, {"description", "Ticker symbol (e.g. AAPL)"}}},
             {"quantity", QJsonObject{{"type", "number"}, {"description", "Number of shares/units"}}},
             {"price", QJsonObject{{"type", "number"}, {"description", "Purchase price per unit"}}},
-            {"date", QJsonObject{{"type", "string"}, {"description", "Purchase date YYYY-MM-DD (optional)"}}}};
+            {"date", QJsonObject{{"type", "string"}, {"description", "Purchase date YYYY-MM-DD (optional)"}}},
+            {"currency", QJsonObject{{"type", "string"},
+                                     {"description", "Native quote currency, ISO code (optional; inferred from the "
+                                                     "symbol's exchange suffix when omitted)"}}},
+            {"fx_rate", QJsonObject{{"type", "number"},
+                                    {"description", "Portfolio-currency units per native unit at purchase (optional; "
+                                                    "looked up from the purchase date when omitted)"}}}};
         t.input_schema.required = {"portfolio_id", "symbol", "quantity", "price"};
         t.handler = [](const QJsonObject& args) -> ToolResult {
             QString portfolio_id = args["portfolio_id"].toString().trimmed();
@@ -299,11 +487,17 @@ std::vector<ToolDef> get_portfolio_tools() {
             double quantity = args["quantity"].toDouble(0.0);
             double price = args["price"].toDouble(0.0);
             QString date = args["date"].toString();
+            QString currency = args["currency"].toString().trimmed().toUpper();
+            double fx_rate = args["fx_rate"].toDouble(0.0);
 
             if (portfolio_id.isEmpty() || symbol.isEmpty() || quantity <= 0 || price <= 0)
                 return ToolResult::fail("Missing or invalid: portfolio_id, symbol, quantity (>0), price (>0)");
+            if (!currency.isEmpty() && currency.size() != 3)
+                return ToolResult::fail("'currency' must be a 3-letter ISO code");
 
-            auto r = PortfolioRepository::instance().add_asset(portfolio_id, symbol, quantity, price, date);
+            // fx_rate 0 = unknown; the summary backfills it from the purchase date.
+            auto r = PortfolioRepository::instance().add_asset(portfolio_id, symbol, quantity, price, date, {}, {}, {},
+                                                               currency, std::max(0.0, fx_rate));
             if (r.is_err())
                 return ToolResult::fail("Failed to add asset: " + QString::fromStdString(r.error()));
 
@@ -480,6 +674,856 @@ std::vector<ToolDef> get_portfolio_tools() {
         tools.push_back(std::move(t));
     }
 
+    // ── get_portfolio_overview ──────────────────────────────────────────
+    // Live summary (quotes via PortfolioService) plus currency exposure and
+    // FX hedging suggestions, in one call.
+    {
+        ToolDef t;
+        t.name = "get_portfolio_overview";
+        t.description = "Portfolio overview with live valuation in the portfolio's base currency, P&L split into "
+                        "local-return and currency-return, and per-currency FX exposure: spot-converted value, "
+                        "forward points / hedge carry from rate differentials, and suggested hedge ratios.";
+        t.category = "portfolio";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("portfolio_id", "Portfolio ID")
+                             .required()
+                             .integer("hedge_horizon_months", "Forward tenor used for hedge cost (1-12)")
+                             .default_int(3)
+                             .between(1, 12)
+                             .build();
+        t.default_timeout_ms = 60000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString id = args["portfolio_id"].toString().trimmed();
+            const int horizon = args["hedge_horizon_months"].toInt(3);
+            if (id.isEmpty()) {
+                promise->addResult(ToolResult::fail("Missing 'portfolio_id'"));
+                promise->finish();
+                return;
+            }
+            auto* svc = &services::PortfolioService::instance();
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, id, horizon](auto resolve) {
+                auto* holder = new QObject(svc);
+                auto summary_json = std::make_shared<QJsonObject>();
+                QObject::connect(svc, &services::PortfolioService::summary_loaded, holder,
+                                 [svc, id, horizon, summary_json](portfolio::PortfolioSummary s) {
+                                     if (s.portfolio.id != id || !summary_json->isEmpty())
+                                         return;
+                                     QJsonArray holdings;
+                                     for (const auto& h : s.holdings)
+                                         holdings.append(QJsonObject{{"symbol", h.symbol},
+                                                                     {"quantity", h.quantity},
+                                                                     {"currency", h.currency},
+                                                                     {"current_price", h.current_price},
+                                                                     {"fx_rate", h.fx_rate},
+                                                                     {"cost_fx_rate", h.cost_fx_rate},
+                                                                     {"market_value_local", h.market_value_local},
+                                                                     {"market_value", h.market_value},
+                                                                     {"unrealized_pnl", h.unrealized_pnl},
+                                                                     {"local_return_pnl", h.local_return_pnl},
+                                                                     {"currency_return_pnl", h.currency_return_pnl},
+                                                                     {"weight", h.weight},
+                                                                     {"sector", h.sector}});
+                                     QJsonObject fx_rates;
+                                     for (auto it = s.fx_rates.constBegin(); it != s.fx_rates.constEnd(); ++it)
+                                         fx_rates.insert(it.key(), it.value());
+                                     *summary_json = QJsonObject{
+                                         {"portfolio_id", s.portfolio.id},
+                                         {"name", s.portfolio.name},
+                                         {"currency", s.portfolio.currency},
+                                         {"total_market_value", s.total_market_value},
+                                         {"total_cost_basis", s.total_cost_basis},
+                                         {"total_unrealized_pnl", s.total_unrealized_pnl},
+                                         {"total_unrealized_pnl_percent", s.total_unrealized_pnl_percent},
+                                         {"total_local_return_pnl", s.total_local_return_pnl},
+                                         {"total_currency_return_pnl", s.total_currency_return_pnl},
+                                         {"fx_rates", fx_rates},
+                                         {"fx_warnings", QJsonArray::fromStringList(s.fx_warnings)},
+                                         {"total_day_change", s.total_day_change},
+                                         {"total_day_change_percent", s.total_day_change_percent},
+                                         {"total_positions", s.total_positions},
+                                         {"holdings", holdings},
+                                         {"last_updated", s.last_updated}};
+                                     svc->compute_fx_exposure(s, horizon);
+                                 });
+                QObject::connect(svc, &services::PortfolioService::fx_exposure_computed, holder,
+                                 [resolve, holder, id, summary_json](portfolio::FxExposureReport r) {
+                                     if (r.portfolio_id != id || summary_json->isEmpty())
+                                         return;
+                                     QJsonObject out = *summary_json;
+                                     out["fx_exposure"] = fx_report_to_json(r);
+                                     resolve(ToolResult::ok_data(out));
+                                     holder->deleteLater();
+                                 });
+                QObject::connect(svc, &services::PortfolioService::summary_error, holder,
+                                 [resolve, holder, id](QString pid, QString err) {
+                                     if (pid != id)
+                                         return;
+                                     resolve(ToolResult::fail("Failed to load portfolio: " + err));
+                                     holder->deleteLater();
+                                 });
+                svc->load_summary(id);
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── set_portfolio_base_currency ─────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "set_portfolio_base_currency";
+        t.description = "Change the base currency a portfolio is valued and reported in. Holdings keep their native "
+                        "currency; purchase FX rates are re-derived against the new base.";
+        t.category = "portfolio";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("portfolio_id", "Portfolio ID")
+                             .required()
+                             .string("currency", "ISO currency code, e.g. USD, EUR, INR")
+                             .required()
+                             .length(3, 3)
+                             .build();
+        t.default_timeout_ms = 60000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString id = args["portfolio_id"].toString().trimmed();
+            const QString ccy = args["currency"].toString().trimmed().toUpper();
+            if (id.isEmpty() || ccy.size() != 3) {
+                promise->addResult(ToolResult::fail("Missing 'portfolio_id' or invalid 'currency'"));
+                promise->finish();
+                return;
+            }
+            auto p = PortfolioRepository::instance().get_portfolio(id);
+            if (p.is_err()) {
+                promise->addResult(ToolResult::fail("Portfolio not found: " + id));
+                promise->finish();
+                return;
+            }
+            if (p.value().currency.toUpper() == ccy) {
+                promise->addResult(ToolResult::ok("Base currency unchanged", QJsonObject{{"currency", ccy}}));
+                promise->finish();
+                return;
+            }
+            auto* svc = &services::PortfolioService::instance();
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, id, ccy](auto resolve) {
+                auto* holder = new QObject(svc);
+                QObject::connect(svc, &services::PortfolioService::summary_loaded, holder,
+                                 [resolve, holder, id, ccy](portfolio::PortfolioSummary s) {
+                                     if (s.portfolio.id != id || s.portfolio.currency.toUpper() != ccy)
+                                         return;
+                                     resolve(ToolResult::ok(
+                                         "Base currency changed",
+                                         QJsonObject{{"portfolio_id", id},
+                                                     {"currency", ccy},
+                                                     {"total_market_value", s.total_market_value},
+                                                     {"total_unrealized_pnl", s.total_unrealized_pnl},
+                                                     {"fx_warnings", QJsonArray::fromStringList(s.fx_warnings)}}));
+                                     holder->deleteLater();
+                                 });
+                QObject::connect(svc, &services::PortfolioService::summary_error, holder,
+                                 [resolve, holder, id](QString pid, QString err) {
+                                     if (pid != id)
+                                         return;
+                                     resolve(ToolResult::fail("Failed to change base currency: " + err));
+                                     holder->deleteLater();
+                                 });
+                svc->set_base_currency(id, ccy);
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── set_portfolio_benchmark ─────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "set_portfolio_benchmark";
+        t.description = "Assign the benchmark index/ETF a portfolio is measured against (e.g. SPY, ^NSEI, ^FTSE). "
+                        "Empty symbol reverts to the default for the portfolio's currency.";
+        t.category = "portfolio";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("portfolio_id", "Portfolio ID")
+                             .required()
+                             .string("symbol", "Benchmark ticker (yfinance format); empty = currency default")
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString id = args["portfolio_id"].toString().trimmed();
+            const QString symbol = args["symbol"].toString().trimmed().toUpper();
+            auto p = PortfolioRepository::instance().get_portfolio(id);
+            if (p.is_err())
+                return ToolResult::fail("Portfolio not found: " + id);
+            services::PortfolioService::instance().set_benchmark(id, symbol);
+            auto updated = p.value();
+            updated.benchmark_symbol = symbol;
+            return ToolResult::ok("Benchmark set",
+                                  QJsonObject{{"portfolio_id", id},
+                                              {"benchmark", services::PortfolioService::benchmark_for(updated)},
+                                              {"is_default", symbol.isEmpty()}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── get_benchmark_analytics ─────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "get_benchmark_analytics";
+        t.description = "Benchmark-relative performance of a portfolio from its daily snapshots (benchmark converted "
+                        "into the portfolio currency at historical FX by default): annualised active "
+                        "return, tracking error, information ratio, beta, up/down capture, and optionally the daily "
+                        "series with cumulative and rolling (tracking error, information ratio, beta) values.";
+        t.category = "portfolio";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("portfolio_id", "Portfolio ID")
+                             .required()
+                             .integer("days", "Lookback in calendar days")
+                             .default_int(365)
+                             .between(30, 3650)
+                             .integer("window", "Rolling window in trading days")
+                             .default_int(63)
+                             .between(10, 252)
+                             .boolean("include_series", "Return the daily time series")
+                             .default_bool(false)
+                             .boolean("fx_adjust", "Convert a foreign-currency benchmark into the portfolio "
+                                                   "currency at historical FX before comparing")
+                             .default_bool(true)
+                             .build();
+        t.default_timeout_ms = 60000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString id = args["portfolio_id"].toString().trimmed();
+            const int days = args["days"].toInt(365);
+            const int window = args["window"].toInt(63);
+            const bool include_series = args["include_series"].toBool(false);
+            const bool fx_adjust = args["fx_adjust"].toBool(true);
+            if (id.isEmpty()) {
+                promise->addResult(ToolResult::fail("Missing 'portfolio_id'"));
+                promise->finish();
+                return;
+            }
+            auto* svc = &services::PortfolioService::instance();
+            AsyncDispatch::callback_to_promise(
+                svc, std::move(ctx), promise, [svc, id, days, window, include_series, fx_adjust](auto resolve) {
+                    auto* holder = new QObject(svc);
+                    QObject::connect(svc, &services::PortfolioService::benchmark_analytics_computed, holder,
+                                     [resolve, holder, id, include_series](portfolio::BenchmarkAnalytics a) {
+                                         if (a.portfolio_id != id)
+                                             return;
+                                         resolve(ToolResult::ok_data(benchmark_analytics_to_json(a, include_series)));
+                                         holder->deleteLater();
+                                     });
+                    QObject::connect(svc, &services::PortfolioService::benchmark_analytics_error, holder,
+                                     [resolve, holder, id](QString pid, QString err) {
+                                         if (pid != id)
+                                             return;
+                                         resolve(ToolResult::fail(err));
+                                         holder->deleteLater();
+                                     });
+                    svc->compute_benchmark_analytics(id, days, window, fx_adjust);
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── get_benchmark_series ────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "get_benchmark_series";
+        t.description = "Daily closes of a benchmark converted into a base currency with historical FX closes, "
+                        "e.g. ^FTSE in USD or SPY in INR, with cumulative return in both currencies.";
+        t.category = "portfolio";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("symbol", "Benchmark ticker (yfinance format)")
+                             .required()
+                             .string("base_currency", "ISO code to express the series in")
+                             .required()
+                             .length(3, 3)
+                             .integer("days", "Lookback in calendar days")
+                             .default_int(365)
+                             .between(7, 3650)
+                             .build();
+        t.default_timeout_ms = 60000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString symbol = args["symbol"].toString().trimmed().toUpper();
+            const QString base = args["base_currency"].toString().trimmed().toUpper();
+            const QString from = QDate::currentDate().addDays(-args["days"].toInt(365)).toString(Qt::ISODate);
+            if (symbol.isEmpty() || base.size() != 3) {
+                promise->addResult(ToolResult::fail("Missing 'symbol' or invalid 'base_currency'"));
+                promise->finish();
+                return;
+            }
+            auto* svc = &services::PortfolioService::instance();
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, symbol, base, from](auto resolve) {
+                const QString local_ccy = services::PortfolioService::benchmark_currency(symbol);
+                svc->benchmark_closes_in(
+                    symbol, local_ccy, from, [svc, symbol, base, from, local_ccy, resolve](auto local, auto) {
+                        svc->benchmark_closes_in(
+                            symbol, base, from,
+                            [symbol, base, local_ccy, local, resolve](auto converted, QStringList warnings) {
+                                if (converted.isEmpty()) {
+                                    resolve(ToolResult::fail("No price history for " + symbol));
+                                    return;
+                                }
+                                QHash<QString, double> local_by_date;
+                                for (const auto& [d, c] : local)
+                                    local_by_date.insert(d, c);
+                                const double first_base = converted.first().second;
+                                const double first_local = local_by_date.value(converted.first().first, 0.0);
+                                QJsonArray series;
+                                for (const auto& [d, c] : converted) {
+                                    const double lc = local_by_date.value(d, 0.0);
+                                    series.append(QJsonObject{
+                                        {"date", d},
+                                        {"close_local", lc},
+                                        {"fx_rate", lc > 0 ? QJsonValue(c / lc) : QJsonValue(QJsonValue::Null)},
+                                        {"close_base", c},
+                                        {"cumulative_local", first_local > 0 && lc > 0
+                                                                 ? QJsonValue(lc / first_local - 1.0)
+                                                                 : QJsonValue(QJsonValue::Null)},
+                                        {"cumulative_base", c / first_base - 1.0}});
+                                }
+                                resolve(ToolResult::ok_data(
+                                    QJsonObject{{"symbol", symbol},
+                                                {"local_currency", local_ccy},
+                                                {"base_currency", base},
+                                                {"series", series},
+                                                {"warnings", QJsonArray::fromStringList(warnings)}}));
+                            });
+                    });
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── get_portfolio_dividends ─────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "get_portfolio_dividends";
+        t.description = "Dividend calendar for a portfolio: forward yield and yield on cost per holding, upcoming "
+                        "ex/pay dates (announced + projected from payment cadence), and 12-month income by month. "
+                        "refresh=true re-syncs dividend history from the data provider first.";
+        t.category = "portfolio";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("portfolio_id", "Portfolio ID")
+                             .required()
+                             .boolean("refresh", "Re-fetch dividends before building the summary")
+                             .default_bool(true)
+                             .build();
+        t.default_timeout_ms = 90000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString id = args["portfolio_id"].toString().trimmed();
+            const bool refresh = args["refresh"].toBool(true);
+            if (id.isEmpty()) {
+                promise->addResult(ToolResult::fail("Missing 'portfolio_id'"));
+                promise->finish();
+                return;
+            }
+            auto* svc = &services::DividendService::instance();
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, id, refresh](auto resolve) {
+                auto* holder = new QObject(svc);
+                QObject::connect(svc, &services::DividendService::dividends_synced, holder,
+                                 [resolve, holder, id](portfolio::DividendSummary d) {
+                                     if (d.portfolio_id != id)
+                                         return;
+                                     resolve(ToolResult::ok_data(dividend_summary_to_json(d)));
+                                     holder->deleteLater();
+                                 });
+                QObject::connect(svc, &services::DividendService::dividends_error, holder,
+                                 [resolve, holder, id](QString pid, QString err) {
+                                     if (pid != id)
+                                         return;
+                                     resolve(ToolResult::fail("Dividend sync failed: " + err));
+                                     holder->deleteLater();
+                                 });
+                if (refresh)
+                    svc->sync(id);
+                else
+                    svc->load(id);
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── get_capital_gains ───────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "get_capital_gains";
+        t.description = "Realized capital gains from the portfolio's transaction ledger (FIFO lots): short/long-term "
+                        "totals per tax year, per-lot dispositions, wash-sale flags (US) or 2018 grandfathering "
+                        "(India), open lots, and optionally a Form 8949-style CSV.";
+        t.category = "portfolio";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("portfolio_id", "Portfolio ID")
+                             .required()
+                             .string("jurisdiction", "Tax rules to apply")
+                             .enums(services::TaxRules::supported())
+                             .default_str("US")
+                             .string("tax_year", "Limit to one tax year, e.g. \"2025\" (US) or \"FY2025-26\" (IN)")
+                             .boolean("include_8949_csv", "Attach Form 8949-style CSV (US only)")
+                             .default_bool(false)
+                             .build();
+        t.default_timeout_ms = 90000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString id = args["portfolio_id"].toString().trimmed();
+            const QString jurisdiction = args["jurisdiction"].toString("US");
+            const QString tax_year = args["tax_year"].toString().trimmed();
+            const bool with_csv = args["include_8949_csv"].toBool(false);
+            if (id.isEmpty()) {
+                promise->addResult(ToolResult::fail("Missing 'portfolio_id'"));
+                promise->finish();
+                return;
+            }
+            auto* svc = &services::PortfolioService::instance();
+            AsyncDispatch::callback_to_promise(
+                svc, std::move(ctx), promise, [svc, id, jurisdiction, tax_year, with_csv](auto resolve) {
+                    auto* holder = new QObject(svc);
+                    QObject::connect(svc, &services::PortfolioService::tax_report_computed, holder,
+                                     [resolve, holder, id, with_csv](portfolio::TaxReport r) {
+                                         if (r.portfolio_id != id)
+                                             return;
+                                         QJsonObject out = tax_report_to_json(r);
+                                         if (with_csv && r.jurisdiction == "US")
+                                             out["form_8949_csv"] = services::TaxLotEngine::to_form_8949_csv(r);
+                                         resolve(ToolResult::ok_data(out));
+                                         holder->deleteLater();
+                                     });
+                    QObject::connect(svc, &services::PortfolioService::tax_report_error, holder,
+                                     [resolve, holder, id](QString pid, QString err) {
+                                         if (pid != id)
+                                             return;
+                                         resolve(ToolResult::fail(err));
+                                         holder->deleteLater();
+                                     });
+                    svc->compute_tax_report(id, jurisdiction, tax_year);
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── simulate_retirement_plan ────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "simulate_retirement_plan";
+        t.description = "Native Monte Carlo retirement/goal simulation (10k+ paths in milliseconds). Supports "
+                        "normal/lognormal/student_t return and inflation distributions, contribution and "
+                        "withdrawal schedules by age, an equity/bond glidepath, and a real-terms goal. Returns "
+                        "success probability (never running out), goal probability, depletion ages and a yearly "
+                        "p5–p95 percentile cone in nominal and today's money.";
+        t.category = "portfolio";
+        const QJsonObject dist_schema{
+            {"type", "object"},
+            {"properties", QJsonObject{{"kind", QJsonObject{{"type", "string"},
+                                                            {"enum", QJsonArray{"normal", "lognormal", "student_t"}}}},
+                                       {"mean", QJsonObject{{"type", "number"}}},
+                                       {"vol", QJsonObject{{"type", "number"}}},
+                                       {"dof", QJsonObject{{"type", "number"}}}}}};
+        const QJsonObject flow_schema{
+            {"type", "object"},
+            {"properties", QJsonObject{{"label", QJsonObject{{"type", "string"}}},
+                                       {"amount", QJsonObject{{"type", "number"}}},
+                                       {"from_age", QJsonObject{{"type", "integer"}}},
+                                       {"to_age", QJsonObject{{"type", "integer"}}},
+                                       {"growth", QJsonObject{{"type", "number"}}},
+                                       {"inflation_indexed", QJsonObject{{"type", "boolean"}}}}}};
+        t.input_schema =
+            ToolSchemaBuilder()
+                .integer("current_age", "Age today")
+                .required()
+                .between(0, 110)
+                .integer("retirement_age", "Age contributions stop and withdrawals start")
+                .required()
+                .between(0, 120)
+                .integer("end_age", "Planning horizon (age)")
+                .default_int(95)
+                .between(1, 130)
+                .number("initial_balance", "Starting portfolio value")
+                .default_num(0)
+                .number("annual_contribution", "Shorthand: flat yearly saving until retirement (today's money)")
+                .number("contribution_growth", "Real yearly growth of the shorthand contribution (decimal)")
+                .number("annual_withdrawal", "Shorthand: flat yearly spending from retirement (today's money)")
+                .array("contributions", "Explicit contribution schedules (override the shorthand)", flow_schema)
+                .array("withdrawals", "Explicit withdrawal schedules (override the shorthand)", flow_schema)
+                .object("equity", "Equity return distribution {kind, mean, vol, dof}; default normal 7%/16%")
+                .object("bonds", "Bond return distribution; default normal 3%/6%")
+                .number("equity_bond_correlation", "Correlation of equity and bond shocks")
+                .between(-1, 1)
+                .array("glidepath", "Equity weight by age: [{age, equity_weight}], interpolated linearly",
+                       QJsonObject{{"type", "object"}})
+                .object("inflation", "Inflation distribution; default normal 2.5%/1%")
+                .number("inflation_persistence", "AR(1) persistence of inflation, 0–0.99")
+                .number("goal_target", "Real (today's money) balance to reach at goal_age")
+                .integer("goal_age", "Age the goal is tested at (default retirement_age)")
+                .number("annual_fee", "Yearly fee drag on the balance (decimal)")
+                .integer("paths", "Number of simulated paths")
+                .default_int(10000)
+                .between(100, services::RetirementSimulator::kMaxPaths)
+                .integer("seed", "RNG seed for reproducible runs")
+                .boolean("include_cone", "Include the yearly percentile cone")
+                .default_bool(true)
+                .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const auto cfg = services::RetirementSimulator::config_from_json(args);
+            const auto r = services::RetirementSimulator::run(cfg);
+            if (!r.valid)
+                return ToolResult::fail("Invalid simulation inputs: " + r.error);
+            return ToolResult::ok_data(
+                services::RetirementSimulator::result_to_json(r, args["include_cone"].toBool(true)));
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── optimize_portfolio_weights ──────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "optimize_portfolio_weights";
+        t.description = "Native portfolio optimisation on stored daily closes (works offline once prices are "
+                        "cached): max Sharpe, min volatility, max return, target return/volatility, risk parity, "
+                        "HRP, equal weight, inverse variance or Black-Litterman equilibrium. Supports long-only, "
+                        "per-asset bounds, sector caps and a choice of covariance estimator (sample, Ledoit-Wolf "
+                        "shrinkage, constant correlation, EWMA, semicovariance, factor model). Returns weights, "
+                        "risk decomposition, efficient frontier and an all-methods comparison.";
+        t.category = "portfolio";
+        t.input_schema = ToolSchemaBuilder()
+                             .array("symbols", "Tickers (yfinance format)", QJsonObject{{"type", "string"}})
+                             .required()
+                             .array("weights", "Current weights aligned with symbols (market proxy for B-L)",
+                                    QJsonObject{{"type", "number"}})
+                             .string("method", "Optimisation objective")
+                             .enums({"max_sharpe", "min_volatility", "max_return", "target_return",
+                                     "target_volatility", "risk_parity", "hrp", "equal_weight", "inverse_variance",
+                                     "black_litterman"})
+                             .default_str("max_sharpe")
+                             .number("risk_free_rate", "Annual risk-free rate (decimal)")
+                             .default_num(0.04)
+                             .number("target_return", "Annual return for target_return (decimal)")
+                             .number("target_volatility", "Annual volatility for target_volatility (decimal)")
+                             .integer("lookback_days", "Price history window in calendar days")
+                             .default_int(365)
+                             .between(60, 3650)
+                             .string("risk_model", "Covariance estimator")
+                             .enums({"sample", "ledoit_wolf", "constant_correlation", "ewma", "semicovariance",
+                                     "factor"})
+                             .default_str("sample")
+                             .number("ewma_lambda", "Decay for the ewma estimator")
+                             .default_num(0.94)
+                             .integer("pca_factors", "Statistical factors when factor_symbols is empty")
+                             .default_int(3)
+                             .array("factor_symbols", "Factor proxies for the factor model, e.g. [\"SPY\", \"TLT\"]",
+                                    QJsonObject{{"type", "string"}})
+                             .boolean("long_only", "Disallow short positions")
+                             .default_bool(true)
+                             .number("min_weight", "Default minimum weight per asset")
+                             .number("max_weight", "Default maximum weight per asset")
+                             .object("bounds", "Per-symbol bounds: {\"AAPL\": [0.05, 0.25], ...}")
+                             .object("sectors", "Sector per symbol (defaults to the cached sector lookup)")
+                             .object("sector_caps", "Maximum total weight per sector: {\"Technology\": 0.4}")
+                             .build();
+        t.default_timeout_ms = 120000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            if (args["symbols"].toArray().isEmpty()) {
+                promise->addResult(ToolResult::fail("Missing 'symbols'"));
+                promise->finish();
+                return;
+            }
+            const QString json = optimizer_payload(args);
+            auto* svc = &services::PortfolioAnalyticsService::instance();
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, json](auto resolve) {
+                svc->optimize_weights(json, [resolve](const services::AnalyticsResult& r) {
+                    resolve(r.success ? ToolResult::ok_data(r.data) : ToolResult::fail(r.error));
+                });
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── black_litterman_optimize ────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "black_litterman_optimize";
+        t.description = "Black-Litterman: market-cap equilibrium prior plus investor views (absolute \"AAPL returns "
+                        "12%\" or relative \"AAPL beats XOM by 3%\", each with a 0–1 confidence) → posterior "
+                        "expected returns and covariance, then native optimisation on the posterior. Returns the "
+                        "weights and every intermediate: π, P, Q, Ω, μ_BL, prior and posterior covariance.";
+        t.category = "portfolio";
+        const QJsonObject view_schema{
+            {"type", "object"},
+            {"properties",
+             QJsonObject{{"type", QJsonObject{{"type", "string"}, {"enum", QJsonArray{"absolute", "relative"}}}},
+                         {"asset", QJsonObject{{"type", "string"}}},
+                         {"long", QJsonObject{{"type", "array"}, {"items", QJsonObject{{"type", "string"}}}}},
+                         {"short", QJsonObject{{"type", "array"}, {"items", QJsonObject{{"type", "string"}}}}},
+                         {"return", QJsonObject{{"type", "number"}}},
+                         {"confidence", QJsonObject{{"type", "number"}}},
+                         {"label", QJsonObject{{"type", "string"}}}}},
+            {"required", QJsonArray{"return"}}};
+        t.input_schema = ToolSchemaBuilder()
+                             .array("symbols", "Tickers (yfinance format)", QJsonObject{{"type", "string"}})
+                             .required()
+                             .object("market_caps", "Market capitalisation per symbol — the prior allocation")
+                             .array("weights", "Fallback prior weights aligned with symbols",
+                                    QJsonObject{{"type", "number"}})
+                             .array("views", "Investor views", view_schema)
+                             .number("tau", "Uncertainty of the prior relative to the covariance")
+                             .default_num(0.05)
+                             .number("risk_aversion", "δ for π = δΣw (default 2.5)")
+                             .number("market_return", "Expected market return; derives δ when risk_aversion is unset")
+                             .number("risk_free_rate", "Annual risk-free rate (decimal)")
+                             .default_num(0.04)
+                             .string("method", "Objective applied to the posterior")
+                             .enums({"max_sharpe", "min_volatility", "max_return", "target_return",
+                                     "target_volatility"})
+                             .default_str("max_sharpe")
+                             .number("target_return", "Annual return for target_return (decimal)")
+                             .number("target_volatility", "Annual volatility for target_volatility (decimal)")
+                             .integer("lookback_days", "Price history window for the covariance, calendar days")
+                             .default_int(365)
+                             .between(60, 3650)
+                             .string("risk_model", "Covariance estimator")
+                             .enums({"sample", "ledoit_wolf", "constant_correlation", "ewma", "semicovariance",
+                                     "factor"})
+                             .default_str("sample")
+                             .number("ewma_lambda", "Decay for the ewma estimator")
+                             .default_num(0.94)
+                             .integer("pca_factors", "Statistical factors when factor_symbols is empty")
+                             .default_int(3)
+                             .array("factor_symbols", "Factor proxies for the factor model, e.g. [\"SPY\", \"TLT\"]",
+                                    QJsonObject{{"type", "string"}})
+                             .boolean("long_only", "Disallow short positions")
+                             .default_bool(true)
+                             .number("min_weight", "Default minimum weight per asset")
+                             .number("max_weight", "Default maximum weight per asset")
+                             .object("bounds", "Per-symbol bounds: {\"AAPL\": [0.05, 0.25], ...}")
+                             .object("sectors", "Sector per symbol (defaults to the cached sector lookup)")
+                             .object("sector_caps", "Maximum total weight per sector")
+                             .build();
+        t.default_timeout_ms = 120000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            if (args["symbols"].toArray().isEmpty()) {
+                promise->addResult(ToolResult::fail("Missing 'symbols'"));
+                promise->finish();
+                return;
+            }
+            const QString json = optimizer_payload(args);
+            auto* svc = &services::PortfolioAnalyticsService::instance();
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, json](auto resolve) {
+                svc->black_litterman(json, [resolve](const services::AnalyticsResult& r) {
+                    resolve(r.success ? ToolResult::ok_data(r.data) : ToolResult::fail(r.error));
+                });
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── forecast_portfolio_risk ─────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "forecast_portfolio_risk";
+        t.description = "Ex-ante risk of a weighted portfolio from stored daily closes under a chosen covariance "
+                        "estimator (sample, Ledoit-Wolf shrinkage, constant correlation, EWMA, semicovariance, or a "
+                        "factor model on factor proxies / principal components). Returns annual and daily "
+                        "volatility, 1-day parametric VaR, risk contributions, diversification ratio and the "
+                        "volatility under every estimator for comparison.";
+        t.category = "portfolio";
+        t.input_schema = ToolSchemaBuilder()
+                             .array("symbols", "Tickers (yfinance format)", QJsonObject{{"type", "string"}})
+                             .required()
+                             .array("weights", "Weights aligned with symbols (default equal weight)",
+                                    QJsonObject{{"type", "number"}})
+                             .number("portfolio_value", "Portfolio value, to express VaR in currency")
+                             .integer("lookback_days", "Price history window in calendar days")
+                             .default_int(365)
+                             .between(60, 3650)
+                             .string("risk_model", "Covariance estimator")
+                             .enums({"sample", "ledoit_wolf", "constant_correlation", "ewma", "semicovariance",
+                                     "factor"})
+                             .default_str("sample")
+                             .number("ewma_lambda", "Decay for the ewma estimator")
+                             .default_num(0.94)
+                             .integer("pca_factors", "Statistical factors when factor_symbols is empty")
+                             .default_int(3)
+                             .array("factor_symbols", "Factor proxies for the factor model, e.g. [\"SPY\", \"TLT\"]",
+                                    QJsonObject{{"type", "string"}})
+                             .boolean("include_matrix", "Include the covariance matrix and factor loadings")
+                             .default_bool(false)
+                             .build();
+        t.default_timeout_ms = 120000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            if (args["symbols"].toArray().isEmpty()) {
+                promise->addResult(ToolResult::fail("Missing 'symbols'"));
+                promise->finish();
+                return;
+            }
+            const QString json = QString::fromUtf8(QJsonDocument(args).toJson(QJsonDocument::Compact));
+            auto* svc = &services::PortfolioAnalyticsService::instance();
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, json](auto resolve) {
+                svc->forecast_risk(json, [resolve](const services::AnalyticsResult& r) {
+                    resolve(r.success ? ToolResult::ok_data(r.data) : ToolResult::fail(r.error));
+                });
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── run_portfolio_scenario ──────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "run_portfolio_scenario";
+        t.description = "What-if P&L: apply hypothetical shocks to a portfolio's current holdings, e.g. "
+                        "[\"SPX -10%\", \"rates +100bp\", \"USDINR +3%\", \"AAPL -20%\", \"vol +5\"]. Index and "
+                        "rate shocks flow through betas regressed on stored daily closes (rates betas are "
+                        "empirical durations), FX shocks through the currency translation, and OCC option "
+                        "symbols are repriced under Black-Scholes. Returns projected P&L by position (worst "
+                        "first), by shock and by sector, currency and asset class.";
+        t.category = "portfolio";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("portfolio_id", "Portfolio ID")
+                             .required()
+                             .array("shocks",
+                                    "Shocks as text (\"NIFTY -5%\", \"UST10Y +50bp\", \"EURUSD -2%\") or objects "
+                                    "{type: factor|ticker|rates|fx|vol, symbol, move_pct | move_bp | move_pts, label}",
+                                    QJsonObject{})
+                             .required()
+                             .integer("lookback_days", "Daily history the betas are estimated on")
+                             .default_int(365)
+                             .between(60, 3650)
+                             .integer("horizon_days", "Option time decay applied with the shocks")
+                             .default_int(0)
+                             .between(0, 365)
+                             .number("risk_free_rate", "Annual rate for option repricing")
+                             .default_num(0.04)
+                             .object("betas", "Beta overrides: {\"AAPL\": {\"SPX\": 1.2}}")
+                             .object("durations", "Modified durations in years, replacing the rates beta: "
+                                                  "{\"TLT\": 17}")
+                             .build();
+        t.default_timeout_ms = 120000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString id = args["portfolio_id"].toString().trimmed();
+            auto spec = services::ScenarioEngine::spec_from_json(args);
+            if (id.isEmpty() || spec.is_err()) {
+                const QString err = id.isEmpty() ? QStringLiteral("Missing 'portfolio_id'")
+                                                 : QString::fromStdString(spec.error());
+                promise->addResult(ToolResult::fail(err));
+                promise->finish();
+                return;
+            }
+            auto* svc = &services::PortfolioService::instance();
+            const auto parsed = spec.value();
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, id, parsed](auto resolve) {
+                auto* holder = new QObject(svc);
+                auto started = std::make_shared<bool>(false);
+                QObject::connect(svc, &services::PortfolioService::summary_loaded, holder,
+                                 [svc, holder, id, parsed, started, resolve](portfolio::PortfolioSummary s) {
+                                     if (s.portfolio.id != id || *started)
+                                         return;
+                                     *started = true;
+                                     svc->run_scenario(s, parsed, [resolve, holder](services::ScenarioResult r) {
+                                         holder->deleteLater();
+                                         if (!r.ok) {
+                                             resolve(ToolResult::fail(r.error));
+                                             return;
+                                         }
+                                         const QString msg = QString("Scenario P&L %1 %2 (%3%)")
+                                                                 .arg(r.total_pnl, 0, 'f', 2)
+                                                                 .arg(r.base_currency)
+                                                                 .arg(r.pnl_pct, 0, 'f', 2);
+                                         resolve(ToolResult::ok(msg, services::ScenarioEngine::result_to_json(r)));
+                                     });
+                                 });
+                QObject::connect(svc, &services::PortfolioService::summary_error, holder,
+                                 [resolve, holder, id, started](QString pid, QString err) {
+                                     if (pid != id || *started)
+                                         return;
+                                     resolve(ToolResult::fail("Failed to load portfolio: " + err));
+                                     holder->deleteLater();
+                                 });
+                svc->load_summary(id);
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── get_position_event_risk ─────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "get_position_event_risk";
+        t.description = "Binary events ahead for every open position (portfolio holdings plus live / paper broker "
+                        "positions): next earnings date or window with EPS estimate, announced ex-dividend dates, "
+                        "and central-bank decisions (FOMC, ECB, BoE, RBI, ...) for the economy of each holding's "
+                        "currency. Holdings with an event come first, soonest first; 'events' is the same as one "
+                        "calendar and 'macro' lists other high-importance releases for those economies.";
+        t.category = "portfolio";
+        t.input_schema = ToolSchemaBuilder()
+                             .integer("horizon_days", "Days ahead to look")
+                             .default_int(services::EventRiskService::kDefaultHorizonDays)
+                             .between(1, 365)
+                             .boolean("refresh", "Re-fetch every earnings date instead of only those older than a day")
+                             .default_bool(false)
+                             .string("symbol", "Only this holding")
+                             .build();
+        t.default_timeout_ms = 120000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const int horizon = std::clamp(args["horizon_days"].toInt(services::EventRiskService::kDefaultHorizonDays),
+                                           1, 365);
+            const bool refresh = args["refresh"].toBool(false);
+            const QString symbol = args["symbol"].toString().trimmed().toUpper();
+            auto* svc = &services::EventRiskService::instance();
+            AsyncDispatch::callback_to_promise(
+                svc, std::move(ctx), promise, [svc, horizon, refresh, symbol](auto resolve) {
+                    auto* holder = new QObject(svc);
+                    auto request = std::make_shared<QString>();
+                    QObject::connect(svc, &services::EventRiskService::report_ready, holder,
+                                     [resolve, holder, request, symbol](QString id, QJsonObject report) {
+                                         if (id != *request)
+                                             return;
+                                         holder->deleteLater();
+                                         if (!symbol.isEmpty()) {
+                                             QJsonArray kept;
+                                             for (const auto& v : report["holdings"].toArray())
+                                                 if (v.toObject()["symbol"].toString() == symbol)
+                                                     kept.append(v);
+                                             if (kept.isEmpty()) {
+                                                 resolve(ToolResult::fail("No open position in " + symbol));
+                                                 return;
+                                             }
+                                             report["holdings"] = kept;
+                                             report["events"] = kept.first().toObject()["events"];
+                                         }
+                                         const QString msg = QString("%1/%2 positions with events in %3 days")
+                                                                 .arg(report["positions_with_events"].toInt())
+                                                                 .arg(report["positions"].toInt())
+                                                                 .arg(report["horizon_days"].toInt());
+                                         resolve(ToolResult::ok(msg, report));
+                                     });
+                    *request = svc->scan(horizon, refresh);
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── set_event_risk_alerts ───────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "set_event_risk_alerts";
+        t.description = "Remind N days before an earnings date or central-bank decision that affects an open "
+                        "position (one notification per event). 0 turns the reminders off. Ex-dividend reminders "
+                        "are handled by the dividend tracker.";
+        t.category = "portfolio";
+        t.input_schema = ToolSchemaBuilder()
+                             .integer("days", "Days before the event; 0 = off")
+                             .required()
+                             .between(0, 30)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            if (!args.contains("days"))
+                return ToolResult::fail("Missing 'days'");
+            auto& svc = services::EventRiskService::instance();
+            int days = 0;
+            detail::run_on_target_thread_sync(&svc, [&]() {
+                svc.set_alert_days(args["days"].toInt());
+                days = svc.alert_days();
+                svc.check_alerts();
+            });
+            const QString msg = days > 0 ? QString("Event reminders fire %1 days ahead").arg(days)
+                                         : QStringLiteral("Event reminders off");
+            return ToolResult::ok(msg, QJsonObject{{"alert_days", days}});
+        };
+        tools.push_back(std::move(t));
+    }
+
     return tools;
 }
 
@@ -0,0 +1,464 @@
+// ScreenerTools.cpp — native screener MCP tools: filter trees over cached fundamentals, technicals and
+// ownership, saved screens with scheduled re-runs, run history and CSV export
+
+#include "mcp/tools/ScreenerTools.h"
+
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "mcp/tools/ThreadHelper.h"
+#include "services/screener/ScreenFilter.h"
+#include "services/screener/ScreenerEngine.h"
+#include "storage/repositories/ScreenerRepository.h"
+
+#include <QCoreApplication>
+#include <QDate>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+
+#include <algorithm>
+#include <functional>
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+namespace {
+
+using services::ScreenerEngine;
+using services::ScreenFilter;
+using services::ScreenSpec;
+
+const QString kFilterHelp =
+    "Filter tree: {\"all\": [...]}, {\"any\": [...]}, {\"not\": node}, or a condition {\"field\", \"op\", \"value\"} "
+    "with op < <= > >= == != between ([lo, hi]) in / not_in ([...]) contains exists missing. Compare two fields "
+    "with \"value_field\" instead of \"value\", e.g. {\"field\": \"price\", \"op\": \">\", \"value_field\": "
+    "\"sma_200\"}. Percent fields are in percent (15 = 15%). {} matches everything. See screener_fields.";
+
+// Runs `fn` on the main thread; a non-empty returned string is the failure message.
+ToolResult on_main(const std::function<QString()>& fn, const std::function<ToolResult()>& ok) {
+    QString error;
+    detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+        error = fn();
+        signal_done();
+    });
+    return error.isEmpty() ? ok() : ToolResult::fail(error);
+}
+
+QStringList string_list(const QJsonValue& v) {
+    QStringList out;
+    for (const auto& e : v.toArray()) {
+        const QString s = e.toString().trimmed();
+        if (!s.isEmpty())
+            out.append(s);
+    }
+    return out;
+}
+
+ToolSchemaBuilder& add_spec_params(ToolSchemaBuilder& b) {
+    return b.object("filter", kFilterHelp)
+        .array("symbols", "Universe to screen; omit to screen every symbol with cached metrics",
+               QJsonObject{{"type", "string"}})
+        .string("sort_field", "Field to sort by (default market_cap)")
+        .boolean("sort_desc", "Sort descending")
+        .default_bool(true)
+        .integer("max_results", "Rows to return")
+        .default_int(50)
+        .between(1, 1000)
+        .array("columns", "Extra fields to include in each row", QJsonObject{{"type", "string"}});
+}
+
+ScreenSpec spec_from_args(const QJsonObject& args) {
+    ScreenSpec spec;
+    spec.filter = args["filter"].toObject();
+    spec.universe = string_list(args["symbols"]);
+    spec.sort_field = args["sort_field"].toString().trimmed();
+    spec.sort_desc = args["sort_desc"].toBool(true);
+    spec.max_results = std::clamp(args["max_results"].toInt(50), 1, 1000);
+    spec.columns = string_list(args["columns"]);
+    return spec;
+}
+
+QString run_summary(const QJsonObject& run) {
+    return QString("%1 of %2 symbols match; %3 new, %4 dropped since the previous run")
+        .arg(run["match_count"].toInt())
+        .arg(run["universe_size"].toInt())
+        .arg(run["added"].toArray().size())
+        .arg(run["removed"].toArray().size());
+}
+
+} // namespace
+
+std::vector<ToolDef> get_screener_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── screener_fields ────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "screener_fields";
+        t.description = "Fields the native screener can filter, sort and display, grouped by category (profile, "
+                        "fundamental, technical, ownership, alternative), with units, plus how many symbols have "
+                        "cached metrics.";
+        t.category = "markets";
+        t.handler = [](const QJsonObject&) -> ToolResult {
+            QJsonObject groups;
+            for (const auto& f : ScreenFilter::fields()) {
+                QJsonArray list = groups[f.category].toArray();
+                list.append(QJsonObject{{"key", f.key},
+                                        {"label", f.label},
+                                        {"unit", f.unit},
+                                        {"type", f.text ? "text" : "number"}});
+                groups[f.category] = list;
+            }
+            int cached = 0;
+            return on_main(
+                [&]() {
+                    auto r = ScreenerRepository::instance().cached_symbols();
+                    if (r.is_err())
+                        return QString::fromStdString(r.error());
+                    cached = static_cast<int>(r.value().size());
+                    return QString();
+                },
+                [&]() { return ToolResult::ok_data(QJsonObject{{"fields", groups}, {"cached_symbols", cached}}); });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── screener_refresh_data ──────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "screener_refresh_data";
+        t.description = "Fetch fundamentals, 1y daily technicals, ownership and analyst data for symbols into the "
+                        "screener's local store. Screens only see symbols refreshed at least once; about 1-2 s per "
+                        "symbol.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .array("symbols", "Symbols to refresh (yfinance tickers)", QJsonObject{{"type", "string"}})
+                             .required()
+                             .build();
+        t.default_timeout_ms = 600000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            auto* svc = &ScreenerEngine::instance();
+            const QStringList symbols = string_list(args["symbols"]);
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, symbols](auto resolve) {
+                auto* holder = new QObject(svc);
+                auto request = std::make_shared<QString>();
+                QObject::connect(svc, &ScreenerEngine::data_refreshed, holder,
+                                 [resolve, holder, request](QString id, int updated, QJsonArray failed) {
+                                     if (id != *request)
+                                         return;
+                                     resolve(ToolResult::ok(QString("Refreshed %1 symbols, %2 failed")
+                                                                .arg(updated)
+                                                                .arg(failed.size()),
+                                                            QJsonObject{{"updated", updated}, {"failed", failed}}));
+                                     holder->deleteLater();
+                                 });
+                QObject::connect(svc, &ScreenerEngine::error_occurred, holder,
+                                 [resolve, holder, request](QString id, QString msg) {
+                                     if (id != *request)
+                                         return;
+                                     resolve(ToolResult::fail(msg));
+                                     holder->deleteLater();
+                                 });
+                *request = svc->refresh_data(symbols);
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── screener_run ───────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "screener_run";
+        t.description = "Screen the locally cached metrics with a filter tree combining fundamental, technical, "
+                        "ownership and alternative fields (e.g. P/E < 20 AND RSI < 35 AND insider ownership > 5%). "
+                        "Runs offline against the last screener_refresh_data. Returns sorted rows with the sort, "
+                        "filter and requested fields; `uncached` lists universe symbols that need a refresh. With "
+                        "as_of, screens the point-in-time fundamentals filed by that date instead (margins, ROE, "
+                        "ROA, growth, debt / equity, current ratio; see fundamentals_pit_ingest) — no lookahead.";
+        t.category = "markets";
+        ToolSchemaBuilder b;
+        t.input_schema = add_spec_params(b)
+                             .string("export_path", "Also write the rows to this CSV file")
+                             .string("as_of", "Screen fundamentals as filed by this date (YYYY-MM-DD)")
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            ScreenSpec spec = spec_from_args(args);
+            if (const QString d = args["as_of"].toString().trimmed(); !d.isEmpty()) {
+                spec.as_of = QDate::fromString(d, Qt::ISODate);
+                if (!spec.as_of.isValid())
+                    return ToolResult::fail("as_of must be a YYYY-MM-DD date");
+            }
+            const QString export_path = args["export_path"].toString().trimmed();
+            QJsonObject out;
+            return on_main(
+                [&]() {
+                    auto r = ScreenerEngine::instance().run(spec);
+                    if (r.is_err())
+                        return QString::fromStdString(r.error());
+                    out = ScreenerEngine::result_to_json(r.value());
+                    if (!export_path.isEmpty()) {
+                        auto e = ScreenerEngine::export_csv(r.value().columns, r.value().rows, export_path);
+                        if (e.is_err())
+                            return QString::fromStdString(e.error());
+                        out["export_path"] = export_path;
+                    }
+                    return QString();
+                },
+                [&]() {
+                    return ToolResult::ok(QString("%1 of %2 symbols match %3")
+                                              .arg(out["match_count"].toInt())
+                                              .arg(out["universe_size"].toInt())
+                                              .arg(out["filter_text"].toString()),
+                                          out);
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── screener_save ──────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "screener_save";
+        t.description = "Create or update a saved screen. With schedule_minutes > 0 it re-runs automatically "
+                        "(refreshing its universe's metrics first when refresh_data), stores every run with the "
+                        "symbols that entered or left the results, rewrites export_path as CSV, and posts a toast "
+                        "when the results change.";
+        t.category = "markets";
+        ToolSchemaBuilder b;
+        b.string("id", "Existing screen id to update; omit to create").string("name", "Screen name").required();
+        t.input_schema = add_spec_params(b)
+                             .string("description", "What the screen looks for")
+                             .integer("schedule_minutes", "Re-run every N minutes (0 = manual only)")
+                             .default_int(0)
+                             .between(0, 60 * 24 * 7)
+                             .boolean("refresh_data", "Refresh metrics before each scheduled run")
+                             .default_bool(true)
+                             .boolean("notify", "Toast when a scheduled run changes the results")
+                             .default_bool(true)
+                             .string("export_path", "CSV file rewritten after every run")
+                             .build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const ScreenSpec spec = spec_from_args(args);
+            SavedScreen s;
+            s.id = args["id"].toString().trimmed();
+            s.name = args["name"].toString().trimmed();
+            s.description = args["description"].toString().trimmed();
+            s.filter = spec.filter;
+            s.universe = spec.universe;
+            s.sort_field = spec.sort_field;
+            s.sort_desc = spec.sort_desc;
+            s.max_results = spec.max_results;
+            s.columns = spec.columns;
+            s.schedule_minutes = std::max(0, args["schedule_minutes"].toInt(0));
+            s.refresh_data = args["refresh_data"].toBool(true);
+            s.notify = args["notify"].toBool(true);
+            s.export_path = args["export_path"].toString().trimmed();
+            if (s.name.isEmpty())
+                return ToolResult::fail("'name' is required");
+            if (const QString err = ScreenFilter::validate(s.filter); !err.isEmpty())
+                return ToolResult::fail("Invalid filter: " + err);
+            for (const QString& f : ScreenerEngine::columns_for(spec)) {
+                if (!ScreenFilter::field(f))
+                    return ToolResult::fail(QString("Unknown field '%1'").arg(f));
+            }
+
+            SavedScreen saved;
+            return on_main(
+                [&]() {
+                    auto& repo = ScreenerRepository::instance();
+                    if (!s.id.isEmpty()) {
+                        const auto existing = repo.get_screen(s.id);
+                        if (!existing)
+                            return "Unknown saved screen: " + s.id;
+                        s.last_run_at = existing->last_run_at;
+                        s.last_match_count = existing->last_match_count;
+                    }
+                    auto r = repo.save_screen(s);
+                    if (r.is_err())
+                        return QString::fromStdString(r.error());
+                    saved = r.value();
+                    return QString();
+                },
+                [&]() { return ToolResult::ok("Saved screen " + saved.name, ScreenerEngine::screen_to_json(saved)); });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── screener_list ──────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "screener_list";
+        t.description = "List saved screens with their filter, schedule, export path and last run.";
+        t.category = "markets";
+        t.handler = [](const QJsonObject&) -> ToolResult {
+            QJsonArray list;
+            return on_main(
+                [&]() {
+                    auto r = ScreenerRepository::instance().list_screens();
+                    if (r.is_err())
+                        return QString::fromStdString(r.error());
+                    for (const auto& s : r.value())
+                        list.append(ScreenerEngine::screen_to_json(s));
+                    return QString();
+                },
+                [&]() { return ToolResult::ok_data(QJsonObject{{"screens", list}, {"count", list.size()}}); });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── screener_run_saved ─────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "screener_run_saved";
+        t.description = "Run a saved screen now and store the run. Returns the rows plus the symbols that entered "
+                        "or left the results since the previous run.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("screen", "Saved screen id or name")
+                             .required()
+                             .boolean("refresh_data", "Refresh the universe's metrics first")
+                             .default_bool(false)
+                             .build();
+        t.default_timeout_ms = 600000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            auto* svc = &ScreenerEngine::instance();
+            const QString screen = args["screen"].toString().trimmed();
+            const bool refresh = args["refresh_data"].toBool(false);
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, screen, refresh](auto resolve) {
+                auto* holder = new QObject(svc);
+                auto request = std::make_shared<QString>();
+                QObject::connect(svc, &ScreenerEngine::screen_ran, holder,
+                                 [resolve, holder, request](QString id, QString, QJsonObject run) {
+                                     if (id != *request)
+                                         return;
+                                     resolve(ToolResult::ok(run["name"].toString() + ": " + run_summary(run), run));
+                                     holder->deleteLater();
+                                 });
+                QObject::connect(svc, &ScreenerEngine::error_occurred, holder,
+                                 [resolve, holder, request](QString id, QString msg) {
+                                     if (id != *request)
+                                         return;
+                                     resolve(ToolResult::fail(msg));
+                                     holder->deleteLater();
+                                 });
+                const auto s = ScreenerRepository::instance().find_screen(screen);
+                *request = svc->run_saved(s ? s->id : screen, refresh);
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── screener_history ───────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "screener_history";
+        t.description = "Stored runs of a saved screen, newest first: match count and the symbols that entered or "
+                        "left the results each time.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("screen", "Saved screen id or name")
+                             .required()
+                             .integer("limit", "Maximum runs")
+                             .default_int(20)
+                             .between(1, ScreenerEngine::kKeepRuns)
+                             .boolean("include_rows", "Include each run's result rows")
+                             .default_bool(false)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString screen = args["screen"].toString().trimmed();
+            const int limit = std::clamp(args["limit"].toInt(20), 1, ScreenerEngine::kKeepRuns);
+            const bool rows = args["include_rows"].toBool(false);
+            QJsonArray list;
+            return on_main(
+                [&]() {
+                    auto& repo = ScreenerRepository::instance();
+                    const auto s = repo.find_screen(screen);
+                    if (!s)
+                        return "Unknown saved screen: " + screen;
+                    auto r = repo.list_runs(s->id, limit);
+                    if (r.is_err())
+                        return QString::fromStdString(r.error());
+                    for (const auto& run : r.value())
+                        list.append(ScreenerEngine::run_to_json(run, rows));
+                    return QString();
+                },
+                [&]() { return ToolResult::ok_data(QJsonObject{{"runs", list}, {"count", list.size()}}); });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── screener_export ────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "screener_export";
+        t.description = "Write the latest run of a saved screen to a CSV file: symbol, name, sector, then the sort, "
+                        "filter and requested columns.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("screen", "Saved screen id or name")
+                             .required()
+                             .string("path", "Destination .csv path")
+                             .required()
+                             .build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString screen = args["screen"].toString().trimmed();
+            const QString path = args["path"].toString().trimmed();
+            if (path.isEmpty())
+                return ToolResult::fail("'path' is required");
+            int rows = 0;
+            return on_main(
+                [&]() {
+                    auto& repo = ScreenerRepository::instance();
+                    const auto s = repo.find_screen(screen);
+                    if (!s)
+                        return "Unknown saved screen: " + screen;
+                    const auto run = repo.last_run(s->id);
+                    if (!run)
+                        return QString("Screen '%1' has not run yet — use screener_run_saved first").arg(s->name);
+                    const QStringList columns = ScreenerEngine::columns_for(ScreenerEngine::spec_for(*s));
+                    auto e = ScreenerEngine::export_csv(columns, run->rows, path);
+                    if (e.is_err())
+                        return QString::fromStdString(e.error());
+                    rows = static_cast<int>(run->rows.size());
+                    return QString();
+                },
+                [&]() {
+                    return ToolResult::ok(QString("Exported %1 rows to %2").arg(rows).arg(path),
+                                          QJsonObject{{"path", path}, {"rows", rows}});
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── screener_delete ────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "screener_delete";
+        t.description = "Delete a saved screen and its stored runs.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder().string("screen", "Saved screen id or name").required().build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString screen = args["screen"].toString().trimmed();
+            QString name;
+            return on_main(
+                [&]() {
+                    auto& repo = ScreenerRepository::instance();
+                    const auto s = repo.find_screen(screen);
+                    if (!s)
+                        return "Unknown saved screen: " + screen;
+                    name = s->name;
+                    auto r = repo.remove_screen(s->id);
+                    return r.is_err() ? QString::fromStdString(r.error()) : QString();
+                },
+                [&]() { return ToolResult::ok("Deleted saved screen " + name); });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
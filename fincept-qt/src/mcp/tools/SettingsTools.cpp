@@ -2,18 +2,28 @@
 
 #include "mcp/tools/SettingsTools.h"
 
+#include "core/config/AppPaths.h"
 #include "core/events/EventBus.h"
+#include "core/layout/LayoutCatalog.h"
 #include "core/logging/Logger.h"
+#include "mcp/AsyncDispatch.h"
 #include "mcp/ToolSchemaBuilder.h"
+#include "storage/ConfigBundle.h"
 #include "storage/repositories/LlmConfigRepository.h"
 #include "storage/repositories/SettingsRepository.h"
 
+#include <QDateTime>
+#include <QJsonArray>
 #include <QVariantMap>
 
 namespace fincept::mcp::tools {
 
 static constexpr const char* TAG = "SettingsTools";
 
+static QJsonObject bundle_section_items() {
+    return QJsonObject{{"type", "string"}, {"enum", QJsonArray::fromStringList(ConfigBundle::sections())}};
+}
+
 std::vector<ToolDef> get_settings_tools() {
     std::vector<ToolDef> tools;
 
@@ -135,9 +145,9 @@ std::vector<ToolDef> get_settings_tools() {
         t.auth_required = AuthLevel::Authenticated;
         t.is_destructive = true;
         t.input_schema = ToolSchemaBuilder()
-                             .string("provider", "Provider id (openai, anthropic, ollama, groq, google, fincept)")
+                             .string("provider", "Provider id (ollama / llama_cpp run locally)")
                              .required()
-                             .enums({"openai", "anthropic", "ollama", "groq", "google", "fincept"})
+                             .enums({"openai", "anthropic", "ollama", "llama_cpp", "groq", "google", "fincept"})
                              .build();
         t.handler = [](const QJsonObject& args) -> ToolResult {
             QString provider = args["provider"].toString();
@@ -155,6 +165,90 @@ std::vector<ToolDef> get_settings_tools() {
         tools.push_back(std::move(t));
     }
 
+    // ── export_config_bundle ────────────────────────────────────────────
+    // Credentials are never exported from here: the passphrase would pass
+    // through the model. The command palette action handles that case.
+    {
+        ToolDef t;
+        t.name = "export_config_bundle";
+        t.description = "Export settings, watchlists, screens, strategies and templates into one .fcbundle "
+                        "file for moving to another machine. Credentials are not included — use the "
+                        "'Export Configuration' command for that.";
+        t.category = "settings";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("path", "Output file path; defaults to a timestamped file in the files folder")
+                             .array("sections", "Subset to export; empty = all",
+                                    bundle_section_items())
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            // Layouts live behind LayoutCatalog's UI-thread connection.
+            auto* catalog = &LayoutCatalog::instance();
+            AsyncDispatch::callback_to_promise(catalog, std::move(ctx), promise, [args](auto resolve) {
+                QString path = args["path"].toString().trimmed();
+                if (path.isEmpty())
+                    path = AppPaths::files() + "/fincept_config_" +
+                           QDateTime::currentDateTime().toString("yyyyMMdd_HHmmss") + "." + ConfigBundle::kFileSuffix;
+                ConfigBundleOptions opts;
+                for (const auto& s : args["sections"].toArray())
+                    opts.sections << s.toString();
+                auto r = ConfigBundle::export_to(path, opts);
+                if (r.is_err()) {
+                    resolve(ToolResult::fail(QString::fromStdString(r.error())));
+                    return;
+                }
+                resolve(ToolResult::ok("Config bundle written to " + path, r.value()));
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── import_config_bundle ────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "import_config_bundle";
+        t.description = "Merge a config bundle into this profile (settings, watchlists, screens, strategies, "
+                        "templates). Rows with the same id are replaced. Use dry_run to see what the bundle "
+                        "holds first. Credentials in the bundle are left alone. A restart applies the changes.";
+        t.category = "settings";
+        t.auth_required = AuthLevel::Authenticated;
+        t.is_destructive = true;
+        t.input_schema = ToolSchemaBuilder()
+                             .string("path", "Bundle file path")
+                             .required()
+                             .array("sections", "Subset to import; empty = all",
+                                    bundle_section_items())
+                             .boolean("dry_run", "Only report the bundle's contents")
+                             .default_bool(false)
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            auto* catalog = &LayoutCatalog::instance();
+            AsyncDispatch::callback_to_promise(catalog, std::move(ctx), promise, [args](auto resolve) {
+                const QString path = args["path"].toString().trimmed();
+                if (path.isEmpty()) {
+                    resolve(ToolResult::fail("Missing 'path'"));
+                    return;
+                }
+                if (args["dry_run"].toBool()) {
+                    auto r = ConfigBundle::inspect(path);
+                    resolve(r.is_ok() ? ToolResult::ok_data(r.value())
+                                      : ToolResult::fail(QString::fromStdString(r.error())));
+                    return;
+                }
+                ConfigBundleOptions opts;
+                for (const auto& s : args["sections"].toArray())
+                    opts.sections << s.toString();
+                auto r = ConfigBundle::import_from(path, opts);
+                if (r.is_err()) {
+                    resolve(ToolResult::fail(QString::fromStdString(r.error())));
+                    return;
+                }
+                LOG_INFO(TAG, "Imported config bundle " + path);
+                resolve(ToolResult::ok("Config bundle imported — restart the terminal to apply it", r.value()));
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
     return tools;
 }
 
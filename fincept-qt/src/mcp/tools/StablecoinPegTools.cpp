@@ -0,0 +1,251 @@
+// StablecoinPegTools.cpp — stablecoin peg status, price / Curve pool history, depeg episodes and config MCP tools
+
+#include "mcp/tools/StablecoinPegTools.h"
+
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "mcp/tools/ThreadHelper.h"
+#include "services/crypto/StablecoinPegService.h"
+#include "storage/repositories/StablecoinPegRepository.h"
+
+#include <QCoreApplication>
+#include <QDateTime>
+#include <QJsonArray>
+#include <QJsonObject>
+
+#include <algorithm>
+#include <functional>
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+namespace {
+
+using services::StablecoinPegService;
+
+// Runs `fn` on the main thread, where the service and repository live; a
+// non-empty returned string is the failure message.
+ToolResult spt_on_main(const std::function<QString()>& fn, const std::function<ToolResult()>& ok) {
+    QString error;
+    detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+        error = fn();
+        signal_done();
+    });
+    return error.isEmpty() ? ok() : ToolResult::fail(error);
+}
+
+qint64 spt_since(const QJsonObject& args, int default_days, int max_days) {
+    const int days = std::clamp(args["days"].toInt(default_days), 1, max_days);
+    return QDateTime::currentDateTimeUtc().addDays(-days).toMSecsSinceEpoch();
+}
+
+} // namespace
+
+std::vector<ToolDef> get_stablecoin_peg_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── stablecoin_peg_status ──────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "stablecoin_peg_status";
+        t.description = "Current stablecoin peg state: each monitored coin's USD price on Kraken, Coinbase and "
+                        "Bitstamp, the cross-venue median and its deviation from $1 in bps, plus the composition "
+                        "of the watched Curve pools (share of each coin vs its fair share). Shows breaches in "
+                        "progress and open depeg episodes. refresh=true polls now.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .boolean("refresh", "Poll the venues and pools before answering")
+                             .default_bool(false)
+                             .build();
+        t.default_timeout_ms = 60000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            auto* svc = &StablecoinPegService::instance();
+            const bool refresh = args["refresh"].toBool(false);
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, refresh](auto resolve) {
+                auto answer = [svc, resolve](const QStringList&) { resolve(ToolResult::ok_data(svc->status())); };
+                if (refresh || svc->status()["polled_at"].toString().isEmpty())
+                    svc->refresh(answer);
+                else
+                    answer({});
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── stablecoin_peg_history ─────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "stablecoin_peg_history";
+        t.description = "Stored per-venue USD prices of a monitored stablecoin, oldest first, one row per venue "
+                        "and poll (every minute).";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("coin", "Stablecoin (USDT, USDC, DAI...)")
+                             .required()
+                             .string("venue", "Only this venue")
+                             .enums({"kraken", "coinbase", "bitstamp"})
+                             .integer("days", "Look back this many days")
+                             .default_int(1)
+                             .between(1, 365)
+                             .integer("limit", "Maximum rows (newest kept)")
+                             .default_int(2000)
+                             .between(1, 20000)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString coin = args["coin"].toString().trimmed().toUpper();
+            const QString venue = args["venue"].toString().trimmed();
+            const qint64 from = spt_since(args, 1, 365);
+            const int limit = std::clamp(args["limit"].toInt(2000), 1, 20000);
+            if (coin.isEmpty())
+                return ToolResult::fail("'coin' is required");
+            QJsonArray rows;
+            return spt_on_main(
+                [&]() {
+                    auto r = StablecoinPegRepository::instance().price_history(coin, venue, from, limit);
+                    if (r.is_err())
+                        return QString::fromStdString(r.error());
+                    for (const auto& p : r.value()) {
+                        rows.append(QJsonObject{{"venue", p.venue},
+                                                {"time", QDateTime::fromMSecsSinceEpoch(p.ts).toString(Qt::ISODate)},
+                                                {"price", p.price}});
+                    }
+                    return QString();
+                },
+                [&]() {
+                    return ToolResult::ok_data(QJsonObject{{"coin", coin}, {"rows", rows}, {"count", rows.size()}});
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── stablecoin_pool_history ────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "stablecoin_pool_history";
+        t.description = "Stored composition history of a watched Curve pool: each coin's balance and share of the "
+                        "pool per poll (every 5 minutes), oldest first.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("pool", "Pool address")
+                             .required()
+                             .integer("days", "Look back this many days")
+                             .default_int(7)
+                             .between(1, 365)
+                             .integer("limit", "Maximum rows (newest kept)")
+                             .default_int(5000)
+                             .between(1, 50000)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString pool = args["pool"].toString().trimmed();
+            const qint64 from = spt_since(args, 7, 365);
+            const int limit = std::clamp(args["limit"].toInt(5000), 1, 50000);
+            if (pool.isEmpty())
+                return ToolResult::fail("'pool' is required");
+            QJsonArray rows;
+            return spt_on_main(
+                [&]() {
+                    auto r = StablecoinPegRepository::instance().pool_history(pool, from, limit);
+                    if (r.is_err())
+                        return QString::fromStdString(r.error());
+                    for (const auto& b : r.value()) {
+                        rows.append(QJsonObject{{"time", QDateTime::fromMSecsSinceEpoch(b.ts).toString(Qt::ISODate)},
+                                                {"coin", b.coin},
+                                                {"balance", b.balance},
+                                                {"share_pct", b.share_pct}});
+                    }
+                    return QString();
+                },
+                [&]() {
+                    return ToolResult::ok_data(QJsonObject{{"pool", pool}, {"rows", rows}, {"count", rows.size()}});
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── stablecoin_depeg_events ────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "stablecoin_depeg_events";
+        t.description = "Depeg episodes, newest first: kind price (median deviation from $1 in bps) or pool "
+                        "(coin share above fair share in a Curve pool, points), with when the breach started, was "
+                        "alerted and ended, peak and last value, and the venue prices / pool composition at the "
+                        "peak. Open episodes have ended_at null.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("coin", "Only this stablecoin")
+                             .integer("days", "Look back this many days")
+                             .default_int(90)
+                             .between(1, 3650)
+                             .integer("limit", "Maximum episodes")
+                             .default_int(50)
+                             .between(1, 500)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString coin = args["coin"].toString().trimmed();
+            const qint64 since = spt_since(args, 90, 3650);
+            const int limit = std::clamp(args["limit"].toInt(50), 1, 500);
+            QJsonArray list;
+            return spt_on_main(
+                [&]() {
+                    auto r = StablecoinPegRepository::instance().query_events(coin, since, limit);
+                    if (r.is_err())
+                        return QString::fromStdString(r.error());
+                    for (const auto& e : r.value())
+                        list.append(StablecoinPegService::event_to_json(e));
+                    return QString();
+                },
+                [&]() { return ToolResult::ok_data(QJsonObject{{"events", list}, {"count", list.size()}}); });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── stablecoin_peg_config ──────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "stablecoin_peg_config";
+        t.description = "Get or update the stablecoin peg monitor settings. Only the keys given change: coins, "
+                        "threshold_bps, coin_threshold_bps ({coin: bps}), duration_min, pools (Curve pool "
+                        "addresses), pool_imbalance_pct, pool_duration_min, toast, providers, retention_days. "
+                        "Returns the resulting config.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .array("coins", "Stablecoins to monitor", QJsonObject{{"type", "string"}})
+                             .number("threshold_bps", "Median deviation from $1 that counts as a breach, bps")
+                             .min(1)
+                             .object("coin_threshold_bps", "Per-coin thresholds, {coin: bps}")
+                             .integer("duration_min", "Minutes a price breach must last before alerting")
+                             .between(1, 1440)
+                             .array("pools", "Curve pool addresses to watch", QJsonObject{{"type", "string"}})
+                             .number("pool_imbalance_pct", "Coin share above fair share that counts, points")
+                             .between(1, 100)
+                             .integer("pool_duration_min", "Minutes a pool breach must last before alerting")
+                             .between(1, 1440)
+                             .boolean("toast", "Show in-app toasts")
+                             .boolean("providers", "Also send through configured notification providers")
+                             .integer("retention_days", "Days of price / pool history to keep")
+                             .between(7, 3650)
+                             .build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            QJsonObject result;
+            return spt_on_main(
+                [&]() {
+                    auto& svc = StablecoinPegService::instance();
+                    if (!args.isEmpty()) {
+                        QJsonObject merged = svc.config().to_json();
+                        for (auto it = args.begin(); it != args.end(); ++it)
+                            merged.insert(it.key(), it.value());
+                        svc.set_config(StablecoinPegService::Config::from_json(merged));
+                    }
+                    result = svc.config().to_json();
+                    return QString();
+                },
+                [&]() { return ToolResult::ok_data(result); });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
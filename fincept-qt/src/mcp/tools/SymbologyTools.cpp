@@ -0,0 +1,166 @@
+// SymbologyTools.cpp — Cross-provider identifier resolution, table seeding and manual mapping MCP tools
+
+#include "mcp/tools/SymbologyTools.h"
+
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "mcp/tools/ThreadHelper.h"
+#include "services/markets/SymbologyService.h"
+
+#include <QCoreApplication>
+#include <QDateTime>
+#include <QJsonObject>
+#include <QObject>
+
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+namespace {
+
+using services::SymbologyService;
+
+} // namespace
+
+std::vector<ToolDef> get_symbology_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── symbology_resolve ──────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "symbology_resolve";
+        t.description = "Resolve any identifier — ISIN (INE002A01018), FIGI (BBG000B9XRY4), EXCHANGE:SYMBOL "
+                        "(NSE:RELIANCE, NASDAQ:AAPL), a Yahoo ticker (TCS.NS, BRK-B) or a bare ticker — to the "
+                        "symbol every provider expects: canonical EXCHANGE:SYMBOL, Yahoo, TradingView, OpenFIGI "
+                        "ids, ISIN, and the brsymbol / token of each broker whose master contract is loaded. "
+                        "Unknown identifiers and rows missing a FIGI are looked up on OpenFIGI and stored.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("identifier", "ISIN, FIGI, EXCHANGE:SYMBOL, Yahoo ticker or bare ticker")
+                             .required()
+                             .string("exchange", "Exchange for a bare ticker (NSE, BSE, US, LSE, TSE, HKEX, ...)")
+                             .boolean("online", "Query OpenFIGI when the local table can't answer fully")
+                             .default_bool(true)
+                             .build();
+        t.default_timeout_ms = 60000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            auto* svc = &SymbologyService::instance();
+            const QString identifier = args["identifier"].toString().trimmed();
+            const QString exchange = args["exchange"].toString().trimmed();
+            const bool online = args["online"].toBool(true);
+            AsyncDispatch::callback_to_promise(
+                svc, std::move(ctx), promise, [svc, identifier, exchange, online](auto resolve) {
+                    auto* holder = new QObject(svc);
+                    auto request = std::make_shared<QString>();
+                    QObject::connect(svc, &SymbologyService::resolved, holder,
+                                     [resolve, holder, request](QString id, QJsonObject mapping) {
+                                         if (id != *request)
+                                             return;
+                                         resolve(ToolResult::ok(
+                                             mapping["providers"].toObject()["canonical"].toString(), mapping));
+                                         holder->deleteLater();
+                                     });
+                    QObject::connect(svc, &SymbologyService::error_occurred, holder,
+                                     [resolve, holder, request](QString id, QString msg) {
+                                         if (id != *request)
+                                             return;
+                                         resolve(ToolResult::fail(msg));
+                                         holder->deleteLater();
+                                     });
+                    *request = svc->resolve(identifier, exchange, online);
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── symbology_seed ─────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "symbology_seed";
+        t.description = "Add every equity and index listing from the downloaded broker master contracts to the "
+                        "symbology table (ISINs come from Upstox instrument keys). Runs on its own after each "
+                        "instrument refresh; call it after loading a new broker's instruments.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder().build();
+        t.handler = [](const QJsonObject&) -> ToolResult {
+            Result<int> added = Result<int>::err("not run");
+            int total = 0;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                added = SymbologyService::instance().seed();
+                auto n = SymbologyRepository::instance().count();
+                total = n.is_ok() ? n.value() : 0;
+                signal_done();
+            });
+            if (added.is_err())
+                return ToolResult::fail(QString::fromStdString(added.error()));
+            return ToolResult::ok(QString("Seeded %1 listing(s); %2 in the table").arg(added.value()).arg(total),
+                                  QJsonObject{{"seeded", added.value()}, {"total", total}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── symbology_map ──────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "symbology_map";
+        t.description = "Set a manual mapping for one listing, or remove it with remove=true. Manual rows take "
+                        "precedence: broker seeding and OpenFIGI lookups never overwrite them. Only the fields "
+                        "passed are changed on an existing row.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("exchange", "Canonical exchange key (NSE, BSE, US, LSE, ...)")
+                             .required()
+                             .string("symbol", "Ticker on that exchange")
+                             .required()
+                             .string("listing", "Primary venue for a country key (NASDAQ, NYSE, ...)")
+                             .string("name", "Security name")
+                             .string("isin", "ISIN")
+                             .string("figi", "Listing-level FIGI")
+                             .string("composite_figi", "Composite FIGI")
+                             .string("share_class_figi", "Share-class FIGI")
+                             .string("security_type", "e.g. Common Stock, ETP, Index")
+                             .boolean("remove", "Delete the row instead")
+                             .default_bool(false)
+                             .build();
+        t.is_destructive = true;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            SymbolMapping m;
+            m.exchange = SymbologyService::canonical_exchange(args["exchange"].toString());
+            m.symbol = args["symbol"].toString().trimmed().toUpper();
+            if (m.exchange.isEmpty() || m.symbol.isEmpty())
+                return ToolResult::fail("'exchange' and 'symbol' are required");
+            m.listing = args["listing"].toString().trimmed();
+            m.name = args["name"].toString().trimmed();
+            m.isin = args["isin"].toString().trimmed();
+            m.figi = args["figi"].toString().trimmed();
+            m.composite_figi = args["composite_figi"].toString().trimmed();
+            m.share_class_figi = args["share_class_figi"].toString().trimmed();
+            m.security_type = args["security_type"].toString().trimmed();
+            m.source = "manual";
+            m.updated_at = QDateTime::currentMSecsSinceEpoch();
+            const bool remove = args["remove"].toBool(false);
+
+            Result<void> r = Result<void>::ok();
+            QJsonObject mapping;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto& repo = SymbologyRepository::instance();
+                r = remove ? repo.remove(m.exchange, m.symbol) : repo.upsert(m);
+                if (r.is_ok() && !remove)
+                    if (auto saved = repo.find(m.exchange, m.symbol))
+                        mapping = SymbologyService::instance().describe(*saved);
+                signal_done();
+            });
+            if (r.is_err())
+                return ToolResult::fail(QString::fromStdString(r.error()));
+            const QString key = m.exchange + ':' + m.symbol;
+            if (remove)
+                return ToolResult::ok("Removed mapping for " + key);
+            return ToolResult::ok("Mapped " + key, mapping);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
@@ -1,11 +1,14 @@
-// SystemTools.cpp — Auth status, cache, app info (Qt port)
+// SystemTools.cpp — Auth status, cache, app info, health, diagnostics and usage metrics (Qt port)
 
 #include "mcp/tools/SystemTools.h"
 
 #include "auth/AuthManager.h"
+#include "core/DependencyDoctor.h"
 #include "core/HealthMonitor.h"
 #include "core/logging/Logger.h"
+#include "core/telemetry/UsageMetrics.h"
 #include "mcp/McpProvider.h"
+#include "mcp/ToolSchemaBuilder.h"
 #include "python/PythonRunner.h"
 #include "storage/cache/CacheManager.h"
 
@@ -108,6 +111,90 @@ std::vector<ToolDef> get_system_tools() {
         tools.push_back(std::move(t));
     }
 
+    // ── run_diagnostics ────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "run_diagnostics";
+        t.description = "Diagnose the runtime environment: UV / Python paths and versions, venvs, required "
+                        "Python packages with installed versions, writable app directories and disk space, "
+                        "SQLite integrity and reachability of key APIs. Returns a structured report plus "
+                        "'report_text' (Markdown) for pasting into a support issue. Takes up to a minute.";
+        t.category = "system";
+        t.input_schema = ToolSchemaBuilder()
+                             .boolean("packages", "Compare venv packages against the requirements (slowest check)")
+                             .default_bool(true)
+                             .boolean("network", "Probe API reachability")
+                             .default_bool(true)
+                             .integer("network_timeout_ms", "Per-request network timeout")
+                             .default_int(5000)
+                             .between(500, 30000)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            DiagnosticsOptions opts;
+            opts.packages = args["packages"].toBool(true);
+            opts.network = args["network"].toBool(true);
+            opts.network_timeout_ms = args["network_timeout_ms"].toInt(5000);
+            const auto report = DependencyDoctor::instance().run(opts);
+            QJsonObject data = report.to_json();
+            data["report_text"] = report.to_text();
+            return ToolResult::ok(QString("Diagnostics: %1 ok, %2 warn, %3 fail")
+                                      .arg(report.count(DiagnosticsReport::Level::Ok))
+                                      .arg(report.count(DiagnosticsReport::Level::Warn))
+                                      .arg(report.count(DiagnosticsReport::Level::Fail)),
+                                  data);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── usage_metrics ──────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "usage_metrics";
+        t.description = "Local usage metrics and profiler (opt-in, never uploaded): command and tool latencies, "
+                        "data-source refresh latency, cache hit rates and websocket throughput, aggregated "
+                        "over the last N hours. Use to find what is making the workspace slow — "
+                        "'slowest_source' names the worst data source. Includes 'summary_text' (Markdown).";
+        t.category = "system";
+        t.input_schema = ToolSchemaBuilder()
+                             .integer("hours", "Look-back window in hours")
+                             .default_int(24)
+                             .between(1, telemetry::UsageMetrics::kRetentionDays * 24)
+                             .string("kind", "Narrow the latency section to one kind")
+                             .enums({"action", "tool", "datasource"})
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            auto& metrics = telemetry::UsageMetrics::instance();
+            QJsonObject data = metrics.summary(args["hours"].toInt(24), args["kind"].toString());
+            data["summary_text"] = telemetry::UsageMetrics::summary_text(data);
+            if (!metrics.enabled())
+                return ToolResult::ok("Usage metrics are off — enable them with usage_metrics_control", data);
+            return ToolResult::ok_data(data);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── usage_metrics_control ──────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "usage_metrics_control";
+        t.description = "Turn local usage metrics recording on or off, or delete all recorded samples.";
+        t.category = "system";
+        t.is_destructive = true; // reset deletes history
+        t.input_schema =
+            ToolSchemaBuilder().string("action", "What to do").enums({"enable", "disable", "reset"}).required().build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            auto& metrics = telemetry::UsageMetrics::instance();
+            const QString action = args["action"].toString();
+            const auto r = action == "reset" ? metrics.reset() : metrics.set_enabled(action == "enable");
+            if (r.is_err())
+                return ToolResult::fail(QString::fromStdString(r.error()));
+            return ToolResult::ok(action == "reset" ? "Usage metrics cleared"
+                                                    : QString("Usage metrics %1d").arg(action),
+                                  QJsonObject{{"enabled", metrics.enabled()}});
+        };
+        tools.push_back(std::move(t));
+    }
+
     return tools;
 }
 
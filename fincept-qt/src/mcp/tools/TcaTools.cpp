@@ -0,0 +1,144 @@
+// TcaTools.cpp — Transaction cost analysis MCP tools (fill slippage reports)
+
+#include "mcp/tools/TcaTools.h"
+
+#include "core/logging/Logger.h"
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "services/report_builder/ReportBuilderService.h"
+#include "services/tca/TcaService.h"
+
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+
+#include <functional>
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+static constexpr const char* TAG = "TcaTools";
+
+namespace {
+
+using services::tca::TcaQuery;
+using services::tca::TcaService;
+
+ToolSchemaBuilder tca_filter_schema() {
+    return ToolSchemaBuilder()
+        .integer("days", "Fills from the last N days")
+        .default_int(30)
+        .between(1, 730)
+        .array("sources", "Fill sources to include (empty = all): paper, live, algo, exec",
+               QJsonObject{{"type", "string"}, {"enum", QJsonArray{"paper", "live", "algo", "exec"}}})
+        .string("account_id", "Only fills of this broker account")
+        .string("broker", "Only fills through this broker id ('paper' for paper portfolios)")
+        .string("strategy", "Only fills of strategies whose name contains this")
+        .string("symbol", "Only fills of this symbol")
+        .integer("vwap_window_minutes", "VWAP window around fills that completed within one bar")
+        .default_int(30)
+        .between(1, 390);
+}
+
+// Runs a report and hands it to `done` on the main thread.
+void tca_run(const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise,
+             std::function<ToolResult(const QJsonObject&)> done) {
+    auto* svc = &TcaService::instance();
+    const TcaQuery query = TcaQuery::from_json(args);
+    AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, query, done](auto resolve) {
+        auto* holder = new QObject(svc);
+        auto request = std::make_shared<QString>();
+        QObject::connect(svc, &TcaService::report_ready, holder,
+                         [resolve, holder, request, done](QString id, QJsonObject report) {
+                             if (id != *request)
+                                 return;
+                             resolve(done(report));
+                             holder->deleteLater();
+                         });
+        QObject::connect(svc, &TcaService::error_occurred, holder,
+                         [resolve, holder, request](QString id, QString msg) {
+                             if (id != *request)
+                                 return;
+                             resolve(ToolResult::fail(msg));
+                             holder->deleteLater();
+                         });
+        *request = svc->run(query);
+    });
+}
+
+} // namespace
+
+std::vector<ToolDef> get_tca_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── tca_report ─────────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "tca_report";
+        t.description = "Transaction cost analysis of executed fills (paper trades, live order books, algo "
+                        "deployments, execution algos): each fill against its arrival price, interval VWAP and "
+                        "session close from intraday bars, with notional-weighted slippage in bps and currency "
+                        "aggregated by strategy, broker and symbol. Positive slippage is a cost.";
+        t.category = "live-trading";
+        t.input_schema = tca_filter_schema()
+                             .integer("max_fills", "Per-fill rows returned, newest first")
+                             .default_int(200)
+                             .between(0, 5000)
+                             .build();
+        t.default_timeout_ms = 180000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            QJsonObject a = args;
+            if (!a.contains("max_fills"))
+                a["max_fills"] = 200;
+            tca_run(a, std::move(ctx), promise, [](const QJsonObject& report) {
+                const QJsonObject s = report["summary"].toObject();
+                return ToolResult::ok(QString("%1 fills, %2 benchmarked; %3 bps vs arrival")
+                                          .arg(report["fills_total"].toInt())
+                                          .arg(report["fills_benchmarked"].toInt())
+                                          .arg(s["arrival_bps"].toDouble(), 0, 'f', 1),
+                                      report);
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── tca_add_to_report ──────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "tca_add_to_report";
+        t.description = "Run a transaction cost analysis (same filters as tca_report) and append it to the open "
+                        "report in the Report Builder: summary, execution-cost stats and slippage tables by "
+                        "strategy, broker and symbol. Set use_last to reuse the latest tca_report result.";
+        t.category = "report-builder";
+        t.input_schema = tca_filter_schema().boolean("use_last", "Append the last report without re-running").build();
+        t.default_timeout_ms = 180000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            auto append = [](const QJsonObject& report) {
+                auto& builder = services::ReportBuilderService::instance();
+                QJsonArray ids;
+                for (const auto& c : TcaService::report_components(report))
+                    ids.append(builder.add_component(c));
+                LOG_INFO(TAG, QString("Appended TCA report (%1 components)").arg(ids.size()));
+                return ToolResult::ok(QString("Added %1 TCA components to the report").arg(ids.size()),
+                                      QJsonObject{{"component_ids", ids},
+                                                  {"fills_total", report["fills_total"]},
+                                                  {"summary", report["summary"]}});
+            };
+            auto* svc = &TcaService::instance();
+            if (args["use_last"].toBool()) {
+                AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, append](auto resolve) {
+                    const QJsonObject last = svc->last_report();
+                    resolve(last.isEmpty() ? ToolResult::fail("No TCA report yet; run tca_report first")
+                                           : append(last));
+                });
+                return;
+            }
+            tca_run(args, std::move(ctx), promise, append);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
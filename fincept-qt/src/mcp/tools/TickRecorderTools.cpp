@@ -0,0 +1,194 @@
+// TickRecorderTools.cpp — Live tick recording config / status, recorded-stream reads and market_ticks import MCP tools
+
+#include "mcp/tools/TickRecorderTools.h"
+
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "mcp/tools/ThreadHelper.h"
+#include "services/markets/TickRecorder.h"
+
+#include <QCoreApplication>
+#include <QDateTime>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QTimeZone>
+
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+namespace {
+
+using services::TickRecorder;
+
+// "yyyy-MM-dd" (start or end of day) or ISO-8601; 0 for empty / unparseable.
+qint64 tr_parse_ms(const QString& s, bool end_of_day) {
+    if (s.isEmpty())
+        return 0;
+    const QDate d = QDate::fromString(s, Qt::ISODate);
+    if (d.isValid())
+        return QDateTime(end_of_day ? d.addDays(1) : d, QTime(0, 0), QTimeZone::utc()).toMSecsSinceEpoch() -
+               (end_of_day ? 1 : 0);
+    QDateTime dt = QDateTime::fromString(s, Qt::ISODate);
+    if (!dt.isValid())
+        return 0;
+    if (dt.timeSpec() == Qt::LocalTime)
+        dt.setTimeZone(QTimeZone::utc());
+    return dt.toMSecsSinceEpoch();
+}
+
+} // namespace
+
+std::vector<ToolDef> get_tick_recorder_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── tick_recorder_config ───────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "tick_recorder_config";
+        t.description = "Turn live tick recording on or off and choose what it keeps. Topics are DataHub patterns "
+                        "with * wildcards: broker quotes (broker:*:quote:*), crypto trades / tickers "
+                        "(ws:*:trades:*, ws:*:ticker:*) or Yahoo quotes (market:quote:*). Recordings go to "
+                        "compressed, rotating files and/or the market_ticks table used by tick backtests. Only "
+                        "the fields passed change; call with no arguments to read the current settings.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .boolean("enabled", "Record")
+                             .array("topics", "Topic patterns to record", QJsonObject{{"type", "string"}})
+                             .string("sink", "Where recordings go")
+                             .enums({"file", "sqlite", "both"})
+                             .integer("rotate_mb", "Start a new file past this size")
+                             .between(1, 4096)
+                             .integer("retention_days", "Delete recording files older than this")
+                             .between(1, 3650)
+                             .integer("flush_ms", "Buffer flush interval")
+                             .between(200, 60000)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            QString error;
+            QJsonObject status;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto& rec = TickRecorder::instance();
+                if (!args.isEmpty()) {
+                    QJsonObject merged = rec.config().to_json();
+                    for (auto it = args.begin(); it != args.end(); ++it)
+                        if (merged.contains(it.key()))
+                            merged[it.key()] = it.value();
+                    auto r = rec.set_config(TickRecorder::Config::from_json(merged));
+                    if (r.is_err())
+                        error = QString::fromStdString(r.error());
+                }
+                status = rec.status();
+                signal_done();
+            });
+            if (!error.isEmpty())
+                return ToolResult::fail(error);
+            return ToolResult::ok(status["recording"].toBool() ? "Tick recorder on" : "Tick recorder off", status);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── tick_recorder_status ───────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "tick_recorder_status";
+        t.description = "Tick recorder state: whether it is recording, the current file, messages and bytes "
+                        "written this session, and every recording file on disk.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder().build();
+        t.handler = [](const QJsonObject&) -> ToolResult {
+            QJsonObject status;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto& rec = TickRecorder::instance();
+                status = rec.status();
+                status["files"] = rec.files();
+                signal_done();
+            });
+            return ToolResult::ok(QString("%1, %2 file(s)")
+                                      .arg(status["recording"].toBool() ? "Recording" : "Not recording")
+                                      .arg(status["files"].toArray().size()),
+                                  status);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── tick_recording_read ────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "tick_recording_read";
+        t.description = "Read recorded messages back in arrival order: t (receive time, epoch ms), topic, kind "
+                        "(quote / trade / ticker), sym, ex, px and, when present, sz, bid, ask, bsz, asz, side, ts "
+                        "(the source's timestamp).";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("from", "Start, yyyy-MM-dd or ISO-8601 (default: everything)")
+                             .string("to", "End, yyyy-MM-dd or ISO-8601")
+                             .string("topic", "Topic pattern with * wildcards")
+                             .integer("limit", "Max messages")
+                             .default_int(500)
+                             .between(1, 20000)
+                             .build();
+        t.default_timeout_ms = 60000;
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const qint64 from_ms = tr_parse_ms(args["from"].toString().trimmed(), false);
+            const qint64 to_ms = tr_parse_ms(args["to"].toString().trimmed(), true);
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                TickRecorder::instance().flush();
+                signal_done();
+            });
+            QJsonArray rows;
+            for (const auto& r : TickRecorder::read(from_ms, to_ms, args["topic"].toString().trimmed(),
+                                                    args["limit"].toInt(500)))
+                rows.append(r.to_json());
+            return ToolResult::ok(QString("%1 recorded message(s)").arg(rows.size()),
+                                  QJsonObject{{"messages", rows}, {"count", rows.size()}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── tick_recording_import ──────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "tick_recording_import";
+        t.description = "Copy recorded prints for a range into the market_ticks table so a tick-level backtest "
+                        "(algo_tick_backtest) can replay them. One series per symbol and exchange; re-importing "
+                        "the same range is idempotent.";
+        t.category = "markets";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("from", "Start, yyyy-MM-dd or ISO-8601")
+                             .required()
+                             .string("to", "End, yyyy-MM-dd or ISO-8601 (default now)")
+                             .string("symbol", "Only this symbol")
+                             .build();
+        t.is_destructive = true;
+        t.default_timeout_ms = 300000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const qint64 from_ms = tr_parse_ms(args["from"].toString().trimmed(), false);
+            const qint64 to_ms = tr_parse_ms(args["to"].toString().trimmed(), true);
+            const QString symbol = args["symbol"].toString().trimmed();
+            if (from_ms <= 0) {
+                promise->addResult(ToolResult::fail("'from' must be yyyy-MM-dd or ISO-8601"));
+                promise->finish();
+                return;
+            }
+            auto* rec = &TickRecorder::instance();
+            AsyncDispatch::callback_to_promise(
+                rec, std::move(ctx), promise, [rec, from_ms, to_ms, symbol](auto resolve) {
+                    rec->import(from_ms, to_ms, symbol, [resolve](QJsonObject summary, QString error) {
+                        if (!error.isEmpty())
+                            resolve(ToolResult::fail(error));
+                        else
+                            resolve(ToolResult::ok(QString("Imported %1 tick(s) into %2 series")
+                                                       .arg(summary["records"].toInt())
+                                                       .arg(summary["series"].toArray().size()),
+                                                   summary));
+                    });
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
@@ -0,0 +1,325 @@
+// TradeJournalTools.cpp — Trade journal MCP tools: round trips from the fills ledger with notes, setups,
+// tags and chart snapshots, win rate / expectancy / MAE-MFE statistics and report export
+
+#include "mcp/tools/TradeJournalTools.h"
+
+#include "core/logging/Logger.h"
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "mcp/tools/ThreadHelper.h"
+#include "services/journal/TradeJournalService.h"
+#include "services/report_builder/ReportBuilderService.h"
+
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+
+#include <algorithm>
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+static constexpr const char* TAG = "TradeJournalTools";
+
+namespace {
+
+using services::journal::JournalAnnotation;
+using services::journal::JournalQuery;
+using services::journal::TradeJournalEngine;
+using services::journal::TradeJournalService;
+
+const QStringList kGroupBy{"tag", "setup", "strategy", "symbol", "account", "direction", "rating"};
+
+ToolSchemaBuilder journal_filter_schema() {
+    return ToolSchemaBuilder()
+        .integer("days", "Trades entered in the last N days")
+        .default_int(90)
+        .between(1, 3650)
+        .string("account_id", "Only trades of this broker account")
+        .string("symbol", "Only trades of this symbol")
+        .string("strategy", "Only trades of strategies whose name contains this ('manual' for hand-placed orders)")
+        .string("setup", "Only trades recorded with this setup")
+        .string("tag", "Only trades carrying this tag")
+        .string("status", "open or closed (default both)")
+        .enums({"open", "closed"});
+}
+
+QStringList string_list(const QJsonValue& v) {
+    QStringList out;
+    for (const auto& e : v.toArray()) {
+        const QString s = e.toString().trimmed();
+        if (!s.isEmpty())
+            out.append(s);
+    }
+    return out;
+}
+
+QJsonArray trades_json(const QVector<JournalTrade>& trades) {
+    QJsonArray out;
+    for (const auto& t : trades)
+        out.append(TradeJournalEngine::trade_to_json(t));
+    return out;
+}
+
+} // namespace
+
+std::vector<ToolDef> get_trade_journal_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── journal_sync ───────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "journal_sync";
+        t.description = "Pull recent fills from the fills ledger (paper trades, live order books, algo deployments, "
+                        "execution algos) into the trade journal and rebuild its round trips (flat to flat per "
+                        "account and symbol) with P&L and MAE / MFE from intraday bars. Notes, setups, tags and "
+                        "snapshots already recorded are kept. Runs hourly on its own; call before reading "
+                        "today's trades.";
+        t.category = "live-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .integer("days", "Ledger window to pull; older journaled fills are kept")
+                             .default_int(7)
+                             .between(1, 730)
+                             .build();
+        t.default_timeout_ms = 180000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            auto* svc = &TradeJournalService::instance();
+            const int days = std::clamp(args["days"].toInt(7), 1, 730);
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, days](auto resolve) {
+                auto* holder = new QObject(svc);
+                auto request = std::make_shared<QString>();
+                QObject::connect(svc, &TradeJournalService::synced, holder,
+                                 [resolve, holder, request](QString id, QJsonObject summary) {
+                                     if (id != *request)
+                                         return;
+                                     resolve(ToolResult::ok(QString("Journal holds %1 trades (%2 open)")
+                                                                .arg(summary["trades"].toInt())
+                                                                .arg(summary["open_trades"].toInt()),
+                                                            summary));
+                                     holder->deleteLater();
+                                 });
+                QObject::connect(svc, &TradeJournalService::error_occurred, holder,
+                                 [resolve, holder, request](QString id, QString msg) {
+                                     if (id != *request)
+                                         return;
+                                     resolve(ToolResult::fail(msg));
+                                     holder->deleteLater();
+                                 });
+                *request = svc->sync(days);
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── journal_list_trades ────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "journal_list_trades";
+        t.description = "List trade-journal round trips, newest first: side, size, average entry / exit, P&L net of "
+                        "fees, return, MAE / MFE (percent of entry), the fills behind them, and the recorded "
+                        "setup, notes, rating, tags and snapshot files. Pass trade_id for a single trade.";
+        t.category = "live-trading";
+        t.input_schema = journal_filter_schema()
+                             .string("trade_id", "Return just this trade")
+                             .integer("limit", "Trades to return")
+                             .default_int(50)
+                             .between(1, 1000)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            auto& svc = TradeJournalService::instance();
+            const QString id = args["trade_id"].toString().trimmed();
+            if (!id.isEmpty()) {
+                std::optional<JournalTrade> trade;
+                detail::run_on_target_thread_sync(&svc, [&]() { trade = svc.trade(id); });
+                if (!trade)
+                    return ToolResult::fail("Unknown journal trade: " + id);
+                return ToolResult::ok_data(TradeJournalEngine::trade_to_json(*trade));
+            }
+            QJsonObject a = args;
+            if (!a.contains("limit"))
+                a["limit"] = 50;
+            const JournalQuery query = JournalQuery::from_json(a);
+            Result<QVector<JournalTrade>> r = Result<QVector<JournalTrade>>::err("not run");
+            detail::run_on_target_thread_sync(&svc, [&]() { r = svc.trades(query); });
+            if (r.is_err())
+                return ToolResult::fail(QString::fromStdString(r.error()));
+            return ToolResult::ok(QString("%1 trades").arg(r.value().size()),
+                                  QJsonObject{{"trades", trades_json(r.value())}, {"query", query.to_json()}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── journal_annotate_trade ─────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "journal_annotate_trade";
+        t.description = "Record a journal trade's setup, notes, 0-5 rating and tags. Only the fields given change; "
+                        "append_notes adds a timestamped line instead of replacing the notes. Tags are "
+                        "lower-cased; use tags to replace them all or add_tags / remove_tags to edit.";
+        t.category = "live-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("trade_id", "Journal trade id (journal_list_trades)")
+                             .required()
+                             .string("setup", "Setup the trade was taken on, e.g. 'opening range breakout'")
+                             .string("notes", "Notes on the trade")
+                             .boolean("append_notes", "Append notes as a dated line")
+                             .default_bool(false)
+                             .integer("rating", "Execution rating, 1-5 (0 clears it)")
+                             .between(0, 5)
+                             .array("tags", "Replace all tags with these", QJsonObject{{"type", "string"}})
+                             .array("add_tags", "Tags to add", QJsonObject{{"type", "string"}})
+                             .array("remove_tags", "Tags to remove", QJsonObject{{"type", "string"}})
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            JournalAnnotation changes;
+            if (args.contains("setup"))
+                changes.setup = args["setup"].toString();
+            if (args.contains("notes"))
+                changes.notes = args["notes"].toString();
+            changes.append_notes = args["append_notes"].toBool();
+            if (args.contains("rating"))
+                changes.rating = args["rating"].toInt();
+            if (args.contains("tags"))
+                changes.tags = string_list(args["tags"]);
+            changes.add_tags = string_list(args["add_tags"]);
+            changes.remove_tags = string_list(args["remove_tags"]);
+
+            auto& svc = TradeJournalService::instance();
+            Result<JournalTrade> r = Result<JournalTrade>::err("not run");
+            detail::run_on_target_thread_sync(&svc, [&]() { r = svc.annotate(args["trade_id"].toString(), changes); });
+            if (r.is_err())
+                return ToolResult::fail(QString::fromStdString(r.error()));
+            return ToolResult::ok(QString("Annotated %1 trade %2").arg(r.value().symbol, r.value().id),
+                                  TradeJournalEngine::trade_to_json(r.value()));
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── journal_attach_snapshot ────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "journal_attach_snapshot";
+        t.description = "Attach a chart snapshot (or any file) to a journal trade by absolute path, or remove an "
+                        "attachment with remove_id. The file stays where it is; images are placed in reports "
+                        "exported with journal_add_to_report.";
+        t.category = "live-trading";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("trade_id", "Journal trade id")
+                             .string("path", "Absolute path of the image file")
+                             .string("caption", "Caption shown under the image")
+                             .string("remove_id", "Attachment id to remove instead")
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            auto& svc = TradeJournalService::instance();
+            const QString remove_id = args["remove_id"].toString().trimmed();
+            if (!remove_id.isEmpty()) {
+                Result<void> r = Result<void>::err("not run");
+                detail::run_on_target_thread_sync(&svc, [&]() { r = svc.detach(remove_id); });
+                if (r.is_err())
+                    return ToolResult::fail(QString::fromStdString(r.error()));
+                return ToolResult::ok("Attachment removed", QJsonObject{{"removed", remove_id}});
+            }
+            if (args["trade_id"].toString().trimmed().isEmpty() || args["path"].toString().trimmed().isEmpty())
+                return ToolResult::fail("trade_id and path are required");
+            Result<JournalAttachment> r = Result<JournalAttachment>::err("not run");
+            detail::run_on_target_thread_sync(&svc, [&]() {
+                r = svc.attach(args["trade_id"].toString(), args["path"].toString(), args["caption"].toString());
+            });
+            if (r.is_err())
+                return ToolResult::fail(QString::fromStdString(r.error()));
+            const JournalAttachment& a = r.value();
+            LOG_INFO(TAG, QString("Attached %1 to trade %2").arg(a.path, a.trade_id));
+            return ToolResult::ok("Snapshot attached", QJsonObject{{"id", a.id},
+                                                                   {"trade_id", a.trade_id},
+                                                                   {"path", a.path},
+                                                                   {"caption", a.caption}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── journal_stats ──────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "journal_stats";
+        t.description = "Trade-journal statistics over closed trades, overall and grouped by tag, setup, strategy, "
+                        "symbol, account, direction or rating: win rate, average win / loss, expectancy per trade "
+                        "(currency and percent), profit factor, total P&L, best / worst, average hold, average "
+                        "MAE / MFE and MFE capture (share of the favourable move realized by winners).";
+        t.category = "live-trading";
+        t.input_schema = journal_filter_schema()
+                             .string("group_by", "Grouping of the breakdown")
+                             .enums(kGroupBy)
+                             .default_str("tag")
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            auto& svc = TradeJournalService::instance();
+            const JournalQuery query = JournalQuery::from_json(args);
+            const QString group_by = args["group_by"].toString("tag").trimmed().toLower();
+            Result<QJsonObject> r = Result<QJsonObject>::err("not run");
+            detail::run_on_target_thread_sync(&svc, [&]() { r = svc.stats(query, group_by); });
+            if (r.is_err())
+                return ToolResult::fail(QString::fromStdString(r.error()));
+            const QJsonObject s = r.value()["summary"].toObject();
+            return ToolResult::ok(QString("%1 closed trades, win rate %2%, expectancy %3")
+                                      .arg(s["trades"].toInt())
+                                      .arg(s["win_rate"].toDouble() * 100.0, 0, 'f', 1)
+                                      .arg(s["expectancy"].toDouble(), 0, 'f', 2),
+                                  r.value());
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── journal_add_to_report ──────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "journal_add_to_report";
+        t.description = "Append the trade journal to the open report in the Report Builder: performance stats, "
+                        "tables by tag and by setup, the trades with their notes, and attached chart snapshots. "
+                        "Takes the same filters as journal_stats.";
+        t.category = "report-builder";
+        t.input_schema = journal_filter_schema()
+                             .integer("max_trades", "Trades listed in the table, newest first")
+                             .default_int(50)
+                             .between(0, 500)
+                             .integer("max_images", "Snapshots placed in the report")
+                             .default_int(12)
+                             .between(0, 100)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            auto& svc = TradeJournalService::instance();
+            JournalQuery query = JournalQuery::from_json(args);
+            query.limit = std::clamp(args["max_trades"].toInt(50), 1, 500);
+            const int max_trades = std::clamp(args["max_trades"].toInt(50), 0, 500);
+            const int max_images = std::clamp(args["max_images"].toInt(12), 0, 100);
+
+            QString error;
+            QJsonArray ids;
+            QJsonObject summary;
+            detail::run_on_target_thread_sync(&svc, [&]() {
+                auto by_tag = svc.stats(query, QStringLiteral("tag"));
+                auto by_setup = svc.stats(query, QStringLiteral("setup"));
+                auto trades = svc.trades(query);
+                if (by_tag.is_err() || by_setup.is_err() || trades.is_err()) {
+                    error = "Reading the journal failed";
+                    return;
+                }
+                const QVector<JournalTrade> listed = trades.value().mid(0, max_trades);
+                auto& builder = services::ReportBuilderService::instance();
+                for (const auto& c :
+                     TradeJournalService::report_components(by_tag.value(), by_setup.value(), listed, max_images))
+                    ids.append(builder.add_component(c));
+                summary = by_tag.value()["summary"].toObject();
+            });
+            if (!error.isEmpty())
+                return ToolResult::fail(error);
+            LOG_INFO(TAG, QString("Appended trade journal (%1 components)").arg(ids.size()));
+            return ToolResult::ok(QString("Added %1 journal components to the report").arg(ids.size()),
+                                  QJsonObject{{"component_ids", ids}, {"summary", summary}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
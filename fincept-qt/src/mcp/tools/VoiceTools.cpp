@@ -0,0 +1,103 @@
+// VoiceTools.cpp — Voice command MCP tools.
+// transcribe_audio wraps SpeechService::transcribe_file (local Whisper) and
+// reports the parsed intent without acting on it; run_voice_command parses
+// and executes on the UI thread, exactly as a spoken command would.
+
+#include "mcp/tools/VoiceTools.h"
+
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "services/stt/SpeechService.h"
+#include "ui/command/VoiceCommand.h"
+
+#include <QCoreApplication>
+#include <QFileInfo>
+#include <QJsonObject>
+
+namespace fincept::mcp::tools {
+
+namespace {
+
+// First use downloads the Whisper model (~140 MB for base.en).
+constexpr int kTranscribeTimeoutMs = 300000;
+
+} // namespace
+
+std::vector<ToolDef> get_voice_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── transcribe_audio ─────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "transcribe_audio";
+        t.description = "Transcribe a WAV recording on this machine with local Whisper (offline). Returns the text "
+                        "and, when the speech is a terminal command such as 'quote AAPL' or 'run screener oversold "
+                        "tech', the parsed intent. Nothing is executed — pass the text to run_voice_command for that.";
+        t.category = "voice";
+        t.default_timeout_ms = kTranscribeTimeoutMs;
+        t.input_schema = ToolSchemaBuilder()
+                             .string("wav_path", "Absolute path to a PCM .wav file")
+                             .required()
+                             .length(1, 1024)
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString path = args["wav_path"].toString().trimmed();
+            auto* svc = &services::SpeechService::instance();
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, path](auto resolve) {
+                if (!QFileInfo(path).isAbsolute()) {
+                    resolve(ToolResult::fail("wav_path must be an absolute path"));
+                    return;
+                }
+                svc->transcribe_file(path, [resolve](Result<QString> r) {
+                    if (r.is_err()) {
+                        resolve(ToolResult::fail(QString::fromStdString(r.error())));
+                        return;
+                    }
+                    const auto intent = ui::VoiceCommand::parse(r.value());
+                    QJsonObject data{{"text", r.value()}, {"intent", intent.to_json()}};
+                    resolve(ToolResult::ok(r.value(), data));
+                });
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── run_voice_command ────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "run_voice_command";
+        t.description = "Parse a spoken-style command and run it in the terminal: 'quote AAPL' opens the symbol in "
+                        "Equity Research, 'run screener oversold tech' opens the screener with that preset, 'open "
+                        "news' switches screens, and command-bar verbs ('layout switch Morning') invoke the action. "
+                        "Fails without side effects when the text is not a command.";
+        t.category = "voice";
+        t.is_destructive = true;
+        t.input_schema = ToolSchemaBuilder()
+                             .string("text", "Command text, e.g. 'quote AAPL'")
+                             .required()
+                             .length(1, 500)
+                             .build();
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            const QString text = args["text"].toString().trimmed();
+            auto* app = QCoreApplication::instance();
+            AsyncDispatch::callback_to_promise(app, std::move(ctx), promise, [text](auto resolve) {
+                const auto intent = ui::VoiceCommand::parse(text);
+                if (!intent.is_command()) {
+                    resolve(ToolResult::fail("Not a recognised command: " + text));
+                    return;
+                }
+                auto r = ui::VoiceCommand::execute(intent);
+                if (r.is_err()) {
+                    resolve(ToolResult::fail(QString::fromStdString(r.error())));
+                    return;
+                }
+                resolve(ToolResult::ok(r.value(), intent.to_json()));
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
@@ -0,0 +1,12 @@
+#pragma once
+// VoiceTools.h — Voice command MCP tools
+// Transcribe a WAV file with local Whisper and run spoken commands
+// ("quote AAPL", "run screener oversold tech") through ui::VoiceCommand.
+
+#include "mcp/McpTypes.h"
+
+#include <vector>
+
+namespace fincept::mcp::tools {
+std::vector<ToolDef> get_voice_tools();
+} // namespace fincept::mcp::tools
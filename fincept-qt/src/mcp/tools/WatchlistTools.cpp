@@ -14,12 +14,19 @@
 
 #include "core/events/EventBus.h"
 #include "core/logging/Logger.h"
+#include "mcp/AsyncDispatch.h"
 #include "mcp/tools/ThreadHelper.h"
+#include "services/watchlist/WatchlistColumnService.h"
+#include "services/watchlist/WatchlistExpression.h"
 #include "storage/repositories/WatchlistRepository.h"
 
 #include <QCoreApplication>
+#include <QJsonArray>
 #include <QVariantMap>
 
+#include <algorithm>
+#include <memory>
+
 namespace fincept::mcp::tools {
 
 static constexpr const char* TAG = "WatchlistTools";
@@ -242,6 +249,226 @@ std::vector<ToolDef> get_watchlist_tools() {
         tools.push_back(std::move(t));
     }
 
+    // ── update_watchlist ────────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "update_watchlist";
+        t.description = "Rename a watchlist or change its description or color. Omitted fields are kept.";
+        t.category = "watchlist";
+        t.input_schema.properties =
+            QJsonObject{{"watchlist_id", QJsonObject{{"type", "string"}, {"description", "Watchlist ID"}}},
+                        {"name", QJsonObject{{"type", "string"}, {"description", "New name"}}},
+                        {"description", QJsonObject{{"type", "string"}, {"description", "New description"}}},
+                        {"color", QJsonObject{{"type", "string"}, {"description", "New hex color"}}}};
+        t.input_schema.required = {"watchlist_id"};
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            QString id = args["watchlist_id"].toString().trimmed();
+            if (id.isEmpty())
+                return ToolResult::fail("Missing 'watchlist_id'");
+
+            QString error;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto& repo = WatchlistRepository::instance();
+                auto wl = repo.get(id);
+                if (wl.is_err()) {
+                    error = "Watchlist not found: " + id;
+                    signal_done();
+                    return;
+                }
+                Watchlist w = wl.value();
+                if (args.contains("name") && !args["name"].toString().trimmed().isEmpty())
+                    w.name = args["name"].toString().trimmed();
+                if (args.contains("description"))
+                    w.description = args["description"].toString();
+                if (args.contains("color") && !args["color"].toString().isEmpty())
+                    w.color = args["color"].toString();
+                auto r = repo.update(w);
+                if (r.is_err())
+                    error = "Failed to update watchlist: " + QString::fromStdString(r.error());
+                signal_done();
+            });
+            if (!error.isEmpty())
+                return ToolResult::fail(error);
+
+            EventBus::instance().publish("watchlist.updated", QVariantMap{{"watchlist_id", id}, {"action", "edit"}});
+            return ToolResult::ok("Watchlist updated", QJsonObject{{"id", id}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── reorder_watchlist ───────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "reorder_watchlist";
+        t.description = "Set the order of symbols in a watchlist. Listed symbols come first in the given order; "
+                        "symbols not listed keep their relative order after them.";
+        t.category = "watchlist";
+        t.input_schema.properties = QJsonObject{
+            {"watchlist_id", QJsonObject{{"type", "string"}, {"description", "Watchlist ID"}}},
+            {"symbols", QJsonObject{{"type", "array"},
+                                    {"items", QJsonObject{{"type", "string"}}},
+                                    {"description", "Symbols in the desired order"}}}};
+        t.input_schema.required = {"watchlist_id", "symbols"};
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            QString id = args["watchlist_id"].toString().trimmed();
+            QStringList symbols;
+            for (const auto& v : args["symbols"].toArray())
+                symbols.append(v.toString());
+            if (id.isEmpty() || symbols.isEmpty())
+                return ToolResult::fail("Missing 'watchlist_id' or 'symbols'");
+
+            QString error;
+            QJsonArray order;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto& repo = WatchlistRepository::instance();
+                auto r = repo.reorder_stocks(id, symbols);
+                if (r.is_err()) {
+                    error = "Failed to reorder watchlist: " + QString::fromStdString(r.error());
+                } else if (auto stocks = repo.get_stocks(id); stocks.is_ok()) {
+                    for (const auto& s : stocks.value())
+                        order.append(s.symbol);
+                }
+                signal_done();
+            });
+            if (!error.isEmpty())
+                return ToolResult::fail(error);
+
+            EventBus::instance().publish("watchlist.updated",
+                                         QVariantMap{{"watchlist_id", id}, {"action", "reorder"}});
+            return ToolResult::ok("Watchlist reordered", QJsonObject{{"id", id}, {"symbols", order}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── set_watchlist_column ────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "set_watchlist_column";
+        t.description = "Add a computed column to a watchlist, or replace the expression of the column with the "
+                        "same name. The expression is evaluated per symbol on daily bars, e.g. "
+                        "'(close / highest(252) - 1) * 100' for % from 52-week high, 'change(21)' for 1-month % "
+                        "change, 'rsi(14)', 'close / sma(200)', 'volatility(20)'. Names: " +
+                        services::watchlist::WatchlistExpression::vocabulary().join(", ") +
+                        "; operators + - * / ^ and comparisons (1 or 0).";
+        t.category = "watchlist";
+        t.input_schema.properties = QJsonObject{
+            {"watchlist_id", QJsonObject{{"type", "string"}, {"description", "Watchlist ID"}}},
+            {"name", QJsonObject{{"type", "string"}, {"description", "Column name, e.g. '% from 52w high'"}}},
+            {"expression", QJsonObject{{"type", "string"}, {"description", "Column expression"}}},
+            {"decimals",
+             QJsonObject{{"type", "integer"}, {"description", "Display decimals (default 2)"}, {"default", 2}}}};
+        t.input_schema.required = {"watchlist_id", "name", "expression"};
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            QString id = args["watchlist_id"].toString().trimmed();
+            QString name = args["name"].toString().trimmed();
+            QString expression = args["expression"].toString().trimmed();
+            int decimals = std::clamp(args["decimals"].toInt(2), 0, 8);
+            if (id.isEmpty() || name.isEmpty())
+                return ToolResult::fail("Missing 'watchlist_id' or 'name'");
+
+            QString compile_error;
+            const auto expr = services::watchlist::WatchlistExpression::compile(expression, &compile_error);
+            if (!expr.is_valid())
+                return ToolResult::fail("Invalid expression: " + compile_error);
+
+            QString error;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto r = WatchlistRepository::instance().upsert_column(id, name, expression, decimals);
+                if (r.is_err())
+                    error = "Failed to save column: " + QString::fromStdString(r.error());
+                signal_done();
+            });
+            if (!error.isEmpty())
+                return ToolResult::fail(error);
+
+            EventBus::instance().publish("watchlist.updated",
+                                         QVariantMap{{"watchlist_id", id}, {"action", "column"}, {"column", name}});
+            return ToolResult::ok("Column saved", QJsonObject{{"watchlist_id", id},
+                                                              {"name", name},
+                                                              {"expression", expression},
+                                                              {"lookback_bars", expr.lookback_bars()}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── remove_watchlist_column ─────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "remove_watchlist_column";
+        t.description = "Remove a computed column from a watchlist.";
+        t.category = "watchlist";
+        t.is_destructive = true;
+        t.input_schema.properties =
+            QJsonObject{{"watchlist_id", QJsonObject{{"type", "string"}, {"description", "Watchlist ID"}}},
+                        {"name", QJsonObject{{"type", "string"}, {"description", "Column name"}}}};
+        t.input_schema.required = {"watchlist_id", "name"};
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            QString id = args["watchlist_id"].toString().trimmed();
+            QString name = args["name"].toString().trimmed();
+            if (id.isEmpty() || name.isEmpty())
+                return ToolResult::fail("Missing 'watchlist_id' or 'name'");
+
+            QString error;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto r = WatchlistRepository::instance().remove_column(id, name);
+                if (r.is_err())
+                    error = "Failed to remove column: " + QString::fromStdString(r.error());
+                signal_done();
+            });
+            if (!error.isEmpty())
+                return ToolResult::fail(error);
+
+            EventBus::instance().publish("watchlist.updated",
+                                         QVariantMap{{"watchlist_id", id}, {"action", "column"}, {"column", name}});
+            return ToolResult::ok("Column removed", QJsonObject{{"watchlist_id", id}, {"name", name}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── get_watchlist_values ────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "get_watchlist_values";
+        t.description = "Evaluate a watchlist's computed columns for every symbol in it. Daily history is fetched "
+                        "in one batch and cached for 15 minutes; 'force' refetches. Values are null where a "
+                        "symbol lacks the history an expression needs (see the row's errors).";
+        t.category = "watchlist";
+        t.input_schema.properties = QJsonObject{
+            {"watchlist_id", QJsonObject{{"type", "string"}, {"description", "Watchlist ID"}}},
+            {"force", QJsonObject{{"type", "boolean"}, {"description", "Ignore cached history"}, {"default", false}}}};
+        t.input_schema.required = {"watchlist_id"};
+        t.default_timeout_ms = 120000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            using services::watchlist::WatchlistColumnService;
+            auto* svc = &WatchlistColumnService::instance();
+            const QString watchlist_id = args["watchlist_id"].toString().trimmed();
+            const bool force = args["force"].toBool(false);
+            AsyncDispatch::callback_to_promise(svc, std::move(ctx), promise, [svc, watchlist_id, force](auto resolve) {
+                auto* holder = new QObject(svc);
+                auto id = std::make_shared<QString>();
+                QObject::connect(svc, &WatchlistColumnService::values_ready, holder,
+                                 [resolve, holder, id](QString request_id, QString, QJsonObject result) {
+                                     if (request_id != *id)
+                                         return;
+                                     resolve(ToolResult::ok(QString("%1 symbols × %2 columns")
+                                                                .arg(result["rows"].toArray().size())
+                                                                .arg(result["columns"].toArray().size()),
+                                                            result));
+                                     holder->deleteLater();
+                                 });
+                QObject::connect(svc, &WatchlistColumnService::error_occurred, holder,
+                                 [resolve, holder, id](QString request_id, QString msg) {
+                                     if (request_id != *id)
+                                         return;
+                                     resolve(ToolResult::fail(msg));
+                                     holder->deleteLater();
+                                 });
+                *id = svc->refresh(watchlist_id, force);
+            });
+        };
+        tools.push_back(std::move(t));
+    }
+
     return tools;
 }
 
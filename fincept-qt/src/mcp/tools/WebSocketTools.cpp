@@ -0,0 +1,93 @@
+// WebSocketTools.cpp — Live websocket connection metrics and inbound backpressure policy MCP tools
+
+#include "mcp/tools/WebSocketTools.h"
+
+#include "mcp/ToolSchemaBuilder.h"
+#include "mcp/tools/ThreadHelper.h"
+#include "network/websocket/WebSocketMonitor.h"
+
+#include <QCoreApplication>
+#include <QJsonArray>
+#include <QJsonObject>
+
+namespace fincept::mcp::tools {
+
+std::vector<ToolDef> get_websocket_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── websocket_metrics ──────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "websocket_metrics";
+        t.description = "Per-connection metrics for every live websocket (broker tick feeds, Kalshi, Polymarket, "
+                        "Alpaca, wallet streams): connection state and uptime, reconnects, messages in/out, "
+                        "inbound rate over the last 1s / 10s / 60s and the 1s peak, ping round-trip latency, "
+                        "inbound queue depth and delay, and frames dropped or blocked by backpressure.";
+        t.category = "system";
+        t.input_schema = ToolSchemaBuilder().string("label", "Only connections whose label contains this").build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            const QString filter = args["label"].toString().trimmed();
+            auto& monitor = WebSocketMonitor::instance();
+            QJsonArray connections;
+            for (const auto& v : monitor.snapshot())
+                if (filter.isEmpty() || v.toObject()["label"].toString().contains(filter, Qt::CaseInsensitive))
+                    connections.append(v);
+            QJsonObject data = monitor.summary();
+            data["connections"] = connections;
+            return ToolResult::ok(QString("%1 connection(s), policy %2")
+                                      .arg(connections.size())
+                                      .arg(data["policy"].toString()),
+                                  data);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── websocket_backpressure ─────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "websocket_backpressure";
+        t.description = "Read or set what a websocket does when its consumer falls behind. Each connection buffers "
+                        "at most `capacity` undelivered frames; when full, drop_oldest discards the stalest frame "
+                        "(latest data wins, drops are counted) and block stops reading the socket until the "
+                        "backlog is delivered (nothing lost, the server is throttled instead). Call with no "
+                        "arguments to read the current setting.";
+        t.category = "system";
+        t.input_schema = ToolSchemaBuilder()
+                             .string("policy", "Overflow policy")
+                             .enums({"drop_oldest", "block"})
+                             .integer("capacity", "Undelivered frames buffered per connection")
+                             .between(100, 1000000)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            auto& monitor = WebSocketMonitor::instance();
+            if (!args.isEmpty()) {
+                const auto policy = args.contains("policy")
+                                        ? WebSocketMonitor::policy_from_name(args["policy"].toString())
+                                        : std::optional(monitor.policy());
+                if (!policy)
+                    return ToolResult::fail("policy must be drop_oldest or block");
+                QString error;
+                detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                    monitor.set_backpressure(*policy, args["capacity"].toInt(monitor.capacity()));
+                    auto r = monitor.save_settings();
+                    if (r.is_err())
+                        error = QString::fromStdString(r.error());
+                    signal_done();
+                });
+                if (!error.isEmpty())
+                    return ToolResult::fail(error);
+            }
+            const QJsonObject data{{"policy", WebSocketMonitor::policy_name(monitor.policy())},
+                                   {"capacity", monitor.capacity()}};
+            return ToolResult::ok(QString("Backpressure: %1, %2 frames per connection")
+                                      .arg(data["policy"].toString())
+                                      .arg(data["capacity"].toInt()),
+                                  data);
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
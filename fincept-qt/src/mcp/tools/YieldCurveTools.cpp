@@ -0,0 +1,258 @@
+// YieldCurveTools.cpp — Nelson-Siegel / Svensson yield curve fit MCP tools
+
+#include "mcp/tools/YieldCurveTools.h"
+
+#include "mcp/AsyncDispatch.h"
+#include "mcp/ToolSchemaBuilder.h"
+#include "mcp/tools/ThreadHelper.h"
+#include "services/economics/YieldCurveService.h"
+#include "storage/repositories/YieldCurveRepository.h"
+
+#include <QCoreApplication>
+#include <QDate>
+#include <QHash>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+
+#include <algorithm>
+#include <memory>
+
+namespace fincept::mcp::tools {
+
+namespace {
+
+using services::YieldCurveService;
+
+// Maturities (years) an animation frame is evaluated at unless overridden.
+const QVector<double> kYcDefaultGrid = {0.25, 0.5, 1, 2, 3, 5, 7, 10, 15, 20, 30};
+
+ToolSchemaBuilder series_schema() {
+    return ToolSchemaBuilder()
+        .string("source", "treasury (US H.15 constant maturities) or ecb (euro area AAA spot curve)")
+        .enums({"treasury", "ecb"})
+        .default_str("treasury")
+        .string("model", "Curve model")
+        .enums({"nelson_siegel", "svensson"})
+        .default_str("nelson_siegel");
+}
+
+// Stored fits over the requested window, every `step`-th one plus the
+// latest. Sets `error` on failure.
+QVector<YieldCurveFit> load_fits(const QJsonObject& args, QString& error) {
+    const QString source = args["source"].toString("treasury");
+    const QString model = args["model"].toString("nelson_siegel");
+    QString from = args["from_date"].toString();
+    const QString to = args["to_date"].toString();
+    if (from.isEmpty())
+        from = QDate::currentDate().addDays(-args["days"].toInt(365)).toString(Qt::ISODate);
+    const int step = std::max(args["step"].toInt(1), 1);
+
+    QVector<YieldCurveFit> fits;
+    detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+        auto r = YieldCurveRepository::instance().get_fits(source, model, from, to);
+        if (r.is_err())
+            error = QString::fromStdString(r.error());
+        else
+            fits = r.value();
+        signal_done();
+    });
+    if (error.isEmpty() && fits.isEmpty())
+        error = "No " + model + " fits for " + source + " in that window; run yield_curve_fit";
+    if (step > 1 && fits.size() > 1) {
+        QVector<YieldCurveFit> kept;
+        for (int i = 0; i < fits.size(); i += step)
+            kept.append(fits[i]);
+        if (kept.last().date != fits.last().date)
+            kept.append(fits.last());
+        fits = kept;
+    }
+    return fits;
+}
+
+} // namespace
+
+std::vector<ToolDef> get_yield_curve_tools() {
+    std::vector<ToolDef> tools;
+
+    // ── yield_curve_fit ────────────────────────────────────────────────
+    // Continues the stored series from its last date (or backfills). Stored
+    // series are also brought up to date automatically.
+    {
+        ToolDef t;
+        t.name = "yield_curve_fit";
+        t.description = "Fit Nelson-Siegel or Svensson parameters to every daily Treasury or ECB yield curve since "
+                        "the last stored fit (or `days` back for a new series / backfill) and store them. Returns "
+                        "the latest level/slope/curvature factors.";
+        t.category = "markets";
+        t.input_schema = series_schema()
+                             .boolean("backfill", "Refit the history instead of continuing it")
+                             .default_bool(false)
+                             .integer("days", "Calendar days of history for a new series or backfill")
+                             .default_int(YieldCurveService::kDefaultBackfillDays)
+                             .between(1, YieldCurveService::kMaxBackfillDays)
+                             .build();
+        t.default_timeout_ms = 300000;
+        t.async_handler = [](const QJsonObject& args, ToolContext ctx, std::shared_ptr<QPromise<ToolResult>> promise) {
+            auto* svc = &YieldCurveService::instance();
+            const QString source = args["source"].toString("treasury");
+            const QString model = args["model"].toString("nelson_siegel");
+            const bool backfill = args["backfill"].toBool(false);
+            const int days = args["days"].toInt(YieldCurveService::kDefaultBackfillDays);
+            AsyncDispatch::callback_to_promise(
+                svc, std::move(ctx), promise, [svc, source, model, backfill, days](auto resolve) {
+                    auto* holder = new QObject(svc);
+                    auto request = std::make_shared<QString>();
+                    QObject::connect(
+                        svc, &YieldCurveService::updated, holder,
+                        [resolve, holder, request](QString id, QString src, QString mdl, QJsonObject summary) {
+                            if (id != *request)
+                                return;
+                            resolve(ToolResult::ok(QString("%1 %2: %3 fits written through %4")
+                                                       .arg(src, mdl)
+                                                       .arg(summary["rows_written"].toInt())
+                                                       .arg(summary["last_date"].toString("-")),
+                                                   summary));
+                            holder->deleteLater();
+                        });
+                    QObject::connect(svc, &YieldCurveService::error_occurred, holder,
+                                     [resolve, holder, request](QString id, QString msg) {
+                                         if (id != *request)
+                                             return;
+                                         resolve(ToolResult::fail(msg));
+                                         holder->deleteLater();
+                                     });
+                    *request = svc->update(source, model, backfill, days);
+                });
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── yield_curve_fit_series ─────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "yield_curve_fit_series";
+        t.description = "List stored yield curve fit series (source, model, date range, number of fits).";
+        t.category = "markets";
+        t.handler = [](const QJsonObject&) -> ToolResult {
+            QJsonArray list;
+            QString error;
+            detail::run_async_wait(QCoreApplication::instance(), [&](auto signal_done) {
+                auto r = YieldCurveRepository::instance().list_series();
+                if (r.is_err())
+                    error = QString::fromStdString(r.error());
+                else
+                    for (const auto& s : r.value())
+                        list.append(QJsonObject{{"source", s.source},
+                                                {"model", s.model},
+                                                {"first_date", s.first_date},
+                                                {"last_date", s.last_date},
+                                                {"fits", s.fits},
+                                                {"last_fitted_at", s.last_fitted_at}});
+                signal_done();
+            });
+            if (!error.isEmpty())
+                return ToolResult::fail(error);
+            return ToolResult::ok_data(QJsonObject{{"series", list}, {"count", list.size()}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── yield_curve_factors ────────────────────────────────────────────
+    {
+        ToolDef t;
+        t.name = "yield_curve_factors";
+        t.description = "Level (beta0), slope (-beta1, long minus short end) and curvature (beta2) factor time "
+                        "series from stored curve fits, with model-implied 3M/2Y/10Y yields, the 2s10s spread and "
+                        "fit RMSE, as column arrays (oldest first). Yields are decimals.";
+        t.category = "markets";
+        t.input_schema = series_schema()
+                             .string("from_date", "YYYY-MM-DD (default: `days` back)")
+                             .string("to_date", "YYYY-MM-DD (default: latest)")
+                             .integer("days", "Calendar days back when from_date is not set")
+                             .default_int(365)
+                             .between(1, YieldCurveService::kMaxBackfillDays)
+                             .integer("step", "Keep every n-th fit (the latest is always kept)")
+                             .default_int(1)
+                             .between(1, 60)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            QString error;
+            const QVector<YieldCurveFit> fits = load_fits(args, error);
+            if (!error.isEmpty())
+                return ToolResult::fail(error);
+            const QStringList keys = {"date", "level", "slope", "curvature", "rmse",
+                                      "y_3m", "y_2y", "y_10y", "spread_2s10s"};
+            QHash<QString, QJsonArray> cols;
+            for (const auto& f : fits) {
+                const QJsonObject row = YieldCurveService::factors_to_json(f);
+                for (const auto& k : keys)
+                    cols[k].append(row[k]);
+            }
+            QJsonObject columns;
+            for (const auto& k : keys)
+                columns[k] = cols.value(k);
+            return ToolResult::ok_data(QJsonObject{{"source", fits.first().source},
+                                                   {"model", fits.first().model},
+                                                   {"count", fits.size()},
+                                                   {"factors", columns},
+                                                   {"latest", YieldCurveService::factors_to_json(fits.last())}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    // ── yield_curve_frames ─────────────────────────────────────────────
+    // Animation data: one fitted curve per stored date on a shared maturity
+    // grid, optionally with the observed points each fit was made from.
+    {
+        ToolDef t;
+        t.name = "yield_curve_frames";
+        t.description = "Curve evolution frames for animation: the fitted curve of each stored date evaluated on "
+                        "a maturity grid (years), optionally with the observed yields. Use step to thin long "
+                        "ranges.";
+        t.category = "markets";
+        t.input_schema = series_schema()
+                             .string("from_date", "YYYY-MM-DD (default: `days` back)")
+                             .string("to_date", "YYYY-MM-DD (default: latest)")
+                             .integer("days", "Calendar days back when from_date is not set")
+                             .default_int(365)
+                             .between(1, YieldCurveService::kMaxBackfillDays)
+                             .integer("step", "Keep every n-th fit (the latest is always kept)")
+                             .default_int(5)
+                             .between(1, 60)
+                             .array("maturities", "Maturity grid in years (default 3M to 30Y)",
+                                    QJsonObject{{"type", "number"}})
+                             .boolean("observed", "Include the observed points of each date")
+                             .default_bool(false)
+                             .build();
+        t.handler = [](const QJsonObject& args) -> ToolResult {
+            QJsonObject a = args;
+            if (!a.contains("step"))
+                a["step"] = 5;
+            QString error;
+            const QVector<YieldCurveFit> fits = load_fits(a, error);
+            if (!error.isEmpty())
+                return ToolResult::fail(error);
+            QVector<double> grid;
+            for (const auto& v : args["maturities"].toArray())
+                if (v.toDouble() > 0)
+                    grid.append(v.toDouble());
+            if (grid.isEmpty())
+                grid = kYcDefaultGrid;
+            std::sort(grid.begin(), grid.end());
+            const bool observed = args["observed"].toBool(false);
+            QJsonArray frames;
+            for (const auto& f : fits)
+                frames.append(YieldCurveService::frame_to_json(f, grid, observed));
+            return ToolResult::ok_data(QJsonObject{{"source", fits.first().source},
+                                                   {"model", fits.first().model},
+                                                   {"count", frames.size()},
+                                                   {"frames", frames}});
+        };
+        tools.push_back(std::move(t));
+    }
+
+    return tools;
+}
+
+} // namespace fincept::mcp::tools
@@ -1,18 +1,26 @@
 #include "network/websocket/WebSocketClient.h"
 
 #include "core/logging/Logger.h"
+#include "network/websocket/WebSocketMonitor.h"
 
 #include <QDateTime>
 #include <QMetaObject>
+#include <QMutexLocker>
 #include <QPointer>
 #include <QRegularExpression>
 #include <QThread>
 #include <QTimer>
+#include <QUrl>
+
+#include <algorithm>
 
 namespace fincept {
 
 namespace {
 constexpr const char* kTag = "WS";
+constexpr int kWsDrainBatch = 500;          // frames delivered per event-loop turn
+constexpr int kWsPingIntervalMs = 15000;    // RTT probe while connected
+constexpr qint64 kWsDropLogEveryMs = 60000; // slow-consumer warning throttle
 
 // Mask the `token=` query value so broker access tokens (JWTs) never reach the
 // log file. Applied to every URL we log — these tokens are bearer credentials.
@@ -69,8 +77,24 @@ WebSocketClient::WebSocketClient(QObject* parent) : QObject(parent) {
     connect(socket_, &QWebSocket::textMessageReceived, this, &WebSocketClient::on_text_received);
     connect(socket_, &QWebSocket::binaryMessageReceived, this, &WebSocketClient::on_binary_received);
     connect(socket_, &QWebSocket::errorOccurred, this, &WebSocketClient::on_error);
+    connect(socket_, &QWebSocket::pong, this, &WebSocketClient::on_pong);
     connect(&reconnect_timer_, &QTimer::timeout, this, &WebSocketClient::attempt_reconnect);
     reconnect_timer_.setSingleShot(true);
+    // Parented for the same moveToThread reason as the socket.
+    ping_timer_ = new QTimer(this);
+    ping_timer_->setInterval(kWsPingIntervalMs);
+    connect(ping_timer_, &QTimer::timeout, this, [this]() { socket_->ping(); });
+    clock_.start();
+    stats_ = WebSocketMonitor::instance().register_client(QString());
+}
+
+WebSocketClient::~WebSocketClient() {
+    WebSocketMonitor::instance().unregister_client(stats_->id);
+}
+
+void WebSocketClient::set_label(const QString& label) {
+    QMutexLocker lock(&stats_->mutex);
+    stats_->label = label;
 }
 
 void WebSocketClient::connect_to(const QString& url) {
@@ -82,6 +106,12 @@ void WebSocketClient::connect_to(const QString& url) {
         self->url_ = u;
         self->reconnect_attempts_ = 0;
         self->reconnect_stopped_ = false; // a fresh connect re-enables auto-reconnect
+        {
+            QMutexLocker lock(&self->stats_->mutex);
+            self->stats_->url = redact_url(u);
+            if (self->stats_->label.isEmpty())
+                self->stats_->label = QUrl(u).host();
+        }
         LOG_INFO(kTag, QString("[%1] Connecting to %2").arg(thread_label(), redact_url(u)));
         self->socket_->open(QUrl(u));
     });
@@ -117,6 +147,8 @@ void WebSocketClient::send(const QString& message) {
         if (!self)
             return;
         self->socket_->sendTextMessage(m);
+        QMutexLocker lock(&self->stats_->mutex);
+        ++self->stats_->messages_out;
     });
 }
 
@@ -127,6 +159,8 @@ void WebSocketClient::send_binary(const QByteArray& data) {
         if (!self)
             return;
         self->socket_->sendBinaryMessage(d);
+        QMutexLocker lock(&self->stats_->mutex);
+        ++self->stats_->messages_out;
     });
 }
 
@@ -138,6 +172,12 @@ bool WebSocketClient::is_connected() const {
 void WebSocketClient::on_connected() {
     LOG_INFO(kTag, QString("[%1] Connected to %2").arg(thread_label(), redact_url(url_)));
     reconnect_attempts_ = 0;
+    {
+        QMutexLocker lock(&stats_->mutex);
+        stats_->connected = true;
+        stats_->connected_since_ms = QDateTime::currentMSecsSinceEpoch();
+    }
+    ping_timer_->start();
     emit connected();
 }
 
@@ -145,6 +185,11 @@ void WebSocketClient::on_disconnected() {
     LOG_WARN(kTag, QString("[%1] Disconnected from %2 (state=%3)")
                        .arg(thread_label(), redact_url(url_))
                        .arg(static_cast<int>(socket_ ? socket_->state() : QAbstractSocket::UnconnectedState)));
+    ping_timer_->stop();
+    {
+        QMutexLocker lock(&stats_->mutex);
+        stats_->connected = false;
+    }
     emit disconnected();
     if (!reconnect_stopped_ && reconnect_attempts_ < MAX_RECONNECT_ATTEMPTS) {
         const int delay = std::min(1000 * (1 << reconnect_attempts_), 30000);
@@ -163,11 +208,112 @@ void WebSocketClient::on_disconnected() {
 }
 
 void WebSocketClient::on_text_received(const QString& msg) {
-    emit message_received(msg);
+    InboundFrame f;
+    f.text = msg;
+    enqueue(std::move(f));
 }
 
 void WebSocketClient::on_binary_received(const QByteArray& data) {
-    emit binary_message_received(data);
+    InboundFrame f;
+    f.binary = true;
+    f.data = data;
+    enqueue(std::move(f));
+}
+
+// Frames are queued rather than emitted straight from the socket's read
+// handler so a slow consumer shows up as queue depth / delay instead of an
+// unbounded backlog inside QWebSocket. When the queue is full the monitor's
+// policy decides: DropOldest discards the stalest frame, Block delivers the
+// whole backlog right here — the socket isn't read again until the consumer
+// has caught up, which lets TCP flow control throttle the server.
+void WebSocketClient::enqueue(InboundFrame frame) {
+    auto& monitor = WebSocketMonitor::instance();
+    const int capacity = monitor.capacity();
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    frame.queued_ms = clock_.elapsed();
+
+    bool log_drop = false;
+    if (inbox_.size() >= capacity) {
+        if (monitor.policy() == WebSocketMonitor::Policy::Block) {
+            {
+                QMutexLocker lock(&stats_->mutex);
+                ++stats_->blocked_drains;
+            }
+            QPointer<WebSocketClient> self(this);
+            drain(int(inbox_.size()));
+            if (!self)
+                return;
+        } else {
+            inbox_.dequeue();
+            QMutexLocker lock(&stats_->mutex);
+            ++stats_->dropped;
+            if (now - stats_->last_drop_log_ms >= kWsDropLogEveryMs) {
+                stats_->last_drop_log_ms = now;
+                log_drop = true;
+            }
+        }
+    }
+
+    {
+        QMutexLocker lock(&stats_->mutex);
+        stats_->note_message(now, frame.binary ? int(frame.data.size()) : int(frame.text.size()));
+        stats_->queue_depth = int(inbox_.size()) + 1;
+        stats_->queue_peak = std::max(stats_->queue_peak, stats_->queue_depth);
+    }
+    inbox_.enqueue(std::move(frame));
+
+    if (log_drop)
+        LOG_WARN(kTag, QString("[%1] Slow consumer on %2: inbound queue full (%3 frames), dropping oldest")
+                           .arg(thread_label(), redact_url(url_))
+                           .arg(capacity));
+
+    if (!drain_scheduled_) {
+        drain_scheduled_ = true;
+        QPointer<WebSocketClient> self(this);
+        QTimer::singleShot(0, this, [self]() {
+            if (!self)
+                return;
+            self->drain_scheduled_ = false;
+            self->drain(kWsDrainBatch);
+        });
+    }
+}
+
+void WebSocketClient::drain(int max_frames) {
+    QPointer<WebSocketClient> self(this);
+    for (int n = 0; n < max_frames && !inbox_.isEmpty(); ++n) {
+        InboundFrame f = inbox_.dequeue();
+        {
+            QMutexLocker lock(&stats_->mutex);
+            const double waited = double(clock_.elapsed() - f.queued_ms);
+            stats_->queue_delay_ms = 0.9 * stats_->queue_delay_ms + 0.1 * waited;
+            stats_->queue_delay_max_ms = std::max(stats_->queue_delay_max_ms, waited);
+            stats_->queue_depth = int(inbox_.size());
+        }
+        if (f.binary)
+            emit binary_message_received(f.data);
+        else
+            emit message_received(f.text);
+        if (!self) // a handler destroyed us
+            return;
+    }
+    if (!inbox_.isEmpty() && !drain_scheduled_) {
+        // Yield so the socket keeps being read between batches.
+        drain_scheduled_ = true;
+        QTimer::singleShot(0, this, [self]() {
+            if (!self)
+                return;
+            self->drain_scheduled_ = false;
+            self->drain(kWsDrainBatch);
+        });
+    }
+}
+
+void WebSocketClient::on_pong(quint64 elapsed_ms) {
+    QMutexLocker lock(&stats_->mutex);
+    const double rtt = double(elapsed_ms);
+    stats_->ping_rtt_ms = rtt;
+    stats_->ping_rtt_avg_ms = stats_->ping_rtt_avg_ms < 0 ? rtt : 0.8 * stats_->ping_rtt_avg_ms + 0.2 * rtt;
 }
 
 void WebSocketClient::on_error(QAbstractSocket::SocketError err) {
@@ -186,6 +332,10 @@ void WebSocketClient::attempt_reconnect() {
         return;
     }
     reconnect_attempts_++;
+    {
+        QMutexLocker lock(&stats_->mutex);
+        ++stats_->reconnects;
+    }
     LOG_INFO(kTag, QString("[%1] Reconnect attempt %2/%3 to %4")
                        .arg(thread_label())
                        .arg(reconnect_attempts_)
@@ -197,6 +347,8 @@ void WebSocketClient::attempt_reconnect() {
 #else // No Qt WebSockets — stub implementations
 
 WebSocketClient::WebSocketClient(QObject* parent) : QObject(parent) {}
+WebSocketClient::~WebSocketClient() = default;
+void WebSocketClient::set_label(const QString& /*label*/) {}
 void WebSocketClient::connect_to(const QString& /*url*/) {
     LOG_WARN(kTag, "WebSocket not available — Qt6::WebSockets not installed");
 }
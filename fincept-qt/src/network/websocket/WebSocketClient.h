@@ -1,5 +1,7 @@
 #pragma once
+#include <QElapsedTimer>
 #include <QObject>
+#include <QQueue>
 #include <QTimer>
 
 #ifdef HAS_QT_WEBSOCKETS
@@ -7,14 +9,25 @@
 #endif
 
 #include <functional>
+#include <memory>
 
 namespace fincept {
 
+struct WebSocketStats;
+
 /// WebSocket client with auto-reconnect for real-time market data.
+///
+/// Inbound frames go through a bounded queue and are delivered on the
+/// client's thread in batches; WebSocketMonitor holds the overflow policy and
+/// the per-connection metrics (rates, ping RTT, queue delay, drops).
 class WebSocketClient : public QObject {
     Q_OBJECT
   public:
     explicit WebSocketClient(QObject* parent = nullptr);
+    ~WebSocketClient() override;
+
+    /// Name shown by WebSocketMonitor (defaults to the URL host).
+    void set_label(const QString& label);
 
     void connect_to(const QString& url);
     void disconnect();
@@ -42,6 +55,7 @@ class WebSocketClient : public QObject {
     void on_binary_received(const QByteArray& data);
     void on_error(QAbstractSocket::SocketError err);
     void attempt_reconnect();
+    void on_pong(quint64 elapsed_ms);
 #endif
 
   private:
@@ -52,7 +66,22 @@ class WebSocketClient : public QObject {
     // parent and therefore would not be carried by Qt's child-move
     // machinery — leading to cross-thread socket access on every method.
     QWebSocket* socket_ = nullptr;
+
+    struct InboundFrame {
+        bool binary = false;
+        QString text;
+        QByteArray data;
+        qint64 queued_ms = 0;
+    };
+    void enqueue(InboundFrame frame);
+    void drain(int max_frames);
+
+    QQueue<InboundFrame> inbox_;
+    bool drain_scheduled_ = false;
+    QTimer* ping_timer_ = nullptr; // RTT probe; see WebSocketMonitor
+    QElapsedTimer clock_; // queue-delay timestamps
 #endif
+    std::shared_ptr<WebSocketStats> stats_;
     QTimer reconnect_timer_;
     QString url_;
     int reconnect_attempts_ = 0;
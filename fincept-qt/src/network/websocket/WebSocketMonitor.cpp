@@ -0,0 +1,178 @@
+#include "network/websocket/WebSocketMonitor.h"
+
+#include "core/logging/Logger.h"
+#include "storage/repositories/SettingsRepository.h"
+
+#include <QDateTime>
+#include <QJsonDocument>
+#include <QMutexLocker>
+
+#include <algorithm>
+
+namespace fincept {
+
+namespace {
+
+const char* kWsmSettingsKey = "websocket.backpressure";
+
+struct WsmRates {
+    double rate_1s = 0;
+    double rate_10s = 0;
+    double rate_60s = 0;
+    quint32 peak_1s = 0;
+};
+
+// Rates over complete seconds only — the current second is still filling.
+WsmRates wsm_rates(const WebSocketStats& s, qint64 now_ms) {
+    WsmRates r;
+    const qint64 now_s = now_ms / 1000;
+    quint64 sum10 = 0, sum60 = 0;
+    for (int back = 1; back <= 60; ++back) {
+        const qint64 sec = now_s - back;
+        const int slot = int(sec % 60);
+        const quint32 n = s.sec_stamp[slot] == sec ? s.sec_count[slot] : 0;
+        if (back == 1)
+            r.rate_1s = n;
+        if (back <= 10)
+            sum10 += n;
+        sum60 += n;
+        r.peak_1s = std::max(r.peak_1s, n);
+    }
+    r.rate_10s = sum10 / 10.0;
+    r.rate_60s = sum60 / 60.0;
+    return r;
+}
+
+} // namespace
+
+void WebSocketStats::note_message(qint64 now_ms, int bytes) {
+    ++messages_in;
+    bytes_in += quint64(bytes);
+    last_message_ms = now_ms;
+    const qint64 sec = now_ms / 1000;
+    const int slot = int(sec % 60);
+    if (sec_stamp[slot] != sec) {
+        sec_stamp[slot] = sec;
+        sec_count[slot] = 0;
+    }
+    ++sec_count[slot];
+}
+
+WebSocketMonitor& WebSocketMonitor::instance() {
+    static WebSocketMonitor s;
+    return s;
+}
+
+std::shared_ptr<WebSocketStats> WebSocketMonitor::register_client(const QString& label) {
+    auto stats = std::make_shared<WebSocketStats>();
+    QMutexLocker lock(&mutex_);
+    stats->id = next_id_++;
+    stats->label = label;
+    clients_.insert(stats->id, stats);
+    return stats;
+}
+
+void WebSocketMonitor::unregister_client(int id) {
+    QMutexLocker lock(&mutex_);
+    clients_.remove(id);
+}
+
+void WebSocketMonitor::set_backpressure(Policy policy, int capacity) {
+    policy_.store(int(policy), std::memory_order_relaxed);
+    capacity_.store(std::clamp(capacity, 100, 1000000), std::memory_order_relaxed);
+    LOG_INFO("WS", QString("Backpressure: %1, capacity %2 frames per connection")
+                       .arg(policy_name(policy))
+                       .arg(this->capacity()));
+}
+
+void WebSocketMonitor::load_settings() {
+    auto r = SettingsRepository::instance().get(kWsmSettingsKey);
+    if (r.is_err() || r.value().isEmpty())
+        return;
+    const QJsonObject o = QJsonDocument::fromJson(r.value().toUtf8()).object();
+    const auto p = policy_from_name(o.value("policy").toString());
+    set_backpressure(p.value_or(policy()), o.value("capacity").toInt(capacity()));
+}
+
+Result<void> WebSocketMonitor::save_settings() const {
+    const QJsonObject o{{"policy", policy_name(policy())}, {"capacity", capacity()}};
+    return SettingsRepository::instance().set(
+        kWsmSettingsKey, QString::fromUtf8(QJsonDocument(o).toJson(QJsonDocument::Compact)), "network");
+}
+
+QJsonArray WebSocketMonitor::snapshot() const {
+    QList<std::shared_ptr<WebSocketStats>> clients;
+    {
+        QMutexLocker lock(&mutex_);
+        clients = clients_.values();
+    }
+    std::sort(clients.begin(), clients.end(), [](const auto& a, const auto& b) { return a->id < b->id; });
+
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    QJsonArray out;
+    for (const auto& c : clients) {
+        QMutexLocker lock(&c->mutex);
+        const WsmRates r = wsm_rates(*c, now);
+        out.append(QJsonObject{
+            {"id", c->id},
+            {"label", c->label},
+            {"url", c->url},
+            {"connected", c->connected},
+            {"connected_for_sec", c->connected ? double((now - c->connected_since_ms) / 1000) : 0.0},
+            {"idle_sec", c->last_message_ms > 0 ? double((now - c->last_message_ms) / 1000) : -1.0},
+            {"reconnects", c->reconnects},
+            {"messages_in", double(c->messages_in)},
+            {"bytes_in", double(c->bytes_in)},
+            {"messages_out", double(c->messages_out)},
+            {"rate_1s", r.rate_1s},
+            {"rate_10s", r.rate_10s},
+            {"rate_60s", r.rate_60s},
+            {"peak_1s", double(r.peak_1s)},
+            {"queue_depth", c->queue_depth},
+            {"queue_peak", c->queue_peak},
+            {"queue_capacity", capacity()},
+            {"dropped", double(c->dropped)},
+            {"blocked_drains", double(c->blocked_drains)},
+            {"queue_delay_ms", c->queue_delay_ms},
+            {"queue_delay_max_ms", c->queue_delay_max_ms},
+            {"ping_rtt_ms", c->ping_rtt_ms},
+            {"ping_rtt_avg_ms", c->ping_rtt_avg_ms},
+        });
+    }
+    return out;
+}
+
+QJsonObject WebSocketMonitor::summary() const {
+    int connected = 0;
+    double messages = 0, dropped = 0, rate = 0;
+    const QJsonArray all = snapshot();
+    for (const auto& v : all) {
+        const QJsonObject c = v.toObject();
+        connected += c["connected"].toBool() ? 1 : 0;
+        messages += c["messages_in"].toDouble();
+        dropped += c["dropped"].toDouble();
+        rate += c["rate_1s"].toDouble();
+    }
+    return QJsonObject{{"policy", policy_name(policy())},
+                       {"capacity", capacity()},
+                       {"connections", all.size()},
+                       {"connected", connected},
+                       {"messages_in", messages},
+                       {"dropped", dropped},
+                       {"rate_1s", rate}};
+}
+
+QString WebSocketMonitor::policy_name(Policy p) {
+    return p == Policy::Block ? QStringLiteral("block") : QStringLiteral("drop_oldest");
+}
+
+std::optional<WebSocketMonitor::Policy> WebSocketMonitor::policy_from_name(const QString& name) {
+    const QString n = name.trimmed().toLower();
+    if (n == "drop_oldest")
+        return Policy::DropOldest;
+    if (n == "block")
+        return Policy::Block;
+    return std::nullopt;
+}
+
+} // namespace fincept
@@ -0,0 +1,109 @@
+#pragma once
+// WebSocketMonitor — per-connection metrics and inbound backpressure policy
+// for every WebSocketClient (broker tick feeds, prediction markets, wallet
+// streams, ...).
+//
+// Each client registers a WebSocketStats block on construction and updates it
+// on its own thread; snapshot() reads them from any thread. The backpressure
+// policy is global and read by every client on each inbound frame:
+//   - DropOldest: a full inbound queue discards its oldest frame (counted as
+//     dropped) — the consumer always sees the most recent data.
+//   - Block:      a full inbound queue is drained synchronously before the
+//     socket is read again, so a slow consumer stalls the socket and TCP
+//     pushes back on the server instead of memory growing.
+// Either way a connection never holds more than `capacity` undelivered frames.
+
+#include "core/result/Result.h"
+
+#include <QHash>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QMutex>
+#include <QString>
+
+#include <array>
+#include <atomic>
+#include <memory>
+#include <optional>
+
+namespace fincept {
+
+/// Live counters for one WebSocketClient. Guarded by `mutex`.
+struct WebSocketStats {
+    mutable QMutex mutex;
+    int id = 0;
+    QString label; // adapter name ("zerodha", "kalshi", ...) or the URL host
+    QString url;   // token-redacted
+    bool connected = false;
+    qint64 connected_since_ms = 0;
+    qint64 last_message_ms = 0;
+    int reconnects = 0;
+    quint64 messages_in = 0;
+    quint64 bytes_in = 0;
+    quint64 messages_out = 0;
+    quint64 dropped = 0;        // frames discarded by DropOldest
+    quint64 blocked_drains = 0; // times Block drained synchronously
+    int queue_depth = 0;
+    int queue_peak = 0;
+    double queue_delay_ms = 0;     // EWMA of the time a frame waited for delivery
+    double queue_delay_max_ms = 0; // worst wait seen
+    double ping_rtt_ms = -1;       // last ping → pong round trip, -1 before the first
+    double ping_rtt_avg_ms = -1;   // EWMA of the above
+    qint64 last_drop_log_ms = 0;
+
+    // Inbound frames per second over the last minute: slot = epoch second % 60,
+    // `sec_stamp` says which second the count belongs to.
+    std::array<quint32, 60> sec_count{};
+    std::array<qint64, 60> sec_stamp{};
+
+    /// Counts one inbound frame of `bytes` at `now_ms`. Caller holds `mutex`.
+    void note_message(qint64 now_ms, int bytes);
+};
+
+class WebSocketMonitor {
+  public:
+    enum class Policy { DropOldest, Block };
+
+    static constexpr int kDefaultCapacity = 10000;
+
+    static WebSocketMonitor& instance();
+
+    std::shared_ptr<WebSocketStats> register_client(const QString& label);
+    void unregister_client(int id);
+
+    Policy policy() const { return Policy(policy_.load(std::memory_order_relaxed)); }
+    int capacity() const { return capacity_.load(std::memory_order_relaxed); }
+
+    /// Applies to every connection from its next frame on.
+    void set_backpressure(Policy policy, int capacity);
+
+    /// Reads / writes the settings key websocket.backpressure.
+    void load_settings();
+    Result<void> save_settings() const;
+
+    /// One object per live connection: {id, label, url, connected,
+    /// connected_for_sec, idle_sec, reconnects, messages_in, bytes_in,
+    /// messages_out, rate_1s, rate_10s, rate_60s, peak_1s, queue_depth,
+    /// queue_peak, queue_capacity, dropped, blocked_drains, queue_delay_ms,
+    /// queue_delay_max_ms, ping_rtt_ms, ping_rtt_avg_ms}
+    QJsonArray snapshot() const;
+
+    /// {policy, capacity, connections, connected, messages_in, dropped, rate_1s}
+    QJsonObject summary() const;
+
+    static QString policy_name(Policy p);
+    static std::optional<Policy> policy_from_name(const QString& name);
+
+  private:
+    WebSocketMonitor() = default;
+    WebSocketMonitor(const WebSocketMonitor&) = delete;
+    WebSocketMonitor& operator=(const WebSocketMonitor&) = delete;
+
+    mutable QMutex mutex_;
+    QHash<int, std::shared_ptr<WebSocketStats>> clients_;
+    int next_id_ = 1;
+    std::atomic<int> policy_{int(Policy::DropOldest)};
+    std::atomic<int> capacity_{kDefaultCapacity};
+};
+
+} // namespace fincept
@@ -0,0 +1,264 @@
+#include "python/NotebookKernel.h"
+
+#include "core/logging/Logger.h"
+#include "python/PythonRunner.h"
+#include "python/PythonSetupManager.h"
+
+#include <QCoreApplication>
+#include <QFileInfo>
+#include <QJsonDocument>
+#include <QTimer>
+#include <QUuid>
+
+namespace fincept::python {
+
+namespace {
+
+constexpr int kNkShutdownGraceMs = 5'000; // the bridge shuts its kernel down first
+
+/// Same framing as PythonWorker / scripts/notebook_kernel.py.
+QByteArray nk_encode_frame(const QJsonObject& obj) {
+    const QByteArray body = QJsonDocument(obj).toJson(QJsonDocument::Compact);
+    const quint32 n = static_cast<quint32>(body.size());
+    QByteArray out;
+    out.reserve(4 + body.size());
+    out.append(static_cast<char>((n >> 24) & 0xff));
+    out.append(static_cast<char>((n >> 16) & 0xff));
+    out.append(static_cast<char>((n >> 8) & 0xff));
+    out.append(static_cast<char>(n & 0xff));
+    out.append(body);
+    return out;
+}
+
+/// Cuts a bridge loose from `owner` and lets it wind down on its own: asks
+/// it to shut its kernel down (or kills it outright when `graceful` is
+/// false), kills it after the grace period, and deletes it once it exits.
+/// Never blocks. Parented to the application so a bridge still running at
+/// exit is killed with it.
+void nk_retire(QProcess* p, QObject* owner, bool graceful) {
+    p->disconnect(owner);
+    p->setParent(QCoreApplication::instance());
+    if (p->state() == QProcess::NotRunning) {
+        p->deleteLater();
+        return;
+    }
+    QObject::connect(p, QOverload<int, QProcess::ExitStatus>::of(&QProcess::finished), p, &QObject::deleteLater);
+    QObject::connect(p, &QProcess::errorOccurred, p, [p](QProcess::ProcessError) {
+        if (p->state() == QProcess::NotRunning)
+            p->deleteLater();
+    });
+    if (graceful && p->state() == QProcess::Running) {
+        p->write(nk_encode_frame({{"action", "shutdown"}}));
+        p->closeWriteChannel();
+        QTimer::singleShot(kNkShutdownGraceMs, p, [p]() { p->kill(); });
+    } else {
+        p->kill();
+    }
+}
+
+} // namespace
+
+NotebookKernel::NotebookKernel(QObject* parent) : QObject(parent) {}
+
+NotebookKernel::~NotebookKernel() {
+    if (proc_)
+        nk_retire(proc_, this, state_ != State::Starting);
+}
+
+// ── Public API ───────────────────────────────────────────────────────────────
+
+QString NotebookKernel::execute(const QString& code) {
+    if (state_ == State::Unavailable)
+        return {};
+    if (!proc_) {
+        launch();
+        if (state_ == State::Unavailable)
+            return {};
+    }
+    const QString id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    pending_.append(id);
+    send({{"action", "execute"}, {"id", id}, {"code", code}});
+    return id;
+}
+
+void NotebookKernel::interrupt() {
+    if (proc_ && state_ != State::Starting)
+        send({{"action", "interrupt"}});
+}
+
+void NotebookKernel::restart() {
+    if (state_ == State::Unavailable) {
+        set_state(State::Stopped); // let the next execute() try again
+        return;
+    }
+    if (!proc_)
+        return;
+    if (state_ == State::Starting) {
+        proc_->kill(); // finished handler fails the queued cells
+        return;
+    }
+    send({{"action", "restart"}});
+}
+
+void NotebookKernel::shutdown() {
+    if (!proc_)
+        return;
+    fail_pending(tr("Kernel shut down"));
+    // Detached at once: the old bridge winds down in the background while the
+    // next execute() already starts a fresh one.
+    nk_retire(proc_, this, state_ != State::Starting);
+    proc_ = nullptr;
+    read_buf_.clear();
+    language_version_.clear();
+    if (state_ != State::Unavailable)
+        set_state(State::Stopped);
+}
+
+// ── Bridge process ───────────────────────────────────────────────────────────
+
+void NotebookKernel::launch() {
+    auto& runner = PythonRunner::instance();
+    const QString scripts_dir = runner.scripts_dir();
+    const QString script = scripts_dir + "/notebook_kernel.py";
+
+    // Notebook code always runs in venv-numpy2, as PythonRunner::run_code does;
+    // jupyter (and so jupyter_client + ipykernel) is installed there.
+    QString python = PythonSetupManager::instance().python_path(QStringLiteral("venv-numpy2"));
+    if (!QFileInfo::exists(python))
+        python = runner.python_path();
+    if (python.isEmpty() || !QFileInfo::exists(script)) {
+        set_state(State::Unavailable);
+        emit unavailable(python.isEmpty() ? tr("Python not available") : tr("notebook_kernel.py not found"));
+        return;
+    }
+
+    proc_ = new QProcess(this);
+    proc_->setProcessEnvironment(runner.build_python_env());
+    proc_->setWorkingDirectory(scripts_dir);
+    proc_->setReadChannel(QProcess::StandardOutput);
+
+#ifdef _WIN32
+    proc_->setCreateProcessArgumentsModifier([](QProcess::CreateProcessArguments* cpa) {
+        cpa->flags |= 0x08000000; // CREATE_NO_WINDOW
+    });
+#endif
+
+    connect(proc_, &QProcess::readyReadStandardOutput, this, &NotebookKernel::on_ready_read);
+    connect(proc_, &QProcess::readyReadStandardError, this, [this]() {
+        const QByteArray err = proc_->readAllStandardError().trimmed();
+        if (!err.isEmpty())
+            LOG_DEBUG("NotebookKernel", QString("stderr: %1").arg(QString::fromUtf8(err.left(500))));
+    });
+    connect(proc_, QOverload<int, QProcess::ExitStatus>::of(&QProcess::finished), this,
+            [this](int, QProcess::ExitStatus) { on_process_finished(); });
+    connect(proc_, &QProcess::errorOccurred, this, [this](QProcess::ProcessError err) {
+        if (err == QProcess::FailedToStart)
+            on_process_finished(); // finished is not emitted for a process that never ran
+    });
+
+    QTimer::singleShot(kReadyTimeoutMs, this, [this, p = proc_]() {
+        if (proc_ == p && state_ == State::Starting) {
+            LOG_WARN("NotebookKernel", "Kernel did not become ready — killing bridge");
+            p->kill();
+        }
+    });
+
+    read_buf_.clear();
+    set_state(State::Starting);
+    LOG_INFO("NotebookKernel", QString("Launching kernel bridge: %1 %2").arg(python, script));
+    proc_->start(python, {script, scripts_dir});
+}
+
+void NotebookKernel::send(const QJsonObject& req) {
+    if (!proc_)
+        return;
+    if (state_ == State::Starting) {
+        queued_.append(req);
+        return;
+    }
+    proc_->write(nk_encode_frame(req));
+}
+
+void NotebookKernel::on_ready_read() {
+    read_buf_.append(proc_->readAllStandardOutput());
+    while (read_buf_.size() >= 4) {
+        const auto* b = reinterpret_cast<const uchar*>(read_buf_.constData());
+        const quint32 n = (quint32(b[0]) << 24) | (quint32(b[1]) << 16) | (quint32(b[2]) << 8) | quint32(b[3]);
+        if (n > 64u * 1024u * 1024u) {
+            LOG_ERROR("NotebookKernel", QString("Frame size %1 exceeds 64MB cap — killing bridge").arg(n));
+            read_buf_.clear();
+            proc_->kill();
+            return;
+        }
+        if (static_cast<quint32>(read_buf_.size()) < 4 + n)
+            return;
+        const QJsonDocument doc = QJsonDocument::fromJson(read_buf_.mid(4, static_cast<int>(n)));
+        read_buf_.remove(0, 4 + static_cast<int>(n));
+        if (doc.isObject())
+            on_event(doc.object());
+        if (!proc_)
+            return;
+    }
+}
+
+void NotebookKernel::on_event(const QJsonObject& ev) {
+    const QString type = ev.value("event").toString();
+    if (type == "ready") {
+        const QString lv = ev.value("language_version").toString();
+        language_version_ = lv.isEmpty() ? QString() : "Python " + lv;
+        LOG_INFO("NotebookKernel", QString("Kernel ready: %1 (%2)").arg(ev.value("kernel").toString().trimmed(), lv));
+        set_state(State::Idle);
+        const auto queued = std::move(queued_);
+        queued_.clear();
+        for (const auto& req : queued)
+            send(req);
+    } else if (type == "unavailable") {
+        const QString reason = ev.value("error").toString();
+        LOG_WARN("NotebookKernel", "Kernel unavailable: " + reason);
+        set_state(State::Unavailable); // before fail_pending, so listeners can fall back
+        fail_pending(reason);
+        emit unavailable(reason);
+    } else if (type == "status") {
+        set_state(ev.value("state").toString() == "busy" ? State::Busy : State::Idle);
+    } else if (type == "output") {
+        emit output(ev.value("id").toString(), ev.value("output").toObject());
+    } else if (type == "finished") {
+        const QString id = ev.value("id").toString();
+        pending_.removeAll(id);
+        emit finished(id, ev.value("execution_count").toInt(), ev.value("ok").toBool());
+    } else if (type == "restarted") {
+        LOG_INFO("NotebookKernel", "Kernel restarted");
+        set_state(State::Idle);
+    }
+}
+
+void NotebookKernel::on_process_finished() {
+    if (!proc_)
+        return;
+    fail_pending(tr("Kernel stopped"));
+    proc_->deleteLater();
+    proc_ = nullptr;
+    read_buf_.clear();
+    language_version_.clear();
+    if (state_ != State::Unavailable)
+        set_state(State::Stopped);
+}
+
+void NotebookKernel::fail_pending(const QString& reason) {
+    queued_.clear();
+    const auto ids = std::move(pending_);
+    pending_.clear();
+    for (const QString& id : ids) {
+        emit output(id, QJsonObject{{"type", "error"}, {"ename", "KernelError"}, {"evalue", reason}});
+        emit finished(id, 0, false);
+    }
+}
+
+void NotebookKernel::set_state(State s) {
+    if (state_ == s)
+        return;
+    state_ = s;
+    emit state_changed(s);
+}
+
+} // namespace fincept::python
@@ -0,0 +1,80 @@
+#pragma once
+
+#include <QByteArray>
+#include <QJsonObject>
+#include <QObject>
+#include <QProcess>
+#include <QString>
+#include <QVector>
+
+namespace fincept::python {
+
+/// Persistent Jupyter kernel for one notebook — a long-lived
+/// `scripts/notebook_kernel.py` bridge that starts an IPython kernel through
+/// jupyter_client (ZeroMQ) and relays its messages. Unlike
+/// PythonRunner::run_code, cells share one interpreter, so variables and
+/// imports carry over from cell to cell, and output arrives while the cell
+/// is still running.
+///
+/// Lifecycle: the bridge starts on the first execute() and lives until
+/// shutdown() or destruction. Neither blocks: the old bridge is detached at
+/// once, given a few seconds to stop its kernel, then killed. Executions queue in the kernel and run in
+/// order. interrupt() raises KeyboardInterrupt in the running cell;
+/// restart() gives a fresh kernel (pending cells finish with an error).
+///
+/// When the venv lacks jupyter_client / ipykernel the bridge reports
+/// `unavailable` and execute() returns an empty id from then on, so callers
+/// can fall back to run_code.
+///
+/// Framing: 4-byte big-endian length prefix, UTF-8 JSON body, as PythonWorker.
+/// Main thread only.
+class NotebookKernel : public QObject {
+    Q_OBJECT
+  public:
+    enum class State { Stopped, Starting, Idle, Busy, Unavailable };
+
+    explicit NotebookKernel(QObject* parent = nullptr);
+    ~NotebookKernel() override;
+
+    State state() const { return state_; }
+    /// e.g. "Python 3.12.4"; empty until the kernel is ready.
+    QString language_version() const { return language_version_; }
+
+    /// Queues `code` on the kernel, starting it if needed. Returns the
+    /// execution id carried by output() and finished(); empty when the
+    /// kernel is unavailable.
+    QString execute(const QString& code);
+
+    void interrupt();
+    void restart();
+    /// Stops the kernel; the next execute() starts a fresh one.
+    void shutdown();
+
+  signals:
+    void state_changed(fincept::python::NotebookKernel::State state);
+    /// nbformat-style output: {type: stream|execute_result|display_data|error,
+    /// name, text, image_png (base64), ename, evalue, traceback[]}.
+    void output(const QString& exec_id, const QJsonObject& output);
+    void finished(const QString& exec_id, int execution_count, bool ok);
+    void unavailable(const QString& reason);
+
+  private:
+    void launch();
+    void send(const QJsonObject& req);
+    void on_ready_read();
+    void on_event(const QJsonObject& ev);
+    void on_process_finished();
+    void fail_pending(const QString& reason);
+    void set_state(State s);
+
+    static constexpr int kReadyTimeoutMs = 90'000; // kernel start + first imports
+
+    QProcess* proc_ = nullptr;
+    State state_ = State::Stopped;
+    QString language_version_;
+    QByteArray read_buf_;
+    QVector<QJsonObject> queued_; // requests sent before the bridge is ready
+    QVector<QString> pending_;    // execution ids not yet finished
+};
+
+} // namespace fincept::python
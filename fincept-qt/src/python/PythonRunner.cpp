@@ -1,6 +1,7 @@
 #include "python/PythonRunner.h"
 
 #include "core/logging/Logger.h"
+#include "python/PythonScriptHost.h"
 #include "python/PythonSetupManager.h"
 #include "python/PythonWorker.h"
 #include "storage/secure/SecureStorage.h"
@@ -151,7 +152,7 @@ static const QStringList kManagedCredentialKeys = {
     "ALPHA_VANTAGE_API_KEY", "POLYGON_API_KEY",    "DATABENTO_API_KEY", "FRED_API_KEY",       "NEWSAPI_KEY",
     "BINANCE_API_KEY",       "BINANCE_SECRET_KEY", "KRAKEN_API_KEY",    "KRAKEN_SECRET_KEY",  "IEX_CLOUD_TOKEN",
     "FINNHUB_API_KEY",       "TIINGO_API_KEY",     "QUANDL_API_KEY",    "POLYMARKET_API_KEY", "POLYMARKET_SECRET",
-    "POLYMARKET_PASSPHRASE", "POLYMARKET_WALLET",
+    "POLYMARKET_PASSPHRASE", "POLYMARKET_WALLET",  "TRADIER_API_KEY",
 // Keyed data-connector provider keys (auto-generated; see MCP data_* tools /
 // Settings › Credentials). Kept in sync with CredentialsSection.cpp CRED_KEYS
 // via the same shared X-macro include.
@@ -298,6 +299,37 @@ static QString select_venv_for_script(const QString& script) {
     return "venv-numpy2";
 }
 
+QString PythonRunner::python_for_script(const QString& script) const {
+    const QString venv_py = PythonSetupManager::instance().python_path(select_venv_for_script(script));
+    return QFileInfo::exists(venv_py) ? venv_py : python_path_;
+}
+
+// If the script is inside a sub-package (contains '/'), it runs as -m <module>
+// so Python can resolve relative imports (from .core import ...). This only
+// works if every directory along the path has an `__init__.py` — otherwise the
+// package's `__init__.py` may rely on absolute imports (e.g.
+// `from finagent_core.x import ...`) that resolve against the top-level
+// `sys.path` entry the bare-script invocation sets up via its own
+// `sys.path.insert(...)` bootstrap. When any ancestor dir is missing
+// `__init__.py`, fall back to direct script invocation so the script's
+// bootstrap can run before the failing import.
+QString PythonRunner::module_for_script(const QString& script) const {
+    if (!script.contains('/'))
+        return {};
+    QString check_path = scripts_dir_;
+    QStringList parts = script.split('/');
+    parts.removeLast(); // drop the file itself
+    for (const QString& p : parts) {
+        check_path += "/" + p;
+        if (!QFileInfo::exists(check_path + "/__init__.py"))
+            return {};
+    }
+    QString module = script;
+    module.remove(".py");
+    module.replace('/', '.');
+    return module;
+}
+
 // ── Find Python ──────────────────────────────────────────────────────────────
 
 // Fast, non-blocking detection: checks only file existence (no process spawns)
@@ -470,6 +502,23 @@ void PythonRunner::run(const QString& script, const QStringList& args, Callback
             return;
     }
 
+    // Script-host fast-path: Analytics/ and agents/ scripts run in a warm,
+    // persistent interpreter (PythonScriptHost) instead of a fresh process,
+    // skipping interpreter start-up and the numpy/pandas imports. Streamed
+    // runs stay on the subprocess path, and the host hands the request back
+    // here whenever it can't take it (pool busy, host failed to start, host
+    // died mid-run).
+    if (!on_line && PythonScriptHost::is_eligible(script)) {
+        auto shared_cb = std::make_shared<Callback>(std::move(cb));
+        PythonScriptHost::instance().submit(
+            script, args, [shared_cb](PythonResult r) { (*shared_cb)(std::move(r)); },
+            [this, script, args, shared_cb]() {
+                queue_.enqueue({script, args, *shared_cb, {}});
+                start_next();
+            });
+        return;
+    }
+
     // Queue the request and start if under concurrency limit
     queue_.enqueue({script, args, std::move(cb), std::move(on_line)});
     start_next();
@@ -548,47 +597,18 @@ void PythonRunner::start_next() {
                 python_exe = venv2;
         } else {
             script_path = scripts_dir_ + "/" + req.script;
-            // Route to correct venv based on script name
-            QString venv_name = select_venv_for_script(req.script);
-            QString venv_py = PythonSetupManager::instance().python_path(venv_name);
-            if (QFileInfo::exists(venv_py))
-                python_exe = venv_py;
+            python_exe = python_for_script(req.script);
         }
 
         auto* proc = new QProcess(this);
         QStringList full_args;
 
-        // If the script is inside a sub-package (contains '/'), use -m <module>
-        // so Python can resolve relative imports (from .core import ...). This
-        // only works if every directory along the path has an `__init__.py` —
-        // otherwise the package's `__init__.py` may rely on absolute imports
-        // (e.g. `from finagent_core.x import ...`) that resolve against the
-        // top-level `sys.path` entry the bare-script invocation sets up via
-        // its own `sys.path.insert(...)` bootstrap. When any ancestor dir is
-        // missing `__init__.py`, fall back to direct script invocation so the
-        // script's bootstrap can run before the failing import.
-        bool use_module_form = false;
-        if (!is_code && req.script.contains('/')) {
-            use_module_form = true;
-            QString check_path = scripts_dir_;
-            QStringList parts = req.script.split('/');
-            parts.removeLast(); // drop the file itself
-            for (const QString& p : parts) {
-                check_path += "/" + p;
-                if (!QFileInfo::exists(check_path + "/__init__.py")) {
-                    use_module_form = false;
-                    break;
-                }
-            }
-        }
-        if (use_module_form) {
-            QString module = req.script;
-            module.remove(".py");
-            module.replace('/', '.');
+        // Sub-package scripts run as `-m <module>`; see module_for_script().
+        const QString module = is_code ? QString() : module_for_script(req.script);
+        if (!module.isEmpty())
             full_args << "-m" << module;
-        } else {
+        else
             full_args << script_path;
-        }
         if (!is_code) {
             // Spill large args to temp files to avoid Windows 32KB command-line limit.
             // Python scripts support "@/path/to/file" — they read and delete the file.
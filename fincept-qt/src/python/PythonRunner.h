@@ -47,6 +47,13 @@ class PythonRunner : public QObject {
     /// Resolve path to scripts directory
     QString scripts_dir() const;
 
+    /// Interpreter `script` runs under: its numpy1/numpy2 venv, else python_path().
+    QString python_for_script(const QString& script) const;
+
+    /// Dotted module name when `script` runs as `python -m` (a sub-package
+    /// script with `__init__.py` all the way down); empty otherwise.
+    QString module_for_script(const QString& script) const;
+
     /// Build the standard Python process environment used by all finagent/PyFincept
     /// subprocess spawns. Sets PYTHONIOENCODING, PYTHONDONTWRITEBYTECODE,
     /// PYTHONUNBUFFERED, FINCEPT_DATA_DIR, FINAGENT_DATA_DIR,
@@ -0,0 +1,378 @@
+#include "python/PythonScriptHost.h"
+
+#include "core/logging/Logger.h"
+
+#include <QCoreApplication>
+#include <QDateTime>
+#include <QFileInfo>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QJsonObject>
+#include <QTimer>
+
+#include <algorithm>
+
+namespace fincept::python {
+
+namespace {
+
+/// Same framing as PythonWorker / scripts/script_host.py::_write_frame.
+QByteArray sh_encode_frame(const QJsonObject& obj) {
+    const QByteArray body = QJsonDocument(obj).toJson(QJsonDocument::Compact);
+    const quint32 n = static_cast<quint32>(body.size());
+    QByteArray out;
+    out.reserve(4 + body.size());
+    out.append(static_cast<char>((n >> 24) & 0xff));
+    out.append(static_cast<char>((n >> 16) & 0xff));
+    out.append(static_cast<char>((n >> 8) & 0xff));
+    out.append(static_cast<char>(n & 0xff));
+    out.append(body);
+    return out;
+}
+
+} // namespace
+
+PythonScriptHost& PythonScriptHost::instance() {
+    static PythonScriptHost s;
+    return s;
+}
+
+PythonScriptHost::~PythonScriptHost() {
+    stop();
+}
+
+bool PythonScriptHost::is_eligible(const QString& script) {
+    return script.startsWith(QLatin1String("Analytics/")) || script.startsWith(QLatin1String("agents/"));
+}
+
+void PythonScriptHost::set_enabled(bool enabled) {
+    if (enabled_ == enabled)
+        return;
+    enabled_ = enabled;
+    LOG_INFO("PythonScriptHost", enabled ? "Enabled" : "Disabled — scripts run as subprocesses");
+    if (!enabled)
+        stop();
+}
+
+void PythonScriptHost::set_timeout(const QString& script_prefix, int timeout_ms) {
+    if (timeout_ms > 0)
+        timeouts_.insert(script_prefix, timeout_ms);
+    else
+        timeouts_.remove(script_prefix);
+}
+
+// ── Submit ───────────────────────────────────────────────────────────────────
+
+void PythonScriptHost::submit(const QString& script, const QStringList& args, PythonRunner::Callback cb,
+                              std::function<void()> fallback) {
+    auto& runner = PythonRunner::instance();
+    const QString python = runner.python_for_script(script);
+    if (!enabled_ || QCoreApplication::closingDown() || python.isEmpty() ||
+        start_failures_.value(python) >= kMaxStartFailures ||
+        !QFileInfo::exists(runner.scripts_dir() + "/script_host.py")) {
+        fallback();
+        return;
+    }
+
+    Job job;
+    job.id = next_id_++;
+    job.script = script;
+    job.module = runner.module_for_script(script);
+    job.args = args;
+    job.cb = std::move(cb);
+    job.fallback = std::move(fallback);
+    qsizetype matched = -1;
+    for (auto it = timeouts_.constBegin(); it != timeouts_.constEnd(); ++it) {
+        if (script.startsWith(it.key()) && it.key().size() > matched) {
+            matched = it.key().size();
+            job.timeout_ms = it.value();
+        }
+    }
+
+    auto& pool = hosts_[python];
+    for (Host* h : pool) {
+        if (h->ready && !h->retiring && h->job_id == 0) {
+            send(h, std::move(job));
+            return;
+        }
+    }
+
+    // Wait for a host that is still starting, one job per starting host, or
+    // start another one. A full, busy pool never queues: the script runs as a
+    // subprocess so a long agent run cannot hold up analytics calls.
+    auto& waiting = waiting_[python];
+    const auto starting = std::count_if(pool.begin(), pool.end(), [](const Host* h) { return !h->ready; });
+    if (waiting.size() < starting || (pool.size() < kHostsPerInterpreter && launch(python))) {
+        waiting.enqueue(std::move(job));
+        return;
+    }
+    job.fallback();
+}
+
+// ── Host lifecycle ───────────────────────────────────────────────────────────
+
+PythonScriptHost::Host* PythonScriptHost::launch(const QString& python) {
+    auto& runner = PythonRunner::instance();
+    const QString scripts_dir = runner.scripts_dir();
+
+    auto* host = new Host;
+    host->python = python;
+    host->proc = new QProcess(this);
+    QProcess* proc = host->proc;
+    // The host outlives its process object only until Qt deletes it.
+    connect(proc, &QObject::destroyed, [host]() { delete host; });
+
+    proc->setProcessEnvironment(runner.build_python_env());
+    proc->setWorkingDirectory(scripts_dir);
+    proc->setReadChannel(QProcess::StandardOutput);
+
+#ifdef _WIN32
+    proc->setCreateProcessArgumentsModifier([](QProcess::CreateProcessArguments* cpa) {
+        cpa->flags |= 0x08000000; // CREATE_NO_WINDOW
+    });
+#endif
+
+    connect(proc, &QProcess::readyReadStandardOutput, this, [this, host]() {
+        host->read_buf.append(host->proc->readAllStandardOutput());
+        while (host->read_buf.size() >= 4) {
+            const auto* b = reinterpret_cast<const uchar*>(host->read_buf.constData());
+            const quint32 n = (quint32(b[0]) << 24) | (quint32(b[1]) << 16) | (quint32(b[2]) << 8) | quint32(b[3]);
+            if (n > 64u * 1024u * 1024u) {
+                LOG_ERROR("PythonScriptHost", QString("Frame size %1 exceeds 64MB cap — killing host").arg(n));
+                host->read_buf.clear();
+                host->proc->kill();
+                return;
+            }
+            if (static_cast<quint32>(host->read_buf.size()) < 4 + n)
+                return;
+            const QByteArray body = host->read_buf.mid(4, static_cast<int>(n));
+            host->read_buf.remove(0, 4 + static_cast<int>(n));
+            const QJsonDocument doc = QJsonDocument::fromJson(body);
+            if (doc.isObject())
+                on_frame(host, doc.object());
+        }
+    });
+    // script_host.py captures each script's own stderr; what reaches the
+    // process stderr is import noise and native-library chatter.
+    connect(proc, &QProcess::readyReadStandardError, this, [host]() {
+        const QByteArray err = host->proc->readAllStandardError().trimmed();
+        if (!err.isEmpty())
+            LOG_DEBUG("PythonScriptHost", QString("stderr: %1").arg(QString::fromUtf8(err.left(500))));
+    });
+    connect(proc, QOverload<int, QProcess::ExitStatus>::of(&QProcess::finished), this,
+            [this, host](int, QProcess::ExitStatus) { on_finished(host); });
+    connect(proc, &QProcess::errorOccurred, this, [this, host](QProcess::ProcessError err) {
+        if (err == QProcess::FailedToStart)
+            on_finished(host); // finished is not emitted for a process that never ran
+    });
+
+    QTimer::singleShot(kReadyTimeoutMs, proc, [host]() {
+        if (!host->ready && host->proc->state() != QProcess::NotRunning) {
+            LOG_WARN("PythonScriptHost", "Host handshake timed out — killing");
+            host->proc->kill();
+        }
+    });
+
+    hosts_[python].append(host);
+    LOG_INFO("PythonScriptHost", QString("Launching host %1/%2: %3 script_host.py")
+                                     .arg(hosts_[python].size())
+                                     .arg(kHostsPerInterpreter)
+                                     .arg(python));
+    proc->start(python, {scripts_dir + "/script_host.py", scripts_dir});
+    return host;
+}
+
+void PythonScriptHost::send(Host* host, Job job) {
+    auto& runner = PythonRunner::instance();
+    const QProcessEnvironment env = runner.build_python_env();
+    QJsonObject env_obj;
+    for (const QString& key : env.keys())
+        env_obj[key] = env.value(key);
+
+    QJsonObject req;
+    req["id"] = job.id;
+    req["script"] = runner.scripts_dir() + "/" + job.script;
+    if (!job.module.isEmpty())
+        req["module"] = job.module;
+    req["args"] = QJsonArray::fromStringList(job.args);
+    req["env"] = env_obj;
+    req["cwd"] = runner.scripts_dir();
+
+    const int id = job.id;
+    const int timeout_ms = job.timeout_ms;
+    host->job_id = id;
+    job.start_ms = QDateTime::currentMSecsSinceEpoch();
+    running_.insert(id, std::move(job));
+    host->proc->write(sh_encode_frame(req));
+
+    if (timeout_ms <= 0)
+        return;
+    // The job is resolved here, so on_finished finds the host idle and only
+    // refills the slot.
+    QTimer::singleShot(timeout_ms, host->proc, [this, host, id]() {
+        if (host->job_id != id || host->proc->state() == QProcess::NotRunning)
+            return;
+        Job timed_out = running_.take(id);
+        host->job_id = 0;
+        host->timed_out = true;
+        const QString msg =
+            QString("Script %1 timed out after %2s").arg(timed_out.script).arg(timed_out.timeout_ms / 1000);
+        LOG_WARN("PythonScriptHost", msg + " — killing host");
+        host->proc->kill();
+        PythonResult result;
+        result.error = msg;
+        timed_out.cb(std::move(result));
+    });
+}
+
+void PythonScriptHost::pump(const QString& python) {
+    auto& waiting = waiting_[python];
+    for (Host* h : hosts_.value(python)) {
+        if (waiting.isEmpty())
+            return;
+        if (h->ready && !h->retiring && h->job_id == 0)
+            send(h, waiting.dequeue());
+    }
+}
+
+void PythonScriptHost::on_frame(Host* host, const QJsonObject& obj) {
+    if (!host->ready) {
+        if (!obj.value("ready").toBool())
+            return;
+        host->ready = true;
+        start_failures_[host->python] = 0;
+        LOG_INFO("PythonScriptHost", QString("Host ready (pid=%1)").arg(obj.value("pid").toInt()));
+        pump(host->python);
+        return;
+    }
+
+    const int id = obj.value("id").toInt();
+    if (id == 0 || id != host->job_id)
+        return; // shutdown ack or stray frame
+    Job job = running_.take(id);
+    host->job_id = 0;
+    if (++host->runs >= kMaxRunsPerHost)
+        retire(host);
+
+    const qint64 duration_ms = QDateTime::currentMSecsSinceEpoch() - job.start_ms;
+    if (!obj.value("ok").toBool()) {
+        LOG_WARN("PythonScriptHost", QString("Host could not run %1 (%2) — running as subprocess")
+                                         .arg(job.script, obj.value("error").toString()));
+        job.fallback();
+    } else {
+        // Same shaping as the subprocess path in PythonRunner::start_next.
+        PythonResult result;
+        result.exit_code = obj.value("exit_code").toInt();
+        const QString stdout_str = obj.value("stdout").toString();
+        const QString json_out = extract_json(stdout_str);
+        result.success = (result.exit_code == 0 && !json_out.isEmpty());
+        result.output = json_out.isEmpty() ? stdout_str : json_out;
+        result.error = obj.value("stderr").toString();
+        if (!result.success) {
+            LOG_ERROR("PythonScriptHost", QString("Script %1 failed in %2ms (exit=%3): %4")
+                                              .arg(job.script)
+                                              .arg(duration_ms)
+                                              .arg(result.exit_code)
+                                              .arg(result.error.left(200)));
+        } else {
+            LOG_DEBUG("PythonScriptHost", QString("Script %1 finished in %2ms").arg(job.script).arg(duration_ms));
+        }
+        job.cb(std::move(result));
+    }
+    pump(host->python);
+}
+
+void PythonScriptHost::on_finished(Host* host) {
+    auto& pool = hosts_[host->python];
+    if (!pool.removeAll(host))
+        return; // already handled (errorOccurred + finished)
+
+    QVector<std::function<void()>> fallbacks;
+    if (host->job_id != 0) {
+        Job job = running_.take(host->job_id);
+        LOG_WARN("PythonScriptHost", QString("Host exited while running %1 — running as subprocess").arg(job.script));
+        fallbacks.append(std::move(job.fallback));
+    }
+    if (!host->ready && !host->retiring) {
+        const int failures = ++start_failures_[host->python];
+        LOG_WARN("PythonScriptHost", QString("Host for %1 failed to start (%2/%3)")
+                                         .arg(host->python)
+                                         .arg(failures)
+                                         .arg(kMaxStartFailures));
+    }
+    host->proc->deleteLater();
+
+    // A host killed for a stuck script was healthy otherwise: refill its slot.
+    const bool can_launch = enabled_ && !QCoreApplication::closingDown() &&
+                            start_failures_.value(host->python) < kMaxStartFailures;
+    if (host->timed_out && can_launch && pool.size() < kHostsPerInterpreter)
+        launch(host->python);
+
+    // Jobs that were waiting on this host: start a replacement, or run them as
+    // subprocesses once the interpreter keeps failing.
+    auto& waiting = waiting_[host->python];
+    auto starting = std::count_if(pool.begin(), pool.end(), [](const Host* h) { return !h->ready; });
+    while (waiting.size() > starting) {
+        if (can_launch && pool.size() < kHostsPerInterpreter && launch(host->python)) {
+            ++starting;
+            continue;
+        }
+        fallbacks.append(std::move(waiting.dequeue().fallback));
+    }
+
+    if (!QCoreApplication::closingDown()) {
+        for (auto& f : fallbacks)
+            f();
+    }
+}
+
+void PythonScriptHost::retire(Host* host) {
+    host->retiring = true;
+    QJsonObject req;
+    req["id"] = 0;
+    req["action"] = QStringLiteral("shutdown");
+    host->proc->write(sh_encode_frame(req));
+    host->proc->closeWriteChannel();
+}
+
+void PythonScriptHost::stop() {
+    // A script already running may have had side effects; report it instead of
+    // running it a second time as a subprocess.
+    QVector<PythonRunner::Callback> interrupted;
+    for (auto it = running_.begin(); it != running_.end(); ++it)
+        interrupted.append(std::move(it.value().cb));
+    running_.clear();
+    QVector<std::function<void()>> fallbacks;
+    for (auto& waiting : waiting_) {
+        while (!waiting.isEmpty())
+            fallbacks.append(std::move(waiting.dequeue().fallback));
+    }
+
+    for (auto& pool : hosts_) {
+        for (Host* h : pool) {
+            h->proc->disconnect(this);
+            if (h->proc->state() != QProcess::NotRunning) {
+                if (h->ready && h->job_id == 0) {
+                    retire(h);
+                    h->proc->waitForFinished(1'000); // shutdown-only blocking call, as PythonWorker::stop
+                }
+                if (h->proc->state() != QProcess::NotRunning)
+                    h->proc->kill();
+            }
+            h->proc->deleteLater();
+        }
+    }
+    hosts_.clear();
+
+    if (!QCoreApplication::closingDown()) {
+        for (auto& cb : interrupted) {
+            PythonResult result;
+            result.error = QStringLiteral("Script host stopped while the script was running");
+            cb(std::move(result));
+        }
+        for (auto& f : fallbacks)
+            f();
+    }
+}
+
+} // namespace fincept::python
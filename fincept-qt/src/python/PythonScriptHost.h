@@ -0,0 +1,115 @@
+#pragma once
+
+#include "python/PythonRunner.h"
+
+#include <QHash>
+#include <QObject>
+#include <QProcess>
+#include <QQueue>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+#include <functional>
+
+namespace fincept::python {
+
+/// Pool of persistent script hosts — long-lived interpreters running
+/// `scripts/script_host.py` — that PythonRunner::run() hands Analytics/ and
+/// agents/ scripts to instead of spawning `python <script>` per call. A warm
+/// host skips interpreter start-up and the numpy/pandas/scipy imports
+/// (200–800 ms per call); the result is the same PythonResult the subprocess
+/// path produces.
+///
+/// One pool per interpreter (PythonRunner::python_for_script keeps the
+/// numpy1/numpy2 venv routing), up to kHostsPerInterpreter hosts each, one
+/// script in flight per host. Hosts start lazily on first use and retire
+/// after kMaxRunsPerHost scripts to bound memory growth.
+///
+/// Fallback: whenever a host cannot take the script — pool disabled, all
+/// hosts busy, the interpreter failed to start kMaxStartFailures times in a
+/// row, or the host died mid-run — the caller's `fallback` runs instead
+/// (PythonRunner queues the normal subprocess). Exactly one of `cb` and
+/// `fallback` is invoked.
+///
+/// Timeout: opt-in per script (set_timeout) — agent runs and backtests are
+/// legitimately long, the same reason PythonRunner's subprocess path has no
+/// watchdog. A script past its limit gets its host killed and `cb` a timeout
+/// error (never a rerun); a replacement host starts in the freed slot. A
+/// script running when the pool stops gets an error too, so nothing runs
+/// twice; only scripts that never started fall back.
+///
+/// Framing: 4-byte big-endian length prefix, UTF-8 JSON body, as PythonWorker.
+/// Main thread only (PythonRunner::run marshals before calling).
+class PythonScriptHost : public QObject {
+    Q_OBJECT
+  public:
+    static PythonScriptHost& instance();
+
+    /// Scripts routed through the pool (Analytics/ and agents/ trees).
+    static bool is_eligible(const QString& script);
+
+    void submit(const QString& script, const QStringList& args, PythonRunner::Callback cb,
+                std::function<void()> fallback);
+
+    bool is_enabled() const { return enabled_; }
+    /// Disabling stops every host; later scripts take the subprocess path.
+    void set_enabled(bool enabled);
+
+    /// Stops every host (sent automatically on app exit via destructor).
+    void stop();
+
+    /// Kills a script under `script_prefix` (e.g. "Analytics/foo.py" or
+    /// "Analytics/") still running after `timeout_ms`; the longest matching
+    /// prefix wins, 0 removes the limit. No script has one by default.
+    void set_timeout(const QString& script_prefix, int timeout_ms);
+
+  private:
+    PythonScriptHost() = default;
+    ~PythonScriptHost() override;
+    Q_DISABLE_COPY(PythonScriptHost)
+
+    struct Job {
+        int id = 0;
+        QString script;
+        QString module;
+        QStringList args;
+        PythonRunner::Callback cb;
+        std::function<void()> fallback;
+        qint64 start_ms = 0;
+        int timeout_ms = 0; // 0 = none
+    };
+
+    struct Host {
+        QProcess* proc = nullptr;
+        QString python;
+        bool ready = false;
+        bool retiring = false;
+        bool timed_out = false;
+        int job_id = 0; // 0 = idle
+        int runs = 0;
+        QByteArray read_buf;
+    };
+
+    Host* launch(const QString& python);
+    void send(Host* host, Job job);
+    void pump(const QString& python);
+    void on_frame(Host* host, const QJsonObject& obj);
+    void on_finished(Host* host);
+    void retire(Host* host);
+
+    static constexpr int kHostsPerInterpreter = 2;
+    static constexpr int kMaxRunsPerHost = 100;
+    static constexpr int kMaxStartFailures = 3;
+    static constexpr int kReadyTimeoutMs = 20'000; // numpy/pandas/scipy warm imports
+
+    bool enabled_ = true;
+    int next_id_ = 1;
+    QHash<QString, QVector<Host*>> hosts_;  // by interpreter path
+    QHash<QString, QQueue<Job>> waiting_;   // jobs waiting for a starting host
+    QHash<int, Job> running_;               // by job id
+    QHash<QString, int> start_failures_;    // consecutive hosts that died before handshake
+    QHash<QString, int> timeouts_;          // script prefix → ms (set_timeout)
+};
+
+} // namespace fincept::python
@@ -10,6 +10,9 @@
 #include <QFile>
 #include <QFileInfo>
 #include <QFuture>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QJsonObject>
 #include <QMetaObject>
 #include <QProcess>
 #include <QRegularExpression>
@@ -23,6 +26,61 @@
 
 namespace fincept::python {
 
+// PEP 503-ish normalisation: lowercase + collapse `-`/`.` to `_`.
+static QString normalise_package_name(const QString& name) {
+    QString n = name.toLower();
+    n.replace('-', '_');
+    n.replace('.', '_');
+    return n;
+}
+
+// Strip version specifiers and extras: "numpy>=1.26,<2.0" → "numpy",
+// "scikit-learn>=1.4" → "scikit_learn".
+static QString requirement_name(const QString& spec) {
+    static const QRegularExpression kVersionRe(R"([><=!~\s\[;].*)");
+    QString base = spec;
+    base.remove(kVersionRe);
+    return normalise_package_name(base.trimmed());
+}
+
+// SHA-256 of each UV release archive for kUvVersion, copied from that
+// release's sha256.sum and checked in, so the download origin cannot serve a
+// tampered archive together with a matching checksum. Update together with
+// kUvVersion. A target with no hash here is not installed online — setup
+// stops and points at the offline bundle instead.
+struct UvArchivePin {
+    const char* target;
+    const char* sha256;
+};
+static constexpr UvArchivePin kUvArchiveSha256[] = {
+    {"x86_64-pc-windows-msvc", ""},
+    {"x86_64-apple-darwin", ""},
+    {"aarch64-apple-darwin", ""},
+    {"x86_64-unknown-linux-musl", ""},
+    {"aarch64-unknown-linux-musl", ""},
+};
+
+static QString pinned_uv_sha256(const QString& target) {
+    for (const auto& pin : kUvArchiveSha256) {
+        if (target == QLatin1String(pin.target))
+            return QString::fromLatin1(pin.sha256).toLower();
+    }
+    return {};
+}
+
+// Hash-pinned lock beside a requirements file — `uv pip compile
+// --generate-hashes` output, e.g. requirements-numpy2.lock.txt. When present,
+// online installs use it with --require-hashes so every wheel is checked.
+static QString hash_lock_for(const QString& req_path) {
+    const QFileInfo fi(req_path);
+    const QString lock = fi.dir().filePath(fi.completeBaseName() + ".lock.txt");
+    return QFileInfo::exists(lock) ? lock : QString();
+}
+
+static const char* kOfflineBundleHint =
+    "On a restricted network, put an offline setup bundle (setup-bundle/manifest.json) beside the app or point "
+    "FINCEPT_SETUP_BUNDLE at one, then retry.";
+
 PythonSetupManager& PythonSetupManager::instance() {
     static PythonSetupManager s;
     return s;
@@ -95,7 +153,7 @@ QStringList PythonSetupManager::uv_env_extra() const {
     const int cores = QThread::idealThreadCount();
     const int installs = std::clamp(cores, 2, 8);
     const int downloads = std::clamp(cores * 2, 4, 8);
-    return {
+    QStringList env = {
         "UV_PYTHON_INSTALL_DIR=" + root + "/python",
         "UV_CACHE_DIR=" + root + "/uv-cache",
         "UV_LINK_MODE=hardlink",
@@ -104,6 +162,18 @@ QStringList PythonSetupManager::uv_env_extra() const {
         "UV_CONCURRENT_INSTALLS=" + QString::number(installs),
         "UV_HTTP_TIMEOUT=120",
     };
+    if (offline())
+        env << "UV_OFFLINE=1"; // never touch the network, even for a cache miss
+    return env;
+}
+
+QStringList PythonSetupManager::pip_source_args() const {
+    if (!offline())
+        return {};
+    QStringList args = {"--offline", "--no-index"};
+    for (const auto& dir : bundle_.wheel_dirs)
+        args << "--find-links" << dir;
+    return args;
 }
 
 /// .packages_installed stores the SHA-256 hex of the last successfully-installed requirements file.
@@ -161,30 +231,18 @@ bool PythonSetupManager::verify_packages_installed(const QString& venv_name, con
         return false;
     }
 
-    // PEP 503-ish normalisation: lowercase + collapse `-`/`.` to `_`.
-    auto normalise = [](const QString& name) -> QString {
-        QString n = name.toLower();
-        n.replace('-', '_');
-        n.replace('.', '_');
-        return n;
-    };
-
     QSet<QString> installed;
     const QString output = QString::fromUtf8(proc.readAllStandardOutput());
     for (const QString& line : output.split('\n', Qt::SkipEmptyParts)) {
         const QString name = line.split(' ', Qt::SkipEmptyParts).value(0);
         if (!name.isEmpty())
-            installed.insert(normalise(name));
+            installed.insert(normalise_package_name(name));
     }
 
-    // Check each required package.  Strip version specifiers and extras before
-    // comparing: "numpy>=1.26,<2.0" → "numpy", "scikit-learn>=1.4" → "scikit_learn".
-    static const QRegularExpression kVersionRe(R"([><=!~\s\[].*)");
+    // Check each required package by its bare name.
     QStringList missing;
     for (const QString& pkg : expected) {
-        QString base = pkg;
-        base.remove(kVersionRe);
-        base = normalise(base.trimmed());
+        const QString base = requirement_name(pkg);
         if (base.isEmpty())
             continue;
         if (!installed.contains(base))
@@ -204,6 +262,55 @@ bool PythonSetupManager::verify_packages_installed(const QString& venv_name, con
     return true;
 }
 
+QJsonObject PythonSetupManager::package_report(const QString& venv_name, const QString& requirements_file) const {
+    QJsonObject out{{"venv", venv_name}, {"requirements", requirements_file}};
+    const QString req_path = find_requirements_file(requirements_file);
+    const QStringList expected = req_path.isEmpty() ? QStringList() : read_packages_from_file(req_path);
+    out["required"] = int(expected.size());
+    if (expected.isEmpty()) {
+        out["error"] = "requirements file not found or empty";
+        return out;
+    }
+
+    QHash<QString, QString> versions; // normalised name → installed version
+    QProcess proc;
+    QProcessEnvironment env = QProcessEnvironment::systemEnvironment();
+    env.insert("UV_PYTHON_INSTALL_DIR", install_dir() + "/python");
+    proc.setProcessEnvironment(env);
+#ifdef _WIN32
+    proc.setCreateProcessArgumentsModifier([](QProcess::CreateProcessArguments* cpa) {
+        cpa->flags |= 0x08000000; // CREATE_NO_WINDOW
+    });
+#endif
+    proc.start(uv_path(), {"pip", "list", "--format", "json", "--python", python_path(venv_name)});
+    if (!proc.waitForFinished(90000) || proc.exitCode() != 0) {
+        out["error"] = "uv pip list failed: " + QString::fromUtf8(proc.readAllStandardError()).trimmed().left(300);
+    } else {
+        const auto list = QJsonDocument::fromJson(proc.readAllStandardOutput()).array();
+        for (const auto& v : list) {
+            const QJsonObject o = v.toObject();
+            versions.insert(normalise_package_name(o.value("name").toString()), o.value("version").toString());
+        }
+        out["installed"] = int(versions.size());
+    }
+
+    QJsonArray packages;
+    QJsonArray missing;
+    for (const QString& spec : expected) {
+        const QString name = requirement_name(spec);
+        if (name.isEmpty())
+            continue;
+        const QString version = versions.value(name);
+        if (version.isEmpty())
+            missing.append(name);
+        packages.append(QJsonObject{
+            {"name", name}, {"spec", spec}, {"version", version.isEmpty() ? QJsonValue() : QJsonValue(version)}});
+    }
+    out["missing"] = missing;
+    out["packages"] = packages;
+    return out;
+}
+
 SetupStatus PythonSetupManager::check_status() const {
     SetupStatus status;
     status.install_dir = install_dir();
@@ -372,10 +479,35 @@ SetupStatus PythonSetupManager::check_status() const {
     return status;
 }
 
-void PythonSetupManager::run_setup() {
+void PythonSetupManager::run_setup(const QString& bundle_dir) {
     LOG_INFO("PythonSetup", "=== run_setup START ===");
     QPointer<PythonSetupManager> self = this;
 
+    bundle_ = {};
+    const QString dir = bundle_dir.isEmpty() ? find_offline_bundle() : bundle_dir;
+    if (!dir.isEmpty()) {
+        const QString err = load_bundle(dir, bundle_);
+        if (!err.isEmpty()) {
+            bundle_ = {};
+            const QString msg = "Offline bundle unusable: " + err;
+            LOG_ERROR("PythonSetup", msg + " (" + dir + ")");
+            emit_progress("verify", 0, msg, true, false,
+                          "Fix the bundle's manifest.json, or remove " + dir + " to install online.");
+            QMetaObject::invokeMethod(
+                this,
+                [self, msg]() {
+                    if (self)
+                        emit self->setup_complete(false, msg);
+                },
+                Qt::QueuedConnection);
+            return;
+        }
+        LOG_INFO("PythonSetup", QString("Offline mode — bundle %1 (%2 artifact(s) for %3)")
+                                    .arg(bundle_.dir)
+                                    .arg(bundle_.artifacts.size())
+                                    .arg(platform_key()));
+    }
+
     (void)QtConcurrent::run([self]() {
         if (!self)
             return;
@@ -399,12 +531,28 @@ void PythonSetupManager::run_setup() {
                                     .arg(status.venv_numpy1_ready ? "YES" : "NO")
                                     .arg(status.venv_numpy2_ready ? "YES" : "NO"));
 
+        const bool offline = self->offline();
+        // What to suggest when a step fails: online, the offline bundle; from a
+        // bundle, re-running is pointless until the bundle itself is fixed.
+        const QString hint = offline ? QString() : QString::fromLatin1(kOfflineBundleHint);
+
+        // ── Step 0 (offline): verify every bundle file against the manifest ──
+        // Skipped when nothing is left to install from it.
+        const bool installing = status.needs_setup || status.needs_package_sync;
+        if (offline && installing && !self->verify_bundle()) {
+            fail("Offline bundle failed verification");
+            return;
+        }
+
         // ── Step 1: Download UV standalone binary (~13MB) ────────────────────
         if (!status.uv_installed) {
-            self->emit_progress("uv", 0, "Downloading UV package manager...");
-            if (!self->download_uv()) {
-                self->emit_progress("uv", 0, "Failed to download UV", true);
-                fail("UV download failed");
+            self->emit_progress("uv", 0,
+                                offline ? "Installing UV from the offline bundle..."
+                                        : "Downloading UV package manager...");
+            const QString err = offline ? self->install_uv_from_bundle() : self->download_uv();
+            if (!err.isEmpty()) {
+                self->emit_progress("uv", 0, "Failed to install UV: " + err, true, !offline, hint);
+                fail("UV install failed: " + err);
                 return;
             }
             self->emit_progress("uv", 100, "UV ready");
@@ -414,9 +562,11 @@ void PythonSetupManager::run_setup() {
 
         // ── Step 2: Install Python via UV ────────────────────────────────────
         if (!status.python_installed) {
-            self->emit_progress("python", 0, "Installing Python 3.11 via UV...");
-            if (!self->install_python_via_uv()) {
-                self->emit_progress("python", 0, "Failed to install Python", true);
+            self->emit_progress("python", 0,
+                                offline ? "Installing Python 3.11 from the offline bundle..."
+                                        : "Installing Python 3.11 via UV...");
+            if (!(offline ? self->install_python_from_bundle() : self->install_python_via_uv())) {
+                self->emit_progress("python", 0, "Failed to install Python", true, !offline, hint);
                 fail("Python installation failed");
                 return;
             }
@@ -451,7 +601,7 @@ void PythonSetupManager::run_setup() {
                 f2.waitForFinished();
 
             if (!v1_ok || !v2_ok) {
-                self->emit_progress("venv", 0, "Failed to create virtual environments", true);
+                self->emit_progress("venv", 0, "Failed to create virtual environments", true, true);
                 fail("Venv creation failed");
                 return;
             }
@@ -484,22 +634,22 @@ void PythonSetupManager::run_setup() {
         // Run both package installations in parallel
         QFuture<void> pf1, pf2;
         if (need_pkg1) {
-            pf1 = QtConcurrent::run([self, &p1_ok]() {
+            pf1 = QtConcurrent::run([self, &p1_ok, offline, hint]() {
                 p1_ok = self && self->install_packages("venv-numpy1", "requirements-numpy1.txt");
                 if (self) {
                     self->emit_progress("packages-numpy1", p1_ok ? 100 : 0,
                                         p1_ok ? "NumPy 1.x packages installed" : "NumPy 1.x package install failed",
-                                        !p1_ok);
+                                        !p1_ok, !p1_ok && !offline, hint);
                 }
             });
         }
         if (need_pkg2) {
-            pf2 = QtConcurrent::run([self, &p2_ok]() {
+            pf2 = QtConcurrent::run([self, &p2_ok, offline, hint]() {
                 p2_ok = self && self->install_packages("venv-numpy2", "requirements-numpy2.txt");
                 if (self) {
                     self->emit_progress("packages-numpy2", p2_ok ? 100 : 0,
                                         p2_ok ? "NumPy 2.x packages installed" : "NumPy 2.x package install failed",
-                                        !p2_ok);
+                                        !p2_ok, !p2_ok && !offline, hint);
                 }
             });
         }
@@ -536,26 +686,49 @@ void PythonSetupManager::run_setup() {
     });
 }
 
-bool PythonSetupManager::download_uv() {
+QString PythonSetupManager::uv_target() {
+#ifdef _WIN32
+    return "x86_64-pc-windows-msvc";
+#elif defined(__APPLE__)
+    const QString arch = QSysInfo::currentCpuArchitecture();
+    return (arch == "arm64") ? "aarch64-apple-darwin" : "x86_64-apple-darwin";
+#else
+    const QString arch = QSysInfo::currentCpuArchitecture();
+    return (arch == "aarch64" || arch == "arm64") ? "aarch64-unknown-linux-musl" : "x86_64-unknown-linux-musl";
+#endif
+}
+
+QString PythonSetupManager::platform_key() {
+#ifdef _WIN32
+    const QString os = "windows";
+#elif defined(__APPLE__)
+    const QString os = "macos";
+#else
+    const QString os = "linux";
+#endif
+    const QString arch = QSysInfo::currentCpuArchitecture();
+    return os + "-" + ((arch == "arm64" || arch == "aarch64") ? QString("aarch64") : arch);
+}
+
+QString PythonSetupManager::download_uv() {
     QString dir = install_dir() + "/uv";
     QDir().mkpath(dir);
 
     // Determine platform-specific archive
-    QString target;
-    QString ext;
+    const QString target = uv_target();
 #ifdef _WIN32
-    target = "x86_64-pc-windows-msvc";
-    ext = "zip";
-#elif defined(__APPLE__)
-    QString arch = QSysInfo::currentCpuArchitecture();
-    target = (arch == "arm64") ? "aarch64-apple-darwin" : "x86_64-apple-darwin";
-    ext = "tar.gz";
+    const QString ext = "zip";
 #else
-    QString arch = QSysInfo::currentCpuArchitecture();
-    target = (arch == "aarch64" || arch == "arm64") ? "aarch64-unknown-linux-musl" : "x86_64-unknown-linux-musl";
-    ext = "tar.gz";
+    const QString ext = "tar.gz";
 #endif
 
+    const QString expected = pinned_uv_sha256(target);
+    if (expected.isEmpty()) {
+        const QString msg = QString("no pinned checksum for UV %1 on %2").arg(kUvVersion, target);
+        LOG_ERROR("PythonSetup", msg);
+        return msg;
+    }
+
     QString archive_name = QString("uv-%1.%2").arg(target, ext);
     QString url = QString("https://github.com/astral-sh/uv/releases/download/%1/%2").arg(kUvVersion, archive_name);
     QString archive_path = dir + "/" + archive_name;
@@ -566,11 +739,39 @@ bool PythonSetupManager::download_uv() {
     QString err = download_file(url, archive_path);
     if (!err.isEmpty()) {
         LOG_ERROR("PythonSetup", "UV download failed: " + err);
-        return false;
+        return err;
+    }
+
+    // Check the archive against the pinned checksum before running anything
+    // from it.
+    emit_progress("uv", 50, "Verifying UV checksum...");
+    const QString actual = file_sha256(archive_path);
+    if (actual != expected) {
+        QFile::remove(archive_path);
+        const QString msg =
+            QString("UV archive checksum mismatch (expected %1…, got %2…)").arg(expected.left(12), actual.left(12));
+        LOG_ERROR("PythonSetup", msg);
+        return msg;
     }
 
     emit_progress("uv", 60, "Extracting UV...");
+    const bool ok = extract_uv(archive_path, target);
+    QFile::remove(archive_path);
+    return ok ? QString() : QString("extracting or running the UV binary failed");
+}
 
+QString PythonSetupManager::install_uv_from_bundle() {
+    if (bundle_.uv_archive.isEmpty())
+        return "the offline bundle has no UV archive for " + platform_key();
+    QDir().mkpath(install_dir() + "/uv");
+    emit_progress("uv", 60, "Extracting UV from the offline bundle...");
+    if (!extract_uv(bundle_.uv_archive, uv_target()))
+        return "extracting or running the bundled UV binary failed";
+    return {};
+}
+
+bool PythonSetupManager::extract_uv(const QString& archive_path, const QString& target) {
+    const QString dir = install_dir() + "/uv";
     // Extract — prefer tar.exe (ships with Windows 10 1803+, much faster than
     // PowerShell Expand-Archive). Fall back to PowerShell on older systems.
 #ifdef _WIN32
@@ -612,8 +813,6 @@ bool PythonSetupManager::download_uv() {
     run_command("chmod", {"+x", uv_path()});
 #endif
 
-    QFile::remove(archive_path);
-
     if (!QFileInfo::exists(uv_path())) {
         LOG_ERROR("PythonSetup", "UV binary not found after extraction: " + uv_path());
         return false;
@@ -658,6 +857,43 @@ bool PythonSetupManager::install_python_via_uv() {
     return true;
 }
 
+bool PythonSetupManager::install_python_from_bundle() {
+    if (bundle_.python_archive.isEmpty()) {
+        LOG_ERROR("PythonSetup", "Offline bundle has no Python archive for " + platform_key());
+        return false;
+    }
+
+    // Lay the interpreter out where UV would have put it, so base_python_path()
+    // finds it by the same cpython-3.11* scan.
+    const QString dest = install_dir() + "/python/cpython-" + kPythonVersion + "-" + platform_key();
+    if (QFileInfo::exists(dest))
+        QDir(dest).removeRecursively(); // leftover from an interrupted extract
+    QDir().mkpath(dest);
+
+    emit_progress("python", 30, "Extracting Python...");
+    // install_only archives (python-build-standalone) hold one top-level python/ dir.
+#ifdef _WIN32
+    const QString tar = "tar.exe";
+#else
+    const QString tar = "tar";
+#endif
+    if (!run_command(tar, {"-xzf", bundle_.python_archive, "-C", dest, "--strip-components=1"})) {
+        LOG_ERROR("PythonSetup", "Failed to extract bundled Python: " + bundle_.python_archive);
+        return false;
+    }
+
+    emit_progress("python", 80, "Verifying Python...");
+    cached_python_path_.clear();
+    QString py = base_python_path();
+    if (py.isEmpty() || !QFileInfo::exists(py)) {
+        LOG_ERROR("PythonSetup", "Python not found after extracting the bundle into " + dest);
+        return false;
+    }
+
+    LOG_INFO("PythonSetup", "Python installed from bundle at: " + py);
+    return true;
+}
+
 bool PythonSetupManager::create_venv(const QString& venv_name) {
     QString venv_path = install_dir() + "/" + venv_name;
 
@@ -671,7 +907,10 @@ bool PythonSetupManager::create_venv(const QString& venv_name) {
 
     QStringList env = uv_env_extra();
 
-    if (run_command(uv_path(), {"venv", venv_path, "--python", kPythonVersion}, env)) {
+    // Offline, point UV at the bundled interpreter rather than letting it
+    // resolve (and possibly fetch) a version.
+    const QString python = offline() ? base_python_path() : QString(kPythonVersion);
+    if (run_command(uv_path(), {"venv", venv_path, "--python", python}, env)) {
         LOG_INFO("PythonSetup", "Created venv: " + venv_name);
         return true;
     }
@@ -711,14 +950,26 @@ bool PythonSetupManager::install_packages(const QString& venv_name, const QStrin
     env << "PEEWEE_NO_SQLITE_EXTENSIONS=1"
         << "PEEWEE_NO_C_EXTENSION=1";
 
+    // Online, wheels are hash-checked only through a hash-pinned lock; the
+    // offline bundle's wheels were verified against its manifest already.
+    const QString lock_path = offline() ? QString() : hash_lock_for(req_path);
+    if (!offline() && lock_path.isEmpty())
+        LOG_WARN("PythonSetup", QString("[%1] No hash lock beside %2 — wheels are not hash-checked")
+                                    .arg(venv_name, requirements_file));
+
     // ── Pass 1: try installing everything at once (fast path) ────────────────
     // This succeeds on most machines and is 10-100x faster than one-by-one.
-    LOG_INFO("PythonSetup", QString("[%1] Pass 1: bulk install from %2").arg(venv_name, requirements_file));
+    LOG_INFO("PythonSetup", QString("[%1] Pass 1: bulk install from %2")
+                                .arg(venv_name, lock_path.isEmpty() ? requirements_file : lock_path));
     emit_progress(step_key, 5, "Installing packages (bulk)...");
 
+    const QStringList bulk_args = lock_path.isEmpty()
+                                      ? QStringList{"-r", req_path}
+                                      : QStringList{"-r", lock_path, "--require-hashes"};
     QString bulk_stderr;
-    bool bulk_ok =
-        run_command_capture(uv_path(), {"pip", "install", "--python", venv_python, "-r", req_path}, env, bulk_stderr);
+    bool bulk_ok = run_command_capture(
+        uv_path(), QStringList{"pip", "install", "--python", venv_python} + bulk_args + pip_source_args(), env,
+        bulk_stderr);
 
     if (bulk_ok) {
         LOG_INFO("PythonSetup", QString("[%1] Bulk install succeeded").arg(venv_name));
@@ -729,6 +980,13 @@ bool PythonSetupManager::install_packages(const QString& venv_name, const QStrin
         return true;
     }
 
+    // Per-package installs would bypass the lock's hashes.
+    if (!lock_path.isEmpty()) {
+        LOG_ERROR("PythonSetup", QString("[%1] Hash-checked install failed: %2").arg(venv_name, bulk_stderr.left(600)));
+        emit_progress(step_key, 100, "Package install failed (hash-checked lock) — see the log", /*is_error=*/true);
+        return false;
+    }
+
     // ── Pass 2: bulk failed — install packages one-by-one, skip failures ────
     LOG_WARN("PythonSetup", QString("[%1] Bulk install failed — falling back to per-package install.\n"
                                     "  UV command: %2 pip install --python %3 -r %4\n"
@@ -804,8 +1062,8 @@ QStringList PythonSetupManager::install_packages_individually(const QString& ven
         emit_progress(step_key, pct, QString("Installing %1/%2: %3").arg(i + 1).arg(total).arg(pkg));
 
         QString pkg_stderr;
-        bool ok =
-            run_command_capture(uv_path(), {"pip", "install", "--python", venv_python, pkg}, env_vars, pkg_stderr);
+        const QStringList args = QStringList{"pip", "install", "--python", venv_python, pkg} + pip_source_args();
+        bool ok = run_command_capture(uv_path(), args, env_vars, pkg_stderr);
 
         if (ok) {
             LOG_INFO("PythonSetup", QString("[%1] Installed: %2").arg(venv_name, pkg));
@@ -1015,8 +1273,122 @@ QString PythonSetupManager::compute_requirements_hash(const QString& filename) c
     return hash;
 }
 
-void PythonSetupManager::emit_progress(const QString& step, int pct, const QString& msg, bool err) {
-    SetupProgress p{step, pct, msg, err};
+// ─────────────────────────────────────────────────────────────────────────────
+// Offline bundle
+// ─────────────────────────────────────────────────────────────────────────────
+
+QString PythonSetupManager::find_offline_bundle() const {
+    QStringList candidates;
+    const QString env = qEnvironmentVariable("FINCEPT_SETUP_BUNDLE");
+    if (!env.isEmpty())
+        candidates << env;
+    const QString exe_dir = QCoreApplication::applicationDirPath();
+    candidates << exe_dir + "/setup-bundle" << exe_dir + "/../Resources/setup-bundle" // macOS .app
+               << install_dir() + "/setup-bundle";
+    for (const auto& dir : candidates) {
+        if (QFileInfo::exists(dir + "/manifest.json"))
+            return QDir::cleanPath(QDir(dir).absolutePath());
+    }
+    return {};
+}
+
+QString PythonSetupManager::load_bundle(const QString& dir, OfflineBundle& out) const {
+    out = {};
+    const QString root = QDir::cleanPath(QDir(dir).absolutePath());
+    QFile f(root + "/manifest.json");
+    if (!f.open(QIODevice::ReadOnly))
+        return "manifest.json not found in " + root;
+    QJsonParseError pe;
+    const QJsonDocument doc = QJsonDocument::fromJson(f.readAll(), &pe);
+    if (pe.error != QJsonParseError::NoError || !doc.isObject())
+        return "manifest.json is not valid JSON: " + pe.errorString();
+
+    const QJsonObject m = doc.object();
+    if (m.value("format").toInt(1) != 1)
+        return QString("unsupported manifest format %1").arg(m.value("format").toInt());
+    const QString py_version = m.value("python_version").toString();
+    if (!py_version.isEmpty() && py_version != kPythonVersion)
+        return QString("bundle carries Python %1, this build needs %2").arg(py_version, kPythonVersion);
+    const QString uv_version = m.value("uv_version").toString();
+    if (!uv_version.isEmpty() && uv_version != kUvVersion)
+        LOG_WARN("PythonSetup", QString("Bundle UV %1 differs from the pinned %2").arg(uv_version, kUvVersion));
+
+    const QString platform = platform_key();
+    QSet<QString> wheel_dirs;
+    for (const auto& v : m.value("artifacts").toArray()) {
+        const QJsonObject a = v.toObject();
+        const QString target = a.value("platform").toString();
+        if (!target.isEmpty() && target != "any" && target != platform)
+            continue;
+
+        const QString rel = a.value("path").toString();
+        const QString path = QDir::cleanPath(root + "/" + rel);
+        if (rel.isEmpty() || !path.startsWith(root + "/"))
+            return "artifact path outside the bundle: " + rel;
+        const QString sha = a.value("sha256").toString().trimmed().toLower();
+        if (sha.size() != 64)
+            return "artifact without a SHA-256: " + rel;
+
+        const QString role = a.value("role").toString();
+        if (role == "uv")
+            out.uv_archive = path;
+        else if (role == "python")
+            out.python_archive = path;
+        else if (role == "wheel")
+            wheel_dirs.insert(QFileInfo(path).absolutePath());
+        else
+            return QString("unknown artifact role '%1' for %2").arg(role, rel);
+        out.artifacts.append({path, sha});
+    }
+    if (out.artifacts.isEmpty())
+        return "manifest lists no artifacts for " + platform;
+
+    out.wheel_dirs = QStringList(wheel_dirs.cbegin(), wheel_dirs.cend());
+    out.wheel_dirs.sort();
+    out.dir = root;
+    return {};
+}
+
+bool PythonSetupManager::verify_bundle() {
+    const int total = int(bundle_.artifacts.size());
+    const QDir root(bundle_.dir);
+    QStringList bad;
+    for (int i = 0; i < total; ++i) {
+        const auto& a = bundle_.artifacts[i];
+        const QString name = root.relativeFilePath(a.path);
+        emit_progress("verify", 100 * i / total, QString("Verifying %1/%2: %3").arg(i + 1).arg(total).arg(name));
+        const QString actual = file_sha256(a.path);
+        if (actual.isEmpty())
+            bad << name + " (missing)";
+        else if (actual != a.sha256)
+            bad << name + " (checksum mismatch)";
+    }
+
+    if (!bad.isEmpty()) {
+        LOG_ERROR("PythonSetup", QString("Bundle verification failed: %1").arg(bad.join(", ")));
+        const QString msg =
+            QString("%1 bundle file(s) failed verification: %2").arg(bad.size()).arg(bad.join(", ").left(200));
+        emit_progress("verify", 0, msg, true, false,
+                      "Re-copy the bundle from its source, or rebuild manifest.json from the original files.");
+        return false;
+    }
+    emit_progress("verify", 100, QString("All %1 bundle files verified").arg(total));
+    return true;
+}
+
+QString PythonSetupManager::file_sha256(const QString& path) {
+    QFile f(path);
+    if (!f.open(QIODevice::ReadOnly))
+        return {};
+    QCryptographicHash hash(QCryptographicHash::Sha256);
+    if (!hash.addData(&f)) // streamed — Python archives run to tens of MB
+        return {};
+    return QString::fromLatin1(hash.result().toHex());
+}
+
+void PythonSetupManager::emit_progress(const QString& step, int pct, const QString& msg, bool err, bool retryable,
+                                       const QString& hint) {
+    SetupProgress p{step, pct, msg, err, retryable, hint};
     LOG_INFO("PythonSetup", QString("[%1 %2%] %3").arg(step).arg(pct).arg(msg));
     QMetaObject::invokeMethod(this, [this, p]() { emit progress_changed(p); }, Qt::QueuedConnection);
 }
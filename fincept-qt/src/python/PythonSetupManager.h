@@ -9,11 +9,32 @@
 //   4. uv pip install requirements            — PARALLEL package install (UV is 10-100x faster than pip)
 //
 // Estimated setup time: 3-5 minutes.
+//
+// Offline mode (restricted networks): run_setup() picks up a bundle directory
+// — FINCEPT_SETUP_BUNDLE, or `setup-bundle/` beside the executable or in the
+// install dir — holding manifest.json plus the UV archive, a
+// python-build-standalone "install_only" .tar.gz and a wheelhouse. Every file
+// is SHA-256 verified against the manifest before anything is installed, and
+// nothing is fetched from the network:
+//
+//   { "format": 1, "python_version": "3.11.9", "uv_version": "0.7.12",
+//     "artifacts": [ { "role": "uv" | "python" | "wheel",
+//                      "platform": "windows-x86_64" | "macos-aarch64" | "linux-x86_64" | ... (omit = any),
+//                      "path": "relative/to/bundle", "sha256": "<hex>" }, ... ] }
+//
+// Online, the UV archive is checked against the SHA-256 pinned in the source
+// for kUvVersion (kUvArchiveSha256), uv verifies the Python builds it
+// downloads itself, and wheels are hash-checked when a hash-pinned
+// requirements-*.lock.txt (uv pip compile --generate-hashes) sits beside the
+// requirements file.
 #pragma once
 
+#include <QJsonObject>
 #include <QMap>
 #include <QObject>
 #include <QString>
+#include <QStringList>
+#include <QVector>
 
 namespace fincept::python {
 
@@ -33,10 +54,28 @@ struct SetupStatus {
 
 /// Progress emitted during setup
 struct SetupProgress {
-    QString step;     // "uv", "python", "venv", "packages-numpy1", "packages-numpy2", "complete"
+    QString step;     // "verify", "uv", "python", "venv", "packages-numpy1", "packages-numpy2", "complete"
     int progress = 0; // 0-100 — default-initialized to prevent garbage reads
     QString message;
     bool is_error = false;
+    // Errors only: whether running setup again can succeed (network hiccup,
+    // corrupted download — completed steps are skipped), and what the user
+    // can do instead, e.g. install from an offline bundle.
+    bool retryable = false;
+    QString hint;
+};
+
+/// A locally-provided setup bundle, resolved for the running platform.
+struct OfflineBundle {
+    struct Artifact {
+        QString path; // absolute
+        QString sha256;
+    };
+    QString dir;
+    QString uv_archive; // empty when the manifest has none for this platform
+    QString python_archive;
+    QStringList wheel_dirs;      // --find-links directories
+    QVector<Artifact> artifacts; // every file this platform uses, verified before install
 };
 
 class PythonSetupManager : public QObject {
@@ -47,8 +86,20 @@ class PythonSetupManager : public QObject {
     /// Check current installation status (fast, synchronous)
     SetupStatus check_status() const;
 
-    /// Run the full setup (async, emits progress signals)
-    void run_setup();
+    /// Run the full setup (async, emits progress signals). Installs from
+    /// `bundle_dir` when given, else from an auto-detected offline bundle,
+    /// else online.
+    void run_setup(const QString& bundle_dir = {});
+
+    /// Offline bundle directory found in the standard places; empty if none.
+    QString find_offline_bundle() const;
+
+    /// Reads `dir`/manifest.json into `out`; returns an error message, empty
+    /// on success.
+    QString load_bundle(const QString& dir, OfflineBundle& out) const;
+
+    /// Platform key used by bundle manifests, e.g. "linux-x86_64".
+    static QString platform_key();
 
     /// Get the install directory (com.fincept.terminal)
     QString install_dir() const;
@@ -62,6 +113,12 @@ class PythonSetupManager : public QObject {
     /// Get the UV-managed Python executable (not venv)
     QString base_python_path() const;
 
+    /// Required vs installed packages of a venv, for diagnostics (BLOCKING —
+    /// spawns `uv pip list`): { venv, requirements, required, installed,
+    /// missing: [name], packages: [{ name, spec, version }] }, plus "error"
+    /// when the list could not be read.
+    QJsonObject package_report(const QString& venv_name, const QString& requirements_file) const;
+
   signals:
     void progress_changed(const SetupProgress& progress);
     void setup_complete(bool success, const QString& error);
@@ -70,11 +127,17 @@ class PythonSetupManager : public QObject {
     explicit PythonSetupManager(QObject* parent = nullptr);
     Q_DISABLE_COPY_MOVE(PythonSetupManager)
 
-    void emit_progress(const QString& step, int pct, const QString& msg, bool err = false);
+    void emit_progress(const QString& step, int pct, const QString& msg, bool err = false, bool retryable = false,
+                       const QString& hint = {});
 
-    // Installation steps
-    bool download_uv();
+    // Installation steps. download_uv / install_uv_from_bundle return an error
+    // message, empty on success.
+    QString download_uv();
+    QString install_uv_from_bundle();
+    bool extract_uv(const QString& archive_path, const QString& target);
     bool install_python_via_uv();
+    bool install_python_from_bundle();
+    bool verify_bundle();
     bool create_venv(const QString& venv_name);
     bool install_packages(const QString& venv_name, const QString& requirements_file);
     QString find_requirements_file(const QString& filename) const;
@@ -94,6 +157,13 @@ class PythonSetupManager : public QObject {
     bool run_command_capture(const QString& program, const QStringList& args, const QStringList& env_vars,
                              QString& stderr_out) const;
     QString download_file(const QString& url, const QString& dest_path) const;
+    // SHA-256 hex of a file, streamed; empty when unreadable.
+    static QString file_sha256(const QString& path);
+    // UV release target triple for this platform (x86_64-unknown-linux-musl, ...).
+    static QString uv_target();
+    // Extra `uv pip install` arguments: offline → --offline --no-index --find-links <wheels>.
+    QStringList pip_source_args() const;
+    bool offline() const { return !bundle_.dir.isEmpty(); }
 
     // Two-pass resilient package install helpers
     QStringList read_packages_from_file(const QString& req_path) const;
@@ -114,8 +184,13 @@ class PythonSetupManager : public QObject {
     // same build on every machine. Must match the version documented in
     // README.md / CONTRIBUTING.md / GETTING_STARTED.md.
     static constexpr const char* kPythonVersion = "3.11.9";
+    // Bumping it means updating kUvArchiveSha256 in the .cpp as well.
     static constexpr const char* kUvVersion = "0.7.12";
 
+    // The bundle of the run in progress (empty dir = online). Set on the
+    // calling thread before the worker starts; read-only while it runs.
+    OfflineBundle bundle_;
+
     // Session-lifetime caches — requirements files never change at runtime.
     mutable QString cached_python_path_;               // cleared after fresh Python install
     mutable QMap<QString, QString> cached_req_paths_;  // filename → resolved absolute path
@@ -331,8 +331,13 @@ void CreateAgentPanel::retranslateUi() {
         terminal_name_include_edit_->setPlaceholderText(tr("Tool name include regex (optional, e.g. ^get_)"));
     if (terminal_name_exclude_edit_)
         terminal_name_exclude_edit_->setPlaceholderText(tr("Tool name exclude regex (optional, e.g. ^delete_)"));
+    if (terminal_allow_names_edit_)
+        terminal_allow_names_edit_->setPlaceholderText(
+            tr("Tool allowlist (comma-separated exact names, e.g. get_quote, edgar_get_financials)"));
     if (terminal_max_lbl_)
         terminal_max_lbl_->setText(tr("Max tools (0 = no cap)"));
+    if (terminal_result_cap_lbl_)
+        terminal_result_cap_lbl_->setText(tr("Result cap (chars, 0 = none)"));
 
     // Feature toggles.
     if (reasoning_check_)
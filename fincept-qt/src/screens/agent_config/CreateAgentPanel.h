@@ -79,6 +79,7 @@ class CreateAgentPanel : public QWidget {
     QLabel* terminal_tools_hdr_ = nullptr;
     QLabel* terminal_cat_lbl_ = nullptr;
     QLabel* terminal_max_lbl_ = nullptr;
+    QLabel* terminal_result_cap_lbl_ = nullptr;
     QLabel* features_hdr_ = nullptr;
     QLabel* knowledge_type_lbl_ = nullptr;
     QLabel* knowledge_vectordb_lbl_ = nullptr;
@@ -157,7 +158,9 @@ class CreateAgentPanel : public QWidget {
     QLineEdit* terminal_exclude_cats_edit_ = nullptr;
     QLineEdit* terminal_name_include_edit_ = nullptr;
     QLineEdit* terminal_name_exclude_edit_ = nullptr;
+    QLineEdit* terminal_allow_names_edit_ = nullptr;
     QSpinBox* terminal_max_tools_spin_ = nullptr;
+    QSpinBox* terminal_result_cap_spin_ = nullptr;
     QWidget* terminal_sub_ = nullptr;
 
     // Form actions
@@ -8,6 +8,7 @@
 
 #include "core/logging/Logger.h"
 #include "mcp/McpService.h"
+#include "mcp/TerminalMcpBridge.h"
 #include "screens/agent_config/CreateAgentPanel.h"
 #include "services/agents/AgentService.h"
 #include "services/llm/LlmService.h"
@@ -159,7 +160,13 @@ void CreateAgentPanel::load_agent_into_form(const AgentConfig& cfg) {
     terminal_name_include_edit_->setText(inc_pats.isEmpty() ? QString() : inc_pats.first().toString());
     const QJsonArray exc_pats = tf["exclude_name_patterns"].toArray();
     terminal_name_exclude_edit_->setText(exc_pats.isEmpty() ? QString() : exc_pats.first().toString());
+    QStringList allow_names;
+    for (const auto& v : tf["names"].toArray())
+        allow_names.append(v.toString());
+    terminal_allow_names_edit_->setText(allow_names.join(", "));
     terminal_max_tools_spin_->setValue(tf["max_tools"].toInt(0));
+    terminal_result_cap_spin_->setValue(
+        c["tool_result_max_chars"].toInt(mcp::TerminalMcpBridge::kDefaultMaxResultChars));
 
     status_lbl_->setText(tr("Loaded: %1").arg(cfg.name));
     status_lbl_->setStyleSheet(QString("color:%1;font-size:10px;padding:3px 0;").arg(ui::colors::CYAN()));
@@ -197,7 +204,9 @@ void CreateAgentPanel::clear_form() {
     terminal_exclude_cats_edit_->clear();
     terminal_name_include_edit_->clear();
     terminal_name_exclude_edit_->clear();
+    terminal_allow_names_edit_->clear();
     terminal_max_tools_spin_->setValue(0);
+    terminal_result_cap_spin_->setValue(mcp::TerminalMcpBridge::kDefaultMaxResultChars);
     test_result_->clear();
     test_status_lbl_->clear();
     status_lbl_->setText(tr("Form cleared"));
@@ -293,6 +302,7 @@ QJsonObject CreateAgentPanel::build_config_json() const {
     config["allow_destructive_tools"] = terminal_destructive_check_->isChecked();
     config["include_external_mcp"] = terminal_external_check_->isChecked();
     config["tools_dry_run"] = terminal_dry_run_check_->isChecked();
+    config["tool_result_max_chars"] = terminal_result_cap_spin_->value();
     QJsonArray cat_whitelist;
     for (int i = 0; i < terminal_categories_list_->count(); ++i) {
         auto* it = terminal_categories_list_->item(i);
@@ -317,6 +327,14 @@ QJsonObject CreateAgentPanel::build_config_json() const {
     const QString name_exc = terminal_name_exclude_edit_->text().trimmed();
     if (!name_exc.isEmpty())
         tf["exclude_name_patterns"] = QJsonArray{name_exc};
+    QJsonArray allow_names;
+    for (const auto& part : terminal_allow_names_edit_->text().split(',', Qt::SkipEmptyParts)) {
+        const QString trimmed = part.trimmed();
+        if (!trimmed.isEmpty())
+            allow_names.append(trimmed);
+    }
+    if (!allow_names.isEmpty())
+        tf["names"] = allow_names;
     if (terminal_max_tools_spin_->value() > 0)
         tf["max_tools"] = terminal_max_tools_spin_->value();
     if (!tf.isEmpty())
@@ -431,6 +449,8 @@ QVariantMap CreateAgentPanel::save_draft() const {
         d["term_include"] = terminal_name_include_edit_->text();
     if (terminal_name_exclude_edit_)
         d["term_name_exclude"] = terminal_name_exclude_edit_->text();
+    if (terminal_allow_names_edit_)
+        d["term_allow_names"] = terminal_allow_names_edit_->text();
     if (test_query_edit_)
         d["test_query"] = test_query_edit_->toPlainText();
     if (test_result_ && !test_result_->toPlainText().isEmpty())
@@ -465,6 +485,8 @@ void CreateAgentPanel::restore_draft(const QVariantMap& d) {
         terminal_name_include_edit_->setText(d["term_include"].toString());
     if (terminal_name_exclude_edit_ && d.contains("term_name_exclude"))
         terminal_name_exclude_edit_->setText(d["term_name_exclude"].toString());
+    if (terminal_allow_names_edit_ && d.contains("term_allow_names"))
+        terminal_allow_names_edit_->setText(d["term_allow_names"].toString());
     if (test_query_edit_ && d.contains("test_query"))
         test_query_edit_->setPlainText(d["test_query"].toString());
     if (test_result_ && d.contains("test_result"))
@@ -9,6 +9,7 @@
 
 #include "core/logging/Logger.h"
 #include "mcp/McpService.h"
+#include "mcp/TerminalMcpBridge.h"
 #include "screens/agent_config/CreateAgentPanel.h"
 #include "services/agents/AgentService.h"
 #include "services/llm/LlmService.h"
@@ -307,8 +308,9 @@ QWidget* CreateAgentPanel::build_form_panel() {
     // UI-only categories (navigation/system/settings) and recursive ones
     // (ai-chat/meta) — agents shouldn't drive the UI or call the chat LLM.
     for (const auto& cat : {"markets", "watchlist", "news", "portfolio", "notes", "crypto-trading", "paper-trading",
-                            "sec-edgar", "ma-analytics", "alt-investments", "data-sources", "forum", "profile",
-                            "file_manager", "report-builder", "python", "datahub", "analytics"}) {
+                            "algo-trading", "sec-edgar", "documents", "ma-analytics", "alt-investments",
+                            "data-sources", "forum", "profile", "file_manager", "report-builder", "python", "datahub",
+                            "analytics"}) {
         auto* item = new QListWidgetItem(cat);
         item->setFlags(item->flags() | Qt::ItemIsUserCheckable);
         item->setCheckState(Qt::Unchecked);
@@ -333,6 +335,12 @@ QWidget* CreateAgentPanel::build_form_panel() {
     terminal_name_exclude_edit_->setStyleSheet(input_style());
     sub_vl->addWidget(terminal_name_exclude_edit_);
 
+    terminal_allow_names_edit_ = new QLineEdit;
+    terminal_allow_names_edit_->setPlaceholderText(
+        tr("Tool allowlist (comma-separated exact names, e.g. get_quote, edgar_get_financials)"));
+    terminal_allow_names_edit_->setStyleSheet(input_style());
+    sub_vl->addWidget(terminal_allow_names_edit_);
+
     auto* max_row = new QHBoxLayout;
     max_row->setSpacing(8);
     terminal_max_lbl_ = field_lbl(tr("Max tools (0 = no cap)"));
@@ -342,6 +350,14 @@ QWidget* CreateAgentPanel::build_form_panel() {
     terminal_max_tools_spin_->setValue(0);
     terminal_max_tools_spin_->setStyleSheet(input_style());
     max_row->addWidget(terminal_max_tools_spin_);
+    terminal_result_cap_lbl_ = field_lbl(tr("Result cap (chars, 0 = none)"));
+    max_row->addWidget(terminal_result_cap_lbl_);
+    terminal_result_cap_spin_ = new QSpinBox;
+    terminal_result_cap_spin_->setRange(0, 1000000);
+    terminal_result_cap_spin_->setSingleStep(4000);
+    terminal_result_cap_spin_->setValue(mcp::TerminalMcpBridge::kDefaultMaxResultChars);
+    terminal_result_cap_spin_->setStyleSheet(input_style());
+    max_row->addWidget(terminal_result_cap_spin_);
     max_row->addStretch();
     sub_vl->addLayout(max_row);
 
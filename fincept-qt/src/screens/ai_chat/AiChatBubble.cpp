@@ -18,6 +18,7 @@
 #include "services/stt/SpeechService.h"
 #include "services/tts/TtsService.h"
 #include "services/voice_trigger/ClapDetectorService.h"
+#include "ui/command/VoiceCommand.h"
 #include "ui/theme/Theme.h"
 #include "ui/theme/ThemeManager.h"
 
@@ -686,6 +687,25 @@ void AiChatBubble::on_transcription(const QString& text) {
     if (text.isEmpty())
         return;
 
+    // Spoken commands ("quote AAPL", "open news") run directly instead of
+    // going to the model. Anything that doesn't parse as one is a chat message.
+    if (!streaming_ && ui::VoiceCommand::enabled()) {
+        const auto intent = ui::VoiceCommand::parse(text);
+        if (intent.is_command()) {
+            hide_welcome();
+            add_bubble("user", text);
+            const auto r = ui::VoiceCommand::execute(intent);
+            if (r.is_ok())
+                add_bubble("assistant", r.value());
+            else
+                add_bubble("assistant", tr("Couldn't run that command: %1").arg(QString::fromStdString(r.error())));
+            if (!voice_mode_)
+                stop_listening();
+            render_status();
+            return;
+        }
+    }
+
     input_box_->setPlainText(text);
 
     if (!voice_mode_) {
@@ -11,7 +11,8 @@
 //     → SpeechService::start_listening()
 //     → SpeechService::transcription_ready(text)   [signal, UI thread]
 //     → on_transcription(text)
-//     → input_box_ populated → on_send()
+//     → spoken command ("quote AAPL")? run it via ui::VoiceCommand
+//     → otherwise input_box_ populated → on_send()
 //   AI reply (in voice mode)
 //     → TtsService::speak(text)
 //     → TtsService::speaking_finished() → resume listening
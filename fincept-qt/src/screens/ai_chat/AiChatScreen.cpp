@@ -247,7 +247,7 @@ void AiChatScreen::retranslateUi() {
         typing_dots_lbl_->setText(tr("AI is thinking"));
     // Re-run the helpers that compose live state strings so their tr() calls
     // pick up the new language for the current state.
-    set_input_enabled(send_btn_ ? send_btn_->isEnabled() : true);
+    set_input_enabled(!streaming_);
     update_stats();
 }
 
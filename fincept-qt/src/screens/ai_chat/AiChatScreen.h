@@ -123,6 +123,7 @@ class AiChatScreen : public QWidget, public IStatefulScreen, public fincept::IGr
     mutable QMutex history_mutex_;
     std::vector<ai_chat::ConversationMessage> history_;
     bool streaming_ = false;
+    bool stream_cancellable_ = false; // streaming provider — Send turns into Stop
     bool scroll_pending_ = false;
     QPointer<QLabel> streaming_bubble_;
     int total_tokens_ = 0;
@@ -12,6 +12,7 @@
 #include "mcp/McpService.h"
 #include "screens/ai_chat/AiChatScreen.h"
 #include "screens/ai_chat/ChatBubbleFactory.h"
+#include "services/llm/ChatMemoryManager.h"
 #include "services/llm/LlmService.h"
 #include "storage/repositories/ChatRepository.h"
 #include "ui/theme/Theme.h"
@@ -80,8 +81,12 @@ void AiChatScreen::show_typing(bool show) {
 // ── Session management ────────────────────────────────────────────────────────
 
 void AiChatScreen::on_send() {
-    if (streaming_)
+    if (streaming_) {
+        // While streaming the Send button reads "Stop".
+        if (stream_cancellable_)
+            ai_chat::LlmService::instance().cancel_streaming(pending_req_session_);
         return;
+    }
     const QString raw_text = input_box_->toPlainText().trimmed();
     if (raw_text.isEmpty() && attached_file_path_.isEmpty())
         return;
@@ -138,12 +143,18 @@ void AiChatScreen::on_send() {
     pending_req_model_ = req_model;
     input_box_->clear();
     input_box_->setFixedHeight(44);
+    stream_cancellable_ = ai_chat::provider_supports_streaming(req_provider);
     set_input_enabled(false);
     streaming_ = true;
     show_welcome(false);
     // Display only raw_text in bubble (not full file dump)
     add_message_bubble("user", raw_text.isEmpty() ? tr("[File attached — see context]") : raw_text);
     total_messages_++;
+    // History sent to the model: rolling summary + newest turns that fit this
+    // model's context budget. Read before the new turn is stored — it travels
+    // as the request's user_message, not as history.
+    auto window = ai_chat::ChatMemoryManager::instance().get_context_window(
+        req_session, req_provider, req_model, ai_chat::ChatMemoryManager::estimate_tokens(text));
     ChatRepository::instance().add_message(active_session_id_, "user", text,
                                            ai_chat::LlmService::instance().active_provider(),
                                            ai_chat::LlmService::instance().active_model());
@@ -157,7 +168,14 @@ void AiChatScreen::on_send() {
     // collapsible card created lazily by on_stream_chunk).
     reset_thinking_state();
 
-    std::vector<ai_chat::ConversationMessage> hist_copy = history_;
+    std::vector<ai_chat::ConversationMessage> hist_copy;
+    if (window.is_ok()) {
+        hist_copy = window.value().messages;
+    } else {
+        LOG_WARN("AiChat", "Context window unavailable, sending full history: " +
+                               QString::fromStdString(window.error()));
+        hist_copy = history_;
+    }
     const QString provider = ai_chat::LlmService::instance().active_provider();
     if (ai_chat::provider_supports_streaming(provider)) {
         QPointer<AiChatScreen> self = this;
@@ -249,8 +267,17 @@ void AiChatScreen::on_streaming_done(ai_chat::LlmResponse response) {
     if (need_bubble)
         streaming_bubble_ = add_streaming_bubble();
 
+    stream_cancellable_ = false;
     set_input_enabled(true);
 
+    // Stopped before any answer text arrived — nothing to keep or persist.
+    if (response.cancelled && response.content.isEmpty()) {
+        if (streaming_bubble_)
+            fincept::ai_chat::ChatBubbleFactory::replace_streaming_text(streaming_bubble_, tr("(stopped)"));
+        streaming_bubble_ = nullptr;
+        return;
+    }
+
     if (!response.success) {
         if (streaming_bubble_) {
             const QString err =
@@ -291,6 +318,8 @@ void AiChatScreen::on_streaming_done(ai_chat::LlmResponse response) {
         // Use request‑time captured context to persist the assistant message
         ChatRepository::instance().add_message(pending_req_session_, "assistant", content, pending_req_provider_,
                                                pending_req_model_, response.total_tokens);
+        ai_chat::ChatMemoryManager::instance().maybe_summarize(pending_req_session_, pending_req_provider_,
+                                                               pending_req_model_);
         // Clear pending request context to avoid accidental reuse
         pending_req_session_.clear();
         pending_req_provider_.clear();
@@ -447,13 +476,15 @@ void AiChatScreen::scroll_to_bottom() {
 
 void AiChatScreen::set_input_enabled(bool enabled) {
     input_box_->setEnabled(enabled);
-    send_btn_->setEnabled(enabled);
+    const bool can_stop = !enabled && stream_cancellable_;
+    send_btn_->setEnabled(enabled || can_stop);
     new_btn_->setEnabled(enabled);
     search_edit_->setEnabled(enabled);
     session_list_->setEnabled(enabled);
     delete_btn_->setEnabled(enabled && !active_session_id_.isEmpty());
     rename_btn_->setEnabled(enabled && !active_session_id_.isEmpty());
-    send_btn_->setText(enabled ? tr("Send") : "···");
+    send_btn_->setText(enabled ? tr("Send") : can_stop ? tr("Stop  ■") : "···");
+    send_btn_->setToolTip(can_stop ? tr("Stop generating") : QString());
 
     // Status dot + label
     const QString status_color = enabled ? col::POSITIVE() : col::AMBER();
@@ -115,9 +115,12 @@ void AlgoDeployDialog::build_ui() {
     form->addRow(symbol_label_, symbol_edit_);
 
     mode_combo_ = new QComboBox(this);
-    // Visible labels translatable; userData ("paper"/"live") drives logic.
+    // Visible labels translatable; userData ("paper"/"live"/"shadow") drives logic.
+    // Shadow forward-tests on live data: signals and hypothetical fills are
+    // logged, nothing is routed — not even to the paper portfolio.
     mode_combo_->addItem(tr("Paper"), "paper");
     mode_combo_->addItem(tr("Live"), "live");
+    mode_combo_->addItem(tr("Shadow (forward test)"), "shadow");
     connect(mode_combo_, QOverload<int>::of(&QComboBox::currentIndexChanged), this, &AlgoDeployDialog::on_mode_changed);
     mode_label_ = new QLabel(tr("Mode:"), this);
     form->addRow(mode_label_, mode_combo_);
@@ -173,6 +176,21 @@ void AlgoDeployDialog::build_ui() {
     max_loss_label_ = new QLabel(tr("Max Daily Loss:"), this);
     form->addRow(max_loss_label_, max_loss_spin_);
 
+    max_position_spin_ = new QDoubleSpinBox(this);
+    max_position_spin_->setRange(0, 99999999);
+    max_position_spin_->setValue(0);
+    max_position_spin_->setDecimals(0);
+    max_position_spin_->setSpecialValueText(tr("No limit"));
+    max_position_label_ = new QLabel(tr("Max Position Value:"), this);
+    form->addRow(max_position_label_, max_position_spin_);
+
+    max_rate_spin_ = new QSpinBox(this);
+    max_rate_spin_->setRange(0, 600);
+    max_rate_spin_->setValue(0);
+    max_rate_spin_->setSpecialValueText(tr("No limit"));
+    max_rate_label_ = new QLabel(tr("Max Orders / Min:"), this);
+    form->addRow(max_rate_label_, max_rate_spin_);
+
     root->addLayout(form);
 
     buttons_ = new QDialogButtonBox(QDialogButtonBox::Ok | QDialogButtonBox::Cancel, this);
@@ -290,10 +308,12 @@ void AlgoDeployDialog::on_ok() {
     result_.quantity = quantity_spin_->value();
     result_.max_order_value = max_order_spin_->value();
     result_.max_daily_loss = max_loss_spin_->value();
+    result_.max_position_value = max_position_spin_->value();
+    result_.max_orders_per_minute = max_rate_spin_->value();
 
     // Attach the chosen connected account in BOTH modes — it's the market-data
     // source. Live additionally routes real orders through it (backend=equity_broker);
-    // paper simulates fills (backend=paper) but still sources real data from it.
+    // paper and shadow simulate fills (backend=paper) but still source real data from it.
     const QString account_id = account_combo_->currentData().toString();
     if (!account_id.isEmpty()) {
         auto account = AccountManager::instance().get_account(account_id);
@@ -351,11 +371,16 @@ void AlgoDeployDialog::retranslateUi() {
         max_order_label_->setText(tr("Max Order Value:"));
     if (max_loss_label_)
         max_loss_label_->setText(tr("Max Daily Loss:"));
+    if (max_position_label_)
+        max_position_label_->setText(tr("Max Position Value:"));
+    if (max_rate_label_)
+        max_rate_label_->setText(tr("Max Orders / Min:"));
 
     // Combo visible labels (userData keys unchanged).
-    if (mode_combo_ && mode_combo_->count() >= 2) {
+    if (mode_combo_ && mode_combo_->count() >= 3) {
         mode_combo_->setItemText(0, tr("Paper"));
         mode_combo_->setItemText(1, tr("Live"));
+        mode_combo_->setItemText(2, tr("Shadow (forward test)"));
     }
     if (side_combo_ && side_combo_->count() >= 2) {
         side_combo_->setItemText(0, tr("BUY"));
@@ -365,6 +390,10 @@ void AlgoDeployDialog::retranslateUi() {
         max_order_spin_->setSpecialValueText(tr("No limit"));
     if (max_loss_spin_)
         max_loss_spin_->setSpecialValueText(tr("No limit"));
+    if (max_position_spin_)
+        max_position_spin_->setSpecialValueText(tr("No limit"));
+    if (max_rate_spin_)
+        max_rate_spin_->setSpecialValueText(tr("No limit"));
     if (buttons_)
         buttons_->button(QDialogButtonBox::Ok)->setText(tr("DEPLOY"));
     // account_combo_ "No connected accounts" placeholder is rebuilt by populate_accounts().
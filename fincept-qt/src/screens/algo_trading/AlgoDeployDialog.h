@@ -7,6 +7,7 @@
 #include <QEvent>
 #include <QLabel>
 #include <QLineEdit>
+#include <QSpinBox>
 
 class QDialogButtonBox;
 
@@ -59,6 +60,8 @@ class AlgoDeployDialog : public QDialog {
     QDoubleSpinBox* quantity_spin_ = nullptr;
     QDoubleSpinBox* max_order_spin_ = nullptr;
     QDoubleSpinBox* max_loss_spin_ = nullptr;
+    QDoubleSpinBox* max_position_spin_ = nullptr;
+    QSpinBox* max_rate_spin_ = nullptr;
 
     QLabel* title_label_ = nullptr;
     QLabel* symbol_label_ = nullptr;
@@ -71,6 +74,8 @@ class AlgoDeployDialog : public QDialog {
     QLabel* quantity_label_ = nullptr;
     QLabel* max_order_label_ = nullptr;
     QLabel* max_loss_label_ = nullptr;
+    QLabel* max_position_label_ = nullptr;
+    QLabel* max_rate_label_ = nullptr;
     QDialogButtonBox* buttons_ = nullptr;
 
     fincept::services::algo::AlgoDeployment result_;
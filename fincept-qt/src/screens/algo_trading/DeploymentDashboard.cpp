@@ -12,6 +12,7 @@
 #include <QDateTime>
 #include <QFrame>
 #include <QHBoxLayout>
+#include <QMessageBox>
 #include <QProgressBar>
 #include <QPushButton>
 #include <QScrollArea>
@@ -77,6 +78,13 @@ void DeploymentDashboard::connect_service() {
         algo_ns::AlgoEngine::instance().list_deployments();
     });
     connect(&engine, &algo_ns::AlgoEngine::error_occurred, this, &DeploymentDashboard::on_error);
+    connect(&engine, &algo_ns::AlgoEngine::strategy_reloaded, this,
+            [this](const QString& id, bool ok, const QString& message) {
+                if (!ok)
+                    on_error(id, message);
+                else
+                    algo_ns::AlgoEngine::instance().list_deployments();
+            });
 
     // Also connect to legacy service for QC deployments
     auto& svc = AlgoTradingService::instance();
@@ -165,15 +173,19 @@ QWidget* DeploymentDashboard::build_deployment_card(const AlgoDeployment& d, QWi
     top->addWidget(tf_lbl);
 
     // Mode badge
-    bool is_paper = (d.mode == "paper");
+    // Shadow = forward test: hypothetical fills only, nothing routed.
+    const bool is_paper = (d.mode == "paper");
+    const bool is_shadow = d.is_shadow();
     auto* mode_badge = new QLabel(d.mode.toUpper(), card);
     mode_badge->setStyleSheet(QString("color: %1; font-size: %2px; font-weight: 700; %3"
                                       " padding: 2px 6px; background: rgba(%4,0.08);"
                                       " border: 1px solid rgba(%4,0.25);")
-                                  .arg(is_paper ? fincept::ui::colors::POSITIVE() : fincept::ui::colors::AMBER())
+                                  .arg(is_shadow  ? fincept::ui::colors::CYAN()
+                                       : is_paper ? fincept::ui::colors::POSITIVE()
+                                                  : fincept::ui::colors::AMBER())
                                   .arg(fincept::ui::fonts::TINY)
                                   .arg(kMonoFont())
-                                  .arg(is_paper ? "22,163,74" : "217,119,6"));
+                                  .arg(is_shadow ? "8,145,178" : is_paper ? "22,163,74" : "217,119,6"));
     top->addWidget(mode_badge);
 
     // Broker + connection badge — so users can see WHICH broker is wired and whether
@@ -355,6 +367,22 @@ QWidget* DeploymentDashboard::build_deployment_card(const AlgoDeployment& d, QWi
             LOG_INFO("AlgoTrading", QString("Stop requested: %1").arg(dep_id));
         });
         btn_row->addWidget(stop_btn);
+
+        auto* reload_btn = new QPushButton(tr("RELOAD"), card);
+        reload_btn->setCursor(Qt::PointingHandCursor);
+        reload_btn->setFixedHeight(26);
+        reload_btn->setToolTip(tr("Apply the saved strategy's current rules without closing the position"));
+        reload_btn->setStyleSheet(QString("QPushButton { background: transparent; color: %1; border: 1px solid %1;"
+                                          " font-size: %2px; font-weight: 700; %3 padding: 2px 16px; }"
+                                          "QPushButton:hover { background: rgba(120,120,120,0.1); }")
+                                      .arg(fincept::ui::colors::CYAN())
+                                      .arg(fincept::ui::fonts::TINY)
+                                      .arg(kMonoFont()));
+        connect(reload_btn, &QPushButton::clicked, card, [dep_id = d.id]() {
+            algo_ns::AlgoEngine::instance().reload_strategy(dep_id);
+            LOG_INFO("AlgoTrading", QString("Strategy reload requested: %1").arg(dep_id));
+        });
+        btn_row->addWidget(reload_btn);
     } else {
         auto* remove_btn = new QPushButton(tr("REMOVE"), card);
         remove_btn->setCursor(Qt::PointingHandCursor);
@@ -527,6 +555,39 @@ void DeploymentDashboard::build_ui() {
     });
     control_bar->addWidget(stop_all_btn_);
 
+    kill_btn_ = new QPushButton(content);
+    kill_btn_->setCursor(Qt::PointingHandCursor);
+    kill_btn_->setFixedHeight(30);
+    kill_btn_->setStyleSheet(QString("QPushButton { background: %1; color: %2; border: 1px solid %1;"
+                                     " font-size: %3px; font-weight: 700; %4 padding: 4px 16px; }"
+                                     "QPushButton:hover { border-color: %2; }")
+                                 .arg(fincept::ui::colors::NEGATIVE(), fincept::ui::colors::TEXT_PRIMARY())
+                                 .arg(fincept::ui::fonts::TINY)
+                                 .arg(kMonoFont()));
+    kill_btn_->setText(algo_ns::AlgoEngine::instance().kill_switch_engaged() ? tr("RESET KILL SWITCH")
+                                                                             : tr("KILL SWITCH"));
+    connect(kill_btn_, &QPushButton::clicked, this, [this]() {
+        auto& engine = algo_ns::AlgoEngine::instance();
+        if (engine.kill_switch_engaged()) {
+            engine.reset_kill_switch();
+            return;
+        }
+        const auto answer = QMessageBox::question(
+            this, tr("Kill switch"),
+            tr("Cancel open orders on every live account, flatten every deployment's position at market and "
+               "stop all deployments?"));
+        if (answer != QMessageBox::Yes)
+            return;
+        engine.kill_switch(QStringLiteral("dashboard"));
+        LOG_WARN("AlgoTrading", "Kill switch engaged from the dashboard");
+    });
+    connect(&algo_ns::AlgoEngine::instance(), &algo_ns::AlgoEngine::kill_switch_changed, kill_btn_,
+            [this](bool engaged, const QString&) {
+                kill_btn_->setText(engaged ? tr("RESET KILL SWITCH") : tr("KILL SWITCH"));
+                algo_ns::AlgoEngine::instance().list_deployments();
+            });
+    control_bar->addWidget(kill_btn_);
+
     vl->addLayout(control_bar);
 
     // ── Deployments section ─────────────────────────────────────────────────
@@ -756,6 +817,9 @@ void DeploymentDashboard::retranslateUi() {
         refresh_btn_->setText(tr("REFRESH"));
     if (stop_all_btn_)
         stop_all_btn_->setText(tr("STOP ALL"));
+    if (kill_btn_)
+        kill_btn_->setText(algo_ns::AlgoEngine::instance().kill_switch_engaged() ? tr("RESET KILL SWITCH")
+                                                                                 : tr("KILL SWITCH"));
     if (dep_title_)
         dep_title_->setText(tr("DEPLOYMENTS"));
 
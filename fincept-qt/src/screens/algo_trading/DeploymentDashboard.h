@@ -75,6 +75,7 @@ class DeploymentDashboard : public QWidget {
     // Control bar + section
     QPushButton* refresh_btn_ = nullptr;
     QPushButton* stop_all_btn_ = nullptr;
+    QPushButton* kill_btn_ = nullptr; // engages / resets AlgoEngine's kill switch
     QLabel* dep_title_ = nullptr;
 
     int deployment_count_ = 0; // for status_label_ retranslate
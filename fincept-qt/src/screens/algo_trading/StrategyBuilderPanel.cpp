@@ -2,6 +2,8 @@
 #include "screens/algo_trading/StrategyBuilderPanel.h"
 
 #include "algo_engine/AlgoEngine.h"
+#include "algo_engine/PineImporter.h"
+#include "algo_engine/StrategyAnalyzer.h"
 #include "algo_engine/fno/FnoAlgoTypes.h"
 #include "algo_engine/fno/FnoStrategyPreview.h"
 #include "core/currency/Currency.h"
@@ -27,6 +29,7 @@
 #include <QFutureWatcher>
 #include <QGridLayout>
 #include <QHBoxLayout>
+#include <QInputDialog>
 #include <QJsonArray>
 #include <QJsonDocument>
 #include <QJsonObject>
@@ -311,6 +314,12 @@ QWidget* StrategyBuilderPanel::build_top_toolbar() {
     state_chip_->setObjectName(QStringLiteral("builderStateChip"));
     state_chip_->setStyleSheet(chip_style());
 
+    import_btn_ = new QPushButton(tr("Import Pine…"), this);
+    import_btn_->setObjectName(QStringLiteral("builderImportBtn"));
+    import_btn_->setCursor(Qt::PointingHandCursor);
+    import_btn_->setToolTip(tr("Translate a Pine Script strategy into builder rules"));
+    import_btn_->setStyleSheet(save_btn_style());
+
     save_btn_ = new QPushButton(tr("Save"), this);
     save_btn_->setObjectName(QStringLiteral("builderSaveBtn"));
     save_btn_->setCursor(Qt::PointingHandCursor);
@@ -339,9 +348,11 @@ QWidget* StrategyBuilderPanel::build_top_toolbar() {
     layout->addWidget(instrument_type_combo_);
     layout->addStretch();
     layout->addWidget(state_chip_);
+    layout->addWidget(import_btn_);
     layout->addWidget(save_btn_);
     layout->addWidget(deploy_btn_);
 
+    connect(import_btn_, &QPushButton::clicked, this, &StrategyBuilderPanel::on_import_pine);
     connect(save_btn_, &QPushButton::clicked, this, &StrategyBuilderPanel::on_save);
     connect(deploy_btn_, &QPushButton::clicked, this, &StrategyBuilderPanel::on_deploy);
     connect(template_combo_, QOverload<int>::of(&QComboBox::activated), this, &StrategyBuilderPanel::load_template);
@@ -429,10 +440,31 @@ QWidget* StrategyBuilderPanel::build_right_panel() {
     bt_end_date_->setMaximumDate(today);
     bt_end_date_->setDate(today);
 
+    // Bar type the rules run on; the size only applies to Renko / range bars.
+    bt_bars_combo_ = new QComboBox(this);
+    bt_bars_combo_->setObjectName(QStringLiteral("builderBtBars"));
+    bt_bars_combo_->addItem(tr("Time bars"), QStringLiteral("time"));
+    bt_bars_combo_->addItem(tr("Heikin-Ashi"), QStringLiteral("heikin_ashi"));
+    bt_bars_combo_->addItem(tr("Renko"), QStringLiteral("renko"));
+    bt_bars_combo_->addItem(tr("Range bars"), QStringLiteral("range"));
+
+    bt_bar_size_ = new QDoubleSpinBox(this);
+    bt_bar_size_->setObjectName(QStringLiteral("builderBtBarSize"));
+    bt_bar_size_->setRange(0.0001, 1000000);
+    bt_bar_size_->setDecimals(4);
+    bt_bar_size_->setValue(10);
+    bt_bar_size_->setToolTip(tr("Brick / range size in price units"));
+    bt_bar_size_->setEnabled(false);
+    connect(bt_bars_combo_, QOverload<int>::of(&QComboBox::currentIndexChanged), this, [this](int) {
+        const QString type = bt_bars_combo_->currentData().toString();
+        bt_bar_size_->setEnabled(type == QLatin1String("renko") || type == QLatin1String("range"));
+    });
+
     bt_symbol_label_ = field_label(tr("Symbol"));
     bt_capital_label_ = field_label(tr("Capital"));
     bt_start_label_ = field_label(tr("From"));
     bt_end_label_ = field_label(tr("To"));
+    bt_bars_label_ = field_label(tr("Bars"));
 
     auto* bt_grid = new QGridLayout();
     bt_grid->setHorizontalSpacing(8);
@@ -447,6 +479,9 @@ QWidget* StrategyBuilderPanel::build_right_panel() {
     bt_grid->addWidget(bt_start_date_, 2, 1);
     bt_grid->addWidget(bt_end_label_, 2, 2);
     bt_grid->addWidget(bt_end_date_, 2, 3);
+    bt_grid->addWidget(bt_bars_label_, 3, 0);
+    bt_grid->addWidget(bt_bars_combo_, 3, 1);
+    bt_grid->addWidget(bt_bar_size_, 3, 2, 1, 2);
     setup->addLayout(bt_grid);
 
     backtest_btn_ = new QPushButton(tr("▶  RUN BACKTEST"), this);
@@ -688,12 +723,39 @@ void StrategyBuilderPanel::on_backtest() {
     if (symbol.isEmpty())
         symbol = QStringLiteral("RELIANCE");
 
+    const QJsonObject bars{{"type", bt_bars_combo_->currentData().toString()}, {"size", bt_bar_size_->value()}};
     services::algo::AlgoTradingService::instance().run_backtest(
         strat, symbol, bt_start_date_->date().toString(QStringLiteral("yyyy-MM-dd")),
-        bt_end_date_->date().toString(QStringLiteral("yyyy-MM-dd")), bt_capital_->value());
+        bt_end_date_->date().toString(QStringLiteral("yyyy-MM-dd")), bt_capital_->value(), bars);
     status_label_->setText(tr("Running backtest..."));
 }
 
+void StrategyBuilderPanel::on_import_pine() {
+    bool accepted = false;
+    const QString source = QInputDialog::getMultiLineText(this, tr("Import Pine Script"),
+                                                          tr("Paste a Pine Script v5 strategy:"), {}, &accepted);
+    if (!accepted || source.trimmed().isEmpty())
+        return;
+
+    algo_ns::PineImport imported = algo_ns::PineImporter::transpile(source);
+    if (!imported.ok) {
+        QMessageBox::warning(this, tr("Import Pine Script"),
+                             tr("Nothing could be imported.\n\n%1").arg(imported.errors.join('\n')));
+        return;
+    }
+    imported.strategy.timeframe = timeframe_combo_->currentText();
+    load_strategy(imported.strategy); // no id → saved as a new strategy
+    const int issues = int(imported.errors.size() + imported.warnings.size());
+    status_label_->setText(issues == 0 ? tr("Imported from Pine Script.")
+                                       : tr("Imported from Pine Script with %1 note(s):\n%2")
+                                             .arg(issues)
+                                             .arg((imported.errors + imported.warnings).join('\n')));
+    LOG_INFO("AlgoTrading", QString("Imported Pine strategy '%1' (%2 errors, %3 warnings)")
+                                .arg(imported.strategy.name)
+                                .arg(imported.errors.size())
+                                .arg(imported.warnings.size()));
+}
+
 void StrategyBuilderPanel::on_deploy() {
     // Guards use a visible dialog/banner: the status label lives in the right-hand
     // backtest card, far from the Deploy button, so a silent setText() there reads
@@ -857,6 +919,24 @@ QString StrategyBuilderPanel::validate() const {
     const bool no_exit = exit_section_->conditions().isEmpty();
     if (no_exit && risk_panel_->stop_loss() <= 0.0 && risk_panel_->take_profit() <= 0.0)
         return tr("Strategy never exits: add an exit condition or set a stop-loss / take-profit.");
+
+    // Rule-level errors (unknown field, empty `between` range…) from the static
+    // analyzer — the first one, with the path of the offending condition.
+    services::algo::AlgoStrategy draft;
+    draft.timeframe = timeframe_combo_->currentText();
+    draft.entry_conditions = entry_section_->conditions();
+    draft.exit_conditions = exit_section_->conditions();
+    draft.entry_logic = entry_section_->combined_logic();
+    draft.exit_logic = exit_section_->combined_logic();
+    draft.stop_loss = risk_panel_->stop_loss();
+    draft.take_profit = risk_panel_->take_profit();
+    draft.trailing_stop = risk_panel_->trailing_stop();
+    const QJsonObject analysis = algo_ns::StrategyAnalyzer::analyze(draft);
+    for (const auto& v : analysis.value("diagnostics").toArray()) {
+        const QJsonObject diag = v.toObject();
+        if (diag.value("severity").toString() == QLatin1String("error"))
+            return tr("%1 (at %2)").arg(diag.value("message").toString(), diag.value("path").toString());
+    }
     return QString();
 }
 
@@ -934,6 +1014,8 @@ void StrategyBuilderPanel::retranslateUi() {
     if (template_combo_ && template_combo_->count() > 0)
         template_combo_->setItemText(0, tr("Templates…"));
 
+    if (import_btn_)
+        import_btn_->setText(tr("Import Pine…"));
     if (save_btn_)
         save_btn_->setText(tr("Save"));
     if (backtest_btn_)
@@ -951,6 +1033,14 @@ void StrategyBuilderPanel::retranslateUi() {
         bt_start_label_->setText(tr("From"));
     if (bt_end_label_)
         bt_end_label_->setText(tr("To"));
+    if (bt_bars_label_)
+        bt_bars_label_->setText(tr("Bars"));
+    if (bt_bars_combo_ && bt_bars_combo_->count() == 4) {
+        bt_bars_combo_->setItemText(0, tr("Time bars"));
+        bt_bars_combo_->setItemText(1, tr("Heikin-Ashi"));
+        bt_bars_combo_->setItemText(2, tr("Renko"));
+        bt_bars_combo_->setItemText(3, tr("Range bars"));
+    }
     if (results_header_)
         results_header_->setText(tr("RESULTS"));
     // status_label_ carries transient action feedback — left as-is on language switch.
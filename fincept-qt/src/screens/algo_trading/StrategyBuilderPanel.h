@@ -53,6 +53,7 @@ class StrategyBuilderPanel : public QWidget {
 
   private slots:
     void on_save();
+    void on_import_pine();
     void on_backtest();
     void on_deploy();
     void on_backtest_result(const QJsonObject& data);
@@ -91,6 +92,7 @@ class StrategyBuilderPanel : public QWidget {
     QComboBox* template_combo_ = nullptr;
     // Instrument-type selector added by P2.3
     QComboBox* instrument_type_combo_ = nullptr;
+    QPushButton* import_btn_ = nullptr;
     QPushButton* save_btn_ = nullptr;
     QPushButton* backtest_btn_ = nullptr;
     QPushButton* deploy_btn_ = nullptr;
@@ -109,10 +111,13 @@ class StrategyBuilderPanel : public QWidget {
     QLabel* bt_capital_label_ = nullptr;
     QLabel* bt_start_label_ = nullptr;
     QLabel* bt_end_label_ = nullptr;
+    QLabel* bt_bars_label_ = nullptr;
     QLabel* results_header_ = nullptr;
     QDoubleSpinBox* bt_capital_ = nullptr;
     QDateEdit* bt_start_date_ = nullptr;
     QDateEdit* bt_end_date_ = nullptr;
+    QComboBox* bt_bars_combo_ = nullptr;    // time / Heikin-Ashi / Renko / range bars
+    QDoubleSpinBox* bt_bar_size_ = nullptr; // brick / range size (renko, range)
     QLabel* status_label_ = nullptr;
 
     // Identity of the strategy currently being edited (empty => not yet minted).
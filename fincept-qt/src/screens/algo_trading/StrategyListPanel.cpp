@@ -2,6 +2,7 @@
 #include "screens/algo_trading/StrategyListPanel.h"
 
 #include "algo_engine/AlgoEngine.h"
+#include "algo_engine/StrategyAnalyzer.h"
 #include "core/logging/Logger.h"
 #include "screens/algo_trading/AlgoDeployDialog.h"
 #include "services/algo_trading/AlgoTradingService.h"
@@ -234,6 +235,7 @@ void StrategyListPanel::render_page() {
         // Col 1: name
         auto* name_item = new QTableWidgetItem(s.name);
         name_item->setForeground(QColor(colors::AMBER()));
+        name_item->setToolTip(fincept::algo::StrategyAnalyzer::to_text(s));
         table_->setItem(row, 1, name_item);
 
         // Col 2: category (description field)
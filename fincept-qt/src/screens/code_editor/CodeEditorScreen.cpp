@@ -11,6 +11,7 @@
 #include "core/keys/KeyConfigManager.h"
 #include "core/logging/Logger.h"
 #include "core/session/ScreenStateManager.h"
+#include "python/NotebookKernel.h"
 #include "python/PythonRunner.h"
 #include "services/file_manager/FileManagerService.h"
 #include "services/notebooks/NotebookLibraryService.h"
@@ -142,6 +143,12 @@ CodeEditorScreen::CodeEditorScreen(QWidget* parent) : QWidget(parent) {
     setStyleSheet(kStyle());
     connect(&ui::ThemeManager::instance(), &ui::ThemeManager::theme_changed, this,
             [this](const ui::ThemeTokens&) { setStyleSheet(kStyle()); });
+
+    kernel_ = new python::NotebookKernel(this);
+    connect(kernel_, &python::NotebookKernel::output, this, &CodeEditorScreen::on_kernel_output);
+    connect(kernel_, &python::NotebookKernel::finished, this, &CodeEditorScreen::on_kernel_finished);
+    connect(kernel_, &python::NotebookKernel::state_changed, this, [this]() { refresh_kernel_label(); });
+
     build_ui();
     on_new_notebook();
     populate_library();
@@ -348,6 +355,14 @@ QWidget* CodeEditorScreen::build_toolbar() {
     connect(btn_run_all_, &QPushButton::clicked, this, &CodeEditorScreen::on_run_all);
     hl->addWidget(btn_run_all_);
 
+    btn_interrupt_ = make_btn(tr("■ INTERRUPT"));
+    connect(btn_interrupt_, &QPushButton::clicked, this, &CodeEditorScreen::on_interrupt_kernel);
+    hl->addWidget(btn_interrupt_);
+
+    btn_restart_ = make_btn(tr("↻ RESTART"));
+    connect(btn_restart_, &QPushButton::clicked, this, &CodeEditorScreen::on_restart_kernel);
+    hl->addWidget(btn_restart_);
+
     hl->addStretch();
 
     btn_sidebar_ = make_btn(tr("SIDEBAR"));
@@ -399,6 +414,8 @@ QWidget* CodeEditorScreen::build_status_bar() {
 // ─────────────────────────────────────────────────────────────────────────────
 
 void CodeEditorScreen::on_new_notebook() {
+    // A new notebook gets a fresh kernel, as in Jupyter.
+    kernel_->shutdown();
     cells_.clear();
     execution_counter_ = 0;
     notebook_path_.clear();
@@ -602,6 +619,20 @@ void CodeEditorScreen::on_run_cell(const QString& cell_id) {
     if (code.trimmed().isEmpty())
         return;
 
+    // Persistent kernel: outputs stream in through on_kernel_output and the
+    // kernel numbers the execution. One-shot run_code below is the fallback.
+    if (kernel_->state() != python::NotebookKernel::State::Unavailable) {
+        const QString exec_id = kernel_->execute(code);
+        if (!exec_id.isEmpty()) {
+            kernel_runs_.insert(exec_id, cell_id);
+            cells_[idx].outputs.clear();
+            cells_[idx].running = true;
+            if (cw)
+                cw->set_outputs({}, cells_[idx].execution_count);
+            return;
+        }
+    }
+
     ++execution_counter_;
     int exec_num = execution_counter_;
 
@@ -666,6 +697,69 @@ void CodeEditorScreen::on_run_cell(const QString& cell_id) {
     });
 }
 
+void CodeEditorScreen::on_kernel_output(const QString& exec_id, const QJsonObject& output) {
+    const int idx = find_cell_index(kernel_runs_.value(exec_id));
+    if (idx < 0)
+        return;
+
+    CellOutput out;
+    out.type = output.value("type").toString();
+    out.name = output.value("name").toString();
+    out.text = output.value("text").toString();
+    out.image_png = output.value("image_png").toString();
+    out.error_name = output.value("ename").toString();
+    out.error_value = output.value("evalue").toString();
+    for (const auto& line : output.value("traceback").toArray())
+        out.traceback << line.toString();
+
+    // The kernel sends print() output in chunks; keep one block per stream.
+    auto& outputs = cells_[idx].outputs;
+    if (out.type == "stream" && !outputs.isEmpty() && outputs.last().type == "stream" &&
+        outputs.last().name == out.name)
+        outputs.last().text += out.text;
+    else
+        outputs.append(out);
+
+    if (auto* widget = find_cell_widget(cells_[idx].id))
+        widget->set_outputs(outputs, cells_[idx].execution_count);
+}
+
+void CodeEditorScreen::on_kernel_finished(const QString& exec_id, int execution_count, bool ok) {
+    const QString cid = kernel_runs_.take(exec_id);
+    const int idx = find_cell_index(cid);
+    if (idx < 0)
+        return;
+
+    // No jupyter in the venv: the kernel gave up before running the cell, so
+    // run it one-shot instead (on_run_cell now takes the run_code path).
+    if (!ok && kernel_->state() == python::NotebookKernel::State::Unavailable) {
+        cells_[idx].outputs.clear();
+        on_run_cell(cid);
+        return;
+    }
+
+    if (execution_count > 0) {
+        cells_[idx].execution_count = execution_count;
+        execution_counter_ = qMax(execution_counter_, execution_count);
+    }
+    cells_[idx].running = false;
+
+    if (auto* widget = find_cell_widget(cid)) {
+        widget->set_outputs(cells_[idx].outputs, cells_[idx].execution_count);
+        widget->set_running(false);
+    }
+    update_status();
+    update_navigator();
+}
+
+void CodeEditorScreen::on_interrupt_kernel() {
+    kernel_->interrupt();
+}
+
+void CodeEditorScreen::on_restart_kernel() {
+    kernel_->restart();
+}
+
 void CodeEditorScreen::on_run_and_advance(const QString& cell_id) {
     on_run_cell(cell_id);
     advance_to_next(cell_id);
@@ -713,6 +807,7 @@ bool CodeEditorScreen::load_notebook_from_path(const QString& path) {
 
     const QJsonArray json_cells = doc.object()["cells"].toArray();
 
+    kernel_->shutdown();
     cells_.clear();
     execution_counter_ = 0;
 
@@ -892,10 +987,26 @@ void CodeEditorScreen::update_navigator() {
 void CodeEditorScreen::refresh_kernel_label() {
     if (!kernel_label_)
         return;
-    kernel_label_->setText(kernel_busy_ ? tr("KERNEL: BUSY") : tr("KERNEL: IDLE"));
+    using State = python::NotebookKernel::State;
+    const State state = kernel_ ? kernel_->state() : State::Stopped;
+    QString text = tr("KERNEL: IDLE");
+    QString color = colors::POSITIVE();
+    if (kernel_busy_ || state == State::Busy) {
+        text = tr("KERNEL: BUSY");
+        color = colors::WARNING();
+    } else if (state == State::Starting) {
+        text = tr("KERNEL: STARTING");
+        color = colors::WARNING();
+    } else if (state == State::Unavailable) {
+        text = tr("KERNEL: ONE-SHOT"); // no jupyter — each cell runs in a fresh process
+        color = colors::TEXT_TERTIARY();
+    }
+    kernel_label_->setText(text);
     kernel_label_->setStyleSheet(QString("color:%1; font-family:%2; font-size:10px; font-weight:600;"
                                          " letter-spacing:0.5px; padding:0 8px;")
-                                     .arg(kernel_busy_ ? colors::WARNING() : colors::POSITIVE(), fonts::DATA_FAMILY));
+                                     .arg(color, fonts::DATA_FAMILY));
+    if (py_label_ && kernel_ && !kernel_->language_version().isEmpty())
+        py_label_->setText(kernel_->language_version());
 }
 
 // ── Live language switch ─────────────────────────────────────────────────────
@@ -931,6 +1042,10 @@ void CodeEditorScreen::retranslateUi() {
         btn_clear_out_->setText(tr("CLEAR OUT"));
     if (btn_run_all_)
         btn_run_all_->setText(tr("▶  RUN ALL"));
+    if (btn_interrupt_)
+        btn_interrupt_->setText(tr("■ INTERRUPT"));
+    if (btn_restart_)
+        btn_restart_->setText(tr("↻ RESTART"));
     if (btn_sidebar_)
         btn_sidebar_->setText(tr("SIDEBAR"));
     if (py_label_)
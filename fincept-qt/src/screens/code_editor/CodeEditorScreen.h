@@ -4,10 +4,12 @@
 // runs responsive notebook cells with markdown rendering and collapsible output.
 #pragma once
 
+#include "python/NotebookKernel.h"
 #include "screens/common/IStatefulScreen.h"
 
 #include <QEvent>
 #include <QGridLayout>
+#include <QHash>
 #include <QHideEvent>
 #include <QJsonArray>
 #include <QJsonObject>
@@ -31,8 +33,9 @@ namespace fincept::screens {
 // -- Cell data ----------------------------------------------------------------
 
 struct CellOutput {
-    QString type;       // "stream", "error", "execute_result"
+    QString type;       // "stream", "error", "execute_result", "display_data"
     QString text;       // stdout/stderr/result text
+    QString image_png;  // base64 PNG of a display_data / execute_result (figures)
     QString name;       // "stdout" or "stderr"
     QString error_name; // for errors
     QString error_value;
@@ -209,6 +212,8 @@ class CodeEditorScreen : public QWidget, public IStatefulScreen {
     void on_clear_outputs();
     void on_toggle_sidebar();
     void on_rename_cell(const QString& cell_id);
+    void on_interrupt_kernel();
+    void on_restart_kernel();
 
     // Header / Library
     void on_view_changed(int index); // 0 = Library, 1 = Editor
@@ -235,12 +240,19 @@ class CodeEditorScreen : public QWidget, public IStatefulScreen {
     void update_status();
     void update_navigator();
     void advance_to_next(const QString& current_id);
+    void on_kernel_output(const QString& exec_id, const QJsonObject& output);
+    void on_kernel_finished(const QString& exec_id, int execution_count, bool ok);
 
     // Notebook data
     QVector<NotebookCell> cells_;
     int execution_counter_ = 0;
     QString notebook_path_;
 
+    // Persistent Jupyter kernel shared by the notebook's cells. When it is
+    // unavailable (no jupyter in the venv) cells run one-shot via run_code.
+    python::NotebookKernel* kernel_ = nullptr;
+    QHash<QString, QString> kernel_runs_; // execution id → cell id
+
     // UI
     QSplitter* splitter_ = nullptr;
     CellNavigator* navigator_ = nullptr;
@@ -259,11 +271,14 @@ class CodeEditorScreen : public QWidget, public IStatefulScreen {
     QPushButton* btn_add_cell_ = nullptr;
     QPushButton* btn_clear_out_ = nullptr;
     QPushButton* btn_run_all_ = nullptr;
+    QPushButton* btn_interrupt_ = nullptr;
+    QPushButton* btn_restart_ = nullptr;
     QPushButton* btn_sidebar_ = nullptr;
     QLabel* py_label_ = nullptr;
     QLabel* shortcuts_label_ = nullptr;
 
-    // Tracks whether the kernel is busy so retranslateUi can re-render the badge.
+    // Tracks whether a one-shot run_code cell is running (the persistent
+    // kernel reports its own state) so retranslateUi can re-render the badge.
     bool kernel_busy_ = false;
     void refresh_kernel_label();
 
@@ -29,6 +29,7 @@
 #include <QMenu>
 #include <QMessageBox>
 #include <QMouseEvent>
+#include <QPixmap>
 #include <QPushButton>
 #include <QScrollArea>
 #include <QScrollBar>
@@ -442,7 +443,16 @@ void CellWidget::set_outputs(const QVector<CellOutput>& outputs, int exec_count)
     output_content_->setVisible(true);
 
     for (const auto& out : outputs) {
-        if (out.type == "stream" || out.type == "execute_result") {
+        if (!out.image_png.isEmpty()) {
+            // Figure from the kernel (matplotlib etc.) — scaled down to the cell width.
+            QPixmap pix;
+            pix.loadFromData(QByteArray::fromBase64(out.image_png.toLatin1()), "PNG");
+            auto* img = new QLabel(output_content_);
+            img->setStyleSheet("background:transparent; padding:4px 0;");
+            img->setPixmap(pix.width() > 720 ? pix.scaledToWidth(720, Qt::SmoothTransformation) : pix);
+            output_content_layout_->addWidget(img);
+
+        } else if (out.type == "stream" || out.type == "execute_result" || out.type == "display_data") {
             // Use QTextEdit in read-only mode for proper scrolling of long output
             auto* output_view = new QTextEdit(output_content_);
             output_view->setReadOnly(true);
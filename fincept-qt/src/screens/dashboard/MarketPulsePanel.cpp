@@ -1,5 +1,6 @@
 #include "screens/dashboard/MarketPulsePanel.h"
 
+#include "core/market/MarketCalendar.h"
 #include "datahub/DataHub.h"
 #include "datahub/DataHubMetaTypes.h"
 #include "screens/dashboard/widgets/LoadingOverlay.h"
@@ -710,35 +711,16 @@ QWidget* MarketPulsePanel::build_market_hours_section() {
 
 QString MarketPulsePanel::market_status(const QString& region) {
     // Returns an English source key. Display-time translation happens in
-    // refresh_market_hours() so the key is stable for retranslateUi().
-    auto now = QDateTime::currentDateTimeUtc();
-    int hour = now.time().hour();
-    int day = now.date().dayOfWeek(); // 1=Mon, 7=Sun
-
-    if (day >= 6)
-        return QStringLiteral("CLOSED");
-
-    if (region == "US") {
-        if (hour >= 13 && hour < 14)
-            return QStringLiteral("PRE");
-        if (hour >= 14 && hour < 21)
+    // refresh_market_hours() so the key is stable for retranslateUi(). The
+    // region codes are MarketCalendar aliases (US → NYSE, IN → NSE, ...).
+    switch (core::market::MarketCalendar::instance().phase(region)) {
+        case core::market::SessionPhase::Regular:
             return QStringLiteral("OPEN");
-    } else if (region == "UK") {
-        if (hour >= 7 && hour < 8)
+        case core::market::SessionPhase::PreMarket:
             return QStringLiteral("PRE");
-        if (hour >= 8 && hour < 17)
-            return QStringLiteral("OPEN");
-    } else if (region == "JP") {
-        if (hour >= 0 && hour < 6)
-            return QStringLiteral("OPEN");
-    } else if (region == "CN") {
-        if (hour >= 1 && hour < 7)
-            return QStringLiteral("OPEN");
-    } else if (region == "IN") {
-        if (hour >= 3 && hour < 10)
-            return QStringLiteral("OPEN");
+        default:
+            return QStringLiteral("CLOSED");
     }
-    return QStringLiteral("CLOSED");
 }
 
 // ── Refresh ───────────────────────────────────────────────────────────────────
@@ -456,15 +456,17 @@ void EquityResearchScreen::load_symbol(const QString& symbol) {
     price_label_->setText(tr("Loading…"));
     update_trade_buttons(); // a new symbol may (un)hide BUY/SELL
 
+    // One deep-dive fetch covers quote, info, candles and financials for every
+    // tab; started first so the per-tab loads below coalesce into it instead
+    // of each spawning its own Python call.
+    services::equity::EquityResearchService::instance().load_deep_dive(symbol);
+
     // Overview tab always refreshes (it's the default landing tab)
     overview_tab_->set_symbol(symbol);
 
     // Also refresh whichever tab is currently open
     on_tab_changed(tab_widget_->currentIndex());
 
-    // Tell the service to go fetch quote + info data from the API
-    services::equity::EquityResearchService::instance().load_symbol(symbol);
-
     if (isVisible())
         hub_subscribe_broker_quote();
 
@@ -1,5 +1,6 @@
 #include "screens/markets/MarketsScreen.h"
 
+#include "core/market/MarketCalendar.h"
 #include "screens/markets/MarketPanelEditor.h"
 #include "screens/markets/MarketPanelStore.h"
 #include "services/markets/MarketDataService.h"
@@ -480,46 +481,48 @@ void MarketsScreen::update_auto_style() {
 }
 
 // ---------------------------------------------------------------------------
-// Session status & clocks — DST-correct via QTimeZone
+// Session status & clocks — exchange calendar (DST, holidays, early closes)
 // ---------------------------------------------------------------------------
 
 void MarketsScreen::update_session_status() {
-    QDateTime utc = QDateTime::currentDateTimeUtc();
-    QTimeZone ny_tz("America/New_York");
-    QDateTime et = utc.toTimeZone(ny_tz);
-    int day = et.date().dayOfWeek();
-    int hhmm = et.time().hour() * 100 + et.time().minute();
-    bool weekday = (day >= 1 && day <= 5);
+    const auto status = core::market::MarketCalendar::instance().status(QStringLiteral("NYSE"));
 
     QString label, color;
-    if (!weekday || hhmm < 400 || hhmm >= 2000) {
-        label = tr("NYSE: CLOSED");
-        color = ui::colors::TEXT_DIM();
-    } else if (hhmm < 930) {
-        label = tr("NYSE: PRE-MKT");
-        color = ui::colors::AMBER();
-    } else if (hhmm < 1600) {
-        label = tr("NYSE: OPEN");
-        color = ui::colors::POSITIVE();
-    } else {
-        label = tr("NYSE: AFTER-HRS");
-        color = ui::colors::AMBER();
+    switch (status.phase) {
+        case core::market::SessionPhase::PreMarket:
+            label = tr("NYSE: PRE-MKT");
+            color = ui::colors::AMBER();
+            break;
+        case core::market::SessionPhase::Regular:
+            label = tr("NYSE: OPEN");
+            color = ui::colors::POSITIVE();
+            break;
+        case core::market::SessionPhase::PostMarket:
+            label = tr("NYSE: AFTER-HRS");
+            color = ui::colors::AMBER();
+            break;
+        default:
+            label = status.holiday.isEmpty() ? tr("NYSE: CLOSED") : tr("NYSE: HOLIDAY");
+            color = ui::colors::TEXT_DIM();
+            break;
     }
 
     if (session_label_) {
         session_label_->setText(label);
+        session_label_->setToolTip(status.holiday);
         session_label_->setStyleSheet(lbl_ss(color, true, 11));
     }
 }
 
 void MarketsScreen::update_clocks() {
-    QDateTime utc = QDateTime::currentDateTimeUtc();
+    auto& cal = core::market::MarketCalendar::instance();
+    const QDateTime utc = QDateTime::currentDateTimeUtc();
     if (ny_label_)
-        ny_label_->setText(tr("NY %1").arg(utc.toTimeZone(QTimeZone("America/New_York")).toString("HH:mm:ss")));
+        ny_label_->setText(tr("NY %1").arg(utc.toTimeZone(cal.calendar("NYSE").zone()).toString("HH:mm:ss")));
     if (lon_label_)
-        lon_label_->setText(tr("LON %1").arg(utc.toTimeZone(QTimeZone("Europe/London")).toString("HH:mm:ss")));
+        lon_label_->setText(tr("LON %1").arg(utc.toTimeZone(cal.calendar("LSE").zone()).toString("HH:mm:ss")));
     if (tok_label_)
-        tok_label_->setText(tr("TOK %1").arg(utc.toTimeZone(QTimeZone("Asia/Tokyo")).toString("HH:mm:ss")));
+        tok_label_->setText(tr("TOK %1").arg(utc.toTimeZone(cal.calendar("TSE").zone()).toString("HH:mm:ss")));
 }
 
 // ---------------------------------------------------------------------------
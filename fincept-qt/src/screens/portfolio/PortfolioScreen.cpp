@@ -99,8 +99,7 @@ PortfolioScreen::PortfolioScreen(QWidget* parent) : QWidget(parent) {
                 // currency-normalisation are correct.
                 if (!perf_chart_ || !summary_loaded_)
                     return;
-                const QString want =
-                    services::PortfolioService::default_benchmark_for_currency(current_summary_.portfolio.currency);
+                const QString want = services::PortfolioService::benchmark_for(current_summary_.portfolio);
                 if (symbol != want)
                     return; // ignore the secondary SPY-for-Beta fetch
                 perf_chart_->set_benchmark_history(symbol, dates, closes);
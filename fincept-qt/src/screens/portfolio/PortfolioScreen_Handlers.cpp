@@ -140,13 +140,13 @@ void PortfolioScreen::on_summary_loaded(portfolio::PortfolioSummary summary) {
         services::PortfolioService::instance().fetch_correlation(syms);
     }
 
-    // Fetch benchmark history for perf chart overlay. Use the portfolio's
-    // currency to pick a sensible default index (TSX for CAD, SPY for USD,
-    // FTSE for GBP, …). We also always fetch SPY itself because Beta in
-    // compute_metrics() regresses against SPY regardless of currency.
+    // Fetch benchmark history for perf chart overlay: the portfolio's
+    // assigned benchmark, else a sensible default for its currency (TSX for
+    // CAD, SPY for USD, FTSE for GBP, …). We also always fetch SPY itself
+    // because Beta in compute_metrics() regresses against SPY regardless.
     {
         auto& svc = services::PortfolioService::instance();
-        const QString bench = services::PortfolioService::default_benchmark_for_currency(summary.portfolio.currency);
+        const QString bench = services::PortfolioService::benchmark_for(summary.portfolio);
         svc.fetch_benchmark_history(bench, "1y");
         if (bench != QStringLiteral("SPY"))
             svc.fetch_benchmark_history("SPY", "1y");
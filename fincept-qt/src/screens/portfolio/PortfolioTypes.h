@@ -1,7 +1,9 @@
 // src/screens/portfolio/PortfolioTypes.h
 #pragma once
 #include <QDateTime>
+#include <QHash>
 #include <QString>
+#include <QStringList>
 #include <QVector>
 
 #include <optional>
@@ -23,6 +25,9 @@ struct Portfolio {
     /// PortfolioService can route live quote fetches through the broker
     /// instead of yfinance. See migration v022.
     QString broker_account_id;
+    /// Index/ETF used for benchmark-relative analytics. Empty = the currency
+    /// default (PortfolioService::benchmark_for). See migration v054.
+    QString benchmark_symbol;
 };
 
 struct PortfolioAsset {
@@ -40,6 +45,13 @@ struct PortfolioAsset {
     QString broker_symbol;
     /// Exchange code (e.g. "NSE", "BSE"). Empty for non-broker imports.
     QString exchange;
+    /// Native (quote) currency, ISO code. Empty = infer from the symbol via
+    /// PortfolioService::listing_currency. See migration v053.
+    QString currency;
+    /// Portfolio-currency units per native unit at purchase, cost-weighted
+    /// across buys. 0 = unknown (pre-v053 rows) — the whole P&L is then
+    /// attributed to local return.
+    double cost_fx_rate = 0;
 };
 
 struct Transaction {
@@ -73,6 +85,18 @@ struct HoldingWithQuote {
     double day_change = 0;
     double day_change_percent = 0;
     double weight = 0; // % of total portfolio
+
+    // Currency layer. Prices and day_change above stay in the native quote
+    // unit; market_value, cost_basis and P&L are in the portfolio currency.
+    QString currency;              // native currency
+    double fx_rate = 1.0;          // portfolio-currency units per native unit, now
+    double cost_fx_rate = 1.0;     // same, at purchase
+    double market_value_local = 0; // native currency (minor units normalised)
+    double cost_basis_local = 0;
+    /// unrealized_pnl = local_return_pnl + currency_return_pnl:
+    /// price move at the purchase rate, plus the FX move on today's value.
+    double local_return_pnl = 0;
+    double currency_return_pnl = 0;
 };
 
 struct PortfolioSummary {
@@ -89,6 +113,13 @@ struct PortfolioSummary {
     int gainers = 0;
     int losers = 0;
     QString last_updated;
+
+    // Attribution of total_unrealized_pnl (portfolio currency).
+    double total_local_return_pnl = 0;
+    double total_currency_return_pnl = 0;
+    /// Native currency → rate used (portfolio-currency units per unit).
+    QHash<QString, double> fx_rates;
+    QStringList fx_warnings;
 };
 
 // ── Computed analytics ───────────────────────────────────────────────────────
@@ -106,6 +137,182 @@ struct ComputedMetrics {
     std::optional<double> concentration_top3; // sum of top 3 weights %
 };
 
+// ── Currency exposure & hedging ──────────────────────────────────────────────
+
+/// Exposure to one non-base currency, aggregated over the holdings listed in
+/// it. Rates are annual decimals; `hedge_cost_annual_pct` is the forward
+/// carry paid (positive) or earned (negative) by selling the currency forward.
+struct CurrencyExposure {
+    QString currency;
+    QStringList symbols;
+    double market_value_local = 0; // in `currency`
+    double market_value_base = 0;  // converted at `spot`
+    double weight = 0;             // % of portfolio (base)
+    double spot = 0;               // base units per 1 unit of `currency`
+    std::optional<double> fx_volatility; // annualized %, from 1y daily closes
+
+    double base_rate = 0;
+    double foreign_rate = 0;
+    double forward = 0;        // outright forward for the report horizon
+    double forward_points = 0; // (forward - spot) x 10,000
+    double hedge_cost_annual_pct = 0;
+
+    double suggested_hedge_ratio = 0;    // 0..1, in 25% steps
+    double suggested_hedge_notional = 0; // in `currency`
+    double hedge_cost_base = 0;          // carry of the suggested hedge over the horizon
+    std::optional<double> var_95_unhedged; // 1-day FX VaR, base ccy
+    std::optional<double> var_95_hedged;   // after the suggested hedge
+    QString rationale;
+};
+
+struct FxExposureReport {
+    QString portfolio_id;
+    QString base_currency;
+    int horizon_months = 3;
+    double total_value_base = 0;
+    double foreign_value_base = 0;
+    double foreign_weight = 0; // %
+    QVector<CurrencyExposure> exposures;
+    QStringList warnings; // missing spot/history, assumed rates, ...
+    QString generated_at;
+};
+
+// ── Dividends ────────────────────────────────────────────────────────────────
+
+/// One dividend on the shared calendar (table dividend_events). `status` is
+/// "paid" for history, "announced" when declared but not yet paid, and
+/// "projected" for events extrapolated from the payment cadence (never stored).
+struct DividendEvent {
+    QString symbol;
+    QString ex_date;  // YYYY-MM-DD
+    QString pay_date; // empty when undeclared
+    double amount = 0; // per share, listing currency
+    QString currency;
+    QString status = "paid";
+    QString alerted_at;
+};
+
+struct DividendHolding {
+    QString symbol;
+    double quantity = 0;
+    double price = 0;
+    double avg_cost = 0;
+    QString currency;
+    int payments_per_year = 0; // inferred from history; 0 = no regular dividend
+    double forward_annual_dividend = 0; // per share
+    double forward_yield = 0;           // % of current price
+    double yield_on_cost = 0;           // % of average cost
+    double projected_income_12m = 0;
+    QString next_ex_date;
+    QString next_pay_date;
+};
+
+struct DividendMonth {
+    QString month; // YYYY-MM (by pay date)
+    double income = 0;
+    QStringList symbols;
+};
+
+struct DividendSummary {
+    QString portfolio_id;
+    QVector<DividendHolding> holdings;
+    QVector<DividendMonth> months;   // next 12 calendar months, including empty ones
+    QVector<DividendEvent> upcoming; // announced + projected, next 12 months, by ex-date
+    double projected_income_12m = 0;
+    double portfolio_forward_yield = 0; // % of market value
+    QString synced_at;
+};
+
+// ── Tax lots & realized gains ────────────────────────────────────────────────
+
+/// An open (or partially sold) purchase lot. `cost_per_share` includes any
+/// wash-sale basis carried in from a disallowed loss.
+struct TaxLot {
+    QString symbol;
+    QString acquired; // YYYY-MM-DD (shifted back for wash-sale tacking)
+    double quantity = 0;
+    double cost_per_share = 0;
+    QString source_txn_id;
+};
+
+/// One lot (or part of one) closed by a SELL. Amounts are in portfolio currency.
+struct RealizedGain {
+    QString symbol;
+    QString acquired;
+    QString disposed;
+    double quantity = 0;
+    double proceeds = 0;
+    double cost_basis = 0;     // basis used for the gain (after grandfathering)
+    double adjustment = 0;     // disallowed wash-sale loss added back (>= 0)
+    double gain = 0;           // proceeds - cost_basis + adjustment
+    bool long_term = false;
+    QString tax_year;          // "2025" (US) or "FY2025-26" (India)
+    bool wash_sale = false;
+    bool grandfathered = false;
+    QString sell_txn_id;
+};
+
+struct TaxYearSummary {
+    QString tax_year;
+    double short_term_gain = 0; // net, after wash-sale adjustments
+    double long_term_gain = 0;
+    double proceeds = 0;
+    double cost_basis = 0;
+    double disallowed_loss = 0;
+    int dispositions = 0;
+};
+
+struct TaxReport {
+    QString portfolio_id;
+    QString jurisdiction; // "US" | "IN"
+    QString currency;
+    QVector<RealizedGain> realized; // chronological
+    QVector<TaxYearSummary> years;  // ascending
+    QVector<TaxLot> open_lots;
+    QStringList warnings;
+    QString generated_at;
+};
+
+// ── Benchmark-relative performance ───────────────────────────────────────────
+
+/// One aligned trading day. Returns are daily decimals; cumulative values are
+/// growth since the first aligned day (0.12 = +12%). Rolling fields are
+/// annualised over the analytics' window and empty until it fills.
+struct BenchmarkPoint {
+    QString date;
+    double portfolio_return = 0;
+    double benchmark_return = 0;
+    double active_return = 0;
+    double portfolio_cumulative = 0;
+    double benchmark_cumulative = 0;
+    double active_cumulative = 0; // portfolio_cumulative − benchmark_cumulative
+    std::optional<double> rolling_tracking_error;
+    std::optional<double> rolling_information_ratio;
+    std::optional<double> rolling_beta;
+};
+
+struct BenchmarkAnalytics {
+    QString portfolio_id;
+    QString benchmark;
+    QString benchmark_currency;
+    QString base_currency;
+    bool fx_adjusted = false; // benchmark converted into base_currency at historical FX
+    int window = 63; // rolling window, trading days
+    int observations = 0;
+    // Whole-period statistics, annualised where it applies (%).
+    std::optional<double> portfolio_return;
+    std::optional<double> benchmark_return;
+    std::optional<double> active_return;
+    std::optional<double> tracking_error;
+    std::optional<double> information_ratio;
+    std::optional<double> beta;
+    std::optional<double> up_capture;   // % of benchmark up-day return captured
+    std::optional<double> down_capture; // % of benchmark down-day return suffered
+    QVector<BenchmarkPoint> series;     // ascending by date
+    QStringList warnings;
+    QString generated_at;
+};
+
 // ── Snapshot for performance history ─────────────────────────────────────────
 
 struct PortfolioSnapshot {
@@ -2,6 +2,7 @@
 #include "screens/portfolio/views/CustomIndexView.h"
 
 #include "core/logging/Logger.h"
+#include "services/markets/CustomIndexService.h"
 #include "ui/theme/Theme.h"
 
 #define QT_CHARTS_USE_NAMESPACE
@@ -385,7 +386,9 @@ void CustomIndexView::create_index() {
     idx.portfolio_id = summary_.portfolio.id;
     idx.constituents = constituents;
 
-    auto result = CustomIndexRepository::instance().create(idx);
+    // The service assigns the @-symbol and builds the daily series when the
+    // constituents have cached bars.
+    auto result = services::CustomIndexService::instance().create(idx);
     if (result.is_err()) {
         const QString msg = QString::fromStdString(result.error());
         LOG_ERROR("CustomIndex", "Failed to create index: " + msg);
@@ -398,13 +401,15 @@ void CustomIndexView::create_index() {
     LOG_INFO("CustomIndex",
              QString("Created index '%1' (%2) with %3 constituents").arg(name, idx.method).arg(constituents.size()));
 
-    // Save initial value
-    const QString index_id = result.value();
-    const double current_val = compute_index_value(idx);
-    const QString today = QDateTime::currentDateTime().toString("yyyy-MM-dd");
-    CustomIndexRepository::instance().save_value(index_id, today, current_val);
+    // Save initial value unless a series was built
+    if (result.value().computed_at == 0) {
+        const QString index_id = result.value().id;
+        const double current_val = compute_index_value(idx);
+        const QString today = QDateTime::currentDateTime().toString("yyyy-MM-dd");
+        CustomIndexRepository::instance().save_value(index_id, today, current_val);
+    }
 
-    create_status_->setText(tr("Index '%1' created successfully.").arg(name));
+    create_status_->setText(tr("Index '%1' created successfully as %2.").arg(name, result.value().symbol));
     create_status_->setStyleSheet(QString("color:%1; font-size:9px;").arg(ui::colors::POSITIVE()));
     create_status_->show();
     name_edit_->clear();
@@ -465,7 +470,7 @@ void CustomIndexView::delete_selected_index() {
 
     const auto& idx = loaded_indices_[row];
 
-    auto r = CustomIndexRepository::instance().remove(idx.id);
+    auto r = services::CustomIndexService::instance().remove(idx);
     if (r.is_err()) {
         LOG_ERROR("CustomIndex", "Delete failed: " + QString::fromStdString(r.error()));
         return;
@@ -1,6 +1,7 @@
 // src/screens/portfolio/views/PlanningView.cpp
 #include "screens/portfolio/views/PlanningView.h"
 
+#include "services/portfolio/RetirementSimulator.h"
 #include "storage/repositories/SettingsRepository.h"
 #include "ui/theme/Theme.h"
 
@@ -23,7 +24,6 @@
 #include <algorithm>
 #include <cmath>
 #include <numeric>
-#include <random>
 
 namespace fincept::screens {
 
@@ -889,30 +889,27 @@ PlanningView::McResult PlanningView::monte_carlo(double start, double monthly, i
     McResult r;
     if (years <= 0)
         return r;
-    constexpr int kSims = 2000;
-    std::mt19937 gen(12345u); // fixed seed → stable, reproducible projections
-    std::normal_distribution<double> nd(mean_annual, std::max(vol_annual, 1e-6));
-    const double annual_contrib = monthly * 12.0;
-    QVector<double> ends;
-    ends.reserve(kSims);
-    int success = 0;
-    for (int s = 0; s < kSims; ++s) {
-        double bal = start;
-        for (int y = 0; y < years; ++y) {
-            bal = bal * (1.0 + nd(gen)) + annual_contrib;
-            if (bal < 0.0)
-                bal = 0.0;
-        }
-        ends.append(bal);
-        if (bal >= target)
-            ++success;
-    }
-    std::sort(ends.begin(), ends.end());
-    auto pct = [&](double p) { return ends[std::clamp(static_cast<int>(p * kSims), 0, kSims - 1)]; };
-    r.p5 = pct(0.05);
-    r.p50 = pct(0.50);
-    r.p95 = pct(0.95);
-    r.success_prob = static_cast<double>(success) / kSims;
+    // Inputs are already real (return net of inflation), so run the native
+    // engine with a flat price level and a single growth asset.
+    services::RetirementSimConfig cfg;
+    cfg.current_age = 0;
+    cfg.retirement_age = years;
+    cfg.end_age = years;
+    cfg.initial_balance = start;
+    cfg.contributions.append({"savings", monthly * 12.0, 0, years, 0.0, false});
+    cfg.equity = {services::ReturnDistribution::Kind::Normal, mean_annual, std::max(vol_annual, 1e-6), 5.0};
+    cfg.inflation = {services::ReturnDistribution::Kind::Normal, 0.0, 0.0, 5.0};
+    cfg.goal_target = std::max(target, 1e-9);
+    cfg.goal_age = years;
+    cfg.paths = kMcPaths;
+    cfg.seed = 12345u; // fixed seed → stable, reproducible projections
+    const auto sim = services::RetirementSimulator::run(cfg);
+    if (!sim.valid || sim.cone.isEmpty())
+        return r;
+    r.p5 = sim.cone.last().real[0];
+    r.p50 = sim.cone.last().real[3];
+    r.p95 = sim.cone.last().real[6];
+    r.success_prob = sim.goal_probability;
     r.valid = true;
     return r;
 }
@@ -1194,7 +1191,7 @@ void PlanningView::recalculate() {
                              : mc.success_prob >= 0.5 ? ui::colors::WARNING
                                                       : ui::colors::NEGATIVE;
             retire_mc_label_->setText(
-                tr("Monte Carlo (2000 runs, %1% vol): <b><span style='color:%2'>%3%% chance</span></b> "
+                tr("Monte Carlo (10,000 runs, %1% vol): <b><span style='color:%2'>%3%% chance</span></b> "
                    "of reaching your target. Likely range %4 %5 \u2013 %6 %7 (median %4 %8).")
                     .arg(QString::number(vol * 100, 'f', 0))
                     .arg(pc)
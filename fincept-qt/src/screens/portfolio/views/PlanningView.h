@@ -48,13 +48,15 @@ class PlanningView : public QWidget {
     void recompute_assumptions();
 
     // Monte Carlo wealth-path simulation calibrated to the portfolio's own
-    // mean/vol. Simulates `years` of annual returns ~Normal(mean, vol) on a
-    // balance that also receives `monthly`*12 each year.
+    // mean/vol. Runs `years` of annual returns ~Normal(mean, vol) on a
+    // balance that also receives `monthly`*12 each year, via the native
+    // RetirementSimulator engine.
     struct McResult {
         double p5 = 0, p50 = 0, p95 = 0; // ending-balance percentiles
         double success_prob = 0;         // fraction of paths reaching `target`
         bool valid = false;
     };
+    static constexpr int kMcPaths = 10000;
     McResult monte_carlo(double start, double monthly, int years, double mean_annual, double vol_annual,
                          double target) const;
 
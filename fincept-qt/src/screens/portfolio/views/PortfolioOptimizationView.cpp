@@ -131,7 +131,7 @@ QWidget* PortfolioOptimizationView::build_optimize_tab() {
         return cb;
     };
 
-    // Method/returns/risk-model items are sent to Python \u2014 keep them in English
+    // Method/returns/risk-model items are sent to the optimiser \u2014 keep them in English
     // for the cache key and analytics service. Display labels are translated
     // via the combo's currentText only after look-up against the storage key.
     method_cb_ = add_combo(tr("METHOD:"), method_field_label_,
@@ -677,7 +677,7 @@ void PortfolioOptimizationView::retranslateUi() {
         bl_table_->setHorizontalHeaderLabels(
             {tr("SYMBOL"), tr("IMPLIED RETURN"), tr("CURRENT WT"), tr("OPTIMIZED WT")});
 
-    // Combo items are English keys sent to Python (Max Sharpe, Risk Parity, etc.)
+    // Combo items are English keys sent to the optimiser (Max Sharpe, Risk Parity, etc.)
     // and intentionally not translated — they double as cache keys + the analytics
     // service contract. Combos remain untranslated. update_allocation() and
     // update_strategies/compare rebuild table content from data, no tr() literals
@@ -1097,7 +1097,7 @@ void PortfolioOptimizationView::update_compare(const QJsonObject& comparison) {
 
 // ── Risk decomposition ──────────────────────────────────────────────────────────
 // Renders the per-asset marginal risk contribution computed by the optimizer
-// (risk_contributions / marginal_risk / asset_volatility from the Python output).
+// (risk_contributions / marginal_risk / asset_volatility from the optimiser output).
 void PortfolioOptimizationView::update_risk(const QJsonObject& root) {
     const auto rc = root["risk_contributions"].toObject();
     const auto av = root["asset_volatility"].toObject();
@@ -1,10 +1,14 @@
 #include "screens/screener/ScreenerScreen.h"
 
+#include "core/events/TypedEvents.h"
 #include "datahub/DataHub.h"
 #include "datahub/DataHubMetaTypes.h"
+#include "services/agents/AgentService.h"
+#include "storage/repositories/AgentDebateRepository.h"
 #include "ui/theme/Theme.h"
 
 #include <QComboBox>
+#include <QDate>
 #include <QEvent>
 #include <QHBoxLayout>
 #include <QHeaderView>
@@ -29,6 +33,24 @@ const QStringList kBasket = {
     "MRK",  "ABBV", "LLY",   "UNH",   "XOM",  "CVX",  "SLB",  "COP",  "NEE",  "DUK",  "SO",   "CAT",  "GE",
     "HON",  "RTX",  "BA",    "DE",    "PLTR", "COIN", "SOFI", "SNAP", "UBER", "ABNB", "SHOP", "SQ"};
 
+// Basket symbols per sector, for the sector selector. Communication services
+// names (GOOGL, META, NFLX, SNAP) are grouped under tech.
+const QHash<QString, QStringList> kSectorMembers = {
+    {"tech", {"AAPL", "MSFT", "GOOGL", "NVDA", "META", "NFLX", "AMD", "INTC", "AVGO", "ORCL", "CRM", "ADBE", "CSCO",
+              "QCOM", "TXN", "PLTR", "SNAP", "SHOP"}},
+    {"financials", {"JPM", "GS", "BAC", "WFC", "MS", "C", "BRK-B", "V", "MA", "AXP", "PYPL", "COIN", "SOFI", "SQ"}},
+    {"consumer", {"AMZN", "TSLA", "WMT", "COST", "TGT", "HD", "LOW", "NKE", "MCD", "SBUX", "UBER", "ABNB"}},
+    {"healthcare", {"AMGN", "PFE", "JNJ", "MRK", "ABBV", "LLY", "UNH"}},
+    {"energy", {"XOM", "CVX", "SLB", "COP"}},
+    {"utilities", {"NEE", "DUK", "SO"}},
+    {"industrials", {"CAT", "GE", "HON", "RTX", "BA", "DE"}},
+};
+
+// Combo order — index i of sector_combo_ / sort_combo_ is key i here.
+const QStringList kSectorKeys = {"all",        "tech",   "financials", "consumer",
+                                 "healthcare", "energy", "utilities",  "industrials"};
+const QStringList kSortKeys = {"change_desc", "change_asc", "volume", "price_desc", "price_asc", "debate"};
+
 QString fmt_volume(double v) {
     if (v >= 1e9)
         return QString("%1B").arg(v / 1e9, 0, 'f', 1);
@@ -38,12 +60,22 @@ QString fmt_volume(double v) {
         return QString("%1K").arg(v / 1e3, 0, 'f', 0);
     return QString::number(static_cast<long long>(v));
 }
+
+// Verdicts older than this drop out of the DEBATE factor.
+constexpr int kDebateLookbackDays = 30;
 } // namespace
 
 ScreenerScreen::ScreenerScreen(QWidget* parent) : QWidget(parent) {
     build_ui();
     apply_styles();
     retranslate();
+    connect(&services::AgentService::instance(), &services::AgentService::debate_verdict_saved, this,
+            [this](const QString&, const QString&) {
+                reload_debate_scores();
+                apply_filter();
+            });
+    events::on<events::ScreenerApplyPreset>(
+        this, [this](const events::ScreenerApplyPreset& e) { apply_preset(e.sort, e.sector); });
 }
 
 void ScreenerScreen::build_ui() {
@@ -74,6 +106,11 @@ void ScreenerScreen::build_ui() {
     connect(search_, &QLineEdit::textChanged, this, [this](const QString&) { apply_filter(); });
     hb->addWidget(search_);
 
+    sector_combo_ = new QComboBox;
+    connect(sector_combo_, QOverload<int>::of(&QComboBox::currentIndexChanged), this,
+            [this](int) { apply_filter(); });
+    hb->addWidget(sector_combo_);
+
     sort_combo_ = new QComboBox;
     connect(sort_combo_, QOverload<int>::of(&QComboBox::currentIndexChanged), this,
             [this](int) { apply_filter(); });
@@ -92,7 +129,7 @@ void ScreenerScreen::build_ui() {
 
     // ── Results table ───────────────────────────────────────────────────────
     table_ = new QTableWidget(this);
-    table_->setColumnCount(5);
+    table_->setColumnCount(6);
     table_->setSelectionBehavior(QAbstractItemView::SelectRows);
     table_->setSelectionMode(QAbstractItemView::SingleSelection);
     table_->setEditTriggers(QAbstractItemView::NoEditTriggers);
@@ -105,6 +142,7 @@ void ScreenerScreen::build_ui() {
     table_->horizontalHeader()->setSectionResizeMode(2, QHeaderView::ResizeToContents);
     table_->horizontalHeader()->setSectionResizeMode(3, QHeaderView::ResizeToContents);
     table_->horizontalHeader()->setSectionResizeMode(4, QHeaderView::ResizeToContents);
+    table_->horizontalHeader()->setSectionResizeMode(5, QHeaderView::ResizeToContents);
     root->addWidget(table_, 1);
 }
 
@@ -140,24 +178,33 @@ void ScreenerScreen::apply_styles() {
 
 void ScreenerScreen::retranslate() {
     title_lbl_->setText(tr("STOCK SCREENER"));
-    subtitle_lbl_->setText(tr("Filter a broad large-cap basket by change, volume, or price"));
+    subtitle_lbl_->setText(tr("Filter a broad large-cap basket by change, volume, price, or agent debate verdict"));
     search_->setPlaceholderText(tr("Search symbol or name…"));
     refresh_btn_->setText(tr("REFRESH"));
 
+    const int prev_sector = sector_combo_->currentIndex();
+    QSignalBlocker block_sector(sector_combo_);
+    sector_combo_->clear();
+    sector_combo_->addItems({tr("ALL SECTORS"), tr("TECH"), tr("FINANCIALS"), tr("CONSUMER"), tr("HEALTHCARE"),
+                             tr("ENERGY"), tr("UTILITIES"), tr("INDUSTRIALS")});
+    sector_combo_->setCurrentIndex(prev_sector < 0 ? 0 : prev_sector);
+
     const int prev = sort_combo_->currentIndex();
     QSignalBlocker block(sort_combo_);
     sort_combo_->clear();
-    sort_combo_->addItems({tr("% CHANGE ↑"), tr("% CHANGE ↓"), tr("VOLUME ↓"), tr("PRICE ↓"), tr("PRICE ↑")});
+    sort_combo_->addItems(
+        {tr("% CHANGE ↑"), tr("% CHANGE ↓"), tr("VOLUME ↓"), tr("PRICE ↓"), tr("PRICE ↑"), tr("DEBATE ↓")});
     sort_combo_->setCurrentIndex(prev < 0 ? 0 : prev);
 
     table_->setHorizontalHeaderLabels(
-        {tr("SYMBOL"), tr("NAME"), tr("PRICE"), tr("CHG%"), tr("VOLUME")});
+        {tr("SYMBOL"), tr("NAME"), tr("PRICE"), tr("CHG%"), tr("VOLUME"), tr("DEBATE")});
 
     rebuild_from_cache();
 }
 
 void ScreenerScreen::showEvent(QShowEvent* e) {
     QWidget::showEvent(e);
+    reload_debate_scores();
     if (!hub_active_)
         hub_subscribe_all();
 }
@@ -204,6 +251,32 @@ void ScreenerScreen::refresh_now() {
     for (const auto& sym : kBasket)
         topics.append(QStringLiteral("market:quote:") + sym);
     hub.request(topics, /*force=*/true); // user-triggered: bypass min_interval
+    reload_debate_scores();
+    apply_filter();
+}
+
+void ScreenerScreen::reload_debate_scores() {
+    debate_scores_.clear();
+    const QString since = QDate::currentDate().addDays(-kDebateLookbackDays).toString(Qt::ISODate);
+    auto r = AgentDebateRepository::instance().latest_per_ticker(since);
+    if (r.is_err())
+        return;
+    for (const auto& v : r.value())
+        debate_scores_.insert(v.ticker, v.score);
+}
+
+void ScreenerScreen::apply_preset(const QString& sort, const QString& sector) {
+    const int sort_idx = kSortKeys.indexOf(sort);
+    const int sector_idx = kSectorKeys.indexOf(sector);
+    {
+        QSignalBlocker block_sort(sort_combo_);
+        QSignalBlocker block_sector(sector_combo_);
+        if (sort_idx >= 0)
+            sort_combo_->setCurrentIndex(sort_idx);
+        if (sector_idx >= 0)
+            sector_combo_->setCurrentIndex(sector_idx);
+    }
+    apply_filter();
 }
 
 void ScreenerScreen::rebuild_from_cache() {
@@ -231,6 +304,14 @@ void ScreenerScreen::apply_filter() {
         rows = filtered;
     }
 
+    const int sector_idx = sector_combo_ ? sector_combo_->currentIndex() : 0;
+    if (sector_idx > 0 && sector_idx < kSectorKeys.size()) {
+        const QStringList members = kSectorMembers.value(kSectorKeys[sector_idx]);
+        rows.erase(std::remove_if(rows.begin(), rows.end(),
+                                  [&members](const auto& q) { return !members.contains(q.symbol); }),
+                   rows.end());
+    }
+
     const int idx = sort_combo_ ? sort_combo_->currentIndex() : 0;
     switch (idx) {
         case 0: // % change desc (top gainers first)
@@ -248,6 +329,12 @@ void ScreenerScreen::apply_filter() {
         case 4: // price asc
             std::sort(rows.begin(), rows.end(), [](const auto& a, const auto& b) { return a.price < b.price; });
             break;
+        case 5: { // debate score desc (most bullish first, symbols without a verdict last)
+            auto key = [this](const services::QuoteData& q) { return debate_scores_.value(q.symbol, -2.0); };
+            std::stable_sort(rows.begin(), rows.end(),
+                             [&key](const auto& a, const auto& b) { return key(a) > key(b); });
+            break;
+        }
         default:
             break;
     }
@@ -287,6 +374,21 @@ void ScreenerScreen::render_rows(const QVector<services::QuoteData>& rows) {
         vol->setTextAlignment(Qt::AlignRight | Qt::AlignVCenter);
         vol->setForeground(QColor(ui::colors::TEXT_SECONDARY()));
         table_->setItem(r, 4, vol);
+
+        auto* debate = new QTableWidgetItem;
+        debate->setTextAlignment(Qt::AlignRight | Qt::AlignVCenter);
+        const auto it = debate_scores_.constFind(q.symbol);
+        if (it == debate_scores_.constEnd()) {
+            debate->setText(QStringLiteral("—"));
+            debate->setForeground(QColor(ui::colors::TEXT_TERTIARY()));
+        } else {
+            const double score = it.value();
+            debate->setText(QString("%1%2").arg(score > 0 ? "+" : "").arg(score, 0, 'f', 2));
+            debate->setForeground(QColor(score > 0   ? ui::colors::POSITIVE()
+                                         : score < 0 ? ui::colors::NEGATIVE()
+                                                     : ui::colors::TEXT_PRIMARY()));
+        }
+        table_->setItem(r, 5, debate);
     }
 }
 
@@ -22,6 +22,14 @@ namespace fincept::screens {
 ///
 /// Hub lifecycle follows P3/D3: subscribe in `showEvent`, unsubscribe in
 /// `hideEvent`, so the producer pauses when the screen isn't visible.
+///
+/// The DEBATE column is the latest agent debate verdict per symbol (signed
+/// conviction from AgentDebateRepository, last 30 days); it reloads when a
+/// new verdict is saved and can be sorted on like any other factor.
+///
+/// The sector selector narrows the basket to one sector. Both selectors can be
+/// set from outside via the `screener.apply_preset` event (voice commands such
+/// as "run screener oversold tech").
 class ScreenerScreen : public QWidget {
     Q_OBJECT
   public:
@@ -44,8 +52,11 @@ class ScreenerScreen : public QWidget {
     void apply_filter();
     void render_rows(const QVector<services::QuoteData>& rows);
     void refresh_now();
+    void reload_debate_scores();
+    void apply_preset(const QString& sort, const QString& sector);
 
     QLineEdit* search_ = nullptr;
+    QComboBox* sector_combo_ = nullptr;
     QComboBox* sort_combo_ = nullptr;
     QPushButton* refresh_btn_ = nullptr;
     QLabel* title_lbl_ = nullptr;
@@ -55,6 +66,7 @@ class ScreenerScreen : public QWidget {
     QTableWidget* table_ = nullptr;
 
     QHash<QString, services::QuoteData> row_cache_;
+    QHash<QString, double> debate_scores_; // symbol -> signed conviction, -1..1
     QVector<services::QuoteData> all_quotes_;
     bool hub_active_ = false;
 };
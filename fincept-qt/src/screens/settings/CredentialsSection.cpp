@@ -8,6 +8,7 @@
 #include "storage/secure/SecureStorage.h"
 #include "ui/theme/Theme.h"
 
+#include <QDateTime>
 #include <QFrame>
 #include <QHBoxLayout>
 #include <QLabel>
@@ -43,6 +44,7 @@ const QList<CredDef> CRED_KEYS = {
     {"POLYMARKET_SECRET", "Polymarket Secret"},
     {"POLYMARKET_PASSPHRASE", "Polymarket Passphrase"},
     {"POLYMARKET_WALLET", "Polymarket Wallet Address"},
+    {"TRADIER_API_KEY", "Tradier (market data token)"},
 // Keyed data-connector provider keys (auto-generated; one entry card each).
 // Kept in sync with PythonRunner.cpp kManagedCredentialKeys via the shared
 // X-macro include below.
@@ -51,6 +53,18 @@ const QList<CredDef> CRED_KEYS = {
 #undef FINCEPT_KEYED_CRED
 };
 
+// "today" / "3d ago" / "5mo ago" — how long a key has gone unrotated.
+QString credential_age(const QDateTime& updated_at) {
+    if (!updated_at.isValid())
+        return {};
+    const qint64 days = updated_at.daysTo(QDateTime::currentDateTimeUtc());
+    if (days < 1)
+        return CredentialsSection::tr("today");
+    if (days < 60)
+        return CredentialsSection::tr("%1d ago").arg(days);
+    return CredentialsSection::tr("%1mo ago").arg(days / 30);
+}
+
 } // namespace
 
 CredentialsSection::CredentialsSection(QWidget* parent) : QWidget(parent) {
@@ -146,8 +160,13 @@ void CredentialsSection::build_ui() {
         bhl->addWidget(save_btn);
         cred_save_btns_[key] = save_btn;
 
-        connect(save_btn, &QPushButton::clicked, this, [key, field, status_lbl]() {
+        connect(save_btn, &QPushButton::clicked, this, [this, key, field, status_lbl]() {
             QString val = field->text().trimmed();
+            if (val.isEmpty() && stored_keys_.contains(key)) {
+                // Empty field on a saved key: nothing typed, nothing to do.
+                // Clearing takes the explicit Remove button.
+                return;
+            }
             if (val.isEmpty()) {
                 SecureStorage::instance().remove(key);
                 field->setPlaceholderText(tr("Not configured"));
@@ -156,21 +175,35 @@ void CredentialsSection::build_ui() {
                     QString("color:%1;background:transparent;").arg(ui::colors::TEXT_SECONDARY()));
                 LOG_INFO("Credentials", "Cleared key: " + key);
             } else {
-                auto r = SecureStorage::instance().store(key, val);
+                // A saved key is rotated (must exist, must change); a new one stored.
+                auto& secure = SecureStorage::instance();
+                const bool rotating = stored_keys_.contains(key);
+                auto r = rotating ? secure.rotate(key, val) : secure.store(key, val);
                 if (r.is_ok()) {
-                    field->clear();
-                    field->setPlaceholderText(tr("•••••••• (saved)"));
-                    status_lbl->setText(tr("Saved ✓"));
-                    status_lbl->setStyleSheet(QString("color:%1;background:transparent;").arg(ui::colors::POSITIVE()));
-                    LOG_INFO("Credentials", "Stored key: " + key);
+                    LOG_INFO("Credentials", (rotating ? "Rotated key: " : "Stored key: ") + key);
+                    reload();
                 } else {
-                    status_lbl->setText(tr("Save failed"));
+                    status_lbl->setText(rotating ? tr("Rotate failed: %1").arg(QString::fromStdString(r.error()))
+                                                 : tr("Save failed"));
                     status_lbl->setStyleSheet(QString("color:%1;background:transparent;").arg(ui::colors::NEGATIVE()));
                     LOG_ERROR("Credentials", "Failed to store " + key);
                 }
             }
         });
 
+        auto* remove_btn = new QPushButton(tr("Remove"));
+        remove_btn->setFixedHeight(30);
+        remove_btn->setFixedWidth(70);
+        remove_btn->setStyleSheet(btn_secondary_ss());
+        bhl->addWidget(remove_btn);
+        cred_remove_btns_[key] = remove_btn;
+
+        connect(remove_btn, &QPushButton::clicked, this, [this, key]() {
+            if (SecureStorage::instance().remove(key).is_ok())
+                LOG_INFO("Credentials", "Cleared key: " + key);
+            reload();
+        });
+
         cvl->addWidget(body);
         vl->addWidget(card);
         vl->addSpacing(8);
@@ -182,6 +215,13 @@ void CredentialsSection::build_ui() {
 }
 
 void CredentialsSection::reload() {
+    // One metadata query for every card; values are never read back here.
+    stored_keys_.clear();
+    if (auto entries = SecureStorage::instance().list(); entries.is_ok()) {
+        for (const auto& e : entries.value())
+            stored_keys_.insert(e.key, e.updated_at);
+    }
+
     for (auto it = cred_fields_.constBegin(); it != cred_fields_.constEnd(); ++it) {
         const QString& key = it.key();
         auto* field = it.value();
@@ -189,11 +229,16 @@ void CredentialsSection::reload() {
         if (!field || !status)
             continue;
 
-        auto r = SecureStorage::instance().retrieve(key);
-        if (r.is_ok() && !r.value().isEmpty()) {
+        const bool saved = stored_keys_.contains(key);
+        if (auto* btn = cred_save_btns_.value(key, nullptr))
+            btn->setText(saved ? tr("Rotate") : tr("Save"));
+        if (auto* btn = cred_remove_btns_.value(key, nullptr))
+            btn->setVisible(saved);
+        if (saved) {
             field->clear();
-            field->setPlaceholderText(tr("•••••••• (saved)"));
-            status->setText(tr("Saved ✓"));
+            field->setPlaceholderText(tr("•••••••• (saved) — enter a new value to rotate"));
+            const QString age = credential_age(stored_keys_.value(key));
+            status->setText(age.isEmpty() ? tr("Saved ✓") : tr("Saved ✓ · updated %1").arg(age));
             status->setStyleSheet(QString("color:%1;background:transparent;").arg(ui::colors::POSITIVE()));
         } else {
             field->clear();
@@ -216,16 +261,16 @@ void CredentialsSection::retranslateUi() {
     if (info_)
         info_->setText(tr("Store API keys securely in the OS keychain. Keys are never written to disk in plain text."));
 
-    // Per-credential Save buttons. (Credential names are product/brand names —
-    // not translated. Provider name labels stay as-is.)
-    for (auto it = cred_save_btns_.constBegin(); it != cred_save_btns_.constEnd(); ++it) {
+    // Per-credential Remove buttons. (Credential names are product/brand
+    // names — not translated. Provider name labels stay as-is.)
+    for (auto it = cred_remove_btns_.constBegin(); it != cred_remove_btns_.constEnd(); ++it) {
         if (it.value())
-            it.value()->setText(tr("Save"));
+            it.value()->setText(tr("Remove"));
     }
 
-    // reload() re-applies state-dependent placeholders ("Not configured" /
-    // "•••••••• (saved)") and status labels ("Not set" / "Saved ✓") in the
-    // current language.
+    // reload() re-applies state-dependent text — Save / Rotate buttons,
+    // placeholders ("Not configured" / "•••••••• (saved)") and status labels
+    // ("Not set" / "Saved ✓") — in the current language.
     reload();
 }
 
@@ -1,7 +1,9 @@
 #pragma once
 // CredentialsSection.h — API key credentials panel for SettingsScreen.
 // Stores keys in OS keychain via SecureStorage; never written to disk plain.
+// Saved keys show their age and are rotated in place.
 
+#include <QDateTime>
 #include <QEvent>
 #include <QHash>
 #include <QLabel>
@@ -33,7 +35,9 @@ class CredentialsSection : public QWidget {
 
     QHash<QString, QLineEdit*> cred_fields_;      // key → password field
     QHash<QString, QLabel*> cred_status_;         // key → status label
-    QHash<QString, QPushButton*> cred_save_btns_; // key → Save button
+    QHash<QString, QPushButton*> cred_save_btns_;   // key → Save / Rotate button
+    QHash<QString, QPushButton*> cred_remove_btns_; // key → Remove button (saved keys only)
+    QHash<QString, QDateTime> stored_keys_;         // saved key → last update (UTC)
 
     // Static text widgets cached for retranslateUi.
     QLabel* title_ = nullptr;
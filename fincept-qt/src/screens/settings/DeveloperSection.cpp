@@ -1,16 +1,21 @@
-// DeveloperSection.cpp — DataHub Inspector + Agentic Mode toggle.
+// DeveloperSection.cpp — DataHub Inspector + Agentic Mode toggle + local API server.
 
 #include "screens/settings/DeveloperSection.h"
 
 #include "core/events/EventBus.h"
+#include "mcp/LocalApiServer.h"
 #include "screens/devtools/DataHubInspector.h"
 #include "screens/settings/SettingsStyles.h"
 #include "storage/repositories/SettingsRepository.h"
 #include "ui/theme/Theme.h"
 
+#include <QApplication>
 #include <QCheckBox>
+#include <QClipboard>
 #include <QFrame>
+#include <QHBoxLayout>
 #include <QLabel>
+#include <QPushButton>
 #include <QString>
 #include <QVBoxLayout>
 #include <QVariantMap>
@@ -53,11 +58,86 @@ DeveloperSection::DeveloperSection(QWidget* parent) : QWidget(parent) {
     });
     vl->addWidget(agentic_toggle_);
 
-    auto* sep = new QFrame;
-    sep->setFrameShape(QFrame::HLine);
-    sep->setStyleSheet(QString("color:%1;").arg(ui::colors::BORDER_DIM()));
-    sep->setFixedHeight(1);
-    vl->addWidget(sep);
+    auto add_separator = [vl]() {
+        auto* sep = new QFrame;
+        sep->setFrameShape(QFrame::HLine);
+        sep->setStyleSheet(QString("color:%1;").arg(ui::colors::BORDER_DIM()));
+        sep->setFixedHeight(1);
+        vl->addWidget(sep);
+    };
+    add_separator();
+
+    // ── Local API server ─────────────────────────────────────────────────────
+    api_title_ = new QLabel(tr("Local API Server"));
+    api_title_->setStyleSheet(section_title_ss());
+    vl->addWidget(api_title_);
+
+    api_desc_ = new QLabel(tr("JSON-RPC 2.0 on 127.0.0.1 for your own Python / R research code: quotes, history, "
+                              "portfolios and positions (POST /rpc, e.g. method \"market.quote\"; "
+                              "\"rpc.methods\" lists the rest). Every call needs the header "
+                              "\"Authorization: Bearer <token>\"."));
+    api_desc_->setWordWrap(true);
+    api_desc_->setStyleSheet(QString("color:%1;font-size:11px;").arg(ui::colors::TEXT_SECONDARY()));
+    vl->addWidget(api_desc_);
+
+    const auto api_cfg = mcp::LocalApiServer::instance().config();
+    auto* api_row = new QHBoxLayout;
+    api_toggle_ = new QCheckBox(tr("Enable API server"));
+    api_toggle_->setStyleSheet(check_ss());
+    api_toggle_->setChecked(api_cfg.enabled);
+    api_row->addWidget(api_toggle_);
+    auto* port_label = new QLabel(tr("Port"));
+    port_label->setStyleSheet(label_ss());
+    api_row->addWidget(port_label);
+    api_port_ = new QSpinBox;
+    api_port_->setRange(1024, 65535);
+    api_port_->setValue(api_cfg.port);
+    api_row->addWidget(api_port_);
+    api_orders_ = new QCheckBox(tr("Allow order submission"));
+    api_orders_->setStyleSheet(check_ss());
+    api_orders_->setChecked(api_cfg.allow_orders);
+    api_row->addWidget(api_orders_);
+    api_row->addStretch();
+    vl->addLayout(api_row);
+
+    auto* token_row = new QHBoxLayout;
+    api_token_ = new QLineEdit;
+    api_token_->setReadOnly(true);
+    api_token_->setEchoMode(QLineEdit::Password);
+    api_token_->setStyleSheet(input_ss());
+    api_token_->setPlaceholderText(tr("Generated when the server is first enabled"));
+    token_row->addWidget(api_token_, 1);
+    auto* show_btn = new QPushButton(tr("Show"));
+    show_btn->setCheckable(true);
+    show_btn->setStyleSheet(btn_secondary_ss());
+    token_row->addWidget(show_btn);
+    auto* copy_btn = new QPushButton(tr("Copy"));
+    copy_btn->setStyleSheet(btn_secondary_ss());
+    token_row->addWidget(copy_btn);
+    auto* rotate_btn = new QPushButton(tr("Rotate"));
+    rotate_btn->setStyleSheet(btn_danger_ss());
+    token_row->addWidget(rotate_btn);
+    vl->addLayout(token_row);
+
+    api_status_ = new QLabel;
+    api_status_->setStyleSheet(QString("color:%1;font-size:11px;").arg(ui::colors::TEXT_SECONDARY()));
+    vl->addWidget(api_status_);
+    refresh_api_status();
+
+    connect(api_toggle_, &QCheckBox::toggled, this, [this]() { apply_api_server(); });
+    connect(api_orders_, &QCheckBox::toggled, this, [this]() { apply_api_server(); });
+    connect(api_port_, &QSpinBox::editingFinished, this, [this]() { apply_api_server(); });
+    connect(show_btn, &QPushButton::toggled, this, [this](bool on) {
+        api_token_->setEchoMode(on ? QLineEdit::Normal : QLineEdit::Password);
+    });
+    connect(copy_btn, &QPushButton::clicked, this,
+            [this]() { QApplication::clipboard()->setText(api_token_->text()); });
+    connect(rotate_btn, &QPushButton::clicked, this,
+            [this]() { api_token_->setText(mcp::LocalApiServer::instance().rotate_token()); });
+    connect(&mcp::LocalApiServer::instance(), &mcp::LocalApiServer::status_changed, this,
+            [this]() { refresh_api_status(); });
+
+    add_separator();
 
     // ── DataHub Inspector ────────────────────────────────────────────────────
     inspector_title_ = new QLabel(tr("DataHub Inspector"));
@@ -74,6 +154,25 @@ DeveloperSection::DeveloperSection(QWidget* parent) : QWidget(parent) {
     vl->addWidget(new devtools::DataHubInspector(this), 1);
 }
 
+void DeveloperSection::apply_api_server() {
+    mcp::LocalApiServer::Config cfg;
+    cfg.enabled = api_toggle_->isChecked();
+    cfg.port = api_port_->value();
+    cfg.allow_orders = api_orders_->isChecked();
+    QString error;
+    if (!mcp::LocalApiServer::instance().apply(cfg, &error))
+        api_status_->setText(error);
+    else
+        refresh_api_status();
+}
+
+void DeveloperSection::refresh_api_status() {
+    auto& server = mcp::LocalApiServer::instance();
+    api_token_->setText(server.stored_token());
+    api_status_->setText(server.is_listening() ? tr("Listening on %1/rpc").arg(server.endpoint())
+                                               : tr("Stopped"));
+}
+
 void DeveloperSection::changeEvent(QEvent* event) {
     if (event->type() == QEvent::LanguageChange)
         retranslateUi();
@@ -91,6 +190,21 @@ void DeveloperSection::retranslateUi() {
                                   "Leave off for standard chatbot behavior."));
     if (agentic_toggle_)
         agentic_toggle_->setText(tr("Enable Agentic Mode"));
+    if (api_title_)
+        api_title_->setText(tr("Local API Server"));
+    if (api_desc_)
+        api_desc_->setText(tr("JSON-RPC 2.0 on 127.0.0.1 for your own Python / R research code: quotes, history, "
+                              "portfolios and positions (POST /rpc, e.g. method \"market.quote\"; "
+                              "\"rpc.methods\" lists the rest). Every call needs the header "
+                              "\"Authorization: Bearer <token>\"."));
+    if (api_toggle_)
+        api_toggle_->setText(tr("Enable API server"));
+    if (api_orders_)
+        api_orders_->setText(tr("Allow order submission"));
+    if (api_token_)
+        api_token_->setPlaceholderText(tr("Generated when the server is first enabled"));
+    if (api_status_)
+        refresh_api_status();
     if (inspector_title_)
         inspector_title_->setText(tr("DataHub Inspector"));
     if (inspector_desc_)
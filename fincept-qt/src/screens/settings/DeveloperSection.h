@@ -1,9 +1,11 @@
 #pragma once
-// DeveloperSection.h — DataHub Inspector and other devtools surfaces.
+// DeveloperSection.h — DataHub Inspector, local API server and other devtools surfaces.
 
 #include <QCheckBox>
 #include <QEvent>
 #include <QLabel>
+#include <QLineEdit>
+#include <QSpinBox>
 #include <QWidget>
 
 namespace fincept::screens {
@@ -20,10 +22,20 @@ class DeveloperSection : public QWidget {
     /// Re-apply tr() lookups to every widget whose text we keep a handle to.
     /// Called from changeEvent() on QEvent::LanguageChange.
     void retranslateUi();
+    /// Pushes the API server widgets into LocalApiServer::apply().
+    void apply_api_server();
+    void refresh_api_status();
 
     QLabel* agentic_title_ = nullptr;
     QLabel* agentic_desc_ = nullptr;
     QCheckBox* agentic_toggle_ = nullptr;
+    QLabel* api_title_ = nullptr;
+    QLabel* api_desc_ = nullptr;
+    QCheckBox* api_toggle_ = nullptr;
+    QSpinBox* api_port_ = nullptr;
+    QCheckBox* api_orders_ = nullptr;
+    QLineEdit* api_token_ = nullptr;
+    QLabel* api_status_ = nullptr;
     QLabel* inspector_title_ = nullptr;
     QLabel* inspector_desc_ = nullptr;
 };
@@ -529,9 +529,10 @@ void LlmConfigSection::populate_form(const QString& provider) {
     provider_edit_->setText(provider);
 
     bool is_fincept = (provider.toLower() == "fincept");
-    bool is_ollama = (provider.toLower() == "ollama");
+    // Local servers (Ollama, llama.cpp) take no key and list their models live.
+    bool is_local = !is_fincept && !ai_chat::provider_requires_api_key(provider.toLower());
 
-    // Populate model combo with fallback suggestions (empty for ollama — see fallback_models).
+    // Populate model combo with fallback suggestions (empty for local providers — see fallback_models).
     model_combo_->blockSignals(true);
     model_combo_->clear();
     model_combo_->addItems(fallback_models(provider));
@@ -574,7 +575,7 @@ void LlmConfigSection::populate_form(const QString& provider) {
                 model_combo_->setVisible(false);
                 fetch_btn_->setVisible(false);
                 base_url_edit_->setVisible(false);
-            } else if (is_ollama) {
+            } else if (is_local) {
                 // Local provider — no API key needed. Mark the field clearly so users
                 // don't think it's broken or required.
                 api_key_edit_->clear();
@@ -586,7 +587,7 @@ void LlmConfigSection::populate_form(const QString& provider) {
                 fetch_btn_->setEnabled(true);
                 base_url_edit_->setVisible(true);
                 base_url_edit_->setEnabled(true);
-                // Auto-fetch installed models from the local Ollama server so the
+                // Auto-fetch installed models from the local server so the
                 // combo only shows what the user actually has, not hardcoded guesses.
                 QTimer::singleShot(0, this, [this]() { on_fetch_models(); });
             } else {
@@ -606,7 +607,7 @@ void LlmConfigSection::populate_form(const QString& provider) {
 
     // New provider — clear form
     api_key_edit_->clear();
-    api_key_edit_->setEnabled(!is_fincept && !is_ollama);
+    api_key_edit_->setEnabled(!is_fincept && !is_local);
     if (is_fincept) {
         const QString stored = fincept::auth::AuthManager::instance().fincept_api_key();
         if (!stored.isEmpty())
@@ -616,7 +617,7 @@ void LlmConfigSection::populate_form(const QString& provider) {
         model_combo_->setVisible(false);
         fetch_btn_->setVisible(false);
         base_url_edit_->setVisible(false);
-    } else if (is_ollama) {
+    } else if (is_local) {
         api_key_edit_->setPlaceholderText(tr("Not required — local provider"));
         model_combo_->setVisible(true);
         model_combo_->setEnabled(true);
@@ -678,7 +679,7 @@ void LlmConfigSection::on_save_provider() {
     }
 
     // Basic validation
-    if (!is_fincept && provider != "ollama" && cfg.api_key.isEmpty()) {
+    if (!is_fincept && ai_chat::provider_requires_api_key(provider) && cfg.api_key.isEmpty()) {
         show_status(tr("API key is required for %1").arg(provider), true);
         return;
     }
@@ -780,7 +781,7 @@ void LlmConfigSection::on_test_connection() {
         return;
     }
 
-    if (provider != "ollama") {
+    if (ai_chat::provider_requires_api_key(provider)) {
         if (api_key_edit_->text().trimmed().isEmpty()) {
             show_status(tr("API key required for test"), true);
             return;
@@ -822,7 +823,7 @@ void LlmConfigSection::on_fetch_models() {
         return;
     }
 
-    if (provider != "ollama") {
+    if (ai_chat::provider_requires_api_key(provider)) {
         if (api_key_edit_->text().trimmed().isEmpty()) {
             show_status(tr("Enter API key first, then fetch models"), true);
             return;
@@ -849,6 +850,8 @@ void LlmConfigSection::on_models_fetched(const QString& provider, const QStringL
         // the local server in case that's the cause.
         if (provider.toLower() == "ollama")
             show_status(tr("Ollama fetch failed: %1 — check `ollama serve` and base URL").arg(error), true);
+        else if (provider.toLower() == "llama_cpp")
+            show_status(tr("llama.cpp fetch failed: %1 — check `llama-server` and base URL").arg(error), true);
         else
             show_status(tr("Fetch failed: ") + error, true);
         return;
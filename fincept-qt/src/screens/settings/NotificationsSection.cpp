@@ -7,6 +7,7 @@
 #include "screens/settings/SettingsStyles.h"
 #include "services/notifications/NotificationService.h"
 #include "storage/repositories/SettingsRepository.h"
+#include "storage/secure/SecureStorage.h"
 #include "ui/theme/Theme.h"
 
 #include <QFrame>
@@ -57,11 +58,11 @@ const QVector<ProviderDef>& provider_defs() {
          "✈",
          {{"bot_token", "Bot Token", "Enter bot token from @BotFather", true},
           {"chat_id", "Chat ID", "e.g. 123456789"}}},
-        {"discord", "Discord", "", {{"webhook_url", "Webhook URL", "https://discord.com/api/webhooks/..."}}},
+        {"discord", "Discord", "", {{"webhook_url", "Webhook URL", "https://discord.com/api/webhooks/...", true}}},
         {"slack",
          "Slack",
          "",
-         {{"webhook_url", "Webhook URL", "https://hooks.slack.com/services/..."},
+         {{"webhook_url", "Webhook URL", "https://hooks.slack.com/services/...", true},
           {"channel", "Channel", "#alerts (optional)"}}},
         {"email",
          "Email",
@@ -103,11 +104,14 @@ const QVector<ProviderDef>& provider_defs() {
         {"mattermost",
          "Mattermost",
          "",
-         {{"webhook_url", "Webhook URL", "https://your-mattermost.com/hooks/..."},
+         {{"webhook_url", "Webhook URL", "https://your-mattermost.com/hooks/...", true},
           {"channel", "Channel", "#town-square (optional)"},
           {"username", "Username", "Fincept (optional)"}}},
-        {"teams", "MS Teams", "", {{"webhook_url", "Webhook URL", "https://outlook.office.com/webhook/..."}}},
-        {"webhook", "Webhook", "", {{"url", "URL", "https://your-endpoint.com/notify"}, {"method", "Method", "POST"}}},
+        {"teams", "MS Teams", "", {{"webhook_url", "Webhook URL", "https://outlook.office.com/webhook/...", true}}},
+        {"webhook",
+         "Webhook",
+         "",
+         {{"url", "URL", "https://your-endpoint.com/notify", true}, {"method", "Method", "POST"}}},
         {"pagerduty", "PagerDuty", "", {{"routing_key", "Routing Key", "32-character integration key", true}}},
         {"opsgenie", "Opsgenie", "", {{"api_key", "API Key", "Your Opsgenie API key", true}}},
         {"sms",
@@ -121,6 +125,20 @@ const QVector<ProviderDef>& provider_defs() {
     return defs;
 }
 
+// Password fields live in SecureStorage under the same "notif_<id>.<field>"
+// key the providers read them from (BaseProvider::get_secret).
+bool is_secret_field(const QString& provider_id, const QString& key) {
+    for (const auto& def : provider_defs()) {
+        if (def.id != provider_id)
+            continue;
+        for (const auto& fd : def.fields) {
+            if (fd.key == key)
+                return fd.is_password;
+        }
+    }
+    return false;
+}
+
 } // namespace
 
 NotificationsSection::NotificationsSection(QWidget* parent) : QWidget(parent) {
@@ -307,6 +325,10 @@ void NotificationsSection::build_ui() {
     trigger_news_->setStyleSheet(check_ss());
     trigger_orders_ = new QCheckBox;
     trigger_orders_->setStyleSheet(check_ss());
+    trigger_dividends_ = new QCheckBox;
+    trigger_dividends_->setStyleSheet(check_ss());
+    trigger_algo_ = new QCheckBox;
+    trigger_algo_->setStyleSheet(check_ss());
 
     auto* row_inapp =
         make_row(tr("In-App Alerts (toast + bell)"), trigger_inapp_, tr("Show slide-in toasts and update bell badge."));
@@ -367,6 +389,16 @@ void NotificationsSection::build_ui() {
     capture_row_labels(row_orders, &row_orders_lbl_, &row_orders_desc_);
     vl->addWidget(row_orders);
 
+    auto* row_dividends = make_row(tr("Ex-Dividend Reminders"), trigger_dividends_,
+                                   tr("Notify a few days before a held symbol goes ex-dividend."));
+    capture_row_labels(row_dividends, &row_dividends_lbl_, &row_dividends_desc_);
+    vl->addWidget(row_dividends);
+
+    auto* row_algo = make_row(tr("Algo Trading Alerts"), trigger_algo_,
+                              tr("Notify on algo deployment fills, errors, crashes and the kill switch."));
+    capture_row_labels(row_algo, &row_algo_lbl_, &row_algo_desc_);
+    vl->addWidget(row_algo);
+
     vl->addSpacing(16);
 
     // Save
@@ -380,6 +412,8 @@ void NotificationsSection::build_ui() {
         repo.set("notifications.price_alerts", b(trigger_price_->isChecked()), "notifications");
         repo.set("notifications.news_alerts", b(trigger_news_->isChecked()), "notifications");
         repo.set("notifications.order_fills", b(trigger_orders_->isChecked()), "notifications");
+        repo.set("notifications.dividend_alerts", b(trigger_dividends_->isChecked()), "notifications");
+        repo.set("notifications.algo_alerts", b(trigger_algo_->isChecked()), "notifications");
         repo.set("notifications.news_breaking", b(news_breaking_->isChecked()), "notifications");
         repo.set("notifications.news_monitors", b(news_monitors_->isChecked()), "notifications");
         repo.set("notifications.news_deviations", b(news_deviations_->isChecked()), "notifications");
@@ -414,6 +448,10 @@ void NotificationsSection::reload() {
         trigger_price_->setChecked(get_bool("notifications.price_alerts", true));
     if (trigger_orders_)
         trigger_orders_->setChecked(get_bool("notifications.order_fills", true));
+    if (trigger_dividends_)
+        trigger_dividends_->setChecked(get_bool("notifications.dividend_alerts", true));
+    if (trigger_algo_)
+        trigger_algo_->setChecked(get_bool("notifications.algo_alerts", true));
 
     const bool news_on = get_bool("notifications.news_alerts", false);
     if (trigger_news_)
@@ -444,7 +482,14 @@ void NotificationsSection::reload() {
         for (const auto& fd : def.fields) {
             if (!pw.fields.contains(fd.key))
                 continue;
-            auto r = repo.get(cat + "." + fd.key);
+            const QString key = cat + "." + fd.key;
+            auto r = repo.get(key);
+            if (fd.is_password) {
+                const QString plaintext = r.is_ok() ? r.value() : QString{};
+                r = SecureStorage::instance().adopt(key, plaintext);
+                if (r.is_ok() && !plaintext.isEmpty())
+                    repo.remove(key);
+            }
             if (r.is_ok() && pw.fields[fd.key])
                 pw.fields[fd.key]->setText(r.value());
         }
@@ -482,6 +527,14 @@ void NotificationsSection::retranslateUi() {
         row_orders_lbl_->setText(tr("Order Fill Alerts"));
     if (row_orders_desc_)
         row_orders_desc_->setText(tr("Notify when orders are filled or rejected."));
+    if (row_dividends_lbl_)
+        row_dividends_lbl_->setText(tr("Ex-Dividend Reminders"));
+    if (row_dividends_desc_)
+        row_dividends_desc_->setText(tr("Notify a few days before a held symbol goes ex-dividend."));
+    if (row_algo_lbl_)
+        row_algo_lbl_->setText(tr("Algo Trading Alerts"));
+    if (row_algo_desc_)
+        row_algo_desc_->setText(tr("Notify on algo deployment fills, errors, crashes and the kill switch."));
 
     // News sub-option rows.
     if (row_breaking_lbl_)
@@ -525,8 +578,20 @@ void NotificationsSection::save_provider_fields(const QString& provider_id, cons
         repo.set(cat + ".enabled", pw.enabled->isChecked() ? "1" : "0", cat);
 
     for (auto it = pw.fields.constBegin(); it != pw.fields.constEnd(); ++it) {
-        if (it.value())
-            repo.set(cat + "." + it.key(), it.value()->text().trimmed(), cat);
+        if (!it.value())
+            continue;
+        const QString key = cat + "." + it.key();
+        const QString value = it.value()->text().trimmed();
+        if (!is_secret_field(provider_id, it.key())) {
+            repo.set(key, value, cat);
+            continue;
+        }
+        auto& secure = SecureStorage::instance();
+        if (value.isEmpty())
+            secure.remove(key);
+        else if (secure.store(key, value).is_err())
+            continue;
+        repo.remove(key);
     }
 }
 
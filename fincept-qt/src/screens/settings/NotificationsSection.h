@@ -50,6 +50,8 @@ class NotificationsSection : public QWidget {
     QCheckBox* trigger_price_ = nullptr;
     QCheckBox* trigger_news_ = nullptr;
     QCheckBox* trigger_orders_ = nullptr;
+    QCheckBox* trigger_dividends_ = nullptr;
+    QCheckBox* trigger_algo_ = nullptr;
 
     // News alert sub-options (visible only when trigger_news_ is checked)
     QCheckBox* news_breaking_ = nullptr;
@@ -72,6 +74,10 @@ class NotificationsSection : public QWidget {
     QLabel* row_news_desc_ = nullptr;
     QLabel* row_orders_lbl_ = nullptr;
     QLabel* row_orders_desc_ = nullptr;
+    QLabel* row_dividends_lbl_ = nullptr;
+    QLabel* row_dividends_desc_ = nullptr;
+    QLabel* row_algo_lbl_ = nullptr;
+    QLabel* row_algo_desc_ = nullptr;
     QLabel* row_breaking_lbl_ = nullptr;
     QLabel* row_breaking_desc_ = nullptr;
     QLabel* row_monitors_lbl_ = nullptr;
@@ -7,6 +7,7 @@
 #include "screens/settings/SettingsStyles.h"
 #include "ui/theme/Theme.h"
 
+#include <QCheckBox>
 #include <QCoreApplication>
 #include <QFrame>
 #include <QHBoxLayout>
@@ -65,8 +66,12 @@ QWidget* ProfilesSection::build_content() {
     vl->addWidget(title);
 
     auto* desc = new QLabel(tr("Each profile has its own isolated database, credentials, logs and workspaces.\n"
-                               "Launch the terminal with  --profile <name>  to open a specific profile.\n"
-                               "Different profiles can run simultaneously — useful for separate trading accounts."));
+                               "Launch the terminal with  --profile <name>  to open a specific profile, or "
+                               "--choose-profile to pick one.\n"
+                               "Setup from a shell:  --list-profiles,  --create-profile <name>,  "
+                               "--set-startup-profile <name>,  --ask-profile-at-startup on|off.\n"
+                               "Different profiles can run simultaneously — useful for separate trading accounts "
+                               "or keeping research apart from live trading."));
     desc->setWordWrap(true);
     desc->setStyleSheet(label_ss());
     vl->addWidget(desc);
@@ -97,6 +102,21 @@ QWidget* ProfilesSection::build_content() {
         name_lbl->setStyleSheet(label_ss());
         hl->addWidget(name_lbl, 1);
 
+        if (name == pm.startup_profile()) {
+            auto* startup = new QLabel(tr("OPENS AT STARTUP"));
+            startup->setStyleSheet(QString("color:%1;font-size:10px;background:transparent;")
+                                       .arg(ui::colors::TEXT_SECONDARY()));
+            hl->addWidget(startup);
+        } else {
+            auto* startup_btn = new QPushButton(tr("Open at startup"));
+            startup_btn->setStyleSheet(btn_secondary_ss());
+            connect(startup_btn, &QPushButton::clicked, this, [this, name]() {
+                ProfileManager::instance().set_startup_profile(name);
+                rebuild();
+            });
+            hl->addWidget(startup_btn);
+        }
+
         if (name == pm.active()) {
             auto* badge = new QLabel(tr("ACTIVE"));
             badge->setStyleSheet(
@@ -116,6 +136,12 @@ QWidget* ProfilesSection::build_content() {
         list_vl->addWidget(row);
     }
     vl->addWidget(list_widget);
+
+    auto* ask_check = new QCheckBox(tr("Ask which profile to open at startup"));
+    ask_check->setStyleSheet(check_ss());
+    ask_check->setChecked(pm.ask_at_startup());
+    connect(ask_check, &QCheckBox::toggled, this, [](bool on) { ProfileManager::instance().set_ask_at_startup(on); });
+    vl->addWidget(ask_check);
     vl->addWidget(make_sep());
 
     auto* new_title = new QLabel(tr("Create new profile"));
@@ -61,6 +61,13 @@ static QString combo_ss() {
         .arg(ui::colors::BG_RAISED(), ui::colors::TEXT_PRIMARY(), ui::colors::BORDER_MED(), ui::colors::AMBER(),
              ui::colors::BG_HOVER());
 }
+static QString checkbox_ss() {
+    return QString("QCheckBox{color:%1;background:transparent;}"
+                   "QCheckBox::indicator{width:14px;height:14px;}"
+                   "QCheckBox::indicator:unchecked{border:1px solid %2;background:%3;}"
+                   "QCheckBox::indicator:checked{border:1px solid %4;background:%4;}")
+        .arg(ui::colors::TEXT_PRIMARY(), ui::colors::BORDER_MED(), ui::colors::BG_RAISED(), ui::colors::AMBER());
+}
 static QString btn_primary_ss() {
     return QString("QPushButton{background:%1;color:%2;border:none;font-weight:700;padding:0 16px;height:32px;}"
                    "QPushButton:hover{background:%3;}")
@@ -131,6 +138,7 @@ void VoiceConfigSection::build_ui() {
     stt_provider_combo_->setStyleSheet(combo_ss());
     stt_provider_combo_->addItem(tr("Google (free, default)"), "google");
     stt_provider_combo_->addItem(tr("Deepgram (API key required)"), "deepgram");
+    stt_provider_combo_->addItem(tr("Whisper (local, offline)"), "whisper");
     vl->addWidget(labelled_row(tr("Provider"), stt_provider_combo_, &stt_provider_row_lbl_));
 
     // STT Deepgram-only rows
@@ -178,6 +186,35 @@ void VoiceConfigSection::build_ui() {
 
     vl->addWidget(stt_dg_group_);
 
+    // STT Whisper-only rows. The model is downloaded on first use; larger
+    // models are more accurate but slower on CPU.
+    stt_wh_group_ = new QWidget;
+    auto* stt_wh_vl = new QVBoxLayout(stt_wh_group_);
+    stt_wh_vl->setContentsMargins(0, 0, 0, 0);
+    stt_wh_vl->setSpacing(10);
+
+    whisper_model_combo_ = new QComboBox;
+    whisper_model_combo_->setStyleSheet(combo_ss());
+    whisper_model_combo_->addItem(tr("tiny.en (fastest, ~75 MB)"), "tiny.en");
+    whisper_model_combo_->addItem(tr("base.en (recommended, ~140 MB)"), "base.en");
+    whisper_model_combo_->addItem(tr("small.en (more accurate, ~470 MB)"), "small.en");
+    whisper_model_combo_->addItem(tr("medium.en (most accurate, ~1.5 GB)"), "medium.en");
+    whisper_model_combo_->addItem(tr("base (multilingual)"), "base");
+    whisper_model_combo_->addItem(tr("small (multilingual)"), "small");
+    stt_wh_vl->addWidget(labelled_row(tr("Whisper model"), whisper_model_combo_, &whisper_model_row_lbl_));
+
+    whisper_language_combo_ = new QComboBox;
+    whisper_language_combo_->setStyleSheet(combo_ss());
+    whisper_language_combo_->addItem(tr("English"), "en");
+    whisper_language_combo_->addItem(tr("Auto-detect (multilingual models)"), "auto");
+    stt_wh_vl->addWidget(labelled_row(tr("Language"), whisper_language_combo_, &whisper_language_row_lbl_));
+
+    vl->addWidget(stt_wh_group_);
+
+    voice_commands_cb_ = new QCheckBox(tr("Run spoken commands (\"quote AAPL\", \"run screener oversold tech\")"));
+    voice_commands_cb_->setStyleSheet(checkbox_ss());
+    vl->addWidget(voice_commands_cb_);
+
     // ── TTS provider ─────────────────────────────────────────────────────────
     tts_section_lbl_ = new QLabel(tr("TEXT-TO-SPEECH (assistant reply -> spoken audio)"));
     tts_section_lbl_->setStyleSheet(subtitle_ss());
@@ -250,12 +287,7 @@ void VoiceConfigSection::build_ui() {
     vl->addWidget(clap_section_lbl_);
 
     clap_enabled_cb_ = new QCheckBox(tr("Clap to open mic"));
-    clap_enabled_cb_->setStyleSheet(
-        QString("QCheckBox{color:%1;background:transparent;}"
-                "QCheckBox::indicator{width:14px;height:14px;}"
-                "QCheckBox::indicator:unchecked{border:1px solid %2;background:%3;}"
-                "QCheckBox::indicator:checked{border:1px solid %4;background:%4;}")
-            .arg(ui::colors::TEXT_PRIMARY(), ui::colors::BORDER_MED(), ui::colors::BG_RAISED(), ui::colors::AMBER()));
+    clap_enabled_cb_->setStyleSheet(checkbox_ss());
     vl->addWidget(clap_enabled_cb_);
 
     clap_blurb_lbl_ = new QLabel(tr("Listens to the mic in the background and opens the chat bubble when "
@@ -339,6 +371,14 @@ void VoiceConfigSection::reload() {
     gain_spin_->setValue(cfg.get("voice/deepgram/gain", 3.0).toDouble());
     device_edit_->setText(cfg.get("voice/deepgram/device", "").toString());
 
+    // STT-Whisper tunables
+    idx = whisper_model_combo_->findData(cfg.get("voice/whisper/model", "base.en").toString());
+    whisper_model_combo_->setCurrentIndex(idx >= 0 ? idx : 1);
+    idx = whisper_language_combo_->findData(cfg.get("voice/whisper/language", "en").toString());
+    whisper_language_combo_->setCurrentIndex(idx >= 0 ? idx : 0);
+
+    voice_commands_cb_->setChecked(cfg.get("voice/commands/enabled", true).toBool());
+
     // TTS-Deepgram tunables
     const QString tts_model = cfg.get("voice/deepgram/tts_model", "aura-2-thalia-en").toString();
     idx = tts_model_combo_->findData(tts_model);
@@ -362,6 +402,7 @@ void VoiceConfigSection::apply_provider_visibility() {
     const bool any_dg = stt_dg || tts_dg;
 
     stt_dg_group_->setVisible(stt_dg);
+    stt_wh_group_->setVisible(stt == "whisper");
     tts_dg_group_->setVisible(tts_dg);
     deepgram_group_->setVisible(any_dg);
     test_btn_->setVisible(any_dg);
@@ -397,6 +438,9 @@ void VoiceConfigSection::on_save() {
     cfg.set("voice/deepgram/gain", QString::number(gain_spin_->value(), 'f', 1));
     cfg.set("voice/deepgram/device", device_edit_->text().trimmed());
     cfg.set("voice/deepgram/tts_model", tts_model_combo_->currentData().toString());
+    cfg.set("voice/whisper/model", whisper_model_combo_->currentData().toString());
+    cfg.set("voice/whisper/language", whisper_language_combo_->currentData().toString());
+    cfg.set("voice/commands/enabled", voice_commands_cb_->isChecked());
 
     cfg.set("voice/clap_to_start/enabled", clap_enabled_cb_->isChecked());
     cfg.set("voice/clap_to_start/mode", clap_mode_combo_->currentData().toString());
@@ -532,7 +576,26 @@ void VoiceConfigSection::retranslateUi() {
     if (stt_provider_combo_) {
         stt_provider_combo_->setItemText(0, tr("Google (free, default)"));
         stt_provider_combo_->setItemText(1, tr("Deepgram (API key required)"));
+        stt_provider_combo_->setItemText(2, tr("Whisper (local, offline)"));
+    }
+    if (whisper_model_row_lbl_)
+        whisper_model_row_lbl_->setText(tr("Whisper model"));
+    if (whisper_language_row_lbl_)
+        whisper_language_row_lbl_->setText(tr("Language"));
+    if (whisper_model_combo_) {
+        whisper_model_combo_->setItemText(0, tr("tiny.en (fastest, ~75 MB)"));
+        whisper_model_combo_->setItemText(1, tr("base.en (recommended, ~140 MB)"));
+        whisper_model_combo_->setItemText(2, tr("small.en (more accurate, ~470 MB)"));
+        whisper_model_combo_->setItemText(3, tr("medium.en (most accurate, ~1.5 GB)"));
+        whisper_model_combo_->setItemText(4, tr("base (multilingual)"));
+        whisper_model_combo_->setItemText(5, tr("small (multilingual)"));
+    }
+    if (whisper_language_combo_) {
+        whisper_language_combo_->setItemText(0, tr("English"));
+        whisper_language_combo_->setItemText(1, tr("Auto-detect (multilingual models)"));
     }
+    if (voice_commands_cb_)
+        voice_commands_cb_->setText(tr("Run spoken commands (\"quote AAPL\", \"run screener oversold tech\")"));
     if (stt_model_combo_) {
         stt_model_combo_->setItemText(0, tr("nova-3 (recommended)"));
         stt_model_combo_->setItemText(2, tr("enhanced"));
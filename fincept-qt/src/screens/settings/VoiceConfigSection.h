@@ -65,6 +65,13 @@ class VoiceConfigSection : public QWidget {
     QDoubleSpinBox* gain_spin_ = nullptr;
     QLineEdit* device_edit_ = nullptr;
 
+    // Whisper (local) STT tunables
+    QComboBox* whisper_model_combo_ = nullptr;
+    QComboBox* whisper_language_combo_ = nullptr;
+
+    // Spoken commands ("quote AAPL") — any STT provider
+    QCheckBox* voice_commands_cb_ = nullptr;
+
     // Deepgram TTS tunables
     QComboBox* tts_model_combo_ = nullptr;
 
@@ -80,6 +87,7 @@ class VoiceConfigSection : public QWidget {
     // Container groups for visibility toggling
     QWidget* deepgram_group_ = nullptr; // shared key + buttons
     QWidget* stt_dg_group_ = nullptr;   // Deepgram STT-only rows
+    QWidget* stt_wh_group_ = nullptr;   // Whisper STT-only rows
     QWidget* tts_dg_group_ = nullptr;   // Deepgram TTS-only rows
 
     // ── Fixed text widgets / row labels (captured for retranslateUi) ──────────
@@ -92,6 +100,8 @@ class VoiceConfigSection : public QWidget {
     QLabel* keyterms_row_lbl_ = nullptr;
     QLabel* gain_row_lbl_ = nullptr;
     QLabel* device_row_lbl_ = nullptr;
+    QLabel* whisper_model_row_lbl_ = nullptr;
+    QLabel* whisper_language_row_lbl_ = nullptr;
     QLabel* tts_section_lbl_ = nullptr;
     QLabel* tts_provider_row_lbl_ = nullptr;
     QLabel* tts_voice_row_lbl_ = nullptr;
@@ -10,6 +10,7 @@
 
 #include <QDateTime>
 #include <QEvent>
+#include <QFileDialog>
 #include <QHBoxLayout>
 #include <QLabel>
 #include <QMessageBox>
@@ -204,6 +205,15 @@ void SetupScreen::build_ui() {
     connect(skip_btn_, &QPushButton::clicked, this, &SetupScreen::on_skip_clicked);
     cl->addWidget(skip_btn_);
 
+    // Shown after a failure — for machines that cannot reach the download hosts.
+    offline_btn_ = new QPushButton(center);
+    offline_btn_->setFixedHeight(36);
+    offline_btn_->setCursor(Qt::PointingHandCursor);
+    offline_btn_->setVisible(false);
+    offline_btn_->setStyleSheet(skip_btn_->styleSheet());
+    connect(offline_btn_, &QPushButton::clicked, this, &SetupScreen::on_offline_clicked);
+    cl->addWidget(offline_btn_);
+
     install_dir_lbl_ = new QLabel(center);
     install_dir_lbl_->setAlignment(Qt::AlignCenter);
     install_dir_lbl_->setStyleSheet(QString("color:%1; font-family:%2; font-size:9px; margin-top:6px;")
@@ -333,6 +343,8 @@ void SetupScreen::retranslateUi() {
     update_elapsed_label();
     if (skip_btn_)
         skip_btn_->setText(tr("SKIP & CONTINUE"));
+    if (offline_btn_)
+        offline_btn_->setText(tr("INSTALL FROM OFFLINE BUNDLE..."));
 }
 
 void SetupScreen::update_subtitle() {
@@ -399,6 +411,8 @@ void SetupScreen::update_status_label() {
         case StatusState::Failed:
             text = tr("Setup failed: %1")
                        .arg(status_detail_.isEmpty() ? tr("Unknown error — see logs for details.") : status_detail_);
+            if (!failure_hint_.isEmpty())
+                text += "\n" + failure_hint_;
             color = colors::RED();
             break;
         case StatusState::Timeout:
@@ -495,7 +509,22 @@ void SetupScreen::mark_step_active(const QString& key) {
 // ─────────────────────────────────────────────────────────────────────────────
 
 void SetupScreen::on_begin_setup() {
+    start_setup();
+}
+
+void SetupScreen::on_offline_clicked() {
+    const QString dir = QFileDialog::getExistingDirectory(this, tr("Select the offline setup bundle folder"));
+    if (dir.isEmpty())
+        return;
+    LOG_INFO("SetupScreen", "Installing from offline bundle: " + dir);
+    start_setup(dir);
+}
+
+void SetupScreen::start_setup(const QString& bundle_dir) {
     begin_btn_->setEnabled(false);
+    if (offline_btn_)
+        offline_btn_->setVisible(false);
+    failure_hint_.clear();
     begin_btn_state_ = BeginBtnState::SettingUp;
     update_begin_button();
     status_state_ = StatusState::InProgress;
@@ -517,7 +546,7 @@ void SetupScreen::on_begin_setup() {
 
     if (timeout_timer_)
         timeout_timer_->start();
-    python::PythonSetupManager::instance().run_setup();
+    python::PythonSetupManager::instance().run_setup(bundle_dir);
 }
 
 void SetupScreen::on_net_speed(qint64 down_bps, qint64 up_bps) {
@@ -540,6 +569,8 @@ void SetupScreen::on_elapsed_tick() {
 
 void SetupScreen::on_progress(const python::SetupProgress& progress) {
     const QString& key = progress.step;
+    if (progress.is_error)
+        failure_hint_ = progress.hint; // "verify" has no step row but can still fail
 
     if (steps_.contains(key)) {
         auto& s = steps_[key];
@@ -603,6 +634,8 @@ void SetupScreen::on_setup_done(bool success, const QString& error) {
         status_detail_ = error.trimmed().left(240);
         update_status_label();
         status_label_->setWordWrap(true);
+        if (offline_btn_)
+            offline_btn_->setVisible(true);
         LOG_ERROR("SetupScreen", "Setup failed: " + error);
     }
 }
@@ -42,6 +42,7 @@ class SetupScreen : public QWidget {
     void on_progress(const fincept::python::SetupProgress& progress);
     void on_setup_done(bool success, const QString& error);
     void on_skip_clicked();
+    void on_offline_clicked();
     void on_setup_timeout();
     void on_net_speed(qint64 down_bps, qint64 up_bps);
     void on_elapsed_tick();
@@ -51,6 +52,9 @@ class SetupScreen : public QWidget {
 
   private:
     void build_ui();
+    /// Starts (or retries) setup; a non-empty `bundle_dir` installs from that
+    /// offline bundle instead of the network.
+    void start_setup(const QString& bundle_dir = {});
     QWidget* build_step_row(const QString& key);
     void prefill_completed_steps();
     void mark_step_done(const QString& key);
@@ -81,9 +85,13 @@ class SetupScreen : public QWidget {
     /// StatusState::Custom. Stored verbatim and re-rendered with the active
     /// language template.
     QString status_detail_;
+    /// What the last failed step suggested doing next (PythonSetupManager
+    /// SetupProgress::hint), shown under the error.
+    QString failure_hint_;
 
     QPushButton* begin_btn_ = nullptr;
     QPushButton* skip_btn_ = nullptr;
+    QPushButton* offline_btn_ = nullptr;
     QLabel* title_lbl_ = nullptr; // FINCEPT TERMINAL — brand, not translated
     QLabel* status_label_ = nullptr;
     QLabel* subtitle_lbl_ = nullptr;
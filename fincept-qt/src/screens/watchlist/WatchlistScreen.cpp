@@ -9,6 +9,8 @@
 #include "datahub/DataHubMetaTypes.h"
 #include "services/backtesting/BacktestingService.h"
 #include "services/cloud/CloudSyncEngine.h"
+#include "services/watchlist/WatchlistColumnService.h"
+#include "services/watchlist/WatchlistExpression.h"
 #include "ui/formatting/NumberFormat.h"
 #include "ui/theme/Theme.h"
 #include "ui/theme/ThemeManager.h"
@@ -18,6 +20,7 @@
 #include <QHBoxLayout>
 #include <QHideEvent>
 #include <QInputDialog>
+#include <QJsonArray>
 #include <QMessageBox>
 #include <QPointer>
 #include <QSet>
@@ -115,6 +118,19 @@ WatchlistScreen::WatchlistScreen(QWidget* parent) : QWidget(parent) {
             [this](const ThemeTokens&) { refresh_theme(); });
     refresh_theme();
 
+    connect(&services::watchlist::WatchlistColumnService::instance(),
+            &services::watchlist::WatchlistColumnService::values_ready, this,
+            [this](const QString& request_id, const QString&, const QJsonObject& result) {
+                if (request_id != columns_request_)
+                    return;
+                computed_.clear();
+                for (const auto& v : result["rows"].toArray()) {
+                    const QJsonObject row = v.toObject();
+                    computed_.insert(row["symbol"].toString(), row);
+                }
+                rebuild_from_cache();
+            });
+
     // Reload from the local cache when a cloud pull updates watchlists.
     connect(&fincept::services::cloud::CloudSyncEngine::instance(),
             &fincept::services::cloud::CloudSyncEngine::cloud_data_changed, this, [this](const QString& entity) {
@@ -187,11 +203,21 @@ void WatchlistScreen::retranslateUi() {
     if (remove_btn_)
         remove_btn_->setText(tr("REMOVE SELECTED"));
 
+    if (columns_btn_)
+        columns_btn_->setText(tr("COLUMNS"));
+
     // Table headers — reapply so the live header row reflects the new language.
-    if (table_) {
-        table_->set_headers(
-            {tr("SYMBOL"), tr("NAME"), tr("PRICE"), tr("CHANGE"), tr("CHG %"), tr("HIGH"), tr("LOW"), tr("VOLUME")});
-    }
+    apply_headers();
+}
+
+void WatchlistScreen::apply_headers() {
+    if (!table_)
+        return;
+    QStringList headers = {tr("SYMBOL"), tr("NAME"), tr("PRICE"), tr("CHANGE"),
+                           tr("CHG %"),  tr("HIGH"), tr("LOW"),   tr("VOLUME")};
+    for (const auto& c : columns_)
+        headers.append(c.name.toUpper());
+    table_->set_headers(headers);
 }
 
 // ── MCP-driven UI sync ──────────────────────────────────────────────────────
@@ -335,6 +361,11 @@ QWidget* WatchlistScreen::build_main_panel() {
     export_csv_btn_->setEnabled(false);
     tl->addWidget(export_csv_btn_);
 
+    columns_btn_ = new QPushButton(tr("COLUMNS"));
+    columns_btn_->setToolTip(tr("Add, change or remove a computed column"));
+    connect(columns_btn_, &QPushButton::clicked, this, &WatchlistScreen::on_edit_column);
+    tl->addWidget(columns_btn_);
+
     auto* backtest_btn = new QPushButton(tr("BACKTEST"));
     connect(backtest_btn, &QPushButton::clicked, this, [this]() {
         if (stocks_.isEmpty())
@@ -485,6 +516,9 @@ void WatchlistScreen::refresh_theme() {
     if (export_csv_btn_)
         export_csv_btn_->setStyleSheet(std_btn_style());
 
+    if (columns_btn_)
+        columns_btn_->setStyleSheet(std_btn_style());
+
     // Add bar
     if (add_bar_)
         add_bar_->setStyleSheet(
@@ -556,9 +590,44 @@ void WatchlistScreen::load_stocks() {
     }
 
     stock_count_->setText(tr("%1 symbols").arg(stocks_.size()));
+    load_columns();
     fetch_quotes();
 }
 
+void WatchlistScreen::load_columns() {
+    auto r = fincept::WatchlistRepository::instance().get_columns(current_wl_id_);
+    columns_ = r.is_ok() ? r.value() : QVector<fincept::WatchlistColumn>{};
+    computed_.clear();
+    apply_headers();
+    columns_request_.clear();
+    if (!columns_.isEmpty() && !stocks_.isEmpty())
+        columns_request_ = services::watchlist::WatchlistColumnService::instance().refresh(current_wl_id_);
+}
+
+QStringList WatchlistScreen::computed_cells(const QString& symbol) const {
+    QStringList cells;
+    const QJsonObject values = computed_.value(symbol)["values"].toObject();
+    for (const auto& c : columns_) {
+        const QJsonValue v = values[c.name];
+        cells.append(v.isDouble() ? QString::number(v.toDouble(), 'f', c.decimals) : QStringLiteral("--"));
+    }
+    return cells;
+}
+
+void WatchlistScreen::stamp_computed(int row, const QString& symbol) {
+    const QJsonObject entry = computed_.value(symbol);
+    const QJsonObject values = entry["values"].toObject();
+    const QJsonObject errors = entry["errors"].toObject();
+    for (int i = 0; i < columns_.size(); ++i) {
+        const int col = 8 + i;
+        const QJsonValue v = values[columns_[i].name];
+        if (v.isDouble())
+            table_->set_cell_numeric(row, col, v.toDouble());
+        if (auto* item = table_->item(row, col); item && errors.contains(columns_[i].name))
+            item->setToolTip(errors[columns_[i].name].toString());
+    }
+}
+
 void WatchlistScreen::fetch_quotes() {
     if (stocks_.isEmpty()) {
         table_->clear_data();
@@ -585,7 +654,9 @@ void WatchlistScreen::rebuild_from_cache() {
         table_->setSortingEnabled(false);
         table_->clear_data();
         for (const auto& s : stocks_) {
-            table_->add_row({s.symbol, s.name, "--", "--", "--", "--", "--", "--"});
+            table_->add_row(QStringList{s.symbol, s.name, "--", "--", "--", "--", "--", "--"} +
+                            computed_cells(s.symbol));
+            stamp_computed(table_->rowCount() - 1, s.symbol);
         }
         table_->setSortingEnabled(true);
         return;
@@ -651,13 +722,19 @@ void WatchlistScreen::populate_table(const QVector<services::QuoteData>& quotes)
         auto it = quote_map.find(s.symbol);
         if (it != quote_map.end()) {
             const auto& q = it.value();
-            table_->add_row({q.symbol, q.name.isEmpty() ? s.name : q.name, QString("$%1").arg(q.price, 0, 'f', 2),
-                             QString("%1%2").arg(q.change >= 0 ? "+" : "").arg(q.change, 0, 'f', 2),
-                             QString("%1%2%").arg(q.change_pct >= 0 ? "+" : "").arg(q.change_pct, 0, 'f', 2),
-                             QString("$%1").arg(q.high, 0, 'f', 2), QString("$%1").arg(q.low, 0, 'f', 2),
-                             fincept::ui::formatting::format_compact_volume(static_cast<qint64>(q.volume))});
+            const QStringList cells{
+                q.symbol,
+                q.name.isEmpty() ? s.name : q.name,
+                QString("$%1").arg(q.price, 0, 'f', 2),
+                QString("%1%2").arg(q.change >= 0 ? "+" : "").arg(q.change, 0, 'f', 2),
+                QString("%1%2%").arg(q.change_pct >= 0 ? "+" : "").arg(q.change_pct, 0, 'f', 2),
+                QString("$%1").arg(q.high, 0, 'f', 2),
+                QString("$%1").arg(q.low, 0, 'f', 2),
+                fincept::ui::formatting::format_compact_volume(static_cast<qint64>(q.volume))};
+            table_->add_row(cells + computed_cells(s.symbol));
 
             int row = table_->rowCount() - 1;
+            stamp_computed(row, s.symbol);
 
             // Stamp numeric EditRole values so Qt sorts by magnitude,
             // not by the display string ("$2.5M" vs "$999K" etc.).
@@ -673,7 +750,9 @@ void WatchlistScreen::populate_table(const QVector<services::QuoteData>& quotes)
             table_->set_cell_color(row, 3, chg_color);
             table_->set_cell_color(row, 4, chg_color);
         } else {
-            table_->add_row({s.symbol, s.name, "--", "--", "--", "--", "--", "--"});
+            table_->add_row(QStringList{s.symbol, s.name, "--", "--", "--", "--", "--", "--"} +
+                            computed_cells(s.symbol));
+            stamp_computed(table_->rowCount() - 1, s.symbol);
         }
     }
 
@@ -778,10 +857,57 @@ void WatchlistScreen::on_remove_stock() {
 
 void WatchlistScreen::on_refresh() {
     if (!current_wl_id_.isEmpty()) {
+        load_columns();
         fetch_quotes();
     }
 }
 
+void WatchlistScreen::on_edit_column() {
+    if (current_wl_id_.isEmpty())
+        return;
+
+    bool ok = false;
+    const QString name = QInputDialog::getText(this, tr("Computed Column"), tr("Column name:"), QLineEdit::Normal,
+                                               {}, &ok)
+                             .trimmed();
+    if (!ok || name.isEmpty())
+        return;
+
+    QString column = name;
+    QString current;
+    for (const auto& c : columns_) {
+        if (c.name.compare(name, Qt::CaseInsensitive) == 0) {
+            column = c.name;
+            current = c.expression;
+        }
+    }
+    const QString expression =
+        QInputDialog::getText(this, tr("Computed Column"),
+                              tr("Expression for \"%1\" (evaluated on daily bars), e.g.\n"
+                                 "(close / highest(252) - 1) * 100     % from 52-week high\n"
+                                 "change(21)     rsi(14)     close / sma(200)     volatility(20)\n\n"
+                                 "Leave empty to remove the column.")
+                                  .arg(column),
+                              QLineEdit::Normal, current, &ok)
+            .trimmed();
+    if (!ok)
+        return;
+
+    auto& repo = fincept::WatchlistRepository::instance();
+    if (expression.isEmpty()) {
+        repo.remove_column(current_wl_id_, column);
+    } else {
+        QString error;
+        if (!services::watchlist::WatchlistExpression::compile(expression, &error).is_valid()) {
+            QMessageBox::warning(this, tr("Computed Column"), tr("Invalid expression: %1").arg(error));
+            return;
+        }
+        repo.upsert_column(current_wl_id_, column, expression);
+    }
+    load_columns();
+    rebuild_from_cache();
+}
+
 void WatchlistScreen::on_export_csv() {
     if (current_wl_id_.isEmpty())
         return;
@@ -8,6 +8,7 @@
 
 #include <QHash>
 #include <QHideEvent>
+#include <QJsonObject>
 #include <QLabel>
 #include <QLineEdit>
 #include <QList>
@@ -53,6 +54,7 @@ class WatchlistScreen : public QWidget, public IStatefulScreen, public IGroupLin
     void on_refresh();
     void on_export_csv();
     void on_import_csv();
+    void on_edit_column();
     void refresh_theme();
 
   private:
@@ -68,6 +70,12 @@ class WatchlistScreen : public QWidget, public IStatefulScreen, public IGroupLin
     void fetch_quotes();
     void populate_table(const QVector<services::QuoteData>& quotes);
 
+    // Computed columns (WatchlistColumnService), appended after VOLUME.
+    void load_columns();
+    void apply_headers();
+    QStringList computed_cells(const QString& symbol) const;
+    void stamp_computed(int row, const QString& symbol);
+
     void hub_resubscribe_stocks();
     void hub_unsubscribe_all();
     void rebuild_from_cache();
@@ -96,6 +104,7 @@ class WatchlistScreen : public QWidget, public IStatefulScreen, public IGroupLin
     QPushButton* refresh_btn_ = nullptr;
     QPushButton* del_wl_btn_ = nullptr;
     QPushButton* export_csv_btn_ = nullptr;
+    QPushButton* columns_btn_ = nullptr;
     QPushButton* import_csv_btn_ = nullptr;
     QPushButton* add_btn_ = nullptr;
     QPushButton* remove_btn_ = nullptr;
@@ -103,6 +112,9 @@ class WatchlistScreen : public QWidget, public IStatefulScreen, public IGroupLin
     QSplitter* splitter_ = nullptr;
 
     QHash<QString, services::QuoteData> row_cache_;
+    QVector<fincept::WatchlistColumn> columns_;
+    QHash<QString, QJsonObject> computed_; // symbol → {values, errors}
+    QString columns_request_;
     bool hub_active_ = false;
 
     // Symbol group link — SymbolGroup::None when unlinked.
@@ -9,6 +9,8 @@
 //   - AgentService_Execution.cpp    — run_agent, run_team, routing, multi
 //   - AgentService_Workflows.cpp    — plans, portfolio analytics, etc.
 //   - AgentService_Repositories.cpp — memory, sessions, paper trading
+//   - AgentService_History.cpp      — agent_runs history and rerun
+//   - AgentService_Documents.cpp    — document index (RAG) and retrieval
 #include "services/agents/AgentService.h"
 
 #include "auth/AuthManager.h"
@@ -140,9 +142,15 @@ QJsonObject AgentService::build_api_keys() const {
     auto providers = LlmConfigRepository::instance().list_providers();
     if (providers.is_ok()) {
         for (const auto& p : providers.value()) {
+            const QString lower = p.provider.toLower();
+            // Local providers (ollama, llama_cpp) have no key but still need
+            // their base_url shipped, or Python falls back to the default port.
+            if (!p.base_url.isEmpty() && !ai_chat::provider_requires_api_key(lower)) {
+                keys[lower + "_base_url"] = p.base_url;
+                keys[lower.toUpper() + "_BASE_URL"] = p.base_url;
+            }
             if (p.api_key.isEmpty())
                 continue;
-            const QString lower = p.provider.toLower();
             keys[lower] = p.api_key; // lowercase form
             const QString env_name = kEnvVarNames.value(lower);
             if (!env_name.isEmpty())
@@ -188,7 +196,8 @@ QJsonObject AgentService::build_payload(const QString& action, const QJsonObject
 
     // Inject the resolved LLM config as active_llm so Python can build the exact
     // model instance without re-resolving credentials.
-    // Priority: config["model"] (per-agent resolved profile) > global active LLM.
+    // Priority: config["model"] (per-agent resolved profile) > profile assigned to
+    // config["agent_id"] > global active LLM.
     // This means: if the caller already embedded a resolved profile in config["model"]
     // (as build_config_from_editor() now does), Python gets the right per-agent creds.
     {
@@ -197,7 +206,13 @@ QJsonObject AgentService::build_payload(const QString& action, const QJsonObject
             payload["active_llm"] = config["model"].toObject();
         } else {
             auto& llm = ai_chat::LlmService::instance();
-            if (llm.is_configured()) {
+            // A saved agent referenced by id (MCP, workflows) uses the profile assigned to it in
+            // Settings — e.g. a local Ollama / llama.cpp model — via the usual resolution chain.
+            const QString agent_id = config.value("agent_id").toString();
+            const auto profile = agent_id.isEmpty() ? ResolvedLlmProfile{} : llm.resolve_profile("agent", agent_id);
+            if (!profile.provider.isEmpty()) {
+                payload["active_llm"] = ai_chat::LlmService::profile_to_json(profile);
+            } else if (llm.is_configured()) {
                 QJsonObject active_llm;
                 active_llm["provider"] = llm.active_provider();
                 active_llm["model_id"] = llm.active_model();
@@ -253,6 +268,8 @@ QJsonObject AgentService::build_payload(const QString& action, const QJsonObject
             //   exclude_categories[]      — blacklist, ON TOP of defaults below
             //   name_patterns[]           — regex include on tool name
             //   exclude_name_patterns[]   — regex exclude on tool name
+            //   names[]                   — exact tool-name allowlist
+            //   read_only (bool)          — drop is_destructive tools
             //   max_tools (int)           — hard cap
             // Default excludes UI-only and recursive categories so agents
             // don't drive the UI or call the chat LLM.
@@ -289,12 +306,31 @@ QJsonObject AgentService::build_payload(const QString& action, const QJsonObject
                             filter.exclude_name_patterns.append(p);
                     }
                 }
+                if (tf.contains("names")) {
+                    for (const auto& v : tf["names"].toArray()) {
+                        const QString n = v.toString().trimmed();
+                        if (!n.isEmpty())
+                            filter.names.append(n);
+                    }
+                }
+                filter.exclude_destructive = tf.value("read_only").toBool(false);
                 filter.max_tools = tf.value("max_tools").toInt(0);
             }
             const bool include_external = enriched_config.value("include_external_mcp").toBool(true);
             enriched_config["terminal_tools"] = bridge.tool_definitions(filter, include_external);
         }
 
+        // Swap the process-wide token for one scoped to this run: the bridge then
+        // refuses any tool outside the catalog above (a model can't reach a tool
+        // it was never offered by guessing its name) and trims each result to
+        // `tool_result_max_chars` (0 = no cap). An explicit token from the caller wins.
+        if (!config.contains("terminal_mcp_token")) {
+            const int max_chars = enriched_config.value("tool_result_max_chars")
+                                      .toInt(mcp::TerminalMcpBridge::kDefaultMaxResultChars);
+            enriched_config["terminal_mcp_token"] =
+                bridge.issue_scoped_token(enriched_config.value("terminal_tools").toArray(), max_chars);
+        }
+
         // Dry-run mode is opt-in and read by the Python TerminalToolkit. When
         // true, the toolkit short-circuits each call and returns a synthetic
         // result without crossing the bridge — useful for testing prompts /
@@ -311,16 +347,26 @@ QJsonObject AgentService::build_payload(const QString& action, const QJsonObject
     return payload;
 }
 
+QString AgentService::issued_run_token(const QJsonObject& payload, const QJsonObject& config) {
+    if (config.contains("terminal_mcp_token"))
+        return {};
+    const QString token = payload.value("config").toObject().value("terminal_mcp_token").toString();
+    return token == mcp::TerminalMcpBridge::instance().token() ? QString() : token;
+}
+
 // ── Python lightweight runner (via PythonRunner args) ─────────────────────────
 
 void AgentService::run_python_light(const QString& action, const QJsonObject& params,
                                     std::function<void(bool, QJsonObject)> on_result) {
     QJsonObject payload = build_payload(action, params);
     QString payload_str = QString::fromUtf8(QJsonDocument(payload).toJson(QJsonDocument::Compact));
+    const QString run_token = issued_run_token(payload, {});
 
     QPointer<AgentService> self = this;
     python::PythonRunner::instance().run(
-        "agents/finagent_core/main.py", {payload_str}, [self, action, on_result](python::PythonResult pr) {
+        "agents/finagent_core/main.py", {payload_str}, [self, action, on_result, run_token](python::PythonResult pr) {
+            if (!run_token.isEmpty())
+                mcp::TerminalMcpBridge::instance().revoke_scoped_token(run_token);
             if (!self)
                 return;
             if (!pr.success) {
@@ -362,6 +408,8 @@ void AgentService::run_python_stdin(const QString& action, const QJsonObject& pa
 
     // Spawn QProcess directly for stdin writing (P4 exception like ExchangeService)
     auto* proc = new QProcess(this);
+    // The run's bridge token lives exactly as long as its process.
+    mcp::TerminalMcpBridge::instance().bind_scoped_token(issued_run_token(payload, config), proc);
     // Share the standard Python env + cwd + Windows console suppression with
     // PythonRunner so every finagent spawn sees the same FINCEPT_DATA_DIR,
     // FINAGENT_DATA_DIR, and PYTHONPATH.
@@ -531,6 +579,9 @@ void AgentService::ensure_registered_with_hub() {
 }
 
 void AgentService::publish_agent_result(const AgentExecutionResult& r, bool final) {
+    // Every completed run passes through here exactly once with final=true.
+    if (final && !r.request_id.isEmpty())
+        record_run_finish(r);
     if (!hub_registered_ || r.request_id.isEmpty())
         return;
     QJsonObject obj{
@@ -39,6 +39,19 @@ class AgentService : public QObject, public fincept::datahub::Producer {
     QString route_query(const QString& query);
     void execute_routed_query(const QString& query, const QJsonObject& config = {}, const QString& session_id = {});
 
+    // ── Run history (AgentService_History.cpp) ──────────────────────────────
+    // run_agent / run_agent_streaming / run_agent_structured / run_team are
+    // recorded in agent_runs (AgentRunRepository) as they start and finish.
+
+    /// Executes a stored run again with the same kind, query, config and
+    /// output model; credentials stripped from the stored config are filled
+    /// from the saved LLM providers. Returns the new request_id (empty for an
+    /// unknown run); the result arrives on the original kind's signal.
+    QString rerun(const QString& run_id);
+    /// The input_hash a run with these inputs is stored under.
+    static QString run_input_hash(const QString& kind, const QString& query, const QJsonObject& config,
+                                  const QString& output_model = {});
+
     // ── Multi-query & parallel ───────────────────────────────────────────────
     void execute_multi_query(const QString& query, bool aggregate = true, const QJsonObject& config = {});
 
@@ -47,6 +60,11 @@ class AgentService : public QObject, public fincept::datahub::Producer {
     void run_portfolio_rebalancing(const QJsonObject& portfolio_data = {});
     void run_risk_assessment(const QJsonObject& portfolio_data = {});
     void run_portfolio_analysis(const QString& analysis_type, const QJsonObject& portfolio_summary = {});
+    /// Bull / bear / risk agents debate `ticker` concurrently for up to `rounds`
+    /// rebuttal rounds (capped at 4), then a judge returns a structured verdict
+    /// that is stored in AgentDebateRepository. Returns the request id.
+    QString run_debate(const QString& ticker, const QJsonObject& config = {}, int rounds = 2,
+                       const QString& context = {});
 
     // ── Team execution ───────────────────────────────────────────────────────
     QString run_team(const QString& query, const QJsonObject& team_config);
@@ -110,6 +128,20 @@ class AgentService : public QObject, public fincept::datahub::Producer {
     void save_memory_repo(const QString& content, const QString& agent_id = {}, const QJsonObject& options = {});
     void search_memories_repo(const QString& query, const QString& agent_id = {}, int limit = 10);
 
+    // ── Document index (RAG) ─────────────────────────────────────────────────
+    // Chunked, embedded store over filings, transcripts, notes and files that
+    // agents search for cited context. Each call returns a request id; the
+    // outcome arrives on document_index_result. `embedder` is "local" (offline
+    // feature hashing) or "<provider>[:<model>]" from the embedder registry.
+    QString index_document(const QJsonObject& document, const QString& embedder = "local");
+    QString index_filing(const QString& ticker, const QString& form = "10-K", const QString& embedder = "local");
+    QString index_notes(const QString& embedder = "local", bool include_archived = false);
+    QString index_file(const QString& file_id, const QString& kind = "file", const QString& ticker = {},
+                       const QString& embedder = "local");
+    QString search_documents(const QString& query, int k = 8, const QString& kind = {}, const QString& ticker = {});
+    QString list_documents(const QString& kind = {}, const QString& ticker = {}, int limit = 100);
+    QString remove_document(const QString& doc_id);
+
     // ── Session management ───────────────────────────────────────────────────
     void save_session(const QJsonObject& session_data);
     void get_session(const QString& session_id);
@@ -159,6 +191,8 @@ class AgentService : public QObject, public fincept::datahub::Producer {
     void trade_executed(QJsonObject result);
     void trade_decisions_loaded(QJsonArray decisions);
     void multi_query_result(QJsonObject result);
+    void debate_verdict_saved(const QString& ticker, const QString& as_of_date);
+    void document_index_result(const QString& request_id, bool success, const QJsonObject& result);
     void error_occurred(const QString& context, const QString& message);
 
     // ── Agentic Mode signals ─────────────────────────────────────────────────
@@ -190,9 +224,19 @@ class AgentService : public QObject, public fincept::datahub::Producer {
                           std::function<void(bool, QJsonObject)> on_result);
     void run_python_stdin(const QString& action, const QJsonObject& params, const QJsonObject& config,
                           std::function<void(bool, QJsonObject)> on_result);
+    /// Runs a doc_index_* action and emits document_index_result; returns the request id.
+    QString run_document_action(const QString& action, const QJsonObject& params);
     QJsonObject build_api_keys() const;
     QJsonObject build_payload(const QString& action, const QJsonObject& params = {},
                               const QJsonObject& config = {}) const;
+    /// The run-scoped bridge token build_payload() issued for `payload`; empty
+    /// when the caller's `config` brought its own token or none was issued.
+    static QString issued_run_token(const QJsonObject& payload, const QJsonObject& config);
+
+    // ── Run history ──────────────────────────────────────────────────────────
+    void record_run_start(const QString& run_id, const QString& kind, const QString& query, const QJsonObject& config,
+                          const QString& output_model = {});
+    void record_run_finish(const AgentExecutionResult& r);
 
     // ── Cache TTLs — delegated to CacheManager ───────────────────────────────
     static constexpr int kAgentCacheTtlSec = 5 * 60;
@@ -0,0 +1,128 @@
+// src/services/agents/AgentService_Documents.cpp
+//
+// Document index (RAG): feeds SEC filings, transcripts, notes and managed
+// files into the Python-side DocumentIndex (finagent_core/doc_index.py) and
+// runs the hybrid vector + BM25 retrieval agents use to cite their sources.
+// Notes and files are resolved here (NotesRepository / FileManagerService)
+// so the Python side never touches the terminal's own database.
+//
+// Part of the partial-class split of AgentService.cpp.
+
+#include "core/logging/Logger.h"
+#include "services/agents/AgentService.h"
+#include "services/file_manager/FileManagerService.h"
+#include "storage/repositories/NotesRepository.h"
+
+#include <QJsonArray>
+#include <QPointer>
+#include <QUuid>
+
+namespace fincept::services {
+
+namespace {
+
+// Fails a request that never reached Python; queued so callers can connect first.
+QString doc_fail_later(AgentService* svc, const QString& error) {
+    const QString req_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    QPointer<AgentService> self = svc;
+    QMetaObject::invokeMethod(
+        svc,
+        [self, req_id, error]() {
+            if (self)
+                emit self->document_index_result(req_id, false, QJsonObject{{"error", error}});
+        },
+        Qt::QueuedConnection);
+    return req_id;
+}
+
+} // namespace
+
+QString AgentService::run_document_action(const QString& action, const QJsonObject& params) {
+    const QString req_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    LOG_INFO("AgentService", QString("Document index %1 [%2]").arg(action, req_id.left(8)));
+
+    // No terminal tool wiring: indexing and retrieval never call back into the bridge.
+    const QJsonObject config{{"terminal_tools_enabled", false}};
+    QPointer<AgentService> self = this;
+    run_python_stdin(action, params, config, [self, req_id](bool ok, QJsonObject result) {
+        if (!self)
+            return;
+        const bool success = ok && result["success"].toBool(ok);
+        if (!success && !result.contains("error"))
+            result["error"] = QStringLiteral("Document index request failed");
+        emit self->document_index_result(req_id, success, result);
+    });
+    return req_id;
+}
+
+QString AgentService::index_document(const QJsonObject& document, const QString& embedder) {
+    QJsonObject params = document;
+    params["embedder"] = embedder;
+    return run_document_action("doc_index_add", params);
+}
+
+QString AgentService::index_filing(const QString& ticker, const QString& form, const QString& embedder) {
+    return run_document_action("doc_index_filing",
+                               {{"ticker", ticker.trimmed().toUpper()}, {"form", form}, {"embedder", embedder}});
+}
+
+QString AgentService::index_notes(const QString& embedder, bool include_archived) {
+    auto notes = NotesRepository::instance().list_all(include_archived);
+    if (notes.is_err())
+        return doc_fail_later(this, QString::fromStdString(notes.error()));
+    QJsonArray docs;
+    for (const auto& n : notes.value()) {
+        if (n.content.trimmed().isEmpty())
+            continue;
+        // A note can name several tickers; the first one files it, all are searchable in the text.
+        const QString ticker = n.tickers.split(',', Qt::SkipEmptyParts).value(0).trimmed();
+        QString text = n.title + "\n\n" + n.content;
+        if (!n.tickers.isEmpty())
+            text += "\n\nTickers: " + n.tickers;
+        docs.append(QJsonObject{
+            {"kind", "note"},
+            {"title", n.title.isEmpty() ? QString("Note %1").arg(n.id) : n.title},
+            {"text", text},
+            {"ticker", ticker},
+            {"source_ref", QString("note:%1").arg(n.id)},
+            {"doc_date", n.updated_at.left(10)},
+            {"metadata", QJsonObject{{"category", n.category}, {"sentiment", n.sentiment}, {"tags", n.tags}}},
+        });
+    }
+    if (docs.isEmpty())
+        return doc_fail_later(this, "No notes to index");
+    return run_document_action("doc_index_add", {{"documents", docs}, {"embedder", embedder}});
+}
+
+QString AgentService::index_file(const QString& file_id, const QString& kind, const QString& ticker,
+                                 const QString& embedder) {
+    const ManagedFile f = FileManagerService::instance().find_by_id(file_id);
+    if (f.id.isEmpty())
+        return doc_fail_later(this, "Unknown file: " + file_id);
+    const QJsonObject doc{
+        {"kind", kind},
+        {"title", f.original_name.isEmpty() ? file_id : f.original_name},
+        {"ticker", ticker.trimmed().toUpper()},
+        {"source_ref", "file:" + file_id},
+        {"doc_date", f.uploaded_at.left(10)},
+        {"path", FileManagerService::instance().full_path(f.name)},
+        {"metadata", QJsonObject{{"mime_type", f.mime_type}}},
+    };
+    return index_document(doc, embedder);
+}
+
+QString AgentService::search_documents(const QString& query, int k, const QString& kind, const QString& ticker) {
+    return run_document_action(
+        "doc_index_search", {{"query", query}, {"k", k}, {"kind", kind}, {"ticker", ticker.trimmed().toUpper()}});
+}
+
+QString AgentService::list_documents(const QString& kind, const QString& ticker, int limit) {
+    return run_document_action("doc_index_list",
+                               {{"kind", kind}, {"ticker", ticker.trimmed().toUpper()}, {"limit", limit}});
+}
+
+QString AgentService::remove_document(const QString& doc_id) {
+    return run_document_action("doc_index_remove", {{"id", doc_id}});
+}
+
+} // namespace fincept::services
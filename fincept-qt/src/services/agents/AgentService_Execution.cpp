@@ -40,6 +40,7 @@ QString AgentService::run_agent(const QString& query, const QJsonObject& config)
 
     QJsonObject params;
     params["query"] = query;
+    record_run_start(req_id, "run", query, config);
 
     QPointer<AgentService> self = this;
     run_python_stdin("run", params, config, [self, req_id](bool ok, QJsonObject result) {
@@ -49,6 +50,7 @@ QString AgentService::run_agent(const QString& query, const QJsonObject& config)
         r.request_id = req_id;
         r.success = ok && result["success"].toBool(ok);
         r.execution_time_ms = result["execution_time_ms"].toInt();
+        r.usage = result["usage"].toObject();
 
         if (r.success) {
             if (result.contains("response"))
@@ -74,6 +76,7 @@ QString AgentService::run_agent(const QString& query, const QJsonObject& config)
 QString AgentService::run_agent_streaming(const QString& query, const QJsonObject& config) {
     const QString req_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
     LOG_INFO("AgentService", QString("Streaming agent query [%1]: %2").arg(req_id.left(8), query.left(80)));
+    record_run_start(req_id, "stream", query, config);
 
     auto& py = python::PythonRunner::instance();
     if (!py.is_available()) {
@@ -96,6 +99,8 @@ QString AgentService::run_agent_streaming(const QString& query, const QJsonObjec
     QString script_path = py.scripts_dir() + "/agents/finagent_core/main.py";
 
     auto* proc = new QProcess(this);
+    // The run's bridge token lives exactly as long as its process.
+    mcp::TerminalMcpBridge::instance().bind_scoped_token(issued_run_token(payload, config), proc);
     // Share the standard Python env + cwd + Windows console suppression with
     // PythonRunner so every finagent spawn sees the same FINCEPT_DATA_DIR,
     // FINAGENT_DATA_DIR, and PYTHONPATH.
@@ -274,6 +279,7 @@ QString AgentService::route_query(const QString& query) {
 QString AgentService::run_team(const QString& query, const QJsonObject& team_config) {
     const QString req_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
     LOG_INFO("AgentService", QString("Running team query [%1]: %2").arg(req_id.left(8), query.left(80)));
+    record_run_start(req_id, "team", query, team_config);
 
     auto& py = python::PythonRunner::instance();
     if (!py.is_available()) {
@@ -302,6 +308,7 @@ QString AgentService::run_team(const QString& query, const QJsonObject& team_con
     QString script_path = py.scripts_dir() + "/agents/finagent_core/main.py";
 
     auto* proc = new QProcess(this);
+    mcp::TerminalMcpBridge::instance().bind_scoped_token(issued_run_token(payload, coord_config), proc);
     // Share the standard Python env + cwd + Windows console suppression with
     // PythonRunner so every finagent spawn sees the same FINCEPT_DATA_DIR,
     // FINAGENT_DATA_DIR, and PYTHONPATH.
@@ -441,6 +448,7 @@ QString AgentService::run_agent_structured(const QString& query, const QJsonObje
     QJsonObject params;
     params["query"] = query;
     params["output_model"] = output_model;
+    record_run_start(req_id, "structured", query, config, output_model);
 
     QPointer<AgentService> self = this;
     run_python_stdin("run_structured", params, config, [self, req_id](bool ok, QJsonObject result) {
@@ -450,6 +458,7 @@ QString AgentService::run_agent_structured(const QString& query, const QJsonObje
         r.request_id = req_id;
         r.success = ok && result["success"].toBool(ok);
         r.execution_time_ms = result["execution_time_ms"].toInt();
+        r.usage = result["usage"].toObject();
         r.response = result.contains("response") ? result["response"].toString()
                                                  : QJsonDocument(result).toJson(QJsonDocument::Indented);
         r.error = result["error"].toString();
@@ -529,6 +538,7 @@ QString AgentService::run_agentic_streaming(const QString& action, const QJsonOb
     QString script_path = py.scripts_dir() + "/agents/finagent_core/main.py";
 
     auto* proc = new QProcess(this);
+    mcp::TerminalMcpBridge::instance().bind_scoped_token(issued_run_token(payload, config), proc);
     proc->setProcessEnvironment(py.build_python_env());
     proc->setWorkingDirectory(py.scripts_dir());
 #ifdef _WIN32
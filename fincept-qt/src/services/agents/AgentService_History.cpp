@@ -0,0 +1,170 @@
+// src/services/agents/AgentService_History.cpp
+//
+// Run history: every run_agent / run_agent_streaming / run_agent_structured /
+// run_team execution is written to agent_runs when it starts and completed
+// from publish_agent_result(final) when it finishes, so past answers can be
+// listed, searched, compared across identical inputs and re-run.
+//
+// Part of the partial-class split of AgentService.cpp.
+
+#include "core/logging/Logger.h"
+#include "services/agents/AgentService.h"
+#include "services/llm/LlmService.h"
+#include "storage/repositories/AgentRunRepository.h"
+#include "storage/repositories/LlmConfigRepository.h"
+
+#include <QCryptographicHash>
+#include <QDateTime>
+#include <QJsonArray>
+#include <QJsonDocument>
+
+namespace fincept::services {
+
+namespace {
+
+bool ah_is_secret_key(const QString& key) {
+    const QString k = key.toLower();
+    return k.contains("api_key") || k.contains("apikey") || k.contains("secret") || k.contains("password") ||
+           k == "token" || k.endsWith("_token");
+}
+
+QJsonValue ah_strip(const QJsonValue& v);
+
+/// Config with every credential-looking key removed, recursively.
+QJsonObject ah_strip_secrets(const QJsonObject& o) {
+    QJsonObject out;
+    for (auto it = o.begin(); it != o.end(); ++it) {
+        if (!ah_is_secret_key(it.key()))
+            out.insert(it.key(), ah_strip(it.value()));
+    }
+    return out;
+}
+
+QJsonValue ah_strip(const QJsonValue& v) {
+    if (v.isObject())
+        return ah_strip_secrets(v.toObject());
+    if (v.isArray()) {
+        QJsonArray out;
+        for (const auto& e : v.toArray())
+            out.append(ah_strip(e));
+        return out;
+    }
+    return v;
+}
+
+/// Puts the saved provider key back into every model block ({provider, ...})
+/// that lost its api_key to ah_strip_secrets.
+QJsonValue ah_fill_credentials(const QJsonValue& v, const QHash<QString, QString>& keys) {
+    if (v.isArray()) {
+        QJsonArray out;
+        for (const auto& e : v.toArray())
+            out.append(ah_fill_credentials(e, keys));
+        return out;
+    }
+    if (!v.isObject())
+        return v;
+    QJsonObject o = v.toObject();
+    for (auto it = o.begin(); it != o.end(); ++it)
+        it.value() = ah_fill_credentials(it.value(), keys);
+    const QString provider = o.value("provider").toString().toLower();
+    if (!provider.isEmpty() && !o.contains("api_key") && keys.contains(provider))
+        o["api_key"] = keys.value(provider);
+    return o;
+}
+
+} // namespace
+
+QString AgentService::run_input_hash(const QString& kind, const QString& query, const QJsonObject& config,
+                                     const QString& output_model) {
+    // QJsonObject keeps keys sorted, so the compact form is canonical.
+    const QJsonObject inputs{{"kind", kind},
+                             {"query", query.trimmed()},
+                             {"config", ah_strip_secrets(config)},
+                             {"output_model", output_model}};
+    return QString::fromLatin1(
+        QCryptographicHash::hash(QJsonDocument(inputs).toJson(QJsonDocument::Compact), QCryptographicHash::Sha256)
+            .toHex());
+}
+
+void AgentService::record_run_start(const QString& run_id, const QString& kind, const QString& query,
+                                    const QJsonObject& config, const QString& output_model) {
+    AgentRunRecord r;
+    r.id = run_id;
+    r.kind = kind;
+    r.query = query;
+    r.config = ah_strip_secrets(config);
+    r.output_model = output_model;
+    r.agent_name = config.value("name").toString();
+    r.input_hash = run_input_hash(kind, query, config, output_model);
+    r.started_at = QDateTime::currentMSecsSinceEpoch();
+    // Same precedence as build_payload: the config's own model, the agent's assigned
+    // profile, else the global LLM.
+    const QJsonObject model = config.value("model").toObject();
+    const QString agent_id = config.value("agent_id").toString();
+    auto& llm = ai_chat::LlmService::instance();
+    const auto profile = agent_id.isEmpty() ? ResolvedLlmProfile{} : llm.resolve_profile("agent", agent_id);
+    if (!model.value("provider").toString().isEmpty()) {
+        r.provider = model.value("provider").toString();
+        r.model = model.value("model_id").toString(model.value("model").toString());
+    } else if (!profile.provider.isEmpty()) {
+        r.provider = profile.provider;
+        r.model = profile.model_id;
+    } else {
+        r.provider = llm.active_provider();
+        r.model = llm.active_model();
+    }
+    auto w = AgentRunRepository::instance().insert(r);
+    if (w.is_err())
+        LOG_WARN("AgentService", "Recording agent run failed: " + QString::fromStdString(w.error()));
+}
+
+void AgentService::record_run_finish(const AgentExecutionResult& res) {
+    auto& repo = AgentRunRepository::instance();
+    auto existing = repo.get(res.request_id);
+    if (!existing || existing->finished_at > 0)
+        return;
+    AgentRunRecord r = *existing;
+    r.finished_at = QDateTime::currentMSecsSinceEpoch();
+    r.status = res.success ? QStringLiteral("success") : QStringLiteral("error");
+    r.response = res.response;
+    r.error = res.error;
+    r.latency_ms = int(r.finished_at - r.started_at);
+    r.input_tokens = res.usage.value("input_tokens").toInt();
+    r.output_tokens = res.usage.value("output_tokens").toInt();
+    r.total_tokens = res.usage.value("total_tokens").toInt(r.input_tokens + r.output_tokens);
+    repo.finish(r);
+}
+
+QString AgentService::rerun(const QString& run_id) {
+    auto& repo = AgentRunRepository::instance();
+    const auto prev = repo.get(run_id);
+    if (!prev) {
+        LOG_WARN("AgentService", "Rerun: unknown agent run " + run_id);
+        return {};
+    }
+
+    QHash<QString, QString> keys;
+    auto providers = LlmConfigRepository::instance().list_providers();
+    if (providers.is_ok()) {
+        for (const auto& p : providers.value()) {
+            if (!p.api_key.isEmpty())
+                keys.insert(p.provider.toLower(), p.api_key);
+        }
+    }
+    const QJsonObject config = ah_fill_credentials(prev->config, keys).toObject();
+
+    QString req_id;
+    if (prev->kind == "stream")
+        req_id = run_agent_streaming(prev->query, config);
+    else if (prev->kind == "structured")
+        req_id = run_agent_structured(prev->query, config, prev->output_model);
+    else if (prev->kind == "team")
+        req_id = run_team(prev->query, config);
+    else
+        req_id = run_agent(prev->query, config);
+    repo.set_rerun_of(req_id, run_id);
+    LOG_INFO("AgentService", QString("Rerun of %1 started as %2").arg(run_id.left(8), req_id.left(8)));
+    return req_id;
+}
+
+} // namespace fincept::services
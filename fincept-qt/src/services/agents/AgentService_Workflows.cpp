@@ -1,8 +1,8 @@
 // src/services/agents/AgentService_Workflows.cpp
 //
 // Higher-level orchestrations: run_workflow, create_plan / execute_plan,
-// portfolio analysis helpers (stock / rebalancing / risk / generic), and the
-// canned plan factories.
+// portfolio analysis helpers (stock / rebalancing / risk / generic), the
+// bull / bear / risk debate, and the canned plan factories.
 //
 // Part of the partial-class split of AgentService.cpp.
 
@@ -17,9 +17,12 @@
 #include "services/agents/AgentService.h"
 #include "services/llm/LlmService.h"
 #include "storage/cache/CacheManager.h"
+#include "storage/repositories/AgentDebateRepository.h"
 #include "storage/repositories/LlmConfigRepository.h"
 
 #include <QCoreApplication>
+#include <QDate>
+#include <QDateTime>
 #include <QElapsedTimer>
 #include <QJsonArray>
 #include <QJsonDocument>
@@ -268,6 +271,65 @@ void AgentService::run_portfolio_analysis(const QString& analysis_type, const QJ
     });
 }
 
+// ── Debate ───────────────────────────────────────────────────────────────────
+
+QString AgentService::run_debate(const QString& ticker, const QJsonObject& config, int rounds,
+                                 const QString& context) {
+    const QString req_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    const QString sym = ticker.trimmed().toUpper();
+    rounds = qBound(0, rounds, 4);
+    LOG_INFO("AgentService", QString("Debate [%1]: %2, %3 round(s)").arg(req_id.left(8), sym).arg(rounds));
+    QJsonObject params{{"symbol", sym}, {"rounds", rounds}};
+    if (!context.isEmpty())
+        params["context"] = context;
+
+    // Same model the payload resolves to when the config doesn't pin one.
+    const QJsonObject model = config.value("model").toObject();
+    auto& llm = ai_chat::LlmService::instance();
+    const QString provider = model.value("provider").toString(llm.active_provider());
+    const QString model_id = model.value("provider").toString().isEmpty()
+                                 ? llm.active_model()
+                                 : model.value("model_id").toString(model.value("model").toString());
+
+    QPointer<AgentService> self = this;
+    run_python_stdin("debate", params, config, [self, req_id, sym, provider, model_id](bool ok, QJsonObject result) {
+        if (!self)
+            return;
+        AgentExecutionResult r;
+        r.request_id = req_id;
+        r.success = ok && result["success"].toBool(ok);
+        r.execution_time_ms = result["execution_time_ms"].toInt();
+        r.response = result["response"].toString();
+        r.error = result["error"].toString();
+
+        if (r.success) {
+            const QJsonObject verdict = result["verdict"].toObject();
+            DebateVerdict v;
+            v.ticker = sym;
+            v.as_of_date = result["as_of"].toString(QDate::currentDate().toString(Qt::ISODate));
+            v.direction = verdict["direction"].toString("neutral");
+            v.conviction = qBound(0.0, verdict["conviction"].toDouble(), 1.0);
+            v.score = v.direction == "bullish" ? v.conviction : v.direction == "bearish" ? -v.conviction : 0.0;
+            v.key_evidence = verdict["key_evidence"].toArray();
+            v.dissent = verdict["dissent"].toArray();
+            v.summary = verdict["summary"].toString();
+            v.transcript = result["transcript"].toArray();
+            v.rounds = result["rounds"].toInt();
+            v.provider = provider;
+            v.model = model_id;
+            v.created_at = QDateTime::currentMSecsSinceEpoch();
+            auto w = AgentDebateRepository::instance().upsert(v);
+            if (w.is_err())
+                LOG_WARN("AgentService", "Storing debate verdict failed: " + QString::fromStdString(w.error()));
+            else
+                emit self->debate_verdict_saved(v.ticker, v.as_of_date);
+        }
+        emit self->agent_result(r);
+        self->publish_agent_result(r, /*final=*/true);
+    });
+    return req_id;
+}
+
 // ── Plan variants ────────────────────────────────────────────────────────────
 
 QString AgentService::create_stock_analysis_plan(const QString& symbol, const QJsonObject& /*config*/) {
@@ -37,6 +37,7 @@ struct AgentExecutionResult {
     QString error;
     int execution_time_ms = 0;
     QString request_id; // set by AgentService; panels guard on this to avoid cross-contamination
+    QJsonObject usage;  // {input_tokens, output_tokens, total_tokens} when the model reported it
 };
 
 // ── Routing ─────────────────────────────────────────────────────────────────
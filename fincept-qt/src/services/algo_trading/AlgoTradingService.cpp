@@ -2,16 +2,35 @@
 #include "services/algo_trading/AlgoTradingService.h"
 
 #include "algo_engine/BacktestEngine.h"
+#include "algo_engine/BacktestTradeInspector.h"
+#include "algo_engine/BarTransforms.h"
 #include "algo_engine/CandleDataFetcher.h"
+#include "algo_engine/PairsAnalytics.h"
+#include "algo_engine/ParameterSweepEngine.h"
+#include "algo_engine/PortfolioBacktestEngine.h"
+#include "algo_engine/SpreadBacktestEngine.h"
+#include "algo_engine/TickBacktestEngine.h"
+#include "algo_engine/TradeResampler.h"
+#include "algo_engine/WalkForwardEngine.h"
 #include "core/logging/Logger.h"
 #include "services/algo_trading/AlgoStrategyLibrary.h"
+#include "storage/HistoricalDataStore.h"
 #include "storage/sqlite/Database.h"
 #include "trading/AccountManager.h"
 
+#include <QCryptographicHash>
 #include <QDate>
+#include <QDateTime>
+#include <QHash>
 #include <QJsonArray>
 #include <QJsonDocument>
+#include <QSqlQuery>
+#include <QTimeZone>
 #include <QUuid>
+#include <QtConcurrent>
+
+#include <algorithm>
+#include <memory>
 
 namespace fincept::services::algo {
 
@@ -184,8 +203,9 @@ void AlgoTradingService::delete_strategy(const QString& id) {
 // Use fincept::algo::AlgoEngine::instance() for all deployment operations.
 
 // ── Backtesting (native C++ engine) ────────────────────────────────────────────
-void AlgoTradingService::run_backtest(const AlgoStrategy& strategy, const QString& symbol, const QString& start_date,
-                                      const QString& end_date, double capital) {
+void AlgoTradingService::fetch_backtest_candles(const QString& symbol, const QString& timeframe,
+                                                const QString& start_date, const QString& end_date,
+                                                fincept::algo::CandleCallback callback) {
     // Derive the historical window from the date range (fallback: 1 year).
     int lookback_days = 365;
     const QDate d1 = QDate::fromString(start_date, "yyyy-MM-dd");
@@ -208,39 +228,1072 @@ void AlgoTradingService::run_backtest(const AlgoStrategy& strategy, const QStrin
     const fincept::algo::DataSource source =
         broker_id.isEmpty() ? fincept::algo::DataSource::YFinance : fincept::algo::DataSource::Auto;
 
-    // Capture strategy parameters for the async callback.
+    LOG_INFO("AlgoTrading", QString("Backtest data %1 [%2] %3d — source=%4")
+                                .arg(symbol, timeframe)
+                                .arg(lookback_days)
+                                .arg(broker_id.isEmpty() ? "yahoo" : broker_id));
+    fincept::algo::CandleDataFetcher::instance().fetch(symbol, timeframe, lookback_days, source, broker_id, account_id,
+                                                       std::move(callback));
+}
+
+fincept::algo::BacktestParams AlgoTradingService::backtest_params(const AlgoStrategy& strategy, double capital) {
+    fincept::algo::BacktestParams p;
+    p.entry_conditions = strategy.entry_conditions;
+    p.exit_conditions = strategy.exit_conditions;
+    p.entry_logic = strategy.entry_logic.isEmpty() ? QStringLiteral("AND") : strategy.entry_logic;
+    p.exit_logic = strategy.exit_logic.isEmpty() ? QStringLiteral("AND") : strategy.exit_logic;
+    p.stop_loss_pct = strategy.stop_loss;
+    p.take_profit_pct = strategy.take_profit;
+    p.trailing_stop_pct = strategy.trailing_stop;
+    p.initial_capital = capital;
+    p.timeframe = strategy.timeframe.isEmpty() ? QStringLiteral("1d") : strategy.timeframe;
+    p.position_size_pct = strategy.position_size_pct > 0 ? strategy.position_size_pct : 100.0;
+    return p;
+}
+
+std::optional<AlgoStrategy> AlgoTradingService::strategy_by_id(const QString& id) const {
+    for (const auto& s : load_dsl_strategies_from_db())
+        if (s.id == id)
+            return s;
+    return std::nullopt;
+}
+
+void AlgoTradingService::run_backtest(const AlgoStrategy& strategy, const QString& symbol, const QString& start_date,
+                                      const QString& end_date, double capital, const QJsonObject& bar_transform) {
+    QString transform_error;
+    const auto transform = fincept::algo::BarTransform::from_json(bar_transform, &transform_error);
+    if (!transform_error.isEmpty()) {
+        emit error_occurred("backtest", transform_error);
+        return;
+    }
+    const fincept::algo::BacktestParams params = backtest_params(strategy, capital);
+    LOG_INFO("AlgoTrading", QString("Backtest %1 [%2] %3").arg(symbol, params.timeframe, strategy.name));
+
+    // Singleton — `this` outlives any async work, so capture directly.
+    QJsonObject inputs{{"symbol", symbol.toUpper()},
+                       {"start_date", start_date},
+                       {"end_date", end_date},
+                       {"initial_capital", capital}};
+    if (!transform.is_identity())
+        inputs["bar_transform"] = transform.to_json();
+    fetch_backtest_candles(
+        symbol, params.timeframe, start_date, end_date,
+        [this, params, strategy, inputs, transform](bool ok, const QVector<fincept::algo::OhlcvCandle>& source,
+                                                    const QString& err) {
+            if (!ok || source.isEmpty()) {
+                emit error_occurred("backtest", err.isEmpty() ? QStringLiteral("No data") : err);
+                return;
+            }
+            const auto candles = fincept::algo::BarTransforms::apply(transform, source);
+            if (candles.isEmpty()) {
+                emit error_occurred("backtest", QStringLiteral("No complete bars after the transform — reduce the "
+                                                               "brick/range size"));
+                return;
+            }
+            QJsonObject result = fincept::algo::BacktestEngine::run(candles, params);
+            if (!result.value("success").toBool(false)) {
+                emit error_occurred("backtest", result.value("error").toString(QStringLiteral("Backtest failed")));
+                return;
+            }
+            last_backtest_ = {candles,           params.entry_conditions, params.exit_conditions,
+                              params.entry_logic, params.exit_logic,       result.value("trades").toArray()};
+            const QJsonObject saved = save_backtest_run(QStringLiteral("bar"), strategy, params, inputs, result);
+            for (auto it = saved.begin(); it != saved.end(); ++it)
+                result[it.key()] = it.value();
+            emit backtest_result(result);
+        });
+}
+
+void AlgoTradingService::run_walk_forward(const AlgoStrategy& strategy, const QString& symbol,
+                                          const QString& start_date, const QString& end_date, double capital,
+                                          const QJsonObject& config) {
+    QString config_error;
+    const auto wf = fincept::algo::WalkForwardConfig::from_json(config, &config_error);
+    if (!config_error.isEmpty()) {
+        emit error_occurred("walk_forward", config_error);
+        return;
+    }
+    const fincept::algo::BacktestParams params = backtest_params(strategy, capital);
+    const QString request_id = config.value("request_id").toString();
+    LOG_INFO("AlgoTrading", QString("Walk-forward %1 [%2] %3 — %4 param(s)")
+                                .arg(symbol, params.timeframe, strategy.name)
+                                .arg(wf.param_grid.size()));
+
+    fetch_backtest_candles(
+        symbol, params.timeframe, start_date, end_date,
+        [this, params, wf, symbol, strategy, request_id](bool ok, const QVector<fincept::algo::OhlcvCandle>& candles,
+                                                         const QString& err) {
+            if (!ok || candles.isEmpty()) {
+                emit error_occurred("walk_forward", err.isEmpty() ? QStringLiteral("No data") : err);
+                return;
+            }
+            // Hundreds of backtests — keep them off the UI thread; progress is
+            // throttled to whole percents.
+            (void)QtConcurrent::run([this, candles, params, wf, symbol, strategy, request_id]() {
+                auto last_pct = std::make_shared<int>(-1);
+                QJsonObject result = fincept::algo::WalkForwardEngine::run(
+                    candles, params, wf, [this, last_pct](int done, int total) {
+                        const int pct = total > 0 ? done * 100 / total : 100;
+                        if (pct == *last_pct)
+                            return;
+                        *last_pct = pct;
+                        QMetaObject::invokeMethod(
+                            this, [this, done, total]() { emit walk_forward_progress(done, total); },
+                            Qt::QueuedConnection);
+                    });
+                result["symbol"] = symbol;
+                result["strategy_id"] = strategy.id;
+                result["strategy_name"] = strategy.name;
+                result["timeframe"] = params.timeframe;
+                if (!request_id.isEmpty())
+                    result["request_id"] = request_id;
+                QMetaObject::invokeMethod(
+                    this,
+                    [this, result]() {
+                        if (!result.value("success").toBool(false)) {
+                            emit error_occurred("walk_forward", result.value("error").toString(
+                                                                    QStringLiteral("Walk-forward failed")));
+                            return;
+                        }
+                        emit walk_forward_result(result);
+                    },
+                    Qt::QueuedConnection);
+            });
+        });
+}
+
+void AlgoTradingService::run_parameter_sweep(const AlgoStrategy& strategy, const QString& symbol,
+                                             const QString& start_date, const QString& end_date, double capital,
+                                             const QJsonObject& config) {
+    QString config_error;
+    const auto sweep = fincept::algo::SweepConfig::from_json(config, &config_error);
+    if (!config_error.isEmpty()) {
+        emit error_occurred("parameter_sweep", config_error);
+        return;
+    }
+    const fincept::algo::BacktestParams params = backtest_params(strategy, capital);
+    const QString request_id = config.value("request_id").toString();
+    LOG_INFO("AlgoTrading", QString("Parameter sweep %1 [%2] %3 — %4 param(s)")
+                                .arg(symbol, params.timeframe, strategy.name)
+                                .arg(sweep.param_grid.size()));
+    sweep_cancel_ = false;
+
+    fetch_backtest_candles(
+        symbol, params.timeframe, start_date, end_date,
+        [this, params, sweep, symbol, strategy, start_date, end_date,
+         request_id](bool ok, const QVector<fincept::algo::OhlcvCandle>& candles, const QString& err) {
+            if (!ok || candles.isEmpty()) {
+                emit error_occurred("parameter_sweep", err.isEmpty() ? QStringLiteral("No data") : err);
+                return;
+            }
+            // The engine fans the backtests out on its own pool; this task only
+            // drives it and writes the results matrix.
+            (void)QtConcurrent::run([this, candles, params, sweep, symbol, strategy, start_date, end_date,
+                                     request_id]() {
+                auto last_pct = std::make_shared<int>(-1);
+                QJsonObject result = fincept::algo::ParameterSweepEngine::run(
+                    candles, params, sweep,
+                    [this, last_pct](int done, int total) {
+                        const int pct = total > 0 ? done * 100 / total : 100;
+                        if (pct == *last_pct)
+                            return;
+                        *last_pct = pct;
+                        QMetaObject::invokeMethod(
+                            this, [this, done, total]() { emit parameter_sweep_progress(done, total); },
+                            Qt::QueuedConnection);
+                    },
+                    &sweep_cancel_);
+                result["symbol"] = symbol;
+                result["strategy_id"] = strategy.id;
+                result["strategy_name"] = strategy.name;
+                result["timeframe"] = params.timeframe;
+                if (result.value("success").toBool(false))
+                    result["sweep_id"] = save_parameter_sweep(result, start_date, end_date);
+                if (!request_id.isEmpty())
+                    result["request_id"] = request_id;
+                QMetaObject::invokeMethod(
+                    this,
+                    [this, result]() {
+                        if (!result.value("success").toBool(false)) {
+                            emit error_occurred("parameter_sweep", result.value("error").toString(
+                                                                       QStringLiteral("Parameter sweep failed")));
+                            return;
+                        }
+                        emit parameter_sweep_result(result);
+                    },
+                    Qt::QueuedConnection);
+            });
+        });
+}
+
+void AlgoTradingService::run_tick_backtest(const AlgoStrategy& strategy, const QString& symbol,
+                                           const QString& exchange, const QString& start_date,
+                                           const QString& end_date, double capital, const QJsonObject& fill_model) {
+    const fincept::algo::BacktestParams params = backtest_params(strategy, capital);
+    const auto fill = fincept::algo::TickFillModel::from_json(fill_model);
+    const QString request_id = fill_model.value("request_id").toString();
+    const QDate d1 = QDate::fromString(start_date, "yyyy-MM-dd");
+    const QDate d2 = QDate::fromString(end_date, "yyyy-MM-dd");
+    const qint64 from_ms = d1.isValid() ? QDateTime(d1, QTime(0, 0), QTimeZone::utc()).toMSecsSinceEpoch() : 0;
+    const qint64 to_ms = d2.isValid() ? QDateTime(d2.addDays(1), QTime(0, 0), QTimeZone::utc()).toMSecsSinceEpoch() - 1
+                                      : 0;
+    LOG_INFO("AlgoTrading", QString("Tick backtest %1:%2 [%3] %4 — latency %5 ms")
+                                .arg(symbol, exchange, params.timeframe, strategy.name)
+                                .arg(fill.latency_ms));
+
+    // Tick sets run to millions of rows — load and replay them off the UI thread.
+    (void)QtConcurrent::run([this, params, fill, symbol, exchange, from_ms, to_ms, strategy, request_id, start_date,
+                             end_date, capital]() {
+        auto& store = fincept::storage::HistoricalDataStore::instance();
+        QVector<fincept::algo::TickEvent> ticks;
+        for (const auto& t : store.get_ticks(symbol, exchange, from_ms, to_ms))
+            ticks.append({t.timestamp_ms, t.price, t.size, t.bid, t.ask, t.bid_size, t.ask_size});
+
+        QJsonObject result;
+        if (ticks.isEmpty()) {
+            result = QJsonObject{{"success", false},
+                                 {"error", QString("No stored ticks for %1:%2 in range — import them first")
+                                               .arg(symbol.toUpper(), exchange.toUpper())}};
+        } else {
+            // Warm-up: the cached bars of the aggregation timeframe before the first tick.
+            const auto tf = fincept::algo::timeframe_from_string(params.timeframe);
+            const qint64 period_ms = static_cast<qint64>(fincept::algo::timeframe_seconds(tf)) * 1000;
+            QVector<fincept::algo::OhlcvCandle> warmup;
+            const auto cached = store.get_candles(symbol, exchange, fincept::algo::timeframe_to_string(tf), 0,
+                                                  ticks.first().timestamp_ms - period_ms);
+            for (const auto& c : cached) {
+                fincept::algo::OhlcvCandle o;
+                o.open_time = c.timestamp;
+                o.close_time = c.timestamp + period_ms;
+                o.open = c.open;
+                o.high = c.high;
+                o.low = c.low;
+                o.close = c.close;
+                o.volume = c.volume;
+                o.is_closed = true;
+                warmup.append(o);
+            }
+            result = fincept::algo::TickBacktestEngine::run(ticks, warmup, params, fill);
+        }
+        result["symbol"] = symbol.toUpper();
+        result["exchange"] = exchange.toUpper();
+        result["strategy_id"] = strategy.id;
+        result["strategy_name"] = strategy.name;
+        if (result.value("success").toBool(false)) {
+            const QJsonObject inputs{{"symbol", symbol.toUpper()},
+                                     {"exchange", exchange.toUpper()},
+                                     {"start_date", start_date},
+                                     {"end_date", end_date},
+                                     {"initial_capital", capital},
+                                     {"fill_model", fill.to_json()}};
+            const QJsonObject saved = save_backtest_run(QStringLiteral("tick"), strategy, params, inputs, result);
+            for (auto it = saved.begin(); it != saved.end(); ++it)
+                result[it.key()] = it.value();
+        }
+        if (!request_id.isEmpty())
+            result["request_id"] = request_id;
+        QMetaObject::invokeMethod(
+            this,
+            [this, result]() {
+                if (!result.value("success").toBool(false)) {
+                    emit error_occurred("tick_backtest",
+                                        result.value("error").toString(QStringLiteral("Tick backtest failed")));
+                    return;
+                }
+                emit tick_backtest_result(result);
+            },
+            Qt::QueuedConnection);
+    });
+}
+
+void AlgoTradingService::run_portfolio_backtest(const QVector<PortfolioSleeveSpec>& sleeves,
+                                                const QString& start_date, const QString& end_date, double capital,
+                                                const QJsonObject& allocation) {
+    QString alloc_error;
+    const auto alloc = fincept::algo::PortfolioAllocation::from_json(allocation, &alloc_error);
+    if (!alloc_error.isEmpty() || sleeves.isEmpty()) {
+        emit error_occurred("portfolio_backtest",
+                            sleeves.isEmpty() ? QStringLiteral("No strategies to backtest") : alloc_error);
+        return;
+    }
+    const QString request_id = allocation.value("request_id").toString();
+    LOG_INFO("AlgoTrading", QString("Portfolio backtest: %1 sleeve(s), %2 allocation")
+                                .arg(sleeves.size())
+                                .arg(alloc.to_json().value("method").toString()));
+
+    // Candles per sleeve arrive on the UI thread; the replay starts once all
+    // fetches have answered (a failed fetch leaves that sleeve without candles
+    // and the engine reports it as skipped).
+    auto inputs = std::make_shared<QVector<fincept::algo::PortfolioSleeve>>(sleeves.size());
+    auto remaining = std::make_shared<int>(int(sleeves.size()));
+    for (int i = 0; i < sleeves.size(); ++i) {
+        const PortfolioSleeveSpec& spec = sleeves[i];
+        auto& in = (*inputs)[i];
+        in.id = spec.strategy.id.isEmpty() ? spec.strategy.name : spec.strategy.id;
+        in.name = spec.strategy.name;
+        in.symbol = spec.symbol;
+        in.params = backtest_params(spec.strategy, capital);
+        in.weight = spec.weight;
+        fetch_backtest_candles(
+            spec.symbol, in.params.timeframe, start_date, end_date,
+            [this, inputs, remaining, i, capital, alloc, request_id](
+                bool ok, const QVector<fincept::algo::OhlcvCandle>& candles, const QString& err) {
+                if (ok)
+                    (*inputs)[i].candles = candles;
+                else
+                    LOG_WARN("AlgoTrading", QString("Portfolio backtest: no data for %1 — %2")
+                                                .arg((*inputs)[i].symbol, err));
+                if (--*remaining > 0)
+                    return;
+                (void)QtConcurrent::run([this, inputs, capital, alloc, request_id]() {
+                    QJsonObject result = fincept::algo::PortfolioBacktestEngine::run(*inputs, capital, alloc);
+                    if (!request_id.isEmpty())
+                        result["request_id"] = request_id;
+                    QMetaObject::invokeMethod(
+                        this,
+                        [this, result]() {
+                            if (!result.value("success").toBool(false)) {
+                                emit error_occurred("portfolio_backtest",
+                                                    result.value("error").toString(
+                                                        QStringLiteral("Portfolio backtest failed")));
+                                return;
+                            }
+                            emit portfolio_backtest_result(result);
+                        },
+                        Qt::QueuedConnection);
+                });
+            });
+    }
+}
+
+void AlgoTradingService::cancel_parameter_sweep() {
+    sweep_cancel_ = true;
+}
+
+// ── Parameter sweep persistence ──────────────────────────────────────────────
+// algo_param_sweeps / algo_param_sweep_results (schema owned by migration v055).
+
+QString AlgoTradingService::save_parameter_sweep(const QJsonObject& result, const QString& start_date,
+                                                 const QString& end_date) {
+    auto& db = fincept::Database::instance();
+    const QString id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    const QJsonObject best = result.value("best").toObject();
+    const auto compact = [](const QJsonObject& o) {
+        return QString::fromUtf8(QJsonDocument(o).toJson(QJsonDocument::Compact));
+    };
+    const auto nullable = [](const QJsonObject& row, const char* key) {
+        return row.contains(key) ? row.value(key).toVariant() : QVariant();
+    };
+
+    if (db.begin_transaction().is_err()) {
+        LOG_WARN("AlgoTrading", "Parameter sweep not saved: could not open a transaction");
+        return {};
+    }
+    auto r = db.execute("INSERT INTO algo_param_sweeps (id, strategy_id, strategy_name, symbol, timeframe, "
+                        "start_date, end_date, objective, config_json, combinations, evaluated, stop_reason, "
+                        "best_params, best_objective, elapsed_ms) VALUES (?,?,?,?,?,?,?,?,?,?,?,?,?,?,?)",
+                        {id, result.value("strategy_id").toString(), result.value("strategy_name").toString(),
+                         result.value("symbol").toString(), result.value("timeframe").toString(), start_date,
+                         end_date, result.value("objective").toString(),
+                         compact(result.value("config").toObject()), result.value("combinations").toInt(),
+                         result.value("evaluated").toInt(), result.value("stop_reason").toString(),
+                         compact(best.value("params").toObject()), nullable(best, "objective"),
+                         result.value("elapsed_ms").toInteger()});
+    for (const auto& v : result.value("results").toArray()) {
+        if (r.is_err())
+            break;
+        const QJsonObject row = v.toObject();
+        r = db.execute("INSERT INTO algo_param_sweep_results (sweep_id, combo_index, rank, params_json, objective, "
+                       "total_return, sharpe_ratio, sortino, calmar, max_drawdown, total_trades, win_rate, "
+                       "profit_factor, final_value, qualified, error) VALUES (?,?,?,?,?,?,?,?,?,?,?,?,?,?,?,?)",
+                       {id, row.value("index").toInt(), row.value("rank").toInt(),
+                        compact(row.value("params").toObject()), nullable(row, "objective"),
+                        nullable(row, "total_return"), nullable(row, "sharpe_ratio"), nullable(row, "sortino"),
+                        nullable(row, "calmar"), nullable(row, "max_drawdown"), nullable(row, "total_trades"),
+                        nullable(row, "win_rate"), nullable(row, "profit_factor"), nullable(row, "final_value"),
+                        row.value("qualified").toBool() ? 1 : 0, row.value("error").toString()});
+    }
+    if (r.is_err()) {
+        db.rollback();
+        LOG_WARN("AlgoTrading", "Parameter sweep not saved: " + QString::fromStdString(r.error()));
+        return {};
+    }
+    db.commit();
+    return id;
+}
+
+namespace {
+
+constexpr const char* kSweepColumns =
+    "id, strategy_id, strategy_name, symbol, timeframe, start_date, end_date, objective, combinations, evaluated, "
+    "stop_reason, best_params, best_objective, elapsed_ms, created_at, config_json";
+
+// SQL NULL stays null rather than becoming 0.
+QJsonValue sweep_db_value(const QVariant& v) {
+    return v.isNull() ? QJsonValue(QJsonValue::Null) : QJsonValue::fromVariant(v);
+}
+
+QJsonObject sweep_db_summary(const QSqlQuery& q) {
+    return QJsonObject{{"sweep_id", q.value(0).toString()},
+                       {"strategy_id", q.value(1).toString()},
+                       {"strategy_name", q.value(2).toString()},
+                       {"symbol", q.value(3).toString()},
+                       {"timeframe", q.value(4).toString()},
+                       {"start_date", q.value(5).toString()},
+                       {"end_date", q.value(6).toString()},
+                       {"objective", q.value(7).toString()},
+                       {"combinations", q.value(8).toInt()},
+                       {"evaluated", q.value(9).toInt()},
+                       {"stop_reason", q.value(10).toString()},
+                       {"best_params", QJsonDocument::fromJson(q.value(11).toByteArray()).object()},
+                       {"best_objective", sweep_db_value(q.value(12))},
+                       {"elapsed_ms", q.value(13).toLongLong()},
+                       {"created_at", q.value(14).toString()}};
+}
+
+} // namespace
+
+QJsonArray AlgoTradingService::parameter_sweeps(const QString& strategy_id, int limit) const {
+    QJsonArray out;
+    QVariantList args;
+    QString sql = QString("SELECT %1 FROM algo_param_sweeps").arg(kSweepColumns);
+    if (!strategy_id.isEmpty()) {
+        sql += " WHERE strategy_id = ?";
+        args << strategy_id;
+    }
+    sql += " ORDER BY created_at DESC LIMIT ?";
+    args << std::clamp(limit, 1, 500);
+    auto r = fincept::Database::instance().execute(sql, args);
+    if (r.is_err())
+        return out;
+    while (r.value().next())
+        out.append(sweep_db_summary(r.value()));
+    return out;
+}
+
+QJsonObject AlgoTradingService::parameter_sweep(const QString& sweep_id, const QString& x_key, const QString& y_key,
+                                                const QString& metric) const {
+    auto& db = fincept::Database::instance();
+    auto head = db.execute(QString("SELECT %1 FROM algo_param_sweeps WHERE id = ?").arg(kSweepColumns), {sweep_id});
+    if (head.is_err() || !head.value().next())
+        return {};
+    QJsonObject out = sweep_db_summary(head.value());
+    const QJsonObject config = QJsonDocument::fromJson(head.value().value(15).toByteArray()).object();
+    out["config"] = config;
+
+    static const QStringList kMetrics = {"objective",    "total_return", "sharpe_ratio", "sortino",
+                                         "calmar",       "max_drawdown", "total_trades", "win_rate",
+                                         "profit_factor", "final_value"};
+    QJsonArray results;
+    auto rows = db.execute("SELECT combo_index, rank, params_json, qualified, error, " + kMetrics.join(", ") +
+                               " FROM algo_param_sweep_results WHERE sweep_id = ? ORDER BY rank",
+                           {sweep_id});
+    if (rows.is_ok()) {
+        auto& q = rows.value();
+        while (q.next()) {
+            QJsonObject row{{"index", q.value(0).toInt()},
+                            {"rank", q.value(1).toInt()},
+                            {"params", QJsonDocument::fromJson(q.value(2).toByteArray()).object()}};
+            if (!q.value(4).toString().isEmpty()) {
+                row["error"] = q.value(4).toString();
+            } else {
+                row["qualified"] = q.value(3).toInt() != 0;
+                for (int i = 0; i < kMetrics.size(); ++i)
+                    row[kMetrics[i]] = sweep_db_value(q.value(5 + i));
+            }
+            results.append(row);
+        }
+    }
+
+    const QStringList keys = config.value("param_grid").toObject().keys();
+    const QString x = x_key.isEmpty() ? keys.value(0) : x_key;
+    const QString y = y_key.isEmpty() ? keys.value(1) : y_key;
+    out["param_keys"] = QJsonArray::fromStringList(keys);
+    out["results"] = results;
+    out["heatmap"] = fincept::algo::ParameterSweepEngine::heatmap(results, x, y == x ? QString() : y,
+                                                                  metric.isEmpty() ? QStringLiteral("objective")
+                                                                                   : metric);
+    return out;
+}
+
+// ── Backtest run history ─────────────────────────────────────────────────────
+// algo_backtest_runs (schema owned by migration v057).
+
+namespace {
+
+constexpr const char* kRunColumns =
+    "id, kind, strategy_id, strategy_name, strategy_version, strategy_hash, config_hash, symbol, exchange, "
+    "timeframe, start_date, end_date, initial_capital, total_return, sharpe_ratio, sortino, calmar, max_drawdown, "
+    "total_trades, win_rate, profit_factor, final_value, label, notes, created_at";
+
+// Headline metrics kept as columns, in kRunColumns order (from index 13).
+const QStringList kRunMetrics = {"total_return", "sharpe_ratio", "sortino",       "calmar",     "max_drawdown",
+                                 "total_trades", "win_rate",     "profit_factor", "final_value"};
+
+constexpr int kRunMaxListed = 100; // trades listed per bucket in a diff
+
+QString run_compact(const QJsonValue& v) {
+    const QJsonDocument doc = v.isArray() ? QJsonDocument(v.toArray()) : QJsonDocument(v.toObject());
+    return QString::fromUtf8(doc.toJson(QJsonDocument::Compact));
+}
+
+// QJsonObject keeps its keys sorted, so the compact form is canonical.
+QString run_hash(const QJsonObject& o) {
+    return QString::fromLatin1(
+        QCryptographicHash::hash(run_compact(o).toUtf8(), QCryptographicHash::Sha256).toHex().left(16));
+}
+
+// The parts of a strategy that change its signals — the "code" being versioned.
+QJsonObject run_rules(const fincept::algo::BacktestParams& p) {
+    return QJsonObject{{"entry_conditions", p.entry_conditions}, {"exit_conditions", p.exit_conditions},
+                       {"entry_logic", p.entry_logic},           {"exit_logic", p.exit_logic},
+                       {"stop_loss", p.stop_loss_pct},           {"take_profit", p.take_profit_pct},
+                       {"trailing_stop", p.trailing_stop_pct},   {"position_size_pct", p.position_size_pct},
+                       {"timeframe", p.timeframe}};
+}
+
+QJsonObject run_db_summary(const QSqlQuery& q) {
+    QJsonObject o{{"run_id", q.value(0).toString()},
+                  {"kind", q.value(1).toString()},
+                  {"strategy_id", q.value(2).toString()},
+                  {"strategy_name", q.value(3).toString()},
+                  {"strategy_version", q.value(4).toInt()},
+                  {"strategy_hash", q.value(5).toString()},
+                  {"config_hash", q.value(6).toString()},
+                  {"symbol", q.value(7).toString()},
+                  {"exchange", q.value(8).toString()},
+                  {"timeframe", q.value(9).toString()},
+                  {"start_date", q.value(10).toString()},
+                  {"end_date", q.value(11).toString()},
+                  {"initial_capital", q.value(12).toDouble()},
+                  {"label", q.value(22).toString()},
+                  {"notes", q.value(23).toString()},
+                  {"created_at", q.value(24).toString()}};
+    for (int i = 0; i < kRunMetrics.size(); ++i)
+        o[kRunMetrics[i]] = sweep_db_value(q.value(13 + i));
+    return o;
+}
+
+// Leaf-level differences between two JSON values, keyed by path
+// ("entry_conditions[1].params.period"). Arrays compare index by index.
+void run_json_diff(const QJsonValue& a, const QJsonValue& b, const QString& path, QJsonArray& out) {
+    if (a == b)
+        return;
+    if (a.isObject() && b.isObject()) {
+        const QJsonObject ao = a.toObject(), bo = b.toObject();
+        QStringList keys = ao.keys();
+        for (const auto& k : bo.keys())
+            if (!ao.contains(k))
+                keys << k;
+        keys.sort();
+        for (const auto& k : keys)
+            run_json_diff(ao.value(k), bo.value(k), path.isEmpty() ? k : path + '.' + k, out);
+        return;
+    }
+    if (a.isArray() && b.isArray()) {
+        const QJsonArray aa = a.toArray(), ba = b.toArray();
+        for (int i = 0; i < std::max(aa.size(), ba.size()); ++i)
+            run_json_diff(i < aa.size() ? aa[i] : QJsonValue(QJsonValue::Undefined),
+                          i < ba.size() ? ba[i] : QJsonValue(QJsonValue::Undefined), QString("%1[%2]").arg(path).arg(i),
+                          out);
+        return;
+    }
+    const auto side = [](const QJsonValue& v) { return v.isUndefined() ? QJsonValue(QJsonValue::Null) : v; };
+    const QString change = a.isUndefined() ? "added" : b.isUndefined() ? "removed" : "changed";
+    out.append(QJsonObject{{"path", path}, {"change", change}, {"base", side(a)}, {"other", side(b)}});
+}
+
+// A loaded run without its bulky parts — the summary row again.
+QJsonObject run_brief(const QJsonObject& run) {
+    QJsonObject s = run;
+    for (const char* key : {"config", "metrics", "trades", "equity_curve"})
+        s.remove(key);
+    return s;
+}
+
+bool run_lower_is_better(const QString& metric) {
+    return metric == QLatin1String("max_drawdown");
+}
+
+} // namespace
+
+QJsonObject AlgoTradingService::save_backtest_run(const QString& kind, const AlgoStrategy& strategy,
+                                                  const fincept::algo::BacktestParams& params,
+                                                  const QJsonObject& inputs, const QJsonObject& result) {
+    auto& db = fincept::Database::instance();
+    const QString id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    const QJsonObject rules = run_rules(params);
+    QJsonObject config = inputs;
+    config["kind"] = kind;
+    config["rules"] = rules;
+    const QString strategy_hash = run_hash(rules);
+    const QString config_hash = run_hash(config);
+
+    // Everything except the bulky series goes into metrics_json.
+    QJsonObject metrics = result;
+    for (const char* key : {"trades", "equity_curve", "benchmark_curve", "success", "request_id"})
+        metrics.remove(key);
+
+    if (db.begin_transaction().is_err()) {
+        LOG_WARN("AlgoTrading", "Backtest run not saved: could not open a transaction");
+        return {};
+    }
+    // Inline strategies without an id are versioned by name.
+    const QString owner = strategy.id.isEmpty() ? "strategy_id = '' AND strategy_name = ?" : "strategy_id = ?";
+    const QString owner_arg = strategy.id.isEmpty() ? strategy.name : strategy.id;
+    int version = 1;
+    auto v = db.execute("SELECT strategy_version FROM algo_backtest_runs WHERE " + owner +
+                            " AND strategy_hash = ? LIMIT 1",
+                        {owner_arg, strategy_hash});
+    if (v.is_ok() && v.value().next()) {
+        version = v.value().value(0).toInt();
+    } else {
+        v = db.execute("SELECT COALESCE(MAX(strategy_version), 0) + 1 FROM algo_backtest_runs WHERE " + owner,
+                       {owner_arg});
+        if (v.is_ok() && v.value().next())
+            version = v.value().value(0).toInt();
+    }
+
+    QVariantList args{id,
+                      kind,
+                      strategy.id,
+                      strategy.name,
+                      version,
+                      strategy_hash,
+                      config_hash,
+                      inputs.value("symbol").toString(),
+                      inputs.value("exchange").toString(),
+                      params.timeframe,
+                      inputs.value("start_date").toString(),
+                      inputs.value("end_date").toString(),
+                      inputs.value("initial_capital").toDouble()};
+    for (const auto& m : kRunMetrics)
+        args << (result.contains(m) ? result.value(m).toVariant() : QVariant());
+    args << run_compact(config) << run_compact(metrics) << run_compact(result.value("trades").toArray())
+         << run_compact(result.value("equity_curve").toArray());
+    auto r = db.execute(QString("INSERT INTO algo_backtest_runs (id, kind, strategy_id, strategy_name, "
+                                "strategy_version, strategy_hash, config_hash, symbol, exchange, timeframe, "
+                                "start_date, end_date, initial_capital, %1, config_json, metrics_json, trades_json, "
+                                "equity_curve_json) VALUES (%2)")
+                            .arg(kRunMetrics.join(", "), QStringList(args.size(), "?").join(',')),
+                        args);
+    if (r.is_err()) {
+        db.rollback();
+        LOG_WARN("AlgoTrading", "Backtest run not saved: " + QString::fromStdString(r.error()));
+        return {};
+    }
+    db.commit();
+    return QJsonObject{{"run_id", id}, {"strategy_version", version}, {"config_hash", config_hash}};
+}
+
+QJsonArray AlgoTradingService::backtest_runs(const QString& strategy_id, const QString& symbol, int limit) const {
+    QJsonArray out;
+    QVariantList args;
+    QStringList where;
+    if (!strategy_id.isEmpty()) {
+        where << "strategy_id = ?";
+        args << strategy_id;
+    }
+    if (!symbol.isEmpty()) {
+        where << "symbol = ?";
+        args << symbol.toUpper();
+    }
+    QString sql = QString("SELECT %1 FROM algo_backtest_runs").arg(kRunColumns);
+    if (!where.isEmpty())
+        sql += " WHERE " + where.join(" AND ");
+    sql += " ORDER BY created_at DESC, rowid DESC LIMIT ?";
+    args << std::clamp(limit, 1, 500);
+    auto r = fincept::Database::instance().execute(sql, args);
+    if (r.is_err())
+        return out;
+    while (r.value().next())
+        out.append(run_db_summary(r.value()));
+    return out;
+}
+
+QJsonObject AlgoTradingService::backtest_run(const QString& run_id) const {
+    auto r = fincept::Database::instance().execute(
+        QString("SELECT %1, config_json, metrics_json, trades_json, equity_curve_json FROM algo_backtest_runs "
+                "WHERE id = ?")
+            .arg(kRunColumns),
+        {run_id});
+    if (r.is_err() || !r.value().next())
+        return {};
+    const auto& q = r.value();
+    QJsonObject out = run_db_summary(q);
+    out["config"] = QJsonDocument::fromJson(q.value(25).toByteArray()).object();
+    out["metrics"] = QJsonDocument::fromJson(q.value(26).toByteArray()).object();
+    out["trades"] = QJsonDocument::fromJson(q.value(27).toByteArray()).array();
+    out["equity_curve"] = QJsonDocument::fromJson(q.value(28).toByteArray()).array();
+    return out;
+}
+
+QJsonObject AlgoTradingService::compare_backtest_runs(const QStringList& run_ids) const {
+    auto fail = [](const QString& msg) { return QJsonObject{{"success", false}, {"error", msg}}; };
+    if (run_ids.size() < 2)
+        return fail(QStringLiteral("Pick at least two runs to compare"));
+
+    QVector<QJsonObject> runs;
+    for (const auto& id : run_ids) {
+        const QJsonObject run = backtest_run(id);
+        if (run.isEmpty())
+            return fail(QString("Unknown backtest run '%1'").arg(id));
+        runs.append(run);
+    }
+
+    QJsonArray summaries, curves;
+    for (const auto& run : runs) {
+        summaries.append(run_brief(run));
+        curves.append(QJsonObject{{"run_id", run.value("run_id")}, {"values", run.value("equity_curve")}});
+    }
+
+    // One row per metric any run reported, headline metrics first.
+    QStringList metrics = kRunMetrics;
+    for (const auto& run : runs) {
+        const QJsonObject m = run.value("metrics").toObject();
+        for (auto it = m.begin(); it != m.end(); ++it)
+            if (it.value().isDouble() && !metrics.contains(it.key()))
+                metrics << it.key();
+    }
+
+    QJsonArray table;
+    for (const auto& m : metrics) {
+        QJsonArray values, deltas;
+        const QJsonValue first = runs.first().value("metrics").toObject().value(m);
+        QString best_id;
+        double best = 0.0;
+        for (const auto& run : runs) {
+            const QJsonValue v = run.value("metrics").toObject().value(m);
+            values.append(v.isDouble() ? v : QJsonValue(QJsonValue::Null));
+            deltas.append(v.isDouble() && first.isDouble() ? QJsonValue(v.toDouble() - first.toDouble())
+                                                            : QJsonValue(QJsonValue::Null));
+            if (!v.isDouble())
+                continue;
+            const double x = run_lower_is_better(m) ? -v.toDouble() : v.toDouble();
+            if (best_id.isEmpty() || x > best) {
+                best = x;
+                best_id = run.value("run_id").toString();
+            }
+        }
+        table.append(QJsonObject{
+            {"metric", m}, {"values", values}, {"delta_vs_first", deltas}, {"best_run_id", best_id}});
+    }
+
+    QStringList config_hashes, versions;
+    for (const auto& run : runs) {
+        config_hashes << run.value("config_hash").toString();
+        versions << QString::number(run.value("strategy_version").toInt());
+    }
+    config_hashes.removeDuplicates();
+    versions.removeDuplicates();
+    return QJsonObject{{"success", true},
+                       {"runs", summaries},
+                       {"metrics", table},
+                       {"equity_curves", curves},
+                       {"identical_config", config_hashes.size() == 1},
+                       {"strategy_versions", QJsonArray::fromStringList(versions)}};
+}
+
+QJsonObject AlgoTradingService::diff_backtest_runs(const QString& base_id, const QString& other_id) const {
+    const QJsonObject a = backtest_run(base_id), b = backtest_run(other_id);
+    if (a.isEmpty() || b.isEmpty())
+        return QJsonObject{{"success", false},
+                           {"error", QString("Unknown backtest run '%1'").arg(a.isEmpty() ? base_id : other_id)}};
+
+    QJsonArray config_changes;
+    run_json_diff(a.value("config"), b.value("config"), QString(), config_changes);
+
+    QJsonArray metric_changes;
+    const QJsonObject am = a.value("metrics").toObject(), bm = b.value("metrics").toObject();
+    QStringList metrics = kRunMetrics;
+    for (const auto& k : am.keys())
+        if (am.value(k).isDouble() && bm.value(k).isDouble() && !metrics.contains(k))
+            metrics << k;
+    for (const auto& m : metrics) {
+        const QJsonValue x = am.value(m), y = bm.value(m);
+        if (!x.isDouble() || !y.isDouble())
+            continue;
+        const double delta = y.toDouble() - x.toDouble();
+        const bool improved = run_lower_is_better(m) ? delta < 0 : delta > 0;
+        metric_changes.append(QJsonObject{{"metric", m},
+                                          {"base", x},
+                                          {"other", y},
+                                          {"delta", delta},
+                                          {"improved", delta != 0.0 && improved}});
+    }
+
+    // Trades matched by entry time: the same entry can exit differently when
+    // only the exit rules or stops changed.
+    QHash<qint64, QJsonObject> base_trades;
+    for (const auto& t : a.value("trades").toArray())
+        base_trades.insert(qint64(t.toObject().value("entry_time").toDouble()), t.toObject());
+    QJsonArray added, changed;
+    int unchanged = 0;
+    for (const auto& tv : b.value("trades").toArray()) {
+        const QJsonObject t = tv.toObject();
+        const qint64 key = qint64(t.value("entry_time").toDouble());
+        const auto it = base_trades.constFind(key);
+        if (it == base_trades.constEnd()) {
+            added.append(t);
+            continue;
+        }
+        if (it->value("exit_time") == t.value("exit_time") && it->value("pnl") == t.value("pnl")) {
+            ++unchanged;
+        } else {
+            changed.append(QJsonObject{{"entry_time", t.value("entry_time")},
+                                       {"base", *it},
+                                       {"other", t},
+                                       {"pnl_delta", t.value("pnl").toDouble() - it->value("pnl").toDouble()}});
+        }
+        base_trades.erase(it);
+    }
+    QJsonArray removed;
+    for (const auto& t : base_trades)
+        removed.append(t);
+
+    const auto head = [](const QJsonArray& arr) {
+        QJsonArray out;
+        for (int i = 0; i < std::min(int(arr.size()), kRunMaxListed); ++i)
+            out.append(arr[i]);
+        return out;
+    };
+    const QJsonObject trades{{"base_count", a.value("trades").toArray().size()},
+                             {"other_count", b.value("trades").toArray().size()},
+                             {"unchanged", unchanged},
+                             {"added_count", added.size()},
+                             {"removed_count", removed.size()},
+                             {"changed_count", changed.size()},
+                             {"added", head(added)},
+                             {"removed", head(removed)},
+                             {"changed", head(changed)}};
+
+    return QJsonObject{{"success", true},
+                       {"base", run_brief(a)},
+                       {"other", run_brief(b)},
+                       {"same_strategy_version", a.value("strategy_hash") == b.value("strategy_hash")},
+                       {"identical_config", a.value("config_hash") == b.value("config_hash")},
+                       {"config_changes", config_changes},
+                       {"metric_changes", metric_changes},
+                       {"trades", trades}};
+}
+
+QJsonObject AlgoTradingService::resample_backtest_run(const QString& run_id, const QJsonObject& config) const {
+    const QJsonObject run = backtest_run(run_id);
+    if (run.isEmpty())
+        return QJsonObject{{"success", false}, {"error", QString("Unknown backtest run '%1'").arg(run_id)}};
+    QJsonObject out = fincept::algo::TradeResampler::run(run.value("trades").toArray(),
+                                                         run.value("initial_capital").toDouble(),
+                                                         fincept::algo::ResampleConfig::from_json(config));
+    out["run"] = run_brief(run);
+    return out;
+}
+
+bool AlgoTradingService::annotate_backtest_run(const QString& run_id, const QString& label, const QString& notes) {
+    auto r = fincept::Database::instance().execute("UPDATE algo_backtest_runs SET label = ?, notes = ? WHERE id = ?",
+                                                   {label, notes, run_id});
+    return r.is_ok() && r.value().numRowsAffected() > 0;
+}
+
+bool AlgoTradingService::delete_backtest_run(const QString& run_id) {
+    auto r = fincept::Database::instance().execute("DELETE FROM algo_backtest_runs WHERE id = ?", {run_id});
+    return r.is_ok() && r.value().numRowsAffected() > 0;
+}
+
+void AlgoTradingService::run_spread_backtest(const AlgoStrategy& strategy, const QJsonObject& spread,
+                                             const QString& start_date, const QString& end_date, double capital,
+                                             const QString& direction, double units) {
+    const auto def = fincept::algo::SpreadDefinition::from_json(spread);
+    QString def_error;
+    if (!def.is_valid(&def_error)) {
+        emit error_occurred("spread_backtest", def_error);
+        return;
+    }
+
+    int lookback_days = 365;
+    const QDate d1 = QDate::fromString(start_date, "yyyy-MM-dd");
+    const QDate d2 = QDate::fromString(end_date, "yyyy-MM-dd");
+    if (d1.isValid() && d2.isValid() && d1 < d2)
+        lookback_days = static_cast<int>(d1.daysTo(d2));
+
     const QJsonArray entry = strategy.entry_conditions;
     const QJsonArray exit = strategy.exit_conditions;
     const QString entry_logic = strategy.entry_logic.isEmpty() ? QStringLiteral("AND") : strategy.entry_logic;
     const QString exit_logic = strategy.exit_logic.isEmpty() ? QStringLiteral("AND") : strategy.exit_logic;
+    // Spread stops are absolute points — the strategy's % fields are reused as
+    // point distances since a percentage of a near-zero spread is meaningless.
     const double sl = strategy.stop_loss;
     const double tp = strategy.take_profit;
     const double trail = strategy.trailing_stop;
-    const double size_pct = strategy.position_size_pct > 0 ? strategy.position_size_pct : 100.0;
     const QString timeframe = strategy.timeframe.isEmpty() ? QStringLiteral("1d") : strategy.timeframe;
 
-    LOG_INFO("AlgoTrading", QString("Backtest %1 [%2] %3 — source=%4")
-                                .arg(symbol, timeframe, strategy.name, broker_id.isEmpty() ? "yahoo" : broker_id));
+    LOG_INFO("AlgoTrading", QString("Spread backtest %1 [%2] %3 — legs=%4")
+                                .arg(def.name, timeframe, strategy.name)
+                                .arg(def.legs.size()));
 
-    // Singleton — `this` outlives any async work, so capture directly.
-    fincept::algo::CandleDataFetcher::instance().fetch(
-        symbol, timeframe, lookback_days, source, broker_id, account_id,
-        [this, entry, exit, entry_logic, exit_logic, sl, tp, trail, size_pct, capital, timeframe,
-         symbol](bool ok, const QVector<fincept::algo::OhlcvCandle>& candles, const QString& err) {
-            if (!ok || candles.isEmpty()) {
-                emit error_occurred("backtest", err.isEmpty() ? QStringLiteral("No data") : err);
-                return;
+    fincept::algo::CandleDataFetcher::instance().fetch_multi(
+        def.symbols(), timeframe, lookback_days, fincept::algo::DataSource::YFinance, {}, {},
+        [this, def, entry, exit, entry_logic, exit_logic, sl, tp, trail, capital, timeframe, direction,
+         units](const QHash<QString, QVector<fincept::algo::OhlcvCandle>>& data, const QStringList& errors) {
+            QVector<QVector<fincept::algo::OhlcvCandle>> leg_candles;
+            for (const auto& leg : def.legs) {
+                const auto candles = data.value(leg.symbol);
+                if (candles.isEmpty()) {
+                    emit error_occurred("spread_backtest",
+                                        QString("No data for leg %1%2")
+                                            .arg(leg.symbol, errors.isEmpty() ? QString() : ": " + errors.join("; ")));
+                    return;
+                }
+                leg_candles.append(candles);
             }
-            const QJsonObject result = fincept::algo::BacktestEngine::run(candles, entry, entry_logic, exit, exit_logic,
-                                                                          sl, tp, trail, capital, timeframe, size_pct);
+            const auto series = fincept::algo::build_spread_series(def, leg_candles);
+            const QJsonObject result = fincept::algo::SpreadBacktestEngine::run(
+                def, series, entry, entry_logic, exit, exit_logic, direction, units, sl, tp, trail, capital, timeframe);
             if (!result.value("success").toBool(false)) {
-                emit error_occurred("backtest", result.value("error").toString(QStringLiteral("Backtest failed")));
+                emit error_occurred("spread_backtest",
+                                    result.value("error").toString(QStringLiteral("Spread backtest failed")));
                 return;
             }
+            last_backtest_ = {series.spread, entry, exit, entry_logic, exit_logic, result.value("trades").toArray()};
             emit backtest_result(result);
         });
 }
 
+void AlgoTradingService::inspect_backtest_trade(int trade_index, int context_bars, const QString& request_id) {
+    const QString context =
+        request_id.isEmpty() ? QStringLiteral("inspect_trade") : QStringLiteral("inspect_trade:") + request_id;
+    if (last_backtest_.candles.isEmpty()) {
+        emit error_occurred(context, QStringLiteral("No backtest loaded — run a backtest first"));
+        return;
+    }
+    if (trade_index < 0 || trade_index >= last_backtest_.trades.size()) {
+        emit error_occurred(context, QString("Trade index %1 out of range (%2 trades)")
+                                         .arg(trade_index)
+                                         .arg(last_backtest_.trades.size()));
+        return;
+    }
+    QJsonObject result = fincept::algo::BacktestTradeInspector::inspect(
+        last_backtest_.candles, last_backtest_.trades.at(trade_index).toObject(), last_backtest_.entry_conditions,
+        last_backtest_.entry_logic, last_backtest_.exit_conditions, last_backtest_.exit_logic, context_bars);
+    if (!result.value("success").toBool(false)) {
+        emit error_occurred(context, result.value("error").toString(QStringLiteral("Inspection failed")));
+        return;
+    }
+    result["trade_index"] = trade_index;
+    if (!request_id.isEmpty())
+        result["request_id"] = request_id;
+    emit trade_inspection(result);
+}
+
+void AlgoTradingService::step_backtest(int from_bar, const QStringList& breakpoints, bool backward) {
+    if (last_backtest_.candles.isEmpty()) {
+        emit error_occurred("backtest_step", QStringLiteral("No backtest loaded — run a backtest first"));
+        return;
+    }
+    const QJsonObject result = fincept::algo::BacktestTradeInspector::step(
+        last_backtest_.candles, last_backtest_.entry_conditions, last_backtest_.entry_logic,
+        last_backtest_.exit_conditions, last_backtest_.exit_logic, from_bar, breakpoints, backward);
+    if (!result.value("success").toBool(false)) {
+        emit error_occurred("backtest_step", result.value("error").toString(QStringLiteral("Step failed")));
+        return;
+    }
+    emit backtest_step(result);
+}
+
+void AlgoTradingService::transform_bars(const QString& symbol, const QString& timeframe, const QString& start_date,
+                                        const QString& end_date, const QJsonObject& bar_transform) {
+    QString transform_error;
+    const auto transform = fincept::algo::BarTransform::from_json(bar_transform, &transform_error);
+    if (!transform_error.isEmpty()) {
+        emit error_occurred("transform_bars", transform_error);
+        return;
+    }
+    const QString tf = timeframe.isEmpty() ? QStringLiteral("1d") : timeframe;
+    fetch_backtest_candles(
+        symbol, tf, start_date, end_date,
+        [this, symbol, tf, transform](bool ok, const QVector<fincept::algo::OhlcvCandle>& source, const QString& err) {
+            if (!ok || source.isEmpty()) {
+                emit error_occurred("transform_bars", err.isEmpty() ? QStringLiteral("No data") : err);
+                return;
+            }
+            const auto bars = fincept::algo::BarTransforms::apply(transform, source);
+            QJsonArray rows;
+            for (const auto& c : bars)
+                rows.append(QJsonObject{{"time", static_cast<double>(c.open_time)},
+                                        {"open", c.open},
+                                        {"high", c.high},
+                                        {"low", c.low},
+                                        {"close", c.close},
+                                        {"volume", c.volume}});
+            emit bars_transformed(QJsonObject{{"symbol", symbol.toUpper()},
+                                              {"timeframe", tf},
+                                              {"bar_transform", transform.to_json()},
+                                              {"source_bars", source.size()},
+                                              {"bar_count", rows.size()},
+                                              {"bars", rows}});
+        });
+}
+
+void AlgoTradingService::analyze_pair(const QString& y_symbol, const QString& x_symbol, const QString& timeframe,
+                                      int lookback_days, const QJsonObject& config) {
+    const QString y = y_symbol.trimmed().toUpper();
+    const QString x = x_symbol.trimmed().toUpper();
+    if (y.isEmpty() || x.isEmpty() || y == x) {
+        emit error_occurred("pair_analysis", QStringLiteral("Two different symbols are required"));
+        return;
+    }
+    const auto cfg = fincept::algo::PairsConfig::from_json(config);
+    const QString tf = timeframe.isEmpty() ? QStringLiteral("1d") : timeframe;
+    fincept::algo::CandleDataFetcher::instance().fetch_multi(
+        {y, x}, tf, lookback_days, fincept::algo::DataSource::YFinance, {}, {},
+        [this, y, x, tf, cfg](const QHash<QString, QVector<fincept::algo::OhlcvCandle>>& data,
+                              const QStringList& errors) {
+            for (const QString& sym : {y, x}) {
+                if (data.value(sym).isEmpty()) {
+                    emit error_occurred("pair_analysis",
+                                        QString("No data for %1%2")
+                                            .arg(sym, errors.isEmpty() ? QString() : ": " + errors.join("; ")));
+                    return;
+                }
+            }
+            QJsonObject result = fincept::algo::PairsAnalytics::analyze(y, data.value(y), x, data.value(x), cfg);
+            if (!result.value("success").toBool(false)) {
+                emit error_occurred("pair_analysis", result.value("error").toString());
+                return;
+            }
+            result["timeframe"] = tf;
+            emit pair_analysis(result);
+        });
+}
+
+void AlgoTradingService::scan_pairs(const QStringList& symbols, const QString& timeframe, int lookback_days,
+                                    const QJsonObject& config, double max_significance) {
+    QStringList universe;
+    for (const QString& s : symbols)
+        if (const QString sym = s.trimmed().toUpper(); !sym.isEmpty() && !universe.contains(sym))
+            universe << sym;
+    if (universe.size() < 2) {
+        emit error_occurred("pair_scan", QStringLiteral("At least two symbols are required"));
+        return;
+    }
+    const auto cfg = fincept::algo::PairsConfig::from_json(config);
+    const QString tf = timeframe.isEmpty() ? QStringLiteral("1d") : timeframe;
+    LOG_INFO("AlgoTrading", QString("Pair scan over %1 symbols [%2]").arg(universe.size()).arg(tf));
+    fincept::algo::CandleDataFetcher::instance().fetch_multi(
+        universe, tf, lookback_days, fincept::algo::DataSource::YFinance, {}, {},
+        [this, tf, cfg, max_significance](const QHash<QString, QVector<fincept::algo::OhlcvCandle>>& data,
+                                          const QStringList& errors) {
+            // O(n²) regressions — keep them off the UI thread.
+            (void)QtConcurrent::run([this, data, errors, tf, cfg, max_significance]() {
+                QJsonObject result = fincept::algo::PairsAnalytics::scan(data, cfg, max_significance);
+                result["timeframe"] = tf;
+                if (!errors.isEmpty())
+                    result["fetch_errors"] = QJsonArray::fromStringList(errors);
+                QMetaObject::invokeMethod(
+                    this, [this, result]() { emit pair_scan_result(result); }, Qt::QueuedConnection);
+            });
+        });
+}
+
 // Scanner is now in AlgoScanner (src/algo_engine/AlgoScanner.h/.cpp).
 
 } // namespace fincept::services::algo
@@ -1,9 +1,15 @@
 // src/services/algo_trading/AlgoTradingService.h
 #pragma once
+#include "algo_engine/AlgoEngineTypes.h"
+#include "algo_engine/BacktestEngine.h"
+#include "algo_engine/CandleDataFetcher.h"
 #include "services/algo_trading/AlgoTradingTypes.h"
 
 #include <QObject>
 
+#include <atomic>
+#include <optional>
+
 namespace fincept::services::algo {
 
 /// Singleton service for Algo Trading — strategy CRUD and native C++ backtesting.
@@ -23,9 +29,140 @@ class AlgoTradingService : public QObject {
     // ── Backtesting (native C++ engine) ──────────────────────────────────────
     // Backtests the given strategy's current conditions directly (no DB round-trip),
     // so unsaved builder edits are testable. Candles come from the connected broker
-    // when one is connected, otherwise native Yahoo Finance.
+    // when one is connected, otherwise native Yahoo Finance. `bar_transform` (a
+    // BarTransform JSON — algo_engine/BarTransforms.h) swaps the time bars for
+    // Heikin-Ashi, Renko or range bars before the rules see them.
     void run_backtest(const fincept::services::algo::AlgoStrategy& strategy, const QString& symbol,
-                      const QString& start_date, const QString& end_date, double capital);
+                      const QString& start_date, const QString& end_date, double capital,
+                      const QJsonObject& bar_transform = {});
+
+    // The symbol's bars over the date range converted to Heikin-Ashi, Renko or
+    // range bars (`bar_transform` is a BarTransform JSON) — what a transformed
+    // backtest trades on. Emits bars_transformed.
+    void transform_bars(const QString& symbol, const QString& timeframe, const QString& start_date,
+                        const QString& end_date, const QJsonObject& bar_transform);
+
+    // Backtests the strategy's rules against a synthetic spread (weighted legs of
+    // existing symbols — see algo_engine/SpreadInstrument.h). Each leg's history
+    // is fetched, aligned on bar time and combined; fills are decomposed to legs.
+    // `spread` is a SpreadDefinition JSON; stops/targets are in spread points.
+    // Emits backtest_result.
+    void run_spread_backtest(const fincept::services::algo::AlgoStrategy& strategy, const QJsonObject& spread,
+                             const QString& start_date, const QString& end_date, double capital,
+                             const QString& direction = QStringLiteral("long"), double units = 1.0);
+
+    // Walk-forward optimisation / cross-validation (algo_engine/WalkForwardEngine.h):
+    // rolling, anchored or k-fold train/test windows over the date range, the
+    // parameter grid re-optimised on each train window and scored on the
+    // following unseen test window. `config` is a WalkForwardConfig JSON; its
+    // optional `request_id` is echoed in the result. Runs off the UI thread;
+    // emits walk_forward_progress then walk_forward_result.
+    void run_walk_forward(const fincept::services::algo::AlgoStrategy& strategy, const QString& symbol,
+                          const QString& start_date, const QString& end_date, double capital,
+                          const QJsonObject& config);
+
+    // Parallel parameter sweep (algo_engine/ParameterSweepEngine.h): every
+    // combination of the grid backtested over the date range, ranked by the
+    // objective, with optional early stopping. The results matrix is saved to
+    // algo_param_sweeps / algo_param_sweep_results and the result carries its
+    // `sweep_id`. `config` is a SweepConfig JSON; its optional `request_id` is
+    // echoed. Emits parameter_sweep_progress then parameter_sweep_result.
+    void run_parameter_sweep(const fincept::services::algo::AlgoStrategy& strategy, const QString& symbol,
+                             const QString& start_date, const QString& end_date, double capital,
+                             const QJsonObject& config);
+    // Stops the running sweep after its current batch; what was evaluated is
+    // still ranked and saved.
+    void cancel_parameter_sweep();
+
+    // Saved sweeps, newest first (summary rows; empty strategy_id = all).
+    QJsonArray parameter_sweeps(const QString& strategy_id = {}, int limit = 50) const;
+    // One saved sweep with its ranked results and a heat map of `metric` over
+    // two grid keys (defaults: the first two keys and the sweep's objective).
+    // Empty object when the id is unknown.
+    QJsonObject parameter_sweep(const QString& sweep_id, const QString& x_key = {}, const QString& y_key = {},
+                                const QString& metric = {}) const;
+
+    // Tick-level, event-driven backtest (algo_engine/TickBacktestEngine.h) over
+    // the trades/quotes stored in HistoricalDataStore for (symbol, exchange),
+    // warmed up from the cached candles before the first tick. `fill_model` is
+    // a TickFillModel JSON (latency, slippage, queueing); its optional
+    // `request_id` is echoed. Runs off the UI thread; emits tick_backtest_result.
+    void run_tick_backtest(const fincept::services::algo::AlgoStrategy& strategy, const QString& symbol,
+                           const QString& exchange, const QString& start_date, const QString& end_date,
+                           double capital, const QJsonObject& fill_model);
+
+    // Portfolio-level backtest (algo_engine/PortfolioBacktestEngine.h): every
+    // sleeve (a strategy on a symbol) trades against one capital pool, sized by
+    // the allocation rule in `allocation` (a PortfolioAllocation JSON) with
+    // same-symbol orders netted across sleeves. Its optional `request_id` is
+    // echoed. Emits portfolio_backtest_result.
+    struct PortfolioSleeveSpec {
+        AlgoStrategy strategy;
+        QString symbol;
+        double weight = 0.0; // 0 → equal split of the unassigned weight
+    };
+    void run_portfolio_backtest(const QVector<PortfolioSleeveSpec>& sleeves, const QString& start_date,
+                                const QString& end_date, double capital, const QJsonObject& allocation);
+
+    // ── Pairs analytics (algo_engine/PairsAnalytics.h) ──────────────────────
+    // Cointegration report for y against x over `lookback_days` of history:
+    // ADF of each leg, Engle-Granger, hedge ratio and spread z-score. `config`
+    // is a PairsConfig JSON. Emits pair_analysis.
+    void analyze_pair(const QString& y_symbol, const QString& x_symbol, const QString& timeframe, int lookback_days,
+                      const QJsonObject& config);
+    // Engle-Granger over every pair of `symbols`, cointegrated pairs ranked by
+    // test statistic. Emits pair_scan_result.
+    void scan_pairs(const QStringList& symbols, const QString& timeframe, int lookback_days, const QJsonObject& config,
+                    double max_significance = 0.05);
+
+    // ── Backtest run history ────────────────────────────────────────────────
+    // Every successful bar and tick backtest is saved to algo_backtest_runs
+    // (configuration, metrics, trades, equity curve) and its result carries the
+    // `run_id` and `strategy_version` — a per-strategy counter bumped whenever
+    // the rules (conditions, logic, stops, sizing, timeframe) change. Runs with
+    // the same `config_hash` had identical rules and inputs.
+
+    // Saved runs, newest first (summary rows; empty filters = all).
+    QJsonArray backtest_runs(const QString& strategy_id = {}, const QString& symbol = {}, int limit = 50) const;
+    // One saved run with its configuration, full metrics, trades and equity
+    // curve. Empty object when the id is unknown.
+    QJsonObject backtest_run(const QString& run_id) const;
+    // Side-by-side metrics of two or more runs: each metric's value per run,
+    // its change against the first run and the best run. Returns
+    // {"success": false, "error": …} when an id is unknown.
+    QJsonObject compare_backtest_runs(const QStringList& run_ids) const;
+    // What changed from `base_id` to `other_id`: configuration fields
+    // (rules, symbol, range, capital) by JSON path, metric deltas, and trades
+    // matched by entry time — added, removed or closed differently.
+    QJsonObject diff_backtest_runs(const QString& base_id, const QString& other_id) const;
+    // Monte Carlo resampling of a saved run's trades (algo_engine/TradeResampler.h):
+    // confidence intervals of final equity, drawdown and Sharpe, and a luck
+    // test. `config` is a ResampleConfig JSON. Returns {"success": false, …}
+    // when the id is unknown or the run has too few trades.
+    QJsonObject resample_backtest_run(const QString& run_id, const QJsonObject& config) const;
+    bool annotate_backtest_run(const QString& run_id, const QString& label, const QString& notes);
+    bool delete_backtest_run(const QString& run_id);
+
+    // Loads one active strategy by id (nullopt when missing or soft-deleted).
+    std::optional<AlgoStrategy> strategy_by_id(const QString& id) const;
+
+    // The strategy's backtest inputs with the builder defaults applied
+    // (AND logic, 1d timeframe, 100% sizing).
+    static fincept::algo::BacktestParams backtest_params(const AlgoStrategy& strategy, double capital);
+
+    // Drill-down for one trade of the most recent backtest: surrounding candles,
+    // indicator values at entry/exit and the rule evaluations that fired it.
+    // Emits trade_inspection (or error_occurred when no backtest is loaded).
+    // A non-empty `request_id` is echoed in the result and appended to the
+    // error context ("inspect_trade:<id>") so a caller can pick out its reply.
+    void inspect_backtest_trade(int trade_index, int context_bars = 20, const QString& request_id = {});
+
+    // Rule debugger over the most recent backtest's bars: from `from_bar`,
+    // step to the next (previous when `backward`) bar where a breakpoint rule
+    // path turns met, or one bar without breakpoints, and report the rule
+    // trees and indicator values there (see BacktestTradeInspector::step).
+    // Emits backtest_step (or error_occurred when no backtest is loaded).
+    void step_backtest(int from_bar, const QStringList& breakpoints, bool backward = false);
 
     // Scanner is now in AlgoScanner (src/algo_engine/AlgoScanner.h).
 
@@ -34,9 +171,52 @@ class AlgoTradingService : public QObject {
     void strategies_loaded(QVector<fincept::services::algo::AlgoStrategy> strategies);
     void strategy_deleted(QString id);
     void backtest_result(QJsonObject data);
+    void walk_forward_progress(int done, int total);
+    void walk_forward_result(QJsonObject data);
+    void parameter_sweep_progress(int done, int total);
+    void parameter_sweep_result(QJsonObject data);
+    void tick_backtest_result(QJsonObject data);
+    void portfolio_backtest_result(QJsonObject data);
+    void trade_inspection(QJsonObject data);
+    void backtest_step(QJsonObject data);
+    void bars_transformed(QJsonObject data);
+    void pair_analysis(QJsonObject data);
+    void pair_scan_result(QJsonObject data);
     void error_occurred(QString context, QString message);
 
   private:
+    // Inputs + trades of the last successful backtest, kept so the trade
+    // inspector can replay rule evaluations without refetching candles.
+    struct LastBacktest {
+        QVector<fincept::algo::OhlcvCandle> candles;
+        QJsonArray entry_conditions;
+        QJsonArray exit_conditions;
+        QString entry_logic;
+        QString exit_logic;
+        QJsonArray trades;
+    };
+    LastBacktest last_backtest_;
+
+    std::atomic_bool sweep_cancel_{false};
+
+    // Candles for a backtest over [start_date, end_date]: the connected broker
+    // when one is connected, otherwise native Yahoo Finance.
+    void fetch_backtest_candles(const QString& symbol, const QString& timeframe, const QString& start_date,
+                                const QString& end_date, fincept::algo::CandleCallback callback);
+
+    // Writes a finished sweep and its result rows in one transaction; returns
+    // the new sweep id (empty on failure). Safe off the UI thread.
+    static QString save_parameter_sweep(const QJsonObject& result, const QString& start_date,
+                                        const QString& end_date);
+
+    // Saves a successful backtest to algo_backtest_runs; `inputs` holds what
+    // besides the rules defines the run (symbol, range, capital, fill model…).
+    // Returns {run_id, strategy_version, config_hash} (empty on failure). Safe
+    // off the UI thread.
+    static QJsonObject save_backtest_run(const QString& kind, const AlgoStrategy& strategy,
+                                         const fincept::algo::BacktestParams& params, const QJsonObject& inputs,
+                                         const QJsonObject& result);
+
     explicit AlgoTradingService(QObject* parent = nullptr);
     void seed_library(); // idempotently seeds the curated C++ DSL library
     Q_DISABLE_COPY(AlgoTradingService)
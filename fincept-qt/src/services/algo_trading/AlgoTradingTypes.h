@@ -90,7 +90,7 @@ struct AlgoDeployment {
     QString resolved_expiry;            // concrete expiry chosen at entry, "DD-MMM-YY"
     QJsonArray resolved_legs;           // concrete contracts placed at entry (restart reattach)
     QString product_type;               // e.g. "MIS", "CNC" — broker-specific
-    QString mode;                       // paper | live
+    QString mode;                       // paper | live | shadow (forward test: no orders routed)
     QString entry_side = "BUY";         // BUY | SELL — direction of the entry signal
     QString backend = "paper";          // paper | equity_broker | crypto_exchange
     QString broker_id;                  // BrokerRegistry id; empty for paper
@@ -99,8 +99,10 @@ struct AlgoDeployment {
     QString status;                     // pending | starting | running | stopped | error | crashed
     QString timeframe;
     double quantity = 1.0;
-    double max_order_value = 0; // 0 = no limit
-    double max_daily_loss = 0;  // 0 = no limit
+    double max_order_value = 0;    // 0 = no limit
+    double max_daily_loss = 0;     // 0 = no limit; realized + unrealized, resets each day
+    double max_position_value = 0; // 0 = no limit; open-position notional
+    int max_orders_per_minute = 0; // 0 = no limit; entries beyond it are dropped
     QString error_message;
     qint64 pid = 0; // OS pid of runner process; 0 if not running
     QString created_at;
@@ -120,6 +122,7 @@ struct AlgoDeployment {
     StrategyKind kind() const { return kind_from_id(strategy_id); }
     TradingBackend backend_enum() const { return backend_from_string(backend); }
     bool is_live() const { return mode == QStringLiteral("live"); }
+    bool is_shadow() const { return mode == QStringLiteral("shadow"); }
 };
 
 inline QColor deployment_status_color(const QString& status) {
@@ -167,7 +170,7 @@ struct ParamSpec {
 struct IndicatorDef {
     QString id;
     QString label;
-    QString category; // stock, ma, momentum, trend, volatility, volume
+    QString category; // stock, ma, momentum, trend, volatility, volume, time
     QVector<ParamSpec> params;
     QStringList fields; // named outputs; first is the default
 };
@@ -187,6 +190,11 @@ inline QVector<IndicatorDef> algo_indicators() {
         {"WMA", "WMA", "ma", {{"period", 1, 500, 20, 1, 0}}, {"value"}},
         {"DEMA", "DEMA", "ma", {{"period", 1, 500, 20, 1, 0}}, {"value"}},
         {"TEMA", "TEMA", "ma", {{"period", 1, 500, 20, 1, 0}}, {"value"}},
+        {"KAMA",
+         "Kaufman Adaptive MA",
+         "ma",
+         {{"period", 1, 200, 10, 1, 0}, {"fast", 1, 50, 2, 1, 0}, {"slow", 2, 200, 30, 1, 0}},
+         {"value"}},
         // Momentum
         {"RSI", "RSI", "momentum", {{"period", 2, 100, 14, 1, 0}}, {"value"}},
         {"MACD",
@@ -203,6 +211,7 @@ inline QVector<IndicatorDef> algo_indicators() {
         {"WILLIAMS_R", "Williams %R", "momentum", {{"period", 1, 100, 14, 1, 0}}, {"value"}},
         {"MFI", "MFI", "momentum", {{"period", 1, 100, 14, 1, 0}}, {"value"}},
         {"ROC", "Rate of Change", "momentum", {{"period", 1, 100, 12, 1, 0}}, {"value"}},
+        {"TRIX", "TRIX", "momentum", {{"period", 1, 100, 15, 1, 0}}, {"value"}},
         // Trend
         {"ADX", "ADX", "trend", {{"period", 1, 100, 14, 1, 0}}, {"value", "plus_di", "minus_di"}},
         {"SUPERTREND",
@@ -229,10 +238,32 @@ inline QVector<IndicatorDef> algo_indicators() {
          {{"period", 1, 100, 20, 1, 0}, {"multiplier", 0.5, 10, 2, 0.5, 1}},
          {"upper", "middle", "lower"}},
         {"DONCHIAN", "Donchian Channel", "volatility", {{"period", 1, 100, 20, 1, 0}}, {"upper", "lower"}},
+        // Realised volatility, annualised % — `annualize` is bars per year (252 daily, 52 weekly)
+        {"REALIZED_VOL",
+         "Realized Volatility",
+         "volatility",
+         {{"period", 2, 500, 20, 1, 0}, {"annualize", 1, 525600, 252, 1, 0}},
+         {"close_to_close", "parkinson", "garman_klass", "yang_zhang"}},
         // Volume
         {"OBV", "On Balance Volume", "volume", {}, {"value"}},
         {"CMF", "Chaikin Money Flow", "volume", {{"period", 1, 100, 20, 1, 0}}, {"value"}},
+        {"CHAIKIN_OSC",
+         "Chaikin Oscillator",
+         "volume",
+         {{"fast", 1, 50, 3, 1, 0}, {"slow", 2, 100, 10, 1, 0}},
+         {"value"}},
         {"VOL_WIN_CHG", "Volume Window Δ%", "volume", {{"window", 2, 200, 10, 1, 0}}, {"value"}},
+        // Time — the bar's open time at a fixed UTC offset (minutes, e.g. 330 for IST, -300 for EST)
+        {"TIME",
+         "Bar Time",
+         "time",
+         {{"utc_offset", -720, 840, 0, 15, 0}},
+         {"hour", "minute", "minute_of_day", "day_of_week", "day_of_month", "month", "year"}},
+        {"SESSION",
+         "Session Filter",
+         "time",
+         {{"start", 0, 2359, 930, 1, 0}, {"end", 0, 2400, 1600, 1, 0}, {"utc_offset", -720, 840, 0, 15, 0}},
+         {"in_session", "minutes_from_open", "minutes_to_close"}},
     };
 }
 
@@ -0,0 +1,362 @@
+// src/services/audit/AuditTrail.cpp
+#include "services/audit/AuditTrail.h"
+
+#include "algo_engine/AlgoEngine.h"
+#include "core/events/EventBus.h"
+#include "core/logging/Logger.h"
+#include "storage/sqlite/Database.h"
+#include "trading/AccountManager.h"
+#include "trading/TradingEvents.h"
+
+#include <QCryptographicHash>
+#include <QDateTime>
+#include <QDir>
+#include <QFile>
+#include <QFileInfo>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QMutexLocker>
+#include <QSaveFile>
+#include <QSqlQuery>
+#include <QUuid>
+
+#include <algorithm>
+
+namespace fincept::services::audit {
+
+namespace {
+
+constexpr const char* kAuditTag = "AuditTrail";
+const QString kAtGenesisHash(64, QLatin1Char('0'));
+
+/// The chained hash: previous hash plus every stored column, newline
+/// separated. `detail` is hashed exactly as stored.
+QString at_hash(const QString& prev, qint64 ts, const QString& session, const QString& category,
+                const QString& action, const QString& subject, const QString& detail) {
+    const QString canonical =
+        QStringList{prev, QString::number(ts), session, category, action, subject, detail}.join(QLatin1Char('\n'));
+    return QString::fromLatin1(QCryptographicHash::hash(canonical.toUtf8(), QCryptographicHash::Sha256).toHex());
+}
+
+QString at_csv(const QString& v) {
+    if (!v.contains(QLatin1Char(',')) && !v.contains(QLatin1Char('"')) && !v.contains(QLatin1Char('\n')))
+        return v;
+    return QLatin1Char('"') + QString(v).replace(QLatin1String("\""), QLatin1String("\"\"")) + QLatin1Char('"');
+}
+
+/// WHERE clause + bind values for a query.
+QString at_where(const AuditQuery& q, QVariantList& params) {
+    QStringList clauses;
+    if (q.from_ms > 0) {
+        clauses << "ts >= ?";
+        params << q.from_ms;
+    }
+    if (q.to_ms > 0) {
+        clauses << "ts <= ?";
+        params << q.to_ms;
+    }
+    if (!q.categories.isEmpty()) {
+        QStringList marks;
+        for (const auto& c : q.categories) {
+            marks << "?";
+            params << c;
+        }
+        clauses << QString("category IN (%1)").arg(marks.join(", "));
+    }
+    if (!q.action.isEmpty()) {
+        clauses << "action = ?";
+        params << q.action;
+    }
+    if (!q.subject.isEmpty()) {
+        clauses << "subject = ?";
+        params << q.subject;
+    }
+    if (!q.session_id.isEmpty()) {
+        clauses << "session_id = ?";
+        params << q.session_id;
+    }
+    return clauses.isEmpty() ? QString() : " WHERE " + clauses.join(" AND ");
+}
+
+AuditRecord at_row(QSqlQuery& q) {
+    AuditRecord r;
+    r.id = q.value(0).toLongLong();
+    r.ts = q.value(1).toLongLong();
+    r.session_id = q.value(2).toString();
+    r.category = q.value(3).toString();
+    r.action = q.value(4).toString();
+    r.subject = q.value(5).toString();
+    r.detail = QJsonDocument::fromJson(q.value(6).toString().toUtf8()).object();
+    r.prev_hash = q.value(7).toString();
+    r.hash = q.value(8).toString();
+    return r;
+}
+
+constexpr const char* kAtColumns = "id, ts, session_id, category, action, subject, detail, prev_hash, hash";
+
+} // namespace
+
+QJsonObject AuditRecord::to_json() const {
+    return QJsonObject{{"id", id},
+                       {"ts", QDateTime::fromMSecsSinceEpoch(ts).toUTC().toString(Qt::ISODateWithMs)},
+                       {"session_id", session_id},
+                       {"category", category},
+                       {"action", action},
+                       {"subject", subject},
+                       {"detail", detail},
+                       {"hash", hash}};
+}
+
+AuditQuery AuditQuery::from_json(const QJsonObject& o) {
+    AuditQuery q;
+    const int days = o["days"].toInt(0);
+    if (days > 0)
+        q.from_ms = QDateTime::currentMSecsSinceEpoch() - qint64(days) * 24 * 3600 * 1000;
+    if (o.contains("from"))
+        q.from_ms = QDateTime::fromString(o["from"].toString(), Qt::ISODate).toMSecsSinceEpoch();
+    if (o.contains("to"))
+        q.to_ms = QDateTime::fromString(o["to"].toString(), Qt::ISODate).toMSecsSinceEpoch();
+    for (const auto& c : o["categories"].toArray())
+        q.categories << c.toString();
+    q.action = o["action"].toString();
+    q.subject = o["subject"].toString();
+    q.session_id = o["session_id"].toString();
+    q.limit = std::clamp(o["limit"].toInt(200), 1, 5000);
+    return q;
+}
+
+QJsonObject AuditVerifyResult::to_json() const {
+    QJsonObject o{{"ok", ok}, {"checked", checked}};
+    if (!ok) {
+        o["first_bad_id"] = first_bad_id;
+        o["problem"] = problem;
+    }
+    return o;
+}
+
+AuditTrail& AuditTrail::instance() {
+    static AuditTrail s;
+    return s;
+}
+
+AuditTrail::AuditTrail() : session_id_(QUuid::createUuid().toString(QUuid::WithoutBraces)) {}
+
+// ── Recording ────────────────────────────────────────────────────────────────
+
+void AuditTrail::record(const QString& category, const QString& action, const QString& subject,
+                        const QJsonObject& detail) {
+    auto& db = Database::instance();
+    if (!db.is_open()) {
+        LOG_ERROR(kAuditTag, QString("DB unavailable while recording %1/%2 — audit gap").arg(category, action));
+        return;
+    }
+
+    QMutexLocker lock(&mutex_);
+    if (last_hash_.isEmpty()) {
+        auto tail = db.execute("SELECT hash FROM audit_trail ORDER BY id DESC LIMIT 1");
+        if (tail.is_err()) {
+            LOG_ERROR(kAuditTag, "Cannot read chain tail: " + QString::fromStdString(tail.error()));
+            return;
+        }
+        last_hash_ = tail.value().next() ? tail.value().value(0).toString() : kAtGenesisHash;
+    }
+
+    const qint64 ts = QDateTime::currentMSecsSinceEpoch();
+    const QString detail_json = QString::fromUtf8(QJsonDocument(detail).toJson(QJsonDocument::Compact));
+    const QString hash = at_hash(last_hash_, ts, session_id_, category, action, subject, detail_json);
+    auto r = db.execute("INSERT INTO audit_trail (ts, session_id, category, action, subject, detail, prev_hash, hash) "
+                        "VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+                        {ts, session_id_, category, action, subject, detail_json, last_hash_, hash});
+    if (r.is_err()) {
+        LOG_ERROR(kAuditTag,
+                  QString("Failed to record %1/%2: %3").arg(category, action, QString::fromStdString(r.error())));
+        last_hash_.clear(); // re-read the tail next time rather than trust the cache
+        return;
+    }
+    last_hash_ = hash;
+}
+
+void AuditTrail::install() {
+    if (installed_)
+        return;
+    installed_ = true;
+
+    // Trading events: the payload is the audit detail as-is.
+    auto& bus = EventBus::instance();
+    const QList<QPair<const char*, const char*>> order_events = {
+        {trading::events::kOrderPlaced, "order_placed"},
+        {trading::events::kOrderFailed, "order_failed"},
+        {trading::events::kAllOrdersCancelled, "all_orders_cancelled"},
+        {trading::events::kAllPositionsClosed, "all_positions_closed"},
+        {trading::events::kBasketCompleted, "basket_completed"},
+        {trading::events::kSplitCompleted, "split_completed"},
+    };
+    for (const auto& [event, action] : order_events) {
+        const QString name = QString::fromLatin1(action);
+        bus.subscribe(QString::fromLatin1(event), [this, name](const QVariantMap& d) {
+            record("order", name, d.value("account_id").toString(), QJsonObject::fromVariantMap(d));
+        });
+    }
+
+    // Algo engine (signals arrive queued from the engine thread).
+    auto& engine = algo::AlgoEngine::instance();
+    connect(&engine, &algo::AlgoEngine::trade_executed, this, [this](const algo::AlgoTradeRecord& t) {
+        record("order", "algo_fill", t.deployment_id,
+               {{"trade_id", t.id},
+                {"symbol", t.leg_symbol.isEmpty() ? t.symbol : t.leg_symbol},
+                {"side", t.side},
+                {"quantity", t.quantity},
+                {"price", t.price},
+                {"reason", t.reason},
+                {"broker_order_id", t.broker_order_id}});
+    });
+    connect(&engine, &algo::AlgoEngine::deployment_started, this,
+            [this](const QString& id) { record("deployment", "started", id); });
+    connect(&engine, &algo::AlgoEngine::deployment_stopped, this,
+            [this](const QString& id) { record("deployment", "stopped", id); });
+    connect(&engine, &algo::AlgoEngine::deployment_crashed, this, [this](const QString& id, const QString& reason) {
+        record("deployment", "crashed", id, {{"reason", reason}});
+    });
+    connect(&engine, &algo::AlgoEngine::strategy_reloaded, this,
+            [this](const QString& id, bool ok, const QString& message) {
+                record("deployment", "strategy_reloaded", id, {{"ok", ok}, {"message", message}});
+            });
+    connect(&engine, &algo::AlgoEngine::recovery_resolved, this,
+            [this](const QString& id, const QString& action, bool ok, const QString& message) {
+                record(action == QLatin1String("flatten") ? "destructive" : "deployment", "crash_recovery", id,
+                       {{"action", action}, {"ok", ok}, {"message", message}});
+            });
+    connect(&engine, &algo::AlgoEngine::kill_switch_changed, this, [this](bool engaged, const QString& reason) {
+        record("destructive", engaged ? "kill_switch_engaged" : "kill_switch_reset", {}, {{"reason", reason}});
+    });
+
+    // Broker accounts and their credentials.
+    auto& accounts = trading::AccountManager::instance();
+    auto account_detail = [](const QString& id) {
+        const auto a = trading::AccountManager::instance().get_account(id);
+        return QJsonObject{{"broker", a.broker_id}, {"name", a.display_name}, {"mode", a.trading_mode}};
+    };
+    connect(&accounts, &trading::AccountManager::account_added, this,
+            [this, account_detail](const QString& id) { record("auth", "account_added", id, account_detail(id)); });
+    connect(&accounts, &trading::AccountManager::account_removed, this,
+            [this](const QString& id) { record("destructive", "account_removed", id); });
+    connect(&accounts, &trading::AccountManager::credentials_changed, this, [this, account_detail](const QString& id) {
+        record("auth", "credentials_changed", id, account_detail(id));
+    });
+    connect(&accounts, &trading::AccountManager::connection_state_changed, this,
+            [this](const QString& id, trading::ConnectionState state) {
+                if (state == trading::ConnectionState::TokenExpired)
+                    record("auth", "token_expired", id);
+            });
+
+    LOG_INFO(kAuditTag, "Audit trail installed, session " + session_id_);
+}
+
+// ── Reading ──────────────────────────────────────────────────────────────────
+
+Result<QVector<AuditRecord>> AuditTrail::query(const AuditQuery& q) const {
+    auto& db = Database::instance();
+    if (!db.is_open())
+        return Result<QVector<AuditRecord>>::err("Database not open");
+    QVariantList params;
+    const QString sql =
+        QString("SELECT %1 FROM audit_trail%2 ORDER BY id DESC LIMIT ?").arg(kAtColumns, at_where(q, params));
+    params << q.limit;
+    auto r = db.execute(sql, params);
+    if (r.is_err())
+        return Result<QVector<AuditRecord>>::err(r.error());
+    QVector<AuditRecord> out;
+    while (r.value().next())
+        out.append(at_row(r.value()));
+    return Result<QVector<AuditRecord>>::ok(out);
+}
+
+Result<int> AuditTrail::export_to(const AuditQuery& q, const QString& path) const {
+    auto& db = Database::instance();
+    if (!db.is_open())
+        return Result<int>::err("Database not open");
+    QVariantList params;
+    auto r = db.execute(QString("SELECT %1 FROM audit_trail%2 ORDER BY id").arg(kAtColumns, at_where(q, params)),
+                        params);
+    if (r.is_err())
+        return Result<int>::err(r.error());
+
+    QDir().mkpath(QFileInfo(path).absolutePath());
+    QSaveFile f(path);
+    if (!f.open(QIODevice::WriteOnly | QIODevice::Text))
+        return Result<int>::err(("Cannot write " + path).toStdString());
+
+    const QString suffix = QFileInfo(path).suffix().toLower();
+    const bool json_lines = suffix == "jsonl" || suffix == "json";
+    if (!json_lines)
+        f.write("id,ts,session_id,category,action,subject,detail,prev_hash,hash\n");
+    int rows = 0;
+    while (r.value().next()) {
+        const AuditRecord rec = at_row(r.value());
+        if (json_lines) {
+            QJsonObject o = rec.to_json();
+            o["prev_hash"] = rec.prev_hash;
+            f.write(QJsonDocument(o).toJson(QJsonDocument::Compact) + '\n');
+        } else {
+            // Detail exactly as stored, so the exported rows re-hash.
+            const QStringList cols = {QString::number(rec.id),
+                                      QString::number(rec.ts),
+                                      rec.session_id,
+                                      rec.category,
+                                      rec.action,
+                                      rec.subject,
+                                      r.value().value(6).toString(),
+                                      rec.prev_hash,
+                                      rec.hash};
+            QStringList escaped;
+            for (const auto& c : cols)
+                escaped << at_csv(c);
+            f.write((escaped.join(',') + '\n').toUtf8());
+        }
+        ++rows;
+    }
+    if (!f.commit())
+        return Result<int>::err(("Cannot write " + path).toStdString());
+    LOG_INFO(kAuditTag, QString("Exported %1 audit rows to %2").arg(rows).arg(path));
+    return Result<int>::ok(rows);
+}
+
+AuditVerifyResult AuditTrail::verify() const {
+    AuditVerifyResult out;
+    auto& db = Database::instance();
+    if (!db.is_open()) {
+        out.ok = false;
+        out.problem = "Database not open";
+        return out;
+    }
+    auto r = db.execute(QString("SELECT %1 FROM audit_trail ORDER BY id").arg(kAtColumns));
+    if (r.is_err()) {
+        out.ok = false;
+        out.problem = QString::fromStdString(r.error());
+        return out;
+    }
+    QString prev = kAtGenesisHash;
+    auto& q = r.value();
+    while (q.next()) {
+        const qint64 id = q.value(0).toLongLong();
+        const QString expected = at_hash(prev, q.value(1).toLongLong(), q.value(2).toString(), q.value(3).toString(),
+                                         q.value(4).toString(), q.value(5).toString(), q.value(6).toString());
+        QString problem;
+        if (q.value(7).toString() != prev)
+            problem = "prev_hash does not match the preceding row (row removed or reordered)";
+        else if (q.value(8).toString() != expected)
+            problem = "hash does not match the row's content (row edited)";
+        if (!problem.isEmpty()) {
+            out.ok = false;
+            out.first_bad_id = id;
+            out.problem = problem;
+            LOG_WARN(kAuditTag, QString("Chain broken at row %1: %2").arg(id).arg(problem));
+            return out;
+        }
+        prev = q.value(8).toString();
+        ++out.checked;
+    }
+    return out;
+}
+
+} // namespace fincept::services::audit
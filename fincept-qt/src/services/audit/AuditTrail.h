@@ -0,0 +1,106 @@
+// src/services/audit/AuditTrail.h
+#pragma once
+#include "core/result/Result.h"
+
+#include <QJsonObject>
+#include <QMutex>
+#include <QObject>
+#include <QString>
+#include <QVector>
+
+namespace fincept::services::audit {
+
+/// One row of the `audit_trail` table (migration v059).
+struct AuditRecord {
+    qint64 id = 0;
+    qint64 ts = 0; // epoch ms
+    QString session_id;
+    QString category; // order | auth | deployment | destructive
+    QString action;
+    QString subject; // account / order / deployment / portfolio id
+    QJsonObject detail;
+    QString prev_hash;
+    QString hash;
+
+    QJsonObject to_json() const;
+};
+
+/// Which rows a query or export covers. Empty filters match everything.
+struct AuditQuery {
+    qint64 from_ms = 0;
+    qint64 to_ms = 0; // 0 = now
+    QStringList categories;
+    QString action;
+    QString subject;
+    QString session_id;
+    int limit = 200; // newest first; export ignores it
+
+    static AuditQuery from_json(const QJsonObject& o);
+};
+
+/// Outcome of walking the hash chain.
+struct AuditVerifyResult {
+    bool ok = true;
+    int checked = 0;
+    qint64 first_bad_id = 0; // 0 when ok
+    QString problem;
+
+    QJsonObject to_json() const;
+};
+
+/// Append-only audit log of the actions a compliance reviewer cares about:
+///
+///   order       — placements, failures, cancels, modifies, bulk cancel /
+///                 close, baskets and splits (UnifiedTrading), algo fills
+///   auth        — broker account added / removed, credentials stored or
+///                 refreshed, token expiry
+///   deployment  — algo deployment start / stop / crash / strategy reload
+///   destructive — kill switch, deployment and paper portfolio deletion,
+///                 paper portfolio reset, and every call of an MCP tool
+///                 flagged is_destructive (refused and failed ones too;
+///                 credential args redacted)
+///
+/// Every row hashes the previous row's hash together with its own content,
+/// so verify() detects rows edited or removed outside the app; the table's
+/// triggers refuse UPDATE and DELETE from inside it. `session_id` ties rows
+/// to one run of the terminal.
+///
+/// record() is thread-safe (engine and worker threads record directly).
+/// install() wires the event sources; call once after Database::open().
+class AuditTrail : public QObject {
+    Q_OBJECT
+  public:
+    static AuditTrail& instance();
+
+    /// Subscribes to trading EventBus events and the AlgoEngine /
+    /// AccountManager signals. Idempotent.
+    void install();
+
+    /// Appends one row. Silent on DB errors (logged) — failing to audit must
+    /// not fail the action being audited.
+    void record(const QString& category, const QString& action, const QString& subject = {},
+                const QJsonObject& detail = {});
+
+    Result<QVector<AuditRecord>> query(const AuditQuery& q) const;
+
+    /// Writes every row matching `q` (oldest first) to `path` as CSV, or as
+    /// one JSON object per line for a `.jsonl` / `.json` path. Returns the
+    /// row count.
+    Result<int> export_to(const AuditQuery& q, const QString& path) const;
+
+    /// Recomputes the chain from the first row.
+    AuditVerifyResult verify() const;
+
+    QString session_id() const { return session_id_; }
+
+  private:
+    AuditTrail();
+    Q_DISABLE_COPY(AuditTrail)
+
+    bool installed_ = false;
+    QString session_id_;
+    QMutex mutex_;      // serialises appends so the chain stays linear
+    QString last_hash_; // cached tail of the chain; empty = not loaded yet
+};
+
+} // namespace fincept::services::audit
@@ -0,0 +1,631 @@
+// src/services/backtesting/FactorBacktest.cpp
+#include "services/backtesting/FactorBacktest.h"
+
+#include "core/logging/Logger.h"
+#include "python/PythonRunner.h"
+#include "services/watchlist/WatchlistExpression.h"
+#include "storage/repositories/PitFundamentalsRepository.h"
+
+#include <QDateTime>
+#include <QJsonDocument>
+#include <QPointer>
+#include <QTimeZone>
+#include <QTimer>
+#include <QUuid>
+#include <QtConcurrent>
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+#include <numeric>
+
+namespace fincept::services::backtest {
+
+namespace {
+
+using watchlist::WatchlistExpression;
+
+constexpr const char* kFbTag = "FactorBacktest";
+constexpr double kTradingDays = 252;
+// Term z-scores are clipped here so one outlier cannot own the composite.
+constexpr double kZClip = 3;
+
+// Priced at each rebalance close from the point-in-time fundamentals.
+const QStringList kValuationVars = {"market_cap", "pe", "pb", "ps", "earnings_yield", "fcf_yield", "dividend_yield"};
+
+struct Prices {
+    QVector<algo::OhlcvCandle> bars;
+    QVector<QDate> dates;
+    QVector<double> split_factor; // close × split_factor = the price as it traded
+};
+
+struct Input {
+    FactorSpec spec;
+    QString output; // yfinance_data.py batch_history
+    QVector<PitFact> filings;
+    bool fundamentals = false;
+};
+
+double nan() {
+    return std::numeric_limits<double>::quiet_NaN();
+}
+
+double round_to(double v, int digits) {
+    if (!std::isfinite(v))
+        return v;
+    const double f = std::pow(10.0, digits);
+    return std::round(v * f) / f;
+}
+
+// Percent, two decimals; NaN becomes JSON null.
+QJsonValue pct(double fraction) {
+    return std::isfinite(fraction) ? QJsonValue(round_to(fraction * 100, 2)) : QJsonValue();
+}
+
+QJsonValue num(double v, int digits = 4) {
+    return std::isfinite(v) ? QJsonValue(round_to(v, digits)) : QJsonValue();
+}
+
+// Average ranks, ties sharing the mean rank.
+QVector<double> ranks(const QVector<double>& v) {
+    QVector<int> idx(v.size());
+    std::iota(idx.begin(), idx.end(), 0);
+    std::sort(idx.begin(), idx.end(), [&](int a, int b) { return v[a] < v[b]; });
+    QVector<double> r(v.size());
+    for (int i = 0; i < idx.size();) {
+        int j = i;
+        while (j + 1 < idx.size() && v[idx[j + 1]] == v[idx[i]])
+            ++j;
+        for (int k = i; k <= j; ++k)
+            r[idx[k]] = (i + j) / 2.0;
+        i = j + 1;
+    }
+    return r;
+}
+
+double pearson(const QVector<double>& a, const QVector<double>& b) {
+    const int n = static_cast<int>(a.size());
+    if (n < 3)
+        return nan();
+    const double ma = std::accumulate(a.begin(), a.end(), 0.0) / n;
+    const double mb = std::accumulate(b.begin(), b.end(), 0.0) / n;
+    double cov = 0, va = 0, vb = 0;
+    for (int i = 0; i < n; ++i) {
+        cov += (a[i] - ma) * (b[i] - mb);
+        va += (a[i] - ma) * (a[i] - ma);
+        vb += (b[i] - mb) * (b[i] - mb);
+    }
+    return va > 0 && vb > 0 ? cov / std::sqrt(va * vb) : nan();
+}
+
+double spearman(const QVector<double>& a, const QVector<double>& b) {
+    return pearson(ranks(a), ranks(b));
+}
+
+struct Moments {
+    double mean = nan();
+    double stdev = nan(); // sample
+    int n = 0;
+};
+
+Moments moments(const QVector<double>& v) {
+    Moments m;
+    double sum = 0;
+    for (double x : v) {
+        if (std::isfinite(x)) {
+            sum += x;
+            ++m.n;
+        }
+    }
+    if (m.n == 0)
+        return m;
+    m.mean = sum / m.n;
+    if (m.n < 2)
+        return m;
+    double ss = 0;
+    for (double x : v) {
+        if (std::isfinite(x))
+            ss += (x - m.mean) * (x - m.mean);
+    }
+    m.stdev = std::sqrt(ss / (m.n - 1));
+    return m;
+}
+
+// Performance of a per-period return series; `days` is each period's length
+// in trading days.
+QJsonObject performance(const QVector<double>& returns, const QVector<int>& days) {
+    double equity = 1, peak = 1, max_dd = 0;
+    int total_days = 0, wins = 0;
+    for (int i = 0; i < returns.size(); ++i) {
+        equity *= 1 + returns[i];
+        peak = std::max(peak, equity);
+        max_dd = std::min(max_dd, equity / peak - 1);
+        total_days += days[i];
+        if (returns[i] > 0)
+            ++wins;
+    }
+    const Moments m = moments(returns);
+    const double periods_per_year = returns.isEmpty() ? 0 : kTradingDays * returns.size() / std::max(1, total_days);
+    const double years = total_days / kTradingDays;
+    const double ann = years > 0 && equity > 0 ? std::pow(equity, 1 / years) - 1 : nan();
+    const double vol = m.stdev * std::sqrt(periods_per_year);
+    return QJsonObject{
+        {"total_return", pct(equity - 1)},
+        {"annual_return", pct(ann)},
+        {"annual_volatility", pct(vol)},
+        {"sharpe", num(m.stdev > 0 ? m.mean / m.stdev * std::sqrt(periods_per_year) : nan(), 3)},
+        {"max_drawdown", pct(max_dd)},
+        {"hit_rate", pct(returns.isEmpty() ? nan() : double(wins) / returns.size())},
+        {"periods", static_cast<int>(returns.size())},
+    };
+}
+
+QJsonObject ic_summary(const QVector<double>& ics) {
+    const Moments m = moments(ics);
+    int positive = 0;
+    for (double x : ics) {
+        if (std::isfinite(x) && x > 0)
+            ++positive;
+    }
+    const bool spread = m.stdev > 0;
+    return QJsonObject{
+        {"mean", num(m.mean)},
+        {"stdev", num(m.stdev)},
+        {"ir", num(spread ? m.mean / m.stdev : nan(), 3)},
+        {"t_stat", num(spread ? m.mean / m.stdev * std::sqrt(double(m.n)) : nan(), 3)},
+        {"hit_rate", pct(m.n > 0 ? double(positive) / m.n : nan())},
+        {"periods", m.n},
+    };
+}
+
+// Valuation ratios at `price` (as traded) from the snapshot fields already in
+// `vars`; each needs shares and a positive denominator.
+void add_valuation(QHash<QString, double>& vars, double price) {
+    const double shares = vars.value("shares_diluted", nan());
+    if (!(shares > 0) || !(price > 0))
+        return;
+    const double cap = price * shares;
+    vars["market_cap"] = cap;
+    const double ni = vars.value("net_income", nan());
+    const double equity = vars.value("total_equity", nan());
+    const double revenue = vars.value("revenue", nan());
+    if (ni > 0)
+        vars["pe"] = cap / ni;
+    if (equity > 0)
+        vars["pb"] = cap / equity;
+    if (revenue > 0)
+        vars["ps"] = cap / revenue;
+    if (std::isfinite(ni))
+        vars["earnings_yield"] = ni / cap * 100;
+    if (vars.contains("free_cash_flow"))
+        vars["fcf_yield"] = vars.value("free_cash_flow") / cap * 100;
+    if (vars.contains("dividends_paid"))
+        vars["dividend_yield"] = vars.value("dividends_paid") / cap * 100;
+}
+
+QHash<QString, Prices> parse_prices(const QJsonObject& data) {
+    QHash<QString, Prices> out;
+    for (auto it = data.constBegin(); it != data.constEnd(); ++it) {
+        Prices p;
+        for (const auto& v : it.value().toArray()) {
+            const QJsonArray row = v.toArray();
+            const QDate d = QDate::fromString(row.at(0).toString(), Qt::ISODate);
+            const double close = row.at(4).toDouble();
+            if (!d.isValid() || !(close > 0))
+                continue;
+            algo::OhlcvCandle c;
+            c.open_time = d.startOfDay(QTimeZone::UTC).toMSecsSinceEpoch();
+            c.close_time = c.open_time + 24LL * 3600 * 1000 - 1;
+            c.open = row.at(1).toDouble();
+            c.high = row.at(2).toDouble();
+            c.low = row.at(3).toDouble();
+            c.close = close;
+            c.volume = row.at(5).toDouble();
+            c.is_closed = true;
+            p.bars.append(c);
+            p.dates.append(d);
+            p.split_factor.append(row.size() > 6 ? row.at(6).toDouble(1) : 1);
+        }
+        if (!p.bars.isEmpty())
+            out.insert(it.key(), std::move(p));
+    }
+    return out;
+}
+
+Result<QJsonObject> compute(const Input& in) {
+    using R = Result<QJsonObject>;
+    const FactorSpec& spec = in.spec;
+    const QJsonObject o = QJsonDocument::fromJson(python::extract_json(in.output).toUtf8()).object();
+    if (!o.value("success").toBool(false)) {
+        const QString e = o.value("error").toString();
+        return R::err(e.isEmpty() ? "Price download produced no result" : e.toStdString());
+    }
+    const QHash<QString, Prices> prices = parse_prices(o.value("data").toObject());
+    if (prices.size() < spec.buckets) {
+        const QString msg =
+            QString("Only %1 symbols have prices; need at least %2").arg(prices.size()).arg(spec.buckets);
+        return R::err(msg.toStdString());
+    }
+
+    QVector<WatchlistExpression> exprs;
+    int lookback = 1;
+    for (const FactorTerm& t : spec.terms) {
+        QString error;
+        exprs.append(WatchlistExpression::compile(t.expression, &error, FactorBacktest::variables()));
+        lookback = std::max(lookback, exprs.last().lookback_bars());
+    }
+
+    // Trading calendar: every date any symbol traded, up to `end`.
+    QVector<QDate> calendar;
+    for (const Prices& p : prices) {
+        for (const QDate& d : p.dates) {
+            if (d <= spec.end)
+                calendar.append(d);
+        }
+    }
+    std::sort(calendar.begin(), calendar.end());
+    calendar.erase(std::unique(calendar.begin(), calendar.end()), calendar.end());
+    const int last = static_cast<int>(calendar.size()) - 1;
+    int first = 0;
+    while (first <= last && calendar[first] < spec.start)
+        ++first;
+    QVector<int> rebalances;
+    for (int t = first; t < last; t += spec.rebalance_days)
+        rebalances.append(t);
+    if (rebalances.isEmpty())
+        return R::err("No trading days between start and end to rebalance on");
+
+    // Per symbol, the index of its bar on or before each calendar day (-1 before the first).
+    QStringList symbols = prices.keys();
+    symbols.sort();
+    QHash<QString, QVector<int>> at;
+    for (const QString& s : symbols) {
+        const Prices& p = *prices.constFind(s);
+        QVector<int> idx(calendar.size(), -1);
+        int i = -1;
+        for (int t = 0; t <= last; ++t) {
+            while (i + 1 < p.dates.size() && p.dates[i + 1] <= calendar[t])
+                ++i;
+            idx[t] = i;
+        }
+        at.insert(s, std::move(idx));
+    }
+
+    QVector<QHash<QString, PitSnapshot>> snaps;
+    if (in.fundamentals) {
+        QVector<QDate> known;
+        for (int t : rebalances)
+            known.append(calendar[t].addDays(-spec.fundamentals_lag_days));
+        snaps = PitFundamentals::snapshots(in.filings, known);
+    }
+
+    const int nb = spec.buckets;
+    const int nt = static_cast<int>(spec.terms.size());
+    QVector<QVector<double>> bucket_net(nb);
+    QVector<QVector<double>> bucket_turnover(nb); // rebalances after the initial build
+    QVector<double> bucket_names(nb, 0);
+    QVector<QHash<QString, double>> held(nb); // weights drifted to the end of the last period
+    QVector<double> long_short, benchmark, ic;
+    QVector<QVector<double>> term_ic(nt);
+    QVector<int> days;
+    QJsonArray periods, skipped;
+    QJsonObject latest;
+    bool built = false;
+
+    for (int k = 0; k < rebalances.size(); ++k) {
+        const int t = rebalances[k];
+        const int t_next = std::min(t + spec.rebalance_days, last);
+        const QDate date = calendar[t];
+
+        QStringList names;
+        QVector<QVector<double>> values(nt);
+        QVector<double> fwd;
+        QHash<QString, double> fwd_of;
+        for (const QString& s : symbols) {
+            const Prices& p = *prices.constFind(s);
+            const QVector<int>& pos = *at.constFind(s);
+            const int i = pos[t];
+            if (i < 0 || p.dates[i] != date)
+                continue;
+            const int j = pos[t_next];
+            const double r = p.bars[j].close / p.bars[i].close - 1;
+
+            QHash<QString, double> vars;
+            if (in.fundamentals) {
+                const auto snap = snaps[k].constFind(s);
+                if (snap != snaps[k].constEnd()) {
+                    const QJsonObject& f = snap->fields;
+                    for (auto it = f.constBegin(); it != f.constEnd(); ++it)
+                        vars.insert(it.key(), it.value().toDouble());
+                    add_valuation(vars, p.bars[i].close * p.split_factor[i]);
+                }
+            }
+            const int from = std::max(0, i + 1 - lookback);
+            const QVector<algo::OhlcvCandle> slice = p.bars.mid(from, i + 1 - from);
+            QVector<double> row;
+            for (const WatchlistExpression& e : exprs) {
+                const double v = e.evaluate(slice, nullptr, vars);
+                if (!std::isfinite(v))
+                    break;
+                row.append(v);
+            }
+            if (row.size() != nt)
+                continue;
+            names.append(s);
+            for (int m = 0; m < nt; ++m)
+                values[m].append(row[m]);
+            fwd.append(r);
+            fwd_of.insert(s, r);
+        }
+
+        const int n = static_cast<int>(names.size());
+        if (n < nb) {
+            skipped.append(QJsonObject{{"date", date.toString(Qt::ISODate)}, {"ranked", n}});
+            continue;
+        }
+
+        QVector<double> score(n, 0);
+        for (int m = 0; m < nt; ++m) {
+            const Moments mo = moments(values[m]);
+            const double sign = spec.terms[m].ascending ? -1 : 1;
+            for (int i = 0; i < n; ++i) {
+                const double z = mo.stdev > 0 ? (values[m][i] - mo.mean) / mo.stdev : 0;
+                score[i] += spec.terms[m].weight * sign * std::clamp(z, -kZClip, kZClip);
+            }
+            QVector<double> signed_values = values[m];
+            for (double& v : signed_values)
+                v *= sign;
+            term_ic[m].append(spearman(signed_values, fwd));
+        }
+        const double period_ic = spearman(score, fwd);
+        ic.append(period_ic);
+
+        QVector<int> order(n);
+        std::iota(order.begin(), order.end(), 0);
+        std::sort(order.begin(), order.end(), [&](int a, int b) {
+            return score[a] != score[b] ? score[a] < score[b] : names[a] < names[b];
+        });
+        QVector<QStringList> members(nb);
+        for (int rank = 0; rank < n; ++rank)
+            members[static_cast<int>(static_cast<qint64>(rank) * nb / n)].append(names[order[rank]]);
+
+        QJsonArray row_returns, row_turnover;
+        QVector<double> net(nb);
+        for (int b = 0; b < nb; ++b) {
+            const QStringList& m = members[b];
+            const double w = 1.0 / m.size();
+            double gross = 0;
+            for (const QString& s : m)
+                gross += fwd_of[s] * w;
+            // Σ|Δw| against the drifted book; the first build trades the whole bucket once.
+            double traded = 0;
+            QHash<QString, double> prev = held[b];
+            for (const QString& s : m)
+                traded += std::abs(w - prev.take(s));
+            for (double v : prev)
+                traded += std::abs(v);
+            net[b] = gross - traded * spec.cost_bps / 1e4;
+            bucket_net[b].append(net[b]);
+            bucket_names[b] += m.size();
+            if (built)
+                bucket_turnover[b].append(traded / 2);
+            row_returns.append(pct(net[b]));
+            row_turnover.append(pct(traded / 2));
+
+            QHash<QString, double> drifted;
+            for (const QString& s : m)
+                drifted.insert(s, w * (1 + fwd_of[s]) / (1 + gross));
+            held[b] = std::move(drifted);
+        }
+        built = true;
+
+        const double bench = std::accumulate(fwd.begin(), fwd.end(), 0.0) / n;
+        benchmark.append(bench);
+        long_short.append(net[nb - 1] - net[0]);
+        days.append(t_next - t);
+        periods.append(QJsonObject{
+            {"date", date.toString(Qt::ISODate)},
+            {"end_date", calendar[t_next].toString(Qt::ISODate)},
+            {"ranked", n},
+            {"bucket_returns", row_returns},
+            {"long_short", pct(long_short.last())},
+            {"benchmark", pct(bench)},
+            {"ic", num(period_ic)},
+            {"turnover", row_turnover},
+        });
+        latest = QJsonObject{{"date", date.toString(Qt::ISODate)},
+                             {"top", QJsonArray::fromStringList(members[nb - 1])},
+                             {"bottom", QJsonArray::fromStringList(members[0])}};
+    }
+    if (days.isEmpty()) {
+        const QString msg = QString("No rebalance had %1 rankable symbols — check the expressions, history length "
+                                    "and fundamentals coverage")
+                                .arg(nb);
+        return R::err(msg.toStdString());
+    }
+
+    QJsonArray buckets;
+    QVector<double> bucket_means;
+    for (int b = 0; b < nb; ++b) {
+        QJsonObject perf = performance(bucket_net[b], days);
+        perf["bucket"] = b + 1;
+        perf["avg_names"] = num(bucket_names[b] / days.size(), 1);
+        perf["avg_turnover"] = pct(moments(bucket_turnover[b]).mean);
+        buckets.append(perf);
+        bucket_means.append(moments(bucket_net[b]).mean);
+    }
+    QVector<double> bucket_index(nb);
+    std::iota(bucket_index.begin(), bucket_index.end(), 1.0);
+
+    QJsonArray terms;
+    for (int m = 0; m < nt; ++m) {
+        const FactorTerm& t = spec.terms[m];
+        terms.append(QJsonObject{{"name", t.name},
+                                 {"expression", t.expression},
+                                 {"weight", t.weight},
+                                 {"ascending", t.ascending},
+                                 {"ic", ic_summary(term_ic[m])}});
+    }
+
+    return R::ok(QJsonObject{
+        {"start", periods.first().toObject().value("date")},
+        {"end", periods.last().toObject().value("end_date")},
+        {"universe_size", static_cast<int>(spec.universe.size())},
+        {"priced", static_cast<int>(prices.size())},
+        {"failed", o.value("failed").toArray()},
+        {"buckets_count", nb},
+        {"rebalance_days", spec.rebalance_days},
+        {"cost_bps", spec.cost_bps},
+        {"terms", terms},
+        {"buckets", buckets},
+        {"long_short", performance(long_short, days)},
+        {"benchmark", performance(benchmark, days)},
+        {"ic", ic_summary(ic)},
+        {"monotonicity", num(spearman(bucket_index, bucket_means), 3)},
+        {"periods", periods},
+        {"skipped", skipped},
+        {"latest", latest},
+    });
+}
+
+} // namespace
+
+FactorBacktest& FactorBacktest::instance() {
+    static FactorBacktest s;
+    return s;
+}
+
+const QStringList& FactorBacktest::variables() {
+    static const QStringList v = [] {
+        QStringList out = PitFundamentals::metrics();
+        for (const QString& f : QStringList{"free_cash_flow"} + PitFundamentals::screener_fields() + kValuationVars) {
+            if (!out.contains(f))
+                out.append(f);
+        }
+        return out;
+    }();
+    return v;
+}
+
+Result<FactorSpec> FactorBacktest::spec_from_json(const QJsonObject& o) {
+    using R = Result<FactorSpec>;
+    FactorSpec spec;
+    for (const auto& v : o.value("universe").toArray()) {
+        const QString s = v.toString().trimmed().toUpper();
+        if (!s.isEmpty() && !spec.universe.contains(s))
+            spec.universe.append(s);
+    }
+    for (const auto& v : o.value("factors").toArray()) {
+        const QJsonObject f = v.toObject();
+        FactorTerm t;
+        t.expression = f.value("expression").toString().trimmed();
+        t.name = f.value("name").toString().trimmed();
+        if (t.name.isEmpty())
+            t.name = t.expression;
+        t.weight = f.value("weight").toDouble(1);
+        t.ascending = f.value("ascending").toBool(false);
+        spec.terms.append(t);
+    }
+    spec.end = o.contains("end_date") ? QDate::fromString(o.value("end_date").toString(), Qt::ISODate)
+                                      : QDate::currentDate();
+    spec.start = o.contains("start_date") ? QDate::fromString(o.value("start_date").toString(), Qt::ISODate)
+                                          : spec.end.addYears(-5);
+    spec.buckets = o.value("buckets").toInt(spec.buckets);
+    spec.rebalance_days = o.value("rebalance_days").toInt(spec.rebalance_days);
+    spec.cost_bps = o.value("cost_bps").toDouble(spec.cost_bps);
+    spec.fundamentals_lag_days = o.value("fundamentals_lag_days").toInt(spec.fundamentals_lag_days);
+
+    if (spec.terms.isEmpty())
+        return R::err("At least one factor expression is required");
+    for (const FactorTerm& t : spec.terms) {
+        QString error;
+        if (!WatchlistExpression::compile(t.expression, &error, variables()).is_valid())
+            return R::err(QString("Factor '%1': %2").arg(t.name, error).toStdString());
+        if (!std::isfinite(t.weight) || t.weight == 0)
+            return R::err(QString("Factor '%1' needs a non-zero weight").arg(t.name).toStdString());
+    }
+    if (!spec.start.isValid() || !spec.end.isValid() || spec.start >= spec.end)
+        return R::err("start_date and end_date must be YYYY-MM-DD dates, start before end");
+    if (spec.buckets < 2 || spec.buckets > 20)
+        return R::err("buckets must be between 2 and 20");
+    if (spec.universe.size() < spec.buckets || spec.universe.size() > kMaxUniverse) {
+        const QString msg = QString("universe needs between %1 and %2 symbols").arg(spec.buckets).arg(kMaxUniverse);
+        return R::err(msg.toStdString());
+    }
+    if (spec.rebalance_days < 1 || spec.rebalance_days > 252)
+        return R::err("rebalance_days must be between 1 and 252");
+    if (spec.cost_bps < 0 || spec.fundamentals_lag_days < 0)
+        return R::err("cost_bps and fundamentals_lag_days cannot be negative");
+    return R::ok(std::move(spec));
+}
+
+QString FactorBacktest::run(const FactorSpec& spec) {
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    int lookback = 1;
+    bool fundamentals = false;
+    QString problem;
+    for (const FactorTerm& t : spec.terms) {
+        QString error;
+        const auto e = WatchlistExpression::compile(t.expression, &error, variables());
+        if (!e.is_valid() && problem.isEmpty())
+            problem = QString("Factor '%1': %2").arg(t.name, error);
+        lookback = std::max(lookback, e.lookback_bars());
+        fundamentals = fundamentals || !e.variables().isEmpty();
+    }
+    if (spec.terms.isEmpty())
+        problem = QStringLiteral("At least one factor expression is required");
+    if (!problem.isEmpty()) {
+        QTimer::singleShot(0, this, [this, request_id, problem]() { emit error_occurred(request_id, problem); });
+        return request_id;
+    }
+
+    // Enough history before the first rebalance for the longest lookback.
+    const QDate from = spec.start.addDays(-(lookback * 7 / 5 + 10));
+    QStringList args{"batch_history", from.toString(Qt::ISODate), spec.end.addDays(1).toString(Qt::ISODate)};
+    args += spec.universe;
+    LOG_INFO(kFbTag, QString("Factor backtest %1: %2 symbols, %3 terms, %4 to %5")
+                         .arg(request_id)
+                         .arg(spec.universe.size())
+                         .arg(spec.terms.size())
+                         .arg(spec.start.toString(Qt::ISODate), spec.end.toString(Qt::ISODate)));
+
+    QPointer<FactorBacktest> self = this;
+    python::PythonRunner::instance().run(
+        "yfinance_data.py", args, [self, request_id, spec, fundamentals](python::PythonResult r) {
+            if (!self)
+                return;
+            if (!r.success) {
+                emit self->error_occurred(request_id, r.error.isEmpty() ? "Price download failed" : r.error);
+                return;
+            }
+            Input in{spec, r.output, {}, fundamentals};
+            if (fundamentals) {
+                auto filings = PitFundamentalsRepository::instance().filings(spec.universe);
+                if (filings.is_err()) {
+                    emit self->error_occurred(request_id, QString::fromStdString(filings.error()));
+                    return;
+                }
+                in.filings = filings.value();
+            }
+            (void)QtConcurrent::run([self, request_id, in]() {
+                const Result<QJsonObject> res = compute(in);
+                QMetaObject::invokeMethod(
+                    self.data(),
+                    [self, request_id, res]() {
+                        if (!self)
+                            return;
+                        if (res.is_err()) {
+                            LOG_WARN(kFbTag, QString("Factor backtest %1 failed: %2")
+                                                 .arg(request_id, QString::fromStdString(res.error())));
+                            emit self->error_occurred(request_id, QString::fromStdString(res.error()));
+                            return;
+                        }
+                        emit self->finished(request_id, res.value());
+                    },
+                    Qt::QueuedConnection);
+            });
+        });
+    return request_id;
+}
+
+} // namespace fincept::services::backtest
@@ -0,0 +1,79 @@
+// src/services/backtesting/FactorBacktest.h
+#pragma once
+#include "core/result/Result.h"
+#include "services/fundamentals/PitFundamentals.h"
+
+#include <QDate>
+#include <QHash>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+namespace fincept::services::backtest {
+
+/// One ranking input. The composite score is Σ weight × cross-sectional
+/// z-score of each term, sign-flipped for `ascending` terms (lower is
+/// better, e.g. pe).
+struct FactorTerm {
+    QString name;       // output label; defaults to the expression
+    QString expression; // WatchlistExpression over daily bars + FactorBacktest::variables()
+    double weight = 1;
+    bool ascending = false;
+};
+
+struct FactorSpec {
+    QStringList universe;
+    QVector<FactorTerm> terms;
+    QDate start;                   // first rebalance on or after this date
+    QDate end;                     // last price used
+    int buckets = 10;              // deciles by default; the last bucket holds the highest scores
+    int rebalance_days = 21;       // trading days between rebalances
+    double cost_bps = 0;           // charged on the traded fraction at every rebalance
+    int fundamentals_lag_days = 1; // filings must predate the rebalance by this many days
+};
+
+/// Cross-sectional factor backtest: at every rebalance the universe is ranked
+/// on a composite of expressions — momentum, value, quality or any blend —
+/// and split into equal-weight bucket portfolios held to the next rebalance.
+///
+/// Prices come from one yfinance batch download (split- and
+/// dividend-adjusted). Expressions read those bars like a watchlist column and
+/// may use point-in-time fundamentals (PitFundamentals, ingest first) plus
+/// valuation ratios priced at the rebalance close, so nothing filed after the
+/// rebalance leaks in. The universe is today's symbol list — names delisted
+/// since are missing, which flatters the results.
+///
+/// A symbol is ranked on a date when it traded that day and every term
+/// evaluates. Returns per-period bucket returns, per-bucket performance,
+/// turnover and costs, the top-minus-bottom spread, an equal-weight universe
+/// benchmark and Spearman information coefficients of the composite and each
+/// term against next-period returns. Main thread only; the computation itself
+/// runs on a worker thread.
+class FactorBacktest : public QObject {
+    Q_OBJECT
+  public:
+    static constexpr int kMaxUniverse = 1000;
+
+    static FactorBacktest& instance();
+
+    /// Fundamental and valuation names expressions may read.
+    static const QStringList& variables();
+
+    static Result<FactorSpec> spec_from_json(const QJsonObject& o);
+
+    /// Starts a backtest; returns the request id finished / error_occurred carry.
+    QString run(const FactorSpec& spec);
+
+  signals:
+    void finished(QString request_id, QJsonObject result);
+    void error_occurred(QString request_id, QString message);
+
+  private:
+    FactorBacktest() = default;
+    Q_DISABLE_COPY(FactorBacktest)
+};
+
+} // namespace fincept::services::backtest
@@ -0,0 +1,101 @@
+// src/services/commodities/CommodityCurveService.cpp
+#include "services/commodities/CommodityCurveService.h"
+
+#include "core/events/EventBus.h"
+#include "core/logging/Logger.h"
+#include "python/PythonRunner.h"
+#include "storage/cache/CacheManager.h"
+
+#include <QDateTime>
+#include <QJsonDocument>
+#include <QPointer>
+#include <QUuid>
+
+namespace fincept::services {
+
+namespace {
+
+QString cc_cache_key(const QString& commodity) {
+    return "commodity_curve:" + commodity;
+}
+
+} // namespace
+
+CommodityCurveService& CommodityCurveService::instance() {
+    static CommodityCurveService s;
+    return s;
+}
+
+QStringList CommodityCurveService::commodities() {
+    return {"wti", "natgas", "heating_oil", "rbob", "vix"};
+}
+
+QJsonObject CommodityCurveService::latest(const QString& commodity) {
+    auto it = latest_.find(commodity);
+    if (it == latest_.end()) {
+        QJsonObject obj;
+        if (const auto cached = CacheManager::instance().try_get(cc_cache_key(commodity)))
+            obj = QJsonDocument::fromJson(cached->toUtf8()).object();
+        it = latest_.insert(commodity, obj);
+    }
+    return it.value();
+}
+
+bool CommodityCurveService::is_fresh(const QString& commodity) {
+    const QDateTime at = QDateTime::fromString(latest(commodity).value("computed_at").toString(), Qt::ISODate);
+    return at.isValid() && at.secsTo(QDateTime::currentDateTimeUtc()) < kStaleSecs;
+}
+
+QString CommodityCurveService::refresh(const QString& commodity, int years) {
+    const QString run_key = commodity + ':' + QString::number(years);
+    if (const auto it = running_.constFind(run_key); it != running_.constEnd())
+        return it.value();
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    running_.insert(run_key, request_id);
+
+    QPointer<CommodityCurveService> self = this;
+    python::PythonRunner::instance().run(
+        "Analytics/commodities/term_structure.py", {commodity, QString::number(years)},
+        [self, request_id, commodity, years, run_key](python::PythonResult r) {
+            if (!self)
+                return;
+            self->running_.remove(run_key);
+            const QJsonObject o =
+                r.success ? QJsonDocument::fromJson(python::extract_json(r.output).toUtf8()).object() : QJsonObject();
+            if (!o.value("success").toBool(false)) {
+                QString msg = o.value("error").toString();
+                if (msg.isEmpty())
+                    msg = r.error.isEmpty() ? QStringLiteral("term_structure.py produced no result") : r.error;
+                LOG_WARN("CommodityCurve", QString("%1 run failed: %2").arg(commodity, msg));
+                emit self->error_occurred(request_id, msg);
+                return;
+            }
+
+            const QJsonObject data = o.value("data").toObject();
+            const QString computed_at = data.value("computed_at").toString();
+            QJsonObject results;
+            const QJsonObject commodities = data.value("commodities").toObject();
+            for (auto it = commodities.begin(); it != commodities.end(); ++it) {
+                QJsonObject curve = it.value().toObject();
+                curve["computed_at"] = computed_at;
+                curve["years"] = years;
+                self->latest_.insert(it.key(), curve);
+                CacheManager::instance().put(cc_cache_key(it.key()),
+                                             QString::fromUtf8(QJsonDocument(curve).toJson(QJsonDocument::Compact)),
+                                             kCacheTtlSec, "commodities");
+                results[it.key()] = curve;
+                EventBus::instance().publish("commodity_curve.updated",
+                                             QVariantMap{{"commodity", it.key()},
+                                                         {"structure", curve.value("structure").toString()},
+                                                         {"roll_yield_pct", curve.value("roll_yield_pct").toDouble()}});
+            }
+            const QJsonObject errors = data.value("errors").toObject();
+            LOG_INFO("CommodityCurve", QString("Term structure updated for %1 commodities (%2 failed)")
+                                           .arg(results.size())
+                                           .arg(errors.size()));
+            emit self->updated(request_id, results, errors);
+        });
+    return request_id;
+}
+
+} // namespace fincept::services
@@ -0,0 +1,57 @@
+// src/services/commodities/CommodityCurveService.h
+#pragma once
+#include <QHash>
+#include <QJsonObject>
+#include <QObject>
+#include <QString>
+#include <QStringList>
+
+namespace fincept::services {
+
+/// Commodity term-structure analytics (Analytics/commodities/term_structure.py):
+/// curve snapshot, contango/backwardation, annualised roll yield and slope,
+/// calendar-month seasonality and a weekly chart-ready history per commodity.
+/// Energy curves come from EIA NYMEX contract 1-4 series, VIX from CBOE.
+///
+/// Each commodity's result is kept in CacheManager so charts open on the last
+/// run without re-fetching years of daily history; a result older than
+/// kStaleSecs is treated as stale. Main thread only.
+class CommodityCurveService : public QObject {
+    Q_OBJECT
+  public:
+    static constexpr int kDefaultYears = 5;
+    static constexpr int kStaleSecs = 12 * 60 * 60;
+    static constexpr int kCacheTtlSec = 7 * 24 * 60 * 60;
+
+    static CommodityCurveService& instance();
+
+    /// Commodity ids the script understands (wti, natgas, heating_oil, rbob, vix).
+    static QStringList commodities();
+
+    /// Last result for `commodity` (carries computed_at and the `years` it was
+    /// computed over), empty before the first successful run.
+    QJsonObject latest(const QString& commodity);
+
+    /// True when latest(commodity) is younger than kStaleSecs.
+    bool is_fresh(const QString& commodity);
+
+    /// Recomputes `commodity` (or "all") over `years` of history; returns the
+    /// request id updated / error_occurred carry. A run for the same target and
+    /// window already in flight is joined rather than restarted.
+    QString refresh(const QString& commodity, int years = kDefaultYears);
+
+  signals:
+    /// `results` maps commodity id -> term-structure object; `errors` maps
+    /// commodity id -> message for the ones that failed in an "all" run.
+    void updated(QString request_id, QJsonObject results, QJsonObject errors);
+    void error_occurred(QString request_id, QString message);
+
+  private:
+    CommodityCurveService() = default;
+    Q_DISABLE_COPY(CommodityCurveService)
+
+    QHash<QString, QJsonObject> latest_;
+    QHash<QString, QString> running_; // "target:years" -> request id while a run is in flight
+};
+
+} // namespace fincept::services
@@ -0,0 +1,663 @@
+// src/services/crypto/FundingRateService.cpp
+#include "services/crypto/FundingRateService.h"
+
+#include "core/events/EventBus.h"
+#include "core/logging/Logger.h"
+#include "services/notifications/NotificationService.h"
+#include "storage/repositories/SettingsRepository.h"
+#include "trading/exchanges/hyperliquid/HyperliquidClient.h"
+#include "ui/notifications/NotificationService.h"
+
+#include <QDateTime>
+#include <QJsonDocument>
+#include <QNetworkAccessManager>
+#include <QNetworkReply>
+#include <QNetworkRequest>
+#include <QPointer>
+#include <QSet>
+#include <QUrl>
+#include <QUuid>
+
+#include <algorithm>
+#include <cmath>
+#include <memory>
+
+namespace fincept::services {
+
+using fincept::notifications::NotificationRequest;
+using fincept::notifications::NotificationService;
+using fincept::notifications::NotifLevel;
+using fincept::notifications::NotifTrigger;
+using fincept::ui::ToastService;
+
+namespace {
+
+constexpr const char* kFrTag = "FundingRates";
+constexpr const char* kFrAssetsKey = "crypto_funding.assets";
+constexpr int kFrMaxAssets = 50;
+constexpr qint64 kFrPruneEveryMs = 60LL * 60 * 1000;
+
+QStringList fr_default_assets() {
+    return {"BTC", "ETH", "SOL", "XRP", "DOGE", "BNB", "AVAX", "LINK", "LTC", "ADA"};
+}
+
+double fr_num(const QJsonValue& v) {
+    if (v.isDouble())
+        return v.toDouble();
+    bool ok = false;
+    const double d = v.toString().toDouble(&ok);
+    return ok && std::isfinite(d) ? d : 0;
+}
+
+/// BTCUSDT → BTC; 1000PEPEUSDT / SHIB1000USDT / kPEPE → PEPE. Empty for
+/// anything that is not a USDT perp on Binance / Bybit.
+QString fr_base_asset(const QString& exchange, const QString& symbol) {
+    QString s = symbol;
+    if (exchange == "hyperliquid") {
+        if (s.size() > 1 && s.at(0) == 'k' && s.at(1).isUpper())
+            s.remove(0, 1);
+        return s.toUpper();
+    }
+    if (!s.endsWith("USDT") || s.contains('_') || s.contains('-'))
+        return {};
+    s.chop(4);
+    for (const QString m : {QStringLiteral("1000000"), QStringLiteral("10000"), QStringLiteral("1000")}) {
+        if (s.size() > m.size() && s.startsWith(m)) {
+            s.remove(0, m.size());
+            break;
+        }
+        if (s.size() > m.size() && s.endsWith(m)) {
+            s.chop(m.size());
+            break;
+        }
+    }
+    return s;
+}
+
+FundingPoint fr_point(const QString& exchange, const QString& symbol, const QString& asset, qint64 ts, double rate,
+                      double interval_hours) {
+    FundingPoint p;
+    p.exchange = exchange;
+    p.symbol = symbol;
+    p.asset = asset;
+    p.ts = ts;
+    p.funding_rate = rate;
+    p.interval_hours = interval_hours > 0 ? interval_hours : 8;
+    p.rate_8h = rate * 8.0 / p.interval_hours;
+    p.apr = rate * (24.0 / p.interval_hours) * 365.0 * 100.0;
+    return p;
+}
+
+void fr_get(QNetworkAccessManager* nam, const QString& url,
+            std::function<void(const QJsonDocument& doc, const QString& error)> cb) {
+    QNetworkRequest req{QUrl(url)};
+    req.setRawHeader("Accept", "application/json");
+    req.setTransferTimeout(FundingRateService::kRequestTimeoutMs);
+    QNetworkReply* reply = nam->get(req);
+    QObject::connect(reply, &QNetworkReply::finished, reply, [reply, cb]() {
+        reply->deleteLater();
+        const int http = reply->attribute(QNetworkRequest::HttpStatusCodeAttribute).toInt();
+        const QByteArray body = reply->readAll();
+        if (reply->error() != QNetworkReply::NoError) {
+            cb({}, http > 0 ? QString("HTTP %1: %2").arg(http).arg(reply->errorString()) : reply->errorString());
+            return;
+        }
+        QJsonParseError pe;
+        const QJsonDocument doc = QJsonDocument::fromJson(body, &pe);
+        if (pe.error != QJsonParseError::NoError) {
+            cb({}, "Bad JSON: " + pe.errorString());
+            return;
+        }
+        cb(doc, {});
+    });
+}
+
+const FundingPoint* fr_row(const QVector<FundingPoint>& rows, const QString& exchange) {
+    for (const auto& p : rows) {
+        if (p.exchange == exchange)
+            return &p;
+    }
+    return nullptr;
+}
+
+QJsonObject fr_leg(const FundingPoint& p) {
+    return QJsonObject{{"exchange", p.exchange}, {"symbol", p.symbol}, {"apr", p.apr}, {"rate_8h", p.rate_8h}};
+}
+
+} // namespace
+
+FundingRateService& FundingRateService::instance() {
+    static FundingRateService s;
+    return s;
+}
+
+void FundingRateService::start() {
+    if (timer_)
+        return;
+    timer_ = new QTimer(this);
+    timer_->setInterval(kPollMs);
+    connect(timer_, &QTimer::timeout, this, [this]() { refresh(); });
+    timer_->start();
+    QTimer::singleShot(0, this, [this]() { refresh(); });
+}
+
+void FundingRateService::refresh(std::function<void(const QStringList&)> done) {
+    if (done)
+        waiting_.append(std::move(done));
+    if (!polling_)
+        poll();
+}
+
+QStringList FundingRateService::exchanges() {
+    return {QStringLiteral("binance"), QStringLiteral("bybit"), QStringLiteral("hyperliquid")};
+}
+
+QStringList FundingRateService::metrics() {
+    return {QStringLiteral("funding_apr"), QStringLiteral("arb_spread_apr"), QStringLiteral("oi_usd"),
+            QStringLiteral("oi_change_pct")};
+}
+
+// ── Tracked assets ──────────────────────────────────────────────────────────
+
+QStringList FundingRateService::assets() const {
+    auto r = SettingsRepository::instance().get(kFrAssetsKey);
+    if (r.is_err() || r.value().trimmed().isEmpty())
+        return fr_default_assets();
+    return r.value().split(',', Qt::SkipEmptyParts);
+}
+
+Result<void> FundingRateService::set_assets(const QStringList& in) {
+    QStringList out;
+    for (const QString& raw : in) {
+        const QString a = raw.trimmed().toUpper();
+        if (!a.isEmpty() && !out.contains(a))
+            out.append(a);
+    }
+    if (out.isEmpty())
+        return Result<void>::err("No assets given");
+    if (out.size() > kFrMaxAssets)
+        return Result<void>::err(QString("At most %1 tracked assets").arg(kFrMaxAssets).toStdString());
+    return SettingsRepository::instance().set(kFrAssetsKey, out.join(','), "crypto");
+}
+
+// ── Polling ─────────────────────────────────────────────────────────────────
+
+void FundingRateService::poll() {
+    polling_ = true;
+    if (!nam_)
+        nam_ = new QNetworkAccessManager(this);
+    if (!hl_)
+        hl_ = new trading::hyperliquid::HyperliquidClient(this);
+
+    struct PollState {
+        QVector<FundingPoint> points;
+        QStringList errors;
+        int remaining = 3;
+    };
+    auto st = std::make_shared<PollState>();
+    QPointer<FundingRateService> self = this;
+    auto collect = [self, st](const QString& venue) -> VenueCallback {
+        return [self, st, venue](QVector<FundingPoint> points, const QString& error) {
+            if (!self)
+                return;
+            st->points += points;
+            if (!error.isEmpty())
+                st->errors.append(venue + ": " + error);
+            if (--st->remaining == 0)
+                self->finish_poll(st->points, st->errors);
+        };
+    };
+
+    VenueCallback binance_done = collect("binance");
+    fetch_binance([self, binance_done](QVector<FundingPoint> points, const QString& error) {
+        if (!self || !error.isEmpty()) {
+            binance_done(std::move(points), error);
+            return;
+        }
+        self->fetch_binance_oi(std::move(points), binance_done);
+    });
+    fetch_bybit(collect("bybit"));
+    fetch_hyperliquid(collect("hyperliquid"));
+}
+
+void FundingRateService::fetch_binance(VenueCallback cb) {
+    QPointer<FundingRateService> self = this;
+    // fundingInfo lists only the symbols whose interval differs from 8h.
+    fr_get(nam_, "https://fapi.binance.com/fapi/v1/fundingInfo", [self, cb](const QJsonDocument& info, const QString&) {
+        if (!self)
+            return;
+        if (info.isArray()) {
+            self->binance_intervals_.clear();
+            for (const auto& v : info.array()) {
+                const QJsonObject o = v.toObject();
+                const double h = fr_num(o.value("fundingIntervalHours"));
+                if (h > 0)
+                    self->binance_intervals_.insert(o.value("symbol").toString(), h);
+            }
+        }
+        fr_get(self->nam_, "https://fapi.binance.com/fapi/v1/premiumIndex",
+               [self, cb](const QJsonDocument& doc, const QString& error) {
+                   if (!self)
+                       return;
+                   if (!error.isEmpty() || !doc.isArray()) {
+                       cb({}, error.isEmpty() ? QStringLiteral("Unexpected premiumIndex response") : error);
+                       return;
+                   }
+                   const qint64 now = QDateTime::currentMSecsSinceEpoch();
+                   QVector<FundingPoint> out;
+                   for (const auto& v : doc.array()) {
+                       const QJsonObject o = v.toObject();
+                       const QString symbol = o.value("symbol").toString();
+                       const QString asset = fr_base_asset("binance", symbol);
+                       if (asset.isEmpty())
+                           continue;
+                       FundingPoint p = fr_point("binance", symbol, asset, now, fr_num(o.value("lastFundingRate")),
+                                                 self->binance_intervals_.value(symbol, 8));
+                       p.next_funding_ts = qint64(fr_num(o.value("nextFundingTime")));
+                       p.mark_price = fr_num(o.value("markPrice"));
+                       p.index_price = fr_num(o.value("indexPrice"));
+                       out.append(p);
+                   }
+                   cb(std::move(out), {});
+               });
+    });
+}
+
+void FundingRateService::fetch_binance_oi(QVector<FundingPoint> points, VenueCallback cb) {
+    const QStringList tracked = assets();
+    auto rows = std::make_shared<QVector<FundingPoint>>(std::move(points));
+    auto first_error = std::make_shared<QString>();
+    auto remaining = std::make_shared<int>(0);
+    QVector<int> wanted;
+    for (int i = 0; i < rows->size(); ++i) {
+        if (tracked.contains(rows->at(i).asset))
+            wanted.append(i);
+    }
+    if (wanted.isEmpty()) {
+        cb(*rows, {});
+        return;
+    }
+    *remaining = wanted.size();
+    for (int i : wanted) {
+        const QString url = "https://fapi.binance.com/fapi/v1/openInterest?symbol=" + rows->at(i).symbol;
+        fr_get(nam_, url, [rows, first_error, remaining, i, cb](const QJsonDocument& doc, const QString& error) {
+            auto& p = (*rows)[i];
+            if (error.isEmpty()) {
+                p.open_interest = fr_num(doc.object().value("openInterest"));
+                p.oi_usd = p.open_interest * p.mark_price;
+            } else if (first_error->isEmpty()) {
+                *first_error = "openInterest " + p.symbol + ": " + error;
+            }
+            if (--*remaining == 0)
+                cb(*rows, *first_error);
+        });
+    }
+}
+
+void FundingRateService::fetch_bybit(VenueCallback cb) {
+    fr_get(nam_, "https://api.bybit.com/v5/market/tickers?category=linear",
+           [cb](const QJsonDocument& doc, const QString& error) {
+               const QJsonObject root = doc.object();
+               if (!error.isEmpty() || root.value("retCode").toInt(-1) != 0) {
+                   cb({}, error.isEmpty() ? root.value("retMsg").toString("Unexpected tickers response") : error);
+                   return;
+               }
+               const qint64 now = QDateTime::currentMSecsSinceEpoch();
+               QVector<FundingPoint> out;
+               for (const auto& v : root.value("result").toObject().value("list").toArray()) {
+                   const QJsonObject o = v.toObject();
+                   const QString symbol = o.value("symbol").toString();
+                   const QString asset = fr_base_asset("bybit", symbol);
+                   // Dated futures share the category but carry no funding rate.
+                   if (asset.isEmpty() || o.value("fundingRate").toString().isEmpty())
+                       continue;
+                   const double hours = fr_num(o.value("fundingIntervalHour"));
+                   FundingPoint p = fr_point("bybit", symbol, asset, now, fr_num(o.value("fundingRate")),
+                                             hours > 0 ? hours : 8);
+                   p.next_funding_ts = qint64(fr_num(o.value("nextFundingTime")));
+                   p.mark_price = fr_num(o.value("markPrice"));
+                   p.index_price = fr_num(o.value("indexPrice"));
+                   p.open_interest = fr_num(o.value("openInterest"));
+                   p.oi_usd = fr_num(o.value("openInterestValue"));
+                   out.append(p);
+               }
+               cb(std::move(out), {});
+           });
+}
+
+void FundingRateService::fetch_hyperliquid(VenueCallback cb) {
+    hl_->info(QJsonObject{{"type", "metaAndAssetCtxs"}}, [cb](Result<QJsonDocument> r) {
+        if (r.is_err()) {
+            cb({}, QString::fromStdString(r.error()));
+            return;
+        }
+        // [ {universe: [{name, isDelisted?, ...}]}, [{funding, openInterest, markPx, oraclePx, ...}] ]
+        const QJsonArray root = r.value().array();
+        const QJsonArray universe = root.at(0).toObject().value("universe").toArray();
+        const QJsonArray ctxs = root.at(1).toArray();
+        if (universe.isEmpty() || ctxs.size() != universe.size()) {
+            cb({}, QStringLiteral("Unexpected metaAndAssetCtxs response"));
+            return;
+        }
+        const qint64 now = QDateTime::currentMSecsSinceEpoch();
+        const qint64 hour_ms = 60LL * 60 * 1000;
+        QVector<FundingPoint> out;
+        for (int i = 0; i < universe.size(); ++i) {
+            const QJsonObject u = universe.at(i).toObject();
+            if (u.value("isDelisted").toBool(false))
+                continue;
+            const QJsonObject c = ctxs.at(i).toObject();
+            const QString symbol = u.value("name").toString();
+            // Funding settles every hour on the hour.
+            FundingPoint p = fr_point("hyperliquid", symbol, fr_base_asset("hyperliquid", symbol), now,
+                                      fr_num(c.value("funding")), 1);
+            p.next_funding_ts = (now / hour_ms + 1) * hour_ms;
+            p.mark_price = fr_num(c.value("markPx"));
+            p.index_price = fr_num(c.value("oraclePx"));
+            p.open_interest = fr_num(c.value("openInterest"));
+            p.oi_usd = p.open_interest * p.mark_price;
+            out.append(p);
+        }
+        cb(std::move(out), {});
+    });
+}
+
+void FundingRateService::finish_poll(const QVector<FundingPoint>& points, const QStringList& errors) {
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    const QStringList tracked_list = assets();
+    const QSet<QString> tracked(tracked_list.cbegin(), tracked_list.cend());
+
+    latest_.clear();
+    QVector<FundingPoint> to_store;
+    for (const auto& p : points) {
+        auto& rows = latest_[p.asset];
+        if (fr_row(rows, p.exchange))
+            continue; // e.g. PEPEUSDT and 1000PEPEUSDT both listed — keep the first
+        rows.append(p);
+        if (tracked.contains(p.asset))
+            to_store.append(p);
+    }
+
+    auto& repo = CryptoFundingRepository::instance();
+    auto w = repo.store(to_store);
+    if (w.is_err())
+        LOG_WARN(kFrTag, "Storing funding snapshot failed: " + QString::fromStdString(w.error()));
+    if (now - last_prune_ms_ >= kFrPruneEveryMs) {
+        repo.prune(now - qint64(kRetentionDays) * 24 * 60 * 60 * 1000);
+        last_prune_ms_ = now;
+    }
+
+    last_poll_ms_ = now;
+    last_errors_ = errors;
+    polling_ = false;
+    LOG_INFO(kFrTag, QString("Polled %1 assets (%2 stored)%3")
+                         .arg(latest_.size())
+                         .arg(to_store.size())
+                         .arg(errors.isEmpty() ? QString() : " — " + errors.join("; ")));
+
+    evaluate_rules();
+    emit updated(snapshot());
+
+    const auto waiting = std::move(waiting_);
+    waiting_.clear();
+    for (const auto& cb : waiting)
+        cb(errors);
+}
+
+// ── Aggregates ──────────────────────────────────────────────────────────────
+
+QJsonObject FundingRateService::aggregate(const QString& asset) const {
+    const QVector<FundingPoint> rows = latest_.value(asset);
+    if (rows.isEmpty())
+        return {};
+    double oi_total = 0;
+    double w_rate = 0;
+    double w_apr = 0;
+    double mean_rate = 0;
+    double mean_apr = 0;
+    const FundingPoint* high = &rows.first();
+    const FundingPoint* low = &rows.first();
+    for (const auto& p : rows) {
+        oi_total += p.oi_usd;
+        w_rate += p.rate_8h * p.oi_usd;
+        w_apr += p.apr * p.oi_usd;
+        mean_rate += p.rate_8h / rows.size();
+        mean_apr += p.apr / rows.size();
+        if (p.apr > high->apr)
+            high = &p;
+        if (p.apr < low->apr)
+            low = &p;
+    }
+    QJsonObject agg{{"exchanges", int(rows.size())},
+                    {"oi_usd", oi_total},
+                    // OI-weighted when every venue reported OI; a plain mean otherwise.
+                    {"weighted_rate_8h", oi_total > 0 ? w_rate / oi_total : mean_rate},
+                    {"weighted_apr", oi_total > 0 ? w_apr / oi_total : mean_apr},
+                    {"high", fr_leg(*high)},
+                    {"low", fr_leg(*low)},
+                    {"spread_apr", high->apr - low->apr},
+                    {"oi_change_pct", QJsonValue()}};
+
+    // Compare only venues present in both polls so a venue dropping out is not
+    // read as an OI collapse.
+    const qint64 day_ago = last_poll_ms_ - 24LL * 60 * 60 * 1000;
+    auto prev = CryptoFundingRepository::instance().latest_before(asset, day_ago);
+    if (prev.is_ok() && !prev.value().isEmpty()) {
+        double then = 0;
+        double now = 0;
+        for (const auto& old : prev.value()) {
+            const FundingPoint* cur = fr_row(rows, old.exchange);
+            if (!cur || old.oi_usd <= 0 || cur->oi_usd <= 0 || day_ago - old.ts > 60LL * 60 * 1000)
+                continue;
+            then += old.oi_usd;
+            now += cur->oi_usd;
+        }
+        if (then > 0)
+            agg["oi_change_pct"] = (now - then) / then * 100.0;
+    }
+    return agg;
+}
+
+QJsonObject FundingRateService::snapshot(const QString& asset) const {
+    auto one = [this](const QString& a) {
+        QJsonArray ex;
+        for (const auto& p : latest_.value(a))
+            ex.append(point_to_json(p));
+        return QJsonObject{{"asset", a}, {"exchanges", ex}, {"aggregate", aggregate(a)}};
+    };
+    const QString polled_at =
+        last_poll_ms_ > 0 ? QDateTime::fromMSecsSinceEpoch(last_poll_ms_).toString(Qt::ISODate) : QString();
+    if (!asset.isEmpty()) {
+        QJsonObject o = one(asset.trimmed().toUpper());
+        o["polled_at"] = polled_at;
+        return o;
+    }
+    QJsonArray all;
+    for (const QString& a : assets()) {
+        if (latest_.contains(a))
+            all.append(one(a));
+    }
+    return QJsonObject{{"assets", all}, {"polled_at", polled_at}, {"errors", QJsonArray::fromStringList(last_errors_)}};
+}
+
+QJsonArray FundingRateService::arbitrage(double min_spread_apr, int limit) const {
+    struct Arb {
+        QString asset;
+        const FundingPoint* high;
+        const FundingPoint* low;
+        double spread;
+    };
+    QVector<Arb> arbs;
+    for (auto it = latest_.cbegin(); it != latest_.cend(); ++it) {
+        const auto& rows = it.value();
+        if (rows.size() < 2)
+            continue;
+        const auto [lo, hi] = std::minmax_element(
+            rows.begin(), rows.end(), [](const FundingPoint& a, const FundingPoint& b) { return a.apr < b.apr; });
+        const double spread = hi->apr - lo->apr;
+        if (spread >= min_spread_apr)
+            arbs.append({it.key(), &*hi, &*lo, spread});
+    }
+    std::sort(arbs.begin(), arbs.end(), [](const Arb& a, const Arb& b) { return a.spread > b.spread; });
+    QJsonArray out;
+    for (const auto& a : arbs) {
+        if (out.size() >= limit)
+            break;
+        out.append(QJsonObject{{"asset", a.asset},
+                               {"short", fr_leg(*a.high)},
+                               {"long", fr_leg(*a.low)},
+                               {"spread_apr", a.spread},
+                               {"spread_8h", a.high->rate_8h - a.low->rate_8h}});
+    }
+    return out;
+}
+
+// ── Alert rules ─────────────────────────────────────────────────────────────
+
+Result<FundingAlertRule> FundingRateService::add_rule(const FundingAlertRule& in) {
+    FundingAlertRule r = in;
+    r.asset = r.asset.trimmed().toUpper();
+    r.exchange = r.exchange.trimmed().toLower();
+    if (r.asset.isEmpty())
+        return Result<FundingAlertRule>::err("Rule needs an asset");
+    if (!metrics().contains(r.metric))
+        return Result<FundingAlertRule>::err("Unknown metric: " + r.metric.toStdString());
+    if (r.op != "above" && r.op != "below" && r.op != "abs_above")
+        return Result<FundingAlertRule>::err("op must be above, below or abs_above");
+    if (!r.exchange.isEmpty() && !exchanges().contains(r.exchange))
+        return Result<FundingAlertRule>::err("Unknown exchange: " + r.exchange.toStdString());
+    if (r.metric == "arb_spread_apr" || r.metric == "oi_change_pct")
+        r.exchange.clear(); // cross-exchange by definition
+    if (r.cooldown_min < 1)
+        r.cooldown_min = 1;
+    if (r.name.isEmpty())
+        r.name = QString("%1 %2 %3 %4").arg(r.asset, r.metric, r.op).arg(r.threshold);
+    return CryptoFundingRepository::instance().create_rule(r);
+}
+
+void FundingRateService::evaluate_rules() {
+    auto rules = CryptoFundingRepository::instance().list_rules();
+    if (rules.is_err())
+        return;
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    for (const auto& r : rules.value()) {
+        if (!r.active || now - r.last_fired_at < qint64(r.cooldown_min) * 60 * 1000 || !latest_.contains(r.asset))
+            continue;
+        const QJsonObject agg = aggregate(r.asset);
+        const FundingPoint* row = r.exchange.isEmpty() ? nullptr : fr_row(latest_.value(r.asset), r.exchange);
+        if (!r.exchange.isEmpty() && !row)
+            continue;
+        QJsonValue v;
+        if (r.metric == "funding_apr")
+            v = row ? QJsonValue(row->apr) : agg.value("weighted_apr");
+        else if (r.metric == "oi_usd")
+            v = row ? QJsonValue(row->oi_usd) : agg.value("oi_usd");
+        else if (r.metric == "arb_spread_apr")
+            v = agg.value("spread_apr");
+        else if (r.metric == "oi_change_pct")
+            v = agg.value("oi_change_pct");
+        if (!v.isDouble())
+            continue;
+        const double value = v.toDouble();
+        const bool hit = r.op == "below"       ? value < r.threshold
+                         : r.op == "abs_above" ? std::abs(value) > r.threshold
+                                               : value > r.threshold;
+        if (!hit)
+            continue;
+
+        FundingAlert a;
+        a.id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+        a.rule_id = r.id;
+        a.asset = r.asset;
+        a.metric = r.metric;
+        a.exchange = r.exchange;
+        a.value = value;
+        a.threshold = r.threshold;
+        a.detail = agg;
+        if (row)
+            a.detail["row"] = point_to_json(*row);
+        a.fired_at = now;
+        auto& repo = CryptoFundingRepository::instance();
+        repo.record_alert(a);
+        repo.touch_rule_fired(r.id, now);
+        const QJsonObject js = alert_to_json(a);
+        emit alert_fired(js);
+        EventBus::instance().publish("crypto.funding_alert", js.toVariantMap());
+        notify(r, a);
+    }
+}
+
+void FundingRateService::notify(const FundingAlertRule& rule, const FundingAlert& a) {
+    const bool pct = a.metric != "oi_usd";
+    const QString value = pct ? QString::number(a.value, 'f', 2) + "%" : QString::number(a.value, 'f', 0);
+    const QString threshold = pct ? QString::number(a.threshold, 'f', 2) + "%" : QString::number(a.threshold, 'f', 0);
+    const QString where = a.exchange.isEmpty() ? QString() : " on " + a.exchange;
+    const QString msg = QString("%1 %2%3 at %4 (%5 %6)").arg(a.asset, a.metric, where, value, rule.op, threshold);
+    LOG_INFO(kFrTag, rule.name + " — " + msg);
+    if (rule.toast)
+        ToastService::instance().post(ToastService::Severity::Warning, rule.name + ": " + msg, "funding:" + rule.id);
+    if (rule.providers) {
+        NotificationRequest req;
+        req.title = QString("Funding alert: %1").arg(rule.name);
+        req.message = msg;
+        req.level = NotifLevel::Warning;
+        req.trigger = NotifTrigger::PriceAlert;
+        NotificationService::instance().send(req);
+    }
+}
+
+// ── JSON ────────────────────────────────────────────────────────────────────
+
+QJsonObject FundingRateService::point_to_json(const FundingPoint& p) {
+    return QJsonObject{
+        {"exchange", p.exchange},
+        {"symbol", p.symbol},
+        {"time", QDateTime::fromMSecsSinceEpoch(p.ts).toString(Qt::ISODate)},
+        {"funding_rate", p.funding_rate},
+        {"interval_hours", p.interval_hours},
+        {"rate_8h", p.rate_8h},
+        {"apr", p.apr},
+        {"next_funding", p.next_funding_ts > 0
+                             ? QJsonValue(QDateTime::fromMSecsSinceEpoch(p.next_funding_ts).toString(Qt::ISODate))
+                             : QJsonValue()},
+        {"mark_price", p.mark_price},
+        {"index_price", p.index_price},
+        {"open_interest", p.open_interest},
+        {"oi_usd", p.oi_usd},
+    };
+}
+
+QJsonObject FundingRateService::rule_to_json(const FundingAlertRule& r) {
+    return QJsonObject{
+        {"id", r.id},
+        {"name", r.name},
+        {"asset", r.asset},
+        {"metric", r.metric},
+        {"exchange", r.exchange.isEmpty() ? QJsonValue() : QJsonValue(r.exchange)},
+        {"op", r.op},
+        {"threshold", r.threshold},
+        {"cooldown_min", r.cooldown_min},
+        {"toast", r.toast},
+        {"providers", r.providers},
+        {"active", r.active},
+        {"last_fired_at", r.last_fired_at > 0
+                              ? QJsonValue(QDateTime::fromMSecsSinceEpoch(r.last_fired_at).toString(Qt::ISODate))
+                              : QJsonValue()},
+    };
+}
+
+QJsonObject FundingRateService::alert_to_json(const FundingAlert& a) {
+    return QJsonObject{
+        {"id", a.id},
+        {"rule_id", a.rule_id},
+        {"asset", a.asset},
+        {"metric", a.metric},
+        {"exchange", a.exchange.isEmpty() ? QJsonValue() : QJsonValue(a.exchange)},
+        {"value", a.value},
+        {"threshold", a.threshold},
+        {"detail", a.detail},
+        {"fired_at", QDateTime::fromMSecsSinceEpoch(a.fired_at).toString(Qt::ISODate)},
+    };
+}
+
+} // namespace fincept::services
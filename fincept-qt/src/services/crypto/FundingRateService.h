@@ -0,0 +1,117 @@
+// src/services/crypto/FundingRateService.h
+#pragma once
+#include "core/result/Result.h"
+#include "storage/repositories/CryptoFundingRepository.h"
+
+#include <QHash>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+#include <QString>
+#include <QStringList>
+#include <QTimer>
+#include <QVector>
+
+#include <functional>
+
+class QNetworkAccessManager;
+
+namespace fincept::trading::hyperliquid {
+class HyperliquidClient;
+}
+
+namespace fincept::services {
+
+/// Perpetual funding rates and open interest across Binance USDⓈ-M, Bybit
+/// linear and Hyperliquid, from their public REST endpoints (no keys).
+///
+/// Every kPollMs one bulk request per venue returns funding for all of its
+/// USDT perps (Hyperliquid: USDC), so cross-exchange spreads cover the whole
+/// overlap. Open interest is fetched for the tracked assets only (Binance
+/// serves it per symbol) and only tracked assets are written to
+/// crypto_funding. Rates are normalised to 8 hours and annualised (percent)
+/// so venues with 1h / 4h / 8h intervals compare directly; OI is compared in
+/// USD, which also makes 1000PEPE / kPEPE contracts line up with PEPE.
+///
+/// After each poll the alert rules (CryptoFundingRepository) are evaluated;
+/// a rule fires at most once per cooldown and is pushed through alert_fired,
+/// the `crypto.funding_alert` EventBus event and its toast / notification
+/// providers. Main thread only.
+class FundingRateService : public QObject {
+    Q_OBJECT
+  public:
+    static constexpr int kPollMs = 5 * 60 * 1000;
+    static constexpr int kRequestTimeoutMs = 20 * 1000;
+    static constexpr int kRetentionDays = 90;
+
+    static FundingRateService& instance();
+
+    /// Starts polling. Idempotent.
+    void start();
+
+    /// Polls every venue now (no-op while a poll is running); `done` gets the
+    /// per-venue errors once it finishes.
+    void refresh(std::function<void(const QStringList& errors)> done = {});
+
+    /// Base assets whose history is stored and whose OI is fetched.
+    QStringList assets() const;
+    Result<void> set_assets(const QStringList& assets);
+
+    static QStringList exchanges(); // binance, bybit, hyperliquid
+    static QStringList metrics();   // funding_apr, arb_spread_apr, oi_usd, oi_change_pct
+
+    /// Latest poll for one asset, or every tracked asset:
+    /// {asset, exchanges[{exchange, symbol, funding_rate, interval_hours,
+    ///  rate_8h, apr, next_funding, mark_price, index_price, open_interest,
+    ///  oi_usd}], aggregate{oi_usd, weighted_rate_8h, weighted_apr, high{..},
+    ///  low{..}, spread_apr, oi_change_pct}}
+    QJsonObject snapshot(const QString& asset = {}) const;
+
+    /// Funding-arb candidates across every listed asset, widest spread first:
+    /// short the perp on the highest-funding venue, long it on the lowest.
+    QJsonArray arbitrage(double min_spread_apr = 0, int limit = 50) const;
+
+    qint64 last_poll_ms() const { return last_poll_ms_; }
+    QStringList last_errors() const { return last_errors_; }
+
+    Result<FundingAlertRule> add_rule(const FundingAlertRule& rule);
+
+    static QJsonObject point_to_json(const FundingPoint& p);
+    static QJsonObject rule_to_json(const FundingAlertRule& r);
+    static QJsonObject alert_to_json(const FundingAlert& a);
+
+  signals:
+    /// After every poll, with snapshot() of the tracked assets.
+    void updated(QJsonObject snapshot);
+    void alert_fired(QJsonObject alert);
+
+  private:
+    FundingRateService() = default;
+    Q_DISABLE_COPY(FundingRateService)
+
+    using VenueCallback = std::function<void(QVector<FundingPoint> points, const QString& error)>;
+
+    void poll();
+    void fetch_binance(VenueCallback cb);
+    void fetch_bybit(VenueCallback cb);
+    void fetch_hyperliquid(VenueCallback cb);
+    /// Fills open_interest / oi_usd of the tracked Binance rows.
+    void fetch_binance_oi(QVector<FundingPoint> points, VenueCallback cb);
+    void finish_poll(const QVector<FundingPoint>& points, const QStringList& errors);
+    QJsonObject aggregate(const QString& asset) const;
+    void evaluate_rules();
+    void notify(const FundingAlertRule& rule, const FundingAlert& alert);
+
+    QNetworkAccessManager* nam_ = nullptr;
+    trading::hyperliquid::HyperliquidClient* hl_ = nullptr;
+    QTimer* timer_ = nullptr;
+    bool polling_ = false;
+    QVector<std::function<void(const QStringList&)>> waiting_; // refresh() callers of the running poll
+    QHash<QString, QVector<FundingPoint>> latest_;             // asset → one row per exchange, last poll
+    QHash<QString, double> binance_intervals_;                 // symbol → hours, when not 8
+    QStringList last_errors_;
+    qint64 last_poll_ms_ = 0;
+    qint64 last_prune_ms_ = 0;
+};
+
+} // namespace fincept::services
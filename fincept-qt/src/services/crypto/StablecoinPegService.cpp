@@ -0,0 +1,564 @@
+// src/services/crypto/StablecoinPegService.cpp
+#include "services/crypto/StablecoinPegService.h"
+
+#include "core/events/EventBus.h"
+#include "core/logging/Logger.h"
+#include "services/notifications/NotificationService.h"
+#include "storage/repositories/SettingsRepository.h"
+#include "ui/notifications/NotificationService.h"
+
+#include <QDateTime>
+#include <QJsonDocument>
+#include <QNetworkAccessManager>
+#include <QNetworkReply>
+#include <QNetworkRequest>
+#include <QPointer>
+#include <QUrl>
+#include <QUuid>
+
+#include <algorithm>
+#include <cmath>
+#include <memory>
+
+namespace fincept::services {
+
+using fincept::notifications::NotificationRequest;
+using fincept::notifications::NotificationService;
+using fincept::notifications::NotifLevel;
+using fincept::notifications::NotifTrigger;
+using fincept::ui::ToastService;
+
+namespace {
+
+constexpr const char* kSpTag = "StablecoinPeg";
+constexpr const char* kSpConfigKey = "stablecoin_peg.config";
+constexpr const char* kSpCurvePoolsUrl = "https://api.curve.finance/v1/getPools/ethereum/main";
+constexpr qint64 kSpPruneEveryMs = 60LL * 60 * 1000;
+
+const QStringList kSpVenues = {"kraken", "coinbase", "bitstamp"};
+
+double sp_num(const QJsonValue& v) {
+    if (v.isDouble())
+        return v.toDouble();
+    bool ok = false;
+    const double d = v.toString().toDouble(&ok);
+    return ok && std::isfinite(d) ? d : 0;
+}
+
+QString sp_ticker_url(const QString& venue, const QString& coin) {
+    if (venue == "kraken")
+        return "https://api.kraken.com/0/public/Ticker?pair=" + coin + "USD";
+    if (venue == "coinbase")
+        return "https://api.exchange.coinbase.com/products/" + coin + "-USD/ticker";
+    return "https://www.bitstamp.net/api/v2/ticker/" + coin.toLower() + "usd/";
+}
+
+/// Last traded price from a venue's ticker body; 0 when the venue does not
+/// list the pair.
+double sp_ticker_price(const QString& venue, const QJsonObject& o) {
+    if (venue == "kraken") {
+        // {"error": [], "result": {"USDTZUSD": {"c": ["1.00012", "120.5"], ...}}}
+        if (!o.value("error").toArray().isEmpty())
+            return 0;
+        const QJsonObject result = o.value("result").toObject();
+        return result.isEmpty() ? 0 : sp_num(result.begin().value().toObject().value("c").toArray().at(0));
+    }
+    if (venue == "coinbase")
+        return sp_num(o.value("price"));
+    return sp_num(o.value("last"));
+}
+
+/// `http` is the status code (0 on transport errors).
+void sp_get(QNetworkAccessManager* nam, const QString& url,
+            std::function<void(const QJsonDocument& doc, int http, const QString& error)> cb) {
+    QNetworkRequest req{QUrl(url)};
+    req.setRawHeader("Accept", "application/json");
+    req.setTransferTimeout(StablecoinPegService::kRequestTimeoutMs);
+    QNetworkReply* reply = nam->get(req);
+    QObject::connect(reply, &QNetworkReply::finished, reply, [reply, cb]() {
+        reply->deleteLater();
+        const int http = reply->attribute(QNetworkRequest::HttpStatusCodeAttribute).toInt();
+        const QByteArray body = reply->readAll();
+        if (reply->error() != QNetworkReply::NoError) {
+            cb({}, http, http > 0 ? QString("HTTP %1").arg(http) : reply->errorString());
+            return;
+        }
+        QJsonParseError pe;
+        const QJsonDocument doc = QJsonDocument::fromJson(body, &pe);
+        if (pe.error != QJsonParseError::NoError) {
+            cb({}, http, "Bad JSON: " + pe.errorString());
+            return;
+        }
+        cb(doc, http, {});
+    });
+}
+
+double sp_median(QVector<double> v) {
+    if (v.isEmpty())
+        return 0;
+    std::sort(v.begin(), v.end());
+    const int n = v.size();
+    return n % 2 ? v[n / 2] : (v[n / 2 - 1] + v[n / 2]) / 2;
+}
+
+QString sp_iso(qint64 ms) {
+    return ms > 0 ? QDateTime::fromMSecsSinceEpoch(ms).toString(Qt::ISODate) : QString();
+}
+
+} // namespace
+
+// ── Config ──────────────────────────────────────────────────────────────────
+
+StablecoinPegService::Config StablecoinPegService::Config::from_json(const QJsonObject& o) {
+    Config c;
+    if (o.contains("coins")) {
+        c.coins.clear();
+        for (const auto& v : o.value("coins").toArray()) {
+            const QString coin = v.toString().trimmed().toUpper();
+            if (!coin.isEmpty() && !c.coins.contains(coin))
+                c.coins.append(coin);
+        }
+    }
+    c.threshold_bps = o.value("threshold_bps").toDouble(c.threshold_bps);
+    const QJsonObject per_coin = o.value("coin_threshold_bps").toObject();
+    for (auto it = per_coin.begin(); it != per_coin.end(); ++it)
+        c.coin_threshold_bps.insert(it.key().toUpper(), it.value().toDouble());
+    c.duration_min = o.value("duration_min").toInt(c.duration_min);
+    if (o.contains("pools")) {
+        c.pools.clear();
+        for (const auto& v : o.value("pools").toArray()) {
+            if (!v.toString().trimmed().isEmpty())
+                c.pools.append(v.toString().trimmed());
+        }
+    }
+    c.pool_imbalance_pct = o.value("pool_imbalance_pct").toDouble(c.pool_imbalance_pct);
+    c.pool_duration_min = o.value("pool_duration_min").toInt(c.pool_duration_min);
+    c.toast = o.value("toast").toBool(c.toast);
+    c.providers = o.value("providers").toBool(c.providers);
+    c.retention_days = o.value("retention_days").toInt(c.retention_days);
+    return c;
+}
+
+QJsonObject StablecoinPegService::Config::to_json() const {
+    QJsonObject per_coin;
+    for (auto it = coin_threshold_bps.begin(); it != coin_threshold_bps.end(); ++it)
+        per_coin.insert(it.key(), it.value());
+    return QJsonObject{{"coins", QJsonArray::fromStringList(coins)},
+                       {"threshold_bps", threshold_bps},
+                       {"coin_threshold_bps", per_coin},
+                       {"duration_min", duration_min},
+                       {"pools", QJsonArray::fromStringList(pools)},
+                       {"pool_imbalance_pct", pool_imbalance_pct},
+                       {"pool_duration_min", pool_duration_min},
+                       {"toast", toast},
+                       {"providers", providers},
+                       {"retention_days", retention_days}};
+}
+
+// ── StablecoinPegService ────────────────────────────────────────────────────
+
+StablecoinPegService& StablecoinPegService::instance() {
+    static StablecoinPegService s;
+    return s;
+}
+
+StablecoinPegService::Config StablecoinPegService::config() const {
+    auto r = SettingsRepository::instance().get(kSpConfigKey);
+    if (r.is_err() || r.value().isEmpty())
+        return Config{};
+    return Config::from_json(QJsonDocument::fromJson(r.value().toUtf8()).object());
+}
+
+void StablecoinPegService::set_config(const Config& c) {
+    SettingsRepository::instance().set(kSpConfigKey,
+                                       QString::fromUtf8(QJsonDocument(c.to_json()).toJson(QJsonDocument::Compact)),
+                                       "crypto");
+}
+
+void StablecoinPegService::start() {
+    if (timer_)
+        return;
+    timer_ = new QTimer(this);
+    timer_->setInterval(kPollMs);
+    connect(timer_, &QTimer::timeout, this, [this]() {
+        if (!polling_)
+            poll();
+    });
+    timer_->start();
+    QTimer::singleShot(0, this, [this]() { refresh(); });
+}
+
+void StablecoinPegService::refresh(std::function<void(const QStringList&)> done) {
+    if (done)
+        waiting_.append(std::move(done));
+    if (polling_)
+        return;
+    poll_count_ = 0; // include the pools
+    poll();
+}
+
+// ── Polling ─────────────────────────────────────────────────────────────────
+
+void StablecoinPegService::poll() {
+    polling_ = true;
+    if (!nam_)
+        nam_ = new QNetworkAccessManager(this);
+    if (!loaded_)
+        load_open_events();
+
+    const bool with_pools = poll_count_++ % kPoolEveryPolls == 0;
+    auto errors = std::make_shared<QStringList>();
+    auto remaining = std::make_shared<int>(with_pools ? 2 : 1);
+    QPointer<StablecoinPegService> self = this;
+    auto step = [self, errors, remaining]() {
+        if (self && --*remaining == 0)
+            self->finish_poll(*errors);
+    };
+
+    fetch_prices([self, errors, step](QVector<StablecoinPrice> prices, QStringList errs) {
+        if (!self)
+            return;
+        *errors += errs;
+        self->prices_.clear();
+        for (const auto& p : prices)
+            self->prices_[p.coin][p.venue] = p.price;
+        auto w = StablecoinPegRepository::instance().store_prices(prices);
+        if (w.is_err())
+            LOG_WARN(kSpTag, "Storing prices failed: " + QString::fromStdString(w.error()));
+        step();
+    });
+    if (with_pools) {
+        fetch_pools([self, errors, step](QHash<QString, PoolState> pools, QString error) {
+            if (!self)
+                return;
+            if (!error.isEmpty()) {
+                errors->append("curve: " + error);
+            } else {
+                self->pools_ = pools;
+                QVector<CurvePoolBalance> rows;
+                for (const auto& st : pools)
+                    rows += st.balances;
+                auto w = StablecoinPegRepository::instance().store_pool(rows);
+                if (w.is_err())
+                    LOG_WARN(kSpTag, "Storing pool balances failed: " + QString::fromStdString(w.error()));
+            }
+            step();
+        });
+    }
+}
+
+void StablecoinPegService::fetch_prices(std::function<void(QVector<StablecoinPrice>, QStringList)> cb) {
+    const QStringList coins = config().coins;
+    if (coins.isEmpty()) {
+        cb({}, {});
+        return;
+    }
+    struct PriceState {
+        QVector<StablecoinPrice> prices;
+        QStringList errors;
+        int remaining = 0;
+    };
+    auto st = std::make_shared<PriceState>();
+    st->remaining = coins.size() * kSpVenues.size();
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    for (const QString& coin : coins) {
+        for (const QString& venue : kSpVenues) {
+            sp_get(nam_, sp_ticker_url(venue, coin),
+                   [st, cb, coin, venue, now](const QJsonDocument& doc, int http, const QString& error) {
+                       // 400 / 404: the venue does not list this pair.
+                       if (!error.isEmpty() && http != 400 && http != 404) {
+                           st->errors.append(QString("%1 %2: %3").arg(venue, coin, error));
+                       } else if (error.isEmpty()) {
+                           const double price = sp_ticker_price(venue, doc.object());
+                           if (price > 0)
+                               st->prices.append({coin, venue, now, price});
+                       }
+                       if (--st->remaining == 0)
+                           cb(st->prices, st->errors);
+                   });
+        }
+    }
+}
+
+void StablecoinPegService::fetch_pools(std::function<void(QHash<QString, PoolState>, QString)> cb) {
+    QStringList wanted;
+    for (const QString& p : config().pools)
+        wanted.append(p.toLower());
+    if (wanted.isEmpty()) {
+        cb({}, {});
+        return;
+    }
+    sp_get(nam_, kSpCurvePoolsUrl, [cb, wanted](const QJsonDocument& doc, int, const QString& error) {
+        if (!error.isEmpty()) {
+            cb({}, error);
+            return;
+        }
+        // {"success": true, "data": {"poolData": [{address, name, coins[{symbol, decimals, poolBalance}]}]}}
+        const qint64 now = QDateTime::currentMSecsSinceEpoch();
+        QHash<QString, PoolState> out;
+        for (const auto& v : doc.object().value("data").toObject().value("poolData").toArray()) {
+            const QJsonObject pool = v.toObject();
+            const QString address = pool.value("address").toString().toLower();
+            if (!wanted.contains(address))
+                continue;
+            PoolState st;
+            st.name = pool.value("name").toString();
+            double total = 0;
+            for (const auto& c : pool.value("coins").toArray()) {
+                const QJsonObject coin = c.toObject();
+                CurvePoolBalance b;
+                b.pool = address;
+                b.pool_name = st.name;
+                b.coin = coin.value("symbol").toString().toUpper();
+                b.ts = now;
+                b.balance = sp_num(coin.value("poolBalance")) / std::pow(10.0, sp_num(coin.value("decimals")));
+                total += b.balance;
+                st.balances.append(b);
+            }
+            if (total <= 0)
+                continue;
+            for (auto& b : st.balances)
+                b.share_pct = b.balance / total * 100.0;
+            out.insert(address, st);
+        }
+        cb(out, out.size() < wanted.size() ? QString("%1 of %2 pools not found in the main registry")
+                                                 .arg(wanted.size() - out.size())
+                                                 .arg(wanted.size())
+                                           : QString());
+    });
+}
+
+void StablecoinPegService::finish_poll(const QStringList& errors) {
+    const Config cfg = config();
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    if (now - last_prune_ms_ >= kSpPruneEveryMs) {
+        StablecoinPegRepository::instance().prune(now - qint64(cfg.retention_days) * 24 * 60 * 60 * 1000);
+        last_prune_ms_ = now;
+    }
+    last_poll_ms_ = now;
+    last_errors_ = errors;
+    polling_ = false;
+    if (!errors.isEmpty())
+        LOG_WARN(kSpTag, "Poll errors: " + errors.join("; "));
+
+    evaluate(cfg, now);
+    emit updated(status());
+
+    const auto waiting = std::move(waiting_);
+    waiting_.clear();
+    for (const auto& cb : waiting)
+        cb(errors);
+}
+
+// ── Episodes ────────────────────────────────────────────────────────────────
+
+void StablecoinPegService::evaluate(const Config& cfg, qint64 now) {
+    for (const QString& coin : cfg.coins) {
+        const auto venues = prices_.value(coin);
+        if (venues.isEmpty())
+            continue; // no data this poll — leave any episode as it was
+        const double median = sp_median(venues.values());
+        const double dev_bps = (median - 1.0) * 10000.0;
+        const double threshold = cfg.coin_threshold_bps.value(coin, cfg.threshold_bps);
+        QJsonObject px;
+        for (auto it = venues.begin(); it != venues.end(); ++it)
+            px.insert(it.key(), it.value());
+        track("price|" + coin, "price", coin, "median", dev_bps, threshold, std::abs(dev_bps) > threshold,
+              cfg.duration_min, QJsonObject{{"median", median}, {"venues", px}}, now);
+    }
+
+    for (const QString& p : cfg.pools) {
+        const QString address = p.toLower();
+        const auto it = pools_.constFind(address);
+        if (it == pools_.cend() || it->balances.isEmpty())
+            continue;
+        const double fair = 100.0 / it->balances.size();
+        QJsonArray composition;
+        for (const auto& b : it->balances)
+            composition.append(QJsonObject{{"coin", b.coin}, {"balance", b.balance}, {"share_pct", b.share_pct}});
+        for (const auto& b : it->balances) {
+            const double imbalance = b.share_pct - fair;
+            track("pool|" + address + "|" + b.coin, "pool", b.coin, address, imbalance, cfg.pool_imbalance_pct,
+                  imbalance > cfg.pool_imbalance_pct, cfg.pool_duration_min,
+                  QJsonObject{{"pool_name", it->name}, {"coins", composition}}, now);
+        }
+    }
+}
+
+void StablecoinPegService::track(const QString& key, const QString& kind, const QString& coin, const QString& source,
+                                 double value, double threshold, bool breaching, int duration_min,
+                                 const QJsonObject& detail, qint64 now) {
+    auto& repo = StablecoinPegRepository::instance();
+    auto open = open_.find(key);
+    if (!breaching) {
+        breach_since_.remove(key);
+        if (open == open_.end())
+            return;
+        DepegEvent e = *open;
+        open_.erase(open);
+        e.ended_at = now;
+        e.last_value = value;
+        repo.save_event(e);
+        const QJsonObject js = event_to_json(e);
+        emit depeg_ended(js);
+        QVariantMap ev = js.toVariantMap();
+        ev["state"] = "ended";
+        EventBus::instance().publish("crypto.depeg", ev);
+        notify(e, false);
+        return;
+    }
+
+    if (!breach_since_.contains(key))
+        breach_since_.insert(key, now);
+    if (open != open_.end()) {
+        open->last_value = value;
+        open->threshold = threshold;
+        if (std::abs(value) > std::abs(open->peak_value)) {
+            open->peak_value = value;
+            open->detail = detail;
+        }
+        repo.save_event(*open);
+        return;
+    }
+    const qint64 since = breach_since_.value(key);
+    if (now - since < qint64(duration_min) * 60 * 1000)
+        return;
+
+    DepegEvent e;
+    e.id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    e.kind = kind;
+    e.coin = coin;
+    e.source = source;
+    e.threshold = threshold;
+    e.started_at = since;
+    e.alerted_at = now;
+    e.peak_value = value;
+    e.last_value = value;
+    e.detail = detail;
+    repo.save_event(e);
+    open_.insert(key, e);
+    const QJsonObject js = event_to_json(e);
+    emit depeg_started(js);
+    QVariantMap ev = js.toVariantMap();
+    ev["state"] = "started";
+    EventBus::instance().publish("crypto.depeg", ev);
+    notify(e, true);
+}
+
+void StablecoinPegService::load_open_events() {
+    loaded_ = true;
+    auto r = StablecoinPegRepository::instance().open_events();
+    if (r.is_err())
+        return;
+    // Episodes left open by the last session resume (and close on the first
+    // poll that is back inside the threshold) instead of alerting twice.
+    for (const auto& e : r.value()) {
+        const QString key = e.kind == "pool" ? "pool|" + e.source + "|" + e.coin : "price|" + e.coin;
+        open_.insert(key, e);
+        breach_since_.insert(key, e.started_at);
+    }
+}
+
+void StablecoinPegService::notify(const DepegEvent& e, bool started) {
+    const Config cfg = config();
+    const QString pool_name = e.detail.value("pool_name").toString(e.source);
+    const QString what = e.kind == "pool"
+                             ? QString("%1 imbalance: %2 at %3 pts over fair share")
+                                   .arg(pool_name, e.coin)
+                                   .arg(e.last_value, 0, 'f', 1)
+                             : QString("%1 depeg: %2 bps from $1").arg(e.coin).arg(e.last_value, 0, 'f', 0);
+    const qint64 minutes = ((e.ended_at > 0 ? e.ended_at : e.alerted_at) - e.started_at) / 60000;
+    const QString msg = started ? QString("%1 (for %2 min)").arg(what).arg(minutes)
+                                : QString("%1 %2 back inside threshold after %3 min (peak %4)")
+                                      .arg(e.coin, e.kind == "pool" ? pool_name : QStringLiteral("price"))
+                                      .arg(minutes)
+                                      .arg(e.peak_value, 0, 'f', 1);
+    LOG_INFO(kSpTag, msg);
+    if (cfg.toast)
+        ToastService::instance().post(started ? ToastService::Severity::Warning : ToastService::Severity::Info, msg,
+                                      "depeg:" + e.id);
+    if (cfg.providers) {
+        NotificationRequest req;
+        req.title = started ? QString("Stablecoin alert: %1").arg(e.coin)
+                            : QString("Stablecoin recovered: %1").arg(e.coin);
+        req.message = msg;
+        req.level = started ? NotifLevel::Warning : NotifLevel::Info;
+        req.trigger = NotifTrigger::PriceAlert;
+        NotificationService::instance().send(req);
+    }
+}
+
+// ── Status / JSON ───────────────────────────────────────────────────────────
+
+QJsonObject StablecoinPegService::status() const {
+    const Config cfg = config();
+    QJsonArray coins;
+    for (const QString& coin : cfg.coins) {
+        const auto venues = prices_.value(coin);
+        QJsonObject px;
+        for (auto it = venues.begin(); it != venues.end(); ++it)
+            px.insert(it.key(), it.value());
+        const double median = sp_median(venues.values());
+        const QString key = "price|" + coin;
+        coins.append(QJsonObject{
+            {"coin", coin},
+            {"median", venues.isEmpty() ? QJsonValue() : QJsonValue(median)},
+            {"deviation_bps", venues.isEmpty() ? QJsonValue() : QJsonValue((median - 1.0) * 10000.0)},
+            {"threshold_bps", cfg.coin_threshold_bps.value(coin, cfg.threshold_bps)},
+            {"venues", px},
+            {"breaching_since", breach_since_.contains(key) ? QJsonValue(sp_iso(breach_since_.value(key)))
+                                                            : QJsonValue()},
+            {"open_event", open_.contains(key) ? QJsonValue(event_to_json(open_.value(key))) : QJsonValue()},
+        });
+    }
+
+    QJsonArray pools;
+    for (const QString& p : cfg.pools) {
+        const QString address = p.toLower();
+        const PoolState st = pools_.value(address);
+        const double fair = st.balances.isEmpty() ? 0 : 100.0 / st.balances.size();
+        QJsonArray composition;
+        QJsonArray open_events;
+        qint64 since = 0;
+        for (const auto& b : st.balances) {
+            composition.append(QJsonObject{{"coin", b.coin},
+                                           {"balance", b.balance},
+                                           {"share_pct", b.share_pct},
+                                           {"imbalance_pct", b.share_pct - fair}});
+            const QString key = "pool|" + address + "|" + b.coin;
+            if (breach_since_.contains(key))
+                since = since > 0 ? std::min(since, breach_since_.value(key)) : breach_since_.value(key);
+            if (open_.contains(key))
+                open_events.append(event_to_json(open_.value(key)));
+        }
+        pools.append(QJsonObject{{"pool", address},
+                                 {"name", st.name},
+                                 {"coins", composition},
+                                 {"breaching_since", since > 0 ? QJsonValue(sp_iso(since)) : QJsonValue()},
+                                 {"open_events", open_events}});
+    }
+    return QJsonObject{{"polled_at", sp_iso(last_poll_ms_)},
+                       {"coins", coins},
+                       {"pools", pools},
+                       {"errors", QJsonArray::fromStringList(last_errors_)}};
+}
+
+QJsonObject StablecoinPegService::event_to_json(const DepegEvent& e) {
+    const qint64 end = e.ended_at > 0 ? e.ended_at : QDateTime::currentMSecsSinceEpoch();
+    return QJsonObject{
+        {"id", e.id},
+        {"kind", e.kind},
+        {"coin", e.coin},
+        {"source", e.source},
+        {"threshold", e.threshold},
+        {"started_at", sp_iso(e.started_at)},
+        {"alerted_at", sp_iso(e.alerted_at)},
+        {"ended_at", e.ended_at > 0 ? QJsonValue(sp_iso(e.ended_at)) : QJsonValue()},
+        {"duration_min", double(end - e.started_at) / 60000.0},
+        {"peak_value", e.peak_value},
+        {"last_value", e.last_value},
+        {"detail", e.detail},
+    };
+}
+
+} // namespace fincept::services
@@ -0,0 +1,121 @@
+// src/services/crypto/StablecoinPegService.h
+#pragma once
+#include "storage/repositories/StablecoinPegRepository.h"
+
+#include <QHash>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+#include <QString>
+#include <QStringList>
+#include <QTimer>
+#include <QVector>
+
+#include <functional>
+
+class QNetworkAccessManager;
+
+namespace fincept::services {
+
+/// Stablecoin peg monitor. Every kPollMs it reads each configured coin's USD
+/// price from Kraken, Coinbase and Bitstamp (public tickers, pairs a venue
+/// does not list are skipped) and, every kPoolEveryPolls polls, the
+/// composition of the configured Curve pools from the Curve API.
+///
+/// A coin is depegged when the median of its venue prices is more than
+/// threshold_bps from $1; a pool is imbalanced when one coin's share exceeds
+/// its fair share (100 / coins) by pool_imbalance_pct points — holders
+/// dumping a coin into the pool show up here before the price moves. Either
+/// must persist for duration_min / pool_duration_min before an episode opens:
+/// it is stored in stablecoin_depeg_events, toasted / sent to the
+/// notification providers and published as `crypto.depeg`. The episode
+/// tracks its peak until the value is back inside the threshold, when it is
+/// closed and announced again. Prices and pool balances are kept
+/// retention_days for post-mortems. Main thread only.
+class StablecoinPegService : public QObject {
+    Q_OBJECT
+  public:
+    struct Config {
+        QStringList coins{"USDT", "USDC", "DAI", "PYUSD"};
+        double threshold_bps = 50;                 // |median - 1| in basis points
+        QHash<QString, double> coin_threshold_bps; // per-coin override of threshold_bps
+        int duration_min = 10;                     // breach must last this long before alerting
+        /// Curve pool addresses; the default is 3pool (DAI / USDC / USDT).
+        QStringList pools{"0xbEbc44782C7dB0a1A60Cb6fe97d0b483032FF1C7"};
+        double pool_imbalance_pct = 15; // one coin's share above its fair share, points
+        int pool_duration_min = 30;
+        bool toast = true;
+        bool providers = false; // also send through the notification providers
+        int retention_days = 180;
+
+        /// Defaults overridden by whichever keys `o` carries.
+        static Config from_json(const QJsonObject& o);
+        QJsonObject to_json() const;
+    };
+
+    static constexpr int kPollMs = 60 * 1000;
+    static constexpr int kPoolEveryPolls = 5;
+    static constexpr int kRequestTimeoutMs = 15 * 1000;
+
+    static StablecoinPegService& instance();
+
+    /// Persisted thresholds (settings key stablecoin_peg.config).
+    Config config() const;
+    void set_config(const Config& c);
+
+    /// Starts polling. Idempotent.
+    void start();
+
+    /// Polls prices and pools now (joins a running poll); `done` gets the
+    /// per-source errors once it finishes.
+    void refresh(std::function<void(const QStringList& errors)> done = {});
+
+    /// {polled_at, coins[{coin, median, deviation_bps, threshold_bps,
+    ///  venues{venue: price}, breaching_since, open_event}], pools[{pool, name,
+    ///  coins[{coin, balance, share_pct, imbalance_pct}], breaching_since,
+    ///  open_events[]}], errors[]}
+    QJsonObject status() const;
+
+    static QJsonObject event_to_json(const DepegEvent& e);
+
+  signals:
+    void updated(QJsonObject status);
+    void depeg_started(QJsonObject event);
+    void depeg_ended(QJsonObject event);
+
+  private:
+    StablecoinPegService() = default;
+    Q_DISABLE_COPY(StablecoinPegService)
+
+    struct PoolState {
+        QString name;
+        QVector<CurvePoolBalance> balances;
+    };
+
+    void poll();
+    void fetch_prices(std::function<void(QVector<StablecoinPrice>, QStringList)> cb);
+    void fetch_pools(std::function<void(QHash<QString, PoolState>, QString)> cb);
+    void finish_poll(const QStringList& errors);
+    void evaluate(const Config& cfg, qint64 now);
+    /// Opens, updates or closes the episode for `key` given this poll's value.
+    void track(const QString& key, const QString& kind, const QString& coin, const QString& source, double value,
+               double threshold, bool breaching, int duration_min, const QJsonObject& detail, qint64 now);
+    void notify(const DepegEvent& e, bool started);
+    void load_open_events();
+
+    QNetworkAccessManager* nam_ = nullptr;
+    QTimer* timer_ = nullptr;
+    bool polling_ = false;
+    bool loaded_ = false;
+    int poll_count_ = 0;
+    QVector<std::function<void(const QStringList&)>> waiting_;
+    QHash<QString, QHash<QString, double>> prices_; // coin → venue → last price
+    QHash<QString, PoolState> pools_;               // address → last composition
+    QHash<QString, qint64> breach_since_;           // "price|USDT", "pool|addr|DAI" → first poll past threshold
+    QHash<QString, DepegEvent> open_;               // same keys → open episode
+    QStringList last_errors_;
+    qint64 last_poll_ms_ = 0;
+    qint64 last_prune_ms_ = 0;
+};
+
+} // namespace fincept::services
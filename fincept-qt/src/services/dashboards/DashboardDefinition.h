@@ -0,0 +1,102 @@
+// src/services/dashboards/DashboardDefinition.h
+#pragma once
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+#include <algorithm>
+
+namespace fincept::services::dashboards {
+
+/// One widget on a saved dashboard. `bindings` are DataHub topics
+/// ("market:quote:AAPL", "news:symbol:TSLA", …) the widget renders;
+/// `refresh_ms` forces a re-fetch at that cadence on top of each topic's
+/// own policy (0 = policy only).
+struct DashboardWidgetDef {
+    QString widget_id; // unique within the dashboard
+    QString widget_type;
+    QString title;
+    QStringList bindings;
+    int refresh_ms = 0;
+    int x = 0, y = 0, w = 4, h = 4;
+    QJsonObject config;
+};
+
+struct DashboardDef {
+    QString id;
+    QString name;
+    QString description;
+    QVector<DashboardWidgetDef> widgets;
+    QString created_at;
+    QString updated_at;
+
+    /// Union of every widget's bindings, first-seen order.
+    QStringList topics() const {
+        QStringList out;
+        for (const auto& w : widgets)
+            for (const auto& t : w.bindings)
+                if (!out.contains(t))
+                    out << t;
+        return out;
+    }
+};
+
+// ── JSON round-trip (MCP tools, import/export) ──────────────────────────────
+
+inline QJsonObject widget_def_to_json(const DashboardWidgetDef& w) {
+    return QJsonObject{{"widget_id", w.widget_id},
+                       {"widget_type", w.widget_type},
+                       {"title", w.title},
+                       {"bindings", QJsonArray::fromStringList(w.bindings)},
+                       {"refresh_ms", w.refresh_ms},
+                       {"x", w.x},
+                       {"y", w.y},
+                       {"w", w.w},
+                       {"h", w.h},
+                       {"config", w.config}};
+}
+
+inline DashboardWidgetDef widget_def_from_json(const QJsonObject& o) {
+    DashboardWidgetDef w;
+    w.widget_id = o.value("widget_id").toString();
+    w.widget_type = o.value("widget_type").toString();
+    w.title = o.value("title").toString();
+    for (const auto& v : o.value("bindings").toArray()) {
+        const QString t = v.toString().trimmed();
+        if (!t.isEmpty() && !w.bindings.contains(t))
+            w.bindings << t;
+    }
+    w.refresh_ms = std::max(0, o.value("refresh_ms").toInt());
+    w.x = o.value("x").toInt(0);
+    w.y = o.value("y").toInt(0);
+    w.w = o.value("w").toInt(4);
+    w.h = o.value("h").toInt(4);
+    w.config = o.value("config").toObject();
+    return w;
+}
+
+inline QJsonObject dashboard_def_to_json(const DashboardDef& d) {
+    QJsonArray widgets;
+    for (const auto& w : d.widgets)
+        widgets.append(widget_def_to_json(w));
+    return QJsonObject{{"id", d.id},
+                       {"name", d.name},
+                       {"description", d.description},
+                       {"widgets", widgets},
+                       {"created_at", d.created_at},
+                       {"updated_at", d.updated_at}};
+}
+
+inline DashboardDef dashboard_def_from_json(const QJsonObject& o) {
+    DashboardDef d;
+    d.id = o.value("id").toString();
+    d.name = o.value("name").toString();
+    d.description = o.value("description").toString();
+    for (const auto& v : o.value("widgets").toArray())
+        d.widgets.append(widget_def_from_json(v.toObject()));
+    return d;
+}
+
+} // namespace fincept::services::dashboards
@@ -0,0 +1,201 @@
+// src/services/dashboards/DashboardRunner.cpp
+#include "services/dashboards/DashboardRunner.h"
+
+#include "core/logging/Logger.h"
+#include "datahub/DataHub.h"
+#include "storage/repositories/DashboardDefinitionRepository.h"
+
+#include <QDateTime>
+#include <QMap>
+#include <QTimer>
+
+namespace fincept::services::dashboards {
+
+DashboardRunner& DashboardRunner::instance() {
+    static DashboardRunner s;
+    return s;
+}
+
+DashboardRunner::DashboardRunner() {
+    // Consolidated payloads are pushed by the runner, never pulled.
+    datahub::TopicPolicy policy;
+    policy.push_only = true;
+    datahub::DataHub::instance().set_policy_pattern(QStringLiteral("dashboard:*"), policy);
+}
+
+bool DashboardRunner::start(const QString& dashboard_id, QString* error) {
+    if (auto it = runs_.find(dashboard_id); it != runs_.end()) {
+        ++(*it)->refs;
+        return true;
+    }
+    auto def = DashboardDefinitionRepository::instance().get(dashboard_id);
+    if (def.is_err()) {
+        if (error)
+            *error = QString::fromStdString(def.error());
+        return false;
+    }
+    auto run = std::make_shared<Run>();
+    run->def = def.value();
+    run->refs = 1;
+    runs_.insert(dashboard_id, run);
+    attach(dashboard_id, *run);
+    LOG_INFO("DashboardRunner", QString("Started '%1' (%2 widgets, %3 topics)")
+                                    .arg(run->def.name)
+                                    .arg(run->def.widgets.size())
+                                    .arg(run->def.topics().size()));
+    return true;
+}
+
+void DashboardRunner::stop(const QString& dashboard_id) {
+    auto it = runs_.find(dashboard_id);
+    if (it == runs_.end())
+        return;
+    if (--(*it)->refs > 0)
+        return;
+    detach(**it);
+    runs_.erase(it);
+    LOG_INFO("DashboardRunner", "Stopped " + dashboard_id);
+    emit dashboard_stopped(dashboard_id);
+}
+
+void DashboardRunner::refresh(const QString& dashboard_id, bool force) {
+    auto it = runs_.constFind(dashboard_id);
+    if (it == runs_.constEnd())
+        return;
+    const QStringList topics = (*it)->def.topics();
+    if (!topics.isEmpty())
+        datahub::DataHub::instance().request(topics, force);
+}
+
+void DashboardRunner::reload(const QString& dashboard_id) {
+    auto it = runs_.find(dashboard_id);
+    if (it == runs_.end())
+        return;
+    auto def = DashboardDefinitionRepository::instance().get(dashboard_id);
+    if (def.is_err()) {
+        LOG_WARN("DashboardRunner", "Reload failed for " + dashboard_id + ": " + QString::fromStdString(def.error()));
+        return;
+    }
+    Run& run = **it;
+    detach(run);
+    run.def = def.value();
+    // Values for topics that are no longer bound would otherwise linger in
+    // snapshot() forever.
+    const QStringList topics = run.def.topics();
+    for (auto v = run.values.begin(); v != run.values.end();)
+        v = topics.contains(v.key()) ? std::next(v) : run.values.erase(v);
+    run.errors.clear();
+    attach(dashboard_id, run);
+}
+
+QVariantMap DashboardRunner::snapshot(const QString& dashboard_id) const {
+    auto it = runs_.constFind(dashboard_id);
+    return it == runs_.constEnd() ? QVariantMap{} : (*it)->values;
+}
+
+QVariantMap DashboardRunner::errors(const QString& dashboard_id) const {
+    auto it = runs_.constFind(dashboard_id);
+    return it == runs_.constEnd() ? QVariantMap{} : (*it)->errors;
+}
+
+// ── Run lifecycle ───────────────────────────────────────────────────────────
+
+void DashboardRunner::attach(const QString& id, Run& run) {
+    auto& hub = datahub::DataHub::instance();
+    run.owner = new QObject(this);
+    run.flush_timer = new QTimer(run.owner);
+    run.flush_timer->setSingleShot(true);
+    run.flush_timer->setInterval(kPushCoalesceMs);
+    connect(run.flush_timer, &QTimer::timeout, this, [this, id]() { flush(id); });
+
+    const QStringList topics = run.def.topics();
+    for (const auto& topic : topics) {
+        // One subscription per topic per dashboard, however many widgets
+        // share it. The hub replays the cached value immediately if fresh.
+        hub.subscribe(run.owner, topic, [this, id, topic](const QVariant& v) {
+            auto it = runs_.find(id);
+            if (it == runs_.end())
+                return;
+            (*it)->values.insert(topic, v);
+            (*it)->errors.remove(topic);
+            mark_dirty(id, topic);
+        });
+        hub.subscribe_errors(run.owner, topic, [this, id, topic](const QString& err) {
+            auto it = runs_.find(id);
+            if (it == runs_.end())
+                return;
+            (*it)->errors.insert(topic, err);
+            mark_dirty(id, topic);
+        });
+    }
+
+    // Widget refresh cadences: one timer per distinct interval covering the
+    // union of topics bound at that interval.
+    QMap<int, QStringList> by_interval;
+    for (const auto& w : run.def.widgets) {
+        if (w.refresh_ms <= 0)
+            continue;
+        auto& list = by_interval[w.refresh_ms];
+        for (const auto& t : w.bindings)
+            if (!list.contains(t))
+                list << t;
+    }
+    for (auto it = by_interval.constBegin(); it != by_interval.constEnd(); ++it) {
+        auto* timer = new QTimer(run.owner);
+        timer->setInterval(it.key());
+        const QStringList interval_topics = it.value();
+        connect(timer, &QTimer::timeout, this,
+                [interval_topics]() { datahub::DataHub::instance().request(interval_topics); });
+        timer->start();
+    }
+
+    // Prefetch: anything not already fresh in the hub is fetched now rather
+    // than waiting for the scheduler's next pass.
+    if (!topics.isEmpty())
+        hub.request(topics);
+}
+
+void DashboardRunner::detach(Run& run) {
+    if (!run.owner)
+        return;
+    datahub::DataHub::instance().unsubscribe(run.owner);
+    delete run.owner; // also stops the flush + refresh timers
+    run.owner = nullptr;
+    run.flush_timer = nullptr;
+    run.dirty.clear();
+}
+
+void DashboardRunner::mark_dirty(const QString& id, const QString& topic) {
+    auto it = runs_.find(id);
+    if (it == runs_.end())
+        return;
+    (*it)->dirty.insert(topic);
+    if ((*it)->flush_timer && !(*it)->flush_timer->isActive())
+        (*it)->flush_timer->start();
+}
+
+void DashboardRunner::flush(const QString& id) {
+    auto it = runs_.find(id);
+    if (it == runs_.end() || (*it)->dirty.isEmpty())
+        return;
+    Run& run = **it;
+    QVariantMap changed;
+    QVariantMap errs;
+    for (const auto& topic : run.dirty) {
+        if (run.values.contains(topic))
+            changed.insert(topic, run.values.value(topic));
+        if (run.errors.contains(topic))
+            errs.insert(topic, run.errors.value(topic));
+    }
+    run.dirty.clear();
+
+    QVariantMap payload;
+    payload.insert("dashboard_id", id);
+    payload.insert("changed", changed);
+    payload.insert("errors", errs);
+    payload.insert("updated_at", QDateTime::currentMSecsSinceEpoch());
+    datahub::DataHub::instance().publish(topic_for(id), payload);
+    emit dashboard_updated(id, changed);
+}
+
+} // namespace fincept::services::dashboards
@@ -0,0 +1,79 @@
+// src/services/dashboards/DashboardRunner.h
+#pragma once
+#include "services/dashboards/DashboardDefinition.h"
+
+#include <QHash>
+#include <QObject>
+#include <QSet>
+#include <QVariantMap>
+
+#include <memory>
+
+class QTimer;
+
+namespace fincept::services::dashboards {
+
+/// Runs saved dashboards headlessly: subscribes to every bound DataHub topic
+/// once per dashboard (not per widget), prefetches them on start, re-requests
+/// each widget's topics at its refresh_ms, and pushes one consolidated update
+/// per dashboard — on the `dashboard:<id>` hub topic and via
+/// dashboard_updated — coalesced over kPushCoalesceMs.
+///
+/// start()/stop() are ref-counted so several consumers can share one run.
+class DashboardRunner : public QObject {
+    Q_OBJECT
+  public:
+    static DashboardRunner& instance();
+
+    /// Hub topic carrying the consolidated payload:
+    /// {dashboard_id, changed: {topic: value}, errors: {topic: msg}, updated_at}.
+    static QString topic_for(const QString& dashboard_id) { return "dashboard:" + dashboard_id; }
+
+    /// Load the definition and begin running it (or add a reference to an
+    /// existing run). Returns false with `error` set if it cannot be loaded.
+    bool start(const QString& dashboard_id, QString* error = nullptr);
+    /// Drop one reference; the run is torn down when the last one goes.
+    void stop(const QString& dashboard_id);
+    /// Re-request every bound topic now. `force` bypasses min_interval.
+    void refresh(const QString& dashboard_id, bool force = false);
+    /// Re-read the definition of a running dashboard after it was saved,
+    /// keeping its reference count.
+    void reload(const QString& dashboard_id);
+
+    bool is_running(const QString& dashboard_id) const { return runs_.contains(dashboard_id); }
+    QStringList running() const { return runs_.keys(); }
+
+    /// Latest value per bound topic (QVariant-wrapped as published).
+    QVariantMap snapshot(const QString& dashboard_id) const;
+    /// Last refresh error per bound topic; topics that recovered are absent.
+    QVariantMap errors(const QString& dashboard_id) const;
+
+    static constexpr int kPushCoalesceMs = 250;
+
+  signals:
+    void dashboard_updated(QString dashboard_id, QVariantMap changed);
+    void dashboard_stopped(QString dashboard_id);
+
+  private:
+    DashboardRunner();
+    Q_DISABLE_COPY(DashboardRunner)
+
+    struct Run {
+        DashboardDef def;
+        int refs = 0;
+        QObject* owner = nullptr; // hub subscription + timer lifetime
+        QTimer* flush_timer = nullptr;
+        QVariantMap values;
+        QVariantMap errors;
+        QSet<QString> dirty;
+    };
+
+    void attach(const QString& id, Run& run);
+    void detach(Run& run);
+    void mark_dirty(const QString& id, const QString& topic);
+    void flush(const QString& id);
+
+    QHash<QString, std::shared_ptr<Run>> runs_;
+};
+
+} // namespace fincept::services::dashboards
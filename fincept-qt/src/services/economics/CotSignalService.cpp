@@ -0,0 +1,241 @@
+// src/services/economics/CotSignalService.cpp
+#include "services/economics/CotSignalService.h"
+
+#include "core/events/EventBus.h"
+#include "core/logging/Logger.h"
+#include "python/PythonRunner.h"
+#include "services/notifications/NotificationService.h"
+#include "ui/notifications/NotificationService.h"
+
+#include <QDateTime>
+#include <QJsonDocument>
+#include <QPointer>
+#include <QUuid>
+
+#include <cmath>
+
+namespace fincept::services {
+
+using fincept::notifications::NotificationRequest;
+using fincept::notifications::NotificationService;
+using fincept::notifications::NotifLevel;
+using fincept::notifications::NotifTrigger;
+using fincept::ui::ToastService;
+
+CotSignalService& CotSignalService::instance() {
+    static CotSignalService s;
+    return s;
+}
+
+void CotSignalService::start() {
+    if (timer_)
+        return;
+    timer_ = new QTimer(this);
+    timer_->setInterval(kTickMs);
+    connect(timer_, &QTimer::timeout, this, &CotSignalService::tick);
+    timer_->start();
+    QTimer::singleShot(0, this, &CotSignalService::tick);
+}
+
+void CotSignalService::tick() {
+    auto rules = CotSignalRepository::instance().list_rules();
+    if (rules.is_err())
+        return;
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    for (const auto& r : rules.value()) {
+        if (r.active && !in_flight_.contains(r.id) && now - r.last_checked_at >= qint64(kCheckIntervalSec) * 1000)
+            check(r.id);
+    }
+}
+
+void CotSignalService::run_positioning(const QString& market, const QString& report_type, double years,
+                                       int zscore_window, Callback cb) {
+    QPointer<CotSignalService> self = this;
+    python::PythonRunner::instance().run(
+        "cftc_data.py",
+        {"cot_positioning", market, report_type, QString::number(years), QString::number(zscore_window)},
+        [self, cb](python::PythonResult r) {
+            if (!self)
+                return;
+            const QJsonObject o =
+                r.success ? QJsonDocument::fromJson(python::extract_json(r.output).toUtf8()).object() : QJsonObject();
+            if (!o.value("success").toBool(false)) {
+                // cftc_data.py reports failures as {"error": {"error": msg, ...}}
+                const QJsonValue err = o.value("error");
+                QString msg = err.isObject() ? err.toObject().value("error").toString() : err.toString();
+                if (msg.isEmpty())
+                    msg = r.error.isEmpty() ? QStringLiteral("cftc_data.py produced no result") : r.error;
+                cb({}, msg);
+                return;
+            }
+            cb(o.value("data").toObject(), {});
+        });
+}
+
+QString CotSignalService::positioning(const QString& market, const QString& report_type, double years,
+                                      int zscore_window) {
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    QPointer<CotSignalService> self = this;
+    run_positioning(market, report_type, years, zscore_window,
+                    [self, request_id](const QJsonObject& data, const QString& error) {
+                        if (!error.isEmpty())
+                            emit self->error_occurred(request_id, error);
+                        else
+                            emit self->positioning_ready(request_id, data);
+                    });
+    return request_id;
+}
+
+QString CotSignalService::check(const QString& rule_id) {
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    const auto rule = CotSignalRepository::instance().get_rule(rule_id);
+    if (!rule) {
+        QTimer::singleShot(0, this, [this, request_id, rule_id]() {
+            emit error_occurred(request_id, "Unknown COT signal rule: " + rule_id);
+        });
+        return request_id;
+    }
+    in_flight_.insert(rule_id);
+
+    QPointer<CotSignalService> self = this;
+    const CotSignalRule r = *rule;
+    run_positioning(r.market, r.report_type, r.years, r.zscore_window,
+                    [self, request_id, r](const QJsonObject& data, const QString& error) {
+                        self->in_flight_.remove(r.id);
+                        const qint64 now = QDateTime::currentMSecsSinceEpoch();
+                        if (!error.isEmpty()) {
+                            LOG_WARN("CotSignals", QString("Rule '%1' check failed: %2").arg(r.name, error));
+                            // Back off until the next check interval rather than retrying every tick.
+                            CotSignalRepository::instance().touch_rule_checked(r.id, now, r.last_report_date);
+                            emit self->error_occurred(request_id, error);
+                            return;
+                        }
+                        const QJsonObject group = data.value("groups").toObject().value(r.trader_group).toObject();
+                        if (group.isEmpty()) {
+                            const QString msg = QString("No '%1' positions in the %2 report for %3")
+                                                    .arg(r.trader_group, r.report_type, r.market);
+                            CotSignalRepository::instance().touch_rule_checked(r.id, now, r.last_report_date);
+                            emit self->error_occurred(request_id, msg);
+                            return;
+                        }
+                        const QJsonArray fired = self->evaluate(r, data, group);
+                        CotSignalRepository::instance().touch_rule_checked(r.id, now,
+                                                                           data.value("report_date").toString());
+                        emit self->rule_checked(request_id, r.id, group, fired);
+                    });
+    return request_id;
+}
+
+QJsonArray CotSignalService::evaluate(const CotSignalRule& rule, const QJsonObject& data, const QJsonObject& group) {
+    const QJsonValue index_v = group.value("cot_index");
+    const QJsonValue z_v = group.value("zscore");
+    const double index = index_v.toDouble();
+    const double z = z_v.toDouble();
+
+    QStringList kinds;
+    if (!index_v.isNull() && rule.index_high > 0 && index >= rule.index_high)
+        kinds << "extreme_long";
+    if (!index_v.isNull() && rule.index_low > 0 && index <= rule.index_low)
+        kinds << "extreme_short";
+    if (!z_v.isNull() && rule.zscore_abs > 0 && std::abs(z) >= rule.zscore_abs)
+        kinds << "zscore_spike";
+
+    QVector<CotSignal> fresh;
+    QJsonArray out;
+    for (const QString& kind : kinds) {
+        CotSignal s;
+        s.id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+        s.rule_id = rule.id;
+        s.market = data.value("market").toString();
+        s.contract_code = data.value("contract_code").toString();
+        s.report_type = rule.report_type;
+        s.trader_group = rule.trader_group;
+        s.kind = kind;
+        s.report_date = data.value("report_date").toString();
+        s.cot_index = index;
+        s.zscore = z;
+        s.net = qint64(group.value("net").toDouble());
+        s.detail = group;
+        s.detail["open_interest"] = data.value("open_interest");
+        s.detected_at = QDateTime::currentMSecsSinceEpoch();
+        auto r = CotSignalRepository::instance().record_signal(s);
+        if (r.is_err() || !r.value())
+            continue; // already stored for this report
+        fresh.append(s);
+        const QJsonObject js = signal_to_json(s);
+        out.append(js);
+        emit signal_fired(js);
+        EventBus::instance().publish("cot.signal", js.toVariantMap());
+    }
+    if (!fresh.isEmpty())
+        notify(rule, fresh);
+    return out;
+}
+
+void CotSignalService::notify(const CotSignalRule& rule, const QVector<CotSignal>& fresh) {
+    QStringList lines;
+    for (const auto& s : fresh) {
+        if (s.kind == "zscore_spike")
+            lines.append(QString("%1 net change z=%2").arg(s.trader_group).arg(s.zscore, 0, 'f', 2));
+        else
+            lines.append(QString("%1 COT index %2 (%3)")
+                             .arg(s.trader_group)
+                             .arg(s.cot_index, 0, 'f', 1)
+                             .arg(s.kind == "extreme_long" ? QStringLiteral("extreme long")
+                                                           : QStringLiteral("extreme short")));
+    }
+    const QString msg = QString("%1 %2: %3").arg(fresh.first().market, fresh.first().report_date, lines.join("; "));
+    LOG_INFO("CotSignals", rule.name + " — " + msg);
+    if (rule.toast)
+        ToastService::instance().post(ToastService::Severity::Warning, rule.name + ": " + msg, "cot:" + rule.id);
+    if (rule.providers) {
+        NotificationRequest req;
+        req.title = QString("COT signal: %1").arg(rule.name);
+        req.message = msg;
+        req.level = NotifLevel::Warning;
+        req.trigger = NotifTrigger::PriceAlert;
+        NotificationService::instance().send(req);
+    }
+}
+
+QJsonObject CotSignalService::rule_to_json(const CotSignalRule& r) {
+    return QJsonObject{
+        {"id", r.id},
+        {"name", r.name},
+        {"market", r.market},
+        {"report_type", r.report_type},
+        {"trader_group", r.trader_group},
+        {"years", r.years},
+        {"zscore_window", r.zscore_window},
+        {"index_high", r.index_high},
+        {"index_low", r.index_low},
+        {"zscore_abs", r.zscore_abs},
+        {"toast", r.toast},
+        {"providers", r.providers},
+        {"active", r.active},
+        {"last_checked_at", r.last_checked_at > 0
+                                ? QJsonValue(QDateTime::fromMSecsSinceEpoch(r.last_checked_at).toString(Qt::ISODate))
+                                : QJsonValue()},
+        {"last_report_date", r.last_report_date.isEmpty() ? QJsonValue() : QJsonValue(r.last_report_date)},
+    };
+}
+
+QJsonObject CotSignalService::signal_to_json(const CotSignal& s) {
+    return QJsonObject{
+        {"id", s.id},
+        {"rule_id", s.rule_id},
+        {"market", s.market},
+        {"contract_code", s.contract_code},
+        {"report_type", s.report_type},
+        {"trader_group", s.trader_group},
+        {"kind", s.kind},
+        {"report_date", s.report_date},
+        {"cot_index", s.cot_index},
+        {"zscore", s.zscore},
+        {"net", s.net},
+        {"detail", s.detail},
+        {"detected_at", QDateTime::fromMSecsSinceEpoch(s.detected_at).toString(Qt::ISODate)},
+    };
+}
+
+} // namespace fincept::services
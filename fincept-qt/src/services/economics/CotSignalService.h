@@ -0,0 +1,75 @@
+// src/services/economics/CotSignalService.h
+#pragma once
+#include "storage/repositories/CotSignalRepository.h"
+
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+#include <QSet>
+#include <QString>
+#include <QTimer>
+
+#include <functional>
+
+namespace fincept::services {
+
+/// Derived analytics over the CFTC Commitments of Traders report
+/// (cftc_data.py cot_positioning): per trader group net positioning, the COT
+/// index (net normalised to 0-100 over N years) and the z-score of the
+/// week-over-week change in net.
+///
+/// Signal rules (CotSignalRepository) are re-checked every kCheckIntervalSec;
+/// a new report whose COT index leaves the rule's band or whose z-score
+/// exceeds it is stored once per rule, kind and report date, then pushed
+/// through signal_fired, the `cot.signal` EventBus event and the rule's
+/// toast / notification providers. Main thread only.
+class CotSignalService : public QObject {
+    Q_OBJECT
+  public:
+    static constexpr int kTickMs = 60 * 60 * 1000;        // how often due rules are looked for
+    static constexpr int kCheckIntervalSec = 6 * 60 * 60; // re-check a rule at most this often
+
+    static CotSignalService& instance();
+
+    /// Starts checking active rules. Idempotent.
+    void start();
+
+    /// Computes positioning for `market`; returns the request id
+    /// positioning_ready / error_occurred carry.
+    QString positioning(const QString& market, const QString& report_type = QStringLiteral("legacy"),
+                        double years = 3, int zscore_window = 52);
+
+    /// Checks rule `rule_id` now; returns the request id rule_checked /
+    /// error_occurred carry.
+    QString check(const QString& rule_id);
+
+    static QJsonObject rule_to_json(const CotSignalRule& r);
+    static QJsonObject signal_to_json(const CotSignal& s);
+
+  signals:
+    /// {market, contract_code, report_type, report_date, open_interest,
+    ///  groups{<group>{net, net_change, net_pct_oi, cot_index, zscore, range_min, range_max}}, history{...}}
+    void positioning_ready(QString request_id, QJsonObject data);
+    /// `group` is the rule's trader group snapshot; `fired` holds only the
+    /// signals this check stored for the first time.
+    void rule_checked(QString request_id, QString rule_id, QJsonObject group, QJsonArray fired);
+    void signal_fired(QJsonObject signal);
+    void error_occurred(QString request_id, QString message);
+
+  private:
+    CotSignalService() = default;
+    Q_DISABLE_COPY(CotSignalService)
+
+    using Callback = std::function<void(const QJsonObject& data, const QString& error)>;
+
+    void tick();
+    void run_positioning(const QString& market, const QString& report_type, double years, int zscore_window,
+                         Callback cb);
+    QJsonArray evaluate(const CotSignalRule& rule, const QJsonObject& data, const QJsonObject& group);
+    void notify(const CotSignalRule& rule, const QVector<CotSignal>& fresh);
+
+    QTimer* timer_ = nullptr;
+    QSet<QString> in_flight_; // rule ids being checked
+};
+
+} // namespace fincept::services
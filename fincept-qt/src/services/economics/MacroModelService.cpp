@@ -0,0 +1,100 @@
+// src/services/economics/MacroModelService.cpp
+#include "services/economics/MacroModelService.h"
+
+#include "core/events/EventBus.h"
+#include "core/logging/Logger.h"
+#include "python/PythonRunner.h"
+#include "storage/cache/CacheManager.h"
+
+#include <QDateTime>
+#include <QJsonDocument>
+#include <QPointer>
+#include <QUuid>
+
+namespace fincept::services {
+
+namespace {
+
+constexpr const char* kMmCacheKey = "macro_models:latest";
+
+} // namespace
+
+MacroModelService& MacroModelService::instance() {
+    static MacroModelService s;
+    return s;
+}
+
+QJsonObject MacroModelService::latest() {
+    if (!loaded_) {
+        loaded_ = true;
+        if (const auto cached = CacheManager::instance().try_get(kMmCacheKey))
+            latest_ = QJsonDocument::fromJson(cached->toUtf8()).object();
+    }
+    return latest_;
+}
+
+bool MacroModelService::is_fresh() {
+    const QDateTime at = QDateTime::fromString(latest().value("computed_at").toString(), Qt::ISODate);
+    return at.isValid() && at.secsTo(QDateTime::currentDateTimeUtc()) < kStaleSecs;
+}
+
+QString MacroModelService::refresh() {
+    if (!running_request_.isEmpty())
+        return running_request_;
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    running_request_ = request_id;
+
+    QPointer<MacroModelService> self = this;
+    python::PythonRunner::instance().run(
+        "Analytics/economics/macro_models.py", {"all", QString::number(kHistoryMonths)},
+        [self, request_id](python::PythonResult r) {
+            if (!self)
+                return;
+            self->running_request_.clear();
+            const QJsonObject o =
+                r.success ? QJsonDocument::fromJson(python::extract_json(r.output).toUtf8()).object() : QJsonObject();
+            if (!o.value("success").toBool(false)) {
+                QString msg = o.value("error").toString();
+                if (msg.isEmpty())
+                    msg = r.error.isEmpty() ? QStringLiteral("macro_models.py produced no result") : r.error;
+                LOG_WARN("MacroModels", "Run failed: " + msg);
+                emit self->error_occurred(request_id, msg);
+                return;
+            }
+            self->latest_ = o.value("data").toObject();
+            self->loaded_ = true;
+            CacheManager::instance().put(
+                kMmCacheKey, QString::fromUtf8(QJsonDocument(self->latest_).toJson(QJsonDocument::Compact)),
+                kCacheTtlSec, "macro_models");
+
+            const QJsonObject rec = self->latest_.value("recession").toObject();
+            const QJsonObject now = self->latest_.value("nowcast").toObject();
+            LOG_INFO("MacroModels", QString("Recession probability %1 (as of %2), %3 GDP nowcast %4%")
+                                        .arg(rec.value("probability").toDouble(), 0, 'f', 3)
+                                        .arg(rec.value("as_of").toString(), now.value("quarter").toString())
+                                        .arg(now.value("nowcast_saar_pct").toDouble(), 0, 'f', 2));
+            emit self->updated(request_id, self->latest_);
+            EventBus::instance().publish("macro_models.updated",
+                                         QVariantMap{{"recession_probability", rec.value("probability").toDouble()},
+                                                     {"nowcast_quarter", now.value("quarter").toString()},
+                                                     {"nowcast_saar_pct", now.value("nowcast_saar_pct").toDouble()}});
+        });
+    return request_id;
+}
+
+void MacroModelService::start_auto_refresh() {
+    if (auto_timer_)
+        return;
+    auto_timer_ = new QTimer(this);
+    auto_timer_->setInterval(kAutoCheckMs);
+    connect(auto_timer_, &QTimer::timeout, this, &MacroModelService::auto_refresh);
+    auto_timer_->start();
+}
+
+void MacroModelService::auto_refresh() {
+    if (latest().isEmpty() || is_fresh())
+        return;
+    refresh();
+}
+
+} // namespace fincept::services
@@ -0,0 +1,64 @@
+// src/services/economics/MacroModelService.h
+#pragma once
+#include <QJsonObject>
+#include <QObject>
+#include <QString>
+#include <QTimer>
+
+namespace fincept::services {
+
+/// Macro models over FRED data (Analytics/economics/macro_models.py):
+///
+///   recession — probit probability of an NBER recession 12 months ahead
+///               from the 10Y-3M spread, initial claims growth and the
+///               Philly/Empire Fed activity indexes (plus the classic
+///               spread-only model), with its monthly history.
+///   nowcast   — current-quarter real GDP growth (SAAR %) from bridge
+///               equations on industrial production, payrolls, real retail
+///               sales and claims.
+///
+/// Results are kept in CacheManager so the macro dashboard opens on the last
+/// run; once a run has succeeded, the service re-runs whenever it is older
+/// than kStaleSecs so new FRED releases are picked up. Main thread only.
+class MacroModelService : public QObject {
+    Q_OBJECT
+  public:
+    static constexpr int kHistoryMonths = 240;
+    static constexpr int kAutoCheckMs = 6 * 60 * 60 * 1000; // 6 h
+    static constexpr int kStaleSecs = 24 * 60 * 60;         // re-run results older than this
+    static constexpr int kCacheTtlSec = 30 * 24 * 60 * 60;
+
+    static MacroModelService& instance();
+
+    /// Last result {recession{...}, nowcast{...}, computed_at}, empty before
+    /// the first successful run.
+    QJsonObject latest();
+
+    /// True when latest() is younger than kStaleSecs.
+    bool is_fresh();
+
+    /// Re-runs both models; returns the request id updated / error_occurred
+    /// carry. A run already in flight is joined rather than restarted.
+    QString refresh();
+
+    /// 6-hourly check that re-runs stale results (only after a first
+    /// successful run — the models need FRED_API_KEY).
+    void start_auto_refresh();
+
+  signals:
+    void updated(QString request_id, QJsonObject result);
+    void error_occurred(QString request_id, QString message);
+
+  private:
+    MacroModelService() = default;
+    Q_DISABLE_COPY(MacroModelService)
+
+    void auto_refresh();
+
+    QJsonObject latest_;
+    bool loaded_ = false;
+    QString running_request_; // non-empty while a run is in flight
+    QTimer* auto_timer_ = nullptr;
+};
+
+} // namespace fincept::services
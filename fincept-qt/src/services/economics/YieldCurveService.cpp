@@ -0,0 +1,265 @@
+// src/services/economics/YieldCurveService.cpp
+#include "services/economics/YieldCurveService.h"
+
+#include "core/events/EventBus.h"
+#include "core/logging/Logger.h"
+#include "python/PythonRunner.h"
+
+#include <QDate>
+#include <QDateTime>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QPointer>
+#include <QTimeZone>
+#include <QUuid>
+#include <QtConcurrent>
+
+#include <algorithm>
+#include <cmath>
+#include <optional>
+
+namespace fincept::services {
+
+namespace {
+
+/// (1−e^(−x))/x and its hump counterpart, with the t → 0 limits.
+double yc_slope_loading(double t, double tau) {
+    if (tau <= 0)
+        return 0;
+    const double x = t / tau;
+    return x < 1e-8 ? 1.0 : (1 - std::exp(-x)) / x;
+}
+
+double yc_hump_loading(double t, double tau) {
+    if (tau <= 0)
+        return 0;
+    return yc_slope_loading(t, tau) - std::exp(-t / tau);
+}
+
+/// curve_history.py output → fits, off the UI thread.
+QVector<YieldCurveFit> yc_parse_fits(const QJsonObject& data, const QString& source, const QString& model) {
+    QVector<YieldCurveFit> out;
+    for (const auto& v : data["fits"].toArray()) {
+        const QJsonObject o = v.toObject();
+        const QJsonObject p = o["parameters"].toObject();
+        YieldCurveFit f;
+        f.source = source;
+        f.model = model;
+        f.date = o["date"].toString().left(10);
+        f.beta0 = p["beta0"].toDouble();
+        f.beta1 = p["beta1"].toDouble();
+        f.beta2 = p["beta2"].toDouble();
+        f.beta3 = p["beta3"].toDouble();
+        f.tau1 = p["tau1"].toDouble();
+        f.tau2 = p["tau2"].toDouble();
+        f.rmse = o["rmse"].toDouble();
+        for (const auto& pt : o["observed"].toArray()) {
+            const QJsonObject po = pt.toObject();
+            f.observed.append({po["maturity"].toDouble(), po["yield"].toDouble()});
+        }
+        if (QDate::fromString(f.date, Qt::ISODate).isValid() && f.tau1 > 0)
+            out.append(f);
+    }
+    return out;
+}
+
+} // namespace
+
+YieldCurveService& YieldCurveService::instance() {
+    static YieldCurveService s;
+    return s;
+}
+
+bool YieldCurveService::is_source(const QString& source) {
+    return source == "treasury" || source == "ecb";
+}
+
+bool YieldCurveService::is_model(const QString& model) {
+    return model == "nelson_siegel" || model == "svensson";
+}
+
+double YieldCurveService::yield_at(const YieldCurveFit& f, double t) {
+    double y = f.beta0 + f.beta1 * yc_slope_loading(t, f.tau1) + f.beta2 * yc_hump_loading(t, f.tau1);
+    if (f.model == "svensson")
+        y += f.beta3 * yc_hump_loading(t, f.tau2);
+    return y;
+}
+
+QJsonObject YieldCurveService::factors_to_json(const YieldCurveFit& f) {
+    const double y2 = yield_at(f, 2), y10 = yield_at(f, 10);
+    QJsonObject o{{"date", f.date},
+                  {"level", f.beta0},
+                  {"slope", -f.beta1},
+                  {"curvature", f.beta2},
+                  {"tau1", f.tau1},
+                  {"rmse", f.rmse},
+                  {"y_3m", yield_at(f, 0.25)},
+                  {"y_2y", y2},
+                  {"y_10y", y10},
+                  {"spread_2s10s", y10 - y2}};
+    if (f.model == "svensson") {
+        o["beta3"] = f.beta3;
+        o["tau2"] = f.tau2;
+    }
+    return o;
+}
+
+QJsonObject YieldCurveService::frame_to_json(const YieldCurveFit& f, const QVector<double>& maturities,
+                                             bool observed) {
+    QJsonArray ts, ys;
+    for (double t : maturities) {
+        ts.append(t);
+        ys.append(yield_at(f, t));
+    }
+    QJsonObject o{{"date", f.date}, {"maturities", ts}, {"fitted", ys}};
+    if (observed) {
+        QJsonArray pts;
+        for (const auto& p : f.observed)
+            pts.append(QJsonArray{p.first, p.second});
+        o["observed"] = pts;
+    }
+    return o;
+}
+
+QString YieldCurveService::update(const QString& source, const QString& model, bool backfill, int days) {
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    auto fail = [this, request_id](const QString& msg) {
+        QMetaObject::invokeMethod(
+            this, [this, request_id, msg]() { emit error_occurred(request_id, msg); }, Qt::QueuedConnection);
+        return request_id;
+    };
+    if (!is_source(source))
+        return fail("Unknown curve source: " + source + " (treasury, ecb)");
+    if (!is_model(model))
+        return fail("Unknown curve model: " + model + " (nelson_siegel, svensson)");
+    const QString key = source + '/' + model;
+    if (running_.contains(key))
+        return fail("A fit of " + key + " is already running");
+
+    // Continue after the last stored fit, seeded by its taus; a new series or
+    // a backfill starts `days` back, seeded by whatever precedes that.
+    auto& repo = YieldCurveRepository::instance();
+    const QDate today = QDate::currentDate();
+    std::optional<YieldCurveFit> last;
+    if (!backfill)
+        last = repo.last_fit(source, model);
+    QDate from;
+    std::optional<YieldCurveFit> seed;
+    if (last) {
+        from = QDate::fromString(last->date, Qt::ISODate).addDays(1);
+        seed = last;
+    } else {
+        from = today.addDays(-std::clamp(days, 1, kMaxBackfillDays));
+        seed = repo.last_fit(source, model, from.toString(Qt::ISODate));
+    }
+    if (from > today) {
+        QMetaObject::invokeMethod(
+            this,
+            [this, request_id, source, model, from]() {
+                emit updated(request_id, source, model,
+                             QJsonObject{{"source", source},
+                                         {"model", model},
+                                         {"from_date", from.toString(Qt::ISODate)},
+                                         {"rows_written", 0}});
+            },
+            Qt::QueuedConnection);
+        return request_id;
+    }
+
+    QStringList args{source, model, from.toString(Qt::ISODate), today.toString(Qt::ISODate)};
+    if (seed) {
+        QStringList taus{QString::number(seed->tau1, 'g', 10)};
+        if (model == "svensson" && seed->tau2 > 0)
+            taus.append(QString::number(seed->tau2, 'g', 10));
+        args.append(taus.join(','));
+    }
+
+    running_.insert(key);
+    attempted_ms_[key] = QDateTime::currentMSecsSinceEpoch();
+    QPointer<YieldCurveService> self = this;
+    python::PythonRunner::instance().run(
+        "Analytics/fixedIncome/curve_history.py", args,
+        [self, request_id, source, model, key, from](python::PythonResult r) {
+            if (!self)
+                return;
+            if (!r.success) {
+                self->running_.remove(key);
+                emit self->error_occurred(request_id, r.error.isEmpty() ? "curve_history.py failed" : r.error);
+                return;
+            }
+            (void)QtConcurrent::run([self, request_id, source, model, key, from, out = r.output]() {
+                const QJsonObject o = QJsonDocument::fromJson(python::extract_json(out).toUtf8()).object();
+                QString error;
+                if (o.isEmpty())
+                    error = QStringLiteral("no JSON in curve_history.py output");
+                else if (!o.value("success").toBool(false))
+                    error = o.value("error").toString("curve fit failed");
+                const QVector<YieldCurveFit> fits = yc_parse_fits(o["data"].toObject(), source, model);
+                QMetaObject::invokeMethod(
+                    self.data(),
+                    [self, request_id, source, model, key, from, fits, error]() {
+                        if (!self)
+                            return;
+                        self->running_.remove(key);
+                        if (!error.isEmpty()) {
+                            emit self->error_occurred(request_id, error);
+                            return;
+                        }
+                        auto w = YieldCurveRepository::instance().upsert_fits(fits);
+                        if (w.is_err()) {
+                            const QString msg = "Failed to store curve fits: " + QString::fromStdString(w.error());
+                            emit self->error_occurred(request_id, msg);
+                            return;
+                        }
+                        QJsonObject summary{{"source", source},
+                                            {"model", model},
+                                            {"from_date", from.toString(Qt::ISODate)},
+                                            {"rows_written", int(fits.size())}};
+                        if (!fits.isEmpty()) {
+                            summary["first_date"] = fits.first().date;
+                            summary["last_date"] = fits.last().date;
+                            summary["latest"] = factors_to_json(fits.last());
+                        }
+                        LOG_INFO("YieldCurve", QString("%1: %2 fits written from %3")
+                                                   .arg(key)
+                                                   .arg(fits.size())
+                                                   .arg(from.toString(Qt::ISODate)));
+                        emit self->updated(request_id, source, model, summary);
+                        EventBus::instance().publish(
+                            "yield_curve.fitted",
+                            QVariantMap{{"source", source}, {"model", model}, {"rows", int(fits.size())}});
+                    },
+                    Qt::QueuedConnection);
+            });
+        });
+    return request_id;
+}
+
+void YieldCurveService::start_auto_update() {
+    if (auto_timer_)
+        return;
+    auto_timer_ = new QTimer(this);
+    auto_timer_->setInterval(kAutoCheckMs);
+    connect(auto_timer_, &QTimer::timeout, this, &YieldCurveService::auto_update);
+    auto_timer_->start();
+}
+
+void YieldCurveService::auto_update() {
+    auto series = YieldCurveRepository::instance().list_series();
+    if (series.is_err())
+        return;
+    const QDateTime now = QDateTime::currentDateTimeUtc();
+    for (const auto& s : series.value()) {
+        const QString key = s.source + '/' + s.model;
+        // Weekends and holidays write nothing — don't refetch every hour.
+        if (running_.contains(key) || now.toMSecsSinceEpoch() - attempted_ms_.value(key) < kStaleSecs * 1000LL)
+            continue;
+        QDateTime last = QDateTime::fromString(s.last_fitted_at, "yyyy-MM-dd HH:mm:ss");
+        last.setTimeZone(QTimeZone::utc());
+        if (last.isValid() && last.secsTo(now) < kStaleSecs)
+            continue;
+        update(s.source, s.model);
+    }
+}
+
+} // namespace fincept::services
@@ -0,0 +1,79 @@
+// src/services/economics/YieldCurveService.h
+#pragma once
+#include "storage/repositories/YieldCurveRepository.h"
+
+#include <QHash>
+#include <QJsonObject>
+#include <QObject>
+#include <QSet>
+#include <QString>
+#include <QTimer>
+#include <QVector>
+
+namespace fincept::services {
+
+/// Daily Nelson-Siegel / Svensson fits of government yield curves, stored
+/// per (source, model) in YieldCurveRepository:
+///
+///   treasury — US Treasury constant maturities (Fed H.15, 1M-30Y)
+///   ecb      — euro area AAA spot curve (3M-30Y)
+///
+/// An update runs Analytics/fixedIncome/curve_history.py from the day after
+/// the last stored fit (or `days` back for a new series / backfill), seeding
+/// its tau search with the previous fit's so factor series stay continuous.
+///
+///   y(t) = β0 + β1·(1−e^(−t/τ1))/(t/τ1) + β2·[(1−e^(−t/τ1))/(t/τ1) − e^(−t/τ1)]
+///          + β3·[(1−e^(−t/τ2))/(t/τ2) − e^(−t/τ2)]          (Svensson only)
+///
+/// Factors: level = β0 (long end), slope = −β1 (long minus short end),
+/// curvature = β2 (medium-term hump). Main thread only.
+class YieldCurveService : public QObject {
+    Q_OBJECT
+  public:
+    static constexpr int kDefaultBackfillDays = 365;
+    static constexpr int kMaxBackfillDays = 3650;
+    static constexpr int kAutoCheckMs = 60 * 60 * 1000; // hourly
+    static constexpr int kStaleSecs = 12 * 60 * 60;     // auto-update series older than this
+
+    static YieldCurveService& instance();
+
+    static bool is_source(const QString& source);
+    static bool is_model(const QString& model);
+
+    /// Starts a fit run; returns its request id, which updated and
+    /// error_occurred carry. `backfill` refits the last `days` of history.
+    QString update(const QString& source, const QString& model, bool backfill = false,
+                   int days = kDefaultBackfillDays);
+
+    /// Hourly check that brings every stored series not written within
+    /// kStaleSecs up to date.
+    void start_auto_update();
+
+    /// Model yield (decimal) at maturity `t` years.
+    static double yield_at(const YieldCurveFit& f, double t);
+
+    /// {date, level, slope, curvature, beta3?, tau1, tau2?, rmse,
+    ///  y_3m, y_2y, y_10y, spread_2s10s}
+    static QJsonObject factors_to_json(const YieldCurveFit& f);
+
+    /// {date, maturities[], fitted[], observed?[[t, y]]} — one animation frame.
+    static QJsonObject frame_to_json(const YieldCurveFit& f, const QVector<double>& maturities, bool observed);
+
+  signals:
+    /// {source, model, from_date, rows_written, first_date, last_date,
+    ///  latest{factors_to_json}}
+    void updated(QString request_id, QString source, QString model, QJsonObject summary);
+    void error_occurred(QString request_id, QString message);
+
+  private:
+    YieldCurveService() = default;
+    Q_DISABLE_COPY(YieldCurveService)
+
+    void auto_update();
+
+    QSet<QString> running_;               // "source/model" with a run in flight
+    QHash<QString, qint64> attempted_ms_; // "source/model" → last run start
+    QTimer* auto_timer_ = nullptr;
+};
+
+} // namespace fincept::services
@@ -277,13 +277,100 @@ void EquityResearchService::load_historical_only(const QString& symbol, const QS
 void EquityResearchService::load_symbol(const QString& symbol, const QString& period) {
     if (symbol.isEmpty())
         return;
+    if (period == QLatin1String("1y") && deep_dive_inflight_.contains(symbol))
+        return; // the deep dive emits quote/info/candles for this symbol
     load_quote_only(symbol);
     load_info_only(symbol);
     load_historical_only(symbol, period);
 }
 
+// ── Deep dive (all slices from one process) ───────────────────────────────────
+void EquityResearchService::load_deep_dive(const QString& symbol, bool force_refresh) {
+    if (symbol.isEmpty())
+        return;
+    const QString cache_key = "equity:deep_dive:" + symbol;
+    if (!force_refresh) {
+        const QVariant dcv = fincept::CacheManager::instance().get(cache_key);
+        if (!dcv.isNull()) {
+            apply_deep_dive(symbol, QJsonDocument::fromJson(dcv.toString().toUtf8()).object());
+            return;
+        }
+    }
+    if (deep_dive_inflight_.contains(symbol))
+        return; // the running fetch emits for every listener
+    deep_dive_inflight_.insert(symbol);
+
+    run_python("symbol_deep_dive.py", {symbol, "1y"}, [this, symbol, cache_key](bool ok, const QString& out) {
+        deep_dive_inflight_.remove(symbol);
+        if (!ok) {
+            // Fall back to the per-slice loaders so the page still fills in.
+            LOG_WARN("EquityResearch", "Deep dive failed for " + symbol + ", falling back to per-slice loads");
+            emit error_occurred("DeepDive", "Failed to load deep dive for " + symbol);
+            load_symbol(symbol);
+            return;
+        }
+        auto obj = QJsonDocument::fromJson(python::extract_json(out).toUtf8()).object();
+        if (obj.contains("error")) {
+            emit error_occurred("DeepDive", obj["error"].toString());
+            load_symbol(symbol);
+            return;
+        }
+        // Only cache a payload whose core slice landed; a quote-less result is
+        // usually a transient Yahoo failure and should be retried next time.
+        if (obj["quote"].isObject()) {
+            fincept::CacheManager::instance().put(
+                cache_key, QVariant(QString::fromUtf8(QJsonDocument(obj).toJson(QJsonDocument::Compact))),
+                kDeepDiveTtlSec, "equity");
+        }
+        apply_deep_dive(symbol, obj);
+    });
+}
+
+void EquityResearchService::apply_deep_dive(const QString& symbol, const QJsonObject& payload) {
+    auto& cache = fincept::CacheManager::instance();
+    auto compact = [](const auto& json) {
+        return QVariant(QString::fromUtf8(QJsonDocument(json).toJson(QJsonDocument::Compact)));
+    };
+    const QJsonObject errors = payload["errors"].toObject();
+
+    if (payload["quote"].isObject()) {
+        const QJsonObject q = payload["quote"].toObject();
+        cache.put("equity:quote:" + symbol, compact(q), kQuoteTtlSec, "equity");
+        emit quote_loaded(parse_quote(q));
+    } else {
+        emit error_occurred("Quote", errors["quote"].toString("Failed to fetch quote for " + symbol));
+    }
+
+    if (payload["fundamentals"].isObject()) {
+        const QJsonObject info = payload["fundamentals"].toObject();
+        cache.put("equity:info:" + symbol, compact(info), kInfoTtlSec, "equity");
+        emit info_loaded(parse_info(info));
+    }
+
+    const QJsonArray history = payload["history"].toArray();
+    if (!history.isEmpty()) {
+        cache.put("equity:candles:" + symbol + ":1y", compact(history), kHistoricalTtlSec, "equity");
+        emit historical_loaded(symbol, parse_candles(history));
+    }
+
+    if (payload["financials"].isObject()) {
+        const QJsonObject fin = payload["financials"].toObject();
+        cache.put("equity:financials:" + symbol, compact(fin), kFinancialsTtlSec, "equity");
+        emit financials_loaded(parse_financials(fin));
+    }
+
+    emit deep_dive_loaded(symbol, payload);
+}
+
 // ── Financials ────────────────────────────────────────────────────────────────
 void EquityResearchService::fetch_financials(const QString& symbol) {
+    const QVariant fcv = fincept::CacheManager::instance().get("equity:financials:" + symbol);
+    if (!fcv.isNull()) {
+        emit financials_loaded(parse_financials(QJsonDocument::fromJson(fcv.toString().toUtf8()).object()));
+        return;
+    }
+    if (deep_dive_inflight_.contains(symbol))
+        return; // financials arrive with the deep dive
     run_python("yfinance_data.py", {"financials", symbol}, [this, symbol](bool ok, const QString& out) {
         if (!ok) {
             emit error_occurred("Financials", "Failed to fetch financials for " + symbol);
@@ -294,6 +381,10 @@ void EquityResearchService::fetch_financials(const QString& symbol) {
             emit error_occurred("Financials", obj["error"].toString());
             return;
         }
+        fincept::CacheManager::instance().put(
+            "equity:financials:" + symbol,
+            QVariant(QString::fromUtf8(QJsonDocument(obj).toJson(QJsonDocument::Compact))), kFinancialsTtlSec,
+            "equity");
         emit financials_loaded(parse_financials(obj));
     });
 }
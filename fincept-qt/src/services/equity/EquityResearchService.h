@@ -2,7 +2,9 @@
 #pragma once
 #include "services/equity/EquityResearchModels.h"
 
+#include <QJsonObject>
 #include <QObject>
+#include <QSet>
 #include <QTimer>
 
 #include <functional>
@@ -36,6 +38,14 @@ class EquityResearchService : public QObject {
     void load_info_only(const QString& symbol);
     void load_historical_only(const QString& symbol, const QString& period = "1y");
 
+    /// Everything the research page needs for `symbol` from one Python process
+    /// (symbol_deep_dive.py): quote, fundamentals, 1y candles, financials, key
+    /// levels, insider activity, news sentiment, upcoming events and analyst
+    /// data. Seeds the per-slice caches and emits the matching *_loaded
+    /// signals, then deep_dive_loaded with the whole payload. Cached for
+    /// kDeepDiveTtlSec; concurrent requests for the same symbol share one run.
+    void load_deep_dive(const QString& symbol, bool force_refresh = false);
+
     void fetch_financials(const QString& symbol);
     void fetch_technicals(const QString& symbol, const QString& period = "1y");
     void fetch_peers(const QString& symbol, const QStringList& peer_symbols);
@@ -55,6 +65,7 @@ class EquityResearchService : public QObject {
     void technicals_loaded(fincept::services::equity::TechnicalsData data);
     void peers_loaded(QVector<fincept::services::equity::PeerData> peers);
     void news_loaded(QString symbol, QVector<fincept::services::equity::NewsArticle> articles);
+    void deep_dive_loaded(QString symbol, QJsonObject payload);
     void error_occurred(QString context, QString message);
 
   private:
@@ -78,6 +89,9 @@ class EquityResearchService : public QObject {
     // it falls back to fetch_news(symbol, count, NewsProvider::Auto).
     void fetch_news_newsapi(const QString& symbol, int count, const QString& api_key);
 
+    // Fan a deep-dive payload out to the per-slice caches + signals.
+    void apply_deep_dive(const QString& symbol, const QJsonObject& payload);
+
     // ── Parsers ───────────────────────────────────────────────────────────────
     QuoteData parse_quote(const QJsonObject& obj) const;
     StockInfo parse_info(const QJsonObject& obj) const;
@@ -93,6 +107,10 @@ class EquityResearchService : public QObject {
     static constexpr int kInfoTtlSec = 300;
     static constexpr int kHistoricalTtlSec = 120;
     static constexpr int kNewsTtlSec = 180;
+    static constexpr int kFinancialsTtlSec = 3600;
+    static constexpr int kDeepDiveTtlSec = 300;
+
+    QSet<QString> deep_dive_inflight_;
 
     // ── Debounce ──────────────────────────────────────────────────────────────
     static constexpr int kDebounceMs = 350;
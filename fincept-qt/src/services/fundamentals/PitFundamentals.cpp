@@ -0,0 +1,411 @@
+// src/services/fundamentals/PitFundamentals.cpp
+#include "services/fundamentals/PitFundamentals.h"
+
+#include "core/logging/Logger.h"
+#include "python/PythonRunner.h"
+
+#include <QDateTime>
+#include <QJsonDocument>
+#include <QPair>
+#include <QPointer>
+#include <QTimer>
+#include <QUuid>
+#include <QVector>
+
+#include <algorithm>
+#include <cmath>
+#include <optional>
+
+namespace fincept::services {
+
+namespace {
+
+constexpr const char* kPitTag = "PitFundamentals";
+
+// Summed over four quarters for trailing values.
+const QStringList kFlowMetrics = {"revenue",     "gross_profit",        "operating_income", "net_income",
+                                  "eps_diluted", "operating_cash_flow", "capex",            "dividends_paid"};
+const QStringList kInstantMetrics = {"total_assets",        "total_liabilities", "total_equity", "current_assets",
+                                     "current_liabilities", "cash",              "total_debt"};
+
+// Two report dates this close are the same period (sources disagree on the
+// exact fiscal period end by a few days).
+constexpr int kSamePeriodDays = 20;
+
+struct Point {
+    QDate end;
+    double value = 0;
+    QString filed;
+};
+
+// One symbol's facts as known: metric → period (FY / Q / I) → points, newest first.
+using Series = QHash<QString, QHash<QString, QVector<Point>>>;
+
+struct Trailing {
+    double value = 0;
+    QDate end;
+    QString filed;
+    bool ok = false;
+};
+
+QVector<Point> points(const Series& s, const QString& metric, const QString& period) {
+    return s.value(metric).value(period);
+}
+
+// Adds a fiscal Q4 where a fiscal year and its first three quarters are
+// known but Q4 itself is not — 10-Ks report the year, not the last quarter.
+QVector<Point> with_implied_quarters(QVector<Point> q, const QVector<Point>& fy) {
+    for (const Point& y : fy) {
+        const bool have = std::any_of(q.begin(), q.end(), [&](const Point& p) {
+            return std::abs(p.end.daysTo(y.end)) <= kSamePeriodDays;
+        });
+        if (have)
+            continue;
+        QVector<Point> inside;
+        for (const Point& p : q) {
+            const qint64 days = p.end.daysTo(y.end);
+            if (days >= 60 && days <= 300)
+                inside.append(p);
+        }
+        if (inside.size() != 3)
+            continue;
+        double sum = 0;
+        for (const Point& p : inside)
+            sum += p.value;
+        q.append(Point{y.end, y.value - sum, y.filed});
+    }
+    std::sort(q.begin(), q.end(), [](const Point& a, const Point& b) { return a.end > b.end; });
+    return q;
+}
+
+// Sum of the four newest consecutive quarters ending on or before `cap`, or
+// the newest fiscal year when that is as recent.
+Trailing trailing(const QVector<Point>& q, const QVector<Point>& fy, const QDate& cap) {
+    Trailing t;
+    int i = 0;
+    while (i < q.size() && q[i].end > cap)
+        ++i;
+    if (i + 3 < q.size()) {
+        const qint64 span = q[i + 3].end.daysTo(q[i].end);
+        if (span >= 240 && span <= 300) {
+            t.ok = true;
+            t.end = q[i].end;
+            for (int k = i; k < i + 4; ++k) {
+                t.value += q[k].value;
+                t.filed = std::max(t.filed, q[k].filed);
+            }
+        }
+    }
+    for (const Point& y : fy) {
+        if (y.end > cap)
+            continue;
+        if (!t.ok || y.end >= t.end)
+            t = Trailing{y.value, y.end, y.filed, true};
+        break;
+    }
+    return t;
+}
+
+// Ratios over a zero or negative base (negative equity, …) are left out.
+std::optional<double> ratio(const QJsonObject& f, const char* num, const char* den, double scale) {
+    if (!f.contains(num) || !f.contains(den))
+        return std::nullopt;
+    const double d = f.value(den).toDouble();
+    if (d <= 0)
+        return std::nullopt;
+    return f.value(num).toDouble() / d * scale;
+}
+
+PitSnapshot derive(const QString& symbol, const Series& s, const QDate& as_of) {
+    PitSnapshot snap;
+    snap.symbol = symbol;
+    QDate newest;
+    auto use = [&](const QDate& end, const QString& filed) {
+        if (!newest.isValid() || end > newest)
+            newest = end;
+        snap.filed_date = std::max(snap.filed_date, filed);
+    };
+
+    QJsonObject& f = snap.fields;
+    QHash<QString, Trailing> prior;
+    for (const QString& m : kFlowMetrics) {
+        const QVector<Point> fy = points(s, m, "FY");
+        const QVector<Point> q = with_implied_quarters(points(s, m, "Q"), fy);
+        const Trailing cur = trailing(q, fy, as_of);
+        if (!cur.ok)
+            continue;
+        f[m] = cur.value;
+        use(cur.end, cur.filed);
+        const Trailing before = trailing(q, fy, cur.end.addDays(-365 + kSamePeriodDays));
+        if (before.ok && std::abs(before.end.daysTo(cur.end) - 365) <= kSamePeriodDays)
+            prior.insert(m, before);
+    }
+    for (const QString& m : kInstantMetrics) {
+        const QVector<Point> i = points(s, m, "I");
+        if (i.isEmpty())
+            continue;
+        f[m] = i.first().value;
+        use(i.first().end, i.first().filed);
+    }
+    // A weighted average share count: the newest period's, never summed.
+    QVector<Point> shares = points(s, "shares_diluted", "Q") + points(s, "shares_diluted", "FY");
+    if (!shares.isEmpty()) {
+        const auto it = std::max_element(shares.begin(), shares.end(),
+                                         [](const Point& a, const Point& b) { return a.end < b.end; });
+        f["shares_diluted"] = it->value;
+        use(it->end, it->filed);
+    }
+
+    if (f.contains("operating_cash_flow") && f.contains("capex"))
+        f["free_cash_flow"] = f.value("operating_cash_flow").toDouble() - f.value("capex").toDouble();
+    if (auto v = ratio(f, "gross_profit", "revenue", 100))
+        f["gross_margin"] = *v;
+    if (auto v = ratio(f, "operating_income", "revenue", 100))
+        f["operating_margin"] = *v;
+    if (auto v = ratio(f, "net_income", "revenue", 100))
+        f["profit_margin"] = *v;
+    if (auto v = ratio(f, "net_income", "total_equity", 100))
+        f["roe"] = *v;
+    if (auto v = ratio(f, "net_income", "total_assets", 100))
+        f["roa"] = *v;
+    if (auto v = ratio(f, "total_debt", "total_equity", 100))
+        f["debt_to_equity"] = *v;
+    if (auto v = ratio(f, "current_assets", "current_liabilities", 1))
+        f["current_ratio"] = *v;
+    // Growth off a negative base has no meaningful sign.
+    if (prior.contains("revenue") && prior["revenue"].value > 0)
+        f["revenue_growth"] = (f.value("revenue").toDouble() / prior["revenue"].value - 1) * 100;
+    if (prior.contains("net_income") && prior["net_income"].value > 0)
+        f["earnings_growth"] = (f.value("net_income").toDouble() / prior["net_income"].value - 1) * 100;
+
+    snap.period_end = newest.isValid() ? newest.toString(Qt::ISODate) : QString();
+    return snap;
+}
+
+// Snapshots from facts in PitFundamentalsRepository::known_as_of() order,
+// taking per report date the newest filing made on or before `as_of`.
+QHash<QString, PitSnapshot> build(const QVector<PitFact>& facts, const QDate& as_of) {
+    const QString cutoff = as_of.toString(Qt::ISODate);
+    QHash<QString, Series> by_symbol;
+    QString last_key;
+    for (const PitFact& f : facts) {
+        if (f.filed_date > cutoff)
+            continue;
+        // Older filings of a report already taken (restated later, still before as_of).
+        const QString key = f.symbol + '|' + f.metric + '|' + f.period + '|' + f.period_end;
+        if (key == last_key)
+            continue;
+        last_key = key;
+        const QDate end = QDate::fromString(f.period_end, Qt::ISODate);
+        if (!end.isValid())
+            continue;
+        // Rows arrive per symbol / metric / period, newest report date first.
+        QVector<Point>& pts = by_symbol[f.symbol][f.metric][f.period];
+        // The same period from the other source, a few days apart.
+        if (!pts.isEmpty() && std::abs(pts.last().end.daysTo(end)) <= kSamePeriodDays)
+            continue;
+        pts.append(Point{end, f.value, f.filed_date});
+    }
+
+    QHash<QString, PitSnapshot> out;
+    for (auto it = by_symbol.constBegin(); it != by_symbol.constEnd(); ++it)
+        out.insert(it.key(), derive(it.key(), it.value(), as_of));
+    return out;
+}
+
+QStringList normalized(const QStringList& symbols) {
+    QStringList wanted;
+    for (const QString& s : symbols) {
+        const QString sym = s.trimmed().toUpper();
+        if (!sym.isEmpty() && !wanted.contains(sym))
+            wanted.append(sym);
+    }
+    return wanted;
+}
+
+QString error_text(const QJsonValue& v) {
+    if (v.isObject())
+        return v.toObject().value("error").toString();
+    return v.toString();
+}
+
+} // namespace
+
+struct PitFundamentals::IngestState {
+    QVector<QPair<QString, QString>> pending; // {symbol, source}
+    QString request_id;
+    QJsonArray stored; // {symbol, source, facts}
+    QJsonArray failed; // {symbol, source, error}
+    QString first_error;
+};
+
+PitFundamentals& PitFundamentals::instance() {
+    static PitFundamentals s;
+    return s;
+}
+
+const QStringList& PitFundamentals::metrics() {
+    static const QStringList m = kFlowMetrics + kInstantMetrics + QStringList{"shares_diluted"};
+    return m;
+}
+
+const QStringList& PitFundamentals::screener_fields() {
+    static const QStringList f = {"gross_margin",   "operating_margin", "profit_margin",  "roe",            "roa",
+                                  "revenue_growth", "earnings_growth",  "debt_to_equity", "current_ratio"};
+    return f;
+}
+
+// ── Ingestion ───────────────────────────────────────────────────────────────
+
+QString PitFundamentals::ingest(const QStringList& symbols, const QStringList& sources) {
+    auto st = std::make_shared<IngestState>();
+    st->request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    QString problem;
+    for (const QString& src : sources) {
+        if (src != "sec" && src != "fmp")
+            problem = QString("Unknown fundamentals source '%1' (expected sec or fmp)").arg(src);
+    }
+    for (const QString& s : symbols) {
+        const QString sym = s.trimmed().toUpper();
+        for (const QString& src : sources) {
+            if (!sym.isEmpty() && !st->pending.contains(qMakePair(sym, src)))
+                st->pending.append(qMakePair(sym, src));
+        }
+    }
+    if (problem.isEmpty() && st->pending.isEmpty())
+        problem = QStringLiteral("No symbols or sources given");
+    if (!problem.isEmpty()) {
+        QTimer::singleShot(0, this, [this, id = st->request_id, problem]() { emit error_occurred(id, problem); });
+        return st->request_id;
+    }
+    ingest_next(st);
+    return st->request_id;
+}
+
+void PitFundamentals::ingest_next(std::shared_ptr<IngestState> st) {
+    if (st->pending.isEmpty()) {
+        LOG_INFO(kPitTag, QString("Stored point-in-time facts for %1 symbol/source pairs (%2 failed)")
+                              .arg(st->stored.size())
+                              .arg(st->failed.size()));
+        // Only an ingest where nothing at all came back is an error.
+        if (st->stored.isEmpty())
+            emit error_occurred(st->request_id, st->first_error);
+        else
+            emit ingested(st->request_id, st->stored, st->failed);
+        return;
+    }
+    const QString symbol = st->pending.first().first;
+    const QString source = st->pending.first().second;
+    st->pending.removeFirst();
+    const QString script = source == "sec" ? QStringLiteral("sec_xbrl_data.py") : QStringLiteral("fmp_data.py");
+    const QStringList args = source == "sec" ? QStringList{"pit", symbol}
+                                             : QStringList{"pit_fundamentals", symbol, "all", "40"};
+
+    QPointer<PitFundamentals> self = this;
+    python::PythonRunner::instance().run(script, args, [self, st, symbol, source](python::PythonResult r) {
+        if (!self)
+            return;
+        const QJsonObject o =
+            r.success ? QJsonDocument::fromJson(python::extract_json(r.output).toUtf8()).object() : QJsonObject();
+        QString error;
+        if (!o.value("success").toBool(false)) {
+            error = error_text(o.value("error"));
+            if (error.isEmpty())
+                error = r.error.isEmpty() ? QString("%1 produced no result").arg(source) : r.error;
+        }
+
+        QVector<PitFact> facts;
+        if (error.isEmpty()) {
+            const qint64 now = QDateTime::currentMSecsSinceEpoch();
+            for (const auto& v : o.value("data").toArray()) {
+                const QJsonObject d = v.toObject();
+                PitFact f;
+                f.symbol = symbol;
+                f.metric = d.value("metric").toString();
+                f.period = d.value("period").toString();
+                f.period_end = d.value("period_end").toString();
+                f.filed_date = d.value("filed").toString();
+                f.source = source;
+                f.value = d.value("value").toDouble();
+                f.unit = d.value("unit").toString();
+                f.period_start = d.value("period_start").toString();
+                f.fiscal_year = d.value("fiscal_year").toVariant().toInt();
+                f.fiscal_period = d.value("fiscal_period").toString();
+                f.form = d.value("form").toString();
+                f.accession = d.value("accession").toString();
+                f.ingested_at = now;
+                if (!f.metric.isEmpty() && !f.period_end.isEmpty() && !f.filed_date.isEmpty())
+                    facts.append(f);
+            }
+            if (auto w = PitFundamentalsRepository::instance().upsert_facts(facts); w.is_err())
+                error = QString::fromStdString(w.error());
+        }
+
+        if (error.isEmpty()) {
+            st->stored.append(QJsonObject{{"symbol", symbol}, {"source", source}, {"facts", facts.size()}});
+        } else {
+            LOG_WARN(kPitTag, QString("%1 fundamentals for %2 failed: %3").arg(source, symbol, error));
+            if (st->first_error.isEmpty())
+                st->first_error = error;
+            st->failed.append(QJsonObject{{"symbol", symbol}, {"source", source}, {"error", error}});
+        }
+        self->ingest_next(st);
+    });
+}
+
+// ── Point-in-time snapshot ──────────────────────────────────────────────────
+
+Result<QHash<QString, PitSnapshot>> PitFundamentals::snapshot(const QStringList& symbols, const QDate& as_of) {
+    using R = Result<QHash<QString, PitSnapshot>>;
+    if (!as_of.isValid())
+        return R::err("Invalid as-of date");
+    auto facts = PitFundamentalsRepository::instance().known_as_of(normalized(symbols), as_of.toString(Qt::ISODate));
+    if (facts.is_err())
+        return R::err(facts.error());
+    return R::ok(build(facts.value(), as_of));
+}
+
+QVector<QHash<QString, PitSnapshot>> PitFundamentals::snapshots(const QVector<PitFact>& filings,
+                                                                const QVector<QDate>& dates) {
+    QVector<QHash<QString, PitSnapshot>> out;
+    out.reserve(dates.size());
+    for (const QDate& d : dates)
+        out.append(d.isValid() ? build(filings, d) : QHash<QString, PitSnapshot>());
+    return out;
+}
+
+// ── JSON ────────────────────────────────────────────────────────────────────
+
+QJsonObject PitFundamentals::fact_to_json(const PitFact& f) {
+    return QJsonObject{
+        {"symbol", f.symbol},
+        {"metric", f.metric},
+        {"period", f.period},
+        {"period_start", f.period_start.isEmpty() ? QJsonValue() : QJsonValue(f.period_start)},
+        {"period_end", f.period_end},
+        {"filed_date", f.filed_date},
+        {"source", f.source},
+        {"value", f.value},
+        {"unit", f.unit},
+        {"fiscal_year", f.fiscal_year},
+        {"fiscal_period", f.fiscal_period},
+        {"form", f.form},
+        {"accession", f.accession.isEmpty() ? QJsonValue() : QJsonValue(f.accession)},
+    };
+}
+
+QJsonObject PitFundamentals::coverage_to_json(const PitCoverage& c) {
+    return QJsonObject{
+        {"symbol", c.symbol},
+        {"source", c.source},
+        {"facts", c.facts},
+        {"first_period_end", c.first_period_end},
+        {"last_period_end", c.last_period_end},
+        {"last_filed_date", c.last_filed_date},
+        {"ingested_at", c.ingested_at > 0 ? QJsonValue(QDateTime::fromMSecsSinceEpoch(c.ingested_at)
+                                                           .toString(Qt::ISODate))
+                                          : QJsonValue()},
+    };
+}
+
+} // namespace fincept::services
@@ -0,0 +1,84 @@
+// src/services/fundamentals/PitFundamentals.h
+#pragma once
+#include "core/result/Result.h"
+#include "storage/repositories/PitFundamentalsRepository.h"
+
+#include <QDate>
+#include <QHash>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+#include <functional>
+#include <memory>
+
+namespace fincept::services {
+
+/// One symbol's fundamentals as they were known on a date.
+struct PitSnapshot {
+    QString symbol;
+    QJsonObject fields; // raw trailing / latest metrics plus screener ratios (PitFundamentals::screener_fields())
+    QString period_end; // newest report date used
+    QString filed_date; // newest filing used
+};
+
+/// Point-in-time fundamentals: statement metrics stored with both their report
+/// date and their filing date (PitFundamentalsRepository), so screens and
+/// backtests can ask what was public on day D instead of reading today's
+/// restated numbers into the past.
+///
+/// ingest() pulls SEC companyfacts (sec_xbrl_data.py pit — full filing history,
+/// restatements included) and / or FMP statements (fmp_data.py
+/// pit_fundamentals, needs FMP_API_KEY), one symbol per process.
+/// snapshot() turns the facts filed on or before D into trailing-twelve-month
+/// flows (four quarters, a missing fiscal Q4 implied from the annual figure;
+/// the latest fiscal year otherwise), latest balance sheet values and the
+/// screener ratios they support. Filings are counted on their filing date, so
+/// a backtest trading at D's close should ask for D - 1. Main thread only,
+/// snapshots() aside.
+class PitFundamentals : public QObject {
+    Q_OBJECT
+  public:
+    static PitFundamentals& instance();
+
+    /// Metric keys both sources populate.
+    static const QStringList& metrics();
+    /// ScreenFilter fields snapshot() derives; the only non-profile fields a
+    /// point-in-time screen may use.
+    static const QStringList& screener_fields();
+
+    /// Fetches `sources` ("sec", "fmp") for `symbols`; returns the request id
+    /// ingested / error_occurred carry.
+    QString ingest(const QStringList& symbols, const QStringList& sources);
+
+    /// Fundamentals known on `as_of` for `symbols` (every stored symbol when
+    /// empty). Symbols without a filing by then are absent.
+    static Result<QHash<QString, PitSnapshot>> snapshot(const QStringList& symbols, const QDate& as_of);
+    /// snapshot() for each of `dates` (same order) from `filings` as
+    /// PitFundamentalsRepository::filings() returns them — one read of the
+    /// store for a backtest that rebalances many times. Touches no database,
+    /// so it may run off the main thread.
+    static QVector<QHash<QString, PitSnapshot>> snapshots(const QVector<PitFact>& filings,
+                                                          const QVector<QDate>& dates);
+
+    static QJsonObject fact_to_json(const PitFact& f);
+    static QJsonObject coverage_to_json(const PitCoverage& c);
+
+  signals:
+    /// `stored` is per symbol {symbol, source, facts}; `failed` {symbol, source, error}.
+    void ingested(QString request_id, QJsonArray stored, QJsonArray failed);
+    void error_occurred(QString request_id, QString message);
+
+  private:
+    PitFundamentals() = default;
+    Q_DISABLE_COPY(PitFundamentals)
+
+    struct IngestState;
+
+    void ingest_next(std::shared_ptr<IngestState> st);
+};
+
+} // namespace fincept::services
@@ -0,0 +1,109 @@
+#include "services/futures/ContinuousFutures.h"
+
+#include <QDateTime>
+#include <QTimeZone>
+
+#include <algorithm>
+#include <limits>
+
+namespace fincept::services::futures {
+
+namespace {
+
+struct CfSegment {
+    int index = 0; // into the chain
+    QVector<trading::BrokerCandle> bars;
+};
+
+qint64 cf_day_start_ms(const QDate& d) {
+    return d.startOfDay(QTimeZone::UTC).toMSecsSinceEpoch();
+}
+
+QVector<trading::BrokerCandle> cf_sorted(QVector<trading::BrokerCandle> bars) {
+    std::sort(bars.begin(), bars.end(),
+              [](const trading::BrokerCandle& a, const trading::BrokerCandle& b) { return a.timestamp < b.timestamp; });
+    return bars;
+}
+
+void cf_apply(QVector<trading::BrokerCandle>& bars, const QString& adjust, double add, double mul) {
+    if (adjust == "none")
+        return;
+    const bool ratio = adjust == "ratio";
+    for (auto& c : bars) {
+        for (double* px : {&c.open, &c.high, &c.low, &c.close})
+            *px = ratio ? *px * mul : *px + add;
+    }
+}
+
+} // namespace
+
+ContinuousSeries build_continuous(const QString& symbol, const QVector<ContractBars>& chain, const QString& adjust) {
+    ContinuousSeries out;
+    out.symbol = symbol;
+    out.adjust = adjust;
+
+    QVector<QVector<trading::BrokerCandle>> sorted;
+    sorted.reserve(chain.size());
+    QVector<CfSegment> segs;
+    qint64 start = std::numeric_limits<qint64>::min();
+    for (int i = 0; i < chain.size(); ++i) {
+        sorted.append(cf_sorted(chain[i].candles));
+        const qint64 end = cf_day_start_ms(chain[i].contract.roll_date);
+        CfSegment seg;
+        seg.index = i;
+        for (const auto& c : sorted.last()) {
+            if (c.timestamp >= start && c.timestamp < end)
+                seg.bars.append(c);
+        }
+        if (seg.bars.isEmpty())
+            out.missing.append(chain[i].contract.code);
+        else
+            segs.append(std::move(seg));
+        start = end;
+    }
+    if (segs.isEmpty())
+        return out;
+
+    // Walk back from the newest contract, accumulating the roll adjustments.
+    double add = 0;
+    double mul = 1;
+    for (int k = segs.size() - 2; k >= 0; --k) {
+        const auto& last_old = segs[k].bars.last();
+        const auto& incoming = sorted[segs[k + 1].index];
+        auto it = std::upper_bound(incoming.begin(), incoming.end(), last_old.timestamp,
+                                   [](qint64 t, const trading::BrokerCandle& c) { return t < c.timestamp; });
+        RollEvent ev;
+        ev.date = chain[segs[k].index].contract.roll_date;
+        ev.from_contract = chain[segs[k].index].contract.code;
+        ev.to_contract = chain[segs[k + 1].index].contract.code;
+        ev.from_close = last_old.close;
+        ev.matched = it != incoming.begin();
+        const auto& pair = ev.matched ? *(it - 1) : segs[k + 1].bars.first();
+        ev.timestamp_ms = pair.timestamp;
+        ev.to_close = pair.close;
+        ev.gap = ev.to_close - ev.from_close;
+        ev.ratio = ev.from_close > 0 && ev.to_close > 0 ? ev.to_close / ev.from_close : 1;
+        add += ev.gap;
+        mul *= ev.ratio;
+        cf_apply(segs[k].bars, adjust, add, mul);
+        out.rolls.prepend(ev);
+    }
+
+    for (const auto& s : segs)
+        out.candles += s.bars;
+    return out;
+}
+
+QJsonObject roll_to_json(const RollEvent& r) {
+    return QJsonObject{{"date", r.date.toString(Qt::ISODate)},
+                       {"from", r.from_contract},
+                       {"to", r.to_contract},
+                       {"timestamp", r.timestamp_ms},
+                       {"from_close", r.from_close},
+                       {"to_close", r.to_close},
+                       {"gap", r.gap},
+                       {"ratio", r.ratio},
+                       {"matched", r.matched}};
+}
+
+} // namespace fincept::services::futures
@@ -0,0 +1,59 @@
+#pragma once
+// ContinuousFutures — stitches individual contracts into one back-adjusted
+// series. Pure, any thread.
+//
+// Each contract covers [previous contract's roll_date, its own roll_date).
+// At every roll the outgoing contract's last bar is paired with the incoming
+// contract's bar at the same timestamp (or the latest one before it), and
+// everything before the roll is shifted so the series has no roll gap:
+//
+//   panama — adds the price difference (keeps point moves, P&L in points)
+//   ratio  — multiplies by the price ratio (keeps percentage returns, never
+//            goes negative)
+//   none   — raw stitch, roll gaps left in
+//
+// The newest contract is never adjusted, so the series ends at real prices.
+
+#include "services/futures/FuturesCalendar.h"
+#include "trading/TradingTypes.h"
+
+#include <QJsonObject>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+namespace fincept::services::futures {
+
+/// One contract and its cached bars (epoch ms, any order).
+struct ContractBars {
+    FuturesContract contract;
+    QVector<trading::BrokerCandle> candles;
+};
+
+struct RollEvent {
+    QDate date; // first day on the incoming contract
+    QString from_contract;
+    QString to_contract;
+    qint64 timestamp_ms = 0; // bar the two closes were taken from
+    double from_close = 0;
+    double to_close = 0;
+    double gap = 0;       // to_close - from_close
+    double ratio = 1;     // to_close / from_close
+    bool matched = false; // false: no incoming bar at or before the outgoing one; its first bar was used
+};
+
+struct ContinuousSeries {
+    QString symbol;
+    QString adjust;
+    QVector<trading::BrokerCandle> candles; // ascending, adjusted
+    QVector<RollEvent> rolls;               // ascending
+    QStringList missing;                    // contracts with no cached bars in their window
+};
+
+/// `chain` ascending by expiry, each with its roll_date set.
+ContinuousSeries build_continuous(const QString& symbol, const QVector<ContractBars>& chain, const QString& adjust);
+
+/// {date, from, to, timestamp, from_close, to_close, gap, ratio, matched}
+QJsonObject roll_to_json(const RollEvent& r);
+
+} // namespace fincept::services::futures
@@ -0,0 +1,175 @@
+#include "services/futures/FuturesCalendar.h"
+
+#include <algorithm>
+#include <cstdlib>
+
+namespace fincept::services::futures {
+
+namespace {
+
+constexpr char kFutMonthCodes[] = "FGHJKMNQUVXZ";
+
+QDate fut_nth_weekday(int year, int month, int weekday, int n) {
+    QDate d(year, month, 1);
+    while (d.dayOfWeek() != weekday)
+        d = d.addDays(1);
+    return d.addDays(7 * (n - 1));
+}
+
+QDate fut_last_business_day(int year, int month) {
+    QDate d = QDate(year, month, 1).addMonths(1).addDays(-1);
+    while (!is_business_day(d))
+        d = d.addDays(-1);
+    return d;
+}
+
+} // namespace
+
+QStringList expiry_rules() {
+    return {QStringLiteral("third_friday"), QStringLiteral("third_wednesday_minus_2bd"),
+            QStringLiteral("prior_25th_minus_3bd"), QStringLiteral("first_minus_3bd"),
+            QStringLiteral("third_last_bd"), QStringLiteral("bd_before_15th"),
+            QStringLiteral("last_bd_minus_7bd"), QStringLiteral("last_bd")};
+}
+
+QStringList adjust_methods() {
+    return {QStringLiteral("panama"), QStringLiteral("ratio"), QStringLiteral("none")};
+}
+
+QChar month_code(int month) {
+    if (month < 1 || month > 12)
+        return {};
+    return QChar::fromLatin1(kFutMonthCodes[month - 1]);
+}
+
+int month_from_code(QChar code) {
+    const char c = code.toUpper().toLatin1();
+    for (int i = 0; i < 12; ++i) {
+        if (kFutMonthCodes[i] == c)
+            return i + 1;
+    }
+    return 0;
+}
+
+bool is_business_day(const QDate& d) {
+    return d.isValid() && d.dayOfWeek() <= 5;
+}
+
+QDate add_business_days(const QDate& d, int n) {
+    QDate out = d;
+    const int step = n < 0 ? -1 : 1;
+    for (int left = std::abs(n); left > 0;) {
+        out = out.addDays(step);
+        if (is_business_day(out))
+            --left;
+    }
+    return out;
+}
+
+QDate rule_expiry(const QString& rule, int year, int month) {
+    if (month < 1 || month > 12)
+        return {};
+    if (rule == "third_friday")
+        return fut_nth_weekday(year, month, Qt::Friday, 3);
+    if (rule == "third_wednesday_minus_2bd")
+        return add_business_days(fut_nth_weekday(year, month, Qt::Wednesday, 3), -2);
+    if (rule == "prior_25th_minus_3bd") {
+        const QDate d25 = QDate(year, month, 1).addMonths(-1).addDays(24);
+        return add_business_days(d25, is_business_day(d25) ? -3 : -4);
+    }
+    if (rule == "first_minus_3bd")
+        return add_business_days(QDate(year, month, 1), -3);
+    if (rule == "third_last_bd")
+        return add_business_days(fut_last_business_day(year, month), -2);
+    if (rule == "bd_before_15th")
+        return add_business_days(QDate(year, month, 15), -1);
+    if (rule == "last_bd_minus_7bd")
+        return add_business_days(fut_last_business_day(year, month), -7);
+    if (rule == "last_bd")
+        return fut_last_business_day(year, month);
+    return {};
+}
+
+QString contract_code(const QString& root, int year, int month) {
+    return root.toUpper() + month_code(month) + QString::number(year % 100).rightJustified(2, '0');
+}
+
+std::optional<FuturesContract> parse_contract(const QString& code) {
+    const QString s = code.trimmed().toUpper();
+    if (s.size() < 4 || !s.at(s.size() - 1).isDigit() || !s.at(s.size() - 2).isDigit())
+        return std::nullopt;
+    const int month = month_from_code(s.at(s.size() - 3));
+    if (month == 0)
+        return std::nullopt;
+    FuturesContract c;
+    c.root = s.left(s.size() - 3);
+    c.code = s;
+    c.year = 2000 + s.right(2).toInt();
+    c.month = month;
+    return c;
+}
+
+std::optional<ContinuousSymbol> parse_continuous(const QString& symbol) {
+    const QString s = symbol.trimmed().toUpper();
+    const int hash = s.indexOf('#');
+    if (hash < 1 || hash != s.size() - 2)
+        return std::nullopt;
+    ContinuousSymbol c;
+    c.root = s.left(hash);
+    switch (s.at(hash + 1).toLatin1()) {
+        case 'C':
+            break;
+        case 'P':
+            c.adjust = QStringLiteral("panama");
+            break;
+        case 'R':
+            c.adjust = QStringLiteral("ratio");
+            break;
+        case 'N':
+            c.adjust = QStringLiteral("none");
+            break;
+        default:
+            return std::nullopt;
+    }
+    return c;
+}
+
+bool is_continuous_symbol(const QString& symbol) {
+    return parse_continuous(symbol).has_value();
+}
+
+QVector<FuturesContract> contracts(const FuturesSpec& spec, const QHash<QString, QDate>& overrides, const QDate& from,
+                                   const QDate& to) {
+    QVector<FuturesContract> out;
+    if (!from.isValid() || !to.isValid() || to < from)
+        return out;
+    // Contract months run up to a month ahead of the last-trade date (CL, NG),
+    // so scan one year either side.
+    for (int year = from.year() - 1; year <= to.year() + 1; ++year) {
+        for (const QChar code : spec.months) {
+            const int month = month_from_code(code);
+            if (month == 0)
+                continue;
+            FuturesContract c;
+            c.root = spec.root.toUpper();
+            c.code = contract_code(c.root, year, month);
+            c.year = year;
+            c.month = month;
+            c.from_override = overrides.contains(c.code);
+            c.expiry = c.from_override ? overrides.value(c.code) : rule_expiry(spec.expiry_rule, year, month);
+            if (!c.expiry.isValid() || c.expiry < from || c.expiry > to)
+                continue;
+            c.roll_date = add_business_days(c.expiry, spec.roll_days > 0 ? -spec.roll_days : 1);
+            out.append(c);
+        }
+    }
+    std::sort(out.begin(), out.end(),
+              [](const FuturesContract& a, const FuturesContract& b) { return a.expiry < b.expiry; });
+    return out;
+}
+
+QString yahoo_symbol(const FuturesSpec& spec, const FuturesContract& c) {
+    return c.code + spec.yahoo_suffix;
+}
+
+} // namespace fincept::services::futures
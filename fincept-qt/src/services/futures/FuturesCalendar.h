@@ -0,0 +1,85 @@
+#pragma once
+// FuturesCalendar — contract codes, last-trade dates and roll dates for the
+// roots in futures_specs. Pure functions, any thread.
+//
+// Contract codes are ROOT + month code + two-digit year (ESH26 = ES March
+// 2026). Last-trade dates come from a named rule per root:
+//
+//   third_friday              — 3rd Friday of the contract month (equity index)
+//   third_wednesday_minus_2bd — 2 business days before the 3rd Wednesday (FX)
+//   prior_25th_minus_3bd      — 3 business days before the 25th of the prior
+//                               month, 4 when the 25th is not one (WTI crude)
+//   first_minus_3bd           — 3 business days before the 1st (natural gas)
+//   third_last_bd             — 3rd-last business day of the month (metals)
+//   bd_before_15th            — business day before the 15th (grains)
+//   last_bd_minus_7bd         — 7 business days before the last business day
+//                               (Treasuries)
+//   last_bd                   — last business day of the month
+//
+// Business days are Monday–Friday; exchange holidays are not modelled, so a
+// rule date that lands on one is corrected through futures_expiries.
+
+#include "storage/repositories/FuturesRepository.h"
+
+#include <QChar>
+#include <QDate>
+#include <QHash>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+#include <optional>
+
+namespace fincept::services::futures {
+
+struct FuturesContract {
+    QString root;
+    QString code; // ESH26
+    int year = 0;
+    int month = 0;
+    QDate expiry;               // last trade date
+    QDate roll_date;            // a continuous series holds the next contract from this date
+    bool from_override = false; // expiry came from futures_expiries, not the rule
+};
+
+/// "ES#C" (the root's default adjustment), "ES#P" (panama), "ES#R" (ratio)
+/// or "ES#N" (unadjusted).
+struct ContinuousSymbol {
+    QString root;
+    QString adjust; // empty = spec default
+};
+
+/// Names accepted in FuturesSpec::expiry_rule.
+QStringList expiry_rules();
+/// panama | ratio | none
+QStringList adjust_methods();
+
+/// F G H J K M N Q U V X Z for months 1..12; a null QChar otherwise.
+QChar month_code(int month);
+/// 0 for anything that is not a month code.
+int month_from_code(QChar code);
+
+bool is_business_day(const QDate& d);
+/// Steps `n` business days from `d` (negative = backwards), not counting `d`.
+QDate add_business_days(const QDate& d, int n);
+
+/// Last-trade date of the `year`/`month` contract under `rule`; invalid for
+/// an unknown rule.
+QDate rule_expiry(const QString& rule, int year, int month);
+
+QString contract_code(const QString& root, int year, int month);
+/// "ESH26" → ES / 2026 / 3. The root is everything before the month code.
+std::optional<FuturesContract> parse_contract(const QString& code);
+
+std::optional<ContinuousSymbol> parse_continuous(const QString& symbol);
+bool is_continuous_symbol(const QString& symbol);
+
+/// Listed contracts of `spec` expiring in [from, to], ascending by expiry.
+/// `overrides` (contract code → last-trade date) win over the rule.
+QVector<FuturesContract> contracts(const FuturesSpec& spec, const QHash<QString, QDate>& overrides, const QDate& from,
+                                   const QDate& to);
+
+/// ESH26 + ".CME" → "ESH26.CME", the Yahoo ticker for an individual contract.
+QString yahoo_symbol(const FuturesSpec& spec, const FuturesContract& c);
+
+} // namespace fincept::services::futures
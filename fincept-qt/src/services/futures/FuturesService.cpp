@@ -0,0 +1,166 @@
+#include "services/futures/FuturesService.h"
+
+#include "core/logging/Logger.h"
+#include "services/markets/HistoryDownloadManager.h"
+#include "storage/HistoricalDataStore.h"
+
+#include <QDateTime>
+#include <QTimeZone>
+
+#include <algorithm>
+
+namespace fincept::services::futures {
+
+namespace {
+
+constexpr const char* kFutTag = "Futures";
+// Bars fetched before a contract's window so the roll into it can be paired
+// with the outgoing contract's last bar across a weekend or holiday.
+constexpr qint64 kFutPairLeadMs = 14LL * 86400 * 1000;
+
+qint64 fut_day_ms(const QDate& d) {
+    return d.startOfDay(QTimeZone::UTC).toMSecsSinceEpoch();
+}
+
+QVector<trading::BrokerCandle> fut_contract_bars(const FuturesSpec& spec, const FuturesContract& c,
+                                                 const QString& interval, qint64 from_ms, qint64 to_ms) {
+    auto& store = storage::HistoricalDataStore::instance();
+    auto bars = store.get_candles(c.code, spec.exchange, interval, from_ms, to_ms);
+    if (bars.isEmpty() && !spec.yahoo_suffix.isEmpty())
+        bars = store.get_candles(yahoo_symbol(spec, c), "YF", interval, from_ms, to_ms);
+    return bars;
+}
+
+} // namespace
+
+FuturesService& FuturesService::instance() {
+    static FuturesService s;
+    return s;
+}
+
+QVector<FuturesSpec> FuturesService::specs() const {
+    auto r = FuturesRepository::instance().list_specs();
+    return r.is_ok() ? r.value() : QVector<FuturesSpec>{};
+}
+
+std::optional<FuturesSpec> FuturesService::spec(const QString& root) const {
+    return FuturesRepository::instance().get_spec(root.trimmed().toUpper());
+}
+
+Result<void> FuturesService::save_spec(const FuturesSpec& in) {
+    FuturesSpec s = in;
+    s.root = s.root.trimmed().toUpper();
+    s.months = s.months.trimmed().toUpper();
+    if (s.root.isEmpty() || s.root.contains('#'))
+        return Result<void>::err("Futures root must be non-empty and cannot contain '#'");
+    if (s.months.isEmpty())
+        return Result<void>::err("No listed months");
+    for (const QChar m : s.months) {
+        if (month_from_code(m) == 0)
+            return Result<void>::err(QString("'%1' is not a month code (F..Z)").arg(m).toStdString());
+    }
+    if (!expiry_rules().contains(s.expiry_rule))
+        return Result<void>::err("Unknown expiry rule: " + s.expiry_rule.toStdString());
+    if (!adjust_methods().contains(s.adjust))
+        return Result<void>::err("Unknown adjustment method: " + s.adjust.toStdString());
+    if (s.roll_days < 0 || s.multiplier <= 0 || s.tick_size <= 0)
+        return Result<void>::err("roll_days must be >= 0, multiplier and tick_size > 0");
+    return FuturesRepository::instance().save_spec(s);
+}
+
+Result<void> FuturesService::set_expiry_override(const QString& root, const QString& contract, const QDate& expiry) {
+    const auto c = parse_contract(contract);
+    if (!c || c->root != root.trimmed().toUpper())
+        return Result<void>::err("Contract code must be ROOT + month code + YY, e.g. ESH26");
+    return FuturesRepository::instance().set_expiry_override(c->root, c->code, expiry);
+}
+
+QVector<FuturesContract> FuturesService::calendar(const QString& root, const QDate& from, const QDate& to) const {
+    const auto s = spec(root);
+    if (!s)
+        return {};
+    return contracts(*s, FuturesRepository::instance().expiry_overrides(s->root), from, to);
+}
+
+QVector<FuturesContract> FuturesService::front_chain(const FuturesSpec& spec, const QDate& from,
+                                                     const QDate& to) const {
+    const auto all = contracts(spec, FuturesRepository::instance().expiry_overrides(spec.root), from, to.addYears(1));
+    QVector<FuturesContract> out;
+    for (const auto& c : all) {
+        if (c.roll_date <= from)
+            continue;
+        out.append(c);
+        if (c.roll_date > to)
+            break;
+    }
+    return out;
+}
+
+ContinuousSeries FuturesService::continuous(const QString& symbol, const QString& interval, qint64 from_ms,
+                                            qint64 to_ms, QString* error) {
+    const auto cs = parse_continuous(symbol);
+    const auto s = cs ? spec(cs->root) : std::nullopt;
+    if (!s) {
+        if (error)
+            *error = cs ? "No futures spec for root " + cs->root : "Not a continuous symbol (ROOT#C): " + symbol;
+        return {};
+    }
+    const QString adjust = cs->adjust.isEmpty() ? s->adjust : cs->adjust;
+    const QDate from = QDateTime::fromMSecsSinceEpoch(from_ms, QTimeZone::UTC).date();
+    const QDate to = QDateTime::fromMSecsSinceEpoch(to_ms, QTimeZone::UTC).date();
+
+    QVector<ContractBars> chain;
+    qint64 window_start = from_ms;
+    for (const auto& c : front_chain(*s, from, to)) {
+        const qint64 roll_ms = fut_day_ms(c.roll_date);
+        chain.append({c, fut_contract_bars(*s, c, interval, window_start - kFutPairLeadMs, std::min(to_ms, roll_ms))});
+        window_start = std::max(from_ms, roll_ms);
+    }
+
+    ContinuousSeries series = build_continuous(symbol.trimmed().toUpper(), chain, adjust);
+    series.candles.erase(std::remove_if(series.candles.begin(), series.candles.end(),
+                                        [&](const trading::BrokerCandle& c) {
+                                            return c.timestamp < from_ms || c.timestamp > to_ms;
+                                        }),
+                         series.candles.end());
+    if (series.candles.isEmpty()) {
+        if (error)
+            *error = QString("No cached %1 bars for %2 contracts in range — backfill them first")
+                         .arg(interval, s->root);
+        return series;
+    }
+
+    storage::HistoricalDataStore::instance().replace_candles(series.symbol, s->exchange, interval, from_ms, to_ms,
+                                                             series.candles);
+    LOG_INFO(kFutTag, QString("%1 %2 (%3): %4 bars, %5 rolls, %6 contracts missing")
+                          .arg(series.symbol, interval, adjust)
+                          .arg(series.candles.size())
+                          .arg(series.rolls.size())
+                          .arg(series.missing.size()));
+    return series;
+}
+
+Result<DownloadJob> FuturesService::backfill(const QString& root, const QString& interval, const QDate& from,
+                                             const QDate& to) {
+    const auto s = spec(root);
+    if (!s)
+        return Result<DownloadJob>::err("No futures spec for root " + root.toStdString());
+    if (s->yahoo_suffix.isEmpty())
+        return Result<DownloadJob>::err("Root " + s->root.toStdString() + " has no Yahoo suffix to download from");
+    QStringList symbols;
+    for (const auto& c : front_chain(*s, from, to))
+        symbols.append(yahoo_symbol(*s, c));
+    if (symbols.isEmpty())
+        return Result<DownloadJob>::err("No contracts of " + s->root.toStdString() + " in range");
+
+    DownloadJob job;
+    job.name = QString("%1 contracts %2..%3").arg(s->root, from.toString(Qt::ISODate), to.toString(Qt::ISODate));
+    job.exchange = QStringLiteral("YF");
+    job.interval = interval;
+    job.from_date = from.addDays(-kFutPairLeadMs / 86400000).toString(Qt::ISODate);
+    job.to_date = to.toString(Qt::ISODate);
+    job.source = QStringLiteral("yahoo");
+    return HistoryDownloadManager::instance().enqueue(job, symbols);
+}
+
+} // namespace fincept::services::futures
@@ -0,0 +1,61 @@
+#pragma once
+// FuturesService — futures specs, expiry calendars and continuous series.
+//
+// Continuous symbols (ES#C, CL#R, ...) are synthetic: they are built on
+// demand from the individual contracts already in the Historify candle
+// store, looked up under (ESH26, spec exchange) — broker downloads — then
+// (ESH26.CME, "YF") — Yahoo downloads, see backfill(). Every build is written
+// back to the store under (ES#C, spec exchange, interval) so exports and the
+// catalog see it like any other series. Charts (MarketDataService) and
+// backtests (algo::CandleDataFetcher) resolve continuous symbols here.
+//
+// Main thread (SQLite access through Database::instance()).
+
+#include "core/result/Result.h"
+#include "services/futures/ContinuousFutures.h"
+#include "services/futures/FuturesCalendar.h"
+#include "storage/repositories/DownloadJobRepository.h"
+#include "storage/repositories/FuturesRepository.h"
+
+#include <QDate>
+#include <QString>
+#include <QVector>
+
+#include <optional>
+
+namespace fincept::services::futures {
+
+class FuturesService {
+  public:
+    static FuturesService& instance();
+
+    QVector<FuturesSpec> specs() const;
+    std::optional<FuturesSpec> spec(const QString& root) const;
+    /// Validates the month codes, rule and adjustment method, then upserts.
+    Result<void> save_spec(const FuturesSpec& spec);
+    Result<void> set_expiry_override(const QString& root, const QString& contract, const QDate& expiry);
+
+    /// Listed contracts of `root` expiring in [from, to], overrides applied.
+    QVector<FuturesContract> calendar(const QString& root, const QDate& from, const QDate& to) const;
+
+    /// Builds `symbol` (ROOT#C/#P/#R/#N) over [from_ms, to_ms] at `interval`
+    /// from cached contract bars and stores the result. An empty series with
+    /// `error` set when the root is unknown or no contract has bars.
+    ContinuousSeries continuous(const QString& symbol, const QString& interval, qint64 from_ms, qint64 to_ms,
+                                QString* error = nullptr);
+
+    /// Queues a Yahoo download job for every contract of `root` that is front
+    /// at some point in [from, to].
+    Result<DownloadJob> backfill(const QString& root, const QString& interval, const QDate& from, const QDate& to);
+
+  private:
+    FuturesService() = default;
+    FuturesService(const FuturesService&) = delete;
+    FuturesService& operator=(const FuturesService&) = delete;
+
+    /// Contracts that are front at some point in [from, to]: rolls after
+    /// `from`, up to the first one rolling after `to`.
+    QVector<FuturesContract> front_chain(const FuturesSpec& spec, const QDate& from, const QDate& to) const;
+};
+
+} // namespace fincept::services::futures
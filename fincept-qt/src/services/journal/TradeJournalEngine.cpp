@@ -0,0 +1,342 @@
+// src/services/journal/TradeJournalEngine.cpp
+#include "services/journal/TradeJournalEngine.h"
+
+#include <QCryptographicHash>
+#include <QDateTime>
+#include <QHash>
+#include <QJsonArray>
+#include <QMap>
+#include <QSet>
+#include <QTimeZone>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::services::journal {
+
+namespace {
+
+using tca::TcaFill;
+
+constexpr double kJournalQtyEps = 1e-9;
+
+int journal_source_rank(const QString& source) {
+    static const QStringList order{"exec", "algo", "paper", "live"};
+    const int i = int(order.indexOf(source));
+    return i < 0 ? int(order.size()) : i;
+}
+
+QString journal_iso(qint64 ms) {
+    return ms > 0 ? QDateTime::fromMSecsSinceEpoch(ms, QTimeZone::UTC).toString(Qt::ISODate) : QString();
+}
+
+double journal_round(double v, int decimals) {
+    const double m = std::pow(10.0, decimals);
+    return std::round(v * m) / m;
+}
+
+QString journal_book(const TcaFill& f) {
+    const QString owner = f.account_id.isEmpty() ? f.broker + ':' + f.account : f.account_id;
+    return owner + '|' + f.symbol.toUpper();
+}
+
+// A trade being replayed, with the running sums its averages come from.
+struct JournalOpenTrade {
+    JournalTrade t;
+    double position = 0; // signed
+    double entry_qty = 0;
+    double entry_notional = 0;
+    double exit_qty = 0;
+    double exit_notional = 0;
+    double gross = 0;
+
+    void finish() {
+        t.entry_price = entry_qty > 0 ? entry_notional / entry_qty : 0.0;
+        t.exit_price = exit_qty > 0 ? exit_notional / exit_qty : 0.0;
+        t.pnl = gross - t.fees;
+        t.return_pct = entry_notional > 0 ? t.pnl / entry_notional : 0.0;
+    }
+};
+
+struct JournalAgg {
+    int trades = 0;
+    int wins = 0;
+    int losses = 0;
+    double win_sum = 0;
+    double loss_sum = 0;
+    double return_sum = 0;
+    double best = 0;
+    double worst = 0;
+    double hold_minutes = 0;
+    int excursions = 0;
+    double mae_sum = 0;
+    double mfe_sum = 0;
+    int captures = 0;
+    double capture_sum = 0;
+
+    void add(const JournalTrade& t) {
+        if (trades == 0 || t.pnl > best)
+            best = t.pnl;
+        if (trades == 0 || t.pnl < worst)
+            worst = t.pnl;
+        ++trades;
+        if (t.pnl > 0) {
+            ++wins;
+            win_sum += t.pnl;
+        } else if (t.pnl < 0) {
+            ++losses;
+            loss_sum += t.pnl;
+        }
+        return_sum += t.return_pct;
+        hold_minutes += double(t.exit_ms - t.entry_ms) / 60000.0;
+        if (t.has_excursion) {
+            ++excursions;
+            mae_sum += t.mae_pct;
+            mfe_sum += t.mfe_pct;
+            if (t.pnl > 0 && t.mfe_pct > 0) {
+                ++captures;
+                capture_sum += t.return_pct / t.mfe_pct;
+            }
+        }
+    }
+
+    QJsonObject to_json(const QString& key) const {
+        QJsonObject o;
+        if (!key.isNull())
+            o["key"] = key;
+        o["trades"] = trades;
+        o["wins"] = wins;
+        o["losses"] = losses;
+        o["win_rate"] = trades > 0 ? journal_round(double(wins) / trades, 4) : 0.0;
+        o["avg_win"] = wins > 0 ? journal_round(win_sum / wins, 2) : 0.0;
+        o["avg_loss"] = losses > 0 ? journal_round(loss_sum / losses, 2) : 0.0;
+        o["expectancy"] = trades > 0 ? journal_round((win_sum + loss_sum) / trades, 2) : 0.0;
+        o["expectancy_pct"] = trades > 0 ? journal_round(return_sum / trades * 100.0, 3) : 0.0;
+        if (loss_sum < 0)
+            o["profit_factor"] = journal_round(win_sum / -loss_sum, 3);
+        else
+            o["profit_factor"] = QJsonValue(); // no losing trades
+        o["total_pnl"] = journal_round(win_sum + loss_sum, 2);
+        o["best"] = journal_round(best, 2);
+        o["worst"] = journal_round(worst, 2);
+        o["avg_hold_minutes"] = trades > 0 ? journal_round(hold_minutes / trades, 1) : 0.0;
+        if (excursions > 0) {
+            o["avg_mae_pct"] = journal_round(mae_sum / excursions * 100.0, 3);
+            o["avg_mfe_pct"] = journal_round(mfe_sum / excursions * 100.0, 3);
+        }
+        if (captures > 0)
+            o["mfe_capture"] = journal_round(capture_sum / captures, 3);
+        return o;
+    }
+};
+
+QStringList journal_group_keys(const JournalTrade& t, const QString& group_by) {
+    const QString none = QStringLiteral("(none)");
+    if (group_by == "tag")
+        return t.tags.isEmpty() ? QStringList{none} : t.tags;
+    if (group_by == "setup")
+        return {t.setup.isEmpty() ? none : t.setup};
+    if (group_by == "strategy")
+        return {t.strategy.isEmpty() ? none : t.strategy};
+    if (group_by == "symbol")
+        return {t.symbol};
+    if (group_by == "account")
+        return {t.account.isEmpty() ? t.account_id : t.account};
+    if (group_by == "direction")
+        return {t.direction < 0 ? QStringLiteral("short") : QStringLiteral("long")};
+    return {t.rating > 0 ? QString::number(t.rating) : none};
+}
+
+} // namespace
+
+QVector<JournalTrade> TradeJournalEngine::build_trades(const QVector<TcaFill>& fills) {
+    // Most specific source per fill id.
+    QHash<QString, int> best_rank;
+    for (const auto& f : fills) {
+        const int r = journal_source_rank(f.source);
+        const auto it = best_rank.find(f.id);
+        if (it == best_rank.end() || r < *it)
+            best_rank.insert(f.id, r);
+    }
+    QMap<QString, QVector<TcaFill>> books;
+    for (const auto& f : fills) {
+        if (f.time_ms <= 0 || f.quantity <= 0 || f.price <= 0 || journal_source_rank(f.source) != best_rank.value(f.id))
+            continue;
+        books[journal_book(f)].append(f);
+    }
+
+    QVector<JournalTrade> out;
+    for (auto it = books.begin(); it != books.end(); ++it) {
+        QVector<TcaFill>& book = it.value();
+        std::stable_sort(book.begin(), book.end(), [](const TcaFill& a, const TcaFill& b) {
+            return a.time_ms != b.time_ms ? a.time_ms < b.time_ms : a.id < b.id;
+        });
+
+        JournalOpenTrade cur;
+        bool open = false;
+        for (const TcaFill& f : book) {
+            double qty = f.quantity;
+            const double fee_per_unit = f.fee / f.quantity;
+            while (qty > kJournalQtyEps) {
+                if (!open) {
+                    cur = JournalOpenTrade{};
+                    open = true;
+                    const QByteArray seed = (it.key() + '|' + f.source + '|' + f.id + '|' +
+                                             QString::number(f.time_ms)).toUtf8();
+                    cur.t.id = QString::fromLatin1(
+                        QCryptographicHash::hash(seed, QCryptographicHash::Sha1).toHex().left(16));
+                    cur.t.account_id = f.account_id;
+                    cur.t.account = f.account;
+                    cur.t.broker = f.broker;
+                    cur.t.strategy = f.strategy;
+                    cur.t.symbol = f.symbol.toUpper();
+                    cur.t.direction = f.side;
+                    cur.t.entry_ms = f.time_ms;
+                }
+                if (!cur.t.fill_ids.contains(f.id))
+                    cur.t.fill_ids << f.id;
+
+                if (f.side == cur.t.direction) {
+                    cur.entry_qty += qty;
+                    cur.entry_notional += qty * f.price;
+                    cur.position += f.side * qty;
+                    cur.t.fees += fee_per_unit * qty;
+                    cur.t.quantity = std::max(cur.t.quantity, std::abs(cur.position));
+                    qty = 0;
+                    continue;
+                }
+
+                const double closed = std::min(qty, std::abs(cur.position));
+                const double avg_entry = cur.entry_notional / cur.entry_qty;
+                cur.gross += cur.t.direction * (f.price - avg_entry) * closed;
+                cur.exit_qty += closed;
+                cur.exit_notional += closed * f.price;
+                cur.t.fees += fee_per_unit * closed;
+                cur.position += f.side * closed;
+                qty -= closed;
+                if (std::abs(cur.position) <= kJournalQtyEps) {
+                    cur.t.exit_ms = f.time_ms;
+                    cur.finish();
+                    out.append(cur.t);
+                    open = false;
+                }
+            }
+        }
+        if (open) {
+            cur.finish();
+            out.append(cur.t);
+        }
+    }
+
+    std::sort(out.begin(), out.end(),
+              [](const JournalTrade& a, const JournalTrade& b) { return a.entry_ms > b.entry_ms; });
+    return out;
+}
+
+bool TradeJournalEngine::apply_excursion(JournalTrade& trade, const QVector<algo::OhlcvCandle>& bars, qint64 now_ms,
+                                         const QString& timeframe) {
+    if (trade.entry_price <= 0)
+        return false;
+    const qint64 end = trade.is_open() ? now_ms : trade.exit_ms;
+    double hi = trade.entry_price;
+    double lo = trade.entry_price;
+    if (trade.exit_price > 0) {
+        hi = std::max(hi, trade.exit_price);
+        lo = std::min(lo, trade.exit_price);
+    }
+    int used = 0;
+    for (const auto& c : bars) {
+        if (c.open_time > end)
+            break;
+        if (c.close_time <= trade.entry_ms || c.high <= 0 || c.low <= 0)
+            continue;
+        hi = std::max(hi, c.high);
+        lo = std::min(lo, c.low);
+        ++used;
+    }
+    if (used == 0)
+        return false;
+
+    const double entry = trade.entry_price;
+    const double up = (hi - entry) / entry;
+    const double down = (lo - entry) / entry;
+    trade.mae_pct = std::min(0.0, trade.direction > 0 ? down : -up);
+    trade.mfe_pct = std::max(0.0, trade.direction > 0 ? up : -down);
+    trade.has_excursion = true;
+    trade.excursion_tf = timeframe;
+    return true;
+}
+
+QJsonObject TradeJournalEngine::stats(const QVector<JournalTrade>& trades, const QString& group_by) {
+    JournalAgg total;
+    QHash<QString, JournalAgg> groups;
+    int open_trades = 0;
+    for (const auto& t : trades) {
+        if (t.is_open()) {
+            ++open_trades;
+            continue;
+        }
+        total.add(t);
+        for (const auto& key : journal_group_keys(t, group_by))
+            groups[key].add(t);
+    }
+
+    QVector<QPair<QString, JournalAgg>> sorted;
+    for (auto it = groups.cbegin(); it != groups.cend(); ++it)
+        sorted.append({it.key(), it.value()});
+    std::sort(sorted.begin(), sorted.end(), [](const auto& a, const auto& b) {
+        const double ta = a.second.win_sum + a.second.loss_sum;
+        const double tb = b.second.win_sum + b.second.loss_sum;
+        return ta != tb ? ta > tb : a.first < b.first;
+    });
+    QJsonArray rows;
+    for (const auto& g : sorted)
+        rows.append(g.second.to_json(g.first));
+
+    return QJsonObject{{"summary", total.to_json(QString())},
+                       {"group_by", group_by},
+                       {"groups", rows},
+                       {"open_trades", open_trades}};
+}
+
+QJsonObject TradeJournalEngine::trade_to_json(const JournalTrade& t) {
+    QJsonObject o{{"id", t.id},
+                  {"account_id", t.account_id},
+                  {"account", t.account},
+                  {"broker", t.broker},
+                  {"strategy", t.strategy},
+                  {"symbol", t.symbol},
+                  {"direction", t.direction < 0 ? "short" : "long"},
+                  {"status", t.is_open() ? "open" : "closed"},
+                  {"quantity", t.quantity},
+                  {"entry_price", journal_round(t.entry_price, 6)},
+                  {"exit_price", journal_round(t.exit_price, 6)},
+                  {"entry_time", journal_iso(t.entry_ms)},
+                  {"exit_time", journal_iso(t.exit_ms)},
+                  {"fees", journal_round(t.fees, 2)},
+                  {"pnl", journal_round(t.pnl, 2)},
+                  {"return_pct", journal_round(t.return_pct * 100.0, 3)},
+                  {"fills", QJsonArray::fromStringList(t.fill_ids)},
+                  {"setup", t.setup},
+                  {"notes", t.notes},
+                  {"rating", t.rating},
+                  {"tags", QJsonArray::fromStringList(t.tags)}};
+    if (t.has_excursion) {
+        o["mae_pct"] = journal_round(t.mae_pct * 100.0, 3);
+        o["mfe_pct"] = journal_round(t.mfe_pct * 100.0, 3);
+        o["excursion_timeframe"] = t.excursion_tf;
+    }
+    QJsonArray atts;
+    for (const auto& a : t.attachments)
+        atts.append(QJsonObject{
+            {"id", a.id}, {"path", a.path}, {"caption", a.caption}, {"added_at", journal_iso(a.created_at)}});
+    o["attachments"] = atts;
+    return o;
+}
+
+bool TradeJournalEngine::valid_group_by(const QString& group_by) {
+    static const QSet<QString> keys{"tag", "setup", "strategy", "symbol", "account", "direction", "rating"};
+    return keys.contains(group_by);
+}
+
+} // namespace fincept::services::journal
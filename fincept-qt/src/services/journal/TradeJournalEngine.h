@@ -0,0 +1,56 @@
+// src/services/journal/TradeJournalEngine.h
+#pragma once
+#include "algo_engine/AlgoEngineTypes.h"
+#include "services/tca/TcaEngine.h"
+#include "storage/repositories/TradeJournalRepository.h"
+
+#include <QJsonObject>
+#include <QString>
+#include <QVector>
+
+namespace fincept::services::journal {
+
+/// Trade journal computations: round trips from fills, their excursions and
+/// the statistics over them. Pure computation; TradeJournalService gathers
+/// the fills and bars and keeps the user's annotations.
+///
+/// A book is one account (or unlinked paper portfolio) and one symbol. Its
+/// fills are replayed in time order and a trade runs from the fill that
+/// opens a position to the one that takes it back to flat; a fill that flips
+/// the position closes the trade and opens the next with the remainder.
+/// P&L is average-cost: each exit realizes direction × (exit − average
+/// entry) × quantity, less the fees of the fills in proportion to the
+/// quantity they contributed.
+///
+/// MAE / MFE are the worst and best prices seen between entry and exit (now,
+/// while open) relative to the average entry, signed so MAE ≤ 0 ≤ MFE for
+/// longs and shorts alike. The bar holding the entry counts whole, so on
+/// coarse bars they can include moves shortly before the entry.
+class TradeJournalEngine {
+  public:
+    /// Round trips in `fills`, newest entry first. A fill id seen under
+    /// several sources is kept under the most specific one (exec, algo,
+    /// paper, live). Trade ids are stable: a hash of the book and its opening
+    /// fill.
+    static QVector<JournalTrade> build_trades(const QVector<tca::TcaFill>& fills);
+
+    /// Sets the trade's MAE / MFE from `bars` (ascending, of its symbol);
+    /// false when no bar overlaps the holding period.
+    static bool apply_excursion(JournalTrade& trade, const QVector<algo::OhlcvCandle>& bars, qint64 now_ms,
+                                const QString& timeframe);
+
+    /// {summary, groups (by `group_by`, best total first), open_trades}.
+    /// group_by: tag | setup | strategy | symbol | account | direction |
+    /// rating. A trade with several tags counts under each; untagged and
+    /// unset trades fall under "(none)". Only closed trades are scored:
+    /// trades, wins, losses, win_rate, avg_win, avg_loss, expectancy (mean
+    /// P&L per trade), expectancy_pct (mean return), profit_factor,
+    /// total_pnl, best, worst, avg_hold_minutes, avg_mae_pct, avg_mfe_pct and
+    /// mfe_capture (realized return over MFE, for winners).
+    static QJsonObject stats(const QVector<JournalTrade>& trades, const QString& group_by);
+
+    static QJsonObject trade_to_json(const JournalTrade& t);
+    static bool valid_group_by(const QString& group_by);
+};
+
+} // namespace fincept::services::journal
@@ -0,0 +1,423 @@
+// src/services/journal/TradeJournalService.cpp
+#include "services/journal/TradeJournalService.h"
+
+#include "algo_engine/CandleDataFetcher.h"
+#include "core/logging/Logger.h"
+#include "services/tca/TcaService.h"
+#include "storage/repositories/TradeJournalRepository.h"
+
+#include <QDateTime>
+#include <QDir>
+#include <QFileInfo>
+#include <QHash>
+#include <QJsonArray>
+#include <QPointer>
+#include <QTimeZone>
+#include <QTimer>
+#include <QUuid>
+#include <QtConcurrent>
+
+#include <algorithm>
+#include <climits>
+
+namespace fincept::services::journal {
+
+namespace {
+
+constexpr const char* kJournalTag = "TradeJournal";
+constexpr qint64 kJournalDayMs = 24LL * 60 * 60 * 1000;
+constexpr int kJournalFirstSyncMs = 60 * 1000;
+constexpr int kJournalMaxBarDays = 730;
+
+// Finest Yahoo interval whose history still reaches `age_days` back.
+QString journal_timeframe(int age_days) {
+    if (age_days <= 7)
+        return QStringLiteral("1m");
+    if (age_days <= 59)
+        return QStringLiteral("5m");
+    return QStringLiteral("1h");
+}
+
+bool journal_matches(const JournalTrade& t, const JournalQuery& q) {
+    if (!q.account_id.isEmpty() && t.account_id != q.account_id)
+        return false;
+    if (!q.symbol.isEmpty() && t.symbol.compare(q.symbol, Qt::CaseInsensitive) != 0)
+        return false;
+    if (!q.strategy.isEmpty() && !t.strategy.contains(q.strategy, Qt::CaseInsensitive))
+        return false;
+    if (!q.setup.isEmpty() && t.setup.compare(q.setup, Qt::CaseInsensitive) != 0)
+        return false;
+    if (!q.tag.isEmpty() && !t.tags.contains(q.tag, Qt::CaseInsensitive))
+        return false;
+    if (q.status == "open" && !t.is_open())
+        return false;
+    if (q.status == "closed" && t.is_open())
+        return false;
+    return true;
+}
+
+QStringList journal_clean_tags(const QStringList& tags) {
+    QStringList out;
+    for (const auto& raw : tags) {
+        const QString tag = raw.trimmed().toLower();
+        if (!tag.isEmpty() && !out.contains(tag))
+            out << tag;
+    }
+    return out;
+}
+
+QString journal_num(const QJsonValue& v, int decimals = 2) {
+    return v.isDouble() ? QString::number(v.toDouble(), 'f', decimals) : QStringLiteral("n/a");
+}
+
+QString journal_cell(QString s) {
+    return s.replace(',', ' ').replace('|', ' ').replace('\n', ' ');
+}
+
+report::ReportComponent journal_component(const QString& type, const QString& content,
+                                          const QMap<QString, QString>& config = {}) {
+    report::ReportComponent c;
+    c.type = type;
+    c.content = content;
+    c.config = config;
+    return c;
+}
+
+report::ReportComponent journal_group_table(const QString& label, const QJsonArray& groups, int max_rows) {
+    QStringList rows{label + ",Trades,Win rate %,Avg win,Avg loss,Expectancy,Profit factor,Total P&L,MAE %,MFE %"};
+    for (int i = 0; i < groups.size() && i < max_rows; ++i) {
+        const QJsonObject g = groups[i].toObject();
+        rows << QStringList{journal_cell(g["key"].toString()),
+                            QString::number(g["trades"].toInt()),
+                            QString::number(g["win_rate"].toDouble() * 100.0, 'f', 1),
+                            journal_num(g["avg_win"]),
+                            journal_num(g["avg_loss"]),
+                            journal_num(g["expectancy"]),
+                            journal_num(g["profit_factor"]),
+                            journal_num(g["total_pnl"]),
+                            journal_num(g["avg_mae_pct"]),
+                            journal_num(g["avg_mfe_pct"])}
+                    .join(',');
+    }
+    return journal_component(QStringLiteral("table"), {}, {{"csv", rows.join('|')}});
+}
+
+} // namespace
+
+JournalQuery JournalQuery::from_json(const QJsonObject& o) {
+    JournalQuery q;
+    q.days = std::clamp(o["days"].toInt(q.days), 1, 3650);
+    q.account_id = o["account_id"].toString().trimmed();
+    q.symbol = o["symbol"].toString().trimmed();
+    q.strategy = o["strategy"].toString().trimmed();
+    q.setup = o["setup"].toString().trimmed();
+    q.tag = o["tag"].toString().trimmed();
+    q.status = o["status"].toString().trimmed().toLower();
+    q.limit = std::clamp(o["limit"].toInt(q.limit), 1, 5000);
+    return q;
+}
+
+QJsonObject JournalQuery::to_json() const {
+    return QJsonObject{{"days", days},         {"account_id", account_id}, {"symbol", symbol},
+                       {"strategy", strategy}, {"setup", setup},           {"tag", tag},
+                       {"status", status},     {"limit", limit}};
+}
+
+TradeJournalService& TradeJournalService::instance() {
+    static TradeJournalService s;
+    return s;
+}
+
+void TradeJournalService::start() {
+    if (timer_)
+        return;
+    timer_ = new QTimer(this);
+    timer_->setInterval(kAutoSyncMs);
+    connect(timer_, &QTimer::timeout, this, [this]() { sync(); });
+    timer_->start();
+    QTimer::singleShot(kJournalFirstSyncMs, this, [this]() { sync(); });
+}
+
+QString TradeJournalService::sync(int days) {
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    const qint64 since_ms = QDateTime::currentMSecsSinceEpoch() - qint64(std::max(days, 1)) * kJournalDayMs;
+    const QVector<tca::TcaFill> exec = tca::TcaService::exec_fills(since_ms);
+
+    QPointer<TradeJournalService> self = this;
+    (void)QtConcurrent::run([self, request_id, since_ms, exec]() {
+        const QVector<tca::TcaFill> fills = tca::TcaService::ledger_fills(since_ms, exec);
+        QMetaObject::invokeMethod(
+            self,
+            [self, request_id, fills]() {
+                if (self)
+                    self->rebuild(request_id, fills);
+            },
+            Qt::QueuedConnection);
+    });
+    return request_id;
+}
+
+void TradeJournalService::rebuild(const QString& request_id, const QVector<tca::TcaFill>& gathered) {
+    auto& repo = TradeJournalRepository::instance();
+    QVector<tca::TcaFill> valid;
+    for (const auto& f : gathered)
+        if (f.time_ms > 0)
+            valid.append(f);
+    if (auto w = repo.upsert_fills(valid); w.is_err()) {
+        emit error_occurred(request_id, "Saving fills failed: " + QString::fromStdString(w.error()));
+        return;
+    }
+    auto fills = repo.load_fills();
+    auto existing = repo.list_trades();
+    if (fills.is_err() || existing.is_err()) {
+        emit error_occurred(request_id, "Reading the journal failed");
+        return;
+    }
+
+    // Closed trades keep the excursion they already have; the rest are read
+    // from bars again, keeping the stored one when the bars are unavailable.
+    QHash<QString, JournalTrade> known;
+    for (const auto& t : existing.value())
+        known.insert(t.id, t);
+    QVector<JournalTrade> trades = TradeJournalEngine::build_trades(fills.value());
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    QVector<int> pending;
+    QStringList symbols;
+    qint64 oldest = now;
+    for (int i = 0; i < trades.size(); ++i) {
+        JournalTrade& t = trades[i];
+        const auto it = known.constFind(t.id);
+        if (it != known.cend() && it->has_excursion) {
+            t.has_excursion = true;
+            t.mae_pct = it->mae_pct;
+            t.mfe_pct = it->mfe_pct;
+            t.excursion_tf = it->excursion_tf;
+            if (!t.is_open() && it->exit_ms == t.exit_ms)
+                continue;
+        }
+        if (now - t.entry_ms > qint64(kJournalMaxBarDays) * kJournalDayMs)
+            continue;
+        pending << i;
+        oldest = std::min(oldest, t.entry_ms);
+        if (!symbols.contains(t.symbol))
+            symbols << t.symbol;
+    }
+
+    const int age_days = int((now - oldest) / kJournalDayMs) + 1;
+    const QString timeframe = journal_timeframe(age_days);
+    const int fills_total = int(fills.value().size());
+    auto finish = [this, request_id, trades, pending, timeframe, fills_total,
+                   gathered_count = int(valid.size())](const QHash<QString, QVector<algo::OhlcvCandle>>& bars,
+                                                       const QStringList& errors) mutable {
+        const qint64 at = QDateTime::currentMSecsSinceEpoch();
+        int updated = 0;
+        for (int i : pending)
+            if (TradeJournalEngine::apply_excursion(trades[i], bars.value(trades[i].symbol), at, timeframe))
+                ++updated;
+        if (auto w = TradeJournalRepository::instance().save_trades(trades); w.is_err()) {
+            emit error_occurred(request_id, "Saving trades failed: " + QString::fromStdString(w.error()));
+            return;
+        }
+        int open = 0;
+        for (const auto& t : trades)
+            open += t.is_open() ? 1 : 0;
+        QJsonObject summary{{"request_id", request_id},
+                            {"fills_gathered", gathered_count},
+                            {"fills_total", fills_total},
+                            {"trades", int(trades.size())},
+                            {"open_trades", open},
+                            {"excursions_updated", updated},
+                            {"timeframe", timeframe}};
+        if (!errors.isEmpty())
+            summary["data_errors"] = QJsonArray::fromStringList(errors);
+        LOG_INFO(kJournalTag, QString("Sync %1: %2 fills, %3 trades (%4 open), %5 excursions on %6 bars")
+                                  .arg(request_id.left(8))
+                                  .arg(fills_total)
+                                  .arg(trades.size())
+                                  .arg(open)
+                                  .arg(updated)
+                                  .arg(timeframe));
+        emit synced(request_id, summary);
+    };
+
+    if (symbols.isEmpty()) {
+        finish({}, {});
+        return;
+    }
+    algo::CandleDataFetcher::instance().fetch_multi(symbols, timeframe, std::min(age_days + 1, kJournalMaxBarDays),
+                                                    algo::DataSource::YFinance, {}, {}, finish);
+}
+
+Result<QVector<JournalTrade>> TradeJournalService::trades(const JournalQuery& query) {
+    using R = Result<QVector<JournalTrade>>;
+    const qint64 since_ms = QDateTime::currentMSecsSinceEpoch() - qint64(query.days) * kJournalDayMs;
+    auto all = TradeJournalRepository::instance().list_trades(since_ms);
+    if (all.is_err())
+        return all;
+    QVector<JournalTrade> out;
+    for (const auto& t : all.value()) {
+        if (!journal_matches(t, query))
+            continue;
+        out.append(t);
+        if (out.size() >= query.limit)
+            break;
+    }
+    return R::ok(std::move(out));
+}
+
+std::optional<JournalTrade> TradeJournalService::trade(const QString& id) {
+    return TradeJournalRepository::instance().get_trade(id.trimmed());
+}
+
+Result<QJsonObject> TradeJournalService::stats(const JournalQuery& query, const QString& group_by) {
+    using R = Result<QJsonObject>;
+    if (!TradeJournalEngine::valid_group_by(group_by))
+        return R::err("group_by must be one of tag, setup, strategy, symbol, account, direction, rating");
+    JournalQuery all = query;
+    all.limit = INT_MAX;
+    auto r = trades(all);
+    if (r.is_err())
+        return R::err(r.error());
+    QJsonObject out = TradeJournalEngine::stats(r.value(), group_by);
+    out["query"] = query.to_json();
+    return R::ok(out);
+}
+
+Result<JournalTrade> TradeJournalService::annotate(const QString& trade_id, const JournalAnnotation& changes) {
+    using R = Result<JournalTrade>;
+    auto& repo = TradeJournalRepository::instance();
+    auto found = repo.get_trade(trade_id.trimmed());
+    if (!found)
+        return R::err("Unknown journal trade: " + trade_id.toStdString());
+    JournalTrade t = *found;
+
+    if (changes.setup)
+        t.setup = changes.setup->trimmed();
+    if (changes.notes) {
+        const QString text = changes.notes->trimmed();
+        if (changes.append_notes && !text.isEmpty()) {
+            const QString stamp = QDateTime::currentDateTime().toString(QStringLiteral("yyyy-MM-dd HH:mm"));
+            t.notes = (t.notes.isEmpty() ? QString() : t.notes + '\n') + QString("[%1] %2").arg(stamp, text);
+        } else if (!changes.append_notes) {
+            t.notes = text;
+        }
+    }
+    if (changes.rating) {
+        if (*changes.rating < 0 || *changes.rating > 5)
+            return R::err("rating must be between 0 (unrated) and 5");
+        t.rating = *changes.rating;
+    }
+    if (auto w = repo.save_annotation(t); w.is_err())
+        return R::err(w.error());
+
+    QStringList tags = changes.tags ? journal_clean_tags(*changes.tags) : t.tags;
+    for (const auto& tag : journal_clean_tags(changes.add_tags))
+        if (!tags.contains(tag))
+            tags << tag;
+    for (const auto& tag : journal_clean_tags(changes.remove_tags))
+        tags.removeAll(tag);
+    if (tags != t.tags) {
+        if (auto w = repo.set_tags(t.id, tags); w.is_err())
+            return R::err(w.error());
+    }
+    LOG_INFO(kJournalTag, QString("Annotated trade %1 (%2)").arg(t.id, t.symbol));
+    auto updated = repo.get_trade(t.id);
+    return updated ? R::ok(*updated) : R::err("Trade vanished while annotating");
+}
+
+Result<JournalAttachment> TradeJournalService::attach(const QString& trade_id, const QString& path,
+                                                      const QString& caption) {
+    using R = Result<JournalAttachment>;
+    if (!TradeJournalRepository::instance().get_trade(trade_id.trimmed()))
+        return R::err("Unknown journal trade: " + trade_id.toStdString());
+    const QFileInfo fi(QDir::fromNativeSeparators(path.trimmed()));
+    if (!fi.isAbsolute())
+        return R::err("Attachment path must be absolute");
+    if (!fi.isFile())
+        return R::err("No such file: " + fi.filePath().toStdString());
+    JournalAttachment a;
+    a.trade_id = trade_id.trimmed();
+    a.path = fi.absoluteFilePath();
+    a.caption = caption.trimmed();
+    return TradeJournalRepository::instance().add_attachment(a);
+}
+
+Result<void> TradeJournalService::detach(const QString& attachment_id) {
+    return TradeJournalRepository::instance().remove_attachment(attachment_id.trimmed());
+}
+
+QVector<report::ReportComponent> TradeJournalService::report_components(const QJsonObject& by_tag,
+                                                                        const QJsonObject& by_setup,
+                                                                        const QVector<JournalTrade>& trades,
+                                                                        int max_images) {
+    const QJsonObject s = by_tag["summary"].toObject();
+    const QJsonObject q = by_tag["query"].toObject();
+    QVector<report::ReportComponent> out;
+    out << journal_component(QStringLiteral("heading"), QStringLiteral("Trade Journal"));
+    out << journal_component(
+        QStringLiteral("text"),
+        QString("**%1** closed trades over the last %2 days (%3 still open), rebuilt from the fills ledger. "
+                "P&L is net of fees in each instrument's currency; MAE / MFE are the worst and best moves "
+                "against and in favour of a trade while it was held, in percent of the entry price.")
+            .arg(s["trades"].toInt())
+            .arg(q["days"].toInt())
+            .arg(by_tag["open_trades"].toInt()));
+    out << journal_component(
+        QStringLiteral("stats_block"), {},
+        {{"title", QStringLiteral("Performance")},
+         {"data", QStringList{"Win rate:" + QString::number(s["win_rate"].toDouble() * 100.0, 'f', 1) + "%",
+                              "Expectancy:" + journal_num(s["expectancy"]) + " (" +
+                                  journal_num(s["expectancy_pct"], 3) + "%)",
+                              "Profit factor:" + journal_num(s["profit_factor"]),
+                              "Avg win / loss:" + journal_num(s["avg_win"]) + " / " + journal_num(s["avg_loss"]),
+                              "Total P&L:" + journal_num(s["total_pnl"]),
+                              "Avg MAE / MFE:" + journal_num(s["avg_mae_pct"]) + "% / " +
+                                  journal_num(s["avg_mfe_pct"]) + "%"}
+                      .join('\n')}});
+    out << journal_component(QStringLiteral("heading"), QStringLiteral("By Tag"));
+    out << journal_group_table(QStringLiteral("Tag"), by_tag["groups"].toArray(), 25);
+    out << journal_component(QStringLiteral("heading"), QStringLiteral("By Setup"));
+    out << journal_group_table(QStringLiteral("Setup"), by_setup["groups"].toArray(), 25);
+
+    QStringList rows{"Entry,Symbol,Side,Qty,Entry px,Exit px,P&L,Return %,MAE %,MFE %,Setup,Tags,Notes"};
+    for (const auto& t : trades) {
+        const QJsonObject j = TradeJournalEngine::trade_to_json(t);
+        rows << QStringList{t.entry_ms > 0 ? QDateTime::fromMSecsSinceEpoch(t.entry_ms, QTimeZone::UTC)
+                                                 .toString(QStringLiteral("yyyy-MM-dd HH:mm"))
+                                           : QString(),
+                            journal_cell(t.symbol),
+                            j["direction"].toString(),
+                            QString::number(t.quantity),
+                            journal_num(j["entry_price"], 4),
+                            t.is_open() ? QStringLiteral("open") : journal_num(j["exit_price"], 4),
+                            journal_num(j["pnl"]),
+                            journal_num(j["return_pct"]),
+                            journal_num(j["mae_pct"]),
+                            journal_num(j["mfe_pct"]),
+                            journal_cell(t.setup),
+                            journal_cell(t.tags.join(' ')),
+                            journal_cell(t.notes.left(160))}
+                    .join(',');
+    }
+    out << journal_component(QStringLiteral("heading"), QStringLiteral("Trades"));
+    out << journal_component(QStringLiteral("table"), {}, {{"csv", rows.join('|')}});
+
+    int images = 0;
+    for (const auto& t : trades) {
+        for (const auto& a : t.attachments) {
+            if (images >= max_images)
+                break;
+            const QString suffix = QFileInfo(a.path).suffix().toLower();
+            if (!QStringList{"png", "jpg", "jpeg", "bmp", "gif", "webp"}.contains(suffix) || !QFileInfo::exists(a.path))
+                continue;
+            const QString caption = a.caption.isEmpty() ? QString("%1 %2").arg(t.symbol, t.setup).trimmed()
+                                                        : QString("%1 — %2").arg(t.symbol, a.caption);
+            out << journal_component(QStringLiteral("image"), {},
+                                     {{"path", a.path}, {"width", "600"}, {"caption", caption}, {"align", "center"}});
+            ++images;
+        }
+    }
+    return out;
+}
+
+} // namespace fincept::services::journal
@@ -0,0 +1,98 @@
+// src/services/journal/TradeJournalService.h
+#pragma once
+#include "core/report/ReportDocument.h"
+#include "core/result/Result.h"
+#include "services/journal/TradeJournalEngine.h"
+
+#include <QJsonObject>
+#include <QObject>
+#include <QStringList>
+
+#include <optional>
+
+class QTimer;
+
+namespace fincept::services::journal {
+
+/// Which journal trades a listing or statistic covers. Empty filters match
+/// everything.
+struct JournalQuery {
+    int days = 90; // by entry time
+    QString account_id;
+    QString symbol;
+    QString strategy; // substring, case-insensitive
+    QString setup;    // case-insensitive
+    QString tag;      // case-insensitive
+    QString status;   // open | closed
+    int limit = 200;  // list only, newest first
+
+    static JournalQuery from_json(const QJsonObject& o);
+    QJsonObject to_json() const;
+};
+
+/// Changes to a trade's annotations; unset members are left alone.
+struct JournalAnnotation {
+    std::optional<QString> setup;
+    std::optional<QString> notes;
+    bool append_notes = false; // add `notes` as a dated line instead of replacing
+    std::optional<int> rating;
+    std::optional<QStringList> tags; // replaces the tags
+    QStringList add_tags;
+    QStringList remove_tags;
+};
+
+/// Trade journal over the fills ledger (TcaService::ledger_fills).
+///
+/// sync() copies the ledger's recent fills into journal_fills — the ledger
+/// only sees today's book of live accounts, so syncing regularly (start()
+/// does it hourly) is what keeps live history — then rebuilds every round
+/// trip from the journaled fills with TradeJournalEngine, fetches intraday
+/// bars for the trades whose MAE / MFE are missing or still moving, and
+/// saves them. Setup, notes, rating, tags and chart snapshots recorded
+/// against a trade survive rebuilds. A position opened before the oldest
+/// journaled fill is seen from its first journaled fill on.
+///
+/// Main thread only; the ledger is gathered on a worker.
+class TradeJournalService : public QObject {
+    Q_OBJECT
+  public:
+    static TradeJournalService& instance();
+
+    /// First sync plus the hourly timer. Idempotent.
+    void start();
+
+    /// Starts a sync over the last `days` of the ledger; returns its request
+    /// id, which synced and error_occurred carry.
+    QString sync(int days = 7);
+
+    Result<QVector<JournalTrade>> trades(const JournalQuery& query);
+    std::optional<JournalTrade> trade(const QString& id);
+    /// TradeJournalEngine::stats over the trades matching `query` (no limit).
+    Result<QJsonObject> stats(const JournalQuery& query, const QString& group_by);
+
+    Result<JournalTrade> annotate(const QString& trade_id, const JournalAnnotation& changes);
+    /// Attaches an existing image or other file by absolute path.
+    Result<JournalAttachment> attach(const QString& trade_id, const QString& path, const QString& caption);
+    Result<void> detach(const QString& attachment_id);
+
+    /// Report-builder components: summary stats, tables by tag and setup,
+    /// the trades with their notes and up to `max_images` attached snapshots.
+    static QVector<report::ReportComponent> report_components(const QJsonObject& by_tag, const QJsonObject& by_setup,
+                                                              const QVector<JournalTrade>& trades, int max_images);
+
+  signals:
+    void synced(QString request_id, QJsonObject summary);
+    void error_occurred(QString request_id, QString message);
+
+  private:
+    TradeJournalService() = default;
+    Q_DISABLE_COPY(TradeJournalService)
+
+    void rebuild(const QString& request_id, const QVector<tca::TcaFill>& gathered);
+
+    QTimer* timer_ = nullptr;
+
+    static constexpr int kAutoSyncMs = 60 * 60 * 1000;
+};
+
+} // namespace fincept::services::journal
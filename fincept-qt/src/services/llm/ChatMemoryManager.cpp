@@ -0,0 +1,261 @@
+// ChatMemoryManager.cpp — rolling summaries + token budgeting for AI chat history.
+
+#include "services/llm/ChatMemoryManager.h"
+
+#include "core/logging/Logger.h"
+#include "services/llm/ModelCatalog.h"
+#include "storage/repositories/ChatRepository.h"
+
+#include <QMetaObject>
+#include <QMutexLocker>
+#include <QtConcurrent/QtConcurrent>
+
+#include <algorithm>
+
+namespace fincept::ai_chat {
+
+namespace {
+
+constexpr const char* kChatMemoryTag = "ChatMemory";
+
+constexpr int kDefaultContext = 8192;       // model missing from ModelCatalog
+constexpr int kDefaultOutputReserve = 4096; // model without a published output cap
+constexpr int kPromptReserve = 8000;        // system prompt + tool catalog
+constexpr int kMaxHistoryTokens = 48000;    // history is resent (and billed) every turn, even on 1M-token models
+constexpr int kMinHistoryTokens = 1024;
+constexpr int kPerMessageTokens = 4;       // role / framing overhead per message
+constexpr double kSummarizeAt = 0.8;       // fold once the verbatim part passes this share of the budget…
+constexpr double kKeepAfterFold = 0.5;     // …down to this share, so folds don't run every turn
+constexpr int kSummaryMaxWords = 400;      // ~550 tokens
+constexpr int kFoldMessageChars = 6000;    // per message — attached files are clipped, not summarized whole
+
+const QString kSummaryLead = QStringLiteral("Summary of our conversation so far:\n\n");
+const QString kSummaryAck = QStringLiteral("Understood — I'll continue from that summary.");
+
+int message_tokens(const ChatMessage& m) {
+    if (m.role == "system")
+        return 0; // UI notices; never sent to the model
+    return ChatMemoryManager::estimate_tokens(m.content) + kPerMessageTokens;
+}
+
+int summary_tokens(const QString& summary) {
+    if (summary.isEmpty())
+        return 0;
+    return ChatMemoryManager::estimate_tokens(kSummaryLead + summary) +
+           ChatMemoryManager::estimate_tokens(kSummaryAck) + 2 * kPerMessageTokens;
+}
+
+// Index of the oldest message (>= from) such that it and everything after it
+// fit in `budget`. The newest message is always kept. The start then moves
+// forward to a user turn: Anthropic and Gemini reject a history that opens on
+// an assistant turn right after the summary exchange.
+int window_start(const QVector<ChatMessage>& msgs, int from, int budget) {
+    const int n = static_cast<int>(msgs.size());
+    int start = n;
+    int used = 0;
+    for (int i = n - 1; i >= from; --i) {
+        const int cost = message_tokens(msgs[i]);
+        if (used + cost > budget && start < n)
+            break;
+        used += cost;
+        start = i;
+    }
+    while (start < n && msgs[start].role != "user")
+        ++start;
+    return start;
+}
+
+int verbatim_tokens(const QVector<ChatMessage>& msgs, int from) {
+    int used = 0;
+    for (int i = from; i < msgs.size(); ++i)
+        used += message_tokens(msgs[i]);
+    return used;
+}
+
+QString build_fold_prompt(const QString& previous, const QVector<ChatMessage>& msgs, int from, int to) {
+    QString transcript;
+    for (int i = from; i < to; ++i) {
+        const auto& m = msgs[i];
+        if (m.role == "system")
+            continue;
+        QString content = m.content;
+        if (content.size() > kFoldMessageChars)
+            content = content.left(kFoldMessageChars) + " […]";
+        transcript += QString("[%1]\n%2\n\n").arg(m.role.toUpper(), content);
+    }
+    QString prompt = QString("You maintain the running memory of a conversation between a user and the assistant "
+                             "of a financial terminal. Update the summary so it also covers the new messages below. "
+                             "Keep every ticker, figure, date, decision, user preference and open question; drop "
+                             "pleasantries and tool chatter. Write in the third person, at most %1 words, and reply "
+                             "with the summary only.\n\n")
+                         .arg(kSummaryMaxWords);
+    prompt += previous.isEmpty() ? QStringLiteral("Current summary: (none)\n\n")
+                                 : QString("Current summary:\n%1\n\n").arg(previous);
+    prompt += "New messages:\n\n" + transcript.trimmed();
+    return prompt;
+}
+
+} // namespace
+
+ChatMemoryManager& ChatMemoryManager::instance() {
+    static ChatMemoryManager s;
+    return s;
+}
+
+int ChatMemoryManager::estimate_tokens(const QString& text) {
+    int ascii = 0;
+    int other = 0;
+    for (const QChar c : text) {
+        if (c.unicode() < 0x80)
+            ++ascii;
+        else if (!c.isLowSurrogate())
+            ++other;
+    }
+    return (ascii + 3) / 4 + other;
+}
+
+int ChatMemoryManager::history_budget(const QString& provider, const QString& model) {
+    int context = ModelCatalog::context_window(provider, model);
+    if (context <= 0)
+        context = kDefaultContext;
+    int output = ModelCatalog::output_cap(provider, model);
+    if (output <= 0)
+        output = kDefaultOutputReserve;
+    output = std::min(output, context / 4);
+    const int prompt = std::min(kPromptReserve, context / 4);
+    return std::clamp(context - output - prompt, kMinHistoryTokens, kMaxHistoryTokens);
+}
+
+Result<ContextWindow> ChatMemoryManager::get_context_window(const QString& session_id, const QString& provider,
+                                                            const QString& model, int reserve_tokens) const {
+    auto& repo = ChatRepository::instance();
+    auto mr = repo.get_messages(session_id);
+    if (mr.is_err())
+        return Result<ContextWindow>::err(mr.error());
+    auto memr = repo.get_memory(session_id);
+    if (memr.is_err())
+        return Result<ContextWindow>::err(memr.error());
+    const auto& msgs = mr.value();
+    const auto& mem = memr.value();
+
+    ContextWindow w;
+    w.budget_tokens = history_budget(provider, model);
+    w.total_messages = static_cast<int>(msgs.size());
+    const int covered = std::clamp(mem.covered_count, 0, w.total_messages);
+    if (covered > 0)
+        w.summary = mem.summary;
+
+    const int available = std::max(0, w.budget_tokens - reserve_tokens - summary_tokens(w.summary));
+    const int start = window_start(msgs, covered, available);
+
+    if (!w.summary.isEmpty()) {
+        w.messages.push_back({"user", kSummaryLead + w.summary});
+        w.messages.push_back({"assistant", kSummaryAck});
+        w.used_tokens += summary_tokens(w.summary);
+    }
+    for (int i = start; i < w.total_messages; ++i) {
+        if (msgs[i].role == "system")
+            continue;
+        w.messages.push_back({msgs[i].role, msgs[i].content});
+        w.used_tokens += message_tokens(msgs[i]);
+        ++w.included_messages;
+    }
+    w.summarized_messages = covered;
+    w.dropped_messages = start - covered;
+    return Result<ContextWindow>::ok(std::move(w));
+}
+
+void ChatMemoryManager::maybe_summarize(const QString& session_id, const QString& provider, const QString& model) {
+    if (session_id.isEmpty())
+        return;
+    {
+        QMutexLocker lock(&mutex_);
+        if (in_flight_.contains(session_id))
+            return;
+    }
+
+    auto& repo = ChatRepository::instance();
+    auto mr = repo.get_messages(session_id);
+    auto memr = repo.get_memory(session_id);
+    if (mr.is_err() || memr.is_err())
+        return;
+    const auto msgs = mr.value();
+    const auto mem = memr.value();
+    const int n = static_cast<int>(msgs.size());
+    const int covered = std::clamp(mem.covered_count, 0, n);
+
+    const int budget = history_budget(provider, model);
+    const int pending = verbatim_tokens(msgs, covered) + summary_tokens(mem.summary);
+    if (pending <= static_cast<int>(budget * kSummarizeAt))
+        return;
+
+    // Fold everything older than what fits in the post-fold share, but never
+    // more than one budget's worth of text per call — the summarizer runs on a
+    // model with the same limits. Anything left is picked up by the follow-up.
+    const int keep_from = window_start(msgs, covered, static_cast<int>(budget * kKeepAfterFold));
+    int fold_to = covered;
+    int fold_tokens = 0;
+    while (fold_to < keep_from) {
+        const int cost = std::min(message_tokens(msgs[fold_to]), kFoldMessageChars / 4 + kPerMessageTokens);
+        if (fold_to > covered && fold_tokens + cost > budget)
+            break;
+        fold_tokens += cost;
+        ++fold_to;
+    }
+    // Keep the fold on a turn boundary so the summary never splits a question from its answer.
+    while (fold_to < keep_from && msgs[fold_to].role != "user")
+        ++fold_to;
+    if (fold_to <= covered)
+        return;
+
+    {
+        QMutexLocker lock(&mutex_);
+        if (in_flight_.contains(session_id))
+            return;
+        in_flight_.insert(session_id);
+    }
+
+    const QString prompt = build_fold_prompt(mem.summary, msgs, covered, fold_to);
+    LOG_INFO(kChatMemoryTag,
+             QString("Summarizing messages %1-%2 of session %3").arg(covered).arg(fold_to).arg(session_id));
+
+    // LlmService::chat holds the request-serialization lock for the whole call;
+    // this runs right after a reply lands, so it mostly overlaps the user typing.
+    (void)QtConcurrent::run([this, session_id, provider, model, prompt, fold_to]() {
+        const LlmResponse resp = LlmService::instance().chat(prompt, {}, false);
+        QMetaObject::invokeMethod(
+            this,
+            [this, session_id, provider, model, fold_to, resp]() {
+                {
+                    QMutexLocker lock(&mutex_);
+                    in_flight_.remove(session_id);
+                }
+                const QString summary = resp.content.trimmed();
+                if (!resp.success || summary.isEmpty()) {
+                    LOG_WARN(kChatMemoryTag, QString("Summary for session %1 failed: %2")
+                                                 .arg(session_id, resp.error.isEmpty() ? "empty reply" : resp.error));
+                    return;
+                }
+                ChatSessionMemory mem;
+                mem.session_id = session_id;
+                mem.summary = summary;
+                mem.covered_count = fold_to;
+                mem.summary_tokens = estimate_tokens(summary);
+                mem.provider = LlmService::instance().active_provider();
+                mem.model = LlmService::instance().active_model();
+                auto r = ChatRepository::instance().save_memory(mem);
+                if (r.is_err()) {
+                    // Typically the session was deleted while the summary was in flight.
+                    LOG_WARN(kChatMemoryTag, QString("Saving summary for session %1 failed: %2")
+                                                 .arg(session_id, QString::fromStdString(r.error())));
+                    return;
+                }
+                emit summary_updated(session_id, fold_to);
+                // A capped fold may leave the history over budget — continue from the new summary.
+                maybe_summarize(session_id, provider, model);
+            },
+            Qt::QueuedConnection);
+    });
+}
+
+} // namespace fincept::ai_chat
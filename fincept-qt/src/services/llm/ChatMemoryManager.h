@@ -0,0 +1,77 @@
+#pragma once
+// ChatMemoryManager — keeps long AI chat sessions inside the model's context.
+//
+// chat_messages stores every turn verbatim; resending all of it eventually
+// overruns the provider's context window (and bills the whole history every
+// turn). The manager builds the history actually sent with a request:
+//
+//   [rolling summary of the oldest turns]  +  [newest turns that fit the budget]
+//
+// The budget comes from ModelCatalog::context_window() minus room for the
+// system prompt / tool catalog and the reply. Once the verbatim part outgrows
+// the budget, maybe_summarize() folds the oldest turns into the session's
+// summary (chat_session_memory, v072) with one non-tool LLM call.
+
+#include "core/result/Result.h"
+#include "services/llm/LlmService.h"
+
+#include <QMutex>
+#include <QObject>
+#include <QSet>
+#include <QString>
+
+#include <vector>
+
+namespace fincept::ai_chat {
+
+struct ContextWindow {
+    /// History to pass to LlmService::chat / chat_streaming: the summary as a
+    /// user/assistant exchange (every provider accepts that, unlike a mid-history
+    /// system message), then the newest turns oldest-first, starting on a user turn.
+    std::vector<ConversationMessage> messages;
+    QString summary;
+    int budget_tokens = 0;       // history budget for this model
+    int used_tokens = 0;         // estimated tokens of `messages`
+    int total_messages = 0;      // stored messages in the session
+    int summarized_messages = 0; // covered by the summary
+    int included_messages = 0;   // sent verbatim
+    int dropped_messages = 0;    // neither — older than the window, not yet summarized
+};
+
+class ChatMemoryManager : public QObject {
+    Q_OBJECT
+  public:
+    static ChatMemoryManager& instance();
+
+    /// Rough token count without a tokenizer: ~4 chars per token for ASCII,
+    /// one token per non-ASCII char (CJK and friends tokenize far denser).
+    static int estimate_tokens(const QString& text);
+
+    /// Tokens of conversation history a request to (provider, model) may carry.
+    static int history_budget(const QString& provider, const QString& model);
+
+    /// History for the next request in `session_id`. `reserve_tokens` is held
+    /// back for content sent alongside it (normally the new user message).
+    Result<ContextWindow> get_context_window(const QString& session_id, const QString& provider,
+                                             const QString& model, int reserve_tokens = 0) const;
+
+    /// Folds the oldest unsummarized turns into the session summary when the
+    /// verbatim history has outgrown the budget. Runs on a worker thread with
+    /// the active LLM profile; emits summary_updated on success. No-op when a
+    /// summary for the session is already in flight.
+    void maybe_summarize(const QString& session_id, const QString& provider, const QString& model);
+
+    ChatMemoryManager(const ChatMemoryManager&) = delete;
+    ChatMemoryManager& operator=(const ChatMemoryManager&) = delete;
+
+  signals:
+    void summary_updated(const QString& session_id, int covered_count);
+
+  private:
+    ChatMemoryManager() = default;
+
+    mutable QMutex mutex_;
+    QSet<QString> in_flight_;
+};
+
+} // namespace fincept::ai_chat
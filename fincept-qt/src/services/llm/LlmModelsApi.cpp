@@ -82,6 +82,8 @@ QString LlmService::get_models_url(const QString& provider, const QString& api_k
         return "https://api.moonshot.ai/v1/models";
     if (p == "aihubmix")
         return "https://aihubmix.com/v1/models"; // fallback if prefilled base_url was cleared
+    if (p == "llama_cpp")
+        return "http://localhost:8080/v1/models";
     if (p == "fincept")
         return fincept::AppConfig::instance().api_base_url() + "/research/llm/models";
     // minimax has no public /v1/models — caller falls back to known models.
@@ -99,7 +101,7 @@ QMap<QString, QString> LlmService::get_models_headers(const QString& provider, c
     } else if (p == "gemini" || p == "google") {
         if (!api_key.isEmpty())
             h["x-goog-api-key"] = api_key;
-    } else if (p == "ollama") {
+    } else if (p == "ollama" || p == "llama_cpp") {
         // No auth.
     } else if (p == "fincept") {
         // Same fallback as ensure_config — resolve via AuthManager
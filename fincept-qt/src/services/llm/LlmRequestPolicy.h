@@ -24,7 +24,9 @@
 #include <QSet>
 #include <QString>
 
+#include <atomic>
 #include <functional>
+#include <memory>
 
 namespace fincept::ai_chat::detail {
 
@@ -66,6 +68,26 @@ struct ChatSessionGuard {
     ChatSessionGuard& operator=(const ChatSessionGuard&) = delete;
 };
 
+// Cancellation flag of the in-flight chat_streaming() request, set by the
+// worker for the duration of the request. cancel_streaming() flips it from the
+// UI thread; the SSE loop polls it and aborts the reply, and the tool loops
+// check it between rounds (an in-flight tool round still completes).
+inline thread_local std::shared_ptr<std::atomic_bool> t_cancel_flag;
+
+struct CancelFlagGuard {
+    std::shared_ptr<std::atomic_bool> prev;
+    explicit CancelFlagGuard(std::shared_ptr<std::atomic_bool> f) : prev(std::move(t_cancel_flag)) {
+        t_cancel_flag = std::move(f);
+    }
+    ~CancelFlagGuard() { t_cancel_flag = std::move(prev); }
+    CancelFlagGuard(const CancelFlagGuard&) = delete;
+    CancelFlagGuard& operator=(const CancelFlagGuard&) = delete;
+};
+
+inline bool cancel_requested() {
+    return t_cancel_flag && t_cancel_flag->load();
+}
+
 struct ToolPolicyGuard {
     LlmService::ToolPolicy prev;
     explicit ToolPolicyGuard(LlmService::ToolPolicy p) : prev(t_request_policy) { t_request_policy = p; }
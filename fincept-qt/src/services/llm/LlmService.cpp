@@ -380,6 +380,8 @@ QString LlmService::get_endpoint_url() const {
         return "https://api.moonshot.ai/v1/chat/completions";
     if (p == "ollama")
         return "http://localhost:11434/v1/chat/completions";
+    if (p == "llama_cpp")
+        return "http://localhost:8080/v1/chat/completions";
     if (p == "xai")
         return "https://api.x.ai/v1/chat/completions";
     if (p == "aihubmix")
@@ -517,6 +519,11 @@ LlmResponse LlmService::do_request(const QString& user_message, const std::vecto
             const int kMaxRounds = active_max_tool_rounds();
             const QJsonArray ant_tools = build_anthropic_tools();
             for (int round = 0; round < kMaxRounds; ++round) {
+                if (detail::cancel_requested()) {
+                    resp.cancelled = true;
+                    resp.error = "Cancelled";
+                    return resp;
+                }
                 QJsonObject fu;
                 fu["model"] = model_;
                 fu["messages"] = loop_msgs;
@@ -610,6 +617,11 @@ LlmResponse LlmService::do_request(const QString& user_message, const std::vecto
                 QJsonArray model_parts = parts;
                 QJsonArray last_response_parts;
                 for (int round = 0; round < kMaxRounds; ++round) {
+                    if (detail::cancel_requested()) {
+                        resp.cancelled = true;
+                        resp.error = "Cancelled";
+                        return resp;
+                    }
                     fu_contents.append(QJsonObject{{"role", "model"}, {"parts", model_parts}});
 
                     QJsonArray fn_response_parts;
@@ -901,7 +913,19 @@ LlmResponse LlmService::do_streaming_request(const QString& user_message,
     timeout.setSingleShot(true);
     timeout.start(120000);
 
+    // cancel_streaming() flips an atomic from the UI thread; poll it here
+    // rather than reaching into this thread's QNAM from another thread.
+    bool cancelled = false;
+    QTimer cancel_poll;
+    cancel_poll.start(100);
+
     QObject::connect(&timeout, &QTimer::timeout, &loop, &QEventLoop::quit);
+    QObject::connect(&cancel_poll, &QTimer::timeout, &loop, [&]() {
+        if (detail::cancel_requested()) {
+            cancelled = true;
+            loop.quit();
+        }
+    });
     QObject::connect(reply, &QNetworkReply::finished, &loop, [&]() {
         done = true;
         loop.quit();
@@ -1040,6 +1064,7 @@ LlmResponse LlmService::do_streaming_request(const QString& user_message,
 
     loop.exec();
     timeout.stop();
+    cancel_poll.stop();
 
     if (!in_think && !think_pending.isEmpty())
         on_chunk(think_pending, false);
@@ -1054,6 +1079,19 @@ LlmResponse LlmService::do_streaming_request(const QString& user_message,
         QCoreApplication::sendPostedEvents(nullptr, QEvent::DeferredDelete);
     };
 
+    if (cancelled && !done) {
+        LOG_INFO(kLlmSvcTag, QString("STREAM: cancelled by user after %1 chars").arg(accumulated.size()));
+        reply->abort();
+        drain_nam();
+        resp.cancelled = true;
+        resp.content = strip_think_blocks(accumulated);
+        resp.success = !resp.content.isEmpty();
+        if (!resp.success)
+            resp.error = "Cancelled";
+        on_chunk("", true);
+        return resp;
+    }
+
     if (tool_call_detected) {
         LOG_INFO(kLlmSvcTag, "Tool call detected in stream — falling back to tool loop");
         reply->abort();
@@ -1171,6 +1209,12 @@ void LlmService::chat_streaming(const QString& user_message, const std::vector<C
     // can tail `llm:session:*:stream` without being wired in. Push-only, coalesced.
     const QString stream_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
     const QString stream_topic = QStringLiteral("llm:session:") + stream_id + QStringLiteral(":stream");
+    const QString cancel_key = chat_session_id.isEmpty() ? stream_id : chat_session_id;
+    auto cancel_flag = std::make_shared<std::atomic_bool>(false);
+    {
+        QMutexLocker lock(&mutex_);
+        cancel_flags_.insert(cancel_key, cancel_flag);
+    }
     [[maybe_unused]] static bool policy_once = []() {
         auto& hub = fincept::datahub::DataHub::instance();
         fincept::datahub::TopicPolicy policy;
@@ -1182,12 +1226,14 @@ void LlmService::chat_streaming(const QString& user_message, const std::vector<C
         return true;
     }();
     // Skip on_chunk if LlmService dies before the chunk arrives.
-    StreamCallback guarded_chunk = [self, on_chunk, stream_id, stream_topic](const QString& chunk, bool done) {
+    StreamCallback guarded_chunk = [self, on_chunk, stream_id, stream_topic, chat_session_id](const QString& chunk,
+                                                                                              bool done) {
         if (!self)
             return;
         on_chunk(chunk, done);
         QJsonObject payload{
             {"session_id", stream_id},
+            {"chat_session_id", chat_session_id},
             {"chunk", chunk},
             {"done", done},
         };
@@ -1199,7 +1245,8 @@ void LlmService::chat_streaming(const QString& user_message, const std::vector<C
         }
     };
     (void)QtConcurrent::run(
-        [self, p, k, b, m, sp, t, mx, user_message, history_copy, guarded_chunk, policy, chat_session_id]() {
+        [self, p, k, b, m, sp, t, mx, user_message, history_copy, guarded_chunk, policy, chat_session_id, cancel_key,
+         cancel_flag]() {
             if (!self)
                 return;
 
@@ -1226,9 +1273,18 @@ void LlmService::chat_streaming(const QString& user_message, const std::vector<C
 
             detail::ToolPolicyGuard guard(policy);
             detail::ChatSessionGuard session_guard(chat_session_id);
+            detail::CancelFlagGuard cancel_guard(cancel_flag);
             auto resp = self->do_streaming_request(user_message, history_copy, guarded_chunk);
+            if (cancel_flag->load())
+                resp.cancelled = true;
 
             if (self) {
+                {
+                    QMutexLocker lock(&self->mutex_);
+                    // A newer request for the same session may have replaced the entry.
+                    if (self->cancel_flags_.value(cancel_key) == cancel_flag)
+                        self->cancel_flags_.remove(cancel_key);
+                }
                 QMetaObject::invokeMethod(
                     self,
                     [self, resp]() {
@@ -1240,4 +1296,13 @@ void LlmService::chat_streaming(const QString& user_message, const std::vector<C
         });
 }
 
+void LlmService::cancel_streaming(const QString& chat_session_id) {
+    QMutexLocker lock(&mutex_);
+    auto flag = cancel_flags_.value(chat_session_id);
+    if (!flag)
+        return;
+    flag->store(true);
+    LOG_INFO(kLlmSvcTag, "Cancel requested for chat session " + chat_session_id);
+}
+
 } // namespace fincept::ai_chat
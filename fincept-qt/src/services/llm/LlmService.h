@@ -5,6 +5,7 @@
 #include "mcp/McpTypes.h"
 #include "storage/repositories/LlmProfileRepository.h"
 
+#include <QHash>
 #include <QJsonArray>
 #include <QJsonObject>
 #include <QMutex>
@@ -14,7 +15,9 @@
 #include <QString>
 #include <QStringList>
 
+#include <atomic>
 #include <functional>
+#include <memory>
 #include <optional>
 #include <vector>
 
@@ -24,11 +27,11 @@ inline bool provider_supports_streaming(const QString& provider) {
     return provider == "openai" || provider == "anthropic" || provider == "gemini" || provider == "google" ||
            provider == "groq" || provider == "deepseek" || provider == "openrouter" || provider == "minimax" ||
            provider == "kimi" || provider == "ollama" || provider == "xai" || provider == "fincept" ||
-           provider == "astraflow" || provider == "astraflow_cn" || provider == "aihubmix";
+           provider == "astraflow" || provider == "astraflow_cn" || provider == "aihubmix" || provider == "llama_cpp";
 }
 
 inline bool provider_requires_api_key(const QString& provider) {
-    return provider != "ollama" && provider != "fincept";
+    return provider != "ollama" && provider != "llama_cpp" && provider != "fincept";
 }
 
 /// In-band sentinel prefixed onto a streamed chunk to mark it as chain-of-thought
@@ -55,6 +58,7 @@ struct LlmResponse {
     int completion_tokens = 0;
     int total_tokens = 0;
     bool success = false;
+    bool cancelled = false; // stopped via cancel_streaming(); content holds the partial answer
 };
 
 /// (chunk_text, is_done) — invoked on a background thread.
@@ -84,6 +88,12 @@ class LlmService : public QObject {
     void chat_streaming(const QString& user_message, const std::vector<ConversationMessage>& history,
                         StreamCallback on_chunk, bool use_tools);
 
+    /// Stops the in-flight chat_streaming() request started with `chat_session_id`.
+    /// The SSE read aborts at once; a tool loop stops before its next round.
+    /// finished_streaming() still fires, with `cancelled` set and whatever answer
+    /// text arrived so far. No-op when nothing is streaming for that session.
+    void cancel_streaming(const QString& chat_session_id);
+
     /// Call after the user changes LLM settings.
     void reload_config();
 
@@ -143,6 +153,10 @@ class LlmService : public QObject {
     mutable bool tools_enabled_ = true;
     mutable bool config_loaded_ = false;
 
+    // In-flight chat_streaming() requests by chat session id (stream id when
+    // the caller gave none). Guarded by mutex_.
+    QHash<QString, std::shared_ptr<std::atomic_bool>> cancel_flags_;
+
     // Empty filter = full catalogue. Read in build_*_request under mutex_.
     mcp::ToolFilter tool_filter_;
 
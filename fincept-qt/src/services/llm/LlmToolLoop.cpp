@@ -38,6 +38,12 @@ LlmResponse LlmService::do_tool_loop(QJsonArray loop_messages, const QString& ur
     LOG_INFO(kLlmToolLoopTag, QString("TOOL LOOP: starting (max %1 rounds, model=%2)").arg(MAX_ROUNDS).arg(model_));
 
     for (int round = 0; round < MAX_ROUNDS; ++round) {
+        if (detail::cancel_requested()) {
+            LOG_INFO(kLlmToolLoopTag, QString("TOOL LOOP: cancelled before round %1").arg(round));
+            resp.cancelled = true;
+            resp.error = "Cancelled";
+            return resp;
+        }
         QJsonObject fu;
         fu["model"] = model_;
         fu["messages"] = loop_messages;
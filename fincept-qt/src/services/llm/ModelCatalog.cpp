@@ -2,12 +2,15 @@
 //
 // All numbers verified against provider docs on 2026-04-28. Source URLs are
 // in the comment above each entry. When a model isn't listed here we return
-// 0 and the caller falls back to a conservative default.
+// 0 and the caller falls back to a conservative default. The context-window
+// table below shares the output-cap table's doc sources.
 
 #include "services/llm/ModelCatalog.h"
 
 #include <QRegularExpression>
 
+#include <cstddef>
+
 namespace fincept::ai_chat {
 
 namespace {
@@ -15,7 +18,7 @@ namespace {
 struct CatalogEntry {
     const char* provider;   // exact match, lowercase
     const char* model_glob; // case-insensitive glob (* and ? wildcards)
-    int output_cap_tokens;
+    int tokens;             // output cap in kCatalog, context window in kContextCatalog
 };
 
 // Sentinel used in the catalog where the provider does not publish a
@@ -146,6 +149,12 @@ const CatalogEntry kCatalog[] = {
     // typical local setup.
     {"ollama", "*", 4096},
 
+    // ── llama.cpp (local llama-server) ──────────────────────────────────
+    // https://github.com/ggml-org/llama.cpp/tree/master/tools/server
+    // n_predict is unbounded by default; the real ceiling is the context the
+    // server was started with (-c). Same conservative default as Ollama.
+    {"llama_cpp", "*", 4096},
+
     // ── Astraflow (UCloud — OpenAI-compatible aggregator) ───────────────────
     // https://astraflow.ucloud-global.com  /  https://astraflow.ucloud.cn
     // Supports 200+ models; output cap varies by underlying model. Use a
@@ -166,6 +175,74 @@ const CatalogEntry kCatalog[] = {
     {"fincept", "*", kNoPublishedCap},
 };
 
+// Pass-through aggregators and proxies: the upstream model (and its window)
+// isn't knowable from the id, so budget for a mid-sized model. Long chats
+// get summarized earlier than strictly necessary — cheaper than a 400.
+constexpr int kUnknownUpstreamContext = 32768;
+
+// Same matching rules as kCatalog: first match wins, catch-all last.
+const CatalogEntry kContextCatalog[] = {
+    // ── OpenAI ──────────────────────────────────────────────────────────
+    {"openai", "gpt-5*", 400000},
+    {"openai", "gpt-4.1*", 1047576},
+    {"openai", "gpt-4o*", 128000},
+    {"openai", "gpt-4-turbo*", 128000},
+    {"openai", "gpt-4*", 8192}, // legacy gpt-4
+    {"openai", "gpt-3.5*", 16385},
+    {"openai", "o1-mini*", 128000},
+    {"openai", "o*", 200000}, // o1 / o3 / o4 reasoning series
+    {"openai", "*", 128000},
+
+    // ── Anthropic ───────────────────────────────────────────────────────
+    {"anthropic", "*", 200000},
+
+    // ── Google Gemini ───────────────────────────────────────────────────
+    {"gemini", "gemini-2.5*", 1048576},
+    {"gemini", "gemini-2.0*", 1048576},
+    {"gemini", "gemini-1.5-pro*", 2097152},
+    {"gemini", "gemini-1.5*", 1048576},
+    {"gemini", "*", 32768},
+    {"google", "gemini-2*", 1048576},
+    {"google", "*", 32768},
+
+    // ── xAI (Grok) ──────────────────────────────────────────────────────
+    {"xai", "grok-4*", 256000},
+    {"xai", "*", 131072},
+
+    // ── Groq ────────────────────────────────────────────────────────────
+    {"groq", "gemma2-9b-it*", 8192},
+    {"groq", "mixtral*", 32768},
+    {"groq", "*", 131072}, // llama-3.x / llama-4 / qwen / r1-distill
+
+    // ── DeepSeek ────────────────────────────────────────────────────────
+    {"deepseek", "*", 128000},
+
+    // ── Moonshot / Kimi ─────────────────────────────────────────────────
+    {"kimi", "kimi-k2-0711*", 131072},
+    {"kimi", "kimi-k2*", 262144},
+    {"kimi", "moonshot-v1-32k*", 32768},
+    {"kimi", "moonshot-v1-8k*", 8192},
+    {"kimi", "*", 131072}, // moonshot-v1-128k / -auto
+
+    // ── MiniMax ─────────────────────────────────────────────────────────
+    {"minimax", "minimax-m1*", 1000000},
+    {"minimax", "minimax-text*", 1000000},
+    {"minimax", "*", 204800}, // M2.x
+
+    // ── Local servers ───────────────────────────────────────────────────
+    // Ollama's default num_ctx and llama-server's default -c. Users who run a
+    // bigger context get summarization a little early, never an overflow.
+    {"ollama", "*", 4096},
+    {"llama_cpp", "*", 4096},
+
+    // ── Aggregators / proxies ───────────────────────────────────────────
+    {"openrouter", "*", kUnknownUpstreamContext},
+    {"astraflow", "*", kUnknownUpstreamContext},
+    {"astraflow_cn", "*", kUnknownUpstreamContext},
+    {"aihubmix", "*", kUnknownUpstreamContext},
+    {"fincept", "*", kUnknownUpstreamContext},
+};
+
 // Glob → regex. Supports * (zero-or-more) and ? (single char). Anchored.
 QRegularExpression compile_glob(const QString& glob) {
     QString rx = "^";
@@ -185,18 +262,27 @@ QRegularExpression compile_glob(const QString& glob) {
     return QRegularExpression(rx, QRegularExpression::CaseInsensitiveOption);
 }
 
-} // namespace
-
-int ModelCatalog::output_cap(const QString& provider, const QString& model) {
+template <std::size_t N>
+int lookup(const CatalogEntry (&table)[N], const QString& provider, const QString& model) {
     const QString p = provider.toLower();
-    for (const auto& e : kCatalog) {
+    for (const auto& e : table) {
         if (p != QString::fromLatin1(e.provider))
             continue;
         QRegularExpression rx = compile_glob(QString::fromLatin1(e.model_glob));
         if (rx.match(model).hasMatch())
-            return e.output_cap_tokens;
+            return e.tokens;
     }
     return 0;
 }
 
+} // namespace
+
+int ModelCatalog::output_cap(const QString& provider, const QString& model) {
+    return lookup(kCatalog, provider, model);
+}
+
+int ModelCatalog::context_window(const QString& provider, const QString& model) {
+    return lookup(kContextCatalog, provider, model);
+}
+
 } // namespace fincept::ai_chat
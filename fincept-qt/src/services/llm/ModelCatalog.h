@@ -1,6 +1,6 @@
 #pragma once
-// ModelCatalog — per-model max output token budgets, sourced from each
-// provider's public docs (verified 2026-04-28).
+// ModelCatalog — per-model max output token budgets and context windows,
+// sourced from each provider's public docs (verified 2026-04-28).
 //
 // Design notes
 // ------------
@@ -37,6 +37,13 @@ class ModelCatalog {
     ///
     /// Returns 0 if no match — caller should use a conservative default.
     static int output_cap(const QString& provider, const QString& model);
+
+    /// Total context window (prompt + output) in tokens for a (provider, model)
+    /// pair, same matching rules as output_cap(). Used by ChatMemoryManager
+    /// to budget how much conversation history fits in a request.
+    ///
+    /// Returns 0 if no match — caller should use a conservative default.
+    static int context_window(const QString& provider, const QString& model);
 };
 
 } // namespace fincept::ai_chat
@@ -10,9 +10,9 @@
 namespace fincept::ai_chat {
 
 const QStringList& ProviderCatalog::known_providers() {
-    static const QStringList kProviders = {"openai",     "anthropic", "gemini",       "groq",    "deepseek",
-                                           "openrouter", "minimax",   "kimi",         "ollama",  "xai",
-                                           "fincept",    "astraflow", "astraflow_cn", "aihubmix"};
+    static const QStringList kProviders = {"openai",     "anthropic", "gemini",    "groq",         "deepseek",
+                                           "openrouter", "minimax",   "kimi",      "ollama",       "llama_cpp",
+                                           "xai",        "fincept",   "astraflow", "astraflow_cn", "aihubmix"};
     return kProviders;
 }
 
@@ -37,6 +37,7 @@ QString ProviderCatalog::display_name(const QString& provider_id) {
         {"minimax", "MiniMax"},
         {"kimi", "Kimi"},
         {"ollama", "Ollama"},
+        {"llama_cpp", "llama.cpp (local)"},
         {"xai", "xAI"},
         {"fincept", "Fincept LLM (recommended)"},
         {"astraflow", "AstraFlow"},
@@ -75,6 +76,8 @@ QString ProviderCatalog::default_base_url(const QString& provider) {
         return {}; // defaults to https://api.moonshot.ai
     if (p == "ollama")
         return "http://localhost:11434";
+    if (p == "llama_cpp")
+        return "http://localhost:8080/v1"; // llama-server's OpenAI-compatible API, default port
     if (p == "xai")
         return {};
     if (p == "fincept")
@@ -124,6 +127,8 @@ QStringList ProviderCatalog::fallback_models(const QString& provider) {
     if (p == "ollama")
         return {}; // Local provider — models fetched live from /api/tags. No fallback so the
                    // combo only shows what the user actually has installed locally.
+    if (p == "llama_cpp")
+        return {}; // llama-server serves the single GGUF it was started with — listed by /v1/models.
     if (p == "xai")
         return {"grok-4-latest", "grok-4", "grok-3", "grok-3-mini"};
     if (p == "fincept")
@@ -166,7 +171,12 @@ QStringList ProviderCatalog::fallback_models(const QString& provider) {
 
 bool ProviderCatalog::requires_api_key(const QString& provider) {
     const QString p = provider.toLower();
-    return p != "ollama" && p != "fincept";
+    return p != "ollama" && p != "llama_cpp" && p != "fincept";
+}
+
+bool ProviderCatalog::is_local(const QString& provider) {
+    const QString p = provider.toLower();
+    return p == "ollama" || p == "llama_cpp";
 }
 
 bool ProviderCatalog::is_openai_compatible(const QString& provider) {
@@ -179,7 +189,7 @@ QString ProviderCatalog::brand_color(const QString& provider) {
         {"openai", "#10A37F"},       {"anthropic", "#D97757"},  {"gemini", "#4285F4"},  {"groq", "#F55036"},
         {"deepseek", "#4D6BFE"},     {"openrouter", "#8B5CF6"}, {"minimax", "#FF4D6A"}, {"kimi", "#16D9C4"},
         {"ollama", "#9CA3AF"},       {"xai", "#E7E9EA"},        {"fincept", "#FF8800"}, {"astraflow", "#38BDF8"},
-        {"astraflow_cn", "#38BDF8"}, {"aihubmix", "#F59E0B"},
+        {"astraflow_cn", "#38BDF8"}, {"aihubmix", "#F59E0B"},   {"llama_cpp", "#A78BFA"},
     };
     const auto it = kColors.find(provider.toLower());
     if (it != kColors.end())
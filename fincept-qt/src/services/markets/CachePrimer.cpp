@@ -0,0 +1,277 @@
+// src/services/markets/CachePrimer.cpp
+#include "services/markets/CachePrimer.h"
+
+#include "core/logging/Logger.h"
+#include "core/symbol/SymbolContext.h"
+#include "datahub/DataHub.h"
+#include "services/markets/MarketDataService.h"
+#include "storage/repositories/DashboardDefinitionRepository.h"
+#include "storage/repositories/SettingsRepository.h"
+#include "storage/repositories/WatchlistRepository.h"
+
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QTimer>
+
+namespace fincept::services {
+
+namespace {
+
+constexpr const char* kMruKey = "startup.mru_symbols";
+
+QString primer_quote_topic(const QString& symbol) {
+    return QStringLiteral("market:quote:") + symbol;
+}
+
+} // namespace
+
+CachePrimer& CachePrimer::instance() {
+    static CachePrimer s;
+    return s;
+}
+
+CachePrimer::CachePrimer() {
+    stage_timer_ = new QTimer(this);
+    stage_timer_->setSingleShot(true);
+    connect(stage_timer_, &QTimer::timeout, this, [this]() {
+        if (!running_)
+            return;
+        LOG_INFO("CachePrimer", QString("Stage '%1' timed out with %2 topic(s) outstanding")
+                                    .arg(stages_.value(stage_index_).name)
+                                    .arg(pending_.size()));
+        finish_stage();
+    });
+
+    save_timer_ = new QTimer(this);
+    save_timer_->setSingleShot(true);
+    save_timer_->setInterval(2000);
+    connect(save_timer_, &QTimer::timeout, this, &CachePrimer::save_mru);
+
+    auto& hub = datahub::DataHub::instance();
+    connect(&hub, &datahub::DataHub::topic_updated, this,
+            [this](const QString& topic, const QVariant&) { on_topic_settled(topic, true); });
+    connect(&hub, &datahub::DataHub::topic_error, this,
+            [this](const QString& topic, const QString&) { on_topic_settled(topic, false); });
+
+    load_mru();
+}
+
+// ── MRU symbols ──────────────────────────────────────────────────────────────
+
+void CachePrimer::start_mru_tracking() {
+    if (tracking_)
+        return;
+    tracking_ = true;
+    connect(&SymbolContext::instance(), &SymbolContext::active_symbol_changed, this,
+            [this](const SymbolRef& ref, QObject*) {
+                if (ref.is_valid())
+                    touch_mru(ref.symbol);
+            });
+}
+
+void CachePrimer::load_mru() {
+    auto r = SettingsRepository::instance().get(kMruKey);
+    if (r.is_err() || r.value().isEmpty())
+        return;
+    for (const auto& v : QJsonDocument::fromJson(r.value().toUtf8()).array()) {
+        const QString sym = v.toString().trimmed().toUpper();
+        if (!sym.isEmpty() && !mru_.contains(sym))
+            mru_ << sym;
+    }
+    while (mru_.size() > kMaxMru)
+        mru_.removeLast();
+}
+
+void CachePrimer::touch_mru(const QString& symbol) {
+    const QString sym = symbol.trimmed().toUpper();
+    if (sym.isEmpty() || (!mru_.isEmpty() && mru_.first() == sym))
+        return;
+    mru_.removeAll(sym);
+    mru_.prepend(sym);
+    while (mru_.size() > kMaxMru)
+        mru_.removeLast();
+    save_timer_->start(); // debounce — symbol hopping shouldn't hammer the settings table
+}
+
+void CachePrimer::save_mru() {
+    const QByteArray json = QJsonDocument(QJsonArray::fromStringList(mru_)).toJson(QJsonDocument::Compact);
+    auto r = SettingsRepository::instance().set(kMruKey, QString::fromUtf8(json), "startup");
+    if (r.is_err())
+        LOG_WARN("CachePrimer", "Failed to persist MRU symbols: " + QString::fromStdString(r.error()));
+}
+
+// ── Stages ───────────────────────────────────────────────────────────────────
+
+QVector<CachePrimer::Stage> CachePrimer::build_stages() const {
+    QVector<Stage> out;
+
+    // 1. Dashboard — the default `portfolio_manager` template plus the global
+    //    indices/forex/crypto/commodities universes (kept aligned with the
+    //    widget sources — if a widget's hardcoded list changes, update here
+    //    too), then every binding of the user's saved dashboards.
+    Stage dash{"dashboard", {}};
+    auto add_quotes = [&dash](const QStringList& syms) {
+        for (const auto& s : syms)
+            dash.topics.append(primer_quote_topic(s));
+    };
+    add_quotes(MarketDataService::indices_symbols());
+    add_quotes(MarketDataService::forex_symbols());
+    add_quotes(MarketDataService::crypto_symbols());
+    add_quotes(MarketDataService::commodity_symbols());
+    add_quotes({"^GSPC", "^IXIC", "^DJI", "^RUT", "^VIX", "GC=F"});                                  // performance
+    add_quotes({"^VIX", "SPY", "QQQ", "IWM", "TLT", "NVDA", "TSLA", "AMD", "META", "PLTR", "COIN"}); // risk_metrics
+    add_quotes({"AAPL", "MSFT", "GOOGL", "AMZN", "NVDA", "TSLA", "META", "JPM"});                    // watchlist
+    dash.topics.append(QStringLiteral("news:general"));
+    dash.topics.append(QStringLiteral("econ:fincept:upcoming_events"));
+    auto defs = DashboardDefinitionRepository::instance().list();
+    if (defs.is_ok()) {
+        for (const auto& d : defs.value())
+            dash.topics.append(d.topics());
+    }
+    out.append(dash);
+
+    // 2. Most-recently-used symbols — quote + sparkline, which is what the
+    //    equity/watchlist panels show before any deeper drill-down.
+    Stage mru{"mru", {}};
+    for (const auto& s : mru_) {
+        mru.topics.append(primer_quote_topic(s));
+        mru.topics.append(QStringLiteral("market:sparkline:") + s);
+    }
+    out.append(mru);
+
+    // 3. Watchlists — quotes for every symbol, capped for very large lists.
+    Stage wl{"watchlists", {}};
+    auto lists = WatchlistRepository::instance().list_all();
+    if (lists.is_ok()) {
+        for (const auto& w : lists.value()) {
+            auto stocks = WatchlistRepository::instance().get_stocks(w.id);
+            if (stocks.is_err())
+                continue;
+            for (const auto& s : stocks.value())
+                if (wl.topics.size() < kMaxWatchlistSymbols)
+                    wl.topics.append(primer_quote_topic(s.symbol.trimmed().toUpper()));
+        }
+    }
+    out.append(wl);
+
+    // A topic is primed once, by the earliest stage that wants it.
+    QSet<QString> seen;
+    for (auto& st : out) {
+        QStringList unique;
+        for (const auto& t : st.topics)
+            if (!t.isEmpty() && !seen.contains(t)) {
+                seen.insert(t);
+                unique << t;
+            }
+        st.topics = unique;
+    }
+    return out;
+}
+
+void CachePrimer::prime() {
+    if (running_)
+        return;
+    stages_ = build_stages();
+    total_ = 0;
+    for (const auto& st : stages_)
+        total_ += st.topics.size();
+    done_ = 0;
+    failed_ = 0;
+    running_ = true;
+    clock_.start();
+    LOG_INFO("CachePrimer", QString("Priming %1 topic(s): %2 dashboard, %3 MRU, %4 watchlist")
+                                .arg(total_)
+                                .arg(stages_[0].topics.size())
+                                .arg(stages_[1].topics.size())
+                                .arg(stages_[2].topics.size()));
+    emit priming_started(total_);
+    start_stage(0);
+}
+
+void CachePrimer::start_stage(int index) {
+    if (index >= stages_.size()) {
+        finish();
+        return;
+    }
+    stage_index_ = index;
+    stage_done_ = 0;
+    pending_.clear();
+
+    auto& hub = datahub::DataHub::instance();
+    QStringList to_request;
+    for (const auto& t : stages_[index].topics) {
+        // Already fresh (e.g. restored from a producer's own cache) — count it.
+        if (hub.peek(t).isValid()) {
+            ++stage_done_;
+            ++done_;
+            continue;
+        }
+        pending_.insert(t);
+        to_request << t;
+    }
+    publish_progress();
+    if (to_request.isEmpty()) {
+        finish_stage();
+        return;
+    }
+    // force=true bypasses min_interval_ms so the cold-start fetch isn't gated
+    // by an unrelated earlier refresh; producer rate limits still apply at
+    // dispatch (DataHub::flush_coalesced_requests).
+    hub.request(to_request, /*force=*/true);
+    stage_timer_->start(kStageTimeoutMs);
+}
+
+void CachePrimer::on_topic_settled(const QString& topic, bool ok) {
+    if (!running_ || !pending_.remove(topic))
+        return;
+    ++stage_done_;
+    ++done_;
+    if (!ok)
+        ++failed_;
+    publish_progress();
+    if (pending_.isEmpty())
+        finish_stage();
+}
+
+void CachePrimer::finish_stage() {
+    stage_timer_->stop();
+    // Outstanding topics keep loading in the background; they just stop
+    // holding up the next stage.
+    done_ += pending_.size();
+    stage_done_ += pending_.size();
+    pending_.clear();
+    // Defer so a synchronous publish inside hub.request() can't recurse.
+    const int next = stage_index_ + 1;
+    QTimer::singleShot(0, this, [this, next]() { start_stage(next); });
+}
+
+void CachePrimer::finish() {
+    running_ = false;
+    const qint64 ms = clock_.elapsed();
+    publish_progress();
+    LOG_INFO("CachePrimer",
+             QString("Priming complete: %1 topic(s), %2 failed, %3 ms").arg(total_).arg(failed_).arg(ms));
+    emit priming_finished(total_ - failed_, failed_, ms);
+}
+
+QVariantMap CachePrimer::progress_snapshot() const {
+    const bool in_stage = stage_index_ >= 0 && stage_index_ < stages_.size();
+    return QVariantMap{{"stage", in_stage ? stages_[stage_index_].name : QString()},
+                       {"stage_done", stage_done_},
+                       {"stage_total", in_stage ? stages_[stage_index_].topics.size() : 0},
+                       {"done", done_},
+                       {"total", total_},
+                       {"failed", failed_},
+                       {"finished", !running_ && total_ > 0},
+                       {"elapsed_ms", clock_.isValid() ? clock_.elapsed() : 0}};
+}
+
+void CachePrimer::publish_progress() {
+    const QVariantMap p = progress_snapshot();
+    if (running_)
+        emit priming_progress(p.value("stage").toString(), p.value("stage_done").toInt(),
+                              p.value("stage_total").toInt());
+    datahub::DataHub::instance().publish(progress_topic(), p);
+}
+
+} // namespace fincept::services
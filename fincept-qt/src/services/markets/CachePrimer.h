@@ -0,0 +1,89 @@
+// src/services/markets/CachePrimer.h
+#pragma once
+#include <QElapsedTimer>
+#include <QObject>
+#include <QSet>
+#include <QStringList>
+#include <QVariantMap>
+#include <QVector>
+
+class QTimer;
+
+namespace fincept::services {
+
+/// Warm-start cache priming. On launch it requests, in priority order, the
+/// DataHub topics the first screens are going to ask for — default dashboard
+/// quotes and saved-dashboard bindings, then the most-recently-used symbols,
+/// then every watchlist symbol — so widgets subscribing in showEvent get a
+/// fresh peek() and paint from cache on the first frame.
+///
+/// Each stage advances once all of its topics have published (or errored),
+/// or after kStageTimeoutMs. Progress goes out both as signals and on the
+/// `app:priming` hub topic (QVariantMap, see progress_snapshot()).
+///
+/// The MRU list is fed by SymbolContext::active_symbol_changed and persisted
+/// in settings so the next launch knows what to warm.
+class CachePrimer : public QObject {
+    Q_OBJECT
+  public:
+    static CachePrimer& instance();
+
+    static QString progress_topic() { return QStringLiteral("app:priming"); }
+
+    /// Start recording active symbols into the MRU list. Idempotent.
+    void start_mru_tracking();
+    /// Most recent first, at most kMaxMru entries.
+    QStringList mru_symbols() const { return mru_; }
+
+    /// Begin priming. No-op while a run is in progress.
+    void prime();
+    bool is_running() const { return running_; }
+
+    /// {stage, stage_done, stage_total, done, total, failed, finished, elapsed_ms}
+    QVariantMap progress_snapshot() const;
+
+    static constexpr int kMaxMru = 40;
+    static constexpr int kMaxWatchlistSymbols = 200;
+    static constexpr int kStageTimeoutMs = 8000;
+
+  signals:
+    void priming_started(int total_topics);
+    void priming_progress(QString stage, int stage_done, int stage_total);
+    void priming_finished(int primed, int failed, qint64 elapsed_ms);
+
+  private:
+    CachePrimer();
+    Q_DISABLE_COPY(CachePrimer)
+
+    struct Stage {
+        QString name;
+        QStringList topics;
+    };
+
+    QVector<Stage> build_stages() const;
+    void start_stage(int index);
+    void on_topic_settled(const QString& topic, bool ok);
+    void finish_stage();
+    void finish();
+    void publish_progress();
+
+    void load_mru();
+    void touch_mru(const QString& symbol);
+    void save_mru();
+
+    bool running_ = false;
+    bool tracking_ = false;
+    QVector<Stage> stages_;
+    int stage_index_ = -1;
+    QSet<QString> pending_;
+    int stage_done_ = 0;
+    int done_ = 0;
+    int failed_ = 0;
+    int total_ = 0;
+    QElapsedTimer clock_;
+    QTimer* stage_timer_ = nullptr;
+    QTimer* save_timer_ = nullptr;
+    QStringList mru_;
+};
+
+} // namespace fincept::services
@@ -0,0 +1,669 @@
+// src/services/markets/CustomIndexService.cpp
+#include "services/markets/CustomIndexService.h"
+
+#include "core/logging/Logger.h"
+#include "services/fundamentals/PitFundamentals.h"
+#include "services/markets/HistoryDownloadManager.h"
+#include "storage/HistoricalDataStore.h"
+#include "storage/repositories/PitFundamentalsRepository.h"
+
+#include <QDateTime>
+#include <QJsonArray>
+#include <QRegularExpression>
+#include <QTimeZone>
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+
+namespace fincept::services {
+
+namespace {
+
+// Daily bars read before the first index date, enough for Risk Parity's window.
+constexpr int kCixWarmupDays = 400;
+// A constituent whose last bar is older than this at a rebalance is left out.
+constexpr int kCixStaleBarDays = 10;
+constexpr int kCixMinReturns = 20;
+
+struct CixBars {
+    QVector<QDate> dates;
+    QVector<trading::BrokerCandle> candles;
+};
+
+QDate cix_day(qint64 ms) {
+    return QDateTime::fromMSecsSinceEpoch(ms, QTimeZone::UTC).date();
+}
+
+qint64 cix_ms(const QDate& d) {
+    return d.startOfDay(QTimeZone::UTC).toMSecsSinceEpoch();
+}
+
+// Index of the last bar on or before `d`, -1 before the first.
+int cix_bar_at(const CixBars& b, const QDate& d) {
+    const auto it = std::upper_bound(b.dates.begin(), b.dates.end(), d);
+    return static_cast<int>(it - b.dates.begin()) - 1;
+}
+
+// Whether `d` opens a new rebalance period after `prev`.
+bool cix_new_period(const QString& schedule, const QDate& prev, const QDate& d) {
+    if (schedule == "annual")
+        return d.year() != prev.year();
+    if (schedule == "quarterly")
+        return d.year() != prev.year() || (d.month() - 1) / 3 != (prev.month() - 1) / 3;
+    if (schedule == "monthly")
+        return d.year() != prev.year() || d.month() != prev.month();
+    return false;
+}
+
+// Scales the positive weights to sum to 1 and drops the rest.
+QHash<QString, double> cix_normalised(const QHash<QString, double>& raw) {
+    double total = 0;
+    for (double v : raw)
+        if (v > 0 && std::isfinite(v))
+            total += v;
+    QHash<QString, double> out;
+    if (total <= 0)
+        return out;
+    for (auto it = raw.constBegin(); it != raw.constEnd(); ++it)
+        if (it.value() > 0 && std::isfinite(it.value()))
+            out.insert(it.key(), it.value() / total);
+    return out;
+}
+
+// Caps every weight at `cap`, handing the excess to the uncapped names pro
+// rata until nothing is above it. Needs cap × N >= 1.
+QHash<QString, double> cix_capped(QHash<QString, double> w, double cap) {
+    for (int pass = 0; pass < w.size(); ++pass) {
+        double excess = 0;
+        double free_mass = 0;
+        for (auto& v : w) {
+            if (v > cap) {
+                excess += v - cap;
+                v = cap;
+            } else if (v < cap) {
+                free_mass += v;
+            }
+        }
+        if (excess <= 1e-12 || free_mass <= 0)
+            break;
+        for (auto& v : w)
+            if (v < cap)
+                v += excess * v / free_mass;
+    }
+    return w;
+}
+
+// Standard deviation of the last `lookback` daily returns up to bar `i`; NaN
+// with fewer than kCixMinReturns.
+double cix_volatility(const CixBars& b, int i, int lookback) {
+    QVector<double> r;
+    for (int k = std::max(1, i - lookback + 1); k <= i; ++k) {
+        const double prev = b.candles[k - 1].close;
+        if (prev > 0)
+            r.append(b.candles[k].close / prev - 1.0);
+    }
+    if (r.size() < kCixMinReturns)
+        return std::numeric_limits<double>::quiet_NaN();
+    double mean = 0;
+    for (double x : r)
+        mean += x;
+    mean /= r.size();
+    double ss = 0;
+    for (double x : r)
+        ss += (x - mean) * (x - mean);
+    return std::sqrt(ss / (r.size() - 1));
+}
+
+bool cix_uses_fundamentals(const QString& method) {
+    return method == "Market Cap Weighted" || method == "Capped Weighted" || method == "Fundamental Weighted";
+}
+
+} // namespace
+
+CustomIndexService& CustomIndexService::instance() {
+    static CustomIndexService s;
+    return s;
+}
+
+bool CustomIndexService::is_index_symbol(const QString& symbol) {
+    return symbol.size() > 1 && symbol.startsWith('@');
+}
+
+QString CustomIndexService::symbol_for(const QString& name) {
+    static const QRegularExpression kOther("[^A-Z0-9]+");
+    QString s = name.trimmed().toUpper();
+    s.replace(kOther, "_");
+    while (s.startsWith('_'))
+        s.remove(0, 1);
+    while (s.endsWith('_'))
+        s.chop(1);
+    return s.isEmpty() ? QString() : "@" + s;
+}
+
+const QStringList& CustomIndexService::methods() {
+    static const QStringList m = {
+        "Price Weighted",      "Market Cap Weighted", "Equal Weighted", "Float Adjusted", "Fundamental Weighted",
+        "Modified Market Cap", "Factor Weighted",     "Risk Parity",    "Geometric Mean", "Capped Weighted",
+    };
+    return m;
+}
+
+const QStringList& CustomIndexService::rebalance_schedules() {
+    static const QStringList s = {"none", "monthly", "quarterly", "annual"};
+    return s;
+}
+
+void CustomIndexService::start() {
+    if (timer_)
+        return;
+
+    auto& repo = CustomIndexRepository::instance();
+    auto all = repo.list_all();
+    if (all.is_ok()) {
+        for (auto idx : all.value()) {
+            if (!idx.symbol.isEmpty())
+                continue;
+            idx.symbol = symbol_for(idx.name);
+            if (idx.symbol.isEmpty() || repo.get_by_symbol(idx.symbol).is_ok()) {
+                LOG_WARN("CustomIndex", QString("No free symbol for index '%1'").arg(idx.name));
+                continue;
+            }
+            auto r = repo.update(idx);
+            if (r.is_err())
+                LOG_WARN("CustomIndex", QString("Assigning %1 failed: %2")
+                                            .arg(idx.symbol, QString::fromStdString(r.error())));
+        }
+    }
+
+    connect(&HistoryDownloadManager::instance(), &HistoryDownloadManager::job_finished, this,
+            [this](const QString& job_id, const QJsonObject&) { on_job_finished(job_id); });
+
+    timer_ = new QTimer(this);
+    timer_->setInterval(kTickMs);
+    connect(timer_, &QTimer::timeout, this, &CustomIndexService::tick);
+    timer_->start();
+    QTimer::singleShot(0, this, &CustomIndexService::tick);
+}
+
+// ── Definitions ─────────────────────────────────────────────────────────────
+
+Result<void> CustomIndexService::validate(CustomIndex& idx) const {
+    idx.name = idx.name.trimmed();
+    if (idx.name.isEmpty())
+        return Result<void>::err("Index name is required");
+    if (!methods().contains(idx.method))
+        return Result<void>::err(
+            QString("Unknown method '%1' (%2)").arg(idx.method, methods().join(", ")).toStdString());
+    idx.rebalance = idx.rebalance.trimmed().toLower();
+    if (idx.rebalance.isEmpty())
+        idx.rebalance = "none";
+    if (!rebalance_schedules().contains(idx.rebalance))
+        return Result<void>::err(QString("rebalance must be one of %1")
+                                     .arg(rebalance_schedules().join(", "))
+                                     .toStdString());
+    if (!(idx.base_value > 0))
+        return Result<void>::err("base_value must be positive");
+    if (!idx.start_date.isEmpty() && !QDate::fromString(idx.start_date, Qt::ISODate).isValid())
+        return Result<void>::err("start_date must be YYYY-MM-DD");
+
+    QVector<CustomIndexConstituent> cs;
+    QSet<QString> seen;
+    for (auto c : idx.constituents) {
+        c.symbol = c.symbol.trimmed().toUpper();
+        if (c.symbol.isEmpty() || seen.contains(c.symbol))
+            continue;
+        if (is_index_symbol(c.symbol))
+            return Result<void>::err("An index can't hold another custom index");
+        seen.insert(c.symbol);
+        cs.append(c);
+    }
+    if (cs.isEmpty())
+        return Result<void>::err("An index needs at least one constituent");
+    idx.constituents = cs;
+
+    if (idx.method == "Capped Weighted") {
+        const double cap = idx.options.value("max_weight").toDouble(10);
+        if (!(cap > 0 && cap <= 100))
+            return Result<void>::err("max_weight must be a percentage in (0, 100]");
+    }
+    if (idx.method == "Fundamental Weighted") {
+        const QString metric = idx.options.value("metric").toString("revenue");
+        if (!PitFundamentals::metrics().contains(metric))
+            return Result<void>::err(QString("Unknown fundamental metric '%1' (%2)")
+                                         .arg(metric, PitFundamentals::metrics().join(", "))
+                                         .toStdString());
+    }
+    return Result<void>::ok();
+}
+
+Result<CustomIndex> CustomIndexService::create(CustomIndex idx) {
+    auto v = validate(idx);
+    if (v.is_err())
+        return Result<CustomIndex>::err(v.error());
+    idx.symbol = symbol_for(idx.name);
+    if (idx.symbol.isEmpty())
+        return Result<CustomIndex>::err("The name needs at least one letter or digit");
+    auto& repo = CustomIndexRepository::instance();
+    if (repo.get_by_symbol(idx.symbol).is_ok())
+        return Result<CustomIndex>::err(QString("%1 is already taken").arg(idx.symbol).toStdString());
+
+    auto id = repo.create(idx);
+    if (id.is_err())
+        return Result<CustomIndex>::err(id.error());
+    auto stored = repo.get(id.value());
+    if (stored.is_err())
+        return stored;
+
+    auto built = rebuild(stored.value());
+    if (built.is_err())
+        LOG_INFO("CustomIndex", QString("Created %1 without a series: %2")
+                                    .arg(idx.symbol, QString::fromStdString(built.error())));
+    return repo.get(id.value());
+}
+
+Result<CustomIndex> CustomIndexService::update(const CustomIndex& changed) {
+    auto& repo = CustomIndexRepository::instance();
+    auto current = repo.get(changed.id);
+    if (current.is_err())
+        return Result<CustomIndex>::err("Unknown custom index");
+
+    CustomIndex idx = changed;
+    auto v = validate(idx);
+    if (v.is_err())
+        return Result<CustomIndex>::err(v.error());
+    // The symbol follows the index through renames so charts and benchmarks
+    // keep resolving it.
+    idx.symbol = current.value().symbol.isEmpty() ? symbol_for(idx.name) : current.value().symbol;
+    auto r = repo.update(idx);
+    if (r.is_err())
+        return Result<CustomIndex>::err(r.error());
+
+    auto stored = repo.get(idx.id);
+    if (stored.is_err())
+        return stored;
+    auto built = rebuild(stored.value());
+    if (built.is_err())
+        LOG_INFO("CustomIndex", QString("Updated %1 without a series: %2")
+                                    .arg(idx.symbol, QString::fromStdString(built.error())));
+    return repo.get(idx.id);
+}
+
+Result<void> CustomIndexService::remove(const CustomIndex& idx) {
+    auto r = CustomIndexRepository::instance().remove(idx.id);
+    if (r.is_err())
+        return r;
+    if (!idx.symbol.isEmpty())
+        storage::HistoricalDataStore::instance().replace_candles(idx.symbol, kExchange, "1d", 0,
+                                                                 std::numeric_limits<qint64>::max(), {});
+    return Result<void>::ok();
+}
+
+// ── Series ──────────────────────────────────────────────────────────────────
+
+Result<CustomIndexSeries> CustomIndexService::rebuild(const CustomIndex& idx) {
+    using R = Result<CustomIndexSeries>;
+    if (idx.symbol.isEmpty())
+        return R::err("Index has no symbol yet");
+
+    auto& store = storage::HistoricalDataStore::instance();
+    const QDate start = QDate::fromString(idx.start_date, Qt::ISODate);
+    const qint64 from_ms = start.isValid() ? cix_ms(start.addDays(-kCixWarmupDays)) : 0;
+    const QDate today = QDate::currentDate();
+
+    CustomIndexSeries out;
+    QHash<QString, CixBars> bars;
+    QHash<QString, double> stored_weights;
+    QStringList symbols;
+    for (const auto& c : idx.constituents) {
+        stored_weights.insert(c.symbol, c.weight);
+        const auto candles = store.get_candles(c.symbol, kSourceExchange, "1d", from_ms, 0);
+        CixBars b;
+        for (const auto& k : candles) {
+            const QDate d = cix_day(k.timestamp);
+            if (!b.dates.isEmpty() && b.dates.last() == d) {
+                b.candles.last() = k;
+                continue;
+            }
+            b.dates.append(d);
+            b.candles.append(k);
+        }
+        if (b.dates.isEmpty()) {
+            out.missing.append(c.symbol);
+            continue;
+        }
+        bars.insert(c.symbol, b);
+        symbols.append(c.symbol);
+    }
+    if (symbols.isEmpty()) {
+        const QString msg = QString("No cached daily bars for the constituents of %1 — backfill them first")
+                                .arg(idx.symbol);
+        return R::err(msg.toStdString());
+    }
+
+    // Without a start date the index begins once every cached constituent has
+    // history; with one, names that list later join at a later rebalance.
+    QDate first = start;
+    if (!first.isValid())
+        for (const auto& s : symbols)
+            first = first.isValid() ? std::max(first, bars[s].dates.first()) : bars[s].dates.first();
+
+    QSet<QDate> day_set;
+    for (const auto& s : symbols)
+        for (const auto& d : bars[s].dates)
+            if (d >= first && d <= today)
+                day_set.insert(d);
+    QVector<QDate> calendar(day_set.begin(), day_set.end());
+    std::sort(calendar.begin(), calendar.end());
+    if (calendar.isEmpty())
+        return R::err(QString("No cached bars on or after %1").arg(first.toString(Qt::ISODate)).toStdString());
+
+    // Rebalance days, then the fundamentals known the day before each.
+    QVector<QDate> rebalance_days{calendar.first()};
+    for (int i = 1; i < calendar.size(); ++i)
+        if (cix_new_period(idx.rebalance, calendar[i - 1], calendar[i]))
+            rebalance_days.append(calendar[i]);
+    QHash<QDate, QHash<QString, PitSnapshot>> fundamentals;
+    if (cix_uses_fundamentals(idx.method)) {
+        auto filings = PitFundamentalsRepository::instance().filings(symbols);
+        if (filings.is_err())
+            return R::err(filings.error());
+        QVector<QDate> as_of;
+        for (const auto& d : rebalance_days)
+            as_of.append(d.addDays(-1));
+        const auto snaps = PitFundamentals::snapshots(filings.value(), as_of);
+        for (int i = 0; i < rebalance_days.size(); ++i)
+            fundamentals.insert(rebalance_days[i], snaps[i]);
+    }
+
+    const QString metric = idx.options.value("metric").toString("revenue");
+    const int vol_lookback = std::clamp(idx.options.value("vol_lookback").toInt(63), kCixMinReturns, 756);
+    const double cap = idx.options.value("max_weight").toDouble(10) / 100.0;
+
+    QSet<QString> noted;
+    auto note = [&](const QString& sym, const QString& why) {
+        if (!noted.contains(sym + why)) {
+            noted.insert(sym + why);
+            out.notes.append(QString("%1: %2").arg(sym, why));
+        }
+    };
+
+    auto weigh = [&](const QDate& d) {
+        const auto snap = fundamentals.value(d);
+        QHash<QString, double> raw;
+        QStringList priced;
+        for (const auto& s : symbols) {
+            const CixBars& b = *bars.constFind(s);
+            const int i = cix_bar_at(b, d);
+            if (i < 0 || b.dates[i].daysTo(d) > kCixStaleBarDays) {
+                if (i >= 0)
+                    note(s, "no recent bar, left out");
+                continue;
+            }
+            const double price = b.candles[i].close;
+            if (!(price > 0))
+                continue;
+            priced.append(s);
+            double w = 1;
+            if (idx.method == "Equal Weighted") {
+                w = 1;
+            } else if (idx.method == "Price Weighted") {
+                w = price;
+            } else if (cix_uses_fundamentals(idx.method)) {
+                const auto it = snap.constFind(s);
+                const QString field = idx.method == "Fundamental Weighted" ? metric : QStringLiteral("shares_diluted");
+                const double v = it == snap.constEnd() ? 0 : it->fields.value(field).toDouble();
+                if (!(v > 0)) {
+                    note(s, QString("no point-in-time %1, left out").arg(field));
+                    continue;
+                }
+                w = idx.method == "Fundamental Weighted" ? v : price * v;
+            } else if (idx.method == "Risk Parity") {
+                const double vol = cix_volatility(b, i, vol_lookback);
+                if (!(vol > 0)) {
+                    note(s, "too little history for volatility, left out");
+                    continue;
+                }
+                w = 1.0 / vol;
+            } else {
+                w = stored_weights.value(s);
+            }
+            raw.insert(s, w);
+        }
+
+        auto w = cix_normalised(raw);
+        if (w.isEmpty() && !priced.isEmpty()) {
+            note(idx.method, "no constituent qualified on some rebalances, equal weighted there");
+            for (const auto& s : priced)
+                w.insert(s, 1.0 / priced.size());
+        }
+        if (idx.method == "Capped Weighted" && !w.isEmpty()) {
+            if (cap * w.size() < 1.0) {
+                note(idx.method, QString("a %1% cap needs at least %2 names, equal weighted while fewer qualify")
+                                     .arg(cap * 100)
+                                     .arg(std::ceil(1.0 / cap)));
+                for (auto& v : w)
+                    v = 1.0 / w.size();
+            } else {
+                w = cix_capped(w, cap);
+            }
+        }
+        return w;
+    };
+
+    // Chain-linked level: at a rebalance close the level is split into units
+    // of each constituent, held until the next rebalance.
+    // Every calendar day is some constituent's bar day, so weigh() comes back
+    // empty only when that bar has no usable close.
+    QHash<QString, double> units;
+    double level = idx.base_value;
+    int next_rebalance = 0;
+    for (const QDate& d : calendar) {
+        trading::BrokerCandle bar;
+        bar.timestamp = cix_ms(d);
+        if (!units.isEmpty()) {
+            double o = 0, h = 0, l = 0, c = 0;
+            for (auto it = units.constBegin(); it != units.constEnd(); ++it) {
+                const CixBars& b = *bars.constFind(it.key());
+                const int i = cix_bar_at(b, d);
+                const auto& k = b.candles[i];
+                const bool traded = b.dates[i] == d;
+                o += it.value() * (traded ? k.open : k.close);
+                h += it.value() * (traded ? k.high : k.close);
+                l += it.value() * (traded ? k.low : k.close);
+                c += it.value() * k.close;
+            }
+            level = c;
+            bar.open = o;
+            bar.high = std::max({o, h, c});
+            bar.low = std::min({o, l, c});
+            bar.close = c;
+        }
+
+        if (next_rebalance < rebalance_days.size() && rebalance_days[next_rebalance] == d) {
+            ++next_rebalance;
+            const auto w = weigh(d);
+            if (!w.isEmpty()) {
+                const bool first_day = units.isEmpty();
+                units.clear();
+                QJsonObject wj;
+                for (auto it = w.constBegin(); it != w.constEnd(); ++it) {
+                    const CixBars& b = *bars.constFind(it.key());
+                    units.insert(it.key(), it.value() * level / b.candles[cix_bar_at(b, d)].close);
+                    wj.insert(it.key(), it.value());
+                }
+                out.rebalances.append({d.toString(Qt::ISODate), wj});
+                if (first_day)
+                    bar.open = bar.high = bar.low = bar.close = level;
+            }
+        }
+        if (!units.isEmpty())
+            out.candles.append(bar);
+    }
+    if (out.candles.isEmpty())
+        return R::err(QString("No constituent of %1 could be weighted (%2)")
+                          .arg(idx.symbol, out.notes.join("; "))
+                          .toStdString());
+
+    QVector<QPair<QString, double>> values;
+    values.reserve(out.candles.size());
+    for (const auto& c : out.candles)
+        values.append({cix_day(c.timestamp).toString(Qt::ISODate), c.close});
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    auto saved = CustomIndexRepository::instance().replace_series(idx.id, values, out.rebalances, now);
+    if (saved.is_err())
+        return R::err(saved.error());
+    if (!store.replace_candles(idx.symbol, kExchange, "1d", 0, std::numeric_limits<qint64>::max(), out.candles))
+        return R::err("Writing the index series to the candle store failed");
+
+    LOG_INFO("CustomIndex", QString("Rebuilt %1: %2 days, %3 rebalances%4")
+                                .arg(idx.symbol)
+                                .arg(out.candles.size())
+                                .arg(out.rebalances.size())
+                                .arg(out.missing.isEmpty() ? QString() : ", missing " + out.missing.join(", ")));
+    emit index_rebuilt(idx.id, idx.symbol);
+    return R::ok(out);
+}
+
+QVector<trading::BrokerCandle> CustomIndexService::candles(const QString& symbol, qint64 from_ms, qint64 to_ms,
+                                                           QString* error) {
+    auto idx = CustomIndexRepository::instance().get_by_symbol(symbol.trimmed().toUpper());
+    if (idx.is_err()) {
+        if (error)
+            *error = QString("Unknown custom index %1").arg(symbol);
+        return {};
+    }
+
+    const auto& i = idx.value();
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    const qint64 updated = QDateTime::fromString(i.updated_at, Qt::ISODate).toMSecsSinceEpoch();
+    QString rebuild_error;
+    if (now - i.computed_at > kStaleMs || updated > i.computed_at) {
+        auto r = rebuild(i);
+        if (r.is_err())
+            rebuild_error = QString::fromStdString(r.error());
+    }
+
+    auto bars = storage::HistoricalDataStore::instance().get_candles(i.symbol, kExchange, "1d", from_ms, to_ms);
+    if (bars.isEmpty() && error)
+        *error = rebuild_error.isEmpty() ? QString("No %1 values in that range").arg(i.symbol) : rebuild_error;
+    return bars;
+}
+
+Result<DownloadJob> CustomIndexService::backfill(const CustomIndex& idx, const QDate& from, const QDate& to) {
+    QStringList symbols;
+    for (const auto& c : idx.constituents)
+        symbols.append(c.symbol);
+
+    DownloadJob spec;
+    spec.name = QString("Custom index %1 backfill").arg(idx.name);
+    spec.exchange = kSourceExchange;
+    spec.interval = "1d";
+    spec.from_date = from.toString(Qt::ISODate);
+    spec.to_date = to.toString(Qt::ISODate);
+    spec.source = "yahoo";
+    auto r = HistoryDownloadManager::instance().enqueue(spec, symbols);
+    if (r.is_ok()) {
+        jobs_.insert(r.value().id, idx.id);
+        topping_up_.insert(idx.id);
+    }
+    return r;
+}
+
+QJsonObject CustomIndexService::index_to_json(const CustomIndex& idx) {
+    QJsonArray cs;
+    for (const auto& c : idx.constituents)
+        cs.append(QJsonObject{{"symbol", c.symbol}, {"weight", c.weight}});
+    return QJsonObject{
+        {"id", idx.id},
+        {"name", idx.name},
+        {"symbol", idx.symbol},
+        {"method", idx.method},
+        {"rebalance", idx.rebalance},
+        {"options", idx.options},
+        {"base_value", idx.base_value},
+        {"start_date", idx.start_date},
+        {"constituents", cs},
+        {"portfolio_id", idx.portfolio_id},
+        {"computed_at", idx.computed_at > 0 ? QDateTime::fromMSecsSinceEpoch(idx.computed_at, QTimeZone::UTC)
+                                                  .toString(Qt::ISODate)
+                                            : QString()},
+        {"created_at", idx.created_at},
+        {"updated_at", idx.updated_at},
+    };
+}
+
+// ── Daily top-up ────────────────────────────────────────────────────────────
+
+void CustomIndexService::tick() {
+    auto all = CustomIndexRepository::instance().list_all();
+    if (all.is_err())
+        return;
+
+    auto& store = storage::HistoricalDataStore::instance();
+    const QDate today = QDate::currentDate();
+    // Bars through the last weekday before today count as current.
+    QDate expected = today.addDays(-1);
+    while (expected.dayOfWeek() > 5)
+        expected = expected.addDays(-1);
+
+    for (const auto& idx : all.value()) {
+        if (idx.symbol.isEmpty() || topping_up_.contains(idx.id))
+            continue;
+        if (idx.computed_at > 0 && cix_day(idx.computed_at) >= today)
+            continue;
+
+        QStringList lagging;
+        QDate from;
+        for (const auto& c : idx.constituents) {
+            const auto last = store.get_candles(c.symbol, kSourceExchange, "1d", cix_ms(expected.addDays(-14)), 0);
+            if (last.isEmpty())
+                continue; // never backfilled; left to an explicit backfill()
+            const QDate d = cix_day(last.last().timestamp);
+            if (d < expected) {
+                lagging.append(c.symbol);
+                from = from.isValid() ? std::min(from, d) : d;
+            }
+        }
+
+        if (lagging.isEmpty()) {
+            auto r = rebuild(idx);
+            if (r.is_err())
+                LOG_WARN("CustomIndex",
+                         QString("Rebuilding %1 failed: %2").arg(idx.symbol, QString::fromStdString(r.error())));
+            continue;
+        }
+
+        DownloadJob spec;
+        spec.name = QString("Custom index %1 top-up").arg(idx.name);
+        spec.exchange = kSourceExchange;
+        spec.interval = "1d";
+        spec.from_date = from.toString(Qt::ISODate);
+        spec.to_date = today.toString(Qt::ISODate);
+        spec.source = "yahoo";
+        auto r = HistoryDownloadManager::instance().enqueue(spec, lagging);
+        if (r.is_err()) {
+            LOG_WARN("CustomIndex",
+                     QString("Top-up of %1 failed: %2").arg(idx.symbol, QString::fromStdString(r.error())));
+            continue;
+        }
+        jobs_.insert(r.value().id, idx.id);
+        topping_up_.insert(idx.id);
+    }
+}
+
+void CustomIndexService::on_job_finished(const QString& job_id) {
+    const QString index_id = jobs_.take(job_id);
+    if (index_id.isEmpty())
+        return;
+    topping_up_.remove(index_id);
+    auto idx = CustomIndexRepository::instance().get(index_id);
+    if (idx.is_err())
+        return;
+    auto r = rebuild(idx.value());
+    if (r.is_err())
+        LOG_WARN("CustomIndex",
+                 QString("Rebuilding %1 failed: %2").arg(idx.value().symbol, QString::fromStdString(r.error())));
+}
+
+} // namespace fincept::services
@@ -0,0 +1,110 @@
+// src/services/markets/CustomIndexService.h
+#pragma once
+#include "core/result/Result.h"
+#include "storage/repositories/CustomIndexRepository.h"
+#include "storage/repositories/DownloadJobRepository.h"
+#include "trading/TradingTypes.h"
+
+#include <QDate>
+#include <QHash>
+#include <QJsonObject>
+#include <QObject>
+#include <QSet>
+#include <QString>
+#include <QStringList>
+#include <QTimer>
+#include <QVector>
+
+namespace fincept::services {
+
+/// One rebuild of a custom index.
+struct CustomIndexSeries {
+    QVector<trading::BrokerCandle> candles; // daily, epoch ms at UTC midnight
+    QVector<CustomIndexRebalance> rebalances;
+    QStringList missing; // constituents without cached daily bars
+    QStringList notes;   // constituents left out of a rebalance and why
+};
+
+/// Custom indices as synthetic symbols. An index (CustomIndexRepository) is
+/// a constituent list, a weighting method and a rebalance schedule; its
+/// daily level is rebuilt here from the constituents' cached daily bars in
+/// the Historify store (Yahoo downloads under "YF") and written back under
+/// (@NAME, "IDX", "1d"), the way continuous futures are. Charts and
+/// benchmarks (MarketDataService) and watchlist expressions / algo
+/// backtests (algo::CandleDataFetcher) resolve @-symbols here.
+///
+/// Weighting, set at the close of each rebalance day and held (drifting with
+/// prices) until the next:
+///   Equal Weighted        1/N
+///   Price Weighted        by price — one share of each
+///   Market Cap Weighted   price × point-in-time diluted shares (PitFundamentals)
+///   Capped Weighted       market cap, no name above options.max_weight % (default 10)
+///   Fundamental Weighted  a point-in-time metric, options.metric (default revenue)
+///   Risk Parity           inverse volatility of options.vol_lookback daily returns (default 63)
+///   anything else         the stored constituent weights
+///
+/// Rebuilt whenever the definition changes or the stored series is older
+/// than kStaleMs when asked for, and topped up daily: the tick timer queues
+/// a Yahoo download (HistoryDownloadManager) for constituents whose cached
+/// bars lag and rebuilds when it finishes. Main thread only.
+class CustomIndexService : public QObject {
+    Q_OBJECT
+  public:
+    static constexpr const char* kExchange = "IDX";      // Historify exchange key of the computed series
+    static constexpr const char* kSourceExchange = "YF"; // where constituent bars are read from
+    static constexpr int kTickMs = 60 * 60 * 1000;
+    static constexpr qint64 kStaleMs = 15 * 60 * 1000;
+
+    static CustomIndexService& instance();
+
+    static bool is_index_symbol(const QString& symbol);
+    /// "@" + the name upper-cased with runs of other characters turned into "_".
+    static QString symbol_for(const QString& name);
+    static const QStringList& methods();
+    static const QStringList& rebalance_schedules();
+
+    /// Assigns symbols to indices created before they had one and starts the
+    /// daily top-up timer. Idempotent.
+    void start();
+
+    /// Validates `idx`, assigns its symbol and stores it; builds the series
+    /// when its constituents have cached bars.
+    Result<CustomIndex> create(CustomIndex idx);
+    /// Validates and stores a changed definition, then rebuilds.
+    Result<CustomIndex> update(const CustomIndex& idx);
+    /// Deletes the index, its values and its series in the candle store.
+    Result<void> remove(const CustomIndex& idx);
+
+    /// Rebuilds `idx` from the candle cache and stores the result.
+    Result<CustomIndexSeries> rebuild(const CustomIndex& idx);
+
+    /// Daily bars of index `symbol` in [from_ms, to_ms] (0 = open), rebuilt
+    /// first when stale. Empty with `error` set for an unknown symbol or an
+    /// index none of whose constituents has cached bars.
+    QVector<trading::BrokerCandle> candles(const QString& symbol, qint64 from_ms, qint64 to_ms,
+                                           QString* error = nullptr);
+
+    /// Queues a Yahoo daily download of the constituents over [from, to];
+    /// the index is rebuilt when the job finishes.
+    Result<DownloadJob> backfill(const CustomIndex& idx, const QDate& from, const QDate& to);
+
+    static QJsonObject index_to_json(const CustomIndex& idx);
+
+  signals:
+    /// After every stored rebuild.
+    void index_rebuilt(QString index_id, QString symbol);
+
+  private:
+    CustomIndexService() = default;
+    Q_DISABLE_COPY(CustomIndexService)
+
+    Result<void> validate(CustomIndex& idx) const;
+    void tick();
+    void on_job_finished(const QString& job_id);
+
+    QTimer* timer_ = nullptr;
+    QHash<QString, QString> jobs_; // download job id → index id
+    QSet<QString> topping_up_;     // index ids with a download in flight
+};
+
+} // namespace fincept::services
@@ -0,0 +1,515 @@
+// src/services/markets/DataQualityService.cpp
+#include "services/markets/DataQualityService.h"
+
+#include "algo_engine/CandleDataFetcher.h"
+#include "core/events/EventBus.h"
+#include "core/logging/Logger.h"
+#include "datahub/DataHub.h"
+#include "storage/HistoricalDataStore.h"
+#include "storage/repositories/SettingsRepository.h"
+#include "trading/AccountManager.h"
+
+#include <QDateTime>
+#include <QHash>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QPointer>
+#include <QTimeZone>
+#include <QUuid>
+#include <QtConcurrent>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::services {
+
+namespace {
+
+constexpr qint64 kDqMinuteMs = 60LL * 1000;
+constexpr qint64 kDqDayMs = 24 * 60 * kDqMinuteMs;
+const char* kDqConfigKey = "data_quality.config";
+
+// Bar width for a stored interval string ("1m", "15m", "1h", "60", "1d", "D",
+// "1w", "W"...); 0 when it can't be read (monthly bars included).
+qint64 dq_interval_ms(const QString& interval) {
+    const QString iv = interval.trimmed().toLower();
+    if (iv == "d" || iv == "day")
+        return kDqDayMs;
+    if (iv == "w" || iv == "1wk" || iv == "1week")
+        return 7 * kDqDayMs;
+    bool ok = false;
+    const int plain = iv.toInt(&ok);
+    if (ok)
+        return plain > 0 ? plain * kDqMinuteMs : 0; // broker resolutions: "1", "5", "60"
+    if (iv.size() < 2)
+        return 0;
+    const int n = iv.left(iv.size() - 1).toInt(&ok);
+    if (!ok || n <= 0)
+        return 0;
+    switch (iv.back().toLatin1()) {
+        case 'm':
+            return n * kDqMinuteMs;
+        case 'h':
+            return n * 60 * kDqMinuteMs;
+        case 'd':
+            return n * kDqDayMs;
+        case 'w':
+            return n * 7 * kDqDayMs;
+        default:
+            return 0;
+    }
+}
+
+// CandleDataFetcher timeframe for a stored interval, empty if it can't fetch it.
+QString dq_fetch_timeframe(const QString& interval) {
+    static const QHash<qint64, QString> tf{
+        {kDqMinuteMs, "1m"},       {3 * kDqMinuteMs, "3m"},   {5 * kDqMinuteMs, "5m"},
+        {15 * kDqMinuteMs, "15m"}, {30 * kDqMinuteMs, "30m"}, {60 * kDqMinuteMs, "1h"},
+        {240 * kDqMinuteMs, "4h"}, {kDqDayMs, "1d"}};
+    return tf.value(dq_interval_ms(interval));
+}
+
+QDate dq_date(qint64 ms) {
+    return QDateTime::fromMSecsSinceEpoch(ms, QTimeZone::utc()).date();
+}
+
+QString dq_iso(qint64 ms) {
+    return QDateTime::fromMSecsSinceEpoch(ms, QTimeZone::utc()).toString(Qt::ISODate);
+}
+
+// Interval bucket a bar belongs to: the UTC date for daily bars, the ISO week
+// for weekly ones, floor(ts / width) intraday.
+qint64 dq_bucket(qint64 ts, qint64 width) {
+    if (width >= 7 * kDqDayMs) {
+        int year = 0;
+        const int week = dq_date(ts).weekNumber(&year);
+        return qint64(year) * 100 + week;
+    }
+    if (width >= kDqDayMs)
+        return dq_date(ts).toJulianDay();
+    return ts / width;
+}
+
+double dq_median(QVector<double> v) {
+    if (v.isEmpty())
+        return 0;
+    const auto mid = v.begin() + v.size() / 2;
+    std::nth_element(v.begin(), mid, v.end());
+    return *mid;
+}
+
+QJsonObject dq_issue(const QString& kind, qint64 from_ms, qint64 to_ms, const QJsonObject& detail = {}) {
+    QJsonObject o = detail;
+    o["kind"] = kind;
+    o["from"] = dq_iso(from_ms);
+    o["to"] = dq_iso(to_ms);
+    o["from_ms"] = from_ms;
+    o["to_ms"] = to_ms;
+    return o;
+}
+
+struct DqSeriesResult {
+    QJsonObject json;    // empty when the series is healthy
+    QVector<qint64> bad; // flattened [from, to] pairs worth re-fetching
+    bool stale = false;
+    qint64 last_ts = 0;
+};
+
+DqSeriesResult dq_check_series(const storage::HistoricalDataStore::CatalogEntry& e,
+                               const DataQualityService::Config& cfg, qint64 now_ms) {
+    DqSeriesResult res;
+    res.last_ts = e.last_ts;
+    const qint64 width = dq_interval_ms(e.interval);
+    const qint64 from = width > 0 ? e.last_ts - qint64(cfg.lookback_bars) * width * 3 : 0;
+    auto bars = storage::HistoricalDataStore::instance().get_candles(e.symbol, e.exchange, e.interval, from, 0);
+    if (bars.size() > cfg.lookback_bars)
+        bars = bars.mid(bars.size() - cfg.lookback_bars);
+
+    QJsonArray issues;
+    auto flag = [&](const QString& kind, qint64 a, qint64 b, const QJsonObject& detail, bool refetch) {
+        issues.append(dq_issue(kind, a, b, detail));
+        if (refetch)
+            res.bad << a << b;
+    };
+
+    QVector<double> returns;
+    QVector<int> return_at; // index of the bar each return ends on
+    for (int i = 0; i < bars.size(); ++i) {
+        const auto& c = bars[i];
+        if (c.close <= 0 || c.high < c.low || (c.high > 0 && (c.close > c.high * 1.0001 || c.close < c.low * 0.9999)))
+            flag("invalid_bar", c.timestamp, c.timestamp,
+                 {{"open", c.open}, {"high", c.high}, {"low", c.low}, {"close", c.close}}, true);
+        if (i == 0)
+            continue;
+        const auto& p = bars[i - 1];
+        if (width > 0) {
+            if (dq_bucket(p.timestamp, width) == dq_bucket(c.timestamp, width)) {
+                flag("duplicate", p.timestamp, c.timestamp, {}, true);
+                continue;
+            }
+            const qint64 days = dq_date(p.timestamp).daysTo(dq_date(c.timestamp));
+            if (width < kDqDayMs && days == 0) {
+                const qint64 missing = (c.timestamp - p.timestamp) / width - 1;
+                if (missing >= cfg.gap_bars)
+                    flag("gap", p.timestamp, c.timestamp, {{"missing_bars", missing}}, true);
+            } else if (days > cfg.max_gap_days + (width >= 7 * kDqDayMs ? 7 : 0)) {
+                flag("gap", p.timestamp, c.timestamp, {{"calendar_days", days}}, true);
+            }
+        }
+        if (p.close > 0 && c.close > 0) {
+            returns.append(std::log(c.close / p.close));
+            return_at.append(i);
+        }
+    }
+
+    // Robust sigma (1.4826 x MAD) so the spikes themselves don't inflate it.
+    if (returns.size() >= 20) {
+        const double med = dq_median(returns);
+        QVector<double> dev;
+        dev.reserve(returns.size());
+        for (double r : returns)
+            dev.append(std::abs(r - med));
+        const double sigma = 1.4826 * dq_median(dev);
+        if (sigma > 0) {
+            for (int k = 0; k < returns.size(); ++k) {
+                const double z = (returns[k] - med) / sigma;
+                if (std::abs(z) < cfg.spike_sigma)
+                    continue;
+                const auto& p = bars[return_at[k] - 1];
+                const auto& c = bars[return_at[k]];
+                flag("spike", c.timestamp, c.timestamp,
+                     {{"prev_close", p.close}, {"close", c.close}, {"return_pct", (std::exp(returns[k]) - 1) * 100},
+                      {"sigma_multiple", z}},
+                     true);
+            }
+        }
+    }
+
+    const qint64 age_ms = now_ms - e.last_ts;
+    if (age_ms > qint64(cfg.stale_days) * kDqDayMs + std::max<qint64>(width, 0)) {
+        res.stale = true;
+        flag("stale", e.last_ts, now_ms, {{"age_days", double(age_ms) / kDqDayMs}}, false);
+    }
+
+    if (issues.isEmpty())
+        return res;
+    res.json = QJsonObject{{"symbol", e.symbol},
+                           {"exchange", e.exchange},
+                           {"interval", e.interval},
+                           {"tz", e.tz},
+                           {"bars", e.record_count},
+                           {"first", dq_iso(e.first_ts)},
+                           {"last", dq_iso(e.last_ts)},
+                           {"issues", issues}};
+    return res;
+}
+
+trading::BrokerCandle dq_broker_candle(const algo::OhlcvCandle& c) {
+    trading::BrokerCandle b;
+    b.timestamp = c.open_time;
+    b.open = c.open;
+    b.high = c.high;
+    b.low = c.low;
+    b.close = c.close;
+    b.volume = c.volume;
+    return b;
+}
+
+} // namespace
+
+// ── Config ──────────────────────────────────────────────────────────────────
+
+DataQualityService::Config DataQualityService::Config::from_json(const QJsonObject& o) {
+    Config c;
+    c.spike_sigma = o.value("spike_sigma").toDouble(c.spike_sigma);
+    c.gap_bars = o.value("gap_bars").toInt(c.gap_bars);
+    c.max_gap_days = o.value("max_gap_days").toInt(c.max_gap_days);
+    c.stale_days = o.value("stale_days").toInt(c.stale_days);
+    c.stream_stale_sec = o.value("stream_stale_sec").toInt(c.stream_stale_sec);
+    c.lookback_bars = o.value("lookback_bars").toInt(c.lookback_bars);
+    c.auto_refetch = o.value("auto_refetch").toBool(c.auto_refetch);
+    c.max_refetch = o.value("max_refetch").toInt(c.max_refetch);
+    return c;
+}
+
+QJsonObject DataQualityService::Config::to_json() const {
+    return QJsonObject{{"spike_sigma", spike_sigma},
+                       {"gap_bars", gap_bars},
+                       {"max_gap_days", max_gap_days},
+                       {"stale_days", stale_days},
+                       {"stream_stale_sec", stream_stale_sec},
+                       {"lookback_bars", lookback_bars},
+                       {"auto_refetch", auto_refetch},
+                       {"max_refetch", max_refetch}};
+}
+
+// ── DataQualityService ──────────────────────────────────────────────────────
+
+DataQualityService& DataQualityService::instance() {
+    static DataQualityService s;
+    return s;
+}
+
+DataQualityService::Config DataQualityService::config() const {
+    auto r = SettingsRepository::instance().get(kDqConfigKey);
+    if (r.is_err() || r.value().isEmpty())
+        return Config{};
+    return Config::from_json(QJsonDocument::fromJson(r.value().toUtf8()).object());
+}
+
+void DataQualityService::set_config(const Config& c) {
+    SettingsRepository::instance().set(kDqConfigKey,
+                                       QString::fromUtf8(QJsonDocument(c.to_json()).toJson(QJsonDocument::Compact)),
+                                       "data_quality");
+}
+
+void DataQualityService::start() {
+    if (timer_)
+        return;
+    timer_ = new QTimer(this);
+    timer_->setInterval(kScanIntervalMs);
+    connect(timer_, &QTimer::timeout, this, [this]() { scan({}, {}, {}, config().auto_refetch); });
+    timer_->start();
+    QTimer::singleShot(kFirstScanDelayMs, this, [this]() { scan({}, {}, {}, config().auto_refetch); });
+}
+
+QString DataQualityService::scan(const QString& symbol, const QString& exchange, const QString& interval,
+                                 bool refetch) {
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    const Config cfg = config();
+    const qint64 now_ms = QDateTime::currentMSecsSinceEpoch();
+
+    // DataHub and the watchlist are read here; only the candle checks go to the worker.
+    QJsonArray streams;
+    int streams_scanned = 0;
+    QJsonObject counts;
+    auto count = [&counts](const QString& kind) { counts[kind] = counts.value(kind).toInt() + 1; };
+    for (const auto& s : datahub::DataHub::instance().stats()) {
+        if (s.subscriber_count == 0)
+            continue;
+        ++streams_scanned;
+        const qint64 age_ms = s.last_publish_ms > 0 ? now_ms - s.last_publish_ms : -1;
+        QString kind;
+        if (!s.last_error.isEmpty() && s.last_error_ms >= s.last_publish_ms)
+            kind = "stream_error";
+        else if (age_ms < 0 || age_ms > qint64(cfg.stream_stale_sec) * 1000)
+            kind = "stream_stale";
+        if (kind.isEmpty())
+            continue;
+        count(kind);
+        streams.append(QJsonObject{
+            {"kind", kind},
+            {"topic", s.topic},
+            {"subscribers", s.subscriber_count},
+            {"push_only", s.push_only},
+            {"last_publish", s.last_publish_ms > 0 ? QJsonValue(dq_iso(s.last_publish_ms)) : QJsonValue()},
+            {"age_sec", age_ms >= 0 ? QJsonValue(double(age_ms / 1000)) : QJsonValue()},
+            {"total_errors", s.total_errors},
+            {"last_error", s.last_error.isEmpty() ? QJsonValue() : QJsonValue(s.last_error)}});
+    }
+    QSet<QString> watched;
+    for (const auto& w : storage::HistoricalDataStore::instance().watchlist())
+        watched.insert(w.symbol.toUpper() + '|' + w.exchange.toUpper() + '|' + w.interval);
+
+    QPointer<DataQualityService> self = this;
+    (void)QtConcurrent::run([self, request_id, symbol, exchange, interval, refetch, cfg, now_ms, streams,
+                             streams_scanned, counts, watched]() mutable {
+        QJsonArray series;
+        QVector<RepairJob> jobs;
+        int scanned = 0;
+        for (const auto& e : storage::HistoricalDataStore::instance().catalog()) {
+            if ((!symbol.isEmpty() && e.symbol.compare(symbol, Qt::CaseInsensitive) != 0) ||
+                (!exchange.isEmpty() && e.exchange.compare(exchange, Qt::CaseInsensitive) != 0) ||
+                (!interval.isEmpty() && e.interval != interval))
+                continue;
+            ++scanned;
+            const DqSeriesResult r = dq_check_series(e, cfg, now_ms);
+            if (r.json.isEmpty())
+                continue;
+            for (const auto& v : r.json["issues"].toArray()) {
+                const QString kind = v.toObject()["kind"].toString();
+                counts[kind] = counts.value(kind).toInt() + 1;
+            }
+            series.append(r.json);
+
+            RepairJob job{request_id, e.symbol, e.exchange, e.interval, {}};
+            for (int i = 0; i + 1 < r.bad.size(); i += 2)
+                job.ranges.append({r.bad[i], r.bad[i + 1]});
+            if (r.stale && watched.contains(e.symbol.toUpper() + '|' + e.exchange.toUpper() + '|' + e.interval))
+                job.ranges.append({r.last_ts, now_ms});
+            if (!job.ranges.isEmpty())
+                jobs.append(job);
+        }
+
+        QMetaObject::invokeMethod(
+            self.data(),
+            [self, request_id, refetch, cfg, now_ms, series, streams, scanned, streams_scanned, counts, jobs]() {
+                if (!self)
+                    return;
+                int queued = 0;
+                if (refetch) {
+                    for (const auto& job : jobs) {
+                        if (queued >= cfg.max_refetch)
+                            break;
+                        if (dq_fetch_timeframe(job.interval).isEmpty())
+                            continue;
+                        self->enqueue(job);
+                        ++queued;
+                    }
+                }
+                QJsonObject report{{"generated_at", dq_iso(now_ms)},
+                                   {"config", cfg.to_json()},
+                                   {"series_scanned", scanned},
+                                   {"streams_scanned", streams_scanned},
+                                   {"issue_counts", counts},
+                                   {"series", series},
+                                   {"streams", streams},
+                                   {"refetch_queued", queued}};
+                self->last_report_ = report;
+                int total = 0;
+                for (const auto& v : counts)
+                    total += v.toInt();
+                if (total > 0)
+                    LOG_WARN("DataQuality", QString("%1 issue(s) across %2 series and %3 streams, %4 repair(s) queued")
+                                                .arg(total)
+                                                .arg(series.size())
+                                                .arg(streams.size())
+                                                .arg(queued));
+                emit self->scan_finished(request_id, report);
+                EventBus::instance().publish("data_quality.scanned",
+                                             QVariantMap{{"issues", total}, {"refetch_queued", queued}});
+            },
+            Qt::QueuedConnection);
+    });
+    return request_id;
+}
+
+QString DataQualityService::refetch(const QString& symbol, const QString& exchange, const QString& interval,
+                                    qint64 from_ms, qint64 to_ms) {
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    QString error;
+    if (dq_fetch_timeframe(interval).isEmpty())
+        error = "Interval '" + interval + "' can't be re-fetched";
+    else if (from_ms <= 0 || to_ms < from_ms)
+        error = "Invalid range";
+    else if (queued_.contains(symbol.toUpper() + '|' + exchange.toUpper() + '|' + interval))
+        error = "A repair for this series is already queued";
+    if (!error.isEmpty()) {
+        QTimer::singleShot(0, this, [this, request_id, error]() { emit error_occurred(request_id, error); });
+        return request_id;
+    }
+    enqueue(RepairJob{request_id, symbol.toUpper(), exchange.toUpper(), interval, {{from_ms, to_ms}}});
+    return request_id;
+}
+
+void DataQualityService::enqueue(const RepairJob& job) {
+    const QString key = job.symbol.toUpper() + '|' + job.exchange.toUpper() + '|' + job.interval;
+    if (queued_.contains(key))
+        return;
+    queued_.insert(key);
+    queue_.append(job);
+    if (!running_job_)
+        run_next();
+}
+
+void DataQualityService::run_next() {
+    if (queue_.isEmpty()) {
+        running_job_ = false;
+        return;
+    }
+    running_job_ = true;
+    const RepairJob job = queue_.takeFirst();
+
+    // Daily and longer bars are replaced whole days at a time so a re-fetched
+    // bar stamped differently from the stored one can't land beside it.
+    const qint64 width = dq_interval_ms(job.interval);
+    qint64 earliest = QDateTime::currentMSecsSinceEpoch();
+    QVector<Range> ranges;
+    for (Range r : job.ranges) {
+        if (width >= kDqDayMs) {
+            r.from_ms = QDateTime(dq_date(r.from_ms), QTime(0, 0), QTimeZone::utc()).toMSecsSinceEpoch();
+            r.to_ms = QDateTime(dq_date(r.to_ms).addDays(1), QTime(0, 0), QTimeZone::utc()).toMSecsSinceEpoch() - 1;
+        }
+        earliest = std::min(earliest, r.from_ms);
+        ranges.append(r);
+    }
+    const int lookback_days =
+        int((QDateTime::currentMSecsSinceEpoch() - earliest) / kDqDayMs) + (width >= kDqDayMs ? 5 : 2);
+
+    // The "YF" exchange key holds Yahoo-sourced series; anything else goes to
+    // the first connected broker (CandleDataFetcher falls back to Yahoo).
+    QString broker_id, account_id;
+    if (job.exchange.compare("YF", Qt::CaseInsensitive) != 0) {
+        auto& am = trading::AccountManager::instance();
+        for (const auto& acct : am.active_accounts()) {
+            if (am.connection_state(acct.account_id) == trading::ConnectionState::Connected) {
+                broker_id = acct.broker_id;
+                account_id = acct.account_id;
+                break;
+            }
+        }
+    }
+
+    QPointer<DataQualityService> self = this;
+    algo::CandleDataFetcher::instance().fetch(
+        job.symbol, dq_fetch_timeframe(job.interval), lookback_days, algo::DataSource::Auto, broker_id, account_id,
+        [self, job, ranges](bool ok, const QVector<algo::OhlcvCandle>& candles, const QString& err) {
+            if (!self)
+                return;
+            if (!ok || candles.isEmpty()) {
+                self->finish_job(job, {}, err.isEmpty() ? QStringLiteral("No data returned") : err);
+                return;
+            }
+            QVector<trading::BrokerCandle> fetched;
+            fetched.reserve(candles.size());
+            for (const auto& c : candles)
+                fetched.append(dq_broker_candle(c));
+            const qint64 first = fetched.first().timestamp;
+
+            auto& store = storage::HistoricalDataStore::instance();
+            int replaced = 0;
+            QJsonArray skipped;
+            for (const Range& r : ranges) {
+                // A source that doesn't reach back this far would just delete the range.
+                if (first > r.from_ms) {
+                    skipped.append(QJsonObject{{"from", dq_iso(r.from_ms)}, {"to", dq_iso(r.to_ms)},
+                                               {"reason", "source history starts later"}});
+                    continue;
+                }
+                if (store.replace_candles(job.symbol, job.exchange, job.interval, r.from_ms, r.to_ms, fetched))
+                    ++replaced;
+                else
+                    skipped.append(QJsonObject{{"from", dq_iso(r.from_ms)}, {"to", dq_iso(r.to_ms)},
+                                               {"reason", "store write failed"}});
+            }
+            self->finish_job(job,
+                             QJsonObject{{"symbol", job.symbol},
+                                         {"exchange", job.exchange},
+                                         {"interval", job.interval},
+                                         {"ranges", int(ranges.size())},
+                                         {"replaced", replaced},
+                                         {"bars_fetched", int(fetched.size())},
+                                         {"skipped", skipped}},
+                             {});
+        });
+}
+
+void DataQualityService::finish_job(const RepairJob& job, const QJsonObject& summary, const QString& error) {
+    queued_.remove(job.symbol.toUpper() + '|' + job.exchange.toUpper() + '|' + job.interval);
+    if (!error.isEmpty()) {
+        LOG_WARN("DataQuality",
+                 QString("Re-fetch failed for %1:%2 [%3] — %4").arg(job.exchange, job.symbol, job.interval, error));
+        emit error_occurred(job.request_id, error);
+    } else {
+        LOG_INFO("DataQuality", QString("Repaired %1/%2 range(s) for %3:%4 [%5]")
+                                    .arg(summary["replaced"].toInt())
+                                    .arg(summary["ranges"].toInt())
+                                    .arg(job.exchange, job.symbol, job.interval));
+        emit repaired(job.request_id, summary);
+        EventBus::instance().publish("data_quality.repaired", summary.toVariantMap());
+    }
+    // Sequential, like the Historify watchlist refresh, to stay inside source rate limits.
+    QTimer::singleShot(0, this, &DataQualityService::run_next);
+}
+
+} // namespace fincept::services
@@ -0,0 +1,109 @@
+// src/services/markets/DataQualityService.h
+#pragma once
+#include <QJsonObject>
+#include <QObject>
+#include <QSet>
+#include <QString>
+#include <QTimer>
+#include <QVector>
+
+namespace fincept::services {
+
+/// Health checks over the Historify candle store (HistoricalDataStore) and the
+/// live DataHub topics:
+///   - gap          missing bars inside a session, or more than max_gap_days
+///                  between sessions
+///   - duplicate    two bars in the same interval bucket (e.g. two rows for
+///                  one trading day stored under different timestamps)
+///   - spike        a close-to-close log return more than spike_sigma robust
+///                  standard deviations (MAD) from the series median
+///   - invalid_bar  non-positive close, high < low, or close outside [low, high]
+///   - stale        last bar older than stale_days
+///   - stream_stale / stream_error  subscribed topics that stopped publishing
+///                  or whose producer is reporting errors
+///
+/// Scans run on a worker thread. With refetch on, each series' bad ranges are
+/// re-downloaded (the connected broker, or Yahoo for the "YF" exchange key)
+/// and replaced in the store, one series at a time. Stale tails are only
+/// re-fetched for series on the Historify watchlist. Main thread only.
+class DataQualityService : public QObject {
+    Q_OBJECT
+  public:
+    struct Config {
+        double spike_sigma = 6.0;   // robust sigmas from the median return
+        int gap_bars = 3;           // intraday: missing bars within a session before it counts
+        int max_gap_days = 4;       // calendar days between sessions before it counts
+        int stale_days = 4;         // series whose last bar is older than this
+        int stream_stale_sec = 300; // subscribed topics silent for longer than this
+        int lookback_bars = 2000;   // most recent bars checked per series
+        bool auto_refetch = true;   // the periodic scan repairs what it finds
+        int max_refetch = 10;       // series repaired per scan
+
+        /// Defaults overridden by whichever keys `o` carries.
+        static Config from_json(const QJsonObject& o);
+        QJsonObject to_json() const;
+    };
+
+    static constexpr int kScanIntervalMs = 6 * 60 * 60 * 1000;
+    static constexpr int kFirstScanDelayMs = 5 * 60 * 1000; // keep clear of startup work
+
+    static DataQualityService& instance();
+
+    /// Persisted thresholds (settings key data_quality.config).
+    Config config() const;
+    void set_config(const Config& c);
+
+    /// Periodic scan (with refetch when the config enables it). Idempotent.
+    void start();
+
+    /// Scans every stored series matching the non-empty filters plus the
+    /// DataHub topics; returns the request id scan_finished / error_occurred
+    /// carry. `refetch` queues repairs for what it finds.
+    QString scan(const QString& symbol = {}, const QString& exchange = {}, const QString& interval = {},
+                 bool refetch = false);
+
+    /// Re-downloads [from_ms, to_ms] of one series and replaces it in the
+    /// store; returns the request id repaired / error_occurred carry.
+    QString refetch(const QString& symbol, const QString& exchange, const QString& interval, qint64 from_ms,
+                    qint64 to_ms);
+
+    /// Last report produced by scan(), empty before the first one.
+    QJsonObject last_report() const { return last_report_; }
+
+  signals:
+    /// {generated_at, config, series_scanned, streams_scanned, issue_counts{kind: n},
+    ///  series[{symbol, exchange, interval, bars, first, last, issues[]}], streams[], refetch_queued}
+    /// Only series and streams with issues are listed.
+    void scan_finished(QString request_id, QJsonObject report);
+    /// {symbol, exchange, interval, ranges, replaced, bars_fetched, skipped[]}
+    void repaired(QString request_id, QJsonObject summary);
+    void error_occurred(QString request_id, QString message);
+
+  private:
+    DataQualityService() = default;
+    Q_DISABLE_COPY(DataQualityService)
+
+    struct Range {
+        qint64 from_ms = 0;
+        qint64 to_ms = 0;
+    };
+    struct RepairJob {
+        QString request_id;
+        QString symbol;
+        QString exchange;
+        QString interval;
+        QVector<Range> ranges;
+    };
+
+    void enqueue(const RepairJob& job);
+    void run_next();
+    void finish_job(const RepairJob& job, const QJsonObject& summary, const QString& error);
+
+    QTimer* timer_ = nullptr;
+    QJsonObject last_report_;
+    QVector<RepairJob> queue_;
+    QSet<QString> queued_; // "symbol|exchange|interval" queued or running
+    bool running_job_ = false;
+};
+
+} // namespace fincept::services
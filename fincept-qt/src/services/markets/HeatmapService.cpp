@@ -0,0 +1,478 @@
+// src/services/markets/HeatmapService.cpp
+#include "services/markets/HeatmapService.h"
+
+#include "core/events/EventBus.h"
+#include "core/logging/Logger.h"
+#include "datahub/DataHub.h"
+#include "datahub/DataHubMetaTypes.h"
+#include "python/PythonRunner.h"
+#include "services/markets/MarketDataService.h"
+#include "services/sectors/SectorResolver.h"
+
+#include <QDateTime>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QMap>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::services {
+
+namespace {
+
+const QString kHmCboeScript = QStringLiteral("cboe_data.py");
+const QString kHmNasdaqScript = QStringLiteral("nasdaq_data.py");
+
+/// First of `keys` holding a number (or a "$1,234.5" / "1.2%" string).
+double hm_number(const QJsonObject& o, std::initializer_list<const char*> keys) {
+    for (const char* k : keys) {
+        const QJsonValue v = o.value(QLatin1String(k));
+        if (v.isDouble())
+            return v.toDouble();
+        if (v.isString()) {
+            QString s = v.toString();
+            s.remove('$').remove(',').remove('%');
+            bool ok = false;
+            const double d = s.trimmed().toDouble(&ok);
+            if (ok)
+                return d;
+        }
+    }
+    return std::nan("");
+}
+
+/// Parses a script's stdout; `*error` is set for `{error: true, message}` or
+/// unparseable output.
+QJsonObject hm_parse(const python::PythonResult& r, QString* error) {
+    if (!r.success) {
+        *error = r.error.isEmpty() ? QStringLiteral("script failed") : r.error;
+        return {};
+    }
+    const QJsonObject o = QJsonDocument::fromJson(python::extract_json(r.output).toUtf8()).object();
+    if (o.isEmpty())
+        *error = QStringLiteral("no JSON in script output");
+    else if (o.value("error").toBool(false))
+        *error = o.value("message").toString("script reported an error");
+    return o;
+}
+
+struct HmStats {
+    double cap = 0;
+    double change = std::nan("");
+    int advancers = 0;
+    int decliners = 0;
+    int count = 0;
+};
+
+} // namespace
+
+HeatmapSpec HeatmapSpec::from_json(const QJsonObject& o) {
+    HeatmapSpec s;
+    s.source = o.value("source").toString(s.source).trimmed().toLower();
+    s.index = o.value("index").toString(s.source == "cboe" ? QString() : s.index).trimmed();
+    s.market_cap = o.value("market_cap").toString(s.market_cap).trimmed().toLower();
+    const QJsonValue syms = o.value("symbols");
+    const QStringList raw = syms.isArray() ? syms.toVariant().toStringList() : syms.toString().split(',');
+    for (const QString& r : raw) {
+        const QString sym = r.trimmed().toUpper();
+        if (!sym.isEmpty() && !s.symbols.contains(sym))
+            s.symbols.append(sym);
+    }
+    s.limit = std::clamp(o.value("limit").toInt(s.limit), 1, HeatmapService::kMaxConstituents);
+    return s;
+}
+
+QString HeatmapSpec::label() const {
+    if (source == "cboe")
+        return index.toUpper();
+    if (source == "symbols")
+        return QString("%1 symbols").arg(symbols.size());
+    return QString("%1 %2 caps (top %3)").arg(index.toUpper(), market_cap).arg(limit);
+}
+
+HeatmapService& HeatmapService::instance() {
+    static HeatmapService s;
+    return s;
+}
+
+HeatmapService::HeatmapService() {
+    coalesce_ = new QTimer(this);
+    coalesce_->setSingleShot(true);
+    coalesce_->setInterval(kCoalesceMs);
+    connect(coalesce_, &QTimer::timeout, this, &HeatmapService::flush);
+
+    // A sector (and with it the industry) resolving moves a ticker between
+    // branches of every heatmap it belongs to.
+    connect(&SectorResolver::instance(), &SectorResolver::sector_resolved, this,
+            [this](const QString& symbol, const QString&) {
+                for (auto it = heatmaps_.cbegin(); it != heatmaps_.cend(); ++it) {
+                    if (it->index.contains(symbol))
+                        mark_dirty(it.key());
+                }
+            });
+}
+
+QString HeatmapService::start(const HeatmapSpec& spec) {
+    const QString id =
+        spec.source == "symbols"
+            ? QString("symbols:%1").arg(qHash(spec.symbols.join(',')), 0, 16)
+            : QString("%1:%2:%3:%4").arg(spec.source, spec.index.toLower(), spec.market_cap).arg(spec.limit);
+    if (heatmaps_.contains(id))
+        return id;
+
+    QString problem;
+    if (spec.source != "cboe" && spec.source != "nasdaq" && spec.source != "symbols")
+        problem = "source must be cboe, nasdaq or symbols";
+    else if (spec.source == "cboe" && spec.index.isEmpty())
+        problem = "a cboe heatmap needs an index symbol, e.g. BUK100P";
+    else if (spec.source == "symbols" && spec.symbols.isEmpty())
+        problem = "a symbols heatmap needs at least one symbol";
+    if (!problem.isEmpty()) {
+        QMetaObject::invokeMethod(
+            this, [this, id, problem]() { emit error_occurred(id, problem); }, Qt::QueuedConnection);
+        return id;
+    }
+
+    Heatmap h;
+    h.id = id;
+    h.spec = spec;
+    heatmaps_.insert(id, h);
+    LOG_INFO("Heatmap", QString("Starting %1 (%2)").arg(id, spec.label()));
+    load_constituents(id);
+    return id;
+}
+
+void HeatmapService::stop(const QString& id) {
+    auto it = heatmaps_.find(id);
+    if (it == heatmaps_.end())
+        return;
+    if (it->subscriber) {
+        datahub::DataHub::instance().unsubscribe(it->subscriber.data());
+        it->subscriber->deleteLater();
+    }
+    heatmaps_.erase(it);
+    LOG_INFO("Heatmap", "Stopped " + id);
+}
+
+QStringList HeatmapService::active() const {
+    return heatmaps_.keys();
+}
+
+QJsonObject HeatmapService::snapshot(const QString& id) const {
+    return heatmaps_.value(id).last;
+}
+
+void HeatmapService::load_constituents(const QString& id) {
+    const HeatmapSpec spec = heatmaps_.value(id).spec;
+
+    if (spec.source == "symbols") {
+        QVector<Constituent> items;
+        for (const QString& sym : spec.symbols.mid(0, kMaxConstituents)) {
+            Constituent c;
+            c.symbol = sym;
+            items.append(c);
+        }
+        on_constituents(id, items, {});
+        return;
+    }
+
+    QPointer<HeatmapService> self = this;
+    if (spec.source == "cboe") {
+        python::PythonRunner::instance().run(
+            kHmCboeScript, {"index_constituents", spec.index.toUpper()}, [self, id](python::PythonResult r) {
+                if (!self)
+                    return;
+                QString error;
+                const QJsonObject o = hm_parse(r, &error);
+                QVector<Constituent> items;
+                for (const auto& v : o["data"].toObject()["constituents"].toArray()) {
+                    const QJsonObject row = v.toObject();
+                    Constituent c;
+                    c.symbol = row["symbol"].toString().trimmed().toUpper();
+                    c.name = row["name"].toString(row["security_name"].toString());
+                    c.price = hm_number(row, {"current_price", "last_price", "price", "close"});
+                    c.change_pct = hm_number(row, {"price_change_percent", "change_percent"}) * 100.0;
+                    c.has_change = std::isfinite(c.change_pct);
+                    c.base_cap = hm_number(row, {"market_cap", "marketCap"});
+                    if (!std::isfinite(c.price))
+                        c.price = 0;
+                    if (!std::isfinite(c.base_cap))
+                        c.base_cap = 0;
+                    c.snapshot_price = c.price;
+                    if (!c.symbol.isEmpty())
+                        items.append(c);
+                }
+                self->on_constituents(id, items.mid(0, kMaxConstituents), error);
+            });
+        return;
+    }
+
+    python::PythonRunner::instance().run(
+        kHmNasdaqScript, {"equity_screener", spec.index.toLower(), spec.market_cap, "all", "all"},
+        [self, id, limit = spec.limit](python::PythonResult r) {
+            if (!self)
+                return;
+            QString error;
+            const QJsonObject o = hm_parse(r, &error);
+            QVector<Constituent> items;
+            for (const auto& v : o["data"].toObject()["results"].toArray()) {
+                const QJsonObject row = v.toObject();
+                Constituent c;
+                c.symbol = row["symbol"].toString().trimmed().toUpper();
+                c.name = row["name"].toString();
+                c.price = hm_number(row, {"lastsale"});
+                c.change_pct = hm_number(row, {"pctchange"}) * 100.0; // the script returns a fraction
+                c.has_change = std::isfinite(c.change_pct);
+                c.base_cap = hm_number(row, {"marketCap"});
+                if (!std::isfinite(c.price))
+                    c.price = 0;
+                if (!std::isfinite(c.base_cap))
+                    c.base_cap = 0;
+                c.snapshot_price = c.price;
+                if (!c.symbol.isEmpty())
+                    items.append(c);
+            }
+            std::sort(items.begin(), items.end(),
+                      [](const Constituent& a, const Constituent& b) { return a.base_cap > b.base_cap; });
+            self->on_constituents(id, items.mid(0, limit), error);
+        });
+}
+
+void HeatmapService::on_constituents(const QString& id, QVector<Constituent> items, const QString& error) {
+    auto it = heatmaps_.find(id);
+    if (it == heatmaps_.end())
+        return; // stopped while loading
+    if (items.isEmpty()) {
+        const QString msg = error.isEmpty() ? QStringLiteral("no constituents") : error;
+        LOG_WARN("Heatmap", QString("%1: %2").arg(id, msg));
+        heatmaps_.erase(it);
+        emit error_occurred(id, msg);
+        return;
+    }
+
+    Heatmap& h = it.value();
+    h.items = std::move(items);
+    h.index.clear();
+    QStringList symbols;
+    for (int i = 0; i < h.items.size(); ++i) {
+        h.index.insert(h.items[i].symbol, i);
+        symbols.append(h.items[i].symbol);
+    }
+    h.loaded = true;
+    LOG_INFO("Heatmap", QString("%1: %2 constituents").arg(id).arg(h.items.size()));
+
+    SectorResolver::instance().prefetch(symbols);
+    subscribe_quotes(h);
+    fill_missing_caps(id);
+    mark_dirty(id);
+}
+
+void HeatmapService::fill_missing_caps(const QString& id) {
+    QStringList missing;
+    for (const auto& c : heatmaps_.value(id).items) {
+        if (c.base_cap <= 0)
+            missing.append(c.symbol);
+    }
+    QPointer<HeatmapService> self = this;
+    for (const QString& sym : missing) {
+        MarketDataService::instance().fetch_info(sym, [self, id, sym](bool ok, InfoData info) {
+            if (!self || !ok || info.market_cap <= 0)
+                return;
+            auto it = self->heatmaps_.find(id);
+            if (it == self->heatmaps_.end() || !it->index.contains(sym))
+                return;
+            Constituent& c = it->items[it->index.value(sym)];
+            c.base_cap = info.market_cap;
+            c.snapshot_price = c.price; // info's cap is as of (about) now
+            if (c.name.isEmpty())
+                c.name = info.name;
+            self->mark_dirty(id);
+        });
+    }
+}
+
+void HeatmapService::subscribe_quotes(Heatmap& h) {
+    auto& hub = datahub::DataHub::instance();
+    if (h.subscriber) {
+        hub.unsubscribe(h.subscriber.data());
+        h.subscriber->deleteLater();
+    }
+    h.subscriber = new QObject(this);
+
+    QStringList topics;
+    const QString id = h.id;
+    for (const auto& c : h.items) {
+        const QString sym = c.symbol;
+        const QString topic = QStringLiteral("market:quote:") + sym;
+        topics.append(topic);
+        hub.subscribe(h.subscriber.data(), topic, [this, id, sym](const QVariant& v) {
+            if (!v.canConvert<QuoteData>())
+                return;
+            const QuoteData q = v.value<QuoteData>();
+            auto it = heatmaps_.find(id);
+            if (it == heatmaps_.end() || q.price <= 0)
+                return;
+            Constituent& c = it->items[it->index.value(sym)];
+            if (c.snapshot_price <= 0)
+                c.snapshot_price = q.price;
+            c.price = q.price;
+            c.change_pct = q.change_pct;
+            c.has_change = true;
+            c.quoted = true;
+            if (c.name.isEmpty())
+                c.name = q.name;
+            mark_dirty(id);
+        });
+    }
+    hub.request(topics);
+}
+
+void HeatmapService::mark_dirty(const QString& id) {
+    auto it = heatmaps_.find(id);
+    if (it == heatmaps_.end() || !it->loaded)
+        return;
+    it->dirty = true;
+    if (!coalesce_->isActive())
+        coalesce_->start();
+}
+
+void HeatmapService::flush() {
+    for (auto it = heatmaps_.begin(); it != heatmaps_.end(); ++it) {
+        if (!it->dirty)
+            continue;
+        it->dirty = false;
+        it->last = build(it.value());
+        emit heatmap_updated(it.key(), it->last);
+        EventBus::instance().publish("heatmap.updated", QVariantMap{{"id", it.key()}});
+    }
+}
+
+QJsonObject HeatmapService::build(const Heatmap& h) const {
+    auto live_cap = [](const Constituent& c) {
+        if (c.base_cap <= 0)
+            return 0.0;
+        return c.snapshot_price > 0 && c.price > 0 ? c.base_cap * c.price / c.snapshot_price : c.base_cap;
+    };
+    // Cap-weighted change where caps are known, else the plain mean.
+    auto stats = [&](const QVector<int>& rows) {
+        HmStats s;
+        double w_sum = 0, w_chg = 0, plain = 0;
+        int plain_n = 0;
+        for (int r : rows) {
+            const Constituent& c = h.items[r];
+            const double cap = live_cap(c);
+            s.cap += cap;
+            ++s.count;
+            if (!c.has_change)
+                continue;
+            if (c.change_pct > 0)
+                ++s.advancers;
+            else if (c.change_pct < 0)
+                ++s.decliners;
+            plain += c.change_pct;
+            ++plain_n;
+            if (cap > 0) {
+                w_sum += cap;
+                w_chg += cap * c.change_pct;
+            }
+        }
+        if (w_sum > 0)
+            s.change = w_chg / w_sum;
+        else if (plain_n > 0)
+            s.change = plain / plain_n;
+        return s;
+    };
+    auto node = [](const QString& name, const HmStats& s, double parent_cap, int parent_count) {
+        QJsonObject o{{"name", name},
+                      {"market_cap", s.cap},
+                      {"count", s.count},
+                      {"advancers", s.advancers},
+                      {"decliners", s.decliners}};
+        o["change_pct"] = std::isfinite(s.change) ? QJsonValue(s.change) : QJsonValue();
+        o["weight"] = parent_cap > 0 ? s.cap / parent_cap : double(s.count) / std::max(1, parent_count);
+        return o;
+    };
+
+    auto& resolver = SectorResolver::instance();
+    QMap<QString, QMap<QString, QVector<int>>> tree; // sector → industry → rows
+    QVector<int> all;
+    int quoted = 0;
+    for (int i = 0; i < h.items.size(); ++i) {
+        const QString sym = h.items[i].symbol;
+        QString sector = resolver.sector_for(sym);
+        QString industry = resolver.industry_for(sym);
+        if (sector.isEmpty())
+            sector = QStringLiteral("Unclassified");
+        if (industry.isEmpty())
+            industry = QStringLiteral("Other");
+        tree[sector][industry].append(i);
+        all.append(i);
+        if (h.items[i].quoted)
+            ++quoted;
+    }
+
+    const HmStats root_stats = stats(all);
+    auto by_cap = [](const QJsonValue& a, const QJsonValue& b) {
+        return a["market_cap"].toDouble() > b["market_cap"].toDouble();
+    };
+
+    QVector<QJsonValue> sectors;
+    for (auto s = tree.cbegin(); s != tree.cend(); ++s) {
+        QVector<int> sector_rows;
+        for (const auto& rows : s.value())
+            sector_rows += rows;
+        const HmStats ss = stats(sector_rows);
+
+        QVector<QJsonValue> industries;
+        for (auto ind = s->cbegin(); ind != s->cend(); ++ind) {
+            const HmStats is = stats(ind.value());
+            QVector<QJsonValue> tickers;
+            for (int r : ind.value()) {
+                const Constituent& c = h.items[r];
+                const double cap = live_cap(c);
+                QJsonObject t{{"name", c.symbol},
+                              {"symbol", c.symbol},
+                              {"label", c.name},
+                              {"price", c.price},
+                              {"market_cap", cap},
+                              {"live", c.quoted}};
+                t["change_pct"] = c.has_change ? QJsonValue(c.change_pct) : QJsonValue();
+                t["weight"] = root_stats.cap > 0 ? cap / root_stats.cap : 1.0 / std::max(1, root_stats.count);
+                tickers.append(t);
+            }
+            std::sort(tickers.begin(), tickers.end(), by_cap);
+            QJsonObject io = node(ind.key(), is, ss.cap, ss.count);
+            QJsonArray arr;
+            for (const auto& t : tickers)
+                arr.append(t);
+            io["children"] = arr;
+            industries.append(io);
+        }
+        std::sort(industries.begin(), industries.end(), by_cap);
+        QJsonObject so = node(s.key(), ss, root_stats.cap, root_stats.count);
+        QJsonArray arr;
+        for (const auto& i : industries)
+            arr.append(i);
+        so["children"] = arr;
+        sectors.append(so);
+    }
+    std::sort(sectors.begin(), sectors.end(), by_cap);
+
+    QJsonObject root = node(h.spec.label(), root_stats, 0, root_stats.count);
+    root.remove("weight");
+    QJsonArray arr;
+    for (const auto& s : sectors)
+        arr.append(s);
+    root["children"] = arr;
+
+    return QJsonObject{{"id", h.id},
+                       {"label", h.spec.label()},
+                       {"source", h.spec.source},
+                       {"constituents", int(h.items.size())},
+                       {"quoted", quoted},
+                       {"updated_at", QDateTime::currentDateTimeUtc().toString(Qt::ISODate)},
+                       {"root", root}};
+}
+
+} // namespace fincept::services
@@ -0,0 +1,107 @@
+// src/services/markets/HeatmapService.h
+#pragma once
+#include <QHash>
+#include <QJsonObject>
+#include <QObject>
+#include <QPointer>
+#include <QString>
+#include <QStringList>
+#include <QTimer>
+#include <QVector>
+
+namespace fincept::services {
+
+/// What a heatmap covers.
+///   cboe    — constituents of a CBOE Europe index (`index`, e.g. BUK100P)
+///   nasdaq  — the Nasdaq screener for exchange `index` (nasdaq | nyse | amex |
+///             all), filtered by `market_cap` (mega | large | mid | small |
+///             micro | all), largest `limit` by market cap
+///   symbols — an explicit `symbols` list
+struct HeatmapSpec {
+    QString source = "nasdaq";
+    QString index = "all";
+    QString market_cap = "mega";
+    QStringList symbols;
+    int limit = 500;
+
+    static HeatmapSpec from_json(const QJsonObject& o);
+    QString label() const;
+};
+
+/// Live sector → industry → ticker treemaps over an index's constituents.
+///
+/// start() loads the constituent list once (cboe_data.py / nasdaq_data.py),
+/// resolves sector and industry through SectorResolver and missing market
+/// caps through MarketDataService::fetch_info, then subscribes every
+/// constituent to `market:quote:<SYM>` on the DataHub. Each quote moves the
+/// constituent's change and its market cap (scaled by price since the
+/// snapshot); aggregates are rebuilt at most once per kCoalesceMs and pushed
+/// through heatmap_updated and the `heatmap.updated` EventBus event, so
+/// consumers no longer poll per symbol.
+///
+/// Aggregate change is market-cap weighted; nodes without caps fall back to
+/// an equal weight. Main thread only.
+class HeatmapService : public QObject {
+    Q_OBJECT
+  public:
+    static constexpr int kCoalesceMs = 1000;
+    static constexpr int kMaxConstituents = 2000;
+
+    static HeatmapService& instance();
+
+    /// Starts (or returns the running) heatmap for `spec`; the id is stable
+    /// per spec. The first heatmap_updated follows once constituents load.
+    QString start(const HeatmapSpec& spec);
+    void stop(const QString& id);
+    QStringList active() const;
+
+    /// Latest treemap; empty until the first update.
+    QJsonObject snapshot(const QString& id) const;
+
+  signals:
+    /// {id, label, source, updated_at, constituents, quoted, root{name,
+    ///  market_cap, change_pct, advancers, decliners, children[sector{…,
+    ///  weight, children[industry{…, children[ticker{symbol, name, price,
+    ///  change_pct, market_cap, weight}]}]}]}}
+    void heatmap_updated(QString id, QJsonObject treemap);
+    void error_occurred(QString id, QString message);
+
+  private:
+    HeatmapService();
+    Q_DISABLE_COPY(HeatmapService)
+
+    struct Constituent {
+        QString symbol;
+        QString name;
+        double snapshot_price = 0; // price when the cap was read
+        double price = 0;
+        double change_pct = 0;
+        double base_cap = 0;
+        bool has_change = false;
+        bool quoted = false; // received at least one live quote
+    };
+
+    struct Heatmap {
+        QString id;
+        HeatmapSpec spec;
+        QVector<Constituent> items;
+        QHash<QString, int> index; // symbol → items row
+        QPointer<QObject> subscriber; // owns the DataHub subscriptions
+        QJsonObject last;
+        bool dirty = false;
+        bool loaded = false;
+    };
+
+    void load_constituents(const QString& id);
+    void on_constituents(const QString& id, QVector<Constituent> items, const QString& error);
+    void fill_missing_caps(const QString& id);
+    void subscribe_quotes(Heatmap& h);
+    void mark_dirty(const QString& id);
+    void flush();
+    QJsonObject build(const Heatmap& h) const;
+
+    QHash<QString, Heatmap> heatmaps_;
+    QTimer* coalesce_ = nullptr;
+};
+
+} // namespace fincept::services
@@ -0,0 +1,492 @@
+// src/services/markets/HistoryDownloadManager.cpp
+#include "services/markets/HistoryDownloadManager.h"
+
+#include "core/events/EventBus.h"
+#include "core/logging/Logger.h"
+#include "services/markets/SymbologyService.h"
+#include "storage/HistoricalDataStore.h"
+#include "trading/AccountManager.h"
+#include "trading/HistoricalDataService.h"
+
+#include <QDateTime>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QNetworkAccessManager>
+#include <QNetworkReply>
+#include <QNetworkRequest>
+#include <QPointer>
+#include <QTimeZone>
+#include <QTimer>
+#include <QUrl>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::services {
+
+namespace {
+
+constexpr int kHdFirstBackoffMs = 30 * 1000;
+
+// Yahoo chart interval and how many days back Yahoo serves it (0 = no cap).
+struct HdYahooInterval {
+    const char* interval;
+    const char* yahoo;
+    int max_days_back;
+};
+
+const HdYahooInterval kHdYahooIntervals[] = {
+    {"1m", "1m", 30},   {"5m", "5m", 60}, {"15m", "15m", 60}, {"30m", "30m", 60},
+    {"1h", "60m", 730}, {"1d", "1d", 0},  {"1w", "1wk", 0},
+};
+
+const HdYahooInterval* hd_yahoo_interval(const QString& interval) {
+    for (const auto& i : kHdYahooIntervals)
+        if (interval == QLatin1String(i.interval))
+            return &i;
+    return nullptr;
+}
+
+const QStringList kHdBrokerIntervals = {"1m", "3m", "5m", "15m", "30m", "1h", "1d"};
+
+qint64 hd_day_start_ms(const QDate& d) {
+    return QDateTime(d, QTime(0, 0), QTimeZone::utc()).toMSecsSinceEpoch();
+}
+
+bool hd_rate_limited(const QString& error) {
+    const QString e = error.toLower();
+    return e.contains("429") || e.contains("rate limit") || e.contains("too many requests");
+}
+
+QString hd_iso(qint64 ms) {
+    return ms > 0 ? QDateTime::fromMSecsSinceEpoch(ms).toUTC().toString(Qt::ISODate) : QString();
+}
+
+} // namespace
+
+HistoryDownloadManager& HistoryDownloadManager::instance() {
+    static HistoryDownloadManager s;
+    return s;
+}
+
+void HistoryDownloadManager::start() {
+    if (started_)
+        return;
+    started_ = true;
+    if (active_job_.isEmpty() && !busy_)
+        schedule(kResumeDelayMs);
+}
+
+// ── Job control ─────────────────────────────────────────────────────────────
+
+Result<DownloadJob> HistoryDownloadManager::enqueue(const DownloadJob& spec, const QStringList& symbols) {
+    DownloadJob job = spec;
+    job.source = job.source.trimmed().toLower();
+    job.interval = job.interval.trimmed();
+    job.exchange = job.exchange.trimmed().toUpper();
+    if (job.to_date.isEmpty())
+        job.to_date = QDate::currentDate().toString(Qt::ISODate);
+
+    const QDate from = QDate::fromString(job.from_date, Qt::ISODate);
+    const QDate to = QDate::fromString(job.to_date, Qt::ISODate);
+    if (!from.isValid() || !to.isValid() || from > to)
+        return Result<DownloadJob>::err("from / to must be yyyy-MM-dd with from <= to");
+    if (symbols.isEmpty())
+        return Result<DownloadJob>::err("No symbols to download");
+
+    if (job.source == "yahoo") {
+        const auto* yi = hd_yahoo_interval(job.interval);
+        if (!yi)
+            return Result<DownloadJob>::err(
+                QString("Interval '%1' isn't available from Yahoo (1m, 5m, 15m, 30m, 1h, 1d, 1w)")
+                    .arg(job.interval)
+                    .toStdString());
+        if (yi->max_days_back > 0 && from.daysTo(QDate::currentDate()) > yi->max_days_back)
+            return Result<DownloadJob>::err(QString("Yahoo only serves %1 bars for the last %2 days")
+                                                .arg(job.interval)
+                                                .arg(yi->max_days_back)
+                                                .toStdString());
+    } else if (job.source == "broker") {
+        if (!kHdBrokerIntervals.contains(job.interval))
+            return Result<DownloadJob>::err(QString("Interval '%1' isn't available from brokers (%2)")
+                                                .arg(job.interval, kHdBrokerIntervals.join(", "))
+                                                .toStdString());
+        if (job.exchange.isEmpty() || job.exchange == "YF")
+            return Result<DownloadJob>::err("Broker downloads need the symbols' exchange (e.g. NSE)");
+    } else {
+        return Result<DownloadJob>::err("source must be 'yahoo' or 'broker'");
+    }
+    job.rate_per_minute = std::clamp(job.rate_per_minute, 1, 600);
+    if (job.name.trimmed().isEmpty())
+        job.name = QString("%1 × %2 %3 %4→%5")
+                       .arg(symbols.size())
+                       .arg(job.exchange.isEmpty() ? QStringLiteral("YF") : job.exchange, job.interval,
+                            job.from_date, job.to_date);
+
+    auto r = DownloadJobRepository::instance().create(job, symbols);
+    if (r.is_err())
+        return r;
+    LOG_INFO("Downloads",
+             QString("Queued '%1' (%2 symbols, %3)").arg(r.value().name).arg(r.value().total).arg(job.source));
+    publish(r.value().id, false);
+    schedule(0);
+    return r;
+}
+
+Result<void> HistoryDownloadManager::pause(const QString& job_id) {
+    auto job = DownloadJobRepository::instance().get(job_id);
+    if (!job)
+        return Result<void>::err("Unknown download job");
+    if (job->status != "queued" && job->status != "running")
+        return Result<void>::err(QString("Job is %1").arg(job->status).toStdString());
+    auto r = DownloadJobRepository::instance().set_status(job_id, "paused");
+    if (r.is_ok())
+        publish(job_id, false);
+    return r;
+}
+
+Result<void> HistoryDownloadManager::resume(const QString& job_id) {
+    auto job = DownloadJobRepository::instance().get(job_id);
+    if (!job)
+        return Result<void>::err("Unknown download job");
+    if (job->status == "queued" || job->status == "running")
+        return Result<void>::ok();
+    if (job->status == "cancelled")
+        return Result<void>::err("Job was cancelled");
+    if (job->total - job->done - job->failed <= 0)
+        return Result<void>::err("Nothing left to download; use retry_failed for failed symbols");
+    auto r = DownloadJobRepository::instance().set_status(job_id, "queued");
+    if (r.is_ok()) {
+        publish(job_id, false);
+        schedule(0);
+    }
+    return r;
+}
+
+Result<void> HistoryDownloadManager::cancel(const QString& job_id) {
+    auto job = DownloadJobRepository::instance().get(job_id);
+    if (!job)
+        return Result<void>::err("Unknown download job");
+    if (job->status == "completed" || job->status == "cancelled")
+        return Result<void>::err(QString("Job is already %1").arg(job->status).toStdString());
+    auto r = DownloadJobRepository::instance().set_status(job_id, "cancelled");
+    if (r.is_ok())
+        publish(job_id, true);
+    return r;
+}
+
+Result<int> HistoryDownloadManager::retry_failed(const QString& job_id) {
+    auto& repo = DownloadJobRepository::instance();
+    auto job = repo.get(job_id);
+    if (!job)
+        return Result<int>::err("Unknown download job");
+    auto n = repo.reset_failed(job_id);
+    if (n.is_err() || n.value() == 0)
+        return n;
+    if (job->status != "running" && job->status != "paused") {
+        auto r = repo.set_status(job_id, "queued");
+        if (r.is_err())
+            return Result<int>::err(r.error());
+    }
+    publish(job_id, false);
+    schedule(0);
+    return n;
+}
+
+Result<void> HistoryDownloadManager::remove(const QString& job_id) {
+    if (!DownloadJobRepository::instance().get(job_id))
+        return Result<void>::err("Unknown download job");
+    return DownloadJobRepository::instance().remove(job_id);
+}
+
+// ── Runner ──────────────────────────────────────────────────────────────────
+
+void HistoryDownloadManager::schedule(int delay_ms) {
+    if (!pace_timer_) {
+        pace_timer_ = new QTimer(this);
+        pace_timer_->setSingleShot(true);
+        connect(pace_timer_, &QTimer::timeout, this, &HistoryDownloadManager::pump);
+    }
+    pace_timer_->start(std::max(0, delay_ms));
+}
+
+void HistoryDownloadManager::pump() {
+    if (busy_)
+        return; // the in-flight item pumps again when it lands
+    auto& repo = DownloadJobRepository::instance();
+
+    std::optional<DownloadJob> job;
+    if (!active_job_.isEmpty()) {
+        job = repo.get(active_job_);
+        if (!job || (job->status != "running" && job->status != "queued")) {
+            job.reset();
+            active_job_.clear();
+            current_symbol_.clear();
+            backoff_ms_ = 0;
+        }
+    }
+    if (!job)
+        job = repo.next_runnable();
+    if (!job)
+        return;
+    active_job_ = job->id;
+
+    if (job->status == "queued") {
+        repo.set_status(job->id, "running");
+        job->status = "running";
+        LOG_INFO("Downloads", QString("Starting '%1' (%2/%3 done)").arg(job->name).arg(job->done).arg(job->total));
+        publish(job->id, false);
+    }
+
+    auto item = repo.next_pending(job->id);
+    if (!item) {
+        const bool all_failed = job->done == 0 && job->failed > 0;
+        const QString error = job->failed > 0 ? QString("%1 symbol(s) failed").arg(job->failed) : QString();
+        repo.set_status(job->id, all_failed ? "failed" : "completed", error);
+        LOG_INFO("Downloads", QString("'%1' finished: %2 done, %3 failed, %4 bars")
+                                  .arg(job->name)
+                                  .arg(job->done)
+                                  .arg(job->failed)
+                                  .arg(job->bars));
+        publish(job->id, true);
+        active_job_.clear();
+        current_symbol_.clear();
+        schedule(0);
+        return;
+    }
+
+    // Pace to the job's request rate, plus any rate-limit back-off.
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    const qint64 wait = last_request_ms_ + 60000 / std::max(1, job->rate_per_minute) + backoff_ms_ - now;
+    if (wait > 0) {
+        schedule(int(wait));
+        return;
+    }
+
+    if (job->source == "broker") {
+        bool connected = false;
+        auto& am = trading::AccountManager::instance();
+        for (const auto& acct : am.active_accounts())
+            connected = connected || am.connection_state(acct.account_id) == trading::ConnectionState::Connected;
+        if (!connected) {
+            // Failing every symbol in turn would only burn the attempts.
+            repo.set_status(job->id, "paused", "No connected broker account; resume once one is connected");
+            LOG_WARN("Downloads", QString("Paused '%1': no connected broker account").arg(job->name));
+            publish(job->id, false);
+            active_job_.clear();
+            schedule(0);
+            return;
+        }
+    }
+
+    busy_ = true;
+    last_request_ms_ = now;
+    current_symbol_ = item->symbol;
+    const DownloadJob j = *job;
+    const DownloadItem it = *item;
+    QPointer<HistoryDownloadManager> self = this;
+    auto done = [self, j, it](int bars, const QString& error, bool rate_limited) {
+        if (self)
+            self->item_done(j, it, bars, error, rate_limited);
+    };
+    if (j.source == "broker")
+        fetch_broker(j, it, done);
+    else
+        fetch_yahoo(j, it, done);
+}
+
+void HistoryDownloadManager::item_done(const DownloadJob& job, DownloadItem item, int bars, const QString& error,
+                                       bool rate_limited) {
+    busy_ = false;
+    if (error.isEmpty()) {
+        item.status = "done";
+        item.bars = bars;
+        item.error.clear();
+        ++item.attempts;
+        backoff_ms_ = 0;
+    } else if (rate_limited) {
+        backoff_ms_ = backoff_ms_ == 0 ? kHdFirstBackoffMs : std::min(backoff_ms_ * 2, kMaxBackoffMs);
+        item.error = error;
+        LOG_WARN("Downloads", QString("Rate limited on %1 (%2); backing off %3s")
+                                  .arg(item.symbol, job.source)
+                                  .arg(backoff_ms_ / 1000));
+    } else {
+        ++item.attempts;
+        item.error = error;
+        if (item.attempts >= kMaxAttempts) {
+            item.status = "failed";
+            LOG_WARN("Downloads",
+                     QString("%1 failed after %2 attempts: %3").arg(item.symbol).arg(item.attempts).arg(error));
+        }
+    }
+    DownloadJobRepository::instance().update_item(item);
+    current_symbol_.clear();
+    publish(job.id, false);
+    schedule(0);
+}
+
+void HistoryDownloadManager::publish(const QString& job_id, bool finished) {
+    auto job = DownloadJobRepository::instance().get(job_id);
+    if (!job)
+        return;
+    const QJsonObject p = progress(*job);
+    emit job_progress(job_id, p);
+    EventBus::instance().publish("downloads.progress", p.toVariantMap());
+    if (finished) {
+        emit job_finished(job_id, p);
+        EventBus::instance().publish("downloads.finished", p.toVariantMap());
+    }
+}
+
+QJsonObject HistoryDownloadManager::progress(const DownloadJob& job) const {
+    const int pending = std::max(0, job.total - job.done - job.failed);
+    const bool active = job.id == active_job_ && job.status == "running";
+    const double pace_sec = 60.0 / std::max(1, job.rate_per_minute);
+    return QJsonObject{{"job_id", job.id},
+                       {"name", job.name},
+                       {"status", job.status},
+                       {"exchange", job.exchange},
+                       {"interval", job.interval},
+                       {"from", job.from_date},
+                       {"to", job.to_date},
+                       {"source", job.source},
+                       {"rate_per_minute", job.rate_per_minute},
+                       {"total", job.total},
+                       {"done", job.done},
+                       {"failed", job.failed},
+                       {"pending", pending},
+                       {"bars", double(job.bars)},
+                       {"percent", job.total > 0 ? std::round(1000.0 * (job.done + job.failed) / job.total) / 10
+                                                 : 100.0},
+                       {"current", active ? current_symbol_ : QString()},
+                       {"eta_sec", int(std::ceil(pending * pace_sec + (active ? backoff_ms_ / 1000 : 0)))},
+                       {"backoff_sec", active ? backoff_ms_ / 1000 : 0},
+                       {"error", job.error},
+                       {"created_at", hd_iso(job.created_at)},
+                       {"finished_at", hd_iso(job.finished_at)}};
+}
+
+// ── Sources ─────────────────────────────────────────────────────────────────
+
+void HistoryDownloadManager::fetch_yahoo(const DownloadJob& job, const DownloadItem& item, ItemCallback cb) {
+    const QString yf = job.exchange.isEmpty() || job.exchange == "YF"
+                           ? item.symbol
+                           : SymbologyService::yahoo_symbol(job.exchange, item.symbol);
+    if (yf.isEmpty()) {
+        QTimer::singleShot(0, this,
+                           [cb, job]() { cb(0, "No Yahoo suffix known for exchange " + job.exchange, false); });
+        return;
+    }
+    const auto* yi = hd_yahoo_interval(job.interval);
+    const qint64 from_ms = hd_day_start_ms(QDate::fromString(job.from_date, Qt::ISODate));
+    const qint64 to_ms = hd_day_start_ms(QDate::fromString(job.to_date, Qt::ISODate).addDays(1)) - 1;
+    if (!nam_)
+        nam_ = new QNetworkAccessManager(this);
+
+    QNetworkRequest req{QUrl(QString("https://query1.finance.yahoo.com/v8/finance/chart/%1"
+                                     "?period1=%2&period2=%3&interval=%4&includePrePost=false")
+                                 .arg(QString::fromUtf8(QUrl::toPercentEncoding(yf)))
+                                 .arg(from_ms / 1000)
+                                 .arg((to_ms + 1) / 1000)
+                                 .arg(QString::fromLatin1(yi ? yi->yahoo : "1d")))};
+    // Same bare UA as CandleDataFetcher — Yahoo 429s detailed browser strings.
+    req.setHeader(QNetworkRequest::UserAgentHeader, "Mozilla/5.0");
+    req.setRawHeader("Accept", "application/json");
+
+    QNetworkReply* reply = nam_->get(req);
+    connect(reply, &QNetworkReply::finished, this, [reply, job, yf, from_ms, to_ms, cb]() {
+        reply->deleteLater();
+        const int http = reply->attribute(QNetworkRequest::HttpStatusCodeAttribute).toInt();
+        const QJsonObject chart = QJsonDocument::fromJson(reply->readAll()).object().value("chart").toObject();
+        if (http == 429) {
+            cb(0, "HTTP 429 from Yahoo", true);
+            return;
+        }
+        const QJsonObject err = chart.value("error").toObject();
+        if (!err.isEmpty()) {
+            cb(0, err.value("description").toString(err.value("code").toString("Yahoo error")), false);
+            return;
+        }
+        if (reply->error() != QNetworkReply::NoError) {
+            cb(0, reply->errorString(), false);
+            return;
+        }
+
+        const QJsonObject res = chart.value("result").toArray().at(0).toObject();
+        const QJsonArray ts = res.value("timestamp").toArray();
+        const QJsonObject q = res.value("indicators").toObject().value("quote").toArray().at(0).toObject();
+        const QJsonArray o = q.value("open").toArray(), h = q.value("high").toArray(), l = q.value("low").toArray(),
+                         c = q.value("close").toArray(), v = q.value("volume").toArray();
+        QVector<trading::BrokerCandle> candles;
+        candles.reserve(ts.size());
+        for (int i = 0; i < ts.size(); ++i) {
+            if (c.at(i).isNull())
+                continue; // halted / not yet printed
+            trading::BrokerCandle b;
+            b.timestamp = qint64(ts.at(i).toDouble()) * 1000;
+            if (b.timestamp < from_ms || b.timestamp > to_ms)
+                continue;
+            b.close = c.at(i).toDouble();
+            b.open = o.at(i).isNull() ? b.close : o.at(i).toDouble();
+            b.high = h.at(i).isNull() ? b.close : h.at(i).toDouble();
+            b.low = l.at(i).isNull() ? b.close : l.at(i).toDouble();
+            b.volume = v.at(i).toDouble();
+            candles.append(b);
+        }
+        if (candles.isEmpty()) {
+            cb(0, "No bars in range", false);
+            return;
+        }
+        if (!storage::HistoricalDataStore::instance().store_candles(yf, "YF", job.interval, candles)) {
+            cb(0, "Candle store write failed", false);
+            return;
+        }
+        cb(int(candles.size()), {}, false);
+    });
+}
+
+void HistoryDownloadManager::fetch_broker(const DownloadJob& job, const DownloadItem& item, ItemCallback cb) {
+    QString broker_id, account_id;
+    auto& am = trading::AccountManager::instance();
+    for (const auto& acct : am.active_accounts()) {
+        if (am.connection_state(acct.account_id) == trading::ConnectionState::Connected) {
+            broker_id = acct.broker_id;
+            account_id = acct.account_id;
+            break;
+        }
+    }
+    const QDate from = QDate::fromString(job.from_date, Qt::ISODate);
+    const qint64 from_ms = hd_day_start_ms(from);
+    const qint64 to_ms = hd_day_start_ms(QDate::fromString(job.to_date, Qt::ISODate).addDays(1)) - 1;
+    const int lookback_days = int(from.daysTo(QDate::currentDate())) + 1;
+
+    trading::HistoricalDataService::instance().fetch(
+        item.symbol, job.interval, lookback_days, broker_id, account_id,
+        [job, item, from_ms, to_ms, cb](bool ok, const QVector<trading::BrokerCandle>& all, const QString& err) {
+            if (!ok) {
+                const QString e = err.isEmpty() ? QStringLiteral("No data returned") : err;
+                cb(0, e, hd_rate_limited(e));
+                return;
+            }
+            // Brokers disagree on epoch seconds vs ms; the candle store keys on ms.
+            QVector<trading::BrokerCandle> candles;
+            for (auto b : all) {
+                if (b.timestamp > 0 && b.timestamp < 100000000000LL)
+                    b.timestamp *= 1000;
+                if (b.timestamp >= from_ms && b.timestamp <= to_ms)
+                    candles.append(b);
+            }
+            if (candles.isEmpty()) {
+                cb(0, "No bars in range", false);
+                return;
+            }
+            if (!storage::HistoricalDataStore::instance().store_candles(item.symbol, job.exchange, job.interval,
+                                                                        candles)) {
+                cb(0, "Candle store write failed", false);
+                return;
+            }
+            cb(int(candles.size()), {}, false);
+        });
+}
+
+} // namespace fincept::services
@@ -0,0 +1,91 @@
+// src/services/markets/HistoryDownloadManager.h
+#pragma once
+#include "core/result/Result.h"
+#include "storage/repositories/DownloadJobRepository.h"
+
+#include <QJsonObject>
+#include <QObject>
+#include <QString>
+#include <QStringList>
+
+#include <functional>
+
+class QNetworkAccessManager;
+class QTimer;
+
+namespace fincept::services {
+
+/// Bulk historical backfills into the Historify candle store — e.g. 500
+/// symbols × 10 years of daily bars — run as persistent jobs.
+///
+/// Jobs and their per-symbol progress live in download_jobs / download_items,
+/// so a job interrupted by a restart resumes at its first pending symbol.
+/// One job runs at a time, one request at a time, paced to the job's
+/// rate_per_minute; a rate-limited answer (HTTP 429 or the broker's
+/// equivalent) backs off exponentially and retries the symbol without
+/// counting it as an attempt. Other errors are retried up to kMaxAttempts.
+///
+/// Sources: "yahoo" stores under the "YF" exchange key with the Yahoo symbol
+/// (RELIANCE on NSE → YF / RELIANCE.NS), "broker" fetches from the first
+/// connected broker account and stores under the job's exchange. Main thread
+/// only.
+class HistoryDownloadManager : public QObject {
+    Q_OBJECT
+  public:
+    static constexpr int kMaxAttempts = 3;
+    static constexpr int kMaxBackoffMs = 10 * 60 * 1000;
+    static constexpr int kResumeDelayMs = 30 * 1000; // keep clear of startup work
+
+    static HistoryDownloadManager& instance();
+
+    /// Picks up queued and interrupted jobs. Idempotent.
+    void start();
+
+    /// Validates `spec` (interval, yyyy-MM-dd range, source, rate) and queues
+    /// one item per symbol.
+    Result<DownloadJob> enqueue(const DownloadJob& spec, const QStringList& symbols);
+
+    Result<void> pause(const QString& job_id);
+    Result<void> resume(const QString& job_id);
+    Result<void> cancel(const QString& job_id);
+    /// Re-queues the job's failed symbols; returns how many.
+    Result<int> retry_failed(const QString& job_id);
+    /// Cancels and forgets the job. Candles already stored are kept.
+    Result<void> remove(const QString& job_id);
+
+    /// {job_id, name, status, exchange, interval, from, to, source,
+    ///  rate_per_minute, total, done, failed, pending, bars, percent, current,
+    ///  eta_sec, backoff_sec, error, created_at, finished_at}
+    QJsonObject progress(const DownloadJob& job) const;
+
+  signals:
+    /// After every symbol and on every status change.
+    void job_progress(QString job_id, QJsonObject progress);
+    /// Completed (possibly with failed symbols), failed or cancelled.
+    void job_finished(QString job_id, QJsonObject progress);
+
+  private:
+    HistoryDownloadManager() = default;
+    Q_DISABLE_COPY(HistoryDownloadManager)
+
+    /// `bars` stored, or `error`; `rate_limited` retries after a back-off.
+    using ItemCallback = std::function<void(int bars, const QString& error, bool rate_limited)>;
+
+    void pump();
+    void schedule(int delay_ms);
+    void fetch_yahoo(const DownloadJob& job, const DownloadItem& item, ItemCallback cb);
+    void fetch_broker(const DownloadJob& job, const DownloadItem& item, ItemCallback cb);
+    void item_done(const DownloadJob& job, DownloadItem item, int bars, const QString& error, bool rate_limited);
+    void publish(const QString& job_id, bool finished);
+
+    QTimer* pace_timer_ = nullptr;
+    QNetworkAccessManager* nam_ = nullptr;
+    QString active_job_;
+    QString current_symbol_;
+    bool started_ = false;
+    bool busy_ = false; // a request is in flight
+    qint64 last_request_ms_ = 0;
+    int backoff_ms_ = 0;
+};
+
+} // namespace fincept::services
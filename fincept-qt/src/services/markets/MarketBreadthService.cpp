@@ -0,0 +1,357 @@
+// src/services/markets/MarketBreadthService.cpp
+#include "services/markets/MarketBreadthService.h"
+
+#include "algo_engine/CandleDataFetcher.h"
+#include "core/events/EventBus.h"
+#include "core/logging/Logger.h"
+#include "storage/HistoricalDataStore.h"
+
+#include <QDateTime>
+#include <QMap>
+#include <QPointer>
+#include <QTimeZone>
+#include <QUuid>
+#include <QtConcurrent>
+
+#include <algorithm>
+#include <deque>
+#include <optional>
+
+namespace fincept::services {
+
+namespace {
+
+constexpr int kMbYearBars = 252;
+constexpr qint64 kMbDayMs = 24LL * 60 * 60 * 1000;
+
+struct MbBar {
+    QDate date;
+    double high = 0;
+    double low = 0;
+    double close = 0;
+    double volume = 0;
+};
+
+using MbCandles = QHash<QString, QVector<algo::OhlcvCandle>>;
+
+QDate mb_date(qint64 ms) {
+    return QDateTime::fromMSecsSinceEpoch(ms, QTimeZone::utc()).date();
+}
+
+qint64 mb_ms(const QDate& d) {
+    return QDateTime(d, QTime(0, 0), QTimeZone::utc()).toMSecsSinceEpoch();
+}
+
+/// Per-session counts summed over members; the running series is filled in
+/// afterwards, in date order.
+void mb_accumulate(const QVector<MbBar>& bars, const QDate& from, QMap<QDate, BreadthRow>& out) {
+    std::deque<int> hi, lo; // indices of the trailing-year max high / min low, monotonic
+    double sum50 = 0, sum200 = 0;
+    for (int i = 0; i < bars.size(); ++i) {
+        const MbBar& b = bars[i];
+        // Windows over the previous kMbYearBars bars, excluding this one.
+        while (!hi.empty() && hi.front() < i - kMbYearBars)
+            hi.pop_front();
+        while (!lo.empty() && lo.front() < i - kMbYearBars)
+            lo.pop_front();
+        sum50 += b.close - (i >= 50 ? bars[i - 50].close : 0);
+        sum200 += b.close - (i >= 200 ? bars[i - 200].close : 0);
+
+        if (b.date >= from) {
+            BreadthRow& r = out[b.date];
+            ++r.members;
+            if (i > 0) {
+                const double chg = b.close - bars[i - 1].close;
+                if (chg > 0) {
+                    ++r.advances;
+                    r.up_volume += b.volume;
+                } else if (chg < 0) {
+                    ++r.declines;
+                    r.down_volume += b.volume;
+                } else {
+                    ++r.unchanged;
+                }
+            }
+            if (i >= kMbYearBars) {
+                if (b.high > bars[hi.front()].high)
+                    ++r.new_highs;
+                if (b.low < bars[lo.front()].low)
+                    ++r.new_lows;
+            }
+            if (i >= 49) {
+                ++r.eligible_50;
+                if (b.close > sum50 / 50)
+                    ++r.above_50;
+            }
+            if (i >= 199) {
+                ++r.eligible_200;
+                if (b.close > sum200 / 200)
+                    ++r.above_200;
+            }
+        }
+
+        while (!hi.empty() && bars[hi.back()].high <= b.high)
+            hi.pop_back();
+        hi.push_back(i);
+        while (!lo.empty() && bars[lo.back()].low >= b.low)
+            lo.pop_back();
+        lo.push_back(i);
+    }
+}
+
+/// Sessions on or after `from`, continuing the running fields from `seed`
+/// (the row before `from`), or starting them at the first session.
+QVector<BreadthRow> mb_compute(const QHash<QString, QVector<MbBar>>& series, const QDate& from,
+                               const std::optional<BreadthRow>& seed) {
+    QMap<QDate, BreadthRow> days;
+    for (const auto& bars : series)
+        mb_accumulate(bars, from, days);
+
+    QVector<BreadthRow> rows;
+    std::optional<BreadthRow> prev = seed;
+    for (auto it = days.begin(); it != days.end(); ++it) {
+        BreadthRow r = it.value();
+        r.date = it.key().toString(Qt::ISODate);
+        const double net = r.advances - r.declines;
+        if (prev) {
+            r.ad_line = prev->ad_line + net;
+            r.ema19 = prev->ema19 + 0.10 * (net - prev->ema19);
+            r.ema39 = prev->ema39 + 0.05 * (net - prev->ema39);
+            r.summation = prev->summation + (r.ema19 - r.ema39);
+        } else {
+            r.ad_line = net;
+            r.ema19 = net;
+            r.ema39 = net;
+            r.summation = 0;
+        }
+        rows.append(r);
+        prev = r;
+    }
+    return rows;
+}
+
+trading::BrokerCandle mb_broker_candle(const algo::OhlcvCandle& c) {
+    trading::BrokerCandle b;
+    b.timestamp = c.open_time;
+    b.open = c.open;
+    b.high = c.high;
+    b.low = c.low;
+    b.close = c.close;
+    b.volume = c.volume;
+    return b;
+}
+
+} // namespace
+
+MarketBreadthService& MarketBreadthService::instance() {
+    static MarketBreadthService s;
+    return s;
+}
+
+QJsonObject MarketBreadthService::row_to_json(const BreadthRow& r) {
+    auto pct = [](int n, int d) { return d > 0 ? QJsonValue(100.0 * n / d) : QJsonValue(); };
+    const double total_vol = r.up_volume + r.down_volume;
+    return QJsonObject{{"date", r.date},
+                       {"members", r.members},
+                       {"advances", r.advances},
+                       {"declines", r.declines},
+                       {"unchanged", r.unchanged},
+                       {"net_advances", r.advances - r.declines},
+                       {"up_volume", r.up_volume},
+                       {"down_volume", r.down_volume},
+                       {"up_volume_pct", total_vol > 0 ? QJsonValue(100.0 * r.up_volume / total_vol) : QJsonValue()},
+                       {"new_highs", r.new_highs},
+                       {"new_lows", r.new_lows},
+                       {"pct_above_50dma", pct(r.above_50, r.eligible_50)},
+                       {"pct_above_200dma", pct(r.above_200, r.eligible_200)},
+                       {"ad_line", r.ad_line},
+                       {"mcclellan_oscillator", r.ema19 - r.ema39},
+                       {"mcclellan_summation", r.summation}};
+}
+
+QString MarketBreadthService::update(const QString& universe, bool backfill, int days) {
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    auto fail = [this, request_id](const QString& msg) {
+        QMetaObject::invokeMethod(
+            this, [this, request_id, msg]() { emit error_occurred(request_id, msg); }, Qt::QueuedConnection);
+        return request_id;
+    };
+
+    auto& repo = BreadthRepository::instance();
+    const auto u = repo.get_universe(universe);
+    if (!u)
+        return fail("Unknown breadth universe: " + universe);
+    if (u->symbols.isEmpty())
+        return fail("Universe " + universe + " has no symbols");
+    if (running_.contains(universe))
+        return fail("An update of " + universe + " is already running");
+
+    // Full rebuild, or recompute from the last stored session (it may have
+    // been written from a partial intraday bar) seeded by the row before it.
+    std::optional<BreadthRow> last;
+    if (!backfill)
+        last = repo.last_row(universe);
+    const QDate today = QDate::currentDate();
+    QDate from;
+    std::optional<BreadthRow> seed;
+    if (last) {
+        from = QDate::fromString(last->date, Qt::ISODate);
+        seed = repo.last_row(universe, last->date);
+    } else {
+        from = today.addDays(-std::clamp(days, 1, kMaxBackfillDays));
+    }
+    const QDate warmup_from = from.addDays(-kWarmupDays);
+
+    // Top up the candle store: full history for symbols it lacks (or holds
+    // only from after the warm-up start), the missing tail for the rest.
+    QHash<QString, storage::HistoricalDataStore::CatalogEntry> have;
+    for (const auto& e : storage::HistoricalDataStore::instance().catalog()) {
+        if (e.exchange == u->exchange.toUpper() && e.interval == "1d")
+            have.insert(e.symbol, e);
+    }
+    const qint64 now_ms = QDateTime::currentMSecsSinceEpoch();
+    QStringList deep, tail;
+    int tail_days = 0;
+    for (const QString& sym : u->symbols) {
+        const auto it = have.constFind(sym.toUpper());
+        if (it == have.constEnd() || (!last && it->first_ts > mb_ms(warmup_from) + 10 * kMbDayMs)) {
+            deep.append(sym);
+        } else if (now_ms - it->last_ts > kMbDayMs) {
+            tail.append(sym);
+            tail_days = std::max(tail_days, int((now_ms - it->last_ts) / kMbDayMs) + 5);
+        }
+    }
+    const int deep_days = int(warmup_from.daysTo(today)) + 1;
+
+    running_.insert(universe);
+    LOG_INFO("Breadth", QString("Updating %1 from %2 (%3 members, fetching %4 full / %5 tail)")
+                            .arg(universe, from.toString(Qt::ISODate))
+                            .arg(u->symbols.size())
+                            .arg(deep.size())
+                            .arg(tail.size()));
+
+    QPointer<MarketBreadthService> self = this;
+    const BreadthUniverse uni = *u;
+    const bool full = !last;
+    auto compute = [self, request_id, uni, from, warmup_from, seed, full](const MbCandles& fetched,
+                                                                            const QStringList& errors) {
+        if (!self)
+            return;
+        (void)QtConcurrent::run([self, request_id, uni, from, warmup_from, seed, full, fetched, errors]() {
+            auto& store = storage::HistoricalDataStore::instance();
+            for (auto it = fetched.constBegin(); it != fetched.constEnd(); ++it) {
+                QVector<trading::BrokerCandle> candles;
+                candles.reserve(it->size());
+                for (const auto& c : it.value())
+                    candles.append(mb_broker_candle(c));
+                store.store_candles(it.key(), uni.exchange, "1d", candles);
+            }
+
+            QHash<QString, QVector<MbBar>> series;
+            for (const QString& sym : uni.symbols) {
+                QVector<MbBar> bars;
+                for (const auto& c : store.get_candles(sym, uni.exchange, "1d", mb_ms(warmup_from), 0)) {
+                    const QDate d = mb_date(c.timestamp);
+                    if (c.close <= 0)
+                        continue;
+                    if (!bars.isEmpty() && bars.last().date == d)
+                        bars.removeLast(); // keep the latest bar of a session
+                    bars.append(MbBar{d, c.high, c.low, c.close, c.volume});
+                }
+                if (!bars.isEmpty())
+                    series.insert(sym, bars);
+            }
+            const QVector<BreadthRow> rows = mb_compute(series, from, seed);
+
+            QMetaObject::invokeMethod(
+                self.data(),
+                [self, request_id, uni, from, full, rows, members = int(series.size()), fetched = int(fetched.size()),
+                 errors]() {
+                    if (!self)
+                        return;
+                    self->running_.remove(uni.name);
+                    auto& repo = BreadthRepository::instance();
+                    auto r = repo.replace_rows(uni.name, full ? QString() : from.toString(Qt::ISODate), rows);
+                    if (r.is_err()) {
+                        emit self->error_occurred(request_id,
+                                                  "Failed to store breadth: " + QString::fromStdString(r.error()));
+                        return;
+                    }
+                    repo.mark_updated(uni.name);
+                    QJsonObject summary{{"universe", uni.name},
+                                        {"from_date", from.toString(Qt::ISODate)},
+                                        {"rows_written", int(rows.size())},
+                                        {"members", members},
+                                        {"fetched", fetched},
+                                        {"fetch_errors", int(errors.size())}};
+                    if (!rows.isEmpty()) {
+                        summary["first_date"] = rows.first().date;
+                        summary["last_date"] = rows.last().date;
+                        summary["latest"] = row_to_json(rows.last());
+                    }
+                    LOG_INFO("Breadth", QString("%1: %2 sessions written, %3 of %4 members with history")
+                                            .arg(uni.name)
+                                            .arg(rows.size())
+                                            .arg(members)
+                                            .arg(uni.symbols.size()));
+                    emit self->updated(request_id, uni.name, summary);
+                    EventBus::instance().publish("breadth.updated",
+                                                 QVariantMap{{"universe", uni.name}, {"rows", int(rows.size())}});
+                },
+                Qt::QueuedConnection);
+        });
+    };
+
+    // Both fetches (at most two batches) complete before computing.
+    auto fetch_tail = [self, tail, tail_days, compute](MbCandles fetched, QStringList errors) {
+        if (!self)
+            return;
+        if (tail.isEmpty()) {
+            compute(fetched, errors);
+            return;
+        }
+        algo::CandleDataFetcher::instance().fetch_multi(
+            tail, "1d", tail_days, algo::DataSource::YFinance, {}, {},
+            [fetched, errors, compute](const MbCandles& data, const QStringList& more) mutable {
+                for (auto it = data.constBegin(); it != data.constEnd(); ++it)
+                    fetched.insert(it.key(), it.value());
+                compute(fetched, errors + more);
+            });
+    };
+    if (deep.isEmpty()) {
+        QMetaObject::invokeMethod(
+            this, [fetch_tail]() { fetch_tail({}, {}); }, Qt::QueuedConnection);
+    } else {
+        algo::CandleDataFetcher::instance().fetch_multi(
+            deep, "1d", deep_days, algo::DataSource::YFinance, {}, {},
+            [fetch_tail](const MbCandles& data, const QStringList& errors) { fetch_tail(data, errors); });
+    }
+    return request_id;
+}
+
+void MarketBreadthService::start_auto_update() {
+    if (auto_timer_)
+        return;
+    auto_timer_ = new QTimer(this);
+    auto_timer_->setInterval(kAutoCheckMs);
+    connect(auto_timer_, &QTimer::timeout, this, &MarketBreadthService::auto_update);
+    auto_timer_->start();
+}
+
+void MarketBreadthService::auto_update() {
+    auto universes = BreadthRepository::instance().list_universes();
+    if (universes.is_err())
+        return;
+    const QDateTime now = QDateTime::currentDateTimeUtc();
+    for (const auto& u : universes.value()) {
+        if (u.symbols.isEmpty() || running_.contains(u.name))
+            continue;
+        QDateTime last = QDateTime::fromString(u.last_update, "yyyy-MM-dd HH:mm:ss");
+        last.setTimeZone(QTimeZone::utc());
+        if (last.isValid() && last.secsTo(now) < kStaleSecs)
+            continue;
+        update(u.name);
+    }
+}
+
+} // namespace fincept::services
@@ -0,0 +1,61 @@
+// src/services/markets/MarketBreadthService.h
+#pragma once
+#include "storage/repositories/BreadthRepository.h"
+
+#include <QJsonObject>
+#include <QObject>
+#include <QSet>
+#include <QString>
+#include <QTimer>
+
+namespace fincept::services {
+
+/// Daily market breadth for named symbol universes (BreadthRepository):
+/// advances/declines and the A/D line, up/down volume, 52-week new highs and
+/// lows, share of members above their 50/200-day averages, and the McClellan
+/// oscillator (19/39-day EMAs of net advances) with its summation index.
+///
+/// Inputs are the Historify candle store (HistoricalDataStore, interval
+/// "1d", the universe's exchange key). An update tops up only what the store
+/// lacks from Yahoo — a full history for new members, the missing tail for
+/// the rest — then recomputes on a worker thread from the last stored session
+/// onward, seeding the running series from the row before it. A universe
+/// with no rows, or an explicit backfill, recomputes `days` of history.
+/// Main thread only.
+class MarketBreadthService : public QObject {
+    Q_OBJECT
+  public:
+    static constexpr int kDefaultBackfillDays = 365;
+    static constexpr int kMaxBackfillDays = 3650;
+    static constexpr int kWarmupDays = 400;             // history before the first session (252 bars for highs)
+    static constexpr int kAutoCheckMs = 60 * 60 * 1000; // hourly
+    static constexpr int kStaleSecs = 12 * 60 * 60;     // auto-update universes older than this
+
+    static MarketBreadthService& instance();
+
+    /// Starts an update; returns its request id, which updated and
+    /// error_occurred carry. `backfill` rebuilds the last `days` of history.
+    QString update(const QString& universe, bool backfill = false, int days = kDefaultBackfillDays);
+
+    /// Hourly check that updates every universe not updated within kStaleSecs.
+    void start_auto_update();
+
+    static QJsonObject row_to_json(const BreadthRow& r);
+
+  signals:
+    /// {universe, from_date, rows_written, first_date, last_date, members,
+    ///  fetched, fetch_errors, latest{row_to_json}}
+    void updated(QString request_id, QString universe, QJsonObject summary);
+    void error_occurred(QString request_id, QString message);
+
+  private:
+    MarketBreadthService() = default;
+    Q_DISABLE_COPY(MarketBreadthService)
+
+    void auto_update();
+
+    QSet<QString> running_; // universes with an update in flight
+    QTimer* auto_timer_ = nullptr;
+};
+
+} // namespace fincept::services
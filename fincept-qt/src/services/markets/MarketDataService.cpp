@@ -6,8 +6,15 @@
 #include "datahub/TopicPolicy.h"
 #include "python/PythonRunner.h"
 #include "python/PythonWorker.h"
+#include "services/futures/FuturesService.h"
+#include "services/markets/CustomIndexService.h"
+#include "services/markets/SymbologyService.h"
+#include "storage/HistoricalDataStore.h"
 #include "storage/cache/CacheManager.h"
 #include "storage/repositories/SettingsRepository.h"
+#include "trading/AccountManager.h"
+#include "trading/BrokerRegistry.h"
+#include "trading/HistoricalDataService.h"
 
 #include <QDateTime>
 #include <QJsonArray>
@@ -15,8 +22,10 @@
 #include <QJsonObject>
 #include <QPointer>
 #include <QSet>
+#include <QTimeZone>
 
 #include <memory>
+#include <optional>
 
 namespace fincept::services {
 
@@ -71,8 +80,16 @@ void MarketDataService::refresh(const QStringList& topics) {
         } else if (t.startsWith(kHist)) {
             const QString tail = t.mid(kHist.size());
             const QStringList parts = tail.split(QLatin1Char(':'));
-            if (parts.size() == 3)
-                hist_reqs.append({t, parts.at(0), parts.at(1), parts.at(2)});
+            if (parts.size() != 3)
+                continue;
+            if (refresh_history_from_custom_index(t, parts.at(0), parts.at(1), parts.at(2)))
+                continue;
+            if (refresh_history_from_futures(t, parts.at(0), parts.at(1), parts.at(2)))
+                continue;
+            // Symbols a connected broker lists are charted from the broker's own history.
+            if (refresh_history_from_broker(t, parts.at(0), parts.at(1), parts.at(2)))
+                continue;
+            hist_reqs.append({t, parts.at(0), parts.at(1), parts.at(2)});
         }
     }
 
@@ -618,6 +635,22 @@ void MarketDataService::fetch_info(const QString& symbol, InfoCallback cb) {
 
 void MarketDataService::fetch_history(const QString& symbol, const QString& period, const QString& interval,
                                       HistoryCallback cb) {
+    // Custom indices live in the candle store; answer on the next event loop
+    // pass like the yfinance path does.
+    if (CustomIndexService::is_index_symbol(symbol)) {
+        QPointer<MarketDataService> self = this;
+        QTimer::singleShot(0, this, [self, cb, symbol, period, interval]() {
+            if (!self)
+                return;
+            QString error;
+            const auto points = self->custom_index_history(symbol, period, interval, &error);
+            if (points.isEmpty())
+                LOG_WARN("MarketData", "History fetch failed for " + symbol + ": " + error);
+            cb(!points.isEmpty(), points);
+        });
+        return;
+    }
+
     QStringList args;
     args << "historical_period" << symbol << period << interval;
 
@@ -653,6 +686,245 @@ void MarketDataService::fetch_history(const QString& symbol, const QString& peri
     });
 }
 
+// ── Broker history (OHLCV) ──────────────────────────────────────────────────
+
+namespace {
+
+// How a Yahoo-style period/interval pair maps onto a broker history request.
+struct BrokerHistorySpan {
+    QString timeframe;
+    int fetch_days = 0;
+    int sessions = 0; // > 0: keep only the last N trading days ("1d"/"5d" periods)
+};
+
+std::optional<BrokerHistorySpan> broker_history_span(const QString& period, const QString& interval) {
+    BrokerHistorySpan span;
+    if (interval == "1m" || interval == "5m" || interval == "15m" || interval == "30m" || interval == "1d")
+        span.timeframe = interval;
+    else if (interval == "60m" || interval == "1h")
+        span.timeframe = QStringLiteral("1h");
+    else
+        return std::nullopt; // weekly/monthly bars stay with yfinance
+
+    // Short periods fetch a few extra days so weekends and holidays still
+    // leave the requested number of sessions.
+    if (period == "1d") {
+        span.fetch_days = 5;
+        span.sessions = 1;
+    } else if (period == "5d") {
+        span.fetch_days = 10;
+        span.sessions = 5;
+    } else if (period == "1mo") {
+        span.fetch_days = 31;
+    } else if (period == "3mo") {
+        span.fetch_days = 92;
+    } else if (period == "6mo") {
+        span.fetch_days = 183;
+    } else if (period == "1y") {
+        span.fetch_days = 366;
+    } else if (period == "2y") {
+        span.fetch_days = 731;
+    } else if (period == "5y") {
+        span.fetch_days = 1827;
+    } else if (period == "ytd") {
+        span.fetch_days = QDate::currentDate().dayOfYear();
+    } else {
+        return std::nullopt; // 10y / max — longer than broker history endpoints go
+    }
+    // Broker intraday history rarely reaches back past ~60 days.
+    if (span.timeframe != "1d" && span.fetch_days > 60)
+        return std::nullopt;
+    return span;
+}
+
+QVector<HistoryPoint> broker_candles_to_points(const QVector<trading::BrokerCandle>& candles,
+                                               const BrokerHistorySpan& span) {
+    qint64 from_ms = QDateTime::currentMSecsSinceEpoch() - qint64(span.fetch_days) * 86400 * 1000;
+    if (span.sessions > 0) {
+        QSet<QDate> days;
+        for (auto it = candles.crbegin(); it != candles.crend(); ++it) {
+            const QDate d = QDateTime::fromMSecsSinceEpoch(it->timestamp, QTimeZone::UTC).date();
+            if (!days.contains(d) && days.size() == span.sessions)
+                break;
+            days.insert(d);
+            from_ms = it->timestamp;
+        }
+    }
+    QVector<HistoryPoint> points;
+    points.reserve(candles.size());
+    for (const auto& c : candles) {
+        if (c.timestamp < from_ms)
+            continue;
+        HistoryPoint pt;
+        pt.timestamp = c.timestamp / 1000; // HistoryPoint is epoch seconds
+        pt.open = c.open;
+        pt.high = c.high;
+        pt.low = c.low;
+        pt.close = c.close;
+        pt.volume = static_cast<qint64>(c.volume);
+        points.append(pt);
+    }
+    return points;
+}
+
+// First connected account whose broker lists `exchange` (a canonical key).
+bool broker_account_for(const QString& exchange, QString* broker_id, QString* account_id) {
+    auto& am = trading::AccountManager::instance();
+    for (const auto& acct : am.active_accounts()) {
+        if (am.connection_state(acct.account_id) != trading::ConnectionState::Connected)
+            continue;
+        auto* broker = trading::BrokerRegistry::instance().get(acct.broker_id);
+        if (!broker)
+            continue;
+        for (const auto& ex : broker->profile().exchanges) {
+            if (SymbologyService::canonical_exchange(ex) == exchange) {
+                if (broker_id)
+                    *broker_id = acct.broker_id;
+                if (account_id)
+                    *account_id = acct.account_id;
+                return true;
+            }
+        }
+    }
+    return false;
+}
+
+} // namespace
+
+void MarketDataService::fetch_broker_history(const QString& symbol, const QString& timeframe, int lookback_days,
+                                             BrokerHistoryCallback cb) {
+    const auto id = SymbologyService::parse(symbol);
+    if (id.kind != SymbologyService::IdKind::Ticker || id.value.isEmpty()) {
+        cb(false, {}, {}, QStringLiteral("Not an exchange-listed ticker: ") + symbol);
+        return;
+    }
+    // A Yahoo symbol without a suffix is a US listing.
+    const QString exchange = id.exchange.isEmpty() ? QStringLiteral("US") : id.exchange;
+
+    QString broker_id, account_id;
+    if (!broker_account_for(exchange, &broker_id, &account_id)) {
+        cb(false, {}, {}, QStringLiteral("No connected broker lists ") + exchange);
+        return;
+    }
+
+    trading::HistoricalDataService::instance().fetch_to_store(
+        id.value, exchange, timeframe, lookback_days, broker_id, account_id,
+        [cb, broker_id](bool ok, const QVector<trading::BrokerCandle>& candles, const QString& error) {
+            cb(ok, candles, broker_id, error);
+        });
+}
+
+bool MarketDataService::refresh_history_from_broker(const QString& topic, const QString& symbol,
+                                                    const QString& period, const QString& interval) {
+    // Indices (^GSPC), futures (GC=F), FX (EURUSD=X) and crypto pairs
+    // (BTC-USD) are Yahoo-only symbols.
+    const auto id = SymbologyService::parse(symbol);
+    if (symbol.contains('^') || symbol.contains('=') || (id.exchange.isEmpty() && symbol.contains('-')))
+        return false;
+    const auto span = broker_history_span(period, interval);
+    if (!span)
+        return false;
+
+    // Symbols no connected broker lists stay in the yfinance batch.
+    if (!broker_account_for(id.exchange.isEmpty() ? QStringLiteral("US") : id.exchange, nullptr, nullptr))
+        return false;
+
+    QPointer<MarketDataService> self = this;
+    const BrokerHistorySpan sp = *span;
+    fetch_broker_history(
+        symbol, sp.timeframe, sp.fetch_days,
+        [self, topic, symbol, period, interval, sp](bool ok, const QVector<trading::BrokerCandle>& candles,
+                                                    const QString& broker_id, const QString& error) {
+            if (!self)
+                return;
+            const QVector<HistoryPoint> points = ok ? broker_candles_to_points(candles, sp) : QVector<HistoryPoint>{};
+            if (!points.isEmpty()) {
+                LOG_INFO("MarketData", QString("History for %1 (%2/%3) from %4: %5 bars")
+                                           .arg(symbol, period, interval, broker_id)
+                                           .arg(points.size()));
+                self->publish_history_to_hub(symbol, period, interval, points);
+                return;
+            }
+            LOG_INFO("MarketData", QString("Broker history unavailable for %1 (%2) — falling back to yfinance")
+                                       .arg(symbol, error.isEmpty() ? QStringLiteral("no bars in range") : error));
+            // Every requested topic must end in publish() or publish_error() (see refresh()).
+            self->fetch_history(symbol, period, interval, [self, topic, symbol, period, interval](
+                                                              bool yf_ok, QVector<HistoryPoint> yf_points) {
+                if (!self)
+                    return;
+                if (yf_ok && !yf_points.isEmpty())
+                    self->publish_history_to_hub(symbol, period, interval, yf_points);
+                else
+                    datahub::DataHub::instance().publish_error(topic, QStringLiteral("No history data"));
+            });
+        });
+    return true;
+}
+
+bool MarketDataService::refresh_history_from_futures(const QString& topic, const QString& symbol,
+                                                     const QString& period, const QString& interval) {
+    if (!services::futures::is_continuous_symbol(symbol))
+        return false;
+    auto span = broker_history_span(period, interval);
+    if (!span && interval == "1d" && (period == "10y" || period == "max"))
+        span = BrokerHistorySpan{QStringLiteral("1d"), period == "10y" ? 3653 : 36525, 0};
+    if (!span) {
+        datahub::DataHub::instance().publish_error(
+            topic, QStringLiteral("Continuous futures history is 1m–1d bars, intraday up to 60 days"));
+        return true;
+    }
+    const qint64 to_ms = QDateTime::currentMSecsSinceEpoch();
+    const qint64 from_ms = to_ms - qint64(span->fetch_days) * 86400 * 1000;
+    QString error;
+    const auto series =
+        services::futures::FuturesService::instance().continuous(symbol, span->timeframe, from_ms, to_ms, &error);
+    const QVector<HistoryPoint> points = broker_candles_to_points(series.candles, *span);
+    if (points.isEmpty()) {
+        datahub::DataHub::instance().publish_error(topic, error.isEmpty() ? QStringLiteral("No history data") : error);
+        return true;
+    }
+    publish_history_to_hub(symbol, period, interval, points);
+    return true;
+}
+
+QVector<HistoryPoint> MarketDataService::custom_index_history(const QString& symbol, const QString& period,
+                                                             const QString& interval, QString* error) {
+    if (interval != "1d" && interval != "1wk" && interval != "1mo") {
+        if (error)
+            *error = QStringLiteral("Custom indices have daily, weekly and monthly bars");
+        return {};
+    }
+    auto span = broker_history_span(period, "1d");
+    if (!span && (period == "10y" || period == "max"))
+        span = BrokerHistorySpan{QStringLiteral("1d"), period == "10y" ? 3653 : 36525, 0};
+    if (!span) {
+        if (error)
+            *error = QString("Unsupported period '%1'").arg(period);
+        return {};
+    }
+
+    const qint64 from_ms = QDateTime::currentMSecsSinceEpoch() - qint64(span->fetch_days) * 86400 * 1000;
+    auto candles = CustomIndexService::instance().candles(symbol, from_ms, 0, error);
+    if (interval != "1d" && !candles.isEmpty())
+        candles = storage::HistoricalDataStore::instance().get_resampled(
+            symbol.toUpper(), CustomIndexService::kExchange, interval == "1wk" ? "1w" : "1mo", from_ms, 0);
+    return broker_candles_to_points(candles, *span);
+}
+
+bool MarketDataService::refresh_history_from_custom_index(const QString& topic, const QString& symbol,
+                                                          const QString& period, const QString& interval) {
+    if (!CustomIndexService::is_index_symbol(symbol))
+        return false;
+    QString error;
+    const QVector<HistoryPoint> points = custom_index_history(symbol, period, interval, &error);
+    if (points.isEmpty()) {
+        datahub::DataHub::instance().publish_error(topic, error.isEmpty() ? QStringLiteral("No history data") : error);
+        return true;
+    }
+    publish_history_to_hub(symbol, period, interval, points);
+    return true;
+}
+
 // ── Static symbol lists ─────────────────────────────────────────────────────
 
 QStringList MarketDataService::indices_symbols() {
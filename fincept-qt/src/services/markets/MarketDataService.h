@@ -1,6 +1,7 @@
 #pragma once
 #include "core/result/Result.h"
 #include "datahub/Producer.h"
+#include "trading/TradingTypes.h"
 
 #include <QJsonArray>
 #include <QJsonObject>
@@ -112,6 +113,17 @@ class MarketDataService : public QObject, public fincept::datahub::Producer {
     using HistoryCallback = std::function<void(bool, QVector<HistoryPoint>)>;
     void fetch_history(const QString& symbol, const QString& period, const QString& interval, HistoryCallback cb);
 
+    /// OHLCV history from the first connected broker account that lists the
+    /// symbol's market ("TCS.NS" → NSE, a bare ticker → US). The broker module
+    /// serves it through trading::HistoricalDataService, and the bars (epoch ms)
+    /// are upserted into the shared candle cache (HistoricalDataStore) under the
+    /// bare symbol and exchange. timeframe: "1m","5m","15m","30m","1h","1d".
+    /// Fails when no connected broker lists the market. Main thread only.
+    using BrokerHistoryCallback = std::function<void(bool ok, const QVector<trading::BrokerCandle>& candles,
+                                                     const QString& broker_id, const QString& error)>;
+    void fetch_broker_history(const QString& symbol, const QString& timeframe, int lookback_days,
+                              BrokerHistoryCallback cb);
+
     /// Fetch 5-day hourly sparkline data for multiple symbols in one Python call.
     /// Callback: map of symbol -> list of close prices (chronological).
     /// Phase 3+: prefer `DataHub::subscribe(this, "market:sparkline:<sym>", ...)` for live tables.
@@ -159,6 +171,28 @@ class MarketDataService : public QObject, public fincept::datahub::Producer {
                                 const QVector<HistoryPoint>& points);
     void publish_sparkline_to_hub(const QString& symbol, const QVector<double>& points);
 
+    /// refresh() path for a `market:history:*` topic a connected broker can
+    /// serve; false leaves it to the yfinance batch. Falls back to
+    /// fetch_history() when the broker returns nothing.
+    bool refresh_history_from_broker(const QString& topic, const QString& symbol, const QString& period,
+                                     const QString& interval);
+
+    /// refresh() path for continuous futures (ES#C): stitched from cached
+    /// contracts by FuturesService. True for every continuous symbol — Yahoo
+    /// has no such tickers.
+    bool refresh_history_from_futures(const QString& topic, const QString& symbol, const QString& period,
+                                      const QString& interval);
+
+    /// refresh() path for custom indices (@NAME): daily series built by
+    /// CustomIndexService, weekly / monthly resampled from it. True for every
+    /// @-symbol.
+    bool refresh_history_from_custom_index(const QString& topic, const QString& symbol, const QString& period,
+                                           const QString& interval);
+    /// Points of custom index `symbol` over a Yahoo-style period / interval;
+    /// empty with `error` set when it has none.
+    QVector<HistoryPoint> custom_index_history(const QString& symbol, const QString& period, const QString& interval,
+                                               QString* error);
+
     // ── Display-name cache (symbol → human-readable name) ──
     // Persisted to SettingsRepository so resolution survives restarts and the
     // per-symbol yfinance .info cost is paid at most once.
@@ -0,0 +1,142 @@
+// src/services/markets/QuoteRouter.cpp
+#include "services/markets/QuoteRouter.h"
+
+#include "core/logging/Logger.h"
+#include "python/PythonRunner.h"
+#include "services/markets/MarketDataService.h"
+#include "storage/cache/CacheManager.h"
+#include "storage/repositories/SettingsRepository.h"
+
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QPointer>
+
+namespace fincept::services {
+
+namespace {
+
+const char* kQrChainKey = "market_data.quote_chain";
+
+QJsonObject qr_from_market_data(const QuoteData& q) {
+    QJsonObject sources;
+    for (const char* f : {"price", "change", "change_pct", "high", "low", "volume"})
+        sources[f] = "yfinance";
+    if (!q.name.isEmpty())
+        sources["name"] = "yfinance";
+    return QJsonObject{{"symbol", q.symbol},
+                       {"price", q.price},
+                       {"change", q.change},
+                       {"change_pct", q.change_pct},
+                       {"open", QJsonValue()},
+                       {"high", q.high},
+                       {"low", q.low},
+                       {"previous_close", q.price - q.change},
+                       {"volume", q.volume},
+                       {"name", q.name.isEmpty() ? QJsonValue() : QJsonValue(q.name)},
+                       {"provider", "yfinance"},
+                       {"sources", sources},
+                       {"missing", QJsonArray{"open"}},
+                       {"attempts", QJsonArray{QJsonObject{{"provider", "yfinance"}, {"ok", true}}}}};
+}
+
+} // namespace
+
+QuoteRouter& QuoteRouter::instance() {
+    static QuoteRouter s;
+    return s;
+}
+
+QStringList QuoteRouter::providers() {
+    return {"yfinance", "fmp", "polygon", "finnhub", "alphavantage", "twelvedata"};
+}
+
+QStringList QuoteRouter::default_chain() const {
+    auto r = SettingsRepository::instance().get(kQrChainKey);
+    QStringList chain;
+    if (r.is_ok() && !r.value().isEmpty()) {
+        for (const auto& v : QJsonDocument::fromJson(r.value().toUtf8()).array()) {
+            const QString p = v.toString().trimmed().toLower();
+            if (providers().contains(p) && !chain.contains(p))
+                chain.append(p);
+        }
+    }
+    return chain.isEmpty() ? QStringList{"yfinance"} : chain;
+}
+
+Result<void> QuoteRouter::set_default_chain(const QStringList& chain) {
+    QJsonArray arr;
+    QStringList seen;
+    for (const QString& raw : chain) {
+        const QString p = raw.trimmed().toLower();
+        if (!providers().contains(p))
+            return Result<void>::err(
+                QString("Unknown quote provider '%1' (expected: %2)").arg(raw, providers().join(", ")).toStdString());
+        if (seen.contains(p))
+            continue;
+        seen.append(p);
+        arr.append(p);
+    }
+    if (arr.isEmpty())
+        return Result<void>::err("Provider chain is empty");
+    return SettingsRepository::instance().set(
+        kQrChainKey, QString::fromUtf8(QJsonDocument(arr).toJson(QJsonDocument::Compact)), "market_data");
+}
+
+void QuoteRouter::fetch(const QString& symbol, const QStringList& chain, Callback cb) {
+    QStringList route;
+    for (const QString& p : chain.isEmpty() ? default_chain() : chain) {
+        const QString id = p.trimmed().toLower();
+        if (!id.isEmpty() && !route.contains(id))
+            route.append(id);
+    }
+    const QString sym = symbol.trimmed().toUpper();
+
+    if (route == QStringList{"yfinance"}) {
+        MarketDataService::instance().fetch_quotes({sym}, [cb, sym](bool ok, QVector<QuoteData> quotes) {
+            for (const auto& q : quotes) {
+                if (ok && q.symbol.compare(sym, Qt::CaseInsensitive) == 0) {
+                    cb(true, qr_from_market_data(q), {});
+                    return;
+                }
+            }
+            cb(false, {}, "No quote data available for " + sym);
+        });
+        return;
+    }
+
+    const QString cache_key = "quote_router:" + route.join(',') + ':' + sym;
+    if (auto cached = fincept::CacheManager::instance().try_get(cache_key)) {
+        const auto doc = QJsonDocument::fromJson(cached->toUtf8());
+        if (doc.isObject()) {
+            cb(true, doc.object(), {});
+            return;
+        }
+    }
+
+    QPointer<QuoteRouter> self = this;
+    python::PythonRunner::instance().run(
+        "quote_router.py", {sym, route.join(',')}, [self, cb, sym, route, cache_key](python::PythonResult r) {
+            if (!self)
+                return;
+            const QJsonObject o =
+                r.success ? QJsonDocument::fromJson(python::extract_json(r.output).toUtf8()).object() : QJsonObject();
+            if (!o.value("success").toBool(false)) {
+                QString msg = o.value("error").toString();
+                if (msg.isEmpty())
+                    msg = r.error.isEmpty() ? QStringLiteral("quote_router.py produced no result") : r.error;
+                LOG_WARN("QuoteRouter", QString("%1 via [%2]: %3").arg(sym, route.join(" → "), msg));
+                cb(false, QJsonObject{{"attempts", o.value("attempts")}}, msg);
+                return;
+            }
+            const QJsonObject data = o.value("data").toObject();
+            const QString served = data.value("provider").toString();
+            if (!route.isEmpty() && served != route.first())
+                LOG_INFO("QuoteRouter", QString("%1 served by %2 (chain %3)").arg(sym, served, route.join(" → ")));
+            fincept::CacheManager::instance().put(
+                cache_key, QVariant(QString::fromUtf8(QJsonDocument(data).toJson(QJsonDocument::Compact))),
+                kCacheTtlSec, "market_data");
+            cb(true, data, {});
+        });
+}
+
+} // namespace fincept::services
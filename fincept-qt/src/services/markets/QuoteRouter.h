@@ -0,0 +1,53 @@
+// src/services/markets/QuoteRouter.h
+#pragma once
+#include "core/result/Result.h"
+
+#include <QJsonObject>
+#include <QObject>
+#include <QString>
+#include <QStringList>
+
+#include <functional>
+
+namespace fincept::services {
+
+/// Quote routing over a provider priority list (e.g. polygon → fmp →
+/// yfinance). Providers are tried in order by quote_router.py: one that
+/// errors, is rate limited or has no API key configured is failed over, and
+/// fields the serving provider leaves empty are filled from the ones after
+/// it. The result records which provider supplied each field and every
+/// attempt made.
+///
+/// A yfinance-only chain goes through MarketDataService instead, so the
+/// batched fetch and the `market:quote:*` DataHub topic stay warm. The
+/// user's default chain lives in settings. Main thread only.
+class QuoteRouter : public QObject {
+    Q_OBJECT
+  public:
+    static constexpr int kCacheTtlSec = 30;
+
+    static QuoteRouter& instance();
+
+    /// Provider ids quote_router.py understands.
+    static QStringList providers();
+
+    /// Default chain (settings key market_data.quote_chain); yfinance when unset.
+    QStringList default_chain() const;
+
+    /// Validates and stores `chain` as the default.
+    Result<void> set_default_chain(const QStringList& chain);
+
+    /// `quote`: {symbol, price, change, change_pct, open, high, low,
+    /// previous_close, volume, name, provider, sources{field: provider},
+    /// missing[], attempts[{provider, ok, error?, rate_limited?, fields?, ms?}]}
+    using Callback = std::function<void(bool ok, const QJsonObject& quote, const QString& error)>;
+
+    /// Routes one quote; an empty `chain` uses default_chain().
+    void fetch(const QString& symbol, const QStringList& chain, Callback cb);
+
+  private:
+    QuoteRouter() = default;
+    Q_DISABLE_COPY(QuoteRouter)
+};
+
+} // namespace fincept::services
@@ -0,0 +1,359 @@
+// src/services/markets/SymbologyService.cpp
+#include "services/markets/SymbologyService.h"
+
+#include "core/logging/Logger.h"
+#include "python/PythonRunner.h"
+#include "trading/instruments/InstrumentService.h"
+#include "trading/instruments/SymbolResolver.h"
+
+#include <QDateTime>
+#include <QJsonDocument>
+#include <QPointer>
+#include <QRegularExpression>
+#include <QTimer>
+#include <QUuid>
+
+namespace fincept::services {
+
+namespace {
+
+struct SymVenue {
+    const char* exchange;    // canonical key
+    const char* yahoo;       // Yahoo Finance suffix
+    const char* figi;        // OpenFIGI exchCode asked for on ticker lookups
+    const char* figi_codes;  // every exchCode that maps back to this key
+    const char* tradingview; // TradingView prefix (US uses the listing venue)
+};
+
+const SymVenue kSymVenues[] = {
+    {"NSE", ".NS", "IS", "IS IN", "NSE"},
+    {"BSE", ".BO", "IB", "IB", "BSE"},
+    {"US", "", "US", "US UW UQ UR UN UA UP UF UV", ""},
+    {"LSE", ".L", "LN", "LN", "LSE"},
+    {"TSE", ".T", "JT", "JT JP", "TSE"},
+    {"HKEX", ".HK", "HK", "HK", "HKEX"},
+    {"TSX", ".TO", "CT", "CT CN", "TSX"},
+    {"ASX", ".AX", "AT", "AT AU", "ASX"},
+    {"XETRA", ".DE", "GY", "GY GR", "XETR"},
+    {"EPA", ".PA", "FP", "FP", "EURONEXT"},
+};
+
+const SymVenue* sym_venue(const QString& exchange) {
+    for (const auto& v : kSymVenues)
+        if (exchange == QLatin1String(v.exchange))
+            return &v;
+    return nullptr;
+}
+
+// OpenFIGI venue codes inside the US composite.
+QString sym_us_listing(const QString& figi_code) {
+    if (figi_code == "UW" || figi_code == "UQ" || figi_code == "UR")
+        return "NASDAQ";
+    if (figi_code == "UN")
+        return "NYSE";
+    if (figi_code == "UA")
+        return "AMEX";
+    if (figi_code == "UP")
+        return "ARCA";
+    return {};
+}
+
+QString sym_exchange_for_figi_code(const QString& code) {
+    for (const auto& v : kSymVenues)
+        if (QString(v.figi_codes).split(' ').contains(code))
+            return v.exchange;
+    return {};
+}
+
+QString sym_now_iso(qint64 ms) {
+    return ms > 0 ? QDateTime::fromMSecsSinceEpoch(ms).toUTC().toString(Qt::ISODate) : QString();
+}
+
+} // namespace
+
+SymbologyService& SymbologyService::instance() {
+    static SymbologyService s;
+    return s;
+}
+
+void SymbologyService::start() {
+    if (started_)
+        return;
+    started_ = true;
+    connect(&trading::InstrumentService::instance(), &trading::InstrumentService::refresh_done, this,
+            [this](const QString&, int) { seed(); });
+    QTimer::singleShot(0, this, [this]() { seed(); });
+}
+
+// ── Parsing ─────────────────────────────────────────────────────────────────
+
+QString SymbologyService::canonical_exchange(const QString& exchange) {
+    const QString e = exchange.trimmed().toUpper();
+    if (e == "NASDAQ" || e == "NYSE" || e == "AMEX" || e == "ARCA" || e == "NYSEARCA" || e == "BATS" || e == "USA")
+        return "US";
+    if (e == "XETR" || e == "FRA" || e == "ETR")
+        return "XETRA";
+    if (e == "TYO" || e == "JPX")
+        return "TSE";
+    if (e == "HKG" || e == "HKSE")
+        return "HKEX";
+    if (e == "LON")
+        return "LSE";
+    if (e == "EURONEXT" || e == "PAR")
+        return "EPA";
+    return e;
+}
+
+SymbologyService::Identifier SymbologyService::parse(const QString& identifier, const QString& exchange_hint) {
+    static const QRegularExpression isin_re("^[A-Z]{2}[A-Z0-9]{9}[0-9]$");
+    static const QRegularExpression figi_re("^BBG[A-Z0-9]{9}$");
+
+    Identifier id;
+    QString s = identifier.trimmed().toUpper();
+    if (s.isEmpty())
+        return id;
+    if (figi_re.match(s).hasMatch()) {
+        id.kind = IdKind::Figi;
+        id.value = s;
+        return id;
+    }
+    if (isin_re.match(s).hasMatch()) {
+        id.kind = IdKind::Isin;
+        id.value = s;
+        return id;
+    }
+
+    id.kind = IdKind::Ticker;
+    QString venue = exchange_hint.trimmed().toUpper();
+    const int colon = s.indexOf(':');
+    if (colon > 0) {
+        venue = s.left(colon);
+        s = s.mid(colon + 1);
+    } else {
+        const int dot = s.lastIndexOf('.');
+        if (dot > 0) {
+            for (const auto& v : kSymVenues) {
+                if (*v.yahoo && s.mid(dot) == QLatin1String(v.yahoo)) {
+                    venue = v.exchange;
+                    s = s.left(dot);
+                    break;
+                }
+            }
+        }
+    }
+    id.exchange = canonical_exchange(venue);
+    if (id.exchange == "US" && venue != "US")
+        id.listing = venue == "NYSEARCA" ? QStringLiteral("ARCA") : venue;
+    // Yahoo writes US share classes as BRK-B; the table keeps BRK.B.
+    id.value = id.exchange == "US" ? QString(s).replace('-', '.').replace('/', '.') : s;
+    if (id.value.isEmpty())
+        id.kind = IdKind::Invalid;
+    return id;
+}
+
+QString SymbologyService::yahoo_symbol(const QString& exchange, const QString& symbol) {
+    const QString ex = canonical_exchange(exchange);
+    const QString sym = symbol.trimmed().toUpper();
+    if (ex == "US" || ex.isEmpty())
+        return QString(sym).replace('.', '-');
+    const SymVenue* v = sym_venue(ex);
+    return v ? sym + v->yahoo : QString();
+}
+
+// ── Lookup ──────────────────────────────────────────────────────────────────
+
+std::optional<SymbolMapping> SymbologyService::lookup(const Identifier& id) {
+    auto& repo = SymbologyRepository::instance();
+    switch (id.kind) {
+        case IdKind::Isin:
+            return repo.find_by_isin(id.value);
+        case IdKind::Figi:
+            return repo.find_by_figi(id.value);
+        case IdKind::Ticker: {
+            if (!id.exchange.isEmpty())
+                return repo.find(id.exchange, id.value);
+            auto rows = repo.find_by_symbol(id.value);
+            if (rows.is_ok() && !rows.value().isEmpty())
+                return rows.value().first();
+            return std::nullopt;
+        }
+        case IdKind::Invalid:
+            break;
+    }
+    return std::nullopt;
+}
+
+std::optional<SymbolMapping> SymbologyService::lookup(const QString& identifier, const QString& exchange_hint) {
+    return lookup(parse(identifier, exchange_hint));
+}
+
+QJsonObject SymbologyService::describe(const SymbolMapping& m) const {
+    QString tv_prefix = m.listing;
+    if (tv_prefix.isEmpty())
+        if (const SymVenue* v = sym_venue(m.exchange))
+            tv_prefix = v->tradingview;
+
+    QJsonObject brokers;
+    auto& instruments = trading::InstrumentService::instance();
+    for (const QString& broker_id : trading::SymbolResolver::instance().registered_brokers()) {
+        if (!instruments.is_loaded(broker_id))
+            continue;
+        auto inst = instruments.find(m.symbol, m.exchange, broker_id);
+        if (!inst && !m.listing.isEmpty())
+            inst = instruments.find(m.symbol, m.listing, broker_id);
+        if (!inst)
+            continue;
+        QJsonObject b{{"symbol", inst->symbol},
+                      {"exchange", inst->exchange},
+                      {"brsymbol", inst->brsymbol},
+                      {"brexchange", inst->brexchange},
+                      {"token", QString::number(inst->instrument_token)}};
+        if (!inst->broker_token.isEmpty())
+            b["broker_token"] = inst->broker_token;
+        brokers[broker_id] = b;
+    }
+
+    QJsonObject providers{
+        {"canonical", m.exchange.isEmpty() ? m.symbol : m.exchange + ':' + m.symbol},
+        {"yahoo", yahoo_symbol(m.exchange, m.symbol)},
+        {"tradingview", tv_prefix.isEmpty() ? m.symbol : tv_prefix + ':' + QString(m.symbol).replace('.', '_')},
+        {"openfigi",
+         QJsonObject{{"figi", m.figi}, {"composite_figi", m.composite_figi}, {"share_class_figi", m.share_class_figi}}},
+        {"brokers", brokers},
+    };
+    if (!m.isin.isEmpty())
+        providers["isin"] = m.isin;
+
+    return QJsonObject{{"exchange", m.exchange},
+                       {"symbol", m.symbol},
+                       {"listing", m.listing},
+                       {"name", m.name},
+                       {"isin", m.isin},
+                       {"figi", m.figi},
+                       {"composite_figi", m.composite_figi},
+                       {"share_class_figi", m.share_class_figi},
+                       {"security_type", m.security_type},
+                       {"source", m.source},
+                       {"updated_at", sym_now_iso(m.updated_at)},
+                       {"providers", providers}};
+}
+
+// ── Resolve ─────────────────────────────────────────────────────────────────
+
+void SymbologyService::finish(const QString& request_id, const QJsonObject& mapping) {
+    QTimer::singleShot(0, this, [this, request_id, mapping]() { emit resolved(request_id, mapping); });
+}
+
+void SymbologyService::fail(const QString& request_id, const QString& error) {
+    QTimer::singleShot(0, this, [this, request_id, error]() { emit error_occurred(request_id, error); });
+}
+
+QString SymbologyService::resolve(const QString& identifier, const QString& exchange_hint, bool online) {
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    const Identifier id = parse(identifier, exchange_hint);
+    if (id.kind == IdKind::Invalid) {
+        fail(request_id, QString("Not a recognisable identifier: '%1'").arg(identifier));
+        return request_id;
+    }
+
+    const auto local = lookup(id);
+    if (local && (!online || !local->figi.isEmpty())) {
+        finish(request_id, describe(*local));
+        return request_id;
+    }
+    if (!online) {
+        fail(request_id, QString("'%1' is not in the symbology table").arg(identifier));
+        return request_id;
+    }
+
+    QStringList args{"resolve"};
+    QString exchange = local ? local->exchange : id.exchange;
+    switch (id.kind) {
+        case IdKind::Isin:
+            args << "ID_ISIN" << id.value;
+            break;
+        case IdKind::Figi:
+            args << "ID_BB_GLOBAL" << id.value;
+            break;
+        default: {
+            const SymVenue* v = sym_venue(exchange.isEmpty() ? QStringLiteral("US") : exchange);
+            if (!v) {
+                // OpenFIGI can't be asked about this venue; hand back what we have.
+                if (local)
+                    finish(request_id, describe(*local));
+                else
+                    fail(request_id, QString("No OpenFIGI exchange code for '%1'").arg(exchange));
+                return request_id;
+            }
+            args << "TICKER" << QString(id.value).replace('.', '/') << v->figi;
+            break;
+        }
+    }
+
+    QPointer<SymbologyService> self = this;
+    python::PythonRunner::instance().run(
+        "openfigi_data.py", args, [self, request_id, id, local, exchange](python::PythonResult r) {
+            if (!self)
+                return;
+            const QJsonObject o =
+                r.success ? QJsonDocument::fromJson(python::extract_json(r.output).toUtf8()).object() : QJsonObject();
+            if (!o.value("success").toBool(false)) {
+                QString msg = o.value("error").toString();
+                if (msg.isEmpty())
+                    msg = r.error.isEmpty() ? QStringLiteral("openfigi_data.py produced no result") : r.error;
+                LOG_WARN("Symbology", QString("OpenFIGI lookup for %1 failed: %2").arg(id.value, msg));
+                if (local) {
+                    QJsonObject out = self->describe(*local);
+                    out["warning"] = "OpenFIGI: " + msg;
+                    emit self->resolved(request_id, out);
+                } else {
+                    emit self->error_occurred(request_id, msg);
+                }
+                return;
+            }
+
+            const QJsonObject d = o.value("data").toObject();
+            const QString code = d.value("exch_code").toString();
+            SymbolMapping m = local.value_or(SymbolMapping{});
+            if (m.exchange.isEmpty())
+                m.exchange = exchange.isEmpty() ? sym_exchange_for_figi_code(code) : exchange;
+            if (m.exchange.isEmpty())
+                m.exchange = code.toUpper();
+            if (m.symbol.isEmpty())
+                m.symbol = id.kind == IdKind::Ticker ? id.value : d.value("ticker").toString().replace('/', '.');
+            if (m.listing.isEmpty())
+                m.listing = m.exchange == "US" ? (id.listing.isEmpty() ? sym_us_listing(code) : id.listing) : QString();
+            if (m.isin.isEmpty() && id.kind == IdKind::Isin)
+                m.isin = id.value;
+            if (m.name.isEmpty())
+                m.name = d.value("name").toString();
+            m.figi = d.value("figi").toString();
+            m.composite_figi = d.value("composite_figi").toString();
+            m.share_class_figi = d.value("share_class_figi").toString();
+            m.security_type = d.value("security_type").toString();
+            if (m.source != "manual")
+                m.source = "openfigi";
+            m.updated_at = QDateTime::currentMSecsSinceEpoch();
+
+            auto saved = SymbologyRepository::instance().upsert(m);
+            if (saved.is_err())
+                LOG_WARN("Symbology", QString("Failed to store %1:%2: %3")
+                                          .arg(m.exchange, m.symbol, QString::fromStdString(saved.error())));
+            emit self->resolved(request_id, self->describe(m));
+        });
+    return request_id;
+}
+
+Result<int> SymbologyService::seed() {
+    auto r = SymbologyRepository::instance().seed_from_instruments();
+    if (r.is_err()) {
+        LOG_WARN("Symbology", QString("Seeding from instruments failed: %1").arg(QString::fromStdString(r.error())));
+        return r;
+    }
+    if (r.value() > 0)
+        LOG_INFO("Symbology", QString("Seeded %1 listing(s) from broker master contracts").arg(r.value()));
+    emit seeded(r.value());
+    return r;
+}
+
+} // namespace fincept::services
@@ -0,0 +1,83 @@
+// src/services/markets/SymbologyService.h
+#pragma once
+#include "core/result/Result.h"
+#include "storage/repositories/SymbologyRepository.h"
+
+#include <QJsonObject>
+#include <QObject>
+#include <QString>
+
+#include <optional>
+
+namespace fincept::services {
+
+/// One symbology for the whole app: maps any identifier — an ISIN, a FIGI,
+/// "EXCHANGE:SYMBOL", a Yahoo ticker ("RELIANCE.NS") or a bare ticker — to the
+/// symbol each provider and connected broker expects.
+///
+/// Mappings live in the `symbology` table. It is seeded from the broker master
+/// contracts whenever InstrumentService finishes a refresh, and rows missing
+/// their FIGIs are completed through OpenFIGI (openfigi_data.py resolve) on
+/// the first online resolve. Broker symbols / tokens are read live from
+/// InstrumentService, so they always match the loaded catalog. Main thread only.
+class SymbologyService : public QObject {
+    Q_OBJECT
+  public:
+    enum class IdKind { Invalid, Isin, Figi, Ticker };
+
+    struct Identifier {
+        IdKind kind = IdKind::Invalid;
+        QString value;    // upper-cased ISIN / FIGI / ticker
+        QString exchange; // canonical exchange key, empty when unknown
+        QString listing;  // venue named in the input when it maps to a country key (NYSE → US)
+    };
+
+    static SymbologyService& instance();
+
+    /// Seeds once and re-seeds after every instrument refresh.
+    void start();
+
+    /// Classifies `identifier`; `exchange_hint` applies to bare tickers.
+    static Identifier parse(const QString& identifier, const QString& exchange_hint = {});
+
+    /// Canonical exchange key for an alias ("NASDAQ" → "US", "XETR" → "XETRA");
+    /// unknown keys are returned upper-cased.
+    static QString canonical_exchange(const QString& exchange);
+
+    /// Yahoo Finance symbol for a listing ("NSE", "TCS" → "TCS.NS"); empty when
+    /// Yahoo has no suffix for the exchange.
+    static QString yahoo_symbol(const QString& exchange, const QString& symbol);
+
+    /// Local-only lookup, no network.
+    std::optional<SymbolMapping> lookup(const QString& identifier, const QString& exchange_hint = {});
+
+    /// `m` plus providers{canonical, yahoo, tradingview, openfigi{...},
+    /// brokers{broker_id: {brsymbol, brexchange, token, broker_token}}}.
+    QJsonObject describe(const SymbolMapping& m) const;
+
+    /// Resolves asynchronously; emits resolved() or error_occurred() with the
+    /// returned request id. With `online`, identifiers that are unknown locally
+    /// or lack a FIGI go to OpenFIGI and the answer is stored.
+    QString resolve(const QString& identifier, const QString& exchange_hint = {}, bool online = true);
+
+    /// Pulls new equity / index listings from the instruments table. Returns
+    /// the rows added or completed.
+    Result<int> seed();
+
+  signals:
+    void resolved(const QString& request_id, const QJsonObject& mapping);
+    void error_occurred(const QString& request_id, const QString& error);
+    void seeded(int rows);
+
+  private:
+    SymbologyService() = default;
+    Q_DISABLE_COPY(SymbologyService)
+
+    std::optional<SymbolMapping> lookup(const Identifier& id);
+    void finish(const QString& request_id, const QJsonObject& mapping);
+    void fail(const QString& request_id, const QString& error);
+
+    bool started_ = false;
+};
+
+} // namespace fincept::services
@@ -0,0 +1,445 @@
+// src/services/markets/TickRecorder.cpp
+#include "services/markets/TickRecorder.h"
+
+#include "core/config/AppPaths.h"
+#include "core/logging/Logger.h"
+#include "datahub/DataHub.h"
+#include "datahub/DataHubMetaTypes.h"
+#include "services/markets/MarketDataService.h"
+#include "storage/HistoricalDataStore.h"
+#include "storage/repositories/SettingsRepository.h"
+#include "trading/TradingTypes.h"
+
+#include <QCoreApplication>
+#include <QDir>
+#include <QFileInfo>
+#include <QJsonDocument>
+#include <QPointer>
+#include <QTimeZone>
+#include <QtConcurrent>
+#include <QtEndian>
+
+#include <algorithm>
+
+namespace fincept::services {
+
+namespace {
+
+const char* kTrConfigKey = "tick_recorder.config";
+constexpr int kTrMaxBuffered = 5000; // flush early under a burst
+constexpr qint64 kTrMb = 1024 * 1024;
+
+QRegularExpression tr_wildcard(const QString& pattern) {
+    QStringList parts = pattern.trimmed().split('*');
+    for (auto& p : parts)
+        p = QRegularExpression::escape(p);
+    return QRegularExpression(QRegularExpression::anchoredPattern(parts.join(".*")));
+}
+
+// Recording start time encoded in the file name; 0 for foreign files.
+qint64 tr_file_start_ms(const QString& file_name) {
+    const QDateTime dt = QDateTime::fromString(file_name.mid(6, 15), "yyyyMMdd_HHmmss");
+    if (!dt.isValid())
+        return 0;
+    return QDateTime(dt.date(), dt.time(), QTimeZone::utc()).toMSecsSinceEpoch();
+}
+
+QFileInfoList tr_files() {
+    return QDir(TickRecorder::directory()).entryInfoList({"ticks_*.ftk"}, QDir::Files, QDir::Name);
+}
+
+} // namespace
+
+// ── Config / Record ─────────────────────────────────────────────────────────
+
+TickRecorder::Config TickRecorder::Config::from_json(const QJsonObject& o) {
+    Config c;
+    c.enabled = o.value("enabled").toBool(c.enabled);
+    if (o.contains("topics")) {
+        c.topics.clear();
+        for (const auto& v : o.value("topics").toArray())
+            if (!v.toString().trimmed().isEmpty())
+                c.topics.append(v.toString().trimmed());
+    }
+    const QString sink = o.value("sink").toString(c.sink).toLower();
+    if (sink == "file" || sink == "sqlite" || sink == "both")
+        c.sink = sink;
+    c.rotate_mb = std::clamp(o.value("rotate_mb").toInt(c.rotate_mb), 1, 4096);
+    c.retention_days = std::clamp(o.value("retention_days").toInt(c.retention_days), 1, 3650);
+    c.flush_ms = std::clamp(o.value("flush_ms").toInt(c.flush_ms), 200, 60000);
+    return c;
+}
+
+QJsonObject TickRecorder::Config::to_json() const {
+    return QJsonObject{{"enabled", enabled},
+                       {"topics", QJsonArray::fromStringList(topics)},
+                       {"sink", sink},
+                       {"rotate_mb", rotate_mb},
+                       {"retention_days", retention_days},
+                       {"flush_ms", flush_ms}};
+}
+
+QJsonObject TickRecorder::Record::to_json() const {
+    QJsonObject o{{"t", recv_ms}, {"topic", topic}, {"kind", kind}, {"sym", symbol}, {"ex", exchange}, {"px", price}};
+    if (source_ms > 0)
+        o["ts"] = source_ms;
+    if (size != 0)
+        o["sz"] = size;
+    if (bid > 0)
+        o["bid"] = bid;
+    if (ask > 0)
+        o["ask"] = ask;
+    if (bid_size > 0)
+        o["bsz"] = bid_size;
+    if (ask_size > 0)
+        o["asz"] = ask_size;
+    if (!side.isEmpty())
+        o["side"] = side;
+    return o;
+}
+
+TickRecorder::Record TickRecorder::Record::from_json(const QJsonObject& o) {
+    Record r;
+    r.recv_ms = qint64(o.value("t").toDouble());
+    r.source_ms = qint64(o.value("ts").toDouble());
+    r.topic = o.value("topic").toString();
+    r.kind = o.value("kind").toString();
+    r.symbol = o.value("sym").toString();
+    r.exchange = o.value("ex").toString();
+    r.price = o.value("px").toDouble();
+    r.size = o.value("sz").toDouble();
+    r.bid = o.value("bid").toDouble();
+    r.ask = o.value("ask").toDouble();
+    r.bid_size = o.value("bsz").toDouble();
+    r.ask_size = o.value("asz").toDouble();
+    r.side = o.value("side").toString();
+    return r;
+}
+
+// ── Lifecycle ───────────────────────────────────────────────────────────────
+
+TickRecorder& TickRecorder::instance() {
+    static TickRecorder s;
+    return s;
+}
+
+QString TickRecorder::directory() {
+    return AppPaths::data() + "/ticks";
+}
+
+void TickRecorder::start() {
+    if (started_)
+        return;
+    started_ = true;
+    connect(qApp, &QCoreApplication::aboutToQuit, this, &TickRecorder::flush);
+    auto r = SettingsRepository::instance().get(kTrConfigKey);
+    if (r.is_ok() && !r.value().isEmpty())
+        config_ = Config::from_json(QJsonDocument::fromJson(r.value().toUtf8()).object());
+    apply();
+}
+
+Result<void> TickRecorder::set_config(const Config& c) {
+    auto r = SettingsRepository::instance().set(
+        kTrConfigKey, QString::fromUtf8(QJsonDocument(c.to_json()).toJson(QJsonDocument::Compact)), "market_data");
+    if (r.is_err())
+        return r;
+    config_ = c;
+    started_ = true;
+    apply();
+    return Result<void>::ok();
+}
+
+void TickRecorder::apply() {
+    patterns_.clear();
+    for (const QString& t : config_.topics)
+        patterns_.append(tr_wildcard(t));
+    topic_match_.clear();
+
+    const bool was_recording = bool(hub_conn_);
+    const bool recording = config_.enabled && !patterns_.isEmpty();
+    if (recording && !was_recording) {
+        hub_conn_ = connect(&datahub::DataHub::instance(), &datahub::DataHub::topic_updated, this,
+                            &TickRecorder::on_topic);
+        if (!flush_timer_) {
+            flush_timer_ = new QTimer(this);
+            connect(flush_timer_, &QTimer::timeout, this, &TickRecorder::flush);
+        }
+        LOG_INFO("TickRecorder", QString("Recording %1 (%2 sink)").arg(config_.topics.join(", "), config_.sink));
+    } else if (!recording && was_recording) {
+        disconnect(hub_conn_);
+        hub_conn_ = {};
+        flush();
+        if (flush_timer_)
+            flush_timer_->stop();
+        if (file_.isOpen())
+            file_.close();
+        LOG_INFO("TickRecorder", QString("Stopped; %1 message(s) recorded this session").arg(records_session_));
+    }
+    if (recording)
+        flush_timer_->start(config_.flush_ms);
+    if (recording != was_recording)
+        emit recording_changed(recording);
+}
+
+bool TickRecorder::matches(const QString& topic) const {
+    for (const auto& re : patterns_)
+        if (re.match(topic).hasMatch())
+            return true;
+    return false;
+}
+
+// ── Capture ─────────────────────────────────────────────────────────────────
+
+void TickRecorder::on_topic(const QString& topic, const QVariant& value) {
+    auto memo = topic_match_.find(topic);
+    if (memo == topic_match_.end())
+        memo = topic_match_.insert(topic, matches(topic));
+    if (!memo.value())
+        return;
+
+    Record r;
+    r.recv_ms = QDateTime::currentMSecsSinceEpoch();
+    r.topic = topic;
+    const QStringList parts = topic.split(':');
+    const int type = value.userType();
+
+    if (type == qMetaTypeId<trading::BrokerQuote>()) {
+        // broker:<id>:<account>:quote:<symbol>, symbol possibly "EXCHANGE:SYMBOL"
+        const auto q = value.value<trading::BrokerQuote>();
+        const QString sym = q.symbol.isEmpty() ? parts.mid(4).join(':') : q.symbol;
+        const int colon = sym.indexOf(':');
+        r.kind = "quote";
+        r.symbol = colon > 0 ? sym.mid(colon + 1) : sym;
+        r.exchange = colon > 0 ? sym.left(colon) : parts.value(1).toUpper();
+        r.price = q.ltp;
+        r.bid = q.bid;
+        r.ask = q.ask;
+        r.bid_size = q.bid_size;
+        r.ask_size = q.ask_size;
+        r.source_ms = q.timestamp;
+    } else if (type == qMetaTypeId<trading::TradeData>()) {
+        const auto t = value.value<trading::TradeData>();
+        r.kind = "trade";
+        r.symbol = t.symbol.isEmpty() ? parts.mid(3).join(':') : t.symbol;
+        r.exchange = parts.value(1);
+        r.price = t.price;
+        r.size = t.amount;
+        r.side = t.side;
+        r.source_ms = t.timestamp;
+    } else if (type == qMetaTypeId<trading::TickerData>()) {
+        const auto t = value.value<trading::TickerData>();
+        r.kind = "ticker";
+        r.symbol = t.symbol.isEmpty() ? parts.mid(3).join(':') : t.symbol;
+        r.exchange = parts.value(1);
+        r.price = t.last;
+        r.bid = t.bid;
+        r.ask = t.ask;
+        r.source_ms = t.timestamp;
+    } else if (type == qMetaTypeId<QuoteData>()) {
+        const auto q = value.value<QuoteData>();
+        r.kind = "quote";
+        r.symbol = q.symbol;
+        r.exchange = "YF";
+        r.price = q.price;
+    } else {
+        ++skipped_unsupported_;
+        return;
+    }
+    if (r.price <= 0 || r.symbol.isEmpty())
+        return;
+    buffer_.append(r);
+    if (buffer_.size() >= kTrMaxBuffered)
+        flush();
+}
+
+// ── Writing ─────────────────────────────────────────────────────────────────
+
+bool TickRecorder::open_file() {
+    QDir().mkpath(directory());
+    const QDateTime now = QDateTime::currentDateTimeUtc();
+    file_.setFileName(directory() + "/ticks_" + now.toString("yyyyMMdd_HHmmss") + ".ftk");
+    if (!file_.open(QIODevice::WriteOnly | QIODevice::Append)) {
+        LOG_WARN("TickRecorder", QString("Cannot open %1: %2").arg(file_.fileName(), file_.errorString()));
+        return false;
+    }
+    file_day_ = now.date();
+    LOG_INFO("TickRecorder", "Writing " + QFileInfo(file_).fileName());
+    return true;
+}
+
+void TickRecorder::rotate_if_due() {
+    if (!file_.isOpen())
+        return;
+    if (file_.size() < config_.rotate_mb * kTrMb && QDateTime::currentDateTimeUtc().date() == file_day_)
+        return;
+    file_.close();
+    prune();
+}
+
+void TickRecorder::prune() {
+    const QDateTime cutoff = QDateTime::currentDateTimeUtc().addDays(-config_.retention_days);
+    int removed = 0;
+    for (const QFileInfo& fi : tr_files()) {
+        if (fi.absoluteFilePath() == QFileInfo(file_).absoluteFilePath() && file_.isOpen())
+            continue;
+        if (fi.lastModified().toUTC() < cutoff && QFile::remove(fi.absoluteFilePath()))
+            ++removed;
+    }
+    if (removed > 0)
+        LOG_INFO("TickRecorder",
+                 QString("Removed %1 recording(s) past %2 days").arg(removed).arg(config_.retention_days));
+}
+
+void TickRecorder::flush() {
+    if (buffer_.isEmpty())
+        return;
+    const QVector<Record> batch = std::move(buffer_);
+    buffer_.clear();
+
+    if (config_.sink != "sqlite") {
+        rotate_if_due();
+        if (file_.isOpen() || open_file()) {
+            QByteArray ndjson;
+            for (const auto& r : batch)
+                ndjson += QJsonDocument(r.to_json()).toJson(QJsonDocument::Compact) + '\n';
+            const QByteArray block = qCompress(ndjson);
+            uchar len[4];
+            qToBigEndian<quint32>(quint32(block.size()), len);
+            file_.write(reinterpret_cast<const char*>(len), 4);
+            file_.write(block);
+            file_.flush();
+            bytes_session_ += 4 + block.size();
+            ++blocks_session_;
+        } else {
+            LOG_WARN("TickRecorder", QString("Dropped %1 message(s): no recording file").arg(batch.size()));
+        }
+    }
+    if (config_.sink != "file")
+        write_sqlite(batch);
+    records_session_ += batch.size();
+    last_flush_ms_ = QDateTime::currentMSecsSinceEpoch();
+}
+
+void TickRecorder::write_sqlite(const QVector<Record>& batch) {
+    QHash<QPair<QString, QString>, QVector<storage::HistoricalDataStore::Tick>> series;
+    for (const auto& r : batch)
+        series[{r.symbol, r.exchange}].append({r.recv_ms, r.price, r.size, r.bid, r.ask, r.bid_size, r.ask_size});
+    auto& store = storage::HistoricalDataStore::instance();
+    for (auto it = series.cbegin(); it != series.cend(); ++it)
+        if (!store.store_ticks(it.key().first, it.key().second, it.value()))
+            LOG_WARN("TickRecorder", QString("market_ticks write failed for %1:%2")
+                                         .arg(it.key().second, it.key().first));
+}
+
+// ── Status / replay ─────────────────────────────────────────────────────────
+
+QJsonObject TickRecorder::status() const {
+    const QString last_flush =
+        last_flush_ms_ > 0 ? QDateTime::fromMSecsSinceEpoch(last_flush_ms_).toUTC().toString(Qt::ISODate) : QString();
+    return QJsonObject{
+        {"recording", bool(hub_conn_)},
+        {"current_file", file_.isOpen() ? QFileInfo(file_).fileName() : QString()},
+        {"records_session", double(records_session_)},
+        {"records_buffered", int(buffer_.size())},
+        {"bytes_session", double(bytes_session_)},
+        {"blocks_session", double(blocks_session_)},
+        {"skipped_unsupported", double(skipped_unsupported_)},
+        {"last_flush", last_flush},
+        {"dir", directory()},
+        {"config", config_.to_json()},
+    };
+}
+
+QJsonArray TickRecorder::files() const {
+    QJsonArray out;
+    for (const QFileInfo& fi : tr_files()) {
+        const qint64 started = tr_file_start_ms(fi.fileName());
+        const QString started_iso =
+            started > 0 ? QDateTime::fromMSecsSinceEpoch(started).toUTC().toString(Qt::ISODate) : QString();
+        out.append(QJsonObject{{"file", fi.fileName()}, {"bytes", double(fi.size())}, {"started", started_iso}});
+    }
+    return out;
+}
+
+QVector<TickRecorder::Record> TickRecorder::read(qint64 from_ms, qint64 to_ms, const QString& topic_pattern,
+                                                 int limit) {
+    const QRegularExpression re = topic_pattern.isEmpty() ? QRegularExpression() : tr_wildcard(topic_pattern);
+    const QFileInfoList files = tr_files();
+    QVector<Record> out;
+    for (int i = 0; i < files.size(); ++i) {
+        // A file covers [its start, the next file's start).
+        const qint64 start = tr_file_start_ms(files[i].fileName());
+        const qint64 next = i + 1 < files.size() ? tr_file_start_ms(files[i + 1].fileName()) : 0;
+        if (to_ms > 0 && start > to_ms)
+            break;
+        if (from_ms > 0 && next > 0 && next < from_ms)
+            continue;
+
+        QFile f(files[i].absoluteFilePath());
+        if (!f.open(QIODevice::ReadOnly))
+            continue;
+        while (true) {
+            const QByteArray len = f.read(4);
+            if (len.size() < 4)
+                break;
+            const quint32 n = qFromBigEndian<quint32>(reinterpret_cast<const uchar*>(len.constData()));
+            const QByteArray block = f.read(n);
+            if (quint32(block.size()) < n)
+                break; // block still being written
+            for (const QByteArray& line : qUncompress(block).split('\n')) {
+                if (line.isEmpty())
+                    continue;
+                Record r = Record::from_json(QJsonDocument::fromJson(line).object());
+                if ((from_ms > 0 && r.recv_ms < from_ms) || (to_ms > 0 && r.recv_ms > to_ms))
+                    continue;
+                if (re.isValid() && !re.pattern().isEmpty() && !re.match(r.topic).hasMatch())
+                    continue;
+                out.append(std::move(r));
+                if (limit > 0 && out.size() >= limit)
+                    return out;
+            }
+        }
+    }
+    return out;
+}
+
+void TickRecorder::import(qint64 from_ms, qint64 to_ms, const QString& symbol,
+                          std::function<void(QJsonObject summary, QString error)> cb) {
+    flush();
+    QPointer<TickRecorder> self = this;
+    const QString sym = symbol.trimmed().toUpper();
+    (void)QtConcurrent::run([self, from_ms, to_ms, sym, cb]() {
+        const auto records = TickRecorder::read(from_ms, to_ms);
+        QHash<QPair<QString, QString>, QVector<storage::HistoricalDataStore::Tick>> series;
+        int used = 0;
+        for (const auto& r : records) {
+            if (!sym.isEmpty() && r.symbol.toUpper() != sym)
+                continue;
+            series[{r.symbol, r.exchange}].append({r.recv_ms, r.price, r.size, r.bid, r.ask, r.bid_size, r.ask_size});
+            ++used;
+        }
+        QJsonArray rows;
+        QString error;
+        auto& store = storage::HistoricalDataStore::instance();
+        for (auto it = series.cbegin(); it != series.cend(); ++it) {
+            if (!store.store_ticks(it.key().first, it.key().second, it.value())) {
+                error = QString("market_ticks write failed for %1:%2").arg(it.key().second, it.key().first);
+                break;
+            }
+            rows.append(QJsonObject{
+                {"symbol", it.key().first}, {"exchange", it.key().second}, {"ticks", int(it.value().size())}});
+        }
+        if (error.isEmpty() && used == 0)
+            error = "No recorded ticks in range";
+        const QJsonObject summary{{"records", used}, {"series", rows}};
+        QMetaObject::invokeMethod(
+            &TickRecorder::instance(),
+            [self, cb, summary, error]() {
+                if (self)
+                    cb(summary, error);
+            },
+            Qt::QueuedConnection);
+    });
+}
+
+} // namespace fincept::services
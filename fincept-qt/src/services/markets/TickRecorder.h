@@ -0,0 +1,142 @@
+// src/services/markets/TickRecorder.h
+#pragma once
+#include "core/result/Result.h"
+
+#include <QDateTime>
+#include <QFile>
+#include <QHash>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+#include <QRegularExpression>
+#include <QString>
+#include <QStringList>
+#include <QTimer>
+#include <QVector>
+
+#include <functional>
+
+namespace fincept::services {
+
+/// Records live market messages to disk for later replay.
+///
+/// Listens to DataHub::topic_updated — it never subscribes, so recording
+/// doesn't keep a topic alive or change any producer's schedule — and keeps
+/// the publishes whose topic matches one of the configured patterns and whose
+/// payload is a tick: BrokerQuote (broker:<id>:<acct>:quote:<sym>), TradeData
+/// and TickerData (ws:<exchange>:trades|ticker:<pair>) and QuoteData
+/// (market:quote:<sym>).
+///
+/// Files live in <data>/ticks as ticks_<yyyyMMdd_HHmmss>.ftk: append-only
+/// blocks of [uint32 big-endian length][qCompress'd NDJSON], one block per
+/// flush, so a crash loses at most the unflushed buffer. A file is rotated at
+/// the UTC day boundary or when it reaches rotate_mb; files older than
+/// retention_days are deleted on rotation. The "sqlite" / "both" sinks also
+/// write each flush into the market_ticks table the tick backtester reads;
+/// import() does the same for a recorded range after the fact. Main thread
+/// only (import() reads on a worker).
+class TickRecorder : public QObject {
+    Q_OBJECT
+  public:
+    struct Config {
+        bool enabled = false;
+        // DataHub topic patterns; `*` matches anything, including ':'.
+        QStringList topics = {"broker:*:quote:*", "ws:*:trades:*", "ws:*:ticker:*"};
+        QString sink = QStringLiteral("file"); // file | sqlite | both
+        int rotate_mb = 64;
+        int retention_days = 30;
+        int flush_ms = 2000;
+
+        /// Defaults overridden by whichever keys `o` carries.
+        static Config from_json(const QJsonObject& o);
+        QJsonObject to_json() const;
+    };
+
+    /// One recorded message. `size` is the trade size for trade prints and 0
+    /// for quotes; unset prices are 0.
+    struct Record {
+        qint64 recv_ms = 0;   // when the hub published it
+        qint64 source_ms = 0; // the source's own timestamp, 0 when absent
+        QString topic;
+        QString kind; // quote | trade | ticker
+        QString symbol;
+        QString exchange;
+        double price = 0;
+        double size = 0;
+        double bid = 0;
+        double ask = 0;
+        double bid_size = 0;
+        double ask_size = 0;
+        QString side; // trades: buy | sell
+
+        QJsonObject to_json() const;
+        static Record from_json(const QJsonObject& o);
+    };
+
+    static TickRecorder& instance();
+
+    /// Loads the persisted config (settings key tick_recorder.config) and
+    /// starts recording when it is enabled. Idempotent.
+    void start();
+
+    Config config() const { return config_; }
+    /// Persists `c` and starts / stops / re-filters recording to match.
+    Result<void> set_config(const Config& c);
+
+    /// {recording, current_file, records_session, records_buffered,
+    ///  bytes_session, blocks_session, skipped_unsupported, last_flush, dir, config}
+    QJsonObject status() const;
+
+    /// Recording files oldest first: [{file, bytes, started}].
+    QJsonArray files() const;
+
+    /// Writes the buffer out now (and rotates if due).
+    void flush();
+
+    /// Reads recorded messages with recv_ms in [from_ms, to_ms] (0 = open)
+    /// whose topic matches `topic_pattern` (empty = all), oldest first, up to
+    /// `limit` (0 = no cap). Safe on any thread; the file being written is
+    /// read up to its last complete block.
+    static QVector<Record> read(qint64 from_ms, qint64 to_ms, const QString& topic_pattern = {}, int limit = 0);
+
+    /// Copies recorded prints in the range into the market_ticks table, one
+    /// series per (symbol, exchange), on a worker thread. `symbol` empty =
+    /// every symbol. `cb(summary, error)` runs on the main thread with
+    /// summary {records, series[{symbol, exchange, ticks}]}.
+    void import(qint64 from_ms, qint64 to_ms, const QString& symbol,
+                std::function<void(QJsonObject summary, QString error)> cb);
+
+    static QString directory();
+
+  signals:
+    void recording_changed(bool recording);
+
+  private:
+    TickRecorder() = default;
+    Q_DISABLE_COPY(TickRecorder)
+
+    void apply();
+    void on_topic(const QString& topic, const QVariant& value);
+    bool matches(const QString& topic) const;
+    bool open_file();
+    void rotate_if_due();
+    void prune();
+    void write_sqlite(const QVector<Record>& batch);
+
+    Config config_;
+    bool started_ = false;
+    QVector<QRegularExpression> patterns_;
+    QMetaObject::Connection hub_conn_;
+    QTimer* flush_timer_ = nullptr;
+    QFile file_;
+    QDate file_day_;
+    QVector<Record> buffer_;
+    QHash<QString, bool> topic_match_; // memo: topic → matches a pattern
+    qint64 records_session_ = 0;
+    qint64 bytes_session_ = 0;
+    qint64 blocks_session_ = 0;
+    qint64 skipped_unsupported_ = 0;
+    qint64 last_flush_ms_ = 0;
+};
+
+} // namespace fincept::services
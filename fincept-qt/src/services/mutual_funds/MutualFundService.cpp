@@ -0,0 +1,461 @@
+#include "services/mutual_funds/MutualFundService.h"
+
+#include "core/logging/Logger.h"
+#include "python/PythonRunner.h"
+#include "storage/cache/CacheManager.h"
+
+#include <QJsonDocument>
+#include <QJsonParseError>
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+#include <memory>
+
+namespace fincept::services::mutual_funds {
+
+namespace {
+
+constexpr const char* kScript = "mutual_fund_data.py";
+constexpr const char* kCacheCategory = "mutual_funds";
+constexpr int kNavAllTtlSec = 6 * 60 * 60;      // AMFI publishes once per business day
+constexpr int kHistoryTtlSec = 12 * 60 * 60;    // one new point per day at most
+constexpr int kSearchTtlSec = 24 * 60 * 60;     // scheme registry barely changes
+constexpr int kProfileTtlSec = 24 * 60 * 60;
+constexpr double kDaysPerYear = 365.25;
+constexpr int kSeriesMaxPoints = 250;
+
+double round_to(double v, int decimals) {
+    const double f = std::pow(10.0, decimals);
+    return std::round(v * f) / f;
+}
+
+QJsonValue num_or_null(double v, int decimals = 2) {
+    return std::isfinite(v) ? QJsonValue(round_to(v, decimals)) : QJsonValue(QJsonValue::Null);
+}
+
+/// Index of the last NAV on or before `d`, or -1.
+int index_on_or_before(const QVector<NavPoint>& navs, const QDate& d) {
+    auto it = std::upper_bound(navs.cbegin(), navs.cend(), d,
+                               [](const QDate& date, const NavPoint& p) { return date < p.date; });
+    return static_cast<int>(it - navs.cbegin()) - 1;
+}
+
+/// Index of the first NAV on or after `d`, or navs.size().
+int index_on_or_after(const QVector<NavPoint>& navs, const QDate& d) {
+    auto it = std::lower_bound(navs.cbegin(), navs.cend(), d,
+                               [](const NavPoint& p, const QDate& date) { return p.date < date; });
+    return static_cast<int>(it - navs.cbegin());
+}
+
+double cagr(double start_nav, double end_nav, qint64 days) {
+    if (start_nav <= 0 || end_nav <= 0 || days <= 0)
+        return std::numeric_limits<double>::quiet_NaN();
+    return std::pow(end_nav / start_nav, kDaysPerYear / static_cast<double>(days)) - 1.0;
+}
+
+/// Annualised XIRR by bisection on NPV(rate) = 0. Returns NaN if the cash
+/// flows do not bracket a root in (-99%, +1000%).
+double xirr(const QVector<QPair<QDate, double>>& flows) {
+    if (flows.size() < 2)
+        return std::numeric_limits<double>::quiet_NaN();
+    const QDate t0 = flows.first().first;
+    auto npv = [&](double rate) {
+        double s = 0.0;
+        for (const auto& [d, cf] : flows)
+            s += cf / std::pow(1.0 + rate, t0.daysTo(d) / kDaysPerYear);
+        return s;
+    };
+    double lo = -0.99, hi = 10.0;
+    double f_lo = npv(lo), f_hi = npv(hi);
+    if (!std::isfinite(f_lo) || !std::isfinite(f_hi) || f_lo * f_hi > 0)
+        return std::numeric_limits<double>::quiet_NaN();
+    for (int i = 0; i < 200 && hi - lo > 1e-9; ++i) {
+        const double mid = 0.5 * (lo + hi);
+        const double f_mid = npv(mid);
+        if (f_lo * f_mid <= 0) {
+            hi = mid;
+        } else {
+            lo = mid;
+            f_lo = f_mid;
+        }
+    }
+    return 0.5 * (lo + hi);
+}
+
+} // namespace
+
+MutualFundService& MutualFundService::instance() {
+    static MutualFundService s;
+    return s;
+}
+
+// ── Data ─────────────────────────────────────────────────────────────────────
+
+void MutualFundService::run_script(const QStringList& args, const QString& cache_key, int ttl_sec,
+                                   MutualFundCallback cb) {
+    if (auto cached = fincept::CacheManager::instance().try_get(cache_key)) {
+        const auto doc = QJsonDocument::fromJson(cached->toUtf8());
+        if (doc.isObject()) {
+            if (cb)
+                cb(MutualFundResult{true, doc.object(), {}});
+            return;
+        }
+    }
+
+    fincept::python::PythonRunner::instance().run(
+        kScript, args, [cb = std::move(cb), args, cache_key, ttl_sec](const fincept::python::PythonResult& result) {
+            MutualFundResult out;
+            if (!result.success) {
+                out.error = result.error.isEmpty() ? QStringLiteral("Mutual fund query failed") : result.error;
+                LOG_ERROR("MutualFundService",
+                          QStringLiteral("%1 failed: %2").arg(args.join(' '), out.error.left(300)));
+                if (cb)
+                    cb(out);
+                return;
+            }
+
+            const QString json_str = fincept::python::extract_json(result.output);
+            QJsonParseError err;
+            const auto doc = QJsonDocument::fromJson(json_str.toUtf8(), &err);
+            if (json_str.isEmpty() || !doc.isObject()) {
+                out.error = json_str.isEmpty() ? QStringLiteral("Empty response")
+                                               : QStringLiteral("Invalid JSON: %1").arg(err.errorString());
+                if (cb)
+                    cb(out);
+                return;
+            }
+
+            const QJsonObject obj = doc.object();
+            if (obj.contains("error")) {
+                out.error = obj["error"].toString();
+                if (cb)
+                    cb(out);
+                return;
+            }
+
+            fincept::CacheManager::instance().put(cache_key, QVariant(json_str), ttl_sec, kCacheCategory);
+            out.success = true;
+            out.data = obj;
+            if (cb)
+                cb(out);
+        });
+}
+
+void MutualFundService::fetch_amfi_nav_all(const QString& category_filter, MutualFundCallback cb) {
+    const QString filter = category_filter.trimmed();
+    run_script({QStringLiteral("amfi_nav_all"), filter}, QStringLiteral("mf:amfi_nav_all:%1").arg(filter.toLower()),
+               kNavAllTtlSec, std::move(cb));
+}
+
+void MutualFundService::fetch_nav_history(const QString& id, const QString& region, MutualFundCallback cb) {
+    const QString r = region.trimmed().toUpper() == "US" ? QStringLiteral("US") : QStringLiteral("IN");
+    const QString key = r == "US" ? id.trimmed().toUpper() : id.trimmed();
+    run_script({QStringLiteral("nav_history"), key, r}, QStringLiteral("mf:nav:%1:%2").arg(r, key), kHistoryTtlSec,
+               std::move(cb));
+}
+
+void MutualFundService::fetch_nav_histories(const QStringList& ids, const QString& region, MutualFundCallback cb) {
+    struct State {
+        int pending = 0;
+        QVector<QJsonObject> funds;
+        QJsonArray errors;
+        MutualFundCallback cb;
+    };
+    auto state = std::make_shared<State>();
+    state->pending = ids.size();
+    state->funds.resize(ids.size());
+    state->cb = std::move(cb);
+
+    if (ids.isEmpty()) {
+        if (state->cb)
+            state->cb(MutualFundResult{false, {}, QStringLiteral("No funds given")});
+        return;
+    }
+
+    for (int i = 0; i < ids.size(); ++i) {
+        const QString id = ids[i];
+        fetch_nav_history(id, region, [state, i, id](const MutualFundResult& r) {
+            if (r.success)
+                state->funds[i] = r.data;
+            else
+                state->errors.append(QJsonObject{{"id", id}, {"error", r.error}});
+            if (--state->pending > 0)
+                return;
+
+            QJsonArray funds;
+            for (const auto& f : state->funds)
+                if (!f.isEmpty())
+                    funds.append(f);
+            MutualFundResult out;
+            out.success = !funds.isEmpty();
+            out.data = QJsonObject{{"funds", funds}, {"errors", state->errors}};
+            if (!out.success)
+                out.error = QStringLiteral("No NAV history could be loaded");
+            if (state->cb)
+                state->cb(out);
+        });
+    }
+}
+
+void MutualFundService::search(const QString& query, MutualFundCallback cb) {
+    const QString q = query.trimmed();
+    run_script({QStringLiteral("search"), q}, QStringLiteral("mf:search:%1").arg(q.toLower()), kSearchTtlSec,
+               std::move(cb));
+}
+
+void MutualFundService::fetch_us_profile(const QString& ticker, MutualFundCallback cb) {
+    const QString t = ticker.trimmed().toUpper();
+    run_script({QStringLiteral("us_profile"), t}, QStringLiteral("mf:us_profile:%1").arg(t), kProfileTtlSec,
+               std::move(cb));
+}
+
+// ── Calculators ──────────────────────────────────────────────────────────────
+
+QVector<NavPoint> MutualFundService::parse_nav_points(const QJsonObject& history) {
+    QVector<NavPoint> out;
+    const QJsonArray arr = history.value("data").toArray();
+    out.reserve(arr.size());
+    for (const auto& v : arr) {
+        const QJsonObject o = v.toObject();
+        NavPoint p;
+        p.date = QDate::fromString(o.value("date").toString(), Qt::ISODate);
+        p.nav = o.value("nav").toDouble();
+        if (p.date.isValid() && p.nav > 0)
+            out.append(p);
+    }
+    std::sort(out.begin(), out.end(), [](const NavPoint& a, const NavPoint& b) { return a.date < b.date; });
+    return out;
+}
+
+double MutualFundService::trailing_cagr(const QVector<NavPoint>& navs, double years) {
+    if (navs.size() < 2 || years <= 0)
+        return std::numeric_limits<double>::quiet_NaN();
+    const NavPoint& last = navs.last();
+    const QDate target = last.date.addDays(-static_cast<qint64>(std::llround(years * kDaysPerYear)));
+    const int j = index_on_or_before(navs, target);
+    if (j < 0)
+        return std::numeric_limits<double>::quiet_NaN();
+    return cagr(navs[j].nav, last.nav, navs[j].date.daysTo(last.date));
+}
+
+QJsonObject MutualFundService::rolling_returns(const QVector<NavPoint>& navs, double window_years) {
+    QJsonObject out{{"window_years", window_years}};
+    if (navs.size() < 2 || window_years <= 0) {
+        out["observations"] = 0;
+        return out;
+    }
+
+    const qint64 window_days = std::llround(window_years * kDaysPerYear);
+    QVector<QPair<QDate, double>> series;
+    for (const auto& p : navs) {
+        const int j = index_on_or_before(navs, p.date.addDays(-window_days));
+        if (j < 0)
+            continue;
+        const double r = cagr(navs[j].nav, p.nav, navs[j].date.daysTo(p.date));
+        if (std::isfinite(r))
+            series.append({p.date, r * 100.0});
+    }
+    out["observations"] = series.size();
+    if (series.isEmpty())
+        return out;
+
+    QVector<double> vals;
+    vals.reserve(series.size());
+    for (const auto& s : series)
+        vals.append(s.second);
+    double mean = 0.0;
+    for (double v : vals)
+        mean += v;
+    mean /= vals.size();
+    double var = 0.0;
+    for (double v : vals)
+        var += (v - mean) * (v - mean);
+    const auto count_if = [&vals](double threshold) {
+        return static_cast<double>(std::count_if(vals.cbegin(), vals.cend(), [threshold](double v) {
+                   return v > threshold;
+               })) /
+               vals.size() * 100.0;
+    };
+
+    QVector<double> sorted = vals;
+    std::sort(sorted.begin(), sorted.end());
+    const int m = sorted.size();
+    const double median = m % 2 ? sorted[m / 2] : 0.5 * (sorted[m / 2 - 1] + sorted[m / 2]);
+
+    out["mean"] = round_to(mean, 2);
+    out["median"] = round_to(median, 2);
+    out["min"] = round_to(sorted.first(), 2);
+    out["max"] = round_to(sorted.last(), 2);
+    out["stddev"] = round_to(std::sqrt(var / vals.size()), 2);
+    out["pct_positive"] = round_to(count_if(0.0), 1);
+    out["pct_above_8"] = round_to(count_if(8.0), 1);
+    out["pct_above_12"] = round_to(count_if(12.0), 1);
+
+    QJsonArray pts;
+    const int step = series.size() > kSeriesMaxPoints ? series.size() / kSeriesMaxPoints : 1;
+    for (int i = 0; i < series.size(); i += step)
+        pts.append(
+            QJsonObject{{"date", series[i].first.toString(Qt::ISODate)}, {"cagr", round_to(series[i].second, 2)}});
+    out["series"] = pts;
+    return out;
+}
+
+QJsonObject MutualFundService::sip_returns(const QVector<NavPoint>& navs, double amount, const QDate& start,
+                                           const QDate& end, int day_of_month) {
+    if (navs.isEmpty() || amount <= 0)
+        return QJsonObject{{"error", QStringLiteral("No NAV history or non-positive SIP amount")}};
+
+    const QDate first = start.isValid() ? std::max(start, navs.first().date) : navs.first().date;
+    const QDate last = end.isValid() ? std::min(end, navs.last().date) : navs.last().date;
+    if (first > last)
+        return QJsonObject{{"error", QStringLiteral("SIP window is outside the NAV history")}};
+
+    const int dom = std::clamp(day_of_month, 1, 28);
+    QDate due(first.year(), first.month(), dom);
+    if (due < first)
+        due = due.addMonths(1);
+
+    double units = 0.0;
+    double invested = 0.0;
+    QVector<QPair<QDate, double>> flows;
+    QJsonArray schedule;
+    for (; due <= last; due = due.addMonths(1)) {
+        const int k = index_on_or_after(navs, due);
+        if (k >= navs.size() || navs[k].date > last)
+            break;
+        const double bought = amount / navs[k].nav;
+        units += bought;
+        invested += amount;
+        flows.append({navs[k].date, -amount});
+        schedule.append(QJsonObject{{"date", navs[k].date.toString(Qt::ISODate)},
+                                    {"nav", navs[k].nav},
+                                    {"units", round_to(bought, 4)},
+                                    {"total_units", round_to(units, 4)}});
+    }
+    if (flows.isEmpty())
+        return QJsonObject{{"error", QStringLiteral("No SIP installment falls inside the NAV history")}};
+
+    const int v = index_on_or_before(navs, last);
+    const NavPoint& valuation = navs[v];
+    const double value = units * valuation.nav;
+    flows.append({valuation.date, value});
+
+    return QJsonObject{
+        {"installments", schedule.size()},
+        {"amount", amount},
+        {"invested", round_to(invested, 2)},
+        {"units", round_to(units, 4)},
+        {"valuation_date", valuation.date.toString(Qt::ISODate)},
+        {"valuation_nav", valuation.nav},
+        {"current_value", round_to(value, 2)},
+        {"gain", round_to(value - invested, 2)},
+        {"absolute_return", round_to((value / invested - 1.0) * 100.0, 2)},
+        {"xirr", num_or_null(xirr(flows) * 100.0)},
+        {"schedule", schedule},
+    };
+}
+
+QJsonObject MutualFundService::compare_funds(const QJsonArray& histories) {
+    struct Row {
+        QJsonObject meta;
+        double r1 = 0, r3 = 0, r5 = 0, vol = 0, max_dd = 0, consistency = 0;
+    };
+    QVector<Row> rows;
+    for (const auto& hv : histories) {
+        const QJsonObject h = hv.toObject();
+        const auto navs = parse_nav_points(h);
+        if (navs.size() < 2)
+            continue;
+
+        Row row;
+        row.meta = QJsonObject{{"id", h.value("id")},
+                               {"name", h.value("name")},
+                               {"fund_house", h.value("fund_house")},
+                               {"category", h.value("category")},
+                               {"currency", h.value("currency")}};
+        row.r1 = trailing_cagr(navs, 1.0) * 100.0;
+        row.r3 = trailing_cagr(navs, 3.0) * 100.0;
+        row.r5 = trailing_cagr(navs, 5.0) * 100.0;
+
+        // Volatility and drawdown over the trailing 3 years (or full history).
+        const int from = std::max(0, index_on_or_before(navs, navs.last().date.addYears(-3)));
+        double sum = 0.0, sum_sq = 0.0, peak = navs[from].nav, dd = 0.0;
+        int n = 0;
+        for (int i = from + 1; i < navs.size(); ++i) {
+            const double lr = std::log(navs[i].nav / navs[i - 1].nav);
+            sum += lr;
+            sum_sq += lr * lr;
+            ++n;
+            peak = std::max(peak, navs[i].nav);
+            dd = std::max(dd, (peak - navs[i].nav) / peak);
+        }
+        const double mean = n > 0 ? sum / n : 0.0;
+        row.vol = n > 1 ? std::sqrt(std::max(0.0, sum_sq / n - mean * mean)) * std::sqrt(252.0) * 100.0
+                        : std::numeric_limits<double>::quiet_NaN();
+        row.max_dd = dd * 100.0;
+
+        const QJsonObject roll = rolling_returns(navs, 3.0);
+        row.consistency = roll.value("observations").toInt() > 0 ? roll.value("pct_positive").toDouble()
+                                                                  : std::numeric_limits<double>::quiet_NaN();
+        rows.append(row);
+    }
+
+    // Rank 1 = best. Funds with a missing metric are left unranked for it.
+    struct Metric {
+        const char* key;
+        double Row::*field;
+        bool higher_is_better;
+    };
+    const Metric metrics[] = {
+        {"return_1y", &Row::r1, true},
+        {"return_3y", &Row::r3, true},
+        {"return_5y", &Row::r5, true},
+        {"volatility_3y", &Row::vol, false},
+        {"max_drawdown_3y", &Row::max_dd, false},
+        {"rolling_3y_pct_positive", &Row::consistency, true},
+    };
+
+    QVector<QJsonObject> out_rows(rows.size());
+    QVector<double> rank_sum(rows.size(), 0.0);
+    QVector<int> rank_n(rows.size(), 0);
+    QJsonObject averages;
+    for (const auto& m : metrics) {
+        QVector<int> order;
+        double total = 0.0;
+        for (int i = 0; i < rows.size(); ++i) {
+            const double v = rows[i].*(m.field);
+            out_rows[i][m.key] = num_or_null(v);
+            if (std::isfinite(v)) {
+                order.append(i);
+                total += v;
+            }
+        }
+        std::sort(order.begin(), order.end(), [&](int a, int b) {
+            return m.higher_is_better ? rows[a].*(m.field) > rows[b].*(m.field)
+                                      : rows[a].*(m.field) < rows[b].*(m.field);
+        });
+        QJsonObject ranks;
+        for (int r = 0; r < order.size(); ++r) {
+            QJsonObject rk = out_rows[order[r]].value("ranks").toObject();
+            rk[m.key] = r + 1;
+            out_rows[order[r]]["ranks"] = rk;
+            rank_sum[order[r]] += r + 1;
+            ++rank_n[order[r]];
+        }
+        averages[m.key] = order.isEmpty() ? QJsonValue(QJsonValue::Null) : num_or_null(total / order.size());
+    }
+
+    QJsonArray funds;
+    for (int i = 0; i < rows.size(); ++i) {
+        QJsonObject f = rows[i].meta;
+        for (auto it = out_rows[i].constBegin(); it != out_rows[i].constEnd(); ++it)
+            f[it.key()] = it.value();
+        f["overall_rank_score"] = rank_n[i] > 0 ? QJsonValue(round_to(rank_sum[i] / rank_n[i], 2))
+                                                : QJsonValue(QJsonValue::Null);
+        funds.append(f);
+    }
+    return QJsonObject{{"count", funds.size()}, {"funds", funds}, {"peer_average", averages}};
+}
+
+} // namespace fincept::services::mutual_funds
@@ -0,0 +1,102 @@
+#pragma once
+// MutualFundService — NAV data for Indian (AMFI) and US mutual funds plus the
+// return maths investors actually ask about: rolling returns, SIP (monthly
+// investment) returns, and side-by-side category comparison.
+//
+// Data comes from scripts/mutual_fund_data.py:
+//   - India: AMFI's daily NAVAll.txt (every scheme with its SEBI category) and
+//     per-scheme NAV history via api.mfapi.in.
+//   - US: NAV history and fund profile via yfinance.
+// NAV history is cached through CacheManager (category "mutual_funds") so the
+// calculators can be re-run with different parameters without re-downloading.
+//
+// The calculators are static and pure — callers that already hold NAV history
+// (e.g. MCP tools, a screen) can call them directly.
+
+#include <QDate>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+#include <functional>
+
+namespace fincept::services::mutual_funds {
+
+struct NavPoint {
+    QDate date;
+    double nav = 0.0;
+};
+
+/// Result of any mutual fund data call. `data` is the script JSON (for NAV
+/// history: {id, name, fund_house, category, currency, region, data:[{date, nav}]}).
+struct MutualFundResult {
+    bool success = false;
+    QJsonObject data;
+    QString error;
+};
+
+using MutualFundCallback = std::function<void(const MutualFundResult&)>;
+
+/// All calls are async; callback fires on the main thread. Callers should
+/// capture a QPointer to themselves (P8).
+class MutualFundService : public QObject {
+    Q_OBJECT
+  public:
+    static MutualFundService& instance();
+
+    /// Today's NAV for every AMFI scheme, optionally filtered by a substring of
+    /// the SEBI category (e.g. "Large Cap"). Cached until the next AMFI publish.
+    void fetch_amfi_nav_all(const QString& category_filter, MutualFundCallback cb);
+
+    /// Full NAV history. `region` is "IN" (id = AMFI scheme code) or "US"
+    /// (id = fund ticker, e.g. VFIAX).
+    void fetch_nav_history(const QString& id, const QString& region, MutualFundCallback cb);
+
+    /// Fetches several histories concurrently; `data` is {funds:[history...],
+    /// errors:[{id, error}]}. Succeeds if at least one fund loaded.
+    void fetch_nav_histories(const QStringList& ids, const QString& region, MutualFundCallback cb);
+
+    /// Scheme search by name (India / AMFI registry).
+    void search(const QString& query, MutualFundCallback cb);
+
+    /// US fund profile: category, family, expense ratio, assets, rating.
+    void fetch_us_profile(const QString& ticker, MutualFundCallback cb);
+
+    // ── Calculators ──────────────────────────────────────────────────────────
+
+    /// Parses the `data` array of a NAV history payload, sorted by date.
+    static QVector<NavPoint> parse_nav_points(const QJsonObject& history);
+
+    /// Annualised returns over every `window_years` holding period ending on
+    /// each NAV date. Returns {window_years, observations, mean, median, min,
+    /// max, stddev, pct_positive, pct_above_8, pct_above_12, series:[{date, cagr}]}
+    /// — percentages, series downsampled to ~250 points.
+    static QJsonObject rolling_returns(const QVector<NavPoint>& navs, double window_years);
+
+    /// Monthly SIP of `amount` on `day_of_month` (or the next NAV date) from
+    /// `start` to `end`. Returns {installments, invested, units, current_value,
+    /// gain, absolute_return, xirr, schedule:[...]} with xirr in percent.
+    static QJsonObject sip_returns(const QVector<NavPoint>& navs, double amount, const QDate& start, const QDate& end,
+                                   int day_of_month = 1);
+
+    /// Trailing point-to-point CAGR for `years` (1y is plain return). NaN when
+    /// the history is too short.
+    static double trailing_cagr(const QVector<NavPoint>& navs, double years);
+
+    /// Compares funds (NAV history payloads) on trailing 1/3/5y CAGR, 3y
+    /// annualised volatility, max drawdown and 3y rolling-return consistency.
+    /// Each fund gets a rank per metric and an overall average rank; the
+    /// result also carries peer-group averages.
+    static QJsonObject compare_funds(const QJsonArray& histories);
+
+  private:
+    MutualFundService() = default;
+    Q_DISABLE_COPY(MutualFundService)
+
+    void run_script(const QStringList& args, const QString& cache_key, int ttl_sec, MutualFundCallback cb);
+};
+
+} // namespace fincept::services::mutual_funds
@@ -0,0 +1,615 @@
+// src/services/nl_query/NlQueryService.cpp
+#include "services/nl_query/NlQueryService.h"
+
+#include "core/logging/Logger.h"
+#include "python/PythonRunner.h"
+#include "services/llm/LlmService.h"
+
+#include <QCoreApplication>
+#include <QDateTime>
+#include <QHash>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QMap>
+#include <QPointer>
+#include <QRegularExpression>
+#include <QUuid>
+#include <QVector>
+#include <QtConcurrent>
+
+#include <algorithm>
+#include <memory>
+
+namespace fincept::services {
+
+namespace {
+
+constexpr const char* kNqTag = "NlQuery";
+constexpr auto kNqCi = QRegularExpression::CaseInsensitiveOption;
+
+struct NqConcept {
+    const char* pattern;   ///< regex, matched case-insensitively on word boundaries
+    const char* series_id; ///< FRED series
+    const char* label;
+    const char* transform = ""; ///< default units when the question names none
+};
+
+// Macro vocabulary → FRED. Overlaps resolve to the longest match, so
+// "core cpi" beats "cpi" and "inflation expectations" beats "inflation".
+const QVector<NqConcept> kNqConcepts = {
+    {R"(core\s+cpi)", "CPILFESL", "Core CPI"},
+    {R"(core\s+inflation)", "CPILFESL", "Core CPI inflation", "pc1"},
+    {R"(core\s+pce)", "PCEPILFE", "Core PCE"},
+    {R"(pce(?:\s+(?:price\s+index|prices|deflator))?)", "PCEPI", "PCE price index"},
+    {R"(cpi|consumer\s+price(?:s|\s+index)?)", "CPIAUCSL", "CPI"},
+    {R"(inflation)", "CPIAUCSL", "CPI inflation", "pc1"},
+    {R"(ppi|producer\s+price(?:s|\s+index)?)", "PPIACO", "PPI"},
+    {R"(fed(?:eral)?\s+funds?(?:\s+rate)?|fed\s+rate|policy\s+rate|ffr)", "FEDFUNDS", "Fed funds rate"},
+    {R"(unemployment(?:\s+rate)?|jobless\s+rate)", "UNRATE", "Unemployment rate"},
+    {R"((?:non[\s-]?farm\s+)?payrolls?|nfp|jobs)", "PAYEMS", "Nonfarm payrolls"},
+    {R"((?:initial\s+)?jobless\s+claims|initial\s+claims)", "ICSA", "Initial jobless claims"},
+    {R"((?:real\s+)?gdp)", "GDPC1", "Real GDP"},
+    {R"(nominal\s+gdp)", "GDP", "Nominal GDP"},
+    {R"((?:3|three)[\s-]?(?:month|mo|m)\s+(?:t[\s-]?bill|bill|treasury|yield)|t[\s-]?bills?)", "DGS3MO",
+     "3M Treasury"},
+    {R"((?:2|two)[\s-]?(?:year|yr|y)(?:\s+(?:treasury|yield|note))?)", "DGS2", "2Y Treasury"},
+    {R"((?:10|ten)[\s-]?(?:year|yr|y)(?:\s+(?:treasury|yield|note|bond))?|10s)", "DGS10", "10Y Treasury"},
+    {R"((?:30|thirty)[\s-]?(?:year|yr|y)\s+(?:treasury|yield|bond))", "DGS30", "30Y Treasury"},
+    {R"((?:10y?[\s-]2y?|2s10s|10s2s)\s+spread|yield\s+curve(?:\s+spread)?)", "T10Y2Y", "10Y-2Y spread"},
+    {R"(breakevens?|inflation\s+expectations?)", "T10YIE", "10Y breakeven inflation"},
+    {R"((?:30[\s-]?(?:year|yr)\s+)?mortgage(?:\s+rates?)?)", "MORTGAGE30US", "30Y mortgage rate"},
+    {R"(high[\s-]yield\s+spreads?|junk\s+spreads?|hy\s+spreads?)", "BAMLH0A0HYM2", "High-yield OAS"},
+    {R"(m2|money\s+supply)", "M2SL", "M2 money supply"},
+    {R"(industrial\s+production)", "INDPRO", "Industrial production"},
+    {R"(retail\s+sales)", "RSAFS", "Retail sales"},
+    {R"(housing\s+starts)", "HOUST", "Housing starts"},
+    {R"(home\s+prices?|house\s+prices?|case[\s-]?shiller)", "CSUSHPINSA", "Case-Shiller home prices"},
+    {R"((?:consumer|michigan)\s+sentiment|umich)", "UMCSENT", "Consumer sentiment"},
+    {R"((?:personal\s+)?savings?\s+rate)", "PSAVERT", "Personal savings rate"},
+    {R"(vix|volatility\s+index)", "VIXCLS", "VIX"},
+    {R"(wti|crude(?:\s+oil)?|oil(?:\s+prices?)?)", "DCOILWTICO", "WTI crude"},
+    {R"(brent(?:\s+crude)?)", "DCOILBRENTEU", "Brent crude"},
+    {R"((?:trade[\s-]weighted\s+)?dollar(?:\s+index)?|dxy)", "DTWEXBGS", "Trade-weighted dollar"},
+    {R"(s\s*&\s*p(?:\s*500)?|sp500|spx)", "SP500", "S&P 500"},
+    {R"(nasdaq(?:\s+composite)?)", "NASDAQCOM", "Nasdaq Composite"},
+    {R"(dow(?:\s+jones)?|djia)", "DJIA", "Dow Jones"},
+};
+
+// Transform words → FRED `units`. Checked in order; the first hit wins.
+const QVector<QPair<QString, QString>> kNqTransforms = {
+    {R"(\b(?:yoy|y/y|y-o-y|year[\s-]over[\s-]year|annual\s+(?:rate|change|growth)|yearly\s+change)\b|)"
+     R"(\b12[\s-]month\s+change\b|\bgrowth(?:\s+rate)?\b)",
+     "pc1"},
+    {R"(\b(?:mom|m/m|month[\s-]over[\s-]month|qoq|q/q|quarter[\s-]over[\s-]quarter|percent\s+change)\b|%\s*change)",
+     "pch"},
+    {R"(\b(?:change|diff(?:erence)?|delta)\b)", "chg"},
+    {R"(\blog\b)", "log"},
+};
+
+// Countries the local vocabulary has no series for — "UK CPI" must not come
+// back as US CPI, so such a part is left to the LLM (or reported).
+const QRegularExpression kNqForeign(
+    R"(\b(?:uk|britain|british|england|euro(?:zone|pe|\s+area)?|ecb|germany|german|france|french|italy|italian|)"
+    R"(spain|japan|japanese|boj|china|chinese|india|indian|canada|canadian|australia|australian|brazil|mexico|)"
+    R"(korea|korean|switzerland|swiss)\b)",
+    kNqCi);
+
+// Words a question wraps around its data ("show me", "chart the ...").
+const QRegularExpression kNqFiller(
+    R"(\b(?:us|u\.s\.?|usa|united\s+states|american|show|me|chart|plot|graph|compare|get|give|what|is|was|the|)"
+    R"(of|for|over|in|on|a|an|rate|rates|index|level|levels|price|prices|data|series|history|historical|trend|)"
+    R"(please|how|has|have|did|does|moved|move|vs)\b|[?.!$%()])",
+    kNqCi);
+
+const QStringList kNqNotTickers = {"US", "USA", "UK", "EU", "YOY", "MOM", "QOQ", "YTD", "VS", "AND", "THE",
+                                   "I", "A", "OF", "FOR", "TO", "IN", "GDP", "CPI", "PCE", "PPI", "ECB", "BOJ"};
+
+int nq_number(const QString& word) {
+    static const QHash<QString, int> words = {
+        {"one", 1}, {"two", 2}, {"three", 3}, {"four", 4}, {"five", 5}, {"six", 6}, {"seven", 7},
+        {"eight", 8}, {"nine", 9}, {"ten", 10}, {"fifteen", 15}, {"twenty", 20}, {"thirty", 30}};
+    bool ok = false;
+    const int n = word.toInt(&ok);
+    return ok ? n : words.value(word.toLower(), 0);
+}
+
+QDate nq_back(const QDate& today, int n, const QString& unit) {
+    const QString u = unit.toLower();
+    if (u.startsWith("dec"))
+        return today.addYears(-10 * n);
+    if (u.startsWith('y'))
+        return today.addYears(-n);
+    if (u.startsWith('q'))
+        return today.addMonths(-3 * n);
+    if (u.startsWith('m'))
+        return today.addMonths(-n);
+    if (u.startsWith('w'))
+        return today.addDays(-7 * n);
+    return today.addDays(-n);
+}
+
+void nq_blank(QString& text, qsizetype start, qsizetype length) {
+    text.replace(start, length, QString(length, ' '));
+}
+
+// Extracts the time range from `text` (and blanks it, so "last 10 years" is
+// never read as the 10Y Treasury). Returns false when none is named.
+bool nq_take_range(QString& text, const QDate& today, QDate& start, QDate& end) {
+    static const QRegularExpression span(
+        R"(\b(?:from\s+|between\s+)?((?:19|20)\d{2})\s*(?:-|–|to|through|thru|until|and)\s*((?:19|20)\d{2})\b)", kNqCi);
+    static const QRegularExpression since(R"(\b(?:since|from|after|starting(?:\s+in)?)\s+((?:19|20)\d{2})\b)", kNqCi);
+    static const QRegularExpression last_n(
+        R"(\b(?:(?:over|in|for|during)\s+)?(?:the\s+)?(?:last|past|previous|trailing|prior)\s+)"
+        R"((\d{1,3}|one|two|three|four|five|six|seven|eight|nine|ten|fifteen|twenty|thirty)\s*)"
+        R"((decades?|years?|yrs?|y|quarters?|months?|mos?|m|weeks?|wks?|w|days?|d)\b)",
+        kNqCi);
+    static const QRegularExpression last_one(
+        R"(\b(?:(?:over|in|for|during)\s+)?(?:the\s+)?(?:last|past|previous|trailing|prior)\s+)"
+        R"((decade|year|quarter|month|week)\b)",
+        kNqCi);
+    static const QRegularExpression ytd(R"(\b(?:ytd|year[\s-]to[\s-]date)\b)", kNqCi);
+    static const QRegularExpression single(R"(\b(?:in\s+|during\s+)?((?:19|20)\d{2})\b)", kNqCi);
+
+    QRegularExpressionMatch m;
+    const auto hit = [&](const QRegularExpression& rx) {
+        m = rx.match(text);
+        return m.hasMatch();
+    };
+    end = today;
+    if (hit(span)) {
+        start = QDate(m.captured(1).toInt(), 1, 1);
+        end = std::min(today, QDate(m.captured(2).toInt(), 12, 31));
+    } else if (hit(since)) {
+        start = QDate(m.captured(1).toInt(), 1, 1);
+    } else if (hit(last_n)) {
+        start = nq_back(today, nq_number(m.captured(1)), m.captured(2));
+    } else if (hit(last_one)) {
+        start = nq_back(today, 1, m.captured(1));
+    } else if (hit(ytd)) {
+        start = QDate(today.year(), 1, 1);
+    } else if (hit(single)) {
+        start = QDate(m.captured(1).toInt(), 1, 1);
+        end = std::min(today, QDate(m.captured(1).toInt(), 12, 31));
+    } else {
+        return false;
+    }
+    nq_blank(text, m.capturedStart(), m.capturedLength());
+    return true;
+}
+
+QString nq_take_frequency(QString& text) {
+    static const QRegularExpression rx(
+        R"(\b(daily|weekly|monthly|quarterly|annually|yearly|annual)\b(?!\s+(?:rate|change|growth|average)))", kNqCi);
+    const auto m = rx.match(text);
+    if (!m.hasMatch())
+        return {};
+    const QString w = m.captured(1).toLower();
+    nq_blank(text, m.capturedStart(), m.capturedLength());
+    if (w == "daily")
+        return "d";
+    if (w == "weekly")
+        return "w";
+    if (w == "monthly")
+        return "m";
+    if (w == "quarterly")
+        return "q";
+    return "a";
+}
+
+QString nq_label(const QString& base, const QString& transform) {
+    if (transform == "pc1")
+        return base + " (YoY %)";
+    if (transform == "pch")
+        return base + " (% chg)";
+    if (transform == "chg")
+        return base + " (chg)";
+    if (transform == "log")
+        return base + " (log)";
+    return base;
+}
+
+// One part of the question ("us cpi yoy") → its legs. A part no leg comes
+// from is counted in plan.unresolved and named in plan.notes.
+QList<NlQueryLeg> nq_parse_segment(QString segment, NlQueryPlan& plan) {
+    const QString original = segment.simplified();
+    if (kNqForeign.match(segment).hasMatch()) {
+        plan.unresolved += 1;
+        plan.notes << QString("Not in the local vocabulary (non-US series): '%1'").arg(original);
+        return {};
+    }
+
+    QString transform;
+    for (const auto& [pattern, units] : kNqTransforms) {
+        const QRegularExpression rx(pattern, kNqCi);
+        const auto m = rx.match(segment);
+        if (m.hasMatch()) {
+            transform = units;
+            nq_blank(segment, m.capturedStart(), m.capturedLength());
+            break;
+        }
+    }
+
+    struct Hit {
+        qsizetype start;
+        qsizetype length;
+        int concept;
+    };
+    QVector<Hit> hits;
+    for (int i = 0; i < kNqConcepts.size(); ++i) {
+        const QRegularExpression rx(QString(R"((?<![\w&])(?:%1)(?![\w&]))").arg(kNqConcepts[i].pattern), kNqCi);
+        auto it = rx.globalMatch(segment);
+        while (it.hasNext()) {
+            const auto m = it.next();
+            hits.append({m.capturedStart(), m.capturedLength(), i});
+        }
+    }
+    std::stable_sort(hits.begin(), hits.end(), [](const Hit& a, const Hit& b) { return a.length > b.length; });
+    QVector<Hit> taken;
+    for (const auto& h : hits) {
+        const bool overlaps = std::any_of(taken.begin(), taken.end(), [&](const Hit& t) {
+            return h.start < t.start + t.length && t.start < h.start + h.length;
+        });
+        if (!overlaps)
+            taken.append(h);
+    }
+    std::sort(taken.begin(), taken.end(), [](const Hit& a, const Hit& b) { return a.start < b.start; });
+
+    QList<NlQueryLeg> legs;
+    for (const auto& h : taken) {
+        const NqConcept& c = kNqConcepts[h.concept];
+        // A concept's default units are already in its label ("CPI inflation").
+        const QString label = transform.isEmpty() ? QString::fromUtf8(c.label) : nq_label(c.label, transform);
+        legs.append({"fred", c.series_id, label, transform.isEmpty() ? QString(c.transform) : transform,
+                     segment.mid(h.start, h.length).simplified()});
+    }
+    for (const auto& h : taken)
+        nq_blank(segment, h.start, h.length);
+
+    static const QRegularExpression ticker(R"((?:^|[\s(])\$?([A-Z]{1,5}(?:[.\-][A-Z]{1,3})?)(?=$|[\s),]))");
+    auto it = ticker.globalMatch(segment);
+    QVector<QPair<qsizetype, qsizetype>> ticker_spans;
+    while (it.hasNext()) {
+        const auto m = it.next();
+        const QString sym = m.captured(1);
+        if (kNqNotTickers.contains(sym))
+            continue;
+        legs.append({"yfinance", sym, sym, {}, sym});
+        ticker_spans.append({m.capturedStart(1), m.capturedLength(1)});
+        if (!transform.isEmpty())
+            plan.notes << QString("Transforms apply to FRED series only — %1 is close prices").arg(sym);
+    }
+    for (const auto& [start, length] : ticker_spans)
+        nq_blank(segment, start, length);
+
+    if (legs.isEmpty()) {
+        const QString rest = QString(segment).replace(kNqFiller, " ").simplified();
+        if (!rest.isEmpty()) {
+            plan.unresolved += 1;
+            plan.notes << QString("Not understood: '%1'").arg(original);
+        }
+    }
+    return legs;
+}
+
+bool nq_valid_units(const QString& units) {
+    static const QStringList valid = {"", "pc1", "pch", "chg", "log"};
+    return valid.contains(units);
+}
+
+bool nq_valid_frequency(const QString& frequency) {
+    static const QStringList valid = {"", "d", "w", "m", "q", "a"};
+    return valid.contains(frequency);
+}
+
+// The LLM's reply → a plan, held to the same sources, ids and transforms as
+// the local parser. Anything off-contract rejects the whole reply.
+bool nq_plan_from_llm(const QString& reply, const NlQueryPlan& local, NlQueryPlan& out, QString& why) {
+    static const QRegularExpression id_rx(R"(^[A-Za-z0-9.^=\-]{1,24}$)");
+    const QJsonObject o = QJsonDocument::fromJson(python::extract_json(reply).toUtf8()).object();
+    const QJsonArray legs = o.value("legs").toArray();
+    if (legs.isEmpty()) {
+        why = "no legs in the reply";
+        return false;
+    }
+    out = local;
+    out.legs.clear();
+    out.notes.clear();
+    out.unresolved = 0;
+    out.parser = "llm";
+    for (const auto& v : legs) {
+        const QJsonObject l = v.toObject();
+        NlQueryLeg leg;
+        leg.source = l.value("source").toString().toLower();
+        leg.id = l.value("id").toString().trimmed();
+        leg.transform = l.value("transform").toString().toLower();
+        if ((leg.source != "fred" && leg.source != "yfinance") || !id_rx.match(leg.id).hasMatch() ||
+            !nq_valid_units(leg.transform)) {
+            why = QString("unsupported leg %1").arg(QString::fromUtf8(QJsonDocument(l).toJson(QJsonDocument::Compact)));
+            return false;
+        }
+        if (leg.source == "fred")
+            leg.id = leg.id.toUpper();
+        else
+            leg.transform.clear();
+        leg.label = nq_label(l.value("label").toString(leg.id), leg.transform);
+        leg.matched = l.value("matched").toString();
+        out.legs.append(leg);
+        if (out.legs.size() >= NlQueryService::kMaxLegs)
+            break;
+    }
+    const QDate start = QDate::fromString(o.value("start").toString(), Qt::ISODate);
+    const QDate end = QDate::fromString(o.value("end").toString(), Qt::ISODate);
+    if (start.isValid() && (!end.isValid() || start < end)) {
+        out.start = start;
+        if (end.isValid())
+            out.end = std::min(end, QDate::currentDate());
+    }
+    const QString frequency = o.value("frequency").toString().toLower();
+    if (nq_valid_frequency(frequency))
+        out.frequency = frequency;
+    return true;
+}
+
+QString nq_llm_prompt(const NlQueryPlan& local) {
+    return QString("Translate a market-data question into data commands. Sources: \"fred\" (a FRED series id, "
+                   "e.g. CPIAUCSL, FEDFUNDS, GBRCPIALLMINMEI) and \"yfinance\" (a Yahoo Finance ticker, e.g. "
+                   "AAPL, ^GSPC, EURUSD=X). transform applies to fred only: \"\" level, \"pc1\" YoY %, \"pch\" "
+                   "% change, \"chg\" change, \"log\". frequency: \"\" native, or d, w, m, q, a. At most %1 legs.\n"
+                   "Reply with JSON only: {\"legs\":[{\"source\":\"fred\",\"id\":\"...\",\"label\":\"...\","
+                   "\"transform\":\"\",\"matched\":\"words of the question\"}],\"start\":\"YYYY-MM-DD\","
+                   "\"end\":\"YYYY-MM-DD\",\"frequency\":\"\"}\n"
+                   "Today is %2. If no period is named use %3 to %2.\n"
+                   "Question: %4")
+        .arg(NlQueryService::kMaxLegs)
+        .arg(QDate::currentDate().toString(Qt::ISODate), local.start.toString(Qt::ISODate), local.question);
+}
+
+struct NqRun {
+    QString request_id;
+    NlQueryPlan plan;
+    int pending = 0;
+    QVector<QJsonObject> series; ///< per leg; empty when the leg failed
+    QVector<QMap<QString, double>> points;
+    QJsonArray errors;
+};
+
+QJsonObject nq_result(const NqRun& run) {
+    QMap<QString, bool> axis;
+    for (const auto& p : run.points)
+        for (auto it = p.cbegin(); it != p.cend(); ++it)
+            axis.insert(it.key(), true);
+    const QStringList dates = axis.keys(); // ISO dates sort chronologically
+
+    QJsonArray series;
+    for (int i = 0; i < run.series.size(); ++i) {
+        if (run.series[i].isEmpty())
+            continue;
+        QJsonArray values;
+        for (const auto& d : dates) {
+            const auto v = run.points[i].constFind(d);
+            values.append(v == run.points[i].cend() ? QJsonValue() : QJsonValue(*v));
+        }
+        QJsonObject s = run.series[i];
+        s["values"] = values;
+        series.append(s);
+    }
+    return QJsonObject{{"question", run.plan.question},
+                       {"plan", run.plan.to_json()},
+                       {"dates", QJsonArray::fromStringList(dates)},
+                       {"series", series},
+                       {"errors", run.errors}};
+}
+
+} // namespace
+
+QJsonObject NlQueryPlan::to_json() const {
+    QJsonArray l;
+    for (const auto& leg : legs)
+        l.append(QJsonObject{{"source", leg.source},
+                             {"id", leg.id},
+                             {"label", leg.label},
+                             {"transform", leg.transform},
+                             {"matched", leg.matched}});
+    return QJsonObject{{"question", question},
+                       {"legs", l},
+                       {"start", start.toString(Qt::ISODate)},
+                       {"end", end.toString(Qt::ISODate)},
+                       {"frequency", frequency},
+                       {"parser", parser},
+                       {"unresolved", unresolved},
+                       {"notes", QJsonArray::fromStringList(notes)}};
+}
+
+NlQueryService& NlQueryService::instance() {
+    static NlQueryService s;
+    return s;
+}
+
+NlQueryPlan NlQueryService::parse(const QString& question, const QDate& today) {
+    NlQueryPlan plan;
+    plan.question = question.simplified();
+    plan.parser = "local";
+    QString text = plan.question;
+    if (!nq_take_range(text, today, plan.start, plan.end)) {
+        plan.start = today.addYears(-kDefaultYears);
+        plan.end = today;
+    }
+    plan.frequency = nq_take_frequency(text);
+
+    static const QRegularExpression separators(
+        R"(\s*(?:\bvs\b\.?|\bversus\b|\bagainst\b|\bcompared\s+(?:to|with)\b|\band\b|\bplus\b|[,;])\s*)", kNqCi);
+    for (const auto& segment : text.split(separators, Qt::SkipEmptyParts)) {
+        for (const auto& leg : nq_parse_segment(segment, plan)) {
+            const bool dup = std::any_of(plan.legs.begin(), plan.legs.end(), [&](const NlQueryLeg& l) {
+                return l.source == leg.source && l.id == leg.id && l.transform == leg.transform;
+            });
+            if (!dup && plan.legs.size() < kMaxLegs)
+                plan.legs.append(leg);
+        }
+    }
+    return plan;
+}
+
+QString NlQueryService::run(const QString& question, bool allow_llm, bool dry_run) {
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    const NlQueryPlan local = parse(question);
+    LOG_INFO(kNqTag, QString("'%1' → %2 leg(s), %3 unresolved part(s)")
+                         .arg(local.question)
+                         .arg(local.legs.size())
+                         .arg(local.unresolved));
+
+    // The LLM is only asked when the local parser could not place every part.
+    const bool needs_llm = local.is_empty() || local.unresolved > 0;
+    if (needs_llm && allow_llm && ai_chat::LlmService::instance().is_configured()) {
+        plan_with_llm(request_id, local, dry_run);
+        return request_id;
+    }
+
+    // Signals go out on the next turn so callers can connect to the id first.
+    QMetaObject::invokeMethod(
+        this,
+        [this, request_id, local, dry_run]() {
+            if (local.is_empty()) {
+                emit error_occurred(request_id, "Could not map the question to any data series" +
+                                                    (local.notes.isEmpty() ? QString()
+                                                                           : " (" + local.notes.join("; ") + ")"));
+                return;
+            }
+            if (dry_run) {
+                emit finished(request_id, QJsonObject{{"question", local.question}, {"plan", local.to_json()}});
+                return;
+            }
+            execute(request_id, local);
+        },
+        Qt::QueuedConnection);
+    return request_id;
+}
+
+void NlQueryService::plan_with_llm(const QString& request_id, const NlQueryPlan& local, bool dry_run) {
+    QPointer<NlQueryService> self = this;
+    const QString prompt = nq_llm_prompt(local);
+    (void)QtConcurrent::run([self, request_id, local, dry_run, prompt]() {
+        const ai_chat::LlmResponse resp = ai_chat::LlmService::instance().chat(prompt, {}, false);
+        QMetaObject::invokeMethod(
+            qApp,
+            [self, request_id, local, dry_run, resp]() {
+                if (!self)
+                    return;
+                NlQueryPlan plan;
+                QString why = resp.success ? QString() : resp.error;
+                if (!resp.success || !nq_plan_from_llm(resp.content, local, plan, why)) {
+                    // A bad or missing reply still leaves whatever the local parser found.
+                    LOG_WARN(kNqTag, "LLM plan rejected: " + why);
+                    plan = local;
+                    plan.notes << "LLM plan rejected: " + why;
+                }
+                if (plan.is_empty()) {
+                    emit self->error_occurred(request_id, "Could not map the question to any data series (" +
+                                                              plan.notes.join("; ") + ")");
+                    return;
+                }
+                if (dry_run) {
+                    emit self->finished(request_id,
+                                        QJsonObject{{"question", plan.question}, {"plan", plan.to_json()}});
+                    return;
+                }
+                self->execute(request_id, plan);
+            },
+            Qt::QueuedConnection);
+    });
+}
+
+void NlQueryService::execute(const QString& request_id, const NlQueryPlan& plan) {
+    auto run = std::make_shared<NqRun>();
+    run->request_id = request_id;
+    run->plan = plan;
+    run->pending = int(plan.legs.size());
+    run->series.resize(plan.legs.size());
+    run->points.resize(plan.legs.size());
+
+    const QString start = plan.start.toString(Qt::ISODate);
+    const QString end = plan.end.toString(Qt::ISODate);
+    QPointer<NlQueryService> self = this;
+    for (int i = 0; i < plan.legs.size(); ++i) {
+        const NlQueryLeg leg = plan.legs[i];
+        QString script;
+        QStringList args;
+        if (leg.source == "fred") {
+            script = "fred_data.py";
+            args = {"series", leg.id, start, end, plan.frequency, leg.transform};
+        } else {
+            static const QHash<QString, QString> intervals = {{"w", "1wk"}, {"m", "1mo"}, {"q", "3mo"}, {"a", "3mo"}};
+            script = "yfinance_data.py";
+            // yfinance's end date is exclusive.
+            args = {"historical", leg.id, start, plan.end.addDays(1).toString(Qt::ISODate),
+                    intervals.value(plan.frequency, "1d")};
+        }
+        const QString command = script + ' ' + args.join(' ').simplified();
+
+        python::PythonRunner::instance().run(script, args, [self, run, i, leg, command](python::PythonResult r) {
+            if (!self)
+                return;
+            const QJsonDocument doc = QJsonDocument::fromJson(python::extract_json(r.output).toUtf8());
+            QJsonObject series{{"label", leg.label},
+                               {"source", leg.source},
+                               {"id", leg.id},
+                               {"transform", leg.transform}};
+            QJsonObject provenance{{"command", command},
+                                   {"fetched_at", QDateTime::currentDateTimeUtc().toString(Qt::ISODate)},
+                                   {"parser", run->plan.parser},
+                                   {"matched", leg.matched}};
+            QString error;
+            if (doc.isObject() && doc.object().contains("error")) {
+                error = doc.object().value("error").toString();
+            } else if (!doc.isObject() && !doc.isArray()) {
+                error = r.error.isEmpty() ? QStringLiteral("%1 produced no JSON").arg(command) : r.error;
+            } else if (leg.source == "fred") {
+                const QJsonObject o = doc.object();
+                for (const auto& v : o.value("observations").toArray()) {
+                    const QJsonObject obs = v.toObject();
+                    run->points[i].insert(obs.value("date").toString(), obs.value("value").toDouble());
+                }
+                series["units"] = o.value("units").toString();
+                series["frequency"] = o.value("frequency").toString();
+                provenance["provider"] = "FRED (Federal Reserve Bank of St. Louis)";
+                provenance["title"] = o.value("title").toString();
+                provenance["seasonal_adjustment"] = o.value("seasonal_adjustment").toString();
+                provenance["last_updated"] = o.value("last_updated").toString();
+            } else {
+                for (const auto& v : doc.array()) {
+                    const QJsonObject bar = v.toObject();
+                    const QString date = QDateTime::fromSecsSinceEpoch(bar.value("timestamp").toInteger(), Qt::UTC)
+                                             .date()
+                                             .toString(Qt::ISODate);
+                    run->points[i].insert(date, bar.value("close").toDouble());
+                }
+                series["units"] = "close price";
+                provenance["provider"] = "Yahoo Finance";
+            }
+            if (error.isEmpty() && run->points[i].isEmpty())
+                error = "no observations in range";
+
+            if (error.isEmpty()) {
+                provenance["observations"] = int(run->points[i].size());
+                series["provenance"] = provenance;
+                run->series[i] = series;
+            } else {
+                LOG_WARN(kNqTag, QString("%1 %2 failed: %3").arg(leg.source, leg.id, error));
+                run->errors.append(QJsonObject{{"label", leg.label}, {"command", command}, {"error", error}});
+            }
+
+            if (--run->pending > 0)
+                return;
+            if (run->errors.size() == run->series.size()) {
+                QStringList messages;
+                for (const auto& e : run->errors)
+                    messages << e.toObject().value("label").toString() + ": " + e.toObject().value("error").toString();
+                emit self->error_occurred(run->request_id, messages.join("; "));
+                return;
+            }
+            emit self->finished(run->request_id, nq_result(*run));
+        });
+    }
+}
+
+} // namespace fincept::services
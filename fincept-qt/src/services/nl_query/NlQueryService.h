@@ -0,0 +1,78 @@
+// src/services/nl_query/NlQueryService.h
+#pragma once
+#include <QDate>
+#include <QJsonObject>
+#include <QList>
+#include <QObject>
+#include <QString>
+#include <QStringList>
+
+namespace fincept::services {
+
+/// One data command a question was translated into.
+struct NlQueryLeg {
+    QString source;    ///< "fred" (fred_data.py series) | "yfinance" (yfinance_data.py historical)
+    QString id;        ///< FRED series id or ticker
+    QString label;     ///< legend label, e.g. "CPI (YoY %)"
+    QString transform; ///< FRED units: "" | pc1 (YoY %) | pch (% chg) | chg | log — FRED legs only
+    QString matched;   ///< the words of the question this leg came from
+};
+
+struct NlQueryPlan {
+    QString question;
+    QList<NlQueryLeg> legs;
+    QDate start;
+    QDate end;
+    QString frequency;  ///< "" (native) | d | w | m | q | a
+    QString parser;     ///< "local" | "llm"
+    int unresolved = 0; ///< parts of the question no leg came from
+    QStringList notes;  ///< what was not understood or not applied
+    bool is_empty() const { return legs.isEmpty(); }
+    QJsonObject to_json() const;
+};
+
+/// Natural-language query → data commands ("US CPI YoY last 5 years vs Fed
+/// funds" → FRED CPIAUCSL as pc1 + FRED FEDFUNDS, 5 years back).
+///
+/// parse() is a pure local intent parser: a vocabulary of macro concepts
+/// mapped to FRED series, transform words (YoY, MoM, change, log), a time
+/// range ("last 5 years", "since 2015", "2010-2020", "YTD"), a frequency
+/// word, and capitalised tickers for Yahoo Finance prices. When part of the
+/// question is not understood and an LLM is configured, run() can ask it for
+/// the plan instead (validated against the same sources and transforms).
+///
+/// run() executes every leg through the existing Python scripts, then merges
+/// the results on one date axis — chart-ready, with provenance (provider,
+/// command, title, units, last update, fetch time, parser) per series.
+/// Main thread only; FRED legs need FRED_API_KEY.
+class NlQueryService : public QObject {
+    Q_OBJECT
+  public:
+    static constexpr int kDefaultYears = 5;
+    static constexpr int kMaxLegs = 6;
+
+    static NlQueryService& instance();
+
+    static NlQueryPlan parse(const QString& question, const QDate& today = QDate::currentDate());
+
+    /// Plans (locally, falling back to the LLM when allowed and needed) and
+    /// executes. Returns the request id finished / error_occurred carry. With
+    /// `dry_run` the result holds only the plan.
+    QString run(const QString& question, bool allow_llm = true, bool dry_run = false);
+
+  signals:
+    /// {question, plan, dates: [...], series: [{label, source, id, units,
+    /// frequency, transform, values: [...aligned to dates, null = no obs],
+    /// provenance{...}}], errors: [...]}
+    void finished(QString request_id, QJsonObject result);
+    void error_occurred(QString request_id, QString message);
+
+  private:
+    NlQueryService() = default;
+    Q_DISABLE_COPY(NlQueryService)
+
+    void plan_with_llm(const QString& request_id, const NlQueryPlan& local, bool dry_run);
+    void execute(const QString& request_id, const NlQueryPlan& plan);
+};
+
+} // namespace fincept::services
@@ -2,6 +2,7 @@
 
 #include "core/logging/Logger.h"
 #include "storage/repositories/SettingsRepository.h"
+#include "storage/secure/SecureStorage.h"
 
 // Provider includes
 #include "services/notifications/providers/DiscordProvider.h"
@@ -24,6 +25,31 @@
 
 namespace fincept::notifications {
 
+namespace {
+
+// "notif_<provider>.<field>" keys BaseProvider::get_secret() reads — keep in
+// step with the is_password fields in NotificationsSection's provider_defs.
+constexpr const char* kNsSecretKeys[] = {
+    "notif_telegram.bot_token",
+    "notif_discord.webhook_url",
+    "notif_slack.webhook_url",
+    "notif_email.smtp_pass",
+    "notif_whatsapp.auth_token",
+    "notif_pushover.api_token",
+    "notif_pushover.user_key",
+    "notif_ntfy.token",
+    "notif_pushbullet.api_key",
+    "notif_gotify.app_token",
+    "notif_mattermost.webhook_url",
+    "notif_teams.webhook_url",
+    "notif_webhook.url",
+    "notif_pagerduty.routing_key",
+    "notif_opsgenie.api_key",
+    "notif_sms.auth_token",
+};
+
+} // namespace
+
 // ── Singleton ─────────────────────────────────────────────────────────────────
 
 NotificationService& NotificationService::instance() {
@@ -32,9 +58,38 @@ NotificationService& NotificationService::instance() {
 }
 
 NotificationService::NotificationService() {
+    migrate_plaintext_secrets();
     register_providers();
 }
 
+void NotificationService::migrate_plaintext_secrets() {
+    auto& repo = SettingsRepository::instance();
+    auto flag = repo.get("migration.notif_secrets_v1");
+    if (flag.is_ok() && flag.value() == "done")
+        return;
+
+    auto& secure = SecureStorage::instance();
+    int moved = 0;
+    bool failed = false;
+    for (const char* k : kNsSecretKeys) {
+        const QString key = QString::fromLatin1(k);
+        auto plain = repo.get(key);
+        if (!plain.is_ok() || plain.value().isEmpty())
+            continue;
+        if (secure.adopt(key, plain.value()).is_err()) {
+            failed = true; // leave the row; get_secret() retries on the next read
+            continue;
+        }
+        repo.remove(key);
+        ++moved;
+    }
+
+    if (!failed)
+        repo.set("migration.notif_secrets_v1", "done", "system");
+    if (moved > 0)
+        LOG_INFO("NotificationService", QString("Moved %1 plaintext provider secret(s) to secure storage").arg(moved));
+}
+
 void NotificationService::register_providers() {
     providers_.clear();
     providers_.emplace_back(std::make_unique<TelegramProvider>());
@@ -74,6 +129,8 @@ void NotificationService::send(const NotificationRequest& req) {
     const bool price_on = get_bool("notifications.price_alerts", true);
     const bool orders_on = get_bool("notifications.order_fills", true);
     const bool news_on = get_bool("notifications.news_alerts", false);
+    const bool dividends_on = get_bool("notifications.dividend_alerts", true);
+    const bool algo_on = get_bool("notifications.algo_alerts", true);
 
     bool trigger_allowed = true;
     switch (req.trigger) {
@@ -86,8 +143,15 @@ void NotificationService::send(const NotificationRequest& req) {
         case NotifTrigger::NewsAlert:
             trigger_allowed = news_on;
             break;
+        case NotifTrigger::DividendAlert:
+            trigger_allowed = dividends_on;
+            break;
+        case NotifTrigger::AlgoAlert:
+            trigger_allowed = algo_on;
+            break;
         case NotifTrigger::Manual:
         case NotifTrigger::WorkflowNode:
+        case NotifTrigger::EventRiskAlert: // opt-in via event_risk.alert_days
             trigger_allowed = true;
             break;
     }
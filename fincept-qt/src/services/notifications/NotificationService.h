@@ -13,7 +13,16 @@ namespace fincept::notifications {
 // ── Enumerations ──────────────────────────────────────────────────────────────
 
 enum class NotifLevel { Info, Warning, Alert, Critical };
-enum class NotifTrigger { Manual, PriceAlert, OrderFill, NewsAlert, WorkflowNode };
+enum class NotifTrigger {
+    Manual,
+    PriceAlert,
+    OrderFill,
+    NewsAlert,
+    WorkflowNode,
+    DividendAlert,
+    AlgoAlert,
+    EventRiskAlert
+};
 
 // ── Data structures ───────────────────────────────────────────────────────────
 
@@ -104,6 +113,9 @@ class NotificationService : public QObject {
   private:
     explicit NotificationService();
     void register_providers();
+    /// One-shot: moves every provider secret still in the settings table into
+    /// SecureStorage, including providers that are never loaded or saved.
+    void migrate_plaintext_secrets();
 
     std::vector<std::unique_ptr<INotificationProvider>> providers_;
     QVector<NotificationRecord> history_;
@@ -1,13 +1,15 @@
 #pragma once
 #include "services/notifications/NotificationService.h"
 #include "storage/repositories/SettingsRepository.h"
+#include "storage/secure/SecureStorage.h"
 
 namespace fincept::notifications {
 
 /// Convenience base class for HTTP-based notification providers.
 /// Subclasses implement provider_id(), display_name(), icon(), and send().
 /// load_config() / save_config() / is_configured() / is_enabled() are handled here
-/// via SettingsRepository under category "notif_<provider_id>".
+/// via SettingsRepository under category "notif_<provider_id>"; tokens, keys and
+/// secret-bearing webhook URLs go through get_secret() / set_secret() instead.
 class BaseProvider : public INotificationProvider {
   public:
     bool is_enabled() const override { return enabled_; }
@@ -41,6 +43,28 @@ class BaseProvider : public INotificationProvider {
         return res.is_ok() ? res.value() : QString{};
     }
 
+    /// Reads a secret from SecureStorage under the same key the settings
+    /// table used; a value still in the settings table (pre-vault installs)
+    /// is moved across on first read.
+    static QString get_secret(SettingsRepository& r, const QString& key) {
+        const QString plaintext = get_str(r, key);
+        auto res = SecureStorage::instance().adopt(key, plaintext);
+        if (res.is_err())
+            return plaintext;
+        if (!plaintext.isEmpty())
+            r.remove(key);
+        return res.value();
+    }
+
+    static void set_secret(SettingsRepository& r, const QString& key, const QString& value) {
+        auto& secure = SecureStorage::instance();
+        if (value.isEmpty())
+            secure.remove(key);
+        else if (secure.store(key, value).is_err())
+            return; // keep whatever copy exists rather than lose the secret
+        r.remove(key);
+    }
+
     /// Subclasses load their specific credential fields here.
     virtual void load_fields(SettingsRepository& r, const QString& cat) = 0;
     /// Subclasses persist their specific credential fields here.
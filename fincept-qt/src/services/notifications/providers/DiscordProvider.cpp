@@ -8,11 +8,11 @@
 namespace fincept::notifications {
 
 void DiscordProvider::load_fields(SettingsRepository& r, const QString& cat) {
-    webhook_url_ = get_str(r, cat + ".webhook_url");
+    webhook_url_ = get_secret(r, cat + ".webhook_url");
 }
 
 void DiscordProvider::save_fields(SettingsRepository& r, const QString& cat) {
-    r.set(cat + ".webhook_url", webhook_url_, cat);
+    set_secret(r, cat + ".webhook_url", webhook_url_);
 }
 
 void DiscordProvider::send(const NotificationRequest& req, std::function<void(bool, QString)> cb) {
@@ -7,12 +7,12 @@
 namespace fincept::notifications {
 
 void SlackProvider::load_fields(SettingsRepository& r, const QString& cat) {
-    webhook_url_ = get_str(r, cat + ".webhook_url");
+    webhook_url_ = get_secret(r, cat + ".webhook_url");
     channel_ = get_str(r, cat + ".channel");
 }
 
 void SlackProvider::save_fields(SettingsRepository& r, const QString& cat) {
-    r.set(cat + ".webhook_url", webhook_url_, cat);
+    set_secret(r, cat + ".webhook_url", webhook_url_);
     r.set(cat + ".channel", channel_, cat);
 }
 
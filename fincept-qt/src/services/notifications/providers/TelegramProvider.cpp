@@ -7,12 +7,12 @@
 namespace fincept::notifications {
 
 void TelegramProvider::load_fields(SettingsRepository& r, const QString& cat) {
-    bot_token_ = get_str(r, cat + ".bot_token");
+    bot_token_ = get_secret(r, cat + ".bot_token");
     chat_id_ = get_str(r, cat + ".chat_id");
 }
 
 void TelegramProvider::save_fields(SettingsRepository& r, const QString& cat) {
-    r.set(cat + ".bot_token", bot_token_, cat);
+    set_secret(r, cat + ".bot_token", bot_token_);
     r.set(cat + ".chat_id", chat_id_, cat);
 }
 
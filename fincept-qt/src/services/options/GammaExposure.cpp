@@ -0,0 +1,195 @@
+#include "services/options/GammaExposure.h"
+
+#include "services/options/OptionPricing.h"
+
+#include <QMap>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::services::options::gex {
+
+namespace {
+
+constexpr double kGxMinYears = 1.0 / 365.0; // expiry day — clamp to one day, as the chain Greeks do
+
+double gx_num(const QJsonValue& v) {
+    if (v.isDouble())
+        return v.toDouble();
+    bool ok = false;
+    const double d = v.toString().toDouble(&ok);
+    return ok ? d : 0;
+}
+
+/// "250117" (CBOE) or "2025-01-17".
+QDate gx_expiry(const QString& s) {
+    if (s.size() == 6)
+        return QDate::fromString("20" + s, "yyyyMMdd");
+    return QDate::fromString(s.left(10), Qt::ISODate);
+}
+
+double gx_years(const QDate& today, const QDate& expiry) {
+    return std::max(today.daysTo(expiry) / 365.0, kGxMinYears);
+}
+
+/// Signed GEX of one contract at spot `s`.
+double gx_contract(const GexContract& c, double s, const GexOptions& o) {
+    const double g = pricing::bsm_gamma(s, c.strike, c.t_years, o.risk_free_rate, c.iv, o.dividend_yield);
+    const double v = g * c.open_interest * o.multiplier * s * s * 0.01;
+    return c.is_call ? v : -v;
+}
+
+QJsonArray gx_column(const QVector<GexStrike>& rows, double GexStrike::*field) {
+    QJsonArray a;
+    for (const auto& r : rows)
+        a.append(r.*field);
+    return a;
+}
+
+} // namespace
+
+QVector<GexContract> contracts_from_json(const QJsonArray& options, const QDate& today, int max_dte) {
+    QVector<GexContract> out;
+    out.reserve(options.size());
+    for (const auto& v : options) {
+        const QJsonObject o = v.toObject();
+        const QDate expiry = gx_expiry(o["expiration"].toString());
+        const QString type = o["option_type"].toString().toLower();
+        if (!expiry.isValid() || (type != "call" && type != "put"))
+            continue;
+        const qint64 dte = today.daysTo(expiry);
+        if (dte < 0 || (max_dte > 0 && dte > max_dte))
+            continue;
+        GexContract c;
+        c.strike = gx_num(o["strike"]);
+        c.t_years = gx_years(today, expiry);
+        c.is_call = type == "call";
+        c.open_interest = gx_num(o["open_interest"]);
+        c.iv = gx_num(o["implied_volatility"]);
+        out.append(c);
+    }
+    return out;
+}
+
+QVector<GexContract> contracts_from_chain(const OptionChain& chain, const QDate& today) {
+    QDate expiry = QDate::fromString(chain.expiry, "dd-MMM-yy");
+    if (!expiry.isValid())
+        expiry = QDate::fromString(chain.expiry, "yyyy-MM-dd");
+    if (expiry.isValid())
+        expiry = expiry.year() < 2000 ? expiry.addYears(100) : expiry;
+    const double t = expiry.isValid() ? gx_years(today, expiry) : kGxMinYears;
+
+    QVector<GexContract> out;
+    out.reserve(chain.rows.size() * 2);
+    for (const auto& row : chain.rows) {
+        out.append(GexContract{row.strike, t, true, double(row.ce_quote.oi), row.ce_iv});
+        out.append(GexContract{row.strike, t, false, double(row.pe_quote.oi), row.pe_iv});
+    }
+    return out;
+}
+
+GexResult compute(const QVector<GexContract>& contracts, const GexOptions& opts) {
+    GexResult r;
+    r.spot = opts.spot;
+    if (opts.spot <= 0)
+        return r;
+
+    QVector<GexContract> used;
+    used.reserve(contracts.size());
+    for (const auto& c : contracts) {
+        if (c.strike > 0 && c.open_interest > 0 && c.iv > 0 && c.t_years > 0)
+            used.append(c);
+        else
+            ++r.skipped;
+    }
+    r.contracts = used.size();
+
+    // ── Per strike at the current spot ──
+    QMap<double, GexStrike> by_strike;
+    for (const auto& c : used) {
+        const double v = gx_contract(c, opts.spot, opts);
+        GexStrike& s = by_strike[c.strike];
+        s.strike = c.strike;
+        if (c.is_call) {
+            s.call_oi += c.open_interest;
+            s.call_gex += v;
+            r.call_gex += v;
+        } else {
+            s.put_oi += c.open_interest;
+            s.put_gex += v;
+            r.put_gex += v;
+        }
+    }
+    r.net_gex = r.call_gex + r.put_gex;
+
+    double best_call = 0, best_put = 0;
+    const double lo = opts.spot * (1 - opts.strike_range_pct);
+    const double hi = opts.spot * (1 + opts.strike_range_pct);
+    for (auto it = by_strike.begin(); it != by_strike.end(); ++it) {
+        GexStrike s = it.value();
+        s.net_gex = s.call_gex + s.put_gex;
+        if (s.call_gex > best_call) {
+            best_call = s.call_gex;
+            r.call_wall = s.strike;
+        }
+        if (s.put_gex < best_put) {
+            best_put = s.put_gex;
+            r.put_wall = s.strike;
+        }
+        if (s.strike >= lo && s.strike <= hi)
+            r.strikes.append(s);
+    }
+
+    // ── Profile across hypothetical spots → zero gamma ──
+    const int n = std::max(opts.n_points, 3);
+    const double p_lo = opts.spot * (1 - opts.profile_range_pct);
+    const double p_hi = opts.spot * (1 + opts.profile_range_pct);
+    r.profile.reserve(n);
+    for (int i = 0; i < n; ++i) {
+        const double s = p_lo + (p_hi - p_lo) * i / (n - 1);
+        double net = 0;
+        for (const auto& c : used)
+            net += gx_contract(c, s, opts);
+        r.profile.append(GexProfilePoint{s, net});
+    }
+    double best_dist = std::numeric_limits<double>::infinity();
+    for (int i = 1; i < r.profile.size(); ++i) {
+        const auto& a = r.profile[i - 1];
+        const auto& b = r.profile[i];
+        if ((a.net_gex < 0) == (b.net_gex < 0) || a.net_gex == b.net_gex)
+            continue;
+        const double x = a.spot + (b.spot - a.spot) * a.net_gex / (a.net_gex - b.net_gex);
+        if (std::abs(x - opts.spot) < best_dist) {
+            best_dist = std::abs(x - opts.spot);
+            r.zero_gamma = x;
+        }
+    }
+    return r;
+}
+
+QJsonObject to_json(const GexResult& r) {
+    QJsonArray p_spot, p_net;
+    for (const auto& p : r.profile) {
+        p_spot.append(p.spot);
+        p_net.append(p.net_gex);
+    }
+    const bool has_zero = std::isfinite(r.zero_gamma);
+    return QJsonObject{
+        {"spot", r.spot},
+        {"totals", QJsonObject{{"call_gex", r.call_gex}, {"put_gex", r.put_gex}, {"net_gex", r.net_gex}}},
+        {"zero_gamma", has_zero ? QJsonValue(r.zero_gamma) : QJsonValue()},
+        {"call_wall", r.call_wall},
+        {"put_wall", r.put_wall},
+        {"regime", r.net_gex >= 0 ? "positive" : "negative"},
+        {"contracts", r.contracts},
+        {"skipped", r.skipped},
+        {"strikes", QJsonObject{{"strike", gx_column(r.strikes, &GexStrike::strike)},
+                                {"call_gex", gx_column(r.strikes, &GexStrike::call_gex)},
+                                {"put_gex", gx_column(r.strikes, &GexStrike::put_gex)},
+                                {"net_gex", gx_column(r.strikes, &GexStrike::net_gex)},
+                                {"call_oi", gx_column(r.strikes, &GexStrike::call_oi)},
+                                {"put_oi", gx_column(r.strikes, &GexStrike::put_oi)}}},
+        {"profile", QJsonObject{{"spot", p_spot}, {"net_gex", p_net}}}};
+}
+
+} // namespace fincept::services::options::gex
@@ -0,0 +1,98 @@
+#pragma once
+// GammaExposure — dealer gamma exposure (GEX) from an option chain with open
+// interest. Pure functions; gamma comes from OptionPricing::bsm_gamma so a
+// chain only needs strike, expiry, type, OI and IV.
+//
+// Convention (the usual "dealers long calls, short puts" read of public OI):
+//
+//   contract GEX = ±gamma(S) × OI × multiplier × S² × 1%
+//
+// i.e. the dollar delta dealers must trade for a 1% move in the underlying,
+// positive for calls and negative for puts. Net GEX > 0 means hedging damps
+// moves; < 0 means it amplifies them.
+//
+// The zero-gamma level is where net GEX changes sign when every contract is
+// re-evaluated at a hypothetical spot (IV and OI held fixed) — the profile
+// is sampled across spot ± profile_range_pct and the crossing nearest the
+// current spot is interpolated.
+
+#include "services/options/OptionChainTypes.h"
+
+#include <QDate>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QVector>
+
+#include <limits>
+
+namespace fincept::services::options::gex {
+
+struct GexContract {
+    double strike = 0;
+    double t_years = 0;
+    bool is_call = true;
+    double open_interest = 0;
+    double iv = 0; // decimal
+};
+
+struct GexOptions {
+    double spot = 0;
+    double risk_free_rate = 0.045;
+    double dividend_yield = 0;
+    /// Shares per unit of OI: 100 for US equity options; 1 for broker chains
+    /// that report OI in units (NSE).
+    double multiplier = 100;
+    /// Strikes outside spot ± this fraction are left out of the per-strike
+    /// arrays (they still count towards the totals).
+    double strike_range_pct = 0.20;
+    double profile_range_pct = 0.15;
+    int n_points = 121;
+};
+
+struct GexStrike {
+    double strike = 0;
+    double call_oi = 0;
+    double put_oi = 0;
+    double call_gex = 0; // ≥ 0
+    double put_gex = 0;  // ≤ 0
+    double net_gex = 0;
+};
+
+struct GexProfilePoint {
+    double spot = 0;
+    double net_gex = 0;
+};
+
+struct GexResult {
+    double spot = 0;
+    QVector<GexStrike> strikes; // ascending
+    QVector<GexProfilePoint> profile;
+    double call_gex = 0;
+    double put_gex = 0;
+    double net_gex = 0;
+    double zero_gamma = std::numeric_limits<double>::quiet_NaN(); // NaN when the profile never crosses
+    double call_wall = 0; // strike with the largest call GEX
+    double put_wall = 0;  // strike with the largest (absolute) put GEX
+    int contracts = 0;    // contracts that contributed
+    int skipped = 0;      // no OI, IV or time left
+};
+
+/// Contracts from the normalized chain-script shape
+/// ({contract_symbol, expiration, strike, option_type, open_interest,
+/// implied_volatility}). Expiries beyond `max_dte` days (0 = all) and already
+/// expired ones are dropped.
+QVector<GexContract> contracts_from_json(const QJsonArray& options, const QDate& today, int max_dte = 0);
+
+/// Contracts from a broker chain snapshot (one expiry; IV from row.ce_iv /
+/// row.pe_iv as filled in by the Greeks worker).
+QVector<GexContract> contracts_from_chain(const OptionChain& chain, const QDate& today);
+
+GexResult compute(const QVector<GexContract>& contracts, const GexOptions& opts);
+
+/// {spot, totals{call_gex, put_gex, net_gex}, zero_gamma, call_wall, put_wall,
+///  regime, contracts, skipped, strikes{strike[], call_gex[], put_gex[],
+///  net_gex[], call_oi[], put_oi[]}, profile{spot[], net_gex[]}}
+/// — column arrays, ready for a chart series each.
+QJsonObject to_json(const GexResult& r);
+
+} // namespace fincept::services::options::gex
@@ -8,17 +8,30 @@ namespace fincept::services::options::pricing {
 namespace {
 
 constexpr double kSqrt2 = 1.4142135623730951;
+constexpr double kInvSqrt2Pi = 0.3989422804014327;
 
 inline double max0(double v) {
     return std::max(v, 0.0);
 }
 
+inline double op_d1(double S, double K, double t, double r, double sigma, double q) {
+    return (std::log(S / K) + (r - q + 0.5 * sigma * sigma) * t) / (sigma * std::sqrt(t));
+}
+
+inline bool op_degenerate(double S, double K, double t, double sigma) {
+    return t <= 0 || sigma <= 0 || S <= 0 || K <= 0;
+}
+
 } // namespace
 
 double normal_cdf(double x) {
     return 0.5 * (1.0 + std::erf(x / kSqrt2));
 }
 
+double normal_pdf(double x) {
+    return kInvSqrt2Pi * std::exp(-0.5 * x * x);
+}
+
 double bsm_call(double S, double K, double t, double r, double sigma, double q) {
     if (t <= 0)
         return max0(S - K);
@@ -81,4 +94,81 @@ double black_put(double F, double K, double t, double r, double sigma) {
     return std::exp(-r * t) * (K * normal_cdf(-d2) - F * normal_cdf(-d1));
 }
 
+double bsm_gamma(double S, double K, double t, double r, double sigma, double q) {
+    if (t <= 0 || sigma <= 0 || S <= 0 || K <= 0)
+        return 0.0;
+    const double sqrt_t = std::sqrt(t);
+    const double d1 = (std::log(S / K) + (r - q + 0.5 * sigma * sigma) * t) / (sigma * sqrt_t);
+    return std::exp(-q * t) * normal_pdf(d1) / (S * sigma * sqrt_t);
+}
+
+double bsm_delta(double S, double K, double t, double r, double sigma, double q, bool is_call) {
+    if (op_degenerate(S, K, t, sigma))
+        return 0.0;
+    const double d1 = op_d1(S, K, t, r, sigma, q);
+    const double disc_q = std::exp(-q * t);
+    return is_call ? disc_q * normal_cdf(d1) : -disc_q * normal_cdf(-d1);
+}
+
+double bsm_theta(double S, double K, double t, double r, double sigma, double q, bool is_call) {
+    if (op_degenerate(S, K, t, sigma))
+        return 0.0;
+    const double sqrt_t = std::sqrt(t);
+    const double d1 = op_d1(S, K, t, r, sigma, q);
+    const double d2 = d1 - sigma * sqrt_t;
+    const double disc_q = std::exp(-q * t);
+    const double disc_r = std::exp(-r * t);
+    const double decay = -S * disc_q * normal_pdf(d1) * sigma / (2.0 * sqrt_t);
+    const double annual = is_call ? decay - r * K * disc_r * normal_cdf(d2) + q * S * disc_q * normal_cdf(d1)
+                                  : decay + r * K * disc_r * normal_cdf(-d2) - q * S * disc_q * normal_cdf(-d1);
+    return annual / 365.0;
+}
+
+double bsm_vega(double S, double K, double t, double r, double sigma, double q) {
+    if (op_degenerate(S, K, t, sigma))
+        return 0.0;
+    return S * std::exp(-q * t) * normal_pdf(op_d1(S, K, t, r, sigma, q)) * std::sqrt(t);
+}
+
+double bsm_rho(double S, double K, double t, double r, double sigma, double q, bool is_call) {
+    if (op_degenerate(S, K, t, sigma))
+        return 0.0;
+    const double d2 = op_d1(S, K, t, r, sigma, q) - sigma * std::sqrt(t);
+    const double disc_r = std::exp(-r * t);
+    return is_call ? K * t * disc_r * normal_cdf(d2) : -K * t * disc_r * normal_cdf(-d2);
+}
+
+double bsm_implied_vol(double price, double S, double K, double t, double r, double q, bool is_call) {
+    if (price <= 0 || t <= 0 || S <= 0 || K <= 0)
+        return 0.0;
+    auto value = [&](double sigma) {
+        return is_call ? bsm_call(S, K, t, r, sigma, q) : bsm_put(S, K, t, r, sigma, q);
+    };
+    constexpr double kLo = 0.0001;
+    constexpr double kHi = 5.0;
+    double lo = kLo;
+    double hi = kHi;
+    if (price < value(lo) - 1e-9 || price > value(hi))
+        return 0.0; // below intrinsic or above any sensible vol
+
+    double sigma = 0.3;
+    for (int i = 0; i < 100; ++i) {
+        const double diff = value(sigma) - price;
+        if (std::abs(diff) < 1e-6)
+            return sigma;
+        if (diff > 0)
+            hi = sigma;
+        else
+            lo = sigma;
+        // Newton step while vega is usable and the step stays in the bracket;
+        // bisect otherwise (deep ITM/OTM, where vega vanishes).
+        const double vega = bsm_vega(S, K, t, r, sigma, q);
+        const double next = vega > 1e-8 ? sigma - diff / vega : 0.0;
+        sigma = (next > lo && next < hi) ? next : 0.5 * (lo + hi);
+        if (hi - lo < 1e-8)
+            return sigma;
+    }
+    return 0.0;
+}
+
 } // namespace fincept::services::options::pricing
@@ -16,12 +16,24 @@
 // Edge cases (intrinsic + discount):
 //   - t ≤ 0  → call = max(S − K, 0), put = max(K − S, 0)
 //   - σ ≤ 0  → forward intrinsic value (deterministic future)
+//
+// Gamma is computed here too — GammaExposure re-evaluates it for every
+// contract at every point of its spot profile:
+//
+//   gamma = e^(−qt)·n(d1) / (S·σ·√t)   (same for calls and puts)
+//
+// The remaining first-order Greeks and an implied-vol solver back the unified
+// vendor chain (UnifiedOptionChain), which recomputes Greeks from each
+// contract's IV so every source reports the same units as OptionGreeks.
 
 namespace fincept::services::options::pricing {
 
 /// Standard normal CDF via std::erf — accurate to ~1e-9.
 double normal_cdf(double x);
 
+/// Standard normal density.
+double normal_pdf(double x);
+
 /// Black-Scholes (no dividends, q = 0).
 double bs_call(double S, double K, double t, double r, double sigma);
 double bs_put(double S, double K, double t, double r, double sigma);
@@ -34,4 +46,24 @@ double bsm_put(double S, double K, double t, double r, double sigma, double q);
 double black_call(double F, double K, double t, double r, double sigma);
 double black_put(double F, double K, double t, double r, double sigma);
 
+/// BSM gamma per share (d²V/dS²). 0 when t, σ, S or K is not positive.
+double bsm_gamma(double S, double K, double t, double r, double sigma, double q);
+
+/// BSM delta per share. Calls in [0, 1], puts in [-1, 0]; 0 on degenerate input.
+double bsm_delta(double S, double K, double t, double r, double sigma, double q, bool is_call);
+
+/// BSM theta per share per calendar DAY (annual theta / 365).
+double bsm_theta(double S, double K, double t, double r, double sigma, double q, bool is_call);
+
+/// BSM vega per share per 1.00 σ (divide by 100 for per-1%-vol).
+double bsm_vega(double S, double K, double t, double r, double sigma, double q);
+
+/// BSM rho per share per 1.00 r (divide by 100 for per-1%-rate).
+double bsm_rho(double S, double K, double t, double r, double sigma, double q, bool is_call);
+
+/// σ that reprices `price` under BSM (Newton with a bisection fallback,
+/// σ ∈ [0.0001, 5]). 0 when the price is outside the no-arbitrage bounds or
+/// the solve does not converge.
+double bsm_implied_vol(double price, double S, double K, double t, double r, double q, bool is_call);
+
 } // namespace fincept::services::options::pricing
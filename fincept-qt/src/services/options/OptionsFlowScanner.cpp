@@ -0,0 +1,469 @@
+#include "services/options/OptionsFlowScanner.h"
+
+#include "core/events/EventBus.h"
+#include "core/logging/Logger.h"
+#include "python/PythonRunner.h"
+#include "services/notifications/NotificationService.h"
+#include "ui/notifications/NotificationService.h"
+
+#include <QDateTime>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QPointer>
+#include <QUuid>
+#include <QtConcurrent>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::services::options {
+
+using fincept::notifications::NotificationRequest;
+using fincept::notifications::NotificationService;
+using fincept::notifications::NotifLevel;
+using fincept::notifications::NotifTrigger;
+using fincept::ui::ToastService;
+
+namespace {
+
+constexpr double kOfMultiplier = 100; // US equity option contract size
+
+struct OfContract {
+    QString contract;
+    QDate expiry;
+    QString type; // call | put
+    double strike = 0;
+    double last = 0;
+    double bid = 0;
+    double ask = 0;
+    double volume = 0;
+    double oi = 0;
+    double iv = 0;
+    double delta = std::nan("");
+
+    double price() const {
+        if (last > 0)
+            return last;
+        return bid > 0 && ask > 0 ? (bid + ask) / 2 : std::max(bid, ask);
+    }
+};
+
+double of_num(const QJsonValue& v) {
+    if (v.isDouble())
+        return v.toDouble();
+    bool ok = false;
+    const double d = v.toString().toDouble(&ok);
+    return ok ? d : 0;
+}
+
+/// "250117" (CBOE) or "2025-01-17".
+QDate of_expiry(const QString& s) {
+    if (s.size() == 6)
+        return QDate::fromString("20" + s, "yyyyMMdd");
+    return QDate::fromString(s.left(10), Qt::ISODate);
+}
+
+QVector<OfContract> of_contracts(const QJsonObject& data) {
+    QVector<OfContract> out;
+    for (const auto& v : data["options"].toArray()) {
+        const QJsonObject o = v.toObject();
+        OfContract c;
+        c.contract = o["contract_symbol"].toString();
+        c.expiry = of_expiry(o["expiration"].toString());
+        c.type = o["option_type"].toString().toLower();
+        c.strike = of_num(o["strike"]);
+        c.last = of_num(o["last"]);
+        c.bid = of_num(o["bid"]);
+        c.ask = of_num(o["ask"]);
+        c.volume = of_num(o["volume"]);
+        c.oi = of_num(o["open_interest"]);
+        c.iv = of_num(o["implied_volatility"]);
+        if (o["delta"].isDouble())
+            c.delta = o["delta"].toDouble();
+        if (c.contract.isEmpty() || !c.expiry.isValid() || (c.type != "call" && c.type != "put"))
+            continue;
+        out.append(c);
+    }
+    return out;
+}
+
+struct OfSkew {
+    QDate expiry;
+    double put_iv = 0;
+    double call_iv = 0;
+    double put_strike = 0;
+    double call_strike = 0;
+    bool valid = false;
+};
+
+/// 25-delta put IV minus 25-delta call IV on the first expiry at least a
+/// week out. Without deltas the 95% / 105% moneyness strikes stand in.
+OfSkew of_skew(const QVector<OfContract>& chain, double spot, const QDate& today) {
+    OfSkew s;
+    for (const auto& c : chain) {
+        if (today.daysTo(c.expiry) >= 7 && (!s.expiry.isValid() || c.expiry < s.expiry))
+            s.expiry = c.expiry;
+    }
+    if (!s.expiry.isValid())
+        return s;
+    double best_put = 1e18, best_call = 1e18;
+    for (const auto& c : chain) {
+        if (c.expiry != s.expiry || c.iv <= 0)
+            continue;
+        const bool put = c.type == "put";
+        double dist;
+        if (std::isfinite(c.delta) && c.delta != 0)
+            dist = std::abs(std::abs(c.delta) - 0.25);
+        else if (spot > 0)
+            dist = std::abs(c.strike / spot - (put ? 0.95 : 1.05));
+        else
+            continue;
+        double& best = put ? best_put : best_call;
+        if (dist < best) {
+            best = dist;
+            (put ? s.put_iv : s.call_iv) = c.iv;
+            (put ? s.put_strike : s.call_strike) = c.strike;
+        }
+    }
+    s.valid = s.put_iv > 0 && s.call_iv > 0;
+    return s;
+}
+
+OptionsFlowEvent of_contract_event(const QString& kind, const QString& symbol, const QString& source,
+                                   const OfContract& c, double premium, double score, const QJsonObject& detail) {
+    OptionsFlowEvent e;
+    e.symbol = symbol;
+    e.source = source;
+    e.kind = kind;
+    e.contract = c.contract;
+    e.expiry = c.expiry.toString(Qt::ISODate);
+    e.option_type = c.type;
+    e.strike = c.strike;
+    e.volume = c.volume;
+    e.open_interest = c.oi;
+    e.premium = premium;
+    e.score = score;
+    e.detail = detail;
+    e.detected_at = QDateTime::currentMSecsSinceEpoch();
+    return e;
+}
+
+QString of_script(const QString& source) {
+    if (source == "polygon")
+        return QStringLiteral("polygon_io_data.py");
+    if (source == "tradier")
+        return QStringLiteral("tradier_data.py");
+    return QStringLiteral("cboe_data.py");
+}
+
+QStringList of_args(const QString& source, const QString& symbol) {
+    if (source == "polygon")
+        return {"options_snapshot", symbol};
+    if (source == "tradier")
+        return {"options_chain", symbol};
+    return {"options_chains", symbol};
+}
+
+} // namespace
+
+// ── Thresholds ──────────────────────────────────────────────────────────────
+
+OptionsFlowScanner::Thresholds OptionsFlowScanner::Thresholds::from_json(const QJsonObject& o) {
+    Thresholds t;
+    t.min_volume = o.value("min_volume").toDouble(t.min_volume);
+    t.vol_oi_ratio = o.value("vol_oi_ratio").toDouble(t.vol_oi_ratio);
+    t.sweep_contracts = o.value("sweep_contracts").toDouble(t.sweep_contracts);
+    t.sweep_premium = o.value("sweep_premium").toDouble(t.sweep_premium);
+    t.skew_change = o.value("skew_change").toDouble(t.skew_change);
+    return t;
+}
+
+QJsonObject OptionsFlowScanner::Thresholds::to_json() const {
+    return QJsonObject{{"min_volume", min_volume},
+                       {"vol_oi_ratio", vol_oi_ratio},
+                       {"sweep_contracts", sweep_contracts},
+                       {"sweep_premium", sweep_premium},
+                       {"skew_change", skew_change}};
+}
+
+// ── OptionsFlowScanner ──────────────────────────────────────────────────────
+
+OptionsFlowScanner& OptionsFlowScanner::instance() {
+    static OptionsFlowScanner s;
+    return s;
+}
+
+QJsonObject OptionsFlowScanner::event_to_json(const OptionsFlowEvent& e) {
+    QJsonObject o{{"id", e.id},
+                  {"symbol", e.symbol},
+                  {"source", e.source},
+                  {"kind", e.kind},
+                  {"premium", e.premium},
+                  {"score", e.score},
+                  {"detected_at", QDateTime::fromMSecsSinceEpoch(e.detected_at).toUTC().toString(Qt::ISODate)},
+                  {"detail", e.detail}};
+    if (!e.contract.isEmpty()) {
+        o["contract"] = e.contract;
+        o["option_type"] = e.option_type;
+        o["strike"] = e.strike;
+        o["volume"] = e.volume;
+        o["open_interest"] = e.open_interest;
+    }
+    if (!e.expiry.isEmpty())
+        o["expiry"] = e.expiry;
+    return o;
+}
+
+void OptionsFlowScanner::start() {
+    if (timer_)
+        return;
+    OptionsFlowRepository::instance().prune_events(
+        QDateTime::currentDateTimeUtc().addDays(-kRetentionDays).toMSecsSinceEpoch());
+    timer_ = new QTimer(this);
+    timer_->setInterval(kTickMs);
+    connect(timer_, &QTimer::timeout, this, &OptionsFlowScanner::tick);
+    timer_->start();
+}
+
+void OptionsFlowScanner::tick() {
+    auto watches = OptionsFlowRepository::instance().list_watches();
+    if (watches.is_err())
+        return;
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    for (const auto& w : watches.value()) {
+        const qint64 interval_ms = qint64(std::max(w.interval_sec, kMinIntervalSec)) * 1000;
+        if (w.active && !in_flight_.contains(w.symbol) && now - w.last_scan_at >= interval_ms)
+            scan(w.symbol);
+    }
+}
+
+QString OptionsFlowScanner::scan(const QString& symbol_in, const QString& source_in) {
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    const QString symbol = symbol_in.trimmed().toUpper();
+    auto fail = [this, request_id](const QString& msg) {
+        QMetaObject::invokeMethod(
+            this, [this, request_id, msg]() { emit error_occurred(request_id, msg); }, Qt::QueuedConnection);
+        return request_id;
+    };
+    if (symbol.isEmpty())
+        return fail("Missing symbol");
+    if (in_flight_.contains(symbol))
+        return fail("A scan of " + symbol + " is already running");
+
+    QString source = source_in.trimmed().toLower();
+    Thresholds t;
+    if (auto watches = OptionsFlowRepository::instance().list_watches(); watches.is_ok()) {
+        for (const auto& w : watches.value()) {
+            if (w.symbol == symbol) {
+                source = w.source;
+                t = Thresholds::from_json(w.thresholds);
+            }
+        }
+    }
+    if (source.isEmpty())
+        source = "cboe";
+    if (source != "cboe" && source != "polygon" && source != "tradier")
+        return fail("source must be cboe, polygon or tradier");
+
+    in_flight_.insert(symbol);
+    QPointer<OptionsFlowScanner> self = this;
+    python::PythonRunner::instance().run(
+        of_script(source), of_args(source, symbol), [self, request_id, symbol, source, t](python::PythonResult r) {
+            if (!self)
+                return;
+            if (!r.success) {
+                self->on_snapshot(request_id, symbol, source, t, {},
+                                  r.error.isEmpty() ? QStringLiteral("chain script failed") : r.error);
+                return;
+            }
+            // Full chains run to megabytes of JSON — parse off the UI thread.
+            (void)QtConcurrent::run([self, request_id, symbol, source, t, out = r.output]() {
+                const QJsonObject o = QJsonDocument::fromJson(python::extract_json(out).toUtf8()).object();
+                QString error;
+                if (o.isEmpty())
+                    error = QStringLiteral("no JSON in chain script output");
+                else if (o.contains("error") && !o.value("success").toBool(false))
+                    error = o.value("message").toString(o.value("error").toString("chain script reported an error"));
+                QMetaObject::invokeMethod(
+                    self.data(),
+                    [self, request_id, symbol, source, t, data = o.value("data").toObject(), error]() {
+                        if (self)
+                            self->on_snapshot(request_id, symbol, source, t, data, error);
+                    },
+                    Qt::QueuedConnection);
+            });
+        });
+    return request_id;
+}
+
+void OptionsFlowScanner::on_snapshot(const QString& request_id, const QString& symbol, const QString& source,
+                                     const Thresholds& t, const QJsonObject& data, const QString& error) {
+    in_flight_.remove(symbol);
+    auto& repo = OptionsFlowRepository::instance();
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    repo.touch_scanned(symbol, now);
+
+    const QVector<OfContract> chain = of_contracts(data);
+    if (!error.isEmpty() || chain.isEmpty()) {
+        const QString msg = error.isEmpty() ? "no option contracts for " + symbol : error;
+        LOG_WARN("OptionsFlow", QString("%1 (%2): %3").arg(symbol, source, msg));
+        emit error_occurred(request_id, msg);
+        return;
+    }
+    const double spot = of_num(data["metadata"].toObject()["current_price"]);
+
+    // Volumes reset every session, and so does everything measured from them.
+    const QDate today = QDate::currentDate();
+    Baseline& b = baselines_[symbol];
+    if (b.day != today) {
+        b = Baseline{};
+        b.day = today;
+    }
+    const bool seeded = !b.volume.isEmpty();
+    const double since_poll_sec = b.polled_ms > 0 ? (now - b.polled_ms) / 1000.0 : 0;
+
+    QVector<OptionsFlowEvent> events;
+    double call_vol = 0, put_vol = 0;
+    for (const auto& c : chain) {
+        (c.type == "call" ? call_vol : put_vol) += c.volume;
+        const double px = c.price();
+
+        if (c.volume >= t.min_volume && c.volume >= t.vol_oi_ratio * std::max(c.oi, 1.0) &&
+            !b.flagged_vol_oi.contains(c.contract)) {
+            b.flagged_vol_oi.insert(c.contract);
+            const double ratio = c.volume / std::max(c.oi, 1.0);
+            events.append(of_contract_event("vol_oi", symbol, source, c, c.volume * px * kOfMultiplier, ratio,
+                                            QJsonObject{{"vol_oi_ratio", ratio}, {"price", px}, {"iv", c.iv}}));
+        }
+
+        const auto prev = b.volume.constFind(c.contract);
+        if (seeded && prev != b.volume.constEnd()) {
+            const double added = c.volume - prev.value();
+            const double premium = added * px * kOfMultiplier;
+            if (added >= t.sweep_contracts && premium >= t.sweep_premium) {
+                const QString side = c.ask > 0 && c.last >= c.ask   ? QStringLiteral("ask")
+                                     : c.bid > 0 && c.last <= c.bid ? QStringLiteral("bid")
+                                                                    : QStringLiteral("mid");
+                events.append(of_contract_event("sweep", symbol, source, c, premium, premium / t.sweep_premium,
+                                                QJsonObject{{"contracts", added},
+                                                            {"side", side},
+                                                            {"price", px},
+                                                            {"bid", c.bid},
+                                                            {"ask", c.ask},
+                                                            {"iv", c.iv},
+                                                            {"seconds_since_last_poll", since_poll_sec}}));
+            }
+        }
+        b.volume.insert(c.contract, c.volume);
+    }
+    b.polled_ms = now;
+
+    QJsonObject summary{{"symbol", symbol},
+                        {"source", source},
+                        {"spot", spot},
+                        {"contracts", int(chain.size())},
+                        {"call_volume", call_vol},
+                        {"put_volume", put_vol},
+                        {"pc_volume_ratio", call_vol > 0 ? QJsonValue(put_vol / call_vol) : QJsonValue()},
+                        {"baseline_seeded", seeded}};
+
+    const OfSkew sk = of_skew(chain, spot, today);
+    if (sk.valid) {
+        const double skew = sk.put_iv - sk.call_iv;
+        summary["skew"] = QJsonObject{{"expiry", sk.expiry.toString(Qt::ISODate)},
+                                      {"put_iv", sk.put_iv},
+                                      {"call_iv", sk.call_iv},
+                                      {"skew", skew}};
+        if (!b.has_skew_ref) {
+            b.skew_ref = skew;
+            b.has_skew_ref = true;
+        } else if (std::abs(skew - b.skew_ref) >= t.skew_change) {
+            OptionsFlowEvent e;
+            e.symbol = symbol;
+            e.source = source;
+            e.kind = "skew";
+            e.expiry = sk.expiry.toString(Qt::ISODate);
+            e.premium = 0;
+            e.score = std::abs(skew - b.skew_ref) / t.skew_change;
+            e.detail = QJsonObject{{"skew", skew},
+                                   {"skew_from", b.skew_ref},
+                                   {"change", skew - b.skew_ref},
+                                   {"direction", skew > b.skew_ref ? "puts_richer" : "calls_richer"},
+                                   {"put_iv", sk.put_iv},
+                                   {"call_iv", sk.call_iv},
+                                   {"put_strike", sk.put_strike},
+                                   {"call_strike", sk.call_strike},
+                                   {"pc_volume_ratio", call_vol > 0 ? QJsonValue(put_vol / call_vol) : QJsonValue()},
+                                   {"spot", spot}};
+            e.detected_at = now;
+            events.append(e);
+            b.skew_ref = skew;
+        }
+    }
+
+    QJsonArray event_json;
+    QVector<OptionsFlowEvent> stored;
+    for (const auto& e : events) {
+        auto r = repo.record_event(e);
+        if (r.is_err()) {
+            LOG_WARN("OptionsFlow", "Failed to store event: " + QString::fromStdString(r.error()));
+            continue;
+        }
+        stored.append(r.value());
+        const QJsonObject j = event_to_json(r.value());
+        event_json.append(j);
+        emit flow_event(j);
+        EventBus::instance().publish("options_flow.event", j.toVariantMap());
+    }
+    summary["events"] = event_json;
+    if (!stored.isEmpty()) {
+        LOG_INFO("OptionsFlow",
+                 QString("%1: %2 flagged over %3 contracts").arg(symbol).arg(stored.size()).arg(chain.size()));
+        dispatch_alerts(stored);
+    }
+    emit scan_finished(request_id, symbol, summary);
+}
+
+void OptionsFlowScanner::dispatch_alerts(const QVector<OptionsFlowEvent>& events) {
+    auto& repo = OptionsFlowRepository::instance();
+    auto alerts = repo.list_alerts();
+    if (alerts.is_err())
+        return;
+    for (const auto& a : alerts.value()) {
+        if (!a.active)
+            continue;
+        QStringList lines;
+        for (const auto& e : events) {
+            if (!a.filter.matches(e))
+                continue;
+            if (e.kind == "skew")
+                lines.append(QString("%1 %2 skew %3%4 vol pts")
+                                 .arg(e.symbol, e.expiry)
+                                 .arg(e.detail["change"].toDouble() > 0 ? QStringLiteral("+") : QString())
+                                 .arg(e.detail["change"].toDouble() * 100, 0, 'f', 1));
+            else
+                lines.append(QString("%1 %2 %3%4 %5 — $%6")
+                                 .arg(e.symbol, e.expiry)
+                                 .arg(e.strike)
+                                 .arg(e.option_type == "call" ? QStringLiteral("C") : QStringLiteral("P"), e.kind)
+                                 .arg(e.premium, 0, 'f', 0));
+        }
+        if (lines.isEmpty())
+            continue;
+        const QString msg = lines.mid(0, 5).join("; ") +
+                            (lines.size() > 5 ? QString(" (+%1 more)").arg(lines.size() - 5) : QString());
+        if (a.toast)
+            ToastService::instance().post(ToastService::Severity::Warning, a.name + ": " + msg, "options_flow:" + a.id);
+        if (a.providers) {
+            NotificationRequest req;
+            req.title = QString("Options flow: %1").arg(a.name);
+            req.message = msg;
+            req.level = NotifLevel::Warning;
+            req.trigger = NotifTrigger::PriceAlert;
+            NotificationService::instance().send(req);
+        }
+        repo.touch_alert_fired(a.id, QDateTime::currentMSecsSinceEpoch());
+    }
+}
+
+} // namespace fincept::services::options
@@ -0,0 +1,102 @@
+#pragma once
+// OptionsFlowScanner — unusual options activity from polled chain snapshots.
+//
+// Each watched underlying (OptionsFlowRepository watches) is polled every
+// interval_sec for a full chain snapshot from its source:
+//
+//   cboe    — cboe_data.py options_chains        (delayed, no key)
+//   polygon — polygon_io_data.py options_snapshot (POLYGON_API_KEY)
+//   tradier — tradier_data.py options_chain       (TRADIER_API_KEY)
+//
+// and compared against the previous snapshot of the same session:
+//
+//   vol_oi — day volume at least vol_oi_ratio × open interest (and at
+//            least min_volume): new positioning, flagged once per contract
+//            per day.
+//   sweep  — volume added between two polls of at least sweep_contracts
+//            and sweep_premium traded notional; `side` says whether the
+//            last print was at the ask (bought) or bid (sold).
+//   skew   — front-month 25-delta put IV minus call IV moved by at least
+//            skew_change since the session's first reading (or the last
+//            flag).
+//
+// Flagged events are stored, matched against the alert filters and pushed
+// through flow_event and the `options_flow.event` EventBus event. Snapshot
+// baselines are in memory: the first poll after launch only seeds them.
+// Main thread only.
+
+#include "storage/repositories/OptionsFlowRepository.h"
+
+#include <QDate>
+#include <QHash>
+#include <QJsonObject>
+#include <QObject>
+#include <QSet>
+#include <QString>
+#include <QTimer>
+
+namespace fincept::services::options {
+
+class OptionsFlowScanner : public QObject {
+    Q_OBJECT
+  public:
+    struct Thresholds {
+        double min_volume = 500;
+        double vol_oi_ratio = 2.0;
+        double sweep_contracts = 500;
+        double sweep_premium = 250000;
+        double skew_change = 0.03; // IV, decimal (0.03 = 3 vol points)
+
+        /// Defaults overridden by whichever keys `o` carries.
+        static Thresholds from_json(const QJsonObject& o);
+        QJsonObject to_json() const;
+    };
+
+    static constexpr int kTickMs = 30 * 1000;  // how often due watches are checked
+    static constexpr int kRetentionDays = 30;  // events older than this are pruned
+    static constexpr int kMinIntervalSec = 60; // floor on a watch's poll interval
+
+    static OptionsFlowScanner& instance();
+
+    /// Starts polling active watches. Idempotent.
+    void start();
+
+    /// Scans `symbol` now; returns the request id scan_finished / error_occurred
+    /// carry. Uses the watch's source and thresholds when it is watched,
+    /// otherwise `source` (default cboe) and the default thresholds.
+    QString scan(const QString& symbol, const QString& source = {});
+
+    static QJsonObject event_to_json(const OptionsFlowEvent& e);
+
+  signals:
+    /// {symbol, source, spot, contracts, call_volume, put_volume,
+    ///  pc_volume_ratio, skew{expiry, put_iv, call_iv, skew}?, events[]}
+    void scan_finished(QString request_id, QString symbol, QJsonObject summary);
+    void error_occurred(QString request_id, QString message);
+    void flow_event(QJsonObject event);
+
+  private:
+    OptionsFlowScanner() = default;
+    Q_DISABLE_COPY(OptionsFlowScanner)
+
+    /// Per-underlying state carried between polls of one session.
+    struct Baseline {
+        QDate day;
+        QHash<QString, double> volume; // contract → day volume at the last poll
+        qint64 polled_ms = 0;
+        QSet<QString> flagged_vol_oi; // contracts already flagged today
+        double skew_ref = 0;          // skew the next skew flag is measured from
+        bool has_skew_ref = false;
+    };
+
+    void tick();
+    void on_snapshot(const QString& request_id, const QString& symbol, const QString& source,
+                     const Thresholds& t, const QJsonObject& parsed, const QString& error);
+    void dispatch_alerts(const QVector<OptionsFlowEvent>& events);
+
+    QTimer* timer_ = nullptr;
+    QSet<QString> in_flight_;
+    QHash<QString, Baseline> baselines_;
+};
+
+} // namespace fincept::services::options
@@ -0,0 +1,237 @@
+#include "services/options/UnifiedOptionChain.h"
+
+#include "services/options/OptionPricing.h"
+
+#include <QDateTime>
+#include <QLocale>
+#include <QMap>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::services::options::unified {
+
+namespace {
+
+constexpr double kUcMinYears = 1.0 / 365.0; // expiry day — clamp to one day, as the chain Greeks do
+
+double uc_num(const QJsonValue& v) {
+    if (v.isDouble())
+        return v.toDouble();
+    bool ok = false;
+    const double d = v.toString().toDouble(&ok);
+    return ok && std::isfinite(d) ? d : 0;
+}
+
+/// "250117" (CBOE), "2025-01-17", "17-JAN-25" or "17JAN25".
+QDate uc_expiry(const QString& raw) {
+    const QString s = raw.trimmed();
+    if (s.size() == 6 && s.at(0).isDigit())
+        return QDate::fromString("20" + s, "yyyyMMdd");
+    QDate d = QDate::fromString(s.left(10), Qt::ISODate);
+    if (d.isValid())
+        return d;
+    // Broker formats carry English month names in any case ("MAY", "May").
+    QString title = s.toLower();
+    for (auto& ch : title) {
+        if (ch.isLetter()) {
+            ch = ch.toUpper();
+            break;
+        }
+    }
+    for (const char* fmt : {"dd-MMM-yy", "ddMMMyy"}) {
+        d = QLocale::c().toDate(title, fmt);
+        if (d.isValid())
+            return d.year() < 2000 ? d.addYears(100) : d;
+    }
+    return {};
+}
+
+double uc_years(const QDate& today, const QDate& expiry) {
+    return std::max(today.daysTo(expiry) / 365.0, kUcMinYears);
+}
+
+void uc_sort(QVector<OptionContract>& contracts) {
+    std::sort(contracts.begin(), contracts.end(), [](const OptionContract& a, const OptionContract& b) {
+        if (a.expiry != b.expiry)
+            return a.expiry < b.expiry;
+        if (a.strike != b.strike)
+            return a.strike < b.strike;
+        return a.is_call && !b.is_call;
+    });
+}
+
+OptionContract uc_from_quote(const QString& symbol, const QDate& expiry, double strike, bool is_call,
+                             const trading::BrokerQuote& q) {
+    OptionContract c;
+    c.contract_symbol = symbol;
+    c.expiry = expiry;
+    c.strike = strike;
+    c.is_call = is_call;
+    c.last = q.ltp;
+    c.bid = q.bid;
+    c.ask = q.ask;
+    c.volume = q.volume;
+    c.open_interest = double(q.oi);
+    return c;
+}
+
+} // namespace
+
+QStringList sources() {
+    return {QStringLiteral("cboe"), QStringLiteral("polygon"), QStringLiteral("tradier"), QStringLiteral("broker")};
+}
+
+QString source_script(const QString& source) {
+    if (source == "polygon")
+        return QStringLiteral("polygon_io_data.py");
+    if (source == "tradier")
+        return QStringLiteral("tradier_data.py");
+    if (source == "cboe")
+        return QStringLiteral("cboe_data.py");
+    return {};
+}
+
+QStringList source_args(const QString& source, const QString& symbol) {
+    if (source == "polygon")
+        return {"options_snapshot", symbol};
+    if (source == "tradier")
+        return {"options_chain", symbol, "12"};
+    return {"options_chains", symbol};
+}
+
+ContractChain from_script_json(const QString& source, const QJsonObject& data) {
+    ContractChain chain;
+    const QJsonObject meta = data["metadata"].toObject();
+    chain.source = source;
+    chain.underlying = meta["symbol"].toString().toUpper();
+    chain.spot = uc_num(meta["current_price"]);
+    chain.timestamp_ms = QDateTime::currentMSecsSinceEpoch();
+
+    const QJsonArray options = data["options"].toArray();
+    chain.contracts.reserve(options.size());
+    for (const auto& v : options) {
+        const QJsonObject o = v.toObject();
+        const QString type = o["option_type"].toString().toLower();
+        OptionContract c;
+        c.expiry = uc_expiry(o["expiration"].toString());
+        c.strike = uc_num(o["strike"]);
+        if (!c.expiry.isValid() || c.strike <= 0 || (type != "call" && type != "put"))
+            continue;
+        c.contract_symbol = o["contract_symbol"].toString();
+        c.is_call = type == "call";
+        c.last = uc_num(o["last"]);
+        c.bid = uc_num(o["bid"]);
+        c.ask = uc_num(o["ask"]);
+        c.volume = uc_num(o["volume"]);
+        c.open_interest = uc_num(o["open_interest"]);
+        c.iv = uc_num(o["implied_volatility"]);
+        // Some feeds quote IV in percent (24.5 rather than 0.245).
+        if (c.iv > 5.0)
+            c.iv /= 100.0;
+        chain.contracts.append(c);
+    }
+    uc_sort(chain.contracts);
+    return chain;
+}
+
+ContractChain from_broker_entries(const QString& broker_id, const QString& underlying, const QString& expiry,
+                                  double spot, const QVector<trading::OptionChainEntry>& entries) {
+    ContractChain chain;
+    chain.source = QStringLiteral("broker:") + broker_id;
+    chain.underlying = underlying.toUpper();
+    chain.spot = spot;
+    chain.timestamp_ms = QDateTime::currentMSecsSinceEpoch();
+
+    const QDate exp = uc_expiry(expiry);
+    if (!exp.isValid())
+        return chain;
+    chain.contracts.reserve(entries.size() * 2);
+    for (const auto& e : entries) {
+        if (e.strike_price <= 0)
+            continue;
+        if (!e.ce_symbol.isEmpty())
+            chain.contracts.append(uc_from_quote(e.ce_symbol, exp, e.strike_price, true, e.ce_quote));
+        if (!e.pe_symbol.isEmpty())
+            chain.contracts.append(uc_from_quote(e.pe_symbol, exp, e.strike_price, false, e.pe_quote));
+    }
+    uc_sort(chain.contracts);
+    return chain;
+}
+
+void filter_expiries(ContractChain& chain, const QDate& today, int max_dte, const QDate& expiry) {
+    chain.contracts.erase(std::remove_if(chain.contracts.begin(), chain.contracts.end(),
+                                         [&](const OptionContract& c) {
+                                             const qint64 dte = today.daysTo(c.expiry);
+                                             if (dte < 0 || (max_dte > 0 && dte > max_dte))
+                                                 return true;
+                                             return expiry.isValid() && c.expiry != expiry;
+                                         }),
+                          chain.contracts.end());
+}
+
+int fill_greeks(ContractChain& chain, const QDate& today, double risk_free_rate, double dividend_yield) {
+    if (chain.spot <= 0)
+        return 0;
+    const double S = chain.spot;
+    const double r = risk_free_rate;
+    const double q = dividend_yield;
+    int valid = 0;
+    for (auto& c : chain.contracts) {
+        const double t = uc_years(today, c.expiry);
+        if (c.iv <= 0) {
+            const double price = (c.bid > 0 && c.ask >= c.bid) ? 0.5 * (c.bid + c.ask) : c.last;
+            c.iv = pricing::bsm_implied_vol(price, S, c.strike, t, r, q, c.is_call);
+            c.iv_solved = c.iv > 0;
+        }
+        c.greeks = OptionGreeks{};
+        if (c.iv <= 0)
+            continue;
+        c.greeks.delta = pricing::bsm_delta(S, c.strike, t, r, c.iv, q, c.is_call);
+        c.greeks.gamma = pricing::bsm_gamma(S, c.strike, t, r, c.iv, q);
+        c.greeks.theta = pricing::bsm_theta(S, c.strike, t, r, c.iv, q, c.is_call);
+        c.greeks.vega = pricing::bsm_vega(S, c.strike, t, r, c.iv, q);
+        c.greeks.rho = pricing::bsm_rho(S, c.strike, t, r, c.iv, q, c.is_call);
+        c.greeks.valid = true;
+        ++valid;
+    }
+    return valid;
+}
+
+QJsonObject to_json(const ContractChain& chain) {
+    QJsonArray contracts;
+    QMap<QDate, int> expiries;
+    for (const auto& c : chain.contracts) {
+        ++expiries[c.expiry];
+        QJsonObject o{{"contract_symbol", c.contract_symbol},
+                      {"expiry", c.expiry.toString(Qt::ISODate)},
+                      {"strike", c.strike},
+                      {"type", c.is_call ? "call" : "put"},
+                      {"last", c.last},
+                      {"bid", c.bid},
+                      {"ask", c.ask},
+                      {"volume", c.volume},
+                      {"open_interest", c.open_interest},
+                      {"iv", c.iv},
+                      {"iv_solved", c.iv_solved}};
+        if (c.greeks.valid) {
+            o["delta"] = c.greeks.delta;
+            o["gamma"] = c.greeks.gamma;
+            o["theta"] = c.greeks.theta;
+            o["vega"] = c.greeks.vega;
+            o["rho"] = c.greeks.rho;
+        }
+        contracts.append(o);
+    }
+    QJsonArray exp_arr;
+    for (auto it = expiries.cbegin(); it != expiries.cend(); ++it)
+        exp_arr.append(QJsonObject{{"expiry", it.key().toString(Qt::ISODate)}, {"contracts", it.value()}});
+    return QJsonObject{{"source", chain.source},
+                       {"underlying", chain.underlying},
+                       {"spot", chain.spot},
+                       {"timestamp", QDateTime::fromMSecsSinceEpoch(chain.timestamp_ms).toUTC().toString(Qt::ISODate)},
+                       {"expiries", exp_arr},
+                       {"contracts", contracts}};
+}
+
+} // namespace fincept::services::options::unified
@@ -0,0 +1,85 @@
+#pragma once
+// UnifiedOptionChain — one contract-per-row option chain shape for every
+// chain source, so callers stop special-casing vendors.
+//
+//   cboe    — cboe_data.py options_chains         (delayed, no key)
+//   polygon — polygon_io_data.py options_snapshot (POLYGON_API_KEY)
+//   tradier — tradier_data.py options_chain       (TRADIER_API_KEY)
+//   broker  — IBroker::get_option_chain on a connected account (one expiry)
+//
+// The scripts already emit a common JSON skeleton ({metadata, options[]}) but
+// disagree on details: CBOE writes expiries as YYMMDD, strikes arrive as
+// strings or numbers, and each vendor reports Greeks in its own units (CBOE
+// vega is per 1% vol, Polygon and Tradier send delta only). Broker chains are
+// strike-paired CE/PE rows with no IV at all. The converters here map each
+// onto OptionContract, and fill_greeks() recomputes IV (when missing) and all
+// five Greeks locally with BSM, so every source reports OptionGreeks units.
+//
+// OptionChain (OptionChainTypes.h) stays the strike-paired F&O screen model;
+// this is the flat, cross-vendor view. Pure functions, any thread.
+
+#include "services/options/OptionChainTypes.h"
+
+#include <QDate>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+namespace fincept::services::options::unified {
+
+struct OptionContract {
+    QString contract_symbol; // OCC symbol for US vendors, broker symbol otherwise
+    QDate expiry;
+    double strike = 0;
+    bool is_call = true;
+    double last = 0;
+    double bid = 0;
+    double ask = 0;
+    double volume = 0;
+    double open_interest = 0;
+    double iv = 0;          // decimal; 0 when neither the source nor the solver had one
+    bool iv_solved = false; // iv came from bsm_implied_vol, not the source
+    OptionGreeks greeks;    // per share, OptionGreeks units; filled by fill_greeks()
+};
+
+struct ContractChain {
+    QString source; // "cboe" | "polygon" | "tradier" | "broker:<broker_id>"
+    QString underlying;
+    double spot = 0;
+    qint64 timestamp_ms = 0;
+    QVector<OptionContract> contracts; // expiry asc, then strike asc, calls first
+};
+
+/// Chain sources accepted by get_option_chain.
+QStringList sources();
+
+/// Script + argv that fetch `symbol`'s chain for a script-backed source; an
+/// empty script for "broker".
+QString source_script(const QString& source);
+QStringList source_args(const QString& source, const QString& symbol);
+
+/// From a chain script's `data` object ({metadata{symbol, current_price}, options[]}).
+ContractChain from_script_json(const QString& source, const QJsonObject& data);
+
+/// From IBroker::get_option_chain rows (one expiry, CE/PE paired by strike).
+/// `expiry` is parsed as YYYY-MM-DD, DD-MMM-YY or DDMMMYY.
+ContractChain from_broker_entries(const QString& broker_id, const QString& underlying, const QString& expiry,
+                                  double spot, const QVector<trading::OptionChainEntry>& entries);
+
+/// Drops expired contracts, those past `max_dte` days (0 = no limit) and, when
+/// `expiry` is valid, every other expiry.
+void filter_expiries(ContractChain& chain, const QDate& today, int max_dte, const QDate& expiry = {});
+
+/// Solves IV from the mid (or last) where the source had none, then computes
+/// delta/gamma/theta/vega/rho for every contract with an IV. Needs a spot.
+/// Returns the number of contracts with valid Greeks.
+int fill_greeks(ContractChain& chain, const QDate& today, double risk_free_rate, double dividend_yield);
+
+/// {source, underlying, spot, timestamp, expiries[], contracts[{contract_symbol,
+///  expiry, strike, type, last, bid, ask, volume, open_interest, iv, iv_solved,
+///  delta, gamma, theta, vega, rho}]}
+QJsonObject to_json(const ContractChain& chain);
+
+} // namespace fincept::services::options::unified
@@ -0,0 +1,268 @@
+#include "services/options/VolatilityEstimators.h"
+
+#include <algorithm>
+#include <array>
+#include <cmath>
+#include <limits>
+#include <numeric>
+
+namespace fincept::services::options::vol {
+
+namespace {
+
+constexpr double kNaN = std::numeric_limits<double>::quiet_NaN();
+constexpr double kLn2 = 0.6931471805599453;
+constexpr double kLog2Pi = 1.8378770664093453;
+constexpr int kGarchMinReturns = 30;
+constexpr int kGarchMaxIterations = 1000;
+
+// The window [end − window + 1, end], plus `before` bars ahead of it, exists.
+bool ve_window_ok(int size, int end, int window, int before) {
+    return window >= 1 && end < size && end - window + 1 - before >= 0;
+}
+
+double ve_annualise(double variance, double periods_per_year) {
+    if (!std::isfinite(variance) || variance < 0 || periods_per_year <= 0)
+        return kNaN;
+    return std::sqrt(variance * periods_per_year);
+}
+
+double ve_sample_var(const QVector<double>& x) {
+    const int n = x.size();
+    if (n < 2)
+        return kNaN;
+    const double mean = std::accumulate(x.begin(), x.end(), 0.0) / n;
+    double ss = 0;
+    for (double v : x)
+        ss += (v - mean) * (v - mean);
+    return ss / (n - 1);
+}
+
+double ve_logistic(double x) {
+    return 1.0 / (1.0 + std::exp(-x));
+}
+
+double ve_logit(double p) {
+    return std::log(p / (1.0 - p));
+}
+
+struct VeGarchParams {
+    double omega, alpha, beta;
+};
+
+// Unconstrained → (ω, α, β): ω = e^p0 · var, α + β = 0.9999·logistic(p1),
+// α = (α + β)·logistic(p2).
+VeGarchParams ve_garch_params(const std::array<double, 3>& p, double var) {
+    const double persistence = 0.9999 * ve_logistic(p[1]);
+    const double alpha = persistence * ve_logistic(p[2]);
+    return {std::exp(p[0]) * var, alpha, persistence - alpha};
+}
+
+// Negative Gaussian log-likelihood; the recursion starts at the sample
+// variance. `last_var` receives σ² of the final observation.
+double ve_garch_nll(const QVector<double>& r, const VeGarchParams& g, double var, double* last_var = nullptr) {
+    double s2 = var;
+    double nll = 0;
+    for (int t = 0; t < r.size(); ++t) {
+        if (t > 0)
+            s2 = g.omega + g.alpha * r[t - 1] * r[t - 1] + g.beta * s2;
+        if (!(s2 > 0) || !std::isfinite(s2))
+            return std::numeric_limits<double>::infinity();
+        nll += 0.5 * (kLog2Pi + std::log(s2) + r[t] * r[t] / s2);
+    }
+    if (last_var)
+        *last_var = s2;
+    return nll;
+}
+
+} // namespace
+
+QVector<double> log_returns(const QVector<double>& close) {
+    QVector<double> out;
+    if (close.size() < 2)
+        return out;
+    out.reserve(close.size() - 1);
+    for (int i = 1; i < close.size(); ++i)
+        out.append(close[i] > 0 && close[i - 1] > 0 ? std::log(close[i] / close[i - 1]) : kNaN);
+    return out;
+}
+
+double close_to_close(const QVector<double>& close, int end, int window, double periods_per_year) {
+    if (window < 2 || !ve_window_ok(close.size(), end, window, 1))
+        return kNaN;
+    QVector<double> r;
+    r.reserve(window);
+    for (int i = end - window + 1; i <= end; ++i) {
+        if (close[i] <= 0 || close[i - 1] <= 0)
+            return kNaN;
+        r.append(std::log(close[i] / close[i - 1]));
+    }
+    return ve_annualise(ve_sample_var(r), periods_per_year);
+}
+
+double parkinson(const QVector<double>& high, const QVector<double>& low, int end, int window,
+                 double periods_per_year) {
+    if (!ve_window_ok(high.size(), end, window, 0) || low.size() != high.size())
+        return kNaN;
+    double sum = 0;
+    for (int i = end - window + 1; i <= end; ++i) {
+        if (high[i] <= 0 || low[i] <= 0)
+            return kNaN;
+        const double hl = std::log(high[i] / low[i]);
+        sum += hl * hl;
+    }
+    return ve_annualise(sum / (4.0 * kLn2 * window), periods_per_year);
+}
+
+double garman_klass(const QVector<double>& open, const QVector<double>& high, const QVector<double>& low,
+                    const QVector<double>& close, int end, int window, double periods_per_year) {
+    if (!ve_window_ok(close.size(), end, window, 0))
+        return kNaN;
+    double sum = 0;
+    for (int i = end - window + 1; i <= end; ++i) {
+        if (open[i] <= 0 || high[i] <= 0 || low[i] <= 0 || close[i] <= 0)
+            return kNaN;
+        const double hl = std::log(high[i] / low[i]);
+        const double co = std::log(close[i] / open[i]);
+        sum += 0.5 * hl * hl - (2.0 * kLn2 - 1.0) * co * co;
+    }
+    return ve_annualise(std::max(0.0, sum / window), periods_per_year);
+}
+
+double yang_zhang(const QVector<double>& open, const QVector<double>& high, const QVector<double>& low,
+                  const QVector<double>& close, int end, int window, double periods_per_year) {
+    if (window < 2 || !ve_window_ok(close.size(), end, window, 1))
+        return kNaN;
+    QVector<double> overnight, open_close;
+    overnight.reserve(window);
+    open_close.reserve(window);
+    double rs = 0;
+    for (int i = end - window + 1; i <= end; ++i) {
+        if (open[i] <= 0 || high[i] <= 0 || low[i] <= 0 || close[i] <= 0 || close[i - 1] <= 0)
+            return kNaN;
+        overnight.append(std::log(open[i] / close[i - 1]));
+        open_close.append(std::log(close[i] / open[i]));
+        rs += std::log(high[i] / close[i]) * std::log(high[i] / open[i]) +
+              std::log(low[i] / close[i]) * std::log(low[i] / open[i]);
+    }
+    const double k = 0.34 / (1.34 + double(window + 1) / (window - 1));
+    const double var = ve_sample_var(overnight) + k * ve_sample_var(open_close) + (1.0 - k) * rs / window;
+    return ve_annualise(var, periods_per_year);
+}
+
+Garch11 fit_garch11(const QVector<double>& returns, int horizon, double periods_per_year) {
+    Garch11 out;
+    QVector<double> r;
+    r.reserve(returns.size());
+    for (double v : returns)
+        if (std::isfinite(v))
+            r.append(v);
+    if (r.size() < kGarchMinReturns) {
+        out.error = QString("GARCH(1,1) needs at least %1 returns (got %2)").arg(kGarchMinReturns).arg(r.size());
+        return out;
+    }
+    if (periods_per_year <= 0) {
+        out.error = QStringLiteral("periods_per_year must be positive");
+        return out;
+    }
+    const double mean = std::accumulate(r.begin(), r.end(), 0.0) / r.size();
+    for (double& v : r)
+        v -= mean;
+    const double var = ve_sample_var(r);
+    if (!(var > 0)) {
+        out.error = QStringLiteral("Returns have no variance");
+        return out;
+    }
+
+    // Nelder-Mead over the unconstrained parameters, started at persistence
+    // 0.95 with α = 0.05 and ω matching the sample variance.
+    using Point = std::array<double, 3>;
+    auto f = [&r, var](const Point& p) { return ve_garch_nll(r, ve_garch_params(p, var), var); };
+    const Point start{std::log(0.05), ve_logit(0.95 / 0.9999), ve_logit(0.05 / 0.95)};
+    std::array<Point, 4> simplex{start, start, start, start};
+    for (int d = 0; d < 3; ++d)
+        simplex[d + 1][d] += 0.5;
+    std::array<double, 4> fx;
+    for (int i = 0; i < 4; ++i)
+        fx[i] = f(simplex[i]);
+
+    int it = 0;
+    for (; it < kGarchMaxIterations; ++it) {
+        std::array<int, 4> order{0, 1, 2, 3};
+        std::sort(order.begin(), order.end(), [&fx](int a, int b) { return fx[a] < fx[b]; });
+        const int best = order[0], worst = order[3], second = order[2];
+        if (std::abs(fx[worst] - fx[best]) < 1e-9 * (1.0 + std::abs(fx[best])))
+            break;
+
+        Point centroid{0, 0, 0};
+        for (int i : {order[0], order[1], order[2]})
+            for (int d = 0; d < 3; ++d)
+                centroid[d] += simplex[i][d] / 3.0;
+        auto along = [&](double t) {
+            Point p;
+            for (int d = 0; d < 3; ++d)
+                p[d] = centroid[d] + t * (simplex[worst][d] - centroid[d]);
+            return p;
+        };
+
+        const Point reflected = along(-1.0);
+        const double f_reflected = f(reflected);
+        if (f_reflected < fx[best]) {
+            const Point expanded = along(-2.0);
+            const double f_expanded = f(expanded);
+            if (f_expanded < f_reflected) {
+                simplex[worst] = expanded;
+                fx[worst] = f_expanded;
+            } else {
+                simplex[worst] = reflected;
+                fx[worst] = f_reflected;
+            }
+        } else if (f_reflected < fx[second]) {
+            simplex[worst] = reflected;
+            fx[worst] = f_reflected;
+        } else {
+            const Point contracted = along(f_reflected < fx[worst] ? -0.5 : 0.5);
+            const double f_contracted = f(contracted);
+            if (f_contracted < std::min(f_reflected, fx[worst])) {
+                simplex[worst] = contracted;
+                fx[worst] = f_contracted;
+            } else {
+                for (int i : {order[1], order[2], order[3]}) {
+                    for (int d = 0; d < 3; ++d)
+                        simplex[i][d] = simplex[best][d] + 0.5 * (simplex[i][d] - simplex[best][d]);
+                    fx[i] = f(simplex[i]);
+                }
+            }
+        }
+    }
+
+    const int best = int(std::min_element(fx.begin(), fx.end()) - fx.begin());
+    if (!std::isfinite(fx[best])) {
+        out.error = QStringLiteral("GARCH(1,1) likelihood did not converge");
+        return out;
+    }
+    const VeGarchParams g = ve_garch_params(simplex[best], var);
+    double last_var = var;
+    const double nll = ve_garch_nll(r, g, var, &last_var);
+
+    out.ok = true;
+    out.omega = g.omega;
+    out.alpha = g.alpha;
+    out.beta = g.beta;
+    out.persistence = g.alpha + g.beta;
+    out.log_likelihood = -nll;
+    out.observations = r.size();
+    out.iterations = it;
+    const double long_run_var = g.omega / (1.0 - out.persistence);
+    out.long_run_vol = ve_annualise(long_run_var, periods_per_year);
+    out.current_vol = ve_annualise(last_var, periods_per_year);
+
+    // σ²_T+h = V_L + (α + β)^(h−1)·(σ²_T+1 − V_L)
+    const double next_var = g.omega + g.alpha * r.last() * r.last() + g.beta * last_var;
+    for (int h = 1; h <= std::max(0, horizon); ++h)
+        out.forecast.append(ve_annualise(
+            long_run_var + std::pow(out.persistence, h - 1) * (next_var - long_run_var), periods_per_year));
+    return out;
+}
+
+} // namespace fincept::services::options::vol
@@ -0,0 +1,56 @@
+#pragma once
+// VolatilityEstimators — historical (realised) volatility from OHLC bars and
+// a GARCH(1,1) fit of log returns, in pure C++. Feeds the options / risk side
+// (what implied vol to compare against) and the strategy DSL's REALIZED_VOL
+// operand.
+//
+// Estimators over the `window` bars ending at `end` (inclusive), as an
+// annualised standard deviation (fraction, 0.2 = 20%):
+//
+//   close-to-close  sample stdev of ln(C_t / C_t−1)
+//   Parkinson       Σ ln(H/L)² / (4·ln2·n)
+//   Garman-Klass    Σ [½·ln(H/L)² − (2·ln2 − 1)·ln(C/O)²] / n
+//   Yang-Zhang      σ²_overnight + k·σ²_open-close + (1 − k)·σ²_Rogers-Satchell,
+//                   k = 0.34 / (1.34 + (n + 1)/(n − 1))
+//
+// close-to-close and Yang-Zhang need the close before the window; all return
+// NaN when the bars are missing or a price is not positive.
+
+#include <QString>
+#include <QVector>
+
+namespace fincept::services::options::vol {
+
+/// ln(C_t / C_t−1) for t ≥ 1 — one element shorter than `close`.
+QVector<double> log_returns(const QVector<double>& close);
+
+double close_to_close(const QVector<double>& close, int end, int window, double periods_per_year);
+double parkinson(const QVector<double>& high, const QVector<double>& low, int end, int window,
+                 double periods_per_year);
+double garman_klass(const QVector<double>& open, const QVector<double>& high, const QVector<double>& low,
+                    const QVector<double>& close, int end, int window, double periods_per_year);
+double yang_zhang(const QVector<double>& open, const QVector<double>& high, const QVector<double>& low,
+                  const QVector<double>& close, int end, int window, double periods_per_year);
+
+/// GARCH(1,1) on demeaned returns, σ²_t = ω + α·r²_t−1 + β·σ²_t−1, fitted by
+/// Gaussian maximum likelihood (Nelder-Mead over a reparameterisation that
+/// keeps ω > 0, α, β ≥ 0 and α + β < 1). Volatilities are annualised.
+struct Garch11 {
+    bool ok = false;
+    QString error;
+    double omega = 0; // per-period variance units
+    double alpha = 0;
+    double beta = 0;
+    double persistence = 0;  // α + β
+    double long_run_vol = 0; // √(ω / (1 − α − β)), annualised
+    double current_vol = 0;  // conditional vol of the last observation
+    double log_likelihood = 0;
+    int observations = 0;
+    int iterations = 0;
+    QVector<double> forecast; // conditional vol 1..horizon periods ahead
+};
+
+/// Needs at least 30 returns.
+Garch11 fit_garch11(const QVector<double>& returns, int horizon, double periods_per_year);
+
+} // namespace fincept::services::options::vol
@@ -0,0 +1,288 @@
+// src/services/plugins/PluginService.cpp
+#include "services/plugins/PluginService.h"
+
+#include "algo_engine/CandleDataFetcher.h"
+#include "core/config/AppPaths.h"
+#include "core/logging/Logger.h"
+#include "services/plugins/WasmSandbox.h"
+
+#include <QDateTime>
+#include <QDir>
+#include <QFile>
+#include <QFileInfo>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QThreadPool>
+#include <QTimeZone>
+#include <QTimer>
+#include <QUuid>
+#include <QtConcurrent>
+
+#include <algorithm>
+#include <cmath>
+#include <memory>
+
+namespace fincept::services::plugins {
+
+namespace {
+
+const QStringList kPluginKinds = {"indicator", "transform", "source"};
+
+PluginInfo pl_read_manifest(const QFileInfo& wasm) {
+    PluginInfo info;
+    info.id = wasm.completeBaseName();
+    info.name = info.id;
+    info.wasm_path = wasm.absoluteFilePath();
+
+    QFile f(wasm.absolutePath() + "/" + info.id + ".json");
+    if (!f.open(QIODevice::ReadOnly)) {
+        info.error = "missing manifest " + info.id + ".json";
+        return info;
+    }
+    QJsonParseError pe;
+    const QJsonObject m = QJsonDocument::fromJson(f.readAll(), &pe).object();
+    if (pe.error != QJsonParseError::NoError) {
+        info.error = "manifest: " + pe.errorString();
+        return info;
+    }
+    info.name = m["name"].toString(info.id);
+    info.kind = m["kind"].toString();
+    info.version = m["version"].toString();
+    info.author = m["author"].toString();
+    info.description = m["description"].toString();
+    info.params = m["params"].toObject();
+    info.output_cap = std::clamp(m["output_cap"].toInt(PluginService::kDefaultSourceCap), 1, PluginService::kMaxValues);
+    if (!kPluginKinds.contains(info.kind))
+        info.error = QString("manifest kind '%1' is not one of %2").arg(info.kind, kPluginKinds.join(", "));
+    return info;
+}
+
+/// Allocates `bytes` in the module; alloc() returning 0 or less is a failure.
+bool pl_alloc(WasmSandbox& sb, int bytes, int32_t* ptr, QString* error) {
+    if (!sb.call("alloc", {std::max(bytes, 1)}, ptr, error))
+        return false;
+    if (*ptr <= 0) {
+        *error = QString("alloc(%1) failed").arg(bytes);
+        return false;
+    }
+    return true;
+}
+
+/// Runs one plugin to completion on the calling thread.
+bool pl_execute(const PluginInfo& info, const QVector<double>& in, const QJsonObject& params, QVector<double>* out,
+                QString* error) {
+    QFile f(info.wasm_path);
+    if (!f.open(QIODevice::ReadOnly)) {
+        *error = "cannot read " + info.wasm_path;
+        return false;
+    }
+    WasmSandbox sb(f.readAll());
+    if (!sb.load(error))
+        return false;
+
+    const QByteArray pj = QJsonDocument(params).toJson(QJsonDocument::Compact);
+    const bool is_source = info.kind == "source";
+    const int n = static_cast<int>(in.size());
+    const int cap = is_source ? info.output_cap : n;
+
+    int32_t p_ptr = 0, in_ptr = 0, out_ptr = 0, count = 0;
+    if (!pl_alloc(sb, static_cast<int>(pj.size()), &p_ptr, error) ||
+        !sb.write(static_cast<uint32_t>(p_ptr), pj.constData(), static_cast<uint32_t>(pj.size()), error))
+        return false;
+    if (!is_source) {
+        if (!pl_alloc(sb, n * 8, &in_ptr, error) ||
+            !sb.write(static_cast<uint32_t>(in_ptr), in.constData(), static_cast<uint32_t>(n * 8), error))
+            return false;
+    }
+    if (!pl_alloc(sb, cap * 8, &out_ptr, error))
+        return false;
+
+    const bool called = is_source ? sb.call("source", {p_ptr, int32_t(pj.size()), out_ptr, cap}, &count, error)
+                                  : sb.call("transform", {in_ptr, n, p_ptr, int32_t(pj.size()), out_ptr}, &count,
+                                            error);
+    if (!called)
+        return false;
+    if (count < 0) {
+        *error = QString("plugin returned error code %1").arg(count);
+        return false;
+    }
+    if (count > cap) {
+        *error = QString("plugin wrote %1 values into a buffer of %2").arg(count).arg(cap);
+        return false;
+    }
+    out->resize(count);
+    return sb.read(static_cast<uint32_t>(out_ptr), out->data(), static_cast<uint32_t>(count * 8), error);
+}
+
+double pl_field(const algo::OhlcvCandle& c, const QString& field) {
+    if (field == "open")
+        return c.open;
+    if (field == "high")
+        return c.high;
+    if (field == "low")
+        return c.low;
+    if (field == "volume")
+        return c.volume;
+    return c.close;
+}
+
+} // namespace
+
+QJsonObject PluginInfo::to_json() const {
+    QJsonObject o{{"id", id},           {"name", name},     {"kind", kind},
+                  {"version", version}, {"author", author}, {"description", description},
+                  {"params", params}};
+    if (kind == "source")
+        o["output_cap"] = output_cap;
+    if (!error.isEmpty())
+        o["error"] = error;
+    return o;
+}
+
+PluginRunRequest PluginRunRequest::from_json(const QJsonObject& o) {
+    PluginRunRequest r;
+    r.plugin = o["plugin"].toString();
+    for (const auto& v : o["values"].toArray())
+        r.values.append(v.isDouble() ? v.toDouble() : std::nan(""));
+    r.symbol = o["symbol"].toString().trimmed().toUpper();
+    r.days = std::clamp(o["days"].toInt(365), 5, 3650);
+    r.field = o["field"].toString("close");
+    r.params = o["params"].toObject();
+    return r;
+}
+
+PluginService::PluginService() : pool_(new QThreadPool) {
+    pool_->setMaxThreadCount(kMaxConcurrentRuns);
+    pool_->setObjectName("PluginPool");
+}
+
+PluginService& PluginService::instance() {
+    static PluginService s;
+    return s;
+}
+
+QString PluginService::plugins_dir() const {
+    return AppPaths::plugins();
+}
+
+QVector<PluginInfo> PluginService::plugins() const {
+    QVector<PluginInfo> out;
+    const QDir dir(plugins_dir());
+    for (const QFileInfo& fi : dir.entryInfoList({"*.wasm"}, QDir::Files, QDir::Name))
+        out.append(pl_read_manifest(fi));
+    return out;
+}
+
+QString PluginService::run(const PluginRunRequest& request) {
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    auto fail = [this, request_id](const QString& msg) {
+        QMetaObject::invokeMethod(
+            this, [this, request_id, msg]() { emit error_occurred(request_id, msg); }, Qt::QueuedConnection);
+        return request_id;
+    };
+
+    PluginInfo info;
+    for (const auto& p : plugins()) {
+        if (p.id == request.plugin)
+            info = p;
+    }
+    if (info.id.isEmpty())
+        return fail(QString("No plugin '%1' in %2").arg(request.plugin, plugins_dir()));
+    if (!info.error.isEmpty())
+        return fail(QString("Plugin '%1' cannot run: %2").arg(info.id, info.error));
+
+    if (info.kind == "source" || !request.values.isEmpty()) {
+        if (request.values.size() > kMaxValues)
+            return fail(QString("At most %1 input values").arg(kMaxValues));
+        execute(request_id, info, request, {});
+        return request_id;
+    }
+    if (request.symbol.isEmpty())
+        return fail("A transform needs 'values' or a 'symbol' to read a series from");
+
+    algo::CandleDataFetcher::instance().fetch(
+        request.symbol, "1d", request.days, algo::DataSource::YFinance, {}, {},
+        [this, request_id, info, request](bool success, const QVector<algo::OhlcvCandle>& candles,
+                                          const QString& error) {
+            if (!success || candles.isEmpty()) {
+                // Queued: the fetcher calls back synchronously for continuous
+                // futures and custom indices, before run() has returned the id.
+                const QString why = error.isEmpty() ? QStringLiteral("empty") : error;
+                QMetaObject::invokeMethod(
+                    this,
+                    [this, request_id, msg = QString("No %1 history: %2").arg(request.symbol, why)]() {
+                        emit error_occurred(request_id, msg);
+                    },
+                    Qt::QueuedConnection);
+                return;
+            }
+            PluginRunRequest r = request;
+            QVector<qint64> times;
+            for (const auto& c : candles) {
+                r.values.append(pl_field(c, r.field));
+                times.append(c.open_time);
+            }
+            execute(request_id, info, r, times);
+        });
+    return request_id;
+}
+
+void PluginService::execute(const QString& request_id, const PluginInfo& info, const PluginRunRequest& request,
+                            const QVector<qint64>& times) {
+    // Whichever of the worker and the deadline gets here first reports.
+    auto settled = std::make_shared<bool>(false);
+    QTimer::singleShot(kRunTimeoutMs, this, [this, request_id, settled, id = info.id]() {
+        if (*settled)
+            return;
+        *settled = true;
+        LOG_WARN("PluginService", QString("%1 timed out").arg(id));
+        emit error_occurred(request_id, QString("%1: did not finish within %2 s").arg(id).arg(kRunTimeoutMs / 1000));
+    });
+
+    (void)QtConcurrent::run(pool_, [this, request_id, info, request, times, settled]() {
+        QVector<double> out;
+        QString error;
+        const bool ok = pl_execute(info, request.values, request.params, &out, &error);
+
+        QJsonObject result;
+        if (ok) {
+            QJsonArray values, stamps;
+            for (double v : out)
+                values.append(std::isfinite(v) ? QJsonValue(v) : QJsonValue());
+            result = QJsonObject{{"plugin", info.id},
+                                 {"kind", info.kind},
+                                 {"input_count", int(request.values.size())},
+                                 {"count", int(out.size())},
+                                 {"values", values}};
+            if (!request.symbol.isEmpty() && !times.isEmpty()) {
+                result["symbol"] = request.symbol;
+                result["field"] = request.field;
+                // Outputs align with the tail of the input when shorter.
+                const int offset = int(times.size() - out.size());
+                for (int i = 0; i < out.size() && offset >= 0; ++i)
+                    stamps.append(QDateTime::fromMSecsSinceEpoch(times[offset + i], QTimeZone::UTC)
+                                      .date()
+                                      .toString(Qt::ISODate));
+                if (!stamps.isEmpty())
+                    result["dates"] = stamps;
+            }
+        }
+        QMetaObject::invokeMethod(
+            this,
+            [this, request_id, ok, result, error, settled, id = info.id]() {
+                if (*settled)
+                    return;
+                *settled = true;
+                if (ok) {
+                    LOG_INFO("PluginService", QString("%1 returned %2 values").arg(id).arg(result["count"].toInt()));
+                    emit result_ready(request_id, result);
+                } else {
+                    LOG_WARN("PluginService", QString("%1 failed: %2").arg(id, error));
+                    emit error_occurred(request_id, QString("%1: %2").arg(id, error));
+                }
+            },
+            Qt::QueuedConnection);
+    });
+}
+
+} // namespace fincept::services::plugins
@@ -0,0 +1,100 @@
+// src/services/plugins/PluginService.h
+#pragma once
+#include <QJsonObject>
+#include <QObject>
+#include <QString>
+#include <QVector>
+
+class QThreadPool;
+
+namespace fincept::services::plugins {
+
+/// A plugin found in the plugins directory: `<id>.wasm` plus its `<id>.json`
+/// manifest.
+struct PluginInfo {
+    QString id; // file base name
+    QString name;
+    QString kind; // indicator | transform | source
+    QString version;
+    QString author;
+    QString description;
+    QJsonObject params; // name → {type, default, description}; informational
+    int output_cap = 0; // source plugins: max values returned
+    QString wasm_path;
+    QString error; // manifest problem; the plugin is listed but cannot run
+
+    QJsonObject to_json() const;
+};
+
+/// One plugin run. Transforms take `values`, or the daily `field` series of
+/// `symbol` over the last `days` when `values` is empty; sources take none.
+struct PluginRunRequest {
+    QString plugin;
+    QVector<double> values;
+    QString symbol;
+    int days = 365;
+    QString field = "close"; // open | high | low | close | volume
+    QJsonObject params;
+
+    static PluginRunRequest from_json(const QJsonObject& o);
+};
+
+/// Community indicators, series transforms and data sources as sandboxed
+/// WebAssembly modules, discovered from AppPaths::plugins().
+///
+/// Plugin ABI — every export takes and returns i32; data moves through the
+/// module's own linear memory:
+///
+///   memory                                  the exported linear memory
+///   alloc(size) -> ptr                      scratch buffer for the host
+///   transform(in, n, params, params_len, out) -> count
+///       indicator / transform: reads n f64 from `in`, writes up to n f64
+///       (NaN for warm-up bars) to `out`
+///   source(params, params_len, out, cap) -> count
+///       source: writes up to `cap` f64 to `out`
+///
+/// `params` is the UTF-8 JSON of the request params. A negative count is a
+/// plugin error code. Values are little-endian IEEE doubles.
+///
+/// Modules run in a WasmSandbox (no imports, so no I/O of their own; capped
+/// linear memory), one fresh instance per run, on a pool of their own capped
+/// at kMaxConcurrentRuns threads. A run that has not finished after
+/// kRunTimeoutMs is reported as failed; the interpreter cannot be stopped, so
+/// its thread stays busy until the module returns. Main thread only.
+class PluginService : public QObject {
+    Q_OBJECT
+  public:
+    static constexpr int kMaxValues = 1'000'000;
+    static constexpr int kDefaultSourceCap = 10'000;
+    static constexpr int kMaxConcurrentRuns = 2;
+    static constexpr int kRunTimeoutMs = 30'000;
+
+    static PluginService& instance();
+
+    QString plugins_dir() const;
+
+    /// Rescans the plugins directory (cheap; manifests only).
+    QVector<PluginInfo> plugins() const;
+
+    /// Starts a run; returns its request id, which result_ready and
+    /// error_occurred carry.
+    QString run(const PluginRunRequest& request);
+
+  signals:
+    /// {plugin, kind, input_count, count, values[] (null = NaN), and for a
+    /// symbol series: symbol, field, dates[] aligned with values}
+    void result_ready(QString request_id, QJsonObject result);
+    void error_occurred(QString request_id, QString message);
+
+  private:
+    PluginService();
+    Q_DISABLE_COPY(PluginService)
+
+    void execute(const QString& request_id, const PluginInfo& info, const PluginRunRequest& request,
+                 const QVector<qint64>& times);
+
+    // Never deleted: its destructor would wait on a module that never returns.
+    QThreadPool* pool_ = nullptr;
+};
+
+} // namespace fincept::services::plugins
@@ -0,0 +1,177 @@
+// src/services/plugins/WasmSandbox.cpp
+#include "services/plugins/WasmSandbox.h"
+
+#include <wasm3.h>
+
+#include <cstring>
+#include <vector>
+
+namespace fincept::services::plugins {
+
+namespace {
+
+/// Unsigned LEB128 at `pos`; false past the end or over 5 bytes.
+bool ws_read_uleb(const QByteArray& b, int& pos, uint32_t* out) {
+    uint32_t value = 0;
+    for (int shift = 0; shift < 35; shift += 7) {
+        if (pos >= b.size())
+            return false;
+        const auto byte = static_cast<uint8_t>(b[pos++]);
+        value |= static_cast<uint32_t>(byte & 0x7f) << shift;
+        if (!(byte & 0x80)) {
+            *out = value;
+            return true;
+        }
+    }
+    return false;
+}
+
+/// Number of entries in the module's import section (id 2), or -1 when the
+/// section table is malformed. wasm3 resolves imports lazily, so this is
+/// checked up front to refuse modules that expect host functions.
+int ws_import_count(const QByteArray& wasm) {
+    int pos = 8; // magic + version
+    while (pos < wasm.size()) {
+        const auto id = static_cast<uint8_t>(wasm[pos++]);
+        uint32_t size = 0;
+        if (!ws_read_uleb(wasm, pos, &size) || size > static_cast<uint32_t>(wasm.size() - pos))
+            return -1;
+        if (id == 2) {
+            uint32_t count = 0;
+            int p = pos;
+            return ws_read_uleb(wasm, p, &count) ? static_cast<int>(count) : -1;
+        }
+        pos += static_cast<int>(size);
+    }
+    return 0;
+}
+
+} // namespace
+
+WasmSandbox::WasmSandbox(QByteArray wasm) : wasm_(std::move(wasm)) {}
+
+WasmSandbox::~WasmSandbox() {
+    if (module_ && !loaded_)
+        m3_FreeModule(module_);
+    if (runtime_)
+        m3_FreeRuntime(runtime_);
+    if (env_)
+        m3_FreeEnvironment(env_);
+}
+
+bool WasmSandbox::load(QString* error) {
+    if (wasm_.size() > kMaxModuleBytes) {
+        *error = QString("module is larger than %1 MB").arg(kMaxModuleBytes / (1024 * 1024));
+        return false;
+    }
+    if (wasm_.size() < 8 || std::memcmp(wasm_.constData(), "\0asm", 4) != 0) {
+        *error = "not a WebAssembly module";
+        return false;
+    }
+    const int imports = ws_import_count(wasm_);
+    if (imports != 0) {
+        *error = imports < 0 ? QString("malformed module") : QString("module imports %1 host functions; plugins "
+                                                                       "must be self-contained")
+                                                                   .arg(imports);
+        return false;
+    }
+
+    env_ = m3_NewEnvironment();
+    runtime_ = env_ ? m3_NewRuntime(env_, kStackBytes, nullptr) : nullptr;
+    if (!runtime_) {
+        *error = "out of memory creating the interpreter";
+        return false;
+    }
+    M3Result r = m3_ParseModule(env_, &module_, reinterpret_cast<const uint8_t*>(wasm_.constData()),
+                                static_cast<uint32_t>(wasm_.size()));
+    if (r) {
+        module_ = nullptr;
+        *error = "parse failed: " + QString::fromUtf8(r);
+        return false;
+    }
+    r = m3_LoadModule(runtime_, module_);
+    if (r) {
+        *error = "load failed: " + QString::fromUtf8(r);
+        return false;
+    }
+    loaded_ = true;
+    return true;
+}
+
+bool WasmSandbox::has_export(const char* name) const {
+    if (!loaded_)
+        return false;
+    IM3Function f = nullptr;
+    return m3_FindFunction(&f, runtime_, name) == m3Err_none;
+}
+
+bool WasmSandbox::call(const char* name, std::initializer_list<int32_t> args, int32_t* result, QString* error) {
+    IM3Function f = nullptr;
+    if (!loaded_ || m3_FindFunction(&f, runtime_, name) != m3Err_none) {
+        *error = QString("module does not export %1()").arg(QString::fromLatin1(name));
+        return false;
+    }
+    if (m3_GetArgCount(f) != args.size() || m3_GetRetCount(f) != 1 || m3_GetRetType(f, 0) != c_m3Type_i32) {
+        *error = QString("%1() has the wrong signature (expects %2 i32 arguments, returns i32)")
+                     .arg(QString::fromLatin1(name))
+                     .arg(args.size());
+        return false;
+    }
+    for (uint32_t i = 0; i < m3_GetArgCount(f); ++i) {
+        if (m3_GetArgType(f, i) != c_m3Type_i32) {
+            *error = QString("%1() argument %2 is not i32").arg(QString::fromLatin1(name)).arg(i);
+            return false;
+        }
+    }
+
+    std::vector<int32_t> values(args);
+    std::vector<const void*> ptrs;
+    for (const int32_t& v : values)
+        ptrs.push_back(&v);
+    M3Result r = m3_Call(f, static_cast<uint32_t>(ptrs.size()), ptrs.data());
+    if (r) {
+        *error = QString("%1() trapped: %2").arg(QString::fromLatin1(name), last_error(r));
+        return false;
+    }
+    const void* ret_ptrs[] = {result};
+    r = m3_GetResults(f, 1, ret_ptrs);
+    if (r) {
+        *error = QString("%1() result: %2").arg(QString::fromLatin1(name), QString::fromUtf8(r));
+        return false;
+    }
+    return true;
+}
+
+bool WasmSandbox::write(uint32_t offset, const void* data, uint32_t len, QString* error) {
+    uint32_t size = 0;
+    uint8_t* mem = loaded_ ? m3_GetMemory(runtime_, &size, 0) : nullptr;
+    if (!mem || offset > size || len > size - offset) {
+        *error = QString("write of %1 bytes at %2 is outside module memory").arg(len).arg(offset);
+        return false;
+    }
+    std::memcpy(mem + offset, data, len);
+    return true;
+}
+
+bool WasmSandbox::read(uint32_t offset, void* data, uint32_t len, QString* error) {
+    uint32_t size = 0;
+    const uint8_t* mem = loaded_ ? m3_GetMemory(runtime_, &size, 0) : nullptr;
+    if (!mem || offset > size || len > size - offset) {
+        *error = QString("read of %1 bytes at %2 is outside module memory").arg(len).arg(offset);
+        return false;
+    }
+    std::memcpy(data, mem + offset, len);
+    return true;
+}
+
+QString WasmSandbox::last_error(const char* result) const {
+    M3ErrorInfo info;
+    std::memset(&info, 0, sizeof(info));
+    m3_GetErrorInfo(runtime_, &info);
+    QString msg = QString::fromUtf8(result);
+    if (info.message && *info.message)
+        msg += QString(" (%1)").arg(QString::fromUtf8(info.message));
+    return msg;
+}
+
+} // namespace fincept::services::plugins
@@ -0,0 +1,64 @@
+// src/services/plugins/WasmSandbox.h
+#pragma once
+#include <QByteArray>
+#include <QString>
+
+#include <cstdint>
+#include <initializer_list>
+
+struct M3Environment;
+struct M3Runtime;
+struct M3Module;
+
+namespace fincept::services::plugins {
+
+/// One instantiated WebAssembly module on the wasm3 interpreter.
+///
+/// The sandbox links no imports: a module sees only its own linear memory
+/// and the i32 arguments of each call, so it cannot touch files, the network
+/// or the terminal. A module that imports anything fails to load. Each
+/// instance owns its interpreter state — make one per run, on one thread.
+///
+/// Linear memory is capped at kMaxMemoryPages: wasm3 is built with
+/// d_m3MaxLinearMemoryPages set to the same value (CMakeLists.txt), so a
+/// larger initial memory fails to load and memory.grow past it returns -1.
+///
+/// Execution time is NOT bounded here — wasm3 has no fuel or interruption
+/// hook, so a module that never returns keeps the thread running it. Callers
+/// run it on a bounded pool and stop waiting after a deadline (PluginService).
+class WasmSandbox {
+  public:
+    static constexpr uint32_t kStackBytes = 256 * 1024;
+    static constexpr int kMaxModuleBytes = 16 * 1024 * 1024;
+    static constexpr uint32_t kMaxMemoryPages = 1024; // 64 MB of 64 KB pages
+
+    explicit WasmSandbox(QByteArray wasm);
+    ~WasmSandbox();
+    WasmSandbox(const WasmSandbox&) = delete;
+    WasmSandbox& operator=(const WasmSandbox&) = delete;
+
+    /// Parses and instantiates the module. False with `error` when it is not
+    /// valid wasm, too large, or needs imports.
+    bool load(QString* error);
+
+    bool has_export(const char* name) const;
+
+    /// Calls an exported function taking only i32 arguments and returning
+    /// one i32.
+    bool call(const char* name, std::initializer_list<int32_t> args, int32_t* result, QString* error);
+
+    /// Copies into / out of linear memory, bounds-checked.
+    bool write(uint32_t offset, const void* data, uint32_t len, QString* error);
+    bool read(uint32_t offset, void* data, uint32_t len, QString* error);
+
+  private:
+    QString last_error(const char* result) const;
+
+    QByteArray wasm_; // wasm3 keeps pointers into the module bytes
+    M3Environment* env_ = nullptr;
+    M3Runtime* runtime_ = nullptr;
+    M3Module* module_ = nullptr; // owned until loaded into runtime_
+    bool loaded_ = false;
+};
+
+} // namespace fincept::services::plugins
@@ -20,6 +20,7 @@ PolymarketWebSocket& PolymarketWebSocket::instance() {
 
 PolymarketWebSocket::PolymarketWebSocket() : QObject(nullptr) {
     ws_ = new WebSocketClient(this);
+    ws_->set_label("polymarket");
 
     connect(ws_, &WebSocketClient::connected, this, &PolymarketWebSocket::on_ws_connected);
     connect(ws_, &WebSocketClient::disconnected, this, &PolymarketWebSocket::on_ws_disconnected);
@@ -0,0 +1,321 @@
+// src/services/portfolio/BlackLitterman.cpp
+#include "services/portfolio/BlackLitterman.h"
+
+#include <QJsonArray>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::services {
+
+namespace {
+
+constexpr double kBlMinConfidence = 1e-3;
+
+/// Solves A·X = B in place (A m×m, B m×r, both row-major) by Gaussian
+/// elimination with partial pivoting. False when A is numerically singular.
+bool bl_solve(QVector<double> a, QVector<double>& b, int m, int r) {
+    double scale = 0.0;
+    for (double v : a)
+        scale = std::max(scale, std::abs(v));
+    const double eps = std::max(scale, 1e-300) * 1e-13;
+    for (int col = 0; col < m; ++col) {
+        int piv = col;
+        for (int i = col + 1; i < m; ++i)
+            if (std::abs(a[i * m + col]) > std::abs(a[piv * m + col]))
+                piv = i;
+        if (std::abs(a[piv * m + col]) <= eps)
+            return false;
+        if (piv != col) {
+            for (int j = 0; j < m; ++j)
+                std::swap(a[col * m + j], a[piv * m + j]);
+            for (int j = 0; j < r; ++j)
+                std::swap(b[col * r + j], b[piv * r + j]);
+        }
+        const double d = a[col * m + col];
+        for (int i = col + 1; i < m; ++i) {
+            const double f = a[i * m + col] / d;
+            if (f == 0.0)
+                continue;
+            for (int j = col; j < m; ++j)
+                a[i * m + j] -= f * a[col * m + j];
+            for (int j = 0; j < r; ++j)
+                b[i * r + j] -= f * b[col * r + j];
+        }
+    }
+    for (int col = m - 1; col >= 0; --col) {
+        const double d = a[col * m + col];
+        for (int j = 0; j < r; ++j) {
+            double s = b[col * r + j];
+            for (int k = col + 1; k < m; ++k)
+                s -= a[col * m + k] * b[k * r + j];
+            b[col * r + j] = s / d;
+        }
+    }
+    return true;
+}
+
+QJsonObject bl_by_symbol(const QStringList& symbols, const QVector<double>& v) {
+    QJsonObject o;
+    for (int i = 0; i < symbols.size() && i < v.size(); ++i)
+        o[symbols[i]] = v[i];
+    return o;
+}
+
+QJsonArray bl_matrix(const QVector<double>& m, int rows, int cols) {
+    QJsonArray out;
+    for (int i = 0; i < rows; ++i) {
+        QJsonArray row;
+        for (int j = 0; j < cols; ++j)
+            row.append(m[i * cols + j]);
+        out.append(row);
+    }
+    return out;
+}
+
+QStringList bl_symbol_list(const QJsonValue& v) {
+    QStringList out;
+    if (v.isString()) {
+        out << v.toString().trimmed().toUpper();
+    } else {
+        for (const auto& s : v.toArray())
+            out << s.toString().trimmed().toUpper();
+    }
+    out.removeAll(QString());
+    out.removeDuplicates();
+    return out;
+}
+
+} // namespace
+
+QVector<double> BlackLitterman::implied_returns(const QVector<double>& cov, const QVector<double>& weights,
+                                                double risk_aversion) {
+    const int n = int(weights.size());
+    QVector<double> pi(n, 0.0);
+    if (cov.size() != n * n)
+        return pi;
+    for (int i = 0; i < n; ++i) {
+        double s = 0.0;
+        for (int j = 0; j < n; ++j)
+            s += cov[i * n + j] * weights[j];
+        pi[i] = risk_aversion * s;
+    }
+    return pi;
+}
+
+double BlackLitterman::implied_risk_aversion(const QVector<double>& cov, const QVector<double>& weights,
+                                             double market_return, double risk_free) {
+    const int n = int(weights.size());
+    if (cov.size() != n * n)
+        return 0.0;
+    double var = 0.0;
+    for (int i = 0; i < n; ++i)
+        for (int j = 0; j < n; ++j)
+            var += weights[i] * cov[i * n + j] * weights[j];
+    return var > 1e-12 ? (market_return - risk_free) / var : 0.0;
+}
+
+BlResult BlackLitterman::compute(const BlInputs& in) {
+    BlResult r;
+    const int n = int(in.symbols.size());
+    r.symbols = in.symbols;
+    r.tau = in.tau;
+    r.risk_aversion = in.risk_aversion;
+    if (n == 0 || in.cov.size() != n * n) {
+        r.error = "covariance does not match the symbol list";
+        return r;
+    }
+    if (in.tau <= 0.0 || in.risk_aversion <= 0.0) {
+        r.error = "tau and risk aversion must be positive";
+        return r;
+    }
+
+    // ── Prior ────────────────────────────────────────────────────────────────
+    r.market_weights = in.market_weights.size() == n ? in.market_weights : QVector<double>(n, 1.0);
+    double w_sum = 0.0;
+    for (auto& w : r.market_weights)
+        w_sum += w = std::max(0.0, w);
+    if (w_sum <= 1e-12) {
+        r.error = "market weights are all zero";
+        return r;
+    }
+    for (auto& w : r.market_weights)
+        w /= w_sum;
+    r.prior_cov = in.cov;
+    r.prior_returns = implied_returns(in.cov, r.market_weights, in.risk_aversion);
+
+    // ── Views → P, Q, Ω ─────────────────────────────────────────────────────
+    QVector<const BlView*> usable;
+    for (const auto& v : in.views) {
+        bool known = !v.picks.isEmpty();
+        for (auto it = v.picks.constBegin(); known && it != v.picks.constEnd(); ++it)
+            known = in.symbols.contains(it.key());
+        if (known)
+            usable.append(&v);
+        else
+            r.warnings << QString("View '%1' references a symbol outside the universe — ignored").arg(v.label);
+    }
+    const int k = int(usable.size());
+    r.pick.fill(0.0, k * n);
+    for (int v = 0; v < k; ++v) {
+        const BlView& view = *usable[v];
+        for (auto it = view.picks.constBegin(); it != view.picks.constEnd(); ++it)
+            r.pick[v * n + int(in.symbols.indexOf(it.key()))] += it.value();
+        r.view_labels << view.label;
+        r.view_returns << view.expected;
+        double pp = 0.0;
+        for (int i = 0; i < n; ++i)
+            pp += r.pick[v * n + i] * r.prior_returns[i];
+        r.view_prior << pp;
+    }
+
+    if (k == 0) {
+        // No views: the posterior is the prior with its estimation error.
+        r.posterior_returns = r.prior_returns;
+        r.posterior_cov = in.cov;
+        for (auto& c : r.posterior_cov)
+            c *= 1.0 + in.tau;
+    } else {
+        // ΣPᵀ (n×k) and PΣPᵀ (k×k).
+        QVector<double> sp(n * k, 0.0);
+        for (int i = 0; i < n; ++i)
+            for (int v = 0; v < k; ++v) {
+                double s = 0.0;
+                for (int j = 0; j < n; ++j)
+                    s += in.cov[i * n + j] * r.pick[v * n + j];
+                sp[i * k + v] = s;
+            }
+        QVector<double> a(k * k, 0.0);
+        for (int u = 0; u < k; ++u)
+            for (int v = 0; v < k; ++v) {
+                double s = 0.0;
+                for (int i = 0; i < n; ++i)
+                    s += r.pick[u * n + i] * sp[i * k + v];
+                a[u * k + v] = in.tau * s;
+            }
+        r.omega.resize(k);
+        for (int v = 0; v < k; ++v) {
+            const double c = std::clamp(usable[v]->confidence, kBlMinConfidence, 1.0);
+            r.omega[v] = a[v * k + v] * (1.0 - c) / c;
+            a[v * k + v] += r.omega[v];
+        }
+
+        // x = (PτΣPᵀ + Ω)⁻¹(Q − Pπ) and B = (PτΣPᵀ + Ω)⁻¹·PΣ, solved together.
+        QVector<double> rhs(k * (n + 1));
+        for (int v = 0; v < k; ++v) {
+            rhs[v * (n + 1)] = r.view_returns[v] - r.view_prior[v];
+            for (int j = 0; j < n; ++j)
+                rhs[v * (n + 1) + 1 + j] = sp[j * k + v];
+        }
+        if (!bl_solve(a, rhs, k, n + 1)) {
+            r.error = "views are linearly dependent — drop or merge the duplicates, or lower their confidence";
+            return r;
+        }
+
+        r.posterior_returns = r.prior_returns;
+        r.posterior_cov = in.cov;
+        for (int i = 0; i < n; ++i) {
+            for (int v = 0; v < k; ++v)
+                r.posterior_returns[i] += in.tau * sp[i * k + v] * rhs[v * (n + 1)];
+            for (int j = 0; j < n; ++j) {
+                double s = 0.0;
+                for (int v = 0; v < k; ++v)
+                    s += sp[i * k + v] * rhs[v * (n + 1) + 1 + j];
+                r.posterior_cov[i * n + j] += in.tau * in.cov[i * n + j] - in.tau * in.tau * s;
+            }
+        }
+        // Symmetrise away round-off.
+        for (int i = 0; i < n; ++i)
+            for (int j = i + 1; j < n; ++j) {
+                const double m = 0.5 * (r.posterior_cov[i * n + j] + r.posterior_cov[j * n + i]);
+                r.posterior_cov[i * n + j] = r.posterior_cov[j * n + i] = m;
+            }
+    }
+
+    QVector<double> scaled = r.posterior_cov;
+    for (auto& c : scaled)
+        c *= in.risk_aversion;
+    QVector<double> w = r.posterior_returns;
+    if (bl_solve(scaled, w, n, 1))
+        r.unconstrained_weights = w;
+    else
+        r.warnings << "posterior covariance is singular — unconstrained weights omitted";
+
+    r.ok = true;
+    return r;
+}
+
+bool BlackLitterman::view_from_json(const QJsonObject& o, BlView& out, QString* error) {
+    auto fail = [error](const QString& e) {
+        if (error)
+            *error = e;
+        return false;
+    };
+    if (!o.contains("return"))
+        return fail("view is missing 'return'");
+    out.expected = o.value("return").toDouble();
+    out.confidence = o.value("confidence").toDouble(0.5);
+    if (out.confidence <= 0.0 || out.confidence > 1.0)
+        return fail("view confidence must be in (0, 1]");
+    out.picks.clear();
+
+    const QString type = o.value("type").toString("absolute").toLower();
+    if (type == "absolute") {
+        const QString sym = o.value("asset").toString().trimmed().toUpper();
+        if (sym.isEmpty())
+            return fail("absolute view is missing 'asset'");
+        out.kind = BlView::Kind::Absolute;
+        out.picks.insert(sym, 1.0);
+        out.label = o.value("label").toString(QString("%1 = %2%").arg(sym).arg(out.expected * 100.0, 0, 'f', 1));
+        return true;
+    }
+    if (type != "relative")
+        return fail("view type must be 'absolute' or 'relative'");
+
+    const QStringList longs = bl_symbol_list(o.value("long"));
+    const QStringList shorts = bl_symbol_list(o.value("short"));
+    if (longs.isEmpty() || shorts.isEmpty())
+        return fail("relative view needs both 'long' and 'short' legs");
+    for (const auto& s : longs)
+        if (shorts.contains(s))
+            return fail(QString("%1 is on both legs of a relative view").arg(s));
+    out.kind = BlView::Kind::Relative;
+    for (const auto& s : longs)
+        out.picks.insert(s, 1.0 / longs.size());
+    for (const auto& s : shorts)
+        out.picks.insert(s, -1.0 / shorts.size());
+    out.label = o.value("label").toString(
+        QString("%1 vs %2 = %3%").arg(longs.join('+'), shorts.join('+')).arg(out.expected * 100.0, 0, 'f', 1));
+    return true;
+}
+
+QJsonObject BlackLitterman::result_to_json(const BlResult& r) {
+    if (!r.ok)
+        return QJsonObject{{"error", r.error}};
+    const int n = int(r.symbols.size());
+    const int k = int(r.view_returns.size());
+    QJsonArray views;
+    for (int v = 0; v < k; ++v)
+        views.append(QJsonObject{{"label", r.view_labels.value(v)},
+                                 {"expected", r.view_returns[v]},
+                                 {"prior_implied", r.view_prior[v]},
+                                 {"omega", r.omega.value(v)}});
+    QJsonObject out{{"symbols", QJsonArray::fromStringList(r.symbols)},
+                    {"tau", r.tau},
+                    {"risk_aversion", r.risk_aversion},
+                    {"market_weights", bl_by_symbol(r.symbols, r.market_weights)},
+                    {"prior_returns", bl_by_symbol(r.symbols, r.prior_returns)},
+                    {"posterior_returns", bl_by_symbol(r.symbols, r.posterior_returns)},
+                    {"views", views},
+                    {"P", bl_matrix(r.pick, k, n)},
+                    {"Q", bl_matrix(r.view_returns, k, 1)},
+                    {"omega", bl_matrix(r.omega, k, 1)},
+                    {"prior_covariance", bl_matrix(r.prior_cov, n, n)},
+                    {"posterior_covariance", bl_matrix(r.posterior_cov, n, n)},
+                    {"warnings", QJsonArray::fromStringList(r.warnings)}};
+    if (!r.unconstrained_weights.isEmpty())
+        out["unconstrained_weights"] = bl_by_symbol(r.symbols, r.unconstrained_weights);
+    return out;
+}
+
+} // namespace fincept::services
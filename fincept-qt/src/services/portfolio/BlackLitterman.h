@@ -0,0 +1,84 @@
+// src/services/portfolio/BlackLitterman.h
+#pragma once
+#include <QHash>
+#include <QJsonObject>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+namespace fincept::services {
+
+/// One investor view, i.e. one row of P with its Q and confidence.
+///   absolute — "AAPL returns 12%":            picks {AAPL: 1}
+///   relative — "AAPL+MSFT beat XOM by 3%":   picks {AAPL: .5, MSFT: .5, XOM: -1}
+/// Legs of a relative view are equal-weighted within each side.
+struct BlView {
+    enum class Kind { Absolute, Relative };
+    Kind kind = Kind::Absolute;
+    QString label;
+    QHash<QString, double> picks; // symbol → P entry
+    double expected = 0.0;        // Q, annual decimal
+    double confidence = 0.5;      // (0, 1]; 1 = the posterior honours the view exactly
+};
+
+struct BlInputs {
+    QStringList symbols;
+    QVector<double> cov;            // annualised prior Σ, row-major n×n
+    QVector<double> market_weights; // prior allocation (cap weights), normalised to 1
+    double risk_aversion = 2.5;     // δ
+    double tau = 0.05;              // τ, uncertainty of the prior relative to Σ
+    QVector<BlView> views;
+};
+
+/// Every intermediate of the model so the UI can show its working.
+struct BlResult {
+    bool ok = false;
+    QString error;
+    QStringList symbols;
+    QStringList warnings;
+
+    QVector<double> market_weights;
+    QVector<double> prior_cov; // Σ, n×n
+    double risk_aversion = 0.0;
+    double tau = 0.0;
+    QVector<double> prior_returns; // π = δ·Σ·w_mkt
+
+    QStringList view_labels;
+    QVector<double> pick;         // P, k×n row-major
+    QVector<double> view_returns; // Q
+    QVector<double> omega;        // diagonal of Ω
+    QVector<double> view_prior;   // P·π, what the prior already implies for each view
+
+    QVector<double> posterior_returns; // μ_BL
+    QVector<double> posterior_cov;     // Σ_BL = Σ + M, n×n
+    /// Unconstrained BL allocation (δ·Σ_BL)⁻¹·μ_BL — the market portfolio
+    /// tilted towards the views — before any optimiser constraints.
+    QVector<double> unconstrained_weights;
+};
+
+/// Black-Litterman posterior. Ω is diagonal with
+/// ω_k = τ·(PΣPᵀ)_kk·(1 − c_k)/c_k, so a view's confidence c_k scales how far
+/// the posterior moves from the prior towards it (c = 1 pins it exactly).
+/// The posterior is computed in the view space,
+///   μ_BL = π + τΣPᵀ(PτΣPᵀ + Ω)⁻¹(Q − Pπ)
+///   M    = τΣ − τΣPᵀ(PτΣPᵀ + Ω)⁻¹PτΣ
+/// which stays well defined when Ω is singular.
+class BlackLitterman {
+  public:
+    static BlResult compute(const BlInputs& in);
+
+    /// π = δ·Σ·w.
+    static QVector<double> implied_returns(const QVector<double>& cov, const QVector<double>& weights,
+                                           double risk_aversion);
+    /// δ = (E[r_m] − r_f) / σ²_m for the market portfolio `weights`.
+    static double implied_risk_aversion(const QVector<double>& cov, const QVector<double>& weights,
+                                        double market_return, double risk_free);
+
+    /// {type: "absolute", asset, return, confidence} or
+    /// {type: "relative", long: sym|[syms], short: sym|[syms], return, confidence}.
+    /// Returns false with `error` set when the view is malformed.
+    static bool view_from_json(const QJsonObject& o, BlView& out, QString* error);
+    static QJsonObject result_to_json(const BlResult& r);
+};
+
+} // namespace fincept::services
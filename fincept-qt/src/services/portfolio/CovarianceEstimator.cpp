@@ -0,0 +1,622 @@
+// src/services/portfolio/CovarianceEstimator.cpp
+#include "services/portfolio/CovarianceEstimator.h"
+
+#include <QJsonArray>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::services {
+
+namespace {
+
+using Mat = QVector<double>; // row-major
+
+struct cov_Centered {
+    int t = 0, n = 0;
+    QVector<double> mean;
+    Mat x; // T×n, demeaned
+};
+
+cov_Centered cov_center(const QVector<QVector<double>>& returns, int n) {
+    cov_Centered c;
+    c.t = int(returns.size());
+    c.n = n;
+    c.mean.fill(0.0, n);
+    for (const auto& row : returns)
+        for (int i = 0; i < n; ++i)
+            c.mean[i] += row[i];
+    for (auto& m : c.mean)
+        m /= std::max(c.t, 1);
+    c.x.resize(c.t * n);
+    for (int r = 0; r < c.t; ++r)
+        for (int i = 0; i < n; ++i)
+            c.x[r * n + i] = returns[r][i] - c.mean[i];
+    return c;
+}
+
+/// XᵀX / divisor.
+Mat cov_gram(const cov_Centered& c, double divisor) {
+    const int n = c.n;
+    Mat s(n * n, 0.0);
+    for (int r = 0; r < c.t; ++r) {
+        const double* row = c.x.constData() + r * n;
+        for (int i = 0; i < n; ++i) {
+            const double xi = row[i];
+            for (int j = i; j < n; ++j)
+                s[i * n + j] += xi * row[j];
+        }
+    }
+    for (int i = 0; i < n; ++i)
+        for (int j = i; j < n; ++j)
+            s[j * n + i] = s[i * n + j] /= divisor;
+    return s;
+}
+
+/// In-place Cholesky of an SPD matrix (lower triangle). False when not PD.
+bool cov_cholesky(Mat& a, int n) {
+    for (int j = 0; j < n; ++j) {
+        double d = a[j * n + j];
+        for (int k = 0; k < j; ++k)
+            d -= a[j * n + k] * a[j * n + k];
+        if (d <= 0.0 || !std::isfinite(d))
+            return false;
+        d = std::sqrt(d);
+        a[j * n + j] = d;
+        for (int i = j + 1; i < n; ++i) {
+            double s = a[i * n + j];
+            for (int k = 0; k < j; ++k)
+                s -= a[i * n + k] * a[j * n + k];
+            a[i * n + j] = s / d;
+        }
+    }
+    return true;
+}
+
+/// Solves L·Lᵀ·x = b given the Cholesky factor from cov_cholesky.
+void cov_chol_solve(const Mat& l, int n, QVector<double>& b) {
+    for (int i = 0; i < n; ++i) {
+        double s = b[i];
+        for (int k = 0; k < i; ++k)
+            s -= l[i * n + k] * b[k];
+        b[i] = s / l[i * n + i];
+    }
+    for (int i = n - 1; i >= 0; --i) {
+        double s = b[i];
+        for (int k = i + 1; k < n; ++k)
+            s -= l[k * n + i] * b[k];
+        b[i] = s / l[i * n + i];
+    }
+}
+
+/// Symmetric eigen-decomposition of a small k×k matrix by cyclic Jacobi.
+/// Returns eigenvalues descending; `vecs` columns are the eigenvectors.
+QVector<double> cov_jacobi(Mat a, int k, Mat& vecs) {
+    vecs.fill(0.0, k * k);
+    for (int i = 0; i < k; ++i)
+        vecs[i * k + i] = 1.0;
+    for (int sweep = 0; sweep < 100; ++sweep) {
+        double off = 0.0;
+        for (int p = 0; p < k; ++p)
+            for (int q = p + 1; q < k; ++q)
+                off += a[p * k + q] * a[p * k + q];
+        if (off < 1e-30)
+            break;
+        for (int p = 0; p < k; ++p)
+            for (int q = p + 1; q < k; ++q) {
+                const double apq = a[p * k + q];
+                if (std::abs(apq) < 1e-300)
+                    continue;
+                const double theta = (a[q * k + q] - a[p * k + p]) / (2.0 * apq);
+                const double t = (theta >= 0 ? 1.0 : -1.0) / (std::abs(theta) + std::sqrt(theta * theta + 1.0));
+                const double c = 1.0 / std::sqrt(t * t + 1.0), s = t * c;
+                for (int r = 0; r < k; ++r) {
+                    const double arp = a[r * k + p], arq = a[r * k + q];
+                    a[r * k + p] = c * arp - s * arq;
+                    a[r * k + q] = s * arp + c * arq;
+                }
+                for (int r = 0; r < k; ++r) {
+                    const double apr = a[p * k + r], aqr = a[q * k + r];
+                    a[p * k + r] = c * apr - s * aqr;
+                    a[q * k + r] = s * apr + c * aqr;
+                }
+                for (int r = 0; r < k; ++r) {
+                    const double vrp = vecs[r * k + p], vrq = vecs[r * k + q];
+                    vecs[r * k + p] = c * vrp - s * vrq;
+                    vecs[r * k + q] = s * vrp + c * vrq;
+                }
+            }
+    }
+    QVector<int> order(k);
+    for (int i = 0; i < k; ++i)
+        order[i] = i;
+    std::sort(order.begin(), order.end(), [&](int x, int y) { return a[x * k + x] > a[y * k + y]; });
+    QVector<double> vals(k);
+    Mat sorted(k * k);
+    for (int c = 0; c < k; ++c) {
+        vals[c] = a[order[c] * k + order[c]];
+        for (int r = 0; r < k; ++r)
+            sorted[r * k + c] = vecs[r * k + order[c]];
+    }
+    vecs = sorted;
+    return vals;
+}
+
+// ── Estimators (all on the per-period scale; annualised by the caller) ──────
+
+/// Ledoit & Wolf (2004): δ·μI + (1−δ)·S with S the MLE covariance.
+Mat cov_ledoit_wolf(const cov_Centered& c, double* shrinkage) {
+    const int n = c.n, t = c.t;
+    const Mat s = cov_gram(c, t);
+    double mu = 0.0;
+    for (int i = 0; i < n; ++i)
+        mu += s[i * n + i];
+    mu /= n;
+    double s_norm2 = 0.0, d2 = 0.0;
+    for (int i = 0; i < n; ++i)
+        for (int j = 0; j < n; ++j) {
+            const double v = s[i * n + j];
+            s_norm2 += v * v;
+            const double dv = v - (i == j ? mu : 0.0);
+            d2 += dv * dv;
+        }
+    // Σ_t ‖x_t x_tᵀ − S‖² = Σ_t ‖x_t‖⁴ − T‖S‖².
+    double sum_x4 = 0.0;
+    for (int r = 0; r < t; ++r) {
+        double sq = 0.0;
+        for (int i = 0; i < n; ++i)
+            sq += c.x[r * n + i] * c.x[r * n + i];
+        sum_x4 += sq * sq;
+    }
+    const double b2 = std::min(d2, std::max(0.0, (sum_x4 - t * s_norm2) / (double(t) * t)));
+    const double delta = d2 > 0.0 ? b2 / d2 : 0.0;
+    *shrinkage = delta;
+    Mat out = s;
+    for (int i = 0; i < n; ++i)
+        for (int j = 0; j < n; ++j)
+            out[i * n + j] = (1.0 - delta) * s[i * n + j] + (i == j ? delta * mu : 0.0);
+    return out;
+}
+
+/// Ledoit & Wolf (2003, "Honey, I shrunk…"): towards the constant-correlation
+/// matrix with the sample variances on the diagonal.
+Mat cov_constant_correlation(const cov_Centered& c, double* shrinkage) {
+    const int n = c.n, t = c.t;
+    const Mat s = cov_gram(c, t);
+    QVector<double> sd(n);
+    for (int i = 0; i < n; ++i)
+        sd[i] = std::sqrt(std::max(s[i * n + i], 0.0));
+    double rbar = 0.0;
+    for (int i = 0; i < n; ++i)
+        for (int j = i + 1; j < n; ++j)
+            if (sd[i] > 0 && sd[j] > 0)
+                rbar += s[i * n + j] / (sd[i] * sd[j]);
+    rbar = n > 1 ? 2.0 * rbar / (double(n) * (n - 1)) : 0.0;
+
+    Mat f(n * n);
+    for (int i = 0; i < n; ++i)
+        for (int j = 0; j < n; ++j)
+            f[i * n + j] = i == j ? s[i * n + i] : rbar * sd[i] * sd[j];
+
+    // π̂ = Σ_ij Var(x_i x_j), ρ̂ its part explained by the target, γ̂ = ‖F − S‖².
+    double pi = 0.0, rho = 0.0, gamma = 0.0;
+    for (int i = 0; i < n; ++i)
+        for (int j = 0; j < n; ++j) {
+            const double g = f[i * n + j] - s[i * n + j];
+            gamma += g * g;
+        }
+    QVector<double> x2(t);
+    for (int i = 0; i < n; ++i) {
+        for (int r = 0; r < t; ++r)
+            x2[r] = c.x[r * n + i] * c.x[r * n + i] - s[i * n + i];
+        for (int j = 0; j < n; ++j) {
+            double p = 0.0, theta_ii = 0.0, theta_jj = 0.0;
+            for (int r = 0; r < t; ++r) {
+                const double y = c.x[r * n + i] * c.x[r * n + j] - s[i * n + j];
+                p += y * y;
+                if (i != j) {
+                    theta_ii += x2[r] * y;
+                    const double xj = c.x[r * n + j];
+                    theta_jj += (xj * xj - s[j * n + j]) * y;
+                }
+            }
+            p /= t;
+            pi += p;
+            if (i == j) {
+                rho += p;
+            } else if (sd[i] > 0 && sd[j] > 0) {
+                rho += 0.5 * rbar * (sd[j] / sd[i] * theta_ii / t + sd[i] / sd[j] * theta_jj / t);
+            }
+        }
+    }
+    const double kappa = gamma > 0.0 ? (pi - rho) / gamma : 0.0;
+    const double delta = std::clamp(kappa / t, 0.0, 1.0);
+    *shrinkage = delta;
+    Mat out(n * n);
+    for (int k = 0; k < n * n; ++k)
+        out[k] = delta * f[k] + (1.0 - delta) * s[k];
+    return out;
+}
+
+Mat cov_ewma(const QVector<QVector<double>>& returns, int n, double lambda, double* n_eff) {
+    const int t = int(returns.size());
+    Mat out(n * n, 0.0);
+    double wsum = 0.0, w2 = 0.0;
+    for (int r = 0; r < t; ++r) {
+        const double w = std::pow(lambda, t - 1 - r);
+        wsum += w;
+        w2 += w * w;
+        const auto& row = returns[r];
+        for (int i = 0; i < n; ++i)
+            for (int j = i; j < n; ++j)
+                out[i * n + j] += w * row[i] * row[j];
+    }
+    for (int i = 0; i < n; ++i)
+        for (int j = i; j < n; ++j)
+            out[j * n + i] = out[i * n + j] /= wsum;
+    *n_eff = w2 > 0 ? wsum * wsum / w2 : 0.0;
+    return out;
+}
+
+Mat cov_semicovariance(const QVector<QVector<double>>& returns, int n) {
+    const int t = int(returns.size());
+    Mat out(n * n, 0.0);
+    QVector<double> d(n);
+    for (const auto& row : returns) {
+        for (int i = 0; i < n; ++i)
+            d[i] = std::min(row[i], 0.0);
+        for (int i = 0; i < n; ++i)
+            if (d[i] != 0.0)
+                for (int j = i; j < n; ++j)
+                    out[i * n + j] += d[i] * d[j];
+    }
+    for (int i = 0; i < n; ++i)
+        for (int j = i; j < n; ++j)
+            out[j * n + i] = out[i * n + j] /= t;
+    return out;
+}
+
+/// OLS time-series regression of every asset on the factor series.
+bool cov_explicit_factors(const cov_Centered& assets, const QVector<QVector<double>>& factors, int k,
+                          CovarianceEstimate& e, Mat& out) {
+    const int n = assets.n, t = assets.t;
+    const cov_Centered fc = cov_center(factors, k);
+    Mat ff = cov_gram(fc, 1.0); // FᵀF
+    Mat l = ff;
+    if (!cov_cholesky(l, k)) {
+        e.error = "factor return series are collinear";
+        return false;
+    }
+    e.loadings.fill(0.0, n * k);
+    e.specific_var.fill(0.0, n);
+    e.r_squared.fill(0.0, n);
+    QVector<double> b(k);
+    for (int i = 0; i < n; ++i) {
+        for (int f = 0; f < k; ++f) {
+            double s = 0.0;
+            for (int r = 0; r < t; ++r)
+                s += fc.x[r * k + f] * assets.x[r * n + i];
+            b[f] = s;
+        }
+        cov_chol_solve(l, k, b);
+        double ss_res = 0.0, ss_tot = 0.0;
+        for (int r = 0; r < t; ++r) {
+            double fit = 0.0;
+            for (int f = 0; f < k; ++f)
+                fit += b[f] * fc.x[r * k + f];
+            const double y = assets.x[r * n + i];
+            ss_res += (y - fit) * (y - fit);
+            ss_tot += y * y;
+        }
+        for (int f = 0; f < k; ++f)
+            e.loadings[i * k + f] = b[f];
+        e.specific_var[i] = ss_res / std::max(t - k - 1, 1);
+        e.r_squared[i] = ss_tot > 0 ? 1.0 - ss_res / ss_tot : 0.0;
+    }
+    e.factor_cov = ff;
+    for (auto& v : e.factor_cov)
+        v /= std::max(t - 1, 1);
+
+    out.fill(0.0, n * n);
+    QVector<double> bf(k);
+    for (int i = 0; i < n; ++i) {
+        for (int f = 0; f < k; ++f) {
+            double s = 0.0;
+            for (int g = 0; g < k; ++g)
+                s += e.loadings[i * k + g] * e.factor_cov[g * k + f];
+            bf[f] = s;
+        }
+        for (int j = i; j < n; ++j) {
+            double s = 0.0;
+            for (int f = 0; f < k; ++f)
+                s += bf[f] * e.loadings[j * k + f];
+            out[i * n + j] = out[j * n + i] = s;
+        }
+        out[i * n + i] += e.specific_var[i];
+    }
+    return true;
+}
+
+/// Statistical factor model: top-k principal components of the sample
+/// covariance (subspace iteration + Rayleigh-Ritz) plus a diagonal residual.
+void cov_pca_factors(const cov_Centered& c, int k, CovarianceEstimate& e, Mat& out) {
+    const int n = c.n;
+    const Mat s = cov_gram(c, std::max(c.t - 1, 1));
+    // Deterministic, well-spread start so runs are reproducible.
+    Mat q(n * k);
+    for (int i = 0; i < n; ++i)
+        for (int f = 0; f < k; ++f)
+            q[i * k + f] = std::cos(0.7 * (i + 1) * (f + 1)) + (i % (f + 2) == 0 ? 1.0 : 0.0);
+    auto orthonormalise = [&](Mat& m) {
+        for (int f = 0; f < k; ++f) {
+            for (int g = 0; g < f; ++g) {
+                double d = 0.0;
+                for (int i = 0; i < n; ++i)
+                    d += m[i * k + f] * m[i * k + g];
+                for (int i = 0; i < n; ++i)
+                    m[i * k + f] -= d * m[i * k + g];
+            }
+            double nrm = 0.0;
+            for (int i = 0; i < n; ++i)
+                nrm += m[i * k + f] * m[i * k + f];
+            nrm = std::sqrt(nrm);
+            for (int i = 0; i < n; ++i)
+                m[i * k + f] = nrm > 0 ? m[i * k + f] / nrm : 0.0;
+        }
+    };
+    auto mul = [&](const Mat& m) {
+        Mat z(n * k, 0.0);
+        for (int i = 0; i < n; ++i)
+            for (int j = 0; j < n; ++j) {
+                const double sij = s[i * n + j];
+                for (int f = 0; f < k; ++f)
+                    z[i * k + f] += sij * m[j * k + f];
+            }
+        return z;
+    };
+    orthonormalise(q);
+    for (int it = 0; it < 300; ++it) {
+        Mat z = mul(q);
+        orthonormalise(z);
+        double change = 0.0;
+        for (int f = 0; f < k; ++f) {
+            double d = 0.0;
+            for (int i = 0; i < n; ++i)
+                d += z[i * k + f] * q[i * k + f];
+            change = std::max(change, 1.0 - std::abs(d));
+        }
+        q = z;
+        if (change < 1e-12)
+            break;
+    }
+    // Rayleigh-Ritz on the converged subspace.
+    const Mat sq = mul(q);
+    Mat h(k * k, 0.0);
+    for (int f = 0; f < k; ++f)
+        for (int g = 0; g < k; ++g)
+            for (int i = 0; i < n; ++i)
+                h[f * k + g] += q[i * k + f] * sq[i * k + g];
+    Mat rot;
+    const QVector<double> lambda = cov_jacobi(h, k, rot);
+
+    double trace = 0.0, top = 0.0;
+    for (int i = 0; i < n; ++i)
+        trace += s[i * n + i];
+    e.loadings.fill(0.0, n * k);
+    for (int f = 0; f < k; ++f) {
+        const double lf = std::max(lambda[f], 0.0);
+        top += lf;
+        const double root = std::sqrt(lf);
+        for (int i = 0; i < n; ++i) {
+            double v = 0.0;
+            for (int g = 0; g < k; ++g)
+                v += q[i * k + g] * rot[g * k + f];
+            e.loadings[i * k + f] = v * root;
+        }
+    }
+    e.factor_cov.fill(0.0, k * k);
+    for (int f = 0; f < k; ++f)
+        e.factor_cov[f * k + f] = 1.0;
+    e.explained_variance = trace > 0 ? top / trace : 0.0;
+
+    out.fill(0.0, n * n);
+    e.specific_var.fill(0.0, n);
+    e.r_squared.fill(0.0, n);
+    for (int i = 0; i < n; ++i) {
+        for (int j = i; j < n; ++j) {
+            double v = 0.0;
+            for (int f = 0; f < k; ++f)
+                v += e.loadings[i * k + f] * e.loadings[j * k + f];
+            out[i * n + j] = out[j * n + i] = v;
+        }
+        // Keep a sliver of specific risk so the matrix stays invertible.
+        const double sii = s[i * n + i];
+        e.specific_var[i] = std::max(sii - out[i * n + i], 1e-4 * sii);
+        e.r_squared[i] = sii > 0 ? std::clamp(out[i * n + i] / sii, 0.0, 1.0) : 0.0;
+        out[i * n + i] += e.specific_var[i];
+    }
+    for (int f = 0; f < k; ++f)
+        e.factor_names << QString("PC%1").arg(f + 1);
+}
+
+QJsonObject cov_by_symbol(const QStringList& symbols, const QVector<double>& v) {
+    QJsonObject o;
+    for (int i = 0; i < symbols.size() && i < v.size(); ++i)
+        o[symbols[i]] = v[i];
+    return o;
+}
+
+QJsonArray cov_matrix_json(const QVector<double>& m, int rows, int cols) {
+    QJsonArray out;
+    for (int i = 0; i < rows; ++i) {
+        QJsonArray row;
+        for (int j = 0; j < cols; ++j)
+            row.append(m[i * cols + j]);
+        out.append(row);
+    }
+    return out;
+}
+
+} // namespace
+
+CovarianceEstimate CovarianceEstimator::estimate(const QStringList& symbols, const QVector<QVector<double>>& returns,
+                                                 const CovarianceOptions& options) {
+    CovarianceEstimate e;
+    e.method = options.method;
+    e.symbols = symbols;
+    const int n = int(symbols.size());
+    const int t = int(returns.size());
+    e.observations = t;
+    e.effective_observations = t;
+    if (n == 0) {
+        e.error = "no symbols";
+        return e;
+    }
+    if (t < kMinObservations) {
+        e.error = QString("need at least %1 return observations, got %2").arg(kMinObservations).arg(t);
+        return e;
+    }
+    for (const auto& row : returns)
+        if (row.size() != n) {
+            e.error = "return rows do not match the symbol list";
+            return e;
+        }
+
+    const cov_Centered c = cov_center(returns, n);
+    Mat cov;
+    switch (options.method) {
+        case CovarianceMethod::LedoitWolf:
+            cov = cov_ledoit_wolf(c, &e.shrinkage);
+            break;
+        case CovarianceMethod::ConstantCorrelation:
+            cov = cov_constant_correlation(c, &e.shrinkage);
+            break;
+        case CovarianceMethod::Ewma: {
+            const double lambda = std::clamp(options.ewma_lambda, 0.5, 0.9999);
+            cov = cov_ewma(returns, n, lambda, &e.effective_observations);
+            if (e.effective_observations < n)
+                e.warnings << QString("EWMA effective sample (%1) is below the number of assets — expect a "
+                                      "near-singular matrix; raise lambda")
+                                  .arg(e.effective_observations, 0, 'f', 0);
+            break;
+        }
+        case CovarianceMethod::Semicovariance:
+            cov = cov_semicovariance(returns, n);
+            break;
+        case CovarianceMethod::Factor: {
+            const int k = int(options.factor_names.size());
+            if (k > 0) {
+                if (options.factor_returns.size() != t) {
+                    e.error = "factor returns are not aligned with the asset returns";
+                    return e;
+                }
+                e.factor_names = options.factor_names;
+                if (!cov_explicit_factors(c, options.factor_returns, k, e, cov))
+                    return e;
+            } else {
+                const int kk = std::clamp(options.pca_factors, 1, std::max(1, n - 1));
+                cov_pca_factors(c, kk, e, cov);
+            }
+            break;
+        }
+        case CovarianceMethod::Sample:
+        default:
+            cov = cov_gram(c, t - 1);
+            break;
+    }
+
+    const double ann = options.periods_per_year;
+    for (auto& v : cov)
+        v *= ann;
+    for (auto& v : e.specific_var)
+        v *= ann;
+    if (options.method == CovarianceMethod::Factor && options.factor_names.isEmpty()) {
+        // PCA factors have unit variance, so the loadings carry the scale.
+        for (auto& v : e.loadings)
+            v *= std::sqrt(ann);
+    } else {
+        for (auto& v : e.factor_cov)
+            v *= ann;
+    }
+    e.cov = cov;
+
+    Mat l = cov;
+    e.positive_definite = cov_cholesky(l, n);
+    if (!e.positive_definite)
+        e.warnings << "covariance is not positive definite — more history or a shrinkage/factor estimator "
+                      "will stabilise it";
+    if (options.method == CovarianceMethod::Sample && t < 2 * n)
+        e.warnings << QString("only %1 observations for %2 assets — sample covariance is noisy; consider "
+                              "ledoit_wolf")
+                          .arg(t)
+                          .arg(n);
+    e.ok = true;
+    return e;
+}
+
+CovarianceMethod CovarianceEstimator::method_from_string(const QString& s) {
+    const QString k = s.trimmed().toLower().replace('-', '_').replace(' ', '_');
+    if (k == "ledoit_wolf" || k == "lw" || k == "shrinkage")
+        return CovarianceMethod::LedoitWolf;
+    if (k == "constant_correlation" || k == "ledoit_wolf_constant_correlation")
+        return CovarianceMethod::ConstantCorrelation;
+    if (k == "exponential" || k == "ewma" || k == "exp_cov")
+        return CovarianceMethod::Ewma;
+    if (k == "semicovariance" || k == "semi")
+        return CovarianceMethod::Semicovariance;
+    if (k == "factor" || k == "factor_model" || k == "pca")
+        return CovarianceMethod::Factor;
+    return CovarianceMethod::Sample;
+}
+
+QString CovarianceEstimator::method_to_string(CovarianceMethod m) {
+    switch (m) {
+        case CovarianceMethod::LedoitWolf:
+            return "ledoit_wolf";
+        case CovarianceMethod::ConstantCorrelation:
+            return "constant_correlation";
+        case CovarianceMethod::Ewma:
+            return "ewma";
+        case CovarianceMethod::Semicovariance:
+            return "semicovariance";
+        case CovarianceMethod::Factor:
+            return "factor";
+        case CovarianceMethod::Sample:
+        default:
+            return "sample";
+    }
+}
+
+QJsonObject CovarianceEstimator::estimate_to_json(const CovarianceEstimate& e, bool include_matrix) {
+    if (!e.ok)
+        return QJsonObject{{"error", e.error}};
+    const int n = int(e.symbols.size());
+    QVector<double> vol(n);
+    for (int i = 0; i < n; ++i)
+        vol[i] = std::sqrt(std::max(0.0, e.cov[i * n + i]));
+    QJsonObject out{{"method", method_to_string(e.method)},
+                    {"symbols", QJsonArray::fromStringList(e.symbols)},
+                    {"observations", e.observations},
+                    {"effective_observations", e.effective_observations},
+                    {"positive_definite", e.positive_definite},
+                    {"volatility", cov_by_symbol(e.symbols, vol)},
+                    {"warnings", QJsonArray::fromStringList(e.warnings)}};
+    if (e.shrinkage >= 0)
+        out["shrinkage"] = e.shrinkage;
+    if (!e.factor_names.isEmpty()) {
+        const int k = int(e.factor_names.size());
+        QJsonObject factor{{"factors", QJsonArray::fromStringList(e.factor_names)},
+                           {"loadings", cov_matrix_json(e.loadings, n, k)},
+                           {"factor_covariance", cov_matrix_json(e.factor_cov, k, k)},
+                           {"specific_variance", cov_by_symbol(e.symbols, e.specific_var)},
+                           {"r_squared", cov_by_symbol(e.symbols, e.r_squared)}};
+        if (e.explained_variance > 0)
+            factor["explained_variance"] = e.explained_variance;
+        out["factor_model"] = factor;
+    }
+    if (include_matrix)
+        out["covariance"] = cov_matrix_json(e.cov, n, n);
+    return out;
+}
+
+} // namespace fincept::services
@@ -0,0 +1,77 @@
+// src/services/portfolio/CovarianceEstimator.h
+#pragma once
+#include <QJsonObject>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+namespace fincept::services {
+
+enum class CovarianceMethod {
+    Sample,              // unbiased sample covariance
+    LedoitWolf,          // shrinkage towards a scaled identity (Ledoit-Wolf 2004)
+    ConstantCorrelation, // shrinkage towards constant correlation (Ledoit-Wolf 2003)
+    Ewma,                // RiskMetrics exponentially weighted, zero mean
+    Semicovariance,      // downside only, returns below 0
+    Factor,              // B·Σf·Bᵀ + D, explicit factor series or PCA
+};
+
+struct CovarianceOptions {
+    CovarianceMethod method = CovarianceMethod::Sample;
+    int periods_per_year = 252;
+    double ewma_lambda = 0.94;
+    /// Factor: explicit factor return series (rows aligned with the asset
+    /// returns, columns = factor_names). Empty → statistical (PCA) factors.
+    QStringList factor_names;
+    QVector<QVector<double>> factor_returns;
+    int pca_factors = 3;
+};
+
+/// Annualised covariance plus the diagnostics of how it was built.
+struct CovarianceEstimate {
+    bool ok = false;
+    QString error;
+    CovarianceMethod method = CovarianceMethod::Sample;
+    QStringList symbols;
+    QVector<double> cov; // n×n row-major, annualised
+    int observations = 0;
+    double effective_observations = 0.0; // EWMA: 1/Σw²; otherwise = observations
+    double shrinkage = -1.0;             // Ledoit-Wolf intensity in [0, 1]; −1 when n/a
+    bool positive_definite = false;
+
+    // Factor models.
+    QStringList factor_names;
+    QVector<double> loadings;        // n×k
+    QVector<double> factor_cov;      // k×k, annualised
+    QVector<double> specific_var;    // n, annualised
+    QVector<double> r_squared;       // n, share of each asset's variance the factors explain
+    double explained_variance = 0.0; // PCA: top-k eigenvalues / trace
+
+    QStringList warnings;
+};
+
+/// Covariance estimators over a T×n matrix of periodic simple returns
+/// (rows = dates, oldest first). The sample covariance is noisy whenever the
+/// universe is not small against the history; the shrinkage, EWMA and factor
+/// estimators trade a little bias for a far more stable matrix, which is
+/// what the optimiser and ex-ante risk numbers are sensitive to.
+class CovarianceEstimator {
+  public:
+    static CovarianceEstimate estimate(const QStringList& symbols, const QVector<QVector<double>>& returns,
+                                       const CovarianceOptions& options = {});
+
+    /// Accepts the optimisation screen's risk-model names ("sample_covariance",
+    /// "ledoit-wolf", "exponential", "semicovariance") and the short forms
+    /// ("sample", "lw", "ewma", "constant_correlation", "factor", "pca").
+    /// Unknown → Sample.
+    static CovarianceMethod method_from_string(const QString& s);
+    static QString method_to_string(CovarianceMethod m);
+
+    /// Diagnostics, plus the matrix itself when `include_matrix`.
+    static QJsonObject estimate_to_json(const CovarianceEstimate& e, bool include_matrix);
+
+    /// Returns/factor series shorter than this are rejected.
+    static constexpr int kMinObservations = 20;
+};
+
+} // namespace fincept::services
@@ -0,0 +1,383 @@
+// src/services/portfolio/DividendService.cpp
+#include "services/portfolio/DividendService.h"
+
+#include "core/logging/Logger.h"
+#include "python/PythonRunner.h"
+#include "services/notifications/NotificationService.h"
+#include "storage/repositories/DividendRepository.h"
+#include "storage/repositories/PortfolioRepository.h"
+
+#include <QDateTime>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QJsonObject>
+#include <QPointer>
+#include <QSet>
+#include <QTimer>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::services {
+
+namespace {
+
+constexpr int kHistoryWindowDays = 2 * 365; // cadence inferred from the last two years
+constexpr int kDefaultPayLagDays = 14;      // ex → pay when no declared pay date exists
+
+/// Payments per year from the median gap between consecutive ex-dates.
+int infer_payments_per_year(const QVector<QDate>& ex_dates, int* median_gap_days) {
+    if (ex_dates.size() < 2) {
+        *median_gap_days = 365;
+        return ex_dates.isEmpty() ? 0 : 1;
+    }
+    QVector<qint64> gaps;
+    for (int i = 1; i < ex_dates.size(); ++i)
+        gaps.append(ex_dates[i - 1].daysTo(ex_dates[i]));
+    std::sort(gaps.begin(), gaps.end());
+    const qint64 median = gaps[gaps.size() / 2];
+    *median_gap_days = static_cast<int>(median);
+    if (median <= 45)
+        return 12;
+    if (median <= 120)
+        return 4;
+    if (median <= 240)
+        return 2;
+    return 1;
+}
+
+} // namespace
+
+DividendService& DividendService::instance() {
+    static DividendService s;
+    return s;
+}
+
+void DividendService::start() {
+    if (refresh_timer_)
+        return;
+    refresh_timer_ = new QTimer(this);
+    refresh_timer_->setInterval(kRefreshMs);
+    connect(refresh_timer_, &QTimer::timeout, this, &DividendService::refresh_all);
+    refresh_timer_->start();
+    // First pass shortly after startup so reminders don't wait 6 hours, but
+    // off the critical path of window creation.
+    QTimer::singleShot(60 * 1000, this, &DividendService::refresh_all);
+}
+
+// ── Sync ─────────────────────────────────────────────────────────────────────
+
+void DividendService::fetch_and_store(const QStringList& symbols, std::function<void(QString)> done) {
+    QPointer<DividendService> self = this;
+    python::PythonRunner::instance().run(
+        "yfinance_data.py", QStringList{"dividends"} + symbols, [self, done](python::PythonResult result) {
+            if (!self)
+                return;
+            if (!result.success) {
+                done(result.error.isEmpty() ? QStringLiteral("Dividend fetch failed") : result.error.left(300));
+                return;
+            }
+            const auto doc = QJsonDocument::fromJson(python::extract_json(result.output).toUtf8());
+            if (!doc.isObject() || doc.object().contains("error")) {
+                done(doc.isObject() ? doc.object().value("error").toString() : QStringLiteral("Invalid response"));
+                return;
+            }
+
+            QVector<portfolio::DividendEvent> events;
+            const QJsonObject data = doc.object().value("data").toObject();
+            for (auto it = data.constBegin(); it != data.constEnd(); ++it) {
+                const QString sym = it.key();
+                const QJsonObject o = it.value().toObject();
+                if (o.contains("error")) {
+                    LOG_WARN("DividendSvc", QString("%1: %2").arg(sym, o.value("error").toString()));
+                    continue;
+                }
+                SymbolMeta m;
+                m.price = o.value("price").toDouble();
+                m.dividend_rate = o.value("dividend_rate").toDouble();
+                m.currency = o.value("currency").toString();
+                self->meta_.insert(sym, m);
+
+                for (const auto& hv : o.value("history").toArray()) {
+                    const QJsonObject h = hv.toObject();
+                    portfolio::DividendEvent e;
+                    e.symbol = sym;
+                    e.ex_date = h.value("ex_date").toString();
+                    e.amount = h.value("amount").toDouble();
+                    e.currency = m.currency;
+                    e.status = "paid";
+                    events.append(e);
+                }
+                const QJsonObject a = o.value("announced").toObject();
+                if (!a.isEmpty() && !a.value("ex_date").toString().isEmpty()) {
+                    portfolio::DividendEvent e;
+                    e.symbol = sym;
+                    e.ex_date = a.value("ex_date").toString();
+                    e.pay_date = a.value("pay_date").toString();
+                    e.amount = a.value("amount").toDouble();
+                    e.currency = m.currency;
+                    // An ex-date already in the past is history, whatever the
+                    // provider's calendar still says.
+                    e.status = QDate::fromString(e.ex_date, Qt::ISODate) < QDate::currentDate() ? "paid" : "announced";
+                    events.append(e);
+                }
+            }
+
+            auto r = DividendRepository::instance().upsert_events(events);
+            if (r.is_err()) {
+                done(QString::fromStdString(r.error()));
+                return;
+            }
+            LOG_INFO("DividendSvc",
+                     QString("Synced %1 dividend events for %2 symbols").arg(events.size()).arg(data.size()));
+            done({});
+        });
+}
+
+void DividendService::sync(const QString& portfolio_id) {
+    auto assets_r = PortfolioRepository::instance().get_assets(portfolio_id);
+    if (assets_r.is_err()) {
+        emit dividends_error(portfolio_id, QString::fromStdString(assets_r.error()));
+        return;
+    }
+    QStringList symbols;
+    for (const auto& a : assets_r.value())
+        if (!symbols.contains(a.symbol))
+            symbols << a.symbol;
+    if (symbols.isEmpty()) {
+        load(portfolio_id);
+        return;
+    }
+
+    QPointer<DividendService> self = this;
+    fetch_and_store(symbols, [self, portfolio_id](const QString& error) {
+        if (!self)
+            return;
+        if (!error.isEmpty()) {
+            LOG_ERROR("DividendSvc", QString("sync %1 failed: %2").arg(portfolio_id, error));
+            emit self->dividends_error(portfolio_id, error);
+            return;
+        }
+        self->load(portfolio_id);
+        self->check_ex_date_alerts();
+    });
+}
+
+void DividendService::load(const QString& portfolio_id) {
+    auto assets_r = PortfolioRepository::instance().get_assets(portfolio_id);
+    if (assets_r.is_err()) {
+        emit dividends_error(portfolio_id, QString::fromStdString(assets_r.error()));
+        return;
+    }
+    QStringList symbols;
+    for (const auto& a : assets_r.value())
+        symbols << a.symbol;
+
+    const QDate today = QDate::currentDate();
+    auto events_r = DividendRepository::instance().get_events(
+        symbols, today.addDays(-kHistoryWindowDays).toString(Qt::ISODate));
+    if (events_r.is_err()) {
+        emit dividends_error(portfolio_id, QString::fromStdString(events_r.error()));
+        return;
+    }
+    emit dividends_synced(build_summary(portfolio_id, assets_r.value(), events_r.value(), meta_, today));
+}
+
+void DividendService::refresh_all() {
+    auto ports = PortfolioRepository::instance().list_portfolios();
+    if (ports.is_err())
+        return;
+    QStringList symbols;
+    for (const auto& p : ports.value()) {
+        auto assets = PortfolioRepository::instance().get_assets(p.id);
+        if (assets.is_err())
+            continue;
+        for (const auto& a : assets.value())
+            if (!symbols.contains(a.symbol))
+                symbols << a.symbol;
+    }
+    if (symbols.isEmpty())
+        return;
+
+    QPointer<DividendService> self = this;
+    fetch_and_store(symbols, [self](const QString& error) {
+        if (!self)
+            return;
+        if (!error.isEmpty())
+            LOG_WARN("DividendSvc", "Background dividend refresh failed: " + error);
+        // Alerts run off whatever is stored, even if this refresh failed.
+        self->check_ex_date_alerts();
+    });
+}
+
+// ── Alerts ───────────────────────────────────────────────────────────────────
+
+void DividendService::check_ex_date_alerts(int days_ahead) {
+    const QDate today = QDate::currentDate();
+    auto pending = DividendRepository::instance().pending_alerts(
+        today.toString(Qt::ISODate), today.addDays(std::max(0, days_ahead)).toString(Qt::ISODate));
+    if (pending.is_err() || pending.value().isEmpty())
+        return;
+
+    // symbol → (portfolio name, quantity) for every portfolio holding it.
+    QHash<QString, QVector<QPair<QString, double>>> holders;
+    auto ports = PortfolioRepository::instance().list_portfolios();
+    if (ports.is_ok()) {
+        for (const auto& p : ports.value()) {
+            auto assets = PortfolioRepository::instance().get_assets(p.id);
+            if (assets.is_err())
+                continue;
+            for (const auto& a : assets.value())
+                if (a.quantity > 0)
+                    holders[a.symbol].append({p.name, a.quantity});
+        }
+    }
+
+    for (const auto& e : pending.value()) {
+        auto it = holders.constFind(e.symbol);
+        if (it == holders.constEnd())
+            continue; // no longer held — leave unalerted in case it is bought back
+
+        QStringList where;
+        for (const auto& [name, qty] : it.value()) {
+            where << (e.amount > 0 ? QString("%1 sh in %2 (~%3 %4)")
+                                         .arg(qty)
+                                         .arg(name)
+                                         .arg(qty * e.amount, 0, 'f', 2)
+                                         .arg(e.currency)
+                                   : QString("%1 sh in %2").arg(qty).arg(name));
+        }
+        notifications::NotificationRequest req;
+        req.title = QString("%1 goes ex-dividend %2").arg(e.symbol, e.ex_date);
+        req.message = QString("%1%2. Hold through the close before %3 to receive it.")
+                          .arg(e.amount > 0 ? QString("%1 %2/share").arg(e.amount).arg(e.currency) : "Dividend")
+                          .arg(e.pay_date.isEmpty() ? QString() : QString(", paid %1").arg(e.pay_date))
+                          .arg(e.ex_date) +
+                      "\n" + where.join("; ");
+        req.level = notifications::NotifLevel::Info;
+        req.trigger = notifications::NotifTrigger::DividendAlert;
+        notifications::NotificationService::instance().send(req);
+        DividendRepository::instance().mark_alerted(e.symbol, e.ex_date);
+    }
+}
+
+// ── Projection ───────────────────────────────────────────────────────────────
+
+portfolio::DividendSummary DividendService::build_summary(const QString& portfolio_id,
+                                                          const QVector<portfolio::PortfolioAsset>& assets,
+                                                          const QVector<portfolio::DividendEvent>& events,
+                                                          const QHash<QString, SymbolMeta>& meta,
+                                                          const QDate& today) {
+    portfolio::DividendSummary out;
+    out.portfolio_id = portfolio_id;
+    out.synced_at = QDateTime::currentDateTimeUtc().toString(Qt::ISODate);
+
+    const QDate horizon = today.addDays(365);
+    const QDate first_month(today.year(), today.month(), 1);
+    for (int m = 0; m < 12; ++m)
+        out.months.append({first_month.addMonths(m).toString("yyyy-MM"), 0.0, {}});
+    auto month_index = [&first_month](const QDate& d) {
+        return (d.year() - first_month.year()) * 12 + d.month() - first_month.month();
+    };
+
+    QHash<QString, QVector<portfolio::DividendEvent>> by_symbol;
+    for (const auto& e : events)
+        by_symbol[e.symbol].append(e);
+
+    double total_mv = 0;
+    for (const auto& a : assets) {
+        if (a.quantity <= 0)
+            continue;
+        const SymbolMeta m = meta.value(a.symbol);
+        portfolio::DividendHolding h;
+        h.symbol = a.symbol;
+        h.quantity = a.quantity;
+        h.avg_cost = a.avg_buy_price;
+        h.price = m.price > 0 ? m.price : a.avg_buy_price;
+        h.currency = m.currency;
+        total_mv += h.price * h.quantity;
+
+        auto evs = by_symbol.value(a.symbol);
+        std::sort(evs.begin(), evs.end(), [](const auto& x, const auto& y) { return x.ex_date < y.ex_date; });
+
+        QVector<QDate> ex_dates;
+        QVector<qint64> pay_lags;
+        for (const auto& e : evs) {
+            const QDate ex = QDate::fromString(e.ex_date, Qt::ISODate);
+            if (!ex.isValid())
+                continue;
+            ex_dates.append(ex);
+            const QDate pay = QDate::fromString(e.pay_date, Qt::ISODate);
+            if (pay.isValid() && pay >= ex)
+                pay_lags.append(ex.daysTo(pay));
+        }
+        int gap_days = 365;
+        h.payments_per_year = infer_payments_per_year(ex_dates, &gap_days);
+        std::sort(pay_lags.begin(), pay_lags.end());
+        const int pay_lag = pay_lags.isEmpty() ? kDefaultPayLagDays : static_cast<int>(pay_lags[pay_lags.size() / 2]);
+
+        // Treat the dividend as suspended when the last ex-date is overdue by
+        // more than half a cycle.
+        const bool active = !ex_dates.isEmpty() && ex_dates.last().addDays(gap_days + gap_days / 2) >= today;
+        const double last_amount = evs.isEmpty() ? 0.0 : evs.last().amount;
+        if (!active)
+            h.payments_per_year = 0;
+        if (active)
+            h.forward_annual_dividend = m.dividend_rate > 0 ? m.dividend_rate : last_amount * h.payments_per_year;
+        h.forward_yield = h.price > 0 ? h.forward_annual_dividend / h.price * 100.0 : 0.0;
+        h.yield_on_cost = h.avg_cost > 0 ? h.forward_annual_dividend / h.avg_cost * 100.0 : 0.0;
+
+        // ── Upcoming: announced first, then extrapolate at the cadence ──────
+        QVector<portfolio::DividendEvent> upcoming;
+        for (const auto& e : evs)
+            if (e.status == "announced" && QDate::fromString(e.ex_date, Qt::ISODate) >= today)
+                upcoming.append(e);
+        if (active && h.payments_per_year > 0) {
+            const int step = std::max(1, 365 / h.payments_per_year);
+            QDate next = ex_dates.last().addDays(step);
+            while (next <= horizon) {
+                if (next >= today) {
+                    portfolio::DividendEvent p;
+                    p.symbol = a.symbol;
+                    p.ex_date = next.toString(Qt::ISODate);
+                    p.pay_date = next.addDays(pay_lag).toString(Qt::ISODate);
+                    p.amount = last_amount;
+                    p.currency = m.currency;
+                    p.status = "projected";
+                    upcoming.append(p);
+                }
+                next = next.addDays(step);
+            }
+        }
+
+        for (const auto& e : upcoming) {
+            const QDate ex = QDate::fromString(e.ex_date, Qt::ISODate);
+            const QDate pay = e.pay_date.isEmpty() ? ex.addDays(pay_lag) : QDate::fromString(e.pay_date, Qt::ISODate);
+            if (h.next_ex_date.isEmpty()) {
+                h.next_ex_date = e.ex_date;
+                h.next_pay_date = pay.toString(Qt::ISODate);
+            }
+            const int idx = month_index(pay);
+            const double income = e.amount * h.quantity;
+            if (idx >= 0 && idx < out.months.size()) {
+                out.months[idx].income += income;
+                if (!out.months[idx].symbols.contains(a.symbol))
+                    out.months[idx].symbols << a.symbol;
+                h.projected_income_12m += income;
+            }
+            out.upcoming.append(e);
+        }
+        out.projected_income_12m += h.projected_income_12m;
+        out.holdings.append(h);
+    }
+
+    double forward_income = 0;
+    for (const auto& h : out.holdings)
+        forward_income += h.forward_annual_dividend * h.quantity;
+    out.portfolio_forward_yield = total_mv > 0 ? forward_income / total_mv * 100.0 : 0.0;
+    std::sort(out.upcoming.begin(), out.upcoming.end(),
+              [](const auto& x, const auto& y) { return x.ex_date < y.ex_date; });
+    return out;
+}
+
+} // namespace fincept::services
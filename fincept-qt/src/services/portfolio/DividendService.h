@@ -0,0 +1,77 @@
+// src/services/portfolio/DividendService.h
+#pragma once
+#include "screens/portfolio/PortfolioTypes.h"
+
+#include <QDate>
+#include <QHash>
+#include <QObject>
+#include <QStringList>
+
+#include <functional>
+
+class QTimer;
+
+namespace fincept::services {
+
+/// Dividend tracking for portfolio holdings: syncs paid + announced dividends
+/// from yfinance into the shared dividend calendar (DividendRepository),
+/// derives forward yield and a 12-month income projection by pay month, and
+/// raises ex-dividend reminders through NotificationService.
+///
+/// Payment cadence is inferred from the ex-date history; events beyond the
+/// announced ones are extrapolated at that cadence with the latest amount
+/// (status "projected", never persisted).
+class DividendService : public QObject {
+    Q_OBJECT
+  public:
+    static DividendService& instance();
+
+    /// Arms the background refresh: every kRefreshMs, re-sync the calendar
+    /// for every held symbol and fire ex-date reminders. Idempotent.
+    void start();
+
+    /// Fetch dividends for the portfolio's holdings, persist them, then emit
+    /// dividends_synced with the rebuilt summary (or dividends_error).
+    void sync(const QString& portfolio_id);
+
+    /// Rebuild the summary from the stored calendar only (no network).
+    void load(const QString& portfolio_id);
+
+    /// Notify for every stored event whose ex-date falls within the next
+    /// `days_ahead` days on a currently held symbol. Each event fires once.
+    void check_ex_date_alerts(int days_ahead = kDefaultAlertDays);
+
+    struct SymbolMeta {
+        double price = 0;
+        double dividend_rate = 0; // provider's forward annual rate per share, 0 = unknown
+        QString currency;
+    };
+
+    /// Pure projection used by load()/sync(). `events` may span any symbols;
+    /// only those held in `assets` are used.
+    static portfolio::DividendSummary build_summary(const QString& portfolio_id,
+                                                    const QVector<portfolio::PortfolioAsset>& assets,
+                                                    const QVector<portfolio::DividendEvent>& events,
+                                                    const QHash<QString, SymbolMeta>& meta, const QDate& today);
+
+    static constexpr int kDefaultAlertDays = 3;
+
+  signals:
+    void dividends_synced(portfolio::DividendSummary summary);
+    void dividends_error(QString portfolio_id, QString error);
+
+  private:
+    DividendService() = default;
+    Q_DISABLE_COPY(DividendService)
+
+    /// Runs the yfinance `dividends` command for `symbols` and persists the
+    /// result; `done(error)` fires on the main thread (empty error = success).
+    void fetch_and_store(const QStringList& symbols, std::function<void(QString)> done);
+    void refresh_all();
+
+    QHash<QString, SymbolMeta> meta_; // last price/rate per symbol from sync
+    QTimer* refresh_timer_ = nullptr;
+    static constexpr int kRefreshMs = 6 * 60 * 60 * 1000; // 6 h
+};
+
+} // namespace fincept::services
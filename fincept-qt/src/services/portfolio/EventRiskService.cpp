@@ -0,0 +1,502 @@
+// src/services/portfolio/EventRiskService.cpp
+#include "services/portfolio/EventRiskService.h"
+
+#include "core/logging/Logger.h"
+#include "datahub/DataHub.h"
+#include "python/PythonRunner.h"
+#include "services/markets/SymbologyService.h"
+#include "services/notifications/NotificationService.h"
+#include "services/portfolio/PortfolioService.h"
+#include "storage/repositories/DividendRepository.h"
+#include "storage/repositories/PortfolioRepository.h"
+#include "storage/repositories/SettingsRepository.h"
+#include "trading/IntradayPnlService.h"
+
+#include <QDateTime>
+#include <QHash>
+#include <QJsonDocument>
+#include <QPointer>
+#include <QRegularExpression>
+#include <QSet>
+#include <QTimer>
+#include <QUuid>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::services {
+
+namespace {
+
+constexpr const char* kMacroTopic = "econ:fincept:upcoming_events";
+constexpr int kFirstCheckMs = 90 * 1000;
+constexpr int kAlertRetentionDays = 90;
+constexpr int kMaxAlertDays = 30;
+
+/// Indices, FX, futures, crypto and OCC option symbols report no earnings.
+bool event_risk_has_earnings(const QString& symbol) {
+    static const QRegularExpression occ(QStringLiteral("^[A-Z]{1,6}\\d{6}[CP]\\d{8}$"));
+    return !symbol.startsWith('^') && !symbol.endsWith("=X") && !symbol.endsWith("=F") &&
+           !symbol.endsWith("-USD") && !occ.match(symbol).hasMatch();
+}
+
+/// Calendar country codes / names for the economy behind a currency.
+QStringList event_risk_countries(const QString& currency) {
+    static const QHash<QString, QStringList> table{
+        {"USD", {"US", "USA", "UNITED STATES"}},
+        {"EUR", {"EU", "EA", "EMU", "EZ", "EUROZONE", "EURO AREA", "DE", "FR", "IT", "ES", "NL"}},
+        {"GBP", {"GB", "UK", "UNITED KINGDOM"}},
+        {"JPY", {"JP", "JAPAN"}},
+        {"INR", {"IN", "INDIA"}},
+        {"CNY", {"CN", "CHINA"}},
+        {"HKD", {"HK", "HONG KONG"}},
+        {"CAD", {"CA", "CANADA"}},
+        {"AUD", {"AU", "AUSTRALIA"}},
+        {"NZD", {"NZ", "NEW ZEALAND"}},
+        {"CHF", {"CH", "SWITZERLAND"}},
+        {"SEK", {"SE", "SWEDEN"}},
+        {"NOK", {"NO", "NORWAY"}},
+        {"KRW", {"KR", "SOUTH KOREA"}},
+        {"SGD", {"SG", "SINGAPORE"}},
+        {"BRL", {"BR", "BRAZIL"}},
+        {"ZAR", {"ZA", "SOUTH AFRICA"}},
+    };
+    return table.value(currency.toUpper());
+}
+
+bool event_risk_is_policy_decision(const QString& title) {
+    static const QRegularExpression re(
+        QStringLiteral("\\b(FOMC|fed funds|interest rate decision|rate decision|monetary policy|policy rate|cash "
+                       "rate|bank rate|repo rate|ECB|BoE|BoJ|RBI|RBA|RBNZ|SNB|BoC|PBoC|LPR|Copom|Riksbank|Norges)\\b"),
+        QRegularExpression::CaseInsensitiveOption);
+    return re.match(title).hasMatch();
+}
+
+QString event_risk_money(double v) {
+    const double a = std::abs(v);
+    if (a >= 1e9)
+        return QString::number(v / 1e9, 'f', 2) + "B";
+    if (a >= 1e6)
+        return QString::number(v / 1e6, 'f', 1) + "M";
+    return QString::number(v, 'f', 0);
+}
+
+bool event_risk_sooner(const RiskEvent& a, const RiskEvent& b) {
+    if (a.date != b.date)
+        return a.date < b.date;
+    return a.importance != b.importance ? a.importance > b.importance : a.title < b.title;
+}
+
+} // namespace
+
+// ── Types ────────────────────────────────────────────────────────────────────
+
+QString RiskEvent::key() const {
+    return kind + '|' + (symbol.isEmpty() ? country : symbol) + '|' + date.toString(Qt::ISODate);
+}
+
+QJsonObject RiskEvent::to_json() const {
+    QJsonObject o{{"kind", kind},
+                  {"date", date.toString(Qt::ISODate)},
+                  {"days_until", days_until},
+                  {"title", title},
+                  {"status", status},
+                  {"importance", importance}};
+    if (!symbol.isEmpty())
+        o["symbol"] = symbol;
+    if (!country.isEmpty())
+        o["country"] = country;
+    if (date_end.isValid())
+        o["date_end"] = date_end.toString(Qt::ISODate);
+    if (!detail.isEmpty())
+        o["detail"] = detail;
+    return o;
+}
+
+QJsonObject EventRiskReport::to_json() const {
+    QJsonArray rows;
+    QVector<RiskEvent> calendar;
+    QSet<QString> seen; // a decision shared by several holdings is listed once
+    int exposed = 0;
+    for (const auto& h : holdings) {
+        QJsonArray events;
+        for (const auto& e : h.events) {
+            events.append(e.to_json());
+            if (!seen.contains(e.key())) {
+                seen.insert(e.key());
+                calendar.append(e);
+            }
+        }
+        if (!h.events.isEmpty())
+            ++exposed;
+        rows.append(QJsonObject{{"symbol", h.symbol},
+                                {"currency", h.currency},
+                                {"quantity", h.quantity},
+                                {"held_in", QJsonArray::fromStringList(h.held_in)},
+                                {"next_event", h.events.isEmpty() ? QJsonValue() : h.events.first().to_json()},
+                                {"events", events}});
+    }
+    std::sort(calendar.begin(), calendar.end(), event_risk_sooner);
+    QJsonArray flat;
+    for (const auto& e : calendar)
+        flat.append(e.to_json());
+    QJsonArray macro_rows;
+    for (const auto& e : macro)
+        macro_rows.append(e.to_json());
+
+    return QJsonObject{{"as_of", as_of.toString(Qt::ISODate)},
+                       {"horizon_days", horizon_days},
+                       {"positions", int(holdings.size())},
+                       {"positions_with_events", exposed},
+                       {"holdings", rows},
+                       {"events", flat},
+                       {"macro", macro_rows},
+                       {"warnings", QJsonArray::fromStringList(warnings)}};
+}
+
+// ── Service ──────────────────────────────────────────────────────────────────
+
+EventRiskService& EventRiskService::instance() {
+    static EventRiskService s;
+    return s;
+}
+
+void EventRiskService::start() {
+    if (check_timer_)
+        return;
+    check_timer_ = new QTimer(this);
+    check_timer_->setInterval(kCheckMs);
+    connect(check_timer_, &QTimer::timeout, this, &EventRiskService::check_alerts);
+    check_timer_->start();
+    QTimer::singleShot(kFirstCheckMs, this, &EventRiskService::check_alerts);
+}
+
+int EventRiskService::alert_days() const {
+    auto r = SettingsRepository::instance().get(kAlertDaysKey, "0");
+    return r.is_ok() ? std::clamp(r.value().toInt(), 0, kMaxAlertDays) : 0;
+}
+
+void EventRiskService::set_alert_days(int days) {
+    SettingsRepository::instance().set(kAlertDaysKey, QString::number(std::clamp(days, 0, kMaxAlertDays)),
+                                       "notifications");
+}
+
+QString EventRiskService::scan(int horizon_days, bool refresh) {
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    QPointer<EventRiskService> self = this;
+    run(horizon_days, refresh, [self, request_id](const EventRiskReport& report) {
+        // Queued: with nothing to fetch the report is ready before scan() returns its id.
+        const QJsonObject json = report.to_json();
+        QMetaObject::invokeMethod(
+            self,
+            [self, request_id, json]() {
+                if (self)
+                    emit self->report_ready(request_id, json);
+            },
+            Qt::QueuedConnection);
+    });
+    return request_id;
+}
+
+QVector<RiskHolding> EventRiskService::open_positions() const {
+    QVector<RiskHolding> out;
+    QHash<QString, int> index;
+    auto add = [&](const QString& symbol, const QString& currency, double qty, const QString& where) {
+        const QString sym = symbol.trimmed().toUpper();
+        if (sym.isEmpty() || qty == 0)
+            return;
+        auto it = index.constFind(sym);
+        if (it == index.cend()) {
+            index.insert(sym, int(out.size()));
+            out.append(RiskHolding{sym, currency, qty, {where}, {}});
+            return;
+        }
+        RiskHolding& h = out[*it];
+        h.quantity += qty;
+        if (h.currency.isEmpty())
+            h.currency = currency;
+        if (!h.held_in.contains(where))
+            h.held_in << where;
+    };
+
+    auto ports = PortfolioRepository::instance().list_portfolios();
+    if (ports.is_ok()) {
+        for (const auto& p : ports.value()) {
+            auto assets = PortfolioRepository::instance().get_assets(p.id);
+            if (assets.is_err())
+                continue;
+            for (const auto& a : assets.value()) {
+                const QString ccy =
+                    a.currency.isEmpty() ? PortfolioService::listing_currency(a.symbol, p.currency) : a.currency;
+                add(a.symbol, ccy, a.quantity, p.name);
+            }
+        }
+    }
+    for (const auto& pos : trading::IntradayPnlService::instance().snapshot().positions) {
+        const QString yahoo = SymbologyService::yahoo_symbol(pos.exchange, pos.symbol);
+        add(yahoo.isEmpty() ? pos.symbol : yahoo, pos.currency, pos.quantity, pos.account_label);
+    }
+    return out;
+}
+
+void EventRiskService::fetch_earnings(const QStringList& symbols, std::function<void(QString)> done) {
+    QPointer<EventRiskService> self = this;
+    python::PythonRunner::instance().run(
+        "yfinance_data.py", QStringList{"earnings_dates"} + symbols, [self, done](python::PythonResult result) {
+            if (!self)
+                return;
+            if (!result.success) {
+                done(result.error.isEmpty() ? QStringLiteral("Earnings fetch failed") : result.error.left(300));
+                return;
+            }
+            const auto doc = QJsonDocument::fromJson(python::extract_json(result.output).toUtf8());
+            if (!doc.isObject() || doc.object().contains("error")) {
+                done(doc.isObject() ? doc.object().value("error").toString() : QStringLiteral("Invalid response"));
+                return;
+            }
+
+            const qint64 now = QDateTime::currentMSecsSinceEpoch();
+            QVector<EarningsDate> rows;
+            const QJsonObject data = doc.object().value("data").toObject();
+            for (auto it = data.constBegin(); it != data.constEnd(); ++it) {
+                const QJsonObject o = it.value().toObject();
+                if (o.contains("error")) {
+                    LOG_WARN("EventRisk", QString("%1: %2").arg(it.key(), o.value("error").toString()));
+                    continue;
+                }
+                EarningsDate e;
+                e.symbol = it.key().toUpper();
+                e.earnings_date = o.value("earnings_date").toString();
+                e.earnings_date_end = o.value("earnings_date_end").toString();
+                e.eps_estimate = o.value("eps_estimate").toDouble();
+                e.revenue_estimate = o.value("revenue_estimate").toDouble();
+                e.fetched_at = now;
+                rows.append(e);
+            }
+            auto r = EarningsCalendarRepository::instance().upsert(rows);
+            done(r.is_err() ? QString::fromStdString(r.error()) : QString());
+        });
+}
+
+void EventRiskService::run(int horizon_days, bool refresh, std::function<void(EventRiskReport)> done) {
+    const QVector<RiskHolding> holdings = open_positions();
+    QStringList symbols;
+    for (const auto& h : holdings)
+        symbols << h.symbol;
+
+    QStringList stale;
+    auto stored = EarningsCalendarRepository::instance().get(symbols);
+    QHash<QString, qint64> fetched;
+    if (stored.is_ok())
+        for (const auto& e : stored.value())
+            fetched.insert(e.symbol, e.fetched_at);
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    for (const auto& s : symbols)
+        if (event_risk_has_earnings(s) && (refresh || now - fetched.value(s, 0) > kStaleMs))
+            stale << s;
+
+    QPointer<EventRiskService> self = this;
+    auto finish = [self, holdings, symbols, horizon_days, done](const QString& fetch_error) {
+        if (!self)
+            return;
+        QStringList warnings;
+        if (!fetch_error.isEmpty())
+            warnings << "Earnings dates not refreshed: " + fetch_error;
+
+        const QDate today = QDate::currentDate();
+        QVector<EarningsDate> earnings;
+        if (auto r = EarningsCalendarRepository::instance().get(symbols); r.is_ok())
+            earnings = r.value();
+        QVector<portfolio::DividendEvent> dividends;
+        if (auto r = DividendRepository::instance().get_events(symbols, today.toString(Qt::ISODate)); r.is_ok())
+            dividends = r.value();
+
+        auto& hub = datahub::DataHub::instance();
+        const QJsonArray macro = hub.peek_raw(QString::fromLatin1(kMacroTopic)).toJsonArray();
+        if (macro.isEmpty()) {
+            hub.request(QString::fromLatin1(kMacroTopic));
+            warnings << "Economic calendar not loaded yet; central-bank decisions are missing";
+        }
+
+        EventRiskReport report = build(holdings, earnings, dividends, macro, today, horizon_days);
+        report.warnings = warnings + report.warnings;
+        done(report);
+    };
+
+    if (stale.isEmpty()) {
+        finish({});
+        return;
+    }
+    fetch_earnings(stale, finish);
+}
+
+EventRiskReport EventRiskService::build(QVector<RiskHolding> holdings, const QVector<EarningsDate>& earnings,
+                                        const QVector<portfolio::DividendEvent>& dividends,
+                                        const QJsonArray& macro_events, const QDate& today, int horizon_days) {
+    EventRiskReport report;
+    report.as_of = today;
+    report.horizon_days = std::max(horizon_days, 1);
+    const QDate horizon = today.addDays(report.horizon_days);
+    auto in_range = [&](const QDate& from, const QDate& to) {
+        return from.isValid() && from <= horizon && (to.isValid() ? to : from) >= today;
+    };
+
+    QHash<QString, EarningsDate> by_symbol;
+    for (const auto& e : earnings)
+        by_symbol.insert(e.symbol.toUpper(), e);
+
+    // Central-bank decisions and high-importance releases, parsed once.
+    QVector<RiskEvent> policy;
+    QVector<RiskEvent> releases;
+    for (const auto& v : macro_events) {
+        const QJsonObject o = v.toObject();
+        RiskEvent e;
+        e.date = QDate::fromString(o.value("date").toString().left(10), Qt::ISODate);
+        e.title = o.value("event").toString().trimmed();
+        if (e.title.isEmpty() || !in_range(e.date, {}))
+            continue;
+        e.country = o.value("country").toString().trimmed().toUpper();
+        e.importance = o.value("importance").toInt(0);
+        e.status = "scheduled";
+        e.days_until = int(today.daysTo(e.date));
+        const QString time = o.value("time").toString().trimmed();
+        const QString forecast = o.value("forecast").toString().trimmed();
+        QStringList detail;
+        if (!time.isEmpty())
+            detail << time.left(5);
+        if (!forecast.isEmpty())
+            detail << "forecast " + forecast;
+        e.detail = detail.join(", ");
+        if (event_risk_is_policy_decision(e.title)) {
+            e.kind = "central_bank";
+            e.importance = 3;
+            policy.append(e);
+        } else if (e.importance >= 3) {
+            e.kind = "macro";
+            releases.append(e);
+        }
+    }
+
+    QSet<QString> countries;
+    for (auto& h : holdings) {
+        if (event_risk_has_earnings(h.symbol)) {
+            const auto it = by_symbol.constFind(h.symbol);
+            if (it != by_symbol.cend()) {
+                RiskEvent e;
+                e.kind = "earnings";
+                e.symbol = h.symbol;
+                e.date = QDate::fromString(it->earnings_date, Qt::ISODate);
+                e.date_end = QDate::fromString(it->earnings_date_end, Qt::ISODate);
+                if (in_range(e.date, e.date_end)) {
+                    e.title = h.symbol + " earnings";
+                    e.status = e.date_end.isValid() ? "window" : "scheduled";
+                    e.importance = 3;
+                    e.days_until = int(std::max<qint64>(0, today.daysTo(e.date)));
+                    QStringList detail;
+                    if (it->eps_estimate != 0)
+                        detail << "EPS est. " + QString::number(it->eps_estimate, 'f', 2);
+                    if (it->revenue_estimate != 0)
+                        detail << "revenue est. " + event_risk_money(it->revenue_estimate);
+                    e.detail = detail.join(", ");
+                    h.events.append(e);
+                }
+            }
+        }
+
+        for (const auto& d : dividends) {
+            if (d.symbol.compare(h.symbol, Qt::CaseInsensitive) != 0 || d.status != "announced")
+                continue;
+            RiskEvent e;
+            e.kind = "ex_dividend";
+            e.symbol = h.symbol;
+            e.date = QDate::fromString(d.ex_date, Qt::ISODate);
+            if (!in_range(e.date, {}))
+                continue;
+            e.title = h.symbol + " ex-dividend";
+            e.status = "announced";
+            e.importance = 2;
+            e.days_until = int(today.daysTo(e.date));
+            if (d.amount > 0)
+                e.detail = QString("%1 %2/share").arg(d.amount).arg(d.currency.isEmpty() ? h.currency : d.currency);
+            if (!d.pay_date.isEmpty())
+                e.detail += (e.detail.isEmpty() ? "paid " : ", paid ") + d.pay_date;
+            h.events.append(e);
+        }
+
+        const QStringList names = event_risk_countries(h.currency);
+        for (const auto& n : names)
+            countries.insert(n);
+        for (const auto& p : policy)
+            if (names.contains(p.country))
+                h.events.append(p);
+
+        std::sort(h.events.begin(), h.events.end(), event_risk_sooner);
+    }
+
+    for (const auto& r : releases)
+        if (countries.contains(r.country))
+            report.macro.append(r);
+    std::sort(report.macro.begin(), report.macro.end(), event_risk_sooner);
+
+    std::stable_sort(holdings.begin(), holdings.end(), [](const RiskHolding& a, const RiskHolding& b) {
+        if (a.events.isEmpty() != b.events.isEmpty())
+            return !a.events.isEmpty();
+        if (!a.events.isEmpty() && a.events.first().date != b.events.first().date)
+            return a.events.first().date < b.events.first().date;
+        return a.symbol < b.symbol;
+    });
+    report.holdings = holdings;
+    return report;
+}
+
+// ── Alerts ───────────────────────────────────────────────────────────────────
+
+void EventRiskService::check_alerts() {
+    const int days = alert_days();
+    if (days <= 0)
+        return;
+    run(days, false, [](const EventRiskReport& report) {
+        // One reminder per event; a decision shared by several holdings lists them all.
+        QHash<QString, RiskEvent> events;
+        QHash<QString, QStringList> holders;
+        QStringList order;
+        for (const auto& h : report.holdings) {
+            for (const auto& e : h.events) {
+                if (e.kind != "earnings" && e.kind != "central_bank")
+                    continue;
+                const QString key = e.key();
+                if (!events.contains(key)) {
+                    events.insert(key, e);
+                    order << key;
+                }
+                holders[key] << QString("%1 (%2)").arg(h.symbol, h.held_in.join(", "));
+            }
+        }
+        if (order.isEmpty())
+            return;
+
+        auto& repo = EarningsCalendarRepository::instance();
+        const QSet<QString> done = repo.alerted(order);
+        for (const auto& key : order) {
+            if (done.contains(key))
+                continue;
+            const RiskEvent& e = events[key];
+            const QString when = e.days_until == 0   ? QStringLiteral("today")
+                                 : e.days_until == 1 ? QStringLiteral("tomorrow")
+                                                     : QString("in %1 days").arg(e.days_until);
+            notifications::NotificationRequest req;
+            req.title = QString("%1 %2 (%3)").arg(e.title, when, e.date.toString(Qt::ISODate));
+            QString message = e.detail.isEmpty() ? QString() : e.detail + "\n";
+            if (e.status == "window")
+                message += QString("Unconfirmed — expected by %1.\n").arg(e.date_end.toString(Qt::ISODate));
+            req.message = message + "Exposed: " + holders.value(key).join("; ");
+            req.level = notifications::NotifLevel::Warning;
+            req.trigger = notifications::NotifTrigger::EventRiskAlert;
+            notifications::NotificationService::instance().send(req);
+            repo.mark_alerted(key);
+        }
+        repo.prune_alerts(QDateTime::currentDateTimeUtc().addDays(-kAlertRetentionDays).toMSecsSinceEpoch());
+    });
+}
+
+} // namespace fincept::services
@@ -0,0 +1,117 @@
+// src/services/portfolio/EventRiskService.h
+#pragma once
+#include "screens/portfolio/PortfolioTypes.h"
+#include "storage/repositories/EarningsCalendarRepository.h"
+
+#include <QDate>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+#include <QStringList>
+
+#include <functional>
+
+class QTimer;
+
+namespace fincept::services {
+
+/// A scheduled binary event.
+struct RiskEvent {
+    QString kind;    // earnings | ex_dividend | central_bank | macro
+    QString symbol;  // the holding, for earnings and ex-dividend
+    QString country; // central_bank / macro, as the calendar feed reports it
+    QDate date;
+    QDate date_end; // last day of an unconfirmed earnings window
+    QString title;
+    QString detail;
+    QString status;     // confirmed | window | announced | scheduled
+    int importance = 0; // 1–3
+    int days_until = 0;
+
+    /// kind|symbol-or-country|date — what a reminder is keyed by.
+    QString key() const;
+    QJsonObject to_json() const;
+};
+
+/// One open position, merged across portfolios and trading accounts.
+struct RiskHolding {
+    QString symbol; // Yahoo symbol
+    QString currency;
+    double quantity = 0;
+    QStringList held_in;       // portfolio / account names
+    QVector<RiskEvent> events; // soonest first
+};
+
+struct EventRiskReport {
+    QDate as_of;
+    int horizon_days = 0;
+    QVector<RiskHolding> holdings; // holdings with an event first, soonest first
+    QVector<RiskEvent> macro;      // high-importance releases for the holdings' economies
+    QStringList warnings;
+
+    QJsonObject to_json() const;
+};
+
+/// Event-risk overlay: cross-references open positions — portfolio holdings
+/// and the live / paper positions of trading accounts (IntradayPnlService) —
+/// with the calendars that can gap them:
+///   earnings     — next scheduled date or window (earnings_calendar,
+///                  refreshed from yfinance when older than a day);
+///   ex_dividend  — announced ex-dates in the shared dividend calendar;
+///   central_bank — policy decisions (FOMC, ECB, BoE, RBI, ...) in the
+///                  upcoming-events feed (`econ:fincept:upcoming_events`)
+///                  for the country of each holding's currency.
+/// The feed only carries the next few dozen releases, so a meeting shows up
+/// once it enters it.
+///
+/// Reminders are opt-in: with alert_days() > 0 the service checks every
+/// kCheckMs and notifies once per earnings date and central-bank decision
+/// that falls within that many days. Ex-dividend reminders stay with
+/// DividendService. Main thread only.
+class EventRiskService : public QObject {
+    Q_OBJECT
+  public:
+    static EventRiskService& instance();
+
+    /// Arms the reminder check. Idempotent.
+    void start();
+
+    /// Builds a report over the next `horizon_days`; returns its request id,
+    /// which report_ready carries. `refresh` re-fetches every holding's
+    /// earnings date instead of only the stale ones.
+    QString scan(int horizon_days = kDefaultHorizonDays, bool refresh = false);
+
+    /// Days ahead a reminder fires; 0 = reminders off (the default).
+    int alert_days() const;
+    void set_alert_days(int days);
+
+    /// Notify for the events inside the alert window that have not alerted yet.
+    void check_alerts();
+
+    /// Pure overlay used by scan(): attaches the events in [today, today +
+    /// horizon_days] to `holdings`. `macro_events` is the feed's array.
+    static EventRiskReport build(QVector<RiskHolding> holdings, const QVector<EarningsDate>& earnings,
+                                 const QVector<portfolio::DividendEvent>& dividends, const QJsonArray& macro_events,
+                                 const QDate& today, int horizon_days);
+
+    static constexpr int kDefaultHorizonDays = 30;
+    static constexpr const char* kAlertDaysKey = "event_risk.alert_days";
+
+  signals:
+    void report_ready(QString request_id, QJsonObject report);
+
+  private:
+    EventRiskService() = default;
+    Q_DISABLE_COPY(EventRiskService)
+
+    QVector<RiskHolding> open_positions() const;
+    void run(int horizon_days, bool refresh, std::function<void(EventRiskReport)> done);
+    void fetch_earnings(const QStringList& symbols, std::function<void(QString)> done);
+
+    QTimer* check_timer_ = nullptr;
+
+    static constexpr int kCheckMs = 6 * 60 * 60 * 1000;       // 6 h
+    static constexpr qint64 kStaleMs = 24LL * 60 * 60 * 1000; // earnings dates older than this are re-fetched
+};
+
+} // namespace fincept::services
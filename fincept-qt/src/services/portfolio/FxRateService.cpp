@@ -0,0 +1,217 @@
+// src/services/portfolio/FxRateService.cpp
+#include "services/portfolio/FxRateService.h"
+
+#include "core/logging/Logger.h"
+#include "python/PythonRunner.h"
+#include "services/markets/MarketDataService.h"
+#include "storage/cache/CacheManager.h"
+
+#include <QJsonDocument>
+#include <QJsonObject>
+#include <QPointer>
+
+#include <memory>
+
+namespace fincept::services {
+
+namespace {
+
+QString fx_pair(const QString& currency, const QString& base) {
+    return currency + base + "=X";
+}
+
+QString fx_spot_key(const QString& currency, const QString& base) {
+    return "fx:spot:" + currency + base;
+}
+
+constexpr const char* kEcbTableKey = "fx:ecb:reference";
+
+} // namespace
+
+FxRateService& FxRateService::instance() {
+    static FxRateService s;
+    return s;
+}
+
+std::optional<double> FxRateService::cached_spot(const QString& currency, const QString& base) const {
+    const QString ccy = currency.toUpper();
+    const QString b = base.toUpper();
+    if (ccy == b)
+        return 1.0;
+    const QVariant cv = CacheManager::instance().get(fx_spot_key(ccy, b));
+    if (cv.isNull())
+        return std::nullopt;
+    const double rate = QJsonDocument::fromJson(cv.toString().toUtf8()).object().value("rate").toDouble();
+    return rate > 0 ? std::optional<double>(rate) : std::nullopt;
+}
+
+void FxRateService::remember_spot(const QString& currency, const QString& base, double rate, const QString& source) {
+    const QJsonObject o{{"rate", rate}, {"source", source}};
+    CacheManager::instance().put(fx_spot_key(currency, base),
+                                 QString::fromUtf8(QJsonDocument(o).toJson(QJsonDocument::Compact)), kSpotTtlSec,
+                                 "portfolio");
+}
+
+void FxRateService::spot_rates(const QStringList& currencies, const QString& base, RatesCallback done) {
+    auto out = std::make_shared<Rates>();
+    const QString b = base.toUpper();
+    QStringList missing;
+    for (const auto& c : currencies) {
+        const QString ccy = c.trimmed().toUpper();
+        if (ccy.isEmpty() || out->to_base.contains(ccy) || missing.contains(ccy))
+            continue;
+        if (ccy == b) {
+            out->to_base.insert(ccy, 1.0);
+            out->source.insert(ccy, "identity");
+            continue;
+        }
+        const QVariant cv = CacheManager::instance().get(fx_spot_key(ccy, b));
+        if (!cv.isNull()) {
+            const auto o = QJsonDocument::fromJson(cv.toString().toUtf8()).object();
+            if (o.value("rate").toDouble() > 0) {
+                out->to_base.insert(ccy, o.value("rate").toDouble());
+                out->source.insert(ccy, o.value("source").toString());
+                continue;
+            }
+        }
+        missing << ccy;
+    }
+    if (missing.isEmpty()) {
+        done(*out);
+        return;
+    }
+
+    QStringList pairs;
+    for (const auto& ccy : missing)
+        pairs << fx_pair(ccy, b);
+
+    QPointer<FxRateService> self = this;
+    auto on_quotes = [self, out, missing, b, done](bool ok, QVector<QuoteData> quotes) {
+        if (!self)
+            return;
+        QHash<QString, double> px;
+        if (ok)
+            for (const auto& q : quotes)
+                if (q.price > 0)
+                    px.insert(q.symbol.toUpper(), q.price);
+
+        QStringList unresolved;
+        for (const auto& ccy : missing) {
+            const double rate = px.value(fx_pair(ccy, b), 0.0);
+            if (rate <= 0) {
+                unresolved << ccy;
+                continue;
+            }
+            out->to_base.insert(ccy, rate);
+            out->source.insert(ccy, "market");
+            self->remember_spot(ccy, b, rate, "market");
+        }
+        if (unresolved.isEmpty()) {
+            done(*out);
+            return;
+        }
+
+        // Market feed missed some pairs — fall back to the ECB reference table.
+        self->ecb_cross_rates(b, [self, out, unresolved, b, done](QHash<QString, double> ecb) {
+            for (const auto& ccy : unresolved) {
+                const double rate = ecb.value(ccy, 0.0);
+                if (rate <= 0) {
+                    out->warnings << QString("No %1/%2 rate from the market feed or ECB").arg(ccy, b);
+                    continue;
+                }
+                out->to_base.insert(ccy, rate);
+                out->source.insert(ccy, "ecb");
+                if (self)
+                    self->remember_spot(ccy, b, rate, "ecb");
+            }
+            done(*out);
+        });
+    };
+    MarketDataService::instance().fetch_quotes(pairs, on_quotes);
+}
+
+void FxRateService::ecb_cross_rates(const QString& base, std::function<void(QHash<QString, double>)> done) {
+    // Table is EUR-based: 1 EUR = table[ccy]. Cross: base per ccy = table[base] / table[ccy].
+    auto cross = [base](const QJsonObject& table) {
+        QHash<QString, double> out;
+        const double base_per_eur = table.value(base).toDouble();
+        if (base_per_eur <= 0)
+            return out;
+        for (auto it = table.constBegin(); it != table.constEnd(); ++it) {
+            const double per_eur = it.value().toDouble();
+            if (it.key().size() == 3 && per_eur > 0)
+                out.insert(it.key(), base_per_eur / per_eur);
+        }
+        return out;
+    };
+
+    const QVariant cv = CacheManager::instance().get(kEcbTableKey);
+    if (!cv.isNull()) {
+        done(cross(QJsonDocument::fromJson(cv.toString().toUtf8()).object()));
+        return;
+    }
+
+    QPointer<FxRateService> self = this;
+    python::PythonRunner::instance().run(
+        "ecb_data.py", {"currency_rates"}, [self, cross, done](python::PythonResult result) {
+            if (!self)
+                return;
+            QJsonObject table;
+            if (result.success) {
+                const auto obj = QJsonDocument::fromJson(python::extract_json(result.output).toUtf8()).object();
+                if (obj.value("success").toBool())
+                    table = obj.value("data").toObject();
+            }
+            if (table.isEmpty()) {
+                LOG_WARN("FxRateService", "ECB reference rates unavailable: " + result.error.left(200));
+                done({});
+                return;
+            }
+            CacheManager::instance().put(kEcbTableKey,
+                                         QString::fromUtf8(QJsonDocument(table).toJson(QJsonDocument::Compact)),
+                                         kEcbTtlSec, "portfolio");
+            done(cross(table));
+        });
+}
+
+void FxRateService::rate_on(const QString& currency, const QString& base, const QDate& date, RateCallback done) {
+    const QString ccy = currency.toUpper();
+    const QString b = base.toUpper();
+    if (ccy == b) {
+        done(1.0);
+        return;
+    }
+    if (!date.isValid() || date >= QDate::currentDate()) {
+        spot_rates({ccy}, b, [ccy, done](Rates r) {
+            done(r.to_base.contains(ccy) ? std::optional<double>(r.to_base.value(ccy)) : std::nullopt);
+        });
+        return;
+    }
+
+    const QString iso = date.toString(Qt::ISODate);
+    const QString key = "fx:hist:" + ccy + b + ":" + iso;
+    const QVariant cv = CacheManager::instance().get(key);
+    if (!cv.isNull() && cv.toString().toDouble() > 0) {
+        done(cv.toString().toDouble());
+        return;
+    }
+
+    QPointer<FxRateService> self = this;
+    python::PythonRunner::instance().run(
+        "yfinance_data.py", {"historical_price", fx_pair(ccy, b), iso}, [self, key, done](python::PythonResult result) {
+            if (!self)
+                return;
+            if (result.success) {
+                const auto obj = QJsonDocument::fromJson(python::extract_json(result.output).toUtf8()).object();
+                const double px = obj.value("price").toDouble();
+                if (obj.value("found").toBool() && px > 0) {
+                    CacheManager::instance().put(key, QString::number(px, 'g', 12), kHistoricalTtlSec, "portfolio");
+                    done(px);
+                    return;
+                }
+            }
+            done(std::nullopt);
+        });
+}
+
+} // namespace fincept::services
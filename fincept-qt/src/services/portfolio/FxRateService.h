@@ -0,0 +1,57 @@
+// src/services/portfolio/FxRateService.h
+#pragma once
+#include <QDate>
+#include <QHash>
+#include <QObject>
+#include <QStringList>
+
+#include <functional>
+#include <optional>
+
+namespace fincept::services {
+
+/// FX conversion layer for portfolio valuation. Every rate is expressed as
+/// units of `base` per one unit of the foreign currency (GBP→USD 1.27).
+///
+/// Spot comes from the market feed ("GBPUSD=X" via MarketDataService), with
+/// the ECB daily reference rates (EUR cross) filling any pair the feed
+/// misses. Historical rates are daily closes on or before the date. All of
+/// it is cached in CacheManager so a summary refresh rarely leaves the box.
+class FxRateService : public QObject {
+    Q_OBJECT
+  public:
+    static FxRateService& instance();
+
+    struct Rates {
+        QHash<QString, double> to_base; // currency → rate; absent = unavailable
+        QHash<QString, QString> source; // currency → "identity" | "market" | "ecb"
+        QStringList warnings;
+    };
+    using RatesCallback = std::function<void(Rates)>;
+    using RateCallback = std::function<void(std::optional<double>)>;
+
+    /// Spot rate for each of `currencies` into `base`. `done` runs exactly once.
+    void spot_rates(const QStringList& currencies, const QString& base, RatesCallback done);
+
+    /// Close on (or the last trading day before) `date`. nullopt when neither
+    /// the feed nor the cache has it.
+    void rate_on(const QString& currency, const QString& base, const QDate& date, RateCallback done);
+
+    /// Cached spot only — no fetch. Used on synchronous paths.
+    std::optional<double> cached_spot(const QString& currency, const QString& base) const;
+
+    static constexpr int kSpotTtlSec = 300;
+    static constexpr int kEcbTtlSec = 6 * 60 * 60;            // published once per TARGET day
+    static constexpr int kHistoricalTtlSec = 30 * 24 * 60 * 60; // closes never change
+
+  private:
+    FxRateService() = default;
+    Q_DISABLE_COPY(FxRateService)
+
+    /// ECB reference table turned into `base` cross rates; empty when the ECB
+    /// does not publish `base` or the fetch failed.
+    void ecb_cross_rates(const QString& base, std::function<void(QHash<QString, double>)> done);
+    void remember_spot(const QString& currency, const QString& base, double rate, const QString& source);
+};
+
+} // namespace fincept::services
@@ -2,10 +2,18 @@
 
 #include "core/logging/Logger.h"
 #include "python/PythonRunner.h"
+#include "services/portfolio/BlackLitterman.h"
+#include "services/portfolio/PortfolioService.h"
+#include "services/sectors/SectorResolver.h"
 
 #include <QJsonArray>
 #include <QJsonDocument>
 #include <QJsonObject>
+#include <QPointer>
+#include <QtConcurrent>
+
+#include <algorithm>
+#include <cmath>
 
 namespace fincept::services {
 
@@ -40,8 +48,442 @@ void PortfolioAnalyticsService::run_monte_carlo(const QStringList& symbols, cons
                QString::fromUtf8(QJsonDocument(args).toJson(QJsonDocument::Compact)), std::move(cb));
 }
 
+// ── Native optimisation ──────────────────────────────────────────────────────
+
+static OptimizerConstraints constraints_from_json(const QJsonObject& c, const QStringList& symbols) {
+    OptimizerConstraints out;
+    out.long_only = c.value("long_only").toBool(true);
+    out.default_min = c.value("min_weight").toDouble(out.long_only ? 0.0 : -1.0);
+    out.default_max = c.value("max_weight").toDouble(1.0);
+    const QJsonObject bounds = c.value("bounds").toObject();
+    for (auto it = bounds.begin(); it != bounds.end(); ++it) {
+        double lo = out.default_min, hi = out.default_max;
+        if (it.value().isArray()) {
+            const QJsonArray a = it.value().toArray();
+            lo = a.at(0).toDouble(lo);
+            hi = a.at(1).toDouble(hi);
+        } else {
+            const QJsonObject o = it.value().toObject();
+            lo = o.value("min").toDouble(lo);
+            hi = o.value("max").toDouble(hi);
+        }
+        out.bounds.insert(it.key().trimmed().toUpper(), {lo, hi});
+    }
+    const QJsonObject caps = c.value("sector_caps").toObject();
+    for (auto it = caps.begin(); it != caps.end(); ++it)
+        out.sector_caps.insert(it.key(), it.value().toDouble(1.0));
+    if (!out.sector_caps.isEmpty()) {
+        // Explicit sectors win; the rest come from the resolver's cache.
+        const QJsonObject sectors = c.value("sectors").toObject();
+        for (const auto& sym : symbols) {
+            QString sector = sectors.value(sym).toString();
+            if (sector.isEmpty())
+                sector = SectorResolver::instance().sector_for(sym);
+            if (!sector.isEmpty())
+                out.sector_of.insert(sym, sector);
+        }
+    }
+    return out;
+}
+
+static QJsonObject by_symbol_json(const QStringList& symbols, const QVector<double>& v) {
+    QJsonObject o;
+    for (int i = 0; i < symbols.size() && i < v.size(); ++i)
+        o[symbols[i]] = v[i];
+    return o;
+}
+
+// Weights for `symbols` from either an array aligned with `arg_symbols` or
+// a {symbol: value} object, normalised to 1. Empty when nothing positive.
+static QVector<double> weights_from_json(const QJsonValue& v, const QStringList& arg_symbols,
+                                         const QStringList& symbols) {
+    QVector<double> w(symbols.size(), 0.0);
+    double sum = 0.0;
+    const QJsonObject by_sym = v.toObject();
+    const QJsonArray arr = v.toArray();
+    for (int i = 0; i < symbols.size(); ++i) {
+        double x = 0.0;
+        if (v.isObject()) {
+            x = by_sym.value(symbols[i]).toDouble();
+        } else {
+            const int k = arg_symbols.indexOf(symbols[i]);
+            if (k >= 0 && k < arr.size())
+                x = arr[k].toDouble();
+        }
+        sum += w[i] = std::max(0.0, x);
+    }
+    if (sum <= 1e-12)
+        return {};
+    for (auto& x : w)
+        x /= sum;
+    return w;
+}
+
+static QStringList symbols_from_json(const QJsonObject& args) {
+    QStringList out;
+    for (const auto& v : args.value("symbols").toArray())
+        out << v.toString().trimmed().toUpper();
+    return out;
+}
+
+static OptimizerParams params_from_json(const QJsonObject& args) {
+    OptimizerParams p;
+    p.risk_free = args.value("risk_free_rate").toDouble(p.risk_free);
+    p.target_return = args.value("target_return").toDouble(p.target_return);
+    p.target_volatility = args.value("target_volatility").toDouble(p.target_volatility);
+    return p;
+}
+
+static CovarianceOptions covariance_from_json(const QJsonObject& args) {
+    CovarianceOptions o;
+    o.method = CovarianceEstimator::method_from_string(args.value("risk_model").toString());
+    o.ewma_lambda = args.value("ewma_lambda").toDouble(o.ewma_lambda);
+    o.pca_factors = args.value("pca_factors").toInt(o.pca_factors);
+    return o;
+}
+
+// Annualised mean returns plus the selected covariance estimate and its
+// diagnostics.
+static bool inputs_with_covariance(const QStringList& symbols, const QVector<QVector<double>>& returns,
+                                   const CovarianceOptions& covariance, OptimizerInputs& in, QJsonObject& risk_model,
+                                   QString* error) {
+    in = PortfolioOptimizer::inputs_from_returns(symbols, returns);
+    const CovarianceEstimate est = CovarianceEstimator::estimate(symbols, returns, covariance);
+    risk_model = CovarianceEstimator::estimate_to_json(est, false);
+    if (!est.ok) {
+        *error = "covariance: " + est.error;
+        return false;
+    }
+    in.cov = est.cov;
+    return true;
+}
+
+// Primary method, the all-methods comparison and the market-implied returns,
+// in the shape optimize_portfolio_weights.py had.
+static QJsonObject optimize_job(const QJsonObject& args, const QStringList& symbols,
+                                const QVector<QVector<double>>& returns, const OptimizerConstraints& constraints,
+                                const CovarianceOptions& covariance) {
+    OptimizerInputs in;
+    QJsonObject risk_model;
+    QString error;
+    if (!inputs_with_covariance(symbols, returns, covariance, in, risk_model, &error))
+        return QJsonObject{{"error", error}};
+    const int n = in.size();
+    const OptimizerParams params = params_from_json(args);
+
+    // Market proxy for π = δ·Σ·w_mkt: the caller's current weights, else 1/N.
+    QVector<double> w_mkt = weights_from_json(args.value("weights"), symbols_from_json(args), symbols);
+    if (w_mkt.isEmpty())
+        w_mkt.fill(1.0 / n, n);
+    const QVector<double> implied = BlackLitterman::implied_returns(in.cov, w_mkt, 2.5);
+
+    const QString method_name = args.value("method").toString("max_sharpe").toLower().replace(' ', '_');
+    const bool black_litterman = method_name.startsWith("b-l") || method_name.startsWith("b_l") ||
+                                 method_name == "black_litterman";
+
+    OptimizerResult primary;
+    if (black_litterman) {
+        // Max Sharpe on the equilibrium returns, reported against history.
+        OptimizerInputs eq = in;
+        eq.mu = implied;
+        OptimizerParams p = params;
+        p.frontier_points = 0;
+        primary = PortfolioOptimizer::optimize(eq, OptimizerMethod::MaxSharpe, constraints, p);
+        if (primary.ok) {
+            double ret = 0.0;
+            for (int i = 0; i < n; ++i)
+                ret += primary.weights[i] * in.mu[i];
+            primary.expected_return = ret;
+            primary.sharpe = primary.volatility > 1e-12 ? (ret - params.risk_free) / primary.volatility : 0.0;
+            primary.frontier =
+                PortfolioOptimizer::efficient_frontier(in, constraints, params.frontier_points, params.risk_free);
+        }
+    } else {
+        primary = PortfolioOptimizer::optimize(in, PortfolioOptimizer::method_from_string(method_name), constraints,
+                                               params);
+    }
+    if (!primary.ok)
+        return QJsonObject{{"error", primary.error}};
+
+    QJsonObject out = PortfolioOptimizer::result_to_json(primary);
+    if (black_litterman)
+        out["strategy"] = QStringLiteral("black_litterman");
+
+    OptimizerParams cmp_params = params;
+    cmp_params.frontier_points = 0;
+    QJsonObject comparison;
+    for (auto m : {OptimizerMethod::MaxSharpe, OptimizerMethod::MinVolatility, OptimizerMethod::RiskParity,
+                   OptimizerMethod::Hrp, OptimizerMethod::EqualWeight}) {
+        const auto r = PortfolioOptimizer::optimize(in, m, constraints, cmp_params);
+        if (!r.ok)
+            continue;
+        comparison[PortfolioOptimizer::method_to_string(m)] =
+            QJsonObject{{"weights", by_symbol_json(r.symbols, r.weights)},
+                        {"return", r.expected_return},
+                        {"volatility", r.volatility},
+                        {"sharpe", r.sharpe}};
+    }
+    out["comparison"] = comparison;
+    out["implied_returns"] = by_symbol_json(symbols, implied);
+    out["observations"] = int(returns.size());
+    out["risk_model"] = risk_model;
+    return out;
+}
+
+// Posterior from the market prior and the views, then the optimiser on it.
+static QJsonObject black_litterman_job(const QJsonObject& args, const QStringList& symbols,
+                                       const QVector<QVector<double>>& returns, const OptimizerConstraints& constraints,
+                                       const CovarianceOptions& covariance) {
+    OptimizerInputs hist;
+    QJsonObject risk_model;
+    QString error;
+    if (!inputs_with_covariance(symbols, returns, covariance, hist, risk_model, &error))
+        return QJsonObject{{"error", error}};
+    const OptimizerParams params = params_from_json(args);
+    QStringList warnings;
+
+    BlInputs bl;
+    bl.symbols = symbols;
+    bl.cov = hist.cov;
+    bl.tau = args.value("tau").toDouble(bl.tau);
+    const QStringList arg_symbols = symbols_from_json(args);
+    bl.market_weights = weights_from_json(args.value("market_caps"), arg_symbols, symbols);
+    if (bl.market_weights.isEmpty()) {
+        bl.market_weights = weights_from_json(args.value("weights"), arg_symbols, symbols);
+        warnings << (bl.market_weights.isEmpty() ? "No market caps or weights — using an equal-weight prior"
+                                                 : "No market caps — using the supplied weights as the prior");
+    }
+    if (args.contains("risk_aversion")) {
+        bl.risk_aversion = args.value("risk_aversion").toDouble();
+    } else if (args.contains("market_return")) {
+        const double delta = BlackLitterman::implied_risk_aversion(
+            bl.cov, bl.market_weights.isEmpty() ? QVector<double>(symbols.size(), 1.0 / symbols.size())
+                                                : bl.market_weights,
+            args.value("market_return").toDouble(), params.risk_free);
+        if (delta > 0)
+            bl.risk_aversion = delta;
+        else
+            warnings << "market_return does not exceed the risk-free rate — risk aversion left at 2.5";
+    }
+
+    const QJsonArray views = args.value("views").toArray();
+    for (int i = 0; i < views.size(); ++i) {
+        BlView v;
+        QString err;
+        if (!BlackLitterman::view_from_json(views[i].toObject(), v, &err))
+            return QJsonObject{{"error", QString("view %1: %2").arg(i + 1).arg(err)}};
+        bl.views.append(v);
+    }
+
+    const BlResult post = BlackLitterman::compute(bl);
+    if (!post.ok)
+        return QJsonObject{{"error", post.error}};
+
+    OptimizerInputs in;
+    in.symbols = symbols;
+    in.mu = post.posterior_returns;
+    in.cov = post.posterior_cov;
+    const OptimizerMethod method = PortfolioOptimizer::method_from_string(args.value("method").toString());
+    const OptimizerResult opt = PortfolioOptimizer::optimize(in, method, constraints, params);
+    if (!opt.ok)
+        return QJsonObject{{"error", opt.error}};
+
+    QJsonObject out = BlackLitterman::result_to_json(post);
+    const QJsonObject opt_json = PortfolioOptimizer::result_to_json(opt);
+    for (auto it = opt_json.begin(); it != opt_json.end(); ++it)
+        if (it.key() != "warnings")
+            out[it.key()] = it.value();
+    out["strategy"] = QStringLiteral("black_litterman");
+    out["objective"] = PortfolioOptimizer::method_to_string(method);
+    out["implied_returns"] = out.value("prior_returns");
+    out["historical_returns"] = by_symbol_json(symbols, hist.mu);
+    out["observations"] = int(returns.size());
+    out["risk_model"] = risk_model;
+    out["warnings"] = QJsonArray::fromStringList(warnings + post.warnings + opt.warnings);
+    return out;
+}
+
+// Ex-ante volatility / VaR of the given weights under the chosen estimator,
+// with every other estimator's volatility alongside for comparison.
+static QJsonObject forecast_risk_job(const QJsonObject& args, const QStringList& symbols,
+                                     const QVector<QVector<double>>& returns, const OptimizerConstraints&,
+                                     const CovarianceOptions& covariance) {
+    constexpr double kZ95 = 1.6448536269514722;
+    constexpr double kZ99 = 2.3263478740408408;
+    const int n = int(symbols.size());
+    QVector<double> w = weights_from_json(args.value("weights"), symbols_from_json(args), symbols);
+    QStringList warnings;
+    if (w.isEmpty()) {
+        w.fill(1.0 / n, n);
+        warnings << "No weights given — using equal weights";
+    }
+    auto vol_of = [&](const QVector<double>& cov) {
+        double v = 0.0;
+        for (int i = 0; i < n; ++i)
+            for (int j = 0; j < n; ++j)
+                v += w[i] * cov[i * n + j] * w[j];
+        return std::sqrt(std::max(v, 0.0));
+    };
+
+    const CovarianceEstimate est = CovarianceEstimator::estimate(symbols, returns, covariance);
+    if (!est.ok)
+        return QJsonObject{{"error", "covariance: " + est.error}};
+    const double vol = vol_of(est.cov);
+    const double daily = vol / std::sqrt(double(covariance.periods_per_year));
+
+    QVector<double> rc(n, 0.0), mrc(n, 0.0);
+    double weighted_vol = 0.0;
+    for (int i = 0; i < n; ++i) {
+        double sw = 0.0;
+        for (int j = 0; j < n; ++j)
+            sw += est.cov[i * n + j] * w[j];
+        mrc[i] = vol > 1e-12 ? sw / vol : 0.0;
+        rc[i] = vol > 1e-12 ? w[i] * mrc[i] / vol * 100.0 : 0.0;
+        weighted_vol += std::abs(w[i]) * std::sqrt(std::max(est.cov[i * n + i], 0.0));
+    }
+
+    QJsonObject comparison;
+    for (auto m : {CovarianceMethod::Sample, CovarianceMethod::LedoitWolf, CovarianceMethod::ConstantCorrelation,
+                   CovarianceMethod::Ewma, CovarianceMethod::Semicovariance, CovarianceMethod::Factor}) {
+        CovarianceOptions o = covariance;
+        o.method = m;
+        const CovarianceEstimate e = m == covariance.method ? est : CovarianceEstimator::estimate(symbols, returns, o);
+        if (e.ok)
+            comparison[CovarianceEstimator::method_to_string(m)] = vol_of(e.cov);
+    }
+
+    QJsonObject out{{"symbols", QJsonArray::fromStringList(symbols)},
+                    {"weights", by_symbol_json(symbols, w)},
+                    {"strategy", CovarianceEstimator::method_to_string(est.method)},
+                    {"annual_volatility", vol},
+                    {"daily_volatility", daily},
+                    {"var_95_1d", kZ95 * daily},
+                    {"var_99_1d", kZ99 * daily},
+                    {"risk_contributions", by_symbol_json(symbols, rc)},
+                    {"marginal_risk", by_symbol_json(symbols, mrc)},
+                    {"diversification_ratio", vol > 1e-12 ? weighted_vol / vol : 0.0},
+                    {"volatility_by_estimator", comparison},
+                    {"risk_model", CovarianceEstimator::estimate_to_json(est, args.value("include_matrix").toBool())},
+                    {"observations", int(returns.size())},
+                    {"warnings", QJsonArray::fromStringList(warnings + est.warnings)}};
+    const double value = args.value("portfolio_value").toDouble();
+    if (value > 0) {
+        out["var_95_1d_value"] = kZ95 * daily * value;
+        out["var_99_1d_value"] = kZ99 * daily * value;
+    }
+    return out;
+}
+
 void PortfolioAnalyticsService::optimize_weights(const QString& args_json, AnalyticsCallback cb) {
-    run_script(QStringLiteral("optimize_portfolio_weights"), args_json, std::move(cb));
+    run_native(QStringLiteral("Optimization"), QJsonDocument::fromJson(args_json.toUtf8()).object(), optimize_job,
+               std::move(cb));
+}
+
+void PortfolioAnalyticsService::black_litterman(const QString& args_json, AnalyticsCallback cb) {
+    run_native(QStringLiteral("Black-Litterman"), QJsonDocument::fromJson(args_json.toUtf8()).object(),
+               black_litterman_job, std::move(cb));
+}
+
+void PortfolioAnalyticsService::forecast_risk(const QString& args_json, AnalyticsCallback cb) {
+    run_native(QStringLiteral("Risk forecast"), QJsonDocument::fromJson(args_json.toUtf8()).object(),
+               forecast_risk_job, std::move(cb));
+}
+
+void PortfolioAnalyticsService::run_native(const QString& what, const QJsonObject& args, NativeJob job,
+                                           AnalyticsCallback cb) {
+    const QStringList symbols = symbols_from_json(args);
+    if (symbols.isEmpty()) {
+        if (cb)
+            cb(AnalyticsResult{false, {}, QStringLiteral("No symbols provided")});
+        return;
+    }
+
+    // Explicit factor series ride along with the assets so they share dates.
+    CovarianceOptions covariance = covariance_from_json(args);
+    QStringList factors;
+    if (covariance.method == CovarianceMethod::Factor) {
+        for (const auto& v : args.value("factor_symbols").toArray())
+            factors << v.toString().trimmed().toUpper();
+        factors.removeAll(QString());
+    }
+
+    QPointer<PortfolioAnalyticsService> self = this;
+    PortfolioService::instance().aligned_returns(
+        symbols + factors, args.value("lookback_days").toInt(365),
+        [self, what, args, job, cb, symbols, factors, covariance](QStringList kept, QVector<QVector<double>> returns,
+                                                                   QStringList warnings) mutable {
+            if (!self)
+                return;
+            if (!factors.isEmpty()) {
+                // Split the factor columns off; a factor that is also an
+                // asset stays in both.
+                QVector<int> asset_cols, factor_cols;
+                for (int c = 0; c < kept.size(); ++c) {
+                    if (symbols.contains(kept[c]))
+                        asset_cols << c;
+                    if (factors.contains(kept[c])) {
+                        factor_cols << c;
+                        covariance.factor_names << kept[c];
+                    }
+                }
+                if (covariance.factor_names.size() < factors.size())
+                    warnings << "Some factor series have no history — the factor model uses "
+                                + (covariance.factor_names.isEmpty() ? QStringLiteral("principal components")
+                                                                      : covariance.factor_names.join(", "));
+                QStringList assets;
+                for (int c : asset_cols)
+                    assets << kept[c];
+                for (auto& row : returns) {
+                    QVector<double> a, f;
+                    for (int c : asset_cols)
+                        a << row[c];
+                    for (int c : factor_cols)
+                        f << row[c];
+                    row = a;
+                    covariance.factor_returns.append(f);
+                }
+                kept = assets;
+            }
+            if (kept.isEmpty()) {
+                const QString err = warnings.isEmpty() ? QStringLiteral("No price data for any symbol")
+                                                       : warnings.join("; ");
+                LOG_WARN("PortfolioAnalyticsService", what + " skipped: " + err);
+                if (cb)
+                    cb(AnalyticsResult{false, {}, err});
+                return;
+            }
+            // Sector lookups touch the resolver's cache — do them here, on
+            // the main thread, before handing off.
+            const auto constraints = constraints_from_json(args.value("constraints").toObject(), kept);
+            (void)QtConcurrent::run([self, what, args, job, kept, returns, warnings, constraints, covariance, cb]() {
+                QJsonObject root = job(args, kept, returns, constraints, covariance);
+                QJsonArray all_warnings = QJsonArray::fromStringList(warnings);
+                for (const auto& w : root.value("warnings").toArray())
+                    all_warnings.append(w);
+                if (!root.contains("error"))
+                    root["warnings"] = all_warnings;
+                QMetaObject::invokeMethod(
+                    self,
+                    [what, root, cb]() {
+                        AnalyticsResult out;
+                        if (root.contains("error")) {
+                            out.error = root.value("error").toString();
+                            LOG_WARN("PortfolioAnalyticsService", what + " failed: " + out.error);
+                        } else {
+                            out.success = true;
+                            out.data = root;
+                            LOG_INFO("PortfolioAnalyticsService",
+                                     QString("%1 done: %2 over %3 symbol(s) in %4 ms")
+                                         .arg(what, root.value("strategy").toString())
+                                         .arg(root.value("symbols").toArray().size())
+                                         .arg(root.value("elapsed_ms").toDouble(), 0, 'f', 1));
+                        }
+                        if (cb)
+                            cb(out);
+                    },
+                    Qt::QueuedConnection);
+            });
+        });
 }
 
 void PortfolioAnalyticsService::run_ffn(const QStringList& symbols, const QJsonObject& weights_by_symbol,
@@ -7,6 +7,9 @@
 // async methods with a QPointer-guarded callback; the service forwards to
 // PythonRunner and returns the parsed JSON result (or an error).
 
+#include "services/portfolio/CovarianceEstimator.h"
+#include "services/portfolio/PortfolioOptimizer.h"
+
 #include <QJsonDocument>
 #include <QJsonObject>
 #include <QObject>
@@ -24,8 +27,8 @@ struct AnalyticsResult {
 
 using AnalyticsCallback = std::function<void(const AnalyticsResult&)>;
 
-/// Wraps the quantstats / optimizer / ffn Python scripts invoked by the
-/// portfolio views. All calls are async; callback fires on the main thread.
+/// Wraps the quantstats / ffn Python scripts and the native optimiser used
+/// by the portfolio views. All calls are async; callback fires on the main thread.
 /// Callers should capture a QPointer to themselves per P8.
 class PortfolioAnalyticsService : public QObject {
     Q_OBJECT
@@ -39,16 +42,54 @@ class PortfolioAnalyticsService : public QObject {
     void run_monte_carlo(const QStringList& symbols, const QList<double>& weights, int num_simulations,
                          AnalyticsCallback cb);
 
-    /// Runs `optimize_portfolio_weights` with an opaque pre-built args JSON
-    /// (the view already constructs a method-specific payload).
+    /// Native optimisation (PortfolioOptimizer) on stored daily closes — no
+    /// Python, works offline once prices are cached. `args_json` keeps the
+    /// optimize_portfolio_weights.py payload ({symbols, weights, method,
+    /// risk_model}) plus optional risk_free_rate, target_return,
+    /// target_volatility, lookback_days, covariance settings (ewma_lambda,
+    /// pca_factors, factor_symbols — see CovarianceEstimator) and
+    /// constraints {long_only, min_weight, max_weight, bounds{sym:[min,max]},
+    /// sectors{sym:sector}, sector_caps{sector:max}}.
+    /// The result has the script's shape, including `comparison` and
+    /// `implied_returns`.
     void optimize_weights(const QString& args_json, AnalyticsCallback cb);
 
+    /// Black-Litterman on stored daily closes: market-cap prior plus investor
+    /// views (absolute / relative, each with a confidence) → posterior
+    /// returns and covariance, then the native optimiser on the posterior.
+    /// `args_json`: {symbols, market_caps{sym:cap} (else weights[] or 1/N),
+    /// views[], tau, risk_aversion | market_return, risk_free_rate, method,
+    /// risk_model, constraints, lookback_days}. Besides the optimize_weights
+    /// keys the result carries π, P, Q, Ω, μ_BL and both covariances.
+    void black_litterman(const QString& args_json, AnalyticsCallback cb);
+
+    /// Ex-ante risk of a weight vector under a chosen covariance estimator:
+    /// annual/daily volatility, 1-day parametric VaR 95/99, risk
+    /// contributions, diversification ratio, and the same volatility under
+    /// every other estimator for comparison. `args_json`: {symbols, weights,
+    /// risk_model, ewma_lambda, pca_factors, factor_symbols, portfolio_value,
+    /// lookback_days, include_matrix}.
+    void forecast_risk(const QString& args_json, AnalyticsCallback cb);
+
     /// Runs `ffn_analysis` with `{symbols, weights}` — weights is a symbol→frac map.
     void run_ffn(const QStringList& symbols, const QJsonObject& weights_by_symbol, AnalyticsCallback cb);
 
   private:
     PortfolioAnalyticsService() = default;
 
+    /// Worker-thread body of a native run: aligned symbols, their daily
+    /// returns, the parsed constraints and the covariance estimator settings
+    /// (factor series included) → result JSON ({error} on failure).
+    using NativeJob = std::function<QJsonObject(const QJsonObject& args, const QStringList& symbols,
+                                                const QVector<QVector<double>>& returns,
+                                                const OptimizerConstraints& constraints,
+                                                const CovarianceOptions& covariance)>;
+    /// Loads aligned returns for args.symbols (plus args.factor_symbols for
+    /// factor covariance), runs `job` off the main thread and hands the
+    /// result back on it. Data warnings are merged into the result's
+    /// `warnings`.
+    void run_native(const QString& what, const QJsonObject& args, NativeJob job, AnalyticsCallback cb);
+
     /// Core dispatch: invoke `script` with `args_json` and decode result.
     void run_script(const QString& script, const QString& args_json, AnalyticsCallback cb);
 };
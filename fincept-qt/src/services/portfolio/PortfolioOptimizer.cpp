@@ -0,0 +1,794 @@
+// src/services/portfolio/PortfolioOptimizer.cpp
+#include "services/portfolio/PortfolioOptimizer.h"
+
+#include <QElapsedTimer>
+#include <QJsonArray>
+
+#include <algorithm>
+#include <cmath>
+#include <functional>
+#include <limits>
+#include <numeric>
+#include <vector>
+
+namespace fincept::services {
+
+namespace {
+
+using Vec = std::vector<double>;
+
+constexpr int kOptBisectIters = 56;
+constexpr double kOptTol = 1e-9;
+
+// ── Feasible set ─────────────────────────────────────────────────────────────
+
+/// Budget + boxes + disjoint sector caps, resolved to indices.
+struct opt_Feasible {
+    int n = 0;
+    Vec lb, ub;
+    std::vector<int> group;            // -1 = no capped sector
+    Vec cap;                           // per group
+    std::vector<std::vector<int>> members;
+    QString error;
+
+    double clip(int i, double x) const { return std::clamp(x, lb[i], ub[i]); }
+
+    /// Threshold τ with Σ_g clip(y_i − τ) = cap_g, or −∞ when the cap can't bind.
+    double group_threshold(const Vec& y, int g) const {
+        double ub_sum = 0.0;
+        double lo = std::numeric_limits<double>::max();
+        double hi = std::numeric_limits<double>::lowest();
+        for (int i : members[g]) {
+            ub_sum += ub[i];
+            lo = std::min(lo, y[i] - ub[i]);
+            hi = std::max(hi, y[i] - lb[i]);
+        }
+        if (ub_sum <= cap[g])
+            return -std::numeric_limits<double>::infinity();
+        for (int it = 0; it < kOptBisectIters; ++it) {
+            const double mid = 0.5 * (lo + hi);
+            double s = 0.0;
+            for (int i : members[g])
+                s += clip(i, y[i] - mid);
+            (s > cap[g] ? lo : hi) = mid;
+        }
+        return hi;
+    }
+
+    /// Euclidean projection of `y` onto the feasible set. For a given budget
+    /// multiplier λ each capped group uses max(λ, τ_g); λ is then searched so
+    /// the weights sum to one.
+    Vec project(const Vec& y) const {
+        Vec tau(members.size());
+        for (size_t g = 0; g < members.size(); ++g)
+            tau[g] = group_threshold(y, int(g));
+        auto thr = [&](int i, double lambda) { return group[i] < 0 ? lambda : std::max(lambda, tau[group[i]]); };
+        double lo = std::numeric_limits<double>::max();
+        double hi = std::numeric_limits<double>::lowest();
+        for (int i = 0; i < n; ++i) {
+            lo = std::min(lo, y[i] - ub[i]);
+            hi = std::max(hi, y[i] - lb[i]);
+        }
+        for (int it = 0; it < kOptBisectIters; ++it) {
+            const double mid = 0.5 * (lo + hi);
+            double s = 0.0;
+            for (int i = 0; i < n; ++i)
+                s += clip(i, y[i] - thr(i, mid));
+            (s > 1.0 ? lo : hi) = mid;
+        }
+        const double lambda = 0.5 * (lo + hi);
+        Vec w(n);
+        for (int i = 0; i < n; ++i)
+            w[i] = clip(i, y[i] - thr(i, lambda));
+        return w;
+    }
+
+    /// Exact minimiser of c·w over the set. The constraints are laminar, so
+    /// filling the cheapest assets first from the lower bounds is optimal.
+    Vec linear_min(const Vec& c) const {
+        Vec w = lb;
+        double remaining = 1.0 - std::accumulate(lb.begin(), lb.end(), 0.0);
+        Vec room(members.size());
+        for (size_t g = 0; g < members.size(); ++g) {
+            room[g] = cap[g];
+            for (int i : members[g])
+                room[g] -= lb[i];
+        }
+        std::vector<int> order(n);
+        std::iota(order.begin(), order.end(), 0);
+        std::stable_sort(order.begin(), order.end(), [&c](int a, int b) { return c[a] < c[b]; });
+        for (int i : order) {
+            if (remaining <= 0)
+                break;
+            double add = std::min(ub[i] - lb[i], remaining);
+            if (group[i] >= 0)
+                add = std::min(add, std::max(0.0, room[group[i]]));
+            w[i] += add;
+            remaining -= add;
+            if (group[i] >= 0)
+                room[group[i]] -= add;
+        }
+        return w;
+    }
+};
+
+opt_Feasible opt_build_feasible(const OptimizerInputs& in, const OptimizerConstraints& c) {
+    opt_Feasible f;
+    f.n = in.size();
+    f.lb.resize(f.n);
+    f.ub.resize(f.n);
+    f.group.assign(f.n, -1);
+    const double dmin = c.long_only ? std::max(0.0, c.default_min) : c.default_min;
+    QHash<QString, int> gidx;
+    for (int i = 0; i < f.n; ++i) {
+        const QString& sym = in.symbols[i];
+        const auto b = c.bounds.value(sym, {dmin, c.default_max});
+        f.lb[i] = c.long_only ? std::max(0.0, b.first) : b.first;
+        f.ub[i] = b.second;
+        if (f.lb[i] > f.ub[i] + 1e-12) {
+            f.error = QString("min weight above max for %1").arg(sym);
+            return f;
+        }
+        const QString sector = c.sector_of.value(sym);
+        if (sector.isEmpty() || !c.sector_caps.contains(sector))
+            continue;
+        if (!gidx.contains(sector)) {
+            gidx.insert(sector, int(f.members.size()));
+            f.members.emplace_back();
+            f.cap.push_back(c.sector_caps.value(sector));
+        }
+        f.group[i] = gidx.value(sector);
+        f.members[f.group[i]].push_back(i);
+    }
+
+    double lb_sum = 0.0;
+    double reach = 0.0;
+    for (int i = 0; i < f.n; ++i) {
+        lb_sum += f.lb[i];
+        if (f.group[i] < 0)
+            reach += f.ub[i];
+    }
+    for (size_t g = 0; g < f.members.size(); ++g) {
+        double glb = 0.0, gub = 0.0;
+        for (int i : f.members[g]) {
+            glb += f.lb[i];
+            gub += f.ub[i];
+        }
+        if (glb > f.cap[g] + 1e-9) {
+            f.error = QString("sector cap %1 is below the sum of its minimum weights").arg(f.cap[g]);
+            return f;
+        }
+        reach += std::min(gub, f.cap[g]);
+    }
+    if (lb_sum > 1.0 + 1e-9)
+        f.error = "minimum weights sum above 100%";
+    else if (reach < 1.0 - 1e-9)
+        f.error = "maximum weights and sector caps cannot reach 100% invested";
+    return f;
+}
+
+// ── Linear algebra ───────────────────────────────────────────────────────────
+
+Vec opt_matvec(const OptimizerInputs& in, const Vec& w) {
+    const int n = in.size();
+    Vec out(n, 0.0);
+    for (int i = 0; i < n; ++i) {
+        const double* row = in.cov.constData() + size_t(i) * n;
+        double s = 0.0;
+        for (int j = 0; j < n; ++j)
+            s += row[j] * w[j];
+        out[i] = s;
+    }
+    return out;
+}
+
+double opt_dot(const Vec& a, const Vec& b) {
+    double s = 0.0;
+    for (size_t i = 0; i < a.size(); ++i)
+        s += a[i] * b[i];
+    return s;
+}
+
+double opt_mu_dot(const OptimizerInputs& in, const Vec& w) {
+    double s = 0.0;
+    for (int i = 0; i < in.size(); ++i)
+        s += in.mu[i] * w[i];
+    return s;
+}
+
+double opt_vol(const OptimizerInputs& in, const Vec& w) {
+    return std::sqrt(std::max(0.0, opt_dot(w, opt_matvec(in, w))));
+}
+
+/// Largest eigenvalue of Σ by power iteration — the gradient's Lipschitz constant.
+double opt_lipschitz(const OptimizerInputs& in) {
+    const int n = in.size();
+    Vec v(n, 1.0 / std::sqrt(double(std::max(n, 1))));
+    double lambda = 0.0;
+    for (int it = 0; it < 60; ++it) {
+        Vec av = opt_matvec(in, v);
+        const double norm = std::sqrt(opt_dot(av, av));
+        if (norm < 1e-300)
+            return 1.0;
+        lambda = norm;
+        for (int i = 0; i < n; ++i)
+            v[i] = av[i] / norm;
+    }
+    return lambda * 1.05;
+}
+
+// ── Mean-variance solver ─────────────────────────────────────────────────────
+
+/// argmin ½wᵀΣw − t·μᵀw over the feasible set (FISTA with adaptive restart).
+struct opt_MvSolver {
+    const OptimizerInputs& in;
+    const opt_Feasible& f;
+    double L = 1.0;
+    int iterations = 0;
+
+    Vec solve(double t, Vec x) {
+        const int n = in.size();
+        Vec y = x;
+        double theta = 1.0;
+        for (int k = 0; k < PortfolioOptimizer::kMaxIterations; ++k) {
+            ++iterations;
+            Vec g = opt_matvec(in, y);
+            Vec step(n);
+            for (int i = 0; i < n; ++i)
+                step[i] = y[i] - (g[i] - t * in.mu[i]) / L;
+            Vec xn = f.project(step);
+
+            double delta = 0.0, restart = 0.0;
+            for (int i = 0; i < n; ++i) {
+                delta = std::max(delta, std::abs(xn[i] - x[i]));
+                restart += (y[i] - xn[i]) * (xn[i] - x[i]);
+            }
+            if (restart > 0.0) {
+                theta = 1.0;
+                y = xn;
+            } else {
+                const double theta_n = 0.5 * (1.0 + std::sqrt(1.0 + 4.0 * theta * theta));
+                const double mom = (theta - 1.0) / theta_n;
+                for (int i = 0; i < n; ++i)
+                    y[i] = xn[i] + mom * (xn[i] - x[i]);
+                theta = theta_n;
+            }
+            x.swap(xn);
+            if (delta < kOptTol)
+                break;
+        }
+        return x;
+    }
+};
+
+/// Risk tolerance at which the utility optimum reaches the max-return corner
+/// (the far end of the frontier). Doubling starts from a scale-aware guess.
+double opt_t_max(const OptimizerInputs& in, const opt_Feasible& f, opt_MvSolver& mv, const Vec& start) {
+    Vec neg_mu(in.mu.begin(), in.mu.end());
+    double mu_abs = 1e-12;
+    for (auto& v : neg_mu) {
+        mu_abs = std::max(mu_abs, std::abs(v));
+        v = -v;
+    }
+    const double r_max = opt_mu_dot(in, f.linear_min(neg_mu));
+    Vec x = start;
+    double t = mv.L / (in.size() * mu_abs);
+    for (int k = 0; k < 60; ++k, t *= 2.0) {
+        x = mv.solve(t, x);
+        if (opt_mu_dot(in, x) >= r_max - 1e-7 * std::max(1.0, std::abs(r_max)))
+            break;
+    }
+    return t;
+}
+
+/// `points` frontier portfolios at t = t_max·(k/(points−1))², warm-started
+/// left to right; quadratic spacing keeps the low-risk end well sampled.
+QVector<OptimizerFrontierPoint> opt_frontier(const OptimizerInputs& in, opt_MvSolver& mv, const Vec& start,
+                                             double t_max, int points, double risk_free) {
+    QVector<OptimizerFrontierPoint> out;
+    Vec w = start;
+    for (int k = 0; k < points; ++k) {
+        const double frac = double(k) / (points - 1);
+        w = mv.solve(t_max * frac * frac, w);
+        OptimizerFrontierPoint p;
+        p.ret = opt_mu_dot(in, w);
+        p.volatility = opt_vol(in, w);
+        p.sharpe = p.volatility > 1e-12 ? (p.ret - risk_free) / p.volatility : 0.0;
+        if (out.isEmpty() || p.ret > out.last().ret + 1e-9)
+            out.append(p);
+    }
+    return out;
+}
+
+// ── Risk parity / HRP ────────────────────────────────────────────────────────
+
+/// Equal risk contribution via cyclical coordinate descent on
+/// ½xᵀΣx − Σ bᵢ ln xᵢ (Spinu's convex form), normalised to Σw = 1.
+Vec opt_risk_parity(const OptimizerInputs& in, int* iterations) {
+    const int n = in.size();
+    const double b = 1.0 / n;
+    Vec x(n);
+    for (int i = 0; i < n; ++i)
+        x[i] = 1.0 / std::sqrt(std::max(in.cov_at(i, i), 1e-12));
+    for (int sweep = 0; sweep < 2000; ++sweep) {
+        ++*iterations;
+        double change = 0.0;
+        for (int i = 0; i < n; ++i) {
+            const double sii = std::max(in.cov_at(i, i), 1e-12);
+            double a = 0.0;
+            for (int j = 0; j < n; ++j)
+                if (j != i)
+                    a += in.cov_at(i, j) * x[j];
+            const double xi = (-a + std::sqrt(a * a + 4.0 * sii * b)) / (2.0 * sii);
+            change = std::max(change, std::abs(xi - x[i]) / std::max(x[i], 1e-300));
+            x[i] = xi;
+        }
+        if (change < 1e-10)
+            break;
+    }
+    const double s = std::accumulate(x.begin(), x.end(), 0.0);
+    for (auto& v : x)
+        v /= s;
+    return x;
+}
+
+/// López de Prado's HRP: single-linkage tree on the correlation distance
+/// (built from the minimum spanning tree), quasi-diagonal leaf order, then
+/// recursive bisection by inverse-variance cluster risk.
+Vec opt_hrp(const OptimizerInputs& in) {
+    const int n = in.size();
+    if (n == 1)
+        return Vec{1.0};
+    Vec sd(n);
+    for (int i = 0; i < n; ++i)
+        sd[i] = std::sqrt(std::max(in.cov_at(i, i), 1e-24));
+    auto dist = [&](int i, int j) {
+        const double rho = std::clamp(in.cov_at(i, j) / (sd[i] * sd[j]), -1.0, 1.0);
+        return std::sqrt(std::max(0.0, 0.5 * (1.0 - rho)));
+    };
+
+    // Prim's MST, O(n²).
+    struct Edge {
+        int a, b;
+        double d;
+    };
+    std::vector<Edge> edges;
+    std::vector<char> in_tree(n, 0);
+    Vec best(n, std::numeric_limits<double>::max());
+    std::vector<int> from(n, -1);
+    best[0] = 0.0;
+    for (int k = 0; k < n; ++k) {
+        int u = -1;
+        for (int i = 0; i < n; ++i)
+            if (!in_tree[i] && (u < 0 || best[i] < best[u]))
+                u = i;
+        in_tree[u] = 1;
+        if (from[u] >= 0)
+            edges.push_back({from[u], u, best[u]});
+        for (int v = 0; v < n; ++v) {
+            if (in_tree[v])
+                continue;
+            const double d = dist(u, v);
+            if (d < best[v]) {
+                best[v] = d;
+                from[v] = u;
+            }
+        }
+    }
+    std::stable_sort(edges.begin(), edges.end(), [](const Edge& x, const Edge& y) { return x.d < y.d; });
+
+    // Kruskal over the MST edges = single-linkage merge order.
+    std::vector<int> parent(n);
+    std::iota(parent.begin(), parent.end(), 0);
+    std::function<int(int)> find = [&](int x) { return parent[x] == x ? x : parent[x] = find(parent[x]); };
+    std::vector<int> node_of(n); // union-find root → tree node id
+    std::iota(node_of.begin(), node_of.end(), 0);
+    std::vector<std::pair<int, int>> children(n, {-1, -1});
+    for (const auto& e : edges) {
+        const int ra = find(e.a);
+        const int rb = find(e.b);
+        const int id = int(children.size());
+        children.push_back({node_of[ra], node_of[rb]});
+        parent[rb] = ra;
+        node_of[ra] = id;
+    }
+
+    std::vector<int> order;
+    std::vector<int> stack{int(children.size()) - 1};
+    while (!stack.empty()) {
+        const int node = stack.back();
+        stack.pop_back();
+        if (node < n) {
+            order.push_back(node);
+            continue;
+        }
+        stack.push_back(children[node].second);
+        stack.push_back(children[node].first);
+    }
+
+    auto cluster_var = [&](const std::vector<int>& items) {
+        Vec ivp(items.size());
+        double s = 0.0;
+        for (size_t k = 0; k < items.size(); ++k) {
+            ivp[k] = 1.0 / std::max(in.cov_at(items[k], items[k]), 1e-24);
+            s += ivp[k];
+        }
+        double v = 0.0;
+        for (size_t a = 0; a < items.size(); ++a)
+            for (size_t b = 0; b < items.size(); ++b)
+                v += ivp[a] / s * ivp[b] / s * in.cov_at(items[a], items[b]);
+        return v;
+    };
+
+    Vec w(n, 1.0);
+    std::vector<std::vector<int>> clusters{order};
+    while (!clusters.empty()) {
+        std::vector<std::vector<int>> next;
+        for (const auto& c : clusters) {
+            if (c.size() < 2)
+                continue;
+            const size_t half = c.size() / 2;
+            std::vector<int> left(c.begin(), c.begin() + half);
+            std::vector<int> right(c.begin() + half, c.end());
+            const double vl = cluster_var(left);
+            const double vr = cluster_var(right);
+            const double alpha = vl + vr > 0 ? 1.0 - vl / (vl + vr) : 0.5;
+            for (int i : left)
+                w[i] *= alpha;
+            for (int i : right)
+                w[i] *= 1.0 - alpha;
+            next.push_back(std::move(left));
+            next.push_back(std::move(right));
+        }
+        clusters.swap(next);
+    }
+    return w;
+}
+
+// ── Result assembly ──────────────────────────────────────────────────────────
+
+void opt_fill_metrics(const OptimizerInputs& in, const Vec& w, double rf, OptimizerResult& r) {
+    const int n = in.size();
+    r.symbols = in.symbols;
+    r.weights = QVector<double>(w.begin(), w.end());
+    const Vec sw = opt_matvec(in, w);
+    r.expected_return = opt_mu_dot(in, w);
+    r.volatility = std::sqrt(std::max(0.0, opt_dot(w, sw)));
+    r.sharpe = r.volatility > 1e-12 ? (r.expected_return - rf) / r.volatility : 0.0;
+    r.risk_contribution_pct.resize(n);
+    r.marginal_risk.resize(n);
+    r.asset_volatility.resize(n);
+    for (int i = 0; i < n; ++i) {
+        const double mrc = r.volatility > 1e-12 ? sw[i] / r.volatility : 0.0;
+        r.marginal_risk[i] = mrc;
+        r.risk_contribution_pct[i] = r.volatility > 1e-12 ? w[i] * mrc / r.volatility * 100.0 : 0.0;
+        r.asset_volatility[i] = std::sqrt(std::max(0.0, in.cov_at(i, i)));
+    }
+}
+
+QJsonObject opt_by_symbol(const QStringList& symbols, const QVector<double>& v) {
+    QJsonObject o;
+    for (int i = 0; i < symbols.size() && i < v.size(); ++i)
+        o[symbols[i]] = v[i];
+    return o;
+}
+
+} // namespace
+
+bool OptimizerConstraints::is_trivial() const {
+    return long_only && default_min <= 0.0 && default_max >= 1.0 && bounds.isEmpty() && sector_caps.isEmpty();
+}
+
+// ── Inputs ───────────────────────────────────────────────────────────────────
+
+OptimizerInputs PortfolioOptimizer::inputs_from_returns(const QStringList& symbols,
+                                                         const QVector<QVector<double>>& returns,
+                                                         int periods_per_year) {
+    OptimizerInputs in;
+    in.symbols = symbols;
+    const int n = int(symbols.size());
+    const int t = int(returns.size());
+    in.mu.fill(0.0, n);
+    in.cov.fill(0.0, n * n);
+    if (t < 2)
+        return in;
+    QVector<double> mean(n, 0.0);
+    for (const auto& row : returns)
+        for (int i = 0; i < n; ++i)
+            mean[i] += row[i];
+    for (int i = 0; i < n; ++i)
+        mean[i] /= t;
+    for (const auto& row : returns)
+        for (int i = 0; i < n; ++i) {
+            const double di = row[i] - mean[i];
+            for (int j = i; j < n; ++j)
+                in.cov[i * n + j] += di * (row[j] - mean[j]);
+        }
+    for (int i = 0; i < n; ++i) {
+        in.mu[i] = mean[i] * periods_per_year;
+        for (int j = i; j < n; ++j) {
+            const double c = in.cov[i * n + j] / (t - 1) * periods_per_year;
+            in.cov[i * n + j] = c;
+            in.cov[j * n + i] = c;
+        }
+    }
+    return in;
+}
+
+// ── Method names ─────────────────────────────────────────────────────────────
+
+OptimizerMethod PortfolioOptimizer::method_from_string(const QString& s) {
+    const QString k = s.trimmed().toLower().replace('-', '_').replace(' ', '_');
+    if (k == "min_volatility" || k == "min_variance" || k == "min_vol")
+        return OptimizerMethod::MinVolatility;
+    if (k == "max_return")
+        return OptimizerMethod::MaxReturn;
+    if (k == "target_return" || k == "efficient_return")
+        return OptimizerMethod::TargetReturn;
+    if (k == "target_volatility" || k == "efficient_risk")
+        return OptimizerMethod::TargetVolatility;
+    if (k == "risk_parity" || k == "erc")
+        return OptimizerMethod::RiskParity;
+    if (k == "hrp")
+        return OptimizerMethod::Hrp;
+    if (k == "equal_weight" || k == "equal")
+        return OptimizerMethod::EqualWeight;
+    if (k == "inverse_variance" || k == "inv_var")
+        return OptimizerMethod::InverseVariance;
+    return OptimizerMethod::MaxSharpe;
+}
+
+QString PortfolioOptimizer::method_to_string(OptimizerMethod m) {
+    switch (m) {
+        case OptimizerMethod::MinVolatility:
+            return "min_volatility";
+        case OptimizerMethod::MaxReturn:
+            return "max_return";
+        case OptimizerMethod::TargetReturn:
+            return "target_return";
+        case OptimizerMethod::TargetVolatility:
+            return "target_volatility";
+        case OptimizerMethod::RiskParity:
+            return "risk_parity";
+        case OptimizerMethod::Hrp:
+            return "hrp";
+        case OptimizerMethod::EqualWeight:
+            return "equal_weight";
+        case OptimizerMethod::InverseVariance:
+            return "inverse_variance";
+        case OptimizerMethod::MaxSharpe:
+        default:
+            return "max_sharpe";
+    }
+}
+
+// ── Frontier ─────────────────────────────────────────────────────────────────
+
+QVector<OptimizerFrontierPoint> PortfolioOptimizer::efficient_frontier(const OptimizerInputs& in,
+                                                                       const OptimizerConstraints& constraints,
+                                                                       int points, double risk_free) {
+    const opt_Feasible f = opt_build_feasible(in, constraints);
+    if (!f.error.isEmpty() || in.size() == 0 || points < 2)
+        return {};
+    opt_MvSolver mv{in, f, opt_lipschitz(in)};
+    const Vec start = f.project(Vec(in.size(), 1.0 / in.size()));
+    return opt_frontier(in, mv, start, opt_t_max(in, f, mv, start), points, risk_free);
+}
+
+// ── Optimise ─────────────────────────────────────────────────────────────────
+
+OptimizerResult PortfolioOptimizer::optimize(const OptimizerInputs& in, OptimizerMethod method,
+                                             const OptimizerConstraints& constraints, const OptimizerParams& params) {
+    OptimizerResult r;
+    r.method = method;
+    QElapsedTimer timer;
+    timer.start();
+
+    const int n = in.size();
+    if (n == 0 || in.mu.size() != n || in.cov.size() != n * n) {
+        r.error = "optimizer inputs are empty or mis-sized";
+        return r;
+    }
+    const opt_Feasible f = opt_build_feasible(in, constraints);
+    if (!f.error.isEmpty()) {
+        r.error = "infeasible constraints: " + f.error;
+        return r;
+    }
+
+    opt_MvSolver mv{in, f, opt_lipschitz(in)};
+    const Vec start = f.project(Vec(n, 1.0 / n));
+    Vec w;
+
+    // Returns along the utility path are monotone in t, so targets bisect on t.
+    double t_max_cached = -1.0;
+    auto t_upper = [&]() {
+        if (t_max_cached < 0)
+            t_max_cached = opt_t_max(in, f, mv, start);
+        return t_max_cached;
+    };
+    auto bisect_t = [&](const std::function<double(const Vec&)>& metric, double target, double t_hi) {
+        double lo = 0.0, hi = t_hi;
+        Vec x = start;
+        for (int it = 0; it < 50; ++it) {
+            const double mid = 0.5 * (lo + hi);
+            x = mv.solve(mid, x);
+            (metric(x) < target ? lo : hi) = mid;
+        }
+        return mv.solve(hi, x);
+    };
+    auto project_if_needed = [&](Vec x, const char* what) {
+        if (constraints.is_trivial())
+            return x;
+        r.warnings << QString("%1 weights were projected onto the bounds/sector caps; risk contributions are no "
+                              "longer exactly balanced")
+                          .arg(what);
+        return f.project(x);
+    };
+
+    switch (method) {
+        case OptimizerMethod::MinVolatility:
+            w = mv.solve(0.0, start);
+            break;
+
+        case OptimizerMethod::MaxReturn: {
+            Vec neg_mu(in.mu.begin(), in.mu.end());
+            for (auto& v : neg_mu)
+                v = -v;
+            w = f.linear_min(neg_mu);
+            break;
+        }
+
+        case OptimizerMethod::TargetReturn: {
+            const Vec w_min = mv.solve(0.0, start);
+            const double t_max = t_upper();
+            const Vec w_top = mv.solve(t_max, w_min);
+            if (params.target_return <= opt_mu_dot(in, w_min)) {
+                w = w_min;
+            } else if (params.target_return >= opt_mu_dot(in, w_top)) {
+                r.warnings << QString("target return %1 is above the attainable maximum %2")
+                                  .arg(params.target_return)
+                                  .arg(opt_mu_dot(in, w_top));
+                w = w_top;
+            } else {
+                w = bisect_t([&](const Vec& x) { return opt_mu_dot(in, x); }, params.target_return, t_max);
+            }
+            break;
+        }
+
+        case OptimizerMethod::TargetVolatility: {
+            const Vec w_min = mv.solve(0.0, start);
+            const double t_max = t_upper();
+            const Vec w_top = mv.solve(t_max, w_min);
+            if (params.target_volatility <= opt_vol(in, w_min)) {
+                r.warnings << QString("target volatility %1 is below the minimum-variance portfolio's %2")
+                                  .arg(params.target_volatility)
+                                  .arg(opt_vol(in, w_min));
+                w = w_min;
+            } else if (params.target_volatility >= opt_vol(in, w_top)) {
+                w = w_top;
+            } else {
+                w = bisect_t([&](const Vec& x) { return opt_vol(in, x); }, params.target_volatility, t_max);
+            }
+            break;
+        }
+
+        case OptimizerMethod::RiskParity:
+            w = project_if_needed(opt_risk_parity(in, &mv.iterations), "Risk-parity");
+            break;
+
+        case OptimizerMethod::Hrp:
+            w = project_if_needed(opt_hrp(in), "HRP");
+            break;
+
+        case OptimizerMethod::EqualWeight:
+            w = project_if_needed(Vec(n, 1.0 / n), "Equal");
+            break;
+
+        case OptimizerMethod::InverseVariance: {
+            Vec iv(n);
+            double s = 0.0;
+            for (int i = 0; i < n; ++i)
+                s += iv[i] = 1.0 / std::max(in.cov_at(i, i), 1e-24);
+            for (auto& v : iv)
+                v /= s;
+            w = project_if_needed(iv, "Inverse-variance");
+            break;
+        }
+
+        case OptimizerMethod::MaxSharpe:
+        default: {
+            // Scan the utility path, then golden-section around the best point.
+            const double t_max = t_upper();
+            constexpr int kScan = 16;
+            auto sharpe_of = [&](const Vec& x) {
+                const double v = opt_vol(in, x);
+                return v > 1e-12 ? (opt_mu_dot(in, x) - params.risk_free) / v : -1e300;
+            };
+            Vec x = start;
+            QVector<double> ts;
+            int best = 0;
+            double best_s = -std::numeric_limits<double>::infinity();
+            for (int k = 0; k < kScan; ++k) {
+                const double frac = double(k) / (kScan - 1);
+                ts.append(t_max * frac * frac);
+                x = mv.solve(ts.last(), x);
+                const double s = sharpe_of(x);
+                if (s > best_s) {
+                    best_s = s;
+                    best = k;
+                    w = x;
+                }
+            }
+            double a = ts[std::max(0, best - 1)];
+            double b = ts[std::min(kScan - 1, best + 1)];
+            constexpr double kPhi = 0.6180339887498949;
+            double c = b - kPhi * (b - a);
+            double d = a + kPhi * (b - a);
+            Vec xc = mv.solve(c, w);
+            Vec xd = mv.solve(d, xc);
+            double sc = sharpe_of(xc), sd = sharpe_of(xd);
+            const double tol = 1e-3 * std::max(b - a, 1e-12);
+            for (int it = 0; it < 30 && b - a > tol; ++it) {
+                if (sc >= sd) {
+                    b = d;
+                    d = c;
+                    xd = xc;
+                    sd = sc;
+                    c = b - kPhi * (b - a);
+                    xc = mv.solve(c, xc);
+                    sc = sharpe_of(xc);
+                } else {
+                    a = c;
+                    c = d;
+                    xc = xd;
+                    sc = sd;
+                    d = a + kPhi * (b - a);
+                    xd = mv.solve(d, xd);
+                    sd = sharpe_of(xd);
+                }
+            }
+            const Vec refined = mv.solve(0.5 * (a + b), xc);
+            if (sharpe_of(refined) > best_s) {
+                w = refined;
+                best_s = sharpe_of(refined);
+            }
+            if (best_s <= 0)
+                r.warnings << "no feasible portfolio beats the risk-free rate; returned the highest-Sharpe one";
+            break;
+        }
+    }
+
+    opt_fill_metrics(in, w, params.risk_free, r);
+    if (params.frontier_points > 1)
+        r.frontier = opt_frontier(in, mv, start, t_upper(), params.frontier_points, params.risk_free);
+    r.iterations = mv.iterations;
+    r.ok = true;
+    r.elapsed_ms = double(timer.nsecsElapsed()) / 1e6;
+    return r;
+}
+
+// ── JSON ─────────────────────────────────────────────────────────────────────
+
+QJsonObject PortfolioOptimizer::result_to_json(const OptimizerResult& r) {
+    if (!r.ok)
+        return QJsonObject{{"error", r.error}};
+    QJsonArray frontier;
+    for (const auto& p : r.frontier)
+        frontier.append(QJsonObject{{"volatility", p.volatility}, {"return", p.ret}, {"sharpe", p.sharpe}});
+    return QJsonObject{{"weights", opt_by_symbol(r.symbols, r.weights)},
+                       {"expected_annual_return", r.expected_return},
+                       {"annual_volatility", r.volatility},
+                       {"sharpe_ratio", r.sharpe},
+                       {"strategy", method_to_string(r.method)},
+                       {"symbols", QJsonArray::fromStringList(r.symbols)},
+                       {"frontier", frontier},
+                       {"risk_contributions", opt_by_symbol(r.symbols, r.risk_contribution_pct)},
+                       {"marginal_risk", opt_by_symbol(r.symbols, r.marginal_risk)},
+                       {"asset_volatility", opt_by_symbol(r.symbols, r.asset_volatility)},
+                       {"warnings", QJsonArray::fromStringList(r.warnings)},
+                       {"iterations", r.iterations},
+                       {"elapsed_ms", r.elapsed_ms},
+                       {"engine", "native"}};
+}
+
+} // namespace fincept::services
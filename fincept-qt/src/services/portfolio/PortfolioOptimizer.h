@@ -0,0 +1,122 @@
+// src/services/portfolio/PortfolioOptimizer.h
+#pragma once
+#include <QHash>
+#include <QJsonObject>
+#include <QPair>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+namespace fincept::services {
+
+enum class OptimizerMethod {
+    MaxSharpe,
+    MinVolatility,
+    MaxReturn,
+    TargetReturn,
+    TargetVolatility,
+    RiskParity,
+    Hrp,
+    EqualWeight,
+    InverseVariance,
+};
+
+/// Annualised expected returns and covariance for `symbols` (same order).
+/// `cov` is row-major n×n.
+struct OptimizerInputs {
+    QStringList symbols;
+    QVector<double> mu;
+    QVector<double> cov;
+
+    int size() const { return int(symbols.size()); }
+    double cov_at(int i, int j) const { return cov[i * size() + j]; }
+};
+
+/// Feasible set: Σw = 1, per-asset [min, max], and optional per-sector caps
+/// on the summed weight. Assets without a sector (or in an uncapped sector)
+/// are only bounded individually.
+struct OptimizerConstraints {
+    bool long_only = true;
+    double default_min = 0.0; // −1 when !long_only and not overridden
+    double default_max = 1.0;
+    QHash<QString, QPair<double, double>> bounds; // symbol → (min, max)
+    QHash<QString, QString> sector_of;            // symbol → sector
+    QHash<QString, double> sector_caps;           // sector → max total weight
+
+    /// True when only the long-only box [0, 1] applies.
+    bool is_trivial() const;
+};
+
+struct OptimizerParams {
+    double risk_free = 0.04;
+    double target_return = 0.10;
+    double target_volatility = 0.15;
+    /// Efficient-frontier points to include (0 = skip).
+    int frontier_points = 40;
+};
+
+struct OptimizerFrontierPoint {
+    double volatility = 0.0;
+    double ret = 0.0;
+    double sharpe = 0.0;
+};
+
+struct OptimizerResult {
+    bool ok = false;
+    QString error;
+    OptimizerMethod method = OptimizerMethod::MaxSharpe;
+
+    QStringList symbols;
+    QVector<double> weights; // aligned with symbols
+    double expected_return = 0.0;
+    double volatility = 0.0;
+    double sharpe = 0.0;
+
+    QVector<double> risk_contribution_pct; // share of portfolio vol, sums to 100
+    QVector<double> marginal_risk;         // (Σw)_i / σ_p
+    QVector<double> asset_volatility;
+
+    QVector<OptimizerFrontierPoint> frontier;
+    QStringList warnings;
+    int iterations = 0;
+    double elapsed_ms = 0.0;
+};
+
+/// Native long-only / bounded portfolio optimiser: constrained mean-variance
+/// (max Sharpe, min vol, max return, target return/vol, efficient frontier),
+/// equal-risk-contribution risk parity and hierarchical risk parity.
+///
+/// Mean-variance problems are solved by accelerated projected gradient on
+/// ½wᵀΣw − t·μᵀw with an exact projection onto the constraint set (budget,
+/// boxes and sector caps form a laminar family, so the projection reduces to
+/// two nested threshold searches). Sweeping the risk tolerance t traces the
+/// frontier; max Sharpe and the targets are searches along t.
+class PortfolioOptimizer {
+  public:
+    static OptimizerResult optimize(const OptimizerInputs& in, OptimizerMethod method,
+                                    const OptimizerConstraints& constraints = {}, const OptimizerParams& params = {});
+
+    static QVector<OptimizerFrontierPoint> efficient_frontier(const OptimizerInputs& in,
+                                                              const OptimizerConstraints& constraints, int points,
+                                                              double risk_free);
+
+    /// Sample mean × periods and sample covariance × periods from a T×n
+    /// matrix of periodic simple returns (rows = dates).
+    static OptimizerInputs inputs_from_returns(const QStringList& symbols, const QVector<QVector<double>>& returns,
+                                               int periods_per_year = 252);
+
+    /// Accepts the names used by the optimisation screen and the MCP tools
+    /// ("max_sharpe", "Min Volatility", "hrp", "b-l model" …). Unknown → MaxSharpe.
+    static OptimizerMethod method_from_string(const QString& s);
+    static QString method_to_string(OptimizerMethod m);
+
+    /// Same shape optimize_portfolio_weights.py used to print, so existing
+    /// consumers keep parsing it: weights{}, expected_annual_return,
+    /// annual_volatility, sharpe_ratio, strategy, symbols, frontier[],
+    /// risk_contributions{}, marginal_risk{}, asset_volatility{}.
+    static QJsonObject result_to_json(const OptimizerResult& r);
+
+    static constexpr int kMaxIterations = 5000;
+};
+
+} // namespace fincept::services
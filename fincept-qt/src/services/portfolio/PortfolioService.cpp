@@ -2,13 +2,18 @@
 //
 // Core: singleton, portfolio CRUD (load/create/delete), asset CRUD
 // (add/sell), transactions, dividends, invalidate_cache. Split concerns:
-//   - PortfolioService_Summary.cpp      — load/build/finalize summary
+//   - PortfolioService_Summary.cpp      — load/build/finalize summary, base-currency conversion
+//   - PortfolioService_FxExposure.cpp   — currency exposure + FX hedge suggestions
+//   - PortfolioService_Tax.cpp          — tax lots, realized gains, Form 8949 export
+//   - PortfolioService_Benchmark.cpp    — benchmark assignment, sync, active-risk analytics
 //   - PortfolioService_Metrics.cpp      — analytics + history + snapshots
+//   - PortfolioService_Optimize.cpp     — aligned daily returns for the native optimiser
 //   - PortfolioService_ImportExport.cpp — CSV/JSON round-trip
 #include "services/portfolio/PortfolioService.h"
 
 #include "core/logging/Logger.h"
 #include "python/PythonRunner.h"
+#include "services/portfolio/FxRateService.h"
 #include "services/sectors/SectorResolver.h"
 #include "storage/repositories/PortfolioRepository.h"
 #include "storage/repositories/SettingsRepository.h"
@@ -109,22 +114,48 @@ void PortfolioService::delete_portfolio(const QString& id) {
 
 void PortfolioService::add_asset(const QString& portfolio_id, const QString& symbol, double qty, double price,
                                  const QString& date, const QString& broker_symbol, const QString& exchange) {
-    auto& repo = PortfolioRepository::instance();
+    // Foreign listings record the purchase FX rate so P&L can be split into
+    // local and currency return later. Same-currency buys skip the lookup.
+    auto p = PortfolioRepository::instance().get_portfolio(portfolio_id);
+    const QString base = p.is_ok() && !p.value().currency.isEmpty() ? p.value().currency.toUpper() : "USD";
+    const QString ccy = listing_currency(symbol, base);
+
+    QPointer<PortfolioService> self = this;
+    auto store = [self, portfolio_id, symbol, qty, price, date, broker_symbol, exchange](double fx_rate) {
+        if (!self)
+            return;
+        auto& repo = PortfolioRepository::instance();
+        // Sector left empty here — SectorResolver fills it asynchronously after
+        // the asset lands in the DB. Broker fields pass through verbatim.
+        auto r = repo.add_asset(portfolio_id, symbol, qty, price, date, /*sector=*/QString(), broker_symbol, exchange,
+                                /*currency=*/QString(), fx_rate);
+        if (r.is_err()) {
+            LOG_ERROR("PortfolioSvc", "Failed to add asset: " + QString::fromStdString(r.error()));
+            return;
+        }
 
-    // Sector left empty here — SectorResolver fills it asynchronously after
-    // the asset lands in the DB. Broker fields pass through verbatim.
-    auto r = repo.add_asset(portfolio_id, symbol, qty, price, date, /*sector=*/QString(), broker_symbol, exchange);
-    if (r.is_err()) {
-        LOG_ERROR("PortfolioSvc", "Failed to add asset: " + QString::fromStdString(r.error()));
-        return;
-    }
+        // Record transaction
+        QString txn_date = date.isEmpty() ? QDateTime::currentDateTimeUtc().toString(Qt::ISODate) : date;
+        repo.add_transaction(portfolio_id, symbol, "BUY", qty, price, txn_date);
 
-    // Record transaction
-    QString txn_date = date.isEmpty() ? QDateTime::currentDateTimeUtc().toString(Qt::ISODate) : date;
-    repo.add_transaction(portfolio_id, symbol, "BUY", qty, price, txn_date);
+        self->invalidate_cache(portfolio_id);
+        emit self->asset_added(portfolio_id);
+    };
 
-    invalidate_cache(portfolio_id);
-    emit asset_added(portfolio_id);
+    if (ccy == base) {
+        store(1.0);
+        return;
+    }
+    const QDate trade_date = QDate::fromString(date.left(10), Qt::ISODate);
+    if (!trade_date.isValid() || trade_date >= QDate::currentDate()) {
+        if (auto cached = FxRateService::instance().cached_spot(ccy, base)) {
+            store(*cached);
+            return;
+        }
+    }
+    // 0 = unknown; backfill_cost_fx retries from the purchase date.
+    FxRateService::instance().rate_on(ccy, base, trade_date,
+                                      [store](std::optional<double> rate) { store(rate.value_or(0.0)); });
 }
 
 void PortfolioService::sell_asset(const QString& portfolio_id, const QString& symbol, double qty, double price,
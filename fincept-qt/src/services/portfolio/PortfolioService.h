@@ -3,6 +3,7 @@
 #include "python/PythonRunner.h"
 #include "screens/portfolio/PortfolioTypes.h"
 #include "services/markets/MarketDataService.h"
+#include "services/portfolio/ScenarioEngine.h"
 
 #include <QDateTime>
 #include <QHash>
@@ -12,6 +13,9 @@
 #include <QSet>
 #include <QTimer>
 
+#include <functional>
+#include <optional>
+
 namespace fincept::services {
 
 /// Singleton service managing portfolio data, live quotes, and computed metrics.
@@ -31,9 +35,17 @@ class PortfolioService : public QObject {
     void delete_portfolio(const QString& id);
 
     // ── Summary (assets + live quotes) ───────────────────────────────────────
+    /// Values, cost and P&L come out in the portfolio currency: each holding
+    /// is converted from its native currency at spot (FxRateService), and its
+    /// unrealized P&L split into local-return and currency-return parts.
     void load_summary(const QString& portfolio_id);
     void refresh_summary(const QString& portfolio_id); // invalidates cache first
 
+    /// Change the currency a portfolio is reported in. Purchase FX rates are
+    /// reset and re-derived against the new base from each asset's first
+    /// purchase date, then the summary reloads.
+    void set_base_currency(const QString& portfolio_id, const QString& currency);
+
     // ── Asset operations ─────────────────────────────────────────────────────
     /// `broker_symbol`+`exchange` are stored alongside the canonical
     /// yfinance-format `symbol` so broker quote calls can use the native
@@ -70,6 +82,55 @@ class PortfolioService : public QObject {
     /// Legacy shim — calls fetch_benchmark_history("SPY", period).
     void fetch_spy_history(const QString& period = "1y");
 
+    // ── Benchmark-relative performance ───────────────────────────────────────
+    /// The portfolio's assigned benchmark_symbol, else the currency default.
+    static QString benchmark_for(const portfolio::Portfolio& portfolio);
+    /// Assign a benchmark (empty = back to the currency default), sync its
+    /// closes and emit portfolios_loaded so screens re-read it.
+    void set_benchmark(const QString& portfolio_id, const QString& symbol);
+    /// Bring the stored daily closes for `symbol` up to date — full 5y on
+    /// first use, only the gap afterwards. At most once per
+    /// kBenchmarkSyncIntervalSec per symbol unless `force`.
+    void sync_benchmark_prices(const QString& symbol, std::function<void(bool ok)> done = {}, bool force = false);
+    /// Align the portfolio's flow-adjusted daily returns (from snapshots)
+    /// with its benchmark over the last `days` and emit
+    /// benchmark_analytics_computed: active return, tracking error,
+    /// information ratio, beta, up/down capture, plus rolling series over
+    /// `window` trading days. With `fx_adjust` a foreign-currency benchmark
+    /// is first converted into the portfolio currency (benchmark_closes_in).
+    void compute_benchmark_analytics(const QString& portfolio_id, int days = 365, int window = 63,
+                                     bool fx_adjust = true);
+
+    /// Currency a benchmark is quoted in ("^FTSE" → GBP, "SPY" → USD).
+    static QString benchmark_currency(const QString& symbol);
+
+    using BenchmarkSeriesCallback =
+        std::function<void(QVector<QPair<QString, double>> closes, QStringList warnings)>;
+    /// Daily closes of `symbol` since `from_date` expressed in `base`: each
+    /// close times that day's historical FX close (last rate carried over FX
+    /// holidays). Syncs the benchmark and the FX pair first. `done` gets the
+    /// local series plus a warning when no FX history exists.
+    void benchmark_closes_in(const QString& symbol, const QString& base, const QString& from_date,
+                             BenchmarkSeriesCallback done);
+
+    // ── Optimiser inputs ─────────────────────────────────────────────────────
+    using ReturnsCallback =
+        std::function<void(QStringList symbols, QVector<QVector<double>> returns, QStringList warnings)>;
+    /// Daily simple returns over the last `days` for every symbol that has
+    /// history, aligned on the dates all of them traded (rows = dates,
+    /// columns = `symbols` order). Closes are synced into the stored price
+    /// table first; when offline the stored closes are used as they are.
+    /// Symbols without enough history are dropped with a warning.
+    void aligned_returns(const QStringList& symbols, int days, ReturnsCallback done);
+
+    // ── Scenario P&L ─────────────────────────────────────────────────────────
+    using ScenarioCallback = std::function<void(ScenarioResult result)>;
+    /// Applies `spec`'s shocks to the summary's holdings (ScenarioEngine).
+    /// Betas are regressed on `spec.lookback_days` of aligned daily closes of
+    /// the holdings and the shocked indices / rate proxies; option
+    /// underlyings are priced from the same stored closes.
+    void run_scenario(const portfolio::PortfolioSummary& summary, const ScenarioSpec& spec, ScenarioCallback done);
+
     // ── Risk-free rate ────────────────────────────────────────────────────────
     /// Fetch the current 10-year Treasury yield (DGS10) from FRED.
     /// Result is cached 24h in SettingsRepository. Emits risk_free_rate_loaded(rate).
@@ -78,6 +139,38 @@ class PortfolioService : public QObject {
     // ── Metrics (async computation) ──────────────────────────────────────────
     void compute_metrics(const portfolio::PortfolioSummary& summary);
 
+    // ── Currency exposure (FX hedging) ───────────────────────────────────────
+    /// Groups holdings by listing currency (inferred from the yfinance suffix),
+    /// converts to the portfolio currency at spot, and for every foreign
+    /// currency estimates the forward / hedge carry over `horizon_months` from
+    /// the short-rate differential plus a suggested hedge ratio. Emits
+    /// fx_exposure_computed(report).
+    void compute_fx_exposure(const portfolio::PortfolioSummary& summary, int horizon_months = 3);
+
+    /// Listing currency for a yfinance symbol ("VOD.L" → "GBP", "7203.T" →
+    /// "JPY", "BTC-EUR" → "EUR"); `fallback` when the suffix is unknown.
+    /// `minor_unit` receives the quote divisor (100 for GBp/ZAc listings).
+    static QString listing_currency(const QString& symbol, const QString& fallback, double* minor_unit = nullptr);
+
+    /// Annual short rate used for forward pricing. Reads the optional
+    /// "portfolio.fx_short_rates" setting (JSON {"USD":0.043,...}) and falls
+    /// back to a built-in reference table.
+    static std::optional<double> short_rate(const QString& currency);
+
+    // ── Tax lots & capital gains ─────────────────────────────────────────────
+    /// FIFO-matches the portfolio's BUY/SELL/SPLIT ledger under the given
+    /// jurisdiction's rules ("US" or "IN", see TaxLotEngine) and emits
+    /// tax_report_computed with realized short/long-term gains per tax year,
+    /// wash-sale flags and the remaining open lots. `tax_year` ("2025",
+    /// "FY2025-26") limits the realized rows; empty = all years. For "IN" the
+    /// 31-Jan-2018 grandfathering prices are fetched first when needed.
+    void compute_tax_report(const QString& portfolio_id, const QString& jurisdiction,
+                            const QString& tax_year = {});
+
+    /// Writes the US report for `tax_year` as Form 8949-style CSV; emits
+    /// export_complete(file_path) or tax_report_error.
+    void export_form_8949(const QString& portfolio_id, const QString& tax_year, const QString& file_path);
+
     // ── Import / Export ──────────────────────────────────────────────────────
     void export_csv(const QString& portfolio_id, const QString& file_path);
     void export_json(const QString& portfolio_id, const QString& file_path);
@@ -109,6 +202,11 @@ class PortfolioService : public QObject {
     void transactions_loaded(QVector<portfolio::Transaction> transactions);
 
     void metrics_computed(portfolio::ComputedMetrics metrics);
+    void fx_exposure_computed(portfolio::FxExposureReport report);
+    void tax_report_computed(portfolio::TaxReport report);
+    void tax_report_error(QString portfolio_id, QString error);
+    void benchmark_analytics_computed(portfolio::BenchmarkAnalytics analytics);
+    void benchmark_analytics_error(QString portfolio_id, QString error);
     void snapshots_loaded(QString portfolio_id, QVector<portfolio::PortfolioSnapshot> snapshots);
 
     void asset_added(QString portfolio_id);
@@ -149,6 +247,18 @@ class PortfolioService : public QObject {
     void finalize_summary(const QString& portfolio_id, const QVector<portfolio::PortfolioAsset>& assets,
                           const portfolio::Portfolio& portfolio, const QHash<QString, QuoteData>& quote_map);
 
+    /// Second half of finalize_summary once FX rates for every foreign
+    /// holding are known (native currency → portfolio-currency units).
+    void assemble_summary(const QString& portfolio_id, const QVector<portfolio::PortfolioAsset>& assets,
+                          const portfolio::Portfolio& portfolio, const QHash<QString, QuoteData>& quote_map,
+                          const QHash<QString, double>& fx_rates, const QStringList& fx_warnings);
+
+    /// Fill in unknown purchase FX rates (pre-v053 rows, base changes) from
+    /// each asset's first purchase date. Fire-and-forget; the next summary
+    /// load picks them up.
+    void backfill_cost_fx(const QString& portfolio_id, const QVector<portfolio::PortfolioAsset>& assets,
+                          const QString& base);
+
     /// Try to fetch live quotes via the broker linked to `portfolio.broker_account_id`.
     /// On success, calls finalize_summary with broker-sourced QuoteData.
     /// On any failure (no account, disconnected, broker null, API error),
@@ -186,6 +296,13 @@ class PortfolioService : public QObject {
     // Per-portfolio guard so compute_metrics doesn't kick off backfill on
     // every refresh tick. Cleared on app restart — that's the explicit retry.
     QSet<QString> backfill_attempted_;
+    // "portfolio_id|symbol" keys whose purchase FX lookup already ran this
+    // session — a pair the feed cannot price is not retried every refresh.
+    QSet<QString> cost_fx_attempted_;
+
+    // ── Benchmark sync throttle ──────────────────────────────────────────────
+    static constexpr int kBenchmarkSyncIntervalSec = 6 * 60 * 60;
+    QHash<QString, qint64> benchmark_synced_at_; // symbol → epoch secs
 };
 
 } // namespace fincept::services
@@ -0,0 +1,387 @@
+// src/services/portfolio/PortfolioService_Benchmark.cpp
+//
+// Benchmark-relative performance: per-portfolio benchmark assignment,
+// incremental daily benchmark close sync (benchmark_prices, v054), and
+// active return / tracking error / information ratio / capture / rolling
+// beta over the snapshot history. Foreign-currency benchmarks are converted
+// into the portfolio currency with historical daily FX closes first, so the
+// comparison is not distorted by currency moves.
+//
+// Part of the partial-class split of PortfolioService.cpp.
+
+#include "core/logging/Logger.h"
+#include "services/portfolio/PortfolioService.h"
+#include "storage/repositories/PortfolioRepository.h"
+
+#include <QDateTime>
+#include <QTimeZone>
+
+#include <algorithm>
+#include <cmath>
+#include <memory>
+
+namespace fincept::services {
+
+namespace {
+
+constexpr double kBenchTradingDays = 252.0;
+constexpr int kBenchMinObservations = 20;
+
+struct BenchMoments {
+    double mean_p = 0, mean_b = 0, mean_a = 0;
+    double sd_a = 0; // sample stdev of active returns
+    double cov_pb = 0, var_b = 0;
+};
+
+// Sample moments of returns[from, to).
+BenchMoments bench_moments(const QVector<portfolio::BenchmarkPoint>& pts, int from, int to) {
+    BenchMoments m;
+    const int n = to - from;
+    if (n < 2)
+        return m;
+    for (int i = from; i < to; ++i) {
+        m.mean_p += pts[i].portfolio_return;
+        m.mean_b += pts[i].benchmark_return;
+        m.mean_a += pts[i].active_return;
+    }
+    m.mean_p /= n;
+    m.mean_b /= n;
+    m.mean_a /= n;
+    double ss_a = 0;
+    for (int i = from; i < to; ++i) {
+        const double da = pts[i].active_return - m.mean_a;
+        const double db = pts[i].benchmark_return - m.mean_b;
+        ss_a += da * da;
+        m.cov_pb += (pts[i].portfolio_return - m.mean_p) * db;
+        m.var_b += db * db;
+    }
+    m.sd_a = std::sqrt(ss_a / (n - 1));
+    m.cov_pb /= (n - 1);
+    m.var_b /= (n - 1);
+    return m;
+}
+
+std::optional<double> bench_annualised_growth(double cumulative, int n) {
+    if (n <= 0 || cumulative <= -1.0)
+        return std::nullopt;
+    return (std::pow(1.0 + cumulative, kBenchTradingDays / n) - 1.0) * 100.0;
+}
+
+// Picks the yfinance period that covers a gap of `days` calendar days.
+QString bench_period_for_gap(qint64 days) {
+    if (days <= 25)
+        return QStringLiteral("1mo");
+    if (days <= 85)
+        return QStringLiteral("3mo");
+    if (days <= 170)
+        return QStringLiteral("6mo");
+    if (days <= 350)
+        return QStringLiteral("1y");
+    if (days <= 700)
+        return QStringLiteral("2y");
+    return QStringLiteral("5y");
+}
+
+// Index tickers carry no exchange suffix, so listing_currency would call
+// them all USD.
+const QHash<QString, QString>& bench_index_currencies() {
+    static const QHash<QString, QString> map = {
+        // Americas
+        {"^GSPC", "USD"}, {"^DJI", "USD"}, {"^IXIC", "USD"}, {"^RUT", "USD"},
+        {"^GSPTSE", "CAD"}, {"^BVSP", "BRL"}, {"^MXX", "MXN"},
+        // Europe
+        {"^FTSE", "GBP"}, {"^FTMC", "GBP"}, {"^STOXX50E", "EUR"}, {"^STOXX", "EUR"},
+        {"^GDAXI", "EUR"}, {"^FCHI", "EUR"}, {"^AEX", "EUR"}, {"^IBEX", "EUR"},
+        {"FTSEMIB.MI", "EUR"}, {"^SSMI", "CHF"}, {"^OMX", "SEK"}, {"^J203.JO", "ZAR"},
+        // Asia-Pacific
+        {"^AXJO", "AUD"}, {"^NZ50", "NZD"}, {"^N225", "JPY"}, {"^HSI", "HKD"},
+        {"000001.SS", "CNY"}, {"^KS11", "KRW"}, {"^TWII", "TWD"}, {"^STI", "SGD"},
+        {"^NSEI", "INR"}, {"^BSESN", "INR"}, {"^NSEBANK", "INR"},
+    };
+    return map;
+}
+
+} // namespace
+
+QString PortfolioService::benchmark_currency(const QString& symbol) {
+    const QString sym = symbol.trimmed().toUpper();
+    auto it = bench_index_currencies().constFind(sym);
+    if (it != bench_index_currencies().constEnd())
+        return it.value();
+    return listing_currency(sym, QStringLiteral("USD"));
+}
+
+void PortfolioService::benchmark_closes_in(const QString& symbol, const QString& base, const QString& from_date,
+                                           BenchmarkSeriesCallback done) {
+    const QString sym = symbol.trimmed().toUpper();
+    const QString ccy = benchmark_currency(sym);
+    const QString b = base.isEmpty() ? ccy : base.toUpper();
+
+    QPointer<PortfolioService> self = this;
+    sync_benchmark_prices(sym, [self, sym, ccy, b, from_date, done](bool synced) {
+        if (!self)
+            return;
+        QStringList warnings;
+        if (!synced)
+            warnings << QString("%1 sync failed — using stored closes only").arg(sym);
+        auto closes_r = PortfolioRepository::instance().get_benchmark_closes(sym, from_date);
+        if (closes_r.is_err()) {
+            done({}, warnings << QString::fromStdString(closes_r.error()));
+            return;
+        }
+        const auto closes = closes_r.value();
+        if (ccy == b) {
+            done(closes, warnings);
+            return;
+        }
+
+        // FX pair closes live in the same table — they are just another
+        // daily series ("EURUSD=X" = USD per EUR).
+        const QString pair = ccy + b + "=X";
+        // Start a little early so the first benchmark day has a rate to carry.
+        const QString fx_from = QDate::fromString(from_date, Qt::ISODate).addDays(-10).toString(Qt::ISODate);
+        self->sync_benchmark_prices(pair, [sym, ccy, b, pair, fx_from, closes, warnings, done](bool fx_synced) mutable {
+            if (!fx_synced)
+                warnings << QString("%1 sync failed — using stored rates only").arg(pair);
+            auto fx_r = PortfolioRepository::instance().get_benchmark_closes(pair, fx_from);
+            if (fx_r.is_err() || fx_r.value().isEmpty()) {
+                warnings << QString("No %1/%2 history — %3 left in %1, comparison includes currency moves")
+                                .arg(ccy, b, sym);
+                done(closes, warnings);
+                return;
+            }
+            // Both series ascending: walk them together, carrying the last
+            // FX close over FX-market holidays.
+            const auto fx = fx_r.value();
+            QVector<QPair<QString, double>> converted;
+            converted.reserve(closes.size());
+            int j = -1, dropped = 0;
+            for (const auto& [d, c] : closes) {
+                while (j + 1 < fx.size() && fx[j + 1].first <= d)
+                    ++j;
+                if (j < 0 || fx[j].second <= 0) {
+                    ++dropped;
+                    continue;
+                }
+                converted.append({d, c * fx[j].second});
+            }
+            if (dropped > 0)
+                warnings << QString("%1 early %2 day(s) dropped — no %3/%4 rate yet").arg(dropped).arg(sym, ccy, b);
+            done(converted, warnings);
+        });
+    });
+}
+
+QString PortfolioService::benchmark_for(const portfolio::Portfolio& portfolio) {
+    return portfolio.benchmark_symbol.isEmpty() ? default_benchmark_for_currency(portfolio.currency)
+                                                : portfolio.benchmark_symbol;
+}
+
+void PortfolioService::set_benchmark(const QString& portfolio_id, const QString& symbol) {
+    auto r = PortfolioRepository::instance().set_portfolio_benchmark(portfolio_id, symbol);
+    if (r.is_err()) {
+        emit benchmark_analytics_error(portfolio_id, QString::fromStdString(r.error()));
+        return;
+    }
+    LOG_INFO("PortfolioSvc", QString("Portfolio %1 benchmark → %2")
+                                 .arg(portfolio_id, symbol.isEmpty() ? QStringLiteral("(currency default)") : symbol));
+    auto p = PortfolioRepository::instance().get_portfolio(portfolio_id);
+    if (p.is_ok())
+        sync_benchmark_prices(benchmark_for(p.value()));
+    load_portfolios();
+}
+
+void PortfolioService::sync_benchmark_prices(const QString& symbol, std::function<void(bool ok)> done, bool force) {
+    const QString sym = symbol.trimmed().toUpper();
+    const qint64 now = QDateTime::currentSecsSinceEpoch();
+    if (!force && now - benchmark_synced_at_.value(sym, 0) < kBenchmarkSyncIntervalSec) {
+        if (done)
+            done(true);
+        return;
+    }
+
+    const QString last = PortfolioRepository::instance().last_benchmark_date(sym);
+    const QDate last_date = QDate::fromString(last, Qt::ISODate);
+    const qint64 gap = last_date.isValid() ? last_date.daysTo(QDate::currentDate()) : 100000;
+    if (gap <= 0) {
+        benchmark_synced_at_.insert(sym, now);
+        if (done)
+            done(true);
+        return;
+    }
+
+    QPointer<PortfolioService> self = this;
+    MarketDataService::instance().fetch_history(
+        sym, bench_period_for_gap(gap), "1d", [self, sym, last, done](bool ok, QVector<HistoryPoint> pts) {
+            if (!self)
+                return;
+            if (!ok || pts.isEmpty()) {
+                LOG_WARN("PortfolioSvc", "Benchmark sync failed for " + sym);
+                if (done)
+                    done(false);
+                return;
+            }
+            QVector<QPair<QString, double>> closes;
+            closes.reserve(pts.size());
+            for (const auto& pt : pts) {
+                if (pt.close <= 0)
+                    continue;
+                const QDate day = QDateTime::fromSecsSinceEpoch(pt.timestamp, QTimeZone::UTC).date();
+                const QString d = day.toString(Qt::ISODate);
+                // Re-write the last stored day too — it may have been an
+                // intraday close when it was first synced.
+                if (d >= last)
+                    closes.append({d, pt.close});
+            }
+            auto r = PortfolioRepository::instance().upsert_benchmark_closes(sym, closes);
+            if (r.is_ok())
+                self->benchmark_synced_at_.insert(sym, QDateTime::currentSecsSinceEpoch());
+            LOG_INFO("PortfolioSvc", QString("Benchmark %1 synced: %2 closes").arg(sym).arg(closes.size()));
+            if (done)
+                done(r.is_ok());
+        });
+}
+
+void PortfolioService::compute_benchmark_analytics(const QString& portfolio_id, int days, int window,
+                                                   bool fx_adjust) {
+    auto p = PortfolioRepository::instance().get_portfolio(portfolio_id);
+    if (p.is_err()) {
+        emit benchmark_analytics_error(portfolio_id, QString::fromStdString(p.error()));
+        return;
+    }
+    const QString bench = benchmark_for(p.value());
+    const QString base = p.value().currency.isEmpty() ? QStringLiteral("USD") : p.value().currency.toUpper();
+    const QString bench_ccy = benchmark_currency(bench);
+    days = std::clamp(days, 30, 3650);
+    window = std::clamp(window, 10, 252);
+    const QString from = QDate::currentDate().addDays(-days).toString(Qt::ISODate);
+
+    QPointer<PortfolioService> self = this;
+    auto on_closes = [self, portfolio_id, bench, base, bench_ccy, fx_adjust, days,
+                      window](QVector<QPair<QString, double>> closes, QStringList warnings) {
+        if (!self)
+            return;
+        portfolio::BenchmarkAnalytics out;
+        out.portfolio_id = portfolio_id;
+        out.benchmark = bench;
+        out.benchmark_currency = bench_ccy;
+        out.base_currency = base;
+        out.fx_adjusted = fx_adjust && bench_ccy != base;
+        out.window = window;
+        out.generated_at = QDateTime::currentDateTimeUtc().toString(Qt::ISODate);
+        out.warnings = warnings;
+        if (!fx_adjust && bench_ccy != base)
+            out.warnings << QString("%1 compared in %2 against a %3 portfolio — relative numbers include the %2/%3 "
+                                    "currency move")
+                                .arg(bench, bench_ccy, base);
+
+        auto snaps_r = PortfolioRepository::instance().get_snapshots(portfolio_id, days);
+        if (snaps_r.is_err()) {
+            emit self->benchmark_analytics_error(portfolio_id, QString::fromStdString(snaps_r.error()));
+            return;
+        }
+        QHash<QString, double> bench_close;
+        for (const auto& [d, c] : closes)
+            bench_close.insert(d, c);
+
+        // ── Align on days with both a snapshot and a benchmark close ─────────
+        // Portfolio return is flow-adjusted: the change in cost basis is a
+        // deposit/withdrawal, not performance.
+        const portfolio::PortfolioSnapshot* prev = nullptr;
+        double cum_p = 1.0, cum_b = 1.0;
+        int skipped = 0;
+        for (const auto& s : snaps_r.value()) {
+            const QString d = s.snapshot_date.left(10);
+            if (!bench_close.contains(d) || s.total_value <= 0)
+                continue;
+            if (prev) {
+                const double rp =
+                    ((s.total_value - prev->total_value) - (s.total_cost_basis - prev->total_cost_basis)) /
+                    prev->total_value;
+                const double rb = bench_close.value(d) / bench_close.value(prev->snapshot_date.left(10)) - 1.0;
+                if (!std::isfinite(rp) || !std::isfinite(rb) || std::abs(rp) > 0.5) {
+                    ++skipped; // a bad snapshot or an unrecorded flow, not a real return
+                } else {
+                    portfolio::BenchmarkPoint pt;
+                    pt.date = d;
+                    pt.portfolio_return = rp;
+                    pt.benchmark_return = rb;
+                    pt.active_return = rp - rb;
+                    cum_p *= 1.0 + rp;
+                    cum_b *= 1.0 + rb;
+                    pt.portfolio_cumulative = cum_p - 1.0;
+                    pt.benchmark_cumulative = cum_b - 1.0;
+                    pt.active_cumulative = pt.portfolio_cumulative - pt.benchmark_cumulative;
+                    out.series.append(pt);
+                }
+            }
+            prev = &s;
+        }
+        if (skipped > 0)
+            out.warnings << QString("%1 day(s) skipped with implausible returns (>50%)").arg(skipped);
+
+        const int n = out.series.size();
+        out.observations = n;
+        if (n < kBenchMinObservations) {
+            emit self->benchmark_analytics_error(
+                portfolio_id, QString("Only %1 aligned trading days against %2 — need %3. Backfill history first.")
+                                  .arg(n)
+                                  .arg(bench)
+                                  .arg(kBenchMinObservations));
+            return;
+        }
+
+        // ── Rolling window ──────────────────────────────────────────────────
+        const double ann = std::sqrt(kBenchTradingDays);
+        for (int i = window - 1; i < n; ++i) {
+            const auto m = bench_moments(out.series, i - window + 1, i + 1);
+            auto& pt = out.series[i];
+            pt.rolling_tracking_error = m.sd_a * ann * 100.0;
+            if (m.sd_a > 0)
+                pt.rolling_information_ratio = m.mean_a / m.sd_a * ann;
+            if (m.var_b > 0)
+                pt.rolling_beta = m.cov_pb / m.var_b;
+        }
+
+        // ── Whole period ────────────────────────────────────────────────────
+        const auto m = bench_moments(out.series, 0, n);
+        out.portfolio_return = bench_annualised_growth(cum_p - 1.0, n);
+        out.benchmark_return = bench_annualised_growth(cum_b - 1.0, n);
+        if (out.portfolio_return && out.benchmark_return)
+            out.active_return = *out.portfolio_return - *out.benchmark_return;
+        out.tracking_error = m.sd_a * ann * 100.0;
+        if (m.sd_a > 0)
+            out.information_ratio = m.mean_a / m.sd_a * ann;
+        if (m.var_b > 0)
+            out.beta = m.cov_pb / m.var_b;
+
+        // Capture ratios: mean portfolio return on benchmark up (down) days
+        // over the mean benchmark return on those days.
+        double up_p = 0, up_b = 0, dn_p = 0, dn_b = 0;
+        int up_n = 0, dn_n = 0;
+        for (const auto& pt : out.series) {
+            if (pt.benchmark_return > 0) {
+                up_p += pt.portfolio_return;
+                up_b += pt.benchmark_return;
+                ++up_n;
+            } else if (pt.benchmark_return < 0) {
+                dn_p += pt.portfolio_return;
+                dn_b += pt.benchmark_return;
+                ++dn_n;
+            }
+        }
+        if (up_n > 0 && up_b != 0)
+            out.up_capture = up_p / up_b * 100.0;
+        if (dn_n > 0 && dn_b != 0)
+            out.down_capture = dn_p / dn_b * 100.0;
+
+        LOG_INFO("PortfolioSvc", QString("Benchmark analytics %1 vs %2: %3 days, TE %4%")
+                                     .arg(portfolio_id, bench)
+                                     .arg(n)
+                                     .arg(out.tracking_error.value_or(0.0), 0, 'f', 2));
+        emit self->benchmark_analytics_computed(out);
+    };
+    benchmark_closes_in(bench, fx_adjust ? base : bench_ccy, from, on_closes);
+}
+
+} // namespace fincept::services
@@ -0,0 +1,282 @@
+// src/services/portfolio/PortfolioService_FxExposure.cpp
+//
+// Currency exposure: compute_fx_exposure, listing_currency, short_rate.
+// Turns a PortfolioSummary into per-currency exposure in the portfolio
+// currency, with forward/carry estimates and a suggested hedge ratio.
+//
+// Part of the partial-class split of PortfolioService.cpp.
+
+#include "core/logging/Logger.h"
+#include "services/portfolio/PortfolioService.h"
+#include "storage/repositories/SettingsRepository.h"
+
+#include <QDateTime>
+#include <QJsonDocument>
+#include <QJsonObject>
+
+#include <algorithm>
+#include <cmath>
+#include <memory>
+
+namespace fincept::services {
+
+namespace {
+
+// Exposures below this weight are reported but never hedged — the carry and
+// roll overhead outweighs the risk removed.
+constexpr double kMaterialWeightPct = 5.0;
+constexpr double kZ95 = 1.645;
+
+// Reference policy/short rates (annual decimal). Deliberately coarse: they
+// only drive the forward-points estimate. Users override with the
+// "portfolio.fx_short_rates" setting when they have live deposit rates.
+const QHash<QString, double>& reference_short_rates() {
+    static const QHash<QString, double> rates = {
+        {"USD", 0.0400}, {"EUR", 0.0200}, {"GBP", 0.0375}, {"JPY", 0.0050}, {"CHF", 0.0000},
+        {"CAD", 0.0250}, {"AUD", 0.0360}, {"NZD", 0.0300}, {"INR", 0.0550}, {"CNY", 0.0140},
+        {"HKD", 0.0400}, {"SGD", 0.0200}, {"KRW", 0.0250}, {"TWD", 0.0200}, {"SEK", 0.0175},
+        {"NOK", 0.0400}, {"DKK", 0.0175}, {"BRL", 0.1500}, {"MXN", 0.0750}, {"ZAR", 0.0700},
+    };
+    return rates;
+}
+
+// yfinance exchange suffix → listing currency.
+const QHash<QString, QString>& suffix_currencies() {
+    static const QHash<QString, QString> map = {
+        {"NS", "INR"}, {"BO", "INR"}, {"L", "GBP"},  {"IL", "GBP"}, {"T", "JPY"},  {"HK", "HKD"},
+        {"TO", "CAD"}, {"V", "CAD"},  {"NE", "CAD"}, {"AX", "AUD"}, {"NZ", "NZD"}, {"DE", "EUR"},
+        {"F", "EUR"},  {"PA", "EUR"}, {"AS", "EUR"}, {"MI", "EUR"}, {"MC", "EUR"}, {"BR", "EUR"},
+        {"LS", "EUR"}, {"VI", "EUR"}, {"HE", "EUR"}, {"IR", "EUR"}, {"SW", "CHF"}, {"ST", "SEK"},
+        {"OL", "NOK"}, {"CO", "DKK"}, {"SS", "CNY"}, {"SZ", "CNY"}, {"KS", "KRW"}, {"KQ", "KRW"},
+        {"TW", "TWD"}, {"TWO", "TWD"}, {"SI", "SGD"}, {"SA", "BRL"}, {"MX", "MXN"}, {"JO", "ZAR"},
+    };
+    return map;
+}
+
+double round_fx(double v, int decimals) {
+    const double f = std::pow(10.0, decimals);
+    return std::round(v * f) / f;
+}
+
+double round_hedge_ratio(double v) {
+    return std::clamp(std::round(v * 4.0) / 4.0, 0.0, 1.0);
+}
+
+} // namespace
+
+QString PortfolioService::listing_currency(const QString& symbol, const QString& fallback, double* minor_unit) {
+    if (minor_unit)
+        *minor_unit = 1.0;
+    const QString sym = symbol.trimmed().toUpper();
+
+    // Crypto pairs ("BTC-USD", "ETH-EUR") are quoted in the pair's quote leg.
+    const int dash = sym.lastIndexOf('-');
+    if (dash > 0 && sym.size() - dash - 1 == 3 && reference_short_rates().contains(sym.mid(dash + 1)))
+        return sym.mid(dash + 1);
+
+    const int dot = sym.lastIndexOf('.');
+    if (dot > 0) {
+        const QString suffix = sym.mid(dot + 1);
+        auto it = suffix_currencies().constFind(suffix);
+        if (it != suffix_currencies().constEnd()) {
+            // LSE and JSE equities quote in pence / cents.
+            if (minor_unit && (suffix == "L" || suffix == "IL" || suffix == "JO"))
+                *minor_unit = 100.0;
+            return it.value();
+        }
+    }
+    // No exchange suffix → US listing (covers class shares like "BRK-B").
+    return dot < 0 ? QStringLiteral("USD") : fallback;
+}
+
+std::optional<double> PortfolioService::short_rate(const QString& currency) {
+    const QString ccy = currency.toUpper();
+    auto r = SettingsRepository::instance().get("portfolio.fx_short_rates");
+    if (r.is_ok() && !r.value().isEmpty()) {
+        const auto doc = QJsonDocument::fromJson(r.value().toUtf8());
+        if (doc.isObject() && doc.object().contains(ccy))
+            return doc.object().value(ccy).toDouble();
+    }
+    auto it = reference_short_rates().constFind(ccy);
+    if (it != reference_short_rates().constEnd())
+        return it.value();
+    return std::nullopt;
+}
+
+void PortfolioService::compute_fx_exposure(const portfolio::PortfolioSummary& summary, int horizon_months) {
+    struct State {
+        portfolio::FxExposureReport report;
+        QHash<QString, portfolio::CurrencyExposure> by_ccy;
+        QStringList order; // first-seen order of foreign currencies
+        int pending = 0;
+    };
+    auto st = std::make_shared<State>();
+    auto& report = st->report;
+    report.portfolio_id = summary.portfolio.id;
+    report.base_currency =
+        summary.portfolio.currency.isEmpty() ? QStringLiteral("USD") : summary.portfolio.currency.toUpper();
+    report.horizon_months = std::clamp(horizon_months, 1, 12);
+    report.generated_at = QDateTime::currentDateTimeUtc().toString(Qt::ISODate);
+    const QString base = report.base_currency;
+
+    // ── Group holdings by listing currency ───────────────────────────────────
+    // Holdings carry their native currency and local value from the
+    // summary's currency layer; re-convert at this report's own spot below.
+    for (const auto& h : summary.holdings) {
+        const QString ccy = h.currency.isEmpty() ? listing_currency(h.symbol, base) : h.currency;
+        const double mv = h.market_value_local;
+        if (ccy == base) {
+            report.total_value_base += mv;
+            continue;
+        }
+        if (!st->by_ccy.contains(ccy)) {
+            st->order << ccy;
+            st->by_ccy[ccy].currency = ccy;
+        }
+        auto& e = st->by_ccy[ccy];
+        e.symbols << h.symbol;
+        e.market_value_local += mv;
+    }
+
+    if (st->order.isEmpty()) {
+        emit fx_exposure_computed(report);
+        return;
+    }
+
+    // Runs once spot and every volatility history have landed.
+    QPointer<PortfolioService> self = this;
+    auto finish = [self, st]() {
+        if (!self)
+            return;
+        auto& rep = st->report;
+        const double t = rep.horizon_months / 12.0;
+        const double total = rep.total_value_base;
+        const auto rb = short_rate(rep.base_currency);
+        if (!rb)
+            rep.warnings << QString("No short rate for %1 — forwards assume zero differential").arg(rep.base_currency);
+
+        for (const auto& ccy : st->order) {
+            auto e = st->by_ccy.value(ccy);
+            e.weight = total > 0 ? e.market_value_base / total * 100.0 : 0.0;
+
+            // Covered interest parity: F = S · (1 + r_base·t) / (1 + r_foreign·t).
+            const auto rf = short_rate(ccy);
+            if (!rf)
+                rep.warnings << QString("No short rate for %1 — forward assumes zero differential").arg(ccy);
+            e.base_rate = rb.value_or(0.0);
+            e.foreign_rate = rf.value_or(e.base_rate);
+            if (e.spot > 0) {
+                e.forward = e.spot * (1.0 + e.base_rate * t) / (1.0 + e.foreign_rate * t);
+                e.forward_points = (e.forward - e.spot) * 10000.0;
+                // The hedger sells `ccy` forward: cost = (S − F) / S, annualised.
+                e.hedge_cost_annual_pct = (e.spot - e.forward) / e.spot / t * 100.0;
+            }
+
+            // ── Hedge-ratio heuristic ────────────────────────────────────────
+            // Start from 50% (the regret-minimising default for equity FX
+            // risk), lean up when the currency is volatile or the hedge earns
+            // carry, lean down when it is calm or the carry is expensive.
+            QStringList why;
+            double ratio = 0.0;
+            if (e.spot <= 0) {
+                why << "no spot rate";
+            } else if (e.weight < kMaterialWeightPct) {
+                why << QString("immaterial (%1% of portfolio)").arg(round_fx(e.weight, 1));
+            } else {
+                ratio = 0.5;
+                if (e.fx_volatility && *e.fx_volatility >= 12.0) {
+                    ratio += 0.25;
+                    why << QString("high FX volatility (%1%)").arg(round_fx(*e.fx_volatility, 1));
+                } else if (e.fx_volatility && *e.fx_volatility < 6.0) {
+                    ratio -= 0.25;
+                    why << QString("low FX volatility (%1%)").arg(round_fx(*e.fx_volatility, 1));
+                }
+                if (e.hedge_cost_annual_pct <= 0.0) {
+                    ratio += 0.25;
+                    why << QString("hedge earns carry (%1%/yr)").arg(round_fx(-e.hedge_cost_annual_pct, 2));
+                } else if (e.hedge_cost_annual_pct >= 3.0) {
+                    ratio -= 0.25;
+                    why << QString("expensive carry (%1%/yr)").arg(round_fx(e.hedge_cost_annual_pct, 2));
+                }
+                if (why.isEmpty())
+                    why << "balanced volatility and carry";
+            }
+            e.suggested_hedge_ratio = round_hedge_ratio(ratio);
+            e.suggested_hedge_notional = e.market_value_local * e.suggested_hedge_ratio;
+            e.hedge_cost_base = e.market_value_base * e.suggested_hedge_ratio * e.hedge_cost_annual_pct / 100.0 * t;
+            if (e.fx_volatility) {
+                const double daily = *e.fx_volatility / 100.0 / std::sqrt(252.0);
+                e.var_95_unhedged = kZ95 * daily * e.market_value_base;
+                e.var_95_hedged = *e.var_95_unhedged * (1.0 - e.suggested_hedge_ratio);
+            }
+            e.rationale = why.join("; ");
+
+            rep.foreign_value_base += e.market_value_base;
+            rep.exposures.append(e);
+        }
+        rep.foreign_weight = total > 0 ? rep.foreign_value_base / total * 100.0 : 0.0;
+        std::sort(rep.exposures.begin(), rep.exposures.end(),
+                  [](const auto& a, const auto& b) { return a.market_value_base > b.market_value_base; });
+        LOG_INFO("PortfolioSvc", QString("FX exposure for %1: %2 currencies, %3% foreign")
+                                     .arg(rep.portfolio_id)
+                                     .arg(rep.exposures.size())
+                                     .arg(round_fx(rep.foreign_weight, 1)));
+        emit self->fx_exposure_computed(rep);
+    };
+
+    // ── Spot for every foreign currency, then 1y history for volatility ─────
+    QStringList pairs;
+    for (const auto& ccy : st->order)
+        pairs << ccy + base + "=X";
+
+    MarketDataService::instance().fetch_quotes(pairs, [self, st, base, finish](bool ok, QVector<QuoteData> quotes) {
+        if (!self)
+            return;
+        QHash<QString, double> spot;
+        if (ok)
+            for (const auto& q : quotes)
+                if (q.price > 0)
+                    spot.insert(q.symbol, q.price);
+
+        for (const auto& ccy : st->order) {
+            auto& e = st->by_ccy[ccy];
+            e.spot = spot.value(ccy + base + "=X", 0.0);
+            if (e.spot <= 0) {
+                st->report.warnings << QString("No %1/%2 spot — exposure excluded from totals").arg(ccy, base);
+                continue;
+            }
+            e.market_value_base = e.market_value_local * e.spot;
+            st->report.total_value_base += e.market_value_base;
+        }
+
+        st->pending = st->order.size();
+        for (const auto& ccy : st->order) {
+            MarketDataService::instance().fetch_history(
+                ccy + base + "=X", "1y", "1d", [st, ccy, finish](bool hist_ok, QVector<HistoryPoint> pts) {
+                    if (hist_ok && pts.size() > 20) {
+                        double sum = 0, sum_sq = 0;
+                        int n = 0;
+                        for (int i = 1; i < pts.size(); ++i) {
+                            if (pts[i - 1].close <= 0 || pts[i].close <= 0)
+                                continue;
+                            const double lr = std::log(pts[i].close / pts[i - 1].close);
+                            sum += lr;
+                            sum_sq += lr * lr;
+                            ++n;
+                        }
+                        if (n > 1) {
+                            const double mean = sum / n;
+                            const double var = std::max(0.0, sum_sq / n - mean * mean);
+                            st->by_ccy[ccy].fx_volatility = std::sqrt(var * 252.0) * 100.0;
+                        }
+                    }
+                    if (!st->by_ccy[ccy].fx_volatility)
+                        st->report.warnings << QString("No %1 history — volatility and VaR omitted").arg(ccy);
+                    if (--st->pending == 0)
+                        finish();
+                });
+        }
+    });
+}
+
+} // namespace fincept::services
@@ -0,0 +1,113 @@
+// src/services/portfolio/PortfolioService_Optimize.cpp
+//
+// Optimiser inputs: daily closes for an arbitrary symbol set, synced into
+// the same stored price table the benchmark analytics use (benchmark_prices,
+// v054), aligned on common trading days and turned into simple returns for
+// PortfolioOptimizer. Stored closes are used as-is when the sync fails, so
+// optimisation keeps working offline.
+//
+// Part of the partial-class split of PortfolioService.cpp.
+
+#include "core/logging/Logger.h"
+#include "services/portfolio/PortfolioService.h"
+#include "storage/repositories/PortfolioRepository.h"
+
+#include <QDateTime>
+
+#include <algorithm>
+#include <memory>
+
+namespace fincept::services {
+
+namespace {
+
+constexpr int kAlignMinObservations = 20;
+
+} // namespace
+
+void PortfolioService::aligned_returns(const QStringList& symbols, int days, ReturnsCallback done) {
+    QStringList syms;
+    for (const auto& s : symbols) {
+        const QString sym = s.trimmed().toUpper();
+        if (!sym.isEmpty() && !syms.contains(sym))
+            syms << sym;
+    }
+    if (syms.isEmpty()) {
+        done({}, {}, {QStringLiteral("No symbols")});
+        return;
+    }
+    const QString from = QDate::currentDate().addDays(-std::clamp(days, 30, 3650)).toString(Qt::ISODate);
+
+    // Syncs run in parallel; the last one to report assembles the matrix.
+    struct Pending {
+        int remaining = 0;
+        QStringList failed;
+    };
+    auto pending = std::make_shared<Pending>();
+    pending->remaining = syms.size();
+
+    QPointer<PortfolioService> self = this;
+    auto assemble = [self, syms, from, pending, done]() {
+        if (!self)
+            return;
+        QStringList warnings;
+        if (!pending->failed.isEmpty())
+            warnings << QString("Price sync failed for %1 — using stored closes").arg(pending->failed.join(", "));
+
+        QStringList kept;
+        QVector<QHash<QString, double>> by_date;
+        QHash<QString, int> date_count;
+        for (const auto& sym : syms) {
+            auto r = PortfolioRepository::instance().get_benchmark_closes(sym, from);
+            if (r.is_err() || r.value().size() <= kAlignMinObservations) {
+                warnings << QString("%1 dropped — not enough price history").arg(sym);
+                continue;
+            }
+            QHash<QString, double> m;
+            for (const auto& [d, c] : r.value())
+                if (c > 0) {
+                    m.insert(d, c);
+                    ++date_count[d];
+                }
+            kept << sym;
+            by_date.append(m);
+        }
+
+        QStringList common;
+        for (auto it = date_count.constBegin(); it != date_count.constEnd(); ++it)
+            if (it.value() == kept.size())
+                common << it.key();
+        std::sort(common.begin(), common.end());
+
+        QVector<QVector<double>> returns;
+        if (common.size() > kAlignMinObservations) {
+            returns.reserve(common.size() - 1);
+            for (int t = 1; t < common.size(); ++t) {
+                QVector<double> row(kept.size());
+                for (int i = 0; i < kept.size(); ++i)
+                    row[i] = by_date[i].value(common[t]) / by_date[i].value(common[t - 1]) - 1.0;
+                returns.append(row);
+            }
+        } else if (!kept.isEmpty()) {
+            warnings << QString("Only %1 common trading day(s) across %2 symbol(s) — need %3")
+                            .arg(common.size())
+                            .arg(kept.size())
+                            .arg(kAlignMinObservations + 1);
+            kept.clear();
+        }
+        LOG_INFO("PortfolioSvc",
+                 QString("Aligned returns: %1 symbol(s) × %2 day(s)").arg(kept.size()).arg(returns.size()));
+        done(kept, returns, warnings);
+    };
+
+    for (const auto& sym : syms) {
+        sync_benchmark_prices(sym, [sym, pending, assemble](bool ok) {
+            if (!ok)
+                pending->failed << sym;
+            if (--pending->remaining == 0)
+                assemble();
+        });
+    }
+}
+
+} // namespace fincept::services
@@ -0,0 +1,154 @@
+// src/services/portfolio/PortfolioService_Scenario.cpp
+//
+// Scenario P&L inputs: the summary's holdings become ScenarioPositions, the
+// holdings' risk symbols and the shocked indices / rate proxies are aligned
+// through aligned_returns, and each risk symbol is regressed on the factor
+// columns. Rate proxies (^TNX quotes the yield in percent) are turned from
+// relative changes into basis-point changes first, so rates betas read as
+// empirical durations. ScenarioEngine does the rest.
+//
+// Part of the partial-class split of PortfolioService.cpp.
+
+#include "core/logging/Logger.h"
+#include "services/portfolio/PortfolioService.h"
+#include "storage/repositories/PortfolioRepository.h"
+
+#include <QDate>
+#include <QPointer>
+#include <QSet>
+
+namespace fincept::services {
+
+namespace {
+
+// Last stored close of `symbol` within the window, 0 when there is none.
+double last_close(const QString& symbol, const QString& from) {
+    auto r = PortfolioRepository::instance().get_benchmark_closes(symbol, from);
+    if (r.is_err() || r.value().isEmpty())
+        return 0.0;
+    return r.value().last().second;
+}
+
+} // namespace
+
+void PortfolioService::run_scenario(const portfolio::PortfolioSummary& summary, const ScenarioSpec& spec,
+                                    ScenarioCallback done) {
+    QVector<ScenarioPosition> positions;
+    QStringList risk_symbols;
+    QStringList underlyings;
+    for (const auto& h : summary.holdings) {
+        if (h.quantity == 0)
+            continue;
+        ScenarioPosition p;
+        p.symbol = h.symbol.toUpper();
+        p.quantity = h.quantity;
+        p.price = h.current_price;
+        p.market_value = h.market_value;
+        p.currency = h.currency.isEmpty() ? listing_currency(h.symbol, summary.portfolio.currency) : h.currency;
+        p.sector = h.sector;
+        positions.append(p);
+
+        ScenarioOption opt;
+        const bool is_option = ScenarioEngine::parse_option(p.symbol, &opt);
+        const QString risk = is_option ? opt.underlying : p.symbol;
+        if (!risk_symbols.contains(risk))
+            risk_symbols << risk;
+        if (is_option && !underlyings.contains(risk))
+            underlyings << risk;
+    }
+
+    QStringList factors;
+    QSet<QString> rates;
+    for (const auto& s : spec.shocks) {
+        if (s.kind != ScenarioShock::Kind::Factor && s.kind != ScenarioShock::Kind::Rates)
+            continue;
+        if (!factors.contains(s.symbol))
+            factors << s.symbol;
+        if (s.kind == ScenarioShock::Kind::Rates)
+            rates.insert(s.symbol);
+    }
+
+    ScenarioMarket market;
+    market.base_currency = summary.portfolio.currency;
+    market.factors = factors;
+    if (positions.isEmpty()) {
+        done(ScenarioEngine::run(positions, spec, market));
+        return;
+    }
+
+    QStringList symbols = risk_symbols;
+    for (const auto& f : factors)
+        if (!symbols.contains(f))
+            symbols << f;
+
+    const int lookback = spec.lookback_days;
+    QPointer<PortfolioService> self = this;
+    aligned_returns(symbols, lookback, [self, positions, spec, market, risk_symbols, underlyings, factors, rates,
+                                        lookback, done](QStringList kept, QVector<QVector<double>> returns,
+                                                        QStringList warnings) mutable {
+        if (!self)
+            return;
+        market.warnings = warnings;
+        const QString from = QDate::currentDate().addDays(-lookback).toString(Qt::ISODate);
+
+        // Factor columns that survived alignment; rates as bp changes.
+        QVector<int> cols;
+        QStringList used;
+        for (const auto& f : factors) {
+            const int c = int(kept.indexOf(f));
+            if (c < 0) {
+                market.warnings << QString("%1: no price history — its betas can't be estimated").arg(f);
+                continue;
+            }
+            if (rates.contains(f)) {
+                double level = last_close(f, from);
+                for (int t = int(returns.size()) - 1; t >= 0; --t) {
+                    const double prev = level / (1.0 + returns[t][c]);
+                    returns[t][c] = (level - prev) * 100.0;
+                    level = prev;
+                }
+            }
+            cols << c;
+            used << f;
+        }
+
+        if (!cols.isEmpty()) {
+            QVector<QVector<double>> x(returns.size(), QVector<double>(cols.size()));
+            for (int t = 0; t < returns.size(); ++t)
+                for (int j = 0; j < cols.size(); ++j)
+                    x[t][j] = returns[t][cols[j]];
+            for (const auto& sym : risk_symbols) {
+                const int c = int(kept.indexOf(sym));
+                if (c < 0 || factors.contains(sym))
+                    continue;
+                QVector<double> y(returns.size());
+                for (int t = 0; t < returns.size(); ++t)
+                    y[t] = returns[t][c];
+                auto sens = ScenarioEngine::regress(y, x);
+                if (sens.betas.isEmpty())
+                    continue;
+                // Back onto the full factor list; dropped factors get zero.
+                QVector<double> full(factors.size(), 0.0);
+                for (int j = 0; j < used.size(); ++j)
+                    full[int(factors.indexOf(used[j]))] = sens.betas[j];
+                sens.betas = full;
+                market.by_symbol.insert(sym, sens);
+            }
+        }
+
+        for (const auto& u : underlyings) {
+            const double spot = last_close(u, from);
+            if (spot > 0)
+                market.spots.insert(u, spot);
+        }
+
+        auto result = ScenarioEngine::run(positions, spec, market);
+        LOG_INFO("PortfolioSvc", QString("Scenario: %1 shocks on %2 positions, P&L %3")
+                                     .arg(spec.shocks.size())
+                                     .arg(positions.size())
+                                     .arg(result.total_pnl, 0, 'f', 2));
+        done(result);
+    });
+}
+
+} // namespace fincept::services
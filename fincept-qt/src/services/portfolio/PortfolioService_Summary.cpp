@@ -1,13 +1,15 @@
 // src/services/portfolio/PortfolioService_Summary.cpp
 //
 // Summary loading: load_summary, refresh_summary, build_summary,
-// try_broker_quotes, finalize_summary. These produce the PortfolioSummary
-// object the UI binds to.
+// try_broker_quotes, finalize_summary / assemble_summary, plus the
+// base-currency layer (set_base_currency, backfill_cost_fx). These produce
+// the PortfolioSummary object the UI binds to.
 //
 // Part of the partial-class split of PortfolioService.cpp.
 
 #include "core/logging/Logger.h"
 #include "python/PythonRunner.h"
+#include "services/portfolio/FxRateService.h"
 #include "services/portfolio/PortfolioService.h"
 #include "services/sectors/SectorResolver.h"
 #include "storage/repositories/PortfolioRepository.h"
@@ -232,44 +234,110 @@ bool PortfolioService::try_broker_quotes(const QString& portfolio_id, const QVec
 void PortfolioService::finalize_summary(const QString& portfolio_id, const QVector<portfolio::PortfolioAsset>& assets,
                                         const portfolio::Portfolio& portfolio,
                                         const QHash<QString, QuoteData>& quote_map) {
+    const QString base = portfolio.currency.isEmpty() ? QStringLiteral("USD") : portfolio.currency.toUpper();
+    QStringList foreign;
+    for (const auto& a : assets) {
+        const QString ccy = a.currency.isEmpty() ? listing_currency(a.symbol, base) : a.currency.toUpper();
+        if (ccy != base && !foreign.contains(ccy))
+            foreign << ccy;
+    }
+    if (foreign.isEmpty()) {
+        assemble_summary(portfolio_id, assets, portfolio, quote_map, {}, {});
+        return;
+    }
+
+    QPointer<PortfolioService> self = this;
+    FxRateService::instance().spot_rates(
+        foreign, base, [self, portfolio_id, assets, portfolio, quote_map](FxRateService::Rates rates) {
+            if (!self)
+                return;
+            self->assemble_summary(portfolio_id, assets, portfolio, quote_map, rates.to_base, rates.warnings);
+        });
+}
+
+void PortfolioService::assemble_summary(const QString& portfolio_id, const QVector<portfolio::PortfolioAsset>& assets,
+                                        const portfolio::Portfolio& portfolio,
+                                        const QHash<QString, QuoteData>& quote_map,
+                                        const QHash<QString, double>& fx_rates, const QStringList& fx_warnings) {
     portfolio::PortfolioSummary summary;
     summary.portfolio = portfolio;
     summary.holdings.reserve(assets.size());
+    summary.fx_warnings = fx_warnings;
+    const QString base = portfolio.currency.isEmpty() ? QStringLiteral("USD") : portfolio.currency.toUpper();
 
     double total_mv = 0;
     double total_cost = 0;
     double total_day = 0;
     double total_prev = 0; // previous-close value of PRICED holdings only (day% base)
+    bool needs_cost_fx = false;
 
     for (const auto& asset : assets) {
         portfolio::HoldingWithQuote h;
         h.symbol = asset.symbol;
         h.quantity = asset.quantity;
         h.avg_buy_price = asset.avg_buy_price;
-        h.cost_basis = asset.quantity * asset.avg_buy_price;
         // Prefer stored sector; fall back to resolver cache (which may
         // populate async — see sector_resolved handler in constructor).
         h.sector = asset.sector.isEmpty() ? SectorResolver::instance().sector_for(asset.symbol) : asset.sector;
 
+        // ── Native currency and conversion rates ─────────────────────────────
+        // Prices stay in the quote unit; `minor` turns pence/cents listings
+        // into major units before any value is formed.
+        double minor = 1.0;
+        const QString inferred = listing_currency(asset.symbol, base, &minor);
+        h.currency = asset.currency.isEmpty() ? inferred : asset.currency.toUpper();
+        if (!asset.currency.isEmpty() && h.currency != inferred)
+            minor = 1.0; // explicit currency overrides the suffix heuristic
+        if (h.currency != base) {
+            if (fx_rates.contains(h.currency)) {
+                h.fx_rate = fx_rates.value(h.currency);
+            } else {
+                // No rate at all: fall back to the purchase rate if we have
+                // one, otherwise show it unconverted and say so.
+                h.fx_rate = asset.cost_fx_rate > 0 ? asset.cost_fx_rate : 1.0;
+                summary.fx_warnings << QString("%1 valued at %2 (no live %3/%4 rate)")
+                                           .arg(h.symbol,
+                                                asset.cost_fx_rate > 0 ? QStringLiteral("purchase FX rate")
+                                                                       : QStringLiteral("1:1"),
+                                                h.currency, base);
+            }
+            // Unknown purchase rate → attribute everything to local return
+            // until backfill_cost_fx resolves it.
+            h.cost_fx_rate = asset.cost_fx_rate > 0 ? asset.cost_fx_rate : h.fx_rate;
+            if (asset.cost_fx_rate <= 0)
+                needs_cost_fx = true;
+            summary.fx_rates.insert(h.currency, h.fx_rate);
+        }
+
+        h.cost_basis_local = asset.quantity * asset.avg_buy_price / minor;
+        h.cost_basis = h.cost_basis_local * h.cost_fx_rate;
+
         auto it = quote_map.find(asset.symbol);
         if (it != quote_map.end()) {
             h.current_price = it->price;
-            h.day_change = it->change;
+            h.day_change = it->change; // per unit, quote currency
             h.day_change_percent = it->change_pct;
-            total_prev += (h.current_price - h.day_change) * h.quantity; // priced holdings only
+            total_day += h.day_change / minor * h.quantity * h.fx_rate;
+            // priced holdings only
+            total_prev += (h.current_price - h.day_change) / minor * h.quantity * h.fx_rate;
         } else {
             // Fallback to avg buy price if no quote (broker missed the symbol,
             // or yfinance returned nothing).
             h.current_price = asset.avg_buy_price;
         }
 
-        h.market_value = h.quantity * h.current_price;
+        h.market_value_local = h.quantity * h.current_price / minor;
+        h.market_value = h.market_value_local * h.fx_rate;
         h.unrealized_pnl = h.market_value - h.cost_basis;
         h.unrealized_pnl_percent = (h.cost_basis > 0) ? (h.unrealized_pnl / h.cost_basis) * 100.0 : 0;
+        // Price move valued at the purchase rate + FX move on today's value.
+        h.local_return_pnl = (h.market_value_local - h.cost_basis_local) * h.cost_fx_rate;
+        h.currency_return_pnl = h.market_value_local * (h.fx_rate - h.cost_fx_rate);
 
         total_mv += h.market_value;
         total_cost += h.cost_basis;
-        total_day += h.day_change * h.quantity;
+        summary.total_local_return_pnl += h.local_return_pnl;
+        summary.total_currency_return_pnl += h.currency_return_pnl;
 
         if (h.unrealized_pnl >= 0)
             summary.gainers++;
@@ -309,6 +377,59 @@ void PortfolioService::finalize_summary(const QString& portfolio_id, const QVect
                                                   today);
 
     emit summary_loaded(summary);
+
+    if (needs_cost_fx)
+        backfill_cost_fx(portfolio_id, assets, base);
+}
+
+void PortfolioService::backfill_cost_fx(const QString& portfolio_id, const QVector<portfolio::PortfolioAsset>& assets,
+                                        const QString& base) {
+    QPointer<PortfolioService> self = this;
+    for (const auto& a : assets) {
+        const QString ccy = a.currency.isEmpty() ? listing_currency(a.symbol, base) : a.currency.toUpper();
+        if (ccy == base || a.cost_fx_rate > 0)
+            continue;
+        const QString key = portfolio_id + "|" + a.symbol;
+        if (cost_fx_attempted_.contains(key))
+            continue;
+        cost_fx_attempted_.insert(key);
+        const QDate date = QDate::fromString(a.first_purchase_date.left(10), Qt::ISODate);
+        FxRateService::instance().rate_on(
+            ccy, base, date, [self, portfolio_id, symbol = a.symbol, stored = a.currency](std::optional<double> rate) {
+                if (!self || !rate)
+                    return;
+                PortfolioRepository::instance().set_asset_currency(portfolio_id, symbol, stored, *rate);
+                self->invalidate_cache(portfolio_id);
+            });
+    }
+}
+
+void PortfolioService::set_base_currency(const QString& portfolio_id, const QString& currency) {
+    const QString ccy = currency.trimmed().toUpper();
+    auto& repo = PortfolioRepository::instance();
+    auto p = repo.get_portfolio(portfolio_id);
+    if (p.is_err()) {
+        emit summary_error(portfolio_id, QString::fromStdString(p.error()));
+        return;
+    }
+    if (ccy.size() != 3 || p.value().currency.toUpper() == ccy)
+        return;
+    auto r = repo.update_portfolio(portfolio_id, p.value().name, p.value().owner, ccy, p.value().description);
+    if (r.is_err()) {
+        emit summary_error(portfolio_id, QString::fromStdString(r.error()));
+        return;
+    }
+    // Purchase rates were stored against the old base; re-derive them.
+    auto assets = repo.get_assets(portfolio_id);
+    if (assets.is_ok()) {
+        for (const auto& a : assets.value()) {
+            repo.set_asset_currency(portfolio_id, a.symbol, a.currency, 0);
+            cost_fx_attempted_.remove(portfolio_id + "|" + a.symbol);
+        }
+    }
+    LOG_INFO("PortfolioSvc", QString("Portfolio %1 base currency → %2").arg(portfolio_id, ccy));
+    load_portfolios();
+    refresh_summary(portfolio_id);
 }
 
 // ── Asset operations ─────────────────────────────────────────────────────────
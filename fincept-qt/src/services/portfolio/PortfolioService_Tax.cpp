@@ -0,0 +1,150 @@
+// src/services/portfolio/PortfolioService_Tax.cpp
+//
+// Tax lots and realized capital gains over the transaction ledger, plus the
+// Form 8949 CSV export. Lot matching and jurisdiction rules live in
+// TaxLotEngine; this file loads the ledger and any reference prices.
+//
+// Part of the partial-class split of PortfolioService.cpp.
+
+#include "core/logging/Logger.h"
+#include "python/PythonRunner.h"
+#include "services/portfolio/PortfolioService.h"
+#include "services/portfolio/TaxLotEngine.h"
+#include "storage/cache/CacheManager.h"
+#include "storage/repositories/PortfolioRepository.h"
+
+#include <QFile>
+#include <QJsonDocument>
+#include <QJsonObject>
+#include <QTextStream>
+
+#include <functional>
+#include <memory>
+
+namespace fincept::services {
+
+namespace {
+
+// The ledger is replayed from the first trade, so load all of it.
+constexpr int kTaxLedgerLimit = 1000000;
+// Historical closes never change; keep them for a month.
+constexpr int kFmvCacheTtlSec = 30 * 24 * 60 * 60;
+
+// Closing price per symbol on (or just before) `date`, one yfinance call per
+// uncached symbol. `done` runs once with whatever resolved.
+void fetch_reference_prices(QObject* ctx, const QStringList& symbols, const QDate& date,
+                            std::function<void(QHash<QString, double>)> done) {
+    const QString iso = date.toString(Qt::ISODate);
+    auto prices = std::make_shared<QHash<QString, double>>();
+    QStringList to_fetch;
+    for (const auto& sym : symbols) {
+        const QVariant cv = fincept::CacheManager::instance().get("portfolio:refprice:" + sym + ":" + iso);
+        if (!cv.isNull())
+            prices->insert(sym, cv.toDouble());
+        else
+            to_fetch << sym;
+    }
+    if (to_fetch.isEmpty()) {
+        done(*prices);
+        return;
+    }
+    auto pending = std::make_shared<int>(to_fetch.size());
+    QPointer<QObject> guard = ctx;
+    for (const auto& sym : to_fetch) {
+        python::PythonRunner::instance().run(
+            "yfinance_data.py", {"historical_price", sym, iso},
+            [guard, sym, iso, prices, pending, done](python::PythonResult result) {
+                if (!guard)
+                    return;
+                if (result.success) {
+                    const auto obj = QJsonDocument::fromJson(python::extract_json(result.output).toUtf8()).object();
+                    if (obj.value("found").toBool() && obj.value("price").toDouble() > 0) {
+                        const double px = obj.value("price").toDouble();
+                        prices->insert(sym, px);
+                        fincept::CacheManager::instance().put("portfolio:refprice:" + sym + ":" + iso, QVariant(px),
+                                                              kFmvCacheTtlSec, "portfolio");
+                    }
+                }
+                if (--*pending == 0)
+                    done(*prices);
+            });
+    }
+}
+
+} // namespace
+
+void PortfolioService::compute_tax_report(const QString& portfolio_id, const QString& jurisdiction,
+                                          const QString& tax_year) {
+    std::shared_ptr<TaxRules> rules = TaxRules::for_jurisdiction(jurisdiction);
+    if (!rules) {
+        emit tax_report_error(portfolio_id, QString("Unsupported jurisdiction '%1' (supported: %2)")
+                                                .arg(jurisdiction, TaxRules::supported().join(", ")));
+        return;
+    }
+    auto portfolio_r = PortfolioRepository::instance().get_portfolio(portfolio_id);
+    auto txns_r = PortfolioRepository::instance().get_transactions(portfolio_id, kTaxLedgerLimit);
+    if (portfolio_r.is_err() || txns_r.is_err()) {
+        emit tax_report_error(portfolio_id, QString::fromStdString(portfolio_r.is_err() ? portfolio_r.error()
+                                                                                          : txns_r.error()));
+        return;
+    }
+    const QString currency = portfolio_r.value().currency;
+    const QVector<portfolio::Transaction> txns = txns_r.value();
+
+    auto finish = [this, portfolio_id, currency, txns, rules, tax_year](const QHash<QString, double>& fmv) {
+        auto report = TaxLotEngine::compute(txns, *rules, fmv, tax_year);
+        report.portfolio_id = portfolio_id;
+        report.currency = currency;
+        LOG_INFO("PortfolioSvc", QString("Tax report %1 (%2): %3 dispositions, %4 open lots")
+                                     .arg(portfolio_id, rules->id())
+                                     .arg(report.realized.size())
+                                     .arg(report.open_lots.size()));
+        emit tax_report_computed(report);
+    };
+
+    // Reference prices are only needed for lots bought on or before the
+    // rules' cutoff (India grandfathering).
+    const QDate ref_date = rules->required_fmv_date();
+    QStringList need_ref;
+    if (ref_date.isValid()) {
+        for (const auto& t : txns) {
+            if (t.transaction_type.toUpper() == "BUY" && !need_ref.contains(t.symbol) &&
+                QDate::fromString(t.transaction_date.left(10), Qt::ISODate) <= ref_date)
+                need_ref << t.symbol;
+        }
+    }
+    if (need_ref.isEmpty()) {
+        finish({});
+        return;
+    }
+    fetch_reference_prices(this, need_ref, ref_date, finish);
+}
+
+void PortfolioService::export_form_8949(const QString& portfolio_id, const QString& tax_year,
+                                        const QString& file_path) {
+    auto* holder = new QObject(this);
+    connect(this, &PortfolioService::tax_report_computed, holder,
+            [this, holder, portfolio_id, file_path](portfolio::TaxReport report) {
+                if (report.portfolio_id != portfolio_id || report.jurisdiction != "US")
+                    return;
+                holder->deleteLater();
+                QFile file(file_path);
+                if (!file.open(QIODevice::WriteOnly | QIODevice::Text)) {
+                    LOG_ERROR("PortfolioSvc", "Cannot open file for writing: " + file_path);
+                    emit tax_report_error(portfolio_id, "Cannot open file for writing: " + file_path);
+                    return;
+                }
+                QTextStream out(&file);
+                out << TaxLotEngine::to_form_8949_csv(report);
+                file.close();
+                LOG_INFO("PortfolioSvc", "Exported Form 8949 CSV to " + file_path);
+                emit export_complete(file_path);
+            });
+    connect(this, &PortfolioService::tax_report_error, holder, [holder, portfolio_id](QString pid, QString) {
+        if (pid == portfolio_id)
+            holder->deleteLater();
+    });
+    compute_tax_report(portfolio_id, "US", tax_year);
+}
+
+} // namespace fincept::services
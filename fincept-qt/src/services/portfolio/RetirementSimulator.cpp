@@ -0,0 +1,374 @@
+// src/services/portfolio/RetirementSimulator.cpp
+#include "services/portfolio/RetirementSimulator.h"
+
+#include <QElapsedTimer>
+#include <QJsonArray>
+#include <QtConcurrent/QtConcurrent>
+
+#include <algorithm>
+#include <cmath>
+#include <random>
+#include <vector>
+
+namespace fincept::services {
+
+namespace {
+
+constexpr const char* kPctKeys[7] = {"p5", "p10", "p25", "p50", "p75", "p90", "p95"};
+
+quint64 rsim_splitmix(quint64 x) {
+    x += 0x9E3779B97F4A7C15ull;
+    x = (x ^ (x >> 30)) * 0xBF58476D1CE4E5B9ull;
+    x = (x ^ (x >> 27)) * 0x94D049BB133111EBull;
+    return x ^ (x >> 31);
+}
+
+/// One draw from `d` driven by the standard normal `z` (so assets can share
+/// correlated shocks). Student-t mixes in its own chi-square draw.
+double rsim_draw(const ReturnDistribution& d, double z, std::mt19937_64& gen) {
+    switch (d.kind) {
+        case ReturnDistribution::Kind::LogNormal: {
+            const double gross = 1.0 + d.mean;
+            const double s2 = std::log(1.0 + (d.vol * d.vol) / (gross * gross));
+            return std::exp(std::log(gross) - 0.5 * s2 + std::sqrt(s2) * z) - 1.0;
+        }
+        case ReturnDistribution::Kind::StudentT: {
+            std::chi_squared_distribution<double> chi(d.dof);
+            const double t = z / std::sqrt(std::max(chi(gen), 1e-12) / d.dof);
+            return d.mean + d.vol * t * std::sqrt((d.dof - 2.0) / d.dof);
+        }
+        case ReturnDistribution::Kind::Normal:
+        default:
+            return d.mean + d.vol * z;
+    }
+}
+
+double rsim_equity_weight(const QVector<GlidepathPoint>& gp, int age) {
+    if (gp.isEmpty())
+        return 1.0;
+    if (age <= gp.first().age)
+        return gp.first().equity_weight;
+    if (age >= gp.last().age)
+        return gp.last().equity_weight;
+    for (int i = 1; i < gp.size(); ++i) {
+        if (age <= gp[i].age) {
+            const auto& a = gp[i - 1];
+            const auto& b = gp[i];
+            const double f = b.age == a.age ? 1.0 : double(age - a.age) / double(b.age - a.age);
+            return a.equity_weight + f * (b.equity_weight - a.equity_weight);
+        }
+    }
+    return gp.last().equity_weight;
+}
+
+/// Sum of the schedule's flows for the year starting at `age`, in nominal
+/// terms given the cumulative `price_level` since today.
+double rsim_flow(const QVector<CashFlowSchedule>& flows, int age, double price_level) {
+    double total = 0.0;
+    for (const auto& f : flows) {
+        if (age < f.from_age || age >= f.to_age || f.amount == 0.0)
+            continue;
+        double amt = f.amount * std::pow(1.0 + f.growth, age - f.from_age);
+        if (f.inflation_indexed)
+            amt *= price_level;
+        total += amt;
+    }
+    return total;
+}
+
+ReturnDistribution rsim_dist_from_json(const QJsonObject& o, ReturnDistribution d) {
+    if (o.contains("kind"))
+        d.kind = ReturnDistribution::kind_from_string(o.value("kind").toString());
+    d.mean = o.value("mean").toDouble(d.mean);
+    d.vol = o.value("vol").toDouble(d.vol);
+    d.dof = o.value("dof").toDouble(d.dof);
+    return d;
+}
+
+QVector<CashFlowSchedule> rsim_flows_from_json(const QJsonArray& arr) {
+    QVector<CashFlowSchedule> out;
+    for (const auto& v : arr) {
+        const auto o = v.toObject();
+        CashFlowSchedule f;
+        f.label = o.value("label").toString();
+        f.amount = o.value("amount").toDouble();
+        f.from_age = o.value("from_age").toInt();
+        f.to_age = o.value("to_age").toInt();
+        f.growth = o.value("growth").toDouble(0.0);
+        f.inflation_indexed = o.value("inflation_indexed").toBool(true);
+        out.append(f);
+    }
+    return out;
+}
+
+QJsonObject rsim_pct_to_json(const double (&v)[7]) {
+    QJsonObject o;
+    for (int i = 0; i < 7; ++i)
+        o[kPctKeys[i]] = std::round(v[i] * 100.0) / 100.0;
+    return o;
+}
+
+} // namespace
+
+// ── Distribution names ───────────────────────────────────────────────────────
+
+ReturnDistribution::Kind ReturnDistribution::kind_from_string(const QString& s) {
+    const QString k = s.trimmed().toLower();
+    if (k == "lognormal" || k == "log_normal")
+        return Kind::LogNormal;
+    if (k == "student_t" || k == "studentt" || k == "t")
+        return Kind::StudentT;
+    return Kind::Normal;
+}
+
+QString ReturnDistribution::kind_to_string(Kind k) {
+    switch (k) {
+        case Kind::LogNormal:
+            return "lognormal";
+        case Kind::StudentT:
+            return "student_t";
+        case Kind::Normal:
+        default:
+            return "normal";
+    }
+}
+
+// ── Validation ───────────────────────────────────────────────────────────────
+
+QStringList RetirementSimulator::validate(const RetirementSimConfig& cfg) {
+    QStringList errs;
+    if (cfg.current_age < 0 || cfg.end_age <= cfg.current_age)
+        errs << "end_age must be greater than current_age";
+    if (cfg.end_age - cfg.current_age > 100)
+        errs << "horizon is limited to 100 years";
+    if (cfg.retirement_age < cfg.current_age || cfg.retirement_age > cfg.end_age)
+        errs << "retirement_age must lie between current_age and end_age";
+    if (cfg.paths < 1 || cfg.paths > kMaxPaths)
+        errs << QString("paths must be between 1 and %1").arg(kMaxPaths);
+    auto check = [&errs](const ReturnDistribution& d, const char* name) {
+        if (d.vol < 0)
+            errs << QString("%1.vol must be non-negative").arg(name);
+        if (d.kind == ReturnDistribution::Kind::StudentT && d.dof <= 2.0)
+            errs << QString("%1.dof must be greater than 2 for student_t").arg(name);
+        if (d.kind == ReturnDistribution::Kind::LogNormal && d.mean <= -1.0)
+            errs << QString("%1.mean must be greater than -1 for lognormal").arg(name);
+    };
+    check(cfg.equity, "equity");
+    check(cfg.bonds, "bonds");
+    check(cfg.inflation, "inflation");
+    if (cfg.equity_bond_correlation < -1.0 || cfg.equity_bond_correlation > 1.0)
+        errs << "equity_bond_correlation must be within [-1, 1]";
+    if (cfg.inflation_persistence < 0.0 || cfg.inflation_persistence >= 1.0)
+        errs << "inflation_persistence must be within [0, 1)";
+    for (int i = 0; i < cfg.glidepath.size(); ++i) {
+        const auto& p = cfg.glidepath[i];
+        if (p.equity_weight < 0.0 || p.equity_weight > 1.0)
+            errs << QString("glidepath weight at age %1 must be within [0, 1]").arg(p.age);
+        if (i > 0 && p.age < cfg.glidepath[i - 1].age)
+            errs << "glidepath ages must be ascending";
+    }
+    for (const auto* flows : {&cfg.contributions, &cfg.withdrawals})
+        for (const auto& f : *flows)
+            if (f.amount < 0 || f.to_age < f.from_age)
+                errs << QString("cash flow '%1' needs a non-negative amount and to_age >= from_age").arg(f.label);
+    const int goal_age = cfg.goal_age > 0 ? cfg.goal_age : cfg.retirement_age;
+    if (cfg.goal_target > 0 && (goal_age < cfg.current_age || goal_age > cfg.end_age))
+        errs << "goal_age must lie between current_age and end_age";
+    if (cfg.annual_fee < 0.0 || cfg.annual_fee >= 0.2)
+        errs << "annual_fee must be within [0, 0.2)";
+    return errs;
+}
+
+// ── Simulation ───────────────────────────────────────────────────────────────
+
+RetirementSimResult RetirementSimulator::run(const RetirementSimConfig& cfg) {
+    RetirementSimResult out;
+    const QStringList errs = validate(cfg);
+    if (!errs.isEmpty()) {
+        out.error = errs.join("; ");
+        return out;
+    }
+
+    QElapsedTimer timer;
+    timer.start();
+
+    const int n = cfg.paths;
+    const int years = cfg.end_age - cfg.current_age;
+    const int points = years + 1;
+    const int goal_idx = (cfg.goal_age > 0 ? cfg.goal_age : cfg.retirement_age) - cfg.current_age;
+    const double rho = cfg.equity_bond_correlation;
+    const double rho_c = std::sqrt(std::max(0.0, 1.0 - rho * rho));
+    const double phi = cfg.inflation_persistence;
+    const double phi_c = std::sqrt(1.0 - phi * phi);
+
+    // Year-major so each year's cross-section is contiguous for the percentile pass.
+    std::vector<float> nominal(size_t(points) * n);
+    std::vector<float> real(size_t(points) * n);
+    std::vector<int> depleted_at(n, 0);
+    std::vector<char> goal_hit(n, 0);
+
+    QVector<int> chunks;
+    for (int c = 0; c * kChunkPaths < n; ++c)
+        chunks.append(c);
+
+    QtConcurrent::blockingMap(chunks, [&](int c) {
+        std::mt19937_64 gen(rsim_splitmix(cfg.seed ^ rsim_splitmix(quint64(c))));
+        std::normal_distribution<double> nd(0.0, 1.0);
+        const int first = c * kChunkPaths;
+        const int last = std::min(n, first + kChunkPaths);
+        for (int p = first; p < last; ++p) {
+            double bal = cfg.initial_balance;
+            double price = 1.0;
+            double infl_prev = cfg.inflation.mean;
+            nominal[p] = float(bal);
+            real[p] = float(bal);
+            for (int y = 0; y < years; ++y) {
+                const int age = cfg.current_age + y;
+
+                bal += rsim_flow(cfg.contributions, age, price);
+                const double out_flow = rsim_flow(cfg.withdrawals, age, price);
+                if (out_flow > 0 && out_flow > bal + 1e-9) {
+                    if (depleted_at[p] == 0)
+                        depleted_at[p] = age;
+                    bal = 0.0;
+                } else {
+                    bal -= out_flow;
+                }
+
+                const double z1 = nd(gen);
+                const double z2 = rho * z1 + rho_c * nd(gen);
+                const double w = rsim_equity_weight(cfg.glidepath, age);
+                const double re = std::max(-0.99, rsim_draw(cfg.equity, z1, gen));
+                const double rb = w < 1.0 ? std::max(-0.99, rsim_draw(cfg.bonds, z2, gen)) : 0.0;
+                bal *= std::max(0.0, 1.0 + w * re + (1.0 - w) * rb - cfg.annual_fee);
+
+                const double shock = rsim_draw(cfg.inflation, nd(gen), gen) - cfg.inflation.mean;
+                const double infl = std::max(-0.5, cfg.inflation.mean + phi * (infl_prev - cfg.inflation.mean) +
+                                                       phi_c * shock);
+                infl_prev = infl;
+                price *= 1.0 + infl;
+
+                const size_t at = size_t(y + 1) * n + p;
+                nominal[at] = float(bal);
+                real[at] = float(bal / price);
+            }
+            if (cfg.goal_target > 0 && real[size_t(goal_idx) * n + p] >= cfg.goal_target)
+                goal_hit[p] = 1;
+        }
+    });
+
+    // Terminal statistics before the columns are reordered by sorting.
+    double sum_real = 0.0;
+    for (int p = 0; p < n; ++p)
+        sum_real += real[size_t(years) * n + p];
+    out.mean_terminal_real = sum_real / n;
+    out.success_probability = double(std::count(depleted_at.begin(), depleted_at.end(), 0)) / n;
+    if (cfg.goal_target > 0)
+        out.goal_probability = double(std::count(goal_hit.begin(), goal_hit.end(), char(1))) / n;
+
+    std::vector<int> dep_ages;
+    for (int a : depleted_at)
+        if (a > 0)
+            dep_ages.push_back(a);
+    std::sort(dep_ages.begin(), dep_ages.end());
+    auto depletion_quantile = [&](double q) {
+        const size_t need = size_t(std::ceil(q * n));
+        return need > 0 && dep_ages.size() >= need ? dep_ages[need - 1] : 0;
+    };
+    out.depletion_age_p10 = depletion_quantile(0.10);
+    out.depletion_age_p50 = depletion_quantile(0.50);
+
+    out.cone.resize(points);
+    QVector<int> ys;
+    for (int y = 0; y < points; ++y)
+        ys.append(y);
+    QtConcurrent::blockingMap(ys, [&](int y) {
+        auto& pt = out.cone[y];
+        pt.age = cfg.current_age + y;
+        for (auto* col : {nominal.data(), real.data()}) {
+            float* b = col + size_t(y) * n;
+            std::sort(b, b + n);
+            double* dst = col == nominal.data() ? pt.nominal : pt.real;
+            for (int i = 0; i < 7; ++i) {
+                const int idx = std::clamp(int(RetirementSimResult::kPercentiles[i] * (n - 1) + 0.5), 0, n - 1);
+                dst[i] = b[idx];
+            }
+        }
+        int solvent = 0;
+        for (int a : depleted_at)
+            if (a == 0 || a >= pt.age)
+                ++solvent;
+        pt.solvent = double(solvent) / n;
+    });
+
+    out.median_terminal_nominal = out.cone.last().nominal[3];
+    out.median_terminal_real = out.cone.last().real[3];
+    out.paths = n;
+    out.valid = true;
+    out.elapsed_ms = double(timer.nsecsElapsed()) / 1e6;
+    return out;
+}
+
+// ── JSON ─────────────────────────────────────────────────────────────────────
+
+RetirementSimConfig RetirementSimulator::config_from_json(const QJsonObject& o) {
+    RetirementSimConfig c;
+    c.current_age = o.value("current_age").toInt(c.current_age);
+    c.retirement_age = o.value("retirement_age").toInt(c.retirement_age);
+    c.end_age = o.value("end_age").toInt(c.end_age);
+    c.initial_balance = o.value("initial_balance").toDouble(c.initial_balance);
+    c.contributions = rsim_flows_from_json(o.value("contributions").toArray());
+    c.withdrawals = rsim_flows_from_json(o.value("withdrawals").toArray());
+
+    // Shorthand: one flat contribution until retirement / withdrawal after it.
+    if (c.contributions.isEmpty() && o.value("annual_contribution").toDouble() > 0)
+        c.contributions.append({"contribution", o.value("annual_contribution").toDouble(), c.current_age,
+                                c.retirement_age, o.value("contribution_growth").toDouble(0.0), true});
+    if (c.withdrawals.isEmpty() && o.value("annual_withdrawal").toDouble() > 0)
+        c.withdrawals.append(
+            {"withdrawal", o.value("annual_withdrawal").toDouble(), c.retirement_age, c.end_age, 0.0, true});
+
+    c.equity = rsim_dist_from_json(o.value("equity").toObject(), c.equity);
+    c.bonds = rsim_dist_from_json(o.value("bonds").toObject(), c.bonds);
+    c.inflation = rsim_dist_from_json(o.value("inflation").toObject(), c.inflation);
+    c.equity_bond_correlation = o.value("equity_bond_correlation").toDouble(c.equity_bond_correlation);
+    c.inflation_persistence = o.value("inflation_persistence").toDouble(c.inflation_persistence);
+    for (const auto& v : o.value("glidepath").toArray()) {
+        const auto g = v.toObject();
+        c.glidepath.append({g.value("age").toInt(), g.value("equity_weight").toDouble(1.0)});
+    }
+    c.goal_target = o.value("goal_target").toDouble(c.goal_target);
+    c.goal_age = o.value("goal_age").toInt(c.goal_age);
+    c.annual_fee = o.value("annual_fee").toDouble(c.annual_fee);
+    c.paths = o.value("paths").toInt(c.paths);
+    if (o.contains("seed"))
+        c.seed = quint64(o.value("seed").toInteger());
+    return c;
+}
+
+QJsonObject RetirementSimulator::result_to_json(const RetirementSimResult& r, bool include_cone) {
+    if (!r.valid)
+        return QJsonObject{{"valid", false}, {"error", r.error}};
+    QJsonObject out{{"valid", true},
+                    {"paths", r.paths},
+                    {"success_probability", r.success_probability},
+                    {"goal_probability", r.goal_probability},
+                    {"median_terminal_nominal", r.median_terminal_nominal},
+                    {"median_terminal_real", r.median_terminal_real},
+                    {"mean_terminal_real", r.mean_terminal_real},
+                    {"depletion_age_p10", r.depletion_age_p10 > 0 ? QJsonValue(r.depletion_age_p10) : QJsonValue()},
+                    {"depletion_age_p50", r.depletion_age_p50 > 0 ? QJsonValue(r.depletion_age_p50) : QJsonValue()},
+                    {"elapsed_ms", std::round(r.elapsed_ms * 100.0) / 100.0}};
+    if (include_cone) {
+        QJsonArray cone;
+        for (const auto& pt : r.cone)
+            cone.append(QJsonObject{{"age", pt.age},
+                                    {"solvent", pt.solvent},
+                                    {"nominal", rsim_pct_to_json(pt.nominal)},
+                                    {"real", rsim_pct_to_json(pt.real)}});
+        out["cone"] = cone;
+    }
+    return out;
+}
+
+} // namespace fincept::services
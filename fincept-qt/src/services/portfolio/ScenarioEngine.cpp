@@ -0,0 +1,638 @@
+// src/services/portfolio/ScenarioEngine.cpp
+#include "services/portfolio/ScenarioEngine.h"
+
+#include "services/options/OptionPricing.h"
+
+#include <QJsonArray>
+#include <QRegularExpression>
+#include <QSet>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::services {
+
+namespace {
+
+namespace pricing = options::pricing;
+
+// Index and rate names the shock syntax accepts → Yahoo symbols.
+const QHash<QString, QString>& factor_aliases() {
+    static const QHash<QString, QString> map = {
+        {"SPX", "^GSPC"},       {"S&P", "^GSPC"},    {"S&P500", "^GSPC"}, {"SP500", "^GSPC"},
+        {"NDX", "^NDX"},        {"NASDAQ", "^IXIC"}, {"DOW", "^DJI"},     {"DJI", "^DJI"},
+        {"RUT", "^RUT"},        {"RUSSELL", "^RUT"}, {"NIFTY", "^NSEI"},  {"BANKNIFTY", "^NSEBANK"},
+        {"SENSEX", "^BSESN"},   {"FTSE", "^FTSE"},   {"DAX", "^GDAXI"},   {"CAC", "^FCHI"},
+        {"STOXX", "^STOXX50E"}, {"NIKKEI", "^N225"}, {"HSI", "^HSI"},     {"VIX", "^VIX"},
+    };
+    return map;
+}
+
+const QHash<QString, QString>& rates_aliases() {
+    static const QHash<QString, QString> map = {
+        {"RATES", "^TNX"}, {"RATE", "^TNX"},   {"YIELDS", "^TNX"}, {"YIELD", "^TNX"}, {"UST", "^TNX"},
+        {"10Y", "^TNX"},   {"UST10Y", "^TNX"}, {"^TNX", "^TNX"},   {"30Y", "^TYX"},   {"UST30Y", "^TYX"},
+        {"^TYX", "^TYX"},  {"5Y", "^FVX"},     {"UST5Y", "^FVX"},  {"^FVX", "^FVX"},  {"3M", "^IRX"},
+        {"UST3M", "^IRX"}, {"^IRX", "^IRX"},
+    };
+    return map;
+}
+
+// Kind and canonical symbol for a shock name.
+ScenarioShock::Kind classify(const QString& name, QString* symbol) {
+    QString n = name.trimmed().toUpper();
+    n.remove(' ');
+    if (n == "VOL" || n == "IV" || n == "IMPLIEDVOL") {
+        symbol->clear();
+        return ScenarioShock::Kind::Vol;
+    }
+    if (rates_aliases().contains(n)) {
+        *symbol = rates_aliases().value(n);
+        return ScenarioShock::Kind::Rates;
+    }
+    if (factor_aliases().contains(n)) {
+        *symbol = factor_aliases().value(n);
+        return ScenarioShock::Kind::Factor;
+    }
+    static const QRegularExpression kPair("^([A-Z]{3})/?([A-Z]{3})(=X)?$");
+    const auto m = kPair.match(n);
+    if (m.hasMatch()) {
+        *symbol = m.captured(1) + m.captured(2);
+        return ScenarioShock::Kind::Fx;
+    }
+    *symbol = n;
+    return n.startsWith('^') ? ScenarioShock::Kind::Factor : ScenarioShock::Kind::Ticker;
+}
+
+ScenarioShock::Kind kind_from_string(const QString& s, bool* ok) {
+    *ok = true;
+    const QString k = s.trimmed().toLower();
+    if (k == "factor" || k == "index")
+        return ScenarioShock::Kind::Factor;
+    if (k == "ticker" || k == "stock")
+        return ScenarioShock::Kind::Ticker;
+    if (k == "rates" || k == "rate")
+        return ScenarioShock::Kind::Rates;
+    if (k == "fx" || k == "currency")
+        return ScenarioShock::Kind::Fx;
+    if (k == "vol" || k == "volatility")
+        return ScenarioShock::Kind::Vol;
+    *ok = false;
+    return ScenarioShock::Kind::Factor;
+}
+
+QString default_label(const ScenarioShock& s) {
+    using K = ScenarioShock::Kind;
+    const QString sign = s.move >= 0 ? "+" : "";
+    switch (s.kind) {
+        case K::Rates:
+            return QString("%1 %2%3bp").arg(s.symbol, sign).arg(s.move);
+        case K::Vol:
+            return QString("vol %1%2").arg(sign).arg(s.move * 100);
+        default:
+            return QString("%1 %2%3%").arg(s.symbol, sign).arg(s.move * 100);
+    }
+}
+
+// Multiplier of each currency's USD value under `shocks` (Fx only): a pair
+// moves its quote currency, or its base currency when USD is the quote.
+QHash<QString, double> usd_multipliers(const QVector<ScenarioShock>& shocks) {
+    QHash<QString, double> mult;
+    for (const auto& s : shocks) {
+        if (s.kind != ScenarioShock::Kind::Fx || s.symbol.size() != 6 || s.move <= -1.0)
+            continue;
+        const QString base = s.symbol.left(3);
+        const QString quote = s.symbol.right(3);
+        if (quote == "USD")
+            mult[base] = mult.value(base, 1.0) * (1.0 + s.move);
+        else
+            mult[quote] = mult.value(quote, 1.0) / (1.0 + s.move);
+    }
+    return mult;
+}
+
+double translation(const QHash<QString, double>& mult, const QString& from, const QString& to) {
+    return mult.value(from, 1.0) / mult.value(to, 1.0) - 1.0;
+}
+
+double option_value(const ScenarioOption& o, double spot, double vol, double rate, double years) {
+    return o.is_call ? pricing::bsm_call(spot, o.strike, years, rate, vol, 0.0)
+                     : pricing::bsm_put(spot, o.strike, years, rate, vol, 0.0);
+}
+
+// Solves the p×p system a·x = b in place (partial pivoting); false when singular.
+bool solve(QVector<double>& a, QVector<double>& b, int p) {
+    for (int c = 0; c < p; ++c) {
+        int piv = c;
+        for (int r = c + 1; r < p; ++r)
+            if (std::abs(a[r * p + c]) > std::abs(a[piv * p + c]))
+                piv = r;
+        if (std::abs(a[piv * p + c]) < 1e-14)
+            return false;
+        if (piv != c) {
+            for (int k = 0; k < p; ++k)
+                std::swap(a[c * p + k], a[piv * p + k]);
+            std::swap(b[c], b[piv]);
+        }
+        for (int r = c + 1; r < p; ++r) {
+            const double f = a[r * p + c] / a[c * p + c];
+            for (int k = c; k < p; ++k)
+                a[r * p + k] -= f * a[c * p + k];
+            b[r] -= f * b[c];
+        }
+    }
+    for (int c = p - 1; c >= 0; --c) {
+        double v = b[c];
+        for (int k = c + 1; k < p; ++k)
+            v -= a[c * p + k] * b[k];
+        b[c] = v / a[c * p + c];
+    }
+    return true;
+}
+
+void add_to_bucket(QHash<QString, ScenarioBucket>& buckets, const QString& name, const ScenarioPositionResult& p) {
+    auto& b = buckets[name];
+    b.name = name;
+    ++b.positions;
+    b.market_value += p.market_value;
+    b.pnl += p.pnl;
+}
+
+QVector<ScenarioBucket> sorted_buckets(const QHash<QString, ScenarioBucket>& buckets) {
+    QVector<ScenarioBucket> out;
+    for (auto b : buckets) {
+        b.pnl_pct = b.market_value != 0 ? b.pnl / std::abs(b.market_value) * 100.0 : 0.0;
+        out.append(b);
+    }
+    std::sort(out.begin(), out.end(), [](const auto& a, const auto& b) { return a.pnl < b.pnl; });
+    return out;
+}
+
+QJsonArray buckets_to_json(const QVector<ScenarioBucket>& buckets) {
+    QJsonArray arr;
+    for (const auto& b : buckets)
+        arr.append(QJsonObject{{"name", b.name},
+                               {"positions", b.positions},
+                               {"market_value", b.market_value},
+                               {"pnl", b.pnl},
+                               {"pnl_pct", b.pnl_pct}});
+    return arr;
+}
+
+QJsonObject hash_to_json(const QHash<QString, double>& h) {
+    QJsonObject o;
+    for (auto it = h.constBegin(); it != h.constEnd(); ++it)
+        o.insert(it.key(), it.value());
+    return o;
+}
+
+} // namespace
+
+QString ScenarioShock::kind_to_string(Kind k) {
+    switch (k) {
+        case Kind::Factor:
+            return "factor";
+        case Kind::Ticker:
+            return "ticker";
+        case Kind::Rates:
+            return "rates";
+        case Kind::Fx:
+            return "fx";
+        case Kind::Vol:
+            return "vol";
+    }
+    return "factor";
+}
+
+// ── Parsing ─────────────────────────────────────────────────────────────────
+
+Result<ScenarioShock> ScenarioEngine::parse_shock(const QString& text) {
+    using R = Result<ScenarioShock>;
+    static const QRegularExpression kShock(R"(^\s*(.+?)\s*([+-]?)\s*(\d+(?:\.\d+)?)\s*(%|bps?|pts?|vol)?\s*$)",
+                                           QRegularExpression::CaseInsensitiveOption);
+    QString t = text;
+    t.replace(QChar(0x2212), '-'); // typographic minus
+    const auto m = kShock.match(t);
+    if (!m.hasMatch()) {
+        const QString msg = QString("Can't read shock '%1' — expected e.g. \"SPX -10%\", \"rates +100bp\"").arg(text);
+        return R::err(msg.toStdString());
+    }
+
+    ScenarioShock s;
+    s.kind = classify(m.captured(1), &s.symbol);
+    const double n = (m.captured(2) == "-" ? -1.0 : 1.0) * m.captured(3).toDouble();
+    const QString unit = m.captured(4).toLower();
+    switch (s.kind) {
+        case ScenarioShock::Kind::Rates:
+            s.move = unit == "%" ? n * 100.0 : n;
+            break;
+        case ScenarioShock::Kind::Vol:
+            s.move = n / 100.0;
+            break;
+        default:
+            s.move = unit.startsWith("bp") ? n / 10000.0 : n / 100.0;
+            break;
+    }
+    if (s.kind != ScenarioShock::Kind::Rates && s.kind != ScenarioShock::Kind::Vol && s.move <= -1.0)
+        return R::err(QString("'%1' would take a price to zero or below").arg(text).toStdString());
+    s.label = text.trimmed();
+    return R::ok(s);
+}
+
+Result<ScenarioSpec> ScenarioEngine::spec_from_json(const QJsonObject& o) {
+    using R = Result<ScenarioSpec>;
+    ScenarioSpec spec;
+    for (const auto& v : o["shocks"].toArray()) {
+        if (v.isString()) {
+            auto s = parse_shock(v.toString());
+            if (s.is_err())
+                return R::err(s.error());
+            spec.shocks.append(s.value());
+            continue;
+        }
+        const QJsonObject so = v.toObject();
+        ScenarioShock s;
+        QString symbol;
+        const auto inferred = classify(so["symbol"].toString(), &symbol);
+        if (so.contains("type")) {
+            bool ok = false;
+            s.kind = kind_from_string(so["type"].toString(), &ok);
+            if (!ok)
+                return R::err("Shock type must be factor, ticker, rates, fx or vol");
+        } else {
+            s.kind = inferred;
+        }
+        s.symbol = s.kind == ScenarioShock::Kind::Vol ? QString() : symbol;
+        if (s.kind == ScenarioShock::Kind::Rates && s.symbol.isEmpty())
+            s.symbol = "^TNX";
+        if (s.symbol.isEmpty() && s.kind != ScenarioShock::Kind::Vol)
+            return R::err("Every factor, ticker and fx shock needs a symbol");
+        if (s.kind == ScenarioShock::Kind::Fx && s.symbol.size() != 6)
+            return R::err(QString("'%1' is not a currency pair like USDINR").arg(s.symbol).toStdString());
+        if (s.kind == ScenarioShock::Kind::Rates)
+            s.move = so.contains("move_bp") ? so["move_bp"].toDouble() : so["move_pct"].toDouble() * 100.0;
+        else if (s.kind == ScenarioShock::Kind::Vol)
+            s.move = so["move_pts"].toDouble() / 100.0;
+        else
+            s.move = so["move_pct"].toDouble() / 100.0;
+        if (s.kind != ScenarioShock::Kind::Rates && s.kind != ScenarioShock::Kind::Vol && s.move <= -1.0)
+            return R::err(QString("A %1% move on %2 would take its price to zero or below")
+                              .arg(s.move * 100)
+                              .arg(s.symbol)
+                              .toStdString());
+        s.label = so["label"].toString().trimmed();
+        if (s.label.isEmpty())
+            s.label = default_label(s);
+        spec.shocks.append(s);
+    }
+    if (spec.shocks.isEmpty())
+        return R::err("At least one shock is required, e.g. \"SPX -10%\"");
+
+    QSet<QString> labels;
+    for (const auto& s : spec.shocks) {
+        if (labels.contains(s.label))
+            return R::err(QString("Shock '%1' is listed twice").arg(s.label).toStdString());
+        labels.insert(s.label);
+    }
+
+    spec.lookback_days = std::clamp(o["lookback_days"].toInt(365), 60, 3650);
+    spec.horizon_days = std::clamp(o["horizon_days"].toInt(0), 0, 365);
+    spec.risk_free = o["risk_free_rate"].toDouble(0.04);
+
+    const QJsonObject betas = o["betas"].toObject();
+    for (auto it = betas.constBegin(); it != betas.constEnd(); ++it) {
+        const QJsonObject per = it.value().toObject();
+        QHash<QString, double> m;
+        for (auto f = per.constBegin(); f != per.constEnd(); ++f) {
+            QString factor;
+            classify(f.key(), &factor);
+            m.insert(factor, f.value().toDouble());
+        }
+        spec.betas.insert(it.key().trimmed().toUpper(), m);
+    }
+    const QJsonObject durations = o["durations"].toObject();
+    for (auto it = durations.constBegin(); it != durations.constEnd(); ++it)
+        spec.durations.insert(it.key().trimmed().toUpper(), it.value().toDouble());
+    return R::ok(spec);
+}
+
+bool ScenarioEngine::parse_option(const QString& symbol, ScenarioOption* out) {
+    static const QRegularExpression kOcc(R"(^([A-Z][A-Z.]{0,5})(\d{2})(\d{2})(\d{2})([CP])(\d{8})$)");
+    const auto m = kOcc.match(symbol.trimmed().toUpper());
+    if (!m.hasMatch())
+        return false;
+    const QDate expiry(2000 + m.captured(2).toInt(), m.captured(3).toInt(), m.captured(4).toInt());
+    if (!expiry.isValid())
+        return false;
+    if (out) {
+        out->underlying = m.captured(1);
+        out->expiry = expiry;
+        out->is_call = m.captured(5) == "C";
+        out->strike = m.captured(6).toDouble() / 1000.0;
+    }
+    return true;
+}
+
+// ── Sensitivities ───────────────────────────────────────────────────────────
+
+ScenarioSensitivity ScenarioEngine::regress(const QVector<double>& y, const QVector<QVector<double>>& x) {
+    ScenarioSensitivity out;
+    const int n = int(y.size());
+    const int k = x.isEmpty() ? 0 : int(x.first().size());
+    const int p = k + 1;
+    if (k == 0 || n != x.size() || n < std::max(kMinObservations, p + 5))
+        return out;
+
+    QVector<double> xtx(p * p, 0.0);
+    QVector<double> xty(p, 0.0);
+    for (int t = 0; t < n; ++t) {
+        QVector<double> row(p);
+        row[0] = 1.0;
+        for (int j = 0; j < k; ++j)
+            row[j + 1] = x[t][j];
+        for (int a = 0; a < p; ++a) {
+            xty[a] += row[a] * y[t];
+            for (int b = 0; b < p; ++b)
+                xtx[a * p + b] += row[a] * row[b];
+        }
+    }
+    if (!solve(xtx, xty, p))
+        return out;
+
+    double mean = 0.0;
+    for (double v : y)
+        mean += v;
+    mean /= n;
+    double ss_res = 0.0;
+    double ss_tot = 0.0;
+    for (int t = 0; t < n; ++t) {
+        double fit = xty[0];
+        for (int j = 0; j < k; ++j)
+            fit += xty[j + 1] * x[t][j];
+        ss_res += (y[t] - fit) * (y[t] - fit);
+        ss_tot += (y[t] - mean) * (y[t] - mean);
+    }
+    out.betas = xty.mid(1);
+    out.r_squared = ss_tot > 0 ? std::max(0.0, 1.0 - ss_res / ss_tot) : 0.0;
+    out.observations = n;
+    return out;
+}
+
+// ── Run ─────────────────────────────────────────────────────────────────────
+
+ScenarioResult ScenarioEngine::run(const QVector<ScenarioPosition>& positions, const ScenarioSpec& spec,
+                                   const ScenarioMarket& market) {
+    using K = ScenarioShock::Kind;
+    ScenarioResult r;
+    r.base_currency = market.base_currency.toUpper();
+    r.shocks = spec.shocks;
+    r.factors = market.factors;
+    r.warnings = market.warnings;
+    if (positions.isEmpty()) {
+        r.error = "The portfolio has no holdings";
+        return r;
+    }
+    if (spec.shocks.isEmpty()) {
+        r.error = "No shocks to apply";
+        return r;
+    }
+
+    QSet<QString> rates_factors;
+    double rates_bp = 0.0;
+    double vol_move = 0.0;
+    for (const auto& s : spec.shocks) {
+        if (s.kind == K::Rates) {
+            rates_factors.insert(s.symbol);
+            rates_bp += s.move;
+        } else if (s.kind == K::Vol) {
+            vol_move += s.move;
+        }
+    }
+    const auto fx_all = usd_multipliers(spec.shocks);
+    for (const auto& s : market.by_symbol)
+        r.observations = std::max(r.observations, s.observations);
+
+    const QDate today = QDate::currentDate();
+    QSet<QString> warned;
+    QHash<QString, ScenarioBucket> sectors, currencies, classes;
+
+    for (const auto& pos : positions) {
+        ScenarioPositionResult p;
+        p.symbol = pos.symbol.toUpper();
+        p.market_value = pos.market_value;
+        p.currency = pos.currency.isEmpty() ? r.base_currency : pos.currency.toUpper();
+        p.sector = pos.sector.isEmpty() ? QStringLiteral("Unclassified") : pos.sector;
+        ScenarioOption opt;
+        const bool is_option = parse_option(p.symbol, &opt);
+        p.asset_class = is_option ? "Option" : "Equity";
+        p.risk_symbol = is_option ? opt.underlying : p.symbol;
+
+        // Betas: overrides, then the regression, else none.
+        const auto sens = market.by_symbol.value(p.risk_symbol);
+        const auto overrides = spec.betas.value(p.risk_symbol);
+        p.estimated = !sens.betas.isEmpty();
+        p.r_squared = sens.r_squared;
+        p.betas.fill(0.0, market.factors.size());
+        bool any = false;
+        for (int j = 0; j < market.factors.size(); ++j) {
+            const QString& f = market.factors[j];
+            if (rates_factors.contains(f) && spec.durations.contains(p.risk_symbol)) {
+                p.betas[j] = -spec.durations.value(p.risk_symbol) / 10000.0;
+                any = true;
+            } else if (overrides.contains(f)) {
+                p.betas[j] = overrides.value(f);
+                any = true;
+            } else if (p.estimated && j < sens.betas.size()) {
+                p.betas[j] = sens.betas[j];
+                any = true;
+            } else if (f == p.risk_symbol) {
+                p.betas[j] = 1.0; // a shocked index held directly
+                any = true;
+            }
+            if (rates_factors.contains(f))
+                p.duration = -p.betas[j] * 10000.0;
+        }
+        if (!any && !market.factors.isEmpty() && !warned.contains(p.risk_symbol)) {
+            warned.insert(p.risk_symbol);
+            r.warnings << QString("%1: not enough aligned history for betas — index and rate shocks not applied")
+                              .arg(p.risk_symbol);
+        }
+
+        // Local move of the risk symbol, split by shock.
+        QHash<QString, double> move_by;
+        double s = 0.0;
+        bool ticker_shocked = false;
+        for (const auto& sh : spec.shocks)
+            if (sh.kind == K::Ticker && (sh.symbol == p.risk_symbol || sh.symbol == p.symbol)) {
+                s = sh.move;
+                move_by = {{sh.label, sh.move}};
+                ticker_shocked = true;
+            }
+        if (!ticker_shocked) {
+            for (const auto& sh : spec.shocks) {
+                if (sh.kind != K::Factor && sh.kind != K::Rates)
+                    continue;
+                const int j = int(market.factors.indexOf(sh.symbol));
+                if (j < 0)
+                    continue;
+                const double c = p.betas[j] * sh.move;
+                s += c;
+                move_by[sh.label] += c;
+            }
+        }
+        s = std::max(s, -1.0);
+
+        const double mv = pos.market_value;
+        p.fx_move = translation(fx_all, p.currency, r.base_currency);
+
+        const double spot = market.spots.value(opt.underlying);
+        if (is_option && pos.price > 0 && spot > 0) {
+            const double t = std::max(0.0, today.daysTo(opt.expiry) / 365.0);
+            double iv = pricing::bsm_implied_vol(pos.price, spot, opt.strike, t, spec.risk_free, 0.0, opt.is_call);
+            if (!(iv > 0)) {
+                iv = kDefaultVol;
+                r.warnings << QString("%1: no implied vol from its price — repriced at %2%")
+                                  .arg(p.symbol)
+                                  .arg(kDefaultVol * 100);
+            }
+            p.implied_vol = iv;
+            p.delta = pricing::bsm_delta(spot, opt.strike, t, spec.risk_free, iv, 0.0, opt.is_call);
+            p.gamma = pricing::bsm_gamma(spot, opt.strike, t, spec.risk_free, iv, 0.0);
+            p.vega = pricing::bsm_vega(spot, opt.strike, t, spec.risk_free, iv, 0.0) / 100.0;
+            p.theta = pricing::bsm_theta(spot, opt.strike, t, spec.risk_free, iv, 0.0, opt.is_call);
+
+            // Reprice one shock family at a time so each gets its share.
+            const double s1 = spot * (1.0 + s);
+            const double iv1 = std::max(iv + vol_move, 0.0001);
+            const double r1 = spec.risk_free + rates_bp / 10000.0;
+            const double t1 = std::max(0.0, t - spec.horizon_days / 365.0);
+            const double v0 = option_value(opt, spot, iv, spec.risk_free, t);
+            const double va = option_value(opt, s1, iv, spec.risk_free, t);
+            const double vb = option_value(opt, s1, iv1, spec.risk_free, t);
+            const double vc = option_value(opt, s1, iv1, r1, t);
+            const double vd = option_value(opt, s1, iv1, r1, t1);
+            if (v0 > 0) {
+                const double units = mv / v0;
+                p.local_move = vd / v0 - 1.0;
+                for (auto it = move_by.constBegin(); it != move_by.constEnd(); ++it)
+                    if (s != 0)
+                        p.by_shock[it.key()] += (va - v0) * units * it.value() / s;
+                for (const auto& sh : spec.shocks) {
+                    if (sh.kind == K::Vol && vol_move != 0)
+                        p.by_shock[sh.label] += (vb - va) * units * sh.move / vol_move;
+                    if (sh.kind == K::Rates && rates_bp != 0)
+                        p.by_shock[sh.label] += (vc - vb) * units * sh.move / rates_bp;
+                }
+                if (spec.horizon_days > 0)
+                    p.by_shock["time"] = (vd - vc) * units;
+            }
+        } else {
+            if (is_option && !warned.contains(p.symbol)) {
+                warned.insert(p.symbol);
+                r.warnings << QString("%1: no price for %2 — treated as a linear position")
+                                  .arg(p.symbol, opt.underlying);
+            }
+            p.local_move = s;
+            for (auto it = move_by.constBegin(); it != move_by.constEnd(); ++it)
+                p.by_shock[it.key()] += mv * it.value();
+        }
+
+        for (const auto& sh : spec.shocks)
+            if (sh.kind == K::Fx) {
+                const double f = translation(usd_multipliers({sh}), p.currency, r.base_currency);
+                if (f != 0)
+                    p.by_shock[sh.label] += mv * f;
+            }
+
+        p.pnl = mv * ((1.0 + p.local_move) * (1.0 + p.fx_move) - 1.0);
+        p.pnl_pct = mv != 0 ? p.pnl / std::abs(mv) * 100.0 : 0.0;
+        double explained = 0.0;
+        for (double v : p.by_shock)
+            explained += v;
+        if (std::abs(p.pnl - explained) > 1e-9 * std::max(1.0, std::abs(mv)))
+            p.by_shock["interaction"] = p.pnl - explained;
+
+        r.total_value += mv;
+        r.total_pnl += p.pnl;
+        for (auto it = p.by_shock.constBegin(); it != p.by_shock.constEnd(); ++it)
+            r.by_shock[it.key()] += it.value();
+        add_to_bucket(sectors, p.sector, p);
+        add_to_bucket(currencies, p.currency, p);
+        add_to_bucket(classes, p.asset_class, p);
+        r.positions.append(p);
+    }
+
+    std::sort(r.positions.begin(), r.positions.end(), [](const auto& a, const auto& b) { return a.pnl < b.pnl; });
+    r.by_sector = sorted_buckets(sectors);
+    r.by_currency = sorted_buckets(currencies);
+    r.by_asset_class = sorted_buckets(classes);
+    r.pnl_pct = r.total_value != 0 ? r.total_pnl / std::abs(r.total_value) * 100.0 : 0.0;
+    r.ok = true;
+    return r;
+}
+
+QJsonObject ScenarioEngine::result_to_json(const ScenarioResult& r) {
+    if (!r.ok)
+        return QJsonObject{{"error", r.error}, {"warnings", QJsonArray::fromStringList(r.warnings)}};
+
+    QJsonArray shocks;
+    for (const auto& s : r.shocks) {
+        QJsonObject o{{"label", s.label}, {"type", ScenarioShock::kind_to_string(s.kind)}};
+        if (!s.symbol.isEmpty())
+            o["symbol"] = s.symbol;
+        if (s.kind == ScenarioShock::Kind::Rates)
+            o["move_bp"] = s.move;
+        else if (s.kind == ScenarioShock::Kind::Vol)
+            o["move_pts"] = s.move * 100.0;
+        else
+            o["move_pct"] = s.move * 100.0;
+        shocks.append(o);
+    }
+
+    QJsonArray positions;
+    for (const auto& p : r.positions) {
+        QJsonObject betas;
+        for (int j = 0; j < r.factors.size() && j < p.betas.size(); ++j)
+            betas.insert(r.factors[j], p.betas[j]);
+        QJsonObject o{{"symbol", p.symbol},
+                      {"asset_class", p.asset_class},
+                      {"sector", p.sector},
+                      {"currency", p.currency},
+                      {"market_value", p.market_value},
+                      {"local_move_pct", p.local_move * 100.0},
+                      {"fx_move_pct", p.fx_move * 100.0},
+                      {"pnl", p.pnl},
+                      {"pnl_pct", p.pnl_pct},
+                      {"by_shock", hash_to_json(p.by_shock)},
+                      {"betas", betas},
+                      {"betas_estimated", p.estimated},
+                      {"r_squared", p.r_squared}};
+        if (p.duration != 0)
+            o["duration"] = p.duration;
+        if (p.asset_class == "Option") {
+            o["underlying"] = p.risk_symbol;
+            o["implied_vol"] = p.implied_vol;
+            o["delta"] = p.delta;
+            o["gamma"] = p.gamma;
+            o["vega"] = p.vega;
+            o["theta"] = p.theta;
+        }
+        positions.append(o);
+    }
+
+    return QJsonObject{{"base_currency", r.base_currency},
+                       {"shocks", shocks},
+                       {"factors", QJsonArray::fromStringList(r.factors)},
+                       {"observations", r.observations},
+                       {"total_value", r.total_value},
+                       {"total_pnl", r.total_pnl},
+                       {"pnl_pct", r.pnl_pct},
+                       {"by_shock", hash_to_json(r.by_shock)},
+                       {"positions", positions},
+                       {"by_sector", buckets_to_json(r.by_sector)},
+                       {"by_currency", buckets_to_json(r.by_currency)},
+                       {"by_asset_class", buckets_to_json(r.by_asset_class)},
+                       {"warnings", QJsonArray::fromStringList(r.warnings)}};
+}
+
+} // namespace fincept::services
@@ -0,0 +1,161 @@
+// src/services/portfolio/ScenarioEngine.h
+#pragma once
+#include "core/result/Result.h"
+
+#include <QDate>
+#include <QHash>
+#include <QJsonObject>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+namespace fincept::services {
+
+/// One hypothetical move. `move` is a fraction for Factor / Ticker / Fx
+/// (-0.10 = -10%), basis points for Rates and implied-vol points as a
+/// fraction for Vol (0.05 = +5 vol).
+struct ScenarioShock {
+    enum class Kind { Factor, Ticker, Rates, Fx, Vol };
+    Kind kind = Kind::Factor;
+    QString symbol; // index (^GSPC), ticker, rates proxy (^TNX) or currency pair (USDINR); empty for Vol
+    double move = 0.0;
+    QString label; // how it is reported, e.g. "SPX -10%"
+
+    static QString kind_to_string(Kind k);
+};
+
+struct ScenarioSpec {
+    QVector<ScenarioShock> shocks;
+    int lookback_days = 365; // daily history the betas are estimated on
+    int horizon_days = 0;    // option time decay applied along with the shocks
+    double risk_free = 0.04; // option repricing
+    /// Overrides of the estimated sensitivities: symbol → factor → beta, and
+    /// symbol → modified duration in years (replaces the rates beta).
+    QHash<QString, QHash<QString, double>> betas;
+    QHash<QString, double> durations;
+};
+
+/// A holding as the engine sees it; market_value is in the portfolio currency.
+struct ScenarioPosition {
+    QString symbol;
+    double quantity = 0.0;
+    double price = 0.0; // native quote unit
+    double market_value = 0.0;
+    QString currency; // native listing currency
+    QString sector;
+};
+
+/// An option recognised from its OCC symbol (AAPL250117C00150000).
+struct ScenarioOption {
+    QString underlying;
+    QDate expiry;
+    bool is_call = true;
+    double strike = 0.0;
+};
+
+/// Daily-return regression of one symbol on the shocked factors.
+struct ScenarioSensitivity {
+    QVector<double> betas; // per ScenarioMarket::factors; rates betas are per basis point
+    double r_squared = 0.0;
+    int observations = 0;
+};
+
+/// What the engine needs besides the positions.
+struct ScenarioMarket {
+    QString base_currency;
+    QStringList factors;                           // Factor and Rates shock symbols, in shock order
+    QHash<QString, ScenarioSensitivity> by_symbol; // risk symbol (the underlying for options) → regression
+    QHash<QString, double> spots;                  // option underlying → last close
+    QStringList warnings;
+};
+
+struct ScenarioPositionResult {
+    QString symbol;
+    QString risk_symbol; // the underlying for options
+    QString asset_class; // Equity | Option
+    QString sector;
+    QString currency;
+    double market_value = 0.0;
+    double local_move = 0.0; // price move in the listing currency, fraction
+    double fx_move = 0.0;    // listing → portfolio currency translation, fraction
+    double pnl = 0.0;
+    double pnl_pct = 0.0;
+    QHash<QString, double> by_shock; // shock label, "time" or "interaction" → P&L
+    QVector<double> betas;           // per factor, as applied
+    double r_squared = 0.0;
+    bool estimated = false; // betas came from history rather than overrides / defaults
+    double duration = 0.0;  // modified duration implied by the rates beta, years
+    // Options only, per share at the current state.
+    double implied_vol = 0.0;
+    double delta = 0.0;
+    double gamma = 0.0;
+    double vega = 0.0;
+    double theta = 0.0;
+};
+
+struct ScenarioBucket {
+    QString name;
+    int positions = 0;
+    double market_value = 0.0;
+    double pnl = 0.0;
+    double pnl_pct = 0.0;
+};
+
+struct ScenarioResult {
+    bool ok = false;
+    QString error;
+    QString base_currency;
+    QVector<ScenarioShock> shocks;
+    QStringList factors;
+    int observations = 0;
+    double total_value = 0.0;
+    double total_pnl = 0.0;
+    double pnl_pct = 0.0;
+    QHash<QString, double> by_shock;
+    QVector<ScenarioPositionResult> positions; // worst first
+    QVector<ScenarioBucket> by_sector;
+    QVector<ScenarioBucket> by_currency;
+    QVector<ScenarioBucket> by_asset_class;
+    QStringList warnings;
+};
+
+/// Scenario P&L: applies hypothetical shocks to current holdings and
+/// reports the projected P&L by position, by bucket and by shock.
+///
+/// Index factors and rates move holdings through their betas — a joint
+/// least-squares regression of each holding's daily returns on the shocked
+/// index returns and daily rate changes in basis points, so a rates beta is
+/// an empirical duration. A ticker shock sets that holding's move outright.
+/// FX shocks translate holdings into the portfolio currency: a pair moves
+/// its quote currency against USD, or its base currency when USD is the
+/// quote. Options (OCC symbols) are repriced under Black-Scholes at the
+/// implied vol of their current price with the underlying's move, vol and
+/// rate shocks and the horizon's decay. Everything is instantaneous apart
+/// from that decay; no second-round effects.
+class ScenarioEngine {
+  public:
+    static ScenarioResult run(const QVector<ScenarioPosition>& positions, const ScenarioSpec& spec,
+                              const ScenarioMarket& market);
+
+    /// Least squares of `y` on `x` (rows = dates, columns = factors) with an
+    /// intercept. Empty betas when there are too few rows.
+    static ScenarioSensitivity regress(const QVector<double>& y, const QVector<QVector<double>>& x);
+
+    /// "SPX -10%", "rates +100bp", "USDINR +3%", "AAPL -20%", "vol +5". Index
+    /// names (SPX, NDX, NIFTY, VIX, ...) map to their Yahoo symbols; other
+    /// ^-symbols are factors too, six letters are a currency pair and
+    /// anything else is a ticker.
+    static Result<ScenarioShock> parse_shock(const QString& text);
+    /// {shocks: [string | {type, symbol, move_pct | move_bp | move_pts}], lookback_days,
+    /// horizon_days, risk_free_rate, betas{sym:{factor:beta}}, durations{sym:years}}.
+    static Result<ScenarioSpec> spec_from_json(const QJsonObject& o);
+    static QJsonObject result_to_json(const ScenarioResult& r);
+
+    /// Parses an OCC option symbol; false for anything else.
+    static bool parse_option(const QString& symbol, ScenarioOption* out);
+
+    static constexpr int kMinObservations = 30;
+    static constexpr double kDefaultVol = 0.30;
+};
+
+} // namespace fincept::services
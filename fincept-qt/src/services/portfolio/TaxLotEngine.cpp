@@ -0,0 +1,322 @@
+// src/services/portfolio/TaxLotEngine.cpp
+#include "services/portfolio/TaxLotEngine.h"
+
+#include <QDateTime>
+#include <QMap>
+#include <QSet>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::services {
+
+namespace {
+
+constexpr double kQtyEpsilon = 1e-9;
+
+// Lot under replay. `buy_date` drives FIFO order, eligibility and splits;
+// `lot.acquired` is the holding-period start (differs after wash-sale tacking).
+struct WorkLot {
+    portfolio::TaxLot lot;
+    QDate buy_date;
+    double remaining = 0;
+    double replacement_used = 0; // shares already absorbing a disallowed loss
+};
+
+QDate parse_txn_date(const QString& s) {
+    // transaction_date is stored as YYYY-MM-DD, occasionally with a time part.
+    return QDate::fromString(s.left(10), Qt::ISODate);
+}
+
+QString csv_amount(double v) {
+    return QString::number(v, 'f', 2);
+}
+
+} // namespace
+
+// ── Rules ────────────────────────────────────────────────────────────────────
+
+double TaxRules::adjusted_cost(const QString&, const QDate&, const QDate&, double cost_per_share, double,
+                               const QHash<QString, double>&, bool* grandfathered) const {
+    if (grandfathered)
+        *grandfathered = false;
+    return cost_per_share;
+}
+
+std::unique_ptr<TaxRules> TaxRules::for_jurisdiction(const QString& code) {
+    const QString c = code.trimmed().toUpper();
+    if (c == "US" || c == "USA")
+        return std::make_unique<UsTaxRules>();
+    if (c == "IN" || c == "IND" || c == "INDIA")
+        return std::make_unique<IndiaTaxRules>();
+    return nullptr;
+}
+
+QStringList TaxRules::supported() {
+    return {"US", "IN"};
+}
+
+bool UsTaxRules::is_long_term(const QDate& acquired, const QDate& disposed) const {
+    // "More than one year": a lot bought 2024-03-15 is long-term from 2025-03-16.
+    return disposed > acquired.addYears(1);
+}
+
+QString UsTaxRules::tax_year(const QDate& disposed) const {
+    return QString::number(disposed.year());
+}
+
+bool IndiaTaxRules::is_long_term(const QDate& acquired, const QDate& disposed) const {
+    return disposed > acquired.addMonths(12);
+}
+
+QString IndiaTaxRules::tax_year(const QDate& disposed) const {
+    const int start = disposed.month() >= 4 ? disposed.year() : disposed.year() - 1;
+    return QString("FY%1-%2").arg(start).arg((start + 1) % 100, 2, 10, QChar('0'));
+}
+
+double IndiaTaxRules::adjusted_cost(const QString& symbol, const QDate& acquired, const QDate& disposed,
+                                    double cost_per_share, double sale_price, const QHash<QString, double>& fmv,
+                                    bool* grandfathered) const {
+    if (grandfathered)
+        *grandfathered = false;
+    // Section 112A: only long-term transfers on/after 1-Apr-2018 of shares
+    // acquired before 1-Feb-2018.
+    if (acquired >= QDate(2018, 2, 1) || disposed < QDate(2018, 4, 1) || !is_long_term(acquired, disposed))
+        return cost_per_share;
+    const auto it = fmv.constFind(symbol);
+    if (it == fmv.constEnd() || *it <= 0)
+        return cost_per_share;
+    const double deemed = std::max(cost_per_share, std::min(*it, sale_price));
+    if (grandfathered)
+        *grandfathered = deemed > cost_per_share;
+    return deemed;
+}
+
+// ── Engine ───────────────────────────────────────────────────────────────────
+
+portfolio::TaxReport TaxLotEngine::compute(const QVector<portfolio::Transaction>& transactions, const TaxRules& rules,
+                                           const QHash<QString, double>& fmv, const QString& tax_year) {
+    portfolio::TaxReport report;
+    report.jurisdiction = rules.id();
+    report.generated_at = QDateTime::currentDateTimeUtc().toString(Qt::ISODate);
+
+    QVector<portfolio::Transaction> txns;
+    for (const auto& t : transactions) {
+        const QString type = t.transaction_type.toUpper();
+        if (type != "BUY" && type != "SELL" && type != "SPLIT")
+            continue;
+        if (!parse_txn_date(t.transaction_date).isValid()) {
+            report.warnings << QString("Skipped %1 %2 with unreadable date '%3'")
+                                   .arg(type, t.symbol, t.transaction_date);
+            continue;
+        }
+        txns.append(t);
+    }
+    std::stable_sort(txns.begin(), txns.end(), [](const auto& a, const auto& b) {
+        const QString da = a.transaction_date.left(10), db = b.transaction_date.left(10);
+        return da != db ? da < db : a.created_at < b.created_at;
+    });
+
+    // Every BUY becomes a lot up front so a sale can see replacement shares
+    // bought after it (the forward half of the wash-sale window).
+    QHash<QString, QVector<WorkLot>> lots;
+    for (const auto& t : txns) {
+        if (t.transaction_type.toUpper() != "BUY" || t.quantity <= 0)
+            continue;
+        WorkLot w;
+        w.buy_date = parse_txn_date(t.transaction_date);
+        w.remaining = t.quantity;
+        w.lot.symbol = t.symbol;
+        w.lot.acquired = w.buy_date.toString(Qt::ISODate);
+        w.lot.quantity = t.quantity;
+        w.lot.cost_per_share = t.price;
+        w.lot.source_txn_id = t.id;
+        lots[t.symbol].append(w);
+    }
+
+    const QDate fmv_date = rules.required_fmv_date();
+    const int wash_window = rules.wash_sale_window_days();
+    QSet<QString> missing_fmv;
+    QVector<portfolio::RealizedGain> realized;
+
+    for (const auto& t : txns) {
+        const QString type = t.transaction_type.toUpper();
+        const QDate date = parse_txn_date(t.transaction_date);
+        auto& book = lots[t.symbol];
+
+        if (type == "SPLIT") {
+            if (t.quantity <= 0) {
+                report.warnings << QString("Ignored SPLIT for %1 on %2: ratio must be > 0")
+                                       .arg(t.symbol, t.transaction_date.left(10));
+                continue;
+            }
+            for (auto& w : book) {
+                if (w.buy_date >= date)
+                    continue;
+                w.remaining *= t.quantity;
+                w.replacement_used *= t.quantity;
+                w.lot.quantity *= t.quantity;
+                w.lot.cost_per_share /= t.quantity;
+            }
+            continue;
+        }
+        if (type != "SELL")
+            continue;
+
+        double to_sell = t.quantity;
+        QSet<int> consumed;
+        for (int i = 0; i < book.size() && to_sell > kQtyEpsilon; ++i) {
+            auto& w = book[i];
+            if (w.buy_date > date || w.remaining <= kQtyEpsilon)
+                continue;
+            const double qty = std::min(w.remaining, to_sell);
+            w.remaining -= qty;
+            w.replacement_used = std::min(w.replacement_used, w.remaining);
+            to_sell -= qty;
+            consumed.insert(i);
+
+            const QDate acquired = QDate::fromString(w.lot.acquired, Qt::ISODate);
+            portfolio::RealizedGain g;
+            g.symbol = t.symbol;
+            g.acquired = w.lot.acquired;
+            g.disposed = date.toString(Qt::ISODate);
+            g.quantity = qty;
+            g.proceeds = qty * t.price;
+            g.long_term = rules.is_long_term(acquired, date);
+            g.tax_year = rules.tax_year(date);
+            g.sell_txn_id = t.id;
+            const double basis_ps = rules.adjusted_cost(t.symbol, acquired, date, w.lot.cost_per_share, t.price, fmv,
+                                                        &g.grandfathered);
+            if (fmv_date.isValid() && acquired <= fmv_date && g.long_term && !fmv.contains(t.symbol))
+                missing_fmv.insert(t.symbol);
+            g.cost_basis = qty * basis_ps;
+            g.gain = g.proceeds - g.cost_basis;
+
+            // ── Wash sale: defer the loss into replacement shares ────────────
+            if (wash_window > 0 && g.gain < 0) {
+                const double loss_ps = -g.gain / qty;
+                double unmatched = qty;
+                for (int j = 0; j < book.size() && unmatched > kQtyEpsilon; ++j) {
+                    if (consumed.contains(j))
+                        continue;
+                    auto& r = book[j];
+                    if (std::abs(r.buy_date.daysTo(date)) > wash_window)
+                        continue;
+                    const double capacity = r.remaining - r.replacement_used;
+                    if (capacity <= kQtyEpsilon)
+                        continue;
+                    const double k = std::min(capacity, unmatched);
+                    // Carve the replacement shares into their own lot so only
+                    // they pick up the extra basis and the tacked holding period.
+                    if (r.remaining - k > kQtyEpsilon) {
+                        WorkLot rest = r;
+                        rest.remaining = r.remaining - k;
+                        rest.lot.quantity = rest.remaining;
+                        rest.replacement_used = r.replacement_used;
+                        r.remaining = k;
+                        r.lot.quantity = k;
+                        r.replacement_used = 0;
+                        book.insert(j + 1, rest);
+                        if (j + 1 <= i)
+                            ++i;
+                        QSet<int> shifted;
+                        for (int c : consumed)
+                            shifted.insert(c > j ? c + 1 : c);
+                        consumed = shifted;
+                    }
+                    auto& rl = book[j];
+                    rl.lot.cost_per_share += loss_ps;
+                    rl.replacement_used = rl.remaining;
+                    const qint64 held = acquired.daysTo(date);
+                    const QDate tacked = rl.buy_date.addDays(-held);
+                    if (QDate::fromString(rl.lot.acquired, Qt::ISODate) > tacked)
+                        rl.lot.acquired = tacked.toString(Qt::ISODate);
+                    g.adjustment += loss_ps * k;
+                    unmatched -= k;
+                }
+                if (g.adjustment > 0) {
+                    g.wash_sale = true;
+                    g.gain += g.adjustment;
+                }
+            }
+            realized.append(g);
+        }
+        if (to_sell > kQtyEpsilon) {
+            report.warnings << QString("SELL %1 %2 on %3 exceeds held lots by %4 shares; excess ignored")
+                                   .arg(t.quantity)
+                                   .arg(t.symbol, date.toString(Qt::ISODate))
+                                   .arg(to_sell);
+        }
+    }
+
+    for (const auto& sym : missing_fmv) {
+        report.warnings << QString("No %1 reference price for %2; grandfathering not applied")
+                               .arg(fmv_date.toString(Qt::ISODate), sym);
+    }
+
+    QMap<QString, portfolio::TaxYearSummary> years;
+    for (const auto& g : realized) {
+        if (!tax_year.isEmpty() && g.tax_year != tax_year)
+            continue;
+        report.realized.append(g);
+        auto& y = years[g.tax_year];
+        y.tax_year = g.tax_year;
+        (g.long_term ? y.long_term_gain : y.short_term_gain) += g.gain;
+        y.proceeds += g.proceeds;
+        y.cost_basis += g.cost_basis;
+        y.disallowed_loss += g.adjustment;
+        ++y.dispositions;
+    }
+    report.years = years.values();
+
+    for (auto it = lots.constBegin(); it != lots.constEnd(); ++it) {
+        for (const auto& w : it.value()) {
+            if (w.remaining <= kQtyEpsilon)
+                continue;
+            portfolio::TaxLot open = w.lot;
+            open.quantity = w.remaining;
+            report.open_lots.append(open);
+        }
+    }
+    std::sort(report.open_lots.begin(), report.open_lots.end(), [](const auto& a, const auto& b) {
+        return a.symbol != b.symbol ? a.symbol < b.symbol : a.acquired < b.acquired;
+    });
+    return report;
+}
+
+// ── Form 8949 export ─────────────────────────────────────────────────────────
+
+QString TaxLotEngine::to_form_8949_csv(const portfolio::TaxReport& report) {
+    QStringList lines;
+    lines << "Part,(a) Description,(b) Date acquired,(c) Date sold,(d) Proceeds,(e) Cost basis,(f) Code,"
+             "(g) Adjustment,(h) Gain or loss";
+    auto us_date = [](const QString& iso) { return QDate::fromString(iso, Qt::ISODate).toString("MM/dd/yyyy"); };
+
+    for (const bool long_term : {false, true}) {
+        const QString part = long_term ? "II" : "I";
+        double proceeds = 0, basis = 0, adj = 0, gain = 0;
+        for (const auto& g : report.realized) {
+            if (g.long_term != long_term)
+                continue;
+            lines << QStringList{part,
+                                 QString("%1 sh %2").arg(g.quantity).arg(g.symbol),
+                                 us_date(g.acquired),
+                                 us_date(g.disposed),
+                                 csv_amount(g.proceeds),
+                                 csv_amount(g.cost_basis),
+                                 g.wash_sale ? "W" : "",
+                                 g.adjustment > 0 ? csv_amount(g.adjustment) : "",
+                                 csv_amount(g.gain)}
+                         .join(',');
+            proceeds += g.proceeds;
+            basis += g.cost_basis;
+            adj += g.adjustment;
+            gain += g.gain;
+        }
+        lines << QStringList{part, "Totals", "", "", csv_amount(proceeds), csv_amount(basis), "", csv_amount(adj), csv_amount(gain)}.join(
+            ',');
+    }
+    return lines.join('\n') + '\n';
+}
+
+} // namespace fincept::services
@@ -0,0 +1,81 @@
+// src/services/portfolio/TaxLotEngine.h
+#pragma once
+#include "screens/portfolio/PortfolioTypes.h"
+
+#include <QDate>
+#include <QHash>
+
+#include <memory>
+
+namespace fincept::services {
+
+/// Jurisdiction-specific capital-gains rules plugged into TaxLotEngine.
+/// Lot matching itself (FIFO) is shared; rules decide holding-period
+/// classification, the tax-year bucket, basis adjustments and whether
+/// wash-sale deferral applies.
+class TaxRules {
+  public:
+    virtual ~TaxRules() = default;
+
+    virtual QString id() const = 0;
+    virtual bool is_long_term(const QDate& acquired, const QDate& disposed) const = 0;
+    virtual QString tax_year(const QDate& disposed) const = 0;
+
+    /// Wash-sale window in days either side of a loss sale; 0 disables it.
+    virtual int wash_sale_window_days() const { return 0; }
+
+    /// Cost basis per share actually used for the gain. Default: the lot cost.
+    /// `fmv` holds per-symbol reference prices the rules asked for via
+    /// required_fmv_date() (e.g. India's 31-Jan-2018 grandfathering price).
+    virtual double adjusted_cost(const QString& symbol, const QDate& acquired, const QDate& disposed,
+                                 double cost_per_share, double sale_price, const QHash<QString, double>& fmv,
+                                 bool* grandfathered) const;
+
+    /// Date whose closing price the rules need per symbol, or an invalid date.
+    virtual QDate required_fmv_date() const { return {}; }
+
+    /// "US" / "IN" (case-insensitive); nullptr for an unknown jurisdiction.
+    static std::unique_ptr<TaxRules> for_jurisdiction(const QString& code);
+    static QStringList supported();
+};
+
+/// US: long-term after more than one year, calendar tax year, 30-day
+/// wash-sale rule (disallowed loss carried into the replacement lot).
+class UsTaxRules : public TaxRules {
+  public:
+    QString id() const override { return "US"; }
+    bool is_long_term(const QDate& acquired, const QDate& disposed) const override;
+    QString tax_year(const QDate& disposed) const override;
+    int wash_sale_window_days() const override { return 30; }
+};
+
+/// India (listed equity, STT paid): long-term after 12 months, April–March
+/// financial year, and Section 112A grandfathering — for shares bought before
+/// 1-Feb-2018, cost is max(actual cost, min(FMV on 31-Jan-2018, sale price)).
+class IndiaTaxRules : public TaxRules {
+  public:
+    QString id() const override { return "IN"; }
+    bool is_long_term(const QDate& acquired, const QDate& disposed) const override;
+    QString tax_year(const QDate& disposed) const override;
+    double adjusted_cost(const QString& symbol, const QDate& acquired, const QDate& disposed, double cost_per_share,
+                         double sale_price, const QHash<QString, double>& fmv, bool* grandfathered) const override;
+    QDate required_fmv_date() const override { return QDate(2018, 1, 31); }
+};
+
+/// Pure FIFO lot engine over the portfolio transaction ledger.
+class TaxLotEngine {
+  public:
+    /// Replays BUY/SELL/SPLIT transactions (any order) and returns realized
+    /// gains, per-tax-year totals and the remaining open lots. SPLIT rows
+    /// carry the ratio in `quantity` (2 = 2-for-1). `tax_year` filters
+    /// `realized` and `years` when non-empty; lots are always matched over
+    /// the full history.
+    static portfolio::TaxReport compute(const QVector<portfolio::Transaction>& transactions, const TaxRules& rules,
+                                        const QHash<QString, double>& fmv = {}, const QString& tax_year = {});
+
+    /// IRS Form 8949-style CSV (Part I short-term, Part II long-term; code W
+    /// with the adjustment in column (g) for wash sales).
+    static QString to_form_8949_csv(const portfolio::TaxReport& report);
+};
+
+} // namespace fincept::services
@@ -27,6 +27,7 @@ static double kalshi_fp_to_double(const QJsonValue& v) {
 
 KalshiWsClient::KalshiWsClient(QObject* parent) : QObject(parent) {
     ws_ = new fincept::WebSocketClient(this);
+    ws_->set_label("kalshi");
     connect(ws_, &fincept::WebSocketClient::connected, this, &KalshiWsClient::on_connected);
     connect(ws_, &fincept::WebSocketClient::disconnected, this, &KalshiWsClient::on_disconnected);
     connect(ws_, &fincept::WebSocketClient::message_received, this, &KalshiWsClient::on_message);
@@ -0,0 +1,345 @@
+// src/services/screener/ScreenFilter.cpp
+#include "services/screener/ScreenFilter.h"
+
+#include <QHash>
+#include <QJsonArray>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::services {
+
+namespace {
+
+constexpr int kMaxDepth = 16;
+
+const QStringList kCompareOps = {"<", "<=", ">", ">=", "==", "!="};
+const QStringList kListOps = {"in", "not_in"};
+const QStringList kPresenceOps = {"exists", "missing"};
+
+bool is_leaf(const QJsonObject& node) {
+    return node.contains("field");
+}
+
+QString value_text(const QJsonValue& v) {
+    if (v.isString())
+        return v.toString();
+    if (v.isDouble())
+        return QString::number(v.toDouble(), 'g', 10);
+    if (v.isBool())
+        return v.toBool() ? "true" : "false";
+    return {};
+}
+
+bool compare_numbers(const QString& op, double a, double b) {
+    if (op == "<")
+        return a < b;
+    if (op == "<=")
+        return a <= b;
+    if (op == ">")
+        return a > b;
+    if (op == ">=")
+        return a >= b;
+    if (op == "==")
+        return std::abs(a - b) <= 1e-9 * std::max(1.0, std::abs(b));
+    return std::abs(a - b) > 1e-9 * std::max(1.0, std::abs(b)); // !=
+}
+
+bool compare_text(const QString& op, const QString& a, const QString& b) {
+    const int c = a.compare(b, Qt::CaseInsensitive);
+    if (op == "<")
+        return c < 0;
+    if (op == "<=")
+        return c <= 0;
+    if (op == ">")
+        return c > 0;
+    if (op == ">=")
+        return c >= 0;
+    if (op == "==")
+        return c == 0;
+    return c != 0;
+}
+
+bool list_contains(const QJsonArray& list, const QJsonValue& v) {
+    for (const auto& e : list) {
+        if (v.isString() ? e.toString().compare(v.toString(), Qt::CaseInsensitive) == 0
+                         : (e.isDouble() && compare_numbers("==", v.toDouble(), e.toDouble())))
+            return true;
+    }
+    return false;
+}
+
+bool match_leaf(const QJsonObject& node, const QJsonObject& values) {
+    const QString op = node.value("op").toString();
+    const QJsonValue v = values.value(node.value("field").toString());
+    const bool present = !v.isUndefined() && !v.isNull() && !(v.isString() && v.toString().isEmpty());
+    if (op == "exists")
+        return present;
+    if (op == "missing")
+        return !present;
+    if (!present)
+        return false;
+
+    QJsonValue rhs = node.value("value");
+    if (node.contains("value_field")) {
+        rhs = values.value(node.value("value_field").toString());
+        if (rhs.isUndefined() || rhs.isNull())
+            return false;
+    }
+
+    if (op == "between") {
+        const QJsonArray range = rhs.toArray();
+        const double x = v.toDouble();
+        return v.isDouble() && x >= range.at(0).toDouble() && x <= range.at(1).toDouble();
+    }
+    if (op == "in")
+        return list_contains(rhs.toArray(), v);
+    if (op == "not_in")
+        return !list_contains(rhs.toArray(), v);
+    if (op == "contains")
+        return value_text(v).contains(rhs.toString(), Qt::CaseInsensitive);
+    if (v.isString() || rhs.isString())
+        return compare_text(op, value_text(v), value_text(rhs));
+    return compare_numbers(op, v.toDouble(), rhs.toDouble());
+}
+
+QString validate_node(const QJsonObject& node, const QString& path, int depth) {
+    const QString where = path.isEmpty() ? QString() : path + ": ";
+    if (depth > kMaxDepth)
+        return where + QString("filter nested deeper than %1 levels").arg(kMaxDepth);
+    if (node.isEmpty())
+        return {};
+
+    for (const char* group : {"all", "any"}) {
+        if (!node.contains(group))
+            continue;
+        if (node.size() != 1)
+            return where + QString("'%1' must be the node's only key").arg(group);
+        if (!node.value(group).isArray())
+            return where + QString("'%1' must be an array of filter nodes").arg(group);
+        const QJsonArray children = node.value(group).toArray();
+        for (int i = 0; i < children.size(); ++i) {
+            const QString child_path = QString("%1%2[%3]").arg(path.isEmpty() ? "" : path + ".", group).arg(i);
+            if (!children.at(i).isObject())
+                return child_path + ": not an object";
+            if (const QString err = validate_node(children.at(i).toObject(), child_path, depth + 1); !err.isEmpty())
+                return err;
+        }
+        return {};
+    }
+    if (node.contains("not")) {
+        if (node.size() != 1 || !node.value("not").isObject())
+            return where + "'not' takes a single filter node";
+        return validate_node(node.value("not").toObject(), path.isEmpty() ? "not" : path + ".not", depth + 1);
+    }
+    if (!is_leaf(node))
+        return where + "expected 'all', 'any', 'not' or a 'field' condition";
+
+    const QString key = node.value("field").toString();
+    const ScreenField* f = ScreenFilter::field(key);
+    if (!f)
+        return where + QString("unknown field '%1'").arg(key);
+    const QString op = node.value("op").toString();
+    if (kPresenceOps.contains(op))
+        return {};
+    if (node.contains("value_field")) {
+        if (!kCompareOps.contains(op))
+            return where + QString("'value_field' only works with %1").arg(kCompareOps.join(" "));
+        const QString ref = node.value("value_field").toString();
+        const ScreenField* rf = ScreenFilter::field(ref);
+        if (!rf)
+            return where + QString("unknown field '%1'").arg(ref);
+        if (rf->text != f->text)
+            return where + QString("cannot compare text and numeric fields ('%1' vs '%2')").arg(key, ref);
+        return {};
+    }
+    const QJsonValue value = node.value("value");
+    if (kCompareOps.contains(op)) {
+        if (f->text ? !value.isString() : !value.isDouble())
+            return where + QString("'%1' needs a %2 value").arg(key, f->text ? "string" : "numeric");
+        return {};
+    }
+    if (op == "between") {
+        const QJsonArray range = value.toArray();
+        if (f->text || range.size() != 2 || !range.at(0).isDouble() || !range.at(1).isDouble())
+            return where + "'between' needs a numeric field and a [low, high] value";
+        if (range.at(0).toDouble() > range.at(1).toDouble())
+            return where + "'between' range is reversed";
+        return {};
+    }
+    if (kListOps.contains(op)) {
+        if (!value.isArray() || value.toArray().isEmpty())
+            return where + QString("'%1' needs a non-empty array value").arg(op);
+        return {};
+    }
+    if (op == "contains") {
+        if (!f->text || !value.isString())
+            return where + "'contains' needs a text field and a string value";
+        return {};
+    }
+    return where + QString("unknown op '%1'").arg(op);
+}
+
+void collect_fields(const QJsonObject& node, QStringList& out) {
+    if (is_leaf(node)) {
+        for (const char* k : {"field", "value_field"}) {
+            const QString f = node.value(k).toString();
+            if (!f.isEmpty() && !out.contains(f))
+                out.append(f);
+        }
+        return;
+    }
+    if (node.contains("not")) {
+        collect_fields(node.value("not").toObject(), out);
+        return;
+    }
+    for (const char* group : {"all", "any"})
+        for (const auto& child : node.value(group).toArray())
+            collect_fields(child.toObject(), out);
+}
+
+QString describe_node(const QJsonObject& node, bool nested) {
+    if (node.isEmpty())
+        return QStringLiteral("(everything)");
+    if (is_leaf(node)) {
+        const QString field = node.value("field").toString();
+        const QString op = node.value("op").toString();
+        if (kPresenceOps.contains(op))
+            return QString("%1 %2").arg(field, op);
+        if (node.contains("value_field"))
+            return QString("%1 %2 %3").arg(field, op, node.value("value_field").toString());
+        const QJsonValue v = node.value("value");
+        if (v.isArray()) {
+            QStringList items;
+            for (const auto& e : v.toArray())
+                items.append(value_text(e));
+            return op == "between" ? QString("%1 between %2").arg(field, items.join(" and "))
+                                   : QString("%1 %2 [%3]").arg(field, op, items.join(", "));
+        }
+        return QString("%1 %2 %3").arg(field, op, value_text(v));
+    }
+    if (node.contains("not"))
+        return "NOT " + describe_node(node.value("not").toObject(), true);
+    const bool all = node.contains("all");
+    QStringList parts;
+    for (const auto& child : node.value(all ? "all" : "any").toArray())
+        parts.append(describe_node(child.toObject(), true));
+    if (parts.size() == 1)
+        return parts.first();
+    const QString joined = parts.join(all ? " AND " : " OR ");
+    return nested ? "(" + joined + ")" : joined;
+}
+
+} // namespace
+
+const QVector<ScreenField>& ScreenFilter::fields() {
+    static const QVector<ScreenField> kFields = {
+        // Profile
+        {"symbol", "profile", "Symbol", "", true},
+        {"name", "profile", "Name", "", true},
+        {"sector", "profile", "Sector", "", true},
+        {"industry", "profile", "Industry", "", true},
+        {"exchange", "profile", "Exchange", "", true},
+        {"country", "profile", "Country", "", true},
+        {"currency", "profile", "Currency", "", true},
+        // Fundamentals
+        {"market_cap", "fundamental", "Market cap", "$"},
+        {"enterprise_value", "fundamental", "Enterprise value", "$"},
+        {"pe_ratio", "fundamental", "P/E (trailing)", "x"},
+        {"forward_pe", "fundamental", "P/E (forward)", "x"},
+        {"peg_ratio", "fundamental", "PEG", "x"},
+        {"price_to_book", "fundamental", "Price / book", "x"},
+        {"price_to_sales", "fundamental", "Price / sales", "x"},
+        {"ev_to_ebitda", "fundamental", "EV / EBITDA", "x"},
+        {"dividend_yield", "fundamental", "Dividend yield", "%"},
+        {"gross_margin", "fundamental", "Gross margin", "%"},
+        {"operating_margin", "fundamental", "Operating margin", "%"},
+        {"profit_margin", "fundamental", "Net margin", "%"},
+        {"roe", "fundamental", "Return on equity", "%"},
+        {"roa", "fundamental", "Return on assets", "%"},
+        {"revenue_growth", "fundamental", "Revenue growth (YoY)", "%"},
+        {"earnings_growth", "fundamental", "Earnings growth (YoY)", "%"},
+        {"debt_to_equity", "fundamental", "Debt / equity", "%"},
+        {"current_ratio", "fundamental", "Current ratio", "x"},
+        {"fcf_yield", "fundamental", "Free cash flow yield", "%"},
+        {"beta", "fundamental", "Beta", ""},
+        // Technicals (daily closes)
+        {"price", "technical", "Price", ""},
+        {"change_pct", "technical", "Change 1D", "%"},
+        {"return_1m", "technical", "Return 1M", "%"},
+        {"return_3m", "technical", "Return 3M", "%"},
+        {"return_6m", "technical", "Return 6M", "%"},
+        {"return_12m", "technical", "Return 12M", "%"},
+        {"rsi_14", "technical", "RSI (14)", ""},
+        {"sma_50", "technical", "SMA 50", ""},
+        {"sma_200", "technical", "SMA 200", ""},
+        {"price_vs_sma50_pct", "technical", "Price vs SMA 50", "%"},
+        {"price_vs_sma200_pct", "technical", "Price vs SMA 200", "%"},
+        {"pct_from_52w_high", "technical", "From 52W high", "%"},
+        {"pct_from_52w_low", "technical", "From 52W low", "%"},
+        {"volatility_3m", "technical", "Volatility 3M (ann.)", "%"},
+        {"atr_14_pct", "technical", "ATR (14) / price", "%"},
+        {"avg_volume_3m", "technical", "Avg volume 3M", ""},
+        {"relative_volume", "technical", "Relative volume", "x"},
+        // Ownership
+        {"insider_pct", "ownership", "Insider ownership", "%"},
+        {"institution_pct", "ownership", "Institutional ownership", "%"},
+        {"short_pct_float", "ownership", "Short % of float", "%"},
+        {"short_ratio", "ownership", "Short ratio (days)", ""},
+        {"float_shares", "ownership", "Float shares", ""},
+        {"insider_net_pct", "ownership", "Insider net buying 6M", "%"},
+        // Alternative
+        {"analyst_rating", "alternative", "Analyst rating (1 buy … 5 sell)", ""},
+        {"analyst_count", "alternative", "Analyst count", ""},
+        {"target_upside_pct", "alternative", "Upside to mean target", "%"},
+        {"debate_score", "alternative", "Agent debate score (-1 … +1)", ""},
+    };
+    return kFields;
+}
+
+const ScreenField* ScreenFilter::field(const QString& key) {
+    static const QHash<QString, int> index = [] {
+        QHash<QString, int> h;
+        const auto& all = fields();
+        for (int i = 0; i < all.size(); ++i)
+            h.insert(all[i].key, i);
+        return h;
+    }();
+    const auto it = index.constFind(key);
+    return it == index.constEnd() ? nullptr : &fields()[it.value()];
+}
+
+QString ScreenFilter::validate(const QJsonObject& node) {
+    return validate_node(node, {}, 0);
+}
+
+bool ScreenFilter::matches(const QJsonObject& node, const QJsonObject& values) {
+    if (node.isEmpty())
+        return true;
+    if (is_leaf(node))
+        return match_leaf(node, values);
+    if (node.contains("not"))
+        return !matches(node.value("not").toObject(), values);
+    if (node.contains("any")) {
+        for (const auto& child : node.value("any").toArray())
+            if (matches(child.toObject(), values))
+                return true;
+        return false;
+    }
+    for (const auto& child : node.value("all").toArray())
+        if (!matches(child.toObject(), values))
+            return false;
+    return true;
+}
+
+QStringList ScreenFilter::referenced_fields(const QJsonObject& node) {
+    QStringList out;
+    collect_fields(node, out);
+    return out;
+}
+
+QString ScreenFilter::describe(const QJsonObject& node) {
+    return describe_node(node, false);
+}
+
+} // namespace fincept::services
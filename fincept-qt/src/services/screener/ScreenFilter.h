@@ -0,0 +1,51 @@
+// src/services/screener/ScreenFilter.h
+#pragma once
+#include <QJsonObject>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+namespace fincept::services {
+
+/// A metric the screener can filter, sort or display.
+struct ScreenField {
+    QString key;
+    QString category; // profile | fundamental | technical | ownership | alternative
+    QString label;
+    QString unit;      // "%", "x", "$", "" …
+    bool text = false; // compared as a string (profile columns)
+};
+
+/// Filter trees for the native screener, as JSON:
+///
+///   {"all": [node, …]}   every child matches      {"not": node}
+///   {"any": [node, …]}   at least one matches     {}  matches everything
+///   {"field": "pe_ratio", "op": "<", "value": 20}
+///   {"field": "price", "op": ">", "value_field": "sma_200"}
+///   {"field": "rsi_14", "op": "between", "value": [30, 50]}
+///   {"field": "sector", "op": "in", "value": ["Technology", "Energy"]}
+///
+/// Ops: < <= > >= == != between in not_in contains exists missing. A leaf on
+/// a metric the symbol has no value for never matches (except `missing`), so
+/// "pe_ratio < 15" leaves out loss-makers instead of treating them as cheap.
+/// Text comparisons are case-insensitive.
+class ScreenFilter {
+  public:
+    static const QVector<ScreenField>& fields();
+    static const ScreenField* field(const QString& key);
+
+    /// Empty when `node` is a well-formed tree over known fields; otherwise
+    /// what is wrong, e.g. "all[1]: unknown field 'pe'".
+    static QString validate(const QJsonObject& node);
+
+    /// `values` holds the profile columns and metrics of one symbol.
+    static bool matches(const QJsonObject& node, const QJsonObject& values);
+
+    /// Every field the tree reads, in first-use order.
+    static QStringList referenced_fields(const QJsonObject& node);
+
+    /// One-line form, e.g. "pe_ratio < 20 AND (rsi_14 < 30 OR price > sma_200)".
+    static QString describe(const QJsonObject& node);
+};
+
+} // namespace fincept::services
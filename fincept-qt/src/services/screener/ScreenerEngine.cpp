@@ -0,0 +1,562 @@
+// src/services/screener/ScreenerEngine.cpp
+#include "services/screener/ScreenerEngine.h"
+
+#include "core/events/EventBus.h"
+#include "core/logging/Logger.h"
+#include "python/PythonRunner.h"
+#include "services/fundamentals/PitFundamentals.h"
+#include "services/screener/ScreenFilter.h"
+#include "storage/repositories/AgentDebateRepository.h"
+#include "ui/notifications/NotificationService.h"
+
+#include <QDate>
+#include <QDateTime>
+#include <QFile>
+#include <QHash>
+#include <QJsonDocument>
+#include <QPointer>
+#include <QTextStream>
+#include <QUuid>
+
+#include <algorithm>
+
+namespace fincept::services {
+
+using fincept::ui::ToastService;
+
+namespace {
+
+constexpr const char* kScreenerTag = "Screener";
+
+const QString kDefaultSort = QStringLiteral("market_cap");
+const QString kDefaultPitSort = QStringLiteral("symbol");
+const QStringList kRowProfile = {"symbol", "name", "sector"};
+
+QString sort_key_for(const ScreenSpec& spec) {
+    if (!spec.sort_field.isEmpty())
+        return spec.sort_field;
+    return spec.as_of.isValid() ? kDefaultPitSort : kDefaultSort;
+}
+
+QJsonObject profile_values(const ScreenerDataRow& row) {
+    return QJsonObject{
+        {"symbol", row.symbol},
+        {"name", row.name},
+        {"sector", row.sector},
+        {"industry", row.industry},
+        {"exchange", row.exchange},
+        {"country", row.country},
+        {"currency", row.currency},
+    };
+}
+
+QJsonValue iso_or_null(qint64 ms) {
+    return ms > 0 ? QJsonValue(QDateTime::fromMSecsSinceEpoch(ms).toString(Qt::ISODate)) : QJsonValue();
+}
+
+QStringList normalize_symbols(const QStringList& in) {
+    QStringList out;
+    for (const QString& s : in) {
+        const QString sym = s.trimmed().toUpper();
+        if (!sym.isEmpty() && !out.contains(sym))
+            out.append(sym);
+    }
+    return out;
+}
+
+bool present(const QJsonValue& v) {
+    return !v.isUndefined() && !v.isNull();
+}
+
+QString csv_cell(const QJsonValue& v) {
+    QString s;
+    if (v.isDouble())
+        s = QString::number(v.toDouble(), 'g', 12);
+    else if (v.isString())
+        s = v.toString();
+    if (s.contains(',') || s.contains('"') || s.contains('\n'))
+        s = '"' + QString(s).replace("\"", "\"\"") + '"';
+    return s;
+}
+
+QString symbol_preview(const QStringList& symbols) {
+    constexpr int kShown = 5;
+    QString s = symbols.mid(0, kShown).join(", ");
+    if (symbols.size() > kShown)
+        s += QString(" +%1 more").arg(symbols.size() - kShown);
+    return s;
+}
+
+} // namespace
+
+struct ScreenerEngine::FetchState {
+    QStringList pending;
+    int updated = 0;
+    QJsonArray failed; // {symbol, error}
+    QString first_error;
+    RefreshCallback cb;
+};
+
+ScreenerEngine& ScreenerEngine::instance() {
+    static ScreenerEngine s;
+    return s;
+}
+
+void ScreenerEngine::start() {
+    if (timer_)
+        return;
+    timer_ = new QTimer(this);
+    timer_->setInterval(kTickMs);
+    connect(timer_, &QTimer::timeout, this, &ScreenerEngine::tick);
+    timer_->start();
+}
+
+void ScreenerEngine::tick() {
+    auto screens = ScreenerRepository::instance().list_screens();
+    if (screens.is_err())
+        return;
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    for (const auto& s : screens.value()) {
+        if (s.schedule_minutes > 0 && !in_flight_.contains(s.id) &&
+            now - s.last_run_at >= qint64(s.schedule_minutes) * 60 * 1000)
+            launch(s, s.refresh_data, true);
+    }
+}
+
+// ── Screening ───────────────────────────────────────────────────────────────
+
+ScreenSpec ScreenerEngine::spec_for(const SavedScreen& s) {
+    ScreenSpec spec;
+    spec.filter = s.filter;
+    spec.universe = s.universe;
+    spec.sort_field = s.sort_field;
+    spec.sort_desc = s.sort_desc;
+    spec.max_results = s.max_results;
+    spec.columns = s.columns;
+    return spec;
+}
+
+QStringList ScreenerEngine::columns_for(const ScreenSpec& spec) {
+    QStringList out;
+    auto add = [&out](const QString& key) {
+        if (!key.isEmpty() && !kRowProfile.contains(key) && !out.contains(key))
+            out.append(key);
+    };
+    add(sort_key_for(spec));
+    for (const QString& f : ScreenFilter::referenced_fields(spec.filter))
+        add(f);
+    for (const QString& c : spec.columns)
+        add(c);
+    return out;
+}
+
+Result<ScreenResult> ScreenerEngine::run(const ScreenSpec& spec) const {
+    if (const QString err = ScreenFilter::validate(spec.filter); !err.isEmpty())
+        return Result<ScreenResult>::err(("Invalid filter: " + err).toStdString());
+    const QString sort_key = sort_key_for(spec);
+    const ScreenField* sort_field = ScreenFilter::field(sort_key);
+    if (!sort_field)
+        return Result<ScreenResult>::err(QString("Unknown sort field '%1'").arg(sort_key).toStdString());
+    for (const QString& c : spec.columns) {
+        if (!ScreenFilter::field(c))
+            return Result<ScreenResult>::err(QString("Unknown column '%1'").arg(c).toStdString());
+    }
+    const bool pit = spec.as_of.isValid();
+    if (pit) {
+        // Today's prices, technicals and ownership would leak into a historical screen.
+        const QStringList& allowed = PitFundamentals::screener_fields();
+        for (const QString& key : columns_for(spec)) {
+            if (!allowed.contains(key) && ScreenFilter::field(key)->category != "profile")
+                return Result<ScreenResult>::err(
+                    QString("'%1' has no point-in-time history; as-of screens can use profile fields and %2")
+                        .arg(key, allowed.join(", "))
+                        .toStdString());
+        }
+    }
+
+    const QStringList universe = normalize_symbols(spec.universe);
+    auto data = ScreenerRepository::instance().load_data(universe);
+    if (data.is_err())
+        return Result<ScreenResult>::err(data.error());
+
+    ScreenResult res;
+    res.filter_text = ScreenFilter::describe(spec.filter);
+    res.columns = columns_for(spec);
+
+    // Every evaluated symbol's profile columns and metrics.
+    QVector<QJsonObject> candidates;
+    if (pit) {
+        auto snaps = PitFundamentals::snapshot(universe, spec.as_of);
+        if (snaps.is_err())
+            return Result<ScreenResult>::err(snaps.error());
+        res.as_of = spec.as_of.toString(Qt::ISODate);
+        QHash<QString, QJsonObject> profiles;
+        for (const auto& row : data.value())
+            profiles.insert(row.symbol, profile_values(row));
+        QStringList symbols = snaps.value().keys();
+        symbols.sort();
+        for (const QString& sym : symbols) {
+            QJsonObject values = snaps.value().value(sym).fields;
+            const QJsonObject profile = profiles.value(sym, QJsonObject{{"symbol", sym}});
+            for (auto it = profile.constBegin(); it != profile.constEnd(); ++it)
+                values[it.key()] = it.value();
+            candidates.append(values);
+        }
+    } else {
+        QHash<QString, double> debate;
+        const QString since = QDate::currentDate().addDays(-kDebateLookbackDays).toString(Qt::ISODate);
+        if (auto verdicts = AgentDebateRepository::instance().latest_per_ticker(since); verdicts.is_ok()) {
+            for (const auto& v : verdicts.value())
+                debate.insert(v.ticker.toUpper(), v.score);
+        }
+        for (const auto& row : data.value()) {
+            QJsonObject values = row.fields;
+            const QJsonObject profile = profile_values(row);
+            for (auto it = profile.constBegin(); it != profile.constEnd(); ++it)
+                values[it.key()] = it.value();
+            if (const auto it = debate.constFind(row.symbol); it != debate.constEnd())
+                values["debate_score"] = it.value();
+            if (res.data_as_of == 0 || row.updated_at < res.data_as_of)
+                res.data_as_of = row.updated_at;
+            candidates.append(values);
+        }
+    }
+
+    QSet<QString> seen;
+    QVector<QJsonObject> hits;
+    for (const QJsonObject& values : candidates) {
+        seen.insert(values.value("symbol").toString());
+        ++res.universe_size;
+        if (ScreenFilter::matches(spec.filter, values))
+            hits.append(values);
+    }
+    for (const QString& sym : universe) {
+        if (!seen.contains(sym))
+            res.uncached.append(sym);
+    }
+    res.match_count = static_cast<int>(hits.size());
+
+    // Symbols without a value for the sort field go last in either direction.
+    const bool text = sort_field->text;
+    const bool desc = spec.sort_field.isEmpty() && pit ? false : spec.sort_desc;
+    std::stable_sort(hits.begin(), hits.end(), [&](const QJsonObject& a, const QJsonObject& b) {
+        const QJsonValue va = a.value(sort_key);
+        const QJsonValue vb = b.value(sort_key);
+        if (present(va) != present(vb))
+            return present(va);
+        if (!present(va))
+            return a.value("symbol").toString() < b.value("symbol").toString();
+        if (text) {
+            const int c = va.toString().compare(vb.toString(), Qt::CaseInsensitive);
+            return desc ? c > 0 : c < 0;
+        }
+        return desc ? va.toDouble() > vb.toDouble() : va.toDouble() < vb.toDouble();
+    });
+
+    const int limit = spec.max_results > 0 ? std::min(spec.max_results, res.match_count) : res.match_count;
+    for (int i = 0; i < limit; ++i) {
+        const QJsonObject& v = hits[i];
+        QJsonObject row;
+        for (const QString& k : kRowProfile)
+            row[k] = v.value(k);
+        for (const QString& c : res.columns)
+            row[c] = present(v.value(c)) ? v.value(c) : QJsonValue();
+        res.rows.append(row);
+    }
+    return Result<ScreenResult>::ok(std::move(res));
+}
+
+// ── Metrics refresh ─────────────────────────────────────────────────────────
+
+void ScreenerEngine::fetch(const QStringList& symbols, RefreshCallback cb) {
+    auto st = std::make_shared<FetchState>();
+    st->pending = normalize_symbols(symbols);
+    st->cb = std::move(cb);
+    fetch_next(st);
+}
+
+void ScreenerEngine::fetch_next(std::shared_ptr<FetchState> st) {
+    if (st->pending.isEmpty()) {
+        // Only a refresh where nothing at all came back is an error.
+        st->cb(st->updated, st->failed, st->updated == 0 ? st->first_error : QString());
+        return;
+    }
+    const QStringList batch = st->pending.mid(0, kRefreshBatch);
+    st->pending = st->pending.mid(kRefreshBatch);
+
+    QPointer<ScreenerEngine> self = this;
+    python::PythonRunner::instance().run(
+        "screener_data.py", {"snapshot", batch.join(',')}, [self, st, batch](python::PythonResult r) {
+            if (!self)
+                return;
+            const QJsonObject o =
+                r.success ? QJsonDocument::fromJson(python::extract_json(r.output).toUtf8()).object() : QJsonObject();
+            if (!o.value("success").toBool(false)) {
+                QString msg = o.value("error").toString();
+                if (msg.isEmpty())
+                    msg = r.error.isEmpty() ? QStringLiteral("screener_data.py produced no result") : r.error;
+                LOG_WARN(kScreenerTag, QString("Metrics batch of %1 symbols failed: %2").arg(batch.size()).arg(msg));
+                if (st->first_error.isEmpty())
+                    st->first_error = msg;
+                for (const QString& sym : batch)
+                    st->failed.append(QJsonObject{{"symbol", sym}, {"error", msg}});
+                self->fetch_next(st);
+                return;
+            }
+
+            const qint64 now = QDateTime::currentMSecsSinceEpoch();
+            QVector<ScreenerDataRow> rows;
+            for (const auto& v : o.value("data").toArray()) {
+                const QJsonObject d = v.toObject();
+                ScreenerDataRow row;
+                row.symbol = d.value("symbol").toString().toUpper();
+                row.name = d.value("name").toString();
+                row.sector = d.value("sector").toString();
+                row.industry = d.value("industry").toString();
+                row.exchange = d.value("exchange").toString();
+                row.country = d.value("country").toString();
+                row.currency = d.value("currency").toString();
+                row.fields = d.value("fields").toObject();
+                row.updated_at = now;
+                if (!row.symbol.isEmpty())
+                    rows.append(row);
+            }
+            if (auto w = ScreenerRepository::instance().upsert_data(rows); w.is_err()) {
+                const QString msg = QString::fromStdString(w.error());
+                if (st->first_error.isEmpty())
+                    st->first_error = msg;
+                for (const auto& row : rows)
+                    st->failed.append(QJsonObject{{"symbol", row.symbol}, {"error", msg}});
+            } else {
+                st->updated += static_cast<int>(rows.size());
+            }
+            for (const auto& f : o.value("failed").toArray()) {
+                if (st->first_error.isEmpty())
+                    st->first_error = f.toObject().value("error").toString();
+                st->failed.append(f);
+            }
+            self->fetch_next(st);
+        });
+}
+
+QString ScreenerEngine::refresh_data(const QStringList& symbols) {
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    if (normalize_symbols(symbols).isEmpty()) {
+        QTimer::singleShot(0, this, [this, request_id]() { emit error_occurred(request_id, "No symbols given"); });
+        return request_id;
+    }
+    QPointer<ScreenerEngine> self = this;
+    fetch(symbols, [self, request_id](int updated, const QJsonArray& failed, const QString& error) {
+        if (!self)
+            return;
+        if (!error.isEmpty()) {
+            emit self->error_occurred(request_id, error);
+            return;
+        }
+        LOG_INFO(kScreenerTag, QString("Refreshed metrics for %1 symbols (%2 failed)").arg(updated).arg(failed.size()));
+        emit self->data_refreshed(request_id, updated, failed);
+    });
+    return request_id;
+}
+
+// ── Saved screens ───────────────────────────────────────────────────────────
+
+QString ScreenerEngine::run_saved(const QString& screen_id, bool refresh_first) {
+    const auto s = ScreenerRepository::instance().get_screen(screen_id);
+    if (!s) {
+        const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+        QTimer::singleShot(0, this, [this, request_id, screen_id]() {
+            emit error_occurred(request_id, "Unknown saved screen: " + screen_id);
+        });
+        return request_id;
+    }
+    return launch(*s, refresh_first, false);
+}
+
+QString ScreenerEngine::launch(const SavedScreen& s, bool refresh_first, bool scheduled) {
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    if (in_flight_.contains(s.id)) {
+        QTimer::singleShot(0, this, [this, request_id, name = s.name]() {
+            emit error_occurred(request_id, QString("Screen '%1' is already running").arg(name));
+        });
+        return request_id;
+    }
+    in_flight_.insert(s.id);
+
+    QStringList symbols = s.universe;
+    if (refresh_first && symbols.isEmpty()) {
+        if (auto cached = ScreenerRepository::instance().cached_symbols(); cached.is_ok())
+            symbols = cached.value();
+    }
+    const QString id = s.id;
+    if (!refresh_first || symbols.isEmpty()) {
+        QTimer::singleShot(0, this, [this, request_id, id, scheduled]() { execute_saved(request_id, id, scheduled); });
+        return request_id;
+    }
+    QPointer<ScreenerEngine> self = this;
+    fetch(symbols, [self, request_id, id, scheduled](int, const QJsonArray& failed, const QString& error) {
+        if (!self)
+            return;
+        // Stale metrics still screen; the run reports the refresh problem in the log.
+        if (!error.isEmpty())
+            LOG_WARN(kScreenerTag, QString("Metrics refresh before screen %1 failed: %2").arg(id, error));
+        else if (!failed.isEmpty())
+            LOG_INFO(kScreenerTag, QString("%1 symbols of screen %2 kept stale metrics").arg(failed.size()).arg(id));
+        self->execute_saved(request_id, id, scheduled);
+    });
+    return request_id;
+}
+
+void ScreenerEngine::execute_saved(const QString& request_id, const QString& screen_id, bool scheduled) {
+    in_flight_.remove(screen_id);
+    auto& repo = ScreenerRepository::instance();
+    const auto s = repo.get_screen(screen_id);
+    if (!s) {
+        emit error_occurred(request_id, "Saved screen was deleted while running: " + screen_id);
+        return;
+    }
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    auto result = run(spec_for(*s));
+    if (result.is_err()) {
+        const QString msg = QString::fromStdString(result.error());
+        LOG_WARN(kScreenerTag, QString("Screen '%1' failed: %2").arg(s->name, msg));
+        // Back off until the next scheduled slot rather than retrying every tick.
+        repo.touch_screen(s->id, now);
+        emit error_occurred(request_id, msg);
+        return;
+    }
+    const ScreenResult& res = result.value();
+
+    ScreenRun run;
+    run.screen_id = s->id;
+    run.ran_at = now;
+    run.universe_size = res.universe_size;
+    run.match_count = res.match_count;
+    run.rows = res.rows;
+    for (const auto& r : res.rows)
+        run.symbols.append(r.toObject().value("symbol").toString());
+    const auto previous = repo.last_run(s->id);
+    const QStringList before = previous ? previous->symbols : QStringList();
+    for (const QString& sym : run.symbols) {
+        if (!before.contains(sym))
+            run.added.append(sym);
+    }
+    for (const QString& sym : before) {
+        if (!run.symbols.contains(sym))
+            run.removed.append(sym);
+    }
+
+    auto stored = repo.record_run(run, kKeepRuns);
+    if (stored.is_err()) {
+        emit error_occurred(request_id, QString::fromStdString(stored.error()));
+        return;
+    }
+    run = stored.value();
+
+    if (!s->export_path.isEmpty()) {
+        if (auto e = export_csv(res.columns, res.rows, s->export_path); e.is_err())
+            LOG_WARN(kScreenerTag, QString("Export of screen '%1' failed: %2")
+                                       .arg(s->name, QString::fromStdString(e.error())));
+    }
+
+    QJsonObject js = run_to_json(run, true);
+    js["name"] = s->name;
+    js["filter_text"] = res.filter_text;
+    js["columns"] = QJsonArray::fromStringList(res.columns);
+    js["uncached"] = QJsonArray::fromStringList(res.uncached);
+    js["data_as_of"] = iso_or_null(res.data_as_of);
+    js["scheduled"] = scheduled;
+
+    QJsonObject event = js;
+    event.remove("rows");
+    EventBus::instance().publish("screener.screen_ran", event.toVariantMap());
+    // The first run has nothing to compare against.
+    if (scheduled && s->notify && previous && (!run.added.isEmpty() || !run.removed.isEmpty()))
+        notify(*s, run);
+    emit screen_ran(request_id, s->id, js);
+}
+
+void ScreenerEngine::notify(const SavedScreen& s, const ScreenRun& run) {
+    QStringList parts;
+    if (!run.added.isEmpty())
+        parts.append("new: " + symbol_preview(run.added));
+    if (!run.removed.isEmpty())
+        parts.append("dropped: " + symbol_preview(run.removed));
+    const QString msg = QString("Screen '%1' — %2 matches; %3").arg(s.name).arg(run.match_count).arg(parts.join("; "));
+    LOG_INFO(kScreenerTag, msg);
+    ToastService::instance().post(ToastService::Severity::Info, msg, "screener:" + s.id);
+}
+
+// ── Export / JSON ───────────────────────────────────────────────────────────
+
+Result<void> ScreenerEngine::export_csv(const QStringList& columns, const QJsonArray& rows, const QString& path) {
+    QFile f(path);
+    if (!f.open(QIODevice::WriteOnly | QIODevice::Text | QIODevice::Truncate))
+        return Result<void>::err(QString("Cannot open %1: %2").arg(path, f.errorString()).toStdString());
+    QTextStream ts(&f);
+    const QStringList header = kRowProfile + columns;
+    ts << header.join(',') << '\n';
+    for (const auto& r : rows) {
+        const QJsonObject o = r.toObject();
+        QStringList cells;
+        for (const QString& k : header)
+            cells.append(csv_cell(o.value(k)));
+        ts << cells.join(',') << '\n';
+    }
+    f.close();
+    LOG_INFO(kScreenerTag, QString("Exported %1 rows to %2").arg(rows.size()).arg(path));
+    return Result<void>::ok();
+}
+
+QJsonObject ScreenerEngine::result_to_json(const ScreenResult& r) {
+    return QJsonObject{
+        {"filter_text", r.filter_text},
+        {"universe_size", r.universe_size},
+        {"uncached", QJsonArray::fromStringList(r.uncached)},
+        {"match_count", r.match_count},
+        {"returned", r.rows.size()},
+        {"columns", QJsonArray::fromStringList(r.columns)},
+        {"rows", r.rows},
+        {"data_as_of", iso_or_null(r.data_as_of)},
+        {"as_of", r.as_of.isEmpty() ? QJsonValue() : QJsonValue(r.as_of)},
+    };
+}
+
+QJsonObject ScreenerEngine::screen_to_json(const SavedScreen& s) {
+    return QJsonObject{
+        {"id", s.id},
+        {"name", s.name},
+        {"description", s.description},
+        {"filter", s.filter},
+        {"filter_text", ScreenFilter::describe(s.filter)},
+        {"universe", QJsonArray::fromStringList(s.universe)},
+        {"sort_field", s.sort_field.isEmpty() ? kDefaultSort : s.sort_field},
+        {"sort_desc", s.sort_desc},
+        {"max_results", s.max_results},
+        {"columns", QJsonArray::fromStringList(s.columns)},
+        {"schedule_minutes", s.schedule_minutes},
+        {"refresh_data", s.refresh_data},
+        {"notify", s.notify},
+        {"export_path", s.export_path.isEmpty() ? QJsonValue() : QJsonValue(s.export_path)},
+        {"last_run_at", iso_or_null(s.last_run_at)},
+        {"last_match_count", s.last_match_count},
+    };
+}
+
+QJsonObject ScreenerEngine::run_to_json(const ScreenRun& r, bool include_rows) {
+    QJsonObject o{
+        {"id", r.id},
+        {"screen_id", r.screen_id},
+        {"ran_at", iso_or_null(r.ran_at)},
+        {"universe_size", r.universe_size},
+        {"match_count", r.match_count},
+        {"symbols", QJsonArray::fromStringList(r.symbols)},
+        {"added", QJsonArray::fromStringList(r.added)},
+        {"removed", QJsonArray::fromStringList(r.removed)},
+    };
+    if (include_rows)
+        o["rows"] = r.rows;
+    return o;
+}
+
+} // namespace fincept::services
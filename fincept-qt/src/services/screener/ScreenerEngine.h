@@ -0,0 +1,123 @@
+// src/services/screener/ScreenerEngine.h
+#pragma once
+#include "core/result/Result.h"
+#include "storage/repositories/ScreenerRepository.h"
+
+#include <QDate>
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QObject>
+#include <QSet>
+#include <QString>
+#include <QStringList>
+#include <QTimer>
+
+#include <functional>
+#include <memory>
+
+namespace fincept::services {
+
+/// What to screen and how to present it. An empty universe screens every
+/// symbol with cached metrics.
+///
+/// With `as_of` set the screen runs over point-in-time fundamentals
+/// (PitFundamentals) as they were filed by that date instead of today's
+/// cached metrics; only profile columns and PitFundamentals::screener_fields()
+/// can be used, and an empty universe means every symbol in that store.
+struct ScreenSpec {
+    QJsonObject filter; // ScreenFilter tree
+    QStringList universe;
+    QString sort_field; // empty = market_cap, or symbol (ascending) for as_of screens
+    bool sort_desc = true;
+    int max_results = 50;
+    QStringList columns; // extra result columns
+    QDate as_of;         // invalid = live cached metrics
+};
+
+struct ScreenResult {
+    QString filter_text;   // ScreenFilter::describe()
+    int universe_size = 0; // symbols evaluated (universe members with cached metrics)
+    QStringList uncached;  // universe members without cached metrics
+    int match_count = 0;   // before max_results
+    QStringList columns;   // metric columns of each row, after symbol / name / sector
+    QJsonArray rows;       // {symbol, name, sector, <columns>…}, sorted
+    qint64 data_as_of = 0; // oldest metrics timestamp among evaluated symbols, epoch ms
+    QString as_of;         // point-in-time date (yyyy-MM-dd), empty for live screens
+};
+
+/// Native screener: evaluates ScreenFilter trees over the screener_data
+/// store — fundamentals, daily technicals, ownership and analyst data from
+/// screener_data.py, plus the latest agent debate score per symbol — without
+/// touching the network. refresh_data() re-fetches metrics in batches.
+/// Screens with ScreenSpec::as_of read the point-in-time fundamentals store
+/// instead, so historical screens see only what had been filed.
+///
+/// Saved screens (ScreenerRepository) with a schedule are re-run by the tick
+/// timer: metrics are refreshed first when the screen asks for it, the run
+/// is stored with the symbols that entered / left the match list, the CSV
+/// export is rewritten, and a change posts a toast plus the
+/// `screener.screen_ran` EventBus event. Main thread only.
+class ScreenerEngine : public QObject {
+    Q_OBJECT
+  public:
+    static constexpr int kTickMs = 60 * 1000;      // how often due screens are looked for
+    static constexpr int kRefreshBatch = 60;       // symbols per screener_data.py process
+    static constexpr int kKeepRuns = 100;          // stored runs per saved screen
+    static constexpr int kDebateLookbackDays = 30; // older debate verdicts are ignored
+
+    static ScreenerEngine& instance();
+
+    /// Starts the scheduled re-run timer. Idempotent.
+    void start();
+
+    /// Screens the cached metrics synchronously.
+    Result<ScreenResult> run(const ScreenSpec& spec) const;
+
+    /// Re-fetches metrics for `symbols`; returns the request id
+    /// data_refreshed / error_occurred carry.
+    QString refresh_data(const QStringList& symbols);
+
+    /// Runs saved screen `screen_id` (refreshing its universe first when
+    /// `refresh_first`) and stores the run; returns the request id
+    /// screen_ran / error_occurred carry.
+    QString run_saved(const QString& screen_id, bool refresh_first);
+
+    static ScreenSpec spec_for(const SavedScreen& s);
+    /// Sort field, filter fields and requested columns, deduplicated, without
+    /// the symbol / name / sector columns every row carries.
+    static QStringList columns_for(const ScreenSpec& spec);
+
+    /// Writes `rows` as CSV: symbol, name, sector, then `columns`.
+    static Result<void> export_csv(const QStringList& columns, const QJsonArray& rows, const QString& path);
+
+    static QJsonObject result_to_json(const ScreenResult& r);
+    static QJsonObject screen_to_json(const SavedScreen& s);
+    static QJsonObject run_to_json(const ScreenRun& r, bool include_rows);
+
+  signals:
+    void data_refreshed(QString request_id, int updated, QJsonArray failed);
+    /// `run` is run_to_json(run, true).
+    void screen_ran(QString request_id, QString screen_id, QJsonObject run);
+    void error_occurred(QString request_id, QString message);
+
+  private:
+    ScreenerEngine() = default;
+    Q_DISABLE_COPY(ScreenerEngine)
+
+    using RefreshCallback = std::function<void(int updated, const QJsonArray& failed, const QString& error)>;
+
+    struct FetchState;
+
+    void tick();
+    /// Fetches `symbols` kRefreshBatch at a time, one process after another.
+    void fetch(const QStringList& symbols, RefreshCallback cb);
+    void fetch_next(std::shared_ptr<FetchState> st);
+    QString launch(const SavedScreen& s, bool refresh_first, bool scheduled);
+    void execute_saved(const QString& request_id, const QString& screen_id, bool scheduled);
+    void notify(const SavedScreen& s, const ScreenRun& run);
+
+    QTimer* timer_ = nullptr;
+    QSet<QString> in_flight_; // saved screen ids being run
+};
+
+} // namespace fincept::services
@@ -61,7 +61,7 @@ void SectorResolver::load_cache() {
         return;
 
     QSqlQuery q(db_mgr.raw_db());
-    if (!q.exec("SELECT symbol, sector, resolved_at FROM sector_cache")) {
+    if (!q.exec("SELECT symbol, sector, resolved_at, industry FROM sector_cache")) {
         LOG_WARN("SectorResolver", "Cache load failed: " + q.lastError().text());
         return;
     }
@@ -82,6 +82,9 @@ void SectorResolver::load_cache() {
 
         if (!sec.isEmpty()) {
             cache_.insert(sym, sec);
+            const QString ind = q.value(3).toString();
+            if (!ind.isEmpty())
+                industry_.insert(sym, ind);
             ++loaded;
         }
     }
@@ -141,6 +144,11 @@ QString SectorResolver::sector_for(const QString& symbol) {
     return {};
 }
 
+QString SectorResolver::industry_for(const QString& symbol) const {
+    QMutexLocker lock(&mutex_);
+    return industry_.value(normalize(symbol));
+}
+
 void SectorResolver::prefetch(const QStringList& symbols) {
     for (const auto& s : symbols)
         (void)sector_for(s);
@@ -160,6 +168,8 @@ void SectorResolver::resolve_async(const QString& symbol) {
         {
             QMutexLocker lock(&self->mutex_);
             self->cache_.insert(symbol, final_sector);
+            if (!industry.trimmed().isEmpty() && industry != "N/A")
+                self->industry_.insert(symbol, industry.trimmed());
             self->inflight_.remove(symbol);
         }
         self->persist(symbol, final_sector, industry, quote_type);
@@ -36,6 +36,10 @@ class SectorResolver : public QObject {
     // — subscribe to sector_resolved() to get the result.
     QString sector_for(const QString& symbol);
 
+    // Cached industry for a symbol whose sector has resolved; empty when
+    // unknown. Never triggers a fetch — call sector_for() for that.
+    QString industry_for(const QString& symbol) const;
+
     // Remember an authoritative sector (e.g. from portfolio import JSON).
     // Overwrites any cached value and persists to disk.
     void remember(const QString& symbol, const QString& sector);
@@ -60,7 +64,8 @@ class SectorResolver : public QObject {
     static QString fallback_for_unresolvable(const QString& symbol);
 
     mutable QMutex mutex_;
-    QHash<QString, QString> cache_; // symbol → sector
+    QHash<QString, QString> cache_;    // symbol → sector
+    QHash<QString, QString> industry_; // symbol → industry (when known)
     QSet<QString> inflight_;        // symbols currently being fetched
 };
 
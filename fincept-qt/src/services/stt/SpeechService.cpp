@@ -1,17 +1,21 @@
-// SpeechService.cpp — Pluggable speech-to-text (Google or Deepgram).
+// SpeechService.cpp — Pluggable speech-to-text (Google, Deepgram or local Whisper).
 //
 // Architecture:
 //   SpeechService (public facade)
 //     └── SttProvider (abstract)
 //           ├── PythonSttProvider (shared QProcess + JSON-lines plumbing)
 //           │     ├── GoogleSttProvider   → scripts/voice/speech_to_text.py
-//           │     └── DeepgramSttProvider → scripts/voice/deepgram_stt.py
+//           │     ├── DeepgramSttProvider → scripts/voice/deepgram_stt.py
+//           │     └── WhisperSttProvider  → scripts/voice/whisper_stt.py
 //
-// Both providers share the same JSON-lines stdout protocol:
+// All providers share the same JSON-lines stdout protocol:
 //   {"status": "calibrating"|"listening"|"stopped"}
 //   {"text": "..."}        → transcription
 //   {"error": "..."}       → recoverable error
 //   {"fatal": "..."}       → unrecoverable error (process exits)
+//
+// transcribe_file() runs whisper_stt.py --file once through PythonRunner for
+// audio recorded elsewhere; it is independent of the live provider.
 
 #include "services/stt/SpeechService.h"
 
@@ -365,6 +369,53 @@ class DeepgramSttProvider final : public PythonSttProvider {
     }
 };
 
+// ── WhisperSttProvider ───────────────────────────────────────────────────────
+//
+// Local, offline Whisper via whisper.cpp bindings (pywhispercpp). No API key;
+// the ggml model is downloaded once into <install_dir>/models/whisper and all
+// audio stays on the machine. Model/language/threads (and the same gain /
+// device capture knobs Deepgram has) come from AppConfig voice/whisper/*.
+
+struct WhisperSettings {
+    QString model;
+    QString language;
+    QString threads;
+};
+
+WhisperSettings whisper_settings() {
+    auto& cfg = AppConfig::instance();
+    return {cfg.get("voice/whisper/model", "base.en").toString(), cfg.get("voice/whisper/language", "en").toString(),
+            cfg.get("voice/whisper/threads", "4").toString()};
+}
+
+class WhisperSttProvider final : public PythonSttProvider {
+  public:
+    using PythonSttProvider::PythonSttProvider;
+
+    QString name() const override { return QStringLiteral("whisper"); }
+
+  protected:
+    QString script_path() const override {
+        return python::PythonRunner::instance().scripts_dir() + QStringLiteral("/voice/whisper_stt.py");
+    }
+
+    void extend_environment(QProcessEnvironment& env) const override {
+        auto& cfg = AppConfig::instance();
+        const WhisperSettings w = whisper_settings();
+        const QString gain = cfg.get("voice/whisper/gain", "1.0").toString();
+        const QString device = cfg.get("voice/whisper/device", "").toString();
+
+        LOG_INFO(TAG, QString("Whisper env: model='%1' language='%2' threads=%3 gain=%4 device='%5'")
+                          .arg(w.model, w.language, w.threads, gain, device));
+
+        env.insert("FINCEPT_WHISPER_MODEL", w.model);
+        env.insert("FINCEPT_WHISPER_LANGUAGE", w.language);
+        env.insert("FINCEPT_WHISPER_THREADS", w.threads);
+        env.insert("FINCEPT_STT_GAIN", gain);
+        env.insert("FINCEPT_STT_DEVICE", device);
+    }
+};
+
 } // namespace
 
 // ── Singleton ────────────────────────────────────────────────────────────────
@@ -391,7 +442,41 @@ QString SpeechService::configured_provider() {
     QString v = cfg.get("voice/stt/provider", "").toString().toLower();
     if (v.isEmpty())
         v = cfg.get("voice/provider", "google").toString().toLower();
-    return (v == "deepgram") ? QStringLiteral("deepgram") : QStringLiteral("google");
+    if (v == "deepgram" || v == "whisper")
+        return v;
+    return QStringLiteral("google");
+}
+
+void SpeechService::transcribe_file(const QString& wav_path, TranscribeCallback cb) {
+    if (!QFileInfo::exists(wav_path)) {
+        cb(Result<QString>::err("Audio file not found: " + wav_path.toStdString()));
+        return;
+    }
+    // PythonRunner has no per-call environment, so the Whisper settings go in
+    // as flags (whisper_stt.py lets them override its env vars).
+    const WhisperSettings w = whisper_settings();
+    const QStringList args = {"--file", wav_path, "--model", w.model, "--language", w.language, "--threads", w.threads};
+    LOG_INFO(TAG, QString("transcribe_file('%1') — model=%2").arg(wav_path, w.model));
+
+    python::PythonRunner::instance().run(
+        QStringLiteral("voice/whisper_stt.py"), args, [cb = std::move(cb)](const python::PythonResult& r) {
+            // One JSON line on stdout; model-download progress and diagnostics go to stderr.
+            QJsonObject obj;
+            const QStringList lines = r.output.split('\n', Qt::SkipEmptyParts);
+            for (auto it = lines.crbegin(); it != lines.crend() && obj.isEmpty(); ++it)
+                obj = QJsonDocument::fromJson(it->trimmed().toUtf8()).object();
+
+            const QString text = obj.value("text").toString().trimmed();
+            if (!text.isEmpty()) {
+                cb(Result<QString>::ok(text));
+                return;
+            }
+            QString err = obj.value("error").toString(obj.value("fatal").toString());
+            if (err.isEmpty())
+                err = r.error.trimmed().isEmpty() ? QStringLiteral("Whisper produced no output") : r.error.trimmed();
+            LOG_WARN(TAG, "transcribe_file failed: " + err.left(300));
+            cb(Result<QString>::err(err.toStdString()));
+        });
 }
 
 void SpeechService::start_listening() {
@@ -466,6 +551,8 @@ void SpeechService::install_provider() {
 
     if (id == QStringLiteral("deepgram"))
         provider_ = std::make_unique<DeepgramSttProvider>(this);
+    else if (id == QStringLiteral("whisper"))
+        provider_ = std::make_unique<WhisperSttProvider>(this);
     else
         provider_ = std::make_unique<GoogleSttProvider>(this);
 
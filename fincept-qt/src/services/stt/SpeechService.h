@@ -3,28 +3,33 @@
 //
 // Architecture:
 //   • Public API unchanged: start_listening() / stop_listening() / signals.
-//   • Internally selects a provider (Google or Deepgram) based on AppConfig
-//     key "voice/provider" (default: "google"). Each provider spawns its own
-//     Python script as a long-running QProcess using the same JSON-lines
-//     stdout protocol ({status|text|error|fatal}).
+//   • Internally selects a provider (Google, Deepgram or local Whisper) based
+//     on AppConfig key "voice/stt/provider" (default: "google"). Each provider
+//     spawns its own Python script as a long-running QProcess using the same
+//     JSON-lines stdout protocol ({status|text|error|fatal}).
 //   • AiChatBubble never sees the provider — it only sees the three signals.
+//   • transcribe_file() is the one-shot exception: a recorded WAV goes through
+//     local Whisper via PythonRunner, outside the live-provider lifecycle.
 //
 // Complies with P1 (never block UI), P6 (service layer), P15 (thread safety).
 // Uses its own QProcess (not PythonRunner) because PythonRunner is designed
 // for short-lived scripts with a single callback — this is a long-running
 // streaming process, same pattern as ExchangeService's WebSocket process.
 
+#include "core/result/Result.h"
+
 #include <QObject>
 #include <QProcess>
 #include <QProcessEnvironment>
 #include <QString>
 
 #include <atomic>
+#include <functional>
 #include <memory>
 
 namespace fincept::services {
 
-/// Abstract base for STT providers. Both providers share the Python-QProcess
+/// Abstract base for STT providers. All providers share the Python-QProcess
 /// + JSON-lines parsing plumbing; subclasses only contribute the script path
 /// and any extra environment variables (e.g. API keys, model).
 class SttProvider : public QObject {
@@ -65,10 +70,17 @@ class SpeechService : public QObject {
 
     [[nodiscard]] bool is_listening() const noexcept;
 
-    /// Returns the currently active provider id ("google" or "deepgram").
-    /// Reads AppConfig; does not reflect in-flight state.
+    /// Returns the currently active provider id ("google", "deepgram" or
+    /// "whisper"). Reads AppConfig; does not reflect in-flight state.
     [[nodiscard]] static QString configured_provider();
 
+    using TranscribeCallback = std::function<void(Result<QString>)>;
+
+    /// One-shot transcription of a WAV file with local Whisper (whatever the
+    /// live provider is). Does not touch the microphone or listening state.
+    /// Callback runs on the UI thread.
+    void transcribe_file(const QString& wav_path, TranscribeCallback cb);
+
   signals:
     /// Fired on the UI thread with the transcribed text.
     void transcription_ready(const QString& text);
@@ -0,0 +1,229 @@
+// src/services/tca/TcaEngine.cpp
+#include "services/tca/TcaEngine.h"
+
+#include <QDateTime>
+#include <QJsonArray>
+#include <QMap>
+#include <QTimeZone>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::services::tca {
+
+namespace {
+
+// Outside the session the prevailing price is the last close before the
+// order — as long as that close is recent (a weekend, not a data gap).
+constexpr qint64 kTcaMaxStaleMs = 4LL * 24 * 60 * 60 * 1000;
+
+constexpr int kTcaBenchCount = 3;
+const char* const kTcaBench[kTcaBenchCount] = {"arrival", "vwap", "close"};
+
+double tca_bench(const TcaBenchmarks& b, int i) {
+    return i == 0 ? b.arrival : i == 1 ? b.vwap : b.close;
+}
+
+double tca_bps(const TcaFill& f, double bench) {
+    return bench > 0 ? f.side * (f.price - bench) / bench * 1e4 : 0.0;
+}
+
+double tca_round(double v, int decimals) {
+    const double m = std::pow(10.0, decimals);
+    return std::round(v * m) / m;
+}
+
+QString tca_iso(qint64 ms) {
+    return ms > 0 ? QDateTime::fromMSecsSinceEpoch(ms, QTimeZone::UTC).toString(Qt::ISODate) : QString();
+}
+
+struct TcaAgg {
+    int fills = 0;
+    double quantity = 0;
+    double notional = 0;
+    double fees = 0;
+    double weighted_bps[kTcaBenchCount] = {};
+    double bench_notional[kTcaBenchCount] = {};
+    double cost[kTcaBenchCount] = {};
+
+    void add(const TcaFill& f, const TcaBenchmarks& b) {
+        const double n = f.price * f.quantity;
+        ++fills;
+        quantity += f.quantity;
+        notional += n;
+        fees += f.fee;
+        for (int i = 0; i < kTcaBenchCount; ++i) {
+            const double bench = tca_bench(b, i);
+            if (bench <= 0)
+                continue;
+            weighted_bps[i] += tca_bps(f, bench) * n;
+            bench_notional[i] += n;
+            cost[i] += f.side * (f.price - bench) * f.quantity;
+        }
+    }
+
+    QJsonObject to_json() const {
+        QJsonObject o{{"fills", fills},
+                      {"quantity", quantity},
+                      {"notional", tca_round(notional, 2)},
+                      {"fees", tca_round(fees, 2)}};
+        for (int i = 0; i < kTcaBenchCount; ++i) {
+            const QString name = QString::fromLatin1(kTcaBench[i]);
+            if (bench_notional[i] > 0) {
+                o[name + "_bps"] = tca_round(weighted_bps[i] / bench_notional[i], 2);
+                o[name + "_cost"] = tca_round(cost[i], 2);
+            } else {
+                o[name + "_bps"] = QJsonValue::Null;
+                o[name + "_cost"] = QJsonValue::Null;
+            }
+        }
+        return o;
+    }
+};
+
+QJsonArray tca_groups(const QMap<QString, TcaAgg>& groups) {
+    QVector<QPair<QString, TcaAgg>> rows;
+    for (auto it = groups.constBegin(); it != groups.constEnd(); ++it)
+        rows.append({it.key(), it.value()});
+    std::sort(rows.begin(), rows.end(),
+              [](const auto& a, const auto& b) { return a.second.notional > b.second.notional; });
+    QJsonArray out;
+    for (const auto& [key, agg] : rows) {
+        QJsonObject o = agg.to_json();
+        o["key"] = key;
+        out.append(o);
+    }
+    return out;
+}
+
+} // namespace
+
+TcaBenchmarks TcaEngine::benchmarks(const TcaFill& fill, const QVector<algo::OhlcvCandle>& bars,
+                                    int vwap_window_minutes) {
+    TcaBenchmarks b;
+    if (bars.isEmpty() || fill.time_ms <= 0)
+        return b;
+    const qint64 order_t = fill.order_time_ms > 0 ? std::min(fill.order_time_ms, fill.time_ms) : fill.time_ms;
+    const auto by_open = [](qint64 t, const algo::OhlcvCandle& c) { return t < c.open_time; };
+
+    // Arrival: the bar holding the order time, else the last close before it.
+    if (fill.arrival_hint > 0) {
+        b.arrival = fill.arrival_hint;
+    } else {
+        const auto it = std::upper_bound(bars.cbegin(), bars.cend(), order_t, by_open);
+        if (it != bars.cbegin()) {
+            const algo::OhlcvCandle& bar = *(it - 1);
+            if (order_t < bar.close_time)
+                b.arrival = bar.open;
+            else if (order_t - bar.close_time <= kTcaMaxStaleMs)
+                b.arrival = bar.close;
+        }
+    }
+
+    // Interval VWAP: bars overlapping [order, fill], widened to a window
+    // around the fill when the order filled within one bar.
+    const qint64 span = std::max<qint64>(bars.first().close_time - bars.first().open_time, 60 * 1000);
+    qint64 lo = order_t, hi = fill.time_ms;
+    if (hi - lo < span) {
+        const qint64 half = std::max<qint64>(qint64(vwap_window_minutes) * 60 * 1000 / 2, span / 2);
+        lo = fill.time_ms - half;
+        hi = fill.time_ms + half;
+    }
+    double pv = 0, vol = 0, tp_sum = 0;
+    int n = 0;
+    for (const auto& c : bars) {
+        if (c.open_time >= hi)
+            break;
+        if (c.close_time <= lo)
+            continue;
+        const double tp = (c.high + c.low + c.close) / 3.0;
+        pv += tp * c.volume;
+        vol += c.volume;
+        tp_sum += tp;
+        ++n;
+    }
+    if (vol > 0)
+        b.vwap = pv / vol;
+    else if (n > 0)
+        b.vwap = tp_sum / n; // no volume (indices, FX): plain average
+
+    // Close: the last bar of the fill's (UTC) day — every session the
+    // fetcher serves sits inside one UTC day.
+    const QDate day = QDateTime::fromMSecsSinceEpoch(fill.time_ms, QTimeZone::UTC).date();
+    const qint64 day_end = QDateTime(day.addDays(1), QTime(0, 0), QTimeZone::UTC).toMSecsSinceEpoch();
+    const auto end = std::lower_bound(bars.cbegin(), bars.cend(), day_end,
+                                      [](const algo::OhlcvCandle& c, qint64 t) { return c.open_time < t; });
+    if (end != bars.cbegin()) {
+        const algo::OhlcvCandle& last = *(end - 1);
+        if (QDateTime::fromMSecsSinceEpoch(last.open_time, QTimeZone::UTC).date() == day)
+            b.close = last.close;
+    }
+    return b;
+}
+
+QJsonObject TcaEngine::analyze(const QVector<TcaFill>& fills, const QHash<QString, QVector<algo::OhlcvCandle>>& bars,
+                               int vwap_window_minutes, int max_fills) {
+    QVector<int> order(fills.size());
+    for (int i = 0; i < fills.size(); ++i)
+        order[i] = i;
+    std::sort(order.begin(), order.end(), [&](int a, int b) { return fills[a].time_ms > fills[b].time_ms; });
+
+    TcaAgg total;
+    QMap<QString, TcaAgg> by_strategy, by_broker, by_symbol;
+    QJsonArray rows, unbenchmarked;
+    for (int i : order) {
+        const TcaFill& f = fills[i];
+        const auto& sym_bars = bars.value(f.symbol);
+        const TcaBenchmarks b = benchmarks(f, sym_bars, vwap_window_minutes);
+        if (b.arrival <= 0 && b.vwap <= 0 && b.close <= 0) {
+            unbenchmarked.append(QJsonObject{
+                {"source", f.source},
+                {"id", f.id},
+                {"symbol", f.symbol},
+                {"time", tca_iso(f.time_ms)},
+                {"reason", sym_bars.isEmpty() ? QStringLiteral("No intraday bars for the symbol")
+                                              : QStringLiteral("Fill outside the bar history")}});
+            continue;
+        }
+        total.add(f, b);
+        by_strategy[f.strategy.isEmpty() ? QStringLiteral("manual") : f.strategy].add(f, b);
+        by_broker[f.broker.isEmpty() ? QStringLiteral("unknown") : f.broker].add(f, b);
+        by_symbol[f.symbol].add(f, b);
+
+        if (rows.size() >= max_fills)
+            continue;
+        QJsonObject r{{"source", f.source},
+                      {"id", f.id},
+                      {"time", tca_iso(f.time_ms)},
+                      {"account", f.account},
+                      {"broker", f.broker},
+                      {"strategy", f.strategy},
+                      {"symbol", f.symbol},
+                      {"side", f.side > 0 ? "buy" : "sell"},
+                      {"quantity", f.quantity},
+                      {"price", f.price},
+                      {"fee", f.fee}};
+        if (f.order_time_ms > 0 && f.order_time_ms != f.time_ms)
+            r["order_time"] = tca_iso(f.order_time_ms);
+        for (int k = 0; k < kTcaBenchCount; ++k) {
+            const QString name = QString::fromLatin1(kTcaBench[k]);
+            const double bench = tca_bench(b, k);
+            r[name] = bench > 0 ? QJsonValue(tca_round(bench, 4)) : QJsonValue::Null;
+            r[name + "_bps"] = bench > 0 ? QJsonValue(tca_round(tca_bps(f, bench), 2)) : QJsonValue::Null;
+        }
+        rows.append(r);
+    }
+
+    QJsonObject summary = total.to_json();
+    summary["unbenchmarked"] = int(unbenchmarked.size());
+    return QJsonObject{{"fills_total", int(fills.size())},
+                       {"fills_benchmarked", total.fills},
+                       {"summary", summary},
+                       {"by_strategy", tca_groups(by_strategy)},
+                       {"by_broker", tca_groups(by_broker)},
+                       {"by_symbol", tca_groups(by_symbol)},
+                       {"fills", rows},
+                       {"unbenchmarked", unbenchmarked}};
+}
+
+} // namespace fincept::services::tca
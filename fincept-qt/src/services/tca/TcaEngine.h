@@ -0,0 +1,64 @@
+// src/services/tca/TcaEngine.h
+#pragma once
+#include "algo_engine/AlgoEngineTypes.h"
+
+#include <QHash>
+#include <QJsonObject>
+#include <QString>
+#include <QVector>
+
+namespace fincept::services::tca {
+
+/// One executed fill, whatever placed it. Times are epoch milliseconds.
+struct TcaFill {
+    QString source; // paper | live | algo | exec
+    QString id;     // order id (trade id when the order is unknown)
+    QString account_id;
+    QString account; // display name
+    QString broker;   // broker id; "paper" for portfolios not linked to an account
+    QString strategy; // deployment strategy name, "exec:<algo>", or "manual"
+    QString symbol;
+    int side = 1; // +1 buy, -1 sell
+    double quantity = 0;
+    double price = 0;
+    double fee = 0;
+    qint64 time_ms = 0;       // fill
+    qint64 order_time_ms = 0; // order placed / decided; 0 = the fill time
+    double arrival_hint = 0;  // arrival price known at send time (exec child quote); 0 = from bars
+};
+
+/// Benchmark prices of one fill; 0 = not available from the bars.
+struct TcaBenchmarks {
+    double arrival = 0;
+    double vwap = 0;
+    double close = 0;
+};
+
+/// Transaction cost analysis: each fill against its arrival price, the
+/// interval VWAP and the session close, taken from intraday bars of the
+/// symbol.
+///
+///   arrival — the quote an execution-algo child was sent against, else the
+///             open of the bar holding the order time (the last close before
+///             it, outside the session);
+///   vwap    — volume-weighted typical price of the bars between order and
+///             fill, or of a window centred on the fill when the two coincide;
+///   close   — the last bar of the fill's day.
+///
+/// Slippage is signed so a positive number is a cost for either side:
+/// side × (price − benchmark) / benchmark in bps, and side × (price −
+/// benchmark) × quantity in currency. Group averages weight bps by notional.
+/// Pure computation; TcaService gathers the fills and bars.
+class TcaEngine {
+  public:
+    static TcaBenchmarks benchmarks(const TcaFill& fill, const QVector<algo::OhlcvCandle>& bars,
+                                    int vwap_window_minutes);
+
+    /// {fills_total, fills_benchmarked, summary, by_strategy, by_broker,
+    /// by_symbol, fills (newest first, at most `max_fills`), unbenchmarked}.
+    /// `bars` is keyed by fill symbol.
+    static QJsonObject analyze(const QVector<TcaFill>& fills, const QHash<QString, QVector<algo::OhlcvCandle>>& bars,
+                               int vwap_window_minutes = 30, int max_fills = 500);
+};
+
+} // namespace fincept::services::tca
@@ -0,0 +1,430 @@
+// src/services/tca/TcaService.cpp
+#include "services/tca/TcaService.h"
+
+#include "algo_engine/CandleDataFetcher.h"
+#include "core/logging/Logger.h"
+#include "storage/sqlite/Database.h"
+#include "trading/AccountManager.h"
+#include "trading/BrokerRegistry.h"
+#include "trading/ExecutionAlgoManager.h"
+#include "trading/PaperTrading.h"
+
+#include <QDateTime>
+#include <QHash>
+#include <QJsonArray>
+#include <QPointer>
+#include <QSet>
+#include <QSqlQuery>
+#include <QTimeZone>
+#include <QUuid>
+#include <QtConcurrent>
+
+#include <algorithm>
+
+namespace fincept::services::tca {
+
+namespace {
+
+constexpr const char* kTcaTag = "TCA";
+constexpr qint64 kTcaDayMs = 24LL * 60 * 60 * 1000;
+constexpr int kTcaPaperTradeLimit = 5000;
+constexpr int kTcaIstOffsetSecs = 330 * 60;
+
+int tca_side(const QString& side) {
+    return side.compare(QLatin1String("sell"), Qt::CaseInsensitive) == 0 ? -1 : 1;
+}
+
+// Epoch ms of a stored or broker timestamp (0 = unparseable). Timestamps
+// without a zone are taken as UTC shifted back by `naive_offset_secs`.
+qint64 tca_parse_time(const QString& s, int naive_offset_secs = 0) {
+    const QString t = s.trimmed();
+    if (t.isEmpty())
+        return 0;
+    QDateTime dt = QDateTime::fromString(t, Qt::ISODateWithMs);
+    for (const char* fmt : {"yyyy-MM-dd HH:mm:ss", "yyyy-MM-dd HH:mm:ss.zzz", "dd-MMM-yyyy HH:mm:ss",
+                            "dd-MM-yyyy HH:mm:ss", "yyyy/MM/dd HH:mm:ss"}) {
+        if (dt.isValid())
+            break;
+        dt = QDateTime::fromString(t, QString::fromLatin1(fmt));
+    }
+    if (!dt.isValid())
+        return 0;
+    if (dt.timeSpec() == Qt::LocalTime) {
+        dt.setTimeZone(QTimeZone::UTC);
+        dt = dt.addSecs(-naive_offset_secs);
+    }
+    return dt.toMSecsSinceEpoch();
+}
+
+// Indian brokers report exchange-local (IST) times without a zone.
+int tca_naive_offset(const QString& exchange) {
+    static const QSet<QString> ist{"NSE", "BSE", "NFO", "BFO", "MCX", "CDS", "NCDEX"};
+    return ist.contains(exchange.toUpper()) ? kTcaIstOffsetSecs : 0;
+}
+
+bool tca_matches(const TcaFill& f, const TcaQuery& q) {
+    if (!q.sources.isEmpty() && !q.sources.contains(f.source))
+        return false;
+    if (!q.account_id.isEmpty() && f.account_id != q.account_id)
+        return false;
+    if (!q.broker.isEmpty() && f.broker.compare(q.broker, Qt::CaseInsensitive) != 0)
+        return false;
+    if (!q.strategy.isEmpty() && !f.strategy.contains(q.strategy, Qt::CaseInsensitive))
+        return false;
+    if (!q.symbol.isEmpty() && f.symbol.compare(q.symbol, Qt::CaseInsensitive) != 0)
+        return false;
+    return true;
+}
+
+// Execution-algo children with fills. Read on the main thread.
+QVector<TcaFill> tca_exec_fills(qint64 since_ms) {
+    QVector<TcaFill> out;
+    for (const auto& run : trading::ExecutionAlgoManager::instance().runs()) {
+        const auto account = trading::AccountManager::instance().get_account(run.params.account_id);
+        const QString broker = account.trading_mode == "paper" ? QStringLiteral("paper") : account.broker_id;
+        for (const auto& c : run.children) {
+            if (c.filled_qty <= 0 || c.avg_price <= 0 || c.sent_ms < since_ms)
+                continue;
+            TcaFill f;
+            f.source = QStringLiteral("exec");
+            f.id = c.order_id;
+            f.account_id = account.account_id;
+            f.account = account.display_name;
+            f.broker = broker;
+            f.strategy = "exec:" + QString::fromLatin1(trading::exec_algo_type_str(run.params.type));
+            f.symbol = run.params.symbol;
+            f.side = run.params.side == trading::OrderSide::Sell ? -1 : 1;
+            f.quantity = c.filled_qty;
+            f.price = c.avg_price;
+            f.time_ms = c.sent_ms; // children keep their send time only
+            f.arrival_hint = c.reference_price;
+            out.append(f);
+        }
+    }
+    return out;
+}
+
+// Algo deployment fills, with the signal time as the order time.
+QVector<TcaFill> tca_algo_fills(qint64 since_ms) {
+    QVector<TcaFill> out;
+    const QString since =
+        QDateTime::fromMSecsSinceEpoch(since_ms, QTimeZone::UTC).toString(QStringLiteral("yyyy-MM-dd HH:mm:ss"));
+    auto r = fincept::Database::instance().execute(
+        "SELECT t.id, t.symbol, t.side, t.quantity, t.price, t.broker_order_id, t.leg_symbol, t.leg_index, "
+        "       t.created_at, d.strategy_name, d.mode, d.broker_id, d.broker_account_id, "
+        "       (SELECT MIN(s.created_at) FROM algo_order_signals s "
+        "         WHERE t.broker_order_id <> '' AND s.broker_order_id = t.broker_order_id) AS signal_at "
+        "FROM algo_trades t LEFT JOIN algo_deployments d ON d.id = t.deployment_id "
+        "WHERE t.created_at >= ? ORDER BY t.created_at",
+        {since});
+    if (r.is_err()) {
+        LOG_WARN(kTcaTag, "algo_trades query failed: " + QString::fromStdString(r.error()));
+        return out;
+    }
+    auto& q = r.value();
+    while (q.next()) {
+        const bool leg = q.value("leg_index").toInt() >= 0 && !q.value("leg_symbol").toString().isEmpty();
+        const QString order_id = q.value("broker_order_id").toString();
+        const QString account_id = q.value("broker_account_id").toString();
+        TcaFill f;
+        f.source = QStringLiteral("algo");
+        f.id = order_id.isEmpty() ? q.value("id").toString() : order_id;
+        f.account_id = account_id;
+        if (!account_id.isEmpty())
+            f.account = trading::AccountManager::instance().get_account(account_id).display_name;
+        f.broker = q.value("mode").toString() == "live" ? q.value("broker_id").toString() : QStringLiteral("paper");
+        f.strategy = q.value("strategy_name").toString();
+        f.symbol = leg ? q.value("leg_symbol").toString() : q.value("symbol").toString();
+        f.side = tca_side(q.value("side").toString());
+        f.quantity = q.value("quantity").toDouble();
+        f.price = q.value("price").toDouble();
+        f.time_ms = tca_parse_time(q.value("created_at").toString());
+        f.order_time_ms = tca_parse_time(q.value("signal_at").toString());
+        if (f.quantity > 0 && f.price > 0)
+            out.append(f);
+    }
+    return out;
+}
+
+// Paper-portfolio trades, with the paper order's creation as the order time.
+QVector<TcaFill> tca_paper_fills(qint64 since_ms, const QSet<QString>& claimed) {
+    QHash<QString, trading::BrokerAccount> by_portfolio;
+    for (const auto& a : trading::AccountManager::instance().list_accounts())
+        if (!a.paper_portfolio_id.isEmpty())
+            by_portfolio.insert(a.paper_portfolio_id, a);
+
+    QVector<TcaFill> out;
+    for (const auto& p : trading::pt_list_portfolios()) {
+        const auto trades = trading::pt_get_trades(p.id, kTcaPaperTradeLimit);
+        if (trades.isEmpty())
+            continue;
+        QHash<QString, qint64> order_times;
+        for (const auto& o : trading::pt_get_orders(p.id))
+            order_times.insert(o.id, tca_parse_time(o.created_at));
+        const auto account = by_portfolio.value(p.id);
+        for (const auto& t : trades) {
+            if (claimed.contains(t.order_id))
+                continue;
+            TcaFill f;
+            f.source = QStringLiteral("paper");
+            f.id = t.order_id.isEmpty() ? t.id : t.order_id;
+            f.account_id = account.account_id;
+            f.account = account.account_id.isEmpty() ? p.name : account.display_name;
+            f.broker = account.broker_id.isEmpty() ? QStringLiteral("paper") : account.broker_id;
+            f.strategy = QStringLiteral("manual");
+            f.symbol = t.symbol;
+            f.side = tca_side(t.side);
+            f.quantity = t.quantity;
+            f.price = t.price;
+            f.fee = t.fee;
+            f.time_ms = tca_parse_time(t.timestamp);
+            f.order_time_ms = order_times.value(t.order_id);
+            if (f.time_ms >= since_ms && f.quantity > 0 && f.price > 0)
+                out.append(f);
+        }
+    }
+    return out;
+}
+
+// Filled orders in today's book of each active live account.
+QVector<TcaFill> tca_live_fills(qint64 since_ms, const QSet<QString>& claimed) {
+    QVector<TcaFill> out;
+    for (const auto& a : trading::AccountManager::instance().active_accounts()) {
+        if (a.trading_mode != "live")
+            continue;
+        auto* broker = trading::BrokerRegistry::instance().get(a.broker_id);
+        if (!broker)
+            continue;
+        const auto resp = broker->get_orders(trading::AccountManager::instance().load_credentials(a.account_id));
+        if (!resp.success || !resp.data) {
+            LOG_WARN(kTcaTag, QString("Order book of %1 unavailable: %2").arg(a.display_name, resp.error));
+            continue;
+        }
+        for (const auto& o : *resp.data) {
+            if (o.filled_qty <= 0 || o.avg_price <= 0 || claimed.contains(o.order_id))
+                continue;
+            TcaFill f;
+            f.source = QStringLiteral("live");
+            f.id = o.order_id;
+            f.account_id = a.account_id;
+            f.account = a.display_name;
+            f.broker = a.broker_id;
+            f.strategy = QStringLiteral("manual");
+            f.symbol = o.symbol;
+            f.side = tca_side(o.side);
+            f.quantity = o.filled_qty;
+            f.price = o.avg_price;
+            f.time_ms = tca_parse_time(o.timestamp, tca_naive_offset(o.exchange));
+            if (f.time_ms >= since_ms)
+                out.append(f);
+        }
+    }
+    return out;
+}
+
+// Finest Yahoo interval whose history still reaches `age_days` back.
+QString tca_timeframe(int age_days) {
+    if (age_days <= 7)
+        return QStringLiteral("1m");
+    if (age_days <= 59)
+        return QStringLiteral("5m");
+    return QStringLiteral("1h");
+}
+
+QString tca_num(const QJsonValue& v, int decimals = 2) {
+    return v.isDouble() ? QString::number(v.toDouble(), 'f', decimals) : QStringLiteral("n/a");
+}
+
+QString tca_cell(QString s) {
+    return s.replace(',', ' ').replace('|', ' ');
+}
+
+report::ReportComponent tca_component(const QString& type, const QString& content,
+                                      const QMap<QString, QString>& config = {}) {
+    report::ReportComponent c;
+    c.type = type;
+    c.content = content;
+    c.config = config;
+    return c;
+}
+
+report::ReportComponent tca_group_table(const QString& label, const QJsonArray& groups, int max_rows) {
+    QStringList rows{label + ",Fills,Notional,Arrival bps,VWAP bps,Close bps,Arrival cost,Fees"};
+    for (int i = 0; i < groups.size() && i < max_rows; ++i) {
+        const QJsonObject g = groups[i].toObject();
+        rows << QStringList{tca_cell(g["key"].toString()),
+                            QString::number(g["fills"].toInt()),
+                            tca_num(g["notional"]),
+                            tca_num(g["arrival_bps"]),
+                            tca_num(g["vwap_bps"]),
+                            tca_num(g["close_bps"]),
+                            tca_num(g["arrival_cost"]),
+                            tca_num(g["fees"])}
+                    .join(',');
+    }
+    return tca_component(QStringLiteral("table"), {}, {{"csv", rows.join('|')}});
+}
+
+} // namespace
+
+TcaQuery TcaQuery::from_json(const QJsonObject& o) {
+    TcaQuery q;
+    q.days = std::clamp(o.value("days").toInt(q.days), 1, 730);
+    const QJsonValue src = o.value("sources");
+    if (src.isArray()) {
+        for (const auto& v : src.toArray())
+            if (!v.toString().trimmed().isEmpty())
+                q.sources << v.toString().trimmed().toLower();
+    } else if (!src.toString().trimmed().isEmpty() && src.toString() != "all") {
+        q.sources << src.toString().trimmed().toLower();
+    }
+    q.account_id = o.value("account_id").toString().trimmed();
+    q.broker = o.value("broker").toString().trimmed();
+    q.strategy = o.value("strategy").toString().trimmed();
+    q.symbol = o.value("symbol").toString().trimmed();
+    q.vwap_window_minutes = std::clamp(o.value("vwap_window_minutes").toInt(q.vwap_window_minutes), 1, 390);
+    q.max_fills = std::clamp(o.value("max_fills").toInt(q.max_fills), 0, 5000);
+    return q;
+}
+
+QJsonObject TcaQuery::to_json() const {
+    QJsonObject o{{"days", days},
+                  {"sources", QJsonArray::fromStringList(sources)},
+                  {"vwap_window_minutes", vwap_window_minutes}};
+    if (!account_id.isEmpty())
+        o["account_id"] = account_id;
+    if (!broker.isEmpty())
+        o["broker"] = broker;
+    if (!strategy.isEmpty())
+        o["strategy"] = strategy;
+    if (!symbol.isEmpty())
+        o["symbol"] = symbol;
+    return o;
+}
+
+TcaService& TcaService::instance() {
+    static TcaService s;
+    return s;
+}
+
+QString TcaService::run(const TcaQuery& query) {
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    const qint64 since_ms = QDateTime::currentMSecsSinceEpoch() - qint64(query.days) * kTcaDayMs;
+    const QVector<TcaFill> exec = exec_fills(since_ms);
+
+    QPointer<TcaService> self = this;
+    (void)QtConcurrent::run([self, request_id, query, since_ms, exec]() {
+        const QVector<TcaFill> fills = ledger_fills(since_ms, exec);
+        QVector<TcaFill> matched;
+        for (const auto& f : fills)
+            if (f.time_ms > 0 && tca_matches(f, query))
+                matched.append(f);
+
+        QMetaObject::invokeMethod(
+            self,
+            [self, request_id, query, matched]() {
+                if (self)
+                    self->fetch_and_analyze(request_id, query, matched);
+            },
+            Qt::QueuedConnection);
+    });
+    return request_id;
+}
+
+QVector<TcaFill> TcaService::exec_fills(qint64 since_ms) {
+    return tca_exec_fills(since_ms);
+}
+
+QVector<TcaFill> TcaService::ledger_fills(qint64 since_ms, const QVector<TcaFill>& exec) {
+    // Most specific source first; an order id is counted once.
+    QVector<TcaFill> fills = exec;
+    fills += tca_algo_fills(since_ms);
+    QSet<QString> claimed;
+    for (const auto& f : fills)
+        claimed.insert(f.id);
+    fills += tca_paper_fills(since_ms, claimed);
+    fills += tca_live_fills(since_ms, claimed);
+    return fills;
+}
+
+void TcaService::fetch_and_analyze(const QString& request_id, const TcaQuery& query, const QVector<TcaFill>& fills) {
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    qint64 oldest = now;
+    QStringList symbols;
+    for (const auto& f : fills) {
+        oldest = std::min(oldest, std::min(f.time_ms, f.order_time_ms > 0 ? f.order_time_ms : f.time_ms));
+        if (!symbols.contains(f.symbol))
+            symbols << f.symbol;
+    }
+    const int age_days = int((now - oldest) / kTcaDayMs) + 1;
+    const QString timeframe = tca_timeframe(age_days);
+
+    auto finish = [this, request_id, query, fills, timeframe](const QHash<QString, QVector<algo::OhlcvCandle>>& bars,
+                                                              const QStringList& errors) {
+        QJsonObject report = TcaEngine::analyze(fills, bars, query.vwap_window_minutes, query.max_fills);
+        report["request_id"] = request_id;
+        report["query"] = query.to_json();
+        report["timeframe"] = timeframe;
+        report["generated_at"] = QDateTime::currentDateTimeUtc().toString(Qt::ISODate);
+        if (!errors.isEmpty())
+            report["data_errors"] = QJsonArray::fromStringList(errors);
+        last_ = report;
+        LOG_INFO(kTcaTag, QString("Report %1: %2 fills, %3 benchmarked on %4 bars")
+                              .arg(request_id.left(8))
+                              .arg(fills.size())
+                              .arg(report["fills_benchmarked"].toInt())
+                              .arg(timeframe));
+        emit report_ready(request_id, report);
+    };
+
+    if (symbols.isEmpty()) {
+        finish({}, {});
+        return;
+    }
+    algo::CandleDataFetcher::instance().fetch_multi(symbols, timeframe, std::min(age_days + 1, 730),
+                                                    algo::DataSource::YFinance, {}, {}, finish);
+}
+
+QVector<report::ReportComponent> TcaService::report_components(const QJsonObject& report) {
+    const QJsonObject s = report["summary"].toObject();
+    const QJsonObject q = report["query"].toObject();
+    QVector<report::ReportComponent> out;
+    out << tca_component(QStringLiteral("heading"), QStringLiteral("Transaction Cost Analysis"));
+    out << tca_component(
+        QStringLiteral("text"),
+        QString("**%1** fills over the last %2 days, **%3** benchmarked against %4 bars. Slippage is in basis "
+                "points of the benchmark and signed so a **positive number is a cost** for buys and sells alike; "
+                "averages are notional-weighted.")
+            .arg(report["fills_total"].toInt())
+            .arg(q["days"].toInt())
+            .arg(report["fills_benchmarked"].toInt())
+            .arg(report["timeframe"].toString()));
+    out << tca_component(QStringLiteral("stats_block"), {},
+                         {{"title", QStringLiteral("Execution Costs")},
+                          {"data", QStringList{"Notional:" + tca_num(s["notional"]),
+                                              "vs Arrival:" + tca_num(s["arrival_bps"]) + " bps (" +
+                                                  tca_num(s["arrival_cost"]) + ")",
+                                              "vs Interval VWAP:" + tca_num(s["vwap_bps"]) + " bps (" +
+                                                  tca_num(s["vwap_cost"]) + ")",
+                                              "vs Close:" + tca_num(s["close_bps"]) + " bps (" +
+                                                  tca_num(s["close_cost"]) + ")",
+                                              "Fees:" + tca_num(s["fees"])}
+                                       .join('\n')}});
+    out << tca_component(QStringLiteral("heading"), QStringLiteral("By Strategy"));
+    out << tca_group_table(QStringLiteral("Strategy"), report["by_strategy"].toArray(), 25);
+    out << tca_component(QStringLiteral("heading"), QStringLiteral("By Broker"));
+    out << tca_group_table(QStringLiteral("Broker"), report["by_broker"].toArray(), 25);
+    out << tca_component(QStringLiteral("heading"), QStringLiteral("By Symbol"));
+    out << tca_group_table(QStringLiteral("Symbol"), report["by_symbol"].toArray(), 25);
+    const int unbenchmarked = s["unbenchmarked"].toInt();
+    if (unbenchmarked > 0) {
+        out << tca_component(QStringLiteral("callout"),
+                             QString("%1 fills had no intraday bars to compare against (unsupported symbols or "
+                                     "fills older than the bar history) and are left out of the figures above.")
+                                 .arg(unbenchmarked),
+                             {{"style", QStringLiteral("info")}, {"heading", QStringLiteral("Coverage")}});
+    }
+    return out;
+}
+
+} // namespace fincept::services::tca
@@ -0,0 +1,74 @@
+// src/services/tca/TcaService.h
+#pragma once
+#include "core/report/ReportDocument.h"
+#include "services/tca/TcaEngine.h"
+
+#include <QJsonObject>
+#include <QObject>
+#include <QStringList>
+
+namespace fincept::services::tca {
+
+/// Which fills a TCA report covers. Empty filters match everything.
+struct TcaQuery {
+    int days = 30;
+    QStringList sources; // paper | live | algo | exec
+    QString account_id;
+    QString broker;
+    QString strategy; // substring, case-insensitive
+    QString symbol;
+    int vwap_window_minutes = 30;
+    int max_fills = 500;
+
+    static TcaQuery from_json(const QJsonObject& o);
+    QJsonObject to_json() const;
+};
+
+/// Transaction cost analysis over the fills the terminal has executed:
+///   paper — paper-portfolio trades (manual and UI orders);
+///   live  — filled orders in today's order book of each active live account;
+///   algo  — algo deployment fills (algo_trades), by strategy;
+///   exec  — execution-algo children (ExecutionAlgoManager), by algo.
+/// A fill is counted once, under the most specific source that placed it.
+///
+/// run() gathers the fills on a worker, fetches intraday bars for their
+/// symbols (the finest Yahoo interval that still reaches the oldest fill) and
+/// scores them with TcaEngine. Main thread only.
+class TcaService : public QObject {
+    Q_OBJECT
+  public:
+    static TcaService& instance();
+
+    /// Starts a report; returns its request id, which report_ready and
+    /// error_occurred carry.
+    QString run(const TcaQuery& query);
+
+    /// Last report produced; empty before the first one.
+    QJsonObject last_report() const { return last_; }
+
+    /// Report-builder components (heading, summary, tables by strategy,
+    /// broker and symbol) for a report produced by run().
+    static QVector<report::ReportComponent> report_components(const QJsonObject& report);
+
+    /// The fills ledger, for other consumers (trade journal). exec_fills()
+    /// reads the execution-algo runs and must be called on the main thread;
+    /// ledger_fills() adds the algo, paper and live fills since `since_ms`
+    /// behind `exec`, each order id once, and blocks on the database and
+    /// broker order books — call it from a worker.
+    static QVector<TcaFill> exec_fills(qint64 since_ms);
+    static QVector<TcaFill> ledger_fills(qint64 since_ms, const QVector<TcaFill>& exec);
+
+  signals:
+    void report_ready(QString request_id, QJsonObject report);
+    void error_occurred(QString request_id, QString message);
+
+  private:
+    TcaService() = default;
+    Q_DISABLE_COPY(TcaService)
+
+    void fetch_and_analyze(const QString& request_id, const TcaQuery& query, const QVector<TcaFill>& fills);
+
+    QJsonObject last_;
+};
+
+} // namespace fincept::services::tca
@@ -414,6 +414,7 @@ void WalletBalanceProducer::start_stream_for(const QString& pubkey) {
     auto* s = new StreamSession;
     s->pubkey = pubkey;
     s->ws = new WebSocketClient(this);
+    s->ws->set_label("wallet:" + pubkey.left(8));
     streams_.insert(pubkey, s);
 
     // Per-session FNCPT heartbeat — there's no per-account WS subscription
@@ -0,0 +1,175 @@
+// src/services/watchlist/WatchlistColumnService.cpp
+#include "services/watchlist/WatchlistColumnService.h"
+
+#include "algo_engine/CandleDataFetcher.h"
+#include "core/logging/Logger.h"
+#include "services/watchlist/WatchlistExpression.h"
+#include "storage/repositories/WatchlistRepository.h"
+
+#include <QDateTime>
+#include <QJsonArray>
+#include <QPointer>
+#include <QUuid>
+#include <QtConcurrent>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::services::watchlist {
+
+namespace {
+
+struct WcColumn {
+    WatchlistColumn def;
+    WatchlistExpression expr;
+    QString error; // compile error
+};
+
+/// Calendar days of history to ask for so `bars` daily bars come back
+/// (weekends and holidays), with a floor for short windows.
+int wc_days_for(int bars) {
+    return std::clamp(static_cast<int>(std::ceil(bars * 1.5)) + 10, 30, WatchlistColumnService::kMaxLookbackDays);
+}
+
+QJsonObject wc_evaluate(const QVector<WcColumn>& columns, const QStringList& symbols,
+                        const QHash<QString, QVector<algo::OhlcvCandle>>& bars,
+                        const QHash<QString, QString>& fetch_errors) {
+    QJsonArray rows;
+    for (const QString& sym : symbols) {
+        QJsonObject values, errors;
+        const auto it = bars.constFind(sym);
+        for (const auto& c : columns) {
+            QString err = c.error;
+            double v = std::nan("");
+            if (err.isEmpty()) {
+                if (it == bars.constEnd() || it->isEmpty())
+                    err = fetch_errors.value(sym, QStringLiteral("no history"));
+                else
+                    v = c.expr.evaluate(*it, &err);
+            }
+            values[c.def.name] = std::isfinite(v) ? QJsonValue(v) : QJsonValue();
+            if (!err.isEmpty())
+                errors[c.def.name] = err;
+        }
+        QJsonObject row{{"symbol", sym}, {"values", values}};
+        if (!errors.isEmpty())
+            row["errors"] = errors;
+        rows.append(row);
+    }
+    return QJsonObject{{"rows", rows}};
+}
+
+} // namespace
+
+WatchlistColumnService& WatchlistColumnService::instance() {
+    static WatchlistColumnService s;
+    return s;
+}
+
+QString WatchlistColumnService::refresh(const QString& watchlist_id, bool force) {
+    const QString request_id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    auto fail = [this, request_id](const QString& msg) {
+        QMetaObject::invokeMethod(
+            this, [this, request_id, msg]() { emit error_occurred(request_id, msg); }, Qt::QueuedConnection);
+        return request_id;
+    };
+
+    auto& repo = WatchlistRepository::instance();
+    auto stocks = repo.get_stocks(watchlist_id);
+    if (stocks.is_err())
+        return fail("Failed to load watchlist: " + QString::fromStdString(stocks.error()));
+    auto defs = repo.get_columns(watchlist_id);
+    if (defs.is_err())
+        return fail("Failed to load columns: " + QString::fromStdString(defs.error()));
+
+    QVector<WcColumn> columns;
+    QJsonArray column_json;
+    int lookback = 1;
+    for (const auto& d : defs.value()) {
+        WcColumn c{d, {}, {}};
+        c.expr = WatchlistExpression::compile(d.expression, &c.error);
+        if (c.expr.is_valid())
+            lookback = std::max(lookback, c.expr.lookback_bars());
+        QJsonObject cj{{"name", d.name}, {"expression", d.expression}, {"decimals", d.decimals}};
+        if (!c.error.isEmpty())
+            cj["error"] = c.error;
+        column_json.append(cj);
+        columns.append(c);
+    }
+
+    QStringList symbols;
+    for (const auto& s : stocks.value())
+        symbols.append(s.symbol);
+
+    const int days = wc_days_for(lookback);
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    QHash<QString, QVector<algo::OhlcvCandle>> have;
+    QStringList to_fetch;
+    for (const QString& sym : symbols) {
+        const auto it = cache_.constFind(sym);
+        if (!force && it != cache_.constEnd() && now - it->fetched_ms < kCacheTtlMs && it->days >= days)
+            have.insert(sym, it->bars);
+        else
+            to_fetch.append(sym);
+    }
+
+    QPointer<WatchlistColumnService> self = this;
+    auto finish = [self, request_id, watchlist_id, columns, column_json, symbols,
+                   fetched = int(to_fetch.size())](const QHash<QString, QVector<algo::OhlcvCandle>>& bars,
+                                                   const QHash<QString, QString>& fetch_errors) {
+        if (!self)
+            return;
+        (void)QtConcurrent::run([self, request_id, watchlist_id, columns, column_json, symbols, fetched, bars,
+                                 fetch_errors]() {
+            QJsonObject result = wc_evaluate(columns, symbols, bars, fetch_errors);
+            result["watchlist_id"] = watchlist_id;
+            result["columns"] = column_json;
+            result["fetched"] = fetched;
+            result["cached"] = int(symbols.size()) - fetched;
+            result["evaluated_at"] = QDateTime::currentDateTimeUtc().toString(Qt::ISODate);
+            if (!self)
+                return;
+            QMetaObject::invokeMethod(
+                self.data(),
+                [self, request_id, watchlist_id, result]() {
+                    if (self)
+                        emit self->values_ready(request_id, watchlist_id, result);
+                },
+                Qt::QueuedConnection);
+        });
+    };
+
+    if (columns.isEmpty() || symbols.isEmpty() || to_fetch.isEmpty()) {
+        QMetaObject::invokeMethod(
+            this, [finish, have]() { finish(have, {}); }, Qt::QueuedConnection);
+        return request_id;
+    }
+
+    algo::CandleDataFetcher::instance().fetch_multi(
+        to_fetch, "1d", days, algo::DataSource::YFinance, {}, {},
+        [self, have, to_fetch, days, finish](const QHash<QString, QVector<algo::OhlcvCandle>>& data,
+                                             const QStringList& errors) {
+            if (!self)
+                return;
+            QHash<QString, QVector<algo::OhlcvCandle>> bars = have;
+            const qint64 fetched_at = QDateTime::currentMSecsSinceEpoch();
+            for (auto it = data.constBegin(); it != data.constEnd(); ++it) {
+                bars.insert(it.key(), it.value());
+                self->cache_.insert(it.key(), CachedBars{it.value(), fetched_at, days});
+            }
+            // fetch_multi reports failures as "SYMBOL: reason".
+            QHash<QString, QString> fetch_errors;
+            for (const QString& e : errors) {
+                const int sep = e.indexOf(':');
+                if (sep > 0)
+                    fetch_errors.insert(e.left(sep).trimmed(), e.mid(sep + 1).trimmed());
+            }
+            if (!errors.isEmpty())
+                LOG_WARN("WatchlistColumns",
+                         QString("%1 of %2 symbols had no history").arg(errors.size()).arg(to_fetch.size()));
+            finish(bars, fetch_errors);
+        });
+    return request_id;
+}
+
+} // namespace fincept::services::watchlist
@@ -0,0 +1,52 @@
+// src/services/watchlist/WatchlistColumnService.h
+#pragma once
+#include "algo_engine/AlgoEngineTypes.h"
+
+#include <QHash>
+#include <QJsonObject>
+#include <QObject>
+#include <QString>
+#include <QVector>
+
+namespace fincept::services::watchlist {
+
+/// Evaluates a watchlist's computed columns (WatchlistRepository::get_columns)
+/// for every symbol in it.
+///
+/// One refresh compiles each column once, fetches daily history for all
+/// symbols in a single CandleDataFetcher::fetch_multi batch — only for
+/// symbols whose cached bars are stale or too short for the longest column —
+/// and evaluates on a worker thread. Bars are cached in memory for
+/// kCacheTtlMs. Main thread only.
+class WatchlistColumnService : public QObject {
+    Q_OBJECT
+  public:
+    static constexpr qint64 kCacheTtlMs = 15 * 60 * 1000;
+    static constexpr int kMaxLookbackDays = 3650;
+
+    static WatchlistColumnService& instance();
+
+    /// Starts a refresh; returns its request id, which values_ready and
+    /// error_occurred carry. `force` ignores the bar cache.
+    QString refresh(const QString& watchlist_id, bool force = false);
+
+  signals:
+    /// {watchlist_id, columns[{name, expression, decimals, error?}],
+    ///  rows[{symbol, values{name: number|null}, errors{name: message}}],
+    ///  fetched, cached, evaluated_at}
+    void values_ready(QString request_id, QString watchlist_id, QJsonObject result);
+    void error_occurred(QString request_id, QString message);
+
+  private:
+    WatchlistColumnService() = default;
+    Q_DISABLE_COPY(WatchlistColumnService)
+
+    struct CachedBars {
+        QVector<algo::OhlcvCandle> bars;
+        qint64 fetched_ms = 0;
+        int days = 0; // lookback the fetch asked for
+    };
+    QHash<QString, CachedBars> cache_;
+};
+
+} // namespace fincept::services::watchlist
@@ -0,0 +1,490 @@
+// src/services/watchlist/WatchlistExpression.cpp
+#include "services/watchlist/WatchlistExpression.h"
+
+#include "algo_engine/IndicatorEngine.h"
+
+#include <QJsonObject>
+
+#include <algorithm>
+#include <cmath>
+#include <limits>
+#include <vector>
+
+namespace fincept::services::watchlist {
+
+struct WatchlistExpression::Node {
+    enum Kind { Number, Call, Neg, Binary, Variable };
+    Kind kind = Number;
+    double value = 0;
+    QString name; // Call: function / field name; Binary: operator; Variable: caller-supplied name
+    std::vector<std::shared_ptr<const Node>> args;
+};
+
+namespace {
+
+using Node = WatchlistExpression::Node;
+using NodePtr = std::shared_ptr<const Node>;
+
+constexpr double kWxNaN = std::numeric_limits<double>::quiet_NaN();
+constexpr int kWxUnknownWindow = 252; // lookback assumed for a non-constant window
+
+const QStringList kWxFields = {"close", "open", "high", "low", "volume"};
+const QStringList kWxWindowFns = {"highest", "lowest", "change", "avg_volume", "stdev", "volatility"};
+const QStringList kWxIndicators = {"sma", "ema", "wma",        "dema", "tema", "rsi",
+                                   "cci", "mfi", "williams_r", "roc",  "trix", "atr"};
+// Recursive smoothing needs a few periods of warm-up to settle.
+const QStringList kWxSmoothed = {"ema", "dema", "tema", "trix", "rsi", "atr"};
+
+/// Accepted argument counts per name; empty when the name is unknown.
+QVector<int> wx_arities(const QString& name) {
+    if (kWxFields.contains(name))
+        return {0, 1};
+    if (name == "bars")
+        return {0};
+    if (kWxWindowFns.contains(name) || kWxIndicators.contains(name) || name == "abs" || name == "sqrt" ||
+        name == "log")
+        return {1};
+    if (name == "min" || name == "max")
+        return {2};
+    if (name == "round")
+        return {1, 2};
+    return {};
+}
+
+class WxParser {
+  public:
+    WxParser(const QString& src, const QStringList& variables) : src_(src), variables_(variables) {}
+
+    NodePtr parse(QString* error) {
+        NodePtr root = comparison();
+        skip_ws();
+        if (root && pos_ < src_.size())
+            fail(QString("unexpected '%1'").arg(src_[pos_]));
+        if (!error_.isEmpty()) {
+            *error = error_;
+            return nullptr;
+        }
+        return root;
+    }
+
+    const QStringList& used() const { return used_; }
+
+  private:
+    void skip_ws() {
+        while (pos_ < src_.size() && src_[pos_].isSpace())
+            ++pos_;
+    }
+
+    bool eat(const QString& tok) {
+        skip_ws();
+        if (src_.mid(pos_, tok.size()) != tok)
+            return false;
+        pos_ += static_cast<int>(tok.size());
+        return true;
+    }
+
+    NodePtr fail(const QString& msg) {
+        if (error_.isEmpty())
+            error_ = QString("%1 at position %2").arg(msg).arg(pos_ + 1);
+        return nullptr;
+    }
+
+    static NodePtr binary(const QString& op, NodePtr lhs, NodePtr rhs) {
+        auto n = std::make_shared<Node>();
+        n->kind = Node::Binary;
+        n->name = op;
+        n->args = {std::move(lhs), std::move(rhs)};
+        return n;
+    }
+
+    NodePtr comparison() {
+        NodePtr lhs = additive();
+        if (!lhs)
+            return nullptr;
+        for (const QString op : {"<=", ">=", "==", "!=", "<", ">"}) {
+            if (eat(op)) {
+                NodePtr rhs = additive();
+                return rhs ? binary(op, lhs, rhs) : nullptr;
+            }
+        }
+        return lhs;
+    }
+
+    NodePtr additive() {
+        NodePtr lhs = multiplicative();
+        while (lhs) {
+            const QString op = eat("+") ? "+" : eat("-") ? "-" : QString();
+            if (op.isEmpty())
+                break;
+            NodePtr rhs = multiplicative();
+            lhs = rhs ? binary(op, lhs, rhs) : nullptr;
+        }
+        return lhs;
+    }
+
+    NodePtr multiplicative() {
+        NodePtr lhs = unary();
+        while (lhs) {
+            const QString op = eat("*") ? "*" : eat("/") ? "/" : QString();
+            if (op.isEmpty())
+                break;
+            NodePtr rhs = unary();
+            lhs = rhs ? binary(op, lhs, rhs) : nullptr;
+        }
+        return lhs;
+    }
+
+    NodePtr unary() {
+        if (eat("-")) {
+            NodePtr operand = unary();
+            if (!operand)
+                return nullptr;
+            auto n = std::make_shared<Node>();
+            n->kind = Node::Neg;
+            n->args = {operand};
+            return n;
+        }
+        if (eat("+"))
+            return unary();
+        return power();
+    }
+
+    NodePtr power() {
+        NodePtr base = primary();
+        if (base && eat("^")) {
+            NodePtr exp = unary(); // right-associative: 2^3^2 = 2^9
+            return exp ? binary("^", base, exp) : nullptr;
+        }
+        return base;
+    }
+
+    NodePtr primary() {
+        skip_ws();
+        if (pos_ >= src_.size())
+            return fail("unexpected end of expression");
+
+        if (eat("(")) {
+            NodePtr inner = comparison();
+            if (inner && !eat(")"))
+                return fail("expected ')'");
+            return inner;
+        }
+
+        const QChar c = src_[pos_];
+        if (c.isDigit() || c == '.') {
+            const int start = pos_;
+            while (pos_ < src_.size() && (src_[pos_].isDigit() || src_[pos_] == '.'))
+                ++pos_;
+            bool ok = false;
+            const double v = src_.mid(start, pos_ - start).toDouble(&ok);
+            if (!ok)
+                return fail("bad number");
+            auto n = std::make_shared<Node>();
+            n->value = v;
+            return n;
+        }
+
+        if (c.isLetter() || c == '_') {
+            const int start = pos_;
+            while (pos_ < src_.size() && (src_[pos_].isLetterOrNumber() || src_[pos_] == '_'))
+                ++pos_;
+            auto n = std::make_shared<Node>();
+            n->kind = Node::Call;
+            n->name = src_.mid(start, pos_ - start).toLower();
+            const QVector<int> arities = wx_arities(n->name);
+            if (arities.isEmpty() && variables_.contains(n->name)) {
+                n->kind = Node::Variable;
+                if (!used_.contains(n->name))
+                    used_.append(n->name);
+                return n;
+            }
+            if (arities.isEmpty()) {
+                pos_ = start;
+                return fail(QString("unknown name '%1'").arg(n->name));
+            }
+            if (eat("(")) {
+                if (!eat(")")) {
+                    do {
+                        NodePtr arg = comparison();
+                        if (!arg)
+                            return nullptr;
+                        n->args.push_back(arg);
+                    } while (eat(","));
+                    if (!eat(")"))
+                        return fail("expected ')' or ','");
+                }
+            }
+            if (!arities.contains(static_cast<int>(n->args.size())))
+                return fail(QString("%1 takes %2 argument(s), got %3")
+                                .arg(n->name)
+                                .arg(arities.size() == 1 ? QString::number(arities[0])
+                                                         : QString("%1-%2").arg(arities.first()).arg(arities.last()))
+                                .arg(n->args.size()));
+            return n;
+        }
+
+        return fail(QString("unexpected '%1'").arg(c));
+    }
+
+    const QString& src_;
+    const QStringList& variables_;
+    QStringList used_; // variables referenced, in first-use order
+    int pos_ = 0;
+    QString error_;
+};
+
+int wx_lookback(const Node& n) {
+    int need = 1;
+    for (const auto& a : n.args)
+        need = std::max(need, wx_lookback(*a));
+    if (n.kind != Node::Call || n.args.empty())
+        return need;
+
+    const Node& arg = *n.args[0];
+    const int k = arg.kind == Node::Number ? std::max(0, static_cast<int>(arg.value)) : kWxUnknownWindow;
+    if (kWxFields.contains(n.name))
+        return std::max(need, k + 1);
+    if (kWxSmoothed.contains(n.name))
+        return std::max(need, 3 * k + 1);
+    if (kWxWindowFns.contains(n.name) || kWxIndicators.contains(n.name))
+        return std::max(need, k + 1);
+    return need;
+}
+
+class WxEvaluator {
+  public:
+    WxEvaluator(const QVector<algo::OhlcvCandle>& bars, const QHash<QString, double>& variables, QString* error)
+        : bars_(bars), variables_(variables), error_(error) {}
+
+    double eval(const Node& n) {
+        switch (n.kind) {
+            case Node::Number:
+                return n.value;
+            case Node::Neg:
+                return -eval(*n.args[0]);
+            case Node::Binary:
+                return binary(n.name, eval(*n.args[0]), eval(*n.args[1]));
+            case Node::Call:
+                return call(n);
+            case Node::Variable: {
+                const auto it = variables_.constFind(n.name);
+                if (it == variables_.constEnd() || std::isnan(*it))
+                    return fail(QString("no value for '%1'").arg(n.name));
+                return *it;
+            }
+        }
+        return kWxNaN;
+    }
+
+  private:
+    double fail(const QString& msg) {
+        if (error_ && error_->isEmpty())
+            *error_ = msg;
+        return kWxNaN;
+    }
+
+    double binary(const QString& op, double a, double b) {
+        if (op == "+")
+            return a + b;
+        if (op == "-")
+            return a - b;
+        if (op == "*")
+            return a * b;
+        if (op == "/")
+            return b == 0 ? fail("division by zero") : a / b;
+        if (op == "^")
+            return std::pow(a, b);
+        if (std::isnan(a) || std::isnan(b))
+            return kWxNaN;
+        if (op == "<")
+            return a < b;
+        if (op == "<=")
+            return a <= b;
+        if (op == ">")
+            return a > b;
+        if (op == ">=")
+            return a >= b;
+        if (op == "==")
+            return a == b;
+        return a != b;
+    }
+
+    /// The window argument as a bar count, checked against the history.
+    bool window(const Node& n, int extra, int* out) {
+        const double v = eval(*n.args[0]);
+        if (std::isnan(v) || v < 1) {
+            fail(QString("%1: window must be at least 1").arg(n.name));
+            return false;
+        }
+        *out = static_cast<int>(std::lround(v));
+        if (*out + extra > bars_.size()) {
+            fail(QString("%1(%2) needs %3 bars, have %4").arg(n.name).arg(*out).arg(*out + extra).arg(bars_.size()));
+            return false;
+        }
+        return true;
+    }
+
+    static double field(const algo::OhlcvCandle& c, const QString& name) {
+        if (name == "open")
+            return c.open;
+        if (name == "high")
+            return c.high;
+        if (name == "low")
+            return c.low;
+        if (name == "volume")
+            return c.volume;
+        return c.close;
+    }
+
+    /// Daily % returns of the last n bars.
+    std::vector<double> returns(int n) const {
+        std::vector<double> r;
+        const int last = static_cast<int>(bars_.size()) - 1;
+        for (int i = last - n + 1; i <= last; ++i) {
+            const double prev = bars_[i - 1].close;
+            if (prev != 0)
+                r.push_back((bars_[i].close / prev - 1.0) * 100.0);
+        }
+        return r;
+    }
+
+    double call(const Node& n) {
+        const QString& f = n.name;
+        const int last = static_cast<int>(bars_.size()) - 1;
+
+        if (f == "bars")
+            return bars_.size();
+        if (kWxFields.contains(f)) {
+            int ago = 0;
+            if (!n.args.empty()) {
+                const double v = eval(*n.args[0]);
+                if (std::isnan(v) || v < 0)
+                    return fail(QString("%1: bars ago must be 0 or more").arg(f));
+                ago = static_cast<int>(std::lround(v));
+            }
+            if (ago > last)
+                return fail(QString("%1(%2) needs %3 bars, have %4").arg(f).arg(ago).arg(ago + 1).arg(bars_.size()));
+            return field(bars_[last - ago], f);
+        }
+
+        if (f == "abs")
+            return std::abs(eval(*n.args[0]));
+        if (f == "sqrt") {
+            const double v = eval(*n.args[0]);
+            return v < 0 ? fail("sqrt of a negative number") : std::sqrt(v);
+        }
+        if (f == "log") {
+            const double v = eval(*n.args[0]);
+            return v <= 0 ? fail("log of a non-positive number") : std::log(v);
+        }
+        if (f == "min")
+            return std::min(eval(*n.args[0]), eval(*n.args[1]));
+        if (f == "max")
+            return std::max(eval(*n.args[0]), eval(*n.args[1]));
+        if (f == "round") {
+            const double scale = n.args.size() > 1 ? std::pow(10.0, std::lround(eval(*n.args[1]))) : 1.0;
+            return std::round(eval(*n.args[0]) * scale) / scale;
+        }
+
+        int w = 0;
+        if (f == "highest" || f == "lowest" || f == "avg_volume") {
+            if (!window(n, 0, &w))
+                return kWxNaN;
+            double acc = f == "highest" ? -std::numeric_limits<double>::infinity()
+                                        : f == "lowest" ? std::numeric_limits<double>::infinity() : 0.0;
+            for (int i = last - w + 1; i <= last; ++i) {
+                if (f == "highest")
+                    acc = std::max(acc, bars_[i].high);
+                else if (f == "lowest")
+                    acc = std::min(acc, bars_[i].low);
+                else
+                    acc += bars_[i].volume;
+            }
+            return f == "avg_volume" ? acc / w : acc;
+        }
+        if (f == "change") {
+            if (!window(n, 1, &w))
+                return kWxNaN;
+            const double base = bars_[last - w].close;
+            return base == 0 ? fail("change: zero base price") : (bars_[last].close / base - 1.0) * 100.0;
+        }
+        if (f == "stdev" || f == "volatility") {
+            if (!window(n, 1, &w))
+                return kWxNaN;
+            const std::vector<double> r = returns(w);
+            if (r.size() < 2)
+                return fail(QString("%1 needs at least 2 returns").arg(f));
+            double mean = 0;
+            for (double x : r)
+                mean += x;
+            mean /= static_cast<double>(r.size());
+            double ss = 0;
+            for (double x : r)
+                ss += (x - mean) * (x - mean);
+            const double sd = std::sqrt(ss / static_cast<double>(r.size() - 1));
+            return f == "volatility" ? sd * std::sqrt(252.0) : sd;
+        }
+
+        // IndicatorEngine names are upper-case; every one listed here reports
+        // its latest value under "value".
+        if (!window(n, 0, &w))
+            return kWxNaN;
+        const auto r = algo::IndicatorEngine::compute(f.toUpper(), bars_, QJsonObject{{"period", w}}, {});
+        if (!r.valid)
+            return fail(QString("%1(%2): %3").arg(f).arg(w).arg(r.error));
+        return r.current.value("value", kWxNaN);
+    }
+
+    const QVector<algo::OhlcvCandle>& bars_;
+    const QHash<QString, double>& variables_;
+    QString* error_;
+};
+
+} // namespace
+
+WatchlistExpression WatchlistExpression::compile(const QString& source, QString* error, const QStringList& variables) {
+    WatchlistExpression e;
+    e.source_ = source.trimmed();
+    if (e.source_.isEmpty()) {
+        *error = "empty expression";
+        return e;
+    }
+    WxParser parser(e.source_, variables);
+    e.root_ = parser.parse(error);
+    if (e.root_) {
+        e.lookback_ = wx_lookback(*e.root_);
+        e.variables_ = parser.used();
+    }
+    return e;
+}
+
+double WatchlistExpression::evaluate(const QVector<algo::OhlcvCandle>& bars, QString* error,
+                                     const QHash<QString, double>& variables) const {
+    if (!root_) {
+        if (error)
+            *error = "invalid expression";
+        return kWxNaN;
+    }
+    if (bars.isEmpty()) {
+        if (error)
+            *error = "no history";
+        return kWxNaN;
+    }
+    QString err;
+    WxEvaluator ev(bars, variables, &err);
+    const double v = ev.eval(*root_);
+    if (!err.isEmpty() || !std::isfinite(v)) {
+        if (error)
+            *error = err.isEmpty() ? QStringLiteral("result is not a finite number") : err;
+        return kWxNaN;
+    }
+    return v;
+}
+
+QStringList WatchlistExpression::vocabulary() {
+    QStringList out = kWxFields;
+    out << "bars" << kWxWindowFns << kWxIndicators << QStringList{"abs", "sqrt", "log", "min", "max", "round"};
+    return out;
+}
+
+} // namespace fincept::services::watchlist
@@ -0,0 +1,66 @@
+// src/services/watchlist/WatchlistExpression.h
+#pragma once
+#include "algo_engine/AlgoEngineTypes.h"
+
+#include <QHash>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+#include <memory>
+
+namespace fincept::services::watchlist {
+
+/// A compiled watchlist column expression — a small FinScript-style formula
+/// evaluated against one symbol's daily bars (oldest first, last = latest).
+///
+///   numbers, + - * / ^, unary -, parentheses, < <= > >= == != (1 or 0)
+///
+///   close open high low volume   latest bar; close(n) = n bars ago
+///   bars                         number of bars available
+///   highest(n) lowest(n)         highest high / lowest low of the last n bars
+///   change(n)                    % change of close over n bars
+///   avg_volume(n)                mean volume of the last n bars
+///   stdev(n) volatility(n)       stdev of daily % returns; annualised (√252)
+///   sma ema wma dema tema rsi cci williams_r mfi roc trix atr   (n)
+///   abs(x) sqrt(x) log(x) min(a, b) max(a, b) round(x[, digits])
+///
+/// e.g. "% from 52w high" = (close / highest(252) - 1) * 100
+///
+/// Callers can add named per-symbol values (e.g. point-in-time fundamentals
+/// such as `pe` or `roe`) that the expression reads like fields.
+class WatchlistExpression {
+  public:
+    struct Node;
+
+    /// Parses `source`; on failure the result is invalid and `*error` says
+    /// where. `variables` are extra zero-argument names the caller supplies
+    /// values for at evaluate() time; built-in names win over them.
+    static WatchlistExpression compile(const QString& source, QString* error, const QStringList& variables = {});
+
+    bool is_valid() const { return root_ != nullptr; }
+    QString source() const { return source_; }
+
+    /// Bars of history the expression needs (largest window it reads, with
+    /// warm-up for the smoothed indicators).
+    int lookback_bars() const { return lookback_; }
+
+    /// NaN with `*error` set (when non-null) on insufficient history, a
+    /// domain error or a variable missing from `variables`.
+    double evaluate(const QVector<algo::OhlcvCandle>& bars, QString* error = nullptr,
+                    const QHash<QString, double>& variables = {}) const;
+
+    /// Variables the expression reads, in first-use order.
+    QStringList variables() const { return variables_; }
+
+    /// Function and field names accepted by compile(), for help text.
+    static QStringList vocabulary();
+
+  private:
+    QString source_;
+    std::shared_ptr<const Node> root_;
+    QStringList variables_;
+    int lookback_ = 1;
+};
+
+} // namespace fincept::services::watchlist
@@ -1,5 +1,6 @@
 #include "services/workflow/nodes/SafetyNodes.h"
 
+#include "core/market/MarketCalendar.h"
 #include "services/workflow/NodeRegistry.h"
 
 #include <QDateTime>
@@ -195,7 +196,7 @@ void register_safety_nodes(NodeRegistry& registry) {
     });
 
     // ── Trading Hours Check ───────────────────────────────────────
-    // Uses QDateTime in UTC to check exchange trading hours.
+    // Session phase from the exchange calendar (holidays, DST, lunch breaks).
     registry.register_type({
         .type_id = "safety.trading_hours",
         .display_name = "Trading Hours Check",
@@ -212,7 +213,12 @@ void register_safety_nodes(NodeRegistry& registry) {
             },
         .parameters =
             {
-                {"exchange", "Exchange", "select", "NYSE", {"NYSE", "NASDAQ", "LSE", "TSE", "NSE", "BSE"}, ""},
+                {"exchange",
+                 "Exchange",
+                 "select",
+                 "NYSE",
+                 {"NYSE", "NASDAQ", "LSE", "TSE", "NSE", "BSE", "CRYPTO"},
+                 ""},
                 {"allow_premarket", "Allow Pre-Market", "boolean", false, {}, ""},
             },
         .execute =
@@ -221,44 +227,23 @@ void register_safety_nodes(NodeRegistry& registry) {
                 auto data = inputs.isEmpty() ? QJsonValue{} : inputs[0];
                 QJsonObject obj = data.isObject() ? data.toObject() : QJsonObject{};
 
-                QString exchange = params.value("exchange").toString("NYSE");
-                bool allow_premarket = params.value("allow_premarket").toBool(false);
-
-                QDateTime utc_now = QDateTime::currentDateTimeUtc();
-                int day_of_week = utc_now.date().dayOfWeek(); // 1=Mon, 7=Sun
-                int hour_utc = utc_now.time().hour();
-                int min_utc = utc_now.time().minute();
-                int time_utc = hour_utc * 100 + min_utc;
-
-                // Weekend check (universal)
-                bool is_weekend = (day_of_week == 6 || day_of_week == 7);
-
-                // Session hours in UTC
-                // NYSE/NASDAQ: 14:30-21:00 UTC (pre-market 09:00-14:30)
-                // LSE: 08:00-16:30 UTC
-                // TSE (Tokyo): 00:00-06:00 UTC (09:00-15:00 JST)
-                // NSE/BSE (India): 03:45-10:00 UTC (09:15-15:30 IST)
-                struct Session {
-                    int open;
-                    int close;
-                    int pre_open;
-                };
-                Session session{1430, 2100, 900};
-                if (exchange == "LSE")
-                    session = {800, 1630, 700};
-                else if (exchange == "TSE")
-                    session = {0, 600, 2300};
-                else if (exchange == "NSE" || exchange == "BSE")
-                    session = {345, 1000, 300};
-
-                bool in_regular = !is_weekend && time_utc >= session.open && time_utc < session.close;
-                bool in_premarket = !is_weekend && time_utc >= session.pre_open && time_utc < session.open;
-                bool is_open = in_regular || (allow_premarket && in_premarket);
+                const QString exchange = params.value("exchange").toString("NYSE");
+                const bool allow_premarket = params.value("allow_premarket").toBool(false);
+
+                const QDateTime utc_now = QDateTime::currentDateTimeUtc();
+                const auto status = core::market::MarketCalendar::instance().status(exchange, utc_now);
+                const bool in_regular = status.phase == core::market::SessionPhase::Regular;
+                const bool in_premarket = status.phase == core::market::SessionPhase::PreMarket;
+                const bool is_open = in_regular || (allow_premarket && in_premarket);
 
                 obj["market_open"] = is_open;
                 obj["exchange"] = exchange;
                 obj["utc_time"] = utc_now.toString("HH:mm");
-                obj["session_type"] = in_regular ? "regular" : (in_premarket ? "pre_market" : "closed");
+                obj["session_type"] = core::market::session_phase_name(status.phase);
+                if (status.next_open.isValid())
+                    obj["next_open"] = status.next_open.toString(Qt::ISODate);
+                if (!status.holiday.isEmpty())
+                    obj["holiday"] = status.holiday;
                 // Route: output_open = true branch, output_closed = false branch
                 obj["_branch"] = is_open ? "true" : "false";
                 cb(true, obj, {});
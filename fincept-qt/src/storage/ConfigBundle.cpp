@@ -0,0 +1,525 @@
+#include "storage/ConfigBundle.h"
+
+#include "core/layout/LayoutCatalog.h"
+#include "core/layout/LayoutTypes.h"
+#include "core/logging/Logger.h"
+#include "storage/repositories/SettingsRepository.h"
+#include "storage/secure/SecureStorage.h"
+#include "storage/sqlite/Database.h"
+
+#include <QDateTime>
+#include <QFile>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QSaveFile>
+#include <QSqlError>
+#include <QSqlQuery>
+#include <QSqlRecord>
+#include <QSysInfo>
+
+// FINCEPT_VERSION_STRING is injected by CMake; same fallback as SystemTools.
+#ifndef FINCEPT_VERSION_STRING
+#    define FINCEPT_VERSION_STRING "0.0.0-dev"
+#endif
+
+namespace fincept {
+
+namespace {
+
+constexpr const char* CB_TAG = "ConfigBundle";
+
+// Settings categories describing this device or session, not the user's setup.
+bool cb_local_category(const QString& category) {
+    static const QStringList kLocal = {"auth", "cloud", "startup", "algo"};
+    return kLocal.contains(category);
+}
+
+// SecureStorage keys bound to this machine or login: the Fincept session and
+// the PIN lock state. Everything else (broker, LLM, data-source keys) moves.
+bool cb_portable_secret(const QString& key) {
+    return key != QLatin1String("api_key") && key != QLatin1String("session_token") &&
+           !key.startsWith(QLatin1String("pin_"));
+}
+
+struct CbTable {
+    const char* section;
+    const char* table;
+    const char* drop;  ///< column not carried: autoincrement id or a machine-local reference
+    const char* match; ///< natural key ("a,b"); when set, rows already present are skipped, not replaced
+    const char* secret = nullptr; ///< columns holding keys / tokens ("a,b"); sealed with the credentials only
+    const char* key = nullptr;    ///< primary key the sealed secret columns are matched back on
+};
+
+// Imported in this order, parents before children.
+const CbTable kCbTables[] = {
+    {"settings", "llm_configs", nullptr, nullptr, "api_key", "provider"},
+    {"settings", "llm_global_settings", nullptr, nullptr},
+    {"settings", "llm_model_configs", nullptr, nullptr, "api_key", "id"},
+    {"settings", "llm_profiles", nullptr, nullptr, "api_key", "id"},
+    {"settings", "llm_profile_assignments", "id", nullptr}, // UNIQUE(context_type, context_id) replaces
+    {"settings", "mcp_servers", nullptr, nullptr, "env", "id"},
+    {"settings", "data_sources", nullptr, nullptr, "config", "id"},
+    {"watchlists", "watchlists", nullptr, nullptr},
+    {"watchlists", "watchlist_stocks", "id", "watchlist_id,symbol"},
+    {"watchlists", "watchlist_columns", nullptr, nullptr},
+    {"screens", "saved_screens", nullptr, nullptr},
+    {"screens", "dashboard_definitions", nullptr, nullptr},
+    {"screens", "dashboard_definition_widgets", nullptr, nullptr},
+    {"strategies", "algo_strategies", nullptr, nullptr},
+    {"strategies", "saved_strategies", nullptr, nullptr},
+    {"strategies", "strategies", "id", "name"},
+    {"templates", "report_templates", nullptr, nullptr},
+    {"templates", "note_templates", "id", "name"},
+};
+
+// Broker accounts travel inside the sealed credentials payload; their paper
+// portfolio is not exported, so the link is dropped.
+const CbTable kCbAccounts = {"credentials", "broker_accounts", "paper_portfolio_id", nullptr};
+
+QStringList cb_columns(QSqlDatabase& db, const QString& table) {
+    QStringList cols;
+    QSqlQuery q(db);
+    if (q.exec(QString("PRAGMA table_info(%1)").arg(table))) {
+        while (q.next())
+            cols << q.value(1).toString();
+    }
+    return cols;
+}
+
+// BLOBs as {"$base64": ...} so they survive the JSON round trip.
+QJsonValue cb_to_json(const QVariant& v) {
+    if (v.isNull())
+        return QJsonValue::Null;
+    if (v.typeId() == QMetaType::QByteArray)
+        return QJsonObject{{"$base64", QString::fromLatin1(v.toByteArray().toBase64())}};
+    return QJsonValue::fromVariant(v);
+}
+
+QVariant cb_from_json(const QJsonValue& v) {
+    if (v.isNull() || v.isUndefined())
+        return QVariant();
+    if (v.isObject() && v.toObject().contains("$base64"))
+        return QByteArray::fromBase64(v.toObject().value("$base64").toString().toLatin1());
+    return v.toVariant();
+}
+
+QJsonArray cb_dump(QSqlDatabase& db, const CbTable& t) {
+    QJsonArray rows;
+    if (cb_columns(db, t.table).isEmpty())
+        return rows; // table not in this build
+    QSqlQuery q(db);
+    if (!q.exec(QString("SELECT * FROM %1").arg(t.table))) {
+        LOG_WARN(CB_TAG, QString("Export of %1 failed: %2").arg(t.table, q.lastError().text()));
+        return rows;
+    }
+    const QString drop = t.drop ? QString::fromLatin1(t.drop) : QString();
+    while (q.next()) {
+        const QSqlRecord rec = q.record();
+        QJsonObject row;
+        for (int i = 0; i < rec.count(); ++i) {
+            if (rec.fieldName(i) != drop)
+                row[rec.fieldName(i)] = cb_to_json(rec.value(i));
+        }
+        rows.append(row);
+    }
+    return rows;
+}
+
+// Moves a table's secret columns out of its plain rows into {key: {column: value}}
+// for the sealed credentials payload.
+QJsonObject cb_take_secrets(const CbTable& t, QJsonArray& rows) {
+    QJsonObject secrets;
+    if (!t.secret)
+        return secrets;
+    const QStringList cols = QString::fromLatin1(t.secret).split(',');
+    const QString key = QString::fromLatin1(t.key);
+    for (qsizetype i = 0; i < rows.size(); ++i) {
+        QJsonObject row = rows[i].toObject();
+        QJsonObject values;
+        for (const auto& c : cols) {
+            if (row.contains(c))
+                values[c] = row.take(c);
+        }
+        secrets[row.value(key).toVariant().toString()] = values;
+        rows[i] = row;
+    }
+    return secrets;
+}
+
+// Puts a table's secret columns back: from the bundle's sealed payload when it
+// was opened, else the value this profile already holds for the row, so a
+// credential-less import never blanks a key. Rows left with neither are
+// counted in `missing` for the user to re-enter.
+void cb_merge_secrets(QSqlDatabase& db, const CbTable& t, QJsonArray& rows, const QJsonObject& sealed, int& missing) {
+    if (!t.secret)
+        return;
+    const QStringList cols = QString::fromLatin1(t.secret).split(',');
+    const QString key = QString::fromLatin1(t.key);
+    for (qsizetype i = 0; i < rows.size(); ++i) {
+        QJsonObject row = rows[i].toObject();
+        const QString id = row.value(key).toVariant().toString();
+        QJsonObject values = sealed.value(id).toObject();
+        if (values.isEmpty()) {
+            QSqlQuery q(db);
+            q.prepare(QString("SELECT %1 FROM %2 WHERE %3 = ?").arg(cols.join(", "), t.table, key));
+            q.addBindValue(id);
+            if (q.exec() && q.next()) {
+                for (int c = 0; c < cols.size(); ++c)
+                    values[cols[c]] = cb_to_json(q.value(c));
+            } else {
+                ++missing;
+            }
+        }
+        for (auto it = values.begin(); it != values.end(); ++it)
+            row[it.key()] = it.value();
+        rows[i] = row;
+    }
+}
+
+struct CbCounts {
+    int imported = 0;
+    int skipped = 0;
+};
+
+// Writes rows against the columns this build has; columns the source had and
+// this one lacks are ignored, missing ones take their defaults.
+Result<CbCounts> cb_restore(QSqlDatabase& db, const CbTable& t, const QJsonArray& rows) {
+    CbCounts counts;
+    const QStringList local = cb_columns(db, t.table);
+    if (local.isEmpty()) {
+        counts.skipped = rows.size();
+        return Result<CbCounts>::ok(counts);
+    }
+    const QString drop = t.drop ? QString::fromLatin1(t.drop) : QString();
+    const QStringList match = t.match ? QString::fromLatin1(t.match).split(',') : QStringList();
+
+    for (const auto& v : rows) {
+        const QJsonObject row = v.toObject();
+        if (!match.isEmpty()) {
+            QStringList where;
+            for (const auto& m : match)
+                where << m + " = ?";
+            QSqlQuery eq(db);
+            eq.prepare(QString("SELECT 1 FROM %1 WHERE %2 LIMIT 1").arg(t.table, where.join(" AND ")));
+            for (const auto& m : match)
+                eq.addBindValue(cb_from_json(row.value(m)));
+            if (eq.exec() && eq.next()) {
+                ++counts.skipped;
+                continue;
+            }
+        }
+
+        QStringList cols, marks;
+        QVariantList values;
+        for (auto it = row.begin(); it != row.end(); ++it) {
+            if (it.key() == drop || !local.contains(it.key()))
+                continue;
+            cols << it.key();
+            marks << "?";
+            values << cb_from_json(it.value());
+        }
+        if (cols.isEmpty()) {
+            ++counts.skipped;
+            continue;
+        }
+        QSqlQuery q(db);
+        q.prepare(QString("INSERT OR REPLACE INTO %1 (%2) VALUES (%3)")
+                      .arg(t.table, cols.join(", "), marks.join(", ")));
+        for (const auto& value : values)
+            q.addBindValue(value);
+        if (!q.exec())
+            return Result<CbCounts>::err(
+                QString("%1: %2").arg(QString::fromLatin1(t.table), q.lastError().text()).toStdString());
+        ++counts.imported;
+    }
+    return Result<CbCounts>::ok(counts);
+}
+
+QJsonArray cb_dump_settings(QSqlDatabase& db) {
+    QJsonArray rows;
+    QSqlQuery q(db);
+    if (!q.exec("SELECT key, value, category FROM settings ORDER BY key"))
+        return rows;
+    while (q.next()) {
+        const QString category = q.value(2).toString();
+        if (cb_local_category(category))
+            continue;
+        rows.append(
+            QJsonObject{{"key", q.value(0).toString()}, {"value", q.value(1).toString()}, {"category", category}});
+    }
+    return rows;
+}
+
+QJsonArray cb_dump_layouts() {
+    QJsonArray out;
+    auto& catalog = LayoutCatalog::instance();
+    const auto list = catalog.list_layouts();
+    if (list.is_err())
+        return out;
+    for (const auto& e : list.value()) {
+        if (e.kind != QLatin1String("user"))
+            continue; // auto / builtin / crash snapshots are per machine
+        const auto w = catalog.load_workspace(e.id);
+        if (w.is_ok())
+            out.append(w.value().to_json());
+    }
+    return out;
+}
+
+Result<QJsonObject> cb_read(const QString& path) {
+    QFile f(path);
+    if (!f.open(QIODevice::ReadOnly))
+        return Result<QJsonObject>::err(("Cannot open bundle: " + f.errorString()).toStdString());
+    QJsonParseError err;
+    const QJsonDocument doc = QJsonDocument::fromJson(f.readAll(), &err);
+    if (err.error != QJsonParseError::NoError || !doc.isObject())
+        return Result<QJsonObject>::err(("Not a config bundle: " + err.errorString()).toStdString());
+    const QJsonObject root = doc.object();
+    if (root.value("format").toString() != QLatin1String(ConfigBundle::kFormat))
+        return Result<QJsonObject>::err("Not a config bundle");
+    if (root.value("version").toInt() > ConfigBundle::kVersion)
+        return Result<QJsonObject>::err("Bundle was written by a newer version of Fincept Terminal — update first");
+    return Result<QJsonObject>::ok(root);
+}
+
+bool cb_wanted(const ConfigBundleOptions& options, const QString& section) {
+    return options.sections.isEmpty() || options.sections.contains(section);
+}
+
+int cb_section_rows(const QJsonObject& section) {
+    int n = 0;
+    for (auto it = section.begin(); it != section.end(); ++it)
+        n += it.value().toArray().size();
+    return n;
+}
+
+} // namespace
+
+QStringList ConfigBundle::sections() {
+    return {"settings", "watchlists", "screens", "strategies", "templates"};
+}
+
+Result<QJsonObject> ConfigBundle::export_to(const QString& path, const ConfigBundleOptions& options) {
+    for (const auto& s : options.sections) {
+        if (!sections().contains(s))
+            return Result<QJsonObject>::err(("Unknown section: " + s).toStdString());
+    }
+    if (options.credentials && options.passphrase.size() < kMinPassphraseLength)
+        return Result<QJsonObject>::err(
+            QString("Credentials need a passphrase of at least %1 characters").arg(kMinPassphraseLength).toStdString());
+
+    auto db = Database::instance().connection();
+    QJsonObject data;
+    QJsonObject summary;
+    QJsonObject table_secrets; // only ever written sealed
+    for (const auto& section : sections()) {
+        if (!cb_wanted(options, section))
+            continue;
+        QJsonObject tables;
+        if (section == QLatin1String("settings"))
+            tables["settings"] = cb_dump_settings(db);
+        if (section == QLatin1String("screens"))
+            tables["layouts"] = cb_dump_layouts();
+        for (const auto& t : kCbTables) {
+            if (section != QLatin1String(t.section))
+                continue;
+            QJsonArray rows = cb_dump(db, t);
+            const QJsonObject secrets = cb_take_secrets(t, rows);
+            if (!secrets.isEmpty())
+                table_secrets[QString::fromLatin1(t.table)] = secrets;
+            tables[QString::fromLatin1(t.table)] = rows;
+        }
+        data[section] = tables;
+        summary[section] = cb_section_rows(tables);
+    }
+
+    QJsonObject root{
+        {"format", kFormat},
+        {"version", kVersion},
+        {"created_at", QDateTime::currentDateTimeUtc().toString(Qt::ISODate)},
+        {"app_version", QString::fromUtf8(FINCEPT_VERSION_STRING)},
+        {"source_os", QSysInfo::prettyProductName()},
+        {"source_platform", QSysInfo::productType()},
+        {"data", data},
+    };
+
+    int secret_count = 0;
+    if (options.credentials) {
+        QJsonObject secrets;
+        const auto entries = SecureStorage::instance().list();
+        if (entries.is_ok()) {
+            for (const auto& e : entries.value()) {
+                if (!cb_portable_secret(e.key))
+                    continue;
+                const auto v = SecureStorage::instance().retrieve(e.key);
+                if (v.is_ok())
+                    secrets[e.key] = v.value();
+            }
+        }
+        secret_count = secrets.size();
+        const QJsonObject payload{
+            {"broker_accounts", cb_dump(db, kCbAccounts)}, {"secrets", secrets}, {"table_secrets", table_secrets}};
+        const auto sealed =
+            SecureStorage::seal(QJsonDocument(payload).toJson(QJsonDocument::Compact), options.passphrase);
+        if (sealed.is_err())
+            return Result<QJsonObject>::err("Sealing credentials failed: " + sealed.error());
+        root["credentials"] = QJsonObject{{"cipher", "aes-256-gcm"},
+                                          {"kdf", "pbkdf2-hmac-sha256"},
+                                          {"iterations", SecureStorage::kSealIterations},
+                                          {"data", QString::fromLatin1(sealed.value().toBase64())}};
+    }
+
+    QSaveFile f(path);
+    if (!f.open(QIODevice::WriteOnly))
+        return Result<QJsonObject>::err(("Cannot write bundle: " + f.errorString()).toStdString());
+    f.write(QJsonDocument(root).toJson(QJsonDocument::Indented));
+    if (!f.commit())
+        return Result<QJsonObject>::err(("Cannot write bundle: " + f.errorString()).toStdString());
+
+    LOG_INFO(CB_TAG, QString("Exported config bundle to %1 (%2 secret(s))").arg(path).arg(secret_count));
+    return Result<QJsonObject>::ok(QJsonObject{{"path", path},
+                                               {"sections", summary},
+                                               {"credentials", options.credentials},
+                                               {"secrets", secret_count}});
+}
+
+Result<QJsonObject> ConfigBundle::inspect(const QString& path) {
+    auto r = cb_read(path);
+    if (r.is_err())
+        return r;
+    const QJsonObject root = r.value();
+    QJsonObject counts;
+    const QJsonObject data = root.value("data").toObject();
+    for (auto it = data.begin(); it != data.end(); ++it)
+        counts[it.key()] = cb_section_rows(it.value().toObject());
+    return Result<QJsonObject>::ok(QJsonObject{{"path", path},
+                                               {"version", root.value("version")},
+                                               {"created_at", root.value("created_at")},
+                                               {"app_version", root.value("app_version")},
+                                               {"source_os", root.value("source_os")},
+                                               {"sections", counts},
+                                               {"has_credentials", root.contains("credentials")}});
+}
+
+Result<QJsonObject> ConfigBundle::import_from(const QString& path, const ConfigBundleOptions& options) {
+    auto r = cb_read(path);
+    if (r.is_err())
+        return r;
+    const QJsonObject root = r.value();
+    const QJsonObject data = root.value("data").toObject();
+
+    // Open the credentials first: a wrong passphrase must not leave a half import.
+    QJsonObject credentials;
+    if (options.credentials) {
+        if (!root.contains("credentials"))
+            return Result<QJsonObject>::err("This bundle carries no credentials");
+        const QByteArray blob =
+            QByteArray::fromBase64(root.value("credentials").toObject().value("data").toString().toLatin1());
+        const auto opened = SecureStorage::unseal(blob, options.passphrase);
+        if (opened.is_err())
+            return Result<QJsonObject>::err("Cannot open credentials: " + opened.error());
+        credentials = QJsonDocument::fromJson(opened.value()).object();
+    }
+
+    auto& database = Database::instance();
+    auto db = database.connection();
+    if (auto t = database.begin_transaction(); t.is_err())
+        return Result<QJsonObject>::err(t.error());
+
+    QJsonObject summary;
+    QJsonObject needs_credentials;
+    const QJsonObject table_secrets = credentials.value("table_secrets").toObject();
+    for (const auto& section : sections()) {
+        if (!cb_wanted(options, section) || !data.contains(section))
+            continue;
+        const QJsonObject tables = data.value(section).toObject();
+        CbCounts counts;
+        if (section == QLatin1String("settings")) {
+            for (const auto& v : tables.value("settings").toArray()) {
+                const QJsonObject s = v.toObject();
+                const QString category = s.value("category").toString("general");
+                if (cb_local_category(category)) {
+                    ++counts.skipped;
+                    continue;
+                }
+                auto& settings = SettingsRepository::instance();
+                if (settings.set(s.value("key").toString(), s.value("value").toString(), category).is_ok())
+                    ++counts.imported;
+                else
+                    ++counts.skipped;
+            }
+        }
+        for (const auto& t : kCbTables) {
+            if (section != QLatin1String(t.section))
+                continue;
+            const QString table = QString::fromLatin1(t.table);
+            QJsonArray rows = tables.value(table).toArray();
+            int missing = 0;
+            cb_merge_secrets(db, t, rows, table_secrets.value(table).toObject(), missing);
+            if (missing > 0)
+                needs_credentials[table] = missing;
+            const auto restored = cb_restore(db, t, rows);
+            if (restored.is_err()) {
+                database.rollback();
+                return Result<QJsonObject>::err("Import failed, nothing changed — " + restored.error());
+            }
+            counts.imported += restored.value().imported;
+            counts.skipped += restored.value().skipped;
+        }
+        summary[section] = QJsonObject{{"imported", counts.imported}, {"skipped", counts.skipped}};
+    }
+
+    if (!credentials.isEmpty()) {
+        const auto restored = cb_restore(db, kCbAccounts, credentials.value("broker_accounts").toArray());
+        if (restored.is_err()) {
+            database.rollback();
+            return Result<QJsonObject>::err("Import failed, nothing changed — " + restored.error());
+        }
+        summary["broker_accounts"] = restored.value().imported;
+    }
+
+    if (auto c = database.commit(); c.is_err()) {
+        database.rollback();
+        return Result<QJsonObject>::err(c.error());
+    }
+
+    // Separate stores, written once the database side has committed.
+    if (cb_wanted(options, "screens") && data.contains("screens")) {
+        auto& catalog = LayoutCatalog::instance();
+        int imported = 0, skipped = 0;
+        for (const auto& v : data.value("screens").toObject().value("layouts").toArray()) {
+            layout::Workspace w = layout::Workspace::from_json(v.toObject());
+            if (!catalog.find_by_name(w.name).is_null()) {
+                ++skipped;
+                continue;
+            }
+            w.id = LayoutId::generate();
+            w.kind = "user";
+            if (catalog.save_workspace(w).is_ok())
+                ++imported;
+            else
+                ++skipped;
+        }
+        summary["layouts"] = QJsonObject{{"imported", imported}, {"skipped", skipped}};
+    }
+    if (!credentials.isEmpty()) {
+        int stored = 0;
+        const QJsonObject secrets = credentials.value("secrets").toObject();
+        for (auto it = secrets.begin(); it != secrets.end(); ++it) {
+            if (!cb_portable_secret(it.key()))
+                continue;
+            if (SecureStorage::instance().store(it.key(), it.value().toString()).is_ok())
+                ++stored;
+        }
+        summary["secrets"] = stored;
+    }
+
+    LOG_INFO(CB_TAG, QString("Imported config bundle %1 (from %2)").arg(path, root.value("source_os").toString()));
+    return Result<QJsonObject>::ok(QJsonObject{{"path", path},
+                                               {"source_os", root.value("source_os")},
+                                               {"imported", summary},
+                                               {"needs_credentials", needs_credentials},
+                                               {"restart_required", true}});
+}
+
+} // namespace fincept
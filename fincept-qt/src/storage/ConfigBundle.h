@@ -0,0 +1,66 @@
+#pragma once
+// ConfigBundle — one file that carries a user's setup to another machine.
+//
+// Exports preferences (settings plus LLM, MCP-server and data-source configs),
+// watchlists, screens (saved workspace layouts, screener screens, custom
+// dashboards), strategies (algo rules, options strategies) and templates
+// (report, note) into a single JSON file, and merges one back in. Rows travel
+// by column name and are written against the columns the target actually
+// has, so a bundle moves between builds — Windows to macOS, older to newer —
+// as long as the tables exist there.
+//
+// Left out on purpose: market data, history, trades, chat and caches, plus
+// device-local state (window geometry, cloud-sync flags, the login session,
+// the lock PIN).
+//
+// Credentials — broker accounts, every portable SecureStorage entry, and the
+// key-bearing columns of the preference tables (LLM api_key, MCP server env,
+// data-source config) — are opt-in and only ever written sealed with a user
+// passphrase (SecureStorage::seal). SecureStorage's own key is machine-bound,
+// so the stored ciphertext would not decrypt on the target anyway. Without
+// them, an imported row keeps the key this profile already has for it; rows
+// with none are listed under `needs_credentials` for the user to re-enter.
+//
+// Threading: call on the UI thread; layouts go through LayoutCatalog's
+// main-thread connection.
+
+#include "core/result/Result.h"
+
+#include <QJsonObject>
+#include <QString>
+#include <QStringList>
+
+namespace fincept {
+
+struct ConfigBundleOptions {
+    QStringList sections;     ///< subset of ConfigBundle::sections(); empty = all
+    bool credentials = false; ///< broker accounts + stored secrets; needs `passphrase`
+    QString passphrase;       ///< seals credentials on export, opens them on import
+};
+
+class ConfigBundle {
+  public:
+    static constexpr const char* kFormat = "fincept-config-bundle";
+    static constexpr int kVersion = 1;
+    static constexpr const char* kFileSuffix = "fcbundle";
+    static constexpr int kMinPassphraseLength = 8;
+
+    /// "settings", "watchlists", "screens", "strategies", "templates".
+    static QStringList sections();
+
+    /// Writes the bundle (atomically). Returns {path, sections: {name: rows}, credentials, secrets}.
+    static Result<QJsonObject> export_to(const QString& path, const ConfigBundleOptions& options = {});
+
+    /// Header and per-section row counts of a bundle, without importing it.
+    static Result<QJsonObject> inspect(const QString& path);
+
+    /// Merges a bundle into the active profile. Rows with the same id are
+    /// replaced; rows without a stable id (watchlist symbols, note templates)
+    /// and layouts whose name already exists are skipped. A wrong passphrase
+    /// fails the whole import before anything is written. Returns per-section
+    /// imported / skipped counts and `needs_credentials` ({table: rows});
+    /// open screens pick the changes up on restart.
+    static Result<QJsonObject> import_from(const QString& path, const ConfigBundleOptions& options = {});
+};
+
+} // namespace fincept
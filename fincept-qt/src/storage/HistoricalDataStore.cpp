@@ -1,6 +1,7 @@
 #include "storage/HistoricalDataStore.h"
 
 #include "core/logging/Logger.h"
+#include "core/time/Timestamps.h"
 #include "storage/sqlite/Database.h"
 #include "trading/AccountManager.h"
 #include "trading/BrokerInterface.h"
@@ -8,6 +9,7 @@
 #include <QDate>
 #include <QDateTime>
 #include <QFile>
+#include <QHash>
 #include <QSqlError>
 #include <QSqlQuery>
 #include <QTextStream>
@@ -119,8 +121,8 @@ bool HistoricalDataStore::store_candles(const QString& symbol, const QString& ex
     const QString exc = exchange.toUpper();
 
     for (const auto& c : candles) {
-        auto r = db().execute(sql, {sym, exc, interval, static_cast<qint64>(c.timestamp), c.open, c.high, c.low,
-                                    c.close, c.volume, c.oi});
+        const qint64 ts_ms = core::time::epoch_to_ms(c.timestamp); // the key must be UTC ms, whatever the caller had
+        auto r = db().execute(sql, {sym, exc, interval, ts_ms, c.open, c.high, c.low, c.close, c.volume, c.oi});
         if (r.is_err()) {
             LOG_ERROR("Historify", QString("store_candles failed for %1:%2:%3 — %4")
                                        .arg(sym, exc, interval, QString::fromStdString(r.error())));
@@ -141,6 +143,54 @@ bool HistoricalDataStore::store_candles(const QString& symbol, const QString& ex
     return true;
 }
 
+bool HistoricalDataStore::replace_candles(const QString& symbol, const QString& exchange, const QString& interval,
+                                          qint64 from_ms, qint64 to_ms, const QVector<trading::BrokerCandle>& candles) {
+    auto begin = db().begin_transaction();
+    const bool in_tx = begin.is_ok();
+    if (!in_tx)
+        LOG_WARN("Historify", "transaction begin failed; replacing without batch transaction");
+
+    const QString sym = symbol.toUpper();
+    const QString exc = exchange.toUpper();
+    auto fail = [&](const Result<QSqlQuery>& r) {
+        LOG_ERROR("Historify", QString("replace_candles failed for %1:%2:%3 — %4")
+                                   .arg(sym, exc, interval, QString::fromStdString(r.error())));
+        if (in_tx)
+            db().rollback();
+        return false;
+    };
+
+    auto del = db().execute("DELETE FROM market_data WHERE symbol = ? AND exchange = ? AND interval = ? "
+                            "AND timestamp_ms >= ? AND timestamp_ms <= ?",
+                            {sym, exc, interval, from_ms, to_ms});
+    if (del.is_err())
+        return fail(del);
+
+    const QString sql = QStringLiteral("INSERT OR REPLACE INTO market_data "
+                                       "(symbol, exchange, interval, timestamp_ms, open, high, low, close, volume, oi) "
+                                       "VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)");
+    int stored = 0;
+    for (const auto& c : candles) {
+        if (c.timestamp < from_ms || c.timestamp > to_ms)
+            continue;
+        auto r = db().execute(sql, {sym, exc, interval, static_cast<qint64>(c.timestamp), c.open, c.high, c.low,
+                                    c.close, c.volume, c.oi});
+        if (r.is_err())
+            return fail(r);
+        ++stored;
+    }
+
+    if (in_tx) {
+        auto c = db().commit();
+        if (c.is_err()) {
+            LOG_ERROR("Historify", QString("commit failed — %1").arg(QString::fromStdString(c.error())));
+            return false;
+        }
+    }
+    LOG_INFO("Historify", QString("Replaced %1 candles in range for %2:%3:%4").arg(stored).arg(sym, exc, interval));
+    return true;
+}
+
 QVector<trading::BrokerCandle> HistoricalDataStore::get_candles(const QString& symbol, const QString& exchange,
                                                                 const QString& interval, qint64 from_ms,
                                                                 qint64 to_ms) const {
@@ -224,6 +274,158 @@ QVector<trading::BrokerCandle> HistoricalDataStore::get_resampled(const QString&
     return out;
 }
 
+// ── Tick storage ────────────────────────────────────────────────────────────────
+
+bool HistoricalDataStore::store_ticks(const QString& symbol, const QString& exchange, const QVector<Tick>& ticks) {
+    if (ticks.isEmpty())
+        return true;
+
+    auto begin = db().begin_transaction();
+    const bool in_tx = begin.is_ok();
+    if (!in_tx)
+        LOG_WARN("Historify", "transaction begin failed; storing ticks without batch transaction");
+
+    const QString sql = QStringLiteral("INSERT OR REPLACE INTO market_ticks "
+                                       "(symbol, exchange, timestamp_ms, seq, price, size, bid, ask, bid_size, "
+                                       "ask_size) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)");
+    const QString sym = symbol.toUpper();
+    const QString exc = exchange.toUpper();
+
+    qint64 prev_ts = -1;
+    int seq = 0;
+    for (const auto& t : ticks) {
+        seq = t.timestamp_ms == prev_ts ? seq + 1 : 0;
+        prev_ts = t.timestamp_ms;
+        auto r = db().execute(sql, {sym, exc, t.timestamp_ms, seq, t.price, t.size, t.bid, t.ask, t.bid_size,
+                                    t.ask_size});
+        if (r.is_err()) {
+            LOG_ERROR("Historify", QString("store_ticks failed for %1:%2 — %3")
+                                       .arg(sym, exc, QString::fromStdString(r.error())));
+            if (in_tx)
+                db().rollback();
+            return false;
+        }
+    }
+
+    if (in_tx) {
+        auto c = db().commit();
+        if (c.is_err()) {
+            LOG_ERROR("Historify", QString("commit failed — %1").arg(QString::fromStdString(c.error())));
+            return false;
+        }
+    }
+    LOG_INFO("Historify", QString("Stored %1 ticks for %2:%3").arg(ticks.size()).arg(sym, exc));
+    return true;
+}
+
+QVector<HistoricalDataStore::Tick> HistoricalDataStore::get_ticks(const QString& symbol, const QString& exchange,
+                                                                  qint64 from_ms, qint64 to_ms, int limit) const {
+    QString sql = QStringLiteral("SELECT timestamp_ms, price, size, bid, ask, bid_size, ask_size FROM market_ticks "
+                                 "WHERE symbol = ? AND exchange = ?");
+    QVariantList params{symbol.toUpper(), exchange.toUpper()};
+    if (from_ms > 0) {
+        sql += " AND timestamp_ms >= ?";
+        params.append(from_ms);
+    }
+    if (to_ms > 0) {
+        sql += " AND timestamp_ms <= ?";
+        params.append(to_ms);
+    }
+    sql += " ORDER BY timestamp_ms ASC, seq ASC";
+    if (limit > 0) {
+        sql += " LIMIT ?";
+        params.append(limit);
+    }
+
+    QVector<Tick> out;
+    auto r = db().execute(sql, params);
+    if (r.is_err()) {
+        LOG_ERROR("Historify", QString("get_ticks failed — %1").arg(QString::fromStdString(r.error())));
+        return out;
+    }
+    auto& q = r.value();
+    while (q.next()) {
+        Tick t;
+        t.timestamp_ms = q.value(0).toLongLong();
+        t.price = q.value(1).toDouble();
+        t.size = q.value(2).toDouble();
+        t.bid = q.value(3).toDouble();
+        t.ask = q.value(4).toDouble();
+        t.bid_size = q.value(5).toDouble();
+        t.ask_size = q.value(6).toDouble();
+        out.append(t);
+    }
+    return out;
+}
+
+int HistoricalDataStore::import_ticks_csv(const QString& symbol, const QString& exchange, const QString& file_path,
+                                          QString* error) {
+    auto fail = [error](const QString& msg) {
+        if (error)
+            *error = msg;
+        return -1;
+    };
+    QFile f(file_path);
+    if (!f.open(QIODevice::ReadOnly | QIODevice::Text))
+        return fail(QString("Cannot open %1 — %2").arg(file_path, f.errorString()));
+
+    QTextStream ts(&f);
+    const QStringList header = ts.readLine().trimmed().toLower().split(',');
+    auto col = [&header](std::initializer_list<const char*> names) {
+        for (const char* n : names) {
+            const int i = header.indexOf(QString::fromLatin1(n));
+            if (i >= 0)
+                return i;
+        }
+        return -1;
+    };
+    const int c_ts = col({"timestamp", "timestamp_ms", "time", "datetime"});
+    const int c_price = col({"price", "last", "ltp"});
+    const int c_size = col({"size", "volume", "qty", "quantity"});
+    const int c_bid = col({"bid"}), c_ask = col({"ask"});
+    const int c_bid_size = col({"bid_size"}), c_ask_size = col({"ask_size"});
+    if (c_ts < 0 || c_price < 0)
+        return fail(QStringLiteral("CSV header needs 'timestamp' and 'price' columns"));
+
+    // Naive timestamps (no Z / offset) are the exchange's wall-clock time.
+    const QTimeZone naive_zone = core::time::exchange_zone(exchange);
+    QVector<Tick> ticks;
+    int line_no = 1;
+    while (!ts.atEnd()) {
+        ++line_no;
+        const QString line = ts.readLine().trimmed();
+        if (line.isEmpty())
+            continue;
+        const QStringList cells = line.split(',');
+        auto num = [&cells](int c) { return c >= 0 && c < cells.size() ? cells[c].trimmed().toDouble() : 0.0; };
+
+        const QString raw_ts = cells.value(c_ts).trimmed();
+        const qint64 ts_ms = core::time::to_utc_ms(raw_ts, naive_zone);
+        if (ts_ms <= 0)
+            return fail(QString("Line %1: unreadable timestamp '%2'").arg(line_no).arg(raw_ts));
+
+        Tick t;
+        t.timestamp_ms = ts_ms;
+        t.price = num(c_price);
+        t.size = num(c_size);
+        t.bid = num(c_bid);
+        t.ask = num(c_ask);
+        t.bid_size = num(c_bid_size);
+        t.ask_size = num(c_ask_size);
+        if (t.price <= 0 && (t.bid <= 0 || t.ask <= 0))
+            continue; // neither a trade nor a two-sided quote
+        if (t.price <= 0)
+            t.price = (t.bid + t.ask) / 2.0;
+        ticks.append(t);
+    }
+    std::stable_sort(ticks.begin(), ticks.end(),
+                     [](const Tick& a, const Tick& b) { return a.timestamp_ms < b.timestamp_ms; });
+
+    if (!store_ticks(symbol, exchange, ticks))
+        return fail(QStringLiteral("Storing ticks failed (see log)"));
+    return int(ticks.size());
+}
+
 // ── Watchlist ───────────────────────────────────────────────────────────────────
 
 bool HistoricalDataStore::add_to_watchlist(const QString& symbol, const QString& exchange, const QString& interval) {
@@ -277,6 +479,7 @@ QVector<HistoricalDataStore::CatalogEntry> HistoricalDataStore::catalog() const
         return out;
     }
     auto& q = r.value();
+    QHash<QString, QString> zones; // exchange → tz id
     while (q.next()) {
         CatalogEntry e;
         e.symbol = q.value(0).toString();
@@ -285,6 +488,10 @@ QVector<HistoricalDataStore::CatalogEntry> HistoricalDataStore::catalog() const
         e.first_ts = q.value(3).toLongLong();
         e.last_ts = q.value(4).toLongLong();
         e.record_count = q.value(5).toInt();
+        auto tz = zones.find(e.exchange);
+        if (tz == zones.end())
+            tz = zones.insert(e.exchange, core::time::exchange_tz_id(e.exchange));
+        e.tz = tz.value();
         out.append(e);
     }
     return out;
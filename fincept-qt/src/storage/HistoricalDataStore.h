@@ -18,8 +18,11 @@
 //
 // Conventions:
 //   - namespace fincept::storage (matches the src/storage/ directory).
-//   - Timestamps are epoch MILLISECONDS throughout (trading::BrokerCandle uses
-//     int64 ms). OpenAlgo uses epoch seconds; this store standardises on ms.
+//   - Timestamps are UTC epoch MILLISECONDS throughout (trading::BrokerCandle
+//     uses int64 ms). OpenAlgo uses epoch seconds; this store standardises on
+//     ms, and store_candles() coerces s / µs keys (core::time::epoch_to_ms).
+//     Zones are settled at ingest (core/time/Timestamps.h); the exchange's
+//     zone is reported per series as metadata (CatalogEntry::tz).
 //   - Schema lives in migration v033_historify.cpp.
 //   - Singleton, accessed on the main thread. Uses Database::instance() which
 //     hands out a per-thread connection, so background callers are tolerated.
@@ -45,6 +48,13 @@ class HistoricalDataStore {
     bool store_candles(const QString& symbol, const QString& exchange, const QString& interval,
                        const QVector<trading::BrokerCandle>& candles);
 
+    /// Replace the stored candles in [from_ms, to_ms] inclusive with `candles`
+    /// (those outside the range are ignored) in a single transaction. Used to
+    /// repair a bad range — duplicate or spurious bars the upsert alone would
+    /// leave behind are removed. Returns false on any write error.
+    bool replace_candles(const QString& symbol, const QString& exchange, const QString& interval, qint64 from_ms,
+                         qint64 to_ms, const QVector<trading::BrokerCandle>& candles);
+
     /// Query stored candles in [from_ms, to_ms] inclusive, ordered ascending by
     /// timestamp. Pass from_ms<=0 / to_ms<=0 to leave that bound open.
     QVector<trading::BrokerCandle> get_candles(const QString& symbol, const QString& exchange, const QString& interval,
@@ -59,6 +69,38 @@ class HistoricalDataStore {
     QVector<trading::BrokerCandle> get_resampled(const QString& symbol, const QString& exchange,
                                                  const QString& target_interval, qint64 from_ms, qint64 to_ms) const;
 
+    // ── Tick storage (trades / quotes) ───────────────────────────────────────
+
+    /// One stored tick: last trade price + size and, when the source has one,
+    /// the top-of-book quote (0 = not available). Schema in v056_market_ticks.
+    struct Tick {
+        qint64 timestamp_ms = 0;
+        double price = 0;
+        double size = 0;
+        double bid = 0;
+        double ask = 0;
+        double bid_size = 0;
+        double ask_size = 0;
+    };
+
+    /// Upsert ticks for (symbol, exchange) in one transaction. Ticks sharing a
+    /// millisecond keep their order (a per-millisecond sequence number is part
+    /// of the key), so re-storing the same batch is idempotent.
+    bool store_ticks(const QString& symbol, const QString& exchange, const QVector<Tick>& ticks);
+
+    /// Stored ticks in [from_ms, to_ms] inclusive, ascending. 0 leaves a bound
+    /// open; `limit` > 0 caps the row count (earliest first).
+    QVector<Tick> get_ticks(const QString& symbol, const QString& exchange, qint64 from_ms, qint64 to_ms,
+                            int limit = 0) const;
+
+    /// Imports a CSV of ticks. The header names the columns: `timestamp`
+    /// (epoch s / ms / µs or ISO-8601; without an offset it is read in the
+    /// exchange's zone) and `price` are required; `size` /
+    /// `volume`, `bid`, `ask`, `bid_size`, `ask_size` are optional. Returns
+    /// the number of ticks stored, or -1 with `error` set.
+    int import_ticks_csv(const QString& symbol, const QString& exchange, const QString& file_path,
+                         QString* error = nullptr);
+
     // ── Watchlist (symbols flagged for auto-download) ────────────────────────
 
     /// Add a (symbol, exchange, interval) to the auto-download watchlist.
@@ -85,6 +127,7 @@ class HistoricalDataStore {
         qint64 first_ts = 0; // epoch ms of earliest stored candle
         qint64 last_ts = 0;  // epoch ms of latest stored candle
         int record_count = 0;
+        QString tz; // exchange's IANA zone ("Asia/Kolkata"); "UTC" for unknown / 24/7 venues
     };
     /// Per-series summary (MIN/MAX timestamp + COUNT) derived from market_data.
     QVector<CatalogEntry> catalog() const;
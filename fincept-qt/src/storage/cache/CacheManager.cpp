@@ -1,5 +1,6 @@
 #include "storage/cache/CacheManager.h"
 
+#include "core/telemetry/UsageMetrics.h"
 #include "storage/sqlite/CacheDatabase.h"
 
 #include <QSqlQuery>
@@ -64,7 +65,10 @@ QVariant CacheManager::get(const QString& key) const {
         return {};
 
     QSqlQuery q = std::move(r.value());
-    if (!q.next())
+    const bool hit = q.next();
+    // Keyed by prefix ("quote:AAPL" → "sqlite:quote") so no symbol is recorded.
+    telemetry::UsageMetrics::instance().record_cache("sqlite:" + key.section(':', 0, 0), hit);
+    if (!hit)
         return {};
 
     return q.value(0).toString();
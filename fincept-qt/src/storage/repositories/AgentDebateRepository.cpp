@@ -0,0 +1,85 @@
+// src/storage/repositories/AgentDebateRepository.cpp
+#include "storage/repositories/AgentDebateRepository.h"
+
+#include <QJsonDocument>
+
+namespace fincept {
+
+namespace {
+
+const char* kAdCols = "ticker, as_of_date, direction, conviction, score, key_evidence, dissent, summary, transcript, "
+                      "rounds, provider, model, created_at";
+
+QString ad_compact(const QJsonArray& a) {
+    return QString::fromUtf8(QJsonDocument(a).toJson(QJsonDocument::Compact));
+}
+
+QJsonArray ad_array(const QVariant& v) {
+    return QJsonDocument::fromJson(v.toString().toUtf8()).array();
+}
+
+} // namespace
+
+AgentDebateRepository& AgentDebateRepository::instance() {
+    static AgentDebateRepository s;
+    return s;
+}
+
+DebateVerdict AgentDebateRepository::map_row(QSqlQuery& q) {
+    DebateVerdict v;
+    v.ticker = q.value(0).toString();
+    v.as_of_date = q.value(1).toString();
+    v.direction = q.value(2).toString();
+    v.conviction = q.value(3).toDouble();
+    v.score = q.value(4).toDouble();
+    v.key_evidence = ad_array(q.value(5));
+    v.dissent = ad_array(q.value(6));
+    v.summary = q.value(7).toString();
+    v.transcript = ad_array(q.value(8));
+    v.rounds = q.value(9).toInt();
+    v.provider = q.value(10).toString();
+    v.model = q.value(11).toString();
+    v.created_at = q.value(12).toLongLong();
+    return v;
+}
+
+Result<void> AgentDebateRepository::upsert(const DebateVerdict& v) {
+    return exec_write(
+        QString("INSERT OR REPLACE INTO agent_debate_verdicts (%1) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+            .arg(kAdCols),
+        {v.ticker, v.as_of_date, v.direction, v.conviction, v.score, ad_compact(v.key_evidence),
+         ad_compact(v.dissent), v.summary, ad_compact(v.transcript), v.rounds, v.provider, v.model, v.created_at});
+}
+
+std::optional<DebateVerdict> AgentDebateRepository::get(const QString& ticker, const QString& as_of_date) {
+    return query_optional(
+        QString("SELECT %1 FROM agent_debate_verdicts WHERE ticker = ? AND as_of_date = ?").arg(kAdCols),
+        {ticker, as_of_date}, &AgentDebateRepository::map_row);
+}
+
+Result<QVector<DebateVerdict>> AgentDebateRepository::list(const QString& ticker, const QString& since_date,
+                                                           int limit) {
+    QString sql = QString("SELECT %1 FROM agent_debate_verdicts WHERE as_of_date >= ?").arg(kAdCols);
+    QVariantList params{since_date};
+    if (!ticker.isEmpty()) {
+        sql += " AND ticker = ?";
+        params.append(ticker);
+    }
+    sql += " ORDER BY as_of_date DESC, created_at DESC LIMIT ?";
+    params.append(limit);
+    return query_list(sql, params, &AgentDebateRepository::map_row);
+}
+
+Result<QVector<DebateVerdict>> AgentDebateRepository::latest_per_ticker(const QString& since_date) {
+    return query_list(QString("SELECT %1 FROM agent_debate_verdicts v WHERE as_of_date = "
+                              "(SELECT MAX(as_of_date) FROM agent_debate_verdicts WHERE ticker = v.ticker) "
+                              "AND as_of_date >= ?")
+                          .arg(kAdCols),
+                      {since_date}, &AgentDebateRepository::map_row);
+}
+
+Result<void> AgentDebateRepository::remove(const QString& ticker, const QString& as_of_date) {
+    return exec_write("DELETE FROM agent_debate_verdicts WHERE ticker = ? AND as_of_date = ?", {ticker, as_of_date});
+}
+
+} // namespace fincept
@@ -0,0 +1,50 @@
+// src/storage/repositories/AgentDebateRepository.h
+#pragma once
+#include "storage/repositories/BaseRepository.h"
+
+#include <QJsonArray>
+#include <QString>
+#include <QVector>
+
+#include <optional>
+
+namespace fincept {
+
+/// Verdict of one bull / bear / risk debate. Schema in v071_agent_debates.
+struct DebateVerdict {
+    QString ticker;
+    QString as_of_date; // yyyy-MM-dd
+    QString direction;  // bullish | bearish | neutral
+    double conviction = 0;
+    double score = 0;        // +conviction bullish, -conviction bearish, 0 neutral
+    QJsonArray key_evidence; // strings
+    QJsonArray dissent;      // {role, stance, argument}
+    QString summary;
+    QJsonArray transcript; // {round, arguments{role: text}, stances{role: stance}}
+    int rounds = 0;
+    QString provider;
+    QString model;
+    qint64 created_at = 0; // epoch ms
+};
+
+class AgentDebateRepository : public BaseRepository<DebateVerdict> {
+  public:
+    static AgentDebateRepository& instance();
+
+    /// Replaces any verdict already stored for the same ticker and date.
+    Result<void> upsert(const DebateVerdict& v);
+    std::optional<DebateVerdict> get(const QString& ticker, const QString& as_of_date);
+    /// Newest first; an empty ticker matches every ticker.
+    Result<QVector<DebateVerdict>> list(const QString& ticker, const QString& since_date, int limit);
+    /// Most recent verdict per ticker dated on or after `since_date` — the
+    /// screener's debate factor.
+    Result<QVector<DebateVerdict>> latest_per_ticker(const QString& since_date);
+
+    Result<void> remove(const QString& ticker, const QString& as_of_date);
+
+  private:
+    AgentDebateRepository() = default;
+    static DebateVerdict map_row(QSqlQuery& q);
+};
+
+} // namespace fincept
@@ -0,0 +1,111 @@
+// src/storage/repositories/AgentRunRepository.cpp
+#include "storage/repositories/AgentRunRepository.h"
+
+#include <QJsonDocument>
+
+namespace fincept {
+
+namespace {
+
+const char* kArCols = "id, kind, query, config, output_model, provider, model, agent_name, input_hash, status, "
+                      "response, error, input_tokens, output_tokens, total_tokens, latency_ms, started_at, "
+                      "finished_at, rerun_of";
+
+} // namespace
+
+AgentRunRepository& AgentRunRepository::instance() {
+    static AgentRunRepository s;
+    return s;
+}
+
+AgentRunRecord AgentRunRepository::map_row(QSqlQuery& q) {
+    AgentRunRecord r;
+    r.id = q.value(0).toString();
+    r.kind = q.value(1).toString();
+    r.query = q.value(2).toString();
+    r.config = QJsonDocument::fromJson(q.value(3).toString().toUtf8()).object();
+    r.output_model = q.value(4).toString();
+    r.provider = q.value(5).toString();
+    r.model = q.value(6).toString();
+    r.agent_name = q.value(7).toString();
+    r.input_hash = q.value(8).toString();
+    r.status = q.value(9).toString();
+    r.response = q.value(10).toString();
+    r.error = q.value(11).toString();
+    r.input_tokens = q.value(12).toInt();
+    r.output_tokens = q.value(13).toInt();
+    r.total_tokens = q.value(14).toInt();
+    r.latency_ms = q.value(15).toInt();
+    r.started_at = q.value(16).toLongLong();
+    r.finished_at = q.value(17).toLongLong();
+    r.rerun_of = q.value(18).toString();
+    return r;
+}
+
+Result<void> AgentRunRepository::insert(const AgentRunRecord& r) {
+    return exec_write(
+        QString("INSERT OR REPLACE INTO agent_runs (%1) "
+                "VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+            .arg(kArCols),
+        {r.id, r.kind, r.query, QString::fromUtf8(QJsonDocument(r.config).toJson(QJsonDocument::Compact)),
+         r.output_model, r.provider, r.model, r.agent_name, r.input_hash,
+         r.status.isEmpty() ? QStringLiteral("running") : r.status, r.response, r.error, r.input_tokens,
+         r.output_tokens, r.total_tokens, r.latency_ms, r.started_at, r.finished_at, r.rerun_of});
+}
+
+Result<void> AgentRunRepository::finish(const AgentRunRecord& r) {
+    return exec_write("UPDATE agent_runs SET status = ?, response = ?, error = ?, input_tokens = ?, "
+                      "output_tokens = ?, total_tokens = ?, latency_ms = ?, finished_at = ? "
+                      "WHERE id = ? AND finished_at = 0",
+                      {r.status, r.response, r.error, r.input_tokens, r.output_tokens, r.total_tokens, r.latency_ms,
+                       r.finished_at, r.id});
+}
+
+Result<void> AgentRunRepository::set_rerun_of(const QString& id, const QString& rerun_of) {
+    return exec_write("UPDATE agent_runs SET rerun_of = ? WHERE id = ?", {rerun_of, id});
+}
+
+std::optional<AgentRunRecord> AgentRunRepository::get(const QString& id) {
+    return query_optional(QString("SELECT %1 FROM agent_runs WHERE id = ?").arg(kArCols), {id},
+                          &AgentRunRepository::map_row);
+}
+
+Result<QVector<AgentRunRecord>> AgentRunRepository::list(const QString& text, const QString& kind,
+                                                         const QString& status, const QString& input_hash,
+                                                         qint64 since_ms, int limit) {
+    QString sql = QString("SELECT %1 FROM agent_runs WHERE started_at >= ?").arg(kArCols);
+    QVariantList params{since_ms};
+    if (!text.isEmpty()) {
+        sql += " AND (query LIKE ? OR response LIKE ? OR agent_name LIKE ?)";
+        const QString like = "%" + text + "%";
+        params << like << like << like;
+    }
+    if (!kind.isEmpty()) {
+        sql += " AND kind = ?";
+        params.append(kind);
+    }
+    if (!status.isEmpty()) {
+        sql += " AND status = ?";
+        params.append(status);
+    }
+    if (!input_hash.isEmpty()) {
+        sql += " AND input_hash = ?";
+        params.append(input_hash);
+    }
+    sql += " ORDER BY started_at DESC LIMIT ?";
+    params.append(limit);
+    return query_list(sql, params, &AgentRunRepository::map_row);
+}
+
+std::optional<AgentRunRecord> AgentRunRepository::find_cached(const QString& input_hash, qint64 since_ms) {
+    return query_optional(QString("SELECT %1 FROM agent_runs WHERE input_hash = ? AND status = 'success' "
+                                  "AND started_at >= ? ORDER BY started_at DESC LIMIT 1")
+                              .arg(kArCols),
+                          {input_hash, since_ms}, &AgentRunRepository::map_row);
+}
+
+Result<void> AgentRunRepository::remove(const QString& id) {
+    return exec_write("DELETE FROM agent_runs WHERE id = ?", {id});
+}
+
+} // namespace fincept
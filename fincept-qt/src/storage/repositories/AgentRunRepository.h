@@ -0,0 +1,60 @@
+// src/storage/repositories/AgentRunRepository.h
+#pragma once
+#include "storage/repositories/BaseRepository.h"
+
+#include <QJsonObject>
+#include <QString>
+#include <QVector>
+
+#include <optional>
+
+namespace fincept {
+
+/// One agent execution. Schema in v070_agent_runs.
+struct AgentRunRecord {
+    QString id;     // AgentService request id
+    QString kind;   // run | stream | structured | team
+    QString query;
+    QJsonObject config; // agent / team config as passed, credentials stripped
+    QString output_model;
+    QString provider;
+    QString model;
+    QString agent_name;
+    QString input_hash; // sha256 over kind, query, config and output_model
+    QString status;     // running (default) | success | error
+    QString response;
+    QString error;
+    int input_tokens = 0;
+    int output_tokens = 0;
+    int total_tokens = 0; // 0 when the model did not report usage
+    int latency_ms = 0;
+    qint64 started_at = 0;  // epoch ms
+    qint64 finished_at = 0; // epoch ms, 0 while running
+    QString rerun_of;       // id of the run this one repeats
+};
+
+class AgentRunRepository : public BaseRepository<AgentRunRecord> {
+  public:
+    static AgentRunRepository& instance();
+
+    Result<void> insert(const AgentRunRecord& r);
+    /// Stores the outcome of a running row; finished rows are left alone.
+    Result<void> finish(const AgentRunRecord& r);
+    Result<void> set_rerun_of(const QString& id, const QString& rerun_of);
+    std::optional<AgentRunRecord> get(const QString& id);
+
+    /// Newest first. Empty filters match everything; `text` is a substring of
+    /// the query, response or agent name; `status` is success / error / running.
+    Result<QVector<AgentRunRecord>> list(const QString& text, const QString& kind, const QString& status,
+                                         const QString& input_hash, qint64 since_ms, int limit);
+    /// Latest successful run with these inputs started at or after `since_ms`.
+    std::optional<AgentRunRecord> find_cached(const QString& input_hash, qint64 since_ms);
+
+    Result<void> remove(const QString& id);
+
+  private:
+    AgentRunRepository() = default;
+    static AgentRunRecord map_row(QSqlQuery& q);
+};
+
+} // namespace fincept
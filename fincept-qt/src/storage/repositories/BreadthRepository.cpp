@@ -0,0 +1,139 @@
+#include "storage/repositories/BreadthRepository.h"
+
+#include <QJsonArray>
+#include <QJsonDocument>
+
+namespace fincept {
+
+namespace {
+
+const char* kBrRowColumns = "date, members, advances, declines, unchanged, up_volume, down_volume, new_highs, "
+                            "new_lows, above_50, eligible_50, above_200, eligible_200, ad_line, ema19, ema39, "
+                            "summation";
+
+QString br_symbols_json(const QStringList& symbols) {
+    return QString::fromUtf8(QJsonDocument(QJsonArray::fromStringList(symbols)).toJson(QJsonDocument::Compact));
+}
+
+BreadthUniverse br_map_universe(QSqlQuery& q) {
+    BreadthUniverse u;
+    u.name = q.value(0).toString();
+    u.exchange = q.value(1).toString();
+    for (const auto& v : QJsonDocument::fromJson(q.value(2).toString().toUtf8()).array())
+        u.symbols.append(v.toString());
+    u.last_update = q.value(3).toString();
+    return u;
+}
+
+} // namespace
+
+BreadthRepository& BreadthRepository::instance() {
+    static BreadthRepository s;
+    return s;
+}
+
+BreadthRow BreadthRepository::map_row(QSqlQuery& q) {
+    BreadthRow r;
+    r.date = q.value(0).toString();
+    r.members = q.value(1).toInt();
+    r.advances = q.value(2).toInt();
+    r.declines = q.value(3).toInt();
+    r.unchanged = q.value(4).toInt();
+    r.up_volume = q.value(5).toDouble();
+    r.down_volume = q.value(6).toDouble();
+    r.new_highs = q.value(7).toInt();
+    r.new_lows = q.value(8).toInt();
+    r.above_50 = q.value(9).toInt();
+    r.eligible_50 = q.value(10).toInt();
+    r.above_200 = q.value(11).toInt();
+    r.eligible_200 = q.value(12).toInt();
+    r.ad_line = q.value(13).toDouble();
+    r.ema19 = q.value(14).toDouble();
+    r.ema39 = q.value(15).toDouble();
+    r.summation = q.value(16).toDouble();
+    return r;
+}
+
+Result<QVector<BreadthUniverse>> BreadthRepository::list_universes() {
+    return query_list_as<BreadthUniverse>(
+        "SELECT name, exchange, symbols, last_update FROM breadth_universes ORDER BY name", {}, br_map_universe);
+}
+
+std::optional<BreadthUniverse> BreadthRepository::get_universe(const QString& name) {
+    auto r = query_list_as<BreadthUniverse>(
+        "SELECT name, exchange, symbols, last_update FROM breadth_universes WHERE name = ?", {name}, br_map_universe);
+    if (r.is_err() || r.value().isEmpty())
+        return std::nullopt;
+    return r.value().first();
+}
+
+Result<void> BreadthRepository::upsert_universe(const BreadthUniverse& u) {
+    const auto existing = get_universe(u.name);
+    if (existing && (existing->symbols != u.symbols || existing->exchange != u.exchange)) {
+        auto r = exec_write("DELETE FROM breadth_daily WHERE universe = ?", {u.name});
+        if (r.is_err())
+            return r;
+        r = exec_write("UPDATE breadth_universes SET exchange = ?, symbols = ?, last_update = NULL WHERE name = ?",
+                       {u.exchange, br_symbols_json(u.symbols), u.name});
+        return r;
+    }
+    if (existing)
+        return Result<void>::ok();
+    return exec_write("INSERT INTO breadth_universes (name, exchange, symbols) VALUES (?, ?, ?)",
+                      {u.name, u.exchange, br_symbols_json(u.symbols)});
+}
+
+Result<void> BreadthRepository::remove_universe(const QString& name) {
+    auto r = exec_write("DELETE FROM breadth_daily WHERE universe = ?", {name});
+    if (r.is_err())
+        return r;
+    return exec_write("DELETE FROM breadth_universes WHERE name = ?", {name});
+}
+
+Result<void> BreadthRepository::mark_updated(const QString& name) {
+    return exec_write("UPDATE breadth_universes SET last_update = datetime('now') WHERE name = ?", {name});
+}
+
+Result<QVector<BreadthRow>> BreadthRepository::get_rows(const QString& universe, const QString& since_date) {
+    return query_list(QString("SELECT %1 FROM breadth_daily WHERE universe = ? AND date >= ? ORDER BY date ASC")
+                          .arg(kBrRowColumns),
+                      {universe, since_date}, &BreadthRepository::map_row);
+}
+
+std::optional<BreadthRow> BreadthRepository::last_row(const QString& universe, const QString& before_date) {
+    if (before_date.isEmpty())
+        return query_optional(
+            QString("SELECT %1 FROM breadth_daily WHERE universe = ? ORDER BY date DESC LIMIT 1").arg(kBrRowColumns),
+            {universe}, &BreadthRepository::map_row);
+    return query_optional(
+        QString("SELECT %1 FROM breadth_daily WHERE universe = ? AND date < ? ORDER BY date DESC LIMIT 1")
+            .arg(kBrRowColumns),
+        {universe, before_date}, &BreadthRepository::map_row);
+}
+
+Result<void> BreadthRepository::replace_rows(const QString& universe, const QString& from_date,
+                                            const QVector<BreadthRow>& rows) {
+    if (auto tx = db().begin_transaction(); tx.is_err())
+        return tx;
+    auto r = exec_write("DELETE FROM breadth_daily WHERE universe = ? AND date >= ?", {universe, from_date});
+    for (int i = 0; r.is_ok() && i < rows.size(); ++i) {
+        const BreadthRow& b = rows[i];
+        r = exec_write(QString("INSERT INTO breadth_daily (universe, %1) "
+                               "VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+                           .arg(kBrRowColumns),
+                       {universe, b.date, b.members, b.advances, b.declines, b.unchanged, b.up_volume, b.down_volume,
+                        b.new_highs, b.new_lows, b.above_50, b.eligible_50, b.above_200, b.eligible_200, b.ad_line,
+                        b.ema19, b.ema39, b.summation});
+    }
+    if (r.is_err()) {
+        db().rollback();
+        return r;
+    }
+    if (auto c = db().commit(); c.is_err()) {
+        db().rollback();
+        return c;
+    }
+    return Result<void>::ok();
+}
+
+} // namespace fincept
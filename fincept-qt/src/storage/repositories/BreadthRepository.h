@@ -0,0 +1,72 @@
+#pragma once
+// BreadthRepository — market breadth universes and their daily series.
+//
+// Written by MarketBreadthService after each backfill / daily update; read
+// by the breadth MCP tools. Schema in v061_market_breadth.
+
+#include "storage/repositories/BaseRepository.h"
+
+#include <QString>
+#include <QStringList>
+
+namespace fincept {
+
+struct BreadthUniverse {
+    QString name;
+    QString exchange = "YF"; // Historify exchange key the daily candles live under
+    QStringList symbols;
+    QString last_update; // ISO timestamp of the last completed update, empty = never
+};
+
+/// One session of a universe. The running fields (ad_line, ema19, ema39,
+/// summation) carry the series forward; the McClellan oscillator is
+/// ema19 - ema39.
+struct BreadthRow {
+    QString date; // yyyy-MM-dd
+    int members = 0;
+    int advances = 0;
+    int declines = 0;
+    int unchanged = 0;
+    double up_volume = 0;
+    double down_volume = 0;
+    int new_highs = 0;
+    int new_lows = 0;
+    int above_50 = 0;
+    int eligible_50 = 0; // members with 50 sessions of history
+    int above_200 = 0;
+    int eligible_200 = 0;
+    double ad_line = 0;
+    double ema19 = 0;
+    double ema39 = 0;
+    double summation = 0;
+};
+
+class BreadthRepository : public BaseRepository<BreadthRow> {
+  public:
+    static BreadthRepository& instance();
+
+    Result<QVector<BreadthUniverse>> list_universes();
+    std::optional<BreadthUniverse> get_universe(const QString& name);
+
+    /// Creates or replaces a universe. A changed symbol list or exchange drops
+    /// the stored series, which no longer describes the universe.
+    Result<void> upsert_universe(const BreadthUniverse& u);
+    Result<void> remove_universe(const QString& name);
+    Result<void> mark_updated(const QString& name);
+
+    /// Rows on or after `since_date` (empty = all), ascending.
+    Result<QVector<BreadthRow>> get_rows(const QString& universe, const QString& since_date = {});
+
+    /// Latest row strictly before `date` (empty = latest overall).
+    std::optional<BreadthRow> last_row(const QString& universe, const QString& before_date = {});
+
+    /// Replaces every row on or after `from_date` with `rows`, in one
+    /// transaction. An empty `from_date` replaces the whole series.
+    Result<void> replace_rows(const QString& universe, const QString& from_date, const QVector<BreadthRow>& rows);
+
+  private:
+    BreadthRepository() = default;
+    static BreadthRow map_row(QSqlQuery& q);
+};
+
+} // namespace fincept
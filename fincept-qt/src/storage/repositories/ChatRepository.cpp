@@ -66,7 +66,7 @@ Result<void> ChatRepository::add_message(const QString& session_id, const QStrin
 
 Result<QVector<ChatMessage>> ChatRepository::get_messages(const QString& session_id) {
     auto r = db().execute("SELECT id, session_id, role, content, provider, model, tokens_used, timestamp "
-                          "FROM chat_messages WHERE session_id = ? ORDER BY timestamp ASC",
+                          "FROM chat_messages WHERE session_id = ? ORDER BY timestamp ASC, rowid ASC",
                           {session_id});
     if (r.is_err())
         return Result<QVector<ChatMessage>>::err(r.error());
@@ -78,7 +78,45 @@ Result<QVector<ChatMessage>> ChatRepository::get_messages(const QString& session
 }
 
 Result<void> ChatRepository::delete_messages(const QString& session_id) {
-    return exec_write("DELETE FROM chat_messages WHERE session_id = ?", {session_id});
+    auto r = exec_write("DELETE FROM chat_messages WHERE session_id = ?", {session_id});
+    if (r.is_err())
+        return r;
+    // The summary indexes into the message list — it is meaningless once that's gone.
+    return clear_memory(session_id);
+}
+
+Result<ChatSessionMemory> ChatRepository::get_memory(const QString& session_id) {
+    auto r = db().execute("SELECT session_id, summary, covered_count, summary_tokens, provider, model, updated_at "
+                          "FROM chat_session_memory WHERE session_id = ?",
+                          {session_id});
+    if (r.is_err())
+        return Result<ChatSessionMemory>::err(r.error());
+    ChatSessionMemory m;
+    m.session_id = session_id;
+    auto& q = r.value();
+    if (q.next()) {
+        m.summary = q.value(1).toString();
+        m.covered_count = q.value(2).toInt();
+        m.summary_tokens = q.value(3).toInt();
+        m.provider = q.value(4).toString();
+        m.model = q.value(5).toString();
+        m.updated_at = q.value(6).toString();
+    }
+    return Result<ChatSessionMemory>::ok(std::move(m));
+}
+
+Result<void> ChatRepository::save_memory(const ChatSessionMemory& m) {
+    return exec_write("INSERT INTO chat_session_memory "
+                      "(session_id, summary, covered_count, summary_tokens, provider, model, updated_at) "
+                      "VALUES (?, ?, ?, ?, ?, ?, datetime('now')) "
+                      "ON CONFLICT(session_id) DO UPDATE SET summary = excluded.summary, "
+                      "covered_count = excluded.covered_count, summary_tokens = excluded.summary_tokens, "
+                      "provider = excluded.provider, model = excluded.model, updated_at = excluded.updated_at",
+                      {m.session_id, m.summary, m.covered_count, m.summary_tokens, m.provider, m.model});
+}
+
+Result<void> ChatRepository::clear_memory(const QString& session_id) {
+    return exec_write("DELETE FROM chat_session_memory WHERE session_id = ?", {session_id});
 }
 
 } // namespace fincept
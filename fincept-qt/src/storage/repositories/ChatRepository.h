@@ -24,6 +24,17 @@ struct ChatMessage {
     QString timestamp;
 };
 
+/// Rolling summary of a session's oldest `covered_count` messages (v072).
+struct ChatSessionMemory {
+    QString session_id;
+    QString summary;
+    int covered_count = 0;
+    int summary_tokens = 0;
+    QString provider;
+    QString model;
+    QString updated_at;
+};
+
 class ChatRepository : public BaseRepository<ChatSession> {
   public:
     static ChatRepository& instance();
@@ -41,6 +52,12 @@ class ChatRepository : public BaseRepository<ChatSession> {
     Result<QVector<ChatMessage>> get_messages(const QString& session_id);
     Result<void> delete_messages(const QString& session_id);
 
+    // Memory (rolling summary). get_memory returns an empty memory (covered_count 0)
+    // when the session has never been summarized.
+    Result<ChatSessionMemory> get_memory(const QString& session_id);
+    Result<void> save_memory(const ChatSessionMemory& memory);
+    Result<void> clear_memory(const QString& session_id);
+
   private:
     ChatRepository() = default;
     static ChatSession map_session(QSqlQuery& q);
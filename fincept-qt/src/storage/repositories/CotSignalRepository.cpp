@@ -0,0 +1,138 @@
+// src/storage/repositories/CotSignalRepository.cpp
+#include "storage/repositories/CotSignalRepository.h"
+
+#include <QJsonDocument>
+#include <QUuid>
+
+namespace fincept {
+
+namespace {
+
+const char* kCotRuleCols = "id, name, market, report_type, trader_group, years, zscore_window, index_high, index_low, "
+                           "zscore_abs, toast, providers, active, last_checked_at, last_report_date";
+
+const char* kCotSignalCols = "id, rule_id, market, contract_code, report_type, trader_group, kind, report_date, "
+                             "cot_index, zscore, net, detail, detected_at";
+
+CotSignalRule cot_map_rule(QSqlQuery& q) {
+    CotSignalRule r;
+    r.id = q.value(0).toString();
+    r.name = q.value(1).toString();
+    r.market = q.value(2).toString();
+    r.report_type = q.value(3).toString();
+    r.trader_group = q.value(4).toString();
+    r.years = q.value(5).toDouble();
+    r.zscore_window = q.value(6).toInt();
+    r.index_high = q.value(7).toDouble();
+    r.index_low = q.value(8).toDouble();
+    r.zscore_abs = q.value(9).toDouble();
+    r.toast = q.value(10).toInt() != 0;
+    r.providers = q.value(11).toInt() != 0;
+    r.active = q.value(12).toInt() != 0;
+    r.last_checked_at = q.value(13).toLongLong();
+    r.last_report_date = q.value(14).toString();
+    return r;
+}
+
+} // namespace
+
+CotSignalRepository& CotSignalRepository::instance() {
+    static CotSignalRepository s;
+    return s;
+}
+
+CotSignal CotSignalRepository::map_row(QSqlQuery& q) {
+    CotSignal s;
+    s.id = q.value(0).toString();
+    s.rule_id = q.value(1).toString();
+    s.market = q.value(2).toString();
+    s.contract_code = q.value(3).toString();
+    s.report_type = q.value(4).toString();
+    s.trader_group = q.value(5).toString();
+    s.kind = q.value(6).toString();
+    s.report_date = q.value(7).toString();
+    s.cot_index = q.value(8).toDouble();
+    s.zscore = q.value(9).toDouble();
+    s.net = q.value(10).toLongLong();
+    s.detail = QJsonDocument::fromJson(q.value(11).toString().toUtf8()).object();
+    s.detected_at = q.value(12).toLongLong();
+    return s;
+}
+
+// ── Rules ───────────────────────────────────────────────────────────────────
+
+Result<CotSignalRule> CotSignalRepository::create_rule(const CotSignalRule& in) {
+    CotSignalRule r = in;
+    if (r.id.isEmpty())
+        r.id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    auto w = exec_write(QString("INSERT INTO cot_signal_rules (%1) "
+                                "VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+                            .arg(kCotRuleCols),
+                        {r.id, r.name, r.market, r.report_type, r.trader_group, r.years, r.zscore_window,
+                         r.index_high, r.index_low, r.zscore_abs, r.toast ? 1 : 0, r.providers ? 1 : 0,
+                         r.active ? 1 : 0, r.last_checked_at, r.last_report_date});
+    if (w.is_err())
+        return Result<CotSignalRule>::err(w.error());
+    return Result<CotSignalRule>::ok(r);
+}
+
+Result<void> CotSignalRepository::remove_rule(const QString& id) {
+    return exec_write("DELETE FROM cot_signal_rules WHERE id = ?", {id});
+}
+
+Result<QVector<CotSignalRule>> CotSignalRepository::list_rules() {
+    return query_list_as<CotSignalRule>(
+        QString("SELECT %1 FROM cot_signal_rules ORDER BY created_at").arg(kCotRuleCols), {}, cot_map_rule);
+}
+
+std::optional<CotSignalRule> CotSignalRepository::get_rule(const QString& id) {
+    auto r = query_list_as<CotSignalRule>(QString("SELECT %1 FROM cot_signal_rules WHERE id = ?").arg(kCotRuleCols),
+                                          {id}, cot_map_rule);
+    if (r.is_err() || r.value().isEmpty())
+        return std::nullopt;
+    return r.value().first();
+}
+
+Result<void> CotSignalRepository::touch_rule_checked(const QString& id, qint64 at_ms, const QString& report_date) {
+    return exec_write("UPDATE cot_signal_rules SET last_checked_at = ?, last_report_date = ? WHERE id = ?",
+                      {at_ms, report_date, id});
+}
+
+// ── Signals ─────────────────────────────────────────────────────────────────
+
+Result<bool> CotSignalRepository::record_signal(const CotSignal& in) {
+    CotSignal s = in;
+    if (s.id.isEmpty())
+        s.id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    auto r = db().execute(
+        QString("INSERT OR IGNORE INTO cot_signals (%1) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+            .arg(kCotSignalCols),
+        {s.id, s.rule_id, s.market, s.contract_code, s.report_type, s.trader_group, s.kind, s.report_date,
+         s.cot_index, s.zscore, s.net, QString::fromUtf8(QJsonDocument(s.detail).toJson(QJsonDocument::Compact)),
+         s.detected_at});
+    if (r.is_err()) {
+        LOG_ERROR("Repo", QString("record_signal failed: %1").arg(QString::fromStdString(r.error())));
+        return Result<bool>::err(r.error());
+    }
+    return Result<bool>::ok(r.value().numRowsAffected() > 0);
+}
+
+Result<QVector<CotSignal>> CotSignalRepository::query_signals(const QString& rule_id, const QString& market,
+                                                              qint64 since_ms, int limit) {
+    QString sql = QString("SELECT %1 FROM cot_signals WHERE detected_at >= ?").arg(kCotSignalCols);
+    QVariantList params{since_ms};
+    if (!rule_id.isEmpty()) {
+        sql += " AND rule_id = ?";
+        params.append(rule_id);
+    }
+    if (!market.isEmpty()) {
+        sql += " AND (UPPER(market) LIKE UPPER(?) OR contract_code = ?)";
+        params.append('%' + market + '%');
+        params.append(market);
+    }
+    sql += " ORDER BY detected_at DESC LIMIT ?";
+    params.append(limit);
+    return query_list(sql, params, &CotSignalRepository::map_row);
+}
+
+} // namespace fincept
@@ -0,0 +1,75 @@
+// src/storage/repositories/CotSignalRepository.h
+#pragma once
+#include "storage/repositories/BaseRepository.h"
+
+#include <QJsonObject>
+#include <QString>
+#include <QVector>
+
+namespace fincept {
+
+/// What to watch in the COT report and when it counts as a signal:
+/// COT index >= index_high (extreme_long), <= index_low (extreme_short), or a
+/// week-over-week net change of at least zscore_abs standard deviations
+/// (zscore_spike). A threshold of 0 disables that kind.
+struct CotSignalRule {
+    QString id;
+    QString name;
+    QString market;                                 // CFTC identifier: alias, contract code or name
+    QString report_type = QStringLiteral("legacy"); // legacy | disaggregated | financial
+    QString trader_group;                           // e.g. non_commercial, managed_money, leveraged_funds
+    double years = 3;                               // COT index lookback
+    int zscore_window = 52;                         // weeks behind the z-score
+    double index_high = 90;
+    double index_low = 10;
+    double zscore_abs = 2;
+    bool toast = true;
+    bool providers = false; // also send through the notification providers
+    bool active = true;
+    qint64 last_checked_at = 0; // epoch ms
+    QString last_report_date;   // yyyy-MM-dd of the last report evaluated
+};
+
+/// kind: extreme_long | extreme_short | zscore_spike
+struct CotSignal {
+    QString id;
+    QString rule_id;
+    QString market;
+    QString contract_code;
+    QString report_type;
+    QString trader_group;
+    QString kind;
+    QString report_date; // yyyy-MM-dd
+    double cot_index = 0;
+    double zscore = 0;
+    qint64 net = 0;
+    QJsonObject detail;
+    qint64 detected_at = 0; // epoch ms
+};
+
+class CotSignalRepository : public BaseRepository<CotSignal> {
+  public:
+    static CotSignalRepository& instance();
+
+    // ── Rules ───────────────────────────────────────────────────────────
+    Result<CotSignalRule> create_rule(const CotSignalRule& r);
+    Result<void> remove_rule(const QString& id);
+    Result<QVector<CotSignalRule>> list_rules();
+    std::optional<CotSignalRule> get_rule(const QString& id);
+    Result<void> touch_rule_checked(const QString& id, qint64 at_ms, const QString& report_date);
+
+    // ── Signals ─────────────────────────────────────────────────────────
+    /// Stores `s` unless the rule already produced this kind for the same
+    /// report date; returns whether it was new.
+    Result<bool> record_signal(const CotSignal& s);
+    /// Newest first. `market` matches part of the market name or the contract
+    /// code; empty `rule_id` / `market` match everything.
+    Result<QVector<CotSignal>> query_signals(const QString& rule_id, const QString& market, qint64 since_ms,
+                                             int limit);
+
+  private:
+    CotSignalRepository() = default;
+    static CotSignal map_row(QSqlQuery& q);
+};
+
+} // namespace fincept
@@ -0,0 +1,179 @@
+// src/storage/repositories/CryptoFundingRepository.cpp
+#include "storage/repositories/CryptoFundingRepository.h"
+
+#include <QJsonDocument>
+#include <QUuid>
+
+namespace fincept {
+
+namespace {
+
+const char* kCfPointCols = "exchange, asset, symbol, ts, funding_rate, interval_hours, rate_8h, apr, next_funding_ts, "
+                           "mark_price, index_price, open_interest, oi_usd";
+
+const char* kCfRuleCols =
+    "id, name, asset, metric, exchange, op, threshold, cooldown_min, toast, providers, active, last_fired_at";
+
+const char* kCfAlertCols = "id, rule_id, asset, metric, exchange, value, threshold, detail, fired_at";
+
+FundingAlertRule cf_map_rule(QSqlQuery& q) {
+    FundingAlertRule r;
+    r.id = q.value(0).toString();
+    r.name = q.value(1).toString();
+    r.asset = q.value(2).toString();
+    r.metric = q.value(3).toString();
+    r.exchange = q.value(4).toString();
+    r.op = q.value(5).toString();
+    r.threshold = q.value(6).toDouble();
+    r.cooldown_min = q.value(7).toInt();
+    r.toast = q.value(8).toInt() != 0;
+    r.providers = q.value(9).toInt() != 0;
+    r.active = q.value(10).toInt() != 0;
+    r.last_fired_at = q.value(11).toLongLong();
+    return r;
+}
+
+FundingAlert cf_map_alert(QSqlQuery& q) {
+    FundingAlert a;
+    a.id = q.value(0).toString();
+    a.rule_id = q.value(1).toString();
+    a.asset = q.value(2).toString();
+    a.metric = q.value(3).toString();
+    a.exchange = q.value(4).toString();
+    a.value = q.value(5).toDouble();
+    a.threshold = q.value(6).toDouble();
+    a.detail = QJsonDocument::fromJson(q.value(7).toString().toUtf8()).object();
+    a.fired_at = q.value(8).toLongLong();
+    return a;
+}
+
+} // namespace
+
+CryptoFundingRepository& CryptoFundingRepository::instance() {
+    static CryptoFundingRepository s;
+    return s;
+}
+
+FundingPoint CryptoFundingRepository::map_row(QSqlQuery& q) {
+    FundingPoint p;
+    p.exchange = q.value(0).toString();
+    p.asset = q.value(1).toString();
+    p.symbol = q.value(2).toString();
+    p.ts = q.value(3).toLongLong();
+    p.funding_rate = q.value(4).toDouble();
+    p.interval_hours = q.value(5).toDouble();
+    p.rate_8h = q.value(6).toDouble();
+    p.apr = q.value(7).toDouble();
+    p.next_funding_ts = q.value(8).toLongLong();
+    p.mark_price = q.value(9).toDouble();
+    p.index_price = q.value(10).toDouble();
+    p.open_interest = q.value(11).toDouble();
+    p.oi_usd = q.value(12).toDouble();
+    return p;
+}
+
+// ── Snapshots ───────────────────────────────────────────────────────────────
+
+Result<void> CryptoFundingRepository::store(const QVector<FundingPoint>& points) {
+    if (points.isEmpty())
+        return Result<void>::ok();
+    auto tx = db().begin_transaction();
+    if (tx.is_err())
+        return tx;
+    const QString sql = QString("INSERT OR REPLACE INTO crypto_funding (%1) "
+                                "VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+                            .arg(kCfPointCols);
+    for (const auto& p : points) {
+        auto w = exec_write(sql, {p.exchange, p.asset, p.symbol, p.ts, p.funding_rate, p.interval_hours, p.rate_8h,
+                                  p.apr, p.next_funding_ts, p.mark_price, p.index_price, p.open_interest, p.oi_usd});
+        if (w.is_err()) {
+            db().rollback();
+            return w;
+        }
+    }
+    return db().commit();
+}
+
+Result<QVector<FundingPoint>> CryptoFundingRepository::history(const QString& asset, const QString& exchange,
+                                                               qint64 from_ms, int limit) {
+    QString sql = QString("SELECT %1 FROM crypto_funding WHERE asset = ? AND ts >= ?").arg(kCfPointCols);
+    QVariantList params{asset.toUpper(), from_ms};
+    if (!exchange.isEmpty()) {
+        sql += " AND exchange = ?";
+        params.append(exchange.toLower());
+    }
+    // Newest `limit` rows, returned oldest first.
+    sql = QString("SELECT * FROM (%1 ORDER BY ts DESC LIMIT ?) ORDER BY ts ASC").arg(sql);
+    params.append(limit);
+    return query_list(sql, params, &CryptoFundingRepository::map_row);
+}
+
+Result<QVector<FundingPoint>> CryptoFundingRepository::latest_before(const QString& asset, qint64 at_ms) {
+    return query_list(QString("SELECT %1 FROM crypto_funding f WHERE asset = ? AND ts = (SELECT MAX(ts) FROM "
+                              "crypto_funding WHERE asset = f.asset AND exchange = f.exchange AND ts <= ?)")
+                          .arg(kCfPointCols),
+                      {asset.toUpper(), at_ms}, &CryptoFundingRepository::map_row);
+}
+
+Result<void> CryptoFundingRepository::prune(qint64 before_ms) {
+    return exec_write("DELETE FROM crypto_funding WHERE ts < ?", {before_ms});
+}
+
+// ── Rules ───────────────────────────────────────────────────────────────────
+
+Result<FundingAlertRule> CryptoFundingRepository::create_rule(const FundingAlertRule& in) {
+    FundingAlertRule r = in;
+    if (r.id.isEmpty())
+        r.id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    auto w = exec_write(
+        QString("INSERT INTO crypto_funding_rules (%1) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)").arg(kCfRuleCols),
+        {r.id, r.name, r.asset, r.metric, r.exchange, r.op, r.threshold, r.cooldown_min, r.toast ? 1 : 0,
+         r.providers ? 1 : 0, r.active ? 1 : 0, r.last_fired_at});
+    if (w.is_err())
+        return Result<FundingAlertRule>::err(w.error());
+    return Result<FundingAlertRule>::ok(r);
+}
+
+Result<void> CryptoFundingRepository::remove_rule(const QString& id) {
+    return exec_write("DELETE FROM crypto_funding_rules WHERE id = ?", {id});
+}
+
+Result<QVector<FundingAlertRule>> CryptoFundingRepository::list_rules() {
+    return query_list_as<FundingAlertRule>(
+        QString("SELECT %1 FROM crypto_funding_rules ORDER BY created_at").arg(kCfRuleCols), {}, cf_map_rule);
+}
+
+Result<void> CryptoFundingRepository::touch_rule_fired(const QString& id, qint64 at_ms) {
+    return exec_write("UPDATE crypto_funding_rules SET last_fired_at = ? WHERE id = ?", {at_ms, id});
+}
+
+// ── Alerts ──────────────────────────────────────────────────────────────────
+
+Result<void> CryptoFundingRepository::record_alert(const FundingAlert& in) {
+    FundingAlert a = in;
+    if (a.id.isEmpty())
+        a.id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    return exec_write(
+        QString("INSERT INTO crypto_funding_alerts (%1) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)").arg(kCfAlertCols),
+        {a.id, a.rule_id, a.asset, a.metric, a.exchange, a.value, a.threshold,
+         QString::fromUtf8(QJsonDocument(a.detail).toJson(QJsonDocument::Compact)), a.fired_at});
+}
+
+Result<QVector<FundingAlert>> CryptoFundingRepository::query_alerts(const QString& rule_id, const QString& asset,
+                                                                    qint64 since_ms, int limit) {
+    QString sql = QString("SELECT %1 FROM crypto_funding_alerts WHERE fired_at >= ?").arg(kCfAlertCols);
+    QVariantList params{since_ms};
+    if (!rule_id.isEmpty()) {
+        sql += " AND rule_id = ?";
+        params.append(rule_id);
+    }
+    if (!asset.isEmpty()) {
+        sql += " AND asset = ?";
+        params.append(asset.toUpper());
+    }
+    sql += " ORDER BY fired_at DESC LIMIT ?";
+    params.append(limit);
+    return query_list_as<FundingAlert>(sql, params, cf_map_alert);
+}
+
+} // namespace fincept
@@ -0,0 +1,95 @@
+// src/storage/repositories/CryptoFundingRepository.h
+#pragma once
+#include "storage/repositories/BaseRepository.h"
+
+#include <QJsonObject>
+#include <QString>
+#include <QVector>
+
+#include <optional>
+
+namespace fincept {
+
+/// One exchange's perpetual funding and open interest for an asset at one
+/// poll. Schema in v068_crypto_funding.
+struct FundingPoint {
+    QString exchange;          // binance | bybit | hyperliquid
+    QString asset;             // base asset, contract-size prefixes stripped (1000PEPE, kPEPE → PEPE)
+    QString symbol;            // venue symbol (BTCUSDT, BTC)
+    qint64 ts = 0;             // epoch ms of the poll
+    double funding_rate = 0;   // per interval_hours, decimal
+    double interval_hours = 8; // 1 on Hyperliquid, 8 (some 4) on Binance / Bybit
+    double rate_8h = 0;        // funding_rate normalised to 8h
+    double apr = 0;            // annualised, percent
+    qint64 next_funding_ts = 0;
+    double mark_price = 0;
+    double index_price = 0;
+    double open_interest = 0; // base units
+    double oi_usd = 0;
+};
+
+/// When a funding metric counts as an alert. metric: funding_apr (one
+/// exchange, or OI-weighted across all when exchange is empty) |
+/// arb_spread_apr (highest minus lowest exchange APR) | oi_usd (one exchange
+/// or the total) | oi_change_pct (total, over 24h). op: above | below |
+/// abs_above.
+struct FundingAlertRule {
+    QString id;
+    QString name;
+    QString asset;
+    QString metric;
+    QString exchange; // empty = aggregate
+    QString op = QStringLiteral("above");
+    double threshold = 0;
+    int cooldown_min = 240; // minimum gap between two alerts of this rule
+    bool toast = true;
+    bool providers = false; // also send through the notification providers
+    bool active = true;
+    qint64 last_fired_at = 0; // epoch ms
+};
+
+struct FundingAlert {
+    QString id;
+    QString rule_id;
+    QString asset;
+    QString metric;
+    QString exchange;
+    double value = 0;
+    double threshold = 0;
+    QJsonObject detail;
+    qint64 fired_at = 0; // epoch ms
+};
+
+class CryptoFundingRepository : public BaseRepository<FundingPoint> {
+  public:
+    static CryptoFundingRepository& instance();
+
+    // ── Snapshots ───────────────────────────────────────────────────────
+    /// Upserts one poll's rows in a single transaction.
+    Result<void> store(const QVector<FundingPoint>& points);
+    /// Ascending by time; empty `exchange` matches all.
+    Result<QVector<FundingPoint>> history(const QString& asset, const QString& exchange, qint64 from_ms,
+                                          int limit = 5000);
+    /// Each exchange's latest row for `asset` at or before `at_ms`.
+    Result<QVector<FundingPoint>> latest_before(const QString& asset, qint64 at_ms);
+    /// Deletes rows older than `before_ms`.
+    Result<void> prune(qint64 before_ms);
+
+    // ── Rules ───────────────────────────────────────────────────────────
+    Result<FundingAlertRule> create_rule(const FundingAlertRule& r);
+    Result<void> remove_rule(const QString& id);
+    Result<QVector<FundingAlertRule>> list_rules();
+    Result<void> touch_rule_fired(const QString& id, qint64 at_ms);
+
+    // ── Alerts ──────────────────────────────────────────────────────────
+    Result<void> record_alert(const FundingAlert& a);
+    /// Newest first; empty `rule_id` / `asset` match everything.
+    Result<QVector<FundingAlert>> query_alerts(const QString& rule_id, const QString& asset, qint64 since_ms,
+                                               int limit);
+
+  private:
+    CryptoFundingRepository() = default;
+    static FundingPoint map_row(QSqlQuery& q);
+};
+
+} // namespace fincept
@@ -9,6 +9,21 @@
 
 namespace fincept {
 
+namespace {
+
+const char* kIndexCols = "id, name, method, base_value, portfolio_id, constituents_json, created_at, updated_at, "
+                         "symbol, rebalance, options_json, start_date, computed_at";
+
+QString json_text(const QJsonObject& o) {
+    return QString::fromUtf8(QJsonDocument(o).toJson(QJsonDocument::Compact));
+}
+
+QVariant or_null(const QString& s) {
+    return s.isEmpty() ? QVariant() : QVariant(s);
+}
+
+} // namespace
+
 CustomIndexRepository& CustomIndexRepository::instance() {
     static CustomIndexRepository s;
     return s;
@@ -56,6 +71,11 @@ CustomIndex CustomIndexRepository::map_index(QSqlQuery& q) {
     idx.constituents = json_to_constituents(q.value(5).toString());
     idx.created_at = q.value(6).toString();
     idx.updated_at = q.value(7).toString();
+    idx.symbol = q.value(8).toString();
+    idx.rebalance = q.value(9).toString();
+    idx.options = QJsonDocument::fromJson(q.value(10).toString().toUtf8()).object();
+    idx.start_date = q.value(11).toString();
+    idx.computed_at = q.value(12).toLongLong();
     return idx;
 }
 
@@ -72,26 +92,36 @@ CustomIndexValue CustomIndexRepository::map_value(QSqlQuery& q) {
 
 Result<QString> CustomIndexRepository::create(const CustomIndex& idx) {
     const QString id = QUuid::createUuid().toString(QUuid::WithoutBraces);
-    auto r = exec_write("INSERT INTO custom_indices (id, name, method, base_value, portfolio_id, constituents_json) "
-                        "VALUES (?, ?, ?, ?, ?, ?)",
-                        {id, idx.name, idx.method, idx.base_value,
-                         idx.portfolio_id.isEmpty() ? QVariant() : QVariant(idx.portfolio_id),
-                         constituents_to_json(idx.constituents)});
+    auto r = exec_write("INSERT INTO custom_indices (id, name, method, base_value, portfolio_id, constituents_json, "
+                        "symbol, rebalance, options_json, start_date) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                        {id, idx.name, idx.method, idx.base_value, or_null(idx.portfolio_id),
+                         constituents_to_json(idx.constituents), or_null(idx.symbol), idx.rebalance,
+                         json_text(idx.options), or_null(idx.start_date)});
     if (r.is_err())
         return Result<QString>::err(r.error());
     return Result<QString>::ok(id);
 }
 
 Result<QVector<CustomIndex>> CustomIndexRepository::list_all() {
-    return query_list("SELECT id, name, method, base_value, portfolio_id, constituents_json, created_at, updated_at "
-                      "FROM custom_indices ORDER BY created_at DESC",
-                      {}, map_index);
+    return query_list(QString("SELECT %1 FROM custom_indices ORDER BY created_at DESC").arg(kIndexCols), {},
+                      map_index);
 }
 
 Result<CustomIndex> CustomIndexRepository::get(const QString& id) {
-    return query_one("SELECT id, name, method, base_value, portfolio_id, constituents_json, created_at, updated_at "
-                     "FROM custom_indices WHERE id = ?",
-                     {id}, map_index);
+    return query_one(QString("SELECT %1 FROM custom_indices WHERE id = ?").arg(kIndexCols), {id}, map_index);
+}
+
+Result<CustomIndex> CustomIndexRepository::get_by_symbol(const QString& symbol) {
+    return query_one(QString("SELECT %1 FROM custom_indices WHERE symbol = ?").arg(kIndexCols), {symbol}, map_index);
+}
+
+Result<void> CustomIndexRepository::update(const CustomIndex& idx) {
+    return exec_write("UPDATE custom_indices SET name = ?, method = ?, base_value = ?, portfolio_id = ?, "
+                      "constituents_json = ?, symbol = ?, rebalance = ?, options_json = ?, start_date = ?, "
+                      "updated_at = datetime('now') WHERE id = ?",
+                      {idx.name, idx.method, idx.base_value, or_null(idx.portfolio_id),
+                       constituents_to_json(idx.constituents), or_null(idx.symbol), idx.rebalance,
+                       json_text(idx.options), or_null(idx.start_date), idx.id});
 }
 
 Result<void> CustomIndexRepository::remove(const QString& id) {
@@ -111,6 +141,45 @@ Result<QVector<CustomIndexValue>> CustomIndexRepository::get_values(const QStrin
                                            {index_id, limit}, std::function<CustomIndexValue(QSqlQuery&)>(map_value));
 }
 
+Result<void> CustomIndexRepository::replace_series(const QString& index_id,
+                                                   const QVector<QPair<QString, double>>& values,
+                                                   const QVector<CustomIndexRebalance>& rebalances,
+                                                   qint64 computed_at) {
+    if (auto tx = db().begin_transaction(); tx.is_err())
+        return tx;
+    auto r = exec_write("DELETE FROM custom_index_values WHERE index_id = ?", {index_id});
+    if (r.is_ok())
+        r = exec_write("DELETE FROM custom_index_rebalances WHERE index_id = ?", {index_id});
+    for (int i = 0; r.is_ok() && i < values.size(); ++i)
+        r = exec_write("INSERT INTO custom_index_values (index_id, date, value) VALUES (?, ?, ?)",
+                       {index_id, values[i].first, values[i].second});
+    for (int i = 0; r.is_ok() && i < rebalances.size(); ++i)
+        r = exec_write("INSERT INTO custom_index_rebalances (index_id, date, weights_json) VALUES (?, ?, ?)",
+                       {index_id, rebalances[i].date, json_text(rebalances[i].weights)});
+    if (r.is_ok())
+        r = exec_write("UPDATE custom_indices SET computed_at = ? WHERE id = ?", {computed_at, index_id});
+    if (r.is_err()) {
+        db().rollback();
+        return r;
+    }
+    if (auto c = db().commit(); c.is_err()) {
+        db().rollback();
+        return c;
+    }
+    return Result<void>::ok();
+}
+
+Result<QVector<CustomIndexRebalance>> CustomIndexRepository::get_rebalances(const QString& index_id) {
+    return query_list_as<CustomIndexRebalance>(
+        "SELECT date, weights_json FROM custom_index_rebalances WHERE index_id = ? ORDER BY date", {index_id},
+        std::function<CustomIndexRebalance(QSqlQuery&)>([](QSqlQuery& q) {
+            CustomIndexRebalance rb;
+            rb.date = q.value(0).toString();
+            rb.weights = QJsonDocument::fromJson(q.value(1).toString().toUtf8()).object();
+            return rb;
+        }));
+}
+
 double CustomIndexRepository::latest_value(const QString& index_id) {
     auto r = db().execute("SELECT id, index_id, date, value FROM custom_index_values "
                           "WHERE index_id = ? ORDER BY date DESC LIMIT 1",
@@ -2,6 +2,8 @@
 #pragma once
 #include "storage/repositories/BaseRepository.h"
 
+#include <QJsonObject>
+
 namespace fincept {
 
 struct CustomIndexConstituent {
@@ -19,6 +21,16 @@ struct CustomIndex {
     QVector<CustomIndexConstituent> constituents;
     QString created_at;
     QString updated_at;
+    QString symbol;             // synthetic ticker (@NAME), empty for indices created before v075
+    QString rebalance = "none"; // none, monthly, quarterly, annual
+    QJsonObject options;        // weighting parameters (metric, vol_lookback, max_weight)
+    QString start_date;         // YYYY-MM-DD, empty = earliest cached history
+    qint64 computed_at = 0;     // last rebuild from the candle cache, epoch ms
+};
+
+struct CustomIndexRebalance {
+    QString date;        // YYYY-MM-DD, weights take effect at that close
+    QJsonObject weights; // symbol → weight (0–1)
 };
 
 struct CustomIndexValue {
@@ -36,12 +48,21 @@ class CustomIndexRepository : public BaseRepository<CustomIndex> {
     Result<QString> create(const CustomIndex& idx);
     Result<QVector<CustomIndex>> list_all();
     Result<CustomIndex> get(const QString& id);
+    Result<CustomIndex> get_by_symbol(const QString& symbol);
+    /// Rewrites the definition (everything but id, created_at and computed_at).
+    Result<void> update(const CustomIndex& idx);
     Result<void> remove(const QString& id);
 
     // ── Index values ──────────────────────────────────────────────────────────
     Result<void> save_value(const QString& index_id, const QString& date, double value);
     Result<QVector<CustomIndexValue>> get_values(const QString& index_id, int limit = 365);
 
+    /// Replaces the stored values and rebalances of an index with a full
+    /// rebuild and stamps computed_at, in one transaction.
+    Result<void> replace_series(const QString& index_id, const QVector<QPair<QString, double>>& values,
+                                const QVector<CustomIndexRebalance>& rebalances, qint64 computed_at);
+    Result<QVector<CustomIndexRebalance>> get_rebalances(const QString& index_id);
+
     // ── Convenience: latest computed value for an index ───────────────────────
     double latest_value(const QString& index_id);
 
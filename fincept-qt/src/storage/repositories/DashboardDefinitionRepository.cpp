@@ -0,0 +1,117 @@
+// src/storage/repositories/DashboardDefinitionRepository.cpp
+#include "storage/repositories/DashboardDefinitionRepository.h"
+
+#include <QJsonDocument>
+#include <QUuid>
+
+namespace fincept {
+
+using services::dashboards::DashboardDef;
+using services::dashboards::DashboardWidgetDef;
+
+DashboardDefinitionRepository& DashboardDefinitionRepository::instance() {
+    static DashboardDefinitionRepository s;
+    return s;
+}
+
+DashboardDef DashboardDefinitionRepository::map_header(QSqlQuery& q) {
+    DashboardDef d;
+    d.id = q.value(0).toString();
+    d.name = q.value(1).toString();
+    d.description = q.value(2).toString();
+    d.created_at = q.value(3).toString();
+    d.updated_at = q.value(4).toString();
+    return d;
+}
+
+DashboardWidgetDef DashboardDefinitionRepository::map_widget(QSqlQuery& q) {
+    DashboardWidgetDef w;
+    w.widget_id = q.value(0).toString();
+    w.widget_type = q.value(1).toString();
+    w.title = q.value(2).toString();
+    for (const auto& v : QJsonDocument::fromJson(q.value(3).toString().toUtf8()).array())
+        w.bindings << v.toString();
+    w.refresh_ms = q.value(4).toInt();
+    w.x = q.value(5).toInt();
+    w.y = q.value(6).toInt();
+    w.w = q.value(7).toInt();
+    w.h = q.value(8).toInt();
+    const auto cfg = QJsonDocument::fromJson(q.value(9).toString().toUtf8());
+    if (cfg.isObject())
+        w.config = cfg.object();
+    return w;
+}
+
+Result<QVector<DashboardDef>> DashboardDefinitionRepository::list() {
+    return query_list("SELECT id, name, COALESCE(description, ''), created_at, updated_at "
+                      "FROM dashboard_definitions ORDER BY name COLLATE NOCASE",
+                      {}, map_header);
+}
+
+Result<DashboardDef> DashboardDefinitionRepository::get(const QString& id) {
+    auto head = query_one("SELECT id, name, COALESCE(description, ''), created_at, updated_at "
+                          "FROM dashboard_definitions WHERE id = ?",
+                          {id}, map_header);
+    if (head.is_err())
+        return head;
+    auto widgets = query_list_as<DashboardWidgetDef>(
+        "SELECT widget_id, widget_type, COALESCE(title, ''), bindings_json, refresh_ms, "
+        "grid_x, grid_y, grid_w, grid_h, COALESCE(config_json, '') "
+        "FROM dashboard_definition_widgets WHERE dashboard_id = ? ORDER BY sort_order ASC",
+        {id}, map_widget);
+    if (widgets.is_err())
+        return Result<DashboardDef>::err(widgets.error());
+    DashboardDef d = head.value();
+    d.widgets = widgets.value();
+    return Result<DashboardDef>::ok(std::move(d));
+}
+
+Result<QString> DashboardDefinitionRepository::save(const DashboardDef& def) {
+    const QString id = def.id.isEmpty() ? QUuid::createUuid().toString(QUuid::WithoutBraces) : def.id;
+    if (auto tx = db().begin_transaction(); tx.is_err())
+        return Result<QString>::err(tx.error());
+
+    const auto body = [&]() -> Result<void> {
+        auto r = exec_write("INSERT INTO dashboard_definitions (id, name, description, updated_at) "
+                            "VALUES (?, ?, ?, datetime('now')) "
+                            "ON CONFLICT(id) DO UPDATE SET name = excluded.name, "
+                            "description = excluded.description, updated_at = excluded.updated_at",
+                            {id, def.name, def.description});
+        if (r.is_err())
+            return r;
+        r = exec_write("DELETE FROM dashboard_definition_widgets WHERE dashboard_id = ?", {id});
+        if (r.is_err())
+            return r;
+        int sort = 0;
+        for (const auto& w : def.widgets) {
+            const QString bindings =
+                QString::fromUtf8(QJsonDocument(QJsonArray::fromStringList(w.bindings)).toJson(QJsonDocument::Compact));
+            const QString cfg = QString::fromUtf8(QJsonDocument(w.config).toJson(QJsonDocument::Compact));
+            const QString wid =
+                w.widget_id.isEmpty() ? QUuid::createUuid().toString(QUuid::WithoutBraces) : w.widget_id;
+            r = exec_write("INSERT INTO dashboard_definition_widgets "
+                           "(dashboard_id, widget_id, widget_type, title, bindings_json, refresh_ms, "
+                           "grid_x, grid_y, grid_w, grid_h, config_json, sort_order) "
+                           "VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                           {id, wid, w.widget_type, w.title, bindings, w.refresh_ms, w.x, w.y, w.w, w.h, cfg, sort++});
+            if (r.is_err())
+                return r;
+        }
+        return Result<void>::ok();
+    };
+
+    if (Result<void> br = body(); br.is_err()) {
+        db().rollback();
+        return Result<QString>::err(br.error());
+    }
+    if (auto c = db().commit(); c.is_err())
+        return Result<QString>::err(c.error());
+    return Result<QString>::ok(id);
+}
+
+Result<void> DashboardDefinitionRepository::remove(const QString& id) {
+    // Widgets go with the header via ON DELETE CASCADE.
+    return exec_write("DELETE FROM dashboard_definitions WHERE id = ?", {id});
+}
+
+} // namespace fincept
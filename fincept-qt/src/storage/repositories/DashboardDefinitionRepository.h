@@ -0,0 +1,29 @@
+// src/storage/repositories/DashboardDefinitionRepository.h
+#pragma once
+#include "services/dashboards/DashboardDefinition.h"
+#include "storage/repositories/BaseRepository.h"
+
+namespace fincept {
+
+/// Saved dashboard definitions + widget bindings (migration v052).
+class DashboardDefinitionRepository : public BaseRepository<services::dashboards::DashboardDef> {
+  public:
+    static DashboardDefinitionRepository& instance();
+
+    /// Headers only (id, name, description, timestamps); `widgets` left empty.
+    Result<QVector<services::dashboards::DashboardDef>> list();
+    Result<services::dashboards::DashboardDef> get(const QString& id);
+
+    /// Upsert the header and replace all widgets atomically. An empty `id`
+    /// creates a new dashboard; returns the id either way.
+    Result<QString> save(const services::dashboards::DashboardDef& def);
+    Result<void> remove(const QString& id);
+
+  private:
+    DashboardDefinitionRepository() = default;
+
+    static services::dashboards::DashboardDef map_header(QSqlQuery& q);
+    static services::dashboards::DashboardWidgetDef map_widget(QSqlQuery& q);
+};
+
+} // namespace fincept
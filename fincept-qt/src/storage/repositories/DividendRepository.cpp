@@ -0,0 +1,92 @@
+// src/storage/repositories/DividendRepository.cpp
+#include "storage/repositories/DividendRepository.h"
+
+#include "core/logging/Logger.h"
+
+namespace fincept {
+
+static constexpr const char* kSelectCols =
+    "SELECT symbol, ex_date, COALESCE(pay_date, ''), amount, COALESCE(currency, ''), status, "
+    "COALESCE(alerted_at, '') FROM dividend_events ";
+
+DividendRepository& DividendRepository::instance() {
+    static DividendRepository s;
+    return s;
+}
+
+portfolio::DividendEvent DividendRepository::map_event(QSqlQuery& q) {
+    portfolio::DividendEvent e;
+    e.symbol = q.value(0).toString();
+    e.ex_date = q.value(1).toString();
+    e.pay_date = q.value(2).toString();
+    e.amount = q.value(3).toDouble();
+    e.currency = q.value(4).toString();
+    e.status = q.value(5).toString();
+    e.alerted_at = q.value(6).toString();
+    return e;
+}
+
+Result<void> DividendRepository::upsert_events(const QVector<portfolio::DividendEvent>& events) {
+    if (events.isEmpty())
+        return Result<void>::ok();
+    auto begin = db().begin_transaction();
+    const bool in_tx = begin.is_ok();
+    for (const auto& e : events) {
+        // pay_date/amount only overwrite when the new value is known; a paid
+        // row stays paid even if the provider still lists it as upcoming.
+        auto r = exec_write(
+            "INSERT INTO dividend_events (symbol, ex_date, pay_date, amount, currency, status, synced_at) "
+            "VALUES (?, ?, NULLIF(?, ''), ?, NULLIF(?, ''), ?, datetime('now')) "
+            "ON CONFLICT(symbol, ex_date) DO UPDATE SET "
+            "  pay_date  = COALESCE(excluded.pay_date, dividend_events.pay_date),"
+            "  amount    = CASE WHEN excluded.amount > 0 THEN excluded.amount ELSE dividend_events.amount END,"
+            "  currency  = COALESCE(excluded.currency, dividend_events.currency),"
+            "  status    = CASE WHEN dividend_events.status = 'paid' THEN 'paid' ELSE excluded.status END,"
+            "  synced_at = datetime('now')",
+            {e.symbol, e.ex_date, e.pay_date, e.amount, e.currency, e.status});
+        if (r.is_err()) {
+            if (in_tx)
+                db().rollback();
+            return r;
+        }
+    }
+    if (in_tx) {
+        auto c = db().commit();
+        if (c.is_err())
+            return c;
+    }
+    return Result<void>::ok();
+}
+
+Result<QVector<portfolio::DividendEvent>> DividendRepository::get_events(const QStringList& symbols,
+                                                                        const QString& from_date) {
+    if (symbols.isEmpty())
+        return Result<QVector<portfolio::DividendEvent>>::ok({});
+    QStringList marks;
+    QVariantList params;
+    for (const auto& s : symbols) {
+        marks << "?";
+        params << s;
+    }
+    QString sql = QString(kSelectCols) + "WHERE symbol IN (" + marks.join(',') + ")";
+    if (!from_date.isEmpty()) {
+        sql += " AND ex_date >= ?";
+        params << from_date;
+    }
+    sql += " ORDER BY ex_date ASC";
+    return query_list(sql, params, &DividendRepository::map_event);
+}
+
+Result<QVector<portfolio::DividendEvent>> DividendRepository::pending_alerts(const QString& from_date,
+                                                                            const QString& to_date) {
+    return query_list(QString(kSelectCols) +
+                          "WHERE alerted_at IS NULL AND ex_date >= ? AND ex_date <= ? ORDER BY ex_date ASC",
+                      {from_date, to_date}, &DividendRepository::map_event);
+}
+
+Result<void> DividendRepository::mark_alerted(const QString& symbol, const QString& ex_date) {
+    return exec_write("UPDATE dividend_events SET alerted_at = datetime('now') WHERE symbol = ? AND ex_date = ?",
+                      {symbol, ex_date});
+}
+
+} // namespace fincept
@@ -0,0 +1,34 @@
+// src/storage/repositories/DividendRepository.h
+#pragma once
+#include "screens/portfolio/PortfolioTypes.h"
+#include "storage/repositories/BaseRepository.h"
+
+#include <QStringList>
+
+namespace fincept {
+
+/// Shared dividend calendar (migration v051). Not per-portfolio: the same
+/// symbol held in several portfolios shares one set of events.
+class DividendRepository : public BaseRepository<portfolio::DividendEvent> {
+  public:
+    static DividendRepository& instance();
+
+    /// Insert or refresh events. An existing row keeps its `alerted_at`, and a
+    /// "paid" row is never downgraded back to "announced".
+    Result<void> upsert_events(const QVector<portfolio::DividendEvent>& events);
+
+    /// Events for `symbols` with ex_date >= `from_date` (empty = all), oldest first.
+    Result<QVector<portfolio::DividendEvent>> get_events(const QStringList& symbols, const QString& from_date = {});
+
+    /// Events with ex_date in [from_date, to_date] whose reminder has not fired.
+    Result<QVector<portfolio::DividendEvent>> pending_alerts(const QString& from_date, const QString& to_date);
+
+    Result<void> mark_alerted(const QString& symbol, const QString& ex_date);
+
+  private:
+    DividendRepository() = default;
+
+    static portfolio::DividendEvent map_event(QSqlQuery& q);
+};
+
+} // namespace fincept
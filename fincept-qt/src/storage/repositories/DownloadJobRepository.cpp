@@ -0,0 +1,177 @@
+// src/storage/repositories/DownloadJobRepository.cpp
+#include "storage/repositories/DownloadJobRepository.h"
+
+#include <QDateTime>
+#include <QSet>
+#include <QUuid>
+
+namespace fincept {
+
+namespace {
+
+// Job columns plus the item tallies, aliased `j` / `i`.
+const char* kDlJobSelect =
+    "SELECT j.id, j.name, j.exchange, j.interval, j.from_date, j.to_date, j.source, j.rate_per_minute, j.status, "
+    "j.error, j.created_at, j.updated_at, j.finished_at, COUNT(i.symbol), "
+    "COALESCE(SUM(i.status = 'done'), 0), COALESCE(SUM(i.status = 'failed'), 0), COALESCE(SUM(i.bars), 0) "
+    "FROM download_jobs j LEFT JOIN download_items i ON i.job_id = j.id";
+
+const char* kDlItemCols = "job_id, seq, symbol, status, bars, attempts, error, updated_at";
+
+DownloadItem dl_map_item(QSqlQuery& q) {
+    DownloadItem it;
+    it.job_id = q.value(0).toString();
+    it.seq = q.value(1).toInt();
+    it.symbol = q.value(2).toString();
+    it.status = q.value(3).toString();
+    it.bars = q.value(4).toInt();
+    it.attempts = q.value(5).toInt();
+    it.error = q.value(6).toString();
+    it.updated_at = q.value(7).toLongLong();
+    return it;
+}
+
+} // namespace
+
+DownloadJobRepository& DownloadJobRepository::instance() {
+    static DownloadJobRepository s;
+    return s;
+}
+
+DownloadJob DownloadJobRepository::map_row(QSqlQuery& q) {
+    DownloadJob j;
+    j.id = q.value(0).toString();
+    j.name = q.value(1).toString();
+    j.exchange = q.value(2).toString();
+    j.interval = q.value(3).toString();
+    j.from_date = q.value(4).toString();
+    j.to_date = q.value(5).toString();
+    j.source = q.value(6).toString();
+    j.rate_per_minute = q.value(7).toInt();
+    j.status = q.value(8).toString();
+    j.error = q.value(9).toString();
+    j.created_at = q.value(10).toLongLong();
+    j.updated_at = q.value(11).toLongLong();
+    j.finished_at = q.value(12).toLongLong();
+    j.total = q.value(13).toInt();
+    j.done = q.value(14).toInt();
+    j.failed = q.value(15).toInt();
+    j.bars = q.value(16).toLongLong();
+    return j;
+}
+
+// ── Jobs ────────────────────────────────────────────────────────────────────
+
+Result<DownloadJob> DownloadJobRepository::create(const DownloadJob& in, const QStringList& symbols) {
+    DownloadJob j = in;
+    if (j.id.isEmpty())
+        j.id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    j.created_at = j.updated_at = QDateTime::currentMSecsSinceEpoch();
+    j.status = "queued";
+
+    auto tx = db().begin_transaction();
+    if (tx.is_err())
+        return Result<DownloadJob>::err(tx.error());
+    auto w = exec_write("INSERT INTO download_jobs (id, name, exchange, interval, from_date, to_date, source, "
+                        "rate_per_minute, status, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                        {j.id, j.name, j.exchange, j.interval, j.from_date, j.to_date, j.source, j.rate_per_minute,
+                         j.status, j.created_at, j.updated_at});
+    QSet<QString> seen;
+    int seq = 0;
+    for (const QString& raw : symbols) {
+        if (w.is_err())
+            break;
+        const QString sym = raw.trimmed().toUpper();
+        if (sym.isEmpty() || seen.contains(sym))
+            continue;
+        seen.insert(sym);
+        w = exec_write("INSERT INTO download_items (job_id, seq, symbol) VALUES (?, ?, ?)", {j.id, seq++, sym});
+    }
+    if (w.is_err()) {
+        db().rollback();
+        return Result<DownloadJob>::err(w.error());
+    }
+    auto c = db().commit();
+    if (c.is_err())
+        return Result<DownloadJob>::err(c.error());
+    j.total = seq;
+    return Result<DownloadJob>::ok(j);
+}
+
+Result<void> DownloadJobRepository::set_status(const QString& id, const QString& status, const QString& error) {
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    const bool terminal = status == "completed" || status == "failed" || status == "cancelled";
+    return exec_write("UPDATE download_jobs SET status = ?, error = ?, updated_at = ?, finished_at = ? WHERE id = ?",
+                      {status, error, now, terminal ? now : 0, id});
+}
+
+Result<void> DownloadJobRepository::remove(const QString& id) {
+    auto r = exec_write("DELETE FROM download_items WHERE job_id = ?", {id});
+    if (r.is_err())
+        return r;
+    return exec_write("DELETE FROM download_jobs WHERE id = ?", {id});
+}
+
+std::optional<DownloadJob> DownloadJobRepository::get(const QString& id) {
+    return query_optional(QString("%1 WHERE j.id = ? GROUP BY j.id").arg(kDlJobSelect), {id}, map_row);
+}
+
+Result<QVector<DownloadJob>> DownloadJobRepository::list(const QString& status, int limit) {
+    if (status.isEmpty())
+        return query_list(QString("%1 GROUP BY j.id ORDER BY j.created_at DESC LIMIT ?").arg(kDlJobSelect),
+                          {limit}, map_row);
+    return query_list(
+        QString("%1 WHERE j.status = ? GROUP BY j.id ORDER BY j.created_at DESC LIMIT ?").arg(kDlJobSelect),
+        {status, limit}, map_row);
+}
+
+std::optional<DownloadJob> DownloadJobRepository::next_runnable() {
+    return query_optional(QString("%1 WHERE j.status IN ('running', 'queued') GROUP BY j.id "
+                                  "ORDER BY j.status = 'running' DESC, j.created_at ASC LIMIT 1")
+                              .arg(kDlJobSelect),
+                          {}, map_row);
+}
+
+// ── Items ───────────────────────────────────────────────────────────────────
+
+std::optional<DownloadItem> DownloadJobRepository::next_pending(const QString& job_id) {
+    auto r = db().execute(QString("SELECT %1 FROM download_items WHERE job_id = ? AND status = 'pending' "
+                                  "ORDER BY seq LIMIT 1")
+                              .arg(kDlItemCols),
+                          {job_id});
+    if (r.is_err() || !r.value().next())
+        return std::nullopt;
+    return dl_map_item(r.value());
+}
+
+Result<void> DownloadJobRepository::update_item(const DownloadItem& it) {
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    auto r = exec_write("UPDATE download_items SET status = ?, bars = ?, attempts = ?, error = ?, updated_at = ? "
+                        "WHERE job_id = ? AND symbol = ?",
+                        {it.status, it.bars, it.attempts, it.error, now, it.job_id, it.symbol});
+    if (r.is_err())
+        return r;
+    return exec_write("UPDATE download_jobs SET updated_at = ? WHERE id = ?", {now, it.job_id});
+}
+
+Result<int> DownloadJobRepository::reset_failed(const QString& job_id) {
+    auto r = db().execute("UPDATE download_items SET status = 'pending', attempts = 0, error = '' "
+                          "WHERE job_id = ? AND status = 'failed'",
+                          {job_id});
+    if (r.is_err())
+        return Result<int>::err(r.error());
+    return Result<int>::ok(r.value().numRowsAffected());
+}
+
+Result<QVector<DownloadItem>> DownloadJobRepository::items(const QString& job_id, const QString& status, int limit) {
+    if (status.isEmpty())
+        return query_list_as<DownloadItem>(
+            QString("SELECT %1 FROM download_items WHERE job_id = ? ORDER BY seq LIMIT ?").arg(kDlItemCols),
+            {job_id, limit}, dl_map_item);
+    return query_list_as<DownloadItem>(
+        QString("SELECT %1 FROM download_items WHERE job_id = ? AND status = ? ORDER BY seq LIMIT ?")
+            .arg(kDlItemCols),
+        {job_id, status, limit}, dl_map_item);
+}
+
+} // namespace fincept
@@ -0,0 +1,79 @@
+// src/storage/repositories/DownloadJobRepository.h
+#pragma once
+#include "storage/repositories/BaseRepository.h"
+
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+#include <optional>
+
+namespace fincept {
+
+/// A bulk historical backfill. status: queued | running | paused | completed |
+/// failed | cancelled. Schema in v066_download_jobs.
+struct DownloadJob {
+    QString id;
+    QString name;
+    QString exchange;                         // canonical key for the symbols (NSE, US, ...)
+    QString interval = QStringLiteral("1d");  // 1m, 5m, 15m, 30m, 1h, 1d, 1w
+    QString from_date;                        // yyyy-MM-dd, inclusive
+    QString to_date;                          // yyyy-MM-dd, inclusive
+    QString source = QStringLiteral("yahoo"); // yahoo | broker
+    int rate_per_minute = 30;                 // requests sent to the source
+    QString status = QStringLiteral("queued");
+    QString error;
+    qint64 created_at = 0; // epoch ms
+    qint64 updated_at = 0;
+    qint64 finished_at = 0;
+
+    // Item counts, filled by the list / get queries.
+    int total = 0;
+    int done = 0;
+    int failed = 0;
+    qint64 bars = 0;
+};
+
+/// One symbol of a job. status: pending | done | failed.
+struct DownloadItem {
+    QString job_id;
+    int seq = 0;
+    QString symbol;
+    QString status = QStringLiteral("pending");
+    int bars = 0;
+    int attempts = 0;
+    QString error;
+    qint64 updated_at = 0;
+};
+
+class DownloadJobRepository : public BaseRepository<DownloadJob> {
+  public:
+    static DownloadJobRepository& instance();
+
+    // ── Jobs ────────────────────────────────────────────────────────────
+    /// Creates the job and one pending item per symbol (duplicates dropped)
+    /// in one transaction.
+    Result<DownloadJob> create(const DownloadJob& job, const QStringList& symbols);
+    Result<void> set_status(const QString& id, const QString& status, const QString& error = {});
+    Result<void> remove(const QString& id);
+    std::optional<DownloadJob> get(const QString& id);
+    /// Newest first; empty `status` matches every job.
+    Result<QVector<DownloadJob>> list(const QString& status = {}, int limit = 50);
+    /// Oldest job with status queued or running (running = interrupted by a
+    /// restart), or nullopt.
+    std::optional<DownloadJob> next_runnable();
+
+    // ── Items ───────────────────────────────────────────────────────────
+    std::optional<DownloadItem> next_pending(const QString& job_id);
+    Result<void> update_item(const DownloadItem& item);
+    /// Puts failed items back to pending; returns how many.
+    Result<int> reset_failed(const QString& job_id);
+    /// Items in queue order; empty `status` matches all.
+    Result<QVector<DownloadItem>> items(const QString& job_id, const QString& status = {}, int limit = 500);
+
+  private:
+    DownloadJobRepository() = default;
+    static DownloadJob map_row(QSqlQuery& q);
+};
+
+} // namespace fincept
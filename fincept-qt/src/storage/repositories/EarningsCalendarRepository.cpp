@@ -0,0 +1,102 @@
+// src/storage/repositories/EarningsCalendarRepository.cpp
+#include "storage/repositories/EarningsCalendarRepository.h"
+
+#include <QDateTime>
+
+namespace fincept {
+
+namespace {
+
+const char* kEarningsCols = "symbol, earnings_date, earnings_date_end, eps_estimate, revenue_estimate, fetched_at";
+
+QVariant optional_number(double v) {
+    return v != 0 ? QVariant(v) : QVariant(QMetaType(QMetaType::Double));
+}
+
+} // namespace
+
+EarningsCalendarRepository& EarningsCalendarRepository::instance() {
+    static EarningsCalendarRepository s;
+    return s;
+}
+
+EarningsDate EarningsCalendarRepository::map_row(QSqlQuery& q) {
+    EarningsDate e;
+    e.symbol = q.value(0).toString();
+    e.earnings_date = q.value(1).toString();
+    e.earnings_date_end = q.value(2).toString();
+    e.eps_estimate = q.value(3).toDouble();
+    e.revenue_estimate = q.value(4).toDouble();
+    e.fetched_at = q.value(5).toLongLong();
+    return e;
+}
+
+Result<void> EarningsCalendarRepository::upsert(const QVector<EarningsDate>& rows) {
+    if (rows.isEmpty())
+        return Result<void>::ok();
+    if (auto tx = db().begin_transaction(); tx.is_err())
+        return tx;
+    Result<void> r = Result<void>::ok();
+    for (int i = 0; r.is_ok() && i < rows.size(); ++i) {
+        const EarningsDate& e = rows[i];
+        r = exec_write(QString("INSERT OR REPLACE INTO earnings_calendar (%1) VALUES (?, ?, ?, ?, ?, ?)")
+                           .arg(kEarningsCols),
+                       {e.symbol, e.earnings_date, e.earnings_date_end, optional_number(e.eps_estimate),
+                        optional_number(e.revenue_estimate), e.fetched_at});
+    }
+    if (r.is_err()) {
+        db().rollback();
+        return r;
+    }
+    if (auto c = db().commit(); c.is_err()) {
+        db().rollback();
+        return c;
+    }
+    return Result<void>::ok();
+}
+
+Result<QVector<EarningsDate>> EarningsCalendarRepository::get(const QStringList& symbols) {
+    if (symbols.isEmpty())
+        return query_list(QString("SELECT %1 FROM earnings_calendar ORDER BY symbol").arg(kEarningsCols), {},
+                          &EarningsCalendarRepository::map_row);
+    QStringList marks;
+    QVariantList params;
+    for (const QString& s : symbols) {
+        marks.append("?");
+        params.append(s);
+    }
+    return query_list(
+        QString("SELECT %1 FROM earnings_calendar WHERE symbol IN (%2)").arg(kEarningsCols, marks.join(", ")),
+        params, &EarningsCalendarRepository::map_row);
+}
+
+QSet<QString> EarningsCalendarRepository::alerted(const QStringList& keys) {
+    QSet<QString> out;
+    if (keys.isEmpty())
+        return out;
+    QStringList marks;
+    QVariantList params;
+    for (const QString& k : keys) {
+        marks.append("?");
+        params.append(k);
+    }
+    auto r = db().execute(
+        QString("SELECT event_key FROM event_risk_alerts WHERE event_key IN (%1)").arg(marks.join(", ")), params);
+    if (r.is_err())
+        return out;
+    auto& q = r.value();
+    while (q.next())
+        out.insert(q.value(0).toString());
+    return out;
+}
+
+Result<void> EarningsCalendarRepository::mark_alerted(const QString& key) {
+    return exec_write("INSERT OR REPLACE INTO event_risk_alerts (event_key, alerted_at) VALUES (?, ?)",
+                      {key, QDateTime::currentMSecsSinceEpoch()});
+}
+
+Result<void> EarningsCalendarRepository::prune_alerts(qint64 before_ms) {
+    return exec_write("DELETE FROM event_risk_alerts WHERE alerted_at < ?", {before_ms});
+}
+
+} // namespace fincept
@@ -0,0 +1,41 @@
+// src/storage/repositories/EarningsCalendarRepository.h
+#pragma once
+#include "storage/repositories/BaseRepository.h"
+
+#include <QSet>
+#include <QStringList>
+
+namespace fincept {
+
+/// Next scheduled earnings of one symbol. Schema in v077_event_risk.
+struct EarningsDate {
+    QString symbol;
+    QString earnings_date;     // YYYY-MM-DD; empty = nothing scheduled
+    QString earnings_date_end; // end of the announced window; empty = confirmed day
+    double eps_estimate = 0;   // 0 = no consensus
+    double revenue_estimate = 0;
+    qint64 fetched_at = 0; // epoch ms
+};
+
+/// Shared earnings calendar plus the reminder ledger of the event-risk
+/// overlay (EventRiskService).
+class EarningsCalendarRepository : public BaseRepository<EarningsDate> {
+  public:
+    static EarningsCalendarRepository& instance();
+
+    Result<void> upsert(const QVector<EarningsDate>& rows);
+    /// Rows for `symbols`, or every row when `symbols` is empty.
+    Result<QVector<EarningsDate>> get(const QStringList& symbols);
+
+    /// Keys among `keys` whose reminder has already fired.
+    QSet<QString> alerted(const QStringList& keys);
+    Result<void> mark_alerted(const QString& key);
+    /// Drops reminder keys older than `before_ms`.
+    Result<void> prune_alerts(qint64 before_ms);
+
+  private:
+    EarningsCalendarRepository() = default;
+    static EarningsDate map_row(QSqlQuery& q);
+};
+
+} // namespace fincept
@@ -0,0 +1,87 @@
+// src/storage/repositories/FuturesRepository.cpp
+#include "storage/repositories/FuturesRepository.h"
+
+#include <QDateTime>
+
+namespace fincept {
+
+namespace {
+
+const char* kFutSpecCols = "root, name, exchange, currency, multiplier, tick_size, months, expiry_rule, roll_days, "
+                           "adjust, yahoo_suffix, updated_at";
+
+} // namespace
+
+FuturesRepository& FuturesRepository::instance() {
+    static FuturesRepository s;
+    return s;
+}
+
+FuturesSpec FuturesRepository::map_row(QSqlQuery& q) {
+    FuturesSpec s;
+    s.root = q.value(0).toString();
+    s.name = q.value(1).toString();
+    s.exchange = q.value(2).toString();
+    s.currency = q.value(3).toString();
+    s.multiplier = q.value(4).toDouble();
+    s.tick_size = q.value(5).toDouble();
+    s.months = q.value(6).toString();
+    s.expiry_rule = q.value(7).toString();
+    s.roll_days = q.value(8).toInt();
+    s.adjust = q.value(9).toString();
+    s.yahoo_suffix = q.value(10).toString();
+    s.updated_at = q.value(11).toLongLong();
+    return s;
+}
+
+// ── Specs ───────────────────────────────────────────────────────────────────
+
+Result<QVector<FuturesSpec>> FuturesRepository::list_specs() {
+    return query_list(QString("SELECT %1 FROM futures_specs ORDER BY root").arg(kFutSpecCols), {}, map_row);
+}
+
+std::optional<FuturesSpec> FuturesRepository::get_spec(const QString& root) {
+    return query_optional(QString("SELECT %1 FROM futures_specs WHERE root = ?").arg(kFutSpecCols),
+                          {root.toUpper()}, map_row);
+}
+
+Result<void> FuturesRepository::save_spec(const FuturesSpec& s) {
+    return exec_write(QString("INSERT OR REPLACE INTO futures_specs (%1) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+                          .arg(kFutSpecCols),
+                      {s.root.toUpper(), s.name, s.exchange, s.currency, s.multiplier, s.tick_size, s.months,
+                       s.expiry_rule, s.roll_days, s.adjust, s.yahoo_suffix, QDateTime::currentMSecsSinceEpoch()});
+}
+
+Result<void> FuturesRepository::remove_spec(const QString& root) {
+    auto r = exec_write("DELETE FROM futures_expiries WHERE root = ?", {root.toUpper()});
+    if (r.is_err())
+        return r;
+    return exec_write("DELETE FROM futures_specs WHERE root = ?", {root.toUpper()});
+}
+
+// ── Expiry overrides ────────────────────────────────────────────────────────
+
+QHash<QString, QDate> FuturesRepository::expiry_overrides(const QString& root) {
+    QHash<QString, QDate> out;
+    auto r = db().execute("SELECT contract, expiry_date FROM futures_expiries WHERE root = ?", {root.toUpper()});
+    if (r.is_err())
+        return out;
+    auto& q = r.value();
+    while (q.next()) {
+        const QDate d = QDate::fromString(q.value(1).toString(), Qt::ISODate);
+        if (d.isValid())
+            out.insert(q.value(0).toString(), d);
+    }
+    return out;
+}
+
+Result<void> FuturesRepository::set_expiry_override(const QString& root, const QString& contract,
+                                                    const QDate& expiry) {
+    if (!expiry.isValid())
+        return exec_write("DELETE FROM futures_expiries WHERE root = ? AND contract = ?",
+                          {root.toUpper(), contract.toUpper()});
+    return exec_write("INSERT OR REPLACE INTO futures_expiries (root, contract, expiry_date) VALUES (?, ?, ?)",
+                      {root.toUpper(), contract.toUpper(), expiry.toString(Qt::ISODate)});
+}
+
+} // namespace fincept
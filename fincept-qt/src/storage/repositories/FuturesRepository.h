@@ -0,0 +1,52 @@
+// src/storage/repositories/FuturesRepository.h
+#pragma once
+#include "storage/repositories/BaseRepository.h"
+
+#include <QDate>
+#include <QHash>
+#include <QString>
+#include <QVector>
+
+#include <optional>
+
+namespace fincept {
+
+/// Contract specification for one futures root. Schema in v067_futures.
+struct FuturesSpec {
+    QString root; // ES, CL, 6E, ...
+    QString name;
+    QString exchange; // CME, CBOT, NYMEX, COMEX — the Historify exchange key for its contracts
+    QString currency = QStringLiteral("USD");
+    double multiplier = 1;
+    double tick_size = 0.01;
+    QString months = QStringLiteral("HMUZ");             // listed month codes, F..Z
+    QString expiry_rule = QStringLiteral("third_friday"); // see FuturesCalendar::expiry_rules()
+    int roll_days = 5;                                    // business days before expiry
+    QString adjust = QStringLiteral("panama");            // panama | ratio | none
+    QString yahoo_suffix;                                 // ".CME" → ESH26.CME
+    qint64 updated_at = 0;
+};
+
+class FuturesRepository : public BaseRepository<FuturesSpec> {
+  public:
+    static FuturesRepository& instance();
+
+    // ── Specs ───────────────────────────────────────────────────────────
+    Result<QVector<FuturesSpec>> list_specs();
+    std::optional<FuturesSpec> get_spec(const QString& root);
+    Result<void> save_spec(const FuturesSpec& spec);
+    /// Drops the spec and its expiry overrides.
+    Result<void> remove_spec(const QString& root);
+
+    // ── Expiry overrides ────────────────────────────────────────────────
+    /// contract code ("ESH26") → published last-trade date.
+    QHash<QString, QDate> expiry_overrides(const QString& root);
+    /// An invalid `expiry` removes the override.
+    Result<void> set_expiry_override(const QString& root, const QString& contract, const QDate& expiry);
+
+  private:
+    FuturesRepository() = default;
+    static FuturesSpec map_row(QSqlQuery& q);
+};
+
+} // namespace fincept
@@ -0,0 +1,218 @@
+// src/storage/repositories/OptionsFlowRepository.cpp
+#include "storage/repositories/OptionsFlowRepository.h"
+
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QUuid>
+
+#include <algorithm>
+
+namespace fincept {
+
+namespace {
+
+const char* kOfEventCols = "id, symbol, source, kind, contract, expiry, option_type, strike, volume, open_interest, "
+                           "premium, score, detail, detected_at";
+
+QString of_json(const QJsonObject& o) {
+    return QString::fromUtf8(QJsonDocument(o).toJson(QJsonDocument::Compact));
+}
+
+QJsonObject of_object(const QVariant& v) {
+    return QJsonDocument::fromJson(v.toString().toUtf8()).object();
+}
+
+QStringList of_list(const QJsonValue& v, bool upper) {
+    QStringList out;
+    const QStringList raw = v.isArray() ? v.toVariant().toStringList() : v.toString().split(',');
+    for (const QString& r : raw) {
+        const QString s = upper ? r.trimmed().toUpper() : r.trimmed().toLower();
+        if (!s.isEmpty() && !out.contains(s))
+            out.append(s);
+    }
+    return out;
+}
+
+OptionsFlowWatch of_map_watch(QSqlQuery& q) {
+    OptionsFlowWatch w;
+    w.symbol = q.value(0).toString();
+    w.source = q.value(1).toString();
+    w.interval_sec = q.value(2).toInt();
+    w.thresholds = of_object(q.value(3));
+    w.active = q.value(4).toInt() != 0;
+    w.last_scan_at = q.value(5).toLongLong();
+    return w;
+}
+
+OptionsFlowAlert of_map_alert(QSqlQuery& q) {
+    OptionsFlowAlert a;
+    a.id = q.value(0).toString();
+    a.name = q.value(1).toString();
+    a.filter = OptionsFlowFilter::from_json(of_object(q.value(2)));
+    a.toast = q.value(3).toInt() != 0;
+    a.providers = q.value(4).toInt() != 0;
+    a.active = q.value(5).toInt() != 0;
+    a.last_fired_at = q.value(6).toLongLong();
+    return a;
+}
+
+} // namespace
+
+// ── OptionsFlowFilter ───────────────────────────────────────────────────────
+
+OptionsFlowFilter OptionsFlowFilter::from_json(const QJsonObject& o) {
+    OptionsFlowFilter f;
+    f.symbols = of_list(o.value("symbols"), true);
+    f.kinds = of_list(o.value("kinds"), false);
+    f.option_type = o.value("option_type").toString().trimmed().toLower();
+    f.min_premium = o.value("min_premium").toDouble(0);
+    f.min_score = o.value("min_score").toDouble(0);
+    f.since_ms = o.value("since_ms").toVariant().toLongLong();
+    f.limit = std::clamp(o.value("limit").toInt(f.limit), 1, 1000);
+    return f;
+}
+
+QJsonObject OptionsFlowFilter::to_json() const {
+    QJsonObject o;
+    if (!symbols.isEmpty())
+        o["symbols"] = QJsonArray::fromStringList(symbols);
+    if (!kinds.isEmpty())
+        o["kinds"] = QJsonArray::fromStringList(kinds);
+    if (!option_type.isEmpty())
+        o["option_type"] = option_type;
+    if (min_premium > 0)
+        o["min_premium"] = min_premium;
+    if (min_score > 0)
+        o["min_score"] = min_score;
+    return o;
+}
+
+bool OptionsFlowFilter::matches(const OptionsFlowEvent& e) const {
+    if (!symbols.isEmpty() && !symbols.contains(e.symbol))
+        return false;
+    if (!kinds.isEmpty() && !kinds.contains(e.kind))
+        return false;
+    if (!option_type.isEmpty() && e.option_type != option_type)
+        return false;
+    return e.premium >= min_premium && e.score >= min_score && e.detected_at >= since_ms;
+}
+
+// ── OptionsFlowRepository ───────────────────────────────────────────────────
+
+OptionsFlowRepository& OptionsFlowRepository::instance() {
+    static OptionsFlowRepository s;
+    return s;
+}
+
+OptionsFlowEvent OptionsFlowRepository::map_row(QSqlQuery& q) {
+    OptionsFlowEvent e;
+    e.id = q.value(0).toString();
+    e.symbol = q.value(1).toString();
+    e.source = q.value(2).toString();
+    e.kind = q.value(3).toString();
+    e.contract = q.value(4).toString();
+    e.expiry = q.value(5).toString();
+    e.option_type = q.value(6).toString();
+    e.strike = q.value(7).toDouble();
+    e.volume = q.value(8).toDouble();
+    e.open_interest = q.value(9).toDouble();
+    e.premium = q.value(10).toDouble();
+    e.score = q.value(11).toDouble();
+    e.detail = of_object(q.value(12));
+    e.detected_at = q.value(13).toLongLong();
+    return e;
+}
+
+Result<void> OptionsFlowRepository::upsert_watch(const OptionsFlowWatch& w) {
+    return exec_write("INSERT INTO options_flow_watches (symbol, source, interval_sec, thresholds, active) "
+                      "VALUES (?, ?, ?, ?, ?) "
+                      "ON CONFLICT(symbol) DO UPDATE SET source = excluded.source, "
+                      "interval_sec = excluded.interval_sec, thresholds = excluded.thresholds, "
+                      "active = excluded.active",
+                      {w.symbol, w.source, w.interval_sec, of_json(w.thresholds), w.active ? 1 : 0});
+}
+
+Result<void> OptionsFlowRepository::remove_watch(const QString& symbol) {
+    return exec_write("DELETE FROM options_flow_watches WHERE symbol = ?", {symbol});
+}
+
+Result<QVector<OptionsFlowWatch>> OptionsFlowRepository::list_watches() {
+    return query_list_as<OptionsFlowWatch>(
+        "SELECT symbol, source, interval_sec, thresholds, active, last_scan_at FROM options_flow_watches "
+        "ORDER BY symbol",
+        {}, of_map_watch);
+}
+
+Result<void> OptionsFlowRepository::touch_scanned(const QString& symbol, qint64 at_ms) {
+    return exec_write("UPDATE options_flow_watches SET last_scan_at = ? WHERE symbol = ?", {at_ms, symbol});
+}
+
+Result<OptionsFlowEvent> OptionsFlowRepository::record_event(const OptionsFlowEvent& in) {
+    OptionsFlowEvent e = in;
+    if (e.id.isEmpty())
+        e.id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    const QString sql = QString("INSERT INTO options_flow_events (%1) "
+                                "VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+                            .arg(kOfEventCols);
+    auto r = exec_write(sql, {e.id, e.symbol, e.source, e.kind, e.contract, e.expiry, e.option_type, e.strike, e.volume,
+                              e.open_interest, e.premium, e.score, of_json(e.detail), e.detected_at});
+    if (r.is_err())
+        return Result<OptionsFlowEvent>::err(r.error());
+    return Result<OptionsFlowEvent>::ok(e);
+}
+
+Result<QVector<OptionsFlowEvent>> OptionsFlowRepository::query_events(const OptionsFlowFilter& f) {
+    QString sql = QString("SELECT %1 FROM options_flow_events WHERE detected_at >= ? AND premium >= ? AND score >= ?")
+                      .arg(kOfEventCols);
+    QVariantList params{f.since_ms, f.min_premium, f.min_score};
+    auto in_list = [&](const char* column, const QStringList& values) {
+        if (values.isEmpty())
+            return;
+        sql += QString(" AND %1 IN (%2)").arg(column, QStringList(values.size(), QStringLiteral("?")).join(", "));
+        for (const QString& v : values)
+            params.append(v);
+    };
+    in_list("symbol", f.symbols);
+    in_list("kind", f.kinds);
+    if (!f.option_type.isEmpty()) {
+        sql += " AND option_type = ?";
+        params.append(f.option_type);
+    }
+    sql += " ORDER BY detected_at DESC LIMIT ?";
+    params.append(f.limit);
+    return query_list(sql, params, &OptionsFlowRepository::map_row);
+}
+
+Result<void> OptionsFlowRepository::prune_events(qint64 older_than_ms) {
+    return exec_write("DELETE FROM options_flow_events WHERE detected_at < ?", {older_than_ms});
+}
+
+Result<OptionsFlowAlert> OptionsFlowRepository::create_alert(const OptionsFlowAlert& in) {
+    OptionsFlowAlert a = in;
+    if (a.id.isEmpty())
+        a.id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    auto r = exec_write("INSERT INTO options_flow_alerts (id, name, filter, toast, providers, active) "
+                        "VALUES (?, ?, ?, ?, ?, ?)",
+                        {a.id, a.name, of_json(a.filter.to_json()), a.toast ? 1 : 0, a.providers ? 1 : 0,
+                         a.active ? 1 : 0});
+    if (r.is_err())
+        return Result<OptionsFlowAlert>::err(r.error());
+    return Result<OptionsFlowAlert>::ok(a);
+}
+
+Result<void> OptionsFlowRepository::remove_alert(const QString& id) {
+    return exec_write("DELETE FROM options_flow_alerts WHERE id = ?", {id});
+}
+
+Result<QVector<OptionsFlowAlert>> OptionsFlowRepository::list_alerts() {
+    return query_list_as<OptionsFlowAlert>(
+        "SELECT id, name, filter, toast, providers, active, last_fired_at FROM options_flow_alerts "
+        "ORDER BY created_at",
+        {}, of_map_alert);
+}
+
+Result<void> OptionsFlowRepository::touch_alert_fired(const QString& id, qint64 at_ms) {
+    return exec_write("UPDATE options_flow_alerts SET last_fired_at = ? WHERE id = ?", {at_ms, id});
+}
+
+} // namespace fincept
@@ -0,0 +1,96 @@
+// src/storage/repositories/OptionsFlowRepository.h
+#pragma once
+#include "storage/repositories/BaseRepository.h"
+
+#include <QJsonObject>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+namespace fincept {
+
+/// An underlying the options flow scanner polls. `thresholds` overrides the
+/// scanner defaults per key (see OptionsFlowScanner::Thresholds).
+struct OptionsFlowWatch {
+    QString symbol;
+    QString source = QStringLiteral("cboe"); // cboe | polygon | tradier
+    int interval_sec = 300;
+    QJsonObject thresholds;
+    bool active = true;
+    qint64 last_scan_at = 0; // epoch ms
+};
+
+/// One flagged anomaly. kind: vol_oi | sweep | skew. Contract fields are
+/// empty for skew events, which describe an expiry.
+struct OptionsFlowEvent {
+    QString id;
+    QString symbol;
+    QString source;
+    QString kind;
+    QString contract;
+    QString expiry;      // yyyy-MM-dd
+    QString option_type; // call | put
+    double strike = 0;
+    double volume = 0;
+    double open_interest = 0;
+    double premium = 0; // traded notional behind the flag, in underlying currency
+    double score = 0;   // kind-specific strength; higher is more unusual
+    QJsonObject detail;
+    qint64 detected_at = 0; // epoch ms
+};
+
+/// Event selection shared by event queries and alerts. Empty lists and zero
+/// minimums match everything.
+struct OptionsFlowFilter {
+    QStringList symbols;
+    QStringList kinds;
+    QString option_type; // call | put | '' (both)
+    double min_premium = 0;
+    double min_score = 0;
+    qint64 since_ms = 0;
+    int limit = 100;
+
+    static OptionsFlowFilter from_json(const QJsonObject& o);
+    QJsonObject to_json() const;
+    bool matches(const OptionsFlowEvent& e) const;
+};
+
+struct OptionsFlowAlert {
+    QString id;
+    QString name;
+    OptionsFlowFilter filter;
+    bool toast = true;
+    bool providers = false; // also send through the notification providers
+    bool active = true;
+    qint64 last_fired_at = 0;
+};
+
+class OptionsFlowRepository : public BaseRepository<OptionsFlowEvent> {
+  public:
+    static OptionsFlowRepository& instance();
+
+    // ── Watches ─────────────────────────────────────────────────────────
+    Result<void> upsert_watch(const OptionsFlowWatch& w);
+    Result<void> remove_watch(const QString& symbol);
+    Result<QVector<OptionsFlowWatch>> list_watches();
+    Result<void> touch_scanned(const QString& symbol, qint64 at_ms);
+
+    // ── Events ──────────────────────────────────────────────────────────
+    /// Stores `e`, assigning an id when it has none.
+    Result<OptionsFlowEvent> record_event(const OptionsFlowEvent& e);
+    /// Newest first, at most `f.limit`.
+    Result<QVector<OptionsFlowEvent>> query_events(const OptionsFlowFilter& f);
+    Result<void> prune_events(qint64 older_than_ms);
+
+    // ── Alerts ──────────────────────────────────────────────────────────
+    Result<OptionsFlowAlert> create_alert(const OptionsFlowAlert& a);
+    Result<void> remove_alert(const QString& id);
+    Result<QVector<OptionsFlowAlert>> list_alerts();
+    Result<void> touch_alert_fired(const QString& id, qint64 at_ms);
+
+  private:
+    OptionsFlowRepository() = default;
+    static OptionsFlowEvent map_row(QSqlQuery& q);
+};
+
+} // namespace fincept
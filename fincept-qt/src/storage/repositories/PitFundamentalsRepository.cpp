@@ -0,0 +1,188 @@
+// src/storage/repositories/PitFundamentalsRepository.cpp
+#include "storage/repositories/PitFundamentalsRepository.h"
+
+#include <QSet>
+
+namespace fincept {
+
+namespace {
+
+const char* kFactCols = "symbol, metric, period, period_end, filed_date, source, value, unit, period_start, "
+                        "fiscal_year, fiscal_period, form, accession, ingested_at";
+
+// SQLite's bound-parameter limit is 999 on older builds.
+constexpr int kInChunk = 500;
+
+QString placeholders(int n) {
+    QStringList marks;
+    for (int i = 0; i < n; ++i)
+        marks.append("?");
+    return marks.join(", ");
+}
+
+PitCoverage map_coverage(QSqlQuery& q) {
+    PitCoverage c;
+    c.symbol = q.value(0).toString();
+    c.source = q.value(1).toString();
+    c.facts = q.value(2).toInt();
+    c.first_period_end = q.value(3).toString();
+    c.last_period_end = q.value(4).toString();
+    c.last_filed_date = q.value(5).toString();
+    c.ingested_at = q.value(6).toLongLong();
+    return c;
+}
+
+} // namespace
+
+PitFundamentalsRepository& PitFundamentalsRepository::instance() {
+    static PitFundamentalsRepository s;
+    return s;
+}
+
+PitFact PitFundamentalsRepository::map_row(QSqlQuery& q) {
+    PitFact f;
+    f.symbol = q.value(0).toString();
+    f.metric = q.value(1).toString();
+    f.period = q.value(2).toString();
+    f.period_end = q.value(3).toString();
+    f.filed_date = q.value(4).toString();
+    f.source = q.value(5).toString();
+    f.value = q.value(6).toDouble();
+    f.unit = q.value(7).toString();
+    f.period_start = q.value(8).toString();
+    f.fiscal_year = q.value(9).toInt();
+    f.fiscal_period = q.value(10).toString();
+    f.form = q.value(11).toString();
+    f.accession = q.value(12).toString();
+    f.ingested_at = q.value(13).toLongLong();
+    return f;
+}
+
+Result<void> PitFundamentalsRepository::upsert_facts(const QVector<PitFact>& facts) {
+    if (facts.isEmpty())
+        return Result<void>::ok();
+    if (auto tx = db().begin_transaction(); tx.is_err())
+        return tx;
+    const QString sql =
+        QString("INSERT OR REPLACE INTO fundamentals_pit (%1) VALUES (%2)").arg(kFactCols, placeholders(14));
+    Result<void> r = Result<void>::ok();
+    for (int i = 0; r.is_ok() && i < facts.size(); ++i) {
+        const PitFact& f = facts[i];
+        r = exec_write(sql, {f.symbol, f.metric, f.period, f.period_end, f.filed_date, f.source, f.value, f.unit,
+                             f.period_start, f.fiscal_year, f.fiscal_period, f.form, f.accession, f.ingested_at});
+    }
+    if (r.is_err()) {
+        db().rollback();
+        return r;
+    }
+    if (auto c = db().commit(); c.is_err()) {
+        db().rollback();
+        return c;
+    }
+    return Result<void>::ok();
+}
+
+Result<QVector<PitFact>> PitFundamentalsRepository::select_facts(const QStringList& symbols, const QString& as_of,
+                                                                 const QStringList& metrics) {
+    QString clause;
+    QVariantList tail;
+    if (!as_of.isEmpty()) {
+        clause += " AND filed_date <= ?";
+        tail.append(as_of);
+    }
+    if (!metrics.isEmpty()) {
+        clause += QString(" AND metric IN (%1)").arg(placeholders(static_cast<int>(metrics.size())));
+        for (const QString& m : metrics)
+            tail.append(m);
+    }
+    // Newest filing first within each period, so the first row of a key is
+    // the value as known on `as_of`.
+    const QString order = " ORDER BY symbol, metric, period, period_end DESC, filed_date DESC, "
+                          "CASE source WHEN 'sec' THEN 0 ELSE 1 END";
+
+    if (symbols.isEmpty()) {
+        const QString where = clause.isEmpty() ? QString() : " WHERE" + clause.mid(4);
+        return query_list(QString("SELECT %1 FROM fundamentals_pit%2%3").arg(kFactCols, where, order), tail,
+                          &PitFundamentalsRepository::map_row);
+    }
+    QVector<PitFact> rows;
+    for (int from = 0; from < symbols.size(); from += kInChunk) {
+        const QStringList chunk = symbols.mid(from, kInChunk);
+        QVariantList params;
+        for (const QString& s : chunk)
+            params.append(s);
+        params += tail;
+        auto r = query_list(QString("SELECT %1 FROM fundamentals_pit WHERE symbol IN (%2)%3%4")
+                                .arg(kFactCols, placeholders(static_cast<int>(chunk.size())), clause, order),
+                            params, &PitFundamentalsRepository::map_row);
+        if (r.is_err())
+            return r;
+        rows += r.value();
+    }
+    return Result<QVector<PitFact>>::ok(std::move(rows));
+}
+
+Result<QVector<PitFact>> PitFundamentalsRepository::known_as_of(const QStringList& symbols, const QString& as_of,
+                                                                const QStringList& metrics) {
+    auto rows = select_facts(symbols, as_of, metrics);
+    if (rows.is_err())
+        return rows;
+    QVector<PitFact> out;
+    QSet<QString> seen;
+    for (const PitFact& f : rows.value()) {
+        const QString key = f.symbol + '|' + f.metric + '|' + f.period + '|' + f.period_end;
+        if (seen.contains(key))
+            continue;
+        seen.insert(key);
+        out.append(f);
+    }
+    return Result<QVector<PitFact>>::ok(std::move(out));
+}
+
+Result<QVector<PitFact>> PitFundamentalsRepository::filings(const QStringList& symbols) {
+    return select_facts(symbols, {}, {});
+}
+
+Result<QVector<PitFact>> PitFundamentalsRepository::history(const QString& symbol, const QString& metric,
+                                                            const QString& period) {
+    QVariantList params{symbol, metric};
+    QString period_clause;
+    if (!period.isEmpty()) {
+        period_clause = " AND period = ?";
+        params.append(period);
+    }
+    return query_list(QString("SELECT %1 FROM fundamentals_pit WHERE symbol = ? AND metric = ?%2 "
+                              "ORDER BY period_end DESC, filed_date DESC, period, source")
+                          .arg(kFactCols, period_clause),
+                      params, &PitFundamentalsRepository::map_row);
+}
+
+Result<QVector<PitCoverage>> PitFundamentalsRepository::coverage(const QStringList& symbols) {
+    const QString select = "SELECT symbol, source, COUNT(*), MIN(period_end), MAX(period_end), MAX(filed_date), "
+                           "MAX(ingested_at) FROM fundamentals_pit";
+    const QString group = " GROUP BY symbol, source ORDER BY symbol, source";
+    if (symbols.isEmpty())
+        return query_list_as<PitCoverage>(select + group, {}, map_coverage);
+    QVector<PitCoverage> out;
+    for (int from = 0; from < symbols.size(); from += kInChunk) {
+        const QStringList chunk = symbols.mid(from, kInChunk);
+        QVariantList params;
+        for (const QString& s : chunk)
+            params.append(s);
+        auto r = query_list_as<PitCoverage>(
+            select + QString(" WHERE symbol IN (%1)").arg(placeholders(static_cast<int>(chunk.size()))) + group,
+            params, map_coverage);
+        if (r.is_err())
+            return r;
+        out += r.value();
+    }
+    return Result<QVector<PitCoverage>>::ok(std::move(out));
+}
+
+Result<void> PitFundamentalsRepository::remove(const QString& symbol, const QString& source) {
+    if (source.isEmpty())
+        return exec_write("DELETE FROM fundamentals_pit WHERE symbol = ?", {symbol});
+    return exec_write("DELETE FROM fundamentals_pit WHERE symbol = ? AND source = ?", {symbol, source});
+}
+
+} // namespace fincept
@@ -0,0 +1,79 @@
+// src/storage/repositories/PitFundamentalsRepository.h
+#pragma once
+#include "storage/repositories/BaseRepository.h"
+
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+namespace fincept {
+
+/// One reported value of a statement metric. Schema in v074_fundamentals_pit.
+struct PitFact {
+    QString symbol;
+    QString metric;     // revenue, net_income, total_assets … (PitFundamentals::metrics())
+    QString period;     // FY | Q (durations) | I (balance sheet instant)
+    QString period_end; // report date, yyyy-MM-dd
+    QString filed_date; // date the filing carrying the value was made, yyyy-MM-dd
+    QString source;     // sec | fmp
+    double value = 0;
+    QString unit;
+    QString period_start; // empty for instants and FMP rows
+    int fiscal_year = 0;
+    QString fiscal_period;  // FY, Q1 … Q4
+    QString form;           // 10-K, 10-Q, 10-K/A …
+    QString accession;      // SEC accession number, empty for FMP
+    qint64 ingested_at = 0; // epoch ms
+};
+
+/// What the store holds for one symbol and source.
+struct PitCoverage {
+    QString symbol;
+    QString source;
+    int facts = 0;
+    QString first_period_end;
+    QString last_period_end;
+    QString last_filed_date;
+    qint64 ingested_at = 0; // newest ingest, epoch ms
+};
+
+class PitFundamentalsRepository : public BaseRepository<PitFact> {
+  public:
+    static PitFundamentalsRepository& instance();
+
+    /// Inserts or replaces facts, in one transaction.
+    Result<void> upsert_facts(const QVector<PitFact>& facts);
+
+    /// For every (symbol, metric, period, period_end) with a filing on or
+    /// before `as_of` (yyyy-MM-dd), the value of the newest such filing — a
+    /// later restatement replaces the original only once it was filed. SEC
+    /// beats FMP when both filed the same day. Empty `symbols` / `metrics`
+    /// mean all. Ordered by symbol, metric, period, period_end descending.
+    Result<QVector<PitFact>> known_as_of(const QStringList& symbols, const QString& as_of,
+                                         const QStringList& metrics = {});
+
+    /// Every stored fact of `symbols` (all when empty) in known_as_of()'s
+    /// order, restatements included — for answering many as-of dates from
+    /// one read.
+    Result<QVector<PitFact>> filings(const QStringList& symbols);
+
+    /// Every stored filing of one metric, period_end then filed_date
+    /// descending — shows restatements side by side.
+    Result<QVector<PitFact>> history(const QString& symbol, const QString& metric, const QString& period = {});
+
+    /// Empty `symbols` = every symbol in the store.
+    Result<QVector<PitCoverage>> coverage(const QStringList& symbols);
+
+    /// Empty `source` removes both sources.
+    Result<void> remove(const QString& symbol, const QString& source = {});
+
+  private:
+    PitFundamentalsRepository() = default;
+    static PitFact map_row(QSqlQuery& q);
+    /// Facts filed on or before `as_of` (any date when empty), newest filing
+    /// first within each period.
+    Result<QVector<PitFact>> select_facts(const QStringList& symbols, const QString& as_of,
+                                          const QStringList& metrics);
+};
+
+} // namespace fincept
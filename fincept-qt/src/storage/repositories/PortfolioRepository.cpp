@@ -26,21 +26,24 @@ portfolio::Portfolio PortfolioRepository::map_portfolio(QSqlQuery& q) {
         q.value(5).toString(), // created_at
         q.value(6).toString(), // updated_at
         q.value(7).toString(), // broker_account_id (v022)
+        q.value(8).toString(), // benchmark_symbol (v054)
     };
 }
 
 portfolio::PortfolioAsset PortfolioRepository::map_asset(QSqlQuery& q) {
     return {
-        q.value(0).toInt(),    // id
-        q.value(1).toString(), // portfolio_id
-        q.value(2).toString(), // symbol (yfinance-format)
-        q.value(3).toDouble(), // quantity
-        q.value(4).toDouble(), // avg_buy_price
-        q.value(5).toString(), // first_purchase_date
-        q.value(6).toString(), // last_updated
-        q.value(7).toString(), // sector
-        q.value(8).toString(), // broker_symbol (v022)
-        q.value(9).toString(), // exchange (v022)
+        q.value(0).toInt(),     // id
+        q.value(1).toString(),  // portfolio_id
+        q.value(2).toString(),  // symbol (yfinance-format)
+        q.value(3).toDouble(),  // quantity
+        q.value(4).toDouble(),  // avg_buy_price
+        q.value(5).toString(),  // first_purchase_date
+        q.value(6).toString(),  // last_updated
+        q.value(7).toString(),  // sector
+        q.value(8).toString(),  // broker_symbol (v022)
+        q.value(9).toString(),  // exchange (v022)
+        q.value(10).toString(), // currency (v053)
+        q.value(11).toDouble(), // cost_fx_rate (v053)
     };
 }
 
@@ -75,14 +78,14 @@ portfolio::PortfolioSnapshot PortfolioRepository::map_snapshot(QSqlQuery& q) {
 
 Result<QVector<portfolio::Portfolio>> PortfolioRepository::list_portfolios() {
     return query_list("SELECT id, name, owner, currency, description, created_at, updated_at, "
-                      "COALESCE(broker_account_id, '') "
+                      "COALESCE(broker_account_id, ''), COALESCE(benchmark_symbol, '') "
                       "FROM portfolios ORDER BY name",
                       {}, map_portfolio);
 }
 
 Result<portfolio::Portfolio> PortfolioRepository::get_portfolio(const QString& id) {
     return query_one("SELECT id, name, owner, currency, description, created_at, updated_at, "
-                     "COALESCE(broker_account_id, '') "
+                     "COALESCE(broker_account_id, ''), COALESCE(benchmark_symbol, '') "
                      "FROM portfolios WHERE id = ?",
                      {id}, map_portfolio);
 }
@@ -114,6 +117,14 @@ Result<void> PortfolioRepository::update_portfolio(const QString& id, const QStr
     return r;
 }
 
+Result<void> PortfolioRepository::set_portfolio_benchmark(const QString& id, const QString& symbol) {
+    auto r = exec_write("UPDATE portfolios SET benchmark_symbol = ?, updated_at = datetime('now') WHERE id = ?",
+                        {symbol.trimmed().toUpper(), id});
+    if (r.is_ok())
+        SyncOutbox::record_unique("portfolio", id, "sync");
+    return r;
+}
+
 Result<void> PortfolioRepository::delete_portfolio(const QString& id) {
     LOG_INFO("PortfolioRepo", QString("Deleting portfolio %1").arg(id));
     auto r = exec_write("DELETE FROM portfolios WHERE id = ?", {id});
@@ -127,14 +138,16 @@ Result<void> PortfolioRepository::delete_portfolio(const QString& id) {
 Result<QVector<portfolio::PortfolioAsset>> PortfolioRepository::get_assets(const QString& portfolio_id) {
     return query_list_as<portfolio::PortfolioAsset>(
         "SELECT id, portfolio_id, symbol, quantity, avg_buy_price, first_purchase_date, last_updated, "
-        "COALESCE(sector, ''), COALESCE(broker_symbol, ''), COALESCE(exchange, '') "
+        "COALESCE(sector, ''), COALESCE(broker_symbol, ''), COALESCE(exchange, ''), "
+        "COALESCE(currency, ''), COALESCE(cost_fx_rate, 0) "
         "FROM portfolio_assets WHERE portfolio_id = ? ORDER BY symbol",
         {portfolio_id}, map_asset);
 }
 
 Result<qint64> PortfolioRepository::add_asset(const QString& portfolio_id, const QString& symbol, double qty,
                                               double price, const QString& date, const QString& sector,
-                                              const QString& broker_symbol, const QString& exchange) {
+                                              const QString& broker_symbol, const QString& exchange,
+                                              const QString& currency, double fx_rate) {
     QString purchase_date = date.isEmpty() ? QDateTime::currentDateTimeUtc().toString(Qt::ISODate) : date;
 
     // Upsert: if symbol already exists in portfolio, update quantity and avg price.
@@ -143,7 +156,8 @@ Result<qint64> PortfolioRepository::add_asset(const QString& portfolio_id, const
     // preserving prior values keeps a re-imported broker portfolio working).
     auto existing = query_list_as<portfolio::PortfolioAsset>(
         "SELECT id, portfolio_id, symbol, quantity, avg_buy_price, first_purchase_date, last_updated, "
-        "COALESCE(sector, ''), COALESCE(broker_symbol, ''), COALESCE(exchange, '') "
+        "COALESCE(sector, ''), COALESCE(broker_symbol, ''), COALESCE(exchange, ''), "
+        "COALESCE(currency, ''), COALESCE(cost_fx_rate, 0) "
         "FROM portfolio_assets WHERE portfolio_id = ? AND symbol = ?",
         {portfolio_id, symbol.toUpper()}, map_asset);
 
@@ -158,9 +172,22 @@ Result<qint64> PortfolioRepository::add_asset(const QString& portfolio_id, const
         QString merged_sector = sector.isEmpty() ? asset.sector : sector;
         QString merged_broker_symbol = broker_symbol.isEmpty() ? asset.broker_symbol : broker_symbol;
         QString merged_exchange = exchange.isEmpty() ? asset.exchange : exchange;
+        QString merged_currency = currency.isEmpty() ? asset.currency : currency;
+        // Cost-weight the purchase FX rate so cost_basis_local × rate stays the
+        // true portfolio-currency cost. An unknown rate on either side leaves
+        // the merged rate unknown (0) for the backfill to resolve.
+        const double old_cost = asset.avg_buy_price * asset.quantity;
+        const double add_cost = price * qty;
+        double merged_fx = 0;
+        if (asset.quantity <= 1e-9)
+            merged_fx = fx_rate;
+        else if (asset.cost_fx_rate > 0 && fx_rate > 0 && old_cost + add_cost > 0)
+            merged_fx = (old_cost * asset.cost_fx_rate + add_cost * fx_rate) / (old_cost + add_cost);
         auto r = exec_write("UPDATE portfolio_assets SET quantity = ?, avg_buy_price = ?, sector = ?, "
-                            "broker_symbol = ?, exchange = ?, last_updated = datetime('now') WHERE id = ?",
-                            {new_qty, new_avg, merged_sector, merged_broker_symbol, merged_exchange, asset.id});
+                            "broker_symbol = ?, exchange = ?, currency = ?, cost_fx_rate = ?, "
+                            "last_updated = datetime('now') WHERE id = ?",
+                            {new_qty, new_avg, merged_sector, merged_broker_symbol, merged_exchange, merged_currency,
+                             merged_fx, asset.id});
         if (r.is_err())
             return Result<qint64>::err(r.error());
         SyncOutbox::record_unique("portfolio", portfolio_id, "sync");
@@ -169,8 +196,9 @@ Result<qint64> PortfolioRepository::add_asset(const QString& portfolio_id, const
 
     auto ins =
         exec_insert("INSERT INTO portfolio_assets (portfolio_id, symbol, quantity, avg_buy_price, first_purchase_date, "
-                    "sector, broker_symbol, exchange) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
-                    {portfolio_id, symbol.toUpper(), qty, price, purchase_date, sector, broker_symbol, exchange});
+                    "sector, broker_symbol, exchange, currency, cost_fx_rate) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                    {portfolio_id, symbol.toUpper(), qty, price, purchase_date, sector, broker_symbol, exchange,
+                     currency.toUpper(), fx_rate});
     if (ins.is_ok())
         SyncOutbox::record_unique("portfolio", portfolio_id, "sync");
     return ins;
@@ -186,6 +214,16 @@ Result<void> PortfolioRepository::set_asset_sector(const QString& portfolio_id,
     return r;
 }
 
+Result<void> PortfolioRepository::set_asset_currency(const QString& portfolio_id, const QString& symbol,
+                                                     const QString& currency, double cost_fx_rate) {
+    auto r = exec_write("UPDATE portfolio_assets SET currency = ?, cost_fx_rate = ? "
+                        "WHERE portfolio_id = ? AND symbol = ?",
+                        {currency.toUpper(), cost_fx_rate, portfolio_id, symbol.toUpper()});
+    if (r.is_ok())
+        SyncOutbox::record_unique("portfolio", portfolio_id, "sync");
+    return r;
+}
+
 Result<void> PortfolioRepository::update_asset(const QString& portfolio_id, const QString& symbol, double qty,
                                                double avg_price) {
     auto r = exec_write("UPDATE portfolio_assets SET quantity = ?, avg_buy_price = ?, "
@@ -290,4 +328,41 @@ Result<QVector<portfolio::PortfolioSnapshot>> PortfolioRepository::get_snapshots
         {portfolio_id, days}, map_snapshot);
 }
 
+// ── Benchmark prices ─────────────────────────────────────────────────────────
+
+Result<void> PortfolioRepository::upsert_benchmark_closes(const QString& symbol,
+                                                          const QVector<QPair<QString, double>>& closes) {
+    if (closes.isEmpty())
+        return Result<void>::ok();
+    auto begin = db().begin_transaction();
+    const bool in_tx = begin.is_ok();
+    for (const auto& [date, close] : closes) {
+        auto r = exec_write("INSERT OR REPLACE INTO benchmark_prices (symbol, price_date, close) VALUES (?, ?, ?)",
+                            {symbol, date, close});
+        if (r.is_err()) {
+            if (in_tx)
+                db().rollback();
+            return r;
+        }
+    }
+    if (in_tx)
+        return db().commit();
+    return Result<void>::ok();
+}
+
+Result<QVector<QPair<QString, double>>> PortfolioRepository::get_benchmark_closes(const QString& symbol,
+                                                                                  const QString& from_date) {
+    return query_list_as<QPair<QString, double>>(
+        "SELECT price_date, close FROM benchmark_prices WHERE symbol = ? AND price_date >= ? "
+        "ORDER BY price_date ASC",
+        {symbol, from_date},
+        [](QSqlQuery& q) { return QPair<QString, double>(q.value(0).toString(), q.value(1).toDouble()); });
+}
+
+QString PortfolioRepository::last_benchmark_date(const QString& symbol) {
+    auto r = query_list_as<QString>("SELECT MAX(price_date) FROM benchmark_prices WHERE symbol = ?", {symbol},
+                                    [](QSqlQuery& q) { return q.value(0).toString(); });
+    return r.is_ok() && !r.value().isEmpty() ? r.value().first() : QString();
+}
+
 } // namespace fincept
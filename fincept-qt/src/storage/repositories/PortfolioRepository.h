@@ -19,6 +19,8 @@ class PortfolioRepository : public BaseRepository<portfolio::Portfolio> {
                                      const QString& description = {}, const QString& broker_account_id = {});
     Result<void> update_portfolio(const QString& id, const QString& name, const QString& owner, const QString& currency,
                                   const QString& description = {});
+    /// Empty `symbol` reverts to the currency default. See migration v054.
+    Result<void> set_portfolio_benchmark(const QString& id, const QString& symbol);
     Result<void> delete_portfolio(const QString& id);
 
     // ── Assets CRUD ──────────────────────────────────────────────────────────
@@ -27,10 +29,15 @@ class PortfolioRepository : public BaseRepository<portfolio::Portfolio> {
     /// "RELIANCE" + "NSE"); both empty for manual / JSON imports. The
     /// canonical `symbol` arg stays in yfinance-format ("RELIANCE.NS")
     /// regardless — every downstream consumer treats it as such.
+    /// `currency` is the native quote currency ('' = infer from the symbol)
+    /// and `fx_rate` the portfolio-currency units per native unit at purchase
+    /// (0 = unknown); repeat buys cost-weight it. See migration v053.
     Result<qint64> add_asset(const QString& portfolio_id, const QString& symbol, double qty, double price,
                              const QString& date = {}, const QString& sector = {}, const QString& broker_symbol = {},
-                             const QString& exchange = {});
+                             const QString& exchange = {}, const QString& currency = {}, double fx_rate = 0);
     Result<void> update_asset(const QString& portfolio_id, const QString& symbol, double qty, double avg_price);
+    Result<void> set_asset_currency(const QString& portfolio_id, const QString& symbol, const QString& currency,
+                                    double cost_fx_rate);
     Result<void> set_asset_sector(const QString& portfolio_id, const QString& symbol, const QString& sector);
     Result<void> remove_asset(const QString& portfolio_id, const QString& symbol);
 
@@ -48,6 +55,13 @@ class PortfolioRepository : public BaseRepository<portfolio::Portfolio> {
                                const QString& date);
     Result<QVector<portfolio::PortfolioSnapshot>> get_snapshots(const QString& portfolio_id, int days = 365);
 
+    // ── Benchmark prices (v054) ──────────────────────────────────────────────
+    /// (YYYY-MM-DD, close) pairs; existing dates are overwritten.
+    Result<void> upsert_benchmark_closes(const QString& symbol, const QVector<QPair<QString, double>>& closes);
+    Result<QVector<QPair<QString, double>>> get_benchmark_closes(const QString& symbol, const QString& from_date);
+    /// Latest stored date, empty if the benchmark was never synced.
+    QString last_benchmark_date(const QString& symbol);
+
   private:
     PortfolioRepository() = default;
 
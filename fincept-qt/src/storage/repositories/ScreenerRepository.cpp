@@ -0,0 +1,246 @@
+// src/storage/repositories/ScreenerRepository.cpp
+#include "storage/repositories/ScreenerRepository.h"
+
+#include <QJsonDocument>
+#include <QUuid>
+
+namespace fincept {
+
+namespace {
+
+const char* kScreenCols = "id, name, description, filter, universe, sort_field, sort_desc, max_results, columns, "
+                          "schedule_minutes, refresh_data, notify, export_path, last_run_at, last_match_count";
+
+const char* kDataCols = "symbol, name, sector, industry, exchange, country, currency, fields, updated_at";
+
+const char* kRunCols = "id, screen_id, ran_at, universe_size, match_count, symbols, added, removed, rows";
+
+// SQLite's bound-parameter limit is 999 on older builds.
+constexpr int kInChunk = 500;
+
+QString compact(const QJsonObject& o) {
+    return QString::fromUtf8(QJsonDocument(o).toJson(QJsonDocument::Compact));
+}
+
+QString compact(const QJsonArray& a) {
+    return QString::fromUtf8(QJsonDocument(a).toJson(QJsonDocument::Compact));
+}
+
+QString compact(const QStringList& l) {
+    return compact(QJsonArray::fromStringList(l));
+}
+
+QStringList string_list(const QVariant& v) {
+    QStringList out;
+    for (const auto& e : QJsonDocument::fromJson(v.toString().toUtf8()).array())
+        out.append(e.toString());
+    return out;
+}
+
+ScreenerDataRow map_data(QSqlQuery& q) {
+    ScreenerDataRow d;
+    d.symbol = q.value(0).toString();
+    d.name = q.value(1).toString();
+    d.sector = q.value(2).toString();
+    d.industry = q.value(3).toString();
+    d.exchange = q.value(4).toString();
+    d.country = q.value(5).toString();
+    d.currency = q.value(6).toString();
+    d.fields = QJsonDocument::fromJson(q.value(7).toString().toUtf8()).object();
+    d.updated_at = q.value(8).toLongLong();
+    return d;
+}
+
+ScreenRun map_run(QSqlQuery& q) {
+    ScreenRun r;
+    r.id = q.value(0).toString();
+    r.screen_id = q.value(1).toString();
+    r.ran_at = q.value(2).toLongLong();
+    r.universe_size = q.value(3).toInt();
+    r.match_count = q.value(4).toInt();
+    r.symbols = string_list(q.value(5));
+    r.added = string_list(q.value(6));
+    r.removed = string_list(q.value(7));
+    r.rows = QJsonDocument::fromJson(q.value(8).toString().toUtf8()).array();
+    return r;
+}
+
+} // namespace
+
+ScreenerRepository& ScreenerRepository::instance() {
+    static ScreenerRepository s;
+    return s;
+}
+
+SavedScreen ScreenerRepository::map_row(QSqlQuery& q) {
+    SavedScreen s;
+    s.id = q.value(0).toString();
+    s.name = q.value(1).toString();
+    s.description = q.value(2).toString();
+    s.filter = QJsonDocument::fromJson(q.value(3).toString().toUtf8()).object();
+    s.universe = string_list(q.value(4));
+    s.sort_field = q.value(5).toString();
+    s.sort_desc = q.value(6).toInt() != 0;
+    s.max_results = q.value(7).toInt();
+    s.columns = string_list(q.value(8));
+    s.schedule_minutes = q.value(9).toInt();
+    s.refresh_data = q.value(10).toInt() != 0;
+    s.notify = q.value(11).toInt() != 0;
+    s.export_path = q.value(12).toString();
+    s.last_run_at = q.value(13).toLongLong();
+    s.last_match_count = q.value(14).toInt();
+    return s;
+}
+
+// ── Cached metrics ──────────────────────────────────────────────────────────
+
+Result<void> ScreenerRepository::upsert_data(const QVector<ScreenerDataRow>& rows) {
+    if (rows.isEmpty())
+        return Result<void>::ok();
+    if (auto tx = db().begin_transaction(); tx.is_err())
+        return tx;
+    Result<void> r = Result<void>::ok();
+    for (int i = 0; r.is_ok() && i < rows.size(); ++i) {
+        const ScreenerDataRow& d = rows[i];
+        r = exec_write(QString("INSERT OR REPLACE INTO screener_data (%1) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)")
+                           .arg(kDataCols),
+                       {d.symbol, d.name, d.sector, d.industry, d.exchange, d.country, d.currency, compact(d.fields),
+                        d.updated_at});
+    }
+    if (r.is_err()) {
+        db().rollback();
+        return r;
+    }
+    if (auto c = db().commit(); c.is_err()) {
+        db().rollback();
+        return c;
+    }
+    return Result<void>::ok();
+}
+
+Result<QVector<ScreenerDataRow>> ScreenerRepository::load_data(const QStringList& symbols) {
+    if (symbols.isEmpty())
+        return query_list_as<ScreenerDataRow>(QString("SELECT %1 FROM screener_data ORDER BY symbol").arg(kDataCols),
+                                              {}, map_data);
+    QVector<ScreenerDataRow> out;
+    for (int from = 0; from < symbols.size(); from += kInChunk) {
+        const QStringList chunk = symbols.mid(from, kInChunk);
+        QStringList marks;
+        QVariantList params;
+        for (const QString& s : chunk) {
+            marks.append("?");
+            params.append(s);
+        }
+        auto r = query_list_as<ScreenerDataRow>(
+            QString("SELECT %1 FROM screener_data WHERE symbol IN (%2)").arg(kDataCols, marks.join(", ")), params,
+            map_data);
+        if (r.is_err())
+            return r;
+        out += r.value();
+    }
+    return Result<QVector<ScreenerDataRow>>::ok(std::move(out));
+}
+
+Result<QStringList> ScreenerRepository::cached_symbols() {
+    auto r = db().execute("SELECT symbol FROM screener_data ORDER BY symbol");
+    if (r.is_err())
+        return Result<QStringList>::err(r.error());
+    QStringList out;
+    auto& q = r.value();
+    while (q.next())
+        out.append(q.value(0).toString());
+    return Result<QStringList>::ok(out);
+}
+
+Result<void> ScreenerRepository::remove_data(const QStringList& symbols) {
+    for (const QString& s : symbols) {
+        if (auto r = exec_write("DELETE FROM screener_data WHERE symbol = ?", {s}); r.is_err())
+            return r;
+    }
+    return Result<void>::ok();
+}
+
+// ── Saved screens ───────────────────────────────────────────────────────────
+
+Result<SavedScreen> ScreenerRepository::save_screen(const SavedScreen& in) {
+    SavedScreen s = in;
+    if (s.id.isEmpty())
+        s.id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    auto w = exec_write(
+        QString("INSERT INTO saved_screens (%1) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) "
+                "ON CONFLICT(id) DO UPDATE SET name = excluded.name, description = excluded.description, "
+                "filter = excluded.filter, universe = excluded.universe, sort_field = excluded.sort_field, "
+                "sort_desc = excluded.sort_desc, max_results = excluded.max_results, columns = excluded.columns, "
+                "schedule_minutes = excluded.schedule_minutes, refresh_data = excluded.refresh_data, "
+                "notify = excluded.notify, export_path = excluded.export_path, updated_at = datetime('now')")
+            .arg(kScreenCols),
+        {s.id, s.name, s.description, compact(s.filter), compact(s.universe), s.sort_field, s.sort_desc ? 1 : 0,
+         s.max_results, compact(s.columns), s.schedule_minutes, s.refresh_data ? 1 : 0, s.notify ? 1 : 0,
+         s.export_path, s.last_run_at, s.last_match_count});
+    if (w.is_err())
+        return Result<SavedScreen>::err(w.error());
+    return Result<SavedScreen>::ok(s);
+}
+
+Result<void> ScreenerRepository::remove_screen(const QString& id) {
+    // screen_runs cascade only when foreign keys are on; delete explicitly.
+    if (auto r = exec_write("DELETE FROM screen_runs WHERE screen_id = ?", {id}); r.is_err())
+        return r;
+    return exec_write("DELETE FROM saved_screens WHERE id = ?", {id});
+}
+
+Result<QVector<SavedScreen>> ScreenerRepository::list_screens() {
+    return query_list(QString("SELECT %1 FROM saved_screens ORDER BY name COLLATE NOCASE").arg(kScreenCols), {},
+                      &ScreenerRepository::map_row);
+}
+
+std::optional<SavedScreen> ScreenerRepository::get_screen(const QString& id) {
+    return query_optional(QString("SELECT %1 FROM saved_screens WHERE id = ?").arg(kScreenCols), {id},
+                          &ScreenerRepository::map_row);
+}
+
+std::optional<SavedScreen> ScreenerRepository::find_screen(const QString& id_or_name) {
+    if (auto s = get_screen(id_or_name))
+        return s;
+    return query_optional(
+        QString("SELECT %1 FROM saved_screens WHERE name = ? COLLATE NOCASE LIMIT 1").arg(kScreenCols),
+        {id_or_name.trimmed()}, &ScreenerRepository::map_row);
+}
+
+Result<void> ScreenerRepository::touch_screen(const QString& id, qint64 ran_at) {
+    return exec_write("UPDATE saved_screens SET last_run_at = ? WHERE id = ?", {ran_at, id});
+}
+
+// ── Runs ────────────────────────────────────────────────────────────────────
+
+Result<ScreenRun> ScreenerRepository::record_run(const ScreenRun& in, int keep) {
+    ScreenRun run = in;
+    if (run.id.isEmpty())
+        run.id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    auto w = exec_write(QString("INSERT INTO screen_runs (%1) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)").arg(kRunCols),
+                        {run.id, run.screen_id, run.ran_at, run.universe_size, run.match_count, compact(run.symbols),
+                         compact(run.added), compact(run.removed), compact(run.rows)});
+    if (w.is_err())
+        return Result<ScreenRun>::err(w.error());
+    exec_write("UPDATE saved_screens SET last_run_at = ?, last_match_count = ? WHERE id = ?",
+               {run.ran_at, run.match_count, run.screen_id});
+    exec_write("DELETE FROM screen_runs WHERE screen_id = ? AND id NOT IN "
+               "(SELECT id FROM screen_runs WHERE screen_id = ? ORDER BY ran_at DESC LIMIT ?)",
+               {run.screen_id, run.screen_id, keep});
+    return Result<ScreenRun>::ok(run);
+}
+
+Result<QVector<ScreenRun>> ScreenerRepository::list_runs(const QString& screen_id, int limit) {
+    return query_list_as<ScreenRun>(
+        QString("SELECT %1 FROM screen_runs WHERE screen_id = ? ORDER BY ran_at DESC LIMIT ?").arg(kRunCols),
+        {screen_id, limit}, map_run);
+}
+
+std::optional<ScreenRun> ScreenerRepository::last_run(const QString& screen_id) {
+    auto r = list_runs(screen_id, 1);
+    if (r.is_err() || r.value().isEmpty())
+        return std::nullopt;
+    return r.value().first();
+}
+
+} // namespace fincept
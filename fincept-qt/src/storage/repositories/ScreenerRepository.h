@@ -0,0 +1,94 @@
+// src/storage/repositories/ScreenerRepository.h
+#pragma once
+#include "storage/repositories/BaseRepository.h"
+
+#include <QJsonArray>
+#include <QJsonObject>
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+#include <optional>
+
+namespace fincept {
+
+/// One symbol's cached screener metrics. Schema in v073_screener.
+struct ScreenerDataRow {
+    QString symbol;
+    QString name;
+    QString sector;
+    QString industry;
+    QString exchange;
+    QString country;
+    QString currency;
+    QJsonObject fields;    // metric key → number (see ScreenFilter::fields())
+    qint64 updated_at = 0; // epoch ms
+};
+
+/// A named screen: a filter tree (ScreenFilter) evaluated over `universe`, or
+/// over every cached symbol when the universe is empty.
+struct SavedScreen {
+    QString id;
+    QString name;
+    QString description;
+    QJsonObject filter;
+    QStringList universe;
+    QString sort_field;
+    bool sort_desc = true;
+    int max_results = 50;
+    QStringList columns;      // extra result columns besides the filter / sort fields
+    int schedule_minutes = 0; // 0 = manual runs only
+    bool refresh_data = true; // re-fetch the universe's metrics before a scheduled run
+    bool notify = true;       // toast when a scheduled run changes the match list
+    QString export_path;      // CSV rewritten after every run when set
+    qint64 last_run_at = 0;   // epoch ms
+    int last_match_count = 0;
+};
+
+struct ScreenRun {
+    QString id;
+    QString screen_id;
+    qint64 ran_at = 0; // epoch ms
+    int universe_size = 0;
+    int match_count = 0;
+    QStringList symbols; // matches in result order
+    QStringList added;   // matches absent from the previous run
+    QStringList removed; // previous matches absent from this run
+    QJsonArray rows;     // result rows as returned by ScreenerEngine
+};
+
+class ScreenerRepository : public BaseRepository<SavedScreen> {
+  public:
+    static ScreenerRepository& instance();
+
+    // ── Cached metrics ──────────────────────────────────────────────────
+    Result<void> upsert_data(const QVector<ScreenerDataRow>& rows);
+    /// Rows for `symbols`, or every cached row when `symbols` is empty.
+    Result<QVector<ScreenerDataRow>> load_data(const QStringList& symbols);
+    Result<QStringList> cached_symbols();
+    Result<void> remove_data(const QStringList& symbols);
+
+    // ── Saved screens ───────────────────────────────────────────────────
+    Result<SavedScreen> save_screen(const SavedScreen& s); // insert or replace; generates id if empty
+    Result<void> remove_screen(const QString& id);
+    Result<QVector<SavedScreen>> list_screens();
+    std::optional<SavedScreen> get_screen(const QString& id);
+    /// Looks a screen up by id, then by case-insensitive name.
+    std::optional<SavedScreen> find_screen(const QString& id_or_name);
+    /// Stamps a run that produced no result, so the schedule backs off.
+    Result<void> touch_screen(const QString& id, qint64 ran_at);
+
+    // ── Runs ────────────────────────────────────────────────────────────
+    /// Stores `run`, stamps the screen's last run and keeps the newest
+    /// `keep` runs of that screen.
+    Result<ScreenRun> record_run(const ScreenRun& run, int keep);
+    /// Newest first.
+    Result<QVector<ScreenRun>> list_runs(const QString& screen_id, int limit);
+    std::optional<ScreenRun> last_run(const QString& screen_id);
+
+  private:
+    ScreenerRepository() = default;
+    static SavedScreen map_row(QSqlQuery& q);
+};
+
+} // namespace fincept
@@ -0,0 +1,150 @@
+// src/storage/repositories/StablecoinPegRepository.cpp
+#include "storage/repositories/StablecoinPegRepository.h"
+
+#include <QJsonDocument>
+
+namespace fincept {
+
+namespace {
+
+const char* kSpEventCols =
+    "id, kind, coin, source, threshold, started_at, alerted_at, ended_at, peak_value, last_value, detail";
+
+CurvePoolBalance spr_map_pool(QSqlQuery& q) {
+    CurvePoolBalance b;
+    b.pool = q.value(0).toString();
+    b.pool_name = q.value(1).toString();
+    b.coin = q.value(2).toString();
+    b.ts = q.value(3).toLongLong();
+    b.balance = q.value(4).toDouble();
+    b.share_pct = q.value(5).toDouble();
+    return b;
+}
+
+DepegEvent spr_map_event(QSqlQuery& q) {
+    DepegEvent e;
+    e.id = q.value(0).toString();
+    e.kind = q.value(1).toString();
+    e.coin = q.value(2).toString();
+    e.source = q.value(3).toString();
+    e.threshold = q.value(4).toDouble();
+    e.started_at = q.value(5).toLongLong();
+    e.alerted_at = q.value(6).toLongLong();
+    e.ended_at = q.value(7).toLongLong();
+    e.peak_value = q.value(8).toDouble();
+    e.last_value = q.value(9).toDouble();
+    e.detail = QJsonDocument::fromJson(q.value(10).toString().toUtf8()).object();
+    return e;
+}
+
+} // namespace
+
+StablecoinPegRepository& StablecoinPegRepository::instance() {
+    static StablecoinPegRepository s;
+    return s;
+}
+
+StablecoinPrice StablecoinPegRepository::map_row(QSqlQuery& q) {
+    StablecoinPrice p;
+    p.coin = q.value(0).toString();
+    p.venue = q.value(1).toString();
+    p.ts = q.value(2).toLongLong();
+    p.price = q.value(3).toDouble();
+    return p;
+}
+
+// ── History ─────────────────────────────────────────────────────────────────
+
+Result<void> StablecoinPegRepository::store_prices(const QVector<StablecoinPrice>& prices) {
+    if (prices.isEmpty())
+        return Result<void>::ok();
+    auto tx = db().begin_transaction();
+    if (tx.is_err())
+        return tx;
+    for (const auto& p : prices) {
+        auto w = exec_write("INSERT OR REPLACE INTO stablecoin_prices (coin, venue, ts, price) VALUES (?, ?, ?, ?)",
+                            {p.coin, p.venue, p.ts, p.price});
+        if (w.is_err()) {
+            db().rollback();
+            return w;
+        }
+    }
+    return db().commit();
+}
+
+Result<void> StablecoinPegRepository::store_pool(const QVector<CurvePoolBalance>& balances) {
+    if (balances.isEmpty())
+        return Result<void>::ok();
+    auto tx = db().begin_transaction();
+    if (tx.is_err())
+        return tx;
+    for (const auto& b : balances) {
+        auto w = exec_write("INSERT OR REPLACE INTO stablecoin_pool_balances (pool, pool_name, coin, ts, balance, "
+                            "share_pct) VALUES (?, ?, ?, ?, ?, ?)",
+                            {b.pool, b.pool_name, b.coin, b.ts, b.balance, b.share_pct});
+        if (w.is_err()) {
+            db().rollback();
+            return w;
+        }
+    }
+    return db().commit();
+}
+
+Result<QVector<StablecoinPrice>> StablecoinPegRepository::price_history(const QString& coin, const QString& venue,
+                                                                        qint64 from_ms, int limit) {
+    QString sql = "SELECT coin, venue, ts, price FROM stablecoin_prices WHERE coin = ? AND ts >= ?";
+    QVariantList params{coin.toUpper(), from_ms};
+    if (!venue.isEmpty()) {
+        sql += " AND venue = ?";
+        params.append(venue.toLower());
+    }
+    // Newest `limit` rows, returned oldest first.
+    sql = QString("SELECT * FROM (%1 ORDER BY ts DESC LIMIT ?) ORDER BY ts ASC").arg(sql);
+    params.append(limit);
+    return query_list(sql, params, &StablecoinPegRepository::map_row);
+}
+
+Result<QVector<CurvePoolBalance>> StablecoinPegRepository::pool_history(const QString& pool, qint64 from_ms,
+                                                                        int limit) {
+    return query_list_as<CurvePoolBalance>(
+        "SELECT * FROM (SELECT pool, pool_name, coin, ts, balance, share_pct FROM stablecoin_pool_balances "
+        "WHERE pool = ? COLLATE NOCASE AND ts >= ? ORDER BY ts DESC LIMIT ?) ORDER BY ts ASC, coin",
+        {pool, from_ms, limit}, spr_map_pool);
+}
+
+Result<void> StablecoinPegRepository::prune(qint64 before_ms) {
+    auto r = exec_write("DELETE FROM stablecoin_prices WHERE ts < ?", {before_ms});
+    if (r.is_err())
+        return r;
+    return exec_write("DELETE FROM stablecoin_pool_balances WHERE ts < ?", {before_ms});
+}
+
+// ── Depeg events ────────────────────────────────────────────────────────────
+
+Result<void> StablecoinPegRepository::save_event(const DepegEvent& e) {
+    return exec_write(
+        QString("INSERT OR REPLACE INTO stablecoin_depeg_events (%1) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+            .arg(kSpEventCols),
+        {e.id, e.kind, e.coin, e.source, e.threshold, e.started_at, e.alerted_at, e.ended_at, e.peak_value,
+         e.last_value, QString::fromUtf8(QJsonDocument(e.detail).toJson(QJsonDocument::Compact))});
+}
+
+Result<QVector<DepegEvent>> StablecoinPegRepository::open_events() {
+    return query_list_as<DepegEvent>(
+        QString("SELECT %1 FROM stablecoin_depeg_events WHERE ended_at = 0 ORDER BY started_at").arg(kSpEventCols),
+        {}, spr_map_event);
+}
+
+Result<QVector<DepegEvent>> StablecoinPegRepository::query_events(const QString& coin, qint64 since_ms, int limit) {
+    QString sql = QString("SELECT %1 FROM stablecoin_depeg_events WHERE started_at >= ?").arg(kSpEventCols);
+    QVariantList params{since_ms};
+    if (!coin.isEmpty()) {
+        sql += " AND coin = ?";
+        params.append(coin.toUpper());
+    }
+    sql += " ORDER BY started_at DESC LIMIT ?";
+    params.append(limit);
+    return query_list_as<DepegEvent>(sql, params, spr_map_event);
+}
+
+} // namespace fincept
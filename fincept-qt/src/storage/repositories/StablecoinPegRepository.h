@@ -0,0 +1,74 @@
+// src/storage/repositories/StablecoinPegRepository.h
+#pragma once
+#include "storage/repositories/BaseRepository.h"
+
+#include <QJsonObject>
+#include <QString>
+#include <QVector>
+
+namespace fincept {
+
+/// One venue's USD price for a stablecoin at one poll. Schema in
+/// v069_stablecoin_peg.
+struct StablecoinPrice {
+    QString coin;  // USDT, USDC, DAI...
+    QString venue; // kraken | coinbase | bitstamp
+    qint64 ts = 0; // epoch ms of the poll
+    double price = 0;
+};
+
+/// One coin's balance in a Curve pool at one poll.
+struct CurvePoolBalance {
+    QString pool; // pool address
+    QString pool_name;
+    QString coin;
+    qint64 ts = 0;        // epoch ms of the poll
+    double balance = 0;   // token units
+    double share_pct = 0; // of the pool's total token units
+};
+
+/// One depeg episode. kind "price": value is the cross-venue median's
+/// deviation from $1 in bps, source "median". kind "pool": value is the
+/// coin's share of the pool minus its fair share (100 / coins) in percentage
+/// points, source the pool address.
+struct DepegEvent {
+    QString id;
+    QString kind;
+    QString coin;
+    QString source;
+    double threshold = 0;
+    qint64 started_at = 0; // first poll past the threshold, epoch ms
+    qint64 alerted_at = 0; // when the breach had lasted long enough to alert
+    qint64 ended_at = 0;   // back inside the threshold; 0 while open
+    double peak_value = 0; // largest |value| seen, signed
+    double last_value = 0;
+    QJsonObject detail; // venue prices / pool composition at the peak
+};
+
+class StablecoinPegRepository : public BaseRepository<StablecoinPrice> {
+  public:
+    static StablecoinPegRepository& instance();
+
+    // ── History ─────────────────────────────────────────────────────────
+    Result<void> store_prices(const QVector<StablecoinPrice>& prices);
+    Result<void> store_pool(const QVector<CurvePoolBalance>& balances);
+    /// Ascending by time; empty `venue` matches all.
+    Result<QVector<StablecoinPrice>> price_history(const QString& coin, const QString& venue, qint64 from_ms,
+                                                   int limit = 5000);
+    /// Ascending by time.
+    Result<QVector<CurvePoolBalance>> pool_history(const QString& pool, qint64 from_ms, int limit = 5000);
+    /// Deletes price and pool rows older than `before_ms` (events are kept).
+    Result<void> prune(qint64 before_ms);
+
+    // ── Depeg events ────────────────────────────────────────────────────
+    Result<void> save_event(const DepegEvent& e); // insert or replace
+    Result<QVector<DepegEvent>> open_events();
+    /// Newest first; empty `coin` matches all.
+    Result<QVector<DepegEvent>> query_events(const QString& coin, qint64 since_ms, int limit);
+
+  private:
+    StablecoinPegRepository() = default;
+    static StablecoinPrice map_row(QSqlQuery& q);
+};
+
+} // namespace fincept
@@ -0,0 +1,117 @@
+// src/storage/repositories/SymbologyRepository.cpp
+#include "storage/repositories/SymbologyRepository.h"
+
+#include <QDateTime>
+
+namespace fincept {
+
+namespace {
+
+const char* kSymbologyCols = "exchange, symbol, listing, name, isin, figi, composite_figi, share_class_figi, "
+                             "security_type, source, updated_at";
+
+// Keeps the stored value unless the incoming one is non-empty.
+QString sym_merge(const char* col) {
+    return QString("%1 = CASE WHEN excluded.%1 <> '' THEN excluded.%1 ELSE symbology.%1 END").arg(col);
+}
+
+} // namespace
+
+SymbologyRepository& SymbologyRepository::instance() {
+    static SymbologyRepository s;
+    return s;
+}
+
+SymbolMapping SymbologyRepository::map_row(QSqlQuery& q) {
+    SymbolMapping m;
+    m.exchange = q.value(0).toString();
+    m.symbol = q.value(1).toString();
+    m.listing = q.value(2).toString();
+    m.name = q.value(3).toString();
+    m.isin = q.value(4).toString();
+    m.figi = q.value(5).toString();
+    m.composite_figi = q.value(6).toString();
+    m.share_class_figi = q.value(7).toString();
+    m.security_type = q.value(8).toString();
+    m.source = q.value(9).toString();
+    m.updated_at = q.value(10).toLongLong();
+    return m;
+}
+
+Result<void> SymbologyRepository::upsert(const SymbolMapping& m) {
+    QStringList sets;
+    for (const char* col : {"listing", "name", "isin", "figi", "composite_figi", "share_class_figi", "security_type"})
+        sets << sym_merge(col);
+    sets << "source = excluded.source"
+         << "updated_at = excluded.updated_at";
+    const qint64 now = m.updated_at > 0 ? m.updated_at : QDateTime::currentMSecsSinceEpoch();
+    return exec_write(QString("INSERT INTO symbology (%1) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) "
+                              "ON CONFLICT(exchange, symbol) DO UPDATE SET %2 "
+                              "WHERE symbology.source <> 'manual' OR excluded.source = 'manual'")
+                          .arg(kSymbologyCols, sets.join(", ")),
+                      {m.exchange.toUpper(), m.symbol.toUpper(), m.listing.toUpper(), m.name, m.isin.toUpper(),
+                       m.figi, m.composite_figi, m.share_class_figi, m.security_type, m.source, now});
+}
+
+Result<void> SymbologyRepository::remove(const QString& exchange, const QString& symbol) {
+    return exec_write("DELETE FROM symbology WHERE exchange = ? AND symbol = ?",
+                      {exchange.toUpper(), symbol.toUpper()});
+}
+
+std::optional<SymbolMapping> SymbologyRepository::find(const QString& exchange, const QString& symbol) {
+    return query_optional(QString("SELECT %1 FROM symbology WHERE exchange = ? AND symbol = ?").arg(kSymbologyCols),
+                          {exchange.toUpper(), symbol.toUpper()}, map_row);
+}
+
+std::optional<SymbolMapping> SymbologyRepository::find_by_isin(const QString& isin) {
+    // NSE ahead of BSE for dual-listed Indian names; otherwise the newest row.
+    return query_optional(QString("SELECT %1 FROM symbology WHERE isin = ? "
+                                  "ORDER BY exchange = 'NSE' DESC, updated_at DESC LIMIT 1")
+                              .arg(kSymbologyCols),
+                          {isin.toUpper()}, map_row);
+}
+
+std::optional<SymbolMapping> SymbologyRepository::find_by_figi(const QString& figi) {
+    const QString f = figi.toUpper();
+    return query_optional(QString("SELECT %1 FROM symbology WHERE figi = ? OR composite_figi = ? "
+                                  "OR share_class_figi = ? ORDER BY figi = ? DESC LIMIT 1")
+                              .arg(kSymbologyCols),
+                          {f, f, f, f}, map_row);
+}
+
+Result<QVector<SymbolMapping>> SymbologyRepository::find_by_symbol(const QString& symbol) {
+    return query_list(QString("SELECT %1 FROM symbology WHERE symbol = ? ORDER BY exchange = 'NSE' DESC, exchange")
+                          .arg(kSymbologyCols),
+                      {symbol.toUpper()}, map_row);
+}
+
+Result<int> SymbologyRepository::seed_from_instruments() {
+    auto r = db().execute(
+        "INSERT INTO symbology (exchange, symbol, name, isin, source, updated_at) "
+        "SELECT exchange, symbol, MAX(name), "
+        "       MAX(CASE WHEN broker_token LIKE '%|IN%' THEN substr(broker_token, instr(broker_token, '|') + 1) "
+        "           ELSE '' END), "
+        "       'broker', ? "
+        "FROM instruments WHERE instrument_type IN ('EQ', 'INDEX') AND symbol <> '' "
+        "GROUP BY exchange, symbol "
+        "ON CONFLICT(exchange, symbol) DO UPDATE SET "
+        "  name = CASE WHEN symbology.name = '' THEN excluded.name ELSE symbology.name END, "
+        "  isin = CASE WHEN symbology.isin = '' THEN excluded.isin ELSE symbology.isin END "
+        "WHERE (symbology.name = '' AND excluded.name <> '') OR (symbology.isin = '' AND excluded.isin <> '')",
+        {QDateTime::currentMSecsSinceEpoch()});
+    if (r.is_err()) {
+        LOG_ERROR("Repo", QString("seed_from_instruments failed: %1").arg(QString::fromStdString(r.error())));
+        return Result<int>::err(r.error());
+    }
+    return Result<int>::ok(r.value().numRowsAffected());
+}
+
+Result<int> SymbologyRepository::count() {
+    auto r = db().execute("SELECT COUNT(*) FROM symbology");
+    if (r.is_err())
+        return Result<int>::err(r.error());
+    auto& q = r.value();
+    return Result<int>::ok(q.next() ? q.value(0).toInt() : 0);
+}
+
+} // namespace fincept
@@ -0,0 +1,58 @@
+// src/storage/repositories/SymbologyRepository.h
+#pragma once
+#include "storage/repositories/BaseRepository.h"
+
+#include <QString>
+#include <QVector>
+
+#include <optional>
+
+namespace fincept {
+
+/// One listing and the identifiers other providers know it by. Empty fields
+/// are unknown. Schema in v065_symbology.
+struct SymbolMapping {
+    QString exchange; // canonical venue key: NSE, BSE, US, LSE, ...
+    QString symbol;   // exchange ticker, e.g. RELIANCE, AAPL, BRK.B
+    QString listing;  // primary venue when `exchange` is a country (US → NASDAQ / NYSE)
+    QString name;
+    QString isin;
+    QString figi;
+    QString composite_figi;
+    QString share_class_figi;
+    QString security_type;
+    QString source = QStringLiteral("manual"); // broker | openfigi | manual
+    qint64 updated_at = 0;                     // epoch ms
+};
+
+class SymbologyRepository : public BaseRepository<SymbolMapping> {
+  public:
+    static SymbologyRepository& instance();
+
+    /// Inserts or merges `m` into its (exchange, symbol) row: non-empty fields
+    /// replace stored ones. Rows with source "manual" only change through
+    /// another manual upsert.
+    Result<void> upsert(const SymbolMapping& m);
+    Result<void> remove(const QString& exchange, const QString& symbol);
+
+    std::optional<SymbolMapping> find(const QString& exchange, const QString& symbol);
+    std::optional<SymbolMapping> find_by_isin(const QString& isin);
+    /// Matches the FIGI, composite FIGI or share-class FIGI.
+    std::optional<SymbolMapping> find_by_figi(const QString& figi);
+    /// Every listing of `symbol`, any exchange.
+    Result<QVector<SymbolMapping>> find_by_symbol(const QString& symbol);
+
+    /// Adds every equity and index in the broker master contracts (instruments
+    /// table) not mapped yet, taking ISINs from Upstox instrument keys
+    /// ("NSE_EQ|INE002A01018"); fills the name / ISIN of rows that lack them.
+    /// Returns the rows added or changed.
+    Result<int> seed_from_instruments();
+
+    Result<int> count();
+
+  private:
+    SymbologyRepository() = default;
+    static SymbolMapping map_row(QSqlQuery& q);
+};
+
+} // namespace fincept
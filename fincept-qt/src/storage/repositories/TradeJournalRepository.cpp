@@ -0,0 +1,259 @@
+// src/storage/repositories/TradeJournalRepository.cpp
+#include "storage/repositories/TradeJournalRepository.h"
+
+#include <QDateTime>
+#include <QHash>
+#include <QJsonArray>
+#include <QJsonDocument>
+#include <QUuid>
+
+#include <functional>
+
+namespace fincept {
+
+namespace {
+
+using services::tca::TcaFill;
+
+const char* kFillCols = "source, fill_id, time_ms, account_id, account, broker, strategy, symbol, side, quantity, "
+                        "price, fee";
+
+const char* kTradeCols = "id, account_id, account, broker, strategy, symbol, direction, quantity, entry_price, "
+                         "exit_price, entry_ms, exit_ms, fees, pnl, return_pct, fill_ids, mae_pct, mfe_pct, "
+                         "excursion_tf, setup, notes, rating";
+
+TcaFill map_fill(QSqlQuery& q) {
+    TcaFill f;
+    f.source = q.value(0).toString();
+    f.id = q.value(1).toString();
+    f.time_ms = q.value(2).toLongLong();
+    f.account_id = q.value(3).toString();
+    f.account = q.value(4).toString();
+    f.broker = q.value(5).toString();
+    f.strategy = q.value(6).toString();
+    f.symbol = q.value(7).toString();
+    f.side = q.value(8).toInt() < 0 ? -1 : 1;
+    f.quantity = q.value(9).toDouble();
+    f.price = q.value(10).toDouble();
+    f.fee = q.value(11).toDouble();
+    return f;
+}
+
+JournalAttachment map_attachment(QSqlQuery& q) {
+    JournalAttachment a;
+    a.id = q.value(0).toString();
+    a.trade_id = q.value(1).toString();
+    a.path = q.value(2).toString();
+    a.caption = q.value(3).toString();
+    a.created_at = q.value(4).toLongLong();
+    return a;
+}
+
+QVariant nullable(bool set, double v) {
+    return set ? QVariant(v) : QVariant(QMetaType(QMetaType::Double));
+}
+
+// Runs `body` in one transaction, rolling back on the first error.
+Result<void> in_transaction(const std::function<Result<void>()>& body) {
+    auto& db = Database::instance();
+    if (auto tx = db.begin_transaction(); tx.is_err())
+        return tx;
+    if (auto r = body(); r.is_err()) {
+        db.rollback();
+        return r;
+    }
+    if (auto c = db.commit(); c.is_err()) {
+        db.rollback();
+        return c;
+    }
+    return Result<void>::ok();
+}
+
+} // namespace
+
+TradeJournalRepository& TradeJournalRepository::instance() {
+    static TradeJournalRepository s;
+    return s;
+}
+
+JournalTrade TradeJournalRepository::map_row(QSqlQuery& q) {
+    JournalTrade t;
+    t.id = q.value(0).toString();
+    t.account_id = q.value(1).toString();
+    t.account = q.value(2).toString();
+    t.broker = q.value(3).toString();
+    t.strategy = q.value(4).toString();
+    t.symbol = q.value(5).toString();
+    t.direction = q.value(6).toInt() < 0 ? -1 : 1;
+    t.quantity = q.value(7).toDouble();
+    t.entry_price = q.value(8).toDouble();
+    t.exit_price = q.value(9).toDouble();
+    t.entry_ms = q.value(10).toLongLong();
+    t.exit_ms = q.value(11).toLongLong();
+    t.fees = q.value(12).toDouble();
+    t.pnl = q.value(13).toDouble();
+    t.return_pct = q.value(14).toDouble();
+    for (const auto& v : QJsonDocument::fromJson(q.value(15).toString().toUtf8()).array())
+        t.fill_ids.append(v.toString());
+    t.has_excursion = !q.value(16).isNull();
+    t.mae_pct = q.value(16).toDouble();
+    t.mfe_pct = q.value(17).toDouble();
+    t.excursion_tf = q.value(18).toString();
+    t.setup = q.value(19).toString();
+    t.notes = q.value(20).toString();
+    t.rating = q.value(21).toInt();
+    return t;
+}
+
+// ── Fills ───────────────────────────────────────────────────────────────────
+
+Result<void> TradeJournalRepository::upsert_fills(const QVector<TcaFill>& fills) {
+    if (fills.isEmpty())
+        return Result<void>::ok();
+    return in_transaction([&]() {
+        for (const TcaFill& f : fills) {
+            auto r = exec_write(
+                QString("INSERT OR REPLACE INTO journal_fills (%1) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+                    .arg(kFillCols),
+                {f.source, f.id, f.time_ms, f.account_id, f.account, f.broker, f.strategy, f.symbol, f.side,
+                 f.quantity, f.price, f.fee});
+            if (r.is_err())
+                return r;
+        }
+        return Result<void>::ok();
+    });
+}
+
+Result<QVector<TcaFill>> TradeJournalRepository::load_fills() {
+    return query_list_as<TcaFill>(QString("SELECT %1 FROM journal_fills ORDER BY time_ms").arg(kFillCols), {},
+                                  map_fill);
+}
+
+// ── Trades ──────────────────────────────────────────────────────────────────
+
+Result<void> TradeJournalRepository::save_trades(const QVector<JournalTrade>& trades) {
+    if (trades.isEmpty())
+        return Result<void>::ok();
+    return in_transaction([&]() {
+        for (const JournalTrade& t : trades) {
+            const QString ids = QString::fromUtf8(
+                QJsonDocument(QJsonArray::fromStringList(t.fill_ids)).toJson(QJsonDocument::Compact));
+            auto r = exec_write(
+                QString("INSERT INTO journal_trades (%1) "
+                        "VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, '', '', 0) "
+                        "ON CONFLICT(id) DO UPDATE SET account = excluded.account, broker = excluded.broker, "
+                        "strategy = excluded.strategy, quantity = excluded.quantity, "
+                        "entry_price = excluded.entry_price, exit_price = excluded.exit_price, "
+                        "exit_ms = excluded.exit_ms, fees = excluded.fees, pnl = excluded.pnl, "
+                        "return_pct = excluded.return_pct, fill_ids = excluded.fill_ids, "
+                        "mae_pct = excluded.mae_pct, mfe_pct = excluded.mfe_pct, "
+                        "excursion_tf = excluded.excursion_tf, updated_at = datetime('now')")
+                    .arg(kTradeCols),
+                {t.id, t.account_id, t.account, t.broker, t.strategy, t.symbol, t.direction, t.quantity,
+                 t.entry_price, t.exit_price, t.entry_ms, t.exit_ms, t.fees, t.pnl, t.return_pct, ids,
+                 nullable(t.has_excursion, t.mae_pct), nullable(t.has_excursion, t.mfe_pct), t.excursion_tf});
+            if (r.is_err())
+                return r;
+        }
+        return Result<void>::ok();
+    });
+}
+
+void TradeJournalRepository::attach_children(QVector<JournalTrade>& trades) {
+    QHash<QString, int> index;
+    for (int i = 0; i < trades.size(); ++i)
+        index.insert(trades[i].id, i);
+
+    if (auto r = db().execute("SELECT trade_id, tag FROM journal_trade_tags ORDER BY tag"); r.is_ok()) {
+        auto& q = r.value();
+        while (q.next()) {
+            const auto it = index.constFind(q.value(0).toString());
+            if (it != index.cend())
+                trades[*it].tags.append(q.value(1).toString());
+        }
+    }
+    auto a = query_list_as<JournalAttachment>(
+        "SELECT id, trade_id, path, caption, created_at FROM journal_attachments ORDER BY created_at", {},
+        map_attachment);
+    if (a.is_ok()) {
+        for (const auto& att : a.value()) {
+            const auto it = index.constFind(att.trade_id);
+            if (it != index.cend())
+                trades[*it].attachments.append(att);
+        }
+    }
+}
+
+Result<QVector<JournalTrade>> TradeJournalRepository::list_trades(qint64 since_ms) {
+    auto r = query_list(
+        QString("SELECT %1 FROM journal_trades WHERE entry_ms >= ? ORDER BY entry_ms DESC").arg(kTradeCols),
+        {since_ms}, &TradeJournalRepository::map_row);
+    if (r.is_err())
+        return r;
+    QVector<JournalTrade> trades = r.value();
+    attach_children(trades);
+    return Result<QVector<JournalTrade>>::ok(std::move(trades));
+}
+
+std::optional<JournalTrade> TradeJournalRepository::get_trade(const QString& id) {
+    auto t = query_optional(QString("SELECT %1 FROM journal_trades WHERE id = ?").arg(kTradeCols), {id},
+                            &TradeJournalRepository::map_row);
+    if (!t)
+        return std::nullopt;
+    QVector<JournalTrade> one{*t};
+    attach_children(one);
+    return one.first();
+}
+
+// ── Annotations ─────────────────────────────────────────────────────────────
+
+Result<void> TradeJournalRepository::save_annotation(const JournalTrade& t) {
+    return exec_write("UPDATE journal_trades SET setup = ?, notes = ?, rating = ?, updated_at = datetime('now') "
+                      "WHERE id = ?",
+                      {t.setup, t.notes, t.rating, t.id});
+}
+
+Result<void> TradeJournalRepository::set_tags(const QString& trade_id, const QStringList& tags) {
+    return in_transaction([&]() {
+        if (auto r = exec_write("DELETE FROM journal_trade_tags WHERE trade_id = ?", {trade_id}); r.is_err())
+            return r;
+        for (const QString& tag : tags) {
+            auto r = exec_write("INSERT OR IGNORE INTO journal_trade_tags (trade_id, tag) VALUES (?, ?)",
+                                {trade_id, tag});
+            if (r.is_err())
+                return r;
+        }
+        return Result<void>::ok();
+    });
+}
+
+Result<QStringList> TradeJournalRepository::all_tags() {
+    auto r = db().execute("SELECT DISTINCT tag FROM journal_trade_tags ORDER BY tag");
+    if (r.is_err())
+        return Result<QStringList>::err(r.error());
+    QStringList out;
+    auto& q = r.value();
+    while (q.next())
+        out.append(q.value(0).toString());
+    return Result<QStringList>::ok(out);
+}
+
+Result<JournalAttachment> TradeJournalRepository::add_attachment(const JournalAttachment& in) {
+    JournalAttachment a = in;
+    if (a.id.isEmpty())
+        a.id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    if (a.created_at == 0)
+        a.created_at = QDateTime::currentMSecsSinceEpoch();
+    auto w = exec_write("INSERT INTO journal_attachments (id, trade_id, path, caption, created_at) "
+                        "VALUES (?, ?, ?, ?, ?)",
+                        {a.id, a.trade_id, a.path, a.caption, a.created_at});
+    if (w.is_err())
+        return Result<JournalAttachment>::err(w.error());
+    return Result<JournalAttachment>::ok(a);
+}
+
+Result<void> TradeJournalRepository::remove_attachment(const QString& id) {
+    return exec_write("DELETE FROM journal_attachments WHERE id = ?", {id});
+}
+
+} // namespace fincept
@@ -0,0 +1,89 @@
+// src/storage/repositories/TradeJournalRepository.h
+#pragma once
+#include "services/tca/TcaEngine.h"
+#include "storage/repositories/BaseRepository.h"
+
+#include <QString>
+#include <QStringList>
+#include <QVector>
+
+#include <optional>
+
+namespace fincept {
+
+/// A chart snapshot or other file attached to a journal trade.
+struct JournalAttachment {
+    QString id;
+    QString trade_id;
+    QString path; // absolute file path; the file itself is not copied
+    QString caption;
+    qint64 created_at = 0; // epoch ms
+};
+
+/// One round trip from flat to flat, rebuilt from the journaled fills, and
+/// what the user has recorded against it. Schema in v076_trade_journal.
+struct JournalTrade {
+    QString id;
+    QString account_id;
+    QString account;
+    QString broker;
+    QString strategy; // of the opening fill
+    QString symbol;
+    int direction = 1;      // +1 long, -1 short
+    double quantity = 0;    // largest position held
+    double entry_price = 0; // average over the entries
+    double exit_price = 0;  // average over the exits so far
+    qint64 entry_ms = 0;
+    qint64 exit_ms = 0; // 0 while open
+    double fees = 0;
+    double pnl = 0;        // realized, net of fees
+    double return_pct = 0; // pnl over entry notional, fraction
+    QStringList fill_ids;
+    bool has_excursion = false;
+    double mae_pct = 0;   // worst move against the trade while held, fraction of entry (≤ 0)
+    double mfe_pct = 0;   // best move in its favour while held, fraction of entry (≥ 0)
+    QString excursion_tf; // bar interval MAE / MFE were read from
+    // User-recorded.
+    QString setup;
+    QString notes;
+    int rating = 0; // 0 = unrated, 1–5
+    QStringList tags;
+    QVector<JournalAttachment> attachments;
+
+    bool is_open() const { return exit_ms == 0; }
+};
+
+class TradeJournalRepository : public BaseRepository<JournalTrade> {
+  public:
+    static TradeJournalRepository& instance();
+
+    // ── Fills ───────────────────────────────────────────────────────────
+    /// Inserts or refreshes ledger fills, keyed by (source, id, time).
+    Result<void> upsert_fills(const QVector<services::tca::TcaFill>& fills);
+    /// Every journaled fill, oldest first.
+    Result<QVector<services::tca::TcaFill>> load_fills();
+
+    // ── Trades ──────────────────────────────────────────────────────────
+    /// Inserts the rebuilt trades or updates their derived columns; setup,
+    /// notes, rating, tags and attachments are left alone.
+    Result<void> save_trades(const QVector<JournalTrade>& trades);
+    /// Newest entry first, with tags and attachments.
+    Result<QVector<JournalTrade>> list_trades(qint64 since_ms = 0);
+    std::optional<JournalTrade> get_trade(const QString& id);
+
+    // ── Annotations ─────────────────────────────────────────────────────
+    /// Writes setup, notes and rating of `t`.
+    Result<void> save_annotation(const JournalTrade& t);
+    /// Replaces the trade's tags.
+    Result<void> set_tags(const QString& trade_id, const QStringList& tags);
+    Result<QStringList> all_tags();
+    Result<JournalAttachment> add_attachment(const JournalAttachment& a); // generates id if empty
+    Result<void> remove_attachment(const QString& id);
+
+  private:
+    TradeJournalRepository() = default;
+    static JournalTrade map_row(QSqlQuery& q);
+    void attach_children(QVector<JournalTrade>& trades);
+};
+
+} // namespace fincept
@@ -2,6 +2,8 @@
 
 #include "storage/sync/SyncOutbox.h"
 
+#include <algorithm>
+
 namespace fincept {
 
 WatchlistRepository& WatchlistRepository::instance() {
@@ -23,6 +25,13 @@ WatchlistStock WatchlistRepository::map_stock(QSqlQuery& q) {
     };
 }
 
+WatchlistColumn WatchlistRepository::map_column(QSqlQuery& q) {
+    return {
+        q.value(0).toString(), q.value(1).toString(), q.value(2).toString(),
+        q.value(3).toString(), q.value(4).toInt(),    q.value(5).toInt(),
+    };
+}
+
 Result<Watchlist> WatchlistRepository::create(const QString& name, const QString& color) {
     QString id = QUuid::createUuid().toString(QUuid::WithoutBraces);
     auto r = exec_write("INSERT INTO watchlists (id, name, color) VALUES (?, ?, ?)", {id, name, color});
@@ -62,9 +71,11 @@ Result<void> WatchlistRepository::remove(const QString& id) {
 
 Result<void> WatchlistRepository::add_stock(const QString& watchlist_id, const QString& symbol, const QString& name,
                                             const QString& exchange) {
-    auto r = exec_write("INSERT OR IGNORE INTO watchlist_stocks (watchlist_id, symbol, name, exchange) "
-                        "VALUES (?, ?, ?, ?)",
-                        {watchlist_id, symbol.toUpper(), name, exchange});
+    // New symbols go to the end of the user's ordering.
+    auto r = exec_write("INSERT OR IGNORE INTO watchlist_stocks (watchlist_id, symbol, name, exchange, sort_order) "
+                        "VALUES (?, ?, ?, ?, (SELECT COALESCE(MAX(sort_order) + 1, 0) FROM watchlist_stocks "
+                        "WHERE watchlist_id = ?))",
+                        {watchlist_id, symbol.toUpper(), name, exchange, watchlist_id});
     if (r.is_ok())
         SyncOutbox::record("watchlist", watchlist_id, "stock_add", symbol.toUpper());
     return r;
@@ -94,4 +105,76 @@ Result<QVector<WatchlistStock>> WatchlistRepository::get_stocks(const QString& w
     return Result<QVector<WatchlistStock>>::ok(std::move(result));
 }
 
+Result<void> WatchlistRepository::reorder_stocks(const QString& watchlist_id, const QStringList& symbols) {
+    auto current = get_stocks(watchlist_id);
+    if (current.is_err())
+        return Result<void>::err(current.error());
+
+    QStringList order;
+    for (const QString& s : symbols) {
+        const QString sym = s.trimmed().toUpper();
+        if (!order.contains(sym))
+            order.append(sym);
+    }
+    QStringList known;
+    for (const auto& s : current.value())
+        known.append(s.symbol);
+    order.erase(std::remove_if(order.begin(), order.end(), [&](const QString& s) { return !known.contains(s); }),
+                order.end());
+    for (const QString& s : known) {
+        if (!order.contains(s))
+            order.append(s);
+    }
+
+    if (auto tx = db().begin_transaction(); tx.is_err())
+        return tx;
+    for (int i = 0; i < order.size(); ++i) {
+        auto r = exec_write("UPDATE watchlist_stocks SET sort_order = ? WHERE watchlist_id = ? AND symbol = ?",
+                            {i, watchlist_id, order[i]});
+        if (r.is_err()) {
+            db().rollback();
+            return r;
+        }
+    }
+    if (auto c = db().commit(); c.is_err()) {
+        db().rollback();
+        return c;
+    }
+    SyncOutbox::record("watchlist", watchlist_id, "reorder");
+    return Result<void>::ok();
+}
+
+Result<QVector<WatchlistColumn>> WatchlistRepository::get_columns(const QString& watchlist_id) {
+    return query_list_as<WatchlistColumn>(
+        "SELECT id, watchlist_id, name, expression, decimals, sort_order "
+        "FROM watchlist_columns WHERE watchlist_id = ? ORDER BY sort_order, created_at",
+        {watchlist_id}, map_column);
+}
+
+Result<WatchlistColumn> WatchlistRepository::upsert_column(const QString& watchlist_id, const QString& name,
+                                                           const QString& expression, int decimals) {
+    const QString id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    auto r = exec_write("INSERT INTO watchlist_columns (id, watchlist_id, name, expression, decimals, sort_order) "
+                        "VALUES (?, ?, ?, ?, ?, (SELECT COALESCE(MAX(sort_order) + 1, 0) FROM watchlist_columns "
+                        "WHERE watchlist_id = ?)) "
+                        "ON CONFLICT(watchlist_id, name) DO UPDATE SET "
+                        "expression = excluded.expression, decimals = excluded.decimals",
+                        {id, watchlist_id, name, expression, decimals, watchlist_id});
+    if (r.is_err())
+        return Result<WatchlistColumn>::err(r.error());
+
+    auto cols = get_columns(watchlist_id);
+    if (cols.is_err())
+        return Result<WatchlistColumn>::err(cols.error());
+    for (const auto& c : cols.value()) {
+        if (c.name == name)
+            return Result<WatchlistColumn>::ok(c);
+    }
+    return Result<WatchlistColumn>::err("Not found");
+}
+
+Result<void> WatchlistRepository::remove_column(const QString& watchlist_id, const QString& name) {
+    return exec_write("DELETE FROM watchlist_columns WHERE watchlist_id = ? AND name = ?", {watchlist_id, name});
+}
+
 } // namespace fincept
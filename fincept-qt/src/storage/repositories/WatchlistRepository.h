@@ -27,6 +27,17 @@ struct WatchlistStock {
     QString added_at;
 };
 
+/// A computed column (migration v060): `expression` is a WatchlistExpression
+/// evaluated per symbol; `decimals` is the display precision.
+struct WatchlistColumn {
+    QString id;
+    QString watchlist_id;
+    QString name;
+    QString expression;
+    int decimals = 2;
+    int sort_order = 0;
+};
+
 class WatchlistRepository : public BaseRepository<Watchlist> {
   public:
     static WatchlistRepository& instance();
@@ -44,10 +55,23 @@ class WatchlistRepository : public BaseRepository<Watchlist> {
     Result<void> remove_stock(const QString& watchlist_id, const QString& symbol);
     Result<QVector<WatchlistStock>> get_stocks(const QString& watchlist_id);
 
+    /// Puts `symbols` first, in the given order; symbols not listed keep
+    /// their relative order after them. Unknown symbols are ignored.
+    Result<void> reorder_stocks(const QString& watchlist_id, const QStringList& symbols);
+
+    // Computed columns
+    Result<QVector<WatchlistColumn>> get_columns(const QString& watchlist_id);
+    /// Inserts, or replaces the expression / decimals of the column with the
+    /// same name. New columns go last.
+    Result<WatchlistColumn> upsert_column(const QString& watchlist_id, const QString& name, const QString& expression,
+                                          int decimals = 2);
+    Result<void> remove_column(const QString& watchlist_id, const QString& name);
+
   private:
     WatchlistRepository() = default;
     static Watchlist map_watchlist(QSqlQuery& q);
     static WatchlistStock map_stock(QSqlQuery& q);
+    static WatchlistColumn map_column(QSqlQuery& q);
 };
 
 } // namespace fincept
@@ -0,0 +1,110 @@
+#include "storage/repositories/YieldCurveRepository.h"
+
+#include <QJsonArray>
+#include <QJsonDocument>
+
+namespace fincept {
+
+namespace {
+
+const char* kYcFitColumns = "source, model, date, beta0, beta1, beta2, beta3, tau1, tau2, rmse, observed";
+
+QString yc_observed_json(const QVector<QPair<double, double>>& points) {
+    QJsonArray a;
+    for (const auto& p : points)
+        a.append(QJsonArray{p.first, p.second});
+    return QString::fromUtf8(QJsonDocument(a).toJson(QJsonDocument::Compact));
+}
+
+YieldCurveSeries yc_map_series(QSqlQuery& q) {
+    YieldCurveSeries s;
+    s.source = q.value(0).toString();
+    s.model = q.value(1).toString();
+    s.first_date = q.value(2).toString();
+    s.last_date = q.value(3).toString();
+    s.fits = q.value(4).toInt();
+    s.last_fitted_at = q.value(5).toString();
+    return s;
+}
+
+} // namespace
+
+YieldCurveRepository& YieldCurveRepository::instance() {
+    static YieldCurveRepository s;
+    return s;
+}
+
+YieldCurveFit YieldCurveRepository::map_row(QSqlQuery& q) {
+    YieldCurveFit f;
+    f.source = q.value(0).toString();
+    f.model = q.value(1).toString();
+    f.date = q.value(2).toString();
+    f.beta0 = q.value(3).toDouble();
+    f.beta1 = q.value(4).toDouble();
+    f.beta2 = q.value(5).toDouble();
+    f.beta3 = q.value(6).toDouble();
+    f.tau1 = q.value(7).toDouble();
+    f.tau2 = q.value(8).toDouble();
+    f.rmse = q.value(9).toDouble();
+    for (const auto& v : QJsonDocument::fromJson(q.value(10).toString().toUtf8()).array()) {
+        const QJsonArray p = v.toArray();
+        if (p.size() == 2)
+            f.observed.append({p[0].toDouble(), p[1].toDouble()});
+    }
+    return f;
+}
+
+Result<QVector<YieldCurveSeries>> YieldCurveRepository::list_series() {
+    return query_list_as<YieldCurveSeries>(
+        "SELECT source, model, MIN(date), MAX(date), COUNT(*), MAX(fitted_at) FROM yield_curve_fits "
+        "GROUP BY source, model ORDER BY source, model",
+        {}, yc_map_series);
+}
+
+Result<QVector<YieldCurveFit>> YieldCurveRepository::get_fits(const QString& source, const QString& model,
+                                                              const QString& from_date, const QString& to_date) {
+    return query_list(QString("SELECT %1 FROM yield_curve_fits WHERE source = ? AND model = ? AND date >= ? "
+                              "AND date <= ? ORDER BY date ASC")
+                          .arg(kYcFitColumns),
+                      {source, model, from_date, to_date.isEmpty() ? QStringLiteral("9999-12-31") : to_date},
+                      &YieldCurveRepository::map_row);
+}
+
+std::optional<YieldCurveFit> YieldCurveRepository::last_fit(const QString& source, const QString& model,
+                                                            const QString& before_date) {
+    return query_optional(
+        QString("SELECT %1 FROM yield_curve_fits WHERE source = ? AND model = ? AND date < ? "
+                "ORDER BY date DESC LIMIT 1")
+            .arg(kYcFitColumns),
+        {source, model, before_date.isEmpty() ? QStringLiteral("9999-12-31") : before_date},
+        &YieldCurveRepository::map_row);
+}
+
+Result<void> YieldCurveRepository::upsert_fits(const QVector<YieldCurveFit>& fits) {
+    if (auto tx = db().begin_transaction(); tx.is_err())
+        return tx;
+    Result<void> r = Result<void>::ok();
+    for (int i = 0; r.is_ok() && i < fits.size(); ++i) {
+        const YieldCurveFit& f = fits[i];
+        r = exec_write(QString("INSERT OR REPLACE INTO yield_curve_fits (%1, fitted_at) "
+                               "VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, datetime('now'))")
+                           .arg(kYcFitColumns),
+                       {f.source, f.model, f.date, f.beta0, f.beta1, f.beta2, f.beta3, f.tau1, f.tau2, f.rmse,
+                        yc_observed_json(f.observed)});
+    }
+    if (r.is_err()) {
+        db().rollback();
+        return r;
+    }
+    if (auto c = db().commit(); c.is_err()) {
+        db().rollback();
+        return c;
+    }
+    return Result<void>::ok();
+}
+
+Result<void> YieldCurveRepository::remove_series(const QString& source, const QString& model) {
+    return exec_write("DELETE FROM yield_curve_fits WHERE source = ? AND model = ?", {source, model});
+}
+
+} // namespace fincept
@@ -0,0 +1,64 @@
+#pragma once
+// YieldCurveRepository — daily Nelson-Siegel / Svensson yield curve fits.
+//
+// Written by YieldCurveService after each fit run; read by the yield curve
+// MCP tools for factor series and animation frames. Schema in
+// v063_yield_curve_fits.
+
+#include "storage/repositories/BaseRepository.h"
+
+#include <QPair>
+#include <QString>
+#include <QVector>
+
+namespace fincept {
+
+/// One fitted curve. Nelson-Siegel fits leave beta3 and tau2 at 0.
+struct YieldCurveFit {
+    QString source; // treasury | ecb
+    QString model;  // nelson_siegel | svensson
+    QString date;   // yyyy-MM-dd
+    double beta0 = 0;
+    double beta1 = 0;
+    double beta2 = 0;
+    double beta3 = 0;
+    double tau1 = 0;
+    double tau2 = 0;
+    double rmse = 0;
+    QVector<QPair<double, double>> observed; // (maturity years, yield decimal)
+};
+
+/// A stored (source, model) series.
+struct YieldCurveSeries {
+    QString source;
+    QString model;
+    QString first_date;
+    QString last_date;
+    int fits = 0;
+    QString last_fitted_at; // datetime('now') of the newest write
+};
+
+class YieldCurveRepository : public BaseRepository<YieldCurveFit> {
+  public:
+    static YieldCurveRepository& instance();
+
+    Result<QVector<YieldCurveSeries>> list_series();
+
+    /// Fits dated within [from_date, to_date] (empty = open), ascending.
+    Result<QVector<YieldCurveFit>> get_fits(const QString& source, const QString& model, const QString& from_date = {},
+                                            const QString& to_date = {});
+
+    /// Latest fit strictly before `date` (empty = latest overall).
+    std::optional<YieldCurveFit> last_fit(const QString& source, const QString& model,
+                                          const QString& before_date = {});
+
+    /// Inserts or replaces `fits` by (source, model, date), in one transaction.
+    Result<void> upsert_fits(const QVector<YieldCurveFit>& fits);
+    Result<void> remove_series(const QString& source, const QString& model);
+
+  private:
+    YieldCurveRepository() = default;
+    static YieldCurveFit map_row(QSqlQuery& q);
+};
+
+} // namespace fincept
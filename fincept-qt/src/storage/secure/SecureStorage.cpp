@@ -11,6 +11,7 @@
 #include <QSqlError>
 #include <QSqlQuery>
 #include <QSysInfo>
+#include <QTimeZone>
 
 #include <openssl/err.h>
 #include <openssl/evp.h>
@@ -66,12 +67,16 @@ const QByteArray& master_key() {
     return cached;
 }
 
-QByteArray random_iv() {
-    QByteArray iv(kIvLen, '\0');
+QByteArray random_bytes(int n) {
+    QByteArray out(n, '\0');
     QRandomGenerator* rng = QRandomGenerator::system();
-    for (int i = 0; i < kIvLen; ++i)
-        iv[i] = static_cast<char>(rng->bounded(256));
-    return iv;
+    for (int i = 0; i < n; ++i)
+        out[i] = static_cast<char>(rng->bounded(256));
+    return out;
+}
+
+QByteArray random_iv() {
+    return random_bytes(kIvLen);
 }
 
 // AES-256-GCM encrypt. Returns ciphertext + the GCM tag through `tag_out`.
@@ -172,6 +177,20 @@ QByteArray aes_gcm_decrypt(const QByteArray& key, const QByteArray& iv, const QB
     return out;
 }
 
+// seal() blob layout: [version:1][salt:16][iv:12][tag:16][ciphertext].
+constexpr char kSealVersion = 1;
+constexpr int kSaltLen = 16;
+
+QByteArray passphrase_key(const QString& passphrase, const QByteArray& salt) {
+    const QByteArray pass = passphrase.toUtf8();
+    QByteArray key(kKeyLen, '\0');
+    if (1 != PKCS5_PBKDF2_HMAC(pass.constData(), pass.size(), reinterpret_cast<const unsigned char*>(salt.constData()),
+                               salt.size(), SecureStorage::kSealIterations, EVP_sha256(), kKeyLen,
+                               reinterpret_cast<unsigned char*>(key.data())))
+        return {};
+    return key;
+}
+
 } // anonymous namespace
 
 SecureStorage& SecureStorage::instance() {
@@ -267,4 +286,86 @@ Result<void> SecureStorage::remove(const QString& key) {
     return Result<void>::ok();
 }
 
+Result<void> SecureStorage::rotate(const QString& key, const QString& new_value) {
+    auto existing = retrieve(key);
+    if (existing.is_err())
+        return Result<void>::err(existing.error());
+    if (existing.value() == new_value)
+        return Result<void>::err("New value is the same as the current one");
+    auto r = store(key, new_value);
+    if (r.is_ok())
+        LOG_INFO(TAG, QString("Rotated credential %1").arg(key));
+    return r;
+}
+
+Result<QVector<SecureStorage::Entry>> SecureStorage::list(const QString& prefix) {
+    auto& db = Database::instance();
+    if (!db.is_open())
+        return Result<QVector<Entry>>::err("Database not open");
+
+    QSqlQuery q(db.connection()); // per-thread connection (see retrieve) — thread-safe
+    if (prefix.isEmpty()) {
+        q.prepare("SELECT key, updated_at FROM secure_credentials ORDER BY key");
+    } else {
+        // substr() rather than LIKE: keys contain '_' and '.', which LIKE
+        // would need escaping for.
+        q.prepare("SELECT key, updated_at FROM secure_credentials WHERE substr(key, 1, ?) = ? ORDER BY key");
+        q.addBindValue(prefix.size());
+        q.addBindValue(prefix);
+    }
+    if (!q.exec()) {
+        LOG_ERROR(TAG, QString("SQL list failed: %1").arg(q.lastError().text()));
+        return Result<QVector<Entry>>::err("Read failed");
+    }
+    QVector<Entry> out;
+    while (q.next()) {
+        QDateTime at = QDateTime::fromString(q.value(1).toString(), "yyyy-MM-dd HH:mm:ss");
+        at.setTimeZone(QTimeZone::UTC);
+        out.append({q.value(0).toString(), at});
+    }
+    return Result<QVector<Entry>>::ok(out);
+}
+
+Result<QString> SecureStorage::adopt(const QString& secure_key, const QString& plaintext) {
+    if (plaintext.isEmpty()) {
+        auto existing = retrieve(secure_key);
+        return existing.is_ok() ? existing : Result<QString>::ok(QString{});
+    }
+    auto r = store(secure_key, plaintext);
+    if (r.is_err())
+        return Result<QString>::err(r.error());
+    LOG_INFO(TAG, QString("Moved plaintext credential into secure storage: %1").arg(secure_key));
+    return Result<QString>::ok(plaintext);
+}
+
+Result<QByteArray> SecureStorage::seal(const QByteArray& plaintext, const QString& passphrase) {
+    if (passphrase.isEmpty())
+        return Result<QByteArray>::err("Empty passphrase");
+    const QByteArray salt = random_bytes(kSaltLen);
+    const QByteArray key = passphrase_key(passphrase, salt);
+    if (key.isEmpty())
+        return Result<QByteArray>::err("Key derivation failed");
+    const QByteArray iv = random_iv();
+    QByteArray tag;
+    const QByteArray ciphertext = aes_gcm_encrypt(key, iv, plaintext, tag);
+    if (tag.size() != kTagLen)
+        return Result<QByteArray>::err("Encryption failed");
+    return Result<QByteArray>::ok(QByteArray(1, kSealVersion) + salt + iv + tag + ciphertext);
+}
+
+Result<QByteArray> SecureStorage::unseal(const QByteArray& blob, const QString& passphrase) {
+    constexpr int header = 1 + kSaltLen + kIvLen + kTagLen;
+    if (blob.size() < header || blob.at(0) != kSealVersion)
+        return Result<QByteArray>::err("Unrecognised sealed data");
+    const QByteArray key = passphrase_key(passphrase, blob.mid(1, kSaltLen));
+    if (key.isEmpty())
+        return Result<QByteArray>::err("Key derivation failed");
+    bool ok = false;
+    const QByteArray plaintext = aes_gcm_decrypt(key, blob.mid(1 + kSaltLen, kIvLen), blob.mid(header),
+                                                 blob.mid(1 + kSaltLen + kIvLen, kTagLen), ok);
+    if (!ok)
+        return Result<QByteArray>::err("Wrong passphrase or corrupted data");
+    return Result<QByteArray>::ok(plaintext);
+}
+
 } // namespace fincept
@@ -1,7 +1,10 @@
 #pragma once
 #include "core/result/Result.h"
 
+#include <QByteArray>
+#include <QDateTime>
 #include <QString>
+#include <QVector>
 
 namespace fincept {
 
@@ -32,12 +35,42 @@ namespace fincept {
 /// (PIN hash, API tokens, session IDs).
 class SecureStorage {
   public:
+    /// A stored credential's metadata — never its value.
+    struct Entry {
+        QString key;
+        QDateTime updated_at; // UTC; last store() or rotate()
+    };
+
     static SecureStorage& instance();
 
     Result<void> store(const QString& key, const QString& value);
     Result<QString> retrieve(const QString& key);
     Result<void> remove(const QString& key);
 
+    /// Replaces an existing credential. Unlike store(), fails with
+    /// "Not found" when there is nothing to rotate, so a typo in the key
+    /// cannot silently create a second credential.
+    Result<void> rotate(const QString& key, const QString& new_value);
+
+    /// Keys (optionally under `prefix`) with their last-update time, sorted.
+    Result<QVector<Entry>> list(const QString& prefix = {});
+
+    /// Moves a plaintext value (e.g. a settings row from before the value was
+    /// kept here) into the store. A non-empty `plaintext` is stored — callers
+    /// delete their copy once this returns ok, so any copy still present is
+    /// newer than the stored one. Returns the value to use.
+    Result<QString> adopt(const QString& secure_key, const QString& plaintext);
+
+    /// Passphrase encryption for secrets leaving this machine (the config
+    /// bundle) — the machine-derived key above would not decrypt elsewhere.
+    /// AES-256-GCM under a PBKDF2-HMAC-SHA256 key (kSealIterations rounds,
+    /// random 128-bit salt). The blob is self-contained: version byte, salt,
+    /// IV, tag, ciphertext. unseal() fails on a wrong passphrase or any
+    /// tampering (GCM tag check).
+    static Result<QByteArray> seal(const QByteArray& plaintext, const QString& passphrase);
+    static Result<QByteArray> unseal(const QByteArray& blob, const QString& passphrase);
+    static constexpr int kSealIterations = 210000; // OWASP 2023 guidance for PBKDF2-HMAC-SHA256
+
   private:
     SecureStorage() = default;
 };
@@ -97,5 +97,33 @@ void register_migration_v047();
 void register_migration_v048();
 void register_migration_v049();
 void register_migration_v050();
+void register_migration_v051();
+void register_migration_v052();
+void register_migration_v053();
+void register_migration_v054();
+void register_migration_v055();
+void register_migration_v056();
+void register_migration_v057();
+void register_migration_v058();
+void register_migration_v059();
+void register_migration_v060();
+void register_migration_v061();
+void register_migration_v062();
+void register_migration_v063();
+void register_migration_v064();
+void register_migration_v065();
+void register_migration_v066();
+void register_migration_v067();
+void register_migration_v068();
+void register_migration_v069();
+void register_migration_v070();
+void register_migration_v071();
+void register_migration_v072();
+void register_migration_v073();
+void register_migration_v074();
+void register_migration_v075();
+void register_migration_v076();
+void register_migration_v077();
+void register_migration_v078();
 
 } // namespace fincept
@@ -0,0 +1,43 @@
+// v051_dividend_events — per-symbol dividend calendar shared by every portfolio.
+// Rows are keyed on (symbol, ex_date): history synced from yfinance lands with
+// status 'paid', a declared-but-unpaid dividend with status 'announced'.
+// `alerted_at` records when the ex-date reminder fired so it fires once.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v051(QSqlDatabase& db) {
+    QSqlQuery q(db);
+    if (!q.exec("CREATE TABLE IF NOT EXISTS dividend_events ("
+                "  symbol     TEXT NOT NULL,"
+                "  ex_date    TEXT NOT NULL," // YYYY-MM-DD
+                "  pay_date   TEXT,"          // NULL when not yet declared
+                "  amount     REAL NOT NULL DEFAULT 0," // per share, listing currency
+                "  currency   TEXT,"
+                "  status     TEXT NOT NULL DEFAULT 'paid'," // paid | announced
+                "  alerted_at TEXT,"
+                "  synced_at  TEXT DEFAULT (datetime('now')),"
+                "  PRIMARY KEY (symbol, ex_date)"
+                ")"))
+        return Result<void>::err(q.lastError().text().toStdString());
+    if (!q.exec("CREATE INDEX IF NOT EXISTS idx_dividend_events_ex_date ON dividend_events(ex_date)"))
+        return Result<void>::err(q.lastError().text().toStdString());
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v051() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({51, "dividend_events", apply_v051});
+}
+
+} // namespace fincept
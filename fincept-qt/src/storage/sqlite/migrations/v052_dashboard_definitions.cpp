@@ -0,0 +1,53 @@
+// v052_dashboard_definitions — named dashboards with data bindings.
+// Unlike dashboard_layouts (pixel grid of the Dashboard tab), a definition
+// records which DataHub topics each widget is bound to and how often they
+// refresh, so DashboardRunner can prefetch and refresh them without the UI.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v052(QSqlDatabase& db) {
+    QSqlQuery q(db);
+    if (!q.exec("CREATE TABLE IF NOT EXISTS dashboard_definitions ("
+                "  id          TEXT PRIMARY KEY,"
+                "  name        TEXT NOT NULL,"
+                "  description TEXT,"
+                "  created_at  TEXT DEFAULT (datetime('now')),"
+                "  updated_at  TEXT DEFAULT (datetime('now'))"
+                ")"))
+        return Result<void>::err(q.lastError().text().toStdString());
+    if (!q.exec("CREATE TABLE IF NOT EXISTS dashboard_definition_widgets ("
+                "  dashboard_id  TEXT NOT NULL REFERENCES dashboard_definitions(id) ON DELETE CASCADE,"
+                "  widget_id     TEXT NOT NULL,"
+                "  widget_type   TEXT NOT NULL,"
+                "  title         TEXT,"
+                "  bindings_json TEXT NOT NULL DEFAULT '[]'," // JSON array of DataHub topics
+                "  refresh_ms    INTEGER NOT NULL DEFAULT 0,"  // 0 = topic policy only
+                "  grid_x        INTEGER NOT NULL DEFAULT 0,"
+                "  grid_y        INTEGER NOT NULL DEFAULT 0,"
+                "  grid_w        INTEGER NOT NULL DEFAULT 4,"
+                "  grid_h        INTEGER NOT NULL DEFAULT 4,"
+                "  config_json   TEXT,"
+                "  sort_order    INTEGER NOT NULL DEFAULT 0,"
+                "  PRIMARY KEY (dashboard_id, widget_id)"
+                ")"))
+        return Result<void>::err(q.lastError().text().toStdString());
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v052() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({52, "dashboard_definitions", apply_v052});
+}
+
+} // namespace fincept
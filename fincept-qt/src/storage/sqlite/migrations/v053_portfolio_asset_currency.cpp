@@ -0,0 +1,52 @@
+// v053_portfolio_asset_currency — native currency per portfolio asset.
+//
+//   - portfolio_assets.currency     — ISO code of the quote currency. '' means
+//     "infer from the symbol" (PortfolioService::listing_currency), which is
+//     what every pre-v053 row gets.
+//   - portfolio_assets.cost_fx_rate — portfolio-currency units per native
+//     unit at purchase, cost-weighted across buys. 0 = unknown; the summary
+//     backfills it from the first purchase date on first load.
+//
+// Together they let the summary value holdings in the portfolio currency
+// and split unrealized P&L into local-return and currency-return parts.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+bool v053_column_exists(QSqlDatabase& db, const QString& table, const QString& column) {
+    QSqlQuery q(db);
+    q.exec(QString("PRAGMA table_info(%1)").arg(table));
+    while (q.next()) {
+        if (q.value(1).toString().compare(column, Qt::CaseInsensitive) == 0)
+            return true;
+    }
+    return false;
+}
+
+Result<void> apply_v053(QSqlDatabase& db) {
+    QSqlQuery q(db);
+    if (!v053_column_exists(db, "portfolio_assets", "currency") &&
+        !q.exec("ALTER TABLE portfolio_assets ADD COLUMN currency TEXT DEFAULT ''"))
+        return Result<void>::err(q.lastError().text().toStdString());
+    if (!v053_column_exists(db, "portfolio_assets", "cost_fx_rate") &&
+        !q.exec("ALTER TABLE portfolio_assets ADD COLUMN cost_fx_rate REAL DEFAULT 0"))
+        return Result<void>::err(q.lastError().text().toStdString());
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v053() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({53, "portfolio_asset_currency", apply_v053});
+}
+
+} // namespace fincept
@@ -0,0 +1,51 @@
+// v054_portfolio_benchmarks — benchmark-relative performance tracking.
+//
+//   - portfolios.benchmark_symbol — index/ETF the portfolio is measured
+//     against. '' = PortfolioService::default_benchmark_for_currency.
+//   - benchmark_prices            — daily closes per benchmark, shared by
+//     every portfolio using it and synced incrementally.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+bool v054_column_exists(QSqlDatabase& db, const QString& table, const QString& column) {
+    QSqlQuery q(db);
+    q.exec(QString("PRAGMA table_info(%1)").arg(table));
+    while (q.next()) {
+        if (q.value(1).toString().compare(column, Qt::CaseInsensitive) == 0)
+            return true;
+    }
+    return false;
+}
+
+Result<void> apply_v054(QSqlDatabase& db) {
+    QSqlQuery q(db);
+    if (!v054_column_exists(db, "portfolios", "benchmark_symbol") &&
+        !q.exec("ALTER TABLE portfolios ADD COLUMN benchmark_symbol TEXT DEFAULT ''"))
+        return Result<void>::err(q.lastError().text().toStdString());
+    if (!q.exec("CREATE TABLE IF NOT EXISTS benchmark_prices ("
+                "  symbol     TEXT NOT NULL,"
+                "  price_date TEXT NOT NULL," // YYYY-MM-DD
+                "  close      REAL NOT NULL,"
+                "  PRIMARY KEY (symbol, price_date)"
+                ") WITHOUT ROWID"))
+        return Result<void>::err(q.lastError().text().toStdString());
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v054() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({54, "portfolio_benchmarks", apply_v054});
+}
+
+} // namespace fincept
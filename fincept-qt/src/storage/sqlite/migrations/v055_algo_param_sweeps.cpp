@@ -0,0 +1,73 @@
+// v055_algo_param_sweeps — persisted algo parameter sweeps.
+//
+//   - algo_param_sweeps        — one row per sweep: strategy, symbol, range,
+//     grid/config JSON, how far it got and the best combination.
+//   - algo_param_sweep_results — one row per backtested combination (the
+//     Sharpe / return / drawdown matrix the heat map is drawn from).
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v055(QSqlDatabase& db) {
+    QSqlQuery q(db);
+    if (!q.exec("CREATE TABLE IF NOT EXISTS algo_param_sweeps ("
+                "  id              TEXT PRIMARY KEY,"
+                "  strategy_id     TEXT DEFAULT '',"
+                "  strategy_name   TEXT DEFAULT '',"
+                "  symbol          TEXT NOT NULL,"
+                "  timeframe       TEXT DEFAULT '1d',"
+                "  start_date      TEXT DEFAULT '',"
+                "  end_date        TEXT DEFAULT '',"
+                "  objective       TEXT NOT NULL,"
+                "  config_json     TEXT DEFAULT '{}',"
+                "  combinations    INTEGER DEFAULT 0,"
+                "  evaluated       INTEGER DEFAULT 0,"
+                "  stop_reason     TEXT DEFAULT '',"
+                "  best_params     TEXT DEFAULT '{}',"
+                "  best_objective  REAL,"
+                "  elapsed_ms      INTEGER DEFAULT 0,"
+                "  created_at      TEXT DEFAULT CURRENT_TIMESTAMP"
+                ")"))
+        return Result<void>::err(q.lastError().text().toStdString());
+    if (!q.exec("CREATE INDEX IF NOT EXISTS idx_algo_param_sweeps_strategy "
+                "ON algo_param_sweeps(strategy_id, created_at)"))
+        return Result<void>::err(q.lastError().text().toStdString());
+    if (!q.exec("CREATE TABLE IF NOT EXISTS algo_param_sweep_results ("
+                "  sweep_id       TEXT NOT NULL REFERENCES algo_param_sweeps(id) ON DELETE CASCADE,"
+                "  combo_index    INTEGER NOT NULL,"
+                "  rank           INTEGER NOT NULL,"
+                "  params_json    TEXT NOT NULL,"
+                "  objective      REAL,"
+                "  total_return   REAL,"
+                "  sharpe_ratio   REAL,"
+                "  sortino        REAL,"
+                "  calmar         REAL,"
+                "  max_drawdown   REAL,"
+                "  total_trades   INTEGER,"
+                "  win_rate       REAL,"
+                "  profit_factor  REAL,"
+                "  final_value    REAL,"
+                "  qualified      INTEGER DEFAULT 1,"
+                "  error          TEXT DEFAULT '',"
+                "  PRIMARY KEY (sweep_id, combo_index)"
+                ") WITHOUT ROWID"))
+        return Result<void>::err(q.lastError().text().toStdString());
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v055() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({55, "algo_param_sweeps", apply_v055});
+}
+
+} // namespace fincept
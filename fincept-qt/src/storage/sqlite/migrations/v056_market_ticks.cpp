@@ -0,0 +1,47 @@
+// v056_market_ticks — stored trades / quotes for tick-level backtests.
+//
+// Backs the tick half of `fincept::storage::HistoricalDataStore` (store_ticks /
+// get_ticks), next to the OHLCV market_data table from v033. One row per tick:
+// last trade price + size and, when the source has them, the top-of-book
+// quote. `seq` orders ticks that share a millisecond.
+//
+// timestamp_ms is epoch MILLISECONDS, as in market_data.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v056(QSqlDatabase& db) {
+    QSqlQuery q(db);
+    if (!q.exec("CREATE TABLE IF NOT EXISTS market_ticks ("
+                "  symbol TEXT NOT NULL,"
+                "  exchange TEXT NOT NULL,"
+                "  timestamp_ms INTEGER NOT NULL,"
+                "  seq INTEGER NOT NULL DEFAULT 0,"
+                "  price REAL NOT NULL DEFAULT 0,"
+                "  size REAL NOT NULL DEFAULT 0,"
+                "  bid REAL NOT NULL DEFAULT 0,"
+                "  ask REAL NOT NULL DEFAULT 0,"
+                "  bid_size REAL NOT NULL DEFAULT 0,"
+                "  ask_size REAL NOT NULL DEFAULT 0,"
+                "  PRIMARY KEY (symbol, exchange, timestamp_ms, seq)"
+                ") WITHOUT ROWID"))
+        return Result<void>::err(q.lastError().text().toStdString());
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v056() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({56, "market_ticks", apply_v056});
+}
+
+} // namespace fincept
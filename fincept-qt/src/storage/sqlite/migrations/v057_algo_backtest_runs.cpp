@@ -0,0 +1,69 @@
+// v057_algo_backtest_runs — persisted algo backtest runs.
+//
+//   - algo_backtest_runs — one row per successful bar or tick backtest: the
+//     strategy and its rule version, a hash of the full configuration, the
+//     headline metrics as columns (for listing / sorting), and the complete
+//     metrics, trade list and equity curve as JSON (for diff / comparison).
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v057(QSqlDatabase& db) {
+    QSqlQuery q(db);
+    if (!q.exec("CREATE TABLE IF NOT EXISTS algo_backtest_runs ("
+                "  id                 TEXT PRIMARY KEY,"
+                "  kind               TEXT DEFAULT 'bar',"
+                "  strategy_id        TEXT DEFAULT '',"
+                "  strategy_name      TEXT DEFAULT '',"
+                "  strategy_version   INTEGER DEFAULT 1,"
+                "  strategy_hash      TEXT NOT NULL,"
+                "  config_hash        TEXT NOT NULL,"
+                "  symbol             TEXT NOT NULL,"
+                "  exchange           TEXT DEFAULT '',"
+                "  timeframe          TEXT DEFAULT '1d',"
+                "  start_date         TEXT DEFAULT '',"
+                "  end_date           TEXT DEFAULT '',"
+                "  initial_capital    REAL DEFAULT 0,"
+                "  total_return       REAL,"
+                "  sharpe_ratio       REAL,"
+                "  sortino            REAL,"
+                "  calmar             REAL,"
+                "  max_drawdown       REAL,"
+                "  total_trades       INTEGER,"
+                "  win_rate           REAL,"
+                "  profit_factor      REAL,"
+                "  final_value        REAL,"
+                "  label              TEXT DEFAULT '',"
+                "  notes              TEXT DEFAULT '',"
+                "  config_json        TEXT DEFAULT '{}',"
+                "  metrics_json       TEXT DEFAULT '{}',"
+                "  trades_json        TEXT DEFAULT '[]',"
+                "  equity_curve_json  TEXT DEFAULT '[]',"
+                "  created_at         TEXT DEFAULT CURRENT_TIMESTAMP"
+                ")"))
+        return Result<void>::err(q.lastError().text().toStdString());
+    if (!q.exec("CREATE INDEX IF NOT EXISTS idx_algo_backtest_runs_strategy "
+                "ON algo_backtest_runs(strategy_id, created_at)"))
+        return Result<void>::err(q.lastError().text().toStdString());
+    if (!q.exec("CREATE INDEX IF NOT EXISTS idx_algo_backtest_runs_config "
+                "ON algo_backtest_runs(config_hash)"))
+        return Result<void>::err(q.lastError().text().toStdString());
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v057() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({57, "algo_backtest_runs", apply_v057});
+}
+
+} // namespace fincept
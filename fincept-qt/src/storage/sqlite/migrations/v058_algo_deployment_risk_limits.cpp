@@ -0,0 +1,51 @@
+// v058_algo_deployment_risk_limits — per-deployment risk guards on
+// algo_deployments beside the existing max_order_value / max_daily_loss:
+//
+//   - max_position_value    — cap on the open position's notional (mark × qty).
+//   - max_orders_per_minute — entry order rate limit.
+//
+// Both default to 0 (no limit), so existing deployments are unaffected.
+// Idempotent on re-run (ignores the duplicate-column error, matching v047).
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> v058_add_column(QSqlDatabase& db, const QString& sql) {
+    QSqlQuery q(db);
+    if (!q.exec(sql)) {
+        const QString err = q.lastError().text();
+        if (!err.contains("duplicate column", Qt::CaseInsensitive))
+            return Result<void>::err(err.toStdString());
+    }
+    return Result<void>::ok();
+}
+
+Result<void> apply_v058(QSqlDatabase& db) {
+    const char* stmts[] = {
+        "ALTER TABLE algo_deployments ADD COLUMN max_position_value REAL DEFAULT 0",
+        "ALTER TABLE algo_deployments ADD COLUMN max_orders_per_minute INTEGER DEFAULT 0",
+    };
+    for (const char* s : stmts) {
+        auto r = v058_add_column(db, QString::fromUtf8(s));
+        if (r.is_err())
+            return r;
+    }
+    return Result<void>::ok();
+}
+
+} // anonymous namespace
+
+void register_migration_v058() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({58, "algo_deployment_risk_limits", apply_v058});
+}
+
+} // namespace fincept
@@ -0,0 +1,57 @@
+// v059_audit_trail — append-only audit log of order, auth, deployment and
+// destructive actions (AuditTrail).
+//
+//   - audit_trail — one row per action. Each row carries the SHA-256 of the
+//     previous row (`prev_hash`) and of itself (`hash`), so an edited,
+//     reordered or deleted row breaks the chain that AuditTrail::verify()
+//     walks.
+//   - Triggers reject UPDATE and DELETE outright; the hash chain catches
+//     anything done to the file outside SQLite's triggers.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v059(QSqlDatabase& db) {
+    const char* stmts[] = {
+        "CREATE TABLE IF NOT EXISTS audit_trail ("
+        "  id          INTEGER PRIMARY KEY AUTOINCREMENT,"
+        "  ts          INTEGER NOT NULL,"
+        "  session_id  TEXT NOT NULL,"
+        "  category    TEXT NOT NULL,"
+        "  action      TEXT NOT NULL,"
+        "  subject     TEXT DEFAULT '',"
+        "  detail      TEXT DEFAULT '{}',"
+        "  prev_hash   TEXT NOT NULL,"
+        "  hash        TEXT NOT NULL"
+        ")",
+        "CREATE INDEX IF NOT EXISTS idx_audit_trail_ts ON audit_trail(ts)",
+        "CREATE INDEX IF NOT EXISTS idx_audit_trail_category ON audit_trail(category, ts)",
+        "CREATE TRIGGER IF NOT EXISTS audit_trail_no_update BEFORE UPDATE ON audit_trail "
+        "BEGIN SELECT RAISE(ABORT, 'audit_trail is append-only'); END",
+        "CREATE TRIGGER IF NOT EXISTS audit_trail_no_delete BEFORE DELETE ON audit_trail "
+        "BEGIN SELECT RAISE(ABORT, 'audit_trail is append-only'); END",
+    };
+    QSqlQuery q(db);
+    for (const char* s : stmts) {
+        if (!q.exec(QString::fromUtf8(s)))
+            return Result<void>::err(q.lastError().text().toStdString());
+    }
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v059() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({59, "audit_trail", apply_v059});
+}
+
+} // namespace fincept
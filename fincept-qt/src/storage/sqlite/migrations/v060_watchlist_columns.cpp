@@ -0,0 +1,53 @@
+// v060_watchlist_columns — computed watchlist columns.
+//
+//   - watchlist_columns — one row per user-defined column: a display name and
+//     a column expression (WatchlistExpression) evaluated per symbol against
+//     daily history. Rows go with their watchlist (ON DELETE CASCADE).
+//   - add_stock() appends at the end of the list from now on, so existing
+//     rows get sort_order = their current alphabetical position once here.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v060(QSqlDatabase& db) {
+    const char* stmts[] = {
+        "CREATE TABLE IF NOT EXISTS watchlist_columns ("
+        "  id            TEXT PRIMARY KEY,"
+        "  watchlist_id  TEXT NOT NULL REFERENCES watchlists(id) ON DELETE CASCADE,"
+        "  name          TEXT NOT NULL,"
+        "  expression    TEXT NOT NULL,"
+        "  decimals      INTEGER DEFAULT 2,"
+        "  sort_order    INTEGER DEFAULT 0,"
+        "  created_at    TEXT DEFAULT (datetime('now')),"
+        "  UNIQUE(watchlist_id, name)"
+        ")",
+        "CREATE INDEX IF NOT EXISTS idx_wl_columns_wl ON watchlist_columns(watchlist_id)",
+        "UPDATE watchlist_stocks SET sort_order = ("
+        "  SELECT COUNT(*) FROM watchlist_stocks s2"
+        "  WHERE s2.watchlist_id = watchlist_stocks.watchlist_id AND s2.symbol < watchlist_stocks.symbol"
+        ") WHERE sort_order = 0",
+    };
+    QSqlQuery q(db);
+    for (const char* s : stmts) {
+        if (!q.exec(QString::fromUtf8(s)))
+            return Result<void>::err(q.lastError().text().toStdString());
+    }
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v060() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({60, "watchlist_columns", apply_v060});
+}
+
+} // namespace fincept
@@ -0,0 +1,67 @@
+// v061_market_breadth — market breadth universes and their daily series.
+//
+//   - breadth_universes — a named symbol list plus the Historify exchange key
+//     its daily candles are stored under (market_data, v033).
+//   - breadth_daily — one row per universe and session: advance/decline
+//     counts and volume, 52-week highs/lows, members above their 50/200-day
+//     averages, and the running A/D line and McClellan EMAs that let an
+//     update continue the series from the last stored row.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v061(QSqlDatabase& db) {
+    const char* stmts[] = {
+        "CREATE TABLE IF NOT EXISTS breadth_universes ("
+        "  name          TEXT PRIMARY KEY,"
+        "  exchange      TEXT NOT NULL DEFAULT 'YF',"
+        "  symbols       TEXT NOT NULL DEFAULT '[]',"
+        "  last_update   TEXT,"
+        "  created_at    TEXT DEFAULT (datetime('now'))"
+        ")",
+        "CREATE TABLE IF NOT EXISTS breadth_daily ("
+        "  universe      TEXT NOT NULL REFERENCES breadth_universes(name) ON DELETE CASCADE,"
+        "  date          TEXT NOT NULL,"
+        "  members       INTEGER NOT NULL DEFAULT 0,"
+        "  advances      INTEGER NOT NULL DEFAULT 0,"
+        "  declines      INTEGER NOT NULL DEFAULT 0,"
+        "  unchanged     INTEGER NOT NULL DEFAULT 0,"
+        "  up_volume     REAL NOT NULL DEFAULT 0,"
+        "  down_volume   REAL NOT NULL DEFAULT 0,"
+        "  new_highs     INTEGER NOT NULL DEFAULT 0,"
+        "  new_lows      INTEGER NOT NULL DEFAULT 0,"
+        "  above_50      INTEGER NOT NULL DEFAULT 0,"
+        "  eligible_50   INTEGER NOT NULL DEFAULT 0,"
+        "  above_200     INTEGER NOT NULL DEFAULT 0,"
+        "  eligible_200  INTEGER NOT NULL DEFAULT 0,"
+        "  ad_line       REAL NOT NULL DEFAULT 0,"
+        "  ema19         REAL NOT NULL DEFAULT 0,"
+        "  ema39         REAL NOT NULL DEFAULT 0,"
+        "  summation     REAL NOT NULL DEFAULT 0,"
+        "  PRIMARY KEY (universe, date)"
+        ")",
+    };
+    QSqlQuery q(db);
+    for (const char* s : stmts) {
+        if (!q.exec(QString::fromUtf8(s)))
+            return Result<void>::err(q.lastError().text().toStdString());
+    }
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v061() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({61, "market_breadth", apply_v061});
+}
+
+} // namespace fincept
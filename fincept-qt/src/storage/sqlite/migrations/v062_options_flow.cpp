@@ -0,0 +1,77 @@
+// v062_options_flow — unusual options activity scanner.
+//
+//   - options_flow_watches — underlyings polled for chain snapshots, with the
+//     chain source (cboe | polygon | tradier), poll interval and the
+//     detection thresholds as JSON.
+//   - options_flow_events — every flagged anomaly: volume far above open
+//     interest, a burst of volume between two polls (sweep), or a move in
+//     put/call skew. `detail` holds the kind-specific JSON.
+//   - options_flow_alerts — filters over new events that notify the user.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v062(QSqlDatabase& db) {
+    const char* stmts[] = {
+        "CREATE TABLE IF NOT EXISTS options_flow_watches ("
+        "  symbol        TEXT PRIMARY KEY,"
+        "  source        TEXT NOT NULL DEFAULT 'cboe',"
+        "  interval_sec  INTEGER NOT NULL DEFAULT 300,"
+        "  thresholds    TEXT NOT NULL DEFAULT '{}',"
+        "  active        INTEGER NOT NULL DEFAULT 1,"
+        "  last_scan_at  INTEGER NOT NULL DEFAULT 0,"
+        "  created_at    TEXT DEFAULT (datetime('now'))"
+        ")",
+        "CREATE TABLE IF NOT EXISTS options_flow_events ("
+        "  id            TEXT PRIMARY KEY,"
+        "  symbol        TEXT NOT NULL,"
+        "  source        TEXT NOT NULL,"
+        "  kind          TEXT NOT NULL,"
+        "  contract      TEXT,"
+        "  expiry        TEXT,"
+        "  option_type   TEXT,"
+        "  strike        REAL,"
+        "  volume        REAL,"
+        "  open_interest REAL,"
+        "  premium       REAL,"
+        "  score         REAL,"
+        "  detail        TEXT NOT NULL DEFAULT '{}',"
+        "  detected_at   INTEGER NOT NULL"
+        ")",
+        "CREATE INDEX IF NOT EXISTS idx_options_flow_events_time ON options_flow_events(detected_at)",
+        "CREATE INDEX IF NOT EXISTS idx_options_flow_events_symbol ON options_flow_events(symbol, detected_at)",
+        "CREATE TABLE IF NOT EXISTS options_flow_alerts ("
+        "  id            TEXT PRIMARY KEY,"
+        "  name          TEXT NOT NULL,"
+        "  filter        TEXT NOT NULL DEFAULT '{}',"
+        "  toast         INTEGER NOT NULL DEFAULT 1,"
+        "  providers     INTEGER NOT NULL DEFAULT 0,"
+        "  active        INTEGER NOT NULL DEFAULT 1,"
+        "  last_fired_at INTEGER NOT NULL DEFAULT 0,"
+        "  created_at    TEXT DEFAULT (datetime('now'))"
+        ")",
+    };
+    QSqlQuery q(db);
+    for (const char* s : stmts) {
+        if (!q.exec(QString::fromUtf8(s)))
+            return Result<void>::err(q.lastError().text().toStdString());
+    }
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v062() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({62, "options_flow", apply_v062});
+}
+
+} // namespace fincept
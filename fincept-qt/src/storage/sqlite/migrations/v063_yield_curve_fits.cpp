@@ -0,0 +1,53 @@
+// v063_yield_curve_fits — daily Nelson-Siegel / Svensson yield curve fits.
+//
+//   - yield_curve_fits — one row per (source, model, date): the fitted betas
+//     and taus (beta3/tau2 are 0 for Nelson-Siegel), the fit RMSE and the
+//     observed curve it was fitted to, as [[maturity_years, yield], ...].
+//     Level/slope/curvature factor series and animation frames are derived
+//     from these rows on read.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v063(QSqlDatabase& db) {
+    const char* stmts[] = {
+        "CREATE TABLE IF NOT EXISTS yield_curve_fits ("
+        "  source        TEXT NOT NULL,"
+        "  model         TEXT NOT NULL,"
+        "  date          TEXT NOT NULL,"
+        "  beta0         REAL NOT NULL DEFAULT 0,"
+        "  beta1         REAL NOT NULL DEFAULT 0,"
+        "  beta2         REAL NOT NULL DEFAULT 0,"
+        "  beta3         REAL NOT NULL DEFAULT 0,"
+        "  tau1          REAL NOT NULL DEFAULT 0,"
+        "  tau2          REAL NOT NULL DEFAULT 0,"
+        "  rmse          REAL NOT NULL DEFAULT 0,"
+        "  observed      TEXT NOT NULL DEFAULT '[]',"
+        "  fitted_at     TEXT DEFAULT (datetime('now')),"
+        "  PRIMARY KEY (source, model, date)"
+        ")",
+    };
+    QSqlQuery q(db);
+    for (const char* s : stmts) {
+        if (!q.exec(QString::fromUtf8(s)))
+            return Result<void>::err(q.lastError().text().toStdString());
+    }
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v063() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({63, "yield_curve_fits", apply_v063});
+}
+
+} // namespace fincept
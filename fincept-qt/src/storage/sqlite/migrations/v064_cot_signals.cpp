@@ -0,0 +1,73 @@
+// v064_cot_signals — CFTC Commitments of Traders positioning signals.
+//
+//   - cot_signal_rules — a market (CFTC identifier), report type and trader
+//     group to watch, with the COT index band (extreme long / short) and the
+//     week-over-week z-score that count as a signal, plus how to notify.
+//   - cot_signals — every signal a rule produced, one per rule, kind and
+//     report date, with the positioning snapshot behind it in `detail`.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v064(QSqlDatabase& db) {
+    const char* stmts[] = {
+        "CREATE TABLE IF NOT EXISTS cot_signal_rules ("
+        "  id               TEXT PRIMARY KEY,"
+        "  name             TEXT NOT NULL,"
+        "  market           TEXT NOT NULL,"
+        "  report_type      TEXT NOT NULL DEFAULT 'legacy',"
+        "  trader_group     TEXT NOT NULL,"
+        "  years            REAL NOT NULL DEFAULT 3,"
+        "  zscore_window    INTEGER NOT NULL DEFAULT 52,"
+        "  index_high       REAL NOT NULL DEFAULT 90,"
+        "  index_low        REAL NOT NULL DEFAULT 10,"
+        "  zscore_abs       REAL NOT NULL DEFAULT 2,"
+        "  toast            INTEGER NOT NULL DEFAULT 1,"
+        "  providers        INTEGER NOT NULL DEFAULT 0,"
+        "  active           INTEGER NOT NULL DEFAULT 1,"
+        "  last_checked_at  INTEGER NOT NULL DEFAULT 0,"
+        "  last_report_date TEXT,"
+        "  created_at       TEXT DEFAULT (datetime('now'))"
+        ")",
+        "CREATE TABLE IF NOT EXISTS cot_signals ("
+        "  id            TEXT PRIMARY KEY,"
+        "  rule_id       TEXT NOT NULL,"
+        "  market        TEXT NOT NULL,"
+        "  contract_code TEXT,"
+        "  report_type   TEXT NOT NULL,"
+        "  trader_group  TEXT NOT NULL,"
+        "  kind          TEXT NOT NULL,"
+        "  report_date   TEXT NOT NULL,"
+        "  cot_index     REAL,"
+        "  zscore        REAL,"
+        "  net           INTEGER,"
+        "  detail        TEXT NOT NULL DEFAULT '{}',"
+        "  detected_at   INTEGER NOT NULL,"
+        "  UNIQUE(rule_id, kind, report_date)"
+        ")",
+        "CREATE INDEX IF NOT EXISTS idx_cot_signals_time ON cot_signals(detected_at)",
+    };
+    QSqlQuery q(db);
+    for (const char* s : stmts) {
+        if (!q.exec(QString::fromUtf8(s)))
+            return Result<void>::err(q.lastError().text().toStdString());
+    }
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v064() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({64, "cot_signals", apply_v064});
+}
+
+} // namespace fincept
@@ -0,0 +1,57 @@
+// v065_symbology — cross-provider symbol mapping table.
+//
+//   - symbology — one row per (exchange, symbol) listing with the identifiers
+//     other providers key it by: ISIN, the OpenFIGI FIGI / composite FIGI /
+//     share-class FIGI, the primary venue (`listing`, e.g. NASDAQ for a US
+//     row) and the security type. Seeded from the broker master contracts
+//     (instruments table) and completed by OpenFIGI lookups; `source` is
+//     broker | openfigi | manual, and manual rows are never overwritten.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v065(QSqlDatabase& db) {
+    const char* stmts[] = {
+        "CREATE TABLE IF NOT EXISTS symbology ("
+        "  exchange         TEXT NOT NULL,"
+        "  symbol           TEXT NOT NULL,"
+        "  listing          TEXT NOT NULL DEFAULT '',"
+        "  name             TEXT NOT NULL DEFAULT '',"
+        "  isin             TEXT NOT NULL DEFAULT '',"
+        "  figi             TEXT NOT NULL DEFAULT '',"
+        "  composite_figi   TEXT NOT NULL DEFAULT '',"
+        "  share_class_figi TEXT NOT NULL DEFAULT '',"
+        "  security_type    TEXT NOT NULL DEFAULT '',"
+        "  source           TEXT NOT NULL DEFAULT 'broker',"
+        "  updated_at       INTEGER NOT NULL DEFAULT 0,"
+        "  PRIMARY KEY(exchange, symbol)"
+        ")",
+        "CREATE INDEX IF NOT EXISTS idx_symbology_symbol ON symbology(symbol)",
+        "CREATE INDEX IF NOT EXISTS idx_symbology_isin ON symbology(isin) WHERE isin <> ''",
+        "CREATE INDEX IF NOT EXISTS idx_symbology_figi ON symbology(figi) WHERE figi <> ''",
+        "CREATE INDEX IF NOT EXISTS idx_symbology_composite ON symbology(composite_figi) WHERE composite_figi <> ''",
+    };
+    QSqlQuery q(db);
+    for (const char* s : stmts) {
+        if (!q.exec(QString::fromUtf8(s)))
+            return Result<void>::err(q.lastError().text().toStdString());
+    }
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v065() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({65, "symbology", apply_v065});
+}
+
+} // namespace fincept
@@ -0,0 +1,68 @@
+// v066_download_jobs — bulk historical backfill queue.
+//
+//   - download_jobs — one backfill request: the exchange, interval and date
+//     range to fetch, the source (yahoo | broker), the request rate it must
+//     stay under and its status (queued | running | paused | completed |
+//     failed | cancelled).
+//   - download_items — one row per symbol of a job, with its own status
+//     (pending | done | failed), bars stored, attempts and last error, so an
+//     interrupted job resumes at the first pending symbol after a restart.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v066(QSqlDatabase& db) {
+    const char* stmts[] = {
+        "CREATE TABLE IF NOT EXISTS download_jobs ("
+        "  id              TEXT PRIMARY KEY,"
+        "  name            TEXT NOT NULL DEFAULT '',"
+        "  exchange        TEXT NOT NULL DEFAULT '',"
+        "  interval        TEXT NOT NULL DEFAULT '1d',"
+        "  from_date       TEXT NOT NULL,"
+        "  to_date         TEXT NOT NULL,"
+        "  source          TEXT NOT NULL DEFAULT 'yahoo',"
+        "  rate_per_minute INTEGER NOT NULL DEFAULT 30,"
+        "  status          TEXT NOT NULL DEFAULT 'queued',"
+        "  error           TEXT NOT NULL DEFAULT '',"
+        "  created_at      INTEGER NOT NULL,"
+        "  updated_at      INTEGER NOT NULL,"
+        "  finished_at     INTEGER NOT NULL DEFAULT 0"
+        ")",
+        "CREATE TABLE IF NOT EXISTS download_items ("
+        "  job_id     TEXT NOT NULL,"
+        "  seq        INTEGER NOT NULL,"
+        "  symbol     TEXT NOT NULL,"
+        "  status     TEXT NOT NULL DEFAULT 'pending',"
+        "  bars       INTEGER NOT NULL DEFAULT 0,"
+        "  attempts   INTEGER NOT NULL DEFAULT 0,"
+        "  error      TEXT NOT NULL DEFAULT '',"
+        "  updated_at INTEGER NOT NULL DEFAULT 0,"
+        "  PRIMARY KEY(job_id, symbol)"
+        ")",
+        "CREATE INDEX IF NOT EXISTS idx_download_items_pending ON download_items(job_id, status, seq)",
+        "CREATE INDEX IF NOT EXISTS idx_download_jobs_status ON download_jobs(status, created_at)",
+    };
+    QSqlQuery q(db);
+    for (const char* s : stmts) {
+        if (!q.exec(QString::fromUtf8(s)))
+            return Result<void>::err(q.lastError().text().toStdString());
+    }
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v066() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({66, "download_jobs", apply_v066});
+}
+
+} // namespace fincept
@@ -0,0 +1,82 @@
+// v067_futures — futures contract specifications and expiry calendar.
+//
+//   - futures_specs — one row per root (ES, CL, ...): exchange, currency,
+//     multiplier, tick size, the listed month cycle as month codes ("HMUZ"),
+//     the named last-trade rule, how many business days before expiry a
+//     continuous series rolls, the back-adjustment method (panama | ratio |
+//     none) and the Yahoo suffix used to backfill individual contracts.
+//   - futures_expiries — exchange-published last-trade dates that override
+//     the rule (holiday shifts, irregular listings), keyed by contract code.
+//
+// Seeds the common US roots; INSERT OR IGNORE so user edits survive.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v067(QSqlDatabase& db) {
+    const char* stmts[] = {
+        "CREATE TABLE IF NOT EXISTS futures_specs ("
+        "  root         TEXT PRIMARY KEY,"
+        "  name         TEXT NOT NULL DEFAULT '',"
+        "  exchange     TEXT NOT NULL DEFAULT '',"
+        "  currency     TEXT NOT NULL DEFAULT 'USD',"
+        "  multiplier   REAL NOT NULL DEFAULT 1,"
+        "  tick_size    REAL NOT NULL DEFAULT 0.01,"
+        "  months       TEXT NOT NULL DEFAULT 'HMUZ',"
+        "  expiry_rule  TEXT NOT NULL DEFAULT 'third_friday',"
+        "  roll_days    INTEGER NOT NULL DEFAULT 5,"
+        "  adjust       TEXT NOT NULL DEFAULT 'panama',"
+        "  yahoo_suffix TEXT NOT NULL DEFAULT '',"
+        "  updated_at   INTEGER NOT NULL DEFAULT 0"
+        ")",
+        "CREATE TABLE IF NOT EXISTS futures_expiries ("
+        "  root        TEXT NOT NULL,"
+        "  contract    TEXT NOT NULL,"
+        "  expiry_date TEXT NOT NULL,"
+        "  PRIMARY KEY(root, contract)"
+        ")",
+        "INSERT OR IGNORE INTO futures_specs (root, name, exchange, currency, multiplier, tick_size, months, "
+        "expiry_rule, roll_days, adjust, yahoo_suffix) VALUES "
+        "('ES', 'E-mini S&P 500', 'CME', 'USD', 50, 0.25, 'HMUZ', 'third_friday', 8, 'panama', '.CME'),"
+        "('NQ', 'E-mini Nasdaq-100', 'CME', 'USD', 20, 0.25, 'HMUZ', 'third_friday', 8, 'panama', '.CME'),"
+        "('RTY', 'E-mini Russell 2000', 'CME', 'USD', 50, 0.1, 'HMUZ', 'third_friday', 8, 'panama', '.CME'),"
+        "('YM', 'E-mini Dow', 'CBOT', 'USD', 5, 1, 'HMUZ', 'third_friday', 8, 'panama', '.CBT'),"
+        "('CL', 'WTI Crude Oil', 'NYMEX', 'USD', 1000, 0.01, 'FGHJKMNQUVXZ', 'prior_25th_minus_3bd', 3, "
+        "'panama', '.NYM'),"
+        "('NG', 'Henry Hub Natural Gas', 'NYMEX', 'USD', 10000, 0.001, 'FGHJKMNQUVXZ', 'first_minus_3bd', 3, "
+        "'panama', '.NYM'),"
+        "('GC', 'Gold', 'COMEX', 'USD', 100, 0.1, 'GJMQVZ', 'third_last_bd', 25, 'panama', '.CMX'),"
+        "('SI', 'Silver', 'COMEX', 'USD', 5000, 0.005, 'HKNUZ', 'third_last_bd', 25, 'panama', '.CMX'),"
+        "('ZN', '10-Year T-Note', 'CBOT', 'USD', 1000, 0.015625, 'HMUZ', 'last_bd_minus_7bd', 25, 'panama', "
+        "'.CBT'),"
+        "('ZB', '30-Year T-Bond', 'CBOT', 'USD', 1000, 0.03125, 'HMUZ', 'last_bd_minus_7bd', 25, 'panama', "
+        "'.CBT'),"
+        "('ZC', 'Corn', 'CBOT', 'USD', 50, 0.25, 'HKNUZ', 'bd_before_15th', 15, 'ratio', '.CBT'),"
+        "('ZS', 'Soybeans', 'CBOT', 'USD', 50, 0.25, 'FHKNQUX', 'bd_before_15th', 15, 'ratio', '.CBT'),"
+        "('6E', 'Euro FX', 'CME', 'USD', 125000, 0.00005, 'HMUZ', 'third_wednesday_minus_2bd', 5, 'panama', "
+        "'.CME')",
+    };
+    QSqlQuery q(db);
+    for (const char* s : stmts) {
+        if (!q.exec(QString::fromUtf8(s)))
+            return Result<void>::err(q.lastError().text().toStdString());
+    }
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v067() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({67, "futures", apply_v067});
+}
+
+} // namespace fincept
@@ -0,0 +1,84 @@
+// v068_crypto_funding — perpetual funding rates and open interest.
+//
+//   - crypto_funding — one row per exchange, asset and poll: the venue's
+//     funding rate for its own interval, that rate normalised to 8 hours and
+//     annualised, mark / index price and open interest in base units and USD.
+//   - crypto_funding_rules — a metric to watch (funding APR on one exchange
+//     or OI-weighted across all, cross-exchange arb spread, open interest or
+//     its 24h change), the threshold and how to notify, with a cooldown.
+//   - crypto_funding_alerts — every time a rule fired, with the value.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v068(QSqlDatabase& db) {
+    const char* stmts[] = {
+        "CREATE TABLE IF NOT EXISTS crypto_funding ("
+        "  exchange        TEXT NOT NULL,"
+        "  asset           TEXT NOT NULL,"
+        "  symbol          TEXT NOT NULL,"
+        "  ts              INTEGER NOT NULL,"
+        "  funding_rate    REAL NOT NULL,"
+        "  interval_hours  REAL NOT NULL DEFAULT 8,"
+        "  rate_8h         REAL NOT NULL,"
+        "  apr             REAL NOT NULL,"
+        "  next_funding_ts INTEGER NOT NULL DEFAULT 0,"
+        "  mark_price      REAL NOT NULL DEFAULT 0,"
+        "  index_price     REAL NOT NULL DEFAULT 0,"
+        "  open_interest   REAL NOT NULL DEFAULT 0,"
+        "  oi_usd          REAL NOT NULL DEFAULT 0,"
+        "  PRIMARY KEY(exchange, asset, ts)"
+        ")",
+        "CREATE INDEX IF NOT EXISTS idx_crypto_funding_asset ON crypto_funding(asset, ts)",
+        "CREATE TABLE IF NOT EXISTS crypto_funding_rules ("
+        "  id            TEXT PRIMARY KEY,"
+        "  name          TEXT NOT NULL,"
+        "  asset         TEXT NOT NULL,"
+        "  metric        TEXT NOT NULL,"
+        "  exchange      TEXT NOT NULL DEFAULT '',"
+        "  op            TEXT NOT NULL DEFAULT 'above',"
+        "  threshold     REAL NOT NULL,"
+        "  cooldown_min  INTEGER NOT NULL DEFAULT 240,"
+        "  toast         INTEGER NOT NULL DEFAULT 1,"
+        "  providers     INTEGER NOT NULL DEFAULT 0,"
+        "  active        INTEGER NOT NULL DEFAULT 1,"
+        "  last_fired_at INTEGER NOT NULL DEFAULT 0,"
+        "  created_at    TEXT DEFAULT (datetime('now'))"
+        ")",
+        "CREATE TABLE IF NOT EXISTS crypto_funding_alerts ("
+        "  id        TEXT PRIMARY KEY,"
+        "  rule_id   TEXT NOT NULL,"
+        "  asset     TEXT NOT NULL,"
+        "  metric    TEXT NOT NULL,"
+        "  exchange  TEXT NOT NULL DEFAULT '',"
+        "  value     REAL NOT NULL,"
+        "  threshold REAL NOT NULL,"
+        "  detail    TEXT NOT NULL DEFAULT '{}',"
+        "  fired_at  INTEGER NOT NULL"
+        ")",
+        "CREATE INDEX IF NOT EXISTS idx_crypto_funding_alerts_time ON crypto_funding_alerts(fired_at)",
+    };
+    QSqlQuery q(db);
+    for (const char* s : stmts) {
+        if (!q.exec(QString::fromUtf8(s)))
+            return Result<void>::err(q.lastError().text().toStdString());
+    }
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v068() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({68, "crypto_funding", apply_v068});
+}
+
+} // namespace fincept
@@ -0,0 +1,72 @@
+// v069_stablecoin_peg — stablecoin peg monitoring history.
+//
+//   - stablecoin_prices — each coin's USD price on each venue at every poll.
+//   - stablecoin_pool_balances — Curve pool composition: each coin's balance
+//     and share of the pool at every pool poll.
+//   - stablecoin_depeg_events — one row per depeg episode (price deviation or
+//     pool imbalance past its threshold for the configured duration): when
+//     the breach started, when it was alerted and when it ended (0 while
+//     open), the peak and last values, and a JSON snapshot for post-mortems.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v069(QSqlDatabase& db) {
+    const char* stmts[] = {
+        "CREATE TABLE IF NOT EXISTS stablecoin_prices ("
+        "  coin  TEXT NOT NULL,"
+        "  venue TEXT NOT NULL,"
+        "  ts    INTEGER NOT NULL,"
+        "  price REAL NOT NULL,"
+        "  PRIMARY KEY(coin, venue, ts)"
+        ")",
+        "CREATE INDEX IF NOT EXISTS idx_stablecoin_prices_ts ON stablecoin_prices(ts)",
+        "CREATE TABLE IF NOT EXISTS stablecoin_pool_balances ("
+        "  pool      TEXT NOT NULL,"
+        "  pool_name TEXT NOT NULL DEFAULT '',"
+        "  coin      TEXT NOT NULL,"
+        "  ts        INTEGER NOT NULL,"
+        "  balance   REAL NOT NULL,"
+        "  share_pct REAL NOT NULL,"
+        "  PRIMARY KEY(pool, coin, ts)"
+        ")",
+        "CREATE INDEX IF NOT EXISTS idx_stablecoin_pool_balances_ts ON stablecoin_pool_balances(ts)",
+        "CREATE TABLE IF NOT EXISTS stablecoin_depeg_events ("
+        "  id          TEXT PRIMARY KEY,"
+        "  kind        TEXT NOT NULL,"
+        "  coin        TEXT NOT NULL,"
+        "  source      TEXT NOT NULL,"
+        "  threshold   REAL NOT NULL,"
+        "  started_at  INTEGER NOT NULL,"
+        "  alerted_at  INTEGER NOT NULL,"
+        "  ended_at    INTEGER NOT NULL DEFAULT 0,"
+        "  peak_value  REAL NOT NULL,"
+        "  last_value  REAL NOT NULL,"
+        "  detail      TEXT NOT NULL DEFAULT '{}'"
+        ")",
+        "CREATE INDEX IF NOT EXISTS idx_stablecoin_depeg_events_time ON stablecoin_depeg_events(started_at)",
+    };
+    QSqlQuery q(db);
+    for (const char* s : stmts) {
+        if (!q.exec(QString::fromUtf8(s)))
+            return Result<void>::err(q.lastError().text().toStdString());
+    }
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v069() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({69, "stablecoin_peg", apply_v069});
+}
+
+} // namespace fincept
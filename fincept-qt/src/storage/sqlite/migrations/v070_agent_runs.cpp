@@ -0,0 +1,62 @@
+// v070_agent_runs — history of every agent execution.
+//
+//   - agent_runs — one row per run_agent / streaming / structured / team
+//     execution: the query and agent config (credentials stripped), the
+//     resolved provider / model, the output or error, token usage when the
+//     model reported it, latency, and which run it repeats (rerun_of).
+//     input_hash identifies runs with identical inputs so their outputs can
+//     be compared over time and served from cache.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v070(QSqlDatabase& db) {
+    const char* stmts[] = {
+        "CREATE TABLE IF NOT EXISTS agent_runs ("
+        "  id            TEXT PRIMARY KEY,"
+        "  kind          TEXT NOT NULL,"
+        "  query         TEXT NOT NULL,"
+        "  config        TEXT NOT NULL DEFAULT '{}',"
+        "  output_model  TEXT NOT NULL DEFAULT '',"
+        "  provider      TEXT NOT NULL DEFAULT '',"
+        "  model         TEXT NOT NULL DEFAULT '',"
+        "  agent_name    TEXT NOT NULL DEFAULT '',"
+        "  input_hash    TEXT NOT NULL,"
+        "  status        TEXT NOT NULL DEFAULT 'running',"
+        "  response      TEXT NOT NULL DEFAULT '',"
+        "  error         TEXT NOT NULL DEFAULT '',"
+        "  input_tokens  INTEGER NOT NULL DEFAULT 0,"
+        "  output_tokens INTEGER NOT NULL DEFAULT 0,"
+        "  total_tokens  INTEGER NOT NULL DEFAULT 0,"
+        "  latency_ms    INTEGER NOT NULL DEFAULT 0,"
+        "  started_at    INTEGER NOT NULL,"
+        "  finished_at   INTEGER NOT NULL DEFAULT 0,"
+        "  rerun_of      TEXT NOT NULL DEFAULT ''"
+        ")",
+        "CREATE INDEX IF NOT EXISTS idx_agent_runs_started ON agent_runs(started_at)",
+        "CREATE INDEX IF NOT EXISTS idx_agent_runs_hash ON agent_runs(input_hash, started_at)",
+    };
+    QSqlQuery q(db);
+    for (const char* s : stmts) {
+        if (!q.exec(QString::fromUtf8(s)))
+            return Result<void>::err(q.lastError().text().toStdString());
+    }
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v070() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({70, "agent_runs", apply_v070});
+}
+
+} // namespace fincept
@@ -0,0 +1,55 @@
+// v071_agent_debates — verdicts of the bull / bear / risk agent debate.
+//
+//   - agent_debate_verdicts — one row per ticker per day (a re-run the same
+//     day replaces it): the judged direction and conviction, a signed score
+//     (+conviction bullish, -conviction bearish, 0 neutral) the screener
+//     sorts on, the deciding evidence, dissenting members, and the full
+//     round-by-round transcript.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v071(QSqlDatabase& db) {
+    const char* stmts[] = {
+        "CREATE TABLE IF NOT EXISTS agent_debate_verdicts ("
+        "  ticker       TEXT NOT NULL,"
+        "  as_of_date   TEXT NOT NULL,"
+        "  direction    TEXT NOT NULL,"
+        "  conviction   REAL NOT NULL DEFAULT 0,"
+        "  score        REAL NOT NULL DEFAULT 0,"
+        "  key_evidence TEXT NOT NULL DEFAULT '[]',"
+        "  dissent      TEXT NOT NULL DEFAULT '[]',"
+        "  summary      TEXT NOT NULL DEFAULT '',"
+        "  transcript   TEXT NOT NULL DEFAULT '[]',"
+        "  rounds       INTEGER NOT NULL DEFAULT 0,"
+        "  provider     TEXT NOT NULL DEFAULT '',"
+        "  model        TEXT NOT NULL DEFAULT '',"
+        "  created_at   INTEGER NOT NULL,"
+        "  PRIMARY KEY (ticker, as_of_date)"
+        ")",
+        "CREATE INDEX IF NOT EXISTS idx_agent_debate_verdicts_date ON agent_debate_verdicts(as_of_date)",
+    };
+    QSqlQuery q(db);
+    for (const char* s : stmts) {
+        if (!q.exec(QString::fromUtf8(s)))
+            return Result<void>::err(q.lastError().text().toStdString());
+    }
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v071() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({71, "agent_debates", apply_v071});
+}
+
+} // namespace fincept
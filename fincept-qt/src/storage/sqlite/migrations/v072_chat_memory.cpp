@@ -0,0 +1,47 @@
+// v072_chat_memory — rolling summaries of long AI chat sessions.
+//
+//   - chat_session_memory — one row per chat session: a summary of the
+//     oldest `covered_count` messages (in chat_messages order). Requests send
+//     the summary in place of those messages, followed by as many of the
+//     remaining messages as fit the model's context budget. Deleted with the
+//     session.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v072(QSqlDatabase& db) {
+    const char* stmts[] = {
+        "CREATE TABLE IF NOT EXISTS chat_session_memory ("
+        "  session_id     TEXT PRIMARY KEY REFERENCES chat_sessions(id) ON DELETE CASCADE,"
+        "  summary        TEXT NOT NULL DEFAULT '',"
+        "  covered_count  INTEGER NOT NULL DEFAULT 0,"
+        "  summary_tokens INTEGER NOT NULL DEFAULT 0,"
+        "  provider       TEXT NOT NULL DEFAULT '',"
+        "  model          TEXT NOT NULL DEFAULT '',"
+        "  updated_at     TEXT DEFAULT (datetime('now'))"
+        ")",
+    };
+    QSqlQuery q(db);
+    for (const char* s : stmts) {
+        if (!q.exec(QString::fromUtf8(s)))
+            return Result<void>::err(q.lastError().text().toStdString());
+    }
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v072() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({72, "chat_memory", apply_v072});
+}
+
+} // namespace fincept
@@ -0,0 +1,83 @@
+// v073_screener — native screener over locally cached per-symbol metrics.
+//
+//   - screener_data — one row per symbol: profile columns plus a flat JSON
+//     object of fundamental, technical, ownership and alternative metrics
+//     (screener_data.py snapshot). Filters are evaluated against this table,
+//     so runs never hit the network.
+//   - saved_screens — a named filter tree with its universe, sort, result
+//     columns and optional re-run schedule (schedule_minutes, 0 = manual).
+//   - screen_runs — every run of a saved screen: the ordered matches, the
+//     symbols that entered / left since the previous run, and the result rows.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v073(QSqlDatabase& db) {
+    const char* stmts[] = {
+        "CREATE TABLE IF NOT EXISTS screener_data ("
+        "  symbol     TEXT PRIMARY KEY,"
+        "  name       TEXT NOT NULL DEFAULT '',"
+        "  sector     TEXT NOT NULL DEFAULT '',"
+        "  industry   TEXT NOT NULL DEFAULT '',"
+        "  exchange   TEXT NOT NULL DEFAULT '',"
+        "  country    TEXT NOT NULL DEFAULT '',"
+        "  currency   TEXT NOT NULL DEFAULT '',"
+        "  fields     TEXT NOT NULL DEFAULT '{}',"
+        "  updated_at INTEGER NOT NULL"
+        ")",
+        "CREATE TABLE IF NOT EXISTS saved_screens ("
+        "  id               TEXT PRIMARY KEY,"
+        "  name             TEXT NOT NULL,"
+        "  description      TEXT NOT NULL DEFAULT '',"
+        "  filter           TEXT NOT NULL DEFAULT '{}',"
+        "  universe         TEXT NOT NULL DEFAULT '[]',"
+        "  sort_field       TEXT NOT NULL DEFAULT '',"
+        "  sort_desc        INTEGER NOT NULL DEFAULT 1,"
+        "  max_results      INTEGER NOT NULL DEFAULT 50,"
+        "  columns          TEXT NOT NULL DEFAULT '[]',"
+        "  schedule_minutes INTEGER NOT NULL DEFAULT 0,"
+        "  refresh_data     INTEGER NOT NULL DEFAULT 1,"
+        "  notify           INTEGER NOT NULL DEFAULT 1,"
+        "  export_path      TEXT NOT NULL DEFAULT '',"
+        "  last_run_at      INTEGER NOT NULL DEFAULT 0,"
+        "  last_match_count INTEGER NOT NULL DEFAULT 0,"
+        "  created_at       TEXT DEFAULT (datetime('now')),"
+        "  updated_at       TEXT DEFAULT (datetime('now'))"
+        ")",
+        "CREATE TABLE IF NOT EXISTS screen_runs ("
+        "  id            TEXT PRIMARY KEY,"
+        "  screen_id     TEXT NOT NULL REFERENCES saved_screens(id) ON DELETE CASCADE,"
+        "  ran_at        INTEGER NOT NULL,"
+        "  universe_size INTEGER NOT NULL DEFAULT 0,"
+        "  match_count   INTEGER NOT NULL DEFAULT 0,"
+        "  symbols       TEXT NOT NULL DEFAULT '[]',"
+        "  added         TEXT NOT NULL DEFAULT '[]',"
+        "  removed       TEXT NOT NULL DEFAULT '[]',"
+        "  rows          TEXT NOT NULL DEFAULT '[]'"
+        ")",
+        "CREATE INDEX IF NOT EXISTS idx_screen_runs_screen ON screen_runs(screen_id, ran_at)",
+    };
+    QSqlQuery q(db);
+    for (const char* s : stmts) {
+        if (!q.exec(QString::fromUtf8(s)))
+            return Result<void>::err(q.lastError().text().toStdString());
+    }
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v073() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({73, "screener", apply_v073});
+}
+
+} // namespace fincept
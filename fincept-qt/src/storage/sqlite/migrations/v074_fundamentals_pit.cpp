@@ -0,0 +1,60 @@
+// v074_fundamentals_pit — point-in-time fundamentals for lookahead-free
+// screens and backtests.
+//
+//   - fundamentals_pit — one row per reported value: a statement metric of a
+//     symbol for one period (FY / Q duration or I = balance sheet instant),
+//     keyed by both the period end (report date) and the date the filing
+//     carrying the value was made. Restatements and the comparative figures
+//     repeated in later filings are separate rows with later filed dates, so
+//     "what was known on day D" is the newest row per period with
+//     filed_date <= D. Populated by sec_xbrl_data.py pit and
+//     fmp_data.py pit_fundamentals.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v074(QSqlDatabase& db) {
+    const char* stmts[] = {
+        "CREATE TABLE IF NOT EXISTS fundamentals_pit ("
+        "  symbol        TEXT NOT NULL,"
+        "  metric        TEXT NOT NULL,"
+        "  period        TEXT NOT NULL,"
+        "  period_end    TEXT NOT NULL,"
+        "  filed_date    TEXT NOT NULL,"
+        "  source        TEXT NOT NULL,"
+        "  value         REAL NOT NULL,"
+        "  unit          TEXT NOT NULL DEFAULT '',"
+        "  period_start  TEXT NOT NULL DEFAULT '',"
+        "  fiscal_year   INTEGER NOT NULL DEFAULT 0,"
+        "  fiscal_period TEXT NOT NULL DEFAULT '',"
+        "  form          TEXT NOT NULL DEFAULT '',"
+        "  accession     TEXT NOT NULL DEFAULT '',"
+        "  ingested_at   INTEGER NOT NULL,"
+        "  PRIMARY KEY (symbol, metric, period, period_end, filed_date, source)"
+        ")",
+        "CREATE INDEX IF NOT EXISTS idx_fundamentals_pit_filed ON fundamentals_pit(symbol, filed_date)",
+    };
+    QSqlQuery q(db);
+    for (const char* s : stmts) {
+        if (!q.exec(QString::fromUtf8(s)))
+            return Result<void>::err(q.lastError().text().toStdString());
+    }
+    return Result<void>::ok();
+}
+
+} // namespace
+
+void register_migration_v074() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({74, "fundamentals_pit", apply_v074});
+}
+
+} // namespace fincept
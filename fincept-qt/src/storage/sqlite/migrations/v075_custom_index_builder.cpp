@@ -0,0 +1,66 @@
+// v075_custom_index_builder — custom indices maintained by the backend as
+// synthetic symbols.
+//
+//   - custom_indices gains symbol (the @NAME ticker charts, watchlist
+//     expressions and benchmarks resolve), rebalance (none / monthly /
+//     quarterly / annual), options_json (weighting parameters such as the
+//     fundamental metric or volatility lookback), start_date and
+//     computed_at (epoch ms of the last rebuild from the candle cache).
+//   - custom_index_rebalances — the constituent weights set at each
+//     rebalance of the last rebuild.
+//
+// Idempotent on re-run (ignores the duplicate-column error, matching v058).
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> v075_exec(QSqlDatabase& db, const QString& sql) {
+    QSqlQuery q(db);
+    if (!q.exec(sql)) {
+        const QString err = q.lastError().text();
+        if (!err.contains("duplicate column", Qt::CaseInsensitive))
+            return Result<void>::err(err.toStdString());
+    }
+    return Result<void>::ok();
+}
+
+Result<void> apply_v075(QSqlDatabase& db) {
+    const char* stmts[] = {
+        "ALTER TABLE custom_indices ADD COLUMN symbol TEXT",
+        "ALTER TABLE custom_indices ADD COLUMN rebalance TEXT NOT NULL DEFAULT 'none'",
+        "ALTER TABLE custom_indices ADD COLUMN options_json TEXT NOT NULL DEFAULT '{}'",
+        "ALTER TABLE custom_indices ADD COLUMN start_date TEXT",
+        "ALTER TABLE custom_indices ADD COLUMN computed_at INTEGER NOT NULL DEFAULT 0",
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_custom_indices_symbol ON custom_indices(symbol)",
+
+        "CREATE TABLE IF NOT EXISTS custom_index_rebalances ("
+        "  index_id      TEXT NOT NULL REFERENCES custom_indices(id) ON DELETE CASCADE,"
+        "  date          TEXT NOT NULL," // YYYY-MM-DD, weights take effect at that close
+        "  weights_json  TEXT NOT NULL," // {symbol: weight (0–1)}
+        "  PRIMARY KEY (index_id, date)"
+        ")",
+    };
+    for (const char* s : stmts) {
+        auto r = v075_exec(db, QString::fromUtf8(s));
+        if (r.is_err())
+            return r;
+    }
+    return Result<void>::ok();
+}
+
+} // anonymous namespace
+
+void register_migration_v075() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({75, "custom_index_builder", apply_v075});
+}
+
+} // namespace fincept
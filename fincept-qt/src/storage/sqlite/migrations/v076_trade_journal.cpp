@@ -0,0 +1,97 @@
+// v076_trade_journal — trade journal built on the fills ledger.
+//
+//   - journal_fills — the ledger's fills (TcaService::ledger_fills) as of each
+//     sync, so live fills outlive the broker's same-day order book.
+//   - journal_trades — round trips rebuilt from journal_fills (entry to flat)
+//     with their P&L and MAE / MFE, plus what the user records against them:
+//     setup, notes and a 0–5 rating. Rebuilds only touch the derived columns.
+//   - journal_trade_tags / journal_attachments — free-form tags and chart
+//     snapshots (file paths) per trade.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v076(QSqlDatabase& db) {
+    const char* stmts[] = {
+        "CREATE TABLE IF NOT EXISTS journal_fills ("
+        "  source     TEXT NOT NULL," // paper | live | algo | exec
+        "  fill_id    TEXT NOT NULL,"
+        "  time_ms    INTEGER NOT NULL,"
+        "  account_id TEXT NOT NULL DEFAULT '',"
+        "  account    TEXT NOT NULL DEFAULT '',"
+        "  broker     TEXT NOT NULL DEFAULT '',"
+        "  strategy   TEXT NOT NULL DEFAULT '',"
+        "  symbol     TEXT NOT NULL,"
+        "  side       INTEGER NOT NULL," // +1 buy, -1 sell
+        "  quantity   REAL NOT NULL,"
+        "  price      REAL NOT NULL,"
+        "  fee        REAL NOT NULL DEFAULT 0,"
+        "  PRIMARY KEY (source, fill_id, time_ms)"
+        ")",
+        "CREATE INDEX IF NOT EXISTS idx_journal_fills_time ON journal_fills(time_ms)",
+        "CREATE TABLE IF NOT EXISTS journal_trades ("
+        "  id           TEXT PRIMARY KEY," // hash of the book and the opening fill
+        "  account_id   TEXT NOT NULL DEFAULT '',"
+        "  account      TEXT NOT NULL DEFAULT '',"
+        "  broker       TEXT NOT NULL DEFAULT '',"
+        "  strategy     TEXT NOT NULL DEFAULT '',"
+        "  symbol       TEXT NOT NULL,"
+        "  direction    INTEGER NOT NULL," // +1 long, -1 short
+        "  quantity     REAL NOT NULL,"    // largest position held
+        "  entry_price  REAL NOT NULL,"
+        "  exit_price   REAL NOT NULL DEFAULT 0,"
+        "  entry_ms     INTEGER NOT NULL,"
+        "  exit_ms      INTEGER NOT NULL DEFAULT 0," // 0 while open
+        "  fees         REAL NOT NULL DEFAULT 0,"
+        "  pnl          REAL NOT NULL DEFAULT 0," // realized, net of fees
+        "  return_pct   REAL NOT NULL DEFAULT 0,"
+        "  fill_ids     TEXT NOT NULL DEFAULT '[]',"
+        "  mae_pct      REAL,"
+        "  mfe_pct      REAL,"
+        "  excursion_tf TEXT NOT NULL DEFAULT ''," // bar interval MAE / MFE came from
+        "  setup        TEXT NOT NULL DEFAULT '',"
+        "  notes        TEXT NOT NULL DEFAULT '',"
+        "  rating       INTEGER NOT NULL DEFAULT 0,"
+        "  created_at   TEXT DEFAULT (datetime('now')),"
+        "  updated_at   TEXT DEFAULT (datetime('now'))"
+        ")",
+        "CREATE INDEX IF NOT EXISTS idx_journal_trades_entry ON journal_trades(entry_ms)",
+        "CREATE TABLE IF NOT EXISTS journal_trade_tags ("
+        "  trade_id TEXT NOT NULL REFERENCES journal_trades(id) ON DELETE CASCADE,"
+        "  tag      TEXT NOT NULL,"
+        "  PRIMARY KEY (trade_id, tag)"
+        ")",
+        "CREATE INDEX IF NOT EXISTS idx_journal_trade_tags_tag ON journal_trade_tags(tag)",
+        "CREATE TABLE IF NOT EXISTS journal_attachments ("
+        "  id         TEXT PRIMARY KEY,"
+        "  trade_id   TEXT NOT NULL REFERENCES journal_trades(id) ON DELETE CASCADE,"
+        "  path       TEXT NOT NULL,"
+        "  caption    TEXT NOT NULL DEFAULT '',"
+        "  created_at INTEGER NOT NULL" // epoch ms
+        ")",
+        "CREATE INDEX IF NOT EXISTS idx_journal_attachments_trade ON journal_attachments(trade_id)",
+    };
+    QSqlQuery q(db);
+    for (const char* s : stmts) {
+        if (!q.exec(QString::fromUtf8(s)))
+            return Result<void>::err(q.lastError().text().toStdString());
+    }
+    return Result<void>::ok();
+}
+
+} // anonymous namespace
+
+void register_migration_v076() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({76, "trade_journal", apply_v076});
+}
+
+} // namespace fincept
@@ -0,0 +1,50 @@
+// v077_event_risk — binary-event overlay for open positions.
+//
+//   - earnings_calendar — the next scheduled earnings date per symbol (a
+//     window when the company has not confirmed the day) with consensus
+//     estimates, refreshed from yfinance by EventRiskService.
+//   - event_risk_alerts — event keys whose "N days before" reminder has
+//     fired, so each earnings date or central-bank meeting alerts once.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v077(QSqlDatabase& db) {
+    const char* stmts[] = {
+        "CREATE TABLE IF NOT EXISTS earnings_calendar ("
+        "  symbol            TEXT PRIMARY KEY,"
+        "  earnings_date     TEXT NOT NULL DEFAULT ''," // YYYY-MM-DD, '' = none scheduled
+        "  earnings_date_end TEXT NOT NULL DEFAULT ''," // end of the window when unconfirmed
+        "  eps_estimate      REAL,"
+        "  revenue_estimate  REAL,"
+        "  fetched_at        INTEGER NOT NULL" // epoch ms
+        ")",
+        "CREATE TABLE IF NOT EXISTS event_risk_alerts ("
+        "  event_key  TEXT PRIMARY KEY," // kind|symbol-or-country|date
+        "  alerted_at INTEGER NOT NULL"
+        ")",
+    };
+    QSqlQuery q(db);
+    for (const char* s : stmts) {
+        if (!q.exec(QString::fromUtf8(s)))
+            return Result<void>::err(q.lastError().text().toStdString());
+    }
+    return Result<void>::ok();
+}
+
+} // anonymous namespace
+
+void register_migration_v077() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({77, "event_risk", apply_v077});
+}
+
+} // namespace fincept
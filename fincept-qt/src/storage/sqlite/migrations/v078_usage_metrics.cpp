@@ -0,0 +1,50 @@
+// v078_usage_metrics — opt-in local usage metrics (core/telemetry/UsageMetrics).
+//
+//   - usage_metrics — hourly aggregates per (kind, name): command and tool
+//     latencies, data-source refresh latencies, cache lookups and websocket
+//     throughput. Counts and timings only; never leaves the machine.
+
+#include "storage/sqlite/migrations/MigrationRunner.h"
+
+#include <QSqlError>
+#include <QSqlQuery>
+
+namespace fincept {
+namespace {
+
+Result<void> apply_v078(QSqlDatabase& db) {
+    const char* stmts[] = {
+        "CREATE TABLE IF NOT EXISTS usage_metrics ("
+        "  bucket_ms INTEGER NOT NULL," // start of the UTC hour, epoch ms
+        "  kind      TEXT NOT NULL,"    // action | tool | datasource | cache | websocket
+        "  name      TEXT NOT NULL,"
+        "  count     INTEGER NOT NULL DEFAULT 0," // samples, lookups or messages
+        "  errors    INTEGER NOT NULL DEFAULT 0," // failures; dropped frames for websocket
+        "  slow      INTEGER NOT NULL DEFAULT 0," // samples over UsageMetrics::kSlowMs
+        "  hits      INTEGER NOT NULL DEFAULT 0," // cache hits
+        "  total_ms  REAL NOT NULL DEFAULT 0,"
+        "  max_ms    REAL NOT NULL DEFAULT 0,"
+        "  bytes     INTEGER NOT NULL DEFAULT 0,"
+        "  PRIMARY KEY (bucket_ms, kind, name)"
+        ")",
+        "CREATE INDEX IF NOT EXISTS idx_usage_metrics_kind ON usage_metrics(kind, bucket_ms)",
+    };
+    QSqlQuery q(db);
+    for (const char* s : stmts) {
+        if (!q.exec(QString::fromUtf8(s)))
+            return Result<void>::err(q.lastError().text().toStdString());
+    }
+    return Result<void>::ok();
+}
+
+} // anonymous namespace
+
+void register_migration_v078() {
+    static bool done = false;
+    if (done)
+        return;
+    done = true;
+    MigrationRunner::register_migration({78, "usage_metrics", apply_v078});
+}
+
+} // namespace fincept
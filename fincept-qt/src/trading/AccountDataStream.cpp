@@ -40,6 +40,11 @@ constexpr int ADS_QUOTE_POLL_MS = 300000;
 constexpr int ADS_PORTFOLIO_POLL_MS = 300000;
 constexpr int ADS_WATCHLIST_POLL_MS = 300000;
 constexpr int ADS_ACTIVE_FEED_POLL_MS = 3000; // fast poll for algo/active-feed symbols (non-WS brokers)
+
+constexpr qint64 ADS_GAP_FILL_MIN_MS = 60000; // shorter outages only get a quote snapshot
+constexpr int ADS_GAP_FILL_MAX_SYMBOLS = 50;  // bar fetches per reconnect
+constexpr int ADS_GAP_FILL_STAGGER_MS = 250;  // spacing between bar fetches
+constexpr int ADS_GAP_FILL_MAX_DAYS = 7;      // 1m history most brokers serve
 } // namespace
 
 // ── Construction / Destruction ──────────────────────────────────────────────
@@ -65,6 +70,11 @@ AccountDataStream::AccountDataStream(const QString& account_id, QObject* parent)
     active_feed_timer_ = new QTimer(this);
     active_feed_timer_->setInterval(ADS_ACTIVE_FEED_POLL_MS);
     connect(active_feed_timer_, &QTimer::timeout, this, &AccountDataStream::on_active_feed_timer);
+
+    // Every WS path (base adapters, Fyers, Alpaca, Zerodha, Angel One) reports
+    // through connection_state_changed, so reconnect handling hooks in here.
+    connect(this, &AccountDataStream::connection_state_changed, this,
+            [this](const QString&, ConnectionState state) { on_ws_state(state); });
 }
 
 AccountDataStream::~AccountDataStream() {
@@ -111,6 +121,8 @@ void AccountDataStream::stop() {
     watchlist_timer_->stop();
     active_feed_timer_->stop();
     ws_teardown();
+    ws_down_since_ms_ = 0;
+    ws_down_symbols_.clear();
 
     LOG_INFO(ADS_TAG, QString("Stopped stream for account %1").arg(account_id_));
 }
@@ -1649,4 +1661,140 @@ void AccountDataStream::ws_resubscribe() {
     }
 }
 
+// ── Reconnect gap-fill ──────────────────────────────────────────────────────
+
+void AccountDataStream::on_ws_state(ConnectionState state) {
+    if (!running_ || !ws_)
+        return;
+    QStringList active;
+    if (!selected_symbol_.isEmpty())
+        active.append(selected_symbol_);
+    for (const QString& s : subscribed_symbols())
+        if (!active.contains(s))
+            active.append(s);
+
+    if (state == ConnectionState::Disconnected) {
+        // Failed reconnect attempts report Disconnected again — keep the first.
+        if (ws_down_since_ms_ == 0) {
+            ws_down_since_ms_ = QDateTime::currentMSecsSinceEpoch();
+            ws_down_symbols_ = active;
+        }
+        return;
+    }
+    if (state != ConnectionState::Connected || ws_down_since_ms_ == 0)
+        return;
+
+    const qint64 from_ms = ws_down_since_ms_;
+    const qint64 to_ms = QDateTime::currentMSecsSinceEpoch();
+    const QStringList before = ws_down_symbols_;
+    ws_down_since_ms_ = 0;
+    ws_down_symbols_.clear();
+
+    QStringList resumed, added, released;
+    for (const QString& s : active)
+        (before.contains(s) ? resumed : added).append(s);
+    for (const QString& s : before)
+        if (!active.contains(s))
+            released.append(s);
+    LOG_INFO(ADS_TAG, QString("WS reconnected for %1 after %2s: %3 resumed, %4 added, %5 released while down")
+                          .arg(account_id_)
+                          .arg((to_ms - from_ms) / 1000)
+                          .arg(resumed.size())
+                          .arg(added.size())
+                          .arg(released.size()));
+
+    // Subscribe changes made while the socket was down never reached it (every
+    // ws_resubscribe caller is gated on ws_connected()), and the adapters replay
+    // their last set on connect. Only rebuild when that set is now wrong. Posted
+    // so it runs after the per-broker connected handlers.
+    QPointer<AccountDataStream> self = this;
+    const bool stale = !added.isEmpty() || !released.isEmpty();
+    QTimer::singleShot(0, this, [self, stale, resumed, from_ms, to_ms]() {
+        if (!self || !self->running_)
+            return;
+        if (stale && self->ws_connected())
+            self->ws_resubscribe();
+        self->fill_ws_gap(resumed, from_ms, to_ms);
+    });
+}
+
+void AccountDataStream::fill_ws_gap(const QStringList& symbols, qint64 from_ms, qint64 to_ms) {
+    if (symbols.isEmpty())
+        return;
+    const QString acct_id = account_id_;
+    const QString bid = broker_id_;
+    QPointer<AccountDataStream> self = this;
+
+    // Quotes first: one batched call puts every symbol back on a current price
+    // without waiting for its next tick.
+    auto creds = AccountManager::instance().load_credentials(acct_id);
+    if (!creds.api_key.isEmpty()) {
+        (void)QtConcurrent::run([self, acct_id, bid, symbols, creds]() {
+            auto* broker = BrokerRegistry::instance().get(bid);
+            if (!broker)
+                return;
+            auto result = broker->get_quotes(creds, symbols.toVector());
+            if (!result.success || !result.data) {
+                LOG_WARN(ADS_TAG,
+                         QString("Gap-fill quote snapshot failed for %1/%2: %3").arg(bid, acct_id, result.error));
+                return;
+            }
+            QMetaObject::invokeMethod(
+                self,
+                [self, acct_id, data = *result.data]() {
+                    if (!self)
+                        return;
+                    for (const auto& q : data) {
+                        self->quote_cache_[q.symbol] = q;
+                        emit self->quote_updated(acct_id, q.symbol, q);
+                    }
+                },
+                Qt::QueuedConnection);
+        });
+    }
+
+    if (to_ms - from_ms < ADS_GAP_FILL_MIN_MS)
+        return;
+
+    // Bars: start at the minute the socket dropped in so the partial bar left
+    // behind by the disconnect is covered too.
+    const qint64 bar_from = from_ms - from_ms % 60000;
+    const int days = std::clamp(int((to_ms - bar_from) / 86400000) + 1, 1, ADS_GAP_FILL_MAX_DAYS);
+    const int n = std::min(int(symbols.size()), ADS_GAP_FILL_MAX_SYMBOLS);
+    if (symbols.size() > n)
+        LOG_WARN(ADS_TAG,
+                 QString("Gap-fill for %1 limited to %2 of %3 symbols").arg(acct_id).arg(n).arg(symbols.size()));
+    for (int i = 0; i < n; ++i) {
+        const QString sym = symbols.at(i);
+        QTimer::singleShot(i * ADS_GAP_FILL_STAGGER_MS, this, [self, acct_id, bid, sym, bar_from, to_ms, days]() {
+            if (!self || !self->running_)
+                return;
+            HistoricalDataService::instance().fetch(
+                sym, QStringLiteral("1m"), days, bid, acct_id,
+                [self, acct_id, bid, sym, bar_from, to_ms](bool ok, const QVector<BrokerCandle>& all,
+                                                           const QString& err) {
+                    if (!self)
+                        return;
+                    if (!ok) {
+                        LOG_WARN(ADS_TAG, QString("Gap-fill bars failed for %1 on %2: %3").arg(sym, acct_id, err));
+                        return;
+                    }
+                    BrokerGapFill fill;
+                    fill.broker_id = bid;
+                    fill.account_id = acct_id;
+                    fill.symbol = sym;
+                    fill.from_ms = bar_from;
+                    fill.to_ms = to_ms;
+                    for (auto c : all) {
+                        if (c.timestamp > 0 && c.timestamp < 100000000000LL) // epoch seconds → ms
+                            c.timestamp *= 1000;
+                        if (c.timestamp >= bar_from && c.timestamp < to_ms)
+                            fill.candles.append(c);
+                    }
+                    emit self->gap_filled(acct_id, fill);
+                });
+        });
+    }
+}
+
 } // namespace fincept::trading
@@ -93,6 +93,8 @@ class AccountDataStream : public QObject {
     void clock_fetched(const QString& account_id, const MarketClock& clock);
     void connection_state_changed(const QString& account_id, ConnectionState state);
     void token_expired(const QString& account_id);
+    // One per symbol after a WS reconnect (see on_ws_state).
+    void gap_filled(const QString& account_id, const fincept::trading::BrokerGapFill& fill);
 
   private:
     // --- Token expiry check ---
@@ -136,6 +138,16 @@ class AccountDataStream : public QObject {
     // this stream. Used by all Phase 2 adapters that share BrokerWebSocketBase.
     void wire_base_ws(class BrokerWebSocketBase* ws);
 
+    // --- Reconnect gap-fill ---
+    // A WS drop leaves every streamed symbol with a hole between the last tick
+    // before the disconnect and the first after it. On reconnect: resubscribe
+    // only if the active set changed while the socket was down (adapters replay
+    // their own last set otherwise), snapshot quotes over REST, then fetch the
+    // 1m bars covering the outage for each symbol that was streaming before and
+    // still is, emitting gap_filled() per symbol.
+    void on_ws_state(ConnectionState state);
+    void fill_ws_gap(const QStringList& symbols, qint64 from_ms, qint64 to_ms);
+
     // --- State ---
     QString account_id_;
     QString broker_id_;
@@ -148,6 +160,8 @@ class AccountDataStream : public QObject {
     // so the failure is surfaced to the user exactly once, not on every retry.
     // Reset when the socket reconnects.
     bool ws_permission_denied_ = false;
+    qint64 ws_down_since_ms_ = 0; // first disconnect of the current outage; 0 while connected
+    QStringList ws_down_symbols_; // streaming set at that moment
 
     // Polling timers
     QTimer* quote_timer_ = nullptr;
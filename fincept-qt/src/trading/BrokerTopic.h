@@ -6,6 +6,7 @@
 //   broker:<broker_id>:<account_id>:orders           — TTL 5s
 //   broker:<broker_id>:<account_id>:balance          — TTL 30s
 //   broker:<broker_id>:<account_id>:ticks:<symbol>   — push-only, coalesce 100ms
+//   broker:<broker_id>:<account_id>:gapfill:<symbol> — push-only, after a WS reconnect
 //
 // `<account_id>` defaults to `default` when the caller doesn't have an explicit
 // one — single-account and multi-account code paths use the same format.
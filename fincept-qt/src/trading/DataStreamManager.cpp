@@ -203,6 +203,7 @@ void DataStreamManager::wire_stream_signals(AccountDataStream* stream) {
                 AccountManager::instance().set_connection_state(account_id, state);
             });
     connect(stream, &AccountDataStream::token_expired, this, &DataStreamManager::token_expired);
+    connect(stream, &AccountDataStream::gap_filled, this, &DataStreamManager::gap_filled);
 
     // Dual-fire: publish the same per-account data onto hub topics so
     // consumers subscribed to broker:<id>:<account>:<channel> see it.
@@ -214,6 +215,7 @@ void DataStreamManager::wire_stream_signals(AccountDataStream* stream) {
         connect(stream, &AccountDataStream::orders_updated, this, &DataStreamManager::on_orders_for_hub);
         connect(stream, &AccountDataStream::funds_updated, this, &DataStreamManager::on_funds_for_hub);
         connect(stream, &AccountDataStream::quote_updated, this, &DataStreamManager::on_quote_for_hub);
+        connect(stream, &AccountDataStream::gap_filled, this, &DataStreamManager::on_gap_fill_for_hub);
     }
 }
 
@@ -293,6 +295,11 @@ void DataStreamManager::ensure_registered_with_hub() {
     quote_policy.min_interval_ms = 1 * 1000;
     hub.set_policy_pattern(QStringLiteral("broker:*:*:quote:*"), quote_policy);
 
+    // broker:<id>:<account>:gapfill:<symbol> — one-shot events after a WS reconnect.
+    fincept::datahub::TopicPolicy gapfill_policy;
+    gapfill_policy.push_only = true;
+    hub.set_policy_pattern(QStringLiteral("broker:*:*:gapfill:*"), gapfill_policy);
+
     hub_registered_ = true;
 
     // Back-wire any streams that were created before registration.
@@ -304,6 +311,7 @@ void DataStreamManager::ensure_registered_with_hub() {
         connect(s, &AccountDataStream::orders_updated, this, &DataStreamManager::on_orders_for_hub);
         connect(s, &AccountDataStream::funds_updated, this, &DataStreamManager::on_funds_for_hub);
         connect(s, &AccountDataStream::quote_updated, this, &DataStreamManager::on_quote_for_hub);
+        connect(s, &AccountDataStream::gap_filled, this, &DataStreamManager::on_gap_fill_for_hub);
     }
 
     LOG_INFO(DSM_TAG, "Registered with DataHub (broker:*)");
@@ -361,16 +369,24 @@ void DataStreamManager::on_quote_for_hub(const QString& account_id, const QStrin
     fincept::datahub::DataHub::instance().publish(topic, QVariant::fromValue(quote));
 }
 
+void DataStreamManager::on_gap_fill_for_hub(const QString& account_id, const BrokerGapFill& fill) {
+    if (!hub_registered_)
+        return;
+    const QString topic = broker_topic(fill.broker_id, account_id, QStringLiteral("gapfill"), fill.symbol);
+    fincept::datahub::DataHub::instance().publish(topic, QVariant::fromValue(fill));
+}
+
 // ── Shared quote feed (Stage 2) ─────────────────────────────────────────────
 
 void DataStreamManager::open_quote_feed(QObject* owner, const QString& consumer_id, const QString& account_id,
-                                        const QString& symbol, std::function<void(const BrokerQuote&)> cb) {
+                                        const QString& symbol, std::function<void(const BrokerQuote&)> cb,
+                                        std::function<void(const BrokerGapFill&)> on_gap) {
     // This singleton + DataHub live on the main thread; callers (DeploymentRunner)
     // run on the algo engine thread. Marshal all stream/DataHub mutation here.
     QPointer<QObject> guard(owner);
     QMetaObject::invokeMethod(
         this,
-        [this, guard, consumer_id, account_id, symbol, cb]() {
+        [this, guard, consumer_id, account_id, symbol, cb, on_gap]() {
             if (!guard)
                 return;
             ensure_registered_with_hub();
@@ -388,8 +404,11 @@ void DataStreamManager::open_quote_feed(QObject* owner, const QString& consumer_
 
             // Replace any prior feed registered under this consumer id.
             if (auto old = quote_feeds_.find(consumer_id); old != quote_feeds_.end()) {
-                if (old->owner)
+                if (old->owner) {
                     fincept::datahub::DataHub::instance().unsubscribe(old->owner, old->topic);
+                    if (!old->gap_topic.isEmpty())
+                        fincept::datahub::DataHub::instance().unsubscribe(old->owner, old->gap_topic);
+                }
                 quote_feeds_.erase(old);
             }
 
@@ -406,7 +425,21 @@ void DataStreamManager::open_quote_feed(QObject* owner, const QString& consumer_
                                                                                  },
                                                                                  Qt::QueuedConnection);
                                                                          });
-            quote_feeds_.insert(consumer_id, QuoteFeed{account_id, topic, guard});
+            QString gap_topic;
+            if (on_gap) {
+                gap_topic = broker_topic(stream->broker_id(), account_id, QStringLiteral("gapfill"), symbol);
+                fincept::datahub::DataHub::instance().subscribe<BrokerGapFill>(
+                    guard, gap_topic, [guard, on_gap](const BrokerGapFill& fill) {
+                        QMetaObject::invokeMethod(
+                            guard,
+                            [guard, on_gap, fill]() {
+                                if (guard)
+                                    on_gap(fill);
+                            },
+                            Qt::QueuedConnection);
+                    });
+            }
+            quote_feeds_.insert(consumer_id, QuoteFeed{account_id, topic, gap_topic, guard});
             LOG_INFO(DSM_TAG,
                      QString("Opened quote feed '%1' for %2 on account %3").arg(consumer_id, symbol, account_id));
         },
@@ -418,8 +451,11 @@ void DataStreamManager::close_quote_feed(const QString& consumer_id, const QStri
         this,
         [this, consumer_id, account_id]() {
             if (auto it = quote_feeds_.find(consumer_id); it != quote_feeds_.end()) {
-                if (it->owner)
+                if (it->owner) {
                     fincept::datahub::DataHub::instance().unsubscribe(it->owner, it->topic);
+                    if (!it->gap_topic.isEmpty())
+                        fincept::datahub::DataHub::instance().unsubscribe(it->owner, it->gap_topic);
+                }
                 quote_feeds_.erase(it);
             }
             if (auto* stream = stream_for(account_id)) {
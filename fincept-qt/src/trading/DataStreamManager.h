@@ -57,8 +57,11 @@ class DataStreamManager : public QObject, public fincept::datahub::Producer {
     // published to broker:<id>:<acct>:quote:<symbol>), then forwards every quote
     // to `cb`. `cb` is invoked on `owner`'s thread (queued). `owner` is the
     // lifetime guard. All stream/DataHub mutation happens on the main thread.
+    // `on_gap`, when set, receives the bars covering each WS outage
+    // (broker:<id>:<acct>:gapfill:<symbol>) on the same thread.
     void open_quote_feed(QObject* owner, const QString& consumer_id, const QString& account_id, const QString& symbol,
-                         std::function<void(const BrokerQuote&)> cb);
+                         std::function<void(const BrokerQuote&)> cb,
+                         std::function<void(const BrokerGapFill&)> on_gap = {});
     void close_quote_feed(const QString& consumer_id, const QString& account_id);
 
     // fincept::datahub::Producer
@@ -78,6 +81,7 @@ class DataStreamManager : public QObject, public fincept::datahub::Producer {
     void clock_fetched(const QString& account_id, const MarketClock& clock);
     void connection_state_changed(const QString& account_id, ConnectionState state);
     void token_expired(const QString& account_id);
+    void gap_filled(const QString& account_id, const fincept::trading::BrokerGapFill& fill);
 
   private:
     DataStreamManager();
@@ -100,6 +104,7 @@ class DataStreamManager : public QObject, public fincept::datahub::Producer {
     void on_orders_for_hub(const QString& account_id, const QVector<BrokerOrderInfo>& orders);
     void on_funds_for_hub(const QString& account_id, const BrokerFunds& funds);
     void on_quote_for_hub(const QString& account_id, const QString& symbol, const BrokerQuote& quote);
+    void on_gap_fill_for_hub(const QString& account_id, const BrokerGapFill& fill);
 
     bool hub_registered_ = false;
 
@@ -109,6 +114,7 @@ class DataStreamManager : public QObject, public fincept::datahub::Producer {
     struct QuoteFeed {
         QString account_id;
         QString topic;
+        QString gap_topic; // empty when the consumer didn't ask for gap fills
         QPointer<QObject> owner;
     };
     QHash<QString, QuoteFeed> quote_feeds_;
@@ -0,0 +1,356 @@
+// Execution algos — schedules and per-run bookkeeping (see ExecutionAlgo.h)
+
+#include "trading/ExecutionAlgo.h"
+
+#include <QDateTime>
+#include <QJsonArray>
+
+#include <algorithm>
+#include <cmath>
+
+namespace fincept::trading {
+
+namespace {
+
+constexpr double kEaQtyEps = 1e-9;
+
+double ea_floor_lot(double q, double lot) {
+    if (lot <= 0)
+        return q;
+    return std::floor(q / lot + kEaQtyEps) * lot;
+}
+
+QString ea_iso(qint64 ms) {
+    return ms > 0 ? QDateTime::fromMSecsSinceEpoch(ms).toUTC().toString(Qt::ISODate) : QString();
+}
+
+} // namespace
+
+const char* exec_algo_type_str(ExecAlgoType t) {
+    switch (t) {
+        case ExecAlgoType::TWAP:
+            return "twap";
+        case ExecAlgoType::VWAP:
+            return "vwap";
+        case ExecAlgoType::POV:
+            return "pov";
+        case ExecAlgoType::Iceberg:
+            return "iceberg";
+    }
+    return "twap";
+}
+
+// ============================================================================
+// Params
+// ============================================================================
+
+bool ExecAlgoParams::from_json(const QJsonObject& o, ExecAlgoParams& out, QString* error) {
+    auto fail = [error](const QString& msg) {
+        if (error)
+            *error = msg;
+        return false;
+    };
+    ExecAlgoParams p;
+    const QString algo = o.value("algo").toString().trimmed().toLower();
+    if (algo == "twap")
+        p.type = ExecAlgoType::TWAP;
+    else if (algo == "vwap")
+        p.type = ExecAlgoType::VWAP;
+    else if (algo == "pov")
+        p.type = ExecAlgoType::POV;
+    else if (algo == "iceberg")
+        p.type = ExecAlgoType::Iceberg;
+    else
+        return fail(QString("Unknown algo '%1' (twap, vwap, pov, iceberg)").arg(algo));
+
+    p.account_id = o.value("account_id").toString().trimmed();
+    p.symbol = o.value("symbol").toString().trimmed();
+    p.exchange = o.value("exchange").toString().trimmed();
+    p.side = o.value("side").toString().trimmed().toUpper() == "SELL" ? OrderSide::Sell : OrderSide::Buy;
+    p.product_type = product_from_broker_str(o.value("product").toString("MIS"));
+    p.quantity = o.value("quantity").toDouble();
+    if (p.account_id.isEmpty() || p.symbol.isEmpty())
+        return fail(QStringLiteral("account_id and symbol are required"));
+    if (p.quantity <= 0)
+        return fail(QStringLiteral("quantity must be > 0"));
+
+    const int default_duration = p.type == ExecAlgoType::Iceberg ? 0 : (p.type == ExecAlgoType::POV ? 60 : 30);
+    p.duration_minutes = std::clamp(o.value("duration_minutes").toInt(default_duration), 0, 24 * 60);
+    p.interval_seconds = std::clamp(o.value("interval_seconds").toInt(p.type == ExecAlgoType::Iceberg ? 5 : 60), 5,
+                                    3600);
+    p.participation = std::clamp(o.value("participation").toDouble(p.participation), 0.01, 0.5);
+    p.display_quantity = o.value("display_quantity").toDouble();
+    p.limit_price = std::max(0.0, o.value("limit_price").toDouble());
+    p.lot_size = o.value("lot_size").toDouble(1.0);
+    if (p.lot_size <= 0)
+        return fail(QStringLiteral("lot_size must be > 0"));
+    for (const auto& v : o.value("volume_profile").toArray())
+        if (v.toDouble() >= 0)
+            p.volume_profile.append(v.toDouble());
+
+    if ((p.type == ExecAlgoType::TWAP || p.type == ExecAlgoType::VWAP) && p.duration_minutes <= 0)
+        return fail(QStringLiteral("TWAP / VWAP need duration_minutes > 0"));
+    if (p.type == ExecAlgoType::Iceberg) {
+        if (p.display_quantity <= 0 || p.display_quantity >= p.quantity)
+            return fail(QStringLiteral("Iceberg needs 0 < display_quantity < quantity"));
+        if (p.display_quantity < p.lot_size)
+            return fail(QStringLiteral("display_quantity is smaller than one lot"));
+    }
+    out = p;
+    return true;
+}
+
+QJsonObject ExecAlgoParams::to_json() const {
+    QJsonObject o{{"algo", exec_algo_type_str(type)},
+                  {"account_id", account_id},
+                  {"symbol", symbol},
+                  {"exchange", exchange},
+                  {"side", side == OrderSide::Buy ? "BUY" : "SELL"},
+                  {"product", product_to_broker_str(product_type)},
+                  {"quantity", quantity},
+                  {"duration_minutes", duration_minutes},
+                  {"interval_seconds", interval_seconds},
+                  {"limit_price", limit_price},
+                  {"lot_size", lot_size}};
+    if (type == ExecAlgoType::POV)
+        o["participation"] = participation;
+    if (type == ExecAlgoType::Iceberg)
+        o["display_quantity"] = display_quantity;
+    return o;
+}
+
+QJsonObject ExecChild::to_json() const {
+    return QJsonObject{{"seq", seq},
+                       {"order_id", order_id},
+                       {"sent_at", ea_iso(sent_ms)},
+                       {"quantity", quantity},
+                       {"reference_price", reference_price},
+                       {"filled_qty", filled_qty},
+                       {"avg_price", avg_price},
+                       {"status", status},
+                       {"message", message}};
+}
+
+// ============================================================================
+// Schedules
+// ============================================================================
+
+QVector<double> ExecutionSchedule::twap(double quantity, int slices, double lot) {
+    return vwap(quantity, slices, QVector<double>(std::max(1, slices), 1.0), lot);
+}
+
+QVector<double> ExecutionSchedule::vwap(double quantity, int slices, const QVector<double>& weights, double lot) {
+    slices = std::max(1, slices);
+    // Resample the profile onto the slice grid (nearest bucket).
+    QVector<double> w(slices, 1.0);
+    if (!weights.isEmpty()) {
+        for (int i = 0; i < slices; ++i)
+            w[i] = weights[std::min(int(weights.size()) - 1, int(double(i) * weights.size() / slices))];
+    }
+    double total = 0;
+    for (double x : w)
+        total += x;
+    if (total <= 0) {
+        w.fill(1.0);
+        total = slices;
+    }
+
+    QVector<double> out(slices, 0.0);
+    double assigned = 0, exact = 0;
+    for (int i = 0; i < slices; ++i) {
+        // Round the running total, not each slice, so lots spread evenly.
+        exact += quantity * w[i] / total;
+        const double upto = i + 1 == slices ? quantity : ea_floor_lot(exact, lot);
+        out[i] = std::max(0.0, upto - assigned);
+        assigned += out[i];
+    }
+    return out;
+}
+
+QVector<double> ExecutionSchedule::default_profile(int slices) {
+    slices = std::max(1, slices);
+    QVector<double> w(slices);
+    for (int i = 0; i < slices; ++i) {
+        const double x = (i + 0.5) / slices - 0.5; // −0.5 … 0.5
+        w[i] = 1.0 + 6.0 * x * x;                  // open / close ≈ 2.5× midday
+    }
+    return w;
+}
+
+// ============================================================================
+// Run
+// ============================================================================
+
+void ExecAlgoRun::init(const ExecAlgoParams& p, qint64 now_ms) {
+    params = p;
+    started_ms = now_ms;
+    next_due_ms = now_ms;
+    if (p.type == ExecAlgoType::TWAP || p.type == ExecAlgoType::VWAP) {
+        const int slices = std::max(1, p.duration_minutes * 60 / p.interval_seconds);
+        schedule = p.type == ExecAlgoType::TWAP
+                       ? ExecutionSchedule::twap(p.quantity, slices, p.lot_size)
+                       : ExecutionSchedule::vwap(p.quantity, slices,
+                                                 p.volume_profile.isEmpty() ? ExecutionSchedule::default_profile(slices)
+                                                                            : p.volume_profile,
+                                                 p.lot_size);
+    }
+}
+
+double ExecAlgoRun::filled() const {
+    double f = 0;
+    for (const auto& c : children)
+        f += c.filled_qty;
+    return f;
+}
+
+double ExecAlgoRun::working_quantity() const {
+    double w = 0;
+    for (const auto& c : children)
+        if (c.working())
+            w += c.quantity - c.filled_qty;
+    return w;
+}
+
+qint64 ExecAlgoRun::end_ms() const {
+    if (params.duration_minutes <= 0)
+        return 0;
+    qint64 end = started_ms + qint64(params.duration_minutes) * 60 * 1000;
+    // The last TWAP / VWAP slice goes out one interval before the end; give it
+    // that interval to fill.
+    if (!schedule.isEmpty())
+        end += qint64(params.interval_seconds) * 1000;
+    return end;
+}
+
+double ExecAlgoRun::due_quantity(qint64 now_ms, double last, double volume) {
+    if (last > 0)
+        last_price = last;
+    if (now_ms < next_due_ms || remaining() <= kEaQtyEps)
+        return 0;
+    const double lot = params.lot_size;
+    const qint64 interval_ms = qint64(params.interval_seconds) * 1000;
+
+    switch (params.type) {
+        case ExecAlgoType::TWAP:
+        case ExecAlgoType::VWAP: {
+            while (next_slice < schedule.size() && now_ms >= next_due_ms) {
+                carry += schedule[next_slice++];
+                next_due_ms += interval_ms;
+            }
+            if (next_slice >= schedule.size())
+                next_due_ms = now_ms + interval_ms; // later passes only resend rejected quantity
+            const bool last_slice = next_slice >= schedule.size();
+            double qty = std::min(carry, remaining());
+            if (!last_slice)
+                qty = ea_floor_lot(qty, lot);
+            carry -= qty;
+            return qty;
+        }
+        case ExecAlgoType::POV: {
+            next_due_ms = now_ms + interval_ms;
+            const double delta = last_volume >= 0 && volume >= last_volume ? volume - last_volume : 0.0;
+            last_volume = volume;
+            market_volume += delta;
+            const double target = carry + params.participation * delta;
+            const double qty = std::min(ea_floor_lot(target, lot), remaining());
+            carry = target - qty; // sub-lot remainder rolls into the next interval
+            return qty;
+        }
+        case ExecAlgoType::Iceberg: {
+            next_due_ms = now_ms + interval_ms;
+            if (working_quantity() > kEaQtyEps)
+                return 0;
+            const double rem = remaining();
+            return rem <= params.display_quantity ? rem : params.display_quantity;
+        }
+    }
+    return 0;
+}
+
+void ExecAlgoRun::update_child(int index, double filled_qty, double avg_price, const QString& status,
+                               const QString& message) {
+    if (index < 0 || index >= children.size())
+        return;
+    ExecChild& c = children[index];
+    c.filled_qty = std::min(filled_qty, c.quantity);
+    if (avg_price > 0)
+        c.avg_price = avg_price;
+    if (!message.isEmpty())
+        c.message = message;
+    if (status == c.status)
+        return;
+    c.status = status;
+    if (!c.working() && params.type != ExecAlgoType::POV && params.type != ExecAlgoType::Iceberg)
+        carry += c.quantity - c.filled_qty; // POV / iceberg re-derive size from remaining()
+}
+
+bool ExecAlgoRun::exhausted(qint64 now_ms) const {
+    const bool working = working_quantity() > kEaQtyEps;
+    if (filled() >= params.quantity - kEaQtyEps && !working)
+        return true;
+    const qint64 end = end_ms();
+    if (end > 0 && now_ms >= end)
+        return true;
+    return false;
+}
+
+QJsonObject ExecAlgoRun::shortfall() const {
+    double notional = 0, qty = 0;
+    for (const auto& c : children) {
+        notional += c.filled_qty * c.avg_price;
+        qty += c.filled_qty;
+    }
+    const double avg = qty > 0 ? notional / qty : 0.0;
+    const double sign = params.side == OrderSide::Buy ? 1.0 : -1.0;
+    const double unfilled = std::max(0.0, params.quantity - qty);
+    const double exec_cost = qty > 0 && arrival_price > 0 ? sign * (avg - arrival_price) * qty : 0.0;
+    const double opp_cost = arrival_price > 0 && last_price > 0 ? sign * (last_price - arrival_price) * unfilled : 0.0;
+    const double paper = arrival_price * params.quantity;
+    QJsonObject o{{"arrival_price", arrival_price},
+                  {"avg_fill_price", avg},
+                  {"last_price", last_price},
+                  {"filled_qty", qty},
+                  {"unfilled_qty", unfilled},
+                  {"execution_cost", exec_cost},
+                  {"opportunity_cost", opp_cost},
+                  {"total_cost", exec_cost + opp_cost},
+                  {"execution_bps", qty > 0 && arrival_price > 0 ? sign * (avg - arrival_price) / arrival_price * 1e4
+                                                                 : 0.0},
+                  {"shortfall_bps", paper > 0 ? (exec_cost + opp_cost) / paper * 1e4 : 0.0}};
+    if (params.type == ExecAlgoType::POV)
+        o["realized_participation"] = market_volume > 0 ? qty / market_volume : 0.0;
+    return o;
+}
+
+QJsonObject ExecAlgoRun::to_json(bool with_children) const {
+    const double done = filled();
+    QJsonObject o{{"id", id},
+                  {"algo", exec_algo_type_str(params.type)},
+                  {"params", params.to_json()},
+                  {"state", state},
+                  {"message", message},
+                  {"started_at", ea_iso(started_ms)},
+                  {"finished_at", ea_iso(finished_ms)},
+                  {"ends_at", ea_iso(end_ms())},
+                  {"progress_pct", params.quantity > 0 ? done / params.quantity * 100.0 : 0.0},
+                  {"filled_qty", done},
+                  {"working_qty", working_quantity()},
+                  {"remaining_qty", std::max(0.0, params.quantity - done)},
+                  {"children_sent", int(children.size())},
+                  {"shortfall", shortfall()}};
+    if (!schedule.isEmpty()) {
+        o["slices_total"] = int(schedule.size());
+        o["slices_sent"] = next_slice;
+    }
+    if (state == QLatin1String("running"))
+        o["next_child_at"] = ea_iso(next_due_ms);
+    if (with_children) {
+        QJsonArray kids;
+        for (const auto& c : children)
+            kids.append(c.to_json());
+        o["children"] = kids;
+    }
+    return o;
+}
+
+} // namespace fincept::trading
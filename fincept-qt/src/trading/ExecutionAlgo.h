@@ -0,0 +1,132 @@
+#pragma once
+// Execution algos — TWAP / VWAP / POV schedulers and iceberg slicing of a
+// parent order into child orders, with progress and implementation shortfall.
+//
+// This header is pure bookkeeping: ExecAlgoRun decides how much to send and
+// when, and records what filled. ExecutionAlgoManager owns the clock, quotes
+// and order routing (UnifiedTrading — paper or live account alike).
+
+#include "trading/TradingTypes.h"
+
+#include <QJsonObject>
+#include <QString>
+#include <QVector>
+
+namespace fincept::trading {
+
+enum class ExecAlgoType { TWAP, VWAP, POV, Iceberg };
+
+const char* exec_algo_type_str(ExecAlgoType t);
+
+struct ExecAlgoParams {
+    ExecAlgoType type = ExecAlgoType::TWAP;
+    QString account_id;
+    QString symbol;
+    QString exchange;
+    OrderSide side = OrderSide::Buy;
+    ProductType product_type = ProductType::Intraday;
+    double quantity = 0;
+
+    // TWAP / VWAP: the schedule spans duration_minutes in slices of
+    // interval_seconds. POV / iceberg: stop after duration_minutes (0 = run
+    // until the parent is done).
+    int duration_minutes = 30;
+    int interval_seconds = 60;
+
+    double participation = 0.10; // POV: share of the market volume traded each interval
+    double display_quantity = 0; // iceberg: visible child size
+    double limit_price = 0;      // 0 = market children
+    double lot_size = 1;         // child quantities are whole multiples
+
+    // VWAP: relative volume per slice (resampled to the slice count). Empty →
+    // a U-shaped intraday profile (heavier open and close).
+    QVector<double> volume_profile;
+
+    /// Parses the MCP / UI shape ({algo, account_id, symbol, exchange, side,
+    /// quantity, product, duration_minutes, interval_seconds, participation,
+    /// display_quantity, limit_price, lot_size, volume_profile}). Returns false
+    /// with `error` set on a missing or inconsistent field.
+    static bool from_json(const QJsonObject& o, ExecAlgoParams& out, QString* error);
+    QJsonObject to_json() const;
+};
+
+/// One child order sent by an algo.
+struct ExecChild {
+    int seq = 0;
+    QString order_id;
+    qint64 sent_ms = 0;
+    double quantity = 0;
+    double reference_price = 0; // quote at send time (market children fill here if the broker never reports)
+    double filled_qty = 0;
+    double avg_price = 0;
+    // working (incl. partly filled) | filled | partial (closed part-filled) | rejected | cancelled
+    QString status = QStringLiteral("working");
+    QString message;
+    int polls = 0;
+
+    bool working() const { return status == QLatin1String("working"); }
+    QJsonObject to_json() const;
+};
+
+/// Slice sizing helpers, exposed for previews.
+class ExecutionSchedule {
+  public:
+    /// `slices` equal child quantities summing to `quantity`, in whole lots;
+    /// the rounding remainder goes to the last slice.
+    static QVector<double> twap(double quantity, int slices, double lot);
+    /// Child quantities proportional to `weights` (resampled to `slices`).
+    static QVector<double> vwap(double quantity, int slices, const QVector<double>& weights, double lot);
+    /// U-shaped intraday volume profile over `slices` buckets.
+    static QVector<double> default_profile(int slices);
+};
+
+/// Live state of one algo. Times are epoch milliseconds.
+struct ExecAlgoRun {
+    QString id;
+    ExecAlgoParams params;
+    QString state = QStringLiteral("running"); // running | completed | cancelled | expired | failed
+    QString message;
+    qint64 started_ms = 0;
+    qint64 finished_ms = 0;
+
+    QVector<double> schedule; // TWAP / VWAP slice quantities
+    int next_slice = 0;
+    qint64 next_due_ms = 0;
+
+    double arrival_price = 0; // mid (or last) at the first quote
+    double last_price = 0;
+    double last_volume = -1;  // cumulative session volume at the previous POV interval
+    double market_volume = 0; // POV: volume observed since start
+    double carry = 0;         // scheduled quantity not sent yet (rejections, lot rounding)
+
+    QVector<ExecChild> children;
+
+    void init(const ExecAlgoParams& p, qint64 now_ms);
+
+    double filled() const;
+    double working_quantity() const;
+    double remaining() const { return params.quantity - filled() - working_quantity(); }
+    qint64 end_ms() const;
+
+    /// Quantity to send now (0 = nothing due), advancing the schedule. `last`
+    /// and `volume` are the latest quote (volume = cumulative session volume).
+    double due_quantity(qint64 now_ms, double last, double volume);
+
+    /// Applies a child's latest broker status. Quantity a rejected or
+    /// cancelled child did not fill goes back into the schedule.
+    void update_child(int index, double filled_qty, double avg_price, const QString& status,
+                      const QString& message = {});
+
+    /// Whether the run is over at `now_ms`: the parent filled with no child
+    /// working, or the duration (plus one interval for TWAP / VWAP) ran out —
+    /// the manager then cancels whatever is still working.
+    bool exhausted(qint64 now_ms) const;
+
+    /// {filled, average price, arrival, shortfall in bps and currency}. The
+    /// shortfall is signed so a positive number is a cost for either side;
+    /// the unfilled remainder is charged at the last price (opportunity cost).
+    QJsonObject shortfall() const;
+    QJsonObject to_json(bool with_children = true) const;
+};
+
+} // namespace fincept::trading
@@ -0,0 +1,318 @@
+// ExecutionAlgoManager — clock, quotes and order routing for execution algos
+
+#include "trading/ExecutionAlgoManager.h"
+
+#include "core/logging/Logger.h"
+#include "trading/AccountManager.h"
+#include "trading/BrokerRegistry.h"
+#include "trading/PaperTrading.h"
+#include "trading/UnifiedTrading.h"
+
+#include <QDateTime>
+#include <QPointer>
+#include <QTimer>
+#include <QUuid>
+#include <QtConcurrent>
+
+#include <algorithm>
+
+namespace fincept::trading {
+
+namespace {
+
+constexpr const char* kEamTag = "ExecAlgo";
+constexpr double kEamQtyEps = 1e-9;
+
+struct EamOrderState {
+    double filled_qty = 0;
+    double avg_price = 0;
+    QString status; // raw broker / paper status
+};
+
+// Maps a broker or paper order status onto ExecChild's vocabulary.
+QString eam_child_status(const QString& raw, double filled, double quantity) {
+    const QString s = raw.toLower();
+    if (filled >= quantity - kEamQtyEps || s.contains("complete") || s == "filled" || s == "traded")
+        return QStringLiteral("filled");
+    if (s.contains("reject"))
+        return filled > kEamQtyEps ? QStringLiteral("partial") : QStringLiteral("rejected");
+    if (s.contains("cancel") || s.contains("expire"))
+        return filled > kEamQtyEps ? QStringLiteral("partial") : QStringLiteral("cancelled");
+    return QStringLiteral("working");
+}
+
+// Current state of every order of the account, keyed by order id. Empty when
+// the order book could not be read.
+QHash<QString, EamOrderState> eam_order_book(const BrokerAccount& account) {
+    QHash<QString, EamOrderState> out;
+    if (account.trading_mode == "paper") {
+        for (const auto& o : pt_get_orders(account.paper_portfolio_id))
+            out.insert(o.id, {o.filled_qty, o.avg_price.value_or(0.0), o.status});
+        return out;
+    }
+    auto* broker = BrokerRegistry::instance().get(account.broker_id);
+    if (!broker)
+        return out;
+    const auto resp = broker->get_orders(AccountManager::instance().load_credentials(account.account_id));
+    if (!resp.success || !resp.data)
+        return out;
+    for (const auto& o : *resp.data)
+        out.insert(o.order_id, {o.filled_qty, o.avg_price, o.status});
+    return out;
+}
+
+// Refreshes the working children of `run` from the order book.
+void eam_poll_children(ExecAlgoRun& run, const BrokerAccount& account, int market_max_polls) {
+    bool any = false;
+    for (const auto& c : run.children)
+        any = any || c.working();
+    if (!any)
+        return;
+    const auto book = eam_order_book(account);
+    for (int i = 0; i < run.children.size(); ++i) {
+        ExecChild& c = run.children[i];
+        if (!c.working())
+            continue;
+        ++c.polls;
+        const auto it = book.constFind(c.order_id);
+        if (it != book.constEnd()) {
+            run.update_child(i, it->filled_qty, it->avg_price,
+                             eam_child_status(it->status, it->filled_qty, c.quantity));
+        } else if (run.params.limit_price <= 0 && c.polls >= market_max_polls) {
+            run.update_child(i, c.quantity, c.reference_price, QStringLiteral("filled"),
+                             QStringLiteral("Fill not reported by the broker; booked at the reference price"));
+        }
+    }
+}
+
+} // namespace
+
+ExecutionAlgoManager& ExecutionAlgoManager::instance() {
+    static ExecutionAlgoManager m;
+    return m;
+}
+
+ExecutionAlgoManager::ExecutionAlgoManager() {
+    clock_ = new QTimer(this);
+    clock_->setInterval(1000);
+    connect(clock_, &QTimer::timeout, this, &ExecutionAlgoManager::on_clock);
+}
+
+QString ExecutionAlgoManager::start(const ExecAlgoParams& params, QString* error) {
+    const BrokerAccount account = AccountManager::instance().get_account(params.account_id);
+    if (account.account_id.isEmpty()) {
+        if (error)
+            *error = "Account not found: " + params.account_id;
+        return {};
+    }
+    if (account.trading_mode == "paper" && account.paper_portfolio_id.isEmpty()) {
+        if (error)
+            *error = QStringLiteral("No paper portfolio for this account");
+        return {};
+    }
+
+    ExecAlgoRun run;
+    run.id = QUuid::createUuid().toString(QUuid::WithoutBraces);
+    run.init(params, QDateTime::currentMSecsSinceEpoch());
+    runs_.insert(run.id, run);
+    order_.append(run.id);
+    if (!clock_->isActive())
+        clock_->start();
+    LOG_INFO(kEamTag, QString("Started %1 %2 %3 %4 on %5 (%6)")
+                      .arg(QString::fromLatin1(exec_algo_type_str(params.type)).toUpper(),
+                           QLatin1String(params.side == OrderSide::Buy ? "BUY" : "SELL"))
+                      .arg(params.quantity)
+                      .arg(params.symbol, account.display_name, account.trading_mode));
+    visit(run.id);
+    return run.id;
+}
+
+bool ExecutionAlgoManager::cancel(const QString& id) {
+    const auto it = runs_.constFind(id);
+    if (it == runs_.constEnd() || it->state != QLatin1String("running"))
+        return false;
+    if (in_flight_.contains(id))
+        cancel_requested_.insert(id); // applied when the visit returns
+    else
+        finish(id, QStringLiteral("cancelled"), QStringLiteral("Cancelled by user"));
+    return true;
+}
+
+QJsonObject ExecutionAlgoManager::status(const QString& id) const {
+    const auto it = runs_.constFind(id);
+    return it == runs_.constEnd() ? QJsonObject() : it->to_json();
+}
+
+QJsonArray ExecutionAlgoManager::list(bool include_finished) const {
+    QJsonArray out;
+    for (auto it = order_.crbegin(); it != order_.crend(); ++it) {
+        const ExecAlgoRun& run = runs_[*it];
+        if (include_finished || run.state == QLatin1String("running"))
+            out.append(run.to_json(false));
+    }
+    return out;
+}
+
+QVector<ExecAlgoRun> ExecutionAlgoManager::runs() const {
+    QVector<ExecAlgoRun> out;
+    out.reserve(order_.size());
+    for (const auto& id : order_)
+        out.append(runs_[id]);
+    return out;
+}
+
+void ExecutionAlgoManager::on_clock() {
+    bool running = false;
+    for (const auto& id : order_) {
+        if (runs_[id].state != QLatin1String("running"))
+            continue;
+        running = true;
+        if (!in_flight_.contains(id))
+            visit(id);
+    }
+    if (!running && in_flight_.isEmpty())
+        clock_->stop();
+}
+
+void ExecutionAlgoManager::visit(const QString& id) {
+    in_flight_.insert(id);
+    QPointer<ExecutionAlgoManager> self = this;
+    const int max_polls = kMarketChildMaxPolls;
+    (void)QtConcurrent::run([self, run = runs_[id], max_polls]() mutable {
+        const qint64 now = QDateTime::currentMSecsSinceEpoch();
+        const BrokerAccount account = AccountManager::instance().get_account(run.params.account_id);
+        auto& trading = UnifiedTrading::instance();
+
+        double last = 0, volume = 0, bid = 0, ask = 0;
+        const auto quotes = trading.get_multi_quotes(account.account_id, {{run.params.symbol, run.params.exchange}});
+        if (quotes.success && quotes.data && !quotes.data->isEmpty()) {
+            const BrokerQuote& q = quotes.data->first();
+            last = q.ltp;
+            volume = q.volume;
+            bid = q.bid;
+            ask = q.ask;
+        }
+
+        eam_poll_children(run, account, max_polls);
+
+        if (last <= 0) {
+            run.message = quotes.error.isEmpty() ? QStringLiteral("Waiting for a quote") : quotes.error;
+        } else if (!run.exhausted(now)) {
+            run.message.clear();
+            if (run.arrival_price <= 0)
+                run.arrival_price = bid > 0 && ask > 0 ? (bid + ask) / 2.0 : last;
+            const double qty = run.due_quantity(now, last, volume);
+            if (qty > kEamQtyEps) {
+                UnifiedOrder order;
+                order.symbol = run.params.symbol;
+                order.exchange = run.params.exchange;
+                order.side = run.params.side;
+                order.product_type = run.params.product_type;
+                order.quantity = qty;
+                if (run.params.limit_price > 0) {
+                    order.order_type = OrderType::Limit;
+                    order.price = run.params.limit_price;
+                } else {
+                    order.order_type = OrderType::Market;
+                    order.price = last; // paper market fills need the live price
+                }
+                const auto resp = trading.place_order(account.account_id, order);
+                ExecChild child;
+                child.seq = run.children.size() + 1;
+                child.order_id = resp.order_id;
+                child.sent_ms = now;
+                child.quantity = qty;
+                child.reference_price = last;
+                run.children.append(child);
+                if (!resp.success) {
+                    run.update_child(run.children.size() - 1, 0, 0, QStringLiteral("rejected"), resp.message);
+                    run.message = resp.message;
+                } else if (account.trading_mode == "paper" && order.order_type == OrderType::Market) {
+                    // Paper market orders fill inside place_order.
+                    run.update_child(run.children.size() - 1, qty, last, QStringLiteral("filled"));
+                }
+            }
+        }
+
+        if (!self)
+            return;
+        QMetaObject::invokeMethod(
+            self,
+            [self, run]() {
+                if (!self)
+                    return;
+                const QString id = run.id;
+                self->in_flight_.remove(id);
+                const QString prev_state = self->runs_.value(id).state;
+                if (prev_state != QLatin1String("running"))
+                    return;
+                self->runs_[id] = run;
+                if (self->cancel_requested_.remove(id)) {
+                    self->finish(id, QStringLiteral("cancelled"), QStringLiteral("Cancelled by user"));
+                    return;
+                }
+                if (run.exhausted(QDateTime::currentMSecsSinceEpoch())) {
+                    const bool done = run.filled() >= run.params.quantity - kEamQtyEps;
+                    self->finish(id, done ? QStringLiteral("completed") : QStringLiteral("expired"));
+                    return;
+                }
+                emit self->algo_progress(id, run.to_json(false));
+            },
+            Qt::QueuedConnection);
+    });
+}
+
+void ExecutionAlgoManager::finish(const QString& id, const QString& state, const QString& message) {
+    ExecAlgoRun& run = runs_[id];
+    run.state = state;
+    if (!message.isEmpty())
+        run.message = message;
+    run.finished_ms = QDateTime::currentMSecsSinceEpoch();
+
+    // Cancel what is still working, then take a last look at the fills.
+    in_flight_.insert(id);
+    QPointer<ExecutionAlgoManager> self = this;
+    (void)QtConcurrent::run([self, run]() mutable {
+        const BrokerAccount account = AccountManager::instance().get_account(run.params.account_id);
+        bool cancelled_any = false;
+        for (const auto& c : run.children) {
+            if (!c.working() || c.order_id.isEmpty())
+                continue;
+            UnifiedTrading::instance().cancel_order(account.account_id, c.order_id);
+            cancelled_any = true;
+        }
+        if (cancelled_any) {
+            eam_poll_children(run, account, kMarketChildMaxPolls);
+            for (int i = 0; i < run.children.size(); ++i) {
+                const ExecChild& c = run.children[i];
+                if (!c.working())
+                    continue;
+                const bool part = c.filled_qty > kEamQtyEps;
+                run.update_child(i, c.filled_qty, c.avg_price,
+                                 part ? QStringLiteral("partial") : QStringLiteral("cancelled"));
+            }
+        }
+        if (!self)
+            return;
+        QMetaObject::invokeMethod(
+            self,
+            [self, run]() {
+                if (!self)
+                    return;
+                self->in_flight_.remove(run.id);
+                self->runs_[run.id] = run;
+                const QJsonObject report = run.to_json();
+                const QJsonObject is = report.value("shortfall").toObject();
+                LOG_INFO(kEamTag, QString("%1 %2 %3: filled %4/%5, shortfall %6 bps")
+                                  .arg(run.id.left(8), QString::fromLatin1(exec_algo_type_str(run.params.type)),
+                                       run.state)
+                                  .arg(run.filled())
+                                  .arg(run.params.quantity)
+                                  .arg(is.value("shortfall_bps").toDouble(), 0, 'f', 1));
+                emit self->algo_finished(run.id, report);
+            },
+            Qt::QueuedConnection);
+    });
+}
+
+} // namespace fincept::trading
@@ -0,0 +1,68 @@
+#pragma once
+// ExecutionAlgoManager — runs execution algos (ExecutionAlgo.h) against an
+// account through UnifiedTrading, so the same TWAP / VWAP / POV / iceberg run
+// works on a paper account (simulated fills) and a live broker.
+//
+// A 1-second clock visits each running algo. For each visit it:
+//   1. pulls a quote (UnifiedTrading::get_multi_quotes);
+//   2. refreshes the fills of working children from the broker / paper order book;
+//   3. sends the child ExecAlgoRun::due_quantity() asks for.
+// The visit runs on a worker thread (broker REST calls block) and at most one
+// visit per algo is in flight. Market children on brokers that never report
+// the order back are booked at the quote they were sent against once polling
+// gives up, like AlgoEngine does for its own orders.
+//
+// When an algo finishes (parent filled, duration over, or cancelled), children
+// still working are cancelled and algo_finished carries the final report with
+// its implementation shortfall. Runs live in memory for the session.
+
+#include "trading/ExecutionAlgo.h"
+
+#include <QHash>
+#include <QJsonArray>
+#include <QObject>
+#include <QSet>
+
+class QTimer;
+
+namespace fincept::trading {
+
+class ExecutionAlgoManager : public QObject {
+    Q_OBJECT
+  public:
+    static ExecutionAlgoManager& instance();
+
+    /// Validates and starts an algo; returns its id, or empty with `error`.
+    QString start(const ExecAlgoParams& params, QString* error = nullptr);
+    /// Stops sending children and cancels the working ones. False when the id
+    /// is unknown or the algo already finished.
+    bool cancel(const QString& id);
+
+    /// Snapshot of one run (empty when unknown) / all runs, newest first.
+    QJsonObject status(const QString& id) const;
+    QJsonArray list(bool include_finished = true) const;
+    /// Copies of every run with its children, oldest first.
+    QVector<ExecAlgoRun> runs() const;
+
+  signals:
+    void algo_progress(QString id, QJsonObject status);
+    void algo_finished(QString id, QJsonObject report);
+
+  private:
+    ExecutionAlgoManager();
+    Q_DISABLE_COPY(ExecutionAlgoManager)
+
+    void on_clock();
+    void visit(const QString& id);
+    void finish(const QString& id, const QString& state, const QString& message = {});
+
+    QHash<QString, ExecAlgoRun> runs_;
+    QStringList order_; // ids, oldest first
+    QSet<QString> in_flight_;
+    QSet<QString> cancel_requested_;
+    QTimer* clock_ = nullptr;
+
+    static constexpr int kMarketChildMaxPolls = 30;
+};
+
+} // namespace fincept::trading
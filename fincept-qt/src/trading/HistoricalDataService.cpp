@@ -1,6 +1,9 @@
 // src/trading/HistoricalDataService.cpp
 #include "trading/HistoricalDataService.h"
 
+#include "core/logging/Logger.h"
+#include "core/time/Timestamps.h"
+#include "storage/HistoricalDataStore.h"
 #include "trading/AccountManager.h"
 #include "trading/BrokerInterface.h"
 #include "trading/BrokerRegistry.h"
@@ -11,6 +14,8 @@
 #include <QSet>
 #include <QtConcurrent>
 
+#include <algorithm>
+
 namespace fincept::trading {
 
 namespace {
@@ -100,6 +105,15 @@ QString hds_resolve_broker_symbol(const QString& broker_id, const QString& bare_
     return bare_symbol; // everyone else accepts the bare symbol as-is
 }
 
+// Upsert into the shared candle cache under the bare symbol, so a series reads
+// back the same whichever broker filled it.
+void hds_store(const QString& symbol, const QString& exchange, const QString& timeframe,
+               const QVector<BrokerCandle>& candles) {
+    const QString bare = (symbol.contains(':') ? symbol.section(':', 1, 1) : symbol).trimmed().toUpper();
+    if (!storage::HistoricalDataStore::instance().store_candles(bare, exchange, timeframe, candles))
+        LOG_WARN("HistoricalData", QString("Candle cache write failed for %1:%2 [%3]").arg(exchange, bare, timeframe));
+}
+
 } // namespace
 
 HistoricalDataService& HistoricalDataService::instance() {
@@ -109,12 +123,53 @@ HistoricalDataService& HistoricalDataService::instance() {
 
 void HistoricalDataService::fetch(const QString& symbol, const QString& timeframe, int lookback_days,
                                   const QString& broker_id, const QString& account_id, Callback callback) {
-    const QString key = broker_id + "|" + symbol + "|" + timeframe + "|" + QString::number(lookback_days);
+    fetch_impl(symbol, QStringLiteral("NSE"), timeframe, lookback_days, broker_id, account_id, false,
+               std::move(callback));
+}
+
+void HistoricalDataService::fetch_to_store(const QString& symbol, const QString& exchange, const QString& timeframe,
+                                           int lookback_days, const QString& broker_id, const QString& account_id,
+                                           Callback callback) {
+    fetch_impl(symbol, exchange.isEmpty() ? QStringLiteral("NSE") : exchange.toUpper(), timeframe, lookback_days,
+               broker_id, account_id, true, std::move(callback));
+}
+
+QVector<BrokerCandle> HistoricalDataService::normalize(QVector<BrokerCandle> candles) {
+    for (auto& c : candles)
+        c.timestamp = core::time::epoch_to_ms(c.timestamp);
+    candles.erase(std::remove_if(candles.begin(), candles.end(),
+                                 [](const BrokerCandle& c) { return c.timestamp <= 0 || c.close <= 0; }),
+                  candles.end());
+    std::stable_sort(candles.begin(), candles.end(),
+                     [](const BrokerCandle& a, const BrokerCandle& b) { return a.timestamp < b.timestamp; });
+    QVector<BrokerCandle> out;
+    out.reserve(candles.size());
+    for (const auto& c : candles) {
+        if (!out.isEmpty() && out.last().timestamp == c.timestamp)
+            out.last() = c; // a repeated bar — the later copy is the more complete one
+        else
+            out.append(c);
+    }
+    return out;
+}
+
+void HistoricalDataService::fetch_impl(const QString& symbol, const QString& exchange, const QString& timeframe,
+                                       int lookback_days, const QString& broker_id, const QString& account_id,
+                                       bool store, Callback callback) {
+    const QString key =
+        broker_id + "|" + exchange + "|" + symbol + "|" + timeframe + "|" + QString::number(lookback_days);
 
     // Serve from cache when fresh (main thread).
     const qint64 now = QDateTime::currentMSecsSinceEpoch();
-    if (auto it = cache_.constFind(key); it != cache_.constEnd()) {
+    if (auto it = cache_.find(key); it != cache_.end()) {
         if (now - it->fetched_ms < HDS_CACHE_TTL_MS && !it->candles.isEmpty()) {
+            if (store && !it->stored) {
+                it->stored = true;
+                const QVector<BrokerCandle> candles = it->candles;
+                (void)QtConcurrent::run([symbol, exchange, timeframe, candles]() {
+                    hds_store(symbol, exchange, timeframe, candles);
+                });
+            }
             callback(true, it->candles, {});
             return;
         }
@@ -129,7 +184,8 @@ void HistoricalDataService::fetch(const QString& symbol, const QString& timefram
     }
 
     QPointer<HistoricalDataService> self = this;
-    (void)QtConcurrent::run([self, key, symbol, timeframe, lookback_days, broker_id, creds, callback]() {
+    (void)QtConcurrent::run([self, key, symbol, exchange, timeframe, lookback_days, broker_id, store, creds,
+                             callback]() {
         auto* broker = BrokerRegistry::instance().get(broker_id);
         if (!broker) {
             QMetaObject::invokeMethod(
@@ -143,7 +199,7 @@ void HistoricalDataService::fetch(const QString& symbol, const QString& timefram
         const QString resolution = timeframe_to_resolution(timeframe);
         const QString from_s = from.toString("yyyy-MM-dd");
         const QString to_s = to.toString("yyyy-MM-dd");
-        const QString resolved = hds_resolve_broker_symbol(broker_id, symbol, QStringLiteral("NSE"));
+        const QString resolved = hds_resolve_broker_symbol(broker_id, symbol, exchange);
 
         auto has_data = [](const ApiResponse<QVector<BrokerCandle>>& r) {
             return r.success && r.data.value_or(QVector<BrokerCandle>{}).size() > 0;
@@ -167,15 +223,18 @@ void HistoricalDataService::fetch(const QString& symbol, const QString& timefram
                 last_err = r2.error;
         }
 
-        const bool ok = has_data(result);
-        const QVector<BrokerCandle> candles = result.data.value_or(QVector<BrokerCandle>{});
+        const QVector<BrokerCandle> candles = normalize(result.data.value_or(QVector<BrokerCandle>{}));
+        const bool ok = has_data(result) && !candles.isEmpty();
         const QString err = ok ? QString() : (last_err.isEmpty() ? QStringLiteral("No data") : last_err);
+        // Store from the worker — HistoricalDataStore hands out a per-thread connection.
+        if (ok && store)
+            hds_store(symbol, exchange, timeframe, candles);
 
         QMetaObject::invokeMethod(
             self ? self.data() : nullptr,
-            [self, key, ok, candles, err, callback]() {
+            [self, key, ok, candles, err, store, callback]() {
                 if (self && ok)
-                    self->cache_.insert(key, CacheEntry{QDateTime::currentMSecsSinceEpoch(), candles});
+                    self->cache_.insert(key, CacheEntry{QDateTime::currentMSecsSinceEpoch(), candles, store});
                 callback(ok, ok ? candles : QVector<BrokerCandle>{}, err);
             },
             Qt::QueuedConnection);
@@ -8,6 +8,11 @@
 // get_history call with a bare-symbol fallback, and a short TTL cache so the
 // same series isn't fetched twice when a chart + an algo open it together.
 //
+// Candles come back normalized (UTC epoch-ms timestamps, ascending, one bar per
+// timestamp) whatever the broker module returned, and fetch_to_store() also
+// upserts them into the shared candle cache (storage::HistoricalDataStore) so
+// charts can read broker history instead of a third-party vendor's.
+//
 // It returns the trading-layer BrokerCandle. The algo side converts to its own
 // OhlcvCandle and layers a Yahoo-Finance fallback on top (in CandleDataFetcher)
 // for symbols/brokers the broker history endpoint can't serve.
@@ -37,15 +42,32 @@ class HistoricalDataService : public QObject {
     void fetch(const QString& symbol, const QString& timeframe, int lookback_days, const QString& broker_id,
                const QString& account_id, Callback callback);
 
+    // fetch() for a listing on `exchange` ("NSE"/"BSE"/"US"…) that also upserts
+    // the candles into HistoricalDataStore under (symbol, exchange, timeframe).
+    // A failed store write is logged; the callback still gets the candles.
+    void fetch_to_store(const QString& symbol, const QString& exchange, const QString& timeframe, int lookback_days,
+                        const QString& broker_id, const QString& account_id, Callback callback);
+
+    // Broker modules disagree on timestamp units (epoch s / ms / µs) and some
+    // return bars newest-first or with a duplicated last bar. Converts to ms
+    // (core::time::epoch_to_ms — zones are settled when the module parses),
+    // drops bars without a timestamp or price, sorts ascending and keeps the
+    // last bar seen for each timestamp.
+    static QVector<BrokerCandle> normalize(QVector<BrokerCandle> candles);
+
   private:
     HistoricalDataService() = default;
     Q_DISABLE_COPY(HistoricalDataService)
 
+    void fetch_impl(const QString& symbol, const QString& exchange, const QString& timeframe, int lookback_days,
+                    const QString& broker_id, const QString& account_id, bool store, Callback callback);
+
     struct CacheEntry {
         qint64 fetched_ms = 0;
         QVector<BrokerCandle> candles;
+        bool stored = false; // already written to HistoricalDataStore
     };
-    QHash<QString, CacheEntry> cache_; // key: broker|symbol|tf|lookback
+    QHash<QString, CacheEntry> cache_; // key: broker|exchange|symbol|tf|lookback
 };
 
 } // namespace fincept::trading
@@ -0,0 +1,407 @@
+#include "trading/IntradayPnlService.h"
+
+#include "algo_engine/AlgoEngine.h"
+#include "core/logging/Logger.h"
+#include "datahub/DataHub.h"
+#include "trading/AccountDataStream.h"
+#include "trading/AccountManager.h"
+#include "trading/BrokerRegistry.h"
+#include "trading/DataStreamManager.h"
+#include "trading/PaperTrading.h"
+
+#include <QDateTime>
+#include <QJsonArray>
+#include <QMap>
+#include <QTimer>
+
+#include <algorithm>
+#include <cmath>
+#include <stdexcept>
+
+namespace fincept::trading {
+
+namespace {
+const QString TAG = QStringLiteral("IntradayPnl");
+const QString kConsumer = QStringLiteral("intraday-pnl");
+
+double signed_qty(const QString& side, double qty) {
+    return side.startsWith(QLatin1Char('s'), Qt::CaseInsensitive) ? -std::fabs(qty) : qty;
+}
+
+QDate ist_today() {
+    return QDateTime::currentDateTimeUtc().addSecs(5 * 3600 + 30 * 60).date();
+}
+
+void mark(IntradayPnlPosition& p, double ltp, qint64 now_ms) {
+    if (ltp > 0.0) {
+        p.ltp = ltp;
+        p.marked_ms = now_ms;
+    }
+    p.unrealized = p.quantity != 0.0 && p.ltp > 0.0 ? p.quantity * (p.ltp - p.avg_price) : 0.0;
+    p.total = p.realized + p.unrealized;
+}
+
+void add(IntradayPnlRollup& r, const IntradayPnlPosition& p) {
+    ++r.positions;
+    if (p.quantity != 0.0)
+        ++r.open_positions;
+    r.realized += p.realized;
+    r.unrealized += p.unrealized;
+    r.total += p.total;
+}
+
+QVector<IntradayPnlRollup> sorted(const QMap<QString, IntradayPnlRollup>& m) {
+    QVector<IntradayPnlRollup> out(m.cbegin(), m.cend());
+    std::sort(out.begin(), out.end(), [](const auto& a, const auto& b) { return a.total < b.total; });
+    return out;
+}
+
+QJsonArray rollups_to_json(const QVector<IntradayPnlRollup>& rows) {
+    QJsonArray arr;
+    for (const auto& r : rows)
+        arr.append(QJsonObject{{"key", r.key},
+                               {"label", r.label},
+                               {"currency", r.currency},
+                               {"positions", r.positions},
+                               {"open_positions", r.open_positions},
+                               {"realized", r.realized},
+                               {"unrealized", r.unrealized},
+                               {"total", r.total}});
+    return arr;
+}
+} // namespace
+
+QJsonObject IntradayPnlSnapshot::to_json(bool include_positions) const {
+    QJsonObject o{{"as_of", as_of_ms},
+                  {"by_account", rollups_to_json(by_account)},
+                  {"by_strategy", rollups_to_json(by_strategy)},
+                  {"by_currency", rollups_to_json(by_currency)}};
+    if (include_positions) {
+        QJsonArray rows;
+        for (const auto& p : positions)
+            rows.append(QJsonObject{{"account_id", p.account_id},
+                                    {"account", p.account_label},
+                                    {"mode", p.mode},
+                                    {"symbol", p.symbol},
+                                    {"exchange", p.exchange},
+                                    {"product", p.product},
+                                    {"strategy", p.strategy},
+                                    {"currency", p.currency},
+                                    {"quantity", p.quantity},
+                                    {"avg_price", p.avg_price},
+                                    {"ltp", p.ltp},
+                                    {"realized", p.realized},
+                                    {"unrealized", p.unrealized},
+                                    {"total", p.total},
+                                    {"marked_at", p.marked_ms}});
+        o["positions"] = rows;
+    }
+    return o;
+}
+
+IntradayPnlService& IntradayPnlService::instance() {
+    static IntradayPnlService s;
+    return s;
+}
+
+IntradayPnlService::IntradayPnlService() {
+    // The book is pushed on every throttled update, never pulled.
+    datahub::TopicPolicy policy;
+    policy.push_only = true;
+    datahub::DataHub::instance().set_policy(QString::fromLatin1(kTopic), policy);
+}
+
+void IntradayPnlService::start() {
+    if (started_)
+        return;
+    started_ = true;
+
+    // Strategy attribution follows the deployment list; reload it whenever a
+    // deployment starts or stops rather than polling the table.
+    auto& engine = algo::AlgoEngine::instance();
+    connect(&engine, &algo::AlgoEngine::deployments_loaded, this, &IntradayPnlService::on_deployments);
+    connect(&engine, &algo::AlgoEngine::deployment_started, this, [&engine]() { engine.list_deployments(); });
+    connect(&engine, &algo::AlgoEngine::deployment_stopped, this, [&engine]() { engine.list_deployments(); });
+    engine.list_deployments();
+
+    // Throttle, not debounce: a steady tick stream must still emit every kEmitMs.
+    emit_timer_ = new QTimer(this);
+    emit_timer_->setSingleShot(true);
+    emit_timer_->setInterval(kEmitMs);
+    connect(emit_timer_, &QTimer::timeout, this, &IntradayPnlService::emit_snapshot);
+
+    resync_timer_ = new QTimer(this);
+    resync_timer_->setInterval(kResyncMs);
+    connect(resync_timer_, &QTimer::timeout, this, &IntradayPnlService::resync);
+    resync_timer_->start();
+
+    resync();
+    LOG_INFO(TAG, "Intraday P&L service started");
+}
+
+void IntradayPnlService::resync() {
+    auto& dsm = DataStreamManager::instance();
+    QSet<QString> seen;
+
+    for (const auto& acct : AccountManager::instance().active_accounts()) {
+        const bool paper = acct.trading_mode == QLatin1String("paper");
+        if (paper && acct.paper_portfolio_id.isEmpty())
+            continue;
+        seen.insert(acct.account_id);
+        Acct& a = accts_[acct.account_id];
+        a.mode = paper ? QStringLiteral("paper") : QStringLiteral("live");
+        a.broker_id = acct.broker_id;
+        a.paper_portfolio_id = acct.paper_portfolio_id;
+        const auto* broker = BrokerRegistry::instance().get(acct.broker_id);
+        a.label = broker ? QString("%1 — %2").arg(broker->profile().display_name, acct.display_name)
+                         : acct.display_name;
+        if (paper) {
+            a.label += QStringLiteral(" (paper)");
+            try {
+                a.currency = pt_get_portfolio(acct.paper_portfolio_id).currency;
+            } catch (const std::exception& e) {
+                LOG_WARN(TAG, QString("Paper portfolio %1: %2").arg(acct.paper_portfolio_id, e.what()));
+                continue;
+            }
+            load_paper(acct.account_id, a);
+        } else {
+            a.currency = broker ? broker->profile().currency : QString();
+            if (auto* stream = dsm.stream_for(acct.account_id))
+                load_live(acct.account_id, a, stream->cached_positions());
+        }
+        bind_stream(acct.account_id, a);
+    }
+
+    // Drop accounts that were removed, deactivated or lost their paper link,
+    // releasing their stream subscription.
+    for (auto it = accts_.begin(); it != accts_.end();) {
+        if (!seen.contains(it.key())) {
+            QObject::disconnect(it->quote_conn);
+            QObject::disconnect(it->positions_conn);
+            if (auto* stream = qobject_cast<AccountDataStream*>(it->stream.data()))
+                stream->unsubscribe_consumer(kConsumer);
+            it = accts_.erase(it);
+        } else {
+            ++it;
+        }
+    }
+    schedule_emit();
+}
+
+void IntradayPnlService::bind_stream(const QString& account_id, Acct& a) {
+    auto& dsm = DataStreamManager::instance();
+    QStringList syms;
+    for (const auto& p : a.rows)
+        if (p.quantity != 0.0 && !syms.contains(p.symbol))
+            syms << p.symbol;
+    if (syms.isEmpty() && a.mode == QLatin1String("paper")) {
+        // Nothing to mark — release our symbols, but don't force a stream up.
+        if (auto* stream = qobject_cast<AccountDataStream*>(a.stream.data()); stream && !a.symbols.isEmpty())
+            stream->unsubscribe_consumer(kConsumer);
+        a.symbols.clear();
+        return;
+    }
+
+    dsm.start_stream(account_id);
+    auto* stream = dsm.stream_for(account_id);
+    if (!stream)
+        return;
+
+    // Rebind when the stream object changed — a token refresh destroys and
+    // recreates it, which would leave the old connections dead.
+    if (a.stream != stream) {
+        QObject::disconnect(a.quote_conn);
+        QObject::disconnect(a.positions_conn);
+        a.quote_conn = connect(stream, &AccountDataStream::quote_updated, this, &IntradayPnlService::on_quote);
+        if (a.mode == QLatin1String("live"))
+            a.positions_conn = connect(stream, &AccountDataStream::positions_updated, this,
+                                       [this](const QString& aid, const QVector<BrokerPosition>& positions) {
+                                           auto it = accts_.find(aid);
+                                           if (it == accts_.end())
+                                               return;
+                                           load_live(aid, *it, positions);
+                                           bind_stream(aid, *it);
+                                           schedule_emit();
+                                       });
+        a.stream = stream;
+        a.symbols.clear(); // force a fresh subscribe on the new stream
+    }
+
+    const QSet<QString> want(syms.cbegin(), syms.cend());
+    if (a.symbols != want) {
+        stream->subscribe_symbols(kConsumer, syms);
+        a.symbols = want;
+    }
+}
+
+void IntradayPnlService::load_live(const QString& account_id, Acct& a, const QVector<BrokerPosition>& positions) {
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    QHash<QString, IntradayPnlPosition> previous;
+    for (const auto& p : a.rows)
+        previous.insert(p.symbol + '|' + p.exchange + '|' + p.product, p);
+
+    a.rows.clear();
+    for (const auto& bp : positions) {
+        IntradayPnlPosition p;
+        p.account_id = account_id;
+        p.account_label = a.label;
+        p.mode = a.mode;
+        p.symbol = bp.symbol;
+        p.exchange = bp.exchange;
+        p.product = bp.product_type;
+        p.currency = a.currency;
+        p.quantity = signed_qty(bp.side, bp.quantity);
+        p.avg_price = bp.avg_price;
+        p.strategy = strategy_for(account_id, a, bp.symbol);
+        // Whatever of the broker's P&L the open quantity doesn't explain was
+        // booked by today's closing trades.
+        const double open_at_broker = p.quantity != 0.0 && bp.ltp > 0.0 ? p.quantity * (bp.ltp - bp.avg_price) : 0.0;
+        p.realized = bp.pnl - open_at_broker;
+        // A tick newer than the REST snapshot keeps its mark.
+        const auto prev = previous.value(p.symbol + '|' + p.exchange + '|' + p.product);
+        if (prev.marked_ms > 0 && prev.ltp > 0.0) {
+            p.ltp = prev.ltp;
+            p.marked_ms = prev.marked_ms;
+            mark(p, 0.0, now);
+        } else {
+            mark(p, bp.ltp, now);
+        }
+        a.rows.append(p);
+    }
+}
+
+void IntradayPnlService::load_paper(const QString& account_id, Acct& a) {
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    QHash<QString, double> realized;
+    for (const auto& t : pt_get_trades_for_day(a.paper_portfolio_id, ist_today()))
+        realized[t.symbol] += t.pnl;
+    QHash<QString, IntradayPnlPosition> previous;
+    for (const auto& p : a.rows)
+        previous.insert(p.symbol, p);
+
+    a.rows.clear();
+    auto make = [&](const QString& symbol) {
+        IntradayPnlPosition p;
+        p.account_id = account_id;
+        p.account_label = a.label;
+        p.mode = a.mode;
+        p.symbol = symbol;
+        p.currency = a.currency;
+        p.strategy = strategy_for(account_id, a, symbol);
+        p.realized = realized.take(symbol);
+        return p;
+    };
+    for (const auto& pp : pt_get_positions(a.paper_portfolio_id)) {
+        if (pp.quantity == 0.0)
+            continue;
+        auto p = make(pp.symbol);
+        p.product = pp.product;
+        p.quantity = signed_qty(pp.side, pp.quantity);
+        p.avg_price = pp.entry_price;
+        // The stored mark lags ticks by PaperMarkService's flush window.
+        const auto prev = previous.value(pp.symbol);
+        if (prev.marked_ms > 0 && prev.ltp > 0.0) {
+            p.ltp = prev.ltp;
+            p.marked_ms = prev.marked_ms;
+            mark(p, 0.0, now);
+        } else {
+            mark(p, pp.current_price, now);
+        }
+        a.rows.append(p);
+    }
+    // Symbols closed out earlier today.
+    for (auto it = realized.cbegin(); it != realized.cend(); ++it) {
+        auto p = make(it.key());
+        p.realized = it.value();
+        mark(p, 0.0, now);
+        a.rows.append(p);
+    }
+}
+
+void IntradayPnlService::on_quote(const QString& account_id, const QString& symbol, const BrokerQuote& quote) {
+    auto it = accts_.find(account_id);
+    if (it == accts_.end() || quote.ltp <= 0.0)
+        return;
+    const qint64 now = QDateTime::currentMSecsSinceEpoch();
+    bool changed = false;
+    for (auto& p : it->rows)
+        if (p.symbol == symbol && p.quantity != 0.0 && p.ltp != quote.ltp) {
+            mark(p, quote.ltp, now);
+            changed = true;
+        }
+    if (changed)
+        schedule_emit();
+}
+
+void IntradayPnlService::on_deployments(const QVector<services::algo::AlgoDeployment>& deployments) {
+    deployments_.clear();
+    for (const auto& d : deployments)
+        if (d.status != QLatin1String("stopped") && !d.is_shadow())
+            deployments_.append(d);
+    for (auto it = accts_.begin(); it != accts_.end(); ++it)
+        for (auto& p : it->rows)
+            p.strategy = strategy_for(it.key(), *it, p.symbol);
+    schedule_emit();
+}
+
+QString IntradayPnlService::strategy_for(const QString& account_id, const Acct& a, const QString& symbol) const {
+    for (const auto& d : deployments_) {
+        const bool same_book = d.backend == QLatin1String("paper")
+                                   ? a.mode == QLatin1String("paper") && d.paper_portfolio_id == a.paper_portfolio_id
+                                   : a.mode == QLatin1String("live") &&
+                                         (d.broker_account_id == account_id ||
+                                          (d.broker_account_id.isEmpty() && d.broker_id == a.broker_id));
+        if (!same_book)
+            continue;
+        const bool derivative = d.instrument_type != QLatin1String("equity") && !d.underlying.isEmpty();
+        if (symbol == d.symbol || (derivative && symbol.startsWith(d.underlying)))
+            return d.strategy_name.isEmpty() ? d.strategy_id : d.strategy_name;
+    }
+    return QStringLiteral("Manual");
+}
+
+IntradayPnlSnapshot IntradayPnlService::snapshot(const QString& account_id) const {
+    IntradayPnlSnapshot s;
+    s.as_of_ms = QDateTime::currentMSecsSinceEpoch();
+    QMap<QString, IntradayPnlRollup> accounts, strategies, currencies;
+    for (auto it = accts_.cbegin(); it != accts_.cend(); ++it) {
+        if (!account_id.isEmpty() && it.key() != account_id)
+            continue;
+        auto& acc = accounts[it.key()];
+        acc.key = it.key();
+        acc.label = it->label;
+        acc.currency = it->currency;
+        for (const auto& p : it->rows) {
+            s.positions.append(p);
+            add(acc, p);
+            auto& st = strategies[p.strategy + '|' + p.currency];
+            st.key = p.strategy;
+            st.label = p.strategy;
+            st.currency = p.currency;
+            add(st, p);
+            auto& cur = currencies[p.currency];
+            cur.key = p.currency;
+            cur.label = p.currency;
+            cur.currency = p.currency;
+            add(cur, p);
+        }
+    }
+    std::sort(s.positions.begin(), s.positions.end(), [](const auto& a, const auto& b) { return a.total < b.total; });
+    s.by_account = sorted(accounts);
+    s.by_strategy = sorted(strategies);
+    s.by_currency = sorted(currencies);
+    return s;
+}
+
+void IntradayPnlService::schedule_emit() {
+    if (emit_timer_ && !emit_timer_->isActive())
+        emit_timer_->start();
+}
+
+void IntradayPnlService::emit_snapshot() {
+    const auto s = snapshot();
+    datahub::DataHub::instance().publish(QString::fromLatin1(kTopic), s.to_json().toVariantMap());
+    emit pnl_updated(s);
+}
+
+} // namespace fincept::trading
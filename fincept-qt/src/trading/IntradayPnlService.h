@@ -0,0 +1,133 @@
+#pragma once
+// IntradayPnlService — one streaming P&L book for every active account, broker
+// and paper alike, so P&L headers read a finished number instead of each screen
+// recomputing it from positions and quotes.
+//
+// Positions come from each account's AccountDataStream (live accounts) or the
+// paper engine (pt_get_positions) and are re-read on a 5 s resync; the stream's
+// quote_updated ticks re-mark them in between. Per position:
+//   unrealized = signed qty × (ltp − avg price)
+//   realized   = today's closed P&L. Paper: the day's pt_trades. Broker: the
+//                broker's position P&L minus its unrealized part at the
+//                broker's own LTP, so a fully closed intraday row is all realized.
+// Rollups are per account, per strategy (the AlgoEngine deployment trading the
+// symbol on that account, else "Manual") and per currency — accounts are never
+// summed across currencies.
+//
+// Updates are throttled to one per kEmitMs: pnl_updated() plus the push-only
+// `pnl:intraday` hub topic (payload: IntradayPnlSnapshot::to_json()).
+
+#include "services/algo_trading/AlgoTradingTypes.h"
+#include "trading/TradingTypes.h"
+
+#include <QHash>
+#include <QJsonObject>
+#include <QMetaObject>
+#include <QObject>
+#include <QPointer>
+#include <QSet>
+#include <QString>
+#include <QVector>
+
+class QTimer;
+
+namespace fincept::trading {
+
+struct IntradayPnlPosition {
+    QString account_id;
+    QString account_label;
+    QString mode; // "live" | "paper"
+    QString symbol;
+    QString exchange;
+    QString product;
+    QString strategy; // deployment strategy name, or "Manual"
+    QString currency;
+    double quantity = 0; // signed; 0 once closed for the day
+    double avg_price = 0;
+    double ltp = 0;
+    double realized = 0;
+    double unrealized = 0;
+    double total = 0;
+    qint64 marked_ms = 0; // last tick / refresh that moved ltp
+};
+
+struct IntradayPnlRollup {
+    QString key; // account_id, strategy name or currency
+    QString label;
+    QString currency;
+    int positions = 0;
+    int open_positions = 0;
+    double realized = 0;
+    double unrealized = 0;
+    double total = 0;
+};
+
+struct IntradayPnlSnapshot {
+    QVector<IntradayPnlPosition> positions; // worst total first
+    QVector<IntradayPnlRollup> by_account;
+    QVector<IntradayPnlRollup> by_strategy; // keyed per strategy and currency
+    QVector<IntradayPnlRollup> by_currency;
+    qint64 as_of_ms = 0;
+
+    QJsonObject to_json(bool include_positions = true) const;
+};
+
+class IntradayPnlService : public QObject {
+    Q_OBJECT
+  public:
+    static IntradayPnlService& instance();
+
+    /// Start the resync loop and the deployment listener. Idempotent. Call
+    /// after AccountManager and DataStreamManager are ready.
+    void start();
+
+    /// Re-read every active account's positions and realized P&L and (re)bind
+    /// its stream. Runs on a timer; call directly for an immediate refresh.
+    void resync();
+
+    /// Current book, computed from the latest marks; one account's slice when
+    /// `account_id` is set.
+    IntradayPnlSnapshot snapshot(const QString& account_id = {}) const;
+
+    static constexpr const char* kTopic = "pnl:intraday";
+
+  signals:
+    void pnl_updated(const fincept::trading::IntradayPnlSnapshot& snapshot);
+
+  private:
+    IntradayPnlService();
+    Q_DISABLE_COPY(IntradayPnlService)
+
+    struct Acct {
+        QString label;
+        QString broker_id;
+        QString mode;
+        QString currency;
+        QString paper_portfolio_id;
+        QVector<IntradayPnlPosition> rows;
+        QPointer<QObject> stream; // bound AccountDataStream (detect recreation on re-auth)
+        QMetaObject::Connection quote_conn;
+        QMetaObject::Connection positions_conn;
+        QSet<QString> symbols; // currently subscribed on the stream
+    };
+
+    void bind_stream(const QString& account_id, Acct& a);
+    void load_live(const QString& account_id, Acct& a, const QVector<BrokerPosition>& positions);
+    void load_paper(const QString& account_id, Acct& a);
+    void on_quote(const QString& account_id, const QString& symbol, const BrokerQuote& quote);
+    void on_deployments(const QVector<services::algo::AlgoDeployment>& deployments);
+    QString strategy_for(const QString& account_id, const Acct& a, const QString& symbol) const;
+    void schedule_emit();
+    void emit_snapshot();
+
+    QHash<QString, Acct> accts_; // account_id → book
+    QVector<services::algo::AlgoDeployment> deployments_;
+    QTimer* resync_timer_ = nullptr;
+    QTimer* emit_timer_ = nullptr;
+    bool started_ = false;
+
+    static constexpr int kResyncMs = 5000;
+    static constexpr int kEmitMs = 500;
+};
+
+} // namespace fincept::trading
@@ -6,6 +6,8 @@
 
 #include "core/events/EventBus.h"
 #include "core/logging/Logger.h"
+#include "core/market/MarketCalendar.h"
+#include "services/audit/AuditTrail.h"
 #include "storage/repositories/PaperTradingRepository.h"
 #include "storage/sqlite/Database.h"
 
@@ -151,26 +153,7 @@ double pt_calculate_required_margin(const QString& portfolio_id, const QString&
 // ============================================================================
 
 bool pt_is_market_open(const QString& exchange) {
-    // IST = UTC + 5:30.
-    QDateTime ist = QDateTime::currentDateTimeUtc().addSecs(5 * 3600 + 30 * 60);
-    QTime t = ist.time();
-    const QString ex = exchange.toUpper();
-
-    if (ex == "NSE" || ex == "BSE" || ex == "NFO" || ex == "BFO") {
-        // Equity / equity derivatives: 09:15 – 15:30 IST.
-        return t >= QTime(9, 15) && t <= QTime(15, 30);
-    }
-    if (ex == "MCX") {
-        // Commodities: 09:00 – 23:30 IST.
-        return t >= QTime(9, 0) && t <= QTime(23, 30);
-    }
-    if (ex == "CDS" || ex == "BCD") {
-        // Currency derivatives: 09:00 – 17:00 IST.
-        return t >= QTime(9, 0) && t <= QTime(17, 0);
-    }
-
-    // Unknown / crypto / international: always open (24/7).
-    return true;
+    return core::market::MarketCalendar::instance().is_market_open(exchange);
 }
 
 // ============================================================================
@@ -233,6 +216,7 @@ QVector<PtPortfolio> pt_list_portfolios(const QString& exchange) {
 }
 
 void pt_delete_portfolio(const QString& id) {
+    services::audit::AuditTrail::instance().record("destructive", "paper_portfolio_deleted", id);
     repo().delete_all_trades(id);
     repo().cancel_all_orders(id);
     repo().delete_all_positions(id);
@@ -241,6 +225,7 @@ void pt_delete_portfolio(const QString& id) {
 
 PtPortfolio pt_reset_portfolio(const QString& id) {
     pt_get_portfolio(id); // verify exists
+    services::audit::AuditTrail::instance().record("destructive", "paper_portfolio_reset", id);
     repo().delete_all_trades(id);
     repo().cancel_all_orders(id);
     repo().delete_all_positions(id);
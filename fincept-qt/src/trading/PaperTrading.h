@@ -51,10 +51,10 @@ double pt_calculate_required_margin(const QString& portfolio_id, const QString&
                                     const QString& product, double quantity, double price, const QString& side);
 
 // --- Exchange hours (Phase 3 §4) ---
-// IST-aware (UTC+5:30) market-open check. NSE/BSE/NFO/BFO 09:15–15:30,
-// MCX 09:00–23:30, CDS 09:00–17:00; all other/unknown exchanges (incl. crypto)
-// are treated as always open. This is a free function — enforcement is opt-in
-// per portfolio via pt_set_enforce_market_hours().
+// Regular-session check against the exchange calendar (core/market/
+// MarketCalendar): weekends and trading holidays read as closed; crypto and
+// unknown exchanges are treated as always open. This is a free function —
+// enforcement is opt-in per portfolio via pt_set_enforce_market_hours().
 bool pt_is_market_open(const QString& exchange);
 
 // --- Orders ---
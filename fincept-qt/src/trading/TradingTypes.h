@@ -373,6 +373,20 @@ struct BrokerCandle {
     double oi = 0;
 };
 
+// Synthetic event published after a streaming socket reconnects: the 1m bars
+// covering [from_ms, to_ms) for a symbol that was streaming before the drop.
+// Candle builders merge these instead of bridging the hole with the first
+// post-reconnect tick. Timestamps are epoch ms; `candles` is empty when the
+// market was shut for the whole outage.
+struct BrokerGapFill {
+    QString broker_id;
+    QString account_id;
+    QString symbol;
+    qint64 from_ms = 0;
+    qint64 to_ms = 0;
+    QVector<BrokerCandle> candles;
+};
+
 struct BrokerFunds {
     double available_balance = 0;
     double used_margin = 0;
@@ -883,3 +897,4 @@ Q_DECLARE_METATYPE(QVector<fincept::trading::BrokerPosition>)
 Q_DECLARE_METATYPE(QVector<fincept::trading::BrokerHolding>)
 Q_DECLARE_METATYPE(QVector<fincept::trading::BrokerOrderInfo>)
 Q_DECLARE_METATYPE(QVector<fincept::trading::BrokerQuote>)
+Q_DECLARE_METATYPE(fincept::trading::BrokerGapFill)
@@ -3,6 +3,7 @@
 #include "trading/UnifiedTrading.h"
 
 #include "core/logging/Logger.h"
+#include "services/audit/AuditTrail.h"
 #include "storage/sqlite/Database.h"
 #include "trading/AccountManager.h"
 #include "trading/DataStreamManager.h"
@@ -25,6 +26,17 @@
 
 namespace fincept::trading {
 
+// Cancels and modifies have no EventBus event; they go to the audit trail here.
+static void audit_order_action(const char* action, const QString& account_id, const QString& order_id,
+                               const QJsonObject& modifications, const UnifiedOrderResponse& resp) {
+    QJsonObject detail{{"order_id", order_id}, {"success", resp.success}, {"mode", resp.mode}};
+    if (!modifications.isEmpty())
+        detail["modifications"] = modifications;
+    if (!resp.success)
+        detail["error"] = resp.message;
+    services::audit::AuditTrail::instance().record("order", QString::fromLatin1(action), account_id, detail);
+}
+
 UnifiedTrading& UnifiedTrading::instance() {
     static UnifiedTrading ut;
     return ut;
@@ -226,22 +238,23 @@ UnifiedOrderResponse UnifiedTrading::cancel_order(const QString& account_id, con
     if (account.account_id.isEmpty())
         return {false, "", "Account not found: " + account_id, ""};
 
+    UnifiedOrderResponse resp;
     if (account.trading_mode == "paper") {
         try {
             pt_cancel_order(order_id);
-            return {true, order_id, "Paper order cancelled", "paper"};
+            resp = {true, order_id, "Paper order cancelled", "paper"};
         } catch (const std::exception& e) {
-            return {false, "", QString("Cancel failed: %1").arg(e.what()), "paper"};
+            resp = {false, "", QString("Cancel failed: %1").arg(e.what()), "paper"};
         }
+    } else if (auto* broker = BrokerRegistry::instance().get(account.broker_id)) {
+        auto creds = AccountManager::instance().load_credentials(account_id);
+        auto result = broker->cancel_order(creds, order_id);
+        resp = {result.success, order_id, result.error, "live"};
+    } else {
+        resp = {false, "", "Broker not found: " + account.broker_id, "live"};
     }
-
-    auto* broker = BrokerRegistry::instance().get(account.broker_id);
-    if (!broker)
-        return {false, "", "Broker not found: " + account.broker_id, "live"};
-
-    auto creds = AccountManager::instance().load_credentials(account_id);
-    auto result = broker->cancel_order(creds, order_id);
-    return {result.success, order_id, result.error, "live"};
+    audit_order_action("order_cancelled", account_id, order_id, {}, resp);
+    return resp;
 }
 
 UnifiedOrderResponse UnifiedTrading::modify_order(const QString& account_id, const QString& order_id,
@@ -259,7 +272,9 @@ UnifiedOrderResponse UnifiedTrading::modify_order(const QString& account_id, con
 
     auto creds = AccountManager::instance().load_credentials(account_id);
     auto result = broker->modify_order(creds, order_id, modifications);
-    return {result.success, order_id, result.error, "live"};
+    const UnifiedOrderResponse resp{result.success, order_id, result.error, "live"};
+    audit_order_action("order_modified", account_id, order_id, modifications, resp);
+    return resp;
 }
 
 UnifiedOrderResponse UnifiedTrading::place_paper_order_for_account(const QString& account_id,
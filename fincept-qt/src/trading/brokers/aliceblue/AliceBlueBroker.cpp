@@ -1,5 +1,6 @@
 #include "trading/brokers/aliceblue/AliceBlueBroker.h"
 
+#include "core/time/Timestamps.h"
 #include "trading/adapter/BrokerEnumMap.h"
 #include "trading/brokers/BrokerHttp.h"
 #include "trading/brokers/BrokerTokenUtil.h"
@@ -583,13 +584,12 @@ ApiResponse<QVector<BrokerCandle>> AliceBlueBroker::get_history(const BrokerCred
     QVector<BrokerCandle> result;
     result.reserve(candles.size());
 
+    const QTimeZone ist = core::time::exchange_zone("NSE");
     for (const auto& item : candles) {
         QJsonObject c = item.toObject();
         BrokerCandle candle;
-        // Response fields: time (YYYY-MM-DD HH:MM:SS), open, high, low, close, volume
-        QDateTime dt = QDateTime::fromString(c["time"].toString(), "yyyy-MM-dd HH:mm:ss");
-        dt.setTimeZone(QTimeZone(19800)); // interpret wall-clock as IST (+5:30)
-        candle.timestamp = dt.toMSecsSinceEpoch();
+        // Response fields: time (YYYY-MM-DD HH:MM:SS, IST wall-clock), open, high, low, close, volume
+        candle.timestamp = core::time::to_utc_ms(c["time"].toString(), ist);
         candle.open = c["open"].toDouble();
         candle.high = c["high"].toDouble();
         candle.low = c["low"].toDouble();
@@ -1,6 +1,7 @@
 #include "trading/brokers/alpaca/AlpacaWebSocket.h"
 
 #include "core/logging/Logger.h"
+#include "core/time/Timestamps.h"
 #include "network/websocket/WebSocketClient.h"
 #include "trading/AccountManager.h"
 #include "trading/BrokerRegistry.h"
@@ -25,6 +26,7 @@ QString compact_json(const QJsonObject& obj) {
 AlpacaWebSocket::AlpacaWebSocket(const QString& api_key, const QString& api_secret, QObject* parent)
     : QObject(parent), api_key_(api_key), api_secret_(api_secret) {
     ws_ = new fincept::WebSocketClient(this);
+    ws_->set_label("alpaca");
     connect(ws_, &fincept::WebSocketClient::connected, this, &AlpacaWebSocket::on_ws_connected);
     connect(ws_, &fincept::WebSocketClient::disconnected, this, &AlpacaWebSocket::on_ws_disconnected);
     connect(ws_, &fincept::WebSocketClient::message_received, this, &AlpacaWebSocket::on_ws_message);
@@ -376,13 +378,8 @@ void AlpacaWebSocket::handle_bar(const QJsonObject& msg) {
 // ── Helpers ─────────────────────────────────────────────────────────────────
 
 int64_t AlpacaWebSocket::parse_rfc3339_to_ms(const QString& ts) {
-    if (ts.isEmpty())
-        return 0;
-    const auto dt = QDateTime::fromString(ts, Qt::ISODateWithMs);
-    if (dt.isValid())
-        return dt.toMSecsSinceEpoch();
-    const auto dt2 = QDateTime::fromString(ts, Qt::ISODate);
-    return dt2.isValid() ? dt2.toMSecsSinceEpoch() : 0;
+    // RFC-3339 with nanoseconds ("2024-03-28T13:30:00.123456789Z"); Z is always present.
+    return core::time::to_utc_ms(ts, QTimeZone::utc());
 }
 
 } // namespace fincept::trading
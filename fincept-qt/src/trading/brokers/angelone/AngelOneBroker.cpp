@@ -9,6 +9,7 @@
 #include "trading/brokers/angelone/AngelOneBroker.h"
 
 #include "core/logging/Logger.h"
+#include "core/time/Timestamps.h"
 #include "trading/adapter/BrokerEnumMap.h"
 #include "trading/brokers/BrokerHttp.h"
 #include "trading/brokers/BrokerTokenUtil.h"
@@ -1044,19 +1045,15 @@ ApiResponse<QVector<BrokerCandle>> AngelOneBroker::get_history(const BrokerCrede
 
         // data is array of [timestamp, open, high, low, close, volume]
         auto arr = resp.json.value("data").toArray();
+        const QTimeZone ist = core::time::exchange_zone("NSE");
         for (const auto& v : arr) {
             auto row = v.toArray();
             if (row.size() < 6)
                 continue;
             BrokerCandle c;
-            // AngelOne returns ISO 8601: "2025-03-03T00:00:00+05:30"
-            QString dt_str = row[0].toString();
-            QDateTime dt = QDateTime::fromString(dt_str, Qt::ISODate);
-            if (!dt.isValid())
-                dt = QDateTime::fromString(dt_str, "yyyy-MM-dd HH:mm");
-            if (!dt.isValid())
-                dt = QDateTime::fromString(dt_str, "yyyy-MM-ddTHH:mm:ss");
-            c.timestamp = dt.toMSecsSinceEpoch();
+            // AngelOne returns ISO 8601: "2025-03-03T00:00:00+05:30"; a string
+            // without the offset is IST wall-clock.
+            c.timestamp = core::time::to_utc_ms(row[0].toString(), ist);
             // AngelOne returns OHLCV as numbers (not strings)
             c.open = row[1].toDouble();
             c.high = row[2].toDouble();
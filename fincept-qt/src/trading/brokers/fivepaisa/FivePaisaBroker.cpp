@@ -1,5 +1,6 @@
 #include "trading/brokers/fivepaisa/FivePaisaBroker.h"
 
+#include "core/time/Timestamps.h"
 #include "trading/brokers/BrokerHttp.h"
 #include "trading/brokers/BrokerTokenUtil.h"
 
@@ -666,11 +667,8 @@ ApiResponse<QVector<BrokerCandle>> FivePaisaBroker::get_history(const BrokerCred
             continue;
 
         // [timestamp_str, open, high, low, close, volume]
-        // timestamp: "YYYY-MM-DDTHH:MM:SS" (UTC)
-        QString time_str = c[0].toString();
-        QDateTime dt = QDateTime::fromString(time_str, Qt::ISODate);
-        if (!dt.isValid())
-            dt = QDateTime::fromString(time_str, "yyyy-MM-ddTHH:mm:ss");
+        // timestamp: "YYYY-MM-DDTHH:MM:SS" (UTC) — read as UTC, not the machine's zone
+        const qint64 ts_ms = core::time::to_utc_ms(c[0].toString(), QTimeZone::utc());
 
         double open = c[1].toDouble();
         double high = c[2].toDouble();
@@ -683,7 +681,7 @@ ApiResponse<QVector<BrokerCandle>> FivePaisaBroker::get_history(const BrokerCred
             continue;
 
         BrokerCandle candle;
-        candle.timestamp = dt.toMSecsSinceEpoch();
+        candle.timestamp = ts_ms;
         candle.open = open;
         candle.high = high;
         candle.low = low;
@@ -1,5 +1,6 @@
 #include "trading/brokers/flattrade/FlattradeBroker.h"
 
+#include "core/time/Timestamps.h"
 #include "trading/brokers/BrokerHttp.h"
 #include "trading/brokers/BrokerTokenUtil.h"
 
@@ -691,13 +692,12 @@ ApiResponse<QVector<BrokerCandle>> FlattradeBroker::get_history(const BrokerCred
         if (!doc.isArray())
             return {false, std::nullopt, "get_history: invalid response", ts};
 
+        const QTimeZone ist = core::time::exchange_zone("NSE");
         for (const QJsonValue& v : doc.array()) {
             QJsonObject o = v.toObject();
-            // Timestamp format: "DD-MM-YYYY HH:MM:SS"
-            QString time_str = o.value("time").toString();
-            QDateTime dt = QDateTime::fromString(time_str, "dd-MM-yyyy HH:mm:ss");
+            // Timestamp format: "DD-MM-YYYY HH:MM:SS", IST wall-clock
             BrokerCandle c;
-            c.timestamp = dt.isValid() ? dt.toSecsSinceEpoch() * 1000LL : 0LL;
+            c.timestamp = core::time::to_utc_ms(o.value("time").toString(), ist);
             c.open = o.value("into").toString().toDouble();
             c.high = o.value("inth").toString().toDouble();
             c.low = o.value("intl").toString().toDouble();
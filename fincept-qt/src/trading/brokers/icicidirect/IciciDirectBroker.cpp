@@ -1,6 +1,7 @@
 #include "trading/brokers/icicidirect/IciciDirectBroker.h"
 
 #include "core/logging/Logger.h"
+#include "core/time/Timestamps.h"
 #include "trading/brokers/BrokerHttp.h"
 #include "trading/brokers/BrokerTokenUtil.h"
 #include "trading/instruments/InstrumentService.h"
@@ -577,14 +578,11 @@ ApiResponse<QVector<BrokerCandle>> IciciDirectBroker::get_history(const BrokerCr
         return {false, std::nullopt, checked_error(resp, "Failed to fetch history"), ts};
 
     QVector<BrokerCandle> candles;
+    const QTimeZone ist = core::time::exchange_zone("NSE"); // "datetime" is naive exchange time
     for (const auto& v : resp.json.value("Success").toArray()) {
         const auto o = v.toObject();
         BrokerCandle c;
-        const QString dt = jstr(o, "datetime");
-        QDateTime when = QDateTime::fromString(dt, Qt::ISODate);
-        if (!when.isValid())
-            when = QDateTime::fromString(dt, "yyyy-MM-dd hh:mm:ss");
-        c.timestamp = when.isValid() ? when.toMSecsSinceEpoch() : 0; // BrokerCandle contract = ms
+        c.timestamp = core::time::to_utc_ms(jstr(o, "datetime"), ist); // BrokerCandle contract = UTC ms
         c.open = jnum(o, "open");
         c.high = jnum(o, "high");
         c.low = jnum(o, "low");
@@ -1,5 +1,6 @@
 #include "trading/brokers/paytm/PaytmBroker.h"
 
+#include "core/time/Timestamps.h"
 #include "trading/brokers/BrokerHttp.h"
 
 #include <QDateTime>
@@ -41,27 +42,8 @@ static QString api_exchange_for_history(const QString& exchange) {
 // whose time field shape is unknown. Handles numeric seconds, numeric ms, and a
 // few string date/datetime formats (interpreted as IST). Returns 0 on failure.
 static int64_t pm_history_epoch_ms(const QJsonValue& v) {
-    if (v.isDouble()) {
-        const int64_t n = static_cast<int64_t>(v.toVariant().toLongLong());
-        return (n > 1000000000000LL) ? n : n * 1000LL; // 13-digit ms vs 10-digit s
-    }
-    const QString s = v.toVariant().toString();
-    if (s.isEmpty())
-        return 0;
-    bool ok = false;
-    const qint64 n = s.toLongLong(&ok);
-    if (ok)
-        return (n > 1000000000000LL) ? n : n * 1000LL;
-    const QTimeZone ist(19800); // +5:30
-    for (const char* fmt : {"yyyy-MM-ddTHH:mm:ss", "yyyy-MM-dd HH:mm:ss", "yyyy-MM-dd"}) {
-        QDateTime dt = QDateTime::fromString(s, fmt);
-        if (dt.isValid()) {
-            dt.setTimeZone(ist);
-            return dt.toMSecsSinceEpoch();
-        }
-    }
-    QDateTime iso = QDateTime::fromString(s, Qt::ISODate);
-    return iso.isValid() ? iso.toMSecsSinceEpoch() : 0;
+    static const QTimeZone ist = core::time::exchange_zone("NSE");
+    return core::time::to_utc_ms(v, ist);
 }
 
 // ---------- Static helpers ----------
@@ -1,5 +1,6 @@
 #include "trading/brokers/samco/SamcoBroker.h"
 
+#include "core/time/Timestamps.h"
 #include "trading/brokers/BrokerHttp.h"
 #include "trading/brokers/BrokerTokenUtil.h"
 
@@ -580,15 +581,12 @@ ApiResponse<QVector<BrokerCandle>> SamcoBroker::get_history(const BrokerCredenti
         return {false, std::nullopt, checked_error(resp, "get_history failed"), ts};
 
     QVector<BrokerCandle> candles;
+    const QTimeZone ist = core::time::exchange_zone("NSE");
     for (const QJsonValue& v : obj.value(data_key).toArray()) {
         QJsonObject o = v.toObject();
         BrokerCandle c;
-        // Daily: "date" (yyyy-MM-dd). Intraday: "dateTime" (yyyy-MM-dd HH:mm:ss).
-        QString date_str = o.value("dateTime").toString(o.value("date").toString());
-        QDateTime dt = QDateTime::fromString(date_str, "yyyy-MM-dd HH:mm:ss");
-        if (!dt.isValid())
-            dt = QDateTime(QDate::fromString(date_str.left(10), "yyyy-MM-dd"), QTime(0, 0, 0));
-        c.timestamp = dt.isValid() ? dt.toSecsSinceEpoch() * 1000LL : 0LL;
+        // Daily: "date" (yyyy-MM-dd). Intraday: "dateTime" (yyyy-MM-dd HH:mm:ss). Both IST.
+        c.timestamp = core::time::to_utc_ms(o.value("dateTime").toString(o.value("date").toString()), ist);
         c.open = samco_d(o.value("open"));
         c.high = samco_d(o.value("high"));
         c.low = samco_d(o.value("low"));
@@ -5,11 +5,13 @@
 #include <QDateTime>
 #include <QJsonArray>
 #include <QJsonDocument>
+#include <QMap>
 #include <QRegularExpression>
 #include <QTimeZone>
 #include <QUrlQuery>
 
 #include <algorithm>
+#include <cmath>
 
 namespace fincept::trading {
 
@@ -746,6 +748,104 @@ ApiResponse<QVector<BrokerCandle>> TradierBroker::get_history(const BrokerCreden
     return {true, candles, "", ts};
 }
 
+// ---------- get_option_chain ----------
+// GET /v1/markets/options/expirations?symbol=SPY   (only when no expiry given)
+// GET /v1/markets/options/chains?symbol=SPY&expiration=2026-05-29
+// Calls and puts arrive as one flat list; rows are paired by strike. With
+// strike_count > 0 only that many strikes either side of the ATM strike stay.
+
+ApiResponse<QVector<OptionChainEntry>> TradierBroker::get_option_chain(const BrokerCredentials& creds,
+                                                                       const QString& underlying,
+                                                                       const QString& /*exchange*/,
+                                                                       const QString& expiry, int strike_count) {
+    int64_t ts = now_ts();
+    const QString ticker = (underlying.contains(':') ? underlying.section(':', 1) : underlying).trimmed().toUpper();
+    if (ticker.isEmpty())
+        return {false, std::nullopt, "get_option_chain: missing underlying", ts};
+
+    auto& http = BrokerHttp::instance();
+    QString expiration = expiry.trimmed();
+    if (expiration.isEmpty()) {
+        auto resp = http.get(base(creds) + "/v1/markets/options/expirations?symbol=" + ticker, auth_headers(creds));
+        if (!resp.success)
+            return {false, std::nullopt, checked_error(resp, "get_option_chain: expirations failed"), ts};
+        const QJsonValue dates =
+            QJsonDocument::fromJson(resp.raw_body.toUtf8()).object().value("expirations").toObject().value("date");
+        expiration = dates.isArray() ? dates.toArray().first().toString() : dates.toString();
+        if (expiration.isEmpty())
+            return {false, std::nullopt, "get_option_chain: no expirations for " + ticker, ts};
+    }
+
+    auto resp = http.get(base(creds) + "/v1/markets/options/chains?symbol=" + ticker + "&expiration=" + expiration,
+                         auth_headers(creds));
+    if (!resp.success)
+        return {false, std::nullopt, checked_error(resp, "get_option_chain failed"), ts};
+    QJsonDocument doc = QJsonDocument::fromJson(resp.raw_body.toUtf8());
+    if (!doc.isObject())
+        return {false, std::nullopt, "get_option_chain: invalid response", ts};
+
+    QMap<double, OptionChainEntry> by_strike;
+    for (const QJsonValue& v : normalize_array(doc.object().value("options").toObject().value("option"))) {
+        const QJsonObject o = v.toObject();
+        const double strike = o.value("strike").toDouble();
+        if (strike <= 0)
+            continue;
+        BrokerQuote q;
+        q.symbol = o.value("symbol").toString();
+        q.ltp = o.value("last").toDouble();
+        q.open = o.value("open").toDouble();
+        q.high = o.value("high").toDouble();
+        q.low = o.value("low").toDouble();
+        q.close = o.value("prevclose").toDouble();
+        q.volume = o.value("volume").toDouble();
+        q.change = o.value("change").toDouble();
+        q.change_pct = o.value("change_percentage").toDouble();
+        q.bid = o.value("bid").toDouble();
+        q.ask = o.value("ask").toDouble();
+        q.bid_size = o.value("bidsize").toDouble();
+        q.ask_size = o.value("asksize").toDouble();
+        q.oi = static_cast<qint64>(o.value("open_interest").toDouble());
+        q.timestamp = ts;
+
+        OptionChainEntry& e = by_strike[strike];
+        e.strike_price = strike;
+        if (o.value("option_type").toString() == "call") {
+            e.ce_symbol = q.symbol;
+            e.ce_quote = q;
+        } else {
+            e.pe_symbol = q.symbol;
+            e.pe_quote = q;
+        }
+    }
+    QVector<OptionChainEntry> rows = by_strike.values();
+    if (rows.isEmpty())
+        return {false, std::nullopt, "get_option_chain: no contracts for " + ticker + " " + expiration, ts};
+
+    // Label strikes relative to the underlying (ITM/OTM from the call side).
+    auto spot_resp = get_quotes(creds, {ticker});
+    const double spot = (spot_resp.success && spot_resp.data && !spot_resp.data->isEmpty())
+                            ? spot_resp.data->first().ltp
+                            : 0.0;
+    if (spot > 0) {
+        int atm = 0;
+        for (int i = 1; i < rows.size(); ++i) {
+            if (std::abs(rows[i].strike_price - spot) < std::abs(rows[atm].strike_price - spot))
+                atm = i;
+        }
+        for (int i = 0; i < rows.size(); ++i) {
+            const int dist = std::abs(i - atm);
+            rows[i].label = i == atm ? QStringLiteral("ATM") : QString(i < atm ? "ITM%1" : "OTM%1").arg(dist);
+        }
+        if (strike_count > 0) {
+            const int first = std::max(0, atm - strike_count);
+            const int last = std::min<int>(rows.size() - 1, atm + strike_count);
+            rows = rows.mid(first, last - first + 1);
+        }
+    }
+
+    return {true, rows, "", ts};
+}
+
 // ============================================================================
 // Pre-trade margin calculator — fallback estimator.
 // Tradier exposes no per-order margin endpoint, so we use the shared heuristic
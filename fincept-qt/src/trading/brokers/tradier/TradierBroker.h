@@ -67,6 +67,10 @@ class TradierBroker : public IBroker {
     ApiResponse<QVector<BrokerCandle>> get_history(const BrokerCredentials& creds, const QString& symbol,
                                                    const QString& resolution, const QString& from_date,
                                                    const QString& to_date) override;
+    /// Chain for one expiration (nearest when `expiry` is empty); `exchange` is unused.
+    ApiResponse<QVector<OptionChainEntry>> get_option_chain(const BrokerCredentials& creds, const QString& underlying,
+                                                            const QString& exchange, const QString& expiry,
+                                                            int strike_count = 0) override;
 
     static bool is_token_expired(const BrokerHttpResponse& resp);
     static QString checked_error(const BrokerHttpResponse& resp, const QString& fallback);
@@ -1,6 +1,7 @@
 #include "trading/brokers/upstox/UpstoxBroker.h"
 
 #include "core/logging/Logger.h"
+#include "core/time/Timestamps.h"
 #include "trading/adapter/BrokerEnumMap.h"
 #include "trading/brokers/BrokerHttp.h"
 #include "trading/brokers/BrokerTokenUtil.h"
@@ -518,19 +519,9 @@ ApiResponse<QVector<BrokerCandle>> UpstoxBroker::get_history(const BrokerCredent
             if (c.size() < 6)
                 continue;
             BrokerCandle candle;
-            // v3 returns ISO8601 string; some endpoints/snapshots return epoch ms.
-            // Try string first, fall back to numeric.
-            // BrokerCandle.timestamp contract is MILLISECONDS (seconds → candles in Jan 1970).
-            const QString ts_str = c[0].toString();
-            if (!ts_str.isEmpty()) {
-                candle.timestamp = QDateTime::fromString(ts_str, Qt::ISODate).toMSecsSinceEpoch();
-                if (candle.timestamp == 0)
-                    candle.timestamp = QDateTime::fromString(ts_str, Qt::ISODateWithMs).toMSecsSinceEpoch();
-            }
-            if (candle.timestamp == 0) {
-                const qint64 v = c[0].toVariant().toLongLong();
-                candle.timestamp = v > 1'000'000'000'000LL ? v : v * 1000; // seconds → ms
-            }
+            // v3 returns ISO8601 string with a +05:30 offset; some endpoints/snapshots
+            // return epoch s or ms. BrokerCandle.timestamp contract is UTC MILLISECONDS.
+            candle.timestamp = core::time::to_utc_ms(c[0], QTimeZone::utc());
             candle.open = c[1].toDouble();
             candle.high = c[2].toDouble();
             candle.low = c[3].toDouble();
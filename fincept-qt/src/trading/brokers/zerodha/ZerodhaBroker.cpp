@@ -1,6 +1,7 @@
 #include "trading/brokers/zerodha/ZerodhaBroker.h"
 
 #include "core/logging/Logger.h"
+#include "core/time/Timestamps.h"
 #include "trading/adapter/BrokerEnumMap.h"
 #include "trading/brokers/BrokerHttp.h"
 #include "trading/brokers/BrokerTokenUtil.h"
@@ -479,6 +480,7 @@ ApiResponse<QVector<BrokerCandle>> ZerodhaBroker::get_history(const BrokerCreden
         QVector<BrokerCandle> win_candles;
         if (resp.success) {
             auto arr = resp.json.value("data").toObject().value("candles").toArray();
+            const QTimeZone ist = core::time::exchange_zone("NSE");
             for (const auto& v : arr) {
                 auto c = v.toArray();
                 if (c.size() < 6)
@@ -488,11 +490,8 @@ ApiResponse<QVector<BrokerCandle>> ZerodhaBroker::get_history(const BrokerCreden
                 // +05:30 offset, e.g. "2024-03-28T09:15:00+0530"; emit ms, not seconds —
                 // seconds here landed candles in Jan 1970 and made the live bar roll a
                 // new candle on every tick (chart "going every second").
-                qint64 epoch_ms = QDateTime::fromString(c[0].toString(), Qt::ISODateWithMs).toMSecsSinceEpoch();
-                if (epoch_ms == 0)
-                    epoch_ms = QDateTime::fromString(c[0].toString(), Qt::ISODate).toMSecsSinceEpoch();
                 BrokerCandle bc;
-                bc.timestamp = epoch_ms;
+                bc.timestamp = core::time::to_utc_ms(c[0].toString(), ist);
                 bc.open = c[1].toDouble();
                 bc.high = c[2].toDouble();
                 bc.low = c[3].toDouble();
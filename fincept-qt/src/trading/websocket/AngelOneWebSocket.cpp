@@ -78,6 +78,7 @@ AngelOneWebSocket::AngelOneWebSocket(const QString& api_key, const QString& clie
                                      QObject* parent)
     : QObject(parent), api_key_(api_key), client_code_(client_code), feed_token_(feed_token) {
     ws_ = new WebSocketClient(this);
+    ws_->set_label("angelone");
     connect(ws_, &WebSocketClient::connected, this, &AngelOneWebSocket::on_connected);
     connect(ws_, &WebSocketClient::disconnected, this, &AngelOneWebSocket::on_disconnected);
     connect(ws_, &WebSocketClient::binary_message_received, this, &AngelOneWebSocket::on_binary_message);
@@ -1,5 +1,7 @@
 #include "trading/websocket/BrokerWebSocketBase.h"
 
+#include "core/time/Timestamps.h"
+
 namespace fincept::trading {
 
 BrokerWebSocketBase::BrokerWebSocketBase(QObject* parent) : QObject(parent) {
@@ -18,7 +20,9 @@ BrokerWebSocketBase::BrokerWebSocketBase(QObject* parent) : QObject(parent) {
     });
 }
 
-BrokerQuote BrokerWebSocketBase::merge_tick(const QString& key, const BrokerQuote& partial) {
+BrokerQuote BrokerWebSocketBase::merge_tick(const QString& key, const BrokerQuote& raw) {
+    BrokerQuote partial = raw;
+    partial.timestamp = core::time::epoch_to_ms(raw.timestamp); // feeds mix s / ms / µs; quotes carry UTC ms
     auto it = tick_cache_.find(key);
     if (it == tick_cache_.end()) {
         tick_cache_.insert(key, partial);
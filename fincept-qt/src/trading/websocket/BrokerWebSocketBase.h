@@ -46,7 +46,8 @@ class BrokerWebSocketBase : public QObject {
     // --- Partial tick merging ---
     // Many brokers send incremental ticks where unchanged fields arrive as 0.
     // merge_tick caches the last full tick per key and overlays only non-zero
-    // fields so downstream consumers always see a complete quote.
+    // fields so downstream consumers always see a complete quote. Timestamps
+    // are coerced to UTC epoch ms (core::time::epoch_to_ms).
     BrokerQuote merge_tick(const QString& key, const BrokerQuote& partial);
     void clear_tick_cache();
 
@@ -8,6 +8,7 @@
 #include "trading/websocket/DhanWebSocket.h"
 
 #include "core/logging/Logger.h"
+#include "core/time/Timestamps.h"
 #include "network/websocket/WebSocketClient.h"
 #include "trading/instruments/InstrumentService.h"
 
@@ -103,6 +104,7 @@ QString DhanWebSocket::exchange_to_segment_string(const QString& exchange) {
 DhanWebSocket::DhanWebSocket(const QString& access_token, const QString& client_id, QObject* parent)
     : BrokerWebSocketBase(parent), access_token_(access_token), client_id_(client_id) {
     ws_ = new WebSocketClient(this);
+    ws_->set_label("dhan");
     connect(ws_, &WebSocketClient::connected, this, &DhanWebSocket::on_connected);
     connect(ws_, &WebSocketClient::disconnected, this, &DhanWebSocket::on_disconnected);
     connect(ws_, &WebSocketClient::binary_message_received, this, &DhanWebSocket::on_binary_message);
@@ -405,7 +407,7 @@ BrokerQuote DhanWebSocket::parse_ticker(const uchar* payload, int len, int segme
     q.ltp = read_f32(payload + 0);
     const quint32 ltt = read_u32(payload + 4);
     if (ltt != 0)
-        q.timestamp = qint64(ltt) * 1000; // seconds → ms
+        q.timestamp = core::time::epoch_to_ms(ltt); // seconds → ms
     return q;
 }
 
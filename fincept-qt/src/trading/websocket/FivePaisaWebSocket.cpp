@@ -7,6 +7,7 @@
 #include "trading/websocket/FivePaisaWebSocket.h"
 
 #include "core/logging/Logger.h"
+#include "core/time/Timestamps.h"
 #include "network/websocket/WebSocketClient.h"
 #include "trading/instruments/InstrumentService.h"
 
@@ -16,7 +17,6 @@
 #include <QJsonDocument>
 #include <QJsonObject>
 #include <QJsonValue>
-#include <QRegularExpression>
 
 namespace fincept::trading {
 
@@ -82,14 +82,10 @@ QString FivePaisaWebSocket::fp_exchange_type(const QString& exchange) {
 }
 
 int64_t FivePaisaWebSocket::parse_fp_time(const QString& s) {
-    if (s.isEmpty())
-        return 0;
-    // Format: "/Date(1690000000000)/" or "/Date(1690000000000+0530)/"
-    static const QRegularExpression re(QStringLiteral("/Date\\((\\d+)"));
-    auto m = re.match(s);
-    if (m.hasMatch())
-        return m.captured(1).toLongLong();
-    return 0;
+    // Format: "/Date(1690000000000)/" or "/Date(1690000000000+0530)/" — UTC ms;
+    // anything else without an offset is IST wall-clock.
+    static const QTimeZone ist = core::time::exchange_zone("NSE");
+    return core::time::to_utc_ms(s, ist);
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -99,6 +95,7 @@ int64_t FivePaisaWebSocket::parse_fp_time(const QString& s) {
 FivePaisaWebSocket::FivePaisaWebSocket(const QString& access_token, const QString& client_code, QObject* parent)
     : BrokerWebSocketBase(parent), access_token_(access_token), client_code_(client_code) {
     ws_ = new WebSocketClient(this);
+    ws_->set_label("fivepaisa");
     connect(ws_, &WebSocketClient::connected, this, &FivePaisaWebSocket::on_connected);
     connect(ws_, &WebSocketClient::disconnected, this, &FivePaisaWebSocket::on_disconnected);
     connect(ws_, &WebSocketClient::message_received, this, &FivePaisaWebSocket::on_text_message);
@@ -207,6 +207,7 @@ FyersWebSocket::FyersWebSocket(const QString& client_id, const QString& access_t
     : QObject(parent), client_id_(client_id), access_token_(access_token) {
     hsm_key_ = extract_hsm_key(access_token);
     ws_ = new WebSocketClient(this);
+    ws_->set_label("fyers");
     connect(ws_, &WebSocketClient::connected, this, &FyersWebSocket::on_ws_connected);
     connect(ws_, &WebSocketClient::disconnected, this, &FyersWebSocket::on_ws_disconnected);
     connect(ws_, &WebSocketClient::binary_message_received, this, &FyersWebSocket::on_binary_message);
@@ -33,6 +33,7 @@ static constexpr const char* TAG_IIFL_WS = "IIFLWS";
 IIFLWebSocket::IIFLWebSocket(const QString& market_token, const QString& user_id, QObject* parent)
     : BrokerWebSocketBase(parent), market_token_(market_token), user_id_(user_id) {
     ws_ = new WebSocketClient(this);
+    ws_->set_label("iifl");
     connect(ws_, &WebSocketClient::connected, this, &IIFLWebSocket::on_ws_connected);
     connect(ws_, &WebSocketClient::disconnected, this, &IIFLWebSocket::on_ws_disconnected);
     connect(ws_, &WebSocketClient::message_received, this, &IIFLWebSocket::on_ws_message);
@@ -63,6 +63,7 @@ IciciDirectWebSocket::IciciDirectWebSocket(const QString& session_token_b64, con
     }
 
     ws_ = new WebSocketClient(this);
+    ws_->set_label("icici_direct");
     connect(ws_, &WebSocketClient::connected, this, &IciciDirectWebSocket::on_ws_connected);
     connect(ws_, &WebSocketClient::disconnected, this, &IciciDirectWebSocket::on_ws_disconnected);
     connect(ws_, &WebSocketClient::message_received, this, &IciciDirectWebSocket::on_ws_message);
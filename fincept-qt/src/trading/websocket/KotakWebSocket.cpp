@@ -109,6 +109,7 @@ KotakWebSocket::KotakWebSocket(const QString& auth_token, const QString& sid, co
       hs_server_id_(hs_server_id),
       access_token_(access_token) {
     ws_ = new WebSocketClient(this);
+    ws_->set_label("kotak");
     connect(ws_, &WebSocketClient::connected, this, &KotakWebSocket::on_connected);
     connect(ws_, &WebSocketClient::disconnected, this, &KotakWebSocket::on_disconnected);
     connect(ws_, &WebSocketClient::binary_message_received, this, &KotakWebSocket::on_binary_message);
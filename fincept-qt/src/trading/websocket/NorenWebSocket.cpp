@@ -1,6 +1,7 @@
 #include "trading/websocket/NorenWebSocket.h"
 
 #include "core/logging/Logger.h"
+#include "core/time/Timestamps.h"
 
 #include <QDateTime>
 #include <QJsonDocument>
@@ -69,6 +70,7 @@ NorenWebSocket::NorenWebSocket(const QString& ws_url, const QString& user_id, co
       account_id_(account_id),
       susertoken_raw_(susertoken) {
     ws_ = new WebSocketClient(this);
+    ws_->set_label("noren");
     connect(ws_, &WebSocketClient::connected, this, &NorenWebSocket::on_ws_connected);
     connect(ws_, &WebSocketClient::disconnected, this, &NorenWebSocket::on_ws_disconnected);
     connect(ws_, &WebSocketClient::message_received, this, &NorenWebSocket::on_ws_message);
@@ -342,7 +344,7 @@ void NorenWebSocket::handle_touchline(const QJsonObject& obj, bool full) {
     q.oi = field_i64(obj, "toi");
     // Feed time (ft) is an epoch-seconds string when present.
     const qint64 ft = field_i64(obj, "ft");
-    q.timestamp = ft > 0 ? ft * 1000 : QDateTime::currentMSecsSinceEpoch();
+    q.timestamp = ft > 0 ? core::time::epoch_to_ms(ft) : QDateTime::currentMSecsSinceEpoch();
 
     // Partial (tf) frames carry only changed fields; merge against last full tick.
     const BrokerQuote merged = merge_tick(key, q);
@@ -382,7 +384,7 @@ void NorenWebSocket::handle_depth(const QJsonObject& obj, bool full) {
     if (obj.contains("sq1"))
         q.ask_size = static_cast<double>(field_i64(obj, "sq1"));
     const qint64 ft = field_i64(obj, "ft");
-    q.timestamp = ft > 0 ? ft * 1000 : QDateTime::currentMSecsSinceEpoch();
+    q.timestamp = ft > 0 ? core::time::epoch_to_ms(ft) : QDateTime::currentMSecsSinceEpoch();
 
     const BrokerQuote merged = merge_tick(key, q);
     note_tick();
@@ -428,6 +428,7 @@ PbFeed decode_feed(const uint8_t* data, size_t len) {
 UpstoxWebSocket::UpstoxWebSocket(const QString& access_token, QObject* parent)
     : BrokerWebSocketBase(parent), access_token_(access_token) {
     ws_ = new WebSocketClient(this);
+    ws_->set_label("upstox");
     connect(ws_, &WebSocketClient::connected, this, &UpstoxWebSocket::on_connected);
     connect(ws_, &WebSocketClient::disconnected, this, &UpstoxWebSocket::on_disconnected);
     connect(ws_, &WebSocketClient::binary_message_received, this, &UpstoxWebSocket::on_binary_message);
@@ -47,6 +47,7 @@ double ZerodhaWebSocket::price_from_wire(qint32 raw, quint32 instrument_token) {
 ZerodhaWebSocket::ZerodhaWebSocket(const QString& api_key, const QString& access_token, QObject* parent)
     : QObject(parent), api_key_(api_key), access_token_(access_token) {
     ws_ = new WebSocketClient(this);
+    ws_->set_label("zerodha");
     connect(ws_, &WebSocketClient::connected, this, &ZerodhaWebSocket::on_connected);
     connect(ws_, &WebSocketClient::disconnected, this, &ZerodhaWebSocket::on_disconnected);
     connect(ws_, &WebSocketClient::binary_message_received, this, &ZerodhaWebSocket::on_binary_message);
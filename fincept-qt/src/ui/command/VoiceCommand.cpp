@@ -0,0 +1,332 @@
+#include "ui/command/VoiceCommand.h"
+
+#include "app/DockScreenRouter.h"
+#include "app/TerminalShell.h"
+#include "core/actions/ActionRegistry.h"
+#include "core/config/AppConfig.h"
+#include "core/events/TypedEvents.h"
+#include "core/keys/WindowCycler.h"
+#include "core/logging/Logger.h"
+#include "ui/command/CommandParser.h"
+
+#include <QCoreApplication>
+#include <QHash>
+#include <QMetaObject>
+#include <QRegularExpression>
+#include <QStringList>
+
+namespace fincept::ui {
+
+namespace {
+
+constexpr const char* kVoiceTag = "VoiceCommand";
+
+// Longer spoken sentences are questions for the assistant, not commands.
+constexpr int kMaxActionWords = 6;
+
+constexpr auto kRxFlags = QRegularExpression::CaseInsensitiveOption;
+
+// Wake words and politeness Whisper keeps in front of a command. Longest first.
+const QStringList kLeadIns = {
+    "hey fincept", "okay fincept", "ok fincept", "fincept", "go ahead and", "i'd like to", "i want to", "could you",
+    "can you", "would you", "please", "let's"};
+
+// Company names people say instead of tickers.
+const QHash<QString, QString> kCompanyTickers = {
+    {"apple", "AAPL"}, {"microsoft", "MSFT"}, {"google", "GOOGL"}, {"alphabet", "GOOGL"}, {"amazon", "AMZN"},
+    {"nvidia", "NVDA"}, {"meta", "META"}, {"facebook", "META"}, {"tesla", "TSLA"}, {"netflix", "NFLX"},
+    {"intel", "INTC"}, {"broadcom", "AVGO"}, {"oracle", "ORCL"}, {"salesforce", "CRM"}, {"adobe", "ADBE"},
+    {"cisco", "CSCO"}, {"qualcomm", "QCOM"}, {"palantir", "PLTR"}, {"jp morgan", "JPM"}, {"jpmorgan", "JPM"},
+    {"goldman", "GS"}, {"goldman sachs", "GS"}, {"bank of america", "BAC"}, {"visa", "V"}, {"mastercard", "MA"},
+    {"paypal", "PYPL"}, {"coinbase", "COIN"}, {"walmart", "WMT"}, {"costco", "COST"}, {"nike", "NKE"},
+    {"starbucks", "SBUX"}, {"pfizer", "PFE"}, {"eli lilly", "LLY"}, {"exxon", "XOM"}, {"chevron", "CVX"},
+    {"boeing", "BA"}, {"uber", "UBER"}, {"airbnb", "ABNB"}, {"shopify", "SHOP"}, {"disney", "DIS"},
+    {"berkshire", "BRK-B"}, {"bitcoin", "BTC-USD"}, {"ethereum", "ETH-USD"}};
+
+// Spoken screen names that are not simply the screen id with spaces.
+const QHash<QString, QString> kScreenAliases = {
+    {"research", "equity_research"}, {"stock research", "equity_research"}, {"chat", "ai_chat"}, {"ai", "ai_chat"},
+    {"assistant", "ai_chat"}, {"crypto", "crypto_trading"}, {"trading", "equity_trading"},
+    {"stock trading", "equity_trading"}, {"prediction markets", "polymarket"}, {"options", "derivatives"},
+    {"m and a", "ma_analytics"}, {"mergers", "ma_analytics"}, {"macro", "economics"}, {"economy", "economics"},
+    {"headlines", "news"}, {"settings", "settings"}, {"preferences", "settings"}, {"home", "dashboard"}};
+
+// Screener vocabulary: one word → sort or sector preset.
+const QHash<QString, QString> kScreenerSorts = {
+    {"oversold", "change_asc"}, {"losers", "change_asc"}, {"decliners", "change_asc"}, {"laggards", "change_asc"},
+    {"worst", "change_asc"}, {"overbought", "change_desc"}, {"gainers", "change_desc"}, {"winners", "change_desc"},
+    {"leaders", "change_desc"}, {"momentum", "change_desc"}, {"best", "change_desc"}, {"volume", "volume"},
+    {"active", "volume"}, {"busiest", "volume"}, {"expensive", "price_desc"}, {"cheap", "price_asc"},
+    {"cheapest", "price_asc"}, {"debate", "debate"}, {"bullish", "debate"}, {"conviction", "debate"}};
+
+const QHash<QString, QString> kScreenerSectors = {
+    {"tech", "tech"}, {"technology", "tech"}, {"software", "tech"}, {"semis", "tech"}, {"semiconductors", "tech"},
+    {"chips", "tech"}, {"banks", "financials"}, {"financial", "financials"}, {"financials", "financials"},
+    {"fintech", "financials"}, {"consumer", "consumer"}, {"retail", "consumer"}, {"healthcare", "healthcare"},
+    {"health", "healthcare"}, {"pharma", "healthcare"}, {"biotech", "healthcare"}, {"energy", "energy"},
+    {"oil", "energy"}, {"utilities", "utilities"}, {"industrials", "industrials"}, {"industrial", "industrials"},
+    {"defense", "industrials"}, {"all", "all"}};
+
+QString strip_lead_ins(QString text) {
+    bool stripped = true;
+    while (stripped) {
+        stripped = false;
+        for (const auto& lead : kLeadIns) {
+            if (text.startsWith(lead + ' ', Qt::CaseInsensitive) || text.compare(lead, Qt::CaseInsensitive) == 0) {
+                text = text.mid(lead.size()).trimmed();
+                stripped = true;
+                break;
+            }
+        }
+    }
+    return text;
+}
+
+/// Drops sentence punctuation Whisper adds but keeps the characters tickers
+/// use (BRK-B, RELIANCE.NS). Dots survive only between letters.
+QString clean_transcript(const QString& transcript) {
+    static const QRegularExpression punct(R"([,!?;:"“”]+)");
+    static const QRegularExpression trailing_dots(R"(\.+(\s|$))");
+    static const QRegularExpression spaces(R"(\s+)");
+    QString t = transcript;
+    t.replace(punct, " ");
+    t.replace(trailing_dots, "\\1");
+    t.replace(spaces, " ");
+    return strip_lead_ins(t.trimmed());
+}
+
+/// Ticker for a spoken phrase: a company name, spelled-out letters
+/// ("A A P L", "A-A-P-L"), or a ticker Whisper already wrote in capitals.
+/// Lower-case words are never guessed into tickers.
+QString resolve_symbol(QString phrase) {
+    static const QRegularExpression suffix(R"(\s+(stock|shares|share|inc|corp|corporation|company)$)", kRxFlags);
+    static const QRegularExpression spelled(R"(^([A-Za-z][\s.\-]+){1,5}[A-Za-z]\.?$)");
+    static const QRegularExpression ticker(R"(^\$?([A-Z]{1,5}([.\-][A-Z]{1,3})?)$)");
+    phrase = phrase.trimmed();
+    if (phrase.startsWith("the ", Qt::CaseInsensitive))
+        phrase = phrase.mid(4);
+    while (phrase.contains(suffix))
+        phrase.remove(suffix);
+    phrase = phrase.trimmed();
+    if (phrase.isEmpty())
+        return {};
+
+    const auto alias = kCompanyTickers.constFind(phrase.toLower());
+    if (alias != kCompanyTickers.constEnd())
+        return alias.value();
+    if (spelled.match(phrase).hasMatch()) {
+        QString letters;
+        for (const QChar c : phrase)
+            if (c.isLetter())
+                letters += c.toUpper();
+        return letters;
+    }
+    const auto m = ticker.match(phrase);
+    return m.hasMatch() ? m.captured(1) : QString();
+}
+
+/// Screen id for a spoken screen name, or empty when no such screen exists.
+QString resolve_screen(const QString& phrase) {
+    const QString lower = phrase.trimmed().toLower();
+    const auto alias = kScreenAliases.constFind(lower);
+    if (alias != kScreenAliases.constEnd())
+        return alias.value();
+    QString id = lower;
+    id.replace(QRegularExpression(R"([\s\-]+)"), "_");
+    // title_for_id echoes unknown ids back unchanged.
+    return DockScreenRouter::title_for_id(id) != id ? id : QString();
+}
+
+bool parse_screener(const QString& text, VoiceIntent& intent) {
+    static const QRegularExpression run(R"(^(?:(?:run|open|show|start|launch)\s+)?(?:(?:the|a|my)\s+)?(?:stock\s+)?)"
+                                        R"(screener(?:\s+(?:for|with|on|showing))?\s*(.*)$)",
+                                        kRxFlags);
+    static const QRegularExpression screen_for(R"(^screen\s+(?:for\s+)?(.+)$)", kRxFlags);
+    static const QRegularExpression movers(
+        R"(^(?:show|find|list)(?:\s+me)?\s+(?:the\s+)?((?:top|biggest|most)\s+(?:gainers|losers|active)\b.*)$)",
+        kRxFlags);
+
+    QString rest;
+    if (auto m = run.match(text); m.hasMatch())
+        rest = m.captured(1);
+    else if (auto m2 = screen_for.match(text); m2.hasMatch())
+        rest = m2.captured(1);
+    else if (auto m3 = movers.match(text); m3.hasMatch())
+        rest = m3.captured(1);
+    else
+        return false;
+
+    intent.kind = VoiceIntent::Kind::Screener;
+    intent.screen_id = QStringLiteral("screener");
+    for (const auto& word : rest.toLower().split(' ', Qt::SkipEmptyParts)) {
+        if (intent.sort.isEmpty() && kScreenerSorts.contains(word))
+            intent.sort = kScreenerSorts.value(word);
+        else if (intent.sector.isEmpty() && kScreenerSectors.contains(word))
+            intent.sector = kScreenerSectors.value(word);
+    }
+    return true;
+}
+
+bool parse_quote(const QString& text, VoiceIntent& intent) {
+    static const QList<QRegularExpression> patterns = {
+        QRegularExpression(R"(^(?:(?:get|show|give)(?:\s+me)?\s+)?(?:(?:a|the)\s+)?(?:stock\s+|share\s+)?)"
+                           R"((?:quote|price|chart)s?\s+(?:for\s+|of\s+|on\s+)?(.+)$)",
+                           kRxFlags),
+        QRegularExpression(R"(^(?:look\s*up|pull\s+up|open|show(?:\s+me)?)\s+(.+?)\s+(?:quote|price|chart|stock)$)",
+                           kRxFlags),
+        QRegularExpression(R"(^(?:how\s+is|how's|what\s+is|what's)\s+(.+?)\s+(?:trading\s+at|trading|doing)$)",
+                           kRxFlags),
+        QRegularExpression(R"(^(.+?)\s+(?:quote|stock\s+price|share\s+price|price)$)", kRxFlags),
+    };
+    for (const auto& rx : patterns) {
+        const auto m = rx.match(text);
+        if (!m.hasMatch())
+            continue;
+        const QString symbol = resolve_symbol(m.captured(1));
+        if (symbol.isEmpty())
+            continue;
+        intent.kind = VoiceIntent::Kind::Quote;
+        intent.symbol = symbol;
+        return true;
+    }
+    return false;
+}
+
+bool parse_navigate(const QString& text, VoiceIntent& intent) {
+    static const QRegularExpression rx(
+        R"(^(?:open|go\s+to|switch\s+to|show(?:\s+me)?|take\s+me\s+to|navigate\s+to|bring\s+up|pull\s+up)\s+)"
+        R"((?:(?:the|my)\s+)?(.+?)(?:\s+(?:screen|tab|panel|page|view))?$)",
+        kRxFlags);
+    const auto m = rx.match(text);
+    if (!m.hasMatch())
+        return false;
+    if (const QString id = resolve_screen(m.captured(1)); !id.isEmpty()) {
+        intent.kind = VoiceIntent::Kind::Navigate;
+        intent.screen_id = id;
+        return true;
+    }
+    // "open AAPL" / "show me Tesla" — a symbol rather than a screen.
+    if (const QString symbol = resolve_symbol(m.captured(1)); !symbol.isEmpty()) {
+        intent.kind = VoiceIntent::Kind::Quote;
+        intent.symbol = symbol;
+        return true;
+    }
+    return false;
+}
+
+bool parse_action(const QString& text, VoiceIntent& intent) {
+    if (text.split(' ', Qt::SkipEmptyParts).size() > kMaxActionWords)
+        return false;
+    const auto parsed = CommandParser::parse(text);
+    if (parsed.kind != ParsedCommand::Kind::Action)
+        return false;
+    intent.kind = VoiceIntent::Kind::Action;
+    intent.action_id = parsed.action_id;
+    intent.args = parsed.args;
+    return true;
+}
+
+/// Publishes `e` once the dock router has handled a nav.switch_screen that was
+/// published just before — the router materialises screens on a queued call,
+/// so a direct publish would reach a screen that does not exist yet.
+template <typename E>
+void publish_after_navigation(const E& e) {
+    QMetaObject::invokeMethod(QCoreApplication::instance(), [e]() { events::publish(e); }, Qt::QueuedConnection);
+}
+
+} // namespace
+
+QString VoiceIntent::describe() const {
+    switch (kind) {
+        case Kind::Quote:
+            return QStringLiteral("Quote %1").arg(symbol);
+        case Kind::Screener: {
+            QStringList parts;
+            if (!sort.isEmpty())
+                parts << sort;
+            if (!sector.isEmpty())
+                parts << sector;
+            return parts.isEmpty() ? QStringLiteral("Screener") : QStringLiteral("Screener: ") + parts.join(", ");
+        }
+        case Kind::Navigate:
+            return QStringLiteral("Open %1").arg(DockScreenRouter::title_for_id(screen_id));
+        case Kind::Action:
+            return QStringLiteral("Run %1").arg(action_id);
+        case Kind::None:
+            break;
+    }
+    return {};
+}
+
+QJsonObject VoiceIntent::to_json() const {
+    static const char* const kKinds[] = {"none", "quote", "screener", "navigate", "action"};
+    QJsonObject o{{"kind", kKinds[static_cast<int>(kind)]}, {"transcript", transcript}};
+    if (!symbol.isEmpty())
+        o["symbol"] = symbol;
+    if (!screen_id.isEmpty())
+        o["screen_id"] = screen_id;
+    if (!sort.isEmpty())
+        o["sort"] = sort;
+    if (!sector.isEmpty())
+        o["sector"] = sector;
+    if (!action_id.isEmpty()) {
+        o["action_id"] = action_id;
+        o["args"] = QJsonObject::fromVariantMap(args);
+    }
+    if (kind != Kind::None)
+        o["description"] = describe();
+    return o;
+}
+
+bool VoiceCommand::enabled() {
+    return AppConfig::instance().get("voice/commands/enabled", true).toBool();
+}
+
+VoiceIntent VoiceCommand::parse(const QString& transcript) {
+    VoiceIntent intent;
+    intent.transcript = transcript.trimmed();
+    const QString text = clean_transcript(intent.transcript);
+    if (text.isEmpty())
+        return intent;
+
+    // Screener before navigation so "open the screener for losers" keeps its
+    // preset; quote before navigation so "open Apple chart" is a quote.
+    if (parse_screener(text, intent) || parse_quote(text, intent) || parse_navigate(text, intent) ||
+        parse_action(text, intent)) {
+        LOG_INFO(kVoiceTag, QString("\"%1\" → %2").arg(intent.transcript.left(120), intent.describe()));
+    }
+    return intent;
+}
+
+Result<QString> VoiceCommand::execute(const VoiceIntent& intent) {
+    switch (intent.kind) {
+        case VoiceIntent::Kind::Quote:
+            // Same route as picking an asset in the command bar search.
+            events::publish(events::NavSwitchScreen{QStringLiteral("equity_research")});
+            publish_after_navigation(events::EquityResearchLoadSymbol{intent.symbol, QString()});
+            return Result<QString>::ok(QString("Opened %1 in Equity Research").arg(intent.symbol));
+        case VoiceIntent::Kind::Screener:
+            events::publish(events::NavSwitchScreen{intent.screen_id});
+            if (!intent.sort.isEmpty() || !intent.sector.isEmpty())
+                publish_after_navigation(events::ScreenerApplyPreset{intent.sort, intent.sector});
+            return Result<QString>::ok(intent.describe());
+        case VoiceIntent::Kind::Navigate:
+            events::publish(events::NavSwitchScreen{intent.screen_id});
+            return Result<QString>::ok(intent.describe());
+        case VoiceIntent::Kind::Action: {
+            CommandContext ctx;
+            ctx.shell = &TerminalShell::instance();
+            ctx.focused_frame = WindowCycler::instance().focused_frame();
+            ctx.args = intent.args;
+            auto r = ActionRegistry::instance().invoke(intent.action_id, ctx);
+            if (r.is_err())
+                return Result<QString>::err(r.error());
+            return Result<QString>::ok(intent.describe());
+        }
+        case VoiceIntent::Kind::None:
+            break;
+    }
+    return Result<QString>::err("Not a voice command");
+}
+
+} // namespace fincept::ui
@@ -0,0 +1,54 @@
+#pragma once
+// VoiceCommand — turns a speech transcript into a terminal command.
+//
+// parse() is pure: it maps a transcript ("quote AAPL", "run screener oversold
+// tech", "open news") onto a VoiceIntent. execute() routes the intent through
+// the paths the keyboard already uses — equity_research.load_symbol (command
+// bar asset search), nav.switch_screen, and ActionRegistry for anything the
+// QuickCommandBar grammar accepts. A transcript that is none of these yields
+// Kind::None and the caller treats it as a normal chat message.
+
+#include "core/result/Result.h"
+
+#include <QJsonObject>
+#include <QString>
+#include <QVariantMap>
+
+namespace fincept::ui {
+
+struct VoiceIntent {
+    enum class Kind {
+        None,     ///< not a command — send to chat
+        Quote,    ///< open a symbol in Equity Research
+        Screener, ///< open the screener with a sort / sector preset
+        Navigate, ///< switch to a screen
+        Action,   ///< ActionRegistry action (same grammar as QuickCommandBar)
+    };
+    Kind kind = Kind::None;
+    QString transcript;
+    QString symbol;    ///< Quote
+    QString screen_id; ///< Navigate / Screener
+    QString sort;      ///< Screener: change_desc | change_asc | volume | price_desc | price_asc | debate
+    QString sector;    ///< Screener: tech | financials | consumer | healthcare | energy | utilities | industrials
+    QString action_id; ///< Action
+    QVariantMap args;  ///< Action
+
+    bool is_command() const { return kind != Kind::None; }
+    /// Short human-readable form, e.g. "Quote AAPL" or "Screener: oversold, tech".
+    QString describe() const;
+    QJsonObject to_json() const;
+};
+
+class VoiceCommand {
+  public:
+    /// AppConfig "voice/commands/enabled" (default on). When off, every
+    /// transcript goes to chat unchanged.
+    static bool enabled();
+
+    static VoiceIntent parse(const QString& transcript);
+
+    /// Dispatches the intent. Main thread only. Returns a confirmation line.
+    static Result<QString> execute(const VoiceIntent& intent);
+};
+
+} // namespace fincept::ui